@@ -0,0 +1,99 @@
+//! Criterion benchmarks for the per-cycle hot paths.
+//!
+//! ```text
+//! cargo bench --features testkit
+//! cargo bench --features testkit -- similarity   # only names containing "similarity"
+//! ```
+//!
+//! Inputs come from `oracle::testkit`, so numbers are comparable across
+//! runs. Compare against a run on the base branch before merging a change
+//! to any of these paths; criterion reports the difference.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rust_decimal_macros::dec;
+
+use oracle::engine::scanner::{text_similarity, MarketRouter};
+use oracle::strategy::edge::{EdgeConfig, EdgeDetector};
+use oracle::strategy::kelly::{KellyCalculator, KellyConfig};
+use oracle::strategy::risk::{RiskConfig, RiskManager};
+use oracle::strategy::StrategyOrchestrator;
+use oracle::testkit;
+use oracle::types::AgentState;
+
+const SEED: u64 = 2026;
+
+/// One question against a 500-question corpus.
+fn similarity(c: &mut Criterion) {
+    let corpus: Vec<String> = testkit::questions(500, SEED).into_iter().map(|(q, _)| q).collect();
+    c.bench_function("text_similarity/500", |b| {
+        b.iter(|| {
+            let query = &corpus[0];
+            corpus.iter().map(|q| text_similarity(black_box(query), q)).sum::<f64>()
+        })
+    });
+}
+
+/// 500 Manifold markets against 500 Metaculus questions, through the
+/// inverted index and by comparing every pair as before it.
+fn cross_reference(c: &mut Criterion) {
+    let manifold = testkit::markets(500, "manifold", SEED);
+    let metaculus = testkit::metaculus(500, SEED + 1);
+
+    let mut group = c.benchmark_group("cross_reference/500x500");
+    group.bench_function("indexed", |b| {
+        b.iter_batched_ref(
+            || manifold.clone(),
+            |markets| MarketRouter::cross_reference(markets, &metaculus, 0.45),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("all_pairs", |b| {
+        b.iter(|| {
+            manifold
+                .iter()
+                .filter(|mf| {
+                    metaculus
+                        .iter()
+                        .any(|mc| text_similarity(&mf.question, &mc.question) >= 0.45)
+                })
+                .count()
+        })
+    });
+    group.finish();
+}
+
+/// 200 estimates through edge, Kelly and risk.
+fn select_bets(c: &mut Criterion) {
+    let estimates = testkit::estimates(200, SEED);
+    let mut state = AgentState::new(dec!(1000));
+    state.mana_bankroll = dec!(1000);
+    let mut orchestrator = StrategyOrchestrator::new(
+        EdgeDetector::new(EdgeConfig::default()),
+        KellyCalculator::new(KellyConfig::default()),
+        RiskManager::new(RiskConfig::default()),
+    );
+    c.bench_function("select_bets/200", |b| {
+        b.iter(|| {
+            orchestrator.reset_cycle();
+            orchestrator.select_bets(black_box(&estimates), &state)
+        })
+    });
+}
+
+/// Scanner filter + priority sort over 1000 markets.
+fn filter_and_sort(c: &mut Criterion) {
+    let router = MarketRouter::new(None, None);
+    let scanned = testkit::markets(1000, "polymarket", SEED + 2);
+    c.bench_function("filter_and_sort/1000", |b| {
+        b.iter_batched(
+            || scanned.clone(),
+            |markets| router.filter_and_sort(markets),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(hot_paths, similarity, cross_reference, select_bets, filter_and_sort);
+criterion_main!(hot_paths);
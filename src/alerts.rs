@@ -0,0 +1,77 @@
+//! Operator alerts.
+//!
+//! Posts short text messages to a webhook named by
+//! `[alerts].webhook_url_env`. The body carries the text under both `text`
+//! (Slack and most generic receivers) and `content` (Discord). Delivery is
+//! best-effort: a failed post is logged and never fails the caller.
+
+use std::time::Duration;
+
+use reqwest::Client;
+use tracing::{debug, warn};
+
+use crate::config::AlertsConfig;
+
+/// A configured alert webhook.
+pub struct Webhook {
+    http: Client,
+    url: String,
+}
+
+impl Webhook {
+    pub fn new(url: String) -> Self {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { http, url }
+    }
+
+    /// The webhook from config, when its env var is set and non-empty.
+    pub fn from_config(cfg: &AlertsConfig) -> Option<Self> {
+        let url = std::env::var(cfg.webhook_url_env.as_deref()?).ok()?;
+        (!url.trim().is_empty()).then(|| Self::new(url))
+    }
+
+    /// Post `text`, logging rather than returning a failure.
+    pub async fn send(&self, text: &str) {
+        let result = self.http.post(&self.url).json(&payload(text)).send().await;
+        match result.and_then(|r| r.error_for_status()) {
+            Ok(_) => debug!(text, "Alert delivered"),
+            Err(e) => warn!(error = %e, text, "Alert webhook failed"),
+        }
+    }
+}
+
+/// Webhook body for `text`.
+pub fn payload(text: &str) -> serde_json::Value {
+    serde_json::json!({ "text": text, "content": text })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_carries_text_for_slack_and_discord() {
+        let body = payload("betfair: preflight failed");
+        assert_eq!(body["text"], "betfair: preflight failed");
+        assert_eq!(body["content"], "betfair: preflight failed");
+    }
+
+    #[test]
+    fn test_from_config_requires_env() {
+        let cfg = AlertsConfig {
+            telegram_bot_token_env: None,
+            telegram_chat_id_env: None,
+            webhook_url_env: None,
+        };
+        assert!(Webhook::from_config(&cfg).is_none());
+        let cfg = AlertsConfig { webhook_url_env: Some("ORACLE_TEST_UNSET_WEBHOOK".into()), ..cfg };
+        assert!(Webhook::from_config(&cfg).is_none());
+    }
+}
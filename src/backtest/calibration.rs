@@ -1,11 +1,13 @@
 //! Calibration module.
 //!
 //! Measures how well the LLM's probability estimates match reality.
-//! Computes calibration curves, Brier scores per category, and
-//! generates adjustment recommendations.
+//! Computes calibration curves, Brier scores and log loss per category,
+//! and generates adjustment recommendations.
 
 use std::collections::HashMap;
 
+use serde::Serialize;
+
 use crate::types::MarketCategory;
 
 // ---------------------------------------------------------------------------
@@ -22,12 +24,16 @@ pub struct CalibrationPoint {
 }
 
 /// Calibration analysis results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CalibrationReport {
     pub total_predictions: usize,
     pub overall_brier: f64,
     /// Brier score per category.
     pub category_brier: HashMap<String, f64>,
+    /// Mean log loss (natural log); 0.693 = always 50/50.
+    pub overall_log_loss: f64,
+    /// Log loss per category.
+    pub category_log_loss: HashMap<String, f64>,
     /// Calibration buckets: for each 10% bin, the predicted vs actual rate.
     pub calibration_curve: Vec<CalibrationBucket>,
     /// Whether the model is over-confident, under-confident, or well-calibrated.
@@ -35,7 +41,7 @@ pub struct CalibrationReport {
 }
 
 /// A bucket in the calibration curve (e.g., all predictions between 0.60-0.70).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CalibrationBucket {
     pub bin_start: f64,
     pub bin_end: f64,
@@ -46,7 +52,8 @@ pub struct CalibrationBucket {
     pub deviation: f64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CalibrationDiagnosis {
     WellCalibrated,
     OverConfident,    // Predicted probabilities too extreme
@@ -94,6 +101,8 @@ impl Calibrator {
                 total_predictions: 0,
                 overall_brier: 0.0,
                 category_brier: HashMap::new(),
+                overall_log_loss: 0.0,
+                category_log_loss: HashMap::new(),
                 calibration_curve: Vec::new(),
                 diagnosis: CalibrationDiagnosis::InsufficientData,
             };
@@ -101,6 +110,12 @@ impl Calibrator {
 
         let overall_brier = self.compute_brier(&self.points);
         let category_brier = self.compute_category_brier();
+        let overall_log_loss = log_loss(self.points.iter());
+        let category_log_loss = self
+            .by_category()
+            .into_iter()
+            .map(|(cat, points)| (cat, log_loss(points.into_iter())))
+            .collect();
         let calibration_curve = self.compute_calibration_curve();
         let diagnosis = self.diagnose(&calibration_curve);
 
@@ -108,6 +123,8 @@ impl Calibrator {
             total_predictions: self.points.len(),
             overall_brier,
             category_brier,
+            overall_log_loss,
+            category_log_loss,
             calibration_curve,
             diagnosis,
         }
@@ -127,15 +144,19 @@ impl Calibrator {
         sum / points.len() as f64
     }
 
-    /// Compute Brier score broken down by category.
-    fn compute_category_brier(&self) -> HashMap<String, f64> {
+    /// Points grouped by category name.
+    fn by_category(&self) -> HashMap<String, Vec<&CalibrationPoint>> {
         let mut by_category: HashMap<String, Vec<&CalibrationPoint>> = HashMap::new();
         for p in &self.points {
             let key = format!("{:?}", p.category);
             by_category.entry(key).or_default().push(p);
         }
+        by_category
+    }
 
-        by_category.into_iter().map(|(cat, points)| {
+    /// Compute Brier score broken down by category.
+    fn compute_category_brier(&self) -> HashMap<String, f64> {
+        self.by_category().into_iter().map(|(cat, points)| {
             let brier = if points.is_empty() {
                 0.0
             } else {
@@ -150,19 +171,17 @@ impl Calibrator {
     }
 
     /// Compute the calibration curve — bin predictions and compare to actual rates.
+    ///
+    /// Bins are `[start, end)`, except the last, which also takes 1.0.
     fn compute_calibration_curve(&self) -> Vec<CalibrationBucket> {
-        let bin_width = 1.0 / self.num_bins as f64;
         let mut buckets = Vec::with_capacity(self.num_bins);
 
         for i in 0..self.num_bins {
-            let bin_start = i as f64 * bin_width;
-            let bin_end = bin_start + bin_width;
+            let bin_start = i as f64 / self.num_bins as f64;
+            let bin_end = (i + 1) as f64 / self.num_bins as f64;
 
             let in_bin: Vec<&CalibrationPoint> = self.points.iter()
-                .filter(|p| {
-                    p.estimated_probability >= bin_start
-                        && (p.estimated_probability < bin_end || (i == self.num_bins - 1 && p.estimated_probability <= bin_end))
-                })
+                .filter(|p| bucket_index(p.estimated_probability, self.num_bins) == i)
                 .collect();
 
             if in_bin.is_empty() {
@@ -278,6 +297,28 @@ impl Calibrator {
     }
 }
 
+/// Bin of `probability` among `num_bins` equal bins over [0, 1].
+///
+/// Scaled with a small tolerance so a probability on a boundary (0.3,
+/// 0.7) lands in the bin it starts rather than the one below it through
+/// float error; 1.0 goes in the last bin.
+pub fn bucket_index(probability: f64, num_bins: usize) -> usize {
+    let scaled = (probability.clamp(0.0, 1.0) * num_bins as f64 + 1e-9).floor() as usize;
+    scaled.min(num_bins - 1)
+}
+
+/// Mean log loss of `points`, with probabilities clamped away from 0 and 1
+/// so one certain miss doesn't make it infinite.
+fn log_loss<'a>(points: impl Iterator<Item = &'a CalibrationPoint>) -> f64 {
+    const EPS: f64 = 1e-15;
+    let (sum, count) = points.fold((0.0, 0usize), |(sum, count), p| {
+        let prob = p.estimated_probability.clamp(EPS, 1.0 - EPS);
+        let loss = if p.resolved_yes { -prob.ln() } else { -(1.0 - prob).ln() };
+        (sum + loss, count + 1)
+    });
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -406,6 +447,42 @@ mod tests {
         assert!((report.overall_brier - 0.25).abs() < 0.01, "Brier: {}", report.overall_brier);
     }
 
+    #[test]
+    fn test_brier_and_log_loss_hand_computed() {
+        let mut cal = Calibrator::new();
+        cal.add_points(vec![make_point(0.8, true), make_point(0.3, false), make_point(0.6, false)]);
+        let report = cal.report();
+        // ((0.2)² + (0.3)² + (0.6)²) / 3 = (0.04 + 0.09 + 0.36) / 3
+        assert!((report.overall_brier - 0.49 / 3.0).abs() < 1e-12);
+        // -(ln 0.8 + ln 0.7 + ln 0.4) / 3
+        let expected = -(0.8f64.ln() + 0.7f64.ln() + 0.4f64.ln()) / 3.0;
+        assert!((report.overall_log_loss - expected).abs() < 1e-12);
+        assert!((report.category_log_loss["Weather"] - expected).abs() < 1e-12);
+
+        // A certain miss is large but finite.
+        let mut cal = Calibrator::new();
+        cal.add_point(make_point(1.0, false));
+        assert!(cal.report().overall_log_loss.is_finite());
+    }
+
+    #[test]
+    fn test_bucket_boundaries() {
+        assert_eq!(bucket_index(0.0, 10), 0);
+        assert_eq!(bucket_index(0.0999, 10), 0);
+        assert_eq!(bucket_index(0.1, 10), 1);
+        // 0.3 and 0.7 are inexact in binary but start their own bins.
+        assert_eq!(bucket_index(0.3, 10), 3);
+        assert_eq!(bucket_index(0.7, 10), 7);
+        assert_eq!(bucket_index(0.9999, 10), 9);
+        assert_eq!(bucket_index(1.0, 10), 9);
+
+        let mut cal = Calibrator::new();
+        cal.add_points(vec![make_point(0.3, true), make_point(1.0, true), make_point(0.0, false)]);
+        let curve = cal.report().calibration_curve;
+        assert_eq!(curve.iter().map(|b| b.count).collect::<Vec<_>>(), vec![1, 0, 0, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!((curve[3].bin_start, curve[3].bin_end), (0.3, 0.4));
+    }
+
     #[test]
     fn test_add_points_batch() {
         let mut cal = Calibrator::new();
@@ -2,3 +2,5 @@
 
 pub mod runner;
 pub mod calibration;
+pub mod replay;
+pub mod sensitivity;
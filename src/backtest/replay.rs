@@ -0,0 +1,598 @@
+//! Replay of recorded cycles through the live strategy pipeline.
+//!
+//! With `ORACLE_RECORD_CYCLES=1` the agent writes each cycle's inputs to
+//! the strategy under [`DEFAULT_RECORD_DIR`], one directory per cycle:
+//!
+//! ```text
+//! recorded_cycles/
+//!   cycle-000042/
+//!     cycle.json        # cycle number and decision time
+//!     markets.json      # markets after the cost guard
+//!     contexts.json     # their enrichment contexts, same order
+//!     estimates.json    # final estimates, same order (empty without an LLM)
+//!     resolutions.json  # resolutions observed before this cycle's decisions
+//!   resolutions.json    # optional outcomes added by hand
+//! ```
+//!
+//! `oracle backtest <dir>` replays the directory through a
+//! [`StrategyOrchestrator`] built from the current config: the same edge,
+//! Kelly and risk steps, evaluated at each cycle's recorded time. Approved
+//! bets fill at the recorded YES price and settle through
+//! [`Resolver::settle`] once their market's resolution appears in the
+//! recording; bets still open at the end are reported as unresolved. No
+//! LLM or platform calls are made, and the same directory always produces
+//! the same report.
+//!
+//! The live resolver only checks markets the agent holds, so bets a new
+//! parameter set places on other markets need their outcomes added to the
+//! top-level `resolutions.json`. Venue routing across same-event clusters
+//! is not replayed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::config::CoolDownConfig;
+use crate::engine::resolver::{Resolver, Settlement};
+use crate::strategy::{cooldown, StrategyOrchestrator};
+use crate::types::{AgentState, DataContext, Estimate, Market, MarketResolution, TradeReceipt};
+
+/// Env var that turns cycle recording on (`1`).
+pub const RECORD_ENV: &str = "ORACLE_RECORD_CYCLES";
+
+/// Directory recorded cycles are written to.
+pub const DEFAULT_RECORD_DIR: &str = "recorded_cycles";
+
+const META_FILE: &str = "cycle.json";
+const MARKETS_FILE: &str = "markets.json";
+const CONTEXTS_FILE: &str = "contexts.json";
+const ESTIMATES_FILE: &str = "estimates.json";
+const RESOLUTIONS_FILE: &str = "resolutions.json";
+
+// ---------------------------------------------------------------------------
+// Recording
+// ---------------------------------------------------------------------------
+
+/// Whether `ORACLE_RECORD_CYCLES=1` is set.
+pub fn recording_enabled() -> bool {
+    std::env::var(RECORD_ENV).is_ok_and(|v| v == "1")
+}
+
+/// Directory of cycle `cycle` under `root`.
+pub fn cycle_dir(root: &Path, cycle: u64) -> PathBuf {
+    root.join(format!("cycle-{cycle:06}"))
+}
+
+/// `cycle.json`: when the cycle's decisions were made.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CycleMeta {
+    cycle: u64,
+    recorded_at: DateTime<Utc>,
+}
+
+/// A market outcome as recorded for replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResolution {
+    pub platform: String,
+    pub market_id: String,
+    pub resolution: MarketResolution,
+}
+
+impl From<&Settlement> for RecordedResolution {
+    fn from(s: &Settlement) -> Self {
+        Self {
+            platform: s.platform.clone(),
+            market_id: s.market_id.clone(),
+            resolution: s.resolution,
+        }
+    }
+}
+
+/// Write one cycle's strategy inputs. `estimates` is either empty or
+/// aligned with `enriched`.
+pub fn record_cycle(
+    root: &Path,
+    cycle: u64,
+    recorded_at: DateTime<Utc>,
+    enriched: &[(Market, DataContext)],
+    estimates: &[(Market, Estimate)],
+) -> Result<()> {
+    let dir = cycle_dir(root, cycle);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let markets: Vec<&Market> = enriched.iter().map(|(m, _)| m).collect();
+    let contexts: Vec<&DataContext> = enriched.iter().map(|(_, c)| c).collect();
+    let estimates: Vec<&Estimate> = estimates.iter().map(|(_, e)| e).collect();
+    write_json(&dir.join(META_FILE), &CycleMeta { cycle, recorded_at })?;
+    write_json(&dir.join(MARKETS_FILE), &markets)?;
+    write_json(&dir.join(CONTEXTS_FILE), &contexts)?;
+    write_json(&dir.join(ESTIMATES_FILE), &estimates)
+}
+
+/// Append resolutions observed before cycle `cycle`'s decisions.
+pub fn record_resolutions(root: &Path, cycle: u64, settlements: &[Settlement]) -> Result<()> {
+    let dir = cycle_dir(root, cycle);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(RESOLUTIONS_FILE);
+    let mut resolutions: Vec<RecordedResolution> = read_json_or_default(&path)?;
+    for s in settlements {
+        let r = RecordedResolution::from(s);
+        // One entry per market, however many bets it settled.
+        if !resolutions.contains(&r) {
+            resolutions.push(r);
+        }
+    }
+    write_json(&path, &resolutions)
+}
+
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let json = serde_json::to_vec(value).with_context(|| format!("Failed to serialise {}", path.display()))?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_slice(&bytes).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn read_json_or_default<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    if path.exists() {
+        read_json(path)
+    } else {
+        Ok(T::default())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+/// One recorded cycle. A cycle that failed before recording its markets
+/// may hold resolutions only.
+#[derive(Debug, Clone)]
+pub struct RecordedCycle {
+    pub cycle: u64,
+    /// Decision time; `None` without markets.
+    pub recorded_at: Option<DateTime<Utc>>,
+    pub markets: Vec<(Market, Estimate)>,
+    pub resolutions: Vec<RecordedResolution>,
+}
+
+/// A recording directory, cycles in order.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    pub cycles: Vec<RecordedCycle>,
+    /// Outcomes from the top-level `resolutions.json`, applied after the
+    /// last cycle.
+    pub extra_resolutions: Vec<RecordedResolution>,
+}
+
+impl Recording {
+    /// Load every `cycle-NNNNNN` directory under `root`.
+    pub fn load(root: &Path) -> Result<Self> {
+        let mut dirs = Vec::new();
+        for entry in std::fs::read_dir(root).with_context(|| format!("Failed to read {}", root.display()))? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(cycle) = name.to_str().and_then(|n| n.strip_prefix("cycle-")).and_then(|n| n.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            dirs.push((cycle, entry.path()));
+        }
+        dirs.sort();
+
+        let mut cycles = Vec::with_capacity(dirs.len());
+        for (cycle, dir) in dirs {
+            let resolutions = read_json_or_default(&dir.join(RESOLUTIONS_FILE))?;
+            let meta_path = dir.join(META_FILE);
+            if !meta_path.exists() {
+                cycles.push(RecordedCycle { cycle, recorded_at: None, markets: Vec::new(), resolutions });
+                continue;
+            }
+            let meta: CycleMeta = read_json(&meta_path)?;
+            let markets: Vec<Market> = read_json(&dir.join(MARKETS_FILE))?;
+            let estimates: Vec<Estimate> = read_json(&dir.join(ESTIMATES_FILE))?;
+            anyhow::ensure!(
+                estimates.is_empty() || estimates.len() == markets.len(),
+                "{}: {} estimates for {} markets",
+                dir.display(),
+                estimates.len(),
+                markets.len()
+            );
+            cycles.push(RecordedCycle {
+                cycle,
+                recorded_at: Some(meta.recorded_at),
+                markets: markets.into_iter().zip(estimates).collect(),
+                resolutions,
+            });
+        }
+
+        Ok(Self {
+            cycles,
+            extra_resolutions: read_json_or_default(&root.join(RESOLUTIONS_FILE))?,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+/// Balance path of one stake currency.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ledger {
+    /// Balance when the currency was first staked.
+    pub start: Decimal,
+    pub balance: Decimal,
+    pub peak: Decimal,
+    /// Largest fall from a peak, in the currency.
+    pub max_drawdown: Decimal,
+    /// The same fall as a fraction of that peak.
+    pub max_drawdown_pct: Decimal,
+}
+
+impl Ledger {
+    fn new(balance: Decimal) -> Self {
+        Self { start: balance, balance, peak: balance, ..Self::default() }
+    }
+
+    fn observe(&mut self, balance: Decimal) {
+        self.balance = balance;
+        self.peak = self.peak.max(balance);
+        let drawdown = self.peak - balance;
+        if drawdown > self.max_drawdown {
+            self.max_drawdown = drawdown;
+            if self.peak > Decimal::ZERO {
+                self.max_drawdown_pct = drawdown / self.peak;
+            }
+        }
+    }
+
+    pub fn pnl(&self) -> Decimal {
+        self.balance - self.start
+    }
+}
+
+/// Outcomes of one category's bets.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CategoryResult {
+    pub bets: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub refunds: usize,
+    pub unresolved: usize,
+    /// Realised P&L per stake currency.
+    pub pnl: BTreeMap<String, Decimal>,
+}
+
+/// Result of replaying a recording.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Cycles with recorded markets.
+    pub cycles: usize,
+    pub estimates: usize,
+    pub bets: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub refunds: usize,
+    pub unresolved: usize,
+    /// Per stake currency ("AUD", "Mana", ...).
+    pub ledgers: BTreeMap<String, Ledger>,
+    /// Keyed by category name.
+    pub by_category: BTreeMap<String, CategoryResult>,
+}
+
+impl ReplayReport {
+    /// Wins over settled, non-refunded bets.
+    pub fn win_rate(&self) -> f64 {
+        let settled = self.wins + self.losses;
+        if settled == 0 { 0.0 } else { self.wins as f64 / settled as f64 }
+    }
+}
+
+/// Replay `recording` through `orchestrator`, starting from `state`.
+///
+/// Bets on Manifold are staked in Mana, everything else in `currency`.
+/// Category cool-downs are tracked as in the live loop.
+pub fn run(
+    recording: &Recording,
+    orchestrator: &mut StrategyOrchestrator,
+    mut state: AgentState,
+    currency: &str,
+    cool_down: &CoolDownConfig,
+) -> ReplayReport {
+    let mut report = ReplayReport::default();
+    let mut known: HashMap<(String, String), MarketResolution> = HashMap::new();
+    let mut now = None;
+
+    for cycle in &recording.cycles {
+        for r in &cycle.resolutions {
+            known.insert((r.platform.clone(), r.market_id.clone()), r.resolution);
+        }
+        settle_known(&mut state, &known, &mut report, cool_down, now);
+
+        let Some(recorded_at) = cycle.recorded_at else { continue };
+        now = Some(recorded_at);
+        report.cycles += 1;
+        report.estimates += cycle.markets.len();
+
+        cooldown::lift_expired(&mut state, cool_down, recorded_at);
+        orchestrator.sync_exposure_from_state(&state);
+        orchestrator.reset_cycle();
+        let (approved, decisions) = orchestrator.select_bets_at(&cycle.markets, &state, recorded_at);
+        cooldown::record_skips(&mut state, &decisions, cool_down);
+
+        for (n, bet) in approved.iter().enumerate() {
+            let market = &bet.edge.market;
+            let stake_currency = if market.platform == "manifold" { "Mana" } else { currency };
+            let receipt = TradeReceipt {
+                order_id: format!("backtest-{}-{n}", cycle.cycle),
+                market_id: market.id.clone(),
+                platform: market.platform.clone(),
+                side: bet.edge.side,
+                amount: bet.bet_amount,
+                fill_price: market.current_price_yes,
+                fees: Decimal::ZERO,
+                timestamp: recorded_at,
+                currency: stake_currency.to_string(),
+                deadline: Some(market.deadline),
+                category: Some(market.category),
+                edge: Some(bet.edge.edge),
+                raw_response: None,
+                risk_context: bet.risk_context.clone(),
+                tags: market.tags.clone(),
+                correlation_key: bet.correlation_key.clone(),
+                expected_price: None,
+            };
+            let balance = balance_in(&state, stake_currency);
+            report.ledgers.entry(stake_currency.to_string()).or_insert_with(|| Ledger::new(balance));
+            report.by_category.entry(market.category.to_string()).or_default().bets += 1;
+            report.bets += 1;
+            state.trades_placed += 1;
+            state.open_bets.push(receipt);
+        }
+        state.cycle_count += 1;
+    }
+
+    for r in &recording.extra_resolutions {
+        known.entry((r.platform.clone(), r.market_id.clone())).or_insert(r.resolution);
+    }
+    settle_known(&mut state, &known, &mut report, cool_down, now);
+
+    for bet in &state.open_bets {
+        report.unresolved += 1;
+        if let Some(category) = bet.category {
+            report.by_category.entry(category.to_string()).or_default().unresolved += 1;
+        }
+    }
+    report
+}
+
+/// Settle every open bet whose market has a known resolution.
+fn settle_known(
+    state: &mut AgentState,
+    known: &HashMap<(String, String), MarketResolution>,
+    report: &mut ReplayReport,
+    cool_down: &CoolDownConfig,
+    now: Option<DateTime<Utc>>,
+) {
+    let (settled, open): (Vec<_>, Vec<_>) = std::mem::take(&mut state.open_bets)
+        .into_iter()
+        .partition(|b| known.contains_key(&(b.platform.clone(), b.market_id.clone())));
+    state.open_bets = open;
+
+    for bet in settled {
+        let s = Resolver::settle(&bet, known[&(bet.platform.clone(), bet.market_id.clone())]);
+        Resolver::apply(state, &s);
+        let category = bet.category.map(|c| c.to_string()).unwrap_or_default();
+        let result = report.by_category.entry(category).or_default();
+        if s.is_refund() {
+            report.refunds += 1;
+            result.refunds += 1;
+            continue;
+        }
+        if s.won {
+            report.wins += 1;
+            result.wins += 1;
+        } else {
+            report.losses += 1;
+            result.losses += 1;
+        }
+        *result.pnl.entry(s.currency.clone()).or_default() += s.pnl;
+        if let (Some(category), Some(now)) = (bet.category, now) {
+            cooldown::record_resolution(state, category, s.won, cool_down, now);
+        }
+        let balance = balance_in(state, &s.currency);
+        report.ledgers.entry(s.currency.clone()).or_insert_with(|| Ledger::new(balance - s.pnl)).observe(balance);
+    }
+}
+
+/// Balance bets in `currency` are settled against.
+fn balance_in(state: &AgentState, currency: &str) -> Decimal {
+    if currency == "Mana" { state.mana_bankroll } else { state.bankroll }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::edge::{EdgeConfig, EdgeDetector};
+    use crate::strategy::kelly::{KellyCalculator, KellyConfig};
+    use crate::strategy::risk::{RiskConfig, RiskManager};
+    use crate::types::{CrossReferences, MarketCategory};
+    use chrono::{Duration, TimeZone};
+    use rust_decimal_macros::dec;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("oracle_replay_{name}_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    fn market(id: &str, category: MarketCategory, price: Decimal) -> Market {
+        Market {
+            id: id.to_string(),
+            platform: "betfair".to_string(),
+            question: format!("Will {id} happen?"),
+            description: String::new(),
+            category,
+            current_price_yes: price,
+            current_price_no: Decimal::ONE - price,
+            volume_24h: dec!(1000),
+            liquidity: dec!(5000),
+            // Long past by now: only the recorded decision time matters.
+            deadline: t0() + Duration::days(3),
+            resolution_criteria: String::new(),
+            url: String::new(),
+            cross_refs: CrossReferences::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn estimate(probability: Decimal) -> Estimate {
+        Estimate {
+            probability,
+            confidence: dec!(0.8),
+            reasoning: String::new(),
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    fn resolution(market_id: &str, resolution: MarketResolution) -> RecordedResolution {
+        RecordedResolution { platform: "betfair".to_string(), market_id: market_id.to_string(), resolution }
+    }
+
+    fn orchestrator() -> StrategyOrchestrator {
+        StrategyOrchestrator::new(
+            EdgeDetector::new(EdgeConfig::default()),
+            KellyCalculator::new(KellyConfig { commission_per_trade: Decimal::ZERO, ..KellyConfig::default() }),
+            RiskManager::new(RiskConfig::default()),
+        )
+    }
+
+    fn enriched(markets: &[(Market, Estimate)]) -> Vec<(Market, DataContext)> {
+        markets.iter().map(|(m, _)| (m.clone(), DataContext::empty(m.category))).collect()
+    }
+
+    /// Two cycles: a winning Weather bet and a losing Sports bet, plus a
+    /// market with no edge.
+    fn write_recording(root: &Path) {
+        let first = vec![
+            (market("rain", MarketCategory::Weather, dec!(0.30)), estimate(dec!(0.60))),
+            (market("flat", MarketCategory::Weather, dec!(0.50)), estimate(dec!(0.51))),
+        ];
+        record_cycle(root, 1, t0(), &enriched(&first), &first).unwrap();
+
+        let second = vec![(market("cup", MarketCategory::Sports, dec!(0.40)), estimate(dec!(0.70)))];
+        record_cycle(root, 2, t0() + Duration::hours(1), &enriched(&second), &second).unwrap();
+
+        let rain = Resolver::settle(
+            &TradeReceipt { platform: "betfair".into(), ..TradeReceipt::dry_run("rain", dec!(1), "AUD") },
+            MarketResolution::Yes,
+        );
+        record_resolutions(root, 3, &[rain.clone(), rain]).unwrap();
+        write_json(&root.join(RESOLUTIONS_FILE), &[resolution("cup", MarketResolution::No)]).unwrap();
+    }
+
+    #[test]
+    fn test_record_and_load_round_trip() {
+        let dir = temp_dir("round_trip");
+        write_recording(&dir);
+
+        let recording = Recording::load(&dir).unwrap();
+        let cycles: Vec<u64> = recording.cycles.iter().map(|c| c.cycle).collect();
+        assert_eq!(cycles, vec![1, 2, 3]);
+        assert_eq!(recording.cycles[0].markets.len(), 2);
+        assert_eq!(recording.cycles[0].recorded_at, Some(t0()));
+        // Resolution-only cycle; duplicate settlements recorded once.
+        assert!(recording.cycles[2].recorded_at.is_none());
+        assert_eq!(recording.cycles[2].resolutions, vec![resolution("rain", MarketResolution::Yes)]);
+        assert_eq!(recording.extra_resolutions, vec![resolution("cup", MarketResolution::No)]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_replay_settles_and_breaks_down_by_category() {
+        let dir = temp_dir("settle");
+        write_recording(&dir);
+        let recording = Recording::load(&dir).unwrap();
+
+        let report = run(&recording, &mut orchestrator(), AgentState::new(dec!(100)), "AUD", &CoolDownConfig::default());
+
+        assert_eq!((report.cycles, report.estimates, report.bets), (2, 3, 2));
+        assert_eq!((report.wins, report.losses, report.unresolved), (1, 1, 0));
+        assert_eq!(report.win_rate(), 0.5);
+
+        let weather = &report.by_category["Weather"];
+        let sports = &report.by_category["Sports"];
+        assert_eq!((weather.bets, weather.wins), (1, 1));
+        assert_eq!((sports.bets, sports.losses), (1, 1));
+        assert!(weather.pnl["AUD"] > Decimal::ZERO && sports.pnl["AUD"] < Decimal::ZERO);
+
+        let aud = &report.ledgers["AUD"];
+        assert_eq!(aud.start, dec!(100));
+        assert_eq!(aud.pnl(), weather.pnl["AUD"] + sports.pnl["AUD"]);
+        // The Sports loss came after the Weather win's peak.
+        assert_eq!(aud.max_drawdown, -sports.pnl["AUD"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_replay_is_deterministic() {
+        let dir = temp_dir("deterministic");
+        write_recording(&dir);
+        let recording = Recording::load(&dir).unwrap();
+        let replay = || run(&recording, &mut orchestrator(), AgentState::new(dec!(100)), "AUD", &CoolDownConfig::default());
+        assert_eq!(replay(), replay());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_unresolved_and_cancelled_bets() {
+        let dir = temp_dir("unresolved");
+        let cycle = vec![
+            (market("void", MarketCategory::Politics, dec!(0.30)), estimate(dec!(0.60))),
+            (market("open", MarketCategory::Economics, dec!(0.30)), estimate(dec!(0.60))),
+        ];
+        record_cycle(&dir, 1, t0(), &enriched(&cycle), &cycle).unwrap();
+        write_json(&dir.join(RESOLUTIONS_FILE), &[resolution("void", MarketResolution::Cancelled)]).unwrap();
+
+        let recording = Recording::load(&dir).unwrap();
+        let report = run(&recording, &mut orchestrator(), AgentState::new(dec!(100)), "AUD", &CoolDownConfig::default());
+
+        assert_eq!((report.bets, report.refunds, report.unresolved), (2, 1, 1));
+        assert_eq!((report.wins, report.losses), (0, 0));
+        assert_eq!(report.by_category["Economics"].unresolved, 1);
+        assert_eq!(report.ledgers["AUD"].pnl(), Decimal::ZERO);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_misaligned_estimates_rejected() {
+        let dir = temp_dir("misaligned");
+        let cycle = vec![(market("a", MarketCategory::Other, dec!(0.5)), estimate(dec!(0.5)))];
+        let mut markets = enriched(&cycle);
+        markets.push(markets[0].clone());
+        record_cycle(&dir, 1, t0(), &markets, &cycle).unwrap();
+        assert!(Recording::load(&dir).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
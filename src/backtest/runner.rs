@@ -408,7 +408,13 @@ mod tests {
     fn test_agent_dies_on_bankrupt() {
         let bt = Backtester::new(
             EdgeConfig { min_edge: dec!(0.01), weather_threshold: dec!(0.02), ..EdgeConfig::default() },
-            KellyConfig { multiplier: Decimal::ONE, max_bet_pct: dec!(0.95), min_bet_size: dec!(0.1), commission_per_trade: Decimal::ZERO },
+            KellyConfig {
+                multiplier: Decimal::ONE,
+                max_bet_pct: dec!(0.95),
+                min_bet_size: dec!(0.1),
+                commission_per_trade: Decimal::ZERO,
+                ..KellyConfig::default()
+            },
             RiskConfig { max_exposure_pct: dec!(1.0), ..RiskConfig::default() },
         );
         // Keep losing with huge bets
@@ -0,0 +1,381 @@
+//! Threshold sensitivity analysis over the stored decision history.
+//!
+//! Re-runs edge detection, Kelly sizing and the per-cycle bet cap over the
+//! `decisions` table under a small grid of alternative parameters and
+//! reports, for each variant, which bets it would have added or removed
+//! relative to the current configuration. No LLM calls are made: every
+//! variant reuses the estimates recorded at decision time.
+//!
+//! The grid varies one parameter at a time around the baseline:
+//!
+//! - every category edge threshold shifted by ±2 and ±4 points;
+//! - the Kelly multiplier scaled by ×0.5 and ×1.5 (capped at 1);
+//! - `max_bets_per_cycle` from 3 to 8.
+//!
+//! A market counts as bet once, in the first cycle a variant selects it;
+//! later cycles skip it as held. Stakes are fractions of the bankroll at
+//! decision time (commission and minimum stake are ignored), scaled by the
+//! caller's bankroll for display. Risk limits other than `max_bet_pct` and
+//! the per-cycle cap (exposure, event groups, cool-downs) are not modelled.
+//!
+//! PnL deltas cover resolved markets only. Bets on unresolved markets are
+//! counted in `bets` and `committed` but excluded from `pnl_delta`; each
+//! variant reports how many were excluded.
+
+use std::collections::{BTreeMap, HashSet};
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+
+use crate::storage::metrics::DecisionRow;
+use crate::strategy::edge::{EdgeConfig, EdgeDetector};
+use crate::strategy::kelly::{KellyCalculator, KellyConfig};
+use crate::types::{CrossReferences, Estimate, Market, MarketCategory, Side};
+
+/// Label attached to every report explaining the PnL scope.
+pub const PNL_NOTE: &str = "pnl_delta covers resolved markets only; bets on unresolved markets are excluded";
+
+/// Threshold shifts in the grid (absolute probability points).
+const THRESHOLD_SHIFTS: [Decimal; 4] = [dec!(-0.04), dec!(-0.02), dec!(0.02), dec!(0.04)];
+
+/// Kelly multiplier scales in the grid.
+const KELLY_SCALES: [Decimal; 2] = [dec!(0.5), dec!(1.5)];
+
+/// `max_bets_per_cycle` values in the grid.
+const MAX_BETS_RANGE: std::ops::RangeInclusive<usize> = 3..=8;
+
+/// Strategy parameters one replay runs under.
+#[derive(Debug, Clone)]
+pub struct SensitivityParams {
+    pub edge: EdgeConfig,
+    pub kelly_multiplier: Decimal,
+    pub max_bet_pct: Decimal,
+    pub max_bets_per_cycle: usize,
+}
+
+/// One parameter combination's outcome.
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantReport {
+    /// Human-readable variant name, e.g. `edge_threshold -2pt`.
+    pub label: String,
+    /// Bets the variant would have placed.
+    pub bets: usize,
+    /// Markets bet under this variant but not the baseline.
+    pub bets_added: Vec<String>,
+    /// Markets bet under the baseline but not this variant.
+    pub bets_removed: Vec<String>,
+    /// Sum of stakes as a fraction of bankroll.
+    pub committed_fraction: f64,
+    /// `committed_fraction` scaled by the report's bankroll.
+    pub committed: f64,
+    /// Realised PnL on resolved markets minus the baseline's.
+    pub pnl_delta: f64,
+    /// Bets on unresolved markets, excluded from `pnl_delta`.
+    pub unresolved_excluded: usize,
+}
+
+/// Sensitivity of the decision history to the parameter grid.
+#[derive(Debug, Clone, Serialize)]
+pub struct SensitivityReport {
+    pub cycles: usize,
+    pub decisions: usize,
+    pub bankroll: f64,
+    /// Realised PnL of the baseline on resolved markets.
+    pub baseline_pnl: f64,
+    pub baseline: VariantReport,
+    pub variants: Vec<VariantReport>,
+    pub note: &'static str,
+}
+
+/// A bet a replay would have placed.
+struct ReplayBet {
+    key: String,
+    fraction: Decimal,
+    side: Side,
+    price_yes: Decimal,
+    resolved_yes: Option<bool>,
+}
+
+/// Decision history reshaped for repeated replays: cycles in order, each
+/// with its markets and recorded estimates.
+struct History {
+    cycles: Vec<Vec<(Market, Estimate)>>,
+    resolutions: BTreeMap<String, bool>,
+}
+
+fn key(platform: &str, market_id: &str) -> String {
+    format!("{platform}:{market_id}")
+}
+
+impl History {
+    fn new(rows: &[DecisionRow]) -> Self {
+        let mut by_cycle: BTreeMap<u64, Vec<(Market, Estimate)>> = BTreeMap::new();
+        let mut resolutions = BTreeMap::new();
+        for r in rows {
+            if let Some(yes) = r.resolved_yes {
+                resolutions.insert(key(&r.platform, &r.market_id), yes);
+            }
+            by_cycle.entry(r.cycle).or_default().push(replay_input(r));
+        }
+        Self { cycles: by_cycle.into_values().collect(), resolutions }
+    }
+
+    fn replay(&self, params: &SensitivityParams) -> Vec<ReplayBet> {
+        let detector = EdgeDetector::new(params.edge.clone());
+        let kelly = KellyCalculator::new(KellyConfig {
+            multiplier: params.kelly_multiplier,
+            max_bet_pct: params.max_bet_pct,
+            min_bet_size: Decimal::ZERO,
+            commission_per_trade: Decimal::ZERO,
+            ..KellyConfig::default()
+        });
+        let mut held = HashSet::new();
+        let mut bets = Vec::new();
+        for cycle in &self.cycles {
+            let open: Vec<_> = cycle
+                .iter()
+                .filter(|(m, _)| !held.contains(&key(&m.platform, &m.id)))
+                .cloned()
+                .collect();
+            let sized = detector
+                .find_edges(&open)
+                .into_iter()
+                .filter_map(|e| kelly.size_bet(&e, Decimal::ONE))
+                .take(params.max_bets_per_cycle);
+            for bet in sized {
+                let k = key(&bet.edge.market.platform, &bet.edge.market.id);
+                held.insert(k.clone());
+                bets.push(ReplayBet {
+                    resolved_yes: self.resolutions.get(&k).copied(),
+                    key: k,
+                    fraction: bet.bet_fraction,
+                    side: bet.edge.side,
+                    price_yes: bet.edge.market.current_price_yes,
+                });
+            }
+        }
+        bets
+    }
+}
+
+/// Rebuild the strategy's view of one recorded decision.
+fn replay_input(r: &DecisionRow) -> (Market, Estimate) {
+    let price_yes = Decimal::from_f64(r.price_yes).unwrap_or_default();
+    let market = Market {
+        id: r.market_id.clone(),
+        platform: r.platform.clone(),
+        question: String::new(),
+        description: String::new(),
+        category: r.category.parse().unwrap_or(MarketCategory::Other),
+        current_price_yes: price_yes,
+        current_price_no: Decimal::ONE - price_yes,
+        volume_24h: Decimal::ZERO,
+        liquidity: Decimal::ZERO,
+        deadline: r.timestamp,
+        resolution_criteria: String::new(),
+        url: String::new(),
+        cross_refs: CrossReferences::default(),
+        event_group: None,
+        facts: None,
+        tags: Vec::new(),
+    };
+    let estimate = Estimate {
+        probability: Decimal::from_f64(r.estimate).unwrap_or_default(),
+        confidence: Decimal::from_f64(r.confidence).unwrap_or_default(),
+        reasoning: String::new(),
+        tokens_used: 0,
+        cost: Decimal::ZERO,
+        critique: None,
+        served_by: None,
+        tier: None,
+    };
+    (market, estimate)
+}
+
+/// Realised PnL of a bet as a fraction of bankroll, or `None` while the
+/// market is unresolved.
+fn pnl_fraction(bet: &ReplayBet) -> Option<Decimal> {
+    let resolved_yes = bet.resolved_yes?;
+    let (price, won) = match bet.side {
+        Side::Yes => (bet.price_yes, resolved_yes),
+        Side::No => (Decimal::ONE - bet.price_yes, !resolved_yes),
+    };
+    if price <= Decimal::ZERO {
+        return Some(Decimal::ZERO);
+    }
+    Some(if won { bet.fraction * (Decimal::ONE - price) / price } else { -bet.fraction })
+}
+
+fn pnl(bets: &[ReplayBet]) -> Decimal {
+    bets.iter().filter_map(pnl_fraction).sum()
+}
+
+/// The bounded one-at-a-time parameter grid around `base`.
+pub fn grid(base: &SensitivityParams) -> Vec<(String, SensitivityParams)> {
+    let mut variants = Vec::new();
+    for shift in THRESHOLD_SHIFTS {
+        let mut p = base.clone();
+        for category in MarketCategory::ALL {
+            let t = (p.edge.threshold_for(category) + shift).max(Decimal::ZERO);
+            p.edge.set_threshold(*category, t);
+        }
+        let points = (shift * dec!(100)).normalize();
+        variants.push((format!("edge_threshold {points:+}pt"), p));
+    }
+    for scale in KELLY_SCALES {
+        let mut p = base.clone();
+        p.kelly_multiplier = (base.kelly_multiplier * scale).min(Decimal::ONE);
+        variants.push((format!("kelly_multiplier x{scale}"), p));
+    }
+    for n in MAX_BETS_RANGE.filter(|n| *n != base.max_bets_per_cycle) {
+        let mut p = base.clone();
+        p.max_bets_per_cycle = n;
+        variants.push((format!("max_bets_per_cycle {n}"), p));
+    }
+    variants
+}
+
+/// Replay `rows` under the baseline and every grid variant. `bankroll`
+/// scales committed fractions and PnL into currency for display.
+pub fn analyze(rows: &[DecisionRow], base: &SensitivityParams, bankroll: f64) -> SensitivityReport {
+    let history = History::new(rows);
+    let baseline_bets = history.replay(base);
+    let baseline_keys: HashSet<&str> = baseline_bets.iter().map(|b| b.key.as_str()).collect();
+    let baseline_pnl = pnl(&baseline_bets);
+
+    let report = |label: String, bets: &[ReplayBet]| {
+        let keys: HashSet<&str> = bets.iter().map(|b| b.key.as_str()).collect();
+        let mut added: Vec<String> = keys.difference(&baseline_keys).map(|k| k.to_string()).collect();
+        let mut removed: Vec<String> = baseline_keys.difference(&keys).map(|k| k.to_string()).collect();
+        added.sort();
+        removed.sort();
+        let committed = bets.iter().map(|b| b.fraction).sum::<Decimal>().to_f64().unwrap_or(0.0);
+        VariantReport {
+            label,
+            bets: bets.len(),
+            bets_added: added,
+            bets_removed: removed,
+            committed_fraction: committed,
+            committed: committed * bankroll,
+            pnl_delta: ((pnl(bets) - baseline_pnl).to_f64().unwrap_or(0.0)) * bankroll,
+            unresolved_excluded: bets.iter().filter(|b| b.resolved_yes.is_none()).count(),
+        }
+    };
+
+    let variants = grid(base)
+        .into_iter()
+        .map(|(label, params)| report(label, &history.replay(&params)))
+        .collect();
+    SensitivityReport {
+        cycles: history.cycles.len(),
+        decisions: rows.len(),
+        bankroll,
+        baseline_pnl: baseline_pnl.to_f64().unwrap_or(0.0) * bankroll,
+        baseline: report("baseline".to_string(), &baseline_bets),
+        variants,
+        note: PNL_NOTE,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn row(cycle: u64, market_id: &str, price_yes: f64, estimate: f64, resolved_yes: Option<bool>) -> DecisionRow {
+        DecisionRow {
+            timestamp: Utc::now() - Duration::hours(10 - cycle as i64),
+            cycle,
+            platform: "manifold".into(),
+            market_id: market_id.into(),
+            category: "Weather".into(),
+            price_yes,
+            metaculus_prob: None,
+            manifold_prob: None,
+            estimate,
+            confidence: 1.0,
+            edge: None,
+            side: None,
+            decision: "no_edge".into(),
+            bet_fraction: None,
+            resolved_yes,
+            tags: Vec::new(),
+        }
+    }
+
+    fn base() -> SensitivityParams {
+        // Weather threshold 6pt, noise floor 3pt.
+        SensitivityParams {
+            edge: EdgeConfig::default(),
+            kelly_multiplier: dec!(0.25),
+            max_bet_pct: dec!(0.06),
+            max_bets_per_cycle: 5,
+        }
+    }
+
+    fn variant<'a>(report: &'a SensitivityReport, label: &str) -> &'a VariantReport {
+        report.variants.iter().find(|v| v.label == label).unwrap()
+    }
+
+    #[test]
+    fn test_looser_threshold_adds_expected_decisions() {
+        let rows = [
+            // 7pt edge: bet at the 6pt baseline.
+            row(1, "strong", 0.40, 0.47, Some(true)),
+            // 5pt edges: below the 6pt baseline, above 4pt.
+            row(1, "five_a", 0.40, 0.45, Some(false)),
+            row(2, "five_b", 0.60, 0.55, None),
+            // 3.5pt: above the noise floor but below 4pt.
+            row(2, "small", 0.40, 0.435, Some(true)),
+            // Re-seen while held: counted once.
+            row(2, "strong", 0.42, 0.49, Some(true)),
+        ];
+        let report = analyze(&rows, &base(), 1000.0);
+        assert_eq!((report.cycles, report.decisions), (2, 5));
+        assert_eq!(report.baseline.bets, 1);
+
+        let loose = variant(&report, "edge_threshold -2pt");
+        assert_eq!(loose.bets_added, ["manifold:five_a", "manifold:five_b"]);
+        assert!(loose.bets_removed.is_empty());
+        assert_eq!(loose.bets, 3);
+        // five_b is unresolved: committed but excluded from PnL.
+        assert_eq!(loose.unresolved_excluded, 1);
+        assert!(loose.committed > report.baseline.committed);
+        // five_a (YES at 0.40) resolved NO: the added bets lose its stake.
+        let added_stakes = loose.committed_fraction - report.baseline.committed_fraction;
+        assert!(loose.pnl_delta < 0.0 && loose.pnl_delta > -added_stakes * 1000.0);
+
+        // At -4pt the 3pt noise floor binds, so the 3.5pt edge is added too.
+        let looser = variant(&report, "edge_threshold -4pt");
+        assert_eq!(looser.bets_added, ["manifold:five_a", "manifold:five_b", "manifold:small"]);
+
+        // Tighter thresholds remove the only baseline bet.
+        let tight = variant(&report, "edge_threshold +4pt");
+        assert_eq!(tight.bets_removed, ["manifold:strong"]);
+        assert_eq!(tight.bets, 0);
+        assert_eq!(report.note, PNL_NOTE);
+    }
+
+    #[test]
+    fn test_grid_is_bounded_and_varies_one_parameter() {
+        let variants = grid(&base());
+        // 4 threshold shifts + 2 Kelly scales + max bets 3..=8 without 5.
+        assert_eq!(variants.len(), 11);
+        let kelly = &variants.iter().find(|(l, _)| l == "kelly_multiplier x1.5").unwrap().1;
+        assert_eq!(kelly.kelly_multiplier, dec!(0.375));
+        assert_eq!(kelly.max_bets_per_cycle, 5);
+
+        // The per-cycle cap keeps the largest edges.
+        let rows: Vec<_> = (0..6).map(|i| row(1, &format!("m{i}"), 0.30, 0.40 + i as f64 * 0.01, None)).collect();
+        let report = analyze(&rows, &base(), 100.0);
+        assert_eq!(report.baseline.bets, 5);
+        assert_eq!(variant(&report, "max_bets_per_cycle 3").bets_removed, ["manifold:m1", "manifold:m2"]);
+        assert_eq!(variant(&report, "max_bets_per_cycle 8").bets_added, ["manifold:m0"]);
+        assert_eq!(variant(&report, "max_bets_per_cycle 8").unresolved_excluded, 6);
+    }
+}
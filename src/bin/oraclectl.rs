@@ -0,0 +1,79 @@
+//! oraclectl — remote control for a running ORACLE agent.
+//!
+//! Talks to the agent's dashboard API. The address and bearer token come
+//! from `~/.oraclectl.toml` (`url`, `token`) or `ORACLECTL_URL` /
+//! `ORACLECTL_TOKEN`. Exits non-zero on any API error.
+
+use anyhow::{bail, Result};
+use futures::StreamExt;
+
+use oracle::ctl::{
+    render_config, render_event, render_positions, render_status, ControlAction, CtlClient,
+    CtlConfig, HttpTransport,
+};
+
+const USAGE: &str = "\
+Usage: oraclectl <command> [args]
+
+Commands:
+  status                      Agent status and open positions
+  pause                       Pause trading after the current cycle
+  standby                     Keep scanning and estimating, stop placing bets
+  resume                      Resume trading (after re-checking venue balances and positions)
+  export                      Print a JSON snapshot of all dashboard data
+  config                      Effective configuration and where each value came from
+  tail                        Follow agent events (Ctrl-C to stop)
+
+Config: ~/.oraclectl.toml (url, token) or ORACLECTL_URL / ORACLECTL_TOKEN";
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("oraclectl: {e:#}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let Some(command) = args.first() else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+    if matches!(command.as_str(), "help" | "-h" | "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let client = CtlClient::new(HttpTransport::new(CtlConfig::load()?)?);
+    match (command.as_str(), &args[1..]) {
+        ("status", []) => {
+            println!("{}\n", render_status(&client.status().await?));
+            println!("{}", render_positions(&client.positions().await?));
+        }
+        ("pause", []) => print_result(client.control(&ControlAction::Pause).await?),
+        ("standby", []) => print_result(client.control(&ControlAction::Standby).await?),
+        ("resume", []) => print_result(client.control(&ControlAction::Resume).await?),
+        ("config", []) => println!("{}", render_config(&client.config().await?)),
+        ("export", []) => println!("{}", serde_json::to_string_pretty(&client.export().await?)?),
+        ("tail", []) => {
+            let mut events = client.events().await?;
+            while let Some(event) = events.next().await {
+                if let Some(line) = render_event(&event?) {
+                    println!("{}  {line}", chrono::Local::now().format("%H:%M:%S"));
+                }
+            }
+            bail!("The agent closed the event stream");
+        }
+        _ => bail!("Unknown command or wrong arguments: {}\n\n{USAGE}", args.join(" ")),
+    }
+    Ok(())
+}
+
+fn print_result(value: serde_json::Value) {
+    match value {
+        serde_json::Value::Null => println!("ok"),
+        serde_json::Value::String(s) => println!("{s}"),
+        other => println!("{}", serde_json::to_string_pretty(&other).unwrap_or_default()),
+    }
+}
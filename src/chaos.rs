@@ -0,0 +1,356 @@
+//! Chaos-mode fault injection (dev-only, `--features chaos`).
+//!
+//! Wraps platform clients and the LLM estimator with seeded fault
+//! injectors — request failures, added latency, corrupted JSON, duplicated
+//! fills and truncated batch responses — so retry, timeout and accounting
+//! paths can be exercised without waiting for a real outage. Configured by
+//! the `[chaos]` section; `AppConfig::validate` refuses it in live mode.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tracing::{debug, warn};
+
+use crate::config::{ChaosConfig, ChaosFaults};
+use crate::llm::LlmEstimator;
+use crate::platforms::ladder::{PriceLadder, Rounding};
+use crate::platforms::PredictionPlatform;
+use crate::types::{DataContext, Estimate, LiquidityInfo, LiquidityModel, Market, MarketResolution, Position, Side, TradeReceipt};
+
+/// Body substituted for a real response when corruption is injected.
+const CORRUPT_BODY: &str = r#"{"id":"chaos","probability":0.5,"#;
+
+/// Error returned for an injected request failure.
+#[derive(Debug, thiserror::Error)]
+#[error("chaos: injected {target} failure")]
+pub struct InjectedFault {
+    pub target: String,
+}
+
+// ---------------------------------------------------------------------------
+// Fault injector
+// ---------------------------------------------------------------------------
+
+/// Seeded source of faults for one wrapped client.
+pub struct FaultInjector {
+    target: String,
+    faults: ChaosFaults,
+    /// SplitMix64 state — deterministic for a given seed.
+    state: Mutex<u64>,
+}
+
+impl FaultInjector {
+    pub fn new(target: &str, faults: ChaosFaults, seed: u64) -> Self {
+        // Mix the target into the seed so wrapped clients don't share a sequence.
+        let salt = target.bytes().fold(0u64, |h, b| h.wrapping_mul(31).wrapping_add(b as u64));
+        Self {
+            target: target.to_string(),
+            faults,
+            state: Mutex::new(seed ^ salt),
+        }
+    }
+
+    fn next(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// True with probability `rate`.
+    fn roll(&self, rate: f64) -> bool {
+        rate > 0.0 && ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < rate
+    }
+
+    /// Added latency, then a possible outright failure.
+    async fn before_call(&self, op: &str) -> Result<()> {
+        if self.faults.latency_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.faults.latency_ms)).await;
+        }
+        if self.roll(self.faults.failure_rate) {
+            warn!(target = %self.target, op, "Chaos: injecting request failure");
+            return Err(InjectedFault { target: self.target.clone() }.into());
+        }
+        Ok(())
+    }
+
+    /// A parse error shaped like the one a client returns for malformed JSON.
+    fn corrupt_response(&self, op: &str) -> Result<()> {
+        if !self.roll(self.faults.corrupt_rate) {
+            return Ok(());
+        }
+        warn!(target = %self.target, op, "Chaos: injecting corrupted response");
+        serde_json::from_str::<serde_json::Value>(CORRUPT_BODY)
+            .map(|_| ())
+            .with_context(|| format!("Failed to parse {} {op} response", self.target))
+    }
+
+    /// Drop a random tail of a batch response.
+    fn truncate<T>(&self, items: &mut Vec<T>) {
+        if items.len() > 1 && self.roll(self.faults.partial_batch_rate) {
+            let keep = (self.next() % items.len() as u64) as usize;
+            debug!(target = %self.target, kept = keep, of = items.len(), "Chaos: truncating batch");
+            items.truncate(keep);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Platform wrapper
+// ---------------------------------------------------------------------------
+
+/// A `PredictionPlatform` with injected faults.
+pub struct ChaosPlatform {
+    inner: Arc<dyn PredictionPlatform>,
+    faults: FaultInjector,
+    /// Last real fill, replayed when a duplicate fill is injected.
+    last_fill: Mutex<Option<TradeReceipt>>,
+}
+
+impl ChaosPlatform {
+    pub fn new(inner: Arc<dyn PredictionPlatform>, faults: ChaosFaults, seed: u64) -> Self {
+        let faults = FaultInjector::new(inner.name(), faults, seed);
+        Self { inner, faults, last_fill: Mutex::new(None) }
+    }
+
+    /// Place through the inner venue, keyed when `key` is set and limited
+    /// to `max_price` when that is too.
+    async fn place(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        key: Option<&str>,
+        max_price: Option<Decimal>,
+    ) -> Result<TradeReceipt> {
+        self.faults.before_call("bet").await?;
+        self.faults.corrupt_response("bet")?;
+        if self.faults.roll(self.faults.faults.duplicate_fill_rate) {
+            if let Some(previous) = self.last_fill.lock().unwrap().clone() {
+                warn!(order_id = %previous.order_id, "Chaos: replaying previous fill");
+                return Ok(previous);
+            }
+        }
+        let receipt = match (key, max_price) {
+            (Some(key), Some(max_price)) => self.inner.place_limit_bet(market_id, side, amount, max_price, key).await?,
+            (Some(key), None) => self.inner.place_bet_keyed(market_id, side, amount, key).await?,
+            (None, _) => self.inner.place_bet(market_id, side, amount).await?,
+        };
+        *self.last_fill.lock().unwrap() = Some(receipt.clone());
+        Ok(receipt)
+    }
+}
+
+#[async_trait]
+impl PredictionPlatform for ChaosPlatform {
+    async fn fetch_markets(&self) -> Result<Vec<Market>> {
+        self.faults.before_call("markets").await?;
+        self.faults.corrupt_response("markets")?;
+        let mut markets = self.inner.fetch_markets().await?;
+        self.faults.truncate(&mut markets);
+        Ok(markets)
+    }
+
+    async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+        self.place(market_id, side, amount, None, None).await
+    }
+
+    fn supports_idempotency(&self) -> bool {
+        self.inner.supports_idempotency()
+    }
+
+    async fn place_bet_keyed(&self, market_id: &str, side: Side, amount: Decimal, key: &str) -> Result<TradeReceipt> {
+        self.place(market_id, side, amount, Some(key), None).await
+    }
+
+    fn supports_limit_orders(&self) -> bool {
+        self.inner.supports_limit_orders()
+    }
+
+    async fn place_limit_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        max_price: Decimal,
+        key: &str,
+    ) -> Result<TradeReceipt> {
+        self.place(market_id, side, amount, Some(key), Some(max_price)).await
+    }
+
+    async fn current_price(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        self.faults.before_call("price").await?;
+        self.inner.current_price(market_id, side).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        self.faults.before_call("positions").await?;
+        self.inner.get_positions().await
+    }
+
+    async fn get_balance(&self) -> Result<Decimal> {
+        self.faults.before_call("balance").await?;
+        self.inner.get_balance().await
+    }
+
+    async fn check_liquidity(&self, market_id: &str) -> Result<LiquidityInfo> {
+        self.faults.before_call("liquidity").await?;
+        self.inner.check_liquidity(market_id).await
+    }
+
+    fn liquidity_model(&self) -> LiquidityModel {
+        self.inner.liquidity_model()
+    }
+
+    fn is_real_money(&self) -> bool {
+        self.inner.is_real_money()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn price_increment(&self, market: &Market) -> PriceLadder {
+        self.inner.price_increment(market)
+    }
+
+    fn passive_rounding(&self, side: Side) -> Rounding {
+        self.inner.passive_rounding(side)
+    }
+
+    fn supports_close(&self) -> bool {
+        self.inner.supports_close()
+    }
+
+    async fn close_position(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        self.faults.before_call("close").await?;
+        self.inner.close_position(market_id, side).await
+    }
+
+    async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+        self.faults.before_call("resolution").await?;
+        self.inner.resolution(market_id).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Estimator wrapper
+// ---------------------------------------------------------------------------
+
+/// An `LlmEstimator` with injected faults.
+pub struct ChaosEstimator {
+    inner: Box<dyn LlmEstimator>,
+    faults: FaultInjector,
+}
+
+impl ChaosEstimator {
+    pub fn new(inner: Box<dyn LlmEstimator>, faults: ChaosFaults, seed: u64) -> Self {
+        let faults = FaultInjector::new("llm", faults, seed);
+        Self { inner, faults }
+    }
+}
+
+#[async_trait]
+impl LlmEstimator for ChaosEstimator {
+    async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+        self.faults.before_call("estimate").await?;
+        self.faults.corrupt_response("estimate")?;
+        self.inner.estimate_probability(market, context).await
+    }
+
+    /// Truncation keeps a prefix, so surviving estimates stay aligned
+    /// with their markets.
+    async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+        self.faults.before_call("batch").await?;
+        self.faults.corrupt_response("batch")?;
+        let mut estimates = self.inner.batch_estimate(markets).await?;
+        self.faults.truncate(&mut estimates);
+        Ok(estimates)
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        self.inner.cost_per_call()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Wiring helpers
+// ---------------------------------------------------------------------------
+
+/// Wrap `venue` if the (resolved) chaos config targets it.
+pub fn wrap_platform(venue: Arc<dyn PredictionPlatform>, cfg: &ChaosConfig) -> Arc<dyn PredictionPlatform> {
+    let targeted = cfg.target_platform.as_deref().is_none_or(|t| t == venue.name());
+    if !cfg.enabled || !targeted || cfg.platform == ChaosFaults::default() {
+        return venue;
+    }
+    Arc::new(ChaosPlatform::new(venue, cfg.platform.clone(), cfg.seed))
+}
+
+/// Wrap the estimator if the (resolved) chaos config injects LLM faults.
+pub fn wrap_estimator(llm: Box<dyn LlmEstimator>, cfg: &ChaosConfig) -> Box<dyn LlmEstimator> {
+    if !cfg.enabled || cfg.llm == ChaosFaults::default() {
+        return llm;
+    }
+    Box::new(ChaosEstimator::new(llm, cfg.llm.clone(), cfg.seed))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn faults(rate: f64) -> ChaosFaults {
+        ChaosFaults {
+            failure_rate: rate,
+            corrupt_rate: rate,
+            partial_batch_rate: rate,
+            ..ChaosFaults::default()
+        }
+    }
+
+    #[test]
+    fn test_roll_is_seeded_and_respects_rate() {
+        let a = FaultInjector::new("manifold", faults(0.3), 7);
+        let b = FaultInjector::new("manifold", faults(0.3), 7);
+        let rolls_a: Vec<bool> = (0..1000).map(|_| a.roll(0.3)).collect();
+        let rolls_b: Vec<bool> = (0..1000).map(|_| b.roll(0.3)).collect();
+        assert_eq!(rolls_a, rolls_b);
+
+        let hits = rolls_a.iter().filter(|&&r| r).count();
+        assert!((250..350).contains(&hits), "hits = {hits}");
+        assert!(!(0..100).any(|_| a.roll(0.0)));
+        assert!((0..100).all(|_| a.roll(1.0)));
+    }
+
+    #[tokio::test]
+    async fn test_injected_faults_shape() {
+        let always = FaultInjector::new("manifold", faults(1.0), 1);
+        let err = always.before_call("bet").await.unwrap_err();
+        assert!(err.downcast_ref::<InjectedFault>().is_some());
+
+        let err = always.corrupt_response("markets").unwrap_err();
+        assert!(err.to_string().contains("Failed to parse manifold markets response"));
+        assert!(err.downcast_ref::<serde_json::Error>().is_some());
+
+        let mut items: Vec<u32> = (0..10).collect();
+        always.truncate(&mut items);
+        assert!(items.len() < 10);
+        assert_eq!(items, (0..items.len() as u32).collect::<Vec<_>>());
+
+        let never = FaultInjector::new("manifold", ChaosFaults::default(), 1);
+        assert!(never.before_call("bet").await.is_ok());
+        assert!(never.corrupt_response("bet").is_ok());
+    }
+}
@@ -5,14 +5,24 @@
 //! resolved at runtime via `std::env::var`.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::time::SystemTime;
+use tracing::{error, info, warn};
+
+use crate::data::economics::SeriesRule;
+use crate::notifications::{EventKind, WebhookFormat};
+use crate::platforms::preflight::PreflightLimits;
+use crate::strategy::constraints::BetConstraints;
+use crate::strategy::kelly::FeeModel;
+use crate::types::{MarketCategory, Tier};
 
 /// Top-level application configuration.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub agent: AgentConfig,
     pub llm: LlmConfig,
@@ -24,12 +34,32 @@ pub struct AppConfig {
     pub scanner: ScannerConfig,
     #[serde(default)]
     pub enricher: EnricherConfig,
+    #[serde(default)]
+    pub execution: ExecutionConfig,
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+    #[serde(default)]
+    pub journal: JournalConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub diagnostics: DiagnosticsConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub costs: CostsConfig,
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
     pub data_sources: DataSourcesConfig,
     pub dashboard: DashboardConfig,
     pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AgentConfig {
     pub name: String,
     /// Trading execution mode: "dry" | "paper" | "live"
@@ -39,54 +69,454 @@ pub struct AgentConfig {
     pub initial_bankroll: Decimal,
     pub survival_threshold: Decimal,
     pub currency: String,
+    /// Seconds a cycle in flight at Ctrl+C gets to finish before it is
+    /// abandoned and the state marked for repair.
+    #[serde(default = "AgentConfig::default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
 }
 
 impl AgentConfig {
     fn default_trading_mode() -> String {
         "dry".to_string()
     }
+
+    fn default_shutdown_timeout_secs() -> u64 {
+        120
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LlmConfig {
     pub provider: String,
     pub model: String,
+    /// Env var holding the API key. Empty: the provider's default
+    /// ([`crate::llm::default_api_key_env`]).
+    #[serde(default)]
     pub api_key_env: String,
     pub max_tokens: u32,
+    /// Most markets sent in one batch call (also `max_markets_per_batch`).
+    #[serde(alias = "max_markets_per_batch")]
     pub batch_size: u32,
+    /// Model context window in tokens. When set, batch prompts are fitted
+    /// to it: data summaries are cut and the lowest-priority markets
+    /// deferred to the next cycle (see [`crate::llm::budget`]).
+    #[serde(default)]
+    pub context_window: Option<u32>,
     /// Fallback model for OpenRouter (used when primary model fails).
     #[serde(default)]
     pub fallback_model: Option<String>,
+    /// Chat Completions base URL for the `openai` provider, for
+    /// OpenAI-compatible servers (Azure, vLLM, LM Studio). Defaults to
+    /// `https://api.openai.com/v1`.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// `openai` provider: request `response_format: json_object` and
+    /// JSON-shaped answers. Not every compatible server supports it.
+    #[serde(default)]
+    pub json_mode: bool,
+    /// `grok` provider: ask for recent X posts to be cited when estimating
+    /// Politics and Culture markets.
+    #[serde(default)]
+    pub cite_x_posts: bool,
+    /// `anthropic` provider: ask for JSON answers, falling back to the
+    /// answer lines when a reply isn't JSON. Unset: on for models known
+    /// to follow the format ([`crate::llm::structured::supports`]).
+    #[serde(default)]
+    pub structured_output: Option<bool>,
+    /// `openai` provider: USD per 1K input tokens (default: GPT-4o pricing).
+    #[serde(default)]
+    pub input_cost_per_1k: Option<f64>,
+    /// `openai` provider: USD per 1K output tokens.
+    #[serde(default)]
+    pub output_cost_per_1k: Option<f64>,
+    /// Optional shadow model canary ([llm.shadow]).
+    #[serde(default)]
+    pub shadow: Option<ShadowLlmConfig>,
+    /// Same-model self-critique of high-stake estimates ([llm.self_critique]).
+    #[serde(default)]
+    pub self_critique: SelfCritiqueConfig,
+    /// Secondary provider that takes over batches the primary fails ([llm.secondary]).
+    #[serde(default)]
+    pub secondary: Option<SecondaryLlmConfig>,
+    /// Ensemble of the primary with further providers ([llm.ensemble]).
+    #[serde(default)]
+    pub ensemble: Option<EnsembleConfig>,
+    /// Model serving each listed category in place of the primary
+    /// ([llm.category_models]), keyed like `risk.category_thresholds`.
+    /// Tables are shaped like [llm.secondary] (see [`crate::llm::routing`]).
+    #[serde(default)]
+    pub category_models: HashMap<String, SecondaryLlmConfig>,
+    /// Per-market estimation budget tiers ([llm.tiers]).
+    #[serde(default)]
+    pub tiers: TiersConfig,
+    /// Reuse of recent estimates for unchanged markets ([llm.cache]).
+    #[serde(default)]
+    pub cache: EstimateCacheConfig,
+    /// Cap on one cycle's enrichment + estimation spend in USD; the
+    /// scanned list is cut to fit (see [`crate::engine::cost_guard`]).
+    /// 0 = no cap.
+    #[serde(default)]
+    pub max_cycle_cost: Decimal,
+}
+
+impl LlmConfig {
+    /// Env var holding the primary provider's API key.
+    pub fn key_env(&self) -> &str {
+        if self.api_key_env.is_empty() {
+            crate::llm::default_api_key_env(&self.provider)
+        } else {
+            &self.api_key_env
+        }
+    }
+}
+
+/// Failover provider configuration ([llm.secondary] section).
+///
+/// Built at startup and idle unless a batch fails on the primary after its
+/// own retries; the failed batch is then re-issued here.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SecondaryLlmConfig {
+    /// Provider: "openrouter", "anthropic", "openai" or "grok".
+    pub provider: String,
+    pub model: String,
+    /// Env var holding the secondary provider's API key.
+    pub api_key_env: String,
+}
+
+/// Multi-provider ensemble ([llm.ensemble] section).
+///
+/// Every estimate is asked of the primary provider and each of `members`
+/// at once and combined (see [`crate::llm::ensemble`]). Members are
+/// `[[llm.ensemble.members]]` tables shaped like [llm.secondary].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnsembleConfig {
+    pub members: Vec<SecondaryLlmConfig>,
+    /// Confidence lost per unit of spread between the members'
+    /// probabilities: at 1.0, members 0.2 apart cut confidence by 20%.
+    #[serde(default = "EnsembleConfig::default_disagreement_penalty")]
+    pub disagreement_penalty: Decimal,
+}
+
+impl EnsembleConfig {
+    fn default_disagreement_penalty() -> Decimal { dec!(1.0) }
+}
+
+/// Self-critique pass ([llm.self_critique] section).
+///
+/// Estimates whose tentative Kelly stake is at least `min_bet_pct` of the
+/// bankroll get a follow-up call to the same model asking for the strongest
+/// reasons the estimate could be wrong and a revised probability.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SelfCritiqueConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Tentative stake, as a fraction of bankroll, that triggers a critique.
+    #[serde(default = "SelfCritiqueConfig::default_min_bet_pct")]
+    pub min_bet_pct: Decimal,
+    /// Hard cap on critique calls per cycle (largest stakes first).
+    #[serde(default = "SelfCritiqueConfig::default_max_per_cycle")]
+    pub max_per_cycle: usize,
+    /// Use the revised probability only if it moved by more than this.
+    #[serde(default = "SelfCritiqueConfig::default_revise_threshold")]
+    pub revise_threshold: Decimal,
+}
+
+impl SelfCritiqueConfig {
+    fn default_min_bet_pct() -> Decimal { dec!(0.03) }
+    fn default_max_per_cycle() -> usize { 3 }
+    fn default_revise_threshold() -> Decimal { dec!(0.03) }
+}
+
+impl Default for SelfCritiqueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_bet_pct: Self::default_min_bet_pct(),
+            max_per_cycle: Self::default_max_per_cycle(),
+            revise_threshold: Self::default_revise_threshold(),
+        }
+    }
+}
+
+/// Estimate cache ([llm.cache] section).
+///
+/// A market's last estimate is reused instead of a new LLM call while it
+/// is younger than `ttl_mins` and the YES price has moved less than
+/// `max_price_move` since. Markets closing within `min_hours_to_deadline`
+/// are always re-estimated.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EstimateCacheConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "EstimateCacheConfig::default_ttl_mins")]
+    pub ttl_mins: i64,
+    #[serde(default = "EstimateCacheConfig::default_max_price_move")]
+    pub max_price_move: Decimal,
+    #[serde(default = "EstimateCacheConfig::default_min_hours_to_deadline")]
+    pub min_hours_to_deadline: i64,
+    #[serde(default = "EstimateCacheConfig::default_file")]
+    pub file: String,
+}
+
+impl EstimateCacheConfig {
+    fn default_ttl_mins() -> i64 { 180 }
+    fn default_max_price_move() -> Decimal { dec!(0.03) }
+    fn default_min_hours_to_deadline() -> i64 { 24 }
+    fn default_file() -> String { "oracle_estimate_cache.json".to_string() }
+}
+
+impl Default for EstimateCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_mins: Self::default_ttl_mins(),
+            max_price_move: Self::default_max_price_move(),
+            min_hours_to_deadline: Self::default_min_hours_to_deadline(),
+            file: Self::default_file(),
+        }
+    }
+}
+
+/// Estimation budget tiers ([llm.tiers] section).
+///
+/// Each market is assigned tier A, B or C from its scan priority score and
+/// the largest bet it could size (`risk.max_bet_pct` of the bankroll it
+/// draws on, in AUD). A market takes the highest tier whose `min_priority`
+/// and `min_stake` it meets; tier C takes the rest. The tier sets the
+/// token budget, context and call pattern of its estimate.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TiersConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// AUD value of one Mana, for comparing Manifold stakes.
+    #[serde(default = "TiersConfig::default_mana_aud")]
+    pub mana_aud: Decimal,
+    #[serde(default = "TiersConfig::default_a")]
+    pub a: TierParams,
+    #[serde(default = "TiersConfig::default_b")]
+    pub b: TierParams,
+    #[serde(default = "TiersConfig::default_c")]
+    pub c: TierParams,
+}
+
+/// Estimation budget for one tier.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TierParams {
+    /// Minimum scan priority score (unused for tier C).
+    #[serde(default)]
+    pub min_priority: f64,
+    /// Minimum hypothetical max stake in AUD (unused for tier C).
+    #[serde(default)]
+    pub min_stake: Decimal,
+    /// Output token cap per request.
+    pub max_tokens: u32,
+    /// Estimate each market in its own call rather than in batches.
+    #[serde(default)]
+    pub individual: bool,
+    /// Send the full enrichment context; otherwise a summary only.
+    #[serde(default = "TierParams::default_full_context")]
+    pub full_context: bool,
+    /// Eligible for the [llm.self_critique] pass.
+    #[serde(default)]
+    pub critique: bool,
+    /// Estimates drawn and averaged per market.
+    #[serde(default = "TierParams::default_samples")]
+    pub samples: u32,
+}
+
+impl TierParams {
+    fn default_full_context() -> bool { true }
+    fn default_samples() -> u32 { 1 }
+}
+
+impl TiersConfig {
+    fn default_mana_aud() -> Decimal { dec!(0.01) }
+    fn default_a() -> TierParams {
+        TierParams {
+            min_priority: 80.0,
+            min_stake: dec!(20),
+            max_tokens: 1500,
+            individual: true,
+            full_context: true,
+            critique: true,
+            samples: 3,
+        }
+    }
+    fn default_b() -> TierParams {
+        TierParams {
+            min_priority: 40.0,
+            min_stake: dec!(5),
+            max_tokens: 1024,
+            individual: false,
+            full_context: true,
+            critique: false,
+            samples: 1,
+        }
+    }
+    fn default_c() -> TierParams {
+        TierParams {
+            min_priority: 0.0,
+            min_stake: Decimal::ZERO,
+            max_tokens: 300,
+            individual: false,
+            full_context: false,
+            critique: false,
+            samples: 1,
+        }
+    }
+
+    /// Budget for `tier`.
+    pub fn params(&self, tier: Tier) -> &TierParams {
+        match tier {
+            Tier::A => &self.a,
+            Tier::B => &self.b,
+            Tier::C => &self.c,
+        }
+    }
+}
+
+impl Default for TiersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mana_aud: Self::default_mana_aud(),
+            a: Self::default_a(),
+            b: Self::default_b(),
+            c: Self::default_c(),
+        }
+    }
+}
+
+/// Shadow-mode canary configuration ([llm.shadow] section).
+///
+/// The shadow model double-estimates a random sample of markets each cycle;
+/// its estimates are recorded for comparison and never used for betting.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShadowLlmConfig {
+    /// Model to evaluate (e.g. "anthropic/claude-sonnet-4-6").
+    pub model: String,
+    /// Provider for the shadow model. Defaults to the primary provider.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Env var holding the shadow provider's API key. Defaults to the primary's.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Percentage of each cycle's markets to double-estimate (0–100).
+    #[serde(default = "ShadowLlmConfig::default_sample_pct")]
+    pub sample_pct: f64,
+    /// Hard cap on shadow-estimated markets per cycle.
+    #[serde(default = "ShadowLlmConfig::default_max_per_cycle")]
+    pub max_per_cycle: usize,
+}
+
+impl ShadowLlmConfig {
+    fn default_sample_pct() -> f64 { 10.0 }
+    fn default_max_per_cycle() -> usize { 10 }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PlatformsConfig {
     pub forecastex: ForecastExConfig,
     pub metaculus: MetaculusConfig,
     pub manifold: ManifoldConfig,
     #[serde(default)]
     pub betfair: BetfairConfig,
+    #[serde(default)]
+    pub kalshi: KalshiConfig,
+}
+
+impl PlatformsConfig {
+    fn schedules(&self) -> [(&'static str, &PlatformSchedule); 5] {
+        [
+            ("forecastex", &self.forecastex.schedule),
+            ("metaculus", &self.metaculus.schedule),
+            ("manifold", &self.manifold.schedule),
+            ("betfair", &self.betfair.schedule),
+            ("kalshi", &self.kalshi.schedule),
+        ]
+    }
+
+    /// Scan interval in cycles by platform name, for platforms not scanned
+    /// every cycle.
+    pub fn scan_intervals(&self) -> HashMap<String, u32> {
+        self.schedules()
+            .into_iter()
+            .filter(|(_, s)| s.scan_every_n_cycles > 1)
+            .map(|(p, s)| (p.to_string(), s.scan_every_n_cycles))
+            .collect()
+    }
+
+    /// Request budget per minute by platform name, for budgeted platforms.
+    pub fn request_budgets(&self) -> HashMap<String, u32> {
+        self.schedules()
+            .into_iter()
+            .filter(|(_, s)| s.requests_per_minute > 0)
+            .map(|(p, s)| (p.to_string(), s.requests_per_minute))
+            .collect()
+    }
+}
+
+/// Scan cadence and request budget of one platform, set in its own
+/// `[platforms.<name>]` section.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PlatformSchedule {
+    /// Fetch markets every N cycles and reuse the last list in between.
+    #[serde(default = "PlatformSchedule::default_scan_every_n_cycles")]
+    pub scan_every_n_cycles: u32,
+    /// Calls to the venue per minute (0 = unlimited).
+    #[serde(default)]
+    pub requests_per_minute: u32,
+}
+
+impl Default for PlatformSchedule {
+    fn default() -> Self {
+        Self { scan_every_n_cycles: Self::default_scan_every_n_cycles(), requests_per_minute: 0 }
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl PlatformSchedule {
+    fn default_scan_every_n_cycles() -> u32 { 1 }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ForecastExConfig {
     pub enabled: bool,
     pub ib_host: String,
     pub ib_port: u16,
     pub ib_client_id: u32,
     pub account_id_env: String,
+    /// Only trade a paper account (`DU...`). Paper orders are placed in
+    /// every trading mode; live ones need `trading_mode = "live"`.
+    #[serde(default = "ForecastExConfig::default_paper")]
+    pub paper: bool,
+    /// ForecastEx underlyings scanned (e.g. "FF" for the fed funds target).
+    #[serde(default = "ForecastExConfig::default_symbols")]
+    pub symbols: Vec<String>,
+    /// Contracts quoted per scan, nearest expiry first.
+    #[serde(default = "ForecastExConfig::default_max_markets")]
+    pub max_markets: usize,
+    #[serde(flatten)]
+    pub schedule: PlatformSchedule,
+}
+
+impl ForecastExConfig {
+    fn default_paper() -> bool { true }
+    fn default_symbols() -> Vec<String> { vec!["FF".to_string()] }
+    fn default_max_markets() -> usize { 40 }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetaculusConfig {
     pub enabled: bool,
     /// Env var name for the Metaculus API token (e.g. "METACULUS_API_TOKEN").
     /// Required — Metaculus API requires authentication for all requests.
     #[serde(default)]
     pub api_key_env: Option<String>,
+    #[serde(flatten)]
+    pub schedule: PlatformSchedule,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ManifoldConfig {
     pub enabled: bool,
     /// Env var name for the Manifold API key (default: "MANIFOLD_API_KEY").
@@ -98,9 +528,35 @@ pub struct ManifoldConfig {
     /// real bankroll would be incorrect. Defaults to 1000 Mana if unset.
     #[serde(default)]
     pub mana_bankroll: Option<Decimal>,
+    /// How Mana balance and P&L are accounted. `reconciled` derives them
+    /// from the account's own history, for accounts also traded by hand.
+    #[serde(default)]
+    pub accounting: AccountingMode,
+    /// Reconciled mode: max seconds between a platform bet and a receipt
+    /// (or auto-exit sale) for them to be matched without a shared id.
+    #[serde(default = "ManifoldConfig::default_match_window_secs")]
+    pub match_window_secs: i64,
+    #[serde(flatten)]
+    pub schedule: PlatformSchedule,
+}
+
+impl ManifoldConfig {
+    fn default_match_window_secs() -> i64 { 120 }
+}
+
+/// Per-platform accounting mode.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountingMode {
+    /// The agent assumes it is the only actor on the account.
+    #[default]
+    Internal,
+    /// Balance and P&L come from platform data; activity that doesn't
+    /// match the agent's receipts is tracked as external.
+    Reconciled,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BetfairConfig {
     pub enabled: bool,
     /// Env var name for Betfair app key (default: "BETFAIR_APP_KEY").
@@ -112,6 +568,12 @@ pub struct BetfairConfig {
     /// Env var name for Betfair password (default: "BETFAIR_PASSWORD").
     #[serde(default = "BetfairConfig::default_password_env")]
     pub password_env: String,
+    /// Minutes between background session keep-alives. 0 = none; a session
+    /// near expiry is still extended when next used.
+    #[serde(default = "BetfairConfig::default_keep_alive_mins")]
+    pub keep_alive_mins: u64,
+    #[serde(flatten)]
+    pub schedule: PlatformSchedule,
 }
 
 impl Default for BetfairConfig {
@@ -121,6 +583,8 @@ impl Default for BetfairConfig {
             app_key_env: "BETFAIR_APP_KEY".to_string(),
             username_env: "BETFAIR_USERNAME".to_string(),
             password_env: "BETFAIR_PASSWORD".to_string(),
+            keep_alive_mins: Self::default_keep_alive_mins(),
+            schedule: PlatformSchedule::default(),
         }
     }
 }
@@ -135,119 +599,1311 @@ impl BetfairConfig {
     fn default_password_env() -> String {
         "BETFAIR_PASSWORD".to_string()
     }
+    fn default_keep_alive_mins() -> u64 {
+        60
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KalshiConfig {
+    pub enabled: bool,
+    /// Env var name for the Kalshi API key id (default: "KALSHI_API_KEY_ID").
+    /// Market scanning is public; the key is only needed to trade.
+    #[serde(default = "KalshiConfig::default_api_key_id_env")]
+    pub api_key_id_env: String,
+    /// Env var name for the path to the key's RSA private key PEM
+    /// (default: "KALSHI_PRIVATE_KEY_PATH").
+    #[serde(default = "KalshiConfig::default_private_key_path_env")]
+    pub private_key_path_env: String,
+    /// Use the demo exchange instead of production.
+    #[serde(default)]
+    pub demo: bool,
+    #[serde(flatten)]
+    pub schedule: PlatformSchedule,
+}
+
+impl Default for KalshiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_key_id_env: Self::default_api_key_id_env(),
+            private_key_path_env: Self::default_private_key_path_env(),
+            demo: false,
+            schedule: PlatformSchedule::default(),
+        }
+    }
+}
+
+impl KalshiConfig {
+    fn default_api_key_id_env() -> String {
+        "KALSHI_API_KEY_ID".to_string()
+    }
+    fn default_private_key_path_env() -> String {
+        "KALSHI_PRIVATE_KEY_PATH".to_string()
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RiskConfig {
     pub mispricing_threshold: Decimal,
     pub kelly_multiplier: Decimal,
     pub max_bet_pct: Decimal,
     pub max_exposure_pct: Decimal,
+    /// Halt new bets once today's AUD loss, against the last end-of-day
+    /// snapshot, reaches this fraction of that day's closing bankroll.
+    #[serde(default)]
+    pub max_daily_loss_pct: Option<Decimal>,
+    /// Stake multiplier by drawdown from peak ([[risk.drawdown_tiers]]);
+    /// the last tier halts betting.
+    #[serde(default = "DrawdownTier::defaults")]
+    pub drawdown_tiers: Vec<DrawdownTier>,
+    /// Whether drawdown (tiers, halt and resume) is measured on the booked
+    /// bankroll or on mark-to-market equity.
+    #[serde(default)]
+    pub drawdown_basis: DrawdownBasis,
+    /// A drawdown-paused agent resumes only once its drawdown from peak is
+    /// back below this fraction (the halt tier starts at 40% by default).
+    #[serde(default = "RiskConfig::default_drawdown_resume_pct")]
+    pub drawdown_resume_pct: Decimal,
+    /// Stake cap on one side of one market, counting what the platforms
+    /// report holding; adding beyond it is refused.
+    #[serde(default = "RiskConfig::default_max_holding_pct")]
+    pub max_holding_pct: Decimal,
+    /// Annual return required of locked capital; Kelly sizing discounts
+    /// each edge by it over the market's time to resolution (0 = off).
+    #[serde(default = "RiskConfig::default_capital_hurdle_annual_pct")]
+    pub capital_hurdle_annual_pct: Decimal,
+    /// Largest move in the bought side's probability a bet may cause,
+    /// estimated from the venue's liquidity just before placing; larger
+    /// bets are scaled down to fit, or skipped (`None` = unchecked).
+    #[serde(default)]
+    pub max_price_impact_pct: Option<Decimal>,
     pub min_liquidity_contracts: u64,
     pub category_thresholds: HashMap<String, Decimal>,
+    /// Periodic re-tuning of `category_thresholds` from realised edge
+    /// performance ([risk.adaptive_thresholds]).
+    #[serde(default)]
+    pub adaptive_thresholds: AdaptiveThresholdConfig,
+    /// Per-category cool-down after a losing streak ([risk.cool_down]).
+    #[serde(default)]
+    pub cool_down: CoolDownConfig,
+    /// Edge dampening towards a Metaculus consensus ([risk.cross_ref]).
+    #[serde(default)]
+    pub cross_ref: CrossRefDampeningConfig,
+    /// Edge threshold scaling by estimate confidence, and the confidence
+    /// floor ([risk.confidence_scaling]).
+    #[serde(default)]
+    pub confidence_scaling: ConfidenceScalingConfig,
+    /// Rules over operator-declared market links ([risk.links]).
+    #[serde(default)]
+    pub links: LinkRulesConfig,
+    /// Caps per operator tag ([risk.tags."AU-politics"]), checked on top
+    /// of the category caps.
+    #[serde(default)]
+    pub tags: BTreeMap<String, TagLimit>,
+    /// Keyword clusters of correlated questions ([risk.correlation]).
+    #[serde(default)]
+    pub correlation: CorrelationConfig,
+    /// Fee model per platform for Kelly sizing ([risk.fees]); platforms
+    /// not listed pay the flat IB commission.
+    #[serde(default = "FeeModel::defaults")]
+    pub fees: HashMap<String, FeeModel>,
+    /// Stake granularity per platform ([risk.bet_constraints]); platforms
+    /// not listed take any whole-cent stake.
+    #[serde(default = "BetConstraints::defaults")]
+    pub bet_constraints: HashMap<String, BetConstraints>,
 }
 
-/// Strategy / auto-exit configuration ([strategy] section).
-///
-/// ## Betfair Australia minimum stake (AUD, March 2026)
-/// - Exchange back/lay bets placed via API: **AUD $1.00** minimum
-/// - BSP back bets: same AUD $1.00 minimum
-/// - BSP lay liability: unchanged (not affected by the 2022 reduction)
-/// - Sub-minimum bets via API are technically possible but risk account suspension
-/// - Min Bet Payout exception: bets below $1 AUD are valid when payout ≥ $10 AUD
-///
-/// Oracle defaults `min_close_stake` to $2.00 AUD as a safety buffer above the
-/// $1.00 absolute minimum. Never place a closing bet below this value.
+impl RiskConfig {
+    fn default_drawdown_resume_pct() -> Decimal { dec!(0.30) }
+    fn default_max_holding_pct() -> Decimal { dec!(0.06) }
+    fn default_capital_hurdle_annual_pct() -> Decimal { dec!(0.10) }
+}
+
+/// Caps on the bets carrying one tag. A bet counts against every tag it
+/// carries; a cap left out is not enforced.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TagLimit {
+    /// Combined open stake on the tag, as a fraction of bankroll.
+    #[serde(default)]
+    pub max_exposure_pct: Option<Decimal>,
+    /// Open bets on the tag.
+    #[serde(default)]
+    pub max_open_bets: Option<usize>,
+}
+
+/// What drawdown from peak is measured on.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawdownBasis {
+    /// The AUD bankroll: realised P&L less costs.
+    #[default]
+    Bankroll,
+    /// The bankroll plus open AUD bets' unrealized P&L, against the peak
+    /// equity.
+    Equity,
+}
+
+/// One step of the drawdown de-risking curve: from `from_pct` drawdown
+/// (fraction from peak) until the next tier, risk-approved stakes are
+/// scaled by `multiplier`. A multiplier of 0 halts betting.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DrawdownTier {
+    pub from_pct: Decimal,
+    pub multiplier: Decimal,
+}
+
+impl DrawdownTier {
+    /// Full size under 10%, then down a quarter every 10% to a halt at 40%.
+    pub fn defaults() -> Vec<Self> {
+        [(dec!(0), dec!(1.0)), (dec!(0.10), dec!(0.75)), (dec!(0.20), dec!(0.5)), (dec!(0.30), dec!(0.25)), (dec!(0.40), dec!(0))]
+            .into_iter()
+            .map(|(from_pct, multiplier)| Self { from_pct, multiplier })
+            .collect()
+    }
+
+    /// Multiplier of the last tier `drawdown` has reached (1 below the first).
+    pub fn multiplier_at(tiers: &[Self], drawdown: Decimal) -> Decimal {
+        tiers.iter().rev().find(|t| drawdown >= t.from_pct).map_or(Decimal::ONE, |t| t.multiplier)
+    }
+
+    /// Drawdown at which betting halts, if any tier halts.
+    pub fn halt_pct(tiers: &[Self]) -> Option<Decimal> {
+        tiers.iter().find(|t| t.multiplier <= Decimal::ZERO).map(|t| t.from_pct)
+    }
+
+    /// Tiers must start at 0, rise strictly in `from_pct` within [0, 1),
+    /// fall (or hold) in `multiplier` within [0, 1], and end in a halt.
+    fn validate(tiers: &[Self]) -> Result<()> {
+        anyhow::ensure!(
+            tiers.first().is_some_and(|t| t.from_pct.is_zero()),
+            "risk.drawdown_tiers must start with a tier at from_pct = 0"
+        );
+        for tier in tiers {
+            anyhow::ensure!(
+                tier.from_pct >= Decimal::ZERO && tier.from_pct < Decimal::ONE,
+                "risk.drawdown_tiers from_pct must be in [0, 1), got {}",
+                tier.from_pct
+            );
+            anyhow::ensure!(
+                tier.multiplier >= Decimal::ZERO && tier.multiplier <= Decimal::ONE,
+                "risk.drawdown_tiers multiplier must be in [0, 1], got {}",
+                tier.multiplier
+            );
+        }
+        for pair in tiers.windows(2) {
+            anyhow::ensure!(
+                pair[1].from_pct > pair[0].from_pct && pair[1].multiplier <= pair[0].multiplier,
+                "risk.drawdown_tiers must rise in from_pct and not rise in multiplier ({} → {})",
+                pair[0].from_pct,
+                pair[1].from_pct
+            );
+        }
+        anyhow::ensure!(
+            tiers.last().is_some_and(|t| t.multiplier.is_zero()),
+            "risk.drawdown_tiers must end with a halt tier (multiplier = 0)"
+        );
+        Ok(())
+    }
+}
+
+/// Adaptive per-category edge thresholds.
 ///
-/// ## Manifold minimum sell
-/// - No documented minimum; `shares` parameter is optional (defaults to all)
-/// - Practical minimum: 1 Mana — essentially no meaningful constraint
-#[derive(Debug, Deserialize, Clone)]
-pub struct StrategyConfig {
-    /// Enable automatic take-profit / stop-loss / time-based position closing.
-    #[serde(default = "StrategyConfig::default_enable_auto_exit")]
-    pub enable_auto_exit: bool,
-    /// Close position if unrealized P&L reaches this percentage (e.g. 15.0 = +15%).
-    #[serde(default = "StrategyConfig::default_take_profit_percent")]
-    pub take_profit_percent: Decimal,
-    /// Close position if unrealized P&L falls to this percentage (e.g. -10.0 = -10%).
-    #[serde(default = "StrategyConfig::default_stop_loss_percent")]
-    pub stop_loss_percent: Decimal,
-    /// Force-close after this many hours (0 = disabled).
-    #[serde(default = "StrategyConfig::default_max_hold_hours")]
-    pub max_hold_hours: u64,
-    /// Minimum closing stake in AUD (Betfair) or Mana (Manifold).
-    /// Betfair official minimum is $1.00 AUD; default here is $2.00 as buffer.
-    #[serde(default = "StrategyConfig::default_min_close_stake")]
-    pub min_close_stake: Decimal,
-    /// If true, log close decisions without placing real orders (dry-run).
+/// Every `review_days`, each category's threshold moves towards the lowest
+/// detected-edge bucket whose realised return is positive by at least
+/// `confidence_z` standard errors, bounded to `[min_threshold, max_threshold]`
+/// and by `max_step` per review.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdaptiveThresholdConfig {
     #[serde(default)]
-    pub auto_exit_dry_run: bool,
+    pub enabled: bool,
+    #[serde(default = "AdaptiveThresholdConfig::default_review_days")]
+    pub review_days: i64,
+    /// Width of the detected-edge buckets (e.g. 0.02 = 2 points).
+    #[serde(default = "AdaptiveThresholdConfig::default_bucket_width")]
+    pub bucket_width: Decimal,
+    /// Resolved bets a category needs before it is adjusted at all.
+    #[serde(default = "AdaptiveThresholdConfig::default_min_resolutions")]
+    pub min_resolutions: usize,
+    /// Resolved bets a bucket needs before its return is trusted.
+    #[serde(default = "AdaptiveThresholdConfig::default_min_bucket_trades")]
+    pub min_bucket_trades: usize,
+    /// Standard errors the mean return must clear zero by.
+    #[serde(default = "AdaptiveThresholdConfig::default_confidence_z")]
+    pub confidence_z: f64,
+    #[serde(default = "AdaptiveThresholdConfig::default_min_threshold")]
+    pub min_threshold: Decimal,
+    #[serde(default = "AdaptiveThresholdConfig::default_max_threshold")]
+    pub max_threshold: Decimal,
+    /// Largest change to a threshold in one review.
+    #[serde(default = "AdaptiveThresholdConfig::default_max_step")]
+    pub max_step: Decimal,
+    /// Categories whose thresholds are never adjusted.
+    #[serde(default)]
+    pub frozen: Vec<MarketCategory>,
 }
 
-impl Default for StrategyConfig {
+impl Default for AdaptiveThresholdConfig {
     fn default() -> Self {
         Self {
-            enable_auto_exit: true,
-            take_profit_percent: rust_decimal_macros::dec!(15.0),
-            stop_loss_percent: rust_decimal_macros::dec!(-10.0),
-            max_hold_hours: 48,
-            min_close_stake: rust_decimal_macros::dec!(2.0),
-            auto_exit_dry_run: false,
+            enabled: false,
+            review_days: Self::default_review_days(),
+            bucket_width: Self::default_bucket_width(),
+            min_resolutions: Self::default_min_resolutions(),
+            min_bucket_trades: Self::default_min_bucket_trades(),
+            confidence_z: Self::default_confidence_z(),
+            min_threshold: Self::default_min_threshold(),
+            max_threshold: Self::default_max_threshold(),
+            max_step: Self::default_max_step(),
+            frozen: Vec::new(),
         }
     }
 }
 
-impl StrategyConfig {
-    fn default_enable_auto_exit() -> bool { true }
-    fn default_take_profit_percent() -> Decimal { rust_decimal_macros::dec!(15.0) }
-    fn default_stop_loss_percent() -> Decimal { rust_decimal_macros::dec!(-10.0) }
-    fn default_max_hold_hours() -> u64 { 48 }
-    fn default_min_close_stake() -> Decimal { rust_decimal_macros::dec!(2.0) }
+impl AdaptiveThresholdConfig {
+    fn default_review_days() -> i64 { 7 }
+    fn default_bucket_width() -> Decimal { rust_decimal_macros::dec!(0.02) }
+    fn default_min_resolutions() -> usize { 100 }
+    fn default_min_bucket_trades() -> usize { 20 }
+    fn default_confidence_z() -> f64 { 1.0 }
+    fn default_min_threshold() -> Decimal { rust_decimal_macros::dec!(0.04) }
+    fn default_max_threshold() -> Decimal { rust_decimal_macros::dec!(0.20) }
+    fn default_max_step() -> Decimal { rust_decimal_macros::dec!(0.01) }
 }
 
-/// Scanner / market-router configuration ([scanner] section).
-#[derive(Debug, Deserialize, Clone)]
-pub struct ScannerConfig {
-    /// Minimum Jaccard-based similarity score (0–1) to cross-reference two markets.
-    #[serde(default = "ScannerConfig::default_match_threshold")]
-    pub match_threshold: f64,
-    /// Minimum liquidity (or forecaster count for Metaculus) to keep a market.
-    #[serde(default = "ScannerConfig::default_min_liquidity")]
-    pub min_liquidity: Decimal,
-    /// Maximum hours until deadline — markets closing later than this are skipped.
-    #[serde(default = "ScannerConfig::default_max_hours_to_deadline")]
-    pub max_hours_to_deadline: f64,
-    /// Minimum hours until deadline — markets closing sooner than this are skipped.
-    #[serde(default = "ScannerConfig::default_min_hours_to_deadline")]
-    pub min_hours_to_deadline: f64,
-    /// Maximum markets passed to the enrichment and LLM estimation stages.
-    #[serde(default = "ScannerConfig::default_max_markets_to_process")]
-    pub max_markets_to_process: usize,
+/// What a category cool-down does to new bets in that category.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CoolDownMode {
+    /// Require `edge_multiplier` × the category threshold.
+    #[default]
+    Multiplier,
+    /// Refuse every bet in the category.
+    Block,
 }
 
-impl Default for ScannerConfig {
-    fn default() -> Self {
-        Self {
+/// Losing-streak cool-down per category.
+///
+/// After `losing_streak` consecutive resolved losses in a category, new bets
+/// there are blocked or held to a higher edge until a win, `lift_after_hours`,
+/// or `lift_after_skips` rejected opportunities — whichever comes first.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoolDownConfig {
+    /// Consecutive losses that trigger a cool-down (0 = disabled).
+    #[serde(default = "CoolDownConfig::default_losing_streak")]
+    pub losing_streak: u32,
+    #[serde(default)]
+    pub mode: CoolDownMode,
+    /// Multiple of the category edge threshold required in `multiplier` mode.
+    #[serde(default = "CoolDownConfig::default_edge_multiplier")]
+    pub edge_multiplier: Decimal,
+    /// Hours after which a cool-down lifts on its own (0 = never).
+    #[serde(default = "CoolDownConfig::default_lift_after_hours")]
+    pub lift_after_hours: i64,
+    /// Rejected opportunities after which a cool-down lifts (0 = never).
+    #[serde(default = "CoolDownConfig::default_lift_after_skips")]
+    pub lift_after_skips: u32,
+}
+
+impl Default for CoolDownConfig {
+    fn default() -> Self {
+        Self {
+            losing_streak: Self::default_losing_streak(),
+            mode: CoolDownMode::default(),
+            edge_multiplier: Self::default_edge_multiplier(),
+            lift_after_hours: Self::default_lift_after_hours(),
+            lift_after_skips: Self::default_lift_after_skips(),
+        }
+    }
+}
+
+impl CoolDownConfig {
+    fn default_losing_streak() -> u32 { 4 }
+    fn default_edge_multiplier() -> Decimal { dec!(1.5) }
+    fn default_lift_after_hours() -> i64 { 72 }
+    fn default_lift_after_skips() -> u32 { 10 }
+}
+
+/// Metaculus consensus as a check on the edge.
+///
+/// When a market carries a Metaculus forecast from at least
+/// `min_forecasters` forecasters, the fair value behind its edge is pulled
+/// towards that forecast by `weight`, scaled down linearly for crowds
+/// smaller than `full_weight_forecasters`. An edge the pull takes below
+/// the category threshold is dropped. See [`crate::strategy::edge`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CrossRefDampeningConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "CrossRefDampeningConfig::default_min_forecasters")]
+    pub min_forecasters: u32,
+    /// Pull at `full_weight_forecasters` or more, in [0, 1].
+    #[serde(default = "CrossRefDampeningConfig::default_weight")]
+    pub weight: Decimal,
+    #[serde(default = "CrossRefDampeningConfig::default_full_weight_forecasters")]
+    pub full_weight_forecasters: u32,
+}
+
+impl Default for CrossRefDampeningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_forecasters: Self::default_min_forecasters(),
+            weight: Self::default_weight(),
+            full_weight_forecasters: Self::default_full_weight_forecasters(),
+        }
+    }
+}
+
+impl CrossRefDampeningConfig {
+    fn default_min_forecasters() -> u32 { 30 }
+    fn default_weight() -> Decimal { dec!(0.75) }
+    fn default_full_weight_forecasters() -> u32 { 200 }
+}
+
+/// Confidence-weighted edge thresholds ([risk.confidence_scaling]).
+///
+/// When enabled, an estimate's category threshold becomes
+/// `threshold × (1 + k × (1 − confidence))`, so a low-confidence estimate
+/// needs a larger edge. Estimates under `min_confidence` are skipped
+/// whether or not scaling is enabled. See [`crate::strategy::edge`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfidenceScalingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Curve steepness, ≥ 0.
+    #[serde(default = "ConfidenceScalingConfig::default_k")]
+    pub k: Decimal,
+    /// Confidence floor, in [0, 1].
+    #[serde(default = "ConfidenceScalingConfig::default_min_confidence")]
+    pub min_confidence: Decimal,
+}
+
+impl Default for ConfidenceScalingConfig {
+    fn default() -> Self {
+        Self { enabled: false, k: Self::default_k(), min_confidence: Self::default_min_confidence() }
+    }
+}
+
+impl ConfidenceScalingConfig {
+    fn default_k() -> Decimal { dec!(1) }
+    fn default_min_confidence() -> Decimal { dec!(0.10) }
+}
+
+/// Rules enforced over linked markets declared in `file`.
+///
+/// Links are `implies` pairs and `exclusive` groups keyed
+/// `platform:market_id`; see [`crate::strategy::links`]. A missing file
+/// declares no links.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkRulesConfig {
+    #[serde(default = "LinkRulesConfig::default_file")]
+    pub file: String,
+    /// Refuse bets that logically oppose a held position in a linked set.
+    #[serde(default = "LinkRulesConfig::default_block_opposing")]
+    pub block_opposing: bool,
+    /// Combined stake cap across each linked set, as a fraction of bankroll.
+    #[serde(default = "LinkRulesConfig::default_max_set_exposure_pct")]
+    pub max_set_exposure_pct: Decimal,
+}
+
+impl Default for LinkRulesConfig {
+    fn default() -> Self {
+        Self {
+            file: Self::default_file(),
+            block_opposing: Self::default_block_opposing(),
+            max_set_exposure_pct: Self::default_max_set_exposure_pct(),
+        }
+    }
+}
+
+impl LinkRulesConfig {
+    fn default_file() -> String { "links.toml".to_string() }
+    fn default_block_opposing() -> bool { true }
+    fn default_max_set_exposure_pct() -> Decimal { dec!(0.15) }
+}
+
+/// Caps on correlated markets that no category or link catches.
+///
+/// Each question is matched against the keyword clusters in `groups`; see
+/// [`crate::strategy::correlation`]. With no groups nothing is capped.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CorrelationConfig {
+    /// Combined stake cap per group, as a fraction of bankroll.
+    #[serde(default = "CorrelationConfig::default_max_exposure_per_group_pct")]
+    pub max_exposure_per_group_pct: Decimal,
+    /// Group name to keywords, e.g. `fed = ["fed", "fomc", "recession"]`.
+    #[serde(default)]
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for CorrelationConfig {
+    fn default() -> Self {
+        Self {
+            max_exposure_per_group_pct: Self::default_max_exposure_per_group_pct(),
+            groups: BTreeMap::new(),
+        }
+    }
+}
+
+impl CorrelationConfig {
+    fn default_max_exposure_per_group_pct() -> Decimal { dec!(0.10) }
+}
+
+/// Strategy / auto-exit configuration ([strategy] section).
+///
+/// ## Betfair Australia minimum stake (AUD, March 2026)
+/// - Exchange back/lay bets placed via API: **AUD $1.00** minimum
+/// - BSP back bets: same AUD $1.00 minimum
+/// - BSP lay liability: unchanged (not affected by the 2022 reduction)
+/// - Sub-minimum bets via API are technically possible but risk account suspension
+/// - Min Bet Payout exception: bets below $1 AUD are valid when payout ≥ $10 AUD
+///
+/// Oracle defaults `min_close_stake` to $2.00 AUD as a safety buffer above the
+/// $1.00 absolute minimum. Never place a closing bet below this value.
+///
+/// ## Manifold minimum sell
+/// - No documented minimum; `shares` parameter is optional (defaults to all)
+/// - Practical minimum: 1 Mana — essentially no meaningful constraint
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StrategyConfig {
+    /// Enable automatic take-profit / stop-loss / time-based position closing.
+    #[serde(default = "StrategyConfig::default_enable_auto_exit")]
+    pub enable_auto_exit: bool,
+    /// Close position if unrealized P&L reaches this percentage (e.g. 15.0 = +15%).
+    #[serde(default = "StrategyConfig::default_take_profit_percent")]
+    pub take_profit_percent: Decimal,
+    /// Close position if unrealized P&L falls to this percentage (e.g. -10.0 = -10%).
+    #[serde(default = "StrategyConfig::default_stop_loss_percent")]
+    pub stop_loss_percent: Decimal,
+    /// Force-close after this many hours (0 = disabled).
+    #[serde(default = "StrategyConfig::default_max_hold_hours")]
+    pub max_hold_hours: u64,
+    /// Minimum closing stake in AUD (Betfair) or Mana (Manifold).
+    /// Betfair official minimum is $1.00 AUD; default here is $2.00 as buffer.
+    #[serde(default = "StrategyConfig::default_min_close_stake")]
+    pub min_close_stake: Decimal,
+    /// If true, log close decisions without placing real orders (dry-run).
+    #[serde(default)]
+    pub auto_exit_dry_run: bool,
+    /// Per-platform/category windows ahead of the market deadline in which
+    /// open positions are unwound and new bets refused ([[strategy.unwind_windows]]).
+    #[serde(default)]
+    pub unwind_windows: Vec<UnwindWindow>,
+    /// Routing of bets to the best venue for the same event ([strategy.venues]).
+    #[serde(default)]
+    pub venues: VenueSelectionConfig,
+    /// Pure price arbitrage across venues of the same event ([strategy.arbitrage]).
+    #[serde(default)]
+    pub arbitrage: ArbitrageConfig,
+    /// Blending of cross-reference prices into estimates ([strategy.references]).
+    #[serde(default)]
+    pub references: ReferenceWeightsConfig,
+    /// Closing positions whose edge is spent or reversed ([strategy.exits]).
+    #[serde(default)]
+    pub exits: EdgeExitConfig,
+}
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            enable_auto_exit: true,
+            take_profit_percent: rust_decimal_macros::dec!(15.0),
+            stop_loss_percent: rust_decimal_macros::dec!(-10.0),
+            max_hold_hours: 48,
+            min_close_stake: rust_decimal_macros::dec!(2.0),
+            auto_exit_dry_run: false,
+            unwind_windows: Vec::new(),
+            venues: VenueSelectionConfig::default(),
+            arbitrage: ArbitrageConfig::default(),
+            references: ReferenceWeightsConfig::default(),
+            exits: EdgeExitConfig::default(),
+        }
+    }
+}
+
+impl StrategyConfig {
+    fn default_enable_auto_exit() -> bool { true }
+    fn default_take_profit_percent() -> Decimal { rust_decimal_macros::dec!(15.0) }
+    fn default_stop_loss_percent() -> Decimal { rust_decimal_macros::dec!(-10.0) }
+    fn default_max_hold_hours() -> u64 { 48 }
+    fn default_min_close_stake() -> Decimal { rust_decimal_macros::dec!(2.0) }
+}
+
+/// Cross-reference blending weights.
+///
+/// Each estimate is pulled towards its Metaculus and Manifold reference
+/// prices by a weight per (reference platform, category). Every
+/// `review_days` the weights are re-derived from each reference's Brier
+/// score on resolved markets, shrunk towards `default_weight` with the
+/// strength of `prior_resolutions` resolutions. See
+/// [`crate::strategy::references`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReferenceWeightsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "ReferenceWeightsConfig::default_review_days")]
+    pub review_days: i64,
+    /// Weight of a reference with no track record.
+    #[serde(default = "ReferenceWeightsConfig::default_default_weight")]
+    pub default_weight: f64,
+    /// Largest weight one reference can earn. At most 0.5, so the
+    /// references together never outweigh the estimate.
+    #[serde(default = "ReferenceWeightsConfig::default_max_weight")]
+    pub max_weight: f64,
+    /// Pseudo-resolutions of `default_weight` a learned weight is shrunk with.
+    #[serde(default = "ReferenceWeightsConfig::default_prior_resolutions")]
+    pub prior_resolutions: u32,
+}
+
+impl Default for ReferenceWeightsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            review_days: Self::default_review_days(),
+            default_weight: Self::default_default_weight(),
+            max_weight: Self::default_max_weight(),
+            prior_resolutions: Self::default_prior_resolutions(),
+        }
+    }
+}
+
+impl ReferenceWeightsConfig {
+    fn default_review_days() -> i64 { 7 }
+    fn default_default_weight() -> f64 { 0.10 }
+    fn default_max_weight() -> f64 { 0.40 }
+    fn default_prior_resolutions() -> u32 { 30 }
+}
+
+/// Venue selection across markets matched to the same event on several
+/// executable platforms.
+///
+/// Each venue is scored by the expected profit of the bet re-sized at its
+/// price, net of `fees`, capped at `max_depth_pct` of its liquidity and
+/// weighted by `reliability`. See [`crate::strategy::venue`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VenueSelectionConfig {
+    #[serde(default = "VenueSelectionConfig::default_enabled")]
+    pub enabled: bool,
+    /// Commission on net winnings per platform (0.05 = 5%). Unlisted
+    /// platforms charge nothing.
+    #[serde(default = "VenueSelectionConfig::default_fees")]
+    pub fees: HashMap<String, Decimal>,
+    /// Fill reliability per platform (0–1). Unlisted platforms count as 1.
+    #[serde(default)]
+    pub reliability: HashMap<String, Decimal>,
+    /// Largest share of a venue's liquidity one routed bet may take.
+    #[serde(default = "VenueSelectionConfig::default_max_depth_pct")]
+    pub max_depth_pct: Decimal,
+}
+
+impl Default for VenueSelectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            fees: Self::default_fees(),
+            reliability: HashMap::new(),
+            max_depth_pct: Self::default_max_depth_pct(),
+        }
+    }
+}
+
+impl VenueSelectionConfig {
+    fn default_enabled() -> bool { true }
+    fn default_fees() -> HashMap<String, Decimal> { HashMap::from([("betfair".to_string(), dec!(0.05))]) }
+    fn default_max_depth_pct() -> Decimal { dec!(0.10) }
+}
+
+/// Cross-venue arbitrage between markets matched to the same event.
+///
+/// YES bought on one venue and NO on another pays out whichever way the
+/// event resolves; when the two prices cost less than that payout after
+/// each venue's commission on winnings (`[strategy.venues].fees`), both
+/// legs are bet, sized by the venues' liquidity rather than by Kelly. See
+/// [`crate::strategy::arbitrage`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArbitrageConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Guaranteed profit, as a fraction of the pair's combined stake,
+    /// required after fees.
+    #[serde(default = "ArbitrageConfig::default_min_margin")]
+    pub min_margin: Decimal,
+    /// Largest share of either venue's liquidity one leg may take.
+    #[serde(default = "ArbitrageConfig::default_max_depth_pct")]
+    pub max_depth_pct: Decimal,
+    /// Largest combined stake of a pair, as a fraction of the smaller of the
+    /// two venues' bankrolls.
+    #[serde(default = "ArbitrageConfig::default_max_pair_pct")]
+    pub max_pair_pct: Decimal,
+}
+
+impl Default for ArbitrageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_margin: Self::default_min_margin(),
+            max_depth_pct: Self::default_max_depth_pct(),
+            max_pair_pct: Self::default_max_pair_pct(),
+        }
+    }
+}
+
+impl ArbitrageConfig {
+    fn default_min_margin() -> Decimal { dec!(0.01) }
+    fn default_max_depth_pct() -> Decimal { dec!(0.05) }
+    fn default_max_pair_pct() -> Decimal { dec!(0.10) }
+}
+
+/// Edge-based position exits ([strategy.exits]); see
+/// [`crate::strategy::exit`].
+///
+/// Each cycle, a position whose market was estimated is closed once the
+/// estimate of its side is less than `take_profit_edge` above the price,
+/// or more than `cut_loss_edge` below it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EdgeExitConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "EdgeExitConfig::default_take_profit_edge")]
+    pub take_profit_edge: Decimal,
+    #[serde(default = "EdgeExitConfig::default_cut_loss_edge")]
+    pub cut_loss_edge: Decimal,
+}
+
+impl Default for EdgeExitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            take_profit_edge: Self::default_take_profit_edge(),
+            cut_loss_edge: Self::default_cut_loss_edge(),
+        }
+    }
+}
+
+impl EdgeExitConfig {
+    fn default_take_profit_edge() -> Decimal { dec!(0.01) }
+    fn default_cut_loss_edge() -> Decimal { dec!(0.05) }
+}
+
+/// Liquidity-cliff window before a market's deadline (e.g. Betfair going in-play).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnwindWindow {
+    /// Platform the window applies to ("betfair", "manifold", ...).
+    pub platform: String,
+    /// Restrict to one category; `None` covers every category on the platform.
+    #[serde(default)]
+    pub category: Option<MarketCategory>,
+    /// Minutes before the deadline at which the window opens.
+    pub minutes_before: i64,
+}
+
+impl UnwindWindow {
+    /// Widest matching window for a market, if any.
+    pub fn lead_time(
+        windows: &[UnwindWindow],
+        platform: &str,
+        category: Option<MarketCategory>,
+    ) -> Option<chrono::Duration> {
+        windows
+            .iter()
+            .filter(|w| w.platform == platform)
+            .filter(|w| w.category.is_none() || w.category == category)
+            .map(|w| w.minutes_before)
+            .max()
+            .map(chrono::Duration::minutes)
+    }
+
+    /// Whether `now` falls inside the unwind window ahead of `deadline`.
+    pub fn contains(
+        windows: &[UnwindWindow],
+        platform: &str,
+        category: Option<MarketCategory>,
+        deadline: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        Self::lead_time(windows, platform, category)
+            .is_some_and(|lead| now >= deadline - lead)
+    }
+}
+
+/// Scanner / market-router configuration ([scanner] section).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScannerConfig {
+    /// Minimum Jaccard-based similarity score (0–1) to cross-reference two markets.
+    #[serde(default = "ScannerConfig::default_match_threshold")]
+    pub match_threshold: f64,
+    /// Minimum liquidity (or forecaster count for Metaculus) to keep a market.
+    #[serde(default = "ScannerConfig::default_min_liquidity")]
+    pub min_liquidity: Decimal,
+    /// Maximum hours until deadline — markets closing later than this are skipped.
+    #[serde(default = "ScannerConfig::default_max_hours_to_deadline")]
+    pub max_hours_to_deadline: f64,
+    /// Minimum hours until deadline — markets closing sooner than this are skipped.
+    #[serde(default = "ScannerConfig::default_min_hours_to_deadline")]
+    pub min_hours_to_deadline: f64,
+    /// Maximum markets passed to the enrichment and LLM estimation stages
+    /// (`max_total` is accepted as an alias).
+    #[serde(default = "ScannerConfig::default_max_markets_to_process", alias = "max_total")]
+    pub max_markets_to_process: usize,
+    /// Per-category cap on the markets passed on, applied before
+    /// `max_markets_to_process` (category name → count). Categories not
+    /// listed are uncapped; watched markets are never dropped by it.
+    #[serde(default)]
+    pub max_per_category: HashMap<String, usize>,
+    /// Per-cycle cap on question-pair similarity comparisons in the
+    /// Manifold × Metaculus cross-reference step.
+    #[serde(default = "ScannerConfig::default_max_cross_ref_comparisons")]
+    pub max_cross_ref_comparisons: usize,
+    /// Merge markets on different executable platforms asking the same
+    /// question: the most liquid stays bettable, the others become its
+    /// cross-references.
+    #[serde(default = "ScannerConfig::default_merge_duplicates")]
+    pub merge_duplicates: bool,
+    /// Minimum similarity (0–1) for two executable markets to be merged;
+    /// stricter than `match_threshold`.
+    #[serde(default = "ScannerConfig::default_duplicate_threshold")]
+    pub duplicate_threshold: f64,
+    /// Consecutive scans with no post-filter markets before a platform
+    /// hibernates (0 = never).
+    #[serde(default = "ScannerConfig::default_hibernate_after_empty_scans")]
+    pub hibernate_after_empty_scans: u32,
+    /// A hibernating platform is scanned only every this many cycles.
+    #[serde(default = "ScannerConfig::default_hibernate_scan_every")]
+    pub hibernate_scan_every: u32,
+    /// Markets pinned ahead of the per-cycle cap (see
+    /// [`crate::engine::watchlist`]). Reloaded when the file changes.
+    #[serde(default = "ScannerConfig::default_watchlist_file")]
+    pub watchlist_file: String,
+    /// Question regexes whose markets are dropped. Reloaded when the file
+    /// changes.
+    #[serde(default = "ScannerConfig::default_blocklist_file")]
+    pub blocklist_file: String,
+    /// Rules tagging markets (see [`crate::engine::tags`]). Reloaded with
+    /// the watchlist.
+    #[serde(default = "ScannerConfig::default_tags_file")]
+    pub tags_file: String,
+    /// Questions never traded, even when whitelisted: case-insensitive
+    /// substrings, or regexes written `/…/`.
+    #[serde(default)]
+    pub blacklist_patterns: Vec<String>,
+    /// Questions kept regardless of liquidity and deadline (the price
+    /// sanity filter still applies); same syntax as `blacklist_patterns`.
+    #[serde(default)]
+    pub whitelist_patterns: Vec<String>,
+    /// ISO 639-1 codes of the question languages kept (see
+    /// [`crate::language`]); questions whose language can't be told are
+    /// kept too. Empty keeps every language.
+    #[serde(default = "ScannerConfig::default_allowed_languages")]
+    pub allowed_languages: Vec<String>,
+    /// Embedding-based cross-referencing ([scanner.embeddings]).
+    #[serde(default)]
+    pub embeddings: EmbeddingsConfig,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
             match_threshold: 0.45,
             min_liquidity: dec!(5.0),
             max_hours_to_deadline: 24.0 * 365.0,
             min_hours_to_deadline: 1.0,
             max_markets_to_process: 80,
+            max_per_category: HashMap::new(),
+            max_cross_ref_comparisons: 50_000,
+            merge_duplicates: Self::default_merge_duplicates(),
+            duplicate_threshold: Self::default_duplicate_threshold(),
+            hibernate_after_empty_scans: Self::default_hibernate_after_empty_scans(),
+            hibernate_scan_every: Self::default_hibernate_scan_every(),
+            watchlist_file: Self::default_watchlist_file(),
+            blocklist_file: Self::default_blocklist_file(),
+            tags_file: Self::default_tags_file(),
+            blacklist_patterns: Vec::new(),
+            whitelist_patterns: Vec::new(),
+            allowed_languages: Self::default_allowed_languages(),
+            embeddings: EmbeddingsConfig::default(),
+        }
+    }
+}
+
+impl ScannerConfig {
+    fn default_match_threshold() -> f64 { 0.45 }
+    fn default_min_liquidity() -> Decimal { dec!(5.0) }
+    fn default_max_hours_to_deadline() -> f64 { 24.0 * 365.0 }
+    fn default_min_hours_to_deadline() -> f64 { 1.0 }
+    fn default_max_markets_to_process() -> usize { 80 }
+    fn default_max_cross_ref_comparisons() -> usize { 50_000 }
+    fn default_merge_duplicates() -> bool { true }
+    fn default_duplicate_threshold() -> f64 { 0.80 }
+    fn default_hibernate_after_empty_scans() -> u32 { 12 }
+    fn default_hibernate_scan_every() -> u32 { 6 }
+    fn default_watchlist_file() -> String { "watchlist.toml".to_string() }
+    fn default_blocklist_file() -> String { "blocklist.toml".to_string() }
+    fn default_tags_file() -> String { "tags.toml".to_string() }
+    fn default_allowed_languages() -> Vec<String> { vec!["en".to_string()] }
+}
+
+/// Embedding matcher for the Manifold × Metaculus cross-reference
+/// ([scanner.embeddings]); see [`crate::embeddings`]. Disabled, questions
+/// are matched by word overlap against `scanner.match_threshold`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmbeddingsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// `local` (hashed word features, no API calls) or `api` (the
+    /// embedding endpoint of `llm.provider`, keyed by `llm.api_key_env`).
+    #[serde(default = "EmbeddingsConfig::default_provider")]
+    pub provider: String,
+    /// Embedding model for the `api` provider.
+    #[serde(default = "EmbeddingsConfig::default_model")]
+    pub model: String,
+    /// Vector size of the `local` provider.
+    #[serde(default = "EmbeddingsConfig::default_dimensions")]
+    pub dimensions: usize,
+    /// Minimum cosine similarity (0–1) to cross-reference two markets.
+    #[serde(default = "EmbeddingsConfig::default_match_threshold")]
+    pub match_threshold: f64,
+    /// Embeddings kept between runs, keyed by question hash.
+    #[serde(default = "EmbeddingsConfig::default_cache_file")]
+    pub cache_file: String,
+    /// Most cached embeddings; the least recently used go first.
+    #[serde(default = "EmbeddingsConfig::default_max_cache_entries")]
+    pub max_cache_entries: usize,
+}
+
+impl EmbeddingsConfig {
+    fn default_provider() -> String { "local".to_string() }
+    fn default_model() -> String { "text-embedding-3-small".to_string() }
+    fn default_dimensions() -> usize { 256 }
+    fn default_match_threshold() -> f64 { 0.80 }
+    fn default_cache_file() -> String { "oracle_embeddings.json".to_string() }
+    fn default_max_cache_entries() -> usize { 5_000 }
+}
+
+impl Default for EmbeddingsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: Self::default_provider(),
+            model: Self::default_model(),
+            dimensions: Self::default_dimensions(),
+            match_threshold: Self::default_match_threshold(),
+            cache_file: Self::default_cache_file(),
+            max_cache_entries: Self::default_max_cache_entries(),
+        }
+    }
+}
+
+/// Lifecycle archive for finished markets ([archive] section).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveConfig {
+    /// Directory holding one JSON document per archived market.
+    #[serde(default = "ArchiveConfig::default_dir")]
+    pub dir: String,
+    /// Archive never-resolved markets this many days after their last
+    /// decision (0 = only archive on resolution).
+    #[serde(default = "ArchiveConfig::default_expire_after_days")]
+    pub expire_after_days: i64,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            dir: Self::default_dir(),
+            expire_after_days: Self::default_expire_after_days(),
+        }
+    }
+}
+
+impl ArchiveConfig {
+    fn default_dir() -> String { "archive".to_string() }
+    fn default_expire_after_days() -> i64 { 30 }
+}
+
+/// Decision journal ([journal] section): every strategy decision as one
+/// JSON line, rotated by size and age.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalConfig {
+    #[serde(default = "JournalConfig::default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "JournalConfig::default_file")]
+    pub file: String,
+    /// Rotate once the live file reaches this size.
+    #[serde(default = "JournalConfig::default_max_file_mb")]
+    pub max_file_mb: u64,
+    /// Rotate once the live file's first entry is this old.
+    #[serde(default = "JournalConfig::default_max_age_days")]
+    pub max_age_days: i64,
+    /// Rotated files kept; older ones are deleted.
+    #[serde(default = "JournalConfig::default_keep_files")]
+    pub keep_files: usize,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            file: Self::default_file(),
+            max_file_mb: Self::default_max_file_mb(),
+            max_age_days: Self::default_max_age_days(),
+            keep_files: Self::default_keep_files(),
+        }
+    }
+}
+
+impl JournalConfig {
+    fn default_enabled() -> bool { true }
+    fn default_file() -> String { crate::storage::journal::DEFAULT_JOURNAL_FILE.to_string() }
+    fn default_max_file_mb() -> u64 { 20 }
+    fn default_max_age_days() -> i64 { 7 }
+    fn default_keep_files() -> usize { 4 }
+}
+
+/// Where state, trades, cycles and decisions are persisted ([storage] section).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub backend: StorageBackend,
+    /// Database file for the `sqlite` backend.
+    #[serde(default = "StorageConfig::default_sqlite_path")]
+    pub sqlite_path: String,
+    /// Rolling backups of the state file for the `json` backend
+    /// (`oracle_state.json.1` ... `.N`; 0 = none).
+    #[serde(default = "StorageConfig::default_state_backups")]
+    pub state_backups: usize,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackend::default(),
+            sqlite_path: Self::default_sqlite_path(),
+            state_backups: Self::default_state_backups(),
+        }
+    }
+}
+
+impl StorageConfig {
+    fn default_sqlite_path() -> String { crate::storage::sqlite::DEFAULT_STORAGE_DB.to_string() }
+    fn default_state_backups() -> usize { crate::storage::DEFAULT_STATE_BACKUPS }
+}
+
+/// Persistence backend.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// `oracle_state.json` and JSON-lines files beside it.
+    #[default]
+    Json,
+    /// One SQLite database; imports the JSON files on first open.
+    Sqlite,
+}
+
+/// Bet placement concurrency and latency limits ([execution] section).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExecutionConfig {
+    /// Per-attempt latency budget; slower placements are abandoned as timeouts.
+    #[serde(default = "ExecutionConfig::default_bet_timeout_secs")]
+    pub bet_timeout_secs: u64,
+    /// Placement attempts per bet on transient failures (429/5xx, timeouts).
+    #[serde(default = "ExecutionConfig::default_max_bet_attempts")]
+    pub max_bet_attempts: u32,
+    /// Delay before the first retry; doubled for each one after.
+    #[serde(default = "ExecutionConfig::default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Maximum in-flight bets per platform.
+    #[serde(default = "ExecutionConfig::default_max_parallel_per_platform")]
+    pub max_parallel_per_platform: usize,
+    /// Platforms whose orders must be placed one at a time (nonce ordering).
+    #[serde(default = "ExecutionConfig::default_sequential_platforms")]
+    pub sequential_platforms: Vec<String>,
+    /// Keep the raw placement response on real-money receipts.
+    #[serde(default = "ExecutionConfig::default_raw_response_real_money")]
+    pub raw_response_real_money: bool,
+    /// Keep the raw placement response on paper (play-money) receipts.
+    #[serde(default)]
+    pub raw_response_paper: bool,
+    /// Serialized size cap for a retained response; larger ones are truncated.
+    #[serde(default = "ExecutionConfig::default_raw_response_max_bytes")]
+    pub raw_response_max_bytes: usize,
+    /// Check each venue can take orders at startup, and before every
+    /// cycle in live mode; failing venues stay scan-only.
+    #[serde(default = "ExecutionConfig::default_preflight")]
+    pub preflight: bool,
+    /// Per-platform balance floor below which a venue is not traded.
+    #[serde(default)]
+    pub min_balance: HashMap<String, Decimal>,
+    /// Largest tolerated local vs venue clock difference for signed requests.
+    #[serde(default = "ExecutionConfig::default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: i64,
+    /// Most a bet may pay over the price its edge was computed against:
+    /// the limit on limit-order venues, a pre-placement check elsewhere.
+    #[serde(default = "ExecutionConfig::default_slippage_tolerance")]
+    pub slippage_tolerance: Decimal,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            bet_timeout_secs: 20,
+            max_bet_attempts: Self::default_max_bet_attempts(),
+            retry_backoff_ms: Self::default_retry_backoff_ms(),
+            max_parallel_per_platform: 2,
+            sequential_platforms: Self::default_sequential_platforms(),
+            raw_response_real_money: true,
+            raw_response_paper: false,
+            raw_response_max_bytes: Self::default_raw_response_max_bytes(),
+            preflight: true,
+            min_balance: HashMap::new(),
+            max_clock_skew_secs: Self::default_max_clock_skew_secs(),
+            slippage_tolerance: Self::default_slippage_tolerance(),
+        }
+    }
+}
+
+impl ExecutionConfig {
+    fn default_bet_timeout_secs() -> u64 { 20 }
+    fn default_max_bet_attempts() -> u32 { 3 }
+    fn default_retry_backoff_ms() -> u64 { 500 }
+    fn default_max_parallel_per_platform() -> usize { 2 }
+    fn default_sequential_platforms() -> Vec<String> { vec!["polymarket".to_string()] }
+    fn default_raw_response_real_money() -> bool { true }
+    fn default_raw_response_max_bytes() -> usize { 16 * 1024 }
+    fn default_preflight() -> bool { true }
+    fn default_max_clock_skew_secs() -> i64 { 5 }
+    fn default_slippage_tolerance() -> Decimal { dec!(0.02) }
+
+    /// Preflight thresholds for `platform`.
+    pub fn preflight_limits(&self, platform: &str) -> PreflightLimits {
+        PreflightLimits {
+            min_balance: self.min_balance.get(platform).copied().unwrap_or(Decimal::ZERO),
+            max_clock_skew: chrono::Duration::seconds(self.max_clock_skew_secs),
+        }
+    }
+
+    /// In-flight limit for `platform`.
+    pub fn parallelism_for(&self, platform: &str) -> usize {
+        if self.sequential_platforms.iter().any(|p| p == platform) {
+            1
+        } else {
+            self.max_parallel_per_platform.max(1)
+        }
+    }
+}
+
+/// Span export over OTLP/HTTP ([telemetry] section).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TelemetryConfig {
+    /// Export cycle / phase spans (see [`crate::telemetry`]).
+    #[serde(default)]
+    pub enabled: bool,
+    /// OTLP/HTTP collector base URL; spans are posted to `/v1/traces`.
+    #[serde(default = "TelemetryConfig::default_endpoint")]
+    pub endpoint: String,
+    /// `service.name` resource attribute.
+    #[serde(default = "TelemetryConfig::default_service_name")]
+    pub service_name: String,
+    /// Fraction of cycles (root spans) exported, in [0, 1].
+    #[serde(default = "TelemetryConfig::default_sample_ratio")]
+    pub sample_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: Self::default_endpoint(),
+            service_name: Self::default_service_name(),
+            sample_ratio: Self::default_sample_ratio(),
         }
     }
 }
 
-impl ScannerConfig {
-    fn default_match_threshold() -> f64 { 0.45 }
-    fn default_min_liquidity() -> Decimal { dec!(5.0) }
-    fn default_max_hours_to_deadline() -> f64 { 24.0 * 365.0 }
-    fn default_min_hours_to_deadline() -> f64 { 1.0 }
-    fn default_max_markets_to_process() -> usize { 80 }
+impl TelemetryConfig {
+    fn default_endpoint() -> String { "http://localhost:4318".to_string() }
+    fn default_service_name() -> String { "oracle".to_string() }
+    fn default_sample_ratio() -> f64 { 1.0 }
+}
+
+/// Spending limits on LLM and data calls ([costs] section); see
+/// [`crate::engine::cost_tracker`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CostsConfig {
+    /// USD cap on one cycle's LLM calls. Markets left unestimated when it
+    /// runs out are skipped and tried first next cycle. 0 = no cap.
+    #[serde(default)]
+    pub max_llm_cost_per_cycle: Decimal,
+    /// USD cap on a UTC day's LLM and data spend. Once reached, estimation
+    /// pauses until midnight; the agent keeps running. 0 = no cap.
+    #[serde(default)]
+    pub max_total_cost_per_day: Decimal,
+}
+
+/// Resuming a failed cycle ([recovery] section); see
+/// [`crate::engine::cycle_context`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecoveryConfig {
+    /// Oldest enrichment or approved bets a failed cycle leaves that the
+    /// next one resumes from (0 = always start from the scan).
+    #[serde(default = "RecoveryConfig::default_max_age_mins")]
+    pub max_age_mins: i64,
+    /// JSON file the context is saved to after each stage, so a restart
+    /// resumes too (empty = memory only).
+    #[serde(default)]
+    pub file: String,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self { max_age_mins: Self::default_max_age_mins(), file: String::new() }
+    }
+}
+
+impl RecoveryConfig {
+    fn default_max_age_mins() -> i64 { 30 }
+}
+
+/// Memory and collection-size audit ([diagnostics] section).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiagnosticsConfig {
+    /// Sample every N cycles (0 = disabled).
+    #[serde(default = "DiagnosticsConfig::default_every_cycles")]
+    pub every_cycles: u64,
+    /// Consecutive rising RSS samples before growth is reported.
+    #[serde(default = "DiagnosticsConfig::default_rss_growth_samples")]
+    pub rss_growth_samples: usize,
+    /// Total RSS growth across those samples that triggers a warning.
+    #[serde(default = "DiagnosticsConfig::default_rss_growth_mb")]
+    pub rss_growth_mb: u64,
+    /// NDJSON file samples are appended to (empty = don't write).
+    #[serde(default = "DiagnosticsConfig::default_log_file")]
+    pub log_file: String,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            every_cycles: Self::default_every_cycles(),
+            rss_growth_samples: Self::default_rss_growth_samples(),
+            rss_growth_mb: Self::default_rss_growth_mb(),
+            log_file: Self::default_log_file(),
+        }
+    }
+}
+
+impl DiagnosticsConfig {
+    fn default_every_cycles() -> u64 { 10 }
+    fn default_rss_growth_samples() -> usize { 6 }
+    fn default_rss_growth_mb() -> u64 { 64 }
+    fn default_log_file() -> String { "diagnostics.ndjson".to_string() }
+}
+
+/// Fault-injection settings for resilience testing ([chaos] section).
+///
+/// Only honoured by binaries built with `--features chaos`, and refused
+/// outright in live trading mode.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChaosConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Canned scenario; overrides the fault tables below when set.
+    #[serde(default)]
+    pub scenario: Option<String>,
+    /// PRNG seed so a chaos run can be replayed exactly.
+    #[serde(default = "ChaosConfig::default_seed")]
+    pub seed: u64,
+    /// Only wrap this platform (`None` = every venue).
+    #[serde(default)]
+    pub target_platform: Option<String>,
+    /// Faults injected into platform clients ([chaos.platform]).
+    #[serde(default)]
+    pub platform: ChaosFaults,
+    /// Faults injected into the LLM estimator ([chaos.llm]).
+    #[serde(default)]
+    pub llm: ChaosFaults,
+}
+
+/// Per-call fault probabilities (0–1) and added latency.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ChaosFaults {
+    /// Request fails outright.
+    #[serde(default)]
+    pub failure_rate: f64,
+    /// Added before every call.
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Response body is malformed JSON.
+    #[serde(default)]
+    pub corrupt_rate: f64,
+    /// A fill returns the previous order's receipt (same order id).
+    #[serde(default)]
+    pub duplicate_fill_rate: f64,
+    /// Batch responses (markets, estimates) are truncated.
+    #[serde(default)]
+    pub partial_batch_rate: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scenario: None,
+            seed: 42,
+            target_platform: None,
+            platform: ChaosFaults::default(),
+            llm: ChaosFaults::default(),
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Canned scenarios accepted by `scenario`.
+    pub const SCENARIOS: &'static [&'static str] =
+        &["flaky-manifold", "slow-llm", "duplicate-fills"];
+
+    fn default_seed() -> u64 { 42 }
+
+    /// This config with its canned scenario (if any) expanded into faults.
+    pub fn resolved(&self) -> ChaosConfig {
+        let mut cfg = self.clone();
+        match self.scenario.as_deref() {
+            Some("flaky-manifold") => {
+                cfg.target_platform = Some("manifold".to_string());
+                cfg.platform = ChaosFaults {
+                    failure_rate: 0.3,
+                    latency_ms: 250,
+                    corrupt_rate: 0.1,
+                    partial_batch_rate: 0.2,
+                    ..ChaosFaults::default()
+                };
+                cfg.llm = ChaosFaults::default();
+            }
+            Some("slow-llm") => {
+                cfg.platform = ChaosFaults::default();
+                cfg.llm = ChaosFaults {
+                    failure_rate: 0.1,
+                    latency_ms: 5_000,
+                    partial_batch_rate: 0.2,
+                    ..ChaosFaults::default()
+                };
+            }
+            Some("duplicate-fills") => {
+                cfg.platform = ChaosFaults {
+                    duplicate_fill_rate: 0.5,
+                    ..ChaosFaults::default()
+                };
+                cfg.llm = ChaosFaults::default();
+            }
+            _ => {}
+        }
+        cfg
+    }
 }
 
 /// Enricher cache TTL configuration ([enricher] section).
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EnricherConfig {
     /// Default cache TTL in minutes for data contexts.
     #[serde(default = "EnricherConfig::default_default_cache_ttl_mins")]
@@ -258,6 +1914,18 @@ pub struct EnricherConfig {
     /// Cache TTL in minutes for news/politics data (fast-moving).
     #[serde(default = "EnricherConfig::default_news_cache_ttl_mins")]
     pub news_cache_ttl_mins: i64,
+    /// Append Manifold trade-flow sentiment to Manifold markets' context.
+    #[serde(default)]
+    pub manifold_flow_enabled: bool,
+    /// Only the top N priority Manifold markets per cycle fetch the bet feed.
+    #[serde(default = "EnricherConfig::default_manifold_flow_top_n")]
+    pub manifold_flow_top_n: usize,
+    /// Recent bets fetched per market.
+    #[serde(default = "EnricherConfig::default_manifold_flow_bets")]
+    pub manifold_flow_bets: usize,
+    /// Add a news section to weather, sports and economics markets.
+    #[serde(default = "EnricherConfig::default_supplementary_news")]
+    pub supplementary_news: bool,
 }
 
 impl Default for EnricherConfig {
@@ -266,6 +1934,10 @@ impl Default for EnricherConfig {
             default_cache_ttl_mins: 30,
             weather_cache_ttl_mins: 60,
             news_cache_ttl_mins: 15,
+            manifold_flow_enabled: false,
+            manifold_flow_top_n: 10,
+            manifold_flow_bets: 100,
+            supplementary_news: true,
         }
     }
 }
@@ -274,53 +1946,167 @@ impl EnricherConfig {
     fn default_default_cache_ttl_mins() -> i64 { 30 }
     fn default_weather_cache_ttl_mins() -> i64 { 60 }
     fn default_news_cache_ttl_mins() -> i64 { 15 }
+    fn default_manifold_flow_top_n() -> usize { 10 }
+    fn default_manifold_flow_bets() -> usize { 100 }
+    fn default_supplementary_news() -> bool { true }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DataSourcesConfig {
     pub openweathermap_key_env: Option<String>,
     pub bom_enabled: Option<bool>,
     pub api_sports_key_env: Option<String>,
+    /// API-Sports requests allowed per UTC day (default: the free tier's 100).
+    pub sports_requests_per_day: Option<u32>,
+    /// File the day's API-Sports request count is kept in, so restarts
+    /// don't reset it (default: "oracle_api_sports_usage.json").
+    pub sports_usage_file: Option<String>,
     pub fred_api_key_env: Option<String>,
     /// Env var name for the NewsAPI key (default: "NEWS_API_KEY").
     pub news_api_key_env: Option<String>,
+    /// NewsAPI requests allowed per day (default: the free tier's 100).
+    pub news_requests_per_day: Option<u32>,
+    /// USD charged per NewsAPI request on a paid plan (default 0).
+    pub news_cost_per_call: Option<Decimal>,
     pub coingecko: Option<CoinGeckoConfig>,
+    /// In-flight enrichment requests per batch (default 8); each provider
+    /// may use at most half.
+    pub max_concurrent_requests: Option<usize>,
+    /// Keyword → FRED series rules, matched before the built-in table.
+    #[serde(default)]
+    pub fred_series: Vec<SeriesRule>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CoinGeckoConfig {
     pub enabled: bool,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DashboardConfig {
     pub enabled: bool,
     pub port: u16,
+    /// Serve a second, read-only listener with normalised amounts
+    /// (bankroll indexed to 100, bet sizes as percentages, costs hidden).
+    #[serde(default)]
+    pub public_mode: bool,
+    /// Port for the public-mode listener (ignored unless `public_mode`).
+    #[serde(default = "DashboardConfig::default_public_port")]
+    pub public_port: u16,
+    /// Env var holding the bearer token for privileged endpoints
+    /// (`/api/config`). Those endpoints are refused while it is unset.
+    #[serde(default)]
+    pub api_token_env: Option<String>,
+    /// SQLite file holding per-cycle metrics and hourly rollups for
+    /// long-range charts.
+    #[serde(default = "DashboardConfig::default_metrics_db")]
+    pub metrics_db: String,
+    /// Serve read endpoints from a 1–5 s response cache.
+    #[serde(default = "DashboardConfig::default_response_cache")]
+    pub response_cache: bool,
+    /// Expensive requests (long metrics histories) served at once; more
+    /// get 429.
+    #[serde(default = "DashboardConfig::default_max_expensive_requests")]
+    pub max_expensive_requests: usize,
+}
+
+impl DashboardConfig {
+    fn default_public_port() -> u16 { 8081 }
+    fn default_metrics_db() -> String { "oracle_metrics.db".to_string() }
+    fn default_response_cache() -> bool { true }
+    fn default_max_expensive_requests() -> usize { 2 }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AlertsConfig {
     pub telegram_bot_token_env: Option<String>,
     pub telegram_chat_id_env: Option<String>,
+    /// Env var holding a webhook URL for operator alerts (see [`crate::alerts`]).
+    pub webhook_url_env: Option<String>,
+}
+
+/// Event notifications to a webhook ([notifications] section); see
+/// [`crate::notifications`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Webhook URL; takes precedence over `url_env`.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Env var holding the webhook URL.
+    #[serde(default)]
+    pub url_env: Option<String>,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Events sent (default: all).
+    #[serde(default = "EventKind::all")]
+    pub events: Vec<EventKind>,
+    /// Notices sent per minute; the rest are coalesced.
+    #[serde(default = "NotificationsConfig::default_max_per_minute")]
+    pub max_per_minute: u32,
+    /// An event repeating within this many seconds is coalesced.
+    #[serde(default = "NotificationsConfig::default_coalesce_secs")]
+    pub coalesce_secs: u64,
+    /// UTC hour the daily summary goes out.
+    #[serde(default = "NotificationsConfig::default_daily_summary_hour")]
+    pub daily_summary_hour: u32,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: None,
+            url_env: None,
+            format: WebhookFormat::default(),
+            events: EventKind::all(),
+            max_per_minute: Self::default_max_per_minute(),
+            coalesce_secs: Self::default_coalesce_secs(),
+            daily_summary_hour: Self::default_daily_summary_hour(),
+        }
+    }
+}
+
+impl NotificationsConfig {
+    fn default_max_per_minute() -> u32 { 6 }
+    fn default_coalesce_secs() -> u64 { 600 }
+    fn default_daily_summary_hour() -> u32 { 21 }
 }
 
 impl AppConfig {
     /// Load configuration from a TOML file.
     pub fn load(path: &str) -> Result<Self> {
+        Self::load_effective(path).map(|(config, _)| config)
+    }
+
+    /// Load configuration and its resolved, redacted view with provenance.
+    pub fn load_effective(path: &str) -> Result<(Self, EffectiveConfig)> {
         let contents = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {path}"))?;
         let config: AppConfig = toml::from_str(&contents)
             .with_context(|| format!("Failed to parse config file: {path}"))?;
         config.validate()?;
-        Ok(config)
+        let file: toml::Value = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file: {path}"))?;
+        let effective = EffectiveConfig::resolve(&config, &file, |k| std::env::var(k).ok())?;
+        Ok((config, effective))
     }
 
     /// Validate that key config values are within sensible bounds.
     fn validate(&self) -> Result<()> {
+        anyhow::ensure!(
+            self.notifications.daily_summary_hour < 24 && self.notifications.max_per_minute > 0,
+            "notifications.daily_summary_hour must be 0-23 and max_per_minute > 0"
+        );
         anyhow::ensure!(
             self.agent.initial_bankroll > Decimal::ZERO,
             "agent.initial_bankroll must be > 0"
         );
+        anyhow::ensure!(
+            self.agent.scan_interval_secs > 0,
+            "agent.scan_interval_secs must be > 0"
+        );
         anyhow::ensure!(
             self.risk.kelly_multiplier > Decimal::ZERO,
             "risk.kelly_multiplier must be > 0"
@@ -333,6 +2119,64 @@ impl AppConfig {
             self.risk.max_bet_pct > Decimal::ZERO && self.risk.max_bet_pct <= Decimal::ONE,
             "risk.max_bet_pct must be in (0, 1]"
         );
+        for (platform, fee) in &self.risk.fees {
+            let valid = match *fee {
+                FeeModel::FlatPerTrade(commission) => commission >= Decimal::ZERO,
+                FeeModel::PercentOfWinnings(pct) => pct >= Decimal::ZERO && pct < Decimal::ONE,
+                FeeModel::None => true,
+            };
+            anyhow::ensure!(valid, "risk.fees.{platform} must be a flat fee ≥ 0 or a share of winnings in [0, 1)");
+        }
+        anyhow::ensure!(
+            self.risk.links.max_set_exposure_pct > Decimal::ZERO && self.risk.links.max_set_exposure_pct <= Decimal::ONE,
+            "risk.links.max_set_exposure_pct must be in (0, 1]"
+        );
+        DrawdownTier::validate(&self.risk.drawdown_tiers)?;
+        let halt = DrawdownTier::halt_pct(&self.risk.drawdown_tiers).unwrap_or(Decimal::ONE);
+        anyhow::ensure!(
+            self.risk.drawdown_resume_pct > Decimal::ZERO && self.risk.drawdown_resume_pct < halt,
+            "risk.drawdown_resume_pct must be in (0, {halt})"
+        );
+        anyhow::ensure!(
+            self.risk.max_holding_pct > Decimal::ZERO && self.risk.max_holding_pct <= Decimal::ONE,
+            "risk.max_holding_pct must be in (0, 1]"
+        );
+        anyhow::ensure!(
+            self.risk.capital_hurdle_annual_pct >= Decimal::ZERO && self.risk.capital_hurdle_annual_pct <= Decimal::ONE,
+            "risk.capital_hurdle_annual_pct must be in [0, 1]"
+        );
+        anyhow::ensure!(
+            self.risk.max_daily_loss_pct.is_none_or(|p| p > Decimal::ZERO && p <= Decimal::ONE),
+            "risk.max_daily_loss_pct must be in (0, 1]"
+        );
+        anyhow::ensure!(
+            self.risk.max_price_impact_pct.is_none_or(|p| p > Decimal::ZERO && p < Decimal::ONE),
+            "risk.max_price_impact_pct must be in (0, 1)"
+        );
+        for (tag, limit) in &self.risk.tags {
+            anyhow::ensure!(
+                limit.max_exposure_pct.is_none_or(|p| p > Decimal::ZERO && p <= Decimal::ONE),
+                "risk.tags.{tag}.max_exposure_pct must be in (0, 1]"
+            );
+        }
+        let venues = &self.strategy.venues;
+        anyhow::ensure!(
+            venues.max_depth_pct > Decimal::ZERO && venues.max_depth_pct <= Decimal::ONE,
+            "strategy.venues.max_depth_pct must be in (0, 1]"
+        );
+        anyhow::ensure!(
+            venues.fees.values().chain(venues.reliability.values()).all(|v| (Decimal::ZERO..=Decimal::ONE).contains(v)),
+            "strategy.venues fees and reliability must be in [0, 1]"
+        );
+        let arbitrage = &self.strategy.arbitrage;
+        anyhow::ensure!(
+            arbitrage.min_margin >= Decimal::ZERO,
+            "strategy.arbitrage.min_margin must be non-negative"
+        );
+        anyhow::ensure!(
+            [arbitrage.max_depth_pct, arbitrage.max_pair_pct].iter().all(|p| *p > Decimal::ZERO && *p <= Decimal::ONE),
+            "strategy.arbitrage.max_depth_pct and max_pair_pct must be in (0, 1]"
+        );
         anyhow::ensure!(
             self.scanner.match_threshold > 0.0 && self.scanner.match_threshold <= 1.0,
             "scanner.match_threshold must be in (0, 1]"
@@ -341,6 +2185,214 @@ impl AppConfig {
             self.scanner.max_markets_to_process > 0,
             "scanner.max_markets_to_process must be > 0"
         );
+        for category in self.scanner.max_per_category.keys() {
+            anyhow::ensure!(
+                category.parse::<MarketCategory>().is_ok(),
+                "scanner.max_per_category: unknown category {category:?}"
+            );
+        }
+        crate::engine::watchlist::PatternList::new(&self.scanner.blacklist_patterns).context("scanner.blacklist_patterns")?;
+        crate::engine::watchlist::PatternList::new(&self.scanner.whitelist_patterns).context("scanner.whitelist_patterns")?;
+        for language in &self.scanner.allowed_languages {
+            anyhow::ensure!(
+                language.len() == 2 && language.chars().all(|c| c.is_ascii_lowercase()),
+                "scanner.allowed_languages: {language:?} is not an ISO 639-1 code"
+            );
+        }
+        let embeddings = &self.scanner.embeddings;
+        if embeddings.enabled {
+            anyhow::ensure!(
+                embeddings.match_threshold > 0.0 && embeddings.match_threshold <= 1.0,
+                "scanner.embeddings.match_threshold must be in (0, 1]"
+            );
+            anyhow::ensure!(embeddings.max_cache_entries > 0, "scanner.embeddings.max_cache_entries must be > 0");
+            match embeddings.provider.as_str() {
+                "local" => anyhow::ensure!(embeddings.dimensions > 0, "scanner.embeddings.dimensions must be > 0"),
+                "api" => anyhow::ensure!(
+                    crate::embeddings::endpoint(&self.llm).is_some(),
+                    "scanner.embeddings: llm.provider {:?} has no embedding endpoint; use provider = \"local\"",
+                    self.llm.provider
+                ),
+                other => anyhow::bail!("scanner.embeddings.provider must be \"local\" or \"api\", got {other:?}"),
+            }
+        }
+        anyhow::ensure!(
+            self.execution.bet_timeout_secs > 0,
+            "execution.bet_timeout_secs must be > 0"
+        );
+        anyhow::ensure!(
+            self.execution.max_bet_attempts > 0,
+            "execution.max_bet_attempts must be > 0"
+        );
+        anyhow::ensure!(
+            self.execution.max_parallel_per_platform > 0,
+            "execution.max_parallel_per_platform must be > 0"
+        );
+        anyhow::ensure!(
+            self.execution.max_clock_skew_secs >= 0,
+            "execution.max_clock_skew_secs must be >= 0"
+        );
+        anyhow::ensure!(
+            self.execution.min_balance.values().all(|b| *b >= Decimal::ZERO),
+            "execution.min_balance floors must be >= 0"
+        );
+        anyhow::ensure!(
+            self.execution.slippage_tolerance >= Decimal::ZERO && self.execution.slippage_tolerance < Decimal::ONE,
+            "execution.slippage_tolerance must be in [0, 1)"
+        );
+        if self.chaos.enabled {
+            anyhow::ensure!(
+                self.agent.trading_mode != "live",
+                "chaos mode cannot be enabled in live trading mode"
+            );
+            if let Some(name) = &self.chaos.scenario {
+                anyhow::ensure!(
+                    ChaosConfig::SCENARIOS.contains(&name.as_str()),
+                    "chaos.scenario must be one of {:?}",
+                    ChaosConfig::SCENARIOS
+                );
+            }
+            let faults = [&self.chaos.platform, &self.chaos.llm];
+            for f in faults {
+                for rate in [f.failure_rate, f.corrupt_rate, f.duplicate_fill_rate, f.partial_batch_rate] {
+                    anyhow::ensure!((0.0..=1.0).contains(&rate), "chaos fault rates must be in [0, 1]");
+                }
+            }
+        }
+        anyhow::ensure!(self.llm.max_cycle_cost >= Decimal::ZERO, "llm.max_cycle_cost must be ≥ 0");
+        anyhow::ensure!(self.llm.batch_size > 0, "llm.batch_size must be > 0");
+        if let Some(window) = self.llm.context_window {
+            anyhow::ensure!(
+                window as usize > self.llm.max_tokens as usize + crate::llm::budget::SYSTEM_PROMPT_TOKENS,
+                "llm.context_window must exceed max_tokens plus {} tokens for the system prompt",
+                crate::llm::budget::SYSTEM_PROMPT_TOKENS
+            );
+        }
+        anyhow::ensure!(
+            self.risk.cross_ref.weight >= Decimal::ZERO && self.risk.cross_ref.weight <= Decimal::ONE,
+            "risk.cross_ref.weight must be in [0, 1]"
+        );
+        let confidence = &self.risk.confidence_scaling;
+        anyhow::ensure!(confidence.k >= Decimal::ZERO, "risk.confidence_scaling.k must be ≥ 0");
+        anyhow::ensure!(
+            confidence.min_confidence >= Decimal::ZERO && confidence.min_confidence <= Decimal::ONE,
+            "risk.confidence_scaling.min_confidence must be in [0, 1]"
+        );
+        anyhow::ensure!(
+            self.costs.max_llm_cost_per_cycle >= Decimal::ZERO && self.costs.max_total_cost_per_day >= Decimal::ZERO,
+            "costs.max_llm_cost_per_cycle and max_total_cost_per_day must be ≥ 0"
+        );
+        for category in self.llm.category_models.keys() {
+            anyhow::ensure!(
+                category.parse::<MarketCategory>().is_ok(),
+                "llm.category_models: unknown category {category:?}"
+            );
+        }
+        if let Some(ensemble) = &self.llm.ensemble {
+            anyhow::ensure!(!ensemble.members.is_empty(), "llm.ensemble.members must not be empty");
+            anyhow::ensure!(
+                ensemble.disagreement_penalty >= Decimal::ZERO,
+                "llm.ensemble.disagreement_penalty must be ≥ 0"
+            );
+        }
+        anyhow::ensure!(
+            self.llm.cache.ttl_mins >= 0 && self.llm.cache.min_hours_to_deadline >= 0,
+            "llm.cache.ttl_mins and min_hours_to_deadline must be ≥ 0"
+        );
+        anyhow::ensure!(self.llm.cache.max_price_move >= Decimal::ZERO, "llm.cache.max_price_move must be ≥ 0");
+        anyhow::ensure!(self.recovery.max_age_mins >= 0, "recovery.max_age_mins must be ≥ 0");
+        let exits = &self.strategy.exits;
+        anyhow::ensure!(
+            exits.take_profit_edge >= Decimal::ZERO && exits.cut_loss_edge >= Decimal::ZERO,
+            "strategy.exits.take_profit_edge and cut_loss_edge must be ≥ 0"
+        );
+        if self.telemetry.enabled {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&self.telemetry.sample_ratio),
+                "telemetry.sample_ratio must be in [0, 1]"
+            );
+            anyhow::ensure!(
+                self.telemetry.endpoint.starts_with("http://") || self.telemetry.endpoint.starts_with("https://"),
+                "telemetry.endpoint must be an http(s) URL"
+            );
+        }
+        if self.journal.enabled {
+            anyhow::ensure!(!self.journal.file.is_empty(), "journal.file must not be empty");
+            anyhow::ensure!(
+                self.journal.max_file_mb > 0 && self.journal.max_age_days > 0,
+                "journal.max_file_mb and journal.max_age_days must be > 0"
+            );
+        }
+        for (platform, schedule) in self.platforms.schedules() {
+            anyhow::ensure!(
+                schedule.scan_every_n_cycles >= 1,
+                "platforms.{platform}.scan_every_n_cycles must be ≥ 1"
+            );
+        }
+        if self.storage.backend == StorageBackend::Sqlite {
+            anyhow::ensure!(!self.storage.sqlite_path.is_empty(), "storage.sqlite_path must not be empty");
+        }
+        let references = &self.strategy.references;
+        if references.enabled {
+            anyhow::ensure!(references.review_days > 0, "strategy.references.review_days must be > 0");
+            anyhow::ensure!(
+                (0.0..=0.5).contains(&references.max_weight)
+                    && (0.0..=references.max_weight).contains(&references.default_weight),
+                "strategy.references requires 0 ≤ default_weight ≤ max_weight ≤ 0.5"
+            );
+        }
+        let adaptive = &self.risk.adaptive_thresholds;
+        if adaptive.enabled {
+            anyhow::ensure!(adaptive.review_days > 0, "risk.adaptive_thresholds.review_days must be > 0");
+            anyhow::ensure!(
+                adaptive.bucket_width > Decimal::ZERO && adaptive.max_step > Decimal::ZERO,
+                "risk.adaptive_thresholds.bucket_width and max_step must be > 0"
+            );
+            anyhow::ensure!(
+                adaptive.min_threshold > Decimal::ZERO && adaptive.min_threshold <= adaptive.max_threshold,
+                "risk.adaptive_thresholds requires 0 < min_threshold ≤ max_threshold"
+            );
+        }
+        anyhow::ensure!(
+            self.platforms.manifold.match_window_secs > 0,
+            "platforms.manifold.match_window_secs must be > 0"
+        );
+        for w in &self.strategy.unwind_windows {
+            anyhow::ensure!(
+                w.minutes_before > 0,
+                "strategy.unwind_windows.minutes_before must be > 0"
+            );
+        }
+        let critique = &self.llm.self_critique;
+        if critique.enabled {
+            anyhow::ensure!(
+                critique.min_bet_pct > Decimal::ZERO && critique.revise_threshold >= Decimal::ZERO,
+                "llm.self_critique.min_bet_pct must be > 0 and revise_threshold ≥ 0"
+            );
+        }
+        let tiers = &self.llm.tiers;
+        if tiers.enabled {
+            anyhow::ensure!(
+                tiers.mana_aud >= Decimal::ZERO,
+                "llm.tiers.mana_aud must be ≥ 0"
+            );
+            for (name, t) in [("a", &tiers.a), ("b", &tiers.b), ("c", &tiers.c)] {
+                anyhow::ensure!(
+                    t.max_tokens > 0 && t.samples > 0,
+                    "llm.tiers.{name}.max_tokens and samples must be > 0"
+                );
+            }
+        }
+        if let Some(shadow) = &self.llm.shadow {
+            anyhow::ensure!(
+                (0.0..=100.0).contains(&shadow.sample_pct),
+                "llm.shadow.sample_pct must be in [0, 100]"
+            );
+        }
+        anyhow::ensure!(
+            !self.dashboard.public_mode || self.dashboard.public_port != self.dashboard.port,
+            "dashboard.public_port must differ from dashboard.port"
+        );
         Ok(())
     }
 
@@ -352,10 +2404,259 @@ impl AppConfig {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Effective configuration
+// ---------------------------------------------------------------------------
+
+/// Where an effective config value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    /// Not in the file; the built-in default applies.
+    Default,
+    File,
+    /// Resolved from the environment (secrets named by `*_env` keys).
+    Env,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigEntry {
+    pub value: serde_json::Value,
+    pub source: ConfigSource,
+}
+
+/// The configuration the process is actually running with, flattened to
+/// dotted keys (`risk.kelly_multiplier`). Secrets never appear: each
+/// `*_env` key gets a sibling without the suffix whose value is "set" or
+/// "unset".
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct EffectiveConfig {
+    pub entries: BTreeMap<String, ConfigEntry>,
+}
+
+/// One key that differs between two effective configs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigChange {
+    pub key: String,
+    /// `None` when the key is new.
+    pub previous: Option<serde_json::Value>,
+    /// `None` when the key was removed.
+    pub current: Option<serde_json::Value>,
+}
+
+impl EffectiveConfig {
+    /// Resolve `config` against the parsed file it was loaded from; `env`
+    /// looks up secret env vars (only their presence is recorded).
+    pub fn resolve(
+        config: &AppConfig,
+        file: &toml::Value,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let mut leaves = Vec::new();
+        flatten("", &serde_json::to_value(config)?, &mut leaves);
+
+        let mut entries = BTreeMap::new();
+        for (key, value) in leaves {
+            let source = if file_has_key(file, &key) { ConfigSource::File } else { ConfigSource::Default };
+            if let (Some(secret), Some(var)) = (key.strip_suffix("_env"), value.as_str()) {
+                let present = env(var).is_some_and(|v| !v.is_empty());
+                entries.insert(
+                    secret.to_string(),
+                    ConfigEntry {
+                        value: serde_json::Value::from(if present { "set" } else { "unset" }),
+                        source: ConfigSource::Env,
+                    },
+                );
+            }
+            entries.insert(key, ConfigEntry { value, source });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Plain key → value map, as persisted for the next session's diff.
+    pub fn values(&self) -> BTreeMap<String, serde_json::Value> {
+        self.entries.iter().map(|(k, e)| (k.clone(), e.value.clone())).collect()
+    }
+
+    /// Keys whose value differs from `previous`, sorted by key.
+    pub fn diff(&self, previous: &BTreeMap<String, serde_json::Value>) -> Vec<ConfigChange> {
+        let current = self.values();
+        let mut keys: Vec<&String> = previous.keys().chain(current.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter(|k| previous.get(*k) != current.get(*k))
+            .map(|k| ConfigChange {
+                key: k.clone(),
+                previous: previous.get(k).cloned(),
+                current: current.get(k).cloned(),
+            })
+            .collect()
+    }
+}
+
+/// Collect `(dotted.key, value)` for every non-object leaf; empty tables
+/// count as leaves so they still show up.
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, serde_json::Value)>) {
+    match value.as_object() {
+        Some(map) if !map.is_empty() => {
+            for (k, v) in map {
+                let key = if prefix.is_empty() { k.clone() } else { format!("{prefix}.{k}") };
+                flatten(&key, v, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+fn file_has_key(file: &toml::Value, key: &str) -> bool {
+    key.split('.')
+        .try_fold(file, |node, part| node.get(part))
+        .is_some()
+}
+
+// ---------------------------------------------------------------------------
+// Hot reload
+// ---------------------------------------------------------------------------
+
+/// The config file, re-read when it changes while the agent runs.
+///
+/// Only strategy limits are applied without a restart: the category edge
+/// thresholds, `kelly_multiplier`, `max_bet_pct`, the exposure caps
+/// (`max_exposure_pct`, `[risk.tags]`, `[risk.correlation]`) and
+/// `agent.scan_interval_secs`. Any other edit is logged as needing a
+/// restart and left out of the config in force.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: String,
+    stamp: Option<(SystemTime, u64)>,
+    config: AppConfig,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`, which `config` was loaded from.
+    pub fn new(path: &str, config: AppConfig) -> Self {
+        Self { path: path.to_string(), stamp: stamp(path), config }
+    }
+
+    /// The config in force.
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// Re-read the file if its modification time or length moved. Returns
+    /// the config in force when a hot-reloadable setting changed; an
+    /// invalid file is logged and the previous config kept until the file
+    /// changes again.
+    pub fn reload_if_changed(&mut self) -> Option<&AppConfig> {
+        let stamp = stamp(&self.path);
+        if stamp == self.stamp {
+            return None;
+        }
+        self.stamp = stamp;
+        let next = match AppConfig::load(&self.path) {
+            Ok(next) => next,
+            Err(e) => {
+                error!(error = format!("{e:#}"), file = %self.path, "Config reload rejected — keeping previous settings");
+                return None;
+            }
+        };
+
+        let mut applied = self.config.clone();
+        applied.agent.scan_interval_secs = next.agent.scan_interval_secs;
+        applied.risk.category_thresholds = next.risk.category_thresholds.clone();
+        applied.risk.kelly_multiplier = next.risk.kelly_multiplier;
+        applied.risk.max_bet_pct = next.risk.max_bet_pct;
+        applied.risk.max_exposure_pct = next.risk.max_exposure_pct;
+        applied.risk.tags = next.risk.tags.clone();
+        applied.risk.correlation = next.risk.correlation.clone();
+
+        let ignored = changed_keys(&applied, &next);
+        if !ignored.is_empty() {
+            warn!(keys = %ignored.join(", "), file = %self.path, "Config changes need a restart to take effect");
+        }
+        let applied_keys = changed_keys(&self.config, &applied);
+        if applied_keys.is_empty() {
+            return None;
+        }
+        info!(keys = %applied_keys.join(", "), file = %self.path, "Config reloaded");
+        self.config = applied;
+        Some(&self.config)
+    }
+}
+
+/// Dotted keys whose value differs between `a` and `b`.
+fn changed_keys(a: &AppConfig, b: &AppConfig) -> Vec<String> {
+    let leaves = |config: &AppConfig| {
+        let mut out = Vec::new();
+        flatten("", &serde_json::to_value(config).unwrap_or_default(), &mut out);
+        out.into_iter().collect::<BTreeMap<_, _>>()
+    };
+    let (a, b) = (leaves(a), leaves(b));
+    let mut keys: Vec<String> = a.keys().chain(b.keys()).filter(|k| a.get(*k) != b.get(*k)).cloned().collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn stamp(path: &str) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Smallest file that parses: every required key, nothing optional.
+    const MINIMAL: &str = r#"
+        [agent]
+        name = "T"
+        scan_interval_secs = 60
+        initial_bankroll = 100.0
+        survival_threshold = 0.0
+        currency = "AUD"
+        [llm]
+        provider = "openrouter"
+        model = "m"
+        api_key_env = "T_LLM_KEY"
+        max_tokens = 1000
+        batch_size = 5
+        [platforms.forecastex]
+        enabled = false
+        ib_host = "127.0.0.1"
+        ib_port = 4002
+        ib_client_id = 1
+        account_id_env = "T_IB_ACCOUNT"
+        [platforms.metaculus]
+        enabled = true
+        [platforms.manifold]
+        enabled = true
+        [risk]
+        mispricing_threshold = 0.08
+        kelly_multiplier = 0.25
+        max_bet_pct = 0.06
+        max_exposure_pct = 0.60
+        min_liquidity_contracts = 50
+        [risk.category_thresholds]
+        weather = 0.06
+        [data_sources]
+        [dashboard]
+        enabled = true
+        port = 8080
+        [alerts]
+    "#;
+
+    fn effective(toml_src: &str, env: &[(&str, &str)]) -> EffectiveConfig {
+        let cfg: AppConfig = toml::from_str(toml_src).unwrap();
+        let file: toml::Value = toml::from_str(toml_src).unwrap();
+        EffectiveConfig::resolve(&cfg, &file, |k| {
+            env.iter().find(|(name, _)| *name == k).map(|(_, v)| v.to_string())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn test_load_config() {
         // This test requires config.toml to be in the working directory.
@@ -366,10 +2667,236 @@ mod tests {
             assert_eq!(cfg.agent.scan_interval_secs, 600);
             assert!(cfg.agent.initial_bankroll > Decimal::ZERO);
             assert_eq!(cfg.llm.provider, "openrouter");
-            assert!(!cfg.platforms.forecastex.enabled); // Phase 2 stub — disabled
+            assert!(!cfg.platforms.forecastex.enabled);
+            assert!(cfg.platforms.forecastex.paper);
             assert!(cfg.risk.kelly_multiplier > Decimal::ZERO);
             assert!(cfg.risk.kelly_multiplier <= Decimal::ONE);
         }
         // If config.toml isn't found, that's acceptable in some test environments
     }
+
+    #[test]
+    fn test_llm_key_env_defaults_by_provider() {
+        let grok = MINIMAL
+            .replace(r#"provider = "openrouter""#, r#"provider = "grok""#)
+            .replace(r#"api_key_env = "T_LLM_KEY""#, "cite_x_posts = true");
+        let cfg: AppConfig = toml::from_str(&grok).unwrap();
+        assert_eq!(cfg.llm.key_env(), "XAI_API_KEY");
+        assert!(cfg.llm.cite_x_posts);
+
+        // An explicit env var wins
+        let cfg: AppConfig = toml::from_str(MINIMAL).unwrap();
+        assert_eq!(cfg.llm.key_env(), "T_LLM_KEY");
+        assert!(!cfg.llm.cite_x_posts);
+    }
+
+    #[test]
+    fn test_prompt_budget_keys() {
+        let src = MINIMAL.replace("batch_size = 5", "max_markets_per_batch = 8\ncontext_window = 32000");
+        let mut cfg: AppConfig = toml::from_str(&src).unwrap();
+        assert_eq!((cfg.llm.batch_size, cfg.llm.context_window), (8, Some(32_000)));
+        assert!(cfg.validate().is_ok());
+
+        // No room left for the prompt
+        cfg.llm.context_window = Some(1_500);
+        assert!(cfg.validate().is_err());
+        let defaults: AppConfig = toml::from_str(MINIMAL).unwrap();
+        assert_eq!(defaults.llm.context_window, None);
+    }
+
+    #[test]
+    fn test_category_models_parse_and_validate() {
+        let src = format!(
+            "{MINIMAL}\n[llm.category_models.politics]\nprovider = \"anthropic\"\nmodel = \"claude-opus-4-1\"\napi_key_env = \"T_KEY\"\n"
+        );
+        let mut cfg: AppConfig = toml::from_str(&src).unwrap();
+        assert_eq!(cfg.llm.category_models["politics"].model, "claude-opus-4-1");
+        assert!(cfg.validate().is_ok());
+
+        let politics = cfg.llm.category_models.remove("politics").unwrap();
+        cfg.llm.category_models.insert("astrology".to_string(), politics);
+        assert!(cfg.validate().is_err());
+        let defaults: AppConfig = toml::from_str(MINIMAL).unwrap();
+        assert!(defaults.llm.category_models.is_empty());
+    }
+
+    #[test]
+    fn test_embeddings_endpoint_follows_llm_provider() {
+        let mut cfg: AppConfig = toml::from_str(&format!("{MINIMAL}\n[scanner.embeddings]\nenabled = true\nprovider = \"api\"\n")).unwrap();
+        assert_eq!(cfg.scanner.embeddings.match_threshold, 0.80);
+        assert_eq!(crate::embeddings::endpoint(&cfg.llm).unwrap(), "https://openrouter.ai/api/v1/embeddings");
+        assert!(cfg.validate().is_ok());
+
+        cfg.llm.provider = "openai".to_string();
+        cfg.llm.base_url = Some("http://localhost:8000/v1/".to_string());
+        assert_eq!(crate::embeddings::endpoint(&cfg.llm).unwrap(), "http://localhost:8000/v1/embeddings");
+        // Anthropic serves no embeddings; the local embedder still works.
+        cfg.llm.provider = "anthropic".to_string();
+        assert!(cfg.validate().is_err());
+        cfg.scanner.embeddings.provider = "local".to_string();
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn test_chaos_refused_in_live_mode() {
+        let Ok(contents) = fs::read_to_string("config.toml") else { return };
+        let chaos = contents.replace("[chaos]\nenabled = false", "[chaos]\nenabled = true");
+        assert_ne!(chaos, contents);
+        let paper: AppConfig = toml::from_str(&chaos).unwrap();
+        assert!(paper.validate().is_ok());
+
+        let mut live = paper.clone();
+        live.agent.trading_mode = "live".to_string();
+        assert!(live.validate().is_err());
+    }
+
+    #[test]
+    fn test_drawdown_tiers_parse_and_validate() {
+        let toml_src = format!(
+            "{MINIMAL}\n[[risk.drawdown_tiers]]\nfrom_pct = 0.0\nmultiplier = 1.0\n\
+             [[risk.drawdown_tiers]]\nfrom_pct = 0.15\nmultiplier = 0.5\n\
+             [[risk.drawdown_tiers]]\nfrom_pct = 0.35\nmultiplier = 0.0\n"
+        );
+        let mut cfg: AppConfig = toml::from_str(&toml_src).unwrap();
+        assert_eq!(cfg.risk.drawdown_tiers.len(), 3);
+        assert_eq!(DrawdownTier::halt_pct(&cfg.risk.drawdown_tiers), Some(dec!(0.35)));
+        assert!(cfg.validate().is_ok());
+
+        // Not monotonic in multiplier
+        cfg.risk.drawdown_tiers[1].multiplier = dec!(1.2);
+        assert!(cfg.validate().is_err());
+        cfg.risk.drawdown_tiers[1].multiplier = dec!(0.5);
+        // Not monotonic in drawdown
+        cfg.risk.drawdown_tiers[1].from_pct = dec!(0.40);
+        assert!(cfg.validate().is_err());
+        cfg.risk.drawdown_tiers[1].from_pct = dec!(0.15);
+        // Resume must sit below the halt tier
+        cfg.risk.drawdown_resume_pct = dec!(0.35);
+        assert!(cfg.validate().is_err());
+        cfg.risk.drawdown_resume_pct = dec!(0.30);
+        // No halt tier
+        cfg.risk.drawdown_tiers.pop();
+        assert!(cfg.validate().is_err());
+
+        let defaults: AppConfig = toml::from_str(MINIMAL).unwrap();
+        assert_eq!(defaults.risk.drawdown_tiers, DrawdownTier::defaults());
+    }
+
+    #[test]
+    fn test_confidence_scaling_parse_and_validate() {
+        let defaults: AppConfig = toml::from_str(MINIMAL).unwrap();
+        assert_eq!(defaults.risk.confidence_scaling, ConfidenceScalingConfig::default());
+
+        let toml_src = format!("{MINIMAL}\n[risk.confidence_scaling]\nenabled = true\nk = 2.5\nmin_confidence = 0.3\n");
+        let mut cfg: AppConfig = toml::from_str(&toml_src).unwrap();
+        assert!(cfg.risk.confidence_scaling.enabled);
+        assert_eq!((cfg.risk.confidence_scaling.k, cfg.risk.confidence_scaling.min_confidence), (dec!(2.5), dec!(0.3)));
+        assert!(cfg.validate().is_ok());
+
+        cfg.risk.confidence_scaling.k = dec!(-1);
+        assert!(cfg.validate().is_err());
+        cfg.risk.confidence_scaling.k = dec!(1);
+        cfg.risk.confidence_scaling.min_confidence = dec!(1.5);
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn test_scanner_caps_parse() {
+        let scanner: ScannerConfig = toml::from_str("max_total = 40\n[max_per_category]\nsports = 30\n").unwrap();
+        assert_eq!(scanner.max_markets_to_process, 40);
+        assert_eq!(scanner.max_per_category["sports"], 30);
+        assert!(ScannerConfig::default().max_per_category.is_empty());
+        assert_eq!(scanner.allowed_languages, ["en"]);
+    }
+
+    #[test]
+    fn test_fee_models_parse() {
+        let fees: HashMap<String, FeeModel> = toml::from_str(
+            "betfair = { percent_of_winnings = 0.05 }\nmanifold = \"none\"\nforecastex = { flat_per_trade = 0.5 }\n",
+        )
+        .unwrap();
+        assert_eq!(fees["betfair"], FeeModel::PercentOfWinnings(dec!(0.05)));
+        assert_eq!(fees["manifold"], FeeModel::None);
+        assert_eq!(fees["forecastex"], FeeModel::FlatPerTrade(dec!(0.5)));
+    }
+
+    #[test]
+    fn test_chaos_scenarios_resolve() {
+        for name in ChaosConfig::SCENARIOS {
+            let cfg = ChaosConfig { scenario: Some(name.to_string()), ..ChaosConfig::default() };
+            let resolved = cfg.resolved();
+            assert!(
+                resolved.platform != ChaosFaults::default() || resolved.llm != ChaosFaults::default(),
+                "scenario {name} injects nothing"
+            );
+        }
+        let flaky = ChaosConfig { scenario: Some("flaky-manifold".into()), ..ChaosConfig::default() };
+        assert_eq!(flaky.resolved().target_platform.as_deref(), Some("manifold"));
+    }
+
+    #[test]
+    fn test_unwind_window_matching() {
+        let windows = vec![
+            UnwindWindow { platform: "betfair".into(), category: None, minutes_before: 5 },
+            UnwindWindow {
+                platform: "betfair".into(),
+                category: Some(MarketCategory::Sports),
+                minutes_before: 10,
+            },
+        ];
+        let lead = |p, c| UnwindWindow::lead_time(&windows, p, c);
+        assert_eq!(lead("betfair", Some(MarketCategory::Sports)), Some(chrono::Duration::minutes(10)));
+        assert_eq!(lead("betfair", Some(MarketCategory::Politics)), Some(chrono::Duration::minutes(5)));
+        assert_eq!(lead("manifold", Some(MarketCategory::Sports)), None);
+
+        let now = Utc::now();
+        let sports = Some(MarketCategory::Sports);
+        assert!(UnwindWindow::contains(&windows, "betfair", sports, now + chrono::Duration::minutes(9), now));
+        assert!(!UnwindWindow::contains(&windows, "betfair", sports, now + chrono::Duration::minutes(11), now));
+    }
+
+    #[test]
+    fn test_effective_config_redacts_secrets() {
+        let eff = effective(MINIMAL, &[("T_LLM_KEY", "sk-live-abc123")]);
+        let key = &eff.entries["llm.api_key"];
+        assert_eq!(key.value, serde_json::json!("set"));
+        assert_eq!(key.source, ConfigSource::Env);
+        // The env var's name is shown, its value never is.
+        assert_eq!(eff.entries["llm.api_key_env"].value, serde_json::json!("T_LLM_KEY"));
+        assert_eq!(eff.entries["platforms.forecastex.account_id"].value, serde_json::json!("unset"));
+        let json = serde_json::to_string(&eff).unwrap();
+        assert!(!json.contains("sk-live-abc123"));
+        // Optional secret keys that are not configured get no sibling.
+        assert!(!eff.entries.contains_key("platforms.manifold.api_key"));
+    }
+
+    #[test]
+    fn test_effective_config_provenance() {
+        let eff = effective(MINIMAL, &[]);
+        assert_eq!(eff.entries["risk.kelly_multiplier"].source, ConfigSource::File);
+        assert_eq!(eff.entries["risk.category_thresholds.weather"].source, ConfigSource::File);
+        let mode = &eff.entries["agent.trading_mode"];
+        assert_eq!((mode.value.clone(), mode.source), (serde_json::json!("dry"), ConfigSource::Default));
+        assert_eq!(eff.entries["platforms.manifold.match_window_secs"].source, ConfigSource::Default);
+        assert_eq!(eff.entries["execution.bet_timeout_secs"].source, ConfigSource::Default);
+    }
+
+    #[test]
+    fn test_effective_config_diff() {
+        let before = effective(MINIMAL, &[]).values();
+        let edited = MINIMAL
+            .replace("kelly_multiplier = 0.25", "kelly_multiplier = 0.5")
+            .replace("weather = 0.06", "weather = 0.06\n        sports = 0.09");
+        let after = effective(&edited, &[("T_LLM_KEY", "k")]);
+
+        let changes = after.diff(&before);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["llm.api_key", "risk.category_thresholds.sports", "risk.kelly_multiplier"]);
+        assert_eq!(changes[0].previous, Some(serde_json::json!("unset")));
+        assert_eq!(changes[0].current, Some(serde_json::json!("set")));
+        assert_eq!(changes[1].previous, None);
+        assert_eq!(changes[2].current, Some(serde_json::json!(0.5)));
+
+        assert!(after.diff(&after.values()).is_empty());
+    }
 }
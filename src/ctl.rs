@@ -0,0 +1,670 @@
+//! Remote-control client for the dashboard API.
+//!
+//! Backs the `oraclectl` binary. Requests go through the `Transport` trait
+//! so the client can be exercised against the in-process axum router in
+//! tests; `HttpTransport` is the reqwest implementation used in practice.
+//! Responses are handled as `serde_json::Value` — the client tolerates a
+//! server that is older or newer than itself. `tail` follows the agent's
+//! `/api/events` server-sent event stream.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Default dashboard address when neither the config file nor env sets one.
+pub const DEFAULT_URL: &str = "http://127.0.0.1:8080";
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Client settings from `~/.oraclectl.toml`, overridden by `ORACLECTL_URL`
+/// and `ORACLECTL_TOKEN`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CtlConfig {
+    #[serde(default = "CtlConfig::default_url")]
+    pub url: String,
+    /// Sent as a bearer token when set.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl Default for CtlConfig {
+    fn default() -> Self {
+        Self { url: DEFAULT_URL.to_string(), token: None }
+    }
+}
+
+impl CtlConfig {
+    fn default_url() -> String {
+        DEFAULT_URL.to_string()
+    }
+
+    /// Load from the user's config file and environment.
+    pub fn load() -> Result<Self> {
+        let path = std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".oraclectl.toml"));
+        let file = match path {
+            Some(p) if p.exists() => Some(
+                std::fs::read_to_string(&p)
+                    .with_context(|| format!("Failed to read {}", p.display()))?,
+            ),
+            _ => None,
+        };
+        Self::from_sources(file.as_deref(), |k| std::env::var(k).ok())
+    }
+
+    /// Merge an optional TOML document with environment lookups.
+    pub fn from_sources(file: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut cfg = match file {
+            Some(text) => toml::from_str(text).context("Failed to parse ~/.oraclectl.toml")?,
+            None => Self::default(),
+        };
+        if let Some(url) = env("ORACLECTL_URL") {
+            cfg.url = url;
+        }
+        if let Some(token) = env("ORACLECTL_TOKEN") {
+            cfg.token = Some(token);
+        }
+        cfg.url = cfg.url.trim_end_matches('/').to_string();
+        Ok(cfg)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Transport
+// ---------------------------------------------------------------------------
+
+/// Body of a streaming response, chunk by chunk as it arrives.
+pub type ByteStream = BoxStream<'static, Result<Vec<u8>>>;
+
+/// Sends requests to the dashboard.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send one request and return `(status, body)`.
+    async fn send(&self, method: Method, path: &str, body: Option<Value>) -> Result<(u16, String)>;
+
+    /// GET `path` and return `(status, body)` without waiting for the body
+    /// to end, for event streams.
+    async fn stream(&self, path: &str) -> Result<(u16, ByteStream)>;
+}
+
+/// Longest a request/response call may take; streams are not limited.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// HTTP transport over reqwest.
+pub struct HttpTransport {
+    http: reqwest::Client,
+    config: CtlConfig,
+}
+
+impl HttpTransport {
+    pub fn new(config: CtlConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self { http, config })
+    }
+
+    fn request(&self, method: Method, path: &str) -> reqwest::RequestBuilder {
+        let req = self.http.request(method, format!("{}{path}", self.config.url));
+        match &self.config.token {
+            Some(token) => req.bearer_auth(token),
+            None => req,
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send(&self, method: Method, path: &str, body: Option<Value>) -> Result<(u16, String)> {
+        let mut req = self.request(method, path).timeout(REQUEST_TIMEOUT);
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+        let resp = req
+            .send()
+            .await
+            .with_context(|| format!("Cannot reach {}", self.config.url))?;
+        let status = resp.status().as_u16();
+        Ok((status, resp.text().await.unwrap_or_default()))
+    }
+
+    async fn stream(&self, path: &str) -> Result<(u16, ByteStream)> {
+        let resp = self
+            .request(Method::GET, path)
+            .send()
+            .await
+            .with_context(|| format!("Cannot reach {}", self.config.url))?;
+        let status = resp.status().as_u16();
+        let chunks = stream::try_unfold(resp, |mut resp| async move {
+            Ok(resp.chunk().await?.map(|chunk| (chunk.to_vec(), resp)))
+        });
+        Ok((status, chunks.boxed()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// Error from a dashboard API call.
+#[derive(Debug, thiserror::Error)]
+pub enum CtlError {
+    #[error("{path}: HTTP {status}: {body}")]
+    Api { path: String, status: u16, body: String },
+    #[error("`{command}` is not supported by this server (no {path} endpoint)")]
+    Unsupported { command: String, path: String },
+}
+
+/// Agent control actions. These POST to `/api/control/...`; servers that
+/// don't expose an action answer 404, reported as `CtlError::Unsupported`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlAction {
+    Pause,
+    Standby,
+    Resume,
+}
+
+impl ControlAction {
+    fn command(&self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Standby => "standby",
+            Self::Resume => "resume",
+        }
+    }
+
+    fn path(&self) -> String {
+        format!("/api/control/{}", self.command())
+    }
+}
+
+/// Typed access to the dashboard API.
+pub struct CtlClient<T: Transport> {
+    transport: T,
+}
+
+impl<T: Transport> CtlClient<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    async fn call(&self, method: Method, path: &str, body: Option<Value>) -> Result<Value> {
+        let (status, text) = self.transport.send(method, path, body).await?;
+        if !(200..300).contains(&status) {
+            return Err(CtlError::Api { path: path.to_string(), status, body: text }.into());
+        }
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&text).with_context(|| format!("{path}: invalid JSON response"))
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        self.call(Method::GET, path, None).await
+    }
+
+    pub async fn status(&self) -> Result<Value> {
+        self.get("/api/status").await
+    }
+
+    pub async fn positions(&self) -> Result<Value> {
+        self.get("/api/positions").await
+    }
+
+    /// Latest page of trades, newest last.
+    pub async fn trades(&self) -> Result<Value> {
+        self.get("/api/trades").await.map(page_items)
+    }
+
+    pub async fn progress(&self) -> Result<Value> {
+        self.get("/api/progress").await
+    }
+
+    pub async fn errors(&self) -> Result<Value> {
+        self.get("/api/errors").await
+    }
+
+    /// Effective configuration; requires the dashboard API token.
+    pub async fn config(&self) -> Result<Value> {
+        self.get("/api/config").await
+    }
+
+    /// Follow the agent's event stream: one JSON event per item, starting
+    /// with the current phase, until the agent closes it.
+    pub async fn events(&self) -> Result<BoxStream<'static, Result<Value>>> {
+        const PATH: &str = "/api/events";
+        let (status, body) = self.transport.stream(PATH).await?;
+        if status == 404 {
+            return Err(CtlError::Unsupported { command: "tail".to_string(), path: PATH.to_string() }.into());
+        }
+        if !(200..300).contains(&status) {
+            let text = body.take(1).next().await.and_then(|c| c.ok()).unwrap_or_default();
+            let body = String::from_utf8_lossy(&text).into_owned();
+            return Err(CtlError::Api { path: PATH.to_string(), status, body }.into());
+        }
+        let events = body
+            .scan(SseDecoder::default(), |decoder, chunk| {
+                let data = match chunk {
+                    Ok(bytes) => decoder.push(&bytes).into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                futures::future::ready(Some(stream::iter(data)))
+            })
+            .flatten()
+            .map(|data| {
+                data.and_then(|d| serde_json::from_str(&d).with_context(|| format!("{PATH}: invalid event {d}")))
+            });
+        Ok(events.boxed())
+    }
+
+    /// Run a control action.
+    pub async fn control(&self, action: &ControlAction) -> Result<Value> {
+        let path = action.path();
+        match self.call(Method::POST, &path, None).await {
+            Err(e) if matches!(e.downcast_ref::<CtlError>(), Some(CtlError::Api { status: 404, .. })) => {
+                Err(CtlError::Unsupported { command: action.command().to_string(), path }.into())
+            }
+            other => other,
+        }
+    }
+
+    /// Snapshot of every read endpoint as a single JSON document.
+    pub async fn export(&self) -> Result<Value> {
+        Ok(json!({
+            "exported_at": chrono::Utc::now().to_rfc3339(),
+            "status": self.status().await?,
+            "positions": self.positions().await?,
+            "trades": self.trades().await?,
+            "cycles": self.get("/api/cycles").await.map(page_items)?,
+            "costs": self.get("/api/costs").await?,
+            "metrics": self.get("/api/metrics").await?,
+            "errors": self.errors().await?,
+        }))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tail
+// ---------------------------------------------------------------------------
+
+/// Splits a server-sent event stream into the data of each event.
+/// Comment lines (keep-alives) and other fields are skipped.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buf: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Feed the next chunk; returns the data of every event it completes.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buf.extend(chunk.iter().filter(|&&b| b != b'\r'));
+        let mut events = Vec::new();
+        while let Some(end) = self.buf.windows(2).position(|w| w == b"\n\n") {
+            let block: Vec<u8> = self.buf.drain(..end + 2).collect();
+            let text = String::from_utf8_lossy(&block);
+            let data: Vec<&str> = text
+                .lines()
+                .filter_map(|l| l.strip_prefix("data:"))
+                .map(|d| d.strip_prefix(' ').unwrap_or(d))
+                .collect();
+            if !data.is_empty() {
+                events.push(data.join("\n"));
+            }
+        }
+        events
+    }
+}
+
+/// One `tail` line for an `/api/events` event; `None` for kinds this
+/// client doesn't know.
+pub fn render_event(event: &Value) -> Option<String> {
+    match event["kind"].as_str()? {
+        "phase" => Some(format!("phase    {}", describe_phase(&event["progress"]))),
+        "trade" => Some(format!(
+            "trade    {} {} {} {:.2} {}{}",
+            str_field(event, "platform"),
+            str_field(event, "market_id"),
+            str_field(event, "side"),
+            event["amount"].as_f64().unwrap_or(0.0),
+            str_field(event, "currency"),
+            event["close_reason"].as_str().map(|r| format!(" ({r})")).unwrap_or_default(),
+        )),
+        "error" => Some(format!("error    cycle {}: {}", event["cycle_number"], str_field(event, "error"))),
+        _ => None,
+    }
+}
+
+fn describe_phase(phase: &Value) -> String {
+    let state = phase["state"].as_str().unwrap_or("unknown");
+    match (phase["markets_done"].as_u64(), phase["markets_total"].as_u64(), phase["bets_total"].as_u64()) {
+        (Some(done), Some(total), _) => format!("{state} ({done}/{total})"),
+        (None, Some(total), _) => format!("{state} ({total} markets)"),
+        (_, _, Some(bets)) => format!("{state} ({bets} bets)"),
+        _ => state.to_string(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+fn str_field<'a>(v: &'a Value, key: &str) -> &'a str {
+    v[key].as_str().unwrap_or("-")
+}
+
+fn cell(v: &Value) -> String {
+    match v {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() => format!("{f:.2}"),
+            _ => n.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+/// Left-aligned plain-text table.
+pub fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (w, c) in widths.iter_mut().zip(row) {
+            *w = (*w).max(c.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut out = vec![line(headers.to_vec())];
+    out.push(line(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().iter().map(String::as_str).collect()));
+    for row in rows {
+        out.push(line(row.iter().map(String::as_str).collect()));
+    }
+    out.join("\n")
+}
+
+/// Two-column key/value view of `/api/status`.
+pub fn render_status(status: &Value) -> String {
+    const KEYS: &[&str] = &[
+        "status", "execution_enabled", "trading_mode", "bankroll", "peak_bankroll", "total_pnl", "mana_bankroll",
+        "total_mana_pnl", "cycle_count", "trades_placed", "open_bets_count", "open_bets_staked",
+        "win_rate", "total_costs", "uptime_secs",
+    ];
+    let rows: Vec<Vec<String>> = KEYS
+        .iter()
+        .filter(|k| !status[**k].is_null())
+        .map(|k| vec![k.to_string(), cell(&status[*k])])
+        .collect();
+    table(&["field", "value"], &rows)
+}
+
+/// Effective configuration from `/api/config`, one row per key.
+pub fn render_config(config: &Value) -> String {
+    let rows: Vec<Vec<String>> = config
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(key, entry)| vec![key.clone(), entry["value"].to_string(), cell(&entry["source"])])
+        .collect();
+    table(&["key", "value", "source"], &rows)
+}
+
+/// Open positions from `/api/positions`.
+pub fn render_positions(positions: &Value) -> String {
+    let rows: Vec<Vec<String>> = positions
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|p| {
+            ["platform", "market_id", "side", "amount", "fill_price", "currency", "timestamp"]
+                .iter()
+                .map(|k| cell(&p[*k]))
+                .collect()
+        })
+        .collect();
+    table(&["platform", "market", "side", "amount", "price", "ccy", "placed"], &rows)
+}
+
+/// Entries of a paged response; an agent predating paging answers with
+/// the bare list.
+fn page_items(mut page: Value) -> Value {
+    match page.get_mut("items") {
+        Some(items) => items.take(),
+        None => page,
+    }
+}
+
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::Router;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::dashboard::build_router;
+    use crate::dashboard::routes::{AppState, DashboardState, ErrorLogEntry, EvaluationProgress, TradeLogEntry};
+    use crate::engine::standby::ModeRequest;
+    use crate::types::{AgentState, Side, TradeReceipt};
+
+    /// Bearer token of the test agent's privileged endpoints.
+    const TOKEN: &str = "test-token";
+
+    /// Routes requests into the in-process dashboard router.
+    struct RouterTransport(Router);
+
+    #[async_trait]
+    impl Transport for RouterTransport {
+        async fn send(&self, method: Method, path: &str, body: Option<Value>) -> Result<(u16, String)> {
+            let req = Request::builder()
+                .method(method)
+                .uri(path)
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {TOKEN}"))
+                .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))?;
+            let resp = self.0.clone().oneshot(req).await?;
+            let status = resp.status().as_u16();
+            let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+            Ok((status, String::from_utf8_lossy(&bytes).into_owned()))
+        }
+
+        async fn stream(&self, path: &str) -> Result<(u16, ByteStream)> {
+            let req = Request::get(path).header("authorization", format!("Bearer {TOKEN}")).body(Body::empty())?;
+            let resp = self.0.clone().oneshot(req).await?;
+            let status = resp.status().as_u16();
+            let chunks = resp.into_body().into_data_stream().map(|c| Ok(c?.to_vec()));
+            Ok((status, chunks.boxed()))
+        }
+    }
+
+    fn client() -> CtlClient<RouterTransport> {
+        client_for(agent())
+    }
+
+    fn client_for(state: AppState) -> CtlClient<RouterTransport> {
+        CtlClient::new(RouterTransport(build_router(state)))
+    }
+
+    fn agent() -> AppState {
+        let mut agent = AgentState::new(dec!(100));
+        agent.open_bets.push(TradeReceipt {
+            order_id: "o1".to_string(),
+            market_id: "m1".to_string(),
+            platform: "manifold".to_string(),
+            side: Side::Yes,
+            amount: dec!(12),
+            fill_price: dec!(0.4),
+            fees: dec!(0),
+            timestamp: chrono::Utc::now(),
+            currency: "Mana".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        });
+        let mut state = DashboardState::new(agent);
+        state.api_token = Some(TOKEN.to_string());
+        Arc::new(state)
+    }
+
+    #[tokio::test]
+    async fn test_status_and_positions_roundtrip() {
+        let c = client();
+        let status = c.status().await.unwrap();
+        assert_eq!(status["bankroll"].as_f64(), Some(100.0));
+        let rendered = render_status(&status);
+        assert!(rendered.lines().any(|l| l.starts_with("bankroll") && l.ends_with("100.00")));
+
+        let positions = c.positions().await.unwrap();
+        let rendered = render_positions(&positions);
+        assert_eq!(rendered.lines().count(), 3); // header, rule, one row
+        assert!(rendered.contains("m1"));
+    }
+
+    #[tokio::test]
+    async fn test_control_actions_reach_the_agent() {
+        let state = agent();
+        let c = client_for(Arc::clone(&state));
+        for (action, mode) in [
+            (ControlAction::Pause, ModeRequest::Pause),
+            (ControlAction::Standby, ModeRequest::Standby),
+            (ControlAction::Resume, ModeRequest::Resume),
+        ] {
+            let reply = c.control(&action).await.unwrap_or_else(|e| panic!("{action:?}: {e:#}"));
+            assert_eq!(reply["mode"], json!(mode));
+            assert_eq!(*state.mode_request.read().await, Some(mode));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_control_is_unsupported() {
+        // An agent predating the control endpoints.
+        let old = CtlClient::new(RouterTransport(Router::new()));
+        let err = old.control(&ControlAction::Standby).await.unwrap_err();
+        match err.downcast_ref::<CtlError>() {
+            Some(CtlError::Unsupported { command, path }) => {
+                assert_eq!(command, "standby");
+                assert_eq!(path, "/api/control/standby");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        let err = old.events().await.err().expect("no event stream");
+        assert!(matches!(err.downcast_ref::<CtlError>(), Some(CtlError::Unsupported { command, .. }) if command == "tail"));
+    }
+
+    #[tokio::test]
+    async fn test_export_bundles_endpoints() {
+        let export = client().export().await.unwrap();
+        for key in ["status", "positions", "trades", "cycles", "costs", "metrics", "errors"] {
+            assert!(!export[key].is_null(), "missing {key}");
+        }
+        assert!(export["trades"].is_array() && export["cycles"].is_array());
+    }
+
+    #[test]
+    fn test_config_env_overrides_file() {
+        let file = "url = \"http://agent:9000/\"\ntoken = \"from-file\"";
+        let cfg = CtlConfig::from_sources(Some(file), |_| None).unwrap();
+        assert_eq!(cfg.url, "http://agent:9000");
+        assert_eq!(cfg.token.as_deref(), Some("from-file"));
+
+        let cfg = CtlConfig::from_sources(Some(file), |k| {
+            (k == "ORACLECTL_TOKEN").then(|| "from-env".to_string())
+        })
+        .unwrap();
+        assert_eq!(cfg.token.as_deref(), Some("from-env"));
+        assert_eq!(CtlConfig::from_sources(None, |_| None).unwrap(), CtlConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_tail_follows_event_stream() {
+        let state = agent();
+        let mut events = client_for(Arc::clone(&state)).events().await.unwrap();
+        let mut next = async || render_event(&events.next().await.unwrap().unwrap()).unwrap();
+
+        // Opens with the current phase, not the backlog.
+        assert_eq!(next().await, "phase    idle");
+
+        state.set_progress(EvaluationProgress::Estimating { markets_total: 10, markets_done: 4 }).await;
+        state
+            .push_trades([TradeLogEntry {
+                timestamp: "2026-01-01T00:10:00Z".to_string(),
+                market_id: "new".to_string(),
+                platform: "manifold".to_string(),
+                side: "YES".to_string(),
+                amount: 25.0,
+                currency: "Mana".to_string(),
+                edge_pct: 8.0,
+                confidence: 0.7,
+                category: None,
+                close_reason: None,
+                final_pnl: None,
+            }])
+            .await;
+        state
+            .push_error(ErrorLogEntry {
+                timestamp: "2026-01-01T00:11:00Z".to_string(),
+                cycle_number: 3,
+                error: "boom".to_string(),
+            })
+            .await;
+        assert_eq!(next().await, "phase    estimating (4/10)");
+        assert_eq!(next().await, "trade    manifold new YES 25.00 Mana");
+        assert_eq!(next().await, "error    cycle 3: boom");
+        assert_eq!(state.recent_trades.read().await.len(), 1);
+        assert_eq!(state.error_log.read().await.len(), 1);
+    }
+
+    #[test]
+    fn test_sse_decoder_reassembles_chunks() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b"data: {\"kind\":").is_empty());
+        assert_eq!(decoder.push(b"\"phase\"}\r\n\r\n:\n\ndata: 1\ndata: 2\n\ndata"), ["{\"kind\":\"phase\"}", "1\n2"]);
+        assert_eq!(decoder.push(b": x\n\n"), ["x"]);
+        assert_eq!(render_event(&json!({"kind": "later"})), None);
+    }
+
+    #[test]
+    fn test_render_config() {
+        let config = json!({
+            "agent.trading_mode": {"value": "paper", "source": "file"},
+            "llm.api_key": {"value": "set", "source": "env"},
+        });
+        let rendered = render_config(&config);
+        assert_eq!(rendered.lines().count(), 4);
+        assert!(rendered.lines().any(|l| l.starts_with("llm.api_key") && l.ends_with("env")));
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        let t = table(&["a", "bb"], &[vec!["long".into(), "x".into()]]);
+        assert_eq!(t, "a     bb\n----  --\nlong  x");
+    }
+}
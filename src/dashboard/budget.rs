@@ -0,0 +1,261 @@
+//! Request budget for the dashboard.
+//!
+//! The dashboard shares the tokio runtime with the trading loop, so a tab
+//! refreshing every second must not be able to slow a cycle down. Three
+//! mechanisms keep per-request work bounded:
+//!
+//! - read endpoints are served from a short-TTL response cache
+//!   ([`cache_layer`]), so repeated polls don't touch shared state;
+//! - expensive routes share a small pool of permits ([`limit_expensive`])
+//!   and answer 429 when it is exhausted;
+//! - CPU-heavy work (serialising long histories) runs on the blocking pool
+//!   via [`offload`].
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use super::routes::AppState;
+
+/// Upper bound on a response body we are willing to cache.
+const MAX_CACHED_BYTES: usize = 4 * 1024 * 1024;
+
+/// Cache TTL per read endpoint. Paths not listed are never cached.
+const CACHE_TTLS: &[(&str, u64)] = &[
+    ("/api/status", 1),
+    ("/api/progress", 1),
+    ("/api/cycles", 2),
+    ("/api/balance-history", 2),
+    ("/api/trades", 2),
+    ("/api/costs", 2),
+    ("/api/metrics", 2),
+    ("/api/errors", 2),
+    ("/api/positions", 2),
+    ("/api/risk", 2),
+    ("/api/model-comparison", 5),
+    ("/api/metrics/history", 5),
+    ("/api/sensitivity", 5),
+    ("/api/links", 5),
+    ("/api/calibration", 5),
+    ("/api/decisions", 2),
+    ("/api/trades/history", 5),
+    ("/api/pnl/categories", 5),
+    ("/api/performance", 5),
+];
+
+/// Default number of expensive requests served at once.
+pub const DEFAULT_EXPENSIVE_PERMITS: usize = 2;
+
+fn ttl_for(path: &str) -> Option<Duration> {
+    CACHE_TTLS
+        .iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, secs)| Duration::from_secs(*secs))
+}
+
+struct CachedResponse {
+    expires: Instant,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+/// Successful read responses keyed by path and query.
+pub struct ResponseCache {
+    enabled: bool,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, entries: Mutex::default() }
+    }
+
+    /// Entries held, including expired ones not yet pruned.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &str, now: Instant) -> Option<Response> {
+        let entries = self.entries.lock().unwrap();
+        let hit = entries.get(key).filter(|c| c.expires > now)?;
+        let mut resp = Response::new(Body::from(hit.body.clone()));
+        *resp.headers_mut() = hit.headers.clone();
+        Some(resp)
+    }
+
+    fn put(&self, key: String, headers: HeaderMap, body: Bytes, ttl: Duration, now: Instant) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, c| c.expires > now);
+        entries.insert(key, CachedResponse { expires: now + ttl, headers, body });
+    }
+}
+
+/// Serve cacheable GETs from [`ResponseCache`], filling it on a miss.
+/// Only 200 responses are cached.
+pub async fn cache_layer(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let ttl = ttl_for(req.uri().path());
+    let (true, Some(ttl), &Method::GET) = (state.response_cache.enabled, ttl, req.method()) else {
+        return next.run(req).await;
+    };
+    let key = req.uri().path_and_query().map(|p| p.as_str()).unwrap_or_default().to_string();
+    if let Some(hit) = state.response_cache.get(&key, Instant::now()) {
+        return hit;
+    }
+
+    let resp = next.run(req).await;
+    if resp.status() != StatusCode::OK {
+        return resp;
+    }
+    let (parts, body) = resp.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_CACHED_BYTES).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    state.response_cache.put(key, parts.headers.clone(), bytes.clone(), ttl, Instant::now());
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Admit an expensive request only while a permit is free; otherwise 429.
+pub async fn limit_expensive(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Ok(_permit) = state.expensive_permits.clone().try_acquire_owned() else {
+        return (StatusCode::TOO_MANY_REQUESTS, "Dashboard busy — retry shortly").into_response();
+    };
+    next.run(req).await
+}
+
+/// Run CPU-heavy request work on the blocking pool, off the runtime
+/// workers the trading loop uses.
+pub async fn offload<T, F>(work: F) -> Result<T, StatusCode>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work).await.map_err(|e| {
+        tracing::warn!(error = %e, "Offloaded dashboard work failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dashboard::build_router;
+    use crate::dashboard::routes::DashboardState;
+    use crate::storage::metrics::MetricsStore;
+    use crate::types::AgentState;
+    use axum::http::Request;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn get(state: &AppState, uri: &str) -> (StatusCode, Bytes) {
+        let resp = build_router(Arc::clone(state))
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        (status, axum::body::to_bytes(resp.into_body(), MAX_CACHED_BYTES).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_read_endpoints_served_from_cache_within_ttl() {
+        let state: AppState = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
+        let (_, first) = get(&state, "/api/status").await;
+        let mut agent = state.agent.snapshot().await;
+        agent.bankroll = dec!(250);
+        state.agent.sync(&mut agent).await;
+        let (_, cached) = get(&state, "/api/status").await;
+        assert_eq!(first, cached);
+
+        // Expired entries are refetched.
+        let later = Instant::now() + Duration::from_secs(2);
+        assert!(state.response_cache.get("/api/status", later).is_none());
+        state.response_cache.entries.lock().unwrap().clear();
+        let (_, fresh) = get(&state, "/api/status").await;
+        let json: serde_json::Value = serde_json::from_slice(&fresh).unwrap();
+        assert_eq!(json["bankroll"], 250.0);
+
+        // Disabled: every request is live.
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100))).with_request_budget(false, DEFAULT_EXPENSIVE_PERMITS),
+        );
+        get(&state, "/api/status").await;
+        let mut agent = state.agent.snapshot().await;
+        agent.bankroll = dec!(250);
+        state.agent.sync(&mut agent).await;
+        let (_, live) = get(&state, "/api/status").await;
+        let json: serde_json::Value = serde_json::from_slice(&live).unwrap();
+        assert_eq!(json["bankroll"], 250.0);
+    }
+
+    #[tokio::test]
+    async fn test_expensive_routes_return_429_when_saturated() {
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_metrics(MetricsStore::open_in_memory().await.unwrap())
+                .with_request_budget(true, 1),
+        );
+        let held = state.expensive_permits.clone().try_acquire_owned().unwrap();
+        assert_eq!(get(&state, "/api/metrics/history?hours=1").await.0, StatusCode::TOO_MANY_REQUESTS);
+        // Cheap routes are unaffected.
+        assert_eq!(get(&state, "/api/status").await.0, StatusCode::OK);
+
+        drop(held);
+        assert_eq!(get(&state, "/api/metrics/history?hours=1").await.0, StatusCode::OK);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_writers_not_starved_under_read_load() {
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_metrics(MetricsStore::open_in_memory().await.unwrap()),
+        );
+
+        let readers: Vec<_> = (0..400)
+            .map(|i| {
+                let state = Arc::clone(&state);
+                // Distinct queries defeat the cache on the expensive route.
+                let uri = match i % 4 {
+                    0 => format!("/api/metrics/history?hours={}", 1 + i),
+                    1 => "/api/status".to_string(),
+                    2 => "/api/positions".to_string(),
+                    _ => "/api/risk".to_string(),
+                };
+                tokio::spawn(async move { get(&state, &uri).await.0 })
+            })
+            .collect();
+
+        // The agent loop's writes complete promptly while reads hammer.
+        let mut slowest = Duration::ZERO;
+        for i in 0..50 {
+            let start = Instant::now();
+            let mut agent = state.agent.snapshot().await;
+            agent.cycle_count = i;
+            state.agent.sync(&mut agent).await;
+            *state.progress.write().await = crate::dashboard::routes::EvaluationProgress::Scanning;
+            slowest = slowest.max(start.elapsed());
+            tokio::task::yield_now().await;
+        }
+        assert!(slowest < Duration::from_millis(500), "writer waited {slowest:?}");
+
+        let statuses = futures::future::join_all(readers).await;
+        for status in statuses.into_iter().map(Result::unwrap) {
+            assert!(matches!(status, StatusCode::OK | StatusCode::TOO_MANY_REQUESTS), "{status}");
+        }
+    }
+}
@@ -1,15 +1,20 @@
 //! Dashboard — Axum web server for real-time monitoring.
 //!
 //! Serves a REST API and a self-contained HTML dashboard.
-//! CORS enabled for local development.
+//! CORS enabled for local development. An optional second listener serves
+//! the same UI in public mode (see [`public`]).
 
+pub mod budget;
+pub mod public;
 pub mod routes;
 
 use anyhow::{Context, Result};
 use axum::{
-    http::{header, HeaderValue, Method},
-    response::Html,
-    routing::get,
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
     Router,
 };
 use std::sync::Arc;
@@ -25,14 +30,25 @@ const DASHBOARD_HTML: &str = include_str!("templates/index.html");
 ///
 /// This spawns a background task — it doesn't block.
 pub async fn spawn_dashboard(state: AppState, port: u16) -> Result<()> {
+    info!(port, "Dashboard server starting on http://localhost:{port}");
+    serve(build_router(state), port).await
+}
+
+/// Start the read-only public dashboard on a second port.
+///
+/// Amounts are normalised by [`public::public_layer`]; control endpoints are
+/// disabled. This spawns a background task — it doesn't block.
+pub async fn spawn_public_dashboard(state: AppState, port: u16) -> Result<()> {
+    info!(port, "Public dashboard starting on http://localhost:{port}");
+    serve(build_public_router(state), port).await
+}
+
+async fn serve(app: Router, port: u16) -> Result<()> {
     let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
     let listener = tokio::net::TcpListener::bind(addr)
         .await
         .with_context(|| format!("Failed to bind dashboard port {port} — is it already in use?"))?;
 
-    info!(port, "Dashboard server starting on http://localhost:{port}");
-    let app = build_router(state);
-
     tokio::spawn(async move {
         if let Err(e) = axum::serve(listener, app).await {
             tracing::error!(error = %e, "Dashboard server error");
@@ -62,16 +78,185 @@ pub fn build_router(state: AppState) -> Router {
         .route("/api/trades", get(routes::get_trades))
         .route("/api/costs", get(routes::get_costs))
         .route("/api/metrics", get(routes::get_metrics))
+        .route(
+            "/api/metrics/history",
+            get(routes::get_metrics_history).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
+        .route(
+            "/api/explain/:platform/:market_id",
+            get(routes::get_explain).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
+        .route(
+            "/api/decisions",
+            get(routes::get_decisions).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
+        .route(
+            "/api/rejections/summary",
+            get(routes::get_rejection_summary).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
+        .route(
+            "/api/trades/history",
+            get(routes::get_trade_history).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
+        .route("/api/pnl/categories", get(routes::get_pnl_by_category))
+        .route(
+            "/api/performance",
+            get(routes::get_performance).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
+        .route("/api/links", get(routes::get_links))
+        .route("/api/tags", get(routes::get_tags))
+        .route("/api/daily", get(routes::get_daily))
+        .route(
+            "/api/sensitivity",
+            get(routes::get_sensitivity).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                budget::limit_expensive,
+            )),
+        )
         .route("/api/progress", get(routes::get_progress))
         .route("/api/errors", get(routes::get_errors))
+        .route("/api/events", get(routes::get_events))
         .route("/api/positions", get(routes::get_positions))
+        .route(
+            "/api/positions/:order_id",
+            get(routes::get_position).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route("/api/model-comparison", get(routes::get_model_comparison))
+        .route("/api/risk", get(routes::get_risk))
+        .route("/api/calibration", get(routes::get_calibration))
+        .route(
+            "/api/config",
+            get(routes::get_config).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/diagnostics",
+            get(routes::get_diagnostics).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/control/standby",
+            post(routes::post_standby).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/control/pause",
+            post(routes::post_pause).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/control/resume",
+            post(routes::post_resume).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        // Short aliases of the pause controls.
+        .route(
+            "/api/pause",
+            post(routes::post_pause).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/resume",
+            post(routes::post_resume).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/control/deposit",
+            post(routes::post_deposit).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/control/watchlist",
+            post(routes::post_watchlist).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/blacklist",
+            post(routes::post_blacklist).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
+        .route(
+            "/api/control/wake/:platform",
+            post(routes::post_wake).route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                require_api_token,
+            )),
+        )
         .route("/health", get(routes::health))
+        .route("/metrics", get(routes::get_prometheus))
         // Dashboard HTML
         .route("/", get(serve_dashboard))
+        .layer(middleware::from_fn_with_state(Arc::clone(&state), budget::cache_layer))
         .layer(cors)
         .with_state(state)
 }
 
+/// Build the public-mode router: the normal router wrapped in the
+/// response-transformation layer.
+pub fn build_public_router(state: AppState) -> Router {
+    build_router(Arc::clone(&state))
+        .layer(middleware::from_fn_with_state(state, public::public_layer))
+}
+
+/// Reject requests without `Authorization: Bearer <dashboard token>`.
+/// With no token configured, protected endpoints are unavailable.
+pub async fn require_api_token(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(expected) = state.api_token.as_deref() else {
+        return (StatusCode::FORBIDDEN, "Set dashboard.api_token_env to enable this endpoint")
+            .into_response();
+    };
+    let presented = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if presented != Some(expected) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(req).await
+}
+
 /// Serve the embedded HTML dashboard.
 async fn serve_dashboard() -> Html<&'static str> {
     Html(DASHBOARD_HTML)
@@ -116,6 +301,8 @@ mod tests {
         let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
         let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
         assert!(json["bankroll"].as_f64().unwrap() > 0.0);
+        assert_eq!(json["cash_bankroll"], json["bankroll"]);
+        assert_eq!(json["total_equity"], json["bankroll"]);
     }
 
     #[tokio::test]
@@ -130,16 +317,26 @@ mod tests {
 
     #[tokio::test]
     async fn test_balance_history_endpoint() {
-        let app = build_router(test_state());
-        let resp = app
-            .oneshot(Request::builder().uri("/api/balance-history").body(Body::empty()).unwrap())
+        use crate::storage::backend::{BalanceSample, Storage};
+        use crate::storage::sqlite::SqliteStorage;
+        let storage = Arc::new(SqliteStorage::open_in_memory().await.unwrap());
+        storage.append_balance(&BalanceSample::of(&AgentState::new(dec!(100)), chrono::Utc::now())).await.unwrap();
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_storage(storage));
+        let resp = build_router(state)
+            .oneshot(Request::builder().uri("/api/balance-history?resolution=daily").body(Body::empty()).unwrap())
             .await
             .unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
         let json: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
-        assert!(!json.is_empty());
+        assert_eq!(json.len(), 1);
+
+        let resp = build_router(test_state())
+            .oneshot(Request::builder().uri("/api/balance-history?resolution=weekly").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
@@ -150,6 +347,32 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json, serde_json::json!({ "items": [], "total": 0, "returned": 0, "next_cursor": null }));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_paging_params_are_json_400() {
+        for uri in [
+            "/api/trades?limit=abc",
+            "/api/trades?limit=0",
+            "/api/trades?from=yesterday",
+            "/api/trades?from=2026-03-02T00:00:00Z&to=2026-03-01T00:00:00Z",
+            "/api/trades?category=astrology",
+            "/api/cycles?since_cycle=-1",
+            "/api/cycles?limit=100000",
+        ] {
+            let resp = build_router(test_state())
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+            let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert!(json["error"].as_str().is_some_and(|e| !e.is_empty()), "{uri}: {json}");
+        }
     }
 
     #[tokio::test]
@@ -197,4 +420,703 @@ mod tests {
         // CORS layer should allow the response through
         assert_eq!(resp.status(), StatusCode::OK);
     }
+
+    async fn get_config(state: AppState, auth: Option<&str>) -> axum::response::Response {
+        let mut req = Request::builder().uri("/api/config");
+        if let Some(a) = auth {
+            req = req.header(header::AUTHORIZATION, a);
+        }
+        build_router(state).oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_config_endpoint_requires_token() {
+
+        // No token configured: never served.
+        assert_eq!(get_config(test_state(), Some("Bearer x")).await.status(), StatusCode::FORBIDDEN);
+
+        let mut config = crate::config::EffectiveConfig::default();
+        config.entries.insert(
+            "llm.api_key".into(),
+            crate::config::ConfigEntry {
+                value: serde_json::json!("set"),
+                source: crate::config::ConfigSource::Env,
+            },
+        );
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_effective_config(config)
+                .with_api_token(Some("s3cret".into())),
+        );
+        assert_eq!(get_config(Arc::clone(&state), None).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(get_config(Arc::clone(&state), Some("Bearer nope")).await.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = get_config(Arc::clone(&state), Some("Bearer s3cret")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["llm.api_key"], serde_json::json!({ "value": "set", "source": "env" }));
+
+        // Never exposed on the public listener.
+        let resp = build_public_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/config")
+                    .header(header::AUTHORIZATION, "Bearer s3cret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_bundle_includes_captured_errors() {
+        use crate::engine::scanner::ScanSummary;
+        use tracing_subscriber::prelude::*;
+
+        let errors = crate::error_buffer::RecentErrors::default();
+        let subscriber = tracing_subscriber::registry().with(errors.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("not an error");
+            tracing::error!(platform = "betfair", "Betfair session expired");
+        });
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_recent_errors(errors)
+                .with_api_token(Some("s3cret".into())),
+        );
+        let scan = ScanSummary { fetched: [("kalshi".to_string(), 42)].into(), ..ScanSummary::default() };
+        state.record_scan(scan, chrono::Utc::now()).await;
+        *state.active_model.write().await = "claude-test".into();
+
+        let uri = |auth: &str| {
+            Request::builder().uri("/api/diagnostics").header(header::AUTHORIZATION, auth).body(Body::empty()).unwrap()
+        };
+        let resp = build_router(Arc::clone(&state)).oneshot(uri("Bearer nope")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = build_router(state).oneshot(uri("Bearer s3cret")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 1_000_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let captured = json["recent_errors"].as_array().unwrap();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0]["message"], "Betfair session expired");
+        assert_eq!(captured[0]["fields"]["platform"], "betfair");
+        assert_eq!(json["platforms"]["kalshi"]["markets"], 42);
+        assert_eq!(json["llm"]["model"], "claude-test");
+        assert_eq!(json["agent"]["bankroll"], 100.0);
+        assert!(json["last_cycle"].is_null());
+        assert!(json["process"]["uptime_secs"].as_i64().unwrap() >= 0);
+    }
+
+    #[tokio::test]
+    async fn test_sensitivity_endpoint() {
+        use crate::backtest::sensitivity::SensitivityParams;
+        use crate::storage::metrics::MetricsStore;
+
+        let uri = || Request::builder().uri("/api/sensitivity").body(Body::empty()).unwrap();
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let state: AppState =
+            Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_metrics(store.clone()));
+        assert_eq!(build_router(state).oneshot(uri()).await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_metrics(store)
+                .with_sensitivity_base(SensitivityParams {
+                    edge: crate::strategy::edge::EdgeConfig::default(),
+                    kelly_multiplier: dec!(0.25),
+                    max_bet_pct: dec!(0.06),
+                    max_bets_per_cycle: 5,
+                }),
+        );
+        let resp = build_router(state).oneshot(uri()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["bankroll"], 100.0);
+        assert_eq!(json["variants"].as_array().unwrap().len(), 11);
+        assert!(json["note"].as_str().unwrap().contains("unresolved"));
+    }
+
+    #[tokio::test]
+    async fn test_explain_reads_through_to_archive() {
+        use crate::storage::archive::{Archive, ArchiveReason};
+        use crate::storage::metrics::{DecisionRow, MetricsStore};
+
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        store
+            .record_decisions(&[DecisionRow {
+                timestamp: chrono::Utc::now(),
+                cycle: 7,
+                platform: "manifold".into(),
+                market_id: "m1".into(),
+                category: "weather".into(),
+                price_yes: 0.40,
+                metaculus_prob: None,
+                manifold_prob: None,
+                estimate: 0.6,
+                confidence: 0.8,
+                edge: Some(0.2),
+                side: Some("YES".into()),
+                decision: "selected".into(),
+                bet_fraction: Some(0.02),
+                resolved_yes: None,
+                tags: Vec::new(),
+            }])
+            .await
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("oracle_explain_{}", uuid::Uuid::new_v4()));
+        let archive = Archive::new(&dir);
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_metrics(store.clone())
+                .with_archive(archive.clone())
+                .with_links(
+                    crate::strategy::links::MarketLinks::parse(
+                        "[[implies]]\nmarket = \"manifold:m1\"\nimplies = \"manifold:m2\"",
+                    )
+                    .unwrap(),
+                ),
+        );
+        let explain = |state: AppState| async move {
+            let resp = build_router(state)
+                .oneshot(Request::builder().uri("/api/explain/manifold/m1").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = resp.status();
+            let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+            (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap_or_default())
+        };
+
+        let (status, hot) = explain(Arc::clone(&state)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(hot["source"], "hot");
+        assert_eq!(hot["decisions"][0]["cycle"], 7);
+        assert_eq!(hot["links"][0]["kind"], "implies");
+        assert_eq!(hot["links"][0]["members"][1], "manifold:m2");
+
+        store.record_resolution("manifold", "m1", false).await.unwrap();
+        archive.archive(&store, "manifold", "m1", ArchiveReason::Resolved, Vec::new()).await.unwrap();
+        let (status, archived) = explain(Arc::clone(&state)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(archived["source"], "archive");
+        assert_eq!(archived["reason"], "resolved");
+        assert_eq!(archived["resolved_yes"], false);
+        assert_eq!(archived["decisions"][0]["cycle"], 7);
+        assert_eq!(archived["links"].as_array().unwrap().len(), 1);
+
+        let resp = build_router(state)
+            .oneshot(Request::builder().uri("/api/explain/manifold/m9").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_decisions_route_filters() {
+        use crate::config::JournalConfig;
+        use crate::storage::journal::DecisionJournal;
+
+        let file = std::env::temp_dir().join(format!("oracle_decisions_{}.jsonl", uuid::Uuid::new_v4()));
+        let lines: String = [(1, "m1", "selected"), (1, "m2", "kelly_rejected"), (2, "m1", "risk_rejected")]
+            .iter()
+            .map(|(cycle, id, decision)| {
+                format!("{}\n", serde_json::json!({ "cycle": cycle, "market_id": id, "decision": decision }))
+            })
+            .collect();
+        std::fs::write(&file, lines).unwrap();
+        let journal = DecisionJournal::new(&JournalConfig {
+            file: file.to_str().unwrap().to_string(),
+            ..JournalConfig::default()
+        });
+        let state: AppState = Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_decision_journal(journal));
+        let decisions = |uri: &'static str| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let resp = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+                assert_eq!(resp.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+                serde_json::from_slice::<Vec<serde_json::Value>>(&body).unwrap()
+            }
+        };
+
+        let all = decisions("/api/decisions").await;
+        assert_eq!(all.iter().map(|d| d["decision"].as_str().unwrap()).collect::<Vec<_>>(), vec![
+            "risk_rejected",
+            "kelly_rejected",
+            "selected"
+        ]);
+        assert_eq!(decisions("/api/decisions?market_id=m1").await.len(), 2);
+        let cycle_one = decisions("/api/decisions?cycle=1").await;
+        assert!(cycle_one.iter().all(|d| d["cycle"] == 1) && cycle_one.len() == 2);
+        let both = decisions("/api/decisions?market_id=m1&cycle=2").await;
+        assert_eq!(both[0]["decision"], "risk_rejected");
+        assert_eq!(decisions("/api/decisions?limit=1").await.len(), 1);
+
+        let resp = build_router(Arc::new(DashboardState::new(AgentState::new(dec!(100)))))
+            .oneshot(Request::builder().uri("/api/decisions").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejection_summary_route_counts_latest_cycles() {
+        use crate::config::JournalConfig;
+        use crate::storage::journal::DecisionJournal;
+
+        let file = std::env::temp_dir().join(format!("oracle_rejections_{}.jsonl", uuid::Uuid::new_v4()));
+        let lines: String = [
+            (1, "risk_rejected", Some("drawdown_halt")),
+            (2, "selected", None),
+            (2, "risk_rejected", Some("exposure_limit_exceeded")),
+            (3, "stake_rejected", Some("below_min_stake")),
+            (3, "risk_rejected", Some("exposure_limit_exceeded")),
+            (3, "kelly_rejected", None),
+        ]
+        .iter()
+        .map(|(cycle, decision, code)| {
+            format!("{}\n", serde_json::json!({ "cycle": cycle, "decision": decision, "reason_code": code }))
+        })
+        .collect();
+        std::fs::write(&file, lines).unwrap();
+        let journal = DecisionJournal::new(&JournalConfig {
+            file: file.to_str().unwrap().to_string(),
+            ..JournalConfig::default()
+        });
+        let state: AppState = Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_decision_journal(journal));
+        let summary = |uri: &'static str| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let resp = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+                assert_eq!(resp.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let latest_two = summary("/api/rejections/summary?cycles=2").await;
+        assert_eq!((latest_two["from_cycle"].as_u64(), latest_two["to_cycle"].as_u64()), (Some(2), Some(3)));
+        assert_eq!(latest_two["total"], 3);
+        assert_eq!(latest_two["by_code"], serde_json::json!({ "exposure_limit_exceeded": 2, "below_min_stake": 1 }));
+
+        let all = summary("/api/rejections/summary").await;
+        assert_eq!((all["from_cycle"].as_u64(), all["total"].as_u64()), (Some(1), Some(4)));
+        assert_eq!(all["by_code"]["drawdown_halt"], 1);
+
+        let resp = build_router(Arc::new(DashboardState::new(AgentState::new(dec!(100)))))
+            .oneshot(Request::builder().uri("/api/rejections/summary").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_scrape_after_cycle() {
+        use crate::engine::accountant::{Accountant, CycleCosts};
+        use crate::engine::executor::Executor;
+        use crate::engine::scanner::ScanSummary;
+        use crate::strategy::edge::Edge;
+        use crate::strategy::kelly::SizedBet;
+        use crate::strategy::risk::RejectionReason;
+        use crate::strategy::DecisionRecord;
+        use crate::types::Side;
+
+        let state = test_state();
+        let bet = |edge| SizedBet {
+            edge,
+            kelly_fraction: dec!(0.1),
+            bet_fraction: dec!(0.05),
+            bet_amount: dec!(5),
+            expected_value: dec!(0.5),
+            net_expected_value: dec!(0.5),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.1),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
+        };
+        let mut edges = crate::testkit::estimates(3, 7).into_iter().map(|(market, estimate)| Edge {
+            market,
+            estimate,
+            side: Side::Yes,
+            edge: dec!(0.1),
+            signed_edge: dec!(0.1),
+            raw_edge: dec!(0.1),
+            effective_threshold: dec!(0.1),
+        });
+
+        // One simulated cycle: a scan, two rejected edges and a dry-run fill.
+        let scan = ScanSummary { scan_seconds: [("kalshi".to_string(), 0.4)].into(), ..ScanSummary::default() };
+        state.prometheus.record_scan(&scan);
+        state.prometheus.record_decisions(&[
+            DecisionRecord::KellyRejected { edge: edges.next().unwrap() },
+            DecisionRecord::RiskRejected {
+                bet: bet(edges.next().unwrap()),
+                reason: RejectionReason::DrawdownHalt { drawdown_pct: dec!(12), limit: dec!(40) },
+            },
+        ]);
+        let executor = Executor::new(None, true).with_metrics(Arc::clone(&state.prometheus));
+        let execution = executor.execute_batch(&[bet(edges.next().unwrap())]).await.unwrap();
+        let mut agent = AgentState::new(dec!(100));
+        agent.peak_bankroll = dec!(125);
+        let costs = CycleCosts { llm_cost: dec!(0.12), data_cost: dec!(0.03), ..CycleCosts::default() };
+        let mut report = Accountant::reconcile(&mut agent, &execution, &costs);
+        report.markets_scanned = 3;
+        report.edges_found = 3;
+        state.prometheus.record_cycle(&report, &agent);
+
+        let resp = build_router(Arc::clone(&state))
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain; version=0.0.4"));
+        let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        let bankroll = format!("oracle_bankroll_current {}", report.bankroll_after);
+        for line in [
+            "oracle_cycles_total 1",
+            "oracle_markets_scanned_total 3",
+            "oracle_edges_found_total 3",
+            r#"oracle_bets_placed_total{platform="dry-run"} 1"#,
+            r#"oracle_bets_rejected_total{reason="drawdown_halt"} 1"#,
+            r#"oracle_bets_rejected_total{reason="kelly"} 1"#,
+            "oracle_llm_cost_usd_total 0.12",
+            "oracle_data_cost_usd_total 0.03",
+            &bankroll,
+            r#"oracle_scan_duration_seconds_bucket{platform="kalshi",le="0.5"} 1"#,
+            r#"oracle_scan_duration_seconds_count{platform="kalshi"} 1"#,
+            "# TYPE oracle_llm_latency_seconds histogram",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {line:?} in\n{text}");
+        }
+        let drawdown = text.lines().find_map(|l| l.strip_prefix("oracle_drawdown_current ")).unwrap();
+        assert!((drawdown.parse::<f64>().unwrap() - 0.2).abs() < 0.05);
+
+        // Not published on the public listener.
+        let resp = build_public_router(state)
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_raw_response_only_in_position_detail() {
+        let mut agent = AgentState::new(dec!(100));
+        let mut receipt = crate::types::TradeReceipt::dry_run("1.23", dec!(10), "AUD");
+        receipt.order_id = "bf-1".into();
+        receipt.raw_response = Some(serde_json::json!({ "status": "SUCCESS" }));
+        agent.open_bets.push(receipt);
+        let state: AppState =
+            Arc::new(DashboardState::new(agent).with_api_token(Some("s3cret".into())));
+
+        let get = |uri: &'static str, auth: Option<&'static str>| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let mut req = Request::builder().uri(uri);
+                if let Some(a) = auth {
+                    req = req.header(header::AUTHORIZATION, a);
+                }
+                app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+            }
+        };
+
+        let resp = get("/api/positions", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json[0]["order_id"], "bf-1");
+        assert!(json[0].get("raw_response").is_none());
+
+        assert_eq!(get("/api/positions/bf-1", None).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(get("/api/positions/nope", Some("Bearer s3cret")).await.status(), StatusCode::NOT_FOUND);
+        let resp = get("/api/positions/bf-1", Some("Bearer s3cret")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["raw_response"]["status"], "SUCCESS");
+    }
+
+    #[tokio::test]
+    async fn test_tags_endpoint_reports_per_tag() {
+        let mut agent = AgentState::new(dec!(100));
+        let mut open = crate::types::TradeReceipt::dry_run("m1", dec!(10), "Mana");
+        open.tags = vec!["f1".into(), "motorsport".into()];
+        agent.open_bets.push(open);
+        let mut resolved = crate::types::TradeReceipt::dry_run("m2", dec!(4), "Mana");
+        resolved.tags = vec!["f1".into()];
+        crate::engine::tags::record_resolution(&mut agent, &resolved, dec!(6), true);
+
+        let app = build_router(Arc::new(DashboardState::new(agent)));
+        let resp = app
+            .oneshot(Request::builder().uri("/api/tags").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let tags = json["tags"].as_array().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0]["tag"], "f1");
+        assert_eq!(tags[0]["open_bets"], 1);
+        assert_eq!(tags[0]["won"], 1);
+        assert_eq!(tags[0]["realized_pnl"].as_f64(), Some(6.0));
+        assert_eq!(tags[1]["tag"], "motorsport");
+        assert_eq!(tags[1]["resolved"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_daily_endpoint_and_status_today() {
+        let mut agent = AgentState::new(dec!(100));
+        crate::engine::daily::close_day(&mut agent, chrono::Utc::now());
+        agent.total_pnl += dec!(5);
+        agent.total_api_costs += dec!(1);
+        let state: AppState = Arc::new(DashboardState::new(agent));
+
+        let get = |uri: &'static str| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let resp = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+                assert_eq!(resp.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(resp.into_body(), 10_000).await.unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+        let daily = get("/api/daily").await;
+        assert!(daily["days"].as_array().unwrap().is_empty());
+        let aud = daily["today"].as_array().unwrap().iter().find(|d| d["currency"] == "AUD").unwrap().clone();
+        assert_eq!(aud["pnl"].as_f64(), Some(4.0));
+        assert_eq!(get("/api/status").await["today_pnl"].as_f64(), Some(4.0));
+    }
+
+    #[tokio::test]
+    async fn test_wake_endpoint_queues_platform() {
+        let state: AppState =
+            Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_api_token(Some("s3cret".into())));
+        let post = |uri: &'static str, auth: Option<&'static str>| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let mut req = Request::builder().method("POST").uri(uri);
+                if let Some(a) = auth {
+                    req = req.header(header::AUTHORIZATION, a);
+                }
+                app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+            }
+        };
+
+        assert_eq!(post("/api/control/wake/betfair", None).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(post("/api/control/wake/nowhere", Some("Bearer s3cret")).await.status(), StatusCode::NOT_FOUND);
+        assert_eq!(post("/api/control/wake/betfair", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(post("/api/control/wake/betfair", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.wake_requests.read().await, vec!["betfair".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_standby_and_resume_endpoints_queue_mode() {
+        let state: AppState =
+            Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_api_token(Some("s3cret".into())));
+        let post = |uri: &'static str, auth: Option<&'static str>| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let mut req = Request::builder().method("POST").uri(uri);
+                if let Some(a) = auth {
+                    req = req.header(header::AUTHORIZATION, a);
+                }
+                app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+            }
+        };
+
+        assert_eq!(post("/api/control/standby", None).await.status(), StatusCode::UNAUTHORIZED);
+        assert!(state.mode_request.read().await.is_none());
+        assert_eq!(post("/api/control/standby", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(crate::engine::standby::ModeRequest::Standby));
+        assert_eq!(post("/api/control/resume", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(crate::engine::standby::ModeRequest::Resume));
+
+        // Standby reads differently from pause on /api/status.
+        let mut agent = state.agent.snapshot().await;
+        agent.status = crate::types::AgentStatus::Standby;
+        state.agent.sync(&mut agent).await;
+        let app = build_router(Arc::clone(&state));
+        let resp = app.oneshot(Request::builder().uri("/api/status").body(Body::empty()).unwrap()).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "🔵 STANDBY");
+        assert_eq!(json["execution_enabled"], false);
+        assert_eq!(json["pending_mode"], "resume");
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_forced_resume_endpoints() {
+        use crate::engine::standby::ModeRequest;
+        let state: AppState =
+            Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_api_token(Some("s3cret".into())));
+        let post = |uri: &'static str, auth: Option<&'static str>| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let mut req = Request::builder().method("POST").uri(uri);
+                if let Some(a) = auth {
+                    req = req.header(header::AUTHORIZATION, a);
+                }
+                app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+            }
+        };
+
+        assert_eq!(post("/api/control/pause", None).await.status(), StatusCode::UNAUTHORIZED);
+        assert!(state.mode_request.read().await.is_none());
+        assert_eq!(post("/api/control/pause", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(ModeRequest::Pause));
+        assert_eq!(post("/api/control/resume?force=true", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(ModeRequest::ForceResume));
+        assert_eq!(post("/api/control/resume?force=false", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(ModeRequest::Resume));
+
+        // The short paths are the same handlers, behind the same token.
+        assert_eq!(post("/api/pause", None).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(post("/api/pause", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(ModeRequest::Pause));
+        assert_eq!(post("/api/resume?force=true", Some("Bearer s3cret")).await.status(), StatusCode::ACCEPTED);
+        assert_eq!(*state.mode_request.read().await, Some(ModeRequest::ForceResume));
+
+        // A paused agent reports why, with execution disabled.
+        let mut agent = state.agent.snapshot().await;
+        crate::engine::pause::pause(&mut agent, "Drawdown halt: 41.0% from peak");
+        state.agent.sync(&mut agent).await;
+        let app = build_router(Arc::clone(&state));
+        let resp = app.oneshot(Request::builder().uri("/api/status").body(Body::empty()).unwrap()).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "🟡 PAUSED");
+        assert_eq!(json["execution_enabled"], false);
+        assert_eq!(json["pause_reason"], "Drawdown halt: 41.0% from peak");
+    }
+
+    #[tokio::test]
+    async fn test_deposit_endpoint_updates_shared_state() {
+        let state: AppState =
+            Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_api_token(Some("s3cret".into())));
+        let post = |body: &'static str, auth: Option<&'static str>| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let mut req = Request::builder()
+                    .method("POST")
+                    .uri("/api/control/deposit")
+                    .header(header::CONTENT_TYPE, "application/json");
+                if let Some(a) = auth {
+                    req = req.header(header::AUTHORIZATION, a);
+                }
+                app.oneshot(req.body(Body::from(body)).unwrap()).await.unwrap()
+            }
+        };
+
+        let deposit = r#"{"currency":"AUD","amount":25}"#;
+        assert_eq!(post(deposit, None).await.status(), StatusCode::UNAUTHORIZED);
+        let overdraw = r#"{"currency":"AUD","amount":-500}"#;
+        assert_eq!(post(overdraw, Some("Bearer s3cret")).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(post(deposit, Some("Bearer s3cret")).await.status(), StatusCode::OK);
+
+        // Readers see it at once; the engine gets it at its next sync.
+        assert_eq!(state.agent.read(|a| a.bankroll).await, dec!(125));
+        let mut working = AgentState::new(dec!(100));
+        assert_eq!(state.agent.sync(&mut working).await, 1);
+        assert_eq!(working.bankroll, dec!(125));
+    }
+
+    #[tokio::test]
+    async fn test_watchlist_endpoint_writes_files() {
+        use crate::engine::watchlist::{ListFiles, ListPaths};
+
+        let dir = std::env::temp_dir().join(format!("oracle-watchlist-api-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths = ListPaths::new(dir.join("watchlist.toml"), dir.join("blocklist.toml"));
+        let mut files = ListFiles::load(paths.clone()).unwrap();
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_api_token(Some("s3cret".into()))
+                .with_list_paths(paths.clone()),
+        );
+        let post = |body: &'static str, auth: Option<&'static str>| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let mut req = Request::builder()
+                    .method("POST")
+                    .uri("/api/control/watchlist")
+                    .header(header::CONTENT_TYPE, "application/json");
+                if let Some(a) = auth {
+                    req = req.header(header::AUTHORIZATION, a);
+                }
+                app.oneshot(req.body(Body::from(body)).unwrap()).await.unwrap()
+            }
+        };
+
+        let edit = r#"{"add_markets": ["manifold:abc"], "add_patterns": ["(?i)tweet"]}"#;
+        assert_eq!(post(edit, None).await.status(), StatusCode::UNAUTHORIZED);
+        assert!(!paths.watchlist.exists());
+
+        let resp = post(edit, Some("Bearer s3cret")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), 100_000).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["watch_added"], serde_json::json!(["manifold:abc"]));
+
+        // The running agent picks the edit up from the files.
+        let diff = files.reload_if_changed().unwrap();
+        assert_eq!(diff.block_added, vec!["(?i)tweet".to_string()]);
+        assert!(files.lists().is_watched("manifold", "abc"));
+
+        // Invalid entries are refused and nothing changes on disk.
+        let resp = post(r#"{"add_patterns": ["(broken"]}"#, Some("Bearer s3cret")).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(files.reload_if_changed(), None);
+        assert!(files.lists().is_blocked("Tweet count?"));
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_endpoint_adds_pattern() {
+        use crate::engine::watchlist::{ListFiles, ListPaths};
+
+        let dir = std::env::temp_dir().join(format!("oracle-blacklist-api-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths = ListPaths::new(dir.join("watchlist.toml"), dir.join("blocklist.toml"));
+        let mut files = ListFiles::load(paths.clone()).unwrap();
+        let state: AppState = Arc::new(
+            DashboardState::new(AgentState::new(dec!(100)))
+                .with_api_token(Some("s3cret".into()))
+                .with_list_paths(paths.clone()),
+        );
+        let post = |body: &'static str| {
+            let app = build_router(Arc::clone(&state));
+            async move {
+                let req = Request::builder()
+                    .method("POST")
+                    .uri("/api/blacklist")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::AUTHORIZATION, "Bearer s3cret");
+                app.oneshot(req.body(Body::from(body)).unwrap()).await.unwrap()
+            }
+        };
+
+        assert_eq!(post(r#"{"pattern": "Elon (tweets)"}"#).await.status(), StatusCode::OK);
+        assert_eq!(post(r#"{"pattern": "/^will i /"}"#).await.status(), StatusCode::OK);
+        assert_eq!(post(r#"{"pattern": "/(broken/"}"#).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(post(r#"{"pattern": " "}"#).await.status(), StatusCode::BAD_REQUEST);
+
+        // Substrings are stored escaped, so they match literally.
+        files.reload_if_changed().unwrap();
+        assert!(files.lists().is_blocked("Will ELON (TWEETS) top 100?"));
+        assert!(!files.lists().is_blocked("Will Elon tweets top 100?"));
+        assert!(files.lists().is_blocked("Will I get a job?"));
+    }
 }
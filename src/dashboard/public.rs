@@ -0,0 +1,565 @@
+//! Public (shareable) dashboard mode.
+//!
+//! A response-transformation layer over the normal dashboard API. The same
+//! routes and DTOs are served, but every JSON body is rewritten before it
+//! leaves the process so that no absolute currency figure is exposed:
+//!
+//! - bankroll values are indexed to 100 at session start,
+//! - bet sizes and P&L are expressed as percentages of bankroll,
+//! - absolute costs and venue balances are nulled out,
+//! - error messages and preflight details (which may quote amounts) are
+//!   hidden.
+//!
+//! Market questions, ids and links pass through untouched. Any non-read
+//! request is rejected with 403, and API paths without a known public view
+//! return 404 (default-deny, so new endpoints stay private until a view is
+//! added here). The Prometheus `/metrics` route is not served at all.
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use rust_decimal::prelude::*;
+use serde_json::{Map, Value};
+
+use super::routes::AppState;
+
+/// Upper bound on a dashboard response body we are willing to buffer.
+const MAX_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+/// Placeholder for free-text fields that may contain amounts.
+const HIDDEN_TEXT: &str = "[hidden in public mode]";
+
+// ---------------------------------------------------------------------------
+// Scale — the reference values used to normalise amounts
+// ---------------------------------------------------------------------------
+
+/// Reference values captured per request for normalisation.
+#[derive(Debug, Clone, Copy)]
+struct Scale {
+    /// AUD bankroll at session start (index base).
+    base_bankroll: f64,
+    /// Mana bankroll at session start (index base).
+    base_mana: f64,
+    /// Current AUD bankroll (denominator for AUD bet-size percentages).
+    bankroll: f64,
+    /// Current Mana bankroll (denominator for Mana bet-size percentages).
+    mana: f64,
+}
+
+impl Scale {
+    async fn capture(state: &AppState) -> Self {
+        let (bankroll, mana) = state
+            .agent
+            .read(|a| (a.bankroll.to_f64().unwrap_or(0.0), a.mana_bankroll.to_f64().unwrap_or(0.0)))
+            .await;
+        Self {
+            base_bankroll: state.session_start_bankroll,
+            base_mana: state.session_start_mana,
+            bankroll,
+            mana,
+        }
+    }
+
+    /// Denominator for a bet denominated in `currency`.
+    fn bankroll_for(&self, currency: Option<&str>) -> f64 {
+        match currency {
+            Some(c) if c.eq_ignore_ascii_case("mana") => self.mana,
+            _ => self.bankroll,
+        }
+    }
+}
+
+/// `value / base * 100`, rounded to 2 dp. `null` when the base is unusable.
+fn ratio(value: f64, base: f64) -> Value {
+    if base.abs() < f64::EPSILON || !value.is_finite() {
+        return Value::Null;
+    }
+    let pct = (value / base * 100.0 * 100.0).round() / 100.0;
+    serde_json::Number::from_f64(pct).map(Value::Number).unwrap_or(Value::Null)
+}
+
+/// Rewrite a numeric field in place. Missing or non-numeric fields are left alone.
+fn map_num(obj: &mut Map<String, Value>, key: &str, f: impl FnOnce(f64) -> Value) {
+    if let Some(v) = obj.get_mut(key) {
+        if let Some(n) = v.as_f64() {
+            *v = f(n);
+        }
+    }
+}
+
+/// Null out every number in a JSON tree.
+fn null_numbers(value: &mut Value) {
+    match value {
+        Value::Number(_) => *value = Value::Null,
+        Value::Array(items) => items.iter_mut().for_each(null_numbers),
+        Value::Object(obj) => obj.values_mut().for_each(null_numbers),
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Per-endpoint views
+// ---------------------------------------------------------------------------
+
+/// Public view of one API endpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PublicView {
+    Status,
+    Cycles,
+    BalanceHistory,
+    Trades,
+    Costs,
+    Metrics,
+    Progress,
+    Errors,
+    Positions,
+    ModelComparison,
+    MetricsHistory,
+}
+
+/// Entries of a bare array, or of a paged response's `items`.
+fn entries(value: &mut Value) -> impl Iterator<Item = &mut Value> {
+    let list = match value {
+        Value::Object(page) => page.get_mut("items"),
+        list => Some(list),
+    };
+    list.and_then(Value::as_array_mut).into_iter().flatten()
+}
+
+impl PublicView {
+    fn for_path(path: &str) -> Option<Self> {
+        match path {
+            "/api/status" => Some(Self::Status),
+            "/api/cycles" => Some(Self::Cycles),
+            "/api/balance-history" => Some(Self::BalanceHistory),
+            "/api/trades" => Some(Self::Trades),
+            "/api/costs" => Some(Self::Costs),
+            "/api/metrics" => Some(Self::Metrics),
+            "/api/progress" => Some(Self::Progress),
+            "/api/errors" => Some(Self::Errors),
+            "/api/positions" => Some(Self::Positions),
+            "/api/model-comparison" => Some(Self::ModelComparison),
+            "/api/metrics/history" => Some(Self::MetricsHistory),
+            _ => None,
+        }
+    }
+
+    fn apply(self, mut value: Value, s: &Scale) -> Value {
+        match self {
+            Self::Status => {
+                if let Some(obj) = value.as_object_mut() {
+                    map_num(obj, "bankroll", |v| ratio(v, s.base_bankroll));
+                    map_num(obj, "peak_bankroll", |v| ratio(v, s.base_bankroll));
+                    for key in ["cash_bankroll", "total_equity", "peak_equity", "unrealized_pnl"] {
+                        map_num(obj, key, |v| ratio(v, s.base_bankroll));
+                    }
+                    map_num(obj, "total_pnl", |v| ratio(v, s.base_bankroll));
+                    map_num(obj, "mana_bankroll", |v| ratio(v, s.base_mana));
+                    map_num(obj, "total_mana_pnl", |v| ratio(v, s.base_mana));
+                    map_num(obj, "today_pnl", |v| ratio(v, s.base_bankroll));
+                    map_num(obj, "today_mana_pnl", |v| ratio(v, s.base_mana));
+                    for key in ["total_api_costs", "total_ib_commissions", "total_costs", "open_bets_staked", "external_staked", "external_pnl"] {
+                        map_num(obj, key, |_| Value::Null);
+                    }
+                    obj.remove("diagnostics");
+                    // Venue balances are absolute; venue errors may quote them.
+                    let venues = obj
+                        .get_mut("last_resume_check")
+                        .and_then(|check| check.get_mut("venues"))
+                        .and_then(Value::as_array_mut);
+                    for venue in venues.into_iter().flatten().filter_map(Value::as_object_mut) {
+                        map_num(venue, "balance", |_| Value::Null);
+                        map_num(venue, "ledger_balance", |_| Value::Null);
+                        if venue.get("error").is_some_and(|e| !e.is_null()) {
+                            venue.insert("error".to_string(), Value::String(HIDDEN_TEXT.to_string()));
+                        }
+                    }
+                    // Preflight details quote balances and floors; which
+                    // checks passed is kept.
+                    let checks = obj
+                        .get_mut("preflight")
+                        .and_then(Value::as_array_mut)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|report| report.get_mut("checks").and_then(Value::as_array_mut))
+                        .flatten();
+                    for check in checks.filter_map(Value::as_object_mut) {
+                        check.insert("detail".to_string(), Value::String(HIDDEN_TEXT.to_string()));
+                    }
+                    obj.insert("public_mode".to_string(), Value::Bool(true));
+                }
+            }
+            Self::Cycles => {
+                for entry in entries(&mut value) {
+                    if let Some(obj) = entry.as_object_mut() {
+                        map_num(obj, "bankroll_after", |v| ratio(v, s.base_bankroll));
+                        map_num(obj, "cycle_cost", |_| Value::Null);
+                    }
+                }
+            }
+            Self::BalanceHistory => {
+                for point in value.as_array_mut().into_iter().flatten() {
+                    if let Some(obj) = point.as_object_mut() {
+                        for key in ["bankroll", "cash", "equity"] {
+                            map_num(obj, key, |v| ratio(v, s.base_bankroll));
+                        }
+                        map_num(obj, "mana_bankroll", |v| ratio(v, s.base_mana));
+                    }
+                }
+            }
+            Self::Trades | Self::Positions => {
+                for trade in entries(&mut value) {
+                    if let Some(obj) = trade.as_object_mut() {
+                        let denom = s.bankroll_for(obj.get("currency").and_then(Value::as_str));
+                        map_num(obj, "amount", |v| ratio(v, denom));
+                        map_num(obj, "final_pnl", |v| ratio(v, denom));
+                        map_num(obj, "fees", |_| Value::Null);
+                    }
+                }
+            }
+            Self::Costs => null_numbers(&mut value),
+            Self::Metrics => {
+                if let Some(obj) = value.as_object_mut() {
+                    map_num(obj, "total_pnl", |v| ratio(v, s.base_bankroll));
+                }
+            }
+            Self::MetricsHistory => {
+                let points = value.get_mut("points").and_then(Value::as_array_mut);
+                for point in points.into_iter().flatten() {
+                    if let Some(obj) = point.as_object_mut() {
+                        map_num(obj, "bankroll_close", |v| ratio(v, s.base_bankroll));
+                        map_num(obj, "realized_pnl", |v| ratio(v, s.base_bankroll));
+                        map_num(obj, "mana_bankroll_close", |v| ratio(v, s.base_mana));
+                        map_num(obj, "realized_mana_pnl", |v| ratio(v, s.base_mana));
+                        for key in ["llm_cost", "data_cost", "commission_cost", "other_cost", "exposure_hwm"] {
+                            map_num(obj, key, |_| Value::Null);
+                        }
+                    }
+                }
+            }
+            // Ratios and latencies only — nothing denominated in currency.
+            Self::Progress | Self::ModelComparison => {}
+            Self::Errors => {
+                for entry in value.as_array_mut().into_iter().flatten() {
+                    if let Some(obj) = entry.as_object_mut() {
+                        if obj.contains_key("error") {
+                            obj.insert("error".to_string(), Value::String(HIDDEN_TEXT.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+        value
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Middleware
+// ---------------------------------------------------------------------------
+
+/// Axum middleware that makes a router safe to expose publicly.
+pub async fn public_layer(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    // Read-only: control endpoints are disabled regardless of any auth.
+    if !matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return (StatusCode::FORBIDDEN, "Public dashboard is read-only").into_response();
+    }
+
+    let path = req.uri().path().to_string();
+    // Raw bankroll and cost series: not published at all.
+    if path == "/metrics" {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if !path.starts_with("/api/") {
+        return next.run(req).await;
+    }
+    let Some(view) = PublicView::for_path(&path) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let resp = next.run(req).await;
+    if !resp.status().is_success() {
+        return resp;
+    }
+
+    let (parts, body) = resp.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(b) => b,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let value: Value = match serde_json::from_slice(&bytes) {
+        Ok(v) => v,
+        // Never pass an unparseable body through unredacted.
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let scale = Scale::capture(&state).await;
+    let mut out = Json(view.apply(value, &scale)).into_response();
+    *out.status_mut() = parts.status;
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dashboard::build_public_router;
+    use crate::dashboard::routes::{CycleLogEntry, DashboardState, ErrorLogEntry, TradeLogEntry};
+    use crate::engine::standby::{ResumeCheck, VenueCheck};
+    use crate::platforms::preflight::{PreflightLimits, PreflightReport};
+    use crate::storage::backend::{BalanceSample, Storage};
+    use crate::storage::sqlite::SqliteStorage;
+    use crate::types::{AgentState, Side, TradeReceipt};
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// Distinctive real values that must never appear in public responses.
+    const REAL_VALUES: &[f64] = &[
+        1234.56, 1500.75, 987.65, 4321.0, 17.25, 3.33, 2.22, 42.5, 77.7, 0.37, 5.55, 1111.11, 2468.02, 1357.91,
+        864.2, 250.5,
+    ];
+
+    async fn seeded_state() -> AppState {
+        let mut agent = AgentState::new(dec!(1500.75));
+        agent.mana_bankroll = dec!(4321.0);
+        let storage = Arc::new(SqliteStorage::open_in_memory().await.unwrap());
+        let state = Arc::new(DashboardState::new(agent.clone()).with_storage(storage.clone()));
+
+        agent.bankroll = dec!(1234.56);
+        agent.peak_bankroll = dec!(1500.75);
+        agent.total_pnl = dec!(17.25);
+        agent.total_api_costs = dec!(3.33);
+        agent.total_llm_costs = dec!(2.22);
+        agent.total_data_costs = dec!(1.11);
+        agent.total_ib_commissions = dec!(0.37);
+        agent.mana_bankroll = dec!(987.65);
+        agent.total_mana_pnl = dec!(77.7);
+        agent.open_bets.push(TradeReceipt {
+            order_id: "o1".into(),
+            market_id: "m1".into(),
+            platform: "manifold".into(),
+            side: Side::Yes,
+            amount: dec!(42.5),
+            fill_price: dec!(0.61),
+            fees: dec!(5.55),
+            timestamp: chrono::Utc::now(),
+            currency: "Mana".into(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        });
+        state.agent.sync(&mut agent).await;
+
+        state.cycle_log.write().await.push(CycleLogEntry {
+            cycle_number: 1,
+            timestamp: "2026-02-21T12:00:00Z".into(),
+            markets_scanned: 10,
+            edges_found: 2,
+            bets_placed: 1,
+            bets_failed: 0,
+            cycle_cost: 5.55,
+            bankroll_after: 1111.11,
+            status: "ALIVE".into(),
+            events: Vec::new(),
+        });
+        storage
+            .append_balance(&BalanceSample {
+                at: chrono::Utc::now(),
+                cash: dec!(1111.11),
+                equity: dec!(1234.56),
+                drawdown: dec!(0.18),
+                bankroll: dec!(1234.56),
+                mana_bankroll: dec!(987.65),
+            })
+            .await
+            .unwrap();
+        state.recent_trades.write().await.push(TradeLogEntry {
+            timestamp: "2026-02-21T12:00:00Z".into(),
+            market_id: "m1".into(),
+            platform: "manifold".into(),
+            side: "YES".into(),
+            amount: 42.5,
+            currency: "Mana".into(),
+            edge_pct: 12.0,
+            confidence: 0.8,
+            category: None,
+            close_reason: None,
+            final_pnl: Some(77.7),
+        });
+        *state.last_resume_check.write().await = Some(ResumeCheck {
+            venues: vec![VenueCheck {
+                platform: "manifold".into(),
+                balance: Some(dec!(2468.02)),
+                ledger_balance: Some(dec!(1357.91)),
+                ledger_only: Vec::new(),
+                venue_only: Vec::new(),
+                error: Some("balance 2468.02 does not match ledger 1357.91".into()),
+            }],
+        });
+        let limits = PreflightLimits { min_balance: dec!(250.5), ..PreflightLimits::default() };
+        let mut funded = PreflightReport::new("betfair");
+        funded.balance(Ok(dec!(864.2)), &limits);
+        let mut short = PreflightReport::new("polymarket");
+        short.check_floor(dec!(2.22), &limits);
+        *state.preflight.write().await = vec![funded, short];
+        state.error_log.write().await.push(ErrorLogEntry {
+            timestamp: "2026-02-21T12:00:00Z".into(),
+            cycle_number: 1,
+            error: "Insufficient balance: need $1234.56".into(),
+        });
+        state
+    }
+
+    fn assert_no_real_values(value: &Value, path: &str) {
+        match value {
+            Value::Number(n) => {
+                let v = n.as_f64().unwrap();
+                for real in REAL_VALUES {
+                    assert!((v - real).abs() > 1e-9, "{path}: leaked real value {real}");
+                }
+            }
+            Value::String(s) => {
+                for real in REAL_VALUES {
+                    assert!(!s.contains(&real.to_string()), "{path}: leaked {real} in text");
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|i| assert_no_real_values(i, path)),
+            Value::Object(obj) => obj.values().for_each(|i| assert_no_real_values(i, path)),
+            _ => {}
+        }
+    }
+
+    async fn get_json(state: AppState, uri: &str) -> Value {
+        let resp = build_public_router(state)
+            .oneshot(HttpRequest::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "{uri}");
+        let body = axum::body::to_bytes(resp.into_body(), MAX_BODY_BYTES).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_no_absolute_figures_in_any_public_response() {
+        let state = seeded_state().await;
+        for uri in [
+            "/api/status", "/api/cycles", "/api/balance-history", "/api/trades",
+            "/api/costs", "/api/metrics", "/api/progress", "/api/errors", "/api/positions",
+        ] {
+            let json = get_json(Arc::clone(&state), uri).await;
+            assert_no_real_values(&json, uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_history_normalised() {
+        use crate::storage::metrics::{CycleMetrics, MetricsStore};
+
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        store
+            .record_cycle(&CycleMetrics {
+                timestamp: chrono::Utc::now() - chrono::Duration::minutes(5),
+                bankroll: 1234.56,
+                mana_bankroll: 987.65,
+                realized_pnl: 17.25,
+                realized_mana_pnl: 77.7,
+                llm_cost: 2.22,
+                data_cost: 1.11,
+                commission_cost: 0.37,
+                other_cost: 5.55,
+                bets_placed: 1,
+                bets_failed: 0,
+                markets_scanned: 10,
+                exposure: 42.5,
+            })
+            .await
+            .unwrap();
+        let mut agent = AgentState::new(dec!(1500.75));
+        agent.mana_bankroll = dec!(4321.0);
+        let state = Arc::new(DashboardState::new(agent).with_metrics(store));
+
+        for uri in ["/api/metrics/history?hours=1", "/api/metrics/history?hours=720"] {
+            let json = get_json(Arc::clone(&state), uri).await;
+            assert_eq!(json["points"].as_array().unwrap().len(), 1, "{uri}");
+            assert_no_real_values(&json, uri);
+            // 1234.56 / 1500.75 * 100 = 82.26
+            assert!((json["points"][0]["bankroll_close"].as_f64().unwrap() - 82.26).abs() < 0.01);
+            assert!(json["points"][0]["llm_cost"].is_null());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bankroll_indexed_to_session_start() {
+        let state = seeded_state().await;
+        let json = get_json(state, "/api/status").await;
+        // 1234.56 / 1500.75 * 100 = 82.26
+        assert!((json["bankroll"].as_f64().unwrap() - 82.26).abs() < 0.01);
+        assert!((json["peak_bankroll"].as_f64().unwrap() - 100.0).abs() < 1e-9);
+        assert!(json["total_costs"].is_null());
+        assert_eq!(json["public_mode"], Value::Bool(true));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_details_hidden_outcomes_kept() {
+        let json = get_json(seeded_state().await, "/api/status").await;
+        let checks: Vec<&Value> =
+            json["preflight"].as_array().unwrap().iter().flat_map(|r| r["checks"].as_array().unwrap()).collect();
+        assert_eq!(checks.len(), 3);
+        assert!(checks.iter().all(|c| c["detail"] == HIDDEN_TEXT));
+        let oks: Vec<bool> = checks.iter().map(|c| c["ok"].as_bool().unwrap()).collect();
+        assert_eq!(oks, [true, true, false]);
+    }
+
+    #[tokio::test]
+    async fn test_trade_amount_as_percentage_and_question_fields_intact() {
+        let state = seeded_state().await;
+        let json = get_json(state, "/api/trades").await;
+        let trade = &json["items"][0];
+        // 42.5 Mana of a 987.65 Mana bankroll = 4.30%
+        assert!((trade["amount"].as_f64().unwrap() - 4.30).abs() < 0.01);
+        assert_eq!(trade["market_id"], "m1");
+        assert_eq!(trade["platform"], "manifold");
+    }
+
+    #[tokio::test]
+    async fn test_non_get_requests_forbidden() {
+        let state = seeded_state().await;
+        let resp = build_public_router(state)
+            .oneshot(
+                HttpRequest::builder()
+                    .method(Method::POST)
+                    .uri("/api/status")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_unknown_api_path_has_no_view() {
+        assert!(PublicView::for_path("/api/something-new").is_none());
+        assert_eq!(PublicView::for_path("/api/status"), Some(PublicView::Status));
+    }
+
+    #[test]
+    fn test_ratio_zero_base_is_null() {
+        assert!(ratio(10.0, 0.0).is_null());
+        assert_eq!(ratio(50.0, 200.0), serde_json::json!(25.0));
+    }
+}
@@ -1,16 +1,49 @@
 //! Dashboard API route handlers.
 //!
-//! All endpoints return JSON. State is shared via `Arc<DashboardState>`.
+//! All endpoints return JSON, except `/api/events`, which streams
+//! [`LiveEvent`]s as server-sent events. State is shared via
+//! `Arc<DashboardState>`.
 //! Response structs keep f64 for JSON API responses (display-only).
 //! AgentState fields are Decimal — we convert to f64 in the handlers.
 
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::{header, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::{Stream, StreamExt, TryStreamExt};
 use rust_decimal::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock, Semaphore};
 
-use crate::types::{AgentState, TradeReceipt};
+use super::budget::{self, ResponseCache, DEFAULT_EXPENSIVE_PERMITS};
+
+use crate::backtest::calibration::CalibrationReport;
+use crate::backtest::sensitivity::{self, SensitivityParams, SensitivityReport};
+use crate::config::EffectiveConfig;
+use crate::diagnostics::{self as process, CollectionSize, DiagnosticsSample, SizedStore};
+use crate::engine::accountant;
+use crate::error_buffer::{CapturedError, RecentErrors};
+use crate::engine::daily::{self, DayPnl};
+use crate::engine::scanner::ScanSummary;
+use crate::engine::standby::{ModeRequest, ResumeCheck};
+use crate::engine::state::{SharedState, StateChange};
+use crate::platforms::preflight::PreflightReport;
+use crate::engine::tags::{self, TagStats};
+use crate::engine::watchlist::{pattern_regex, ListDiff, ListEdit, ListPaths};
+use crate::llm::shadow::ComparisonReport;
+use crate::prometheus::{self, Registry};
+use crate::storage::archive::{Archive, MarketLifecycle};
+use crate::storage::backend::{downsample, BalanceSample, CategoryPnl, Resolution, Storage, TradeQuery, TradeRecord};
+use crate::storage::journal::{DecisionJournal, JournalQuery, RejectionSummary};
+use crate::strategy::links::{self, link_key, LinkSet, LinkSuggestion, MarketLinks};
+use crate::strategy::performance::PerformanceReport;
+use crate::storage::metrics::{MetricsStore, HOUR_SECS};
+use crate::types::{AgentState, CycleReport, MarketCategory, TradeReceipt};
 
 // ---------------------------------------------------------------------------
 // Progress tracking types
@@ -38,6 +71,20 @@ pub struct ErrorLogEntry {
     pub error: String,
 }
 
+/// Something the agent did, pushed to `/api/events` subscribers as it
+/// happens. Serialises with a `kind` tag, e.g.
+/// `{"kind":"phase","progress":{"state":"scanning"}}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LiveEvent {
+    /// The cycle moved to another phase.
+    Phase { progress: EvaluationProgress },
+    /// A bet was placed or a position closed.
+    Trade(TradeLogEntry),
+    /// A cycle failed.
+    Error(ErrorLogEntry),
+}
+
 #[derive(Debug, Serialize)]
 pub struct ProgressResponse {
     pub progress: EvaluationProgress,
@@ -48,39 +95,419 @@ pub struct ProgressResponse {
 // Shared state
 // ---------------------------------------------------------------------------
 
+/// Entries kept in each in-memory dashboard log.
+pub const MAX_CYCLE_LOG: usize = 100;
+pub const MAX_RECENT_TRADES: usize = 200;
+pub const MAX_ERROR_LOG: usize = 50;
+
+/// Events an `/api/events` subscriber may fall behind by before the
+/// oldest are skipped.
+pub const EVENT_BUFFER: usize = 256;
+
 /// Shared state accessible by all route handlers.
 pub struct DashboardState {
-    pub agent: RwLock<AgentState>,
+    /// The agent state itself, shared with the engine (not a copy).
+    pub agent: SharedState,
     pub cycle_log: RwLock<Vec<CycleLogEntry>>,
-    pub balance_history: RwLock<Vec<BalancePoint>>,
     pub recent_trades: RwLock<Vec<TradeLogEntry>>,
     pub progress: RwLock<EvaluationProgress>,
     pub error_log: RwLock<Vec<ErrorLogEntry>>,
+    /// Phase changes, trades and errors as they happen, for `/api/events`.
+    /// Written through [`Self::set_progress`], [`Self::push_trades`] and
+    /// [`Self::push_error`].
+    pub events: broadcast::Sender<LiveEvent>,
     pub active_model: RwLock<String>,
     pub trading_mode: RwLock<String>,
+    /// AUD bankroll when this process started — the base (= 100) for the
+    /// public-mode index.
+    pub session_start_bankroll: f64,
+    /// Mana bankroll when this process started.
+    pub session_start_mana: f64,
+    /// Latest primary-vs-shadow model comparison (None when no shadow model).
+    pub model_comparison: RwLock<Option<ComparisonReport>>,
+    /// Long-horizon metrics history (None when the database is unavailable).
+    pub metrics: Option<MetricsStore>,
+    /// Per-category edge thresholds in effect, for `/api/risk`.
+    pub edge_thresholds: RwLock<Vec<CategoryThresholdView>>,
+    /// Resolved configuration served by `/api/config`.
+    pub effective_config: EffectiveConfig,
+    /// Bearer token for privileged endpoints; they are refused when `None`.
+    pub api_token: Option<String>,
+    /// Platforms the operator asked to wake from scan hibernation; drained
+    /// by the agent loop before each cycle.
+    pub wake_requests: RwLock<Vec<String>>,
+    /// Short-TTL cache for read endpoints (see [`super::budget`]).
+    pub response_cache: ResponseCache,
+    /// Permits shared by expensive routes; exhausted means 429.
+    pub expensive_permits: Arc<Semaphore>,
+    /// Lifecycle archive read through by `/api/explain` once a market's
+    /// hot rows are pruned.
+    pub archive: Option<Archive>,
+    /// Configured strategy parameters `/api/sensitivity` varies around.
+    pub sensitivity_base: Option<SensitivityParams>,
+    /// Declared market links, for `/api/links` and `/api/explain`.
+    pub market_links: MarketLinks,
+    /// Candidate links from the latest scan, awaiting operator review.
+    pub link_suggestions: RwLock<Vec<LinkSuggestion>>,
+    /// Latest memory / collection-size sample, for `/api/status`.
+    pub diagnostics: RwLock<Option<DiagnosticsSample>>,
+    /// Standby / resume request awaiting the next tick; the latest wins.
+    pub mode_request: RwLock<Option<ModeRequest>>,
+    /// Venue checks from the most recent resume attempt.
+    pub last_resume_check: RwLock<Option<ResumeCheck>>,
+    /// Watchlist / blocklist files `/api/control/watchlist` edits; the lock
+    /// serialises writers.
+    pub list_paths: Option<std::sync::Mutex<ListPaths>>,
+    /// Latest preflight report per executable venue.
+    pub preflight: RwLock<Vec<PreflightReport>>,
+    /// Scores of the LLM's resolved estimates, for `/api/calibration`.
+    pub estimate_calibration: RwLock<Option<CalibrationReport>>,
+    /// Category composition of the latest scan, for `/api/status`.
+    pub scan_summary: RwLock<Option<ScanSummary>>,
+    /// Decision journal read by `/api/decisions` (None when disabled).
+    pub decision_journal: Option<DecisionJournal>,
+    /// Telemetry scraped at `/metrics`, shared with the engine.
+    pub prometheus: Arc<Registry>,
+    /// Storage backend read by `/api/trades/history` and `/api/pnl/categories`.
+    pub storage: Option<Arc<dyn Storage>>,
+    /// Error-level log events captured by the subscriber, for `/api/diagnostics`.
+    pub recent_errors: RecentErrors,
+    /// Report of the last cycle this process ran.
+    pub last_cycle: RwLock<Option<accountant::CycleReport>>,
+    /// Latest successful fetch of each platform.
+    pub platform_scans: RwLock<BTreeMap<String, PlatformScan>>,
+    /// Enrichment providers and whether their API keys are present.
+    pub enrichment: Vec<EnrichmentAvailability>,
+    /// When this process started serving.
+    pub started_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl DashboardState {
-    pub fn new(initial_state: AgentState) -> Self {
-        let initial_balance = initial_state.bankroll.to_f64().unwrap_or(0.0);
-        let initial_mana = initial_state.mana_bankroll.to_f64().unwrap_or(0.0);
+    pub fn new(agent: impl Into<SharedState>) -> Self {
+        let agent = agent.into();
+        let (initial_balance, initial_mana) = agent
+            .try_read(|s| (s.bankroll.to_f64().unwrap_or(0.0), s.mana_bankroll.to_f64().unwrap_or(0.0)))
+            .unwrap_or_default();
         Self {
-            agent: RwLock::new(initial_state),
+            agent,
             cycle_log: RwLock::new(Vec::new()),
-            balance_history: RwLock::new(vec![BalancePoint {
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                bankroll: initial_balance,
-                mana_bankroll: initial_mana,
-            }]),
             recent_trades: RwLock::new(Vec::new()),
             progress: RwLock::new(EvaluationProgress::Idle),
             error_log: RwLock::new(Vec::new()),
+            events: broadcast::channel(EVENT_BUFFER).0,
             active_model: RwLock::new(String::new()),
             trading_mode: RwLock::new("dry".to_string()),
+            session_start_bankroll: initial_balance,
+            session_start_mana: initial_mana,
+            model_comparison: RwLock::new(None),
+            metrics: None,
+            edge_thresholds: RwLock::new(Vec::new()),
+            effective_config: EffectiveConfig::default(),
+            api_token: None,
+            wake_requests: RwLock::new(Vec::new()),
+            response_cache: ResponseCache::new(true),
+            expensive_permits: Arc::new(Semaphore::new(DEFAULT_EXPENSIVE_PERMITS)),
+            archive: None,
+            sensitivity_base: None,
+            market_links: MarketLinks::default(),
+            link_suggestions: RwLock::new(Vec::new()),
+            diagnostics: RwLock::new(None),
+            mode_request: RwLock::new(None),
+            last_resume_check: RwLock::new(None),
+            list_paths: None,
+            preflight: RwLock::new(Vec::new()),
+            estimate_calibration: RwLock::new(None),
+            scan_summary: RwLock::new(None),
+            decision_journal: None,
+            prometheus: Arc::new(Registry::new()),
+            storage: None,
+            recent_errors: RecentErrors::default(),
+            last_cycle: RwLock::new(None),
+            platform_scans: RwLock::new(BTreeMap::new()),
+            enrichment: Vec::new(),
+            started_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Attach the metrics history store used by `/api/metrics/history`.
+    pub fn with_metrics(mut self, store: MetricsStore) -> Self {
+        self.metrics = Some(store);
+        self
+    }
+
+    /// Attach the market archive `/api/explain` falls back to.
+    pub fn with_archive(mut self, archive: Archive) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Attach the decision journal served by `/api/decisions`.
+    pub fn with_decision_journal(mut self, journal: DecisionJournal) -> Self {
+        self.decision_journal = Some(journal);
+        self
+    }
+
+    /// Attach the storage backend queried for trade history and P&L.
+    pub fn with_storage(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Attach the baseline parameters for `/api/sensitivity`.
+    pub fn with_sensitivity_base(mut self, base: SensitivityParams) -> Self {
+        self.sensitivity_base = Some(base);
+        self
+    }
+
+    /// Attach the declared market links.
+    pub fn with_links(mut self, links: MarketLinks) -> Self {
+        self.market_links = links;
+        self
+    }
+
+    /// Attach the watchlist / blocklist files `/api/control/watchlist` edits.
+    pub fn with_list_paths(mut self, paths: ListPaths) -> Self {
+        self.list_paths = Some(std::sync::Mutex::new(paths));
+        self
+    }
+
+    /// Serve the error events captured into `errors`.
+    pub fn with_recent_errors(mut self, errors: RecentErrors) -> Self {
+        self.recent_errors = errors;
+        self
+    }
+
+    /// Report which enrichment providers have their API keys.
+    pub fn with_enrichment(mut self, enrichment: Vec<EnrichmentAvailability>) -> Self {
+        self.enrichment = enrichment;
+        self
+    }
+
+    /// Attach the resolved configuration served by `/api/config`.
+    pub fn with_effective_config(mut self, config: EffectiveConfig) -> Self {
+        self.effective_config = config;
+        self
+    }
+
+    /// Enable or disable the read-endpoint cache and set how many expensive
+    /// requests may run at once.
+    pub fn with_request_budget(mut self, cache: bool, max_expensive: usize) -> Self {
+        self.response_cache = ResponseCache::new(cache);
+        self.expensive_permits = Arc::new(Semaphore::new(max_expensive));
+        self
+    }
+
+    /// Require `token` as a bearer token on privileged endpoints.
+    pub fn with_api_token(mut self, token: Option<String>) -> Self {
+        self.api_token = token.filter(|t| !t.is_empty());
+        self
+    }
+
+    /// Seed the cycle log from the on-disk cycle history (oldest first), so
+    /// it survives a restart.
+    pub fn with_cycle_history(mut self, history: &[CycleReport]) -> Self {
+        let cycle_log = self.cycle_log.get_mut();
+        let start = history.len().saturating_sub(MAX_CYCLE_LOG);
+        cycle_log.extend(history[start..].iter().map(|r| CycleLogEntry {
+            cycle_number: r.cycle_number,
+            timestamp: r.timestamp.to_rfc3339(),
+            markets_scanned: r.markets_scanned as usize,
+            edges_found: r.edges_found as usize,
+            bets_placed: r.bets_placed as usize,
+            bets_failed: r.bets_failed as usize,
+            cycle_cost: r.cycle_cost.to_f64().unwrap_or(0.0),
+            bankroll_after: r.bankroll_after.to_f64().unwrap_or(0.0),
+            status: r.status.map(|s| s.to_string()).unwrap_or_default(),
+            events: Vec::new(),
+        }));
+        self
+    }
+
+    /// Keep `summary` as the latest scan, stamping each platform it fetched
+    /// with `at`.
+    pub async fn record_scan(&self, summary: ScanSummary, at: chrono::DateTime<chrono::Utc>) {
+        let mut scans = self.platform_scans.write().await;
+        for (platform, &markets) in &summary.fetched {
+            scans.insert(platform.clone(), PlatformScan { scanned_at: at, markets });
+        }
+        drop(scans);
+        *self.scan_summary.write().await = Some(summary);
+    }
+
+    /// Enter cycle phase `progress`.
+    pub async fn set_progress(&self, progress: EvaluationProgress) {
+        *self.progress.write().await = progress.clone();
+        // No subscribers is not an error.
+        let _ = self.events.send(LiveEvent::Phase { progress });
+    }
+
+    /// Append to the recent-trades log, dropping the oldest beyond
+    /// [`MAX_RECENT_TRADES`].
+    pub async fn push_trades(&self, entries: impl IntoIterator<Item = TradeLogEntry>) {
+        let mut trades = self.recent_trades.write().await;
+        for entry in entries {
+            let _ = self.events.send(LiveEvent::Trade(entry.clone()));
+            trades.push(entry);
+        }
+        let excess = trades.len().saturating_sub(MAX_RECENT_TRADES);
+        trades.drain(..excess);
+    }
+
+    /// Append to the error log, dropping the oldest beyond [`MAX_ERROR_LOG`].
+    pub async fn push_error(&self, entry: ErrorLogEntry) {
+        let mut log = self.error_log.write().await;
+        let _ = self.events.send(LiveEvent::Error(entry.clone()));
+        log.push(entry);
+        let excess = log.len().saturating_sub(MAX_ERROR_LOG);
+        log.drain(..excess);
+    }
+
+    /// A page of the recent-trades log; only the matching trades on the
+    /// page are copied.
+    pub async fn trades_page(&self, filter: &TradeFilter, paging: &Paging<chrono::DateTime<chrono::Utc>>) -> Page<TradeLogEntry> {
+        let trades = self.recent_trades.read().await;
+        let matches: Vec<_> = trades.iter().filter(|t| filter.matches(t)).collect();
+        Page::of(&matches, paging, |t| entry_time(&t.timestamp), |t| t.timestamp.clone())
+    }
+
+    /// A page of the cycle log, from cycles numbered above `since_cycle`.
+    pub async fn cycles_page(&self, since_cycle: Option<u64>, paging: &Paging<u64>) -> Page<CycleLogEntry> {
+        let log = self.cycle_log.read().await;
+        let matches: Vec<_> = log.iter().filter(|c| since_cycle.is_none_or(|n| c.cycle_number > n)).collect();
+        Page::of(&matches, paging, |c| Some(c.cycle_number), |c| c.cycle_number.to_string())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Paging
+// ---------------------------------------------------------------------------
+
+/// Entries `/api/trades` and `/api/cycles` return without a `limit`.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+/// Most entries one page returns.
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// One page of a dashboard log. Pages run back from the newest entry;
+/// within a page entries are oldest first, as in the log.
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Entries matching the filters, across all pages.
+    pub total: usize,
+    pub returned: usize,
+    /// `before` for the next, older page; `null` on the last.
+    pub next_cursor: Option<String>,
+}
+
+/// `limit` entries, ending `offset` entries before the first entry at or
+/// after `before` (the end of the log without one).
+#[derive(Debug, Clone)]
+pub struct Paging<K> {
+    pub limit: usize,
+    pub offset: usize,
+    pub before: Option<K>,
+}
+
+impl<T: Clone> Page<T> {
+    /// Page through `matches`, in ascending `key` order; `cursor` renders
+    /// an entry's key as the `before` value that pages past it.
+    fn of<K: PartialOrd>(
+        matches: &[&T],
+        paging: &Paging<K>,
+        key: impl Fn(&T) -> Option<K>,
+        cursor: impl Fn(&T) -> String,
+    ) -> Self {
+        let end = match &paging.before {
+            Some(before) => matches.partition_point(|e| key(e).is_none_or(|k| k < *before)),
+            None => matches.len(),
+        };
+        let end = end.saturating_sub(paging.offset);
+        let start = end.saturating_sub(paging.limit);
+        let items: Vec<T> = matches[start..end].iter().map(|&e| e.clone()).collect();
+        Self {
+            total: matches.len(),
+            returned: items.len(),
+            next_cursor: (start > 0).then(|| cursor(matches[start])),
+            items,
         }
     }
 }
 
+/// Which trades `/api/trades` returns.
+#[derive(Debug, Clone, Default)]
+pub struct TradeFilter {
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range, exclusive.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub category: Option<MarketCategory>,
+    /// Venue name; its auto-exit closes (`"<platform>-closed"`) match too.
+    pub platform: Option<String>,
+}
+
+impl TradeFilter {
+    pub fn matches(&self, trade: &TradeLogEntry) -> bool {
+        if self.from.is_some() || self.to.is_some() {
+            let Some(at) = entry_time(&trade.timestamp) else { return false };
+            if self.from.is_some_and(|from| at < from) || self.to.is_some_and(|to| at >= to) {
+                return false;
+            }
+        }
+        if self.category.is_some() && trade.category != self.category {
+            return false;
+        }
+        self.platform.as_deref().is_none_or(|platform| {
+            trade.platform == platform || trade.platform.strip_suffix("-closed") == Some(platform)
+        })
+    }
+}
+
+fn entry_time(timestamp: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(timestamp).ok().map(|t| t.with_timezone(&chrono::Utc))
+}
+
+/// A rejected query: 400 with a `{"error": "..."}` body.
+#[derive(Debug)]
+pub struct BadRequest(pub String);
+
+impl IntoResponse for BadRequest {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": self.0 }))).into_response()
+    }
+}
+
+impl From<QueryRejection> for BadRequest {
+    fn from(rejection: QueryRejection) -> Self {
+        Self(rejection.body_text())
+    }
+}
+
+fn page_size(limit: Option<usize>) -> Result<usize, BadRequest> {
+    match limit.unwrap_or(DEFAULT_PAGE_SIZE) {
+        limit @ 1..=MAX_PAGE_SIZE => Ok(limit),
+        limit => Err(BadRequest(format!("limit must be between 1 and {MAX_PAGE_SIZE}, got {limit}"))),
+    }
+}
+
+impl SizedStore for DashboardState {
+    /// Lock-free read: a log held by a writer at sample time is skipped.
+    fn collection_sizes(&self) -> Vec<CollectionSize> {
+        fn sized<T>(name: &str, log: &RwLock<Vec<T>>, bound: Option<usize>) -> Option<CollectionSize> {
+            log.try_read().ok().map(|l| CollectionSize::new(name, l.len(), bound))
+        }
+        [
+            sized("dashboard.cycle_log", &self.cycle_log, Some(MAX_CYCLE_LOG)),
+            sized("dashboard.recent_trades", &self.recent_trades, Some(MAX_RECENT_TRADES)),
+            sized("dashboard.error_log", &self.error_log, Some(MAX_ERROR_LOG)),
+            sized("dashboard.link_suggestions", &self.link_suggestions, Some(links::MAX_SUGGESTIONS)),
+            sized("dashboard.wake_requests", &self.wake_requests, None),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(CollectionSize::new("dashboard.response_cache", self.response_cache.len(), None)))
+        .collect()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Response types (f64 for JSON serialization — display only)
 // ---------------------------------------------------------------------------
@@ -88,10 +515,26 @@ impl DashboardState {
 #[derive(Debug, Clone, Serialize)]
 pub struct StatusResponse {
     pub status: String,
+    /// False in standby (and pause): cycles run but nothing is placed.
+    pub execution_enabled: bool,
+    /// Standby / pause / resume request not yet applied.
+    pub pending_mode: Option<ModeRequest>,
+    /// Why the agent is paused, while it is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause_reason: Option<String>,
+    /// Venue checks from the most recent resume attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_resume_check: Option<ResumeCheck>,
     pub trading_mode: String,
     /// AUD operational budget (API costs only). Not affected by Manifold Mana trades.
     pub bankroll: f64,
     pub peak_bankroll: f64,
+    /// AUD bankroll less the stakes of open AUD bets.
+    pub cash_bankroll: f64,
+    /// AUD bankroll plus open AUD bets' unrealized P&L at the last mark.
+    pub total_equity: f64,
+    pub peak_equity: f64,
+    pub unrealized_pnl: f64,
     /// AUD P&L from live real-money trades (Betfair). Zero in paper mode.
     pub total_pnl: f64,
     pub cycle_count: u64,
@@ -107,6 +550,11 @@ pub struct StatusResponse {
     pub mana_bankroll: f64,
     /// Net Mana profit/loss from Manifold paper trades.
     pub total_mana_pnl: f64,
+    /// AUD P&L since the last end-of-day snapshot (realised, mark-to-market
+    /// and costs).
+    pub today_pnl: f64,
+    /// Mana P&L since the last end-of-day snapshot.
+    pub today_mana_pnl: f64,
     /// Mana win rate (paper trades only).
     pub mana_win_rate: f64,
     /// Number of bets currently open (persisted open_bets list length).
@@ -116,6 +564,24 @@ pub struct StatusResponse {
     pub open_bets_staked: f64,
     /// Count of trades resolved (won + lost). Excludes pending bets.
     pub trades_resolved: u64,
+    /// Bets and sales on a shared account not placed by the agent
+    /// (reconciled accounting only; zero otherwise).
+    pub external_trades: u64,
+    /// Net amount staked by external activity.
+    pub external_staked: f64,
+    /// Resolved P&L attributed to external activity.
+    pub external_pnl: f64,
+    /// Platforms in scan hibernation, e.g. "betfair: hibernating, next scan in 4 cycles".
+    pub hibernating: Vec<String>,
+    /// Latest venue preflight; a venue with a failed check is scan-only.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub preflight: Vec<PreflightReport>,
+    /// Latest diagnostics sample (None until the first is taken).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<DiagnosticsSample>,
+    /// Markets kept and dropped per category by the latest scan's caps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan: Option<ScanSummary>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -129,6 +595,18 @@ pub struct CycleLogEntry {
     pub cycle_cost: f64,
     pub bankroll_after: f64,
     pub status: String,
+    /// Operator-visible changes that took effect with this cycle.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<CycleEvent>,
+}
+
+/// Something other than trading that happened in a cycle.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event")]
+pub enum CycleEvent {
+    /// The watchlist or blocklist file changed; lists what was added and
+    /// removed.
+    WatchlistChanged(ListDiff),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -138,6 +616,23 @@ pub struct BalancePoint {
     /// Live Mana balance at this point — 0 in live/dry mode.
     #[serde(default)]
     pub mana_bankroll: f64,
+    pub cash: f64,
+    pub equity: f64,
+    /// Fall of equity from its peak, as a fraction.
+    pub drawdown: f64,
+}
+
+impl From<&BalanceSample> for BalancePoint {
+    fn from(s: &BalanceSample) -> Self {
+        Self {
+            timestamp: s.at.to_rfc3339(),
+            bankroll: s.bankroll.to_f64().unwrap_or(0.0),
+            mana_bankroll: s.mana_bankroll.to_f64().unwrap_or(0.0),
+            cash: s.cash.to_f64().unwrap_or(0.0),
+            equity: s.equity.to_f64().unwrap_or(0.0),
+            drawdown: s.drawdown.to_f64().unwrap_or(0.0),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -150,6 +645,8 @@ pub struct TradeLogEntry {
     pub currency: String,
     pub edge_pct: f64,
     pub confidence: f64,
+    /// Category of the market, when known.
+    pub category: Option<MarketCategory>,
     /// Set when this entry records an auto-close event.
     /// Values: "TakeProfit", "StopLoss", "MaxHoldTime", or null for open positions.
     pub close_reason: Option<String>,
@@ -157,6 +654,75 @@ pub struct TradeLogEntry {
     pub final_pnl: Option<f64>,
 }
 
+/// One category's edge threshold, for `/api/risk`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryThresholdView {
+    pub category: String,
+    /// Threshold from `[risk.category_thresholds]`.
+    pub configured: f64,
+    /// Threshold currently applied (differs when adaptively adjusted).
+    pub effective: f64,
+    /// Excluded from adaptive adjustment by config.
+    pub frozen: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThresholdAdjustmentView {
+    pub category: String,
+    pub previous: f64,
+    pub threshold: f64,
+    pub effective_at: String,
+    pub resolutions: usize,
+}
+
+/// One category's losing streak, for `/api/risk`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoolDownView {
+    pub category: String,
+    pub consecutive_losses: u32,
+    /// Start of the active cool-down; null when not cooling down.
+    pub cool_down_since: Option<String>,
+    pub skipped: u32,
+}
+
+/// One reference platform's accuracy in one category, for `/api/calibration`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceWeightView {
+    pub platform: String,
+    pub category: String,
+    pub resolutions: u32,
+    /// Mean Brier score of the reference price.
+    pub brier: Option<f64>,
+    /// Blending weight; null until first reviewed (the default applies).
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceCalibration {
+    pub weights: Vec<ReferenceWeightView>,
+    pub last_review: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationResponse {
+    pub references: ReferenceCalibration,
+    /// Brier score, log loss and 10-bucket curve of the LLM's resolved
+    /// estimates; null until the store is loaded.
+    pub estimates: Option<CalibrationReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskResponse {
+    pub thresholds: Vec<CategoryThresholdView>,
+    /// Most recent adaptive adjustments, oldest first (last 50).
+    pub adjustments: Vec<ThresholdAdjustmentView>,
+    pub last_review: Option<String>,
+    /// Resolved bets available to the adaptive review.
+    pub edge_realizations: usize,
+    /// Categories with a current losing streak, by category name.
+    pub cool_downs: Vec<CoolDownView>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct CostsResponse {
     pub total_api_costs: f64,
@@ -172,6 +738,57 @@ pub struct CostBreakdown {
     pub ib_commissions: f64,
 }
 
+/// Latest successful fetch of one platform.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlatformScan {
+    pub scanned_at: chrono::DateTime<chrono::Utc>,
+    /// Raw markets it returned.
+    pub markets: usize,
+}
+
+/// Whether an enrichment provider can fetch its full data.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EnrichmentAvailability {
+    pub provider: String,
+    /// Env var holding its API key; `None` for keyless providers.
+    pub key_env: Option<String>,
+    pub available: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmDiagnostics {
+    pub provider: Option<String>,
+    pub model: String,
+    /// LLM spend over the agent's lifetime.
+    pub total_cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessDiagnostics {
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub uptime_secs: i64,
+    /// `None` where `/proc/self` is unavailable.
+    pub rss_bytes: Option<u64>,
+    pub open_fds: Option<usize>,
+}
+
+/// Everything `/api/diagnostics` bundles for a remote look at the agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsResponse {
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    pub agent: AgentState,
+    /// `null` until this process completes a cycle.
+    pub last_cycle: Option<accountant::CycleReport>,
+    /// Effective configuration, secrets shown only as set/unset.
+    pub config: EffectiveConfig,
+    pub platforms: BTreeMap<String, PlatformScan>,
+    pub llm: LlmDiagnostics,
+    pub enrichment: Vec<EnrichmentAvailability>,
+    /// Last error-level log events, oldest first.
+    pub recent_errors: Vec<CapturedError>,
+    pub process: ProcessDiagnostics,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct MetricsResponse {
     pub win_rate: f64,
@@ -191,124 +808,224 @@ pub type AppState = Arc<DashboardState>;
 
 /// GET /api/status
 pub async fn get_status(State(state): State<AppState>) -> Json<StatusResponse> {
-    let agent = state.agent.read().await;
-    let uptime = (chrono::Utc::now() - agent.start_time).num_seconds();
-    // Use resolved trades (won + lost) as denominator, not trades_placed which
-    // includes pending bets that haven't resolved yet.
-    let win_rate = {
-        let resolved = agent.trades_won + agent.trades_lost;
-        if resolved > 0 { agent.trades_won as f64 / resolved as f64 } else { 0.0 }
-    };
+    let trading_mode = state.trading_mode.read().await.clone();
+    let diagnostics = state.diagnostics.read().await.clone();
+    let pending_mode = *state.mode_request.read().await;
+    let last_resume_check = state.last_resume_check.read().await.clone();
+    let preflight = state.preflight.read().await.clone();
+    let scan = state.scan_summary.read().await.clone();
 
-    let bankroll = agent.bankroll.to_f64().unwrap_or(0.0);
-    let peak_bankroll = agent.peak_bankroll.to_f64().unwrap_or(0.0);
-    let total_pnl = agent.total_pnl.to_f64().unwrap_or(0.0);
-    let total_api_costs = agent.total_api_costs.to_f64().unwrap_or(0.0);
-    let total_ib_commissions = agent.total_ib_commissions.to_f64().unwrap_or(0.0);
-    let total_costs = agent.total_costs().to_f64().unwrap_or(0.0);
-    let mana_bankroll = agent.mana_bankroll.to_f64().unwrap_or(0.0);
-    let total_mana_pnl = agent.total_mana_pnl.to_f64().unwrap_or(0.0);
-    let mana_win_rate = agent.mana_win_rate();
-    let open_bets_count = agent.open_bets.len() as u64;
-    let open_bets_staked: f64 = agent.open_bets.iter()
-        .map(|b| b.amount.to_f64().unwrap_or(0.0))
-        .sum();
-    let trades_resolved = agent.trades_won + agent.trades_lost;
+    state.agent.read(|agent| {
+        let uptime = (chrono::Utc::now() - agent.start_time).num_seconds();
+        // Use resolved trades (won + lost) as denominator, not trades_placed which
+        // includes pending bets that haven't resolved yet.
+        let win_rate = {
+            let resolved = agent.trades_won + agent.trades_lost;
+            if resolved > 0 { agent.trades_won as f64 / resolved as f64 } else { 0.0 }
+        };
 
-    let trading_mode = state.trading_mode.read().await.clone();
+        let bankroll = agent.bankroll.to_f64().unwrap_or(0.0);
+        let peak_bankroll = agent.peak_bankroll.to_f64().unwrap_or(0.0);
+        let total_pnl = agent.total_pnl.to_f64().unwrap_or(0.0);
+        let total_api_costs = agent.total_api_costs.to_f64().unwrap_or(0.0);
+        let total_ib_commissions = agent.total_ib_commissions.to_f64().unwrap_or(0.0);
+        let total_costs = agent.total_costs().to_f64().unwrap_or(0.0);
+        let mana_bankroll = agent.mana_bankroll.to_f64().unwrap_or(0.0);
+        let total_mana_pnl = agent.total_mana_pnl.to_f64().unwrap_or(0.0);
+        let mana_win_rate = agent.mana_win_rate();
+        let open_bets_count = agent.open_bets.len() as u64;
+        let open_bets_staked: f64 = agent.open_bets.iter()
+            .map(|b| b.amount.to_f64().unwrap_or(0.0))
+            .sum();
+        let trades_resolved = agent.trades_won + agent.trades_lost;
+        let today = daily::today(agent, chrono::Utc::now());
+        let today_pnl = |currency: &str| {
+            today.iter().find(|d| d.currency == currency).map_or(0.0, |d| d.pnl.to_f64().unwrap_or(0.0))
+        };
+        let external = &agent.external_activity;
+        let mut hibernating: Vec<String> = agent.hibernation.iter().filter_map(|(p, h)| h.note(p)).collect();
+        hibernating.sort();
 
-    Json(StatusResponse {
-        status: format!("{}", agent.status),
-        trading_mode,
-        bankroll,
-        peak_bankroll,
-        total_pnl,
-        cycle_count: agent.cycle_count,
-        trades_placed: agent.trades_placed,
-        trades_won: agent.trades_won,
-        trades_lost: agent.trades_lost,
-        win_rate,
-        total_api_costs,
-        total_ib_commissions,
-        total_costs,
-        uptime_secs: uptime,
-        mana_bankroll,
-        total_mana_pnl,
-        mana_win_rate,
-        open_bets_count,
-        open_bets_staked,
-        trades_resolved,
-    })
+        Json(StatusResponse {
+            status: format!("{}", agent.status),
+            execution_enabled: agent.is_alive(),
+            pending_mode,
+            pause_reason: agent.pause_reason.clone(),
+            last_resume_check,
+            trading_mode,
+            bankroll,
+            peak_bankroll,
+            cash_bankroll: agent.cash_bankroll.to_f64().unwrap_or(0.0),
+            total_equity: agent.total_equity.to_f64().unwrap_or(0.0),
+            peak_equity: agent.peak_equity.to_f64().unwrap_or(0.0),
+            unrealized_pnl: agent.unrealized_pnl.to_f64().unwrap_or(0.0),
+            total_pnl,
+            cycle_count: agent.cycle_count,
+            trades_placed: agent.trades_placed,
+            trades_won: agent.trades_won,
+            trades_lost: agent.trades_lost,
+            win_rate,
+            total_api_costs,
+            total_ib_commissions,
+            total_costs,
+            uptime_secs: uptime,
+            mana_bankroll,
+            total_mana_pnl,
+            today_pnl: today_pnl("AUD"),
+            today_mana_pnl: today_pnl("Mana"),
+            mana_win_rate,
+            open_bets_count,
+            open_bets_staked,
+            trades_resolved,
+            external_trades: external.trades,
+            external_staked: external.staked.to_f64().unwrap_or(0.0),
+            external_pnl: external.pnl.to_f64().unwrap_or(0.0),
+            hibernating,
+            preflight,
+            diagnostics,
+            scan,
+        })
+    }).await
 }
 
-/// GET /api/cycles
-pub async fn get_cycles(State(state): State<AppState>) -> Json<Vec<CycleLogEntry>> {
-    let log = state.cycle_log.read().await;
-    // Return last 100 cycles
-    let start = log.len().saturating_sub(100);
-    Json(log[start..].to_vec())
+/// Query for `/api/cycles`.
+#[derive(Debug, Deserialize)]
+pub struct CyclesQuery {
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: usize,
+    /// Cycle number from a previous page's `next_cursor`.
+    pub before: Option<u64>,
+    /// Only cycles numbered above this.
+    pub since_cycle: Option<u64>,
 }
 
-/// GET /api/balance-history
-pub async fn get_balance_history(State(state): State<AppState>) -> Json<Vec<BalancePoint>> {
-    let history = state.balance_history.read().await;
-    let start = history.len().saturating_sub(500);
-    Json(history[start..].to_vec())
+/// GET /api/cycles?since_cycle=40&limit=20&before=90
+/// Logged cycles, paged back from the latest (see [`Page`]).
+pub async fn get_cycles(
+    State(state): State<AppState>,
+    query: Result<Query<CyclesQuery>, QueryRejection>,
+) -> Result<Json<Page<CycleLogEntry>>, BadRequest> {
+    let Query(q) = query?;
+    let paging = Paging { limit: page_size(q.limit)?, offset: q.offset, before: q.before };
+    Ok(Json(state.cycles_page(q.since_cycle, &paging).await))
 }
 
-/// GET /api/trades
-pub async fn get_trades(State(state): State<AppState>) -> Json<Vec<TradeLogEntry>> {
-    let trades = state.recent_trades.read().await;
-    let start = trades.len().saturating_sub(100);
-    Json(trades[start..].to_vec())
+/// Query for `/api/balance-history`.
+#[derive(Debug, Default, Deserialize)]
+pub struct BalanceHistoryQuery {
+    /// `raw` (default), `hourly` or `daily`.
+    #[serde(default)]
+    pub resolution: Resolution,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range, exclusive.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// GET /api/balance-history?resolution=daily&from=2026-01-01T00:00:00Z
+/// Stored balances after each cycle in the range, oldest first. Hourly and
+/// daily resolutions keep the last sample of each UTC hour or day.
+pub async fn get_balance_history(
+    State(state): State<AppState>,
+    Query(q): Query<BalanceHistoryQuery>,
+) -> Result<Json<Vec<BalancePoint>>, StatusCode> {
+    let storage = state.storage.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    if matches!((q.from, q.to), (Some(from), Some(to)) if from >= to) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let samples = storage.balance_history(q.from, q.to).await.map_err(|e| {
+        tracing::warn!(error = %e, "Balance history query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(downsample(samples, q.resolution).iter().map(BalancePoint::from).collect()))
+}
+
+/// Query for `/api/trades`.
+#[derive(Debug, Deserialize)]
+pub struct TradesQuery {
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: usize,
+    /// Timestamp from a previous page's `next_cursor`.
+    pub before: Option<chrono::DateTime<chrono::Utc>>,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range, exclusive.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub category: Option<String>,
+    pub platform: Option<String>,
+}
+
+/// GET /api/trades?from=2026-01-01T00:00:00Z&category=sports&platform=betfair&limit=50
+/// Recent trades and auto-exit closes, filtered and paged back from the
+/// latest (see [`Page`]).
+pub async fn get_trades(
+    State(state): State<AppState>,
+    query: Result<Query<TradesQuery>, QueryRejection>,
+) -> Result<Json<Page<TradeLogEntry>>, BadRequest> {
+    let Query(q) = query?;
+    if let (Some(from), Some(to)) = (q.from, q.to) {
+        if from >= to {
+            return Err(BadRequest("from must be before to".to_string()));
+        }
+    }
+    let category = q
+        .category
+        .map(|c| c.parse::<MarketCategory>())
+        .transpose()
+        .map_err(|e| BadRequest(e.to_string()))?;
+    let filter = TradeFilter { from: q.from, to: q.to, category, platform: q.platform };
+    let paging = Paging { limit: page_size(q.limit)?, offset: q.offset, before: q.before };
+    Ok(Json(state.trades_page(&filter, &paging).await))
 }
 
 /// GET /api/costs
 pub async fn get_costs(State(state): State<AppState>) -> Json<CostsResponse> {
-    let agent = state.agent.read().await;
-    let total_api_costs = agent.total_api_costs.to_f64().unwrap_or(0.0);
-    let total_ib_commissions = agent.total_ib_commissions.to_f64().unwrap_or(0.0);
-    let total_costs = agent.total_costs().to_f64().unwrap_or(0.0);
-    let llm_costs = agent.total_llm_costs.to_f64().unwrap_or(0.0);
-    let data_costs = agent.total_data_costs.to_f64().unwrap_or(0.0);
-
-    Json(CostsResponse {
-        total_api_costs,
-        total_ib_commissions,
-        total_costs,
-        cost_breakdown: CostBreakdown {
-            llm: llm_costs,
-            data: data_costs,
-            ib_commissions: total_ib_commissions,
-        },
-    })
+    state.agent.read(|agent| {
+        let total_api_costs = agent.total_api_costs.to_f64().unwrap_or(0.0);
+        let total_ib_commissions = agent.total_ib_commissions.to_f64().unwrap_or(0.0);
+        let total_costs = agent.total_costs().to_f64().unwrap_or(0.0);
+        let llm_costs = agent.total_llm_costs.to_f64().unwrap_or(0.0);
+        let data_costs = agent.total_data_costs.to_f64().unwrap_or(0.0);
+
+        Json(CostsResponse {
+            total_api_costs,
+            total_ib_commissions,
+            total_costs,
+            cost_breakdown: CostBreakdown {
+                llm: llm_costs,
+                data: data_costs,
+                ib_commissions: total_ib_commissions,
+            },
+        })
+    }).await
 }
 
 /// GET /api/metrics
 pub async fn get_metrics(State(state): State<AppState>) -> Json<MetricsResponse> {
-    let agent = state.agent.read().await;
-    let bankroll = agent.bankroll.to_f64().unwrap_or(0.0);
-    let total_pnl = agent.total_pnl.to_f64().unwrap_or(0.0);
-    let total_costs = agent.total_costs().to_f64().unwrap_or(0.0);
-    let initial = bankroll - total_pnl + total_costs;
-    let roi = if initial > 0.0 {
-        ((bankroll - initial) / initial) * 100.0
-    } else {
-        0.0
-    };
+    state.agent.read(|agent| {
+        let bankroll = agent.bankroll.to_f64().unwrap_or(0.0);
+        let total_pnl = agent.total_pnl.to_f64().unwrap_or(0.0);
+        let total_costs = agent.total_costs().to_f64().unwrap_or(0.0);
+        let initial = bankroll - total_pnl + total_costs;
+        let roi = if initial > 0.0 {
+            ((bankroll - initial) / initial) * 100.0
+        } else {
+            0.0
+        };
 
-    Json(MetricsResponse {
-        win_rate: {
-            let resolved = agent.trades_won + agent.trades_lost;
-            if resolved > 0 { agent.trades_won as f64 / resolved as f64 } else { 0.0 }
-        },
-        trades_placed: agent.trades_placed,
-        trades_won: agent.trades_won,
-        trades_lost: agent.trades_lost,
-        total_pnl,
-        roi_pct: roi,
-        cycles_run: agent.cycle_count,
-    })
+        Json(MetricsResponse {
+            win_rate: {
+                let resolved = agent.trades_won + agent.trades_lost;
+                if resolved > 0 { agent.trades_won as f64 / resolved as f64 } else { 0.0 }
+            },
+            trades_placed: agent.trades_placed,
+            trades_won: agent.trades_won,
+            trades_lost: agent.trades_lost,
+            total_pnl,
+            roi_pct: roi,
+            cycles_run: agent.cycle_count,
+        })
+    }).await
 }
 
 /// GET /api/progress
@@ -319,6 +1036,28 @@ pub async fn get_progress(State(state): State<AppState>) -> Json<ProgressRespons
     Json(ProgressResponse { progress, model })
 }
 
+/// GET /api/events
+/// Phase changes, trades and cycle errors as server-sent events, one JSON
+/// [`LiveEvent`] per event, opening with the current phase. A subscriber
+/// more than [`EVENT_BUFFER`] events behind skips the oldest.
+pub async fn get_events(State(state): State<AppState>) -> Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>> {
+    let rx = state.events.subscribe();
+    let current = LiveEvent::Phase { progress: state.progress.read().await.clone() };
+    let live = futures::stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((event, rx)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let events = futures::stream::once(async { current })
+        .chain(live)
+        .map(|event| Ok(Event::default().data(serde_json::to_string(&event).unwrap_or_default())));
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
 /// GET /api/errors
 pub async fn get_errors(State(state): State<AppState>) -> Json<Vec<ErrorLogEntry>> {
     let log = state.error_log.read().await;
@@ -327,10 +1066,577 @@ pub async fn get_errors(State(state): State<AppState>) -> Json<Vec<ErrorLogEntry
 }
 
 /// GET /api/positions
-/// Returns all open (unresolved) bets.
+/// Returns all open (unresolved) bets, without raw platform responses.
 pub async fn get_positions(State(state): State<AppState>) -> Json<Vec<TradeReceipt>> {
-    let agent = state.agent.read().await;
-    Json(agent.open_bets.clone())
+    state.agent.read(|agent| {
+        let positions = agent
+            .open_bets
+            .iter()
+            .map(|r| TradeReceipt { raw_response: None, ..r.clone() })
+            .collect();
+        Json(positions)
+    }).await
+}
+
+/// GET /api/positions/:order_id
+/// Full receipt for one open bet, including the retained raw platform
+/// response. Token-protected.
+pub async fn get_position(
+    State(state): State<AppState>,
+    Path(order_id): Path<String>,
+) -> Result<Json<TradeReceipt>, StatusCode> {
+    state.agent.read(|agent| {
+        agent
+            .open_bets
+            .iter()
+            .find(|r| r.order_id == order_id)
+            .cloned()
+            .map(Json)
+            .ok_or(StatusCode::NOT_FOUND)
+    }).await
+}
+
+/// GET /api/risk
+/// Edge thresholds in effect and the adaptive-threshold adjustment history.
+pub async fn get_risk(State(state): State<AppState>) -> Json<RiskResponse> {
+    let thresholds = state.edge_thresholds.read().await.clone();
+    state.agent.read(|agent| {
+        let history = &agent.thresholds.adjustments;
+        let adjustments = history[history.len().saturating_sub(50)..]
+            .iter()
+            .map(|a| ThresholdAdjustmentView {
+                category: a.category.to_string(),
+                previous: a.previous.to_f64().unwrap_or(0.0),
+                threshold: a.threshold.to_f64().unwrap_or(0.0),
+                effective_at: a.effective_at.to_rfc3339(),
+                resolutions: a.resolutions,
+            })
+            .collect();
+        let mut cool_downs: Vec<CoolDownView> = agent
+            .cool_downs
+            .categories
+            .iter()
+            .filter(|(_, s)| s.consecutive_losses > 0 || s.cooling_down())
+            .map(|(category, s)| CoolDownView {
+                category: category.to_string(),
+                consecutive_losses: s.consecutive_losses,
+                cool_down_since: s.cool_down_since.map(|t| t.to_rfc3339()),
+                skipped: s.skipped,
+            })
+            .collect();
+        cool_downs.sort_by(|a, b| a.category.cmp(&b.category));
+        Json(RiskResponse {
+            thresholds,
+            adjustments,
+            last_review: agent.thresholds.last_review.map(|t| t.to_rfc3339()),
+            edge_realizations: agent.edge_realizations.len(),
+            cool_downs,
+        })
+    }).await
+}
+
+/// GET /api/calibration
+/// Accuracy and blending weight of each cross-reference platform per
+/// category, and the calibration of the LLM's own estimates.
+pub async fn get_calibration(State(state): State<AppState>) -> Json<CalibrationResponse> {
+    let estimates = state.estimate_calibration.read().await.clone();
+    state.agent.read(|agent| {
+        let mut weights: Vec<ReferenceWeightView> = agent
+            .references
+            .accuracy
+            .iter()
+            .map(|a| ReferenceWeightView {
+                platform: a.platform.clone(),
+                category: a.category.to_string(),
+                resolutions: a.resolutions,
+                brier: a.brier(),
+                weight: a.weight,
+            })
+            .collect();
+        weights.sort_by(|a, b| (&a.platform, &a.category).cmp(&(&b.platform, &b.category)));
+        Json(CalibrationResponse {
+            references: ReferenceCalibration {
+                weights,
+                last_review: agent.references.last_review.map(|t| t.to_rfc3339()),
+            },
+            estimates,
+        })
+    }).await
+}
+
+/// GET /api/config
+/// Effective configuration with provenance; secrets shown only as set/unset.
+/// Bearer-token protected (see [`super::require_api_token`]).
+pub async fn get_config(State(state): State<AppState>) -> Json<EffectiveConfig> {
+    Json(state.effective_config.clone())
+}
+
+/// GET /api/diagnostics
+/// One bundle of agent state, last cycle, effective config, scan, LLM and
+/// enrichment status, recent errors and process health. Bearer-token
+/// protected (see [`super::require_api_token`]).
+pub async fn get_diagnostics(State(state): State<AppState>) -> Json<DiagnosticsResponse> {
+    let now = chrono::Utc::now();
+    let agent = state.agent.read(|agent| agent.clone()).await;
+    let provider = state.effective_config.entries.get("llm.provider").and_then(|e| e.value.as_str()).map(str::to_string);
+    Json(DiagnosticsResponse {
+        generated_at: now,
+        last_cycle: state.last_cycle.read().await.clone(),
+        config: state.effective_config.clone(),
+        platforms: state.platform_scans.read().await.clone(),
+        llm: LlmDiagnostics {
+            provider,
+            model: state.active_model.read().await.clone(),
+            total_cost: agent.total_llm_costs.to_f64().unwrap_or(0.0),
+        },
+        enrichment: state.enrichment.clone(),
+        recent_errors: state.recent_errors.snapshot(),
+        process: ProcessDiagnostics {
+            started_at: state.started_at,
+            uptime_secs: (now - state.started_at).num_seconds(),
+            rss_bytes: process::rss_bytes(),
+            open_fds: process::open_fds(),
+        },
+        agent,
+    })
+}
+
+/// Platforms that can be woken from scan hibernation.
+const WAKEABLE_PLATFORMS: &[&str] = &["manifold", "polymarket", "betfair"];
+
+/// POST /api/control/wake/:platform
+/// Queue a hibernating platform for a scan on the next cycle.
+/// Bearer-token protected (see [`super::require_api_token`]).
+pub async fn post_wake(
+    State(state): State<AppState>,
+    Path(platform): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), StatusCode> {
+    if !WAKEABLE_PLATFORMS.contains(&platform.as_str()) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let mut requests = state.wake_requests.write().await;
+    if !requests.contains(&platform) {
+        requests.push(platform.clone());
+    }
+    Ok((StatusCode::ACCEPTED, Json(serde_json::json!({ "wake": platform }))))
+}
+
+/// POST /api/control/standby
+/// Keep scanning and estimating but stop placing bets, from the next tick.
+/// Bearer-token protected (see [`super::require_api_token`]).
+pub async fn post_standby(State(state): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
+    *state.mode_request.write().await = Some(ModeRequest::Standby);
+    (StatusCode::ACCEPTED, Json(serde_json::json!({ "mode": ModeRequest::Standby })))
+}
+
+/// POST /api/control/pause (also /api/pause)
+/// Stop running cycles from the next tick: nothing is enriched, estimated
+/// or placed until resumed. Bearer-token protected (see
+/// [`super::require_api_token`]).
+pub async fn post_pause(State(state): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
+    *state.mode_request.write().await = Some(ModeRequest::Pause);
+    (StatusCode::ACCEPTED, Json(serde_json::json!({ "mode": ModeRequest::Pause })))
+}
+
+/// POST /api/control/watchlist
+/// Add or remove watchlist markets and blocklist patterns. The edit is
+/// validated and written to the files, which stay the source of truth; the
+/// agent applies it at the next cycle. Bearer-token protected (see
+/// [`super::require_api_token`]).
+pub async fn post_watchlist(
+    State(state): State<AppState>,
+    Json(edit): Json<ListEdit>,
+) -> Result<Json<ListDiff>, (StatusCode, String)> {
+    let Some(paths) = &state.list_paths else {
+        return Err((StatusCode::NOT_FOUND, "No watchlist configured".to_string()));
+    };
+    let paths = paths.lock().unwrap_or_else(|e| e.into_inner());
+    paths
+        .apply(&edit)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("{e:#}")))
+}
+
+/// POST /api/blacklist
+/// Ban questions matching `{"pattern": "..."}` without a restart: a
+/// case-insensitive substring, or a regex written `/…/` (as in
+/// `scanner.blacklist_patterns`). Added to the blocklist file, so it
+/// persists and applies from the next cycle. Bearer-token protected (see
+/// [`super::require_api_token`]).
+pub async fn post_blacklist(
+    State(state): State<AppState>,
+    Json(request): Json<BlacklistRequest>,
+) -> Result<Json<ListDiff>, (StatusCode, String)> {
+    if request.pattern.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Empty pattern".to_string()));
+    }
+    let edit = ListEdit { add_patterns: vec![pattern_regex(&request.pattern)], ..ListEdit::default() };
+    post_watchlist(State(state), Json(edit)).await
+}
+
+/// Body of `/api/blacklist`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlacklistRequest {
+    pub pattern: String,
+}
+
+/// POST /api/control/deposit
+/// Add funds to (negative: withdraw from) the AUD or Mana bankroll, e.g.
+/// `{"currency":"AUD","amount":50}`. Applied to the shared state at once
+/// and picked up by the engine at its next sync point; not counted as P&L.
+/// Bearer-token protected (see [`super::require_api_token`]).
+pub async fn post_deposit(
+    State(state): State<AppState>,
+    Json(deposit): Json<DepositRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let change = StateChange::Deposit { currency: deposit.currency, amount: deposit.amount };
+    state.agent.apply(change.clone()).await.map_err(|e| (StatusCode::BAD_REQUEST, format!("{e:#}")))?;
+    let (bankroll, mana_bankroll) = state.agent.read(|a| (a.bankroll, a.mana_bankroll)).await;
+    Ok(Json(serde_json::json!({ "applied": change, "bankroll": bankroll, "mana_bankroll": mana_bankroll })))
+}
+
+/// Body of `/api/control/deposit`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DepositRequest {
+    pub currency: String,
+    pub amount: Decimal,
+}
+
+/// Query for `/api/control/resume`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ResumeQuery {
+    /// Leave pause even if the drawdown has not recovered.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// POST /api/control/resume[?force=true] (also /api/resume)
+/// Leave standby on the next tick, once venue balances and positions have
+/// been re-checked; the outcome is reported by `/api/status`. Leave pause
+/// once the drawdown is back below `risk.drawdown_resume_pct`, or at once
+/// with `force=true`. Bearer-token protected (see
+/// [`super::require_api_token`]).
+pub async fn post_resume(
+    State(state): State<AppState>,
+    Query(query): Query<ResumeQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let mode = if query.force { ModeRequest::ForceResume } else { ModeRequest::Resume };
+    *state.mode_request.write().await = Some(mode);
+    (StatusCode::ACCEPTED, Json(serde_json::json!({ "mode": mode })))
+}
+
+/// GET /api/model-comparison
+/// Shadow-model canary statistics; `null` when no shadow model is configured.
+pub async fn get_model_comparison(State(state): State<AppState>) -> Json<Option<ComparisonReport>> {
+    Json(state.model_comparison.read().await.clone())
+}
+
+/// Query for `/api/metrics/history`.
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Range to return, ending now.
+    #[serde(default = "HistoryQuery::default_hours")]
+    pub hours: i64,
+    /// Seconds per point. Defaults to hourly for ranges over two days,
+    /// otherwise one point per cycle.
+    pub resolution: Option<i64>,
+}
+
+impl HistoryQuery {
+    fn default_hours() -> i64 { 24 * 7 }
+}
+
+/// GET /api/metrics/history?hours=720&resolution=3600
+/// Resolutions of an hour or more are served from pre-aggregated rollups.
+/// Rate-limited as an expensive route; long histories are serialised on the
+/// blocking pool.
+pub async fn get_metrics_history(
+    State(state): State<AppState>,
+    Query(q): Query<HistoryQuery>,
+) -> Result<Response, StatusCode> {
+    let store = state.metrics.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    if q.hours <= 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let resolution = q
+        .resolution
+        .unwrap_or(if q.hours > 48 { HOUR_SECS } else { 0 })
+        .max(0);
+    let to = chrono::Utc::now();
+    let from = to - chrono::Duration::hours(q.hours);
+    let history = store.history(from, to, resolution).await.map_err(|e| {
+        tracing::warn!(error = %e, "Metrics history query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let body = budget::offload(move || serde_json::to_vec(&history))
+        .await?
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(([(header::CONTENT_TYPE, "application/json")], body).into_response())
+}
+
+/// Query for `/api/decisions`.
+#[derive(Debug, Deserialize)]
+pub struct DecisionsQuery {
+    pub market_id: Option<String>,
+    pub cycle: Option<u64>,
+    /// Most decisions returned, capped at [`MAX_DECISIONS`].
+    #[serde(default = "DecisionsQuery::default_limit")]
+    pub limit: usize,
+}
+
+impl DecisionsQuery {
+    fn default_limit() -> usize { 100 }
+}
+
+/// Most decisions one `/api/decisions` request returns.
+pub const MAX_DECISIONS: usize = 1000;
+
+/// GET /api/decisions?market_id=m1&cycle=42&limit=100
+/// Latest journalled strategy decisions, newest first. 503 when the journal
+/// is disabled. Rate-limited as an expensive route; the journal is read on
+/// the blocking pool.
+pub async fn get_decisions(
+    State(state): State<AppState>,
+    Query(q): Query<DecisionsQuery>,
+) -> Result<Json<Vec<serde_json::Value>>, StatusCode> {
+    let journal = state.decision_journal.clone().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let query = JournalQuery { market_id: q.market_id, cycle: q.cycle, limit: q.limit.min(MAX_DECISIONS) };
+    let decisions = budget::offload(move || journal.recent(&query)).await?.map_err(|e| {
+        tracing::warn!(error = %e, "Decision journal read failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(decisions))
+}
+
+/// Query for `/api/rejections/summary`.
+#[derive(Debug, Deserialize)]
+pub struct RejectionSummaryQuery {
+    /// Latest cycles counted, capped at [`MAX_SUMMARY_CYCLES`].
+    #[serde(default = "RejectionSummaryQuery::default_cycles")]
+    pub cycles: u64,
+}
+
+impl RejectionSummaryQuery {
+    fn default_cycles() -> u64 { 10 }
+}
+
+/// Most cycles one `/api/rejections/summary` request counts.
+pub const MAX_SUMMARY_CYCLES: u64 = 1000;
+
+/// GET /api/rejections/summary?cycles=10
+/// Bets blocked over the latest journalled cycles, counted by rejection
+/// code (see [`crate::strategy::risk::RejectionReason::code`]). 503 when
+/// the journal is disabled. Rate-limited as an expensive route; the journal
+/// is read on the blocking pool.
+pub async fn get_rejection_summary(
+    State(state): State<AppState>,
+    Query(q): Query<RejectionSummaryQuery>,
+) -> Result<Json<RejectionSummary>, StatusCode> {
+    let journal = state.decision_journal.clone().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let cycles = q.cycles.min(MAX_SUMMARY_CYCLES);
+    let summary = budget::offload(move || journal.rejection_summary(cycles)).await?.map_err(|e| {
+        tracing::warn!(error = %e, "Decision journal read failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(summary))
+}
+
+/// Query for `/api/trades/history`.
+#[derive(Debug, Deserialize)]
+pub struct TradeHistoryQuery {
+    /// Start of the range (RFC 3339); defaults to [`DEFAULT_TRADE_HISTORY_DAYS`] ago.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range, exclusive; defaults to now.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Days of trades `/api/trades/history` returns without a `from`.
+pub const DEFAULT_TRADE_HISTORY_DAYS: i64 = 30;
+
+/// GET /api/trades/history?from=2026-01-01T00:00:00Z&to=2026-02-01T00:00:00Z
+/// Stored trades placed in the range, oldest first, with realised P&L once
+/// settled. Raw venue responses are omitted. Rate-limited as an expensive
+/// route.
+pub async fn get_trade_history(
+    State(state): State<AppState>,
+    Query(q): Query<TradeHistoryQuery>,
+) -> Result<Json<Vec<TradeRecord>>, StatusCode> {
+    let storage = state.storage.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let to = q.to.unwrap_or_else(chrono::Utc::now);
+    let from = q.from.unwrap_or(to - chrono::Duration::days(DEFAULT_TRADE_HISTORY_DAYS));
+    if from >= to {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let mut trades = storage.trades_between(from, to).await.map_err(|e| {
+        tracing::warn!(error = %e, "Trade history query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    for trade in &mut trades {
+        trade.receipt.raw_response = None;
+    }
+    Ok(Json(trades))
+}
+
+/// GET /api/pnl/categories
+/// Realised P&L of settled trades per category and currency, largest stake
+/// first.
+pub async fn get_pnl_by_category(State(state): State<AppState>) -> Result<Json<Vec<CategoryPnl>>, StatusCode> {
+    let storage = state.storage.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let categories = storage.pnl_by_category().await.map_err(|e| {
+        tracing::warn!(error = %e, "P&L by category query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(categories))
+}
+
+/// GET /api/performance
+/// Settled trades attributed by category and platform, with threshold
+/// suggestions for categories whose realised edge falls short of their
+/// configured threshold.
+pub async fn get_performance(State(state): State<AppState>) -> Result<Json<PerformanceReport>, StatusCode> {
+    let storage = state.storage.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let query = TradeQuery { settled: Some(true), ..TradeQuery::default() };
+    let trades = storage.query_trades(&query).await.map_err(|e| {
+        tracing::warn!(error = %e, "Performance trade query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let thresholds = state.edge_thresholds.read().await.clone();
+    let threshold = |category: MarketCategory| {
+        let name = category.to_string();
+        thresholds.iter().find(|t| t.category == name).map(|t| t.configured)
+    };
+    Ok(Json(PerformanceReport::build(&trades, threshold)))
+}
+
+/// One market's lifecycle and where it was read from.
+#[derive(Debug, Serialize)]
+pub struct ExplainResponse {
+    /// `"hot"` (live decision rows) or `"archive"`.
+    pub source: &'static str,
+    /// Declared link sets the market belongs to.
+    pub links: Vec<LinkSet>,
+    #[serde(flatten)]
+    pub lifecycle: MarketLifecycle,
+}
+
+/// GET /api/explain/:platform/:market_id
+/// Every estimate and decision recorded for a market, plus its bets. Reads
+/// hot rows while they exist and falls back to the archive after pruning.
+pub async fn get_explain(
+    State(state): State<AppState>,
+    Path((platform, market_id)): Path<(String, String)>,
+) -> Result<Json<ExplainResponse>, StatusCode> {
+    let key = link_key(&platform, &market_id);
+    let links: Vec<LinkSet> = state.market_links.sets_for(&key).cloned().collect();
+    if let Some(store) = state.metrics.as_ref() {
+        let receipts = state
+            .agent
+            .read(|agent| {
+                agent
+                    .open_bets
+                    .iter()
+                    .filter(|r| r.platform == platform && r.market_id == market_id)
+                    .map(|r| TradeReceipt { raw_response: None, ..r.clone() })
+                    .collect()
+            })
+            .await;
+        let lifecycle = MarketLifecycle::assemble(store, &platform, &market_id, receipts)
+            .await
+            .map_err(|e| {
+                tracing::warn!(error = %e, "Explain query failed");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        if !lifecycle.decisions.is_empty() {
+            return Ok(Json(ExplainResponse { source: "hot", links, lifecycle }));
+        }
+    }
+    let archive = state.archive.clone().ok_or(StatusCode::NOT_FOUND)?;
+    let archived = budget::offload(move || archive.read(&platform, &market_id))
+        .await?
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Archive read failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    archived
+        .map(|lifecycle| Json(ExplainResponse { source: "archive", links, lifecycle }))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Declared links and pending suggestions.
+#[derive(Debug, Serialize)]
+pub struct LinksResponse {
+    pub declared: Vec<LinkSet>,
+    /// Candidates from the latest scan. Never applied: add them to the
+    /// links file to enforce them.
+    pub suggestions: Vec<LinkSuggestion>,
+}
+
+/// GET /api/links
+pub async fn get_links(State(state): State<AppState>) -> Json<LinksResponse> {
+    Json(LinksResponse {
+        declared: state.market_links.sets().to_vec(),
+        suggestions: state.link_suggestions.read().await.clone(),
+    })
+}
+
+/// Per-tag statistics.
+#[derive(Debug, Serialize)]
+pub struct TagsResponse {
+    pub tags: Vec<TagStats>,
+}
+
+/// GET /api/tags
+/// Open exposure and resolved results per operator tag and currency.
+pub async fn get_tags(State(state): State<AppState>) -> Json<TagsResponse> {
+    state.agent.read(|agent| {
+        Json(TagsResponse { tags: tags::report(&agent.open_bets, &agent.tag_results) })
+    }).await
+}
+
+/// Daily P&L from the end-of-day snapshots.
+#[derive(Debug, Serialize)]
+pub struct DailyResponse {
+    /// Closed days, oldest first, one entry per currency.
+    pub days: Vec<DayPnl>,
+    /// Since the latest snapshot.
+    pub today: Vec<DayPnl>,
+    /// Late resolutions booked against an earlier day.
+    pub corrections: usize,
+}
+
+/// GET /api/daily
+pub async fn get_daily(State(state): State<AppState>) -> Json<DailyResponse> {
+    state.agent.read(|agent| {
+        Json(DailyResponse {
+            days: daily::history(&agent.daily),
+            today: daily::today(agent, chrono::Utc::now()),
+            corrections: agent.daily.corrections.len(),
+        })
+    }).await
+}
+
+/// GET /api/sensitivity
+/// Bets each parameter-grid variant would have added or removed over the
+/// stored decision history, scaled by the current bankroll.
+pub async fn get_sensitivity(State(state): State<AppState>) -> Result<Json<SensitivityReport>, StatusCode> {
+    let store = state.metrics.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let base = state.sensitivity_base.clone().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let rows: Vec<_> = store.decisions().try_collect().await.map_err(|e| {
+        tracing::warn!(error = %e, "Decision history query failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let bankroll = state.agent.read(|a| a.bankroll.to_f64().unwrap_or(0.0)).await;
+    let report = budget::offload(move || sensitivity::analyze(&rows, &base, bankroll)).await?;
+    Ok(Json(report))
+}
+
+/// GET /metrics
+/// Prometheus text exposition of the engine's counters, gauges and
+/// histograms. Never cached: scrapers compute rates from it.
+pub async fn get_prometheus(State(state): State<AppState>) -> Response {
+    ([(header::CONTENT_TYPE, prometheus::CONTENT_TYPE)], state.prometheus.render()).into_response()
 }
 
 /// GET /health
@@ -345,6 +1651,7 @@ pub async fn health() -> StatusCode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::AgentState;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -358,9 +1665,17 @@ mod tests {
     fn test_status_response_serializes() {
         let resp = StatusResponse {
             status: "ALIVE".into(),
+            execution_enabled: true,
+            pending_mode: None,
+            pause_reason: None,
+            last_resume_check: None,
             trading_mode: "paper".into(),
             bankroll: 100.0,
             peak_bankroll: 110.0,
+            cash_bankroll: 90.0,
+            total_equity: 102.0,
+            peak_equity: 110.0,
+            unrealized_pnl: 2.0,
             total_pnl: 10.0,
             cycle_count: 5,
             trades_placed: 3,
@@ -373,10 +1688,19 @@ mod tests {
             uptime_secs: 3600,
             mana_bankroll: 714.0,
             total_mana_pnl: -18.0,
+            today_pnl: 0.0,
+            today_mana_pnl: 0.0,
             mana_win_rate: 0.5,
             open_bets_count: 3,
             open_bets_staked: 270.0,
             trades_resolved: 3,
+            external_trades: 0,
+            external_staked: 0.0,
+            external_pnl: 0.0,
+            hibernating: Vec::new(),
+            preflight: Vec::new(),
+            diagnostics: None,
+            scan: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("ALIVE"));
@@ -384,6 +1708,17 @@ mod tests {
         assert!(json.contains("100"));
     }
 
+    #[test]
+    fn test_cycle_event_serializes_tagged() {
+        let event = CycleEvent::WatchlistChanged(ListDiff {
+            watch_added: vec!["manifold:abc".into()],
+            ..Default::default()
+        });
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "WatchlistChanged");
+        assert_eq!(json["watch_added"][0], "manifold:abc");
+    }
+
     #[test]
     fn test_cycle_log_entry_serializes() {
         let entry = CycleLogEntry {
@@ -396,6 +1731,7 @@ mod tests {
             cycle_cost: 0.05,
             bankroll_after: 99.95,
             status: "ALIVE".into(),
+            events: Vec::new(),
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains("50"));
@@ -407,6 +1743,9 @@ mod tests {
             timestamp: "2026-02-21T12:00:00Z".into(),
             bankroll: 105.50,
             mana_bankroll: 714.0,
+            cash: 95.50,
+            equity: 107.0,
+            drawdown: 0.02,
         };
         let json = serde_json::to_string(&point).unwrap();
         assert!(json.contains("105.5"));
@@ -436,19 +1775,327 @@ mod tests {
         assert!(resp.status.contains("ALIVE"));
     }
 
+    fn all_cycles() -> Result<Query<CyclesQuery>, QueryRejection> {
+        Ok(Query(CyclesQuery { limit: None, offset: 0, before: None, since_cycle: None }))
+    }
+
     #[tokio::test]
     async fn test_get_cycles_empty() {
         let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
-        let Json(cycles) = get_cycles(State(state)).await;
-        assert!(cycles.is_empty());
+        let Json(page) = get_cycles(State(state), all_cycles()).await.unwrap();
+        assert!(page.items.is_empty());
+        assert_eq!((page.total, page.returned, page.next_cursor), (0, 0, None));
+    }
+
+    fn trade(minute: u32, platform: &str, category: Option<MarketCategory>) -> TradeLogEntry {
+        TradeLogEntry {
+            timestamp: format!("2026-03-01T12:{minute:02}:00+00:00"),
+            market_id: format!("m{minute}"),
+            platform: platform.to_string(),
+            side: "YES".to_string(),
+            amount: 10.0,
+            currency: "AUD".to_string(),
+            edge_pct: 12.0,
+            confidence: 0.8,
+            category,
+            close_reason: None,
+            final_pnl: None,
+        }
+    }
+
+    async fn trades_state() -> AppState {
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
+        let mut trades = state.recent_trades.write().await;
+        for minute in 0..10 {
+            let (platform, category) = if minute % 2 == 0 {
+                ("betfair", MarketCategory::Sports)
+            } else {
+                ("manifold", MarketCategory::Politics)
+            };
+            trades.push(trade(minute, platform, Some(category)));
+        }
+        trades.push(trade(10, "betfair-closed", Some(MarketCategory::Sports)));
+        drop(trades);
+        state
+    }
+
+    fn paging<K>(limit: usize, offset: usize, before: Option<K>) -> Paging<K> {
+        Paging { limit, offset, before }
     }
 
     #[tokio::test]
-    async fn test_get_balance_history_initial() {
-        let state = Arc::new(DashboardState::new(AgentState::new(dec!(50))));
-        let Json(history) = get_balance_history(State(state)).await;
-        assert_eq!(history.len(), 1);
-        assert!((history[0].bankroll - 50.0).abs() < 1e-10);
+    async fn test_trades_page_walks_back_by_cursor() {
+        let state = trades_state().await;
+        let all = TradeFilter::default();
+
+        let newest = state.trades_page(&all, &paging(4, 0, None)).await;
+        assert_eq!((newest.total, newest.returned), (11, 4));
+        assert_eq!(newest.items.iter().map(|t| t.market_id.as_str()).collect::<Vec<_>>(), ["m7", "m8", "m9", "m10"]);
+        let cursor = newest.next_cursor.unwrap();
+        assert_eq!(cursor, "2026-03-01T12:07:00+00:00");
+
+        let before = entry_time(&cursor);
+        let older = state.trades_page(&all, &paging(4, 0, before)).await;
+        assert_eq!(older.items.first().unwrap().market_id, "m3");
+        let last = state.trades_page(&all, &paging(4, 0, entry_time(&older.next_cursor.unwrap()))).await;
+        assert_eq!(last.returned, 3);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_trades_page_offset_beyond_end_is_empty() {
+        let state = trades_state().await;
+        let page = state.trades_page(&TradeFilter::default(), &paging(5, 50, None)).await;
+        assert!(page.items.is_empty());
+        assert_eq!((page.total, page.returned, page.next_cursor), (11, 0, None));
+
+        // Partly past the end: what is left.
+        let page = state.trades_page(&TradeFilter::default(), &paging(5, 8, None)).await;
+        assert_eq!(page.returned, 3);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_trades_combined_filters() {
+        let state = trades_state().await;
+        let filter = TradeFilter {
+            from: entry_time("2026-03-01T12:03:00Z"),
+            to: None,
+            category: Some(MarketCategory::Sports),
+            platform: Some("betfair".to_string()),
+        };
+        let page = state.trades_page(&filter, &paging(100, 0, None)).await;
+        // Sports on Betfair from 12:03, its close included.
+        assert_eq!(page.items.iter().map(|t| t.market_id.as_str()).collect::<Vec<_>>(), ["m4", "m6", "m8", "m10"]);
+        assert_eq!(page.total, 4);
+
+        let filter = TradeFilter { to: entry_time("2026-03-01T12:06:00Z"), ..filter };
+        assert_eq!(state.trades_page(&filter, &paging(100, 0, None)).await.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cycles_since_cycle() {
+        let history: Vec<CycleReport> = (1..=6)
+            .map(|n| CycleReport {
+                cycle_number: n,
+                timestamp: chrono::Utc::now(),
+                markets_scanned: 0,
+                edges_found: 0,
+                bets_placed: 0,
+                cycle_cost: Decimal::ZERO,
+                cycle_pnl: Decimal::ZERO,
+                bankroll_after: dec!(100),
+                bets_failed: 0,
+                mana_bankroll_after: Decimal::ZERO,
+                status: None,
+            })
+            .collect();
+        let state = DashboardState::new(AgentState::new(dec!(100))).with_cycle_history(&history);
+        let page = state.cycles_page(Some(2), &paging(2, 0, None)).await;
+        assert_eq!(page.items.iter().map(|c| c.cycle_number).collect::<Vec<_>>(), [5, 6]);
+        assert_eq!((page.total, page.next_cursor.as_deref()), (4, Some("5")));
+        let older = state.cycles_page(Some(2), &paging(2, 0, Some(5))).await;
+        assert_eq!(older.items.iter().map(|c| c.cycle_number).collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(older.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_history_resolution_and_range() {
+        use crate::storage::sqlite::SqliteStorage;
+        let storage = Arc::new(SqliteStorage::open_in_memory().await.unwrap());
+        let start = "2026-03-01T22:00:00Z".parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        // Every 20 minutes for six hours, across midnight
+        let mut agent = AgentState::new(dec!(100));
+        for i in 0..18 {
+            agent.bankroll = Decimal::from(100 + i);
+            storage.append_balance(&BalanceSample::of(&agent, start + chrono::Duration::minutes(20 * i))).await.unwrap();
+        }
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_storage(storage));
+        let query = |resolution, from, to| Query(BalanceHistoryQuery { resolution, from, to });
+
+        let Json(raw) = get_balance_history(State(Arc::clone(&state)), query(Resolution::Raw, None, None)).await.unwrap();
+        assert_eq!(raw.len(), 18);
+        let Json(daily) = get_balance_history(State(Arc::clone(&state)), query(Resolution::Daily, None, None)).await.unwrap();
+        assert_eq!(daily.iter().map(|p| p.bankroll).collect::<Vec<_>>(), [105.0, 117.0]);
+
+        let from = start + chrono::Duration::hours(1);
+        let to = start + chrono::Duration::hours(3);
+        let Json(hourly) = get_balance_history(State(Arc::clone(&state)), query(Resolution::Hourly, Some(from), Some(to))).await.unwrap();
+        assert_eq!(hourly.iter().map(|p| p.bankroll).collect::<Vec<_>>(), [105.0, 108.0]);
+        assert_eq!(hourly[1].timestamp, "2026-03-02T00:40:00+00:00");
+
+        let backwards = get_balance_history(State(Arc::clone(&state)), query(Resolution::Raw, Some(to), Some(from))).await;
+        assert_eq!(backwards.unwrap_err(), StatusCode::BAD_REQUEST);
+        let without_store = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
+        let err = get_balance_history(State(without_store), Query(BalanceHistoryQuery::default())).await.unwrap_err();
+        assert_eq!(err, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_cycle_history_seeds_cycle_log() {
+        let history: Vec<CycleReport> = (1..=3)
+            .map(|n| CycleReport {
+                cycle_number: n,
+                timestamp: chrono::Utc::now() - chrono::Duration::minutes(10 - n as i64),
+                markets_scanned: 20,
+                edges_found: 2,
+                bets_placed: 1,
+                cycle_cost: dec!(0.10),
+                cycle_pnl: dec!(-0.10),
+                bankroll_after: Decimal::from(40 + n),
+                bets_failed: 0,
+                mana_bankroll_after: dec!(900),
+                status: Some(crate::types::AgentStatus::Alive),
+            })
+            .collect();
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(50))).with_cycle_history(&history));
+
+        let Json(Page { items: cycles, .. }) = get_cycles(State(state.clone()), all_cycles()).await.unwrap();
+        assert_eq!(cycles.iter().map(|c| c.cycle_number).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(cycles[2].status, "🟢 ALIVE");
+    }
+
+    #[tokio::test]
+    async fn test_get_model_comparison_none_by_default() {
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
+        let Json(report) = get_model_comparison(State(state)).await;
+        assert!(report.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_history_without_store_unavailable() {
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
+        let q = HistoryQuery { hours: 24, resolution: None };
+        let err = get_metrics_history(State(state), Query(q)).await.unwrap_err();
+        assert_eq!(err, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_trade_history_and_category_pnl() {
+        use crate::storage::sqlite::SqliteStorage;
+        let storage = Arc::new(SqliteStorage::open_in_memory().await.unwrap());
+        let mut receipt = TradeReceipt::dry_run("m1", dec!(10), "AUD");
+        receipt.category = Some(crate::types::MarketCategory::Sports);
+        receipt.raw_response = Some(serde_json::json!({"secret": "x"}));
+        storage.append_trade(&receipt).await.unwrap();
+        storage.settle_trade(&receipt.order_id, dec!(-10), chrono::Utc::now()).await.unwrap();
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_storage(storage));
+
+        let q = TradeHistoryQuery { from: None, to: None };
+        let Json(trades) = get_trade_history(State(Arc::clone(&state)), Query(q)).await.unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].pnl, Some(dec!(-10)));
+        assert!(trades[0].receipt.raw_response.is_none());
+
+        let q = TradeHistoryQuery { from: Some(chrono::Utc::now()), to: Some(chrono::Utc::now() - chrono::Duration::days(1)) };
+        assert_eq!(get_trade_history(State(Arc::clone(&state)), Query(q)).await.unwrap_err(), StatusCode::BAD_REQUEST);
+
+        let Json(categories) = get_pnl_by_category(State(Arc::clone(&state))).await.unwrap();
+        assert_eq!((categories[0].category.as_str(), categories[0].pnl), ("Sports", dec!(-10)));
+
+        let Json(performance) = get_performance(State(state)).await.unwrap();
+        assert_eq!(performance.by_category.len(), 1);
+        assert_eq!((performance.by_category[0].trades, performance.by_category[0].won), (1, 0));
+        assert_eq!(performance.by_platform[0].roi, -1.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_history_picks_source_by_range() {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))).with_metrics(store));
+        let source = |resp: Response| async move {
+            let body = axum::body::to_bytes(resp.into_body(), 1_000_000).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()["source"].clone()
+        };
+
+        let q = HistoryQuery { hours: 24 * 30, resolution: None };
+        let long = get_metrics_history(State(Arc::clone(&state)), Query(q)).await.unwrap();
+        assert_eq!(source(long).await, "rollup");
+
+        let q = HistoryQuery { hours: 6, resolution: None };
+        let short = get_metrics_history(State(state), Query(q)).await.unwrap();
+        assert_eq!(source(short).await, "raw");
+    }
+
+    #[tokio::test]
+    async fn test_get_risk_reports_adjustments() {
+        let mut agent = AgentState::new(dec!(100));
+        agent.thresholds.adjustments.push(crate::types::ThresholdAdjustment {
+            category: crate::types::MarketCategory::Weather,
+            previous: dec!(0.06),
+            threshold: dec!(0.07),
+            effective_at: chrono::Utc::now(),
+            resolutions: 120,
+        });
+        agent.cool_downs.categories.insert(
+            crate::types::MarketCategory::Politics,
+            crate::types::CategoryStreak {
+                consecutive_losses: 4,
+                cool_down_since: Some(chrono::Utc::now()),
+                skipped: 2,
+            },
+        );
+        agent.cool_downs.categories.insert(crate::types::MarketCategory::Sports, Default::default());
+        let state = Arc::new(DashboardState::new(agent));
+        *state.edge_thresholds.write().await = vec![CategoryThresholdView {
+            category: "Weather".to_string(),
+            configured: 0.06,
+            effective: 0.07,
+            frozen: false,
+        }];
+
+        let Json(risk) = get_risk(State(state)).await;
+        assert_eq!(risk.thresholds.len(), 1);
+        assert_eq!(risk.adjustments.len(), 1);
+        assert_eq!(risk.adjustments[0].category, "Weather");
+        assert_eq!(risk.adjustments[0].threshold, 0.07);
+        assert!(risk.last_review.is_none());
+        assert_eq!(risk.cool_downs.len(), 1);
+        assert_eq!(risk.cool_downs[0].category, "Politics");
+        assert_eq!((risk.cool_downs[0].consecutive_losses, risk.cool_downs[0].skipped), (4, 2));
+        assert!(risk.cool_downs[0].cool_down_since.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_calibration_lists_reference_weights() {
+        let mut agent = AgentState::new(dec!(100));
+        let weather = crate::types::MarketCategory::Weather;
+        crate::strategy::references::record_resolution(&mut agent, weather, &[("metaculus", 0.8), ("manifold", 0.6)], true);
+        agent.references.accuracy[1].weight = Some(0.25);
+        let state = Arc::new(DashboardState::new(agent));
+
+        let Json(cal) = get_calibration(State(state)).await;
+        let weights = &cal.references.weights;
+        assert_eq!(weights.len(), 2);
+        assert_eq!((weights[0].platform.as_str(), weights[0].category.as_str()), ("manifold", "Weather"));
+        assert_eq!(weights[0].weight, Some(0.25));
+        assert!((weights[0].brier.unwrap() - 0.16).abs() < 1e-9);
+        assert_eq!(weights[1].platform, "metaculus");
+        assert_eq!(weights[1].resolutions, 1);
+        assert!(weights[1].weight.is_none());
+        assert!(cal.references.last_review.is_none());
+        assert!(cal.estimates.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_calibration_serves_estimate_curve() {
+        use crate::backtest::calibration::{CalibrationPoint, Calibrator};
+        let state = Arc::new(DashboardState::new(AgentState::new(dec!(100))));
+        let mut calibrator = Calibrator::new();
+        calibrator.add_point(CalibrationPoint {
+            market_id: "m".into(),
+            category: crate::types::MarketCategory::Weather,
+            estimated_probability: 0.8,
+            resolved_yes: true,
+        });
+        *state.estimate_calibration.write().await = Some(calibrator.report());
+
+        let Json(cal) = get_calibration(State(state)).await;
+        let json = serde_json::to_value(&cal).unwrap();
+        let curve = json["estimates"]["calibration_curve"].as_array().unwrap();
+        assert_eq!(curve.len(), 10);
+        assert_eq!(curve[8]["count"], 1);
+        assert!((json["estimates"]["overall_brier"].as_f64().unwrap() - 0.04).abs() < 1e-12);
     }
 
     #[tokio::test]
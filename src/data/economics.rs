@@ -8,25 +8,57 @@
 //! Auth: API key via `api_key` query param. Free registration.
 //! Rate limit: 120 req/min.
 //!
-//! Key series: CPIAUCSL (CPI), UNRATE (unemployment), GDP, FEDFUNDS,
-//! T10YIE (breakeven inflation), DFF (effective fed funds rate).
+//! Key series: CPIAUCSL (CPI), CPILFESL (core CPI), UNRATE (unemployment),
+//! GDPC1 (real GDP), FEDFUNDS, T10YIE (breakeven inflation), DFF (effective
+//! fed funds rate).
+//!
+//! Question keywords pick the series: rules from `[[data_sources.fred_series]]`
+//! first, then the built-in table. Each series is summarised from its last
+//! 12 observations (latest value, change, trend) with the date of its next
+//! scheduled release. Economics questions no rule matches get a generic
+//! macro snapshot (CPI, unemployment, fed funds).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use super::DataProvider;
+use crate::question_parser;
 use crate::types::{DataContext, Market, MarketCategory};
 
+const FRED_BASE: &str = "https://api.stlouisfed.org/fred";
+
+/// Observations fetched per series.
+const OBSERVATIONS: usize = 12;
+
+/// Most series fetched for one question.
+const MAX_SERIES: usize = 4;
+
+/// Relative change over the window below which a series counts as flat.
+const FLAT_PCT: f64 = 0.5;
+
 // ---------------------------------------------------------------------------
 // FRED series mapping
 // ---------------------------------------------------------------------------
 
+/// Keyword → FRED series lookup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesRule {
+    /// Substrings of the lower-cased question, any of which selects the rule.
+    pub keywords: Vec<String>,
+    /// FRED series IDs, most relevant first.
+    pub series: Vec<String>,
+    pub description: String,
+}
+
 struct EconKeyword {
     keywords: &'static [&'static str],
     series_ids: &'static [&'static str],
@@ -36,7 +68,7 @@ struct EconKeyword {
 const ECON_KEYWORDS: &[EconKeyword] = &[
     EconKeyword {
         keywords: &["cpi", "inflation", "consumer price"],
-        series_ids: &["CPIAUCSL", "T10YIE", "MICH"],
+        series_ids: &["CPIAUCSL", "CPILFESL", "T10YIE", "MICH"],
         description: "CPI / Inflation",
     },
     EconKeyword {
@@ -46,11 +78,11 @@ const ECON_KEYWORDS: &[EconKeyword] = &[
     },
     EconKeyword {
         keywords: &["gdp", "gross domestic", "economic growth"],
-        series_ids: &["GDP", "GDPC1"],
+        series_ids: &["GDPC1", "GDP"],
         description: "GDP / Economic Growth",
     },
     EconKeyword {
-        keywords: &["fed ", "federal reserve", "interest rate", "rate cut", "rate hike", "fomc"],
+        keywords: &["fed ", "federal reserve", "interest rate", "rate cut", "rate hike", "fomc", "fed funds"],
         series_ids: &["DFF", "FEDFUNDS", "T10Y2Y"],
         description: "Federal Reserve / Interest Rates",
     },
@@ -81,6 +113,23 @@ const ECON_KEYWORDS: &[EconKeyword] = &[
     },
 ];
 
+/// Series for economics questions no rule matches.
+const SNAPSHOT: EconKeyword = EconKeyword {
+    keywords: &[],
+    series_ids: &["CPIAUCSL", "UNRATE", "DFF"],
+    description: "Macro snapshot",
+};
+
+impl EconKeyword {
+    fn rule(&self) -> SeriesRule {
+        SeriesRule {
+            keywords: self.keywords.iter().map(|k| k.to_string()).collect(),
+            series: self.series_ids.iter().map(|s| s.to_string()).collect(),
+            description: self.description.to_string(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // FRED API response types
 // ---------------------------------------------------------------------------
@@ -91,39 +140,173 @@ struct FredResponse {
     observations: Vec<FredObservation>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct FredObservation {
     date: String,
+    /// `"."` when the period has no value.
     value: String,
 }
 
+impl FredObservation {
+    fn parsed(&self) -> Option<(NaiveDate, f64)> {
+        let date = NaiveDate::parse_from_str(&self.date, "%Y-%m-%d").ok()?;
+        Some((date, self.value.parse().ok()?))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FredReleases {
+    #[serde(default)]
+    releases: Vec<FredRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FredRelease {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FredReleaseDates {
+    #[serde(default)]
+    release_dates: Vec<FredReleaseDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FredReleaseDate {
+    date: String,
+}
+
+impl FredReleaseDates {
+    /// First scheduled release on or after `today`.
+    fn next_after(&self, today: NaiveDate) -> Option<NaiveDate> {
+        self.release_dates
+            .iter()
+            .filter_map(|r| NaiveDate::parse_from_str(&r.date, "%Y-%m-%d").ok())
+            .filter(|&d| d >= today)
+            .min()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Series summaries
+// ---------------------------------------------------------------------------
+
+/// One series as fetched for a question.
+struct SeriesData {
+    id: String,
+    description: String,
+    /// Newest first, as FRED returns them with `sort_order=desc`.
+    observations: Vec<FredObservation>,
+    next_release: Option<NaiveDate>,
+}
+
+/// Latest value, change on the previous observation and trend over the
+/// window, plus days until the next release. `None` without a value.
+fn summarise_series(series: &SeriesData, today: NaiveDate) -> Option<String> {
+    let values: Vec<(NaiveDate, f64)> = series.observations.iter().filter_map(FredObservation::parsed).collect();
+    let &(latest_date, latest) = values.first()?;
+    let mut lines = vec![format!("{} ({}): latest {latest:.2} ({latest_date})", series.description, series.id)];
+
+    if let Some(&(_, previous)) = values.get(1) {
+        lines.push(format!("  change on previous: {}", change(previous, latest)));
+    }
+    if let Some(&(oldest_date, oldest)) = values.last().filter(|_| values.len() > 2) {
+        let pct = if oldest != 0.0 { (latest - oldest) / oldest.abs() * 100.0 } else { 0.0 };
+        let trend = if pct > FLAT_PCT {
+            "rising"
+        } else if pct < -FLAT_PCT {
+            "falling"
+        } else {
+            "flat"
+        };
+        lines.push(format!("  trend: {trend} over {} observations ({} since {oldest_date})", values.len(), change(oldest, latest)));
+    }
+    let recent: Vec<String> = values.iter().take(6).map(|(d, v)| format!("{d} {v:.2}")).collect();
+    lines.push(format!("  recent: {}", recent.join(", ")));
+    match series.next_release {
+        Some(next) => {
+            let days = (next - today).num_days();
+            let when = if days == 0 { "today".to_string() } else { format!("in {days} days") };
+            lines.push(format!("  next release: {next} ({when})"));
+        }
+        None => lines.push("  next release: not scheduled".to_string()),
+    }
+    Some(lines.join("\n"))
+}
+
+/// `+0.72 (+0.2%)`; the percentage is left out from a zero base.
+fn change(from: f64, to: f64) -> String {
+    let diff = to - from;
+    if from == 0.0 {
+        format!("{diff:+.2}")
+    } else {
+        format!("{diff:+.2} ({:+.1}%)", diff / from.abs() * 100.0)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Provider
 // ---------------------------------------------------------------------------
 
-pub struct EconomicsProvider {
+pub struct FredProvider {
     http: Client,
     fred_api_key: Option<String>,
+    /// Configured rules ahead of the built-in table.
+    rules: Vec<SeriesRule>,
+    /// Next release date per series and the day it was looked up; kept
+    /// until the date passes (unscheduled ones for the day).
+    next_releases: Mutex<HashMap<String, (NaiveDate, Option<NaiveDate>)>>,
 }
 
-impl EconomicsProvider {
+impl FredProvider {
     pub fn new(fred_api_key: Option<String>) -> Result<Self> {
         let http = Client::builder()
             .timeout(std::time::Duration::from_secs(15))
             .user_agent("ORACLE/0.1.0")
             .build()
             .context("Failed to build economics HTTP client")?;
-        Ok(Self { http, fred_api_key })
+        Ok(Self {
+            http,
+            fred_api_key,
+            rules: ECON_KEYWORDS.iter().map(EconKeyword::rule).collect(),
+            next_releases: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Match `rules` before the built-in table.
+    pub fn with_series(mut self, rules: Vec<SeriesRule>) -> Self {
+        let rules = rules.into_iter().map(|mut r| {
+            r.keywords = r.keywords.iter().map(|k| k.to_lowercase()).collect();
+            r
+        });
+        self.rules.splice(0..0, rules);
+        self
     }
 
-    /// Match market question to relevant FRED series.
-    fn match_series(question: &str) -> Vec<&'static EconKeyword> {
+    /// Rules whose keywords appear in the question.
+    fn match_series(&self, question: &str) -> Vec<&SeriesRule> {
         let q = question.to_lowercase();
-        ECON_KEYWORDS.iter()
-            .filter(|ek| ek.keywords.iter().any(|kw| q.contains(kw)))
+        self.rules.iter()
+            .filter(|rule| rule.keywords.iter().any(|kw| q.contains(kw.as_str())))
             .collect()
     }
 
+    /// Series to fetch for `matched` rules: each rule's first series, then
+    /// each one's second and so on, without repeats, up to [`MAX_SERIES`].
+    fn series_to_fetch<'a>(matched: &[&'a SeriesRule]) -> Vec<(&'a str, &'a str)> {
+        let mut picked: Vec<(&str, &str)> = Vec::new();
+        let depth = matched.iter().map(|r| r.series.len()).max().unwrap_or(0);
+        for i in 0..depth {
+            for rule in matched {
+                let Some(id) = rule.series.get(i) else { continue };
+                if picked.len() < MAX_SERIES && !picked.iter().any(|(p, _)| p == id) {
+                    picked.push((id, &rule.description));
+                }
+            }
+        }
+        picked
+    }
+
     /// Fetch recent observations for a FRED series.
     async fn fetch_fred_series(
         &self,
@@ -131,12 +314,43 @@ impl EconomicsProvider {
         api_key: &str,
     ) -> Result<Vec<FredObservation>> {
         let url = format!(
-            "https://api.stlouisfed.org/fred/series/observations?\
+            "{FRED_BASE}/series/observations?\
              series_id={series_id}&api_key={api_key}\
-             &file_type=json&sort_order=desc&limit=12"
+             &file_type=json&sort_order=desc&limit={OBSERVATIONS}"
         );
+        let data: FredResponse = self.get_json(&url, series_id).await?;
+        Ok(data.observations)
+    }
 
-        let resp = self.http.get(&url).send().await
+    /// Next scheduled release of the series' release, cached until it passes.
+    async fn next_release(&self, series_id: &str, api_key: &str, today: NaiveDate) -> Result<Option<NaiveDate>> {
+        let cached = self.next_releases.lock().unwrap_or_else(|e| e.into_inner()).get(series_id).copied();
+        if let Some((checked, next)) = cached {
+            if checked == today || next.is_some_and(|d| d >= today) {
+                return Ok(next);
+            }
+        }
+        let url = format!("{FRED_BASE}/series/release?series_id={series_id}&api_key={api_key}&file_type=json");
+        let releases: FredReleases = self.get_json(&url, series_id).await?;
+        let next = match releases.releases.first() {
+            Some(release) => {
+                let url = format!(
+                    "{FRED_BASE}/release/dates?release_id={}&api_key={api_key}&file_type=json\
+                     &realtime_start={today}&realtime_end=9999-12-31\
+                     &include_release_dates_with_no_data=true&sort_order=asc&limit=5",
+                    release.id
+                );
+                let dates: FredReleaseDates = self.get_json(&url, series_id).await?;
+                dates.next_after(today)
+            }
+            None => None,
+        };
+        self.next_releases.lock().unwrap_or_else(|e| e.into_inner()).insert(series_id.to_string(), (today, next));
+        Ok(next)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str, series_id: &str) -> Result<T> {
+        let resp = self.http.get(url).send().await
             .context(format!("FRED request failed for {series_id}"))?;
 
         if !resp.status().is_success() {
@@ -144,42 +358,25 @@ impl EconomicsProvider {
             anyhow::bail!("FRED API error for {series_id}: {status}");
         }
 
-        let data: FredResponse = resp.json().await
-            .context(format!("Failed to parse FRED response for {series_id}"))?;
-
-        Ok(data.observations)
+        resp.json().await
+            .context(format!("Failed to parse FRED response for {series_id}"))
     }
 
     /// Build summary from FRED data.
-    fn build_summary(
-        matched: &[&EconKeyword],
-        observations: &[(String, Vec<FredObservation>)],
-        market: &Market,
-    ) -> String {
+    fn build_summary(series: &[SeriesData], market: &Market, today: NaiveDate) -> String {
         let mut parts = Vec::new();
         parts.push("Economics context (FRED data):".to_string());
 
-        for (series_id, obs) in observations {
-            if obs.is_empty() {
-                continue;
-            }
-            // Find which keyword group this series belongs to
-            let desc = matched.iter()
-                .find(|ek| ek.series_ids.contains(&series_id.as_str()))
-                .map(|ek| ek.description)
-                .unwrap_or("Economic indicator");
-
-            parts.push(format!("\n{desc} ({series_id}):"));
-
-            // Show last few data points
-            let show = obs.len().min(6);
-            for o in &obs[..show] {
-                if o.value != "." {
-                    parts.push(format!("  {}: {}", o.date, o.value));
-                }
+        for data in series {
+            if let Some(summary) = summarise_series(data, today) {
+                parts.push(format!("\n{summary}"));
             }
         }
 
+        if let Some(terms) = question_parser::describe(&question_parser::facts(market)) {
+            parts.push(format!("Question terms: {terms}"));
+        }
+
         // Cross-references
         if let Some(prob) = market.cross_refs.manifold_prob {
             parts.push(format!("\nManifold market probability: {:.1}%", prob * dec!(100)));
@@ -196,18 +393,22 @@ impl EconomicsProvider {
     }
 
     /// Build a keyword-only summary when no FRED key is available.
-    fn keyword_only_summary(matched: &[&EconKeyword], market: &Market) -> String {
+    fn keyword_only_summary(matched: &[&SeriesRule], market: &Market) -> String {
         let mut parts = Vec::new();
         parts.push("Economics context (no FRED API key, keyword-only):".to_string());
 
-        for ek in matched {
+        for rule in matched {
             parts.push(format!(
                 "Relevant indicator: {} (FRED series: {})",
-                ek.description,
-                ek.series_ids.join(", ")
+                rule.description,
+                rule.series.join(", ")
             ));
         }
 
+        if let Some(terms) = question_parser::describe(&question_parser::facts(market)) {
+            parts.push(format!("Question terms: {terms}"));
+        }
+
         if let Some(prob) = market.cross_refs.manifold_prob {
             parts.push(format!("Manifold market probability: {:.1}%", prob * dec!(100)));
         }
@@ -225,54 +426,58 @@ impl EconomicsProvider {
 }
 
 #[async_trait]
-impl DataProvider for EconomicsProvider {
+impl DataProvider for FredProvider {
     fn category(&self) -> MarketCategory {
         MarketCategory::Economics
     }
 
     async fn fetch_context(&self, market: &Market) -> Result<DataContext> {
-        let matched = Self::match_series(&market.question);
-
+        let snapshot = SNAPSHOT.rule();
+        let mut matched = self.match_series(&market.question);
         if matched.is_empty() {
-            debug!(question = %market.question, "No economic indicators matched");
-            return Ok(DataContext::empty(MarketCategory::Economics));
+            debug!(question = %market.question, "No economic indicators matched — using macro snapshot");
+            matched.push(&snapshot);
         }
 
-        let (summary, raw_data, cost) = match &self.fred_api_key {
+        let (summary, raw_data) = match &self.fred_api_key {
             Some(key) => {
-                // Fetch data for matched series (limit to first 3 to control costs)
-                let mut observations = Vec::new();
-                let series_to_fetch: Vec<&str> = matched.iter()
-                    .flat_map(|ek| ek.series_ids.iter().copied())
-                    .collect::<Vec<_>>();
-                let series_to_fetch: Vec<&str> = series_to_fetch.into_iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .into_iter()
-                    .take(4)
-                    .collect();
-
-                for series_id in &series_to_fetch {
-                    match self.fetch_fred_series(series_id, key).await {
-                        Ok(obs) => observations.push((series_id.to_string(), obs)),
-                        Err(e) => debug!(series = series_id, error = %e, "FRED fetch failed"),
-                    }
+                let today = Utc::now().date_naive();
+                let mut series = Vec::new();
+                for (series_id, description) in Self::series_to_fetch(&matched) {
+                    let observations = match self.fetch_fred_series(series_id, key).await {
+                        Ok(obs) => obs,
+                        Err(e) => {
+                            debug!(series = series_id, error = %e, "FRED fetch failed");
+                            continue;
+                        }
+                    };
+                    let next_release = self.next_release(series_id, key, today).await.unwrap_or_else(|e| {
+                        debug!(series = series_id, error = %e, "FRED release calendar fetch failed");
+                        None
+                    });
+                    series.push(SeriesData {
+                        id: series_id.to_string(),
+                        description: description.to_string(),
+                        observations,
+                        next_release,
+                    });
                 }
 
-                let raw = serde_json::to_value(&observations
+                let raw = serde_json::Value::Object(series
                     .iter()
-                    .map(|(id, obs)| {
-                        (id.clone(), obs.iter().map(|o| format!("{}={}", o.date, o.value)).collect::<Vec<_>>())
+                    .map(|s| {
+                        let observations: Vec<_> = s.observations.iter().map(|o| format!("{}={}", o.date, o.value)).collect();
+                        (s.id.clone(), serde_json::json!({
+                            "description": s.description,
+                            "observations": observations,
+                            "next_release": s.next_release,
+                        }))
                     })
-                    .collect::<Vec<_>>())
-                    .unwrap_or_default();
+                    .collect());
 
-                let summary = Self::build_summary(&matched, &observations, market);
-                (summary, raw, Decimal::ZERO) // FRED is free
-            }
-            None => {
-                let summary = Self::keyword_only_summary(&matched, market);
-                (summary, serde_json::Value::Null, Decimal::ZERO)
+                (Self::build_summary(&series, market, today), raw)
             }
+            None => (Self::keyword_only_summary(&matched, market), serde_json::Value::Null),
         };
 
         Ok(DataContext {
@@ -281,10 +486,11 @@ impl DataProvider for EconomicsProvider {
             summary,
             freshness: Utc::now(),
             source: if self.fred_api_key.is_some() { "fred".to_string() } else { "keyword-extraction".to_string() },
-            cost,
+            cost: Decimal::ZERO, // FRED is free
             metaculus_forecast: market.cross_refs.metaculus_prob,
             metaculus_forecasters: market.cross_refs.metaculus_forecasters,
             manifold_price: market.cross_refs.manifold_prob,
+            sections: Vec::new(),
         })
     }
 
@@ -300,58 +506,143 @@ impl DataProvider for EconomicsProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::d;
+
+    fn provider() -> FredProvider {
+        FredProvider::new(None).unwrap()
+    }
+
+    fn fetched(question: &str, provider: &FredProvider) -> Vec<String> {
+        let matched = provider.match_series(question);
+        FredProvider::series_to_fetch(&matched).into_iter().map(|(id, _)| id.to_string()).collect()
+    }
+
+    fn market(question: &str) -> Market {
+        Market {
+            id: "test".into(), platform: "manifold".into(),
+            question: question.into(),
+            description: String::new(), category: MarketCategory::Economics,
+            current_price_yes: d(0.4), current_price_no: d(0.6),
+            volume_24h: d(100.0), liquidity: d(200.0),
+            deadline: Utc::now() + chrono::Duration::days(30),
+            resolution_criteria: String::new(),
+            url: "https://example.com".into(),
+            cross_refs: crate::types::CrossReferences::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
 
     #[test]
     fn test_match_series_cpi() {
-        let matched = EconomicsProvider::match_series("Will US CPI exceed 3% in Q2 2026?");
+        let p = provider();
+        let matched = p.match_series("Will US CPI exceed 3% in Q2 2026?");
         assert!(!matched.is_empty());
-        assert!(matched[0].series_ids.contains(&"CPIAUCSL"));
+        assert!(matched[0].series.contains(&"CPIAUCSL".to_string()));
     }
 
     #[test]
     fn test_match_series_fed() {
-        let matched = EconomicsProvider::match_series("Will the Fed cut interest rates in 2026?");
+        let p = provider();
+        let matched = p.match_series("Will the Fed cut interest rates in 2026?");
         assert!(!matched.is_empty());
         assert!(matched[0].description.contains("Federal Reserve"));
     }
 
     #[test]
     fn test_match_series_multiple() {
-        let matched = EconomicsProvider::match_series(
-            "Will inflation and unemployment both rise causing a recession?"
-        );
+        let p = provider();
+        let matched = p.match_series("Will inflation and unemployment both rise causing a recession?");
         assert!(matched.len() >= 2, "Should match inflation + unemployment + recession");
+        // Each rule's lead series before any rule's second, capped.
+        assert_eq!(
+            fetched("Will inflation and unemployment both rise causing a recession?", &p),
+            ["CPIAUCSL", "UNRATE", "T10Y2Y", "CPILFESL"]
+        );
     }
 
     #[test]
     fn test_match_series_none() {
-        let matched = EconomicsProvider::match_series("Will it rain tomorrow?");
-        assert!(matched.is_empty());
+        let p = provider();
+        assert!(p.match_series("Will it rain tomorrow?").is_empty());
+    }
+
+    #[test]
+    fn test_inflation_fetches_headline_and_core_cpi() {
+        let series = fetched("Will inflation top 3% this year?", &provider());
+        assert_eq!(&series[..2], ["CPIAUCSL", "CPILFESL"]);
+    }
+
+    #[test]
+    fn test_configured_rules_take_precedence() {
+        let p = provider().with_series(vec![SeriesRule {
+            keywords: vec!["PCE".to_string(), "inflation".to_string()],
+            series: vec!["PCEPILFE".to_string()],
+            description: "Core PCE".to_string(),
+        }]);
+        assert_eq!(fetched("Will core PCE inflation exceed 2.5%?", &p), ["PCEPILFE", "CPIAUCSL", "CPILFESL", "T10YIE"]);
+        // Built-in rules still apply.
+        assert_eq!(fetched("Will unemployment exceed 5%?", &p), ["UNRATE", "PAYEMS", "ICSA"]);
     }
 
     #[test]
     fn test_keyword_only_summary() {
-        use crate::types::d;
-        let matched = EconomicsProvider::match_series("Will US CPI exceed 3%?");
-        let market = Market {
-            id: "test".into(), platform: "manifold".into(),
-            question: "Will US CPI exceed 3%?".into(),
-            description: String::new(), category: MarketCategory::Economics,
-            current_price_yes: d(0.4), current_price_no: d(0.6),
-            volume_24h: d(100.0), liquidity: d(200.0),
-            deadline: Utc::now() + chrono::Duration::days(30),
-            resolution_criteria: String::new(),
-            url: "https://example.com".into(),
-            cross_refs: crate::types::CrossReferences::default(),
-        };
-        let summary = EconomicsProvider::keyword_only_summary(&matched, &market);
+        let p = provider();
+        let matched = p.match_series("Will US CPI exceed 3%?");
+        let summary = FredProvider::keyword_only_summary(&matched, &market("Will US CPI exceed 3%?"));
         assert!(summary.contains("CPIAUCSL"));
         assert!(summary.contains("FRED_API_KEY"));
+        assert!(summary.contains("Question terms: above 3%"));
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_question_falls_back_to_macro_snapshot() {
+        let context = provider().fetch_context(&market("Will the economy be fine?")).await.unwrap();
+        assert!(context.summary.contains("Macro snapshot (FRED series: CPIAUCSL, UNRATE, DFF)"), "{}", context.summary);
+    }
+
+    fn canned_series() -> SeriesData {
+        let observations: FredResponse = serde_json::from_str(&crate::fixtures::read("fred/series-observations.json")).unwrap();
+        let dates: FredReleaseDates = serde_json::from_str(&crate::fixtures::read("fred/release-dates.json")).unwrap();
+        let releases: FredReleases = serde_json::from_str(&crate::fixtures::read("fred/series-release.json")).unwrap();
+        assert_eq!(releases.releases[0].id, 10);
+        SeriesData {
+            id: "CPIAUCSL".to_string(),
+            description: "CPI / Inflation".to_string(),
+            observations: observations.observations,
+            next_release: dates.next_after(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_summary_from_canned_observations() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let summary = summarise_series(&canned_series(), today).unwrap();
+        assert!(summary.starts_with("CPI / Inflation (CPIAUCSL): latest 326.12 (2026-09-01)"), "{summary}");
+        assert!(summary.contains("trend: rising over 11 observations"), "{summary}");
+        assert!(summary.contains("since 2025-10-01"), "{summary}");
+        assert!(summary.contains("next release: 2026-11-13 (in 28 days)"), "{summary}");
+
+        let full = FredProvider::build_summary(&[canned_series()], &market("Will CPI exceed 330?"), today);
+        assert!(full.starts_with("Economics context (FRED data):"));
+        assert!(full.contains("latest 326.12"));
+    }
+
+    #[test]
+    fn test_summary_needs_a_value() {
+        let series = SeriesData {
+            id: "DFF".to_string(),
+            description: "Fed funds".to_string(),
+            observations: vec![FredObservation { date: "2026-10-15".to_string(), value: ".".to_string() }],
+            next_release: None,
+        };
+        assert!(summarise_series(&series, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()).is_none());
     }
 
     #[test]
     fn test_provider_category() {
-        let p = EconomicsProvider::new(None).unwrap();
+        let p = provider();
         assert_eq!(p.category(), MarketCategory::Economics);
         assert_eq!(p.cost_per_call(), Decimal::ZERO);
     }
@@ -0,0 +1,152 @@
+//! Manifold trade-flow sentiment signal.
+//!
+//! Summarises the recent bet feed for a Manifold market (`/v0/bets?contractId=`)
+//! into net signed flow, distinct bettors and the 24h price path, rendered as
+//! a single line appended to the market's `DataContext` summary. The feed is
+//! public, so this signal is cost-free.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::platforms::manifold::ManifoldFeedBet;
+
+/// Aggregated flow over the look-back window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowSummary {
+    /// Net mana towards YES (positive) or NO (negative).
+    pub net_flow: f64,
+    /// Distinct users with at least one counted bet.
+    pub bettors: usize,
+    /// Probability before the earliest counted bet.
+    pub price_start: f64,
+    /// Probability after the latest counted bet.
+    pub price_end: f64,
+}
+
+impl FlowSummary {
+    /// One-line summary for the LLM prompt, e.g.
+    /// `Manifold flow: +M$840 net YES from 17 bettors, price 52%→58% in 24h`.
+    pub fn summary_line(&self) -> String {
+        let side = if self.net_flow >= 0.0 { "YES" } else { "NO" };
+        let sign = if self.net_flow >= 0.0 { '+' } else { '-' };
+        format!(
+            "Manifold flow: {sign}M${:.0} net {side} from {} bettor{}, price {:.0}%→{:.0}% in 24h",
+            self.net_flow.abs(),
+            self.bettors,
+            if self.bettors == 1 { "" } else { "s" },
+            self.price_start * 100.0,
+            self.price_end * 100.0,
+        )
+    }
+}
+
+/// Summarise bets placed within `window` before `now`.
+///
+/// Buying YES (or selling NO) counts as YES flow; buying NO (or selling
+/// YES) counts as NO flow — sales carry a negative `amount`, so the sign
+/// falls out of `amount` × outcome. Cancelled orders and redemptions are
+/// ignored. Returns `None` when no bets fall inside the window.
+pub fn summarize_flow(
+    bets: &[ManifoldFeedBet],
+    now: DateTime<Utc>,
+    window: Duration,
+) -> Option<FlowSummary> {
+    let cutoff = (now - window).timestamp_millis();
+
+    let mut counted: Vec<&ManifoldFeedBet> = bets
+        .iter()
+        .filter(|b| !b.is_cancelled && !b.is_redemption && b.created_time >= cutoff)
+        .filter(|b| matches!(b.outcome.as_str(), "YES" | "NO"))
+        .collect();
+    if counted.is_empty() {
+        return None;
+    }
+    // The feed is newest-first; order chronologically for the price path.
+    counted.sort_by_key(|b| b.created_time);
+
+    let net_flow = counted
+        .iter()
+        .map(|b| if b.outcome == "YES" { b.amount } else { -b.amount })
+        .sum();
+
+    let mut users: Vec<&str> = counted.iter().map(|b| b.user_id.as_str()).collect();
+    users.sort_unstable();
+    users.dedup();
+
+    Some(FlowSummary {
+        net_flow,
+        bettors: users.len(),
+        price_start: counted[0].prob_before,
+        price_end: counted[counted.len() - 1].prob_after,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Recorded `/v0/bets` response (trimmed), newest first.
+    const FIXTURE: &str = r#"[
+        {"id":"b6","userId":"u3","contractId":"c1","amount":-40,"shares":-70.1,"outcome":"NO",
+         "probBefore":0.57,"probAfter":0.58,"createdTime":1760000000000,"isCancelled":false,"isRedemption":false},
+        {"id":"b5","userId":"u1","contractId":"c1","amount":300,"shares":540.2,"outcome":"YES",
+         "probBefore":0.55,"probAfter":0.57,"createdTime":1759990000000,"isRedemption":false},
+        {"id":"b4","userId":"u4","contractId":"c1","amount":-12.5,"shares":-12.5,"outcome":"YES",
+         "probBefore":0.55,"probAfter":0.55,"createdTime":1759985000000,"isRedemption":true},
+        {"id":"b3","userId":"u2","contractId":"c1","amount":100,"shares":210.0,"outcome":"NO",
+         "probBefore":0.56,"probAfter":0.55,"createdTime":1759980000000},
+        {"id":"b2","userId":"u1","contractId":"c1","amount":500,"shares":930.4,"outcome":"YES",
+         "probBefore":0.52,"probAfter":0.56,"createdTime":1759970000000,"isCancelled":true},
+        {"id":"b1","userId":"u5","contractId":"c1","amount":200,"shares":380.0,"outcome":"YES",
+         "probBefore":0.52,"probAfter":0.56,"createdTime":1759950000000},
+        {"id":"b0","userId":"u6","contractId":"c1","amount":1000,"shares":1900.0,"outcome":"YES",
+         "probBefore":0.40,"probAfter":0.52,"createdTime":1759800000000}
+    ]"#;
+
+    fn fixture() -> Vec<ManifoldFeedBet> {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(1_760_000_000_000 + 60_000).unwrap()
+    }
+
+    #[test]
+    fn test_flow_from_recorded_feed() {
+        let s = summarize_flow(&fixture(), now(), Duration::hours(24)).unwrap();
+        // b1 +200, b3 -100, b5 +300, b6 (sell NO) +40; b0 outside window,
+        // b2 cancelled, b4 redemption.
+        assert!((s.net_flow - 440.0).abs() < 1e-9);
+        assert_eq!(s.bettors, 4);
+        assert!((s.price_start - 0.52).abs() < 1e-9);
+        assert!((s.price_end - 0.58).abs() < 1e-9);
+        assert_eq!(
+            s.summary_line(),
+            "Manifold flow: +M$440 net YES from 4 bettors, price 52%→58% in 24h"
+        );
+    }
+
+    #[test]
+    fn test_flow_net_no() {
+        let bets: Vec<ManifoldFeedBet> = serde_json::from_str(
+            r#"[{"userId":"u1","amount":250,"outcome":"NO","probBefore":0.6,"probAfter":0.55,"createdTime":1760000000000}]"#,
+        )
+        .unwrap();
+        let s = summarize_flow(&bets, now(), Duration::hours(24)).unwrap();
+        assert_eq!(
+            s.summary_line(),
+            "Manifold flow: -M$250 net NO from 1 bettor, price 60%→55% in 24h"
+        );
+    }
+
+    #[test]
+    fn test_flow_empty_window() {
+        let later = now() + Duration::days(3);
+        assert!(summarize_flow(&fixture(), later, Duration::hours(24)).is_none());
+        assert!(summarize_flow(&[], now(), Duration::hours(24)).is_none());
+    }
+}
@@ -7,6 +7,7 @@ pub mod weather;
 pub mod sports;
 pub mod economics;
 pub mod news;
+pub mod manifold_flow;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -28,4 +29,8 @@ pub trait DataProvider: Send + Sync {
 
     /// Cost per API call in USD (for survival accounting).
     fn cost_per_call(&self) -> Decimal;
+
+    /// Called at the start of each enrichment batch (once per cycle), for
+    /// providers that budget their requests per cycle.
+    fn begin_cycle(&self) {}
 }
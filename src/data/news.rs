@@ -1,23 +1,31 @@
 //! News and sentiment data provider.
 //!
 //! Provides news context for Politics, Culture, and Other market categories.
-//! Uses keyword extraction from market questions to build context summaries.
-//! Full NewsAPI integration available when API key is configured.
+//! Up to five salient keywords are extracted from the market question —
+//! parsed entities first, then significant words, with stopwords, years
+//! and generic market phrasing ("will", "by 2026", "before the end of")
+//! dropped — and searched on NewsAPI. Headlines are ranked towards the
+//! last 72 hours and listed with their source and age; the raw articles
+//! are kept in `raw_data` for audit. Without an API key, or once the
+//! request quota is spent, the context is keyword-only.
 //!
 //! API: `https://newsapi.org/v2/everything`
-//! Auth: API key via `apiKey` query param. Free tier: 100 req/day.
+//! Auth: API key via `apiKey` query param. Free tier: 100 req/day, enforced
+//! with an internal token bucket.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::Deserialize;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use super::DataProvider;
-use crate::types::{DataContext, Market, MarketCategory};
+use crate::platforms::rate_limit::TokenBucket;
+use crate::question_parser;
+use crate::types::{DataContext, Market, MarketCategory, QuestionFacts};
 
 // ---------------------------------------------------------------------------
 // Topic classification
@@ -77,16 +85,94 @@ const NEWS_TOPICS: &[NewsTopic] = &[
     },
 ];
 
+// ---------------------------------------------------------------------------
+// Keyword extraction
+// ---------------------------------------------------------------------------
+
+/// Most keywords searched per question.
+const MAX_KEYWORDS: usize = 5;
+
+const STOP_WORDS: &[&str] = &[
+    "will", "the", "be", "in", "a", "an", "is", "it", "its", "of", "to", "for", "and", "or",
+    "by", "at", "on", "this", "that", "these", "those", "before", "after", "than", "more",
+    "less", "above", "below", "between", "with", "from", "into", "over", "under", "as",
+    "are", "was", "were", "been", "has", "have", "had", "do", "does", "did", "not", "any",
+    "his", "her", "their", "who", "what", "when", "which", "how", "there", "they", "he",
+    "she", "we", "you", "can", "could", "would", "should", "during", "until", "least",
+];
+
+/// Market phrasing that says nothing about the subject of the question.
+const GENERIC_TERMS: &[&str] = &[
+    "happen", "occur", "resolve", "resolves", "market", "question", "yes", "end", "start",
+    "next", "year", "years", "month", "months", "week", "weeks", "day", "days", "date",
+    "time", "still", "again", "first", "new", "reach", "exceed", "announce", "officially",
+    "january", "february", "march", "april", "may", "june", "july", "august", "september",
+    "october", "november", "december", "jan", "feb", "mar", "apr", "jun", "jul", "aug",
+    "sep", "sept", "oct", "nov", "dec", "q1", "q2", "q3", "q4",
+];
+
+/// Whether `word` carries no search value: a stopword, generic market
+/// phrasing, a number or year, or under three characters.
+fn is_filler(word: &str) -> bool {
+    let w = word.trim_matches('\'').to_lowercase();
+    w.chars().count() < 3
+        || w.chars().all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+        || STOP_WORDS.contains(&w.as_str())
+        || GENERIC_TERMS.contains(&w.as_str())
+}
+
+/// Up to [`MAX_KEYWORDS`] salient keywords for a question: parsed entities
+/// first (without their generic words — "By March 2026" yields nothing),
+/// topped up with significant words in question order.
+pub fn keywords(facts: &QuestionFacts, question: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    let mut covered: Vec<String> = Vec::new();
+    let mut push = |term: String, keywords: &mut Vec<String>| {
+        let words: Vec<String> = term.split_whitespace().map(str::to_lowercase).collect();
+        if keywords.len() < MAX_KEYWORDS && !words.iter().all(|w| covered.contains(w)) {
+            covered.extend(words);
+            keywords.push(term);
+        }
+    };
+    for entity in &facts.entities {
+        let kept: Vec<&str> = entity.split_whitespace().filter(|w| !is_filler(w)).collect();
+        if !kept.is_empty() {
+            push(kept.join(" "), &mut keywords);
+        }
+    }
+    for word in question.split(|c: char| !c.is_alphanumeric() && c != '\'') {
+        let word = word.trim_matches('\'');
+        if !is_filler(word) {
+            push(word.to_string(), &mut keywords);
+        }
+    }
+    keywords
+}
+
+// ---------------------------------------------------------------------------
+// Rate limiting
+// ---------------------------------------------------------------------------
+
+/// Requests allowed per day on the NewsAPI free tier.
+pub const FREE_TIER_REQUESTS_PER_DAY: u32 = 100;
+
+/// Most requests spent back to back.
+const BURST: f64 = 10.0;
+
+/// Token bucket over a daily request quota. The bucket holds at most
+/// `min(BURST, quota)` tokens and refills the rest of the quota evenly over
+/// the day, so no 24-hour window sees more than `quota` requests.
+fn daily_quota(quota: u32) -> TokenBucket {
+    let capacity = BURST.min(quota as f64);
+    TokenBucket::new(capacity, (quota as f64 - capacity).max(0.0) / 86_400.0)
+}
+
 // ---------------------------------------------------------------------------
 // NewsAPI response types
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Deserialize)]
 struct NewsApiResponse {
-    #[serde(default)]
-    status: String,
-    #[serde(default, rename = "totalResults")]
-    total_results: u32,
     #[serde(default)]
     articles: Vec<NewsArticle>,
 }
@@ -96,13 +182,62 @@ struct NewsArticle {
     #[serde(default)]
     title: Option<String>,
     #[serde(default)]
-    description: Option<String>,
-    #[serde(default)]
     source: Option<NewsSource>,
     #[serde(default, rename = "publishedAt")]
     published_at: Option<String>,
 }
 
+impl NewsArticle {
+    fn published(&self) -> Option<DateTime<Utc>> {
+        let at = DateTime::parse_from_rfc3339(self.published_at.as_deref()?).ok()?;
+        Some(at.with_timezone(&Utc))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Recency
+// ---------------------------------------------------------------------------
+
+/// Articles this recent carry full weight.
+const RECENT_HOURS: f64 = 72.0;
+
+/// Headlines listed in a summary.
+const MAX_HEADLINES: usize = 5;
+
+/// Longest headline listed, in characters.
+const MAX_TITLE_CHARS: usize = 120;
+
+/// Weight of an article published `age` ago: 1 within [`RECENT_HOURS`],
+/// halving every further 72 hours. Undated articles weigh as a week old.
+fn recency_weight(age: Option<Duration>) -> f64 {
+    let hours = age.map_or(168.0, |a| a.num_minutes().max(0) as f64 / 60.0);
+    if hours <= RECENT_HOURS {
+        1.0
+    } else {
+        0.5f64.powf((hours - RECENT_HOURS) / RECENT_HOURS)
+    }
+}
+
+/// Compact age: "45m ago", "5h ago", "3d ago".
+fn format_age(age: Option<Duration>) -> String {
+    match age {
+        None => "age unknown".to_string(),
+        Some(a) if a.num_hours() < 1 => format!("{}m ago", a.num_minutes().max(0)),
+        Some(a) if a.num_hours() < 48 => format!("{}h ago", a.num_hours()),
+        Some(a) => format!("{}d ago", a.num_days()),
+    }
+}
+
+/// `title` cut to [`MAX_TITLE_CHARS`] on a character boundary.
+fn truncate_title(title: &str) -> String {
+    let title = title.trim();
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        return title.to_string();
+    }
+    let cut: String = title.chars().take(MAX_TITLE_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
 #[derive(Debug, Deserialize)]
 struct NewsSource {
     #[serde(default)]
@@ -157,6 +292,9 @@ fn sentiment_score(text: &str) -> f64 {
 pub struct NewsProvider {
     http: Client,
     api_key: Option<String>,
+    quota: TokenBucket,
+    /// USD per request; zero on the free tier.
+    cost_per_call: Decimal,
 }
 
 impl NewsProvider {
@@ -166,7 +304,24 @@ impl NewsProvider {
             .user_agent("ORACLE/0.1.0")
             .build()
             .context("Failed to build news HTTP client")?;
-        Ok(Self { http, api_key })
+        Ok(Self {
+            http,
+            api_key,
+            quota: daily_quota(FREE_TIER_REQUESTS_PER_DAY),
+            cost_per_call: Decimal::ZERO,
+        })
+    }
+
+    /// Request quota of the NewsAPI plan (default: the free tier's 100).
+    pub fn with_requests_per_day(mut self, quota: u32) -> Self {
+        self.quota = daily_quota(quota);
+        self
+    }
+
+    /// Price per request on a paid plan, charged to each fetched context.
+    pub fn with_cost_per_call(mut self, cost: Decimal) -> Self {
+        self.cost_per_call = cost;
+        self
     }
 
     /// Match market question to news topics.
@@ -177,57 +332,60 @@ impl NewsProvider {
             .collect()
     }
 
-    /// Extract key search terms from the question directly.
-    fn extract_search_query(question: &str) -> String {
-        // Take significant words from the question
-        let stop_words = [
-            "will", "the", "be", "in", "a", "an", "is", "it", "of", "to",
-            "for", "and", "or", "by", "at", "on", "this", "that", "before",
-            "after", "than", "more", "less", "above", "below", "between",
-        ];
-        let words: Vec<&str> = question
-            .split(|c: char| !c.is_alphanumeric() && c != '\'')
-            .filter(|w| w.len() > 2 && !stop_words.contains(&w.to_lowercase().as_str()))
-            .take(5)
-            .collect();
-        words.join(" ")
-    }
-
-    /// Build summary from NewsAPI articles.
+    /// Build summary from NewsAPI articles: up to [`MAX_HEADLINES`]
+    /// headlines, ranked by [`recency_weight`] (ties keep NewsAPI's
+    /// relevance order), and a recency-weighted headline sentiment.
     fn build_news_summary(
         topics: &[&NewsTopic],
+        keywords: &[String],
         articles: &[NewsArticle],
         market: &Market,
+        now: DateTime<Utc>,
     ) -> String {
         let mut parts = Vec::new();
         parts.push("News context:".to_string());
+        parts.push(format!("Keywords: {}", keywords.join(", ")));
 
         if !topics.is_empty() {
             let labels: Vec<&str> = topics.iter().map(|t| t.label).collect();
             parts.push(format!("Topics: {}", labels.join(", ")));
         }
 
-        if !articles.is_empty() {
-            parts.push(format!("\nRecent headlines ({} articles):", articles.len()));
-            for (i, article) in articles.iter().take(5).enumerate() {
-                let title = article.title.as_deref().unwrap_or("(no title)");
+        let mut ranked: Vec<(f64, Option<Duration>, &NewsArticle)> = articles
+            .iter()
+            .filter(|a| a.title.as_deref().is_some_and(|t| !t.trim().is_empty()))
+            .map(|a| {
+                let age = a.published().map(|at| now - at);
+                (recency_weight(age), age, a)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        if ranked.is_empty() {
+            parts.push("No recent coverage found.".to_string());
+        } else {
+            let recent = ranked.iter().filter(|(w, ..)| *w >= 1.0).count();
+            parts.push(format!(
+                "\nRecent headlines ({} articles, {recent} in the last {RECENT_HOURS:.0}h):",
+                ranked.len()
+            ));
+            for (i, (_, age, article)) in ranked.iter().take(MAX_HEADLINES).enumerate() {
+                let title = truncate_title(article.title.as_deref().unwrap_or_default());
                 let source = article.source.as_ref()
                     .and_then(|s| s.name.as_deref())
                     .unwrap_or("unknown");
-                let date = article.published_at.as_deref().unwrap_or("");
-                parts.push(format!("  {}. [{}] {} ({})", i + 1, source, title, date));
+                parts.push(format!("  {}. [{source}, {}] {title}", i + 1, format_age(*age)));
             }
 
-            // Aggregate sentiment from headlines
-            let headlines: String = articles.iter()
-                .filter_map(|a| a.title.as_deref())
-                .collect::<Vec<_>>()
-                .join(" ");
-            let sent = sentiment_score(&headlines);
+            // Headline sentiment, each headline weighted by its recency
+            let (weighted, total) = ranked.iter().fold((0.0, 0.0), |(sum, total), (w, _, a)| {
+                (sum + w * sentiment_score(a.title.as_deref().unwrap_or_default()), total + w)
+            });
+            let sent = if total > 0.0 { weighted / total } else { 0.0 };
             let sentiment_label = if sent > 0.3 { "positive" }
                 else if sent < -0.3 { "negative" }
                 else { "neutral/mixed" };
-            parts.push(format!("\nHeadline sentiment: {} ({:.2})", sentiment_label, sent));
+            parts.push(format!("\nHeadline sentiment (recency-weighted): {} ({:.2})", sentiment_label, sent));
         }
 
         // Cross-references
@@ -245,6 +403,32 @@ impl NewsProvider {
         parts.join("\n")
     }
 
+    /// Search NewsAPI for the last week's articles on `keywords`. Returns
+    /// the raw articles and the parsed ones.
+    async fn search(&self, key: &str, keywords: &[String]) -> Result<(serde_json::Value, Vec<NewsArticle>)> {
+        let query = keywords.join(" ");
+        let from = (Utc::now() - Duration::days(7)).format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let resp = self.http
+            .get("https://newsapi.org/v2/everything")
+            .query(&[
+                ("q", query.as_str()),
+                ("from", from.as_str()),
+                ("sortBy", "relevancy"),
+                ("pageSize", "20"),
+                ("language", "en"),
+                ("apiKey", key),
+            ])
+            .send()
+            .await
+            .context("NewsAPI request failed")?;
+        let status = resp.status();
+        anyhow::ensure!(status.is_success(), "NewsAPI returned {status}");
+        let raw: serde_json::Value = resp.json().await.context("Failed to parse NewsAPI response")?;
+        let parsed: NewsApiResponse = serde_json::from_value(raw.clone()).context("Failed to parse NewsAPI articles")?;
+        let articles = raw.get("articles").cloned().unwrap_or_else(|| serde_json::json!([]));
+        Ok((serde_json::json!({ "query": query, "articles": articles }), parsed.articles))
+    }
+
     /// Build keyword-only summary when no API key.
     fn keyword_only_summary(topics: &[&NewsTopic], market: &Market) -> String {
         let mut parts = Vec::new();
@@ -290,48 +474,28 @@ impl DataProvider for NewsProvider {
 
     async fn fetch_context(&self, market: &Market) -> Result<DataContext> {
         let topics = Self::match_topics(&market.question);
+        let keywords = keywords(&question_parser::facts(market), &market.question);
 
+        let mut cost = Decimal::ZERO;
         let (summary, raw_data) = match &self.api_key {
-            Some(key) => {
-                let query = if !topics.is_empty() {
-                    topics[0].search_terms.to_string()
-                } else {
-                    Self::extract_search_query(&market.question)
-                };
-
-                let url = format!(
-                    "https://newsapi.org/v2/everything?\
-                     q={}&sortBy=publishedAt&pageSize=10&language=en&apiKey={}",
-                    urlencoding::encode(&query),
-                    key
-                );
-
-                match self.http.get(&url).send().await {
-                    Ok(resp) if resp.status().is_success() => {
-                        match resp.json::<NewsApiResponse>().await {
-                            Ok(data) => {
-                                let raw = serde_json::to_value(&data.articles.iter().map(|a| {
-                                    a.title.as_deref().unwrap_or("")
-                                }).collect::<Vec<_>>()).unwrap_or_default();
-                                let summary = Self::build_news_summary(&topics, &data.articles, market);
-                                (summary, raw)
-                            }
-                            Err(e) => {
-                                debug!(error = %e, "Failed to parse NewsAPI response");
-                                (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
-                            }
-                        }
-                    }
-                    Ok(resp) => {
-                        debug!(status = %resp.status(), "NewsAPI returned error");
-                        (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
-                    }
-                    Err(e) => {
-                        debug!(error = %e, "NewsAPI request failed");
-                        (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
-                    }
-                }
+            Some(_) if keywords.is_empty() => {
+                debug!(market_id = %market.id, "No keywords in question — skipping NewsAPI");
+                (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
+            }
+            Some(_) if !self.quota.try_take() => {
+                warn!(market_id = %market.id, "NewsAPI request quota spent — keyword-only context");
+                (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
             }
+            Some(key) => match self.search(key, &keywords).await {
+                Ok((raw, articles)) => {
+                    cost = self.cost_per_call;
+                    (Self::build_news_summary(&topics, &keywords, &articles, market, Utc::now()), raw)
+                }
+                Err(e) => {
+                    debug!(error = %e, "NewsAPI search failed");
+                    (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
+                }
+            },
             None => {
                 (Self::keyword_only_summary(&topics, market), serde_json::Value::Null)
             }
@@ -345,15 +509,16 @@ impl DataProvider for NewsProvider {
             summary,
             freshness: Utc::now(),
             source: if self.api_key.is_some() { "newsapi".to_string() } else { "keyword-extraction".to_string() },
-            cost: Decimal::ZERO,
+            cost,
             metaculus_forecast: market.cross_refs.metaculus_prob,
             metaculus_forecasters: market.cross_refs.metaculus_forecasters,
             manifold_price: market.cross_refs.manifold_prob,
+            sections: Vec::new(),
         })
     }
 
     fn cost_per_call(&self) -> Decimal {
-        Decimal::ZERO // NewsAPI free tier
+        self.cost_per_call
     }
 }
 
@@ -414,20 +579,14 @@ mod tests {
         assert_eq!(sentiment_score(""), 0.0);
     }
 
-    #[test]
-    fn test_extract_search_query() {
-        let q = NewsProvider::extract_search_query("Will Trump win the 2028 presidential election?");
-        assert!(q.contains("Trump"));
-        assert!(q.contains("presidential") || q.contains("election"));
-        // Should NOT contain stop words
-        assert!(!q.to_lowercase().split_whitespace().any(|w| w == "will" || w == "the"));
+    fn keywords_for(question: &str) -> Vec<String> {
+        let market = Market { question: question.into(), ..test_market() };
+        keywords(&question_parser::facts(&market), question)
     }
 
-    #[test]
-    fn test_keyword_only_summary_with_topics() {
+    fn test_market() -> Market {
         use crate::types::d;
-        let topics = NewsProvider::match_topics("Will Trump finish his second term?");
-        let market = Market {
+        Market {
             id: "test".into(), platform: "manifold".into(),
             question: "Will Trump finish his second term?".into(),
             description: String::new(), category: MarketCategory::Politics,
@@ -437,7 +596,115 @@ mod tests {
             resolution_criteria: String::new(),
             url: "https://example.com".into(),
             cross_refs: crate::types::CrossReferences::default(),
-        };
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn article(title: &str, source: &str, published: DateTime<Utc>) -> NewsArticle {
+        NewsArticle {
+            title: Some(title.into()),
+            source: Some(NewsSource { name: Some(source.into()) }),
+            published_at: Some(published.to_rfc3339()),
+        }
+    }
+
+    #[test]
+    fn test_keywords_drop_filler_and_generic_phrasing() {
+        let k = keywords_for("Will Trump win the 2028 presidential election?");
+        assert_eq!(k[0], "Trump");
+        assert!(k.contains(&"presidential".to_string()));
+        assert!(k.contains(&"election".to_string()));
+        assert!(!k.iter().any(|w| w == "2028" || w.eq_ignore_ascii_case("will") || w == "the"));
+
+        let k = keywords_for("Will the Federal Reserve cut interest rates before the end of March 2026?");
+        assert!(k.iter().any(|w| w.contains("Federal Reserve")), "{k:?}");
+        assert!(k.contains(&"interest".to_string()) && k.contains(&"rates".to_string()), "{k:?}");
+        assert!(!k.iter().any(|w| w.contains("March") || w.contains("2026") || w == "end"), "{k:?}");
+    }
+
+    #[test]
+    fn test_keywords_capped_and_deduplicated() {
+        let k = keywords_for(
+            "Will OpenAI release GPT-5 to the public, and will OpenAI open-source its weights, model cards and evaluation reports?",
+        );
+        assert!(k.len() <= MAX_KEYWORDS);
+        assert_eq!(k.iter().filter(|w| w.eq_ignore_ascii_case("openai")).count(), 1, "{k:?}");
+        assert!(keywords_for("Will it happen by 2026?").is_empty());
+    }
+
+    #[test]
+    fn test_summary_lists_recent_headlines_first_with_age_and_source() {
+        let now = Utc::now();
+        let articles = vec![
+            article("Old profile of the candidate", "Archive", now - Duration::days(6)),
+            article("Candidate surges after debate win", "Reuters", now - Duration::hours(5)),
+        ];
+        let keywords = vec!["Candidate".to_string()];
+        let summary = NewsProvider::build_news_summary(&[], &keywords, &articles, &test_market(), now);
+        let reuters = summary.find("[Reuters, 5h ago] Candidate surges").expect(&summary);
+        let archive = summary.find("[Archive, 6d ago]").expect(&summary);
+        assert!(reuters < archive);
+        assert!(summary.contains("2 articles, 1 in the last 72h"));
+        assert!(summary.contains("recency-weighted"));
+    }
+
+    #[test]
+    fn test_summary_truncates_headlines() {
+        let now = Utc::now();
+        let long = "Ministers ".repeat(30);
+        let articles: Vec<NewsArticle> = (0..8)
+            .map(|i| article(&long, "Wire", now - Duration::hours(i)))
+            .collect();
+        let summary = NewsProvider::build_news_summary(&[], &["Ministers".into()], &articles, &test_market(), now);
+        assert_eq!(summary.matches("[Wire,").count(), MAX_HEADLINES);
+        let line = summary.lines().find(|l| l.contains("[Wire,")).unwrap();
+        let title = line.split("] ").nth(1).unwrap();
+        assert!(title.ends_with('…'));
+        assert!(title.chars().count() <= MAX_TITLE_CHARS);
+    }
+
+    #[test]
+    fn test_summary_without_coverage() {
+        let summary = NewsProvider::build_news_summary(&[], &["Obscure".into()], &[], &test_market(), Utc::now());
+        assert!(summary.contains("No recent coverage found."));
+        assert!(!summary.contains("sentiment"));
+    }
+
+    #[test]
+    fn test_recency_weight() {
+        assert_eq!(recency_weight(Some(Duration::hours(10))), 1.0);
+        assert!((recency_weight(Some(Duration::hours(144))) - 0.5).abs() < 1e-9);
+        assert!(recency_weight(None) < 1.0);
+    }
+
+    #[test]
+    fn test_token_bucket_holds_to_daily_quota() {
+        let bucket = daily_quota(FREE_TIER_REQUESTS_PER_DAY);
+        let start = std::time::Instant::now();
+        let burst = (0..50).filter(|_| bucket.try_take_at(start)).count();
+        assert_eq!(burst, BURST as usize);
+
+        // Over a full day: the burst plus the refill, never more than the quota
+        let mut taken = burst;
+        for minute in 1..=24 * 60 {
+            if bucket.try_take_at(start + std::time::Duration::from_secs(minute * 60)) {
+                taken += 1;
+            }
+        }
+        assert!(taken <= FREE_TIER_REQUESTS_PER_DAY as usize, "took {taken}");
+        assert!(taken >= FREE_TIER_REQUESTS_PER_DAY as usize - 1, "took {taken}");
+
+        let tiny = daily_quota(2);
+        assert!(tiny.try_take_at(start) && tiny.try_take_at(start));
+        assert!(!tiny.try_take_at(start + std::time::Duration::from_secs(86_400)));
+    }
+
+    #[test]
+    fn test_keyword_only_summary_with_topics() {
+        let topics = NewsProvider::match_topics("Will Trump finish his second term?");
+        let market = test_market();
         let summary = NewsProvider::keyword_only_summary(&topics, &market);
         assert!(summary.contains("US Politics"));
         assert!(summary.contains("NEWS_API_KEY"));
@@ -448,5 +715,7 @@ mod tests {
         let p = NewsProvider::new(None).unwrap();
         assert_eq!(p.category(), MarketCategory::Politics);
         assert_eq!(p.cost_per_call(), Decimal::ZERO);
+        let paid = NewsProvider::new(None).unwrap().with_cost_per_call(dec!(0.002));
+        assert_eq!(paid.cost_per_call(), dec!(0.002));
     }
 }
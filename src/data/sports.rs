@@ -1,81 +1,577 @@
 //! Sports data provider.
 //!
-//! Uses the free API-Football (via API-Sports) for fixtures, standings,
-//! and team info. Falls back to a keyword-based summary when no API key
-//! is configured.
+//! Backed by API-Sports. A matchup question ("Arsenal vs Chelsea", "Will
+//! the Lakers beat the Celtics?") is parsed into two teams and a league,
+//! and the context lists their next meeting, each team's last five results,
+//! recent head-to-head games, injuries where the sport reports them, and
+//! the bookmakers' consensus odds. Questions that don't name a matchup —
+//! season awards, championships, player props — get an empty context.
+//! Without an API key, or once the day's requests are spent, the context
+//! is a keyword summary.
 //!
-//! API: `https://v3.football.api-sports.io/` (and similar for other sports)
-//! Auth: `x-apisports-key` header. Free tier: 100 req/day.
-//!
-//! For MVP, we extract team/league names from the question and provide
-//! a structured summary. Full API integration will come when needed.
+//! API: `https://v3.football.api-sports.io/` for football,
+//! `https://v1.{basketball,baseball,hockey,american-football}.api-sports.io/`
+//! for the rest. Auth: `x-apisports-key` header. Free tier: 100 req/day,
+//! shared by all sports and counted in a file so restarts don't reset it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use serde::Deserialize;
-use tracing::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
 
 use super::DataProvider;
 use crate::types::{DataContext, Market, MarketCategory};
 
+/// Requests per day on the API-Sports free tier.
+pub const FREE_TIER_REQUESTS_PER_DAY: u32 = 100;
+
+/// Recent results listed per team.
+const FORM_GAMES: usize = 5;
+
+/// Head-to-head games listed.
+const H2H_GAMES: usize = 5;
+
+/// Statuses of a completed game (full time, extra time, penalties,
+/// overtime).
+const FINISHED: &[&str] = &["FT", "AET", "PEN", "AOT", "AP"];
+
+/// Statuses of a game not yet started.
+const UPCOMING: &[&str] = &["NS", "TBD"];
+
 // ---------------------------------------------------------------------------
 // Known leagues/sports for keyword extraction
 // ---------------------------------------------------------------------------
 
+/// API-Sports product covering a sport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SportApi {
+    Football,
+    Basketball,
+    Baseball,
+    Hockey,
+    AmericanFootball,
+}
+
+impl SportApi {
+    fn base_url(self) -> &'static str {
+        match self {
+            Self::Football => "https://v3.football.api-sports.io",
+            Self::Basketball => "https://v1.basketball.api-sports.io",
+            Self::Baseball => "https://v1.baseball.api-sports.io",
+            Self::Hockey => "https://v1.hockey.api-sports.io",
+            Self::AmericanFootball => "https://v1.american-football.api-sports.io",
+        }
+    }
+
+    /// Season label the v1 APIs file games under on `today`: "2026-2027"
+    /// for basketball and hockey, the starting year for the others.
+    fn season(self, today: NaiveDate) -> String {
+        let year = today.year();
+        match self {
+            Self::Basketball | Self::Hockey => {
+                let start = if today.month() >= 9 { year } else { year - 1 };
+                format!("{start}-{}", start + 1)
+            }
+            Self::AmericanFootball => (if today.month() >= 8 { year } else { year - 1 }).to_string(),
+            Self::Football => (if today.month() >= 7 { year } else { year - 1 }).to_string(),
+            Self::Baseball => year.to_string(),
+        }
+    }
+
+    /// Whether the API reports injuries per fixture.
+    fn has_injuries(self) -> bool {
+        self == Self::Football
+    }
+}
+
 struct SportKeyword {
     keywords: &'static [&'static str],
     sport: &'static str,
     league: &'static str,
+    /// API-Sports product for the sport (None = not covered).
+    api: Option<SportApi>,
 }
 
 const SPORT_KEYWORDS: &[SportKeyword] = &[
-    SportKeyword { keywords: &["nba", "basketball"], sport: "basketball", league: "NBA" },
-    SportKeyword { keywords: &["nfl", "super bowl", "football"], sport: "american_football", league: "NFL" },
-    SportKeyword { keywords: &["mlb", "baseball", "world series"], sport: "baseball", league: "MLB" },
-    SportKeyword { keywords: &["nhl", "hockey", "stanley cup"], sport: "hockey", league: "NHL" },
-    SportKeyword { keywords: &["premier league", "epl", "soccer", "champions league"], sport: "football", league: "EPL/UCL" },
-    SportKeyword { keywords: &["wimbledon", "tennis", "us open", "australian open", "french open"], sport: "tennis", league: "ATP/WTA" },
-    SportKeyword { keywords: &["olympics", "olympic"], sport: "multi", league: "Olympics" },
-    SportKeyword { keywords: &["f1 ", "formula 1", "formula one", "grand prix"], sport: "motorsport", league: "F1" },
-    SportKeyword { keywords: &["ufc", "mma"], sport: "mma", league: "UFC" },
-    SportKeyword { keywords: &["cricket", "ashes", "ipl"], sport: "cricket", league: "Cricket" },
-    SportKeyword { keywords: &["afl", "aussie rules"], sport: "australian_football", league: "AFL" },
-    SportKeyword { keywords: &["nrl", "rugby league"], sport: "rugby_league", league: "NRL" },
+    SportKeyword { keywords: &["nba", "basketball"], sport: "basketball", league: "NBA", api: Some(SportApi::Basketball) },
+    SportKeyword {
+        keywords: &["premier league", "epl", "soccer", "champions league", "europa league", "la liga", "serie a", "bundesliga", "ligue 1", "mls", "fa cup"],
+        sport: "football",
+        league: "EPL/UCL",
+        api: Some(SportApi::Football),
+    },
+    SportKeyword { keywords: &["nfl", "super bowl", "football"], sport: "american_football", league: "NFL", api: Some(SportApi::AmericanFootball) },
+    SportKeyword { keywords: &["mlb", "baseball", "world series"], sport: "baseball", league: "MLB", api: Some(SportApi::Baseball) },
+    SportKeyword { keywords: &["nhl", "hockey", "stanley cup"], sport: "hockey", league: "NHL", api: Some(SportApi::Hockey) },
+    SportKeyword { keywords: &["wimbledon", "tennis", "us open", "australian open", "french open"], sport: "tennis", league: "ATP/WTA", api: None },
+    SportKeyword { keywords: &["olympics", "olympic"], sport: "multi", league: "Olympics", api: None },
+    SportKeyword { keywords: &["f1 ", "formula 1", "formula one", "grand prix"], sport: "motorsport", league: "F1", api: None },
+    SportKeyword { keywords: &["ufc", "mma"], sport: "mma", league: "UFC", api: None },
+    SportKeyword { keywords: &["cricket", "ashes", "ipl"], sport: "cricket", league: "Cricket", api: None },
+    SportKeyword { keywords: &["afl", "aussie rules"], sport: "australian_football", league: "AFL", api: None },
+    SportKeyword { keywords: &["nrl", "rugby league"], sport: "rugby_league", league: "NRL", api: None },
 ];
 
 // ---------------------------------------------------------------------------
-// API response types (API-Sports fixtures)
+// Matchup parsing
+// ---------------------------------------------------------------------------
+
+/// Words joining the two teams, strongest first.
+const SEPARATORS: &[&str] = &["vs", "vs.", "v", "v.", "versus", "@", "beat", "beats", "defeat", "defeats", "against", "at"];
+
+/// Words between the first team and the separator ("Will the Lakers *win* against ...").
+const LEFT_FILLER: &[&str] = &["win", "to", "play", "playing"];
+
+/// Capitalised words that never start or continue a team name.
+const NOT_TEAM: &[&str] = &["Will", "The", "Can", "Do", "Does", "Did", "Who", "Which", "In", "On", "At", "Game", "Match"];
+
+/// Phrases of questions about a season, an award or a player rather than
+/// one game.
+const NOT_MATCHUP: &[&str] = &[
+    "mvp", "award", "rookie of the year", "championship", "title", "season", "playoff", "finals", "super bowl",
+    "world series", "stanley cup", "points", "rebounds", "assists", "yards", "touchdown", "scorer", "hat-trick",
+    "hat trick", "relegat", "top four", "top 4", "qualify", "draft", "transfer", "medal",
+];
+
+/// Two teams meeting in one game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matchup {
+    pub api: SportApi,
+    pub league: &'static str,
+    /// As written in the question, first-named first.
+    pub teams: [String; 2],
+}
+
+fn is_capitalised(word: &str) -> bool {
+    word.chars().next().is_some_and(|c| c.is_uppercase() || c.is_ascii_digit()) && !NOT_TEAM.contains(&word)
+}
+
+/// The run of capitalised words ending just before `words[end]`.
+fn team_before(words: &[&str], end: usize) -> Option<String> {
+    let mut end = end;
+    while end > 0 && LEFT_FILLER.contains(&words[end - 1]) {
+        end -= 1;
+    }
+    let mut start = end;
+    while start > 0 {
+        let word = words[start - 1];
+        // "NBA: Lakers vs ..." — punctuation ends the run.
+        if word.ends_with(|c: char| !c.is_alphanumeric()) && start < end {
+            break;
+        }
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if !is_capitalised(word) {
+            break;
+        }
+        start -= 1;
+    }
+    let team: Vec<&str> = words[start..end].iter().map(|w| w.trim_matches(|c: char| !c.is_alphanumeric())).collect();
+    (!team.is_empty()).then(|| team.join(" "))
+}
+
+/// The run of capitalised words starting at `words[start]`.
+fn team_after(words: &[&str], start: usize) -> Option<String> {
+    let mut start = start;
+    if words.get(start).is_some_and(|w| w.eq_ignore_ascii_case("the")) {
+        start += 1;
+    }
+    let mut team = Vec::new();
+    for word in &words[start.min(words.len())..] {
+        if word.starts_with(|c: char| !c.is_alphanumeric()) {
+            break;
+        }
+        let trimmed = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if !is_capitalised(trimmed) {
+            break;
+        }
+        team.push(trimmed);
+        if word.ends_with(|c: char| !c.is_alphanumeric()) {
+            break;
+        }
+    }
+    (!team.is_empty()).then(|| team.join(" "))
+}
+
+/// Parse the two teams of a single game and the API covering it. A
+/// question naming a matchup but no known sport is taken to be football
+/// (soccer), the sport most team-vs-team questions are about.
+pub fn parse_matchup(question: &str) -> Option<Matchup> {
+    let q = question.to_lowercase();
+    if NOT_MATCHUP.iter().any(|marker| q.contains(marker)) {
+        return None;
+    }
+    let (api, league) = match ApiSportsProvider::extract_sport(question) {
+        Some(sk) => (sk.api?, sk.league),
+        None => (SportApi::Football, "Football"),
+    };
+    let words: Vec<&str> = question.split_whitespace().collect();
+    let at = SEPARATORS.iter().find_map(|sep| {
+        words.iter().position(|w| w.trim_end_matches([',', ':']).eq_ignore_ascii_case(sep))
+    })?;
+    let home = team_before(&words, at)?;
+    let away = team_after(&words, at + 1)?;
+    (!home.eq_ignore_ascii_case(&away)).then_some(Matchup { api, league, teams: [home, away] })
+}
+
+// ---------------------------------------------------------------------------
+// Daily request counter
+// ---------------------------------------------------------------------------
+
+/// Requests made on one UTC day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Usage {
+    date: Option<NaiveDate>,
+    used: u32,
+}
+
+/// Requests allowed per UTC day. With a file, the count survives
+/// restarts, so a crash loop can't spend a day's quota several times.
+pub struct DailyCounter {
+    limit: u32,
+    path: Option<PathBuf>,
+    usage: Mutex<Usage>,
+}
+
+impl DailyCounter {
+    /// A counter kept in memory only.
+    pub fn in_memory(limit: u32) -> Self {
+        Self { limit, path: None, usage: Mutex::new(Usage::default()) }
+    }
+
+    /// A counter persisted at `path`. A missing file is a fresh count.
+    pub fn load(path: impl Into<PathBuf>, limit: u32) -> Result<Self> {
+        let path = path.into();
+        let usage = if path.exists() {
+            let text = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?
+        } else {
+            Usage::default()
+        };
+        Ok(Self { limit, path: Some(path), usage: Mutex::new(usage) })
+    }
+
+    /// Count a request at `now`, or refuse it when the day's are spent.
+    pub fn try_take_at(&self, now: DateTime<Utc>) -> bool {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        Self::roll_over(&mut usage, now);
+        if usage.used >= self.limit {
+            return false;
+        }
+        usage.used += 1;
+        self.save(&usage);
+        true
+    }
+
+    /// Requests left on `now`'s day.
+    pub fn remaining_at(&self, now: DateTime<Utc>) -> u32 {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        Self::roll_over(&mut usage, now);
+        self.limit.saturating_sub(usage.used)
+    }
+
+    /// Spend the rest of the day, e.g. when the API reports its limit hit
+    /// before the count did.
+    fn exhaust_at(&self, now: DateTime<Utc>) {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        Self::roll_over(&mut usage, now);
+        usage.used = usage.used.max(self.limit);
+        self.save(&usage);
+    }
+
+    fn roll_over(usage: &mut Usage, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if usage.date != Some(today) {
+            *usage = Usage { date: Some(today), used: 0 };
+        }
+    }
+
+    /// Write the count, replacing the file only once the new one is
+    /// complete. A failed write is logged; the in-memory count still holds.
+    fn save(&self, usage: &Usage) {
+        let Some(path) = &self.path else { return };
+        let write = || -> Result<()> {
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_string(usage)?)?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        };
+        if let Err(e) = write() {
+            warn!(error = %e, path = %path.display(), "Failed to save API-Sports request count");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// API response types (API-Sports)
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Deserialize)]
 struct ApiSportsResponse {
+    /// `[]` on success, an object of messages on failure.
     #[serde(default)]
-    results: u32,
+    errors: Value,
     #[serde(default)]
-    response: Vec<serde_json::Value>,
+    response: Vec<Value>,
+}
+
+impl ApiSportsResponse {
+    fn error(&self) -> Option<String> {
+        let messages: Vec<String> = match &self.errors {
+            Value::Object(map) => map.iter().map(|(k, v)| format!("{k}: {}", v.as_str().unwrap_or_default())).collect(),
+            Value::Array(list) => list.iter().map(|v| v.to_string()).collect(),
+            _ => Vec::new(),
+        };
+        (!messages.is_empty()).then(|| messages.join("; "))
+    }
+
+    /// The daily request limit, as opposed to the per-minute one.
+    fn daily_limit_hit(&self) -> bool {
+        self.errors.get("requests").is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Team {
+    id: i64,
+    name: String,
+}
+
+impl Team {
+    /// A team from a `teams?search=` result: nested under `team` on the
+    /// football API, top-level on the others.
+    fn parse(value: &Value) -> Option<Self> {
+        let team = value.get("team").unwrap_or(value);
+        Some(Self { id: team.get("id")?.as_i64()?, name: team.get("name")?.as_str()?.to_string() })
+    }
+
+    /// The search result named exactly `name`, else the first.
+    fn parse_best(response: &[Value], name: &str) -> Option<Self> {
+        let teams: Vec<Self> = response.iter().filter_map(Self::parse).collect();
+        teams.iter().find(|t| t.name.eq_ignore_ascii_case(name)).or(teams.first()).cloned()
+    }
+}
+
+/// A game from any of the APIs, which nest the same facts differently.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Game {
+    id: i64,
+    date: Option<DateTime<Utc>>,
+    status: String,
+    league: String,
+    home: Team,
+    away: Team,
+    /// Home and away score once played.
+    score: Option<(i64, i64)>,
+}
+
+impl Game {
+    fn parse(value: &Value) -> Option<Self> {
+        // `fixture` on football, `game` on american-football, top-level elsewhere.
+        let head = value.get("fixture").or_else(|| value.get("game")).unwrap_or(value);
+        let date = match head.get("date") {
+            Some(Value::String(s)) => DateTime::parse_from_rfc3339(s).ok().map(|d| d.with_timezone(&Utc)),
+            Some(d @ Value::Object(_)) => d.get("timestamp").and_then(Value::as_i64).and_then(|ts| DateTime::from_timestamp(ts, 0)),
+            _ => None,
+        }
+        .or_else(|| head.get("timestamp").and_then(Value::as_i64).filter(|&ts| ts > 0).and_then(|ts| DateTime::from_timestamp(ts, 0)));
+        let teams = value.get("teams")?;
+        let scores = value.get("goals").or_else(|| value.get("scores"));
+        let score_of = |side: &str| {
+            let s = scores?.get(side)?;
+            s.as_i64().or_else(|| s.get("total")?.as_i64())
+        };
+        Some(Self {
+            id: head.get("id")?.as_i64()?,
+            date,
+            status: head.pointer("/status/short").and_then(Value::as_str).unwrap_or_default().to_string(),
+            league: value.pointer("/league/name").and_then(Value::as_str).unwrap_or_default().to_string(),
+            home: Team::parse(teams.get("home")?)?,
+            away: Team::parse(teams.get("away")?)?,
+            score: score_of("home").zip(score_of("away")),
+        })
+    }
+
+    fn parse_all(response: &[Value]) -> Vec<Self> {
+        response.iter().filter_map(Self::parse).collect()
+    }
+
+    fn finished(&self) -> bool {
+        FINISHED.contains(&self.status.as_str()) && self.score.is_some()
+    }
+
+    fn upcoming(&self) -> bool {
+        UPCOMING.contains(&self.status.as_str())
+    }
+
+    fn involves(&self, team: i64) -> bool {
+        self.home.id == team || self.away.id == team
+    }
+
+    /// Result for `team` as W/D/L with its score first, and the opponent
+    /// prefixed "v" at home or "@" away.
+    fn line_for(&self, team: i64) -> Option<String> {
+        let (home, away) = self.score?;
+        let (own, other, opponent, venue) = if self.home.id == team {
+            (home, away, &self.away.name, "v")
+        } else {
+            (away, home, &self.home.name, "@")
+        };
+        let result = match own.cmp(&other) {
+            std::cmp::Ordering::Greater => 'W',
+            std::cmp::Ordering::Equal => 'D',
+            std::cmp::Ordering::Less => 'L',
+        };
+        Some(format!("{result} {own}-{other} {venue} {opponent}"))
+    }
+}
+
+/// Finished games involving `team`, newest first, at most `n`.
+fn recent(games: &[Game], team: i64, n: usize) -> Vec<Game> {
+    let mut played: Vec<Game> = games.iter().filter(|g| g.involves(team) && g.finished()).cloned().collect();
+    played.sort_by_key(|g| std::cmp::Reverse(g.date));
+    played.truncate(n);
+    played
+}
+
+/// The earliest upcoming game between `a` and `b`.
+fn next_meeting(games: &[Game], a: i64, b: i64) -> Option<Game> {
+    games.iter().filter(|g| g.upcoming() && g.involves(a) && g.involves(b)).min_by_key(|g| g.date).cloned()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Injury {
+    team: i64,
+    player: String,
+    reason: String,
+}
+
+impl Injury {
+    fn parse(value: &Value) -> Option<Self> {
+        let player = value.get("player")?;
+        let reason = player.get("reason").or_else(|| player.get("type")).and_then(Value::as_str).unwrap_or("unspecified");
+        Some(Self {
+            team: value.pointer("/team/id")?.as_i64()?,
+            player: player.get("name")?.as_str()?.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+}
+
+/// Bookmakers' win probabilities, averaged after removing each one's margin.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Consensus {
+    bookmakers: usize,
+    /// Outcome ("Home", "Draw", "Away") and probability.
+    outcomes: Vec<(String, f64)>,
+}
+
+impl Consensus {
+    /// From an `odds` response, using each bookmaker's match-winner market.
+    fn parse(response: &[Value]) -> Option<Self> {
+        let bookmakers = response.first()?.get("bookmakers")?.as_array()?;
+        let mut sums: Vec<(String, f64)> = Vec::new();
+        let mut counted = 0;
+        for bookmaker in bookmakers {
+            let Some(bet) = bookmaker.get("bets").and_then(Value::as_array).and_then(|bets| {
+                bets.iter().find(|b| matches!(b.get("name").and_then(Value::as_str), Some("Match Winner" | "Home/Away")))
+            }) else {
+                continue;
+            };
+            let implied: Vec<(String, f64)> = bet
+                .get("values")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|v| {
+                    let odd: f64 = v.get("odd")?.as_str()?.parse().ok()?;
+                    if odd <= 1.0 {
+                        return None;
+                    }
+                    Some((v.get("value")?.as_str()?.to_string(), 1.0 / odd))
+                })
+                .collect();
+            let book: f64 = implied.iter().map(|(_, p)| p).sum();
+            if implied.len() < 2 || book <= 0.0 {
+                continue;
+            }
+            counted += 1;
+            for (outcome, p) in implied {
+                match sums.iter_mut().find(|(o, _)| *o == outcome) {
+                    Some((_, sum)) => *sum += p / book,
+                    None => sums.push((outcome, p / book)),
+                }
+            }
+        }
+        (counted > 0).then(|| Self {
+            bookmakers: counted,
+            outcomes: sums.into_iter().map(|(o, sum)| (o, sum / counted as f64)).collect(),
+        })
+    }
+}
+
+/// Everything fetched for one matchup.
+#[derive(Debug, Clone, Default, Serialize)]
+struct MatchupData {
+    /// In question order.
+    teams: Vec<Team>,
+    fixture: Option<Game>,
+    /// Per team, newest first.
+    form: Vec<Vec<Game>>,
+    h2h: Vec<Game>,
+    injuries: Vec<Injury>,
+    odds: Option<Consensus>,
+}
+
+fn format_until(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let hours = (at - now).num_hours();
+    match hours {
+        h if h < 0 => "started".to_string(),
+        h if h < 24 => format!("in {h}h"),
+        h => format!("in {} days", h / 24),
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Provider
 // ---------------------------------------------------------------------------
 
-pub struct SportsProvider {
+pub struct ApiSportsProvider {
     http: Client,
     api_key: Option<String>,
+    requests: DailyCounter,
+    /// Team lookups by sport and lower-cased question name (None = not found).
+    teams: Mutex<HashMap<(SportApi, String), Option<Team>>>,
 }
 
-impl SportsProvider {
+impl ApiSportsProvider {
     pub fn new(api_key: Option<String>) -> Result<Self> {
         let http = Client::builder()
             .timeout(std::time::Duration::from_secs(15))
             .user_agent("ORACLE/0.1.0")
             .build()
             .context("Failed to build sports HTTP client")?;
-        Ok(Self { http, api_key })
+        Ok(Self {
+            http,
+            api_key,
+            requests: DailyCounter::in_memory(FREE_TIER_REQUESTS_PER_DAY),
+            teams: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Count requests against `counter` (default: the free tier's 100 a
+    /// day, in memory).
+    pub fn with_request_counter(mut self, counter: DailyCounter) -> Self {
+        self.requests = counter;
+        self
     }
 
     /// Extract sport/league from market question.
@@ -86,51 +582,9 @@ impl SportsProvider {
         })
     }
 
-    /// Extract team names from question using common patterns.
-    fn extract_teams(question: &str) -> Vec<String> {
-        // Common pattern: "Will the [Team] win..."
-        let q = question.to_lowercase();
-        let mut teams = Vec::new();
-
-        // Look for "the [Proper Noun]" patterns in original question
-        let words: Vec<&str> = question.split_whitespace().collect();
-        let mut i = 0;
-        while i < words.len() {
-            if words[i].eq_ignore_ascii_case("the") && i + 1 < words.len() {
-                // Collect capitalized words after "the"
-                let mut team = Vec::new();
-                for j in (i + 1)..words.len() {
-                    let w = words[j].trim_matches(|c: char| !c.is_alphanumeric());
-                    if w.is_empty() { break; }
-                    if w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
-                        && !["Will", "Win", "The", "In", "And", "Or", "For", "Be"].contains(&w)
-                    {
-                        team.push(w.to_string());
-                    } else {
-                        break;
-                    }
-                }
-                if !team.is_empty() {
-                    teams.push(team.join(" "));
-                }
-            }
-            i += 1;
-        }
-
-        // Also check for "vs" or "versus" patterns
-        if let Some(vs_pos) = q.find(" vs ").or_else(|| q.find(" versus ")) {
-            // There's a versus pattern — teams are on either side
-            debug!(question = %question, "Found vs/versus pattern in sports question");
-        }
-
-        teams.truncate(4); // Limit
-        teams
-    }
-
-    /// Build a keyword-based summary when no API key is available.
-    fn keyword_summary(market: &Market) -> String {
+    /// Build a keyword-based summary when the API can't be used.
+    fn keyword_summary(market: &Market, note: &str) -> String {
         let sport = Self::extract_sport(&market.question);
-        let teams = Self::extract_teams(&market.question);
 
         let mut parts = Vec::new();
         parts.push("Sports context (keyword-extracted):".to_string());
@@ -139,10 +593,33 @@ impl SportsProvider {
             parts.push(format!("Sport: {} ({})", sk.sport, sk.league));
         }
 
-        if !teams.is_empty() {
-            parts.push(format!("Teams mentioned: {}", teams.join(", ")));
+        if let Some(matchup) = parse_matchup(&market.question) {
+            parts.push(format!("Matchup: {} vs {}", matchup.teams[0], matchup.teams[1]));
+        }
+
+        Self::push_cross_refs(&mut parts, market);
+        parts.push(format!("Note: {note} Use general sports knowledge and cross-reference signals."));
+
+        parts.join("\n")
+    }
+
+    /// Keyword summary as the whole context.
+    fn keyword_context(market: &Market, note: &str) -> DataContext {
+        DataContext {
+            category: MarketCategory::Sports,
+            raw_data: Value::Null,
+            summary: Self::keyword_summary(market, note),
+            freshness: Utc::now(),
+            source: "keyword-extraction".to_string(),
+            cost: Decimal::ZERO,
+            metaculus_forecast: market.cross_refs.metaculus_prob,
+            metaculus_forecasters: market.cross_refs.metaculus_forecasters,
+            manifold_price: market.cross_refs.manifold_prob,
+            sections: Vec::new(),
         }
+    }
 
+    fn push_cross_refs(parts: &mut Vec<String>, market: &Market) {
         if let Some(prob) = market.cross_refs.manifold_prob {
             parts.push(format!("Manifold market probability: {:.1}%", prob * dec!(100)));
         }
@@ -153,39 +630,264 @@ impl SportsProvider {
                 market.cross_refs.metaculus_forecasters.unwrap_or(0)
             ));
         }
+    }
+
+    /// Summarise fetched data: next meeting, form, head-to-head, injuries
+    /// and bookmaker consensus.
+    fn build_summary(matchup: &Matchup, data: &MatchupData, market: &Market, now: DateTime<Utc>) -> String {
+        let name = |id: i64| data.teams.iter().find(|t| t.id == id).map_or("?", |t| t.name.as_str());
+        let mut parts = Vec::new();
+        parts.push(format!("Sports context (API-Sports, {}):", matchup.league));
+        parts.push(format!("Matchup: {} vs {}", data.teams[0].name, data.teams[1].name));
+
+        match &data.fixture {
+            Some(g) => {
+                let when = g.date.map_or("date TBD".to_string(), |d| {
+                    format!("{}, {}", d.format("%Y-%m-%d %H:%M UTC"), format_until(d, now))
+                });
+                parts.push(format!("Next fixture: {} (home) vs {} — {}, {when}", g.home.name, g.away.name, g.league));
+            }
+            None => parts.push("Next fixture: none scheduled between these teams".to_string()),
+        }
+
+        for (team, games) in data.teams.iter().zip(&data.form) {
+            let lines: Vec<String> = games.iter().filter_map(|g| g.line_for(team.id)).collect();
+            if lines.is_empty() {
+                parts.push(format!("{} recent results: none", team.name));
+                continue;
+            }
+            let count = |r: char| lines.iter().filter(|l| l.starts_with(r)).count();
+            parts.push(format!(
+                "{} last {}: W{} D{} L{} — {}",
+                team.name,
+                lines.len(),
+                count('W'),
+                count('D'),
+                count('L'),
+                lines.join(", ")
+            ));
+        }
 
-        parts.push("Note: Full sports API data not available. Use general sports knowledge and cross-reference signals.".to_string());
+        if !data.h2h.is_empty() {
+            let wins = |id: i64| data.h2h.iter().filter(|g| g.line_for(id).is_some_and(|l| l.starts_with('W'))).count();
+            let (a, b) = (&data.teams[0], &data.teams[1]);
+            let draws = data.h2h.len() - wins(a.id) - wins(b.id);
+            parts.push(format!(
+                "Head-to-head (last {}): {} {}, {} {}, draws {draws}",
+                data.h2h.len(),
+                a.name,
+                wins(a.id),
+                b.name,
+                wins(b.id)
+            ));
+            for g in &data.h2h {
+                let (home, away) = g.score.unwrap_or_default();
+                let date = g.date.map_or("?".to_string(), |d| d.format("%Y-%m-%d").to_string());
+                parts.push(format!("  {date}: {} {home}-{away} {}", g.home.name, g.away.name));
+            }
+        }
 
+        if matchup.api.has_injuries() && data.fixture.is_some() {
+            let per_team: Vec<String> = data
+                .teams
+                .iter()
+                .map(|t| {
+                    let out: Vec<String> = data
+                        .injuries
+                        .iter()
+                        .filter(|i| i.team == t.id)
+                        .map(|i| format!("{} ({})", i.player, i.reason))
+                        .collect();
+                    let out = if out.is_empty() { "none reported".to_string() } else { out.join(", ") };
+                    format!("{} — {out}", t.name)
+                })
+                .collect();
+            parts.push(format!("Injuries: {}", per_team.join("; ")));
+        }
+
+        if let (Some(odds), Some(g)) = (&data.odds, &data.fixture) {
+            let outcomes: Vec<String> = odds
+                .outcomes
+                .iter()
+                .map(|(outcome, p)| {
+                    let label = match outcome.as_str() {
+                        "Home" => name(g.home.id),
+                        "Away" => name(g.away.id),
+                        other => other,
+                    };
+                    format!("{label} {:.1}%", p * 100.0)
+                })
+                .collect();
+            parts.push(format!(
+                "Bookmaker consensus ({} bookmakers, margin removed): {}",
+                odds.bookmakers,
+                outcomes.join(", ")
+            ));
+        }
+
+        Self::push_cross_refs(&mut parts, market);
         parts.join("\n")
     }
+
+    /// One API request, counted against the day's quota.
+    async fn get(&self, key: &str, api: SportApi, endpoint: &str, query: &[(&str, String)]) -> Result<Vec<Value>> {
+        anyhow::ensure!(self.requests.try_take_at(Utc::now()), "API-Sports daily request limit reached");
+        let resp = self.http
+            .get(format!("{}/{endpoint}", api.base_url()))
+            .header("x-apisports-key", key)
+            .query(query)
+            .send()
+            .await
+            .with_context(|| format!("API-Sports {endpoint} request failed"))?;
+        let status = resp.status();
+        anyhow::ensure!(status.is_success(), "API-Sports {endpoint} returned {status}");
+        let body: ApiSportsResponse = resp.json().await.with_context(|| format!("Failed to parse API-Sports {endpoint} response"))?;
+        if let Some(error) = body.error() {
+            if body.daily_limit_hit() {
+                self.requests.exhaust_at(Utc::now());
+            }
+            anyhow::bail!("API-Sports {endpoint}: {error}");
+        }
+        Ok(body.response)
+    }
+
+    /// The API's team for a question name, looked up once per run.
+    async fn team(&self, key: &str, api: SportApi, name: &str) -> Result<Option<Team>> {
+        let cache_key = (api, name.to_lowercase());
+        if let Some(team) = self.teams.lock().unwrap_or_else(|e| e.into_inner()).get(&cache_key) {
+            return Ok(team.clone());
+        }
+        let found = Team::parse_best(&self.get(key, api, "teams", &[("search", name.to_string())]).await?, name);
+        self.teams.lock().unwrap_or_else(|e| e.into_inner()).insert(cache_key, found.clone());
+        Ok(found)
+    }
+
+    async fn fetch_football(&self, key: &str, teams: [Team; 2]) -> Result<MatchupData> {
+        let api = SportApi::Football;
+        let [a, b] = [teams[0].id, teams[1].id];
+        let upcoming = self.get(key, api, "fixtures", &[("team", a.to_string()), ("next", "10".into())]).await?;
+        let fixture = next_meeting(&Game::parse_all(&upcoming), a, b);
+        let mut form = Vec::new();
+        for id in [a, b] {
+            let last = self.get(key, api, "fixtures", &[("team", id.to_string()), ("last", FORM_GAMES.to_string())]).await?;
+            form.push(recent(&Game::parse_all(&last), id, FORM_GAMES));
+        }
+        let h2h = self.get(key, api, "fixtures/headtohead", &[("h2h", format!("{a}-{b}")), ("last", H2H_GAMES.to_string())]).await?;
+        let h2h = recent(&Game::parse_all(&h2h), a, H2H_GAMES);
+
+        let (mut injuries, mut odds) = (Vec::new(), None);
+        if let Some(g) = &fixture {
+            match self.get(key, api, "injuries", &[("fixture", g.id.to_string())]).await {
+                Ok(response) => injuries = response.iter().filter_map(Injury::parse).collect(),
+                Err(e) => debug!(error = %e, "API-Sports injuries unavailable"),
+            }
+            match self.get(key, api, "odds", &[("fixture", g.id.to_string())]).await {
+                Ok(response) => odds = Consensus::parse(&response),
+                Err(e) => debug!(error = %e, "API-Sports odds unavailable"),
+            }
+        }
+        Ok(MatchupData { teams: teams.to_vec(), fixture, form, h2h, injuries, odds })
+    }
+
+    /// The v1 APIs list a team's whole season in one request, which gives
+    /// form, the next meeting and head-to-head together.
+    async fn fetch_season(&self, key: &str, api: SportApi, teams: [Team; 2], now: DateTime<Utc>) -> Result<MatchupData> {
+        let season = api.season(now.date_naive());
+        let [a, b] = [teams[0].id, teams[1].id];
+        let mut seasons = Vec::new();
+        for id in [a, b] {
+            let games = self.get(key, api, "games", &[("team", id.to_string()), ("season", season.clone())]).await?;
+            seasons.push(Game::parse_all(&games));
+        }
+        let mut data = Self::season_data(teams, &seasons[0], &seasons[1]);
+        if let Some(g) = &data.fixture {
+            match self.get(key, api, "odds", &[("game", g.id.to_string())]).await {
+                Ok(response) => data.odds = Consensus::parse(&response),
+                Err(e) => debug!(error = %e, "API-Sports odds unavailable"),
+            }
+        }
+        Ok(data)
+    }
+
+    fn season_data(teams: [Team; 2], first: &[Game], second: &[Game]) -> MatchupData {
+        let [a, b] = [teams[0].id, teams[1].id];
+        let meetings: Vec<Game> = first.iter().filter(|g| g.involves(b)).cloned().collect();
+        MatchupData {
+            fixture: next_meeting(first, a, b),
+            form: vec![recent(first, a, FORM_GAMES), recent(second, b, FORM_GAMES)],
+            h2h: recent(&meetings, a, H2H_GAMES),
+            teams: teams.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Look up both teams and fetch their matchup. `None` when either
+    /// team isn't known to the API.
+    async fn fetch_matchup(&self, key: &str, matchup: &Matchup, now: DateTime<Utc>) -> Result<Option<MatchupData>> {
+        let mut teams = Vec::new();
+        for name in &matchup.teams {
+            match self.team(key, matchup.api, name).await? {
+                Some(team) => teams.push(team),
+                None => {
+                    debug!(team = %name, "Team not found on API-Sports");
+                    return Ok(None);
+                }
+            }
+        }
+        let teams: [Team; 2] = teams.try_into().expect("two teams");
+        let data = match matchup.api {
+            SportApi::Football => self.fetch_football(key, teams).await?,
+            api => self.fetch_season(key, api, teams, now).await?,
+        };
+        Ok(Some(data))
+    }
 }
 
 #[async_trait]
-impl DataProvider for SportsProvider {
+impl DataProvider for ApiSportsProvider {
     fn category(&self) -> MarketCategory {
         MarketCategory::Sports
     }
 
     async fn fetch_context(&self, market: &Market) -> Result<DataContext> {
-        // For MVP: keyword extraction summary. Full API integration later.
-        // API-Sports free tier is only 100 req/day, too limited for scanning.
-        let summary = Self::keyword_summary(market);
+        let now = Utc::now();
+        let Some(key) = &self.api_key else {
+            return Ok(Self::keyword_context(market, "API-Sports key not configured."));
+        };
+        let Some(matchup) = parse_matchup(&market.question) else {
+            debug!(market_id = %market.id, "No matchup in question — no sports data");
+            return Ok(DataContext::empty(MarketCategory::Sports));
+        };
+        if self.requests.remaining_at(now) == 0 {
+            warn!(market_id = %market.id, "API-Sports daily request limit reached — keyword-only context");
+            return Ok(Self::keyword_context(market, "API-Sports daily request limit reached."));
+        }
+
+        let data = match self.fetch_matchup(key, &matchup, now).await {
+            Ok(Some(data)) => data,
+            Ok(None) => return Ok(Self::keyword_context(market, "Teams not found on API-Sports.")),
+            Err(e) => {
+                debug!(error = %e, "API-Sports fetch failed");
+                return Ok(Self::keyword_context(market, "API-Sports data unavailable."));
+            }
+        };
 
         Ok(DataContext {
             category: MarketCategory::Sports,
-            raw_data: serde_json::Value::Null,
-            summary,
-            freshness: Utc::now(),
-            source: "keyword-extraction".to_string(),
+            raw_data: serde_json::to_value(&data).unwrap_or_default(),
+            summary: Self::build_summary(&matchup, &data, market, now),
+            freshness: now,
+            source: "api-sports".to_string(),
             cost: Decimal::ZERO,
             metaculus_forecast: market.cross_refs.metaculus_prob,
             metaculus_forecasters: market.cross_refs.metaculus_forecasters,
             manifold_price: market.cross_refs.manifold_prob,
+            sections: Vec::new(),
         })
     }
 
     fn cost_per_call(&self) -> Decimal {
-        Decimal::ZERO // Keyword extraction is free; API calls would be ~$0 (free tier)
+        Decimal::ZERO // Free tier
     }
 }
 
@@ -196,63 +898,207 @@ impl DataProvider for SportsProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+
+    fn response(path: &str) -> Vec<Value> {
+        let body: ApiSportsResponse = serde_json::from_str(&crate::fixtures::read(path)).unwrap();
+        body.response
+    }
+
+    fn test_market(question: &str) -> Market {
+        use crate::types::d;
+        Market {
+            id: "test".into(), platform: "manifold".into(),
+            question: question.into(),
+            description: String::new(), category: MarketCategory::Sports,
+            current_price_yes: d(0.3), current_price_no: d(0.7),
+            volume_24h: d(100.0), liquidity: d(500.0),
+            deadline: Utc::now() + chrono::Duration::days(30),
+            resolution_criteria: String::new(),
+            url: "https://example.com".into(),
+            cross_refs: crate::types::CrossReferences::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
 
     #[test]
     fn test_extract_sport_nba() {
-        let sk = SportsProvider::extract_sport("Will the Thunder win the NBA finals?");
+        let sk = ApiSportsProvider::extract_sport("Will the Thunder win the NBA finals?");
         assert!(sk.is_some());
         assert_eq!(sk.unwrap().league, "NBA");
     }
 
     #[test]
     fn test_extract_sport_olympics() {
-        let sk = SportsProvider::extract_sport("Will any athlete win a gold medal at the Olympics?");
+        let sk = ApiSportsProvider::extract_sport("Will any athlete win a gold medal at the Olympics?");
         assert!(sk.is_some());
         assert_eq!(sk.unwrap().league, "Olympics");
     }
 
     #[test]
     fn test_extract_sport_cricket() {
-        let sk = SportsProvider::extract_sport("Will Australia win the Ashes in 2026?");
+        let sk = ApiSportsProvider::extract_sport("Will Australia win the Ashes in 2026?");
         assert!(sk.is_some());
         assert_eq!(sk.unwrap().league, "Cricket");
     }
 
     #[test]
     fn test_extract_sport_none() {
-        let sk = SportsProvider::extract_sport("Will AGI be developed before 2030?");
+        let sk = ApiSportsProvider::extract_sport("Will AGI be developed before 2030?");
         assert!(sk.is_none());
     }
 
     #[test]
-    fn test_extract_teams() {
-        let teams = SportsProvider::extract_teams("Will the Oklahoma City Thunder win the NBA Finals?");
-        assert!(!teams.is_empty());
-        assert!(teams[0].contains("Oklahoma") || teams[0].contains("Thunder"));
+    fn test_parse_matchup_formats() {
+        let cases = [
+            ("Arsenal vs Chelsea", SportApi::Football, ["Arsenal", "Chelsea"]),
+            ("Premier League: Manchester United v. Liverpool (Old Trafford)", SportApi::Football, ["Manchester United", "Liverpool"]),
+            ("Will Arsenal beat Tottenham in the North London derby?", SportApi::Football, ["Arsenal", "Tottenham"]),
+            ("Champions League: Real Madrid vs. Bayern Munich", SportApi::Football, ["Real Madrid", "Bayern Munich"]),
+            ("NBA: Lakers @ Celtics on October 22", SportApi::Basketball, ["Lakers", "Celtics"]),
+            ("NFL: Will the Kansas City Chiefs win against the Buffalo Bills?", SportApi::AmericanFootball, ["Kansas City Chiefs", "Buffalo Bills"]),
+            ("MLB: Yankees at Red Sox", SportApi::Baseball, ["Yankees", "Red Sox"]),
+            ("NHL: Oilers versus Maple Leafs, who wins?", SportApi::Hockey, ["Oilers", "Maple Leafs"]),
+        ];
+        for (question, api, teams) in cases {
+            let matchup = parse_matchup(question).unwrap_or_else(|| panic!("no matchup in {question:?}"));
+            assert_eq!(matchup.api, api, "{question}");
+            assert_eq!(matchup.teams, teams.map(String::from), "{question}");
+        }
+        assert_eq!(parse_matchup("NBA: Lakers @ Celtics").unwrap().league, "NBA");
     }
 
     #[test]
-    fn test_keyword_summary_includes_sport() {
-        use crate::types::d;
-        let market = Market {
-            id: "test".into(), platform: "manifold".into(),
-            question: "Will the Lakers win the NBA championship?".into(),
-            description: String::new(), category: MarketCategory::Sports,
-            current_price_yes: d(0.3), current_price_no: d(0.7),
-            volume_24h: d(100.0), liquidity: d(500.0),
-            deadline: Utc::now() + chrono::Duration::days(30),
-            resolution_criteria: String::new(),
-            url: "https://example.com".into(),
-            cross_refs: crate::types::CrossReferences::default(),
+    fn test_unparseable_questions_have_no_matchup() {
+        for question in [
+            "Will Nikola Jokic win MVP?",
+            "Will the Oklahoma City Thunder win the NBA Finals?",
+            "Will Arsenal win the Premier League this season?",
+            "Will LeBron James score 30+ points vs the Celtics?",
+            "Djokovic vs Alcaraz at Wimbledon",
+            "Will Max Verstappen win the Monaco Grand Prix?",
+            "Will the Chiefs win?",
+        ] {
+            assert_eq!(parse_matchup(question), None, "{question}");
+        }
+    }
+
+    #[test]
+    fn test_daily_counter_caps_and_rolls_over() {
+        let day = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+        let counter = DailyCounter::in_memory(2);
+        assert!(counter.try_take_at(day));
+        assert!(counter.try_take_at(day + chrono::Duration::hours(3)));
+        assert!(!counter.try_take_at(day + chrono::Duration::hours(14)));
+        assert_eq!(counter.remaining_at(day), 0);
+        // The next UTC day starts a fresh count.
+        let next = day + chrono::Duration::hours(15);
+        assert_eq!(counter.remaining_at(next), 2);
+        assert!(counter.try_take_at(next));
+        counter.exhaust_at(next);
+        assert!(!counter.try_take_at(next));
+    }
+
+    #[test]
+    fn test_daily_counter_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("oracle-api-sports-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("usage.json");
+        let _ = std::fs::remove_file(&path);
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+
+        let counter = DailyCounter::load(&path, 3).unwrap();
+        assert!(counter.try_take_at(now) && counter.try_take_at(now));
+        drop(counter);
+
+        let reloaded = DailyCounter::load(&path, 3).unwrap();
+        assert_eq!(reloaded.remaining_at(now), 1);
+        assert!(reloaded.try_take_at(now));
+        assert!(!reloaded.try_take_at(now));
+        assert_eq!(DailyCounter::load(&path, 3).unwrap().remaining_at(now + chrono::Duration::days(1)), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_spent_quota_and_unparseable_questions_skip_the_api() {
+        let provider = ApiSportsProvider::new(Some("key".into())).unwrap().with_request_counter(DailyCounter::in_memory(0));
+        let ctx = provider.fetch_context(&test_market("Arsenal vs Chelsea")).await.unwrap();
+        assert_eq!(ctx.source, "keyword-extraction");
+        assert!(ctx.summary.contains("Matchup: Arsenal vs Chelsea"));
+        assert!(ctx.summary.contains("daily request limit reached"));
+
+        let ctx = provider.fetch_context(&test_market("Will Nikola Jokic win MVP?")).await.unwrap();
+        assert_eq!(ctx.summary, "No enrichment data available.");
+        assert_eq!(ctx.source, "none");
+    }
+
+    #[test]
+    fn test_football_summary_from_fixtures() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let arsenal = Team { id: 42, name: "Arsenal".into() };
+        let chelsea = Team { id: 49, name: "Chelsea".into() };
+        let injuries = serde_json::json!([
+            { "player": { "id": 1460, "name": "B. Saka", "type": "Missing Fixture", "reason": "Hamstring Injury" }, "team": { "id": 42, "name": "Arsenal" } }
+        ]);
+        let data = MatchupData {
+            teams: vec![arsenal.clone(), chelsea.clone()],
+            fixture: next_meeting(&Game::parse_all(&response("api-sports/fixtures-next.json")), 42, 49),
+            form: vec![recent(&Game::parse_all(&response("api-sports/fixtures-last.json")), 42, FORM_GAMES), Vec::new()],
+            h2h: Vec::new(),
+            injuries: injuries.as_array().unwrap().iter().filter_map(Injury::parse).collect(),
+            odds: Consensus::parse(&response("api-sports/odds.json")),
         };
-        let summary = SportsProvider::keyword_summary(&market);
+        let matchup = parse_matchup("Arsenal vs Chelsea").unwrap();
+        let summary = ApiSportsProvider::build_summary(&matchup, &data, &test_market("Arsenal vs Chelsea"), now);
+
+        assert!(summary.contains("Next fixture: Arsenal (home) vs Chelsea — Premier League, 2026-10-18 14:00 UTC, in 2 days"), "{summary}");
+        assert!(summary.contains(
+            "Arsenal last 5: W3 D1 L1 — W 3-1 v Tottenham, W 2-0 @ Aston Villa, D 1-1 v Liverpool, L 0-2 @ Manchester City, W 2-0 v Brighton"
+        ));
+        assert!(summary.contains("Chelsea recent results: none"));
+        assert!(summary.contains("Injuries: Arsenal — B. Saka (Hamstring Injury); Chelsea — none reported"));
+        assert!(summary.contains("Bookmaker consensus (3 bookmakers, margin removed): Arsenal 47.7%, Draw 27.2%, Chelsea 25.1%"), "{summary}");
+    }
+
+    #[test]
+    fn test_season_games_give_form_fixture_and_h2h() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        assert_eq!(SportApi::Basketball.season(now.date_naive()), "2026-2027");
+        assert_eq!(SportApi::AmericanFootball.season(NaiveDate::from_ymd_opt(2027, 1, 10).unwrap()), "2026");
+
+        let lakers = Team { id: 145, name: "Los Angeles Lakers".into() };
+        let celtics = Team { id: 133, name: "Boston Celtics".into() };
+        let games = Game::parse_all(&response("api-sports/games.json"));
+        let data = ApiSportsProvider::season_data([lakers, celtics], &games, &[]);
+
+        let fixture = data.fixture.as_ref().unwrap();
+        assert_eq!((fixture.id, fixture.home.id), (401005, 133));
+        assert_eq!(data.form[0].len(), 4);
+        assert_eq!(data.h2h.len(), 1);
+
+        let matchup = parse_matchup("NBA: Lakers vs Celtics").unwrap();
+        let summary = ApiSportsProvider::build_summary(&matchup, &data, &test_market("NBA: Lakers vs Celtics"), now);
+        assert!(summary.contains("Next fixture: Boston Celtics (home) vs Los Angeles Lakers — NBA, 2026-10-22 23:30 UTC, in 6 days"), "{summary}");
+        assert!(summary.contains("Los Angeles Lakers last 4: W2 D0 L2 — L 109-114 v Boston Celtics, W 121-115 v Denver Nuggets"));
+        assert!(summary.contains("Head-to-head (last 1): Los Angeles Lakers 0, Boston Celtics 1, draws 0"));
+        assert!(summary.contains("  2026-10-14: Los Angeles Lakers 109-114 Boston Celtics"));
+        // Injuries aren't reported outside football.
+        assert!(!summary.contains("Injuries"));
+    }
+
+    #[test]
+    fn test_keyword_summary_includes_sport() {
+        let market = test_market("Will the Lakers win the NBA championship?");
+        let summary = ApiSportsProvider::keyword_summary(&market, "API-Sports key not configured.");
         assert!(summary.contains("NBA"));
         assert!(summary.contains("basketball"));
     }
 
     #[test]
     fn test_provider_category() {
-        let p = SportsProvider::new(None).unwrap();
+        let p = ApiSportsProvider::new(None).unwrap();
         assert_eq!(p.category(), MarketCategory::Sports);
         assert_eq!(p.cost_per_call(), Decimal::ZERO);
     }
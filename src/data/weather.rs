@@ -1,59 +1,124 @@
 //! Weather data provider.
 //!
-//! Uses the free Open-Meteo API (no key required) for global weather
-//! forecasts and current conditions. Extracts location from market
-//! question text using keyword heuristics.
+//! Uses the free Open-Meteo API (no key required). For each market:
 //!
-//! API: `https://api.open-meteo.com/v1/forecast`
+//! 1. Location — the places in the parsed question facts, matched against
+//!    a gazetteer of major cities and regions; failing that, a place named
+//!    after "in" / "at" / "near" ("rain in Auckland") is looked up through
+//!    the Open-Meteo geocoding API. A question with no resolvable place
+//!    gets [`DataContext::empty`].
+//! 2. Date window — the day a question names ("on March 3"), or from today
+//!    to the period it names ("by June", "this summer" → the deadline).
+//! 3. Forecast — daily temperature range, precipitation and its
+//!    probability, and peak wind over the window, in °F when the question
+//!    sets a °F threshold. A window beyond the 16-day horizon falls back to
+//!    the week ahead, flagged as such.
+//!
+//! The API is free, but requests are capped per cycle to stay polite; once
+//! the cap is hit the provider errors (uncached) until the next batch.
+//!
+//! API: `https://api.open-meteo.com/v1/forecast`,
+//! `https://geocoding-api.open-meteo.com/v1/search`
 //! Auth: None required.
-//! Rate limit: Generous (free tier).
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{Duration, NaiveDate, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
-use serde::Deserialize;
-use tracing::{debug, warn};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 use super::DataProvider;
-use crate::types::{DataContext, Market, MarketCategory};
+use crate::question_parser;
+use crate::types::{DataContext, Market, MarketCategory, QuestionFacts};
+
+/// Open-Meteo requests (forecast and geocoding) allowed per enrichment cycle.
+pub const DEFAULT_MAX_REQUESTS_PER_CYCLE: usize = 40;
+
+/// Days ahead the forecast endpoint covers.
+const FORECAST_HORIZON_DAYS: i64 = 16;
+
+/// Days shown when the question's window is beyond the horizon.
+const FALLBACK_DAYS: i64 = 7;
+
+/// Words that introduce a place to geocode ("in Auckland").
+const LOCATIVES: &[&str] = &["in", "at", "near"];
 
 // ---------------------------------------------------------------------------
 // Known locations for keyword extraction
 // ---------------------------------------------------------------------------
 
-/// A city/region we can extract from market question text.
+/// A city/region we can fetch weather for, keyed by the canonical place
+/// names of [`question_parser::PLACES`](crate::question_parser::PLACES).
 struct KnownLocation {
-    keywords: &'static [&'static str],
+    places: &'static [&'static str],
     lat: f64,
     lon: f64,
     name: &'static str,
 }
 
+/// Most specific first: the first location matching any parsed place wins.
 const LOCATIONS: &[KnownLocation] = &[
-    KnownLocation { keywords: &["sydney", "nsw", "new south wales"], lat: -33.87, lon: 151.21, name: "Sydney, AU" },
-    KnownLocation { keywords: &["melbourne", "victoria", "vic"], lat: -37.81, lon: 144.96, name: "Melbourne, AU" },
-    KnownLocation { keywords: &["brisbane", "queensland", "qld"], lat: -27.47, lon: 153.03, name: "Brisbane, AU" },
-    KnownLocation { keywords: &["perth", "western australia"], lat: -31.95, lon: 115.86, name: "Perth, AU" },
-    KnownLocation { keywords: &["australia", "australian"], lat: -25.27, lon: 133.78, name: "Central Australia" },
-    KnownLocation { keywords: &["new york", "nyc", "manhattan"], lat: 40.71, lon: -74.01, name: "New York, US" },
-    KnownLocation { keywords: &["los angeles", "la ", "california", "socal"], lat: 34.05, lon: -118.24, name: "Los Angeles, US" },
-    KnownLocation { keywords: &["chicago"], lat: 41.88, lon: -87.63, name: "Chicago, US" },
-    KnownLocation { keywords: &["miami", "florida", "fl "], lat: 25.76, lon: -80.19, name: "Miami, US" },
-    KnownLocation { keywords: &["houston", "texas", "tx "], lat: 29.76, lon: -95.37, name: "Houston, US" },
-    KnownLocation { keywords: &["london", "uk ", "britain", "england"], lat: 51.51, lon: -0.13, name: "London, UK" },
-    KnownLocation { keywords: &["tokyo", "japan"], lat: 35.68, lon: 139.69, name: "Tokyo, JP" },
-    KnownLocation { keywords: &["washington", "dc ", "d.c."], lat: 38.91, lon: -77.04, name: "Washington DC, US" },
+    KnownLocation { places: &["Sydney", "New South Wales"], lat: -33.87, lon: 151.21, name: "Sydney, AU" },
+    KnownLocation { places: &["Melbourne", "Victoria"], lat: -37.81, lon: 144.96, name: "Melbourne, AU" },
+    KnownLocation { places: &["Brisbane", "Queensland"], lat: -27.47, lon: 153.03, name: "Brisbane, AU" },
+    KnownLocation { places: &["Perth", "Western Australia"], lat: -31.95, lon: 115.86, name: "Perth, AU" },
+    KnownLocation { places: &["New York City", "New York"], lat: 40.71, lon: -74.01, name: "New York, US" },
+    KnownLocation { places: &["Los Angeles", "California"], lat: 34.05, lon: -118.24, name: "Los Angeles, US" },
+    KnownLocation { places: &["Chicago"], lat: 41.88, lon: -87.63, name: "Chicago, US" },
+    KnownLocation { places: &["Miami", "Florida"], lat: 25.76, lon: -80.19, name: "Miami, US" },
+    KnownLocation { places: &["Houston", "Texas"], lat: 29.76, lon: -95.37, name: "Houston, US" },
+    KnownLocation { places: &["Washington DC"], lat: 38.91, lon: -77.04, name: "Washington DC, US" },
+    KnownLocation { places: &["Toronto"], lat: 43.65, lon: -79.38, name: "Toronto, CA" },
+    KnownLocation { places: &["London", "England", "United Kingdom"], lat: 51.51, lon: -0.13, name: "London, UK" },
+    KnownLocation { places: &["Paris", "France"], lat: 48.86, lon: 2.35, name: "Paris, FR" },
+    KnownLocation { places: &["Berlin", "Germany"], lat: 52.52, lon: 13.40, name: "Berlin, DE" },
+    KnownLocation { places: &["Moscow"], lat: 55.76, lon: 37.62, name: "Moscow, RU" },
+    KnownLocation { places: &["Kyiv"], lat: 50.45, lon: 30.52, name: "Kyiv, UA" },
+    KnownLocation { places: &["Dubai"], lat: 25.20, lon: 55.27, name: "Dubai, AE" },
+    KnownLocation { places: &["Beijing"], lat: 39.90, lon: 116.41, name: "Beijing, CN" },
+    KnownLocation { places: &["Hong Kong"], lat: 22.32, lon: 114.17, name: "Hong Kong" },
+    KnownLocation { places: &["Singapore"], lat: 1.35, lon: 103.82, name: "Singapore" },
+    KnownLocation { places: &["Tokyo", "Japan"], lat: 35.68, lon: 139.69, name: "Tokyo, JP" },
+    KnownLocation { places: &["Australia"], lat: -25.27, lon: 133.78, name: "Central Australia" },
     // Fallback US-centric (most ForecastEx markets are US-focused)
-    KnownLocation { keywords: &["united states", "us ", "u.s.", "american", "national"], lat: 39.83, lon: -98.58, name: "Central US" },
+    KnownLocation { places: &["United States"], lat: 39.83, lon: -98.58, name: "Central US" },
 ];
 
+/// A resolved forecast point.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Location {
+    name: String,
+    lat: f64,
+    lon: f64,
+}
+
+impl From<&KnownLocation> for Location {
+    fn from(known: &KnownLocation) -> Self {
+        Self { name: known.name.to_string(), lat: known.lat, lon: known.lon }
+    }
+}
+
+/// Days the forecast covers, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+struct Window {
+    start: NaiveDate,
+    end: NaiveDate,
+    /// The question's window lies past the forecast horizon; the days
+    /// shown are the week ahead instead.
+    beyond_horizon: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Open-Meteo response types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Deserialize, serde::Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct OpenMeteoResponse {
     #[serde(default)]
     current: Option<OpenMeteoCurrent>,
@@ -61,7 +126,7 @@ struct OpenMeteoResponse {
     daily: Option<OpenMeteoDaily>,
 }
 
-#[derive(Debug, Deserialize, serde::Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct OpenMeteoCurrent {
     #[serde(default)]
     temperature_2m: Option<f64>,
@@ -75,55 +140,187 @@ struct OpenMeteoCurrent {
     weather_code: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, serde::Serialize)]
+/// Daily series; Open-Meteo reports missing values as `null`.
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct OpenMeteoDaily {
     #[serde(default)]
     time: Vec<String>,
     #[serde(default)]
-    temperature_2m_max: Vec<f64>,
+    temperature_2m_max: Vec<Option<f64>>,
     #[serde(default)]
-    temperature_2m_min: Vec<f64>,
+    temperature_2m_min: Vec<Option<f64>>,
     #[serde(default)]
-    precipitation_sum: Vec<f64>,
+    precipitation_sum: Vec<Option<f64>>,
     #[serde(default)]
-    precipitation_probability_max: Vec<f64>,
+    precipitation_probability_max: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_speed_10m_max: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResponse {
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    #[serde(default)]
+    country_code: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
 // Provider
 // ---------------------------------------------------------------------------
 
-pub struct WeatherProvider {
+pub struct OpenMeteoProvider {
     http: Client,
+    max_requests_per_cycle: usize,
+    /// Requests made since the cycle began.
+    requests: AtomicUsize,
+    /// Geocoded place names, including misses, for the process lifetime.
+    geocoded: Mutex<HashMap<String, Option<Location>>>,
 }
 
-impl WeatherProvider {
+impl OpenMeteoProvider {
     pub fn new() -> Result<Self> {
         let http = Client::builder()
             .timeout(std::time::Duration::from_secs(15))
             .user_agent("ORACLE/0.1.0")
             .build()
             .context("Failed to build weather HTTP client")?;
-        Ok(Self { http })
+        Ok(Self {
+            http,
+            max_requests_per_cycle: DEFAULT_MAX_REQUESTS_PER_CYCLE,
+            requests: AtomicUsize::new(0),
+            geocoded: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Cap Open-Meteo requests per cycle.
+    pub fn with_max_requests_per_cycle(mut self, max: usize) -> Self {
+        self.max_requests_per_cycle = max;
+        self
+    }
+
+    /// The best-matching gazetteer location for the places in a question.
+    fn locate(facts: &QuestionFacts) -> Option<&'static KnownLocation> {
+        LOCATIONS.iter().find(|loc| facts.geography.iter().any(|g| loc.places.contains(&g.as_str())))
     }
 
     /// Extract the best-matching location from a market question.
+    #[cfg(test)]
     fn extract_location(question: &str) -> Option<&'static KnownLocation> {
-        let q = question.to_lowercase();
-        LOCATIONS.iter().find(|loc| {
-            loc.keywords.iter().any(|kw| q.contains(kw))
-        })
+        Self::locate(&question_parser::parse(question))
+    }
+
+    /// Places worth geocoding: parsed entities the question introduces
+    /// with a locative ("in Auckland", "at Heathrow"), in question order.
+    fn geocode_candidates<'a>(question: &str, facts: &'a QuestionFacts) -> Vec<&'a str> {
+        facts
+            .entities
+            .iter()
+            .filter(|entity| LOCATIVES.iter().any(|l| question.contains(&format!("{l} {entity}"))))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The days a question asks about: the day it names, else today
+    /// through the period it names or the market deadline. Clipped to the
+    /// forecast horizon.
+    fn window(question: &str, facts: &QuestionFacts, deadline: NaiveDate, today: NaiveDate) -> Window {
+        let named = facts.dates.first().copied();
+        let end = named.unwrap_or(deadline).max(today);
+        let start = match named {
+            Some(day) if names_day(question, day) => day.max(today),
+            _ => today,
+        };
+        let horizon = today + Duration::days(FORECAST_HORIZON_DAYS - 1);
+        if start > horizon {
+            return Window { start: today, end: today + Duration::days(FALLBACK_DAYS - 1), beyond_horizon: true };
+        }
+        Window { start, end: end.min(horizon), beyond_horizon: false }
+    }
+
+    /// Count a request against the per-cycle cap.
+    fn take_request(&self) -> Result<()> {
+        let made = self.requests.fetch_add(1, Ordering::SeqCst);
+        anyhow::ensure!(
+            made < self.max_requests_per_cycle,
+            "Open-Meteo request cap reached ({} per cycle)",
+            self.max_requests_per_cycle
+        );
+        Ok(())
+    }
+
+    async fn get_json(&self, url: &str, what: &str) -> Result<serde_json::Value> {
+        self.take_request()?;
+        let resp = self.http.get(url).send().await.with_context(|| format!("{what} request failed"))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            anyhow::bail!("{what} API error: {status}");
+        }
+        resp.json().await.with_context(|| format!("Failed to parse {what} response"))
     }
 
-    /// Build a human-readable summary from the API response.
-    fn summarise(location: &str, resp: &OpenMeteoResponse) -> String {
+    /// Look `place` up through the geocoding API, remembering the answer.
+    async fn geocode(&self, place: &str) -> Result<Option<Location>> {
+        if let Some(known) = self.geocoded.lock().unwrap_or_else(|e| e.into_inner()).get(place) {
+            return Ok(known.clone());
+        }
+        let url = reqwest::Url::parse_with_params(
+            "https://geocoding-api.open-meteo.com/v1/search",
+            &[("name", place), ("count", "1"), ("language", "en"), ("format", "json")],
+        )
+        .context("Invalid geocoding URL")?;
+        let json = self.get_json(url.as_str(), "Open-Meteo geocoding").await?;
+        let resp: GeocodingResponse = serde_json::from_value(json).context("Failed to parse Open-Meteo geocoding response")?;
+        let location = resp.results.into_iter().next().map(|r| Location {
+            name: match r.country_code {
+                Some(cc) => format!("{}, {cc}", r.name),
+                None => r.name,
+            },
+            lat: r.latitude,
+            lon: r.longitude,
+        });
+        debug!(place, found = ?location, "Geocoded weather location");
+        self.geocoded.lock().unwrap_or_else(|e| e.into_inner()).insert(place.to_string(), location.clone());
+        Ok(location)
+    }
+
+    /// The question's location: gazetteer first, then geocoding.
+    async fn resolve(&self, question: &str, facts: &QuestionFacts) -> Result<Option<Location>> {
+        if let Some(known) = Self::locate(facts) {
+            return Ok(Some(known.into()));
+        }
+        for place in Self::geocode_candidates(question, facts) {
+            if let Some(location) = self.geocode(place).await? {
+                return Ok(Some(location));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Build a compact summary from the API response.
+    fn summarise(location: &str, window: &Window, unit: &str, resp: &OpenMeteoResponse) -> String {
         let mut parts = Vec::new();
-        parts.push(format!("Weather data for {location}:"));
+        let span = if window.start == window.end {
+            window.start.to_string()
+        } else {
+            format!("{} to {}", window.start, window.end)
+        };
+        parts.push(format!("Weather for {location}, {span}:"));
+        if window.beyond_horizon {
+            parts.push("Question window is beyond the 16-day forecast; showing the week ahead.".to_string());
+        }
 
         if let Some(cur) = &resp.current {
             let mut current_parts = Vec::new();
             if let Some(t) = cur.temperature_2m {
-                current_parts.push(format!("{t:.1}°C"));
+                current_parts.push(format!("{t:.1}{unit}"));
             }
             if let Some(h) = cur.relative_humidity_2m {
                 current_parts.push(format!("{h:.0}% humidity"));
@@ -139,20 +336,50 @@ impl WeatherProvider {
             }
         }
 
-        if let Some(daily) = &resp.daily {
-            let days = daily.time.len().min(7);
-            if days > 0 {
-                parts.push(format!("{days}-day forecast:"));
-                for i in 0..days {
-                    let hi = daily.temperature_2m_max.get(i).copied().unwrap_or(0.0);
-                    let lo = daily.temperature_2m_min.get(i).copied().unwrap_or(0.0);
-                    let rain = daily.precipitation_sum.get(i).copied().unwrap_or(0.0);
-                    let prob = daily.precipitation_probability_max.get(i).copied().unwrap_or(0.0);
-                    let date = daily.time.get(i).map(|s| s.as_str()).unwrap_or("?");
-                    parts.push(format!(
-                        "  {date}: {lo:.0}–{hi:.0}°C, {rain:.1}mm rain ({prob:.0}% chance)"
-                    ));
-                }
+        let Some(daily) = &resp.daily else {
+            return parts.join("\n");
+        };
+        let at = |series: &[Option<f64>], i: usize| series.get(i).copied().flatten();
+        let fmt = |v: Option<f64>, precision: usize| v.map_or("?".to_string(), |v| format!("{v:.precision$}"));
+        for (i, date) in daily.time.iter().enumerate() {
+            let mut line = format!(
+                "  {date}: {}–{}{unit}, {}mm rain",
+                fmt(at(&daily.temperature_2m_min, i), 0),
+                fmt(at(&daily.temperature_2m_max, i), 0),
+                fmt(at(&daily.precipitation_sum, i), 1),
+            );
+            if let Some(prob) = at(&daily.precipitation_probability_max, i) {
+                line.push_str(&format!(" ({prob:.0}% chance)"));
+            }
+            if let Some(wind) = at(&daily.wind_speed_10m_max, i) {
+                line.push_str(&format!(", wind to {wind:.0}km/h"));
+            }
+            parts.push(line);
+        }
+
+        if daily.time.len() > 1 {
+            let all = |series: &[Option<f64>]| series.iter().flatten().copied().collect::<Vec<f64>>();
+            let max = |v: Vec<f64>| v.into_iter().reduce(f64::max);
+            let min = |v: Vec<f64>| v.into_iter().reduce(f64::min);
+            let rain: Vec<f64> = all(&daily.precipitation_sum);
+            let mut totals = Vec::new();
+            if let Some(hi) = max(all(&daily.temperature_2m_max)) {
+                totals.push(format!("high {hi:.0}{unit}"));
+            }
+            if let Some(lo) = min(all(&daily.temperature_2m_min)) {
+                totals.push(format!("low {lo:.0}{unit}"));
+            }
+            if !rain.is_empty() {
+                totals.push(format!("{:.1}mm rain", rain.iter().sum::<f64>()));
+            }
+            if let Some(prob) = max(all(&daily.precipitation_probability_max)) {
+                totals.push(format!("peak rain chance {prob:.0}%"));
+            }
+            if let Some(wind) = max(all(&daily.wind_speed_10m_max)) {
+                totals.push(format!("peak wind {wind:.0}km/h"));
+            }
+            if !totals.is_empty() {
+                parts.push(format!("Window: {}", totals.join(", ")));
             }
         }
 
@@ -160,61 +387,79 @@ impl WeatherProvider {
     }
 }
 
+/// Whether the question names `day` itself rather than a month or year
+/// it ends ("March 3" / "3rd of March" / "2026-03-03", not "by March").
+fn names_day(question: &str, day: NaiveDate) -> bool {
+    use chrono::Datelike;
+    if question.contains(&day.to_string()) {
+        return true;
+    }
+    question.split_whitespace().any(|word| {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let digits = ["st", "nd", "rd", "th"].iter().find_map(|s| word.strip_suffix(s)).unwrap_or(word);
+        digits.parse::<u32>().is_ok_and(|d| d == day.day())
+    })
+}
+
+/// Temperature unit the question's thresholds are in.
+fn wants_fahrenheit(facts: &QuestionFacts) -> bool {
+    facts.numeric_thresholds.iter().any(|(_, unit)| unit == "°F")
+}
+
 #[async_trait]
-impl DataProvider for WeatherProvider {
+impl DataProvider for OpenMeteoProvider {
     fn category(&self) -> MarketCategory {
         MarketCategory::Weather
     }
 
     async fn fetch_context(&self, market: &Market) -> Result<DataContext> {
-        let location = Self::extract_location(&market.question);
-
-        let (lat, lon, name) = match location {
-            Some(loc) => (loc.lat, loc.lon, loc.name),
-            None => {
-                debug!(question = %market.question, "No location found in weather market, using Central US fallback");
-                (39.83, -98.58, "Central US (fallback)")
-            }
+        let facts = question_parser::facts(market);
+        let Some(location) = self.resolve(&market.question, &facts).await? else {
+            debug!(question = %market.question, "No location found in weather market");
+            return Ok(DataContext::empty(MarketCategory::Weather));
         };
 
+        let today = Utc::now().date_naive();
+        let window = Self::window(&market.question, &facts, market.deadline.date_naive(), today);
+        let (unit_param, unit) = if wants_fahrenheit(&facts) { ("fahrenheit", "°F") } else { ("celsius", "°C") };
         let url = format!(
             "https://api.open-meteo.com/v1/forecast?\
-             latitude={lat}&longitude={lon}\
+             latitude={}&longitude={}\
              &current=temperature_2m,relative_humidity_2m,precipitation,wind_speed_10m,weather_code\
-             &daily=temperature_2m_max,temperature_2m_min,precipitation_sum,precipitation_probability_max\
-             &forecast_days=7&timezone=auto"
+             &daily=temperature_2m_max,temperature_2m_min,precipitation_sum,precipitation_probability_max,wind_speed_10m_max\
+             &start_date={}&end_date={}&temperature_unit={unit_param}&timezone=auto",
+            location.lat, location.lon, window.start, window.end,
         );
-
-        let resp = self.http.get(&url).send().await
-            .context("Open-Meteo request failed")?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            anyhow::bail!("Open-Meteo API error: {status}");
-        }
-
-        let data: OpenMeteoResponse = resp.json().await
+        let raw = self.get_json(&url, "Open-Meteo").await?;
+        let data: OpenMeteoResponse = serde_json::from_value(raw.clone())
             .context("Failed to parse Open-Meteo response")?;
 
-        let summary = Self::summarise(name, &data);
-        let raw = serde_json::to_value(&data).unwrap_or_default();
+        let mut summary = Self::summarise(&location.name, &window, unit, &data);
+        if let Some(terms) = question_parser::describe(&facts) {
+            summary.push_str(&format!("\nQuestion terms: {terms}"));
+        }
 
         Ok(DataContext {
             category: MarketCategory::Weather,
-            raw_data: raw,
+            raw_data: serde_json::json!({ "location": location, "window": window, "forecast": raw }),
             summary,
             freshness: Utc::now(),
-            source: format!("open-meteo ({name})"),
+            source: format!("open-meteo ({})", location.name),
             cost: Decimal::ZERO, // Free API
             metaculus_forecast: market.cross_refs.metaculus_prob,
             metaculus_forecasters: market.cross_refs.metaculus_forecasters,
             manifold_price: market.cross_refs.manifold_prob,
+            sections: Vec::new(),
         })
     }
 
     fn cost_per_call(&self) -> Decimal {
         Decimal::ZERO // Open-Meteo is free
     }
+
+    fn begin_cycle(&self) {
+        self.requests.store(0, Ordering::SeqCst);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -225,33 +470,96 @@ impl DataProvider for WeatherProvider {
 mod tests {
     use super::*;
 
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
     #[test]
     fn test_extract_location_sydney() {
-        let loc = WeatherProvider::extract_location("Will Sydney get more than 100mm rainfall?");
+        let loc = OpenMeteoProvider::extract_location("Will Sydney get more than 100mm rainfall?");
         assert!(loc.is_some());
         assert_eq!(loc.unwrap().name, "Sydney, AU");
     }
 
     #[test]
     fn test_extract_location_california() {
-        let loc = WeatherProvider::extract_location("California heat wave above 45C in 2026?");
+        let loc = OpenMeteoProvider::extract_location("California heat wave above 45C in 2026?");
         assert!(loc.is_some());
         assert!(loc.unwrap().name.contains("Los Angeles"));
     }
 
     #[test]
     fn test_extract_location_us_fallback() {
-        let loc = WeatherProvider::extract_location("Will there be a major hurricane in the US this year?");
+        let loc = OpenMeteoProvider::extract_location("Will there be a major hurricane in the US this year?");
         assert!(loc.is_some());
         // "us " matches the US fallback
     }
 
     #[test]
     fn test_extract_location_none() {
-        let loc = WeatherProvider::extract_location("Will it rain on Mars?");
+        let loc = OpenMeteoProvider::extract_location("Will it rain on Mars?");
         assert!(loc.is_none());
     }
 
+    #[test]
+    fn test_extract_location_real_phrasings() {
+        for (question, expected) in [
+            ("Will it rain in Sydney on March 3?", "Sydney, AU"),
+            ("Will NYC hit 100°F this summer?", "New York, US"),
+            ("Will London see snow on Christmas Day 2026?", "London, UK"),
+            ("Will the temperature in Melbourne exceed 40°C before February?", "Melbourne, AU"),
+            ("Will Tokyo record more than 50mm of rain on 14 July?", "Tokyo, JP"),
+            ("Will Paris reach 35°C during the Olympics?", "Paris, FR"),
+        ] {
+            let loc = OpenMeteoProvider::extract_location(question);
+            assert_eq!(loc.map(|l| l.name), Some(expected), "{question}");
+        }
+    }
+
+    #[test]
+    fn test_geocode_candidates_follow_locatives() {
+        let question = "Will it snow in Denver on Christmas Day?";
+        let facts = question_parser::parse(question);
+        assert!(OpenMeteoProvider::locate(&facts).is_none());
+        assert_eq!(OpenMeteoProvider::geocode_candidates(question, &facts), vec!["Denver"]);
+
+        // A capitalised name that isn't introduced as a place is left alone.
+        let question = "Will it rain on Mars?";
+        let facts = question_parser::parse(question);
+        assert!(OpenMeteoProvider::geocode_candidates(question, &facts).is_empty());
+    }
+
+    #[test]
+    fn test_window_for_named_day_and_period() {
+        let today = date("2026-02-25");
+        let deadline = date("2026-09-01");
+
+        let q = "Will it rain in Sydney on March 3?";
+        let w = OpenMeteoProvider::window(q, &question_parser::parse_at(q, today), deadline, today);
+        assert_eq!((w.start, w.end, w.beyond_horizon), (date("2026-03-03"), date("2026-03-03"), false));
+
+        // A month runs from today, clipped to the 16-day horizon.
+        let q = "Will Sydney get 100mm of rain by March?";
+        let w = OpenMeteoProvider::window(q, &question_parser::parse_at(q, today), deadline, today);
+        assert_eq!((w.start, w.end), (today, date("2026-03-12")));
+
+        // No date: the deadline bounds the window.
+        let q = "Will NYC hit 100°F this summer?";
+        let w = OpenMeteoProvider::window(q, &question_parser::parse_at(q, today), date("2026-03-01"), today);
+        assert_eq!((w.start, w.end), (today, date("2026-03-01")));
+
+        // Past the horizon: the week ahead, flagged.
+        let q = "Will it rain in Sydney on May 3?";
+        let w = OpenMeteoProvider::window(q, &question_parser::parse_at(q, today), deadline, today);
+        assert_eq!((w.start, w.end, w.beyond_horizon), (today, date("2026-03-03"), true));
+    }
+
+    #[test]
+    fn test_fahrenheit_from_thresholds() {
+        assert!(wants_fahrenheit(&question_parser::parse("Will NYC hit 100°F this summer?")));
+        assert!(!wants_fahrenheit(&question_parser::parse("Will Perth exceed 40°C in January?")));
+    }
+
     #[test]
     fn test_summarise_with_current() {
         let resp = OpenMeteoResponse {
@@ -264,10 +572,11 @@ mod tests {
             }),
             daily: None,
         };
-        let summary = WeatherProvider::summarise("Sydney, AU", &resp);
+        let window = Window { start: date("2026-02-17"), end: date("2026-02-17"), beyond_horizon: false };
+        let summary = OpenMeteoProvider::summarise("Sydney, AU", &window, "°C", &resp);
+        assert!(summary.starts_with("Weather for Sydney, AU, 2026-02-17:"));
         assert!(summary.contains("25.3°C"));
         assert!(summary.contains("60% humidity"));
-        assert!(summary.contains("Sydney"));
     }
 
     #[test]
@@ -276,20 +585,54 @@ mod tests {
             current: None,
             daily: Some(OpenMeteoDaily {
                 time: vec!["2026-02-17".to_string(), "2026-02-18".to_string()],
-                temperature_2m_max: vec![30.0, 28.0],
-                temperature_2m_min: vec![20.0, 18.0],
-                precipitation_sum: vec![5.0, 0.0],
-                precipitation_probability_max: vec![80.0, 10.0],
+                temperature_2m_max: vec![Some(30.0), Some(28.0)],
+                temperature_2m_min: vec![Some(20.0), Some(18.0)],
+                precipitation_sum: vec![Some(5.0), Some(0.0)],
+                precipitation_probability_max: vec![Some(80.0), None],
+                wind_speed_10m_max: vec![Some(25.0), Some(31.0)],
             }),
         };
-        let summary = WeatherProvider::summarise("Test", &resp);
-        assert!(summary.contains("2-day forecast"));
-        assert!(summary.contains("80% chance"));
+        let window = Window { start: date("2026-02-17"), end: date("2026-02-18"), beyond_horizon: false };
+        let summary = OpenMeteoProvider::summarise("Test", &window, "°C", &resp);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Weather for Test, 2026-02-17 to 2026-02-18:");
+        assert_eq!(lines[1], "  2026-02-17: 20–30°C, 5.0mm rain (80% chance), wind to 25km/h");
+        assert_eq!(lines[2], "  2026-02-18: 18–28°C, 0.0mm rain, wind to 31km/h");
+        assert_eq!(lines[3], "Window: high 30°C, low 18°C, 5.0mm rain, peak rain chance 80%, peak wind 31km/h");
+    }
+
+    #[test]
+    fn test_summarise_flags_beyond_horizon_in_fahrenheit() {
+        let resp = OpenMeteoResponse {
+            current: None,
+            daily: Some(OpenMeteoDaily {
+                time: vec!["2026-02-17".to_string()],
+                temperature_2m_max: vec![Some(91.0)],
+                temperature_2m_min: vec![Some(70.0)],
+                precipitation_sum: vec![None],
+                ..OpenMeteoDaily::default()
+            }),
+        };
+        let window = Window { start: date("2026-02-17"), end: date("2026-02-23"), beyond_horizon: true };
+        let summary = OpenMeteoProvider::summarise("New York, US", &window, "°F", &resp);
+        assert!(summary.contains("beyond the 16-day forecast"));
+        assert!(summary.contains("2026-02-17: 70–91°F, ?mm rain"));
+        assert!(!summary.contains("Window:"));
+    }
+
+    #[test]
+    fn test_request_cap_resets_each_cycle() {
+        let p = OpenMeteoProvider::new().unwrap().with_max_requests_per_cycle(2);
+        assert!(p.take_request().is_ok());
+        assert!(p.take_request().is_ok());
+        assert!(p.take_request().is_err());
+        p.begin_cycle();
+        assert!(p.take_request().is_ok());
     }
 
     #[test]
     fn test_provider_category() {
-        let p = WeatherProvider::new().unwrap();
+        let p = OpenMeteoProvider::new().unwrap();
         assert_eq!(p.category(), MarketCategory::Weather);
         assert_eq!(p.cost_per_call(), Decimal::ZERO);
     }
@@ -0,0 +1,224 @@
+//! Process memory and collection-size audit.
+//!
+//! Every `[diagnostics].every_cycles` cycles the agent samples its resident
+//! set size and open file descriptors (from `/proc/self` on Linux; `None`
+//! elsewhere) and the size of every collection that is meant to stay
+//! bounded. A sample warns when:
+//!
+//! - a collection holds more than its bound, or
+//! - RSS rose at every one of the last `rss_growth_samples` samples, by
+//!   more than `rss_growth_mb` in total.
+//!
+//! Samples are appended to an NDJSON file and the latest is served in the
+//! `diagnostics` section of `/api/status`. Stores report their collections
+//! by implementing [`SizedStore`].
+
+use std::collections::VecDeque;
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::DiagnosticsConfig;
+
+/// Page size assumed when converting `/proc/self/statm` pages to bytes.
+const PAGE_SIZE: u64 = 4096;
+
+/// Current size of one collection a store maintains.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionSize {
+    pub name: String,
+    pub len: usize,
+    /// Upper bound the store enforces; `None` when growth is bounded only
+    /// by the data (e.g. open bets).
+    pub bound: Option<usize>,
+}
+
+impl CollectionSize {
+    pub fn new(name: &str, len: usize, bound: Option<usize>) -> Self {
+        Self { name: name.to_string(), len, bound }
+    }
+
+    fn over_bound(&self) -> bool {
+        self.bound.is_some_and(|b| self.len > b)
+    }
+}
+
+/// A store whose collections are reported in diagnostics samples.
+pub trait SizedStore {
+    fn collection_sizes(&self) -> Vec<CollectionSize>;
+}
+
+/// One diagnostics sample.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticsSample {
+    pub timestamp: DateTime<Utc>,
+    pub cycle: u64,
+    pub rss_bytes: Option<u64>,
+    pub open_fds: Option<usize>,
+    pub collections: Vec<CollectionSize>,
+    /// Bound violations and RSS growth detected in this sample.
+    pub warnings: Vec<String>,
+}
+
+/// Resident set size from `/proc/self/statm`, or `None` off Linux.
+pub fn rss_bytes() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * PAGE_SIZE)
+}
+
+/// Number of open file descriptors, or `None` off Linux.
+pub fn open_fds() -> Option<usize> {
+    Some(std::fs::read_dir("/proc/self/fd").ok()?.count())
+}
+
+/// Periodic sampler holding the recent RSS history.
+pub struct Diagnostics {
+    config: DiagnosticsConfig,
+    rss_history: VecDeque<u64>,
+}
+
+impl Diagnostics {
+    pub fn new(config: DiagnosticsConfig) -> Self {
+        Self { config, rss_history: VecDeque::new() }
+    }
+
+    /// Whether a sample is due after `cycle` completes.
+    pub fn due(&self, cycle: u64) -> bool {
+        self.config.every_cycles > 0 && cycle.is_multiple_of(self.config.every_cycles)
+    }
+
+    /// Sample the process and `stores`, log warnings, and append the sample
+    /// to the NDJSON log.
+    pub fn sample(&mut self, cycle: u64, stores: &[&dyn SizedStore]) -> DiagnosticsSample {
+        let collections = stores.iter().flat_map(|s| s.collection_sizes()).collect();
+        let sample = self.record(cycle, rss_bytes(), open_fds(), collections);
+        if let Err(e) = self.append(&sample) {
+            warn!(error = %e, path = %self.config.log_file, "Failed to write diagnostics sample");
+        }
+        sample
+    }
+
+    /// Build a sample from measured values and check it against the bounds
+    /// and the RSS history.
+    fn record(
+        &mut self,
+        cycle: u64,
+        rss_bytes: Option<u64>,
+        open_fds: Option<usize>,
+        collections: Vec<CollectionSize>,
+    ) -> DiagnosticsSample {
+        let mut warnings = Vec::new();
+        for c in collections.iter().filter(|c| c.over_bound()) {
+            let bound = c.bound.unwrap_or_default();
+            warn!(collection = %c.name, len = c.len, bound, "Collection exceeds its bound");
+            warnings.push(format!("{} holds {} entries (bound {bound})", c.name, c.len));
+        }
+
+        if let Some(rss) = rss_bytes {
+            self.rss_history.push_back(rss);
+            let window = self.config.rss_growth_samples.max(2);
+            while self.rss_history.len() > window {
+                self.rss_history.pop_front();
+            }
+            let rising = self.rss_history.len() == window
+                && self.rss_history.iter().zip(self.rss_history.iter().skip(1)).all(|(a, b)| b > a);
+            let growth = rss.saturating_sub(self.rss_history[0]);
+            if rising && growth > self.config.rss_growth_mb * 1024 * 1024 {
+                let mb = growth / (1024 * 1024);
+                warn!(samples = window, growth_mb = mb, "RSS grew at every recent diagnostics sample");
+                warnings.push(format!("RSS grew {mb} MB over {window} consecutive samples"));
+            }
+        }
+
+        DiagnosticsSample { timestamp: Utc::now(), cycle, rss_bytes, open_fds, collections, warnings }
+    }
+
+    fn append(&self, sample: &DiagnosticsSample) -> anyhow::Result<()> {
+        if self.config.log_file.is_empty() {
+            return Ok(());
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.config.log_file)?;
+        writeln!(file, "{}", serde_json::to_string(sample)?)?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedStore(Vec<CollectionSize>);
+
+    impl SizedStore for FixedStore {
+        fn collection_sizes(&self) -> Vec<CollectionSize> {
+            self.0.clone()
+        }
+    }
+
+    fn config() -> DiagnosticsConfig {
+        DiagnosticsConfig { log_file: String::new(), ..DiagnosticsConfig::default() }
+    }
+
+    #[test]
+    fn test_overfilled_store_warns() {
+        let mut diag = Diagnostics::new(config());
+        let overfilled = FixedStore(vec![
+            CollectionSize::new("cycle_log", 101, Some(100)),
+            CollectionSize::new("recent_trades", 200, Some(200)),
+            CollectionSize::new("open_bets", 10_000, None),
+        ]);
+        let sample = diag.sample(10, &[&overfilled]);
+        assert_eq!(sample.collections.len(), 3);
+        assert_eq!(sample.warnings, ["cycle_log holds 101 entries (bound 100)"]);
+
+        // Real stores stay within their bounds.
+        let state = crate::types::AgentState::new(rust_decimal::Decimal::ONE_HUNDRED);
+        assert!(diag.sample(20, &[&state]).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_monotonic_rss_growth_warns() {
+        let mut diag = Diagnostics::new(DiagnosticsConfig { rss_growth_samples: 3, rss_growth_mb: 10, ..config() });
+        let mb = 1024 * 1024;
+        // Rising but below the growth threshold.
+        for rss in [100, 104, 108] {
+            assert!(diag.record(0, Some(rss * mb), None, Vec::new()).warnings.is_empty());
+        }
+        // 104 → 108 → 120: rising and +16 MB across the window.
+        let sample = diag.record(0, Some(120 * mb), None, Vec::new());
+        assert_eq!(sample.warnings, ["RSS grew 16 MB over 3 consecutive samples"]);
+        // A dip resets the streak.
+        assert!(diag.record(0, Some(119 * mb), None, Vec::new()).warnings.is_empty());
+        // No measurement (non-Linux): nothing to compare.
+        assert!(diag.record(0, None, None, Vec::new()).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_due_and_ndjson_log() {
+        let path = std::env::temp_dir().join(format!("oracle_diag_{}.ndjson", uuid::Uuid::new_v4()));
+        let mut diag = Diagnostics::new(DiagnosticsConfig {
+            every_cycles: 5,
+            log_file: path.to_string_lossy().into_owned(),
+            ..DiagnosticsConfig::default()
+        });
+        assert!(!diag.due(4) && diag.due(5) && diag.due(10));
+        diag.sample(5, &[]);
+        diag.sample(10, &[]);
+        let lines: Vec<DiagnosticsSample> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.iter().map(|s| s.cycle).collect::<Vec<_>>(), [5, 10]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(!Diagnostics::new(DiagnosticsConfig { every_cycles: 0, ..config() }).due(10));
+    }
+}
@@ -0,0 +1,343 @@
+//! Question embeddings for cross-platform matching.
+//!
+//! Word overlap misses paraphrases ("Fed cuts rates in March" vs "FOMC
+//! lowers the federal funds target at the March meeting") and scores
+//! templated questions that differ in one name as near-identical. With
+//! `[scanner.embeddings]` enabled, the scanner instead compares questions
+//! by the cosine similarity of their embeddings (see
+//! [`crate::engine::scanner::MarketRouter::with_embeddings`]).
+//!
+//! Two [`Embedder`]s are provided: [`HashEmbedder`], which hashes a
+//! question's words and their trigrams into a fixed-size vector and needs
+//! no network, and [`ApiEmbedder`], which calls the OpenAI-compatible
+//! `/embeddings` endpoint of the configured LLM provider. [`EmbeddingMatcher`]
+//! puts an [`store::EmbeddingStore`] in front of either, so a question is
+//! embedded once and reused across cycles and restarts.
+
+pub mod store;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::{EmbeddingsConfig, LlmConfig};
+use crate::language;
+use store::EmbeddingStore;
+
+/// Turns question text into vectors comparable by [`cosine`].
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// One vector per text, in order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// Name of the model; cached vectors of another model are discarded.
+    fn model_name(&self) -> &str;
+}
+
+/// Cosine similarity of two vectors; 0 when either is zero or they differ
+/// in length.
+pub fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (&x, &y) in a.iter().zip(b) {
+        let (x, y) = (f64::from(x), f64::from(y));
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+// ---------------------------------------------------------------------------
+// Local embedder
+// ---------------------------------------------------------------------------
+
+/// Weight of a character trigram relative to a whole word.
+const TRIGRAM_WEIGHT: f32 = 0.5;
+
+/// Deterministic embedder hashing each word of the normalised question
+/// (longer than two characters) and its character trigrams into
+/// `dimensions` signed buckets. Shared trigrams let inflections ("cuts",
+/// "cut") score above unrelated words; it knows no synonyms.
+pub struct HashEmbedder {
+    dimensions: usize,
+    name: String,
+}
+
+impl HashEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions: dimensions.max(1), name: format!("local-hash-{dimensions}") }
+    }
+
+    /// The vector of one text, L2-normalised.
+    pub fn vector(&self, text: &str) -> Vec<f32> {
+        let mut v = vec![0.0f32; self.dimensions];
+        let normalized = language::normalize(text).to_lowercase();
+        for word in normalized.split(|c: char| !c.is_alphanumeric()).filter(|w| w.chars().count() > 2) {
+            self.add(&mut v, word, 1.0);
+            let chars: Vec<char> = format!("<{word}>").chars().collect();
+            for gram in chars.windows(3) {
+                self.add(&mut v, &gram.iter().collect::<String>(), TRIGRAM_WEIGHT);
+            }
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            v.iter_mut().for_each(|x| *x /= norm);
+        }
+        v
+    }
+
+    fn add(&self, v: &mut [f32], feature: &str, weight: f32) {
+        let hash = fnv1a(feature.as_bytes());
+        let bucket = (hash % self.dimensions as u64) as usize;
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        v[bucket] += sign * weight;
+    }
+}
+
+/// 64-bit FNV-1a: stable across runs and platforms, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[async_trait]
+impl Embedder for HashEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|t| self.vector(t)).collect())
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+}
+
+// ---------------------------------------------------------------------------
+// API embedder
+// ---------------------------------------------------------------------------
+
+/// Most texts sent in one embeddings request.
+const MAX_INPUTS_PER_CALL: usize = 256;
+
+/// Embeddings URL of `llm.provider`, `None` for providers without one
+/// (Anthropic).
+pub fn endpoint(llm: &LlmConfig) -> Option<String> {
+    let base = match llm.provider.as_str() {
+        "openai" => llm.base_url.as_deref().unwrap_or("https://api.openai.com/v1"),
+        "grok" => "https://api.x.ai/v1",
+        "openrouter" => "https://openrouter.ai/api/v1",
+        _ => return None,
+    };
+    Some(format!("{}/embeddings", base.trim_end_matches('/')))
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Client for an OpenAI-compatible `/embeddings` endpoint.
+pub struct ApiEmbedder {
+    http: Client,
+    endpoint: String,
+    api_key: String,
+    model: String,
+}
+
+impl ApiEmbedder {
+    pub fn new(endpoint: String, api_key: String, model: String) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(std::time::Duration::from_secs(60))
+            .build()
+            .context("Failed to build embeddings HTTP client")?;
+        Ok(Self { http, endpoint, api_key, model })
+    }
+
+    async fn embed_chunk(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let resp = self
+            .http
+            .post(&self.endpoint)
+            .bearer_auth(&self.api_key)
+            .json(&EmbeddingRequest { model: &self.model, input: texts })
+            .send()
+            .await
+            .context("Embeddings request failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Embeddings API returned {status}: {}", body.chars().take(200).collect::<String>());
+        }
+        let mut body: EmbeddingResponse = resp.json().await.context("Failed to parse embeddings response")?;
+        anyhow::ensure!(
+            body.data.len() == texts.len(),
+            "Embeddings API returned {} vectors for {} texts",
+            body.data.len(),
+            texts.len()
+        );
+        body.data.sort_by_key(|d| d.index);
+        Ok(body.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+#[async_trait]
+impl Embedder for ApiEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(MAX_INPUTS_PER_CALL) {
+            vectors.extend(self.embed_chunk(chunk).await?);
+        }
+        Ok(vectors)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cached matcher
+// ---------------------------------------------------------------------------
+
+/// An embedder behind the embedding cache, with the cosine threshold two
+/// questions must reach to match.
+pub struct EmbeddingMatcher {
+    embedder: Box<dyn Embedder>,
+    store: Mutex<EmbeddingStore>,
+    threshold: f64,
+}
+
+impl EmbeddingMatcher {
+    pub fn new(embedder: Box<dyn Embedder>, store: EmbeddingStore, threshold: f64) -> Self {
+        Self { embedder, store: Mutex::new(store), threshold }
+    }
+
+    /// The matcher `[scanner.embeddings]` describes, with its cache loaded
+    /// from `cache_file`. The `api` provider reads its key from the LLM's
+    /// key variable.
+    pub fn from_config(config: &EmbeddingsConfig, llm: &LlmConfig) -> Result<Self> {
+        let embedder: Box<dyn Embedder> = match config.provider.as_str() {
+            "api" => {
+                let url = endpoint(llm).with_context(|| format!("{} has no embedding endpoint", llm.provider))?;
+                let key = std::env::var(llm.key_env())
+                    .with_context(|| format!("{} not set for the embeddings API", llm.key_env()))?;
+                Box::new(ApiEmbedder::new(url, key, config.model.clone())?)
+            }
+            _ => Box::new(HashEmbedder::new(config.dimensions)),
+        };
+        let store = EmbeddingStore::load(&config.cache_file, embedder.model_name(), config.max_cache_entries)?;
+        Ok(Self::new(embedder, store, config.match_threshold))
+    }
+
+    /// Minimum cosine similarity for a match.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Embeddings of `questions`, in order. Cached ones are reused; the
+    /// rest are embedded in one call and cached.
+    pub async fn embed_all(&self, questions: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let hashes: Vec<String> = questions.iter().map(|q| store::question_hash(q)).collect();
+        let mut vectors: Vec<Option<Vec<f32>>> = {
+            let mut store = self.lock();
+            hashes.iter().map(|h| store.get(h)).collect()
+        };
+        // The same question listed twice is embedded once.
+        let mut seen = HashSet::new();
+        let missing: Vec<usize> =
+            (0..questions.len()).filter(|&i| vectors[i].is_none() && seen.insert(&hashes[i])).collect();
+        if !missing.is_empty() {
+            let texts: Vec<String> = missing.iter().map(|&i| questions[i].to_string()).collect();
+            let embedded = self.embedder.embed(&texts).await?;
+            anyhow::ensure!(
+                embedded.len() == texts.len(),
+                "{} returned {} embeddings for {} questions",
+                self.embedder.model_name(),
+                embedded.len(),
+                texts.len()
+            );
+            let fresh: HashMap<&String, Vec<f32>> = missing.iter().map(|&i| &hashes[i]).zip(embedded).collect();
+            for (hash, slot) in hashes.iter().zip(&mut vectors) {
+                if slot.is_none() {
+                    *slot = fresh.get(hash).cloned();
+                }
+            }
+            let mut store = self.lock();
+            for (hash, vector) in fresh {
+                store.insert(hash.clone(), vector);
+            }
+        }
+        debug!(questions = questions.len(), embedded = missing.len(), "Questions embedded");
+        Ok(vectors.into_iter().map(Option::unwrap_or_default).collect())
+    }
+
+    /// Write the cache, logging a failure.
+    pub fn save(&self) {
+        if let Err(e) = self.lock().save() {
+            warn!(error = %format!("{e:#}"), "Failed to save embedding cache");
+        }
+    }
+
+    /// Cache lookups that found an embedding and those that didn't, since
+    /// the cache was loaded.
+    pub fn cache_stats(&self) -> (u64, u64) {
+        let store = self.lock();
+        (store.hits(), store.misses())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, EmbeddingStore> {
+        self.store.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn similarity(a: &str, b: &str) -> f64 {
+        let e = HashEmbedder::new(256);
+        cosine(&e.vector(a), &e.vector(b))
+    }
+
+    #[test]
+    fn test_hash_embedding_is_deterministic_and_normalised() {
+        let e = HashEmbedder::new(64);
+        let v = e.vector("Will the Fed cut rates in March?");
+        assert_eq!(v, HashEmbedder::new(64).vector("Will the Fed cut rates in March?"));
+        assert!((v.iter().map(|x| x * x).sum::<f32>() - 1.0).abs() < 1e-5);
+        assert!(e.vector("a b").iter().all(|&x| x == 0.0));
+    }
+
+    #[test]
+    fn test_hash_similarity_ranks_rewordings_above_other_topics() {
+        let question = "Will the Fed cut rates in March 2026?";
+        let reworded = similarity(question, "Fed cuts interest rates at the March 2026 meeting?");
+        let other = similarity(question, "Will the Lakers win the NBA finals?");
+        assert!((similarity(question, question) - 1.0).abs() < 1e-9);
+        assert!(reworded > 0.6 && other < 0.4, "reworded {reworded}, other {other}");
+    }
+}
@@ -0,0 +1,244 @@
+//! Embedding cache.
+//!
+//! Questions rarely change between cycles, so each one is embedded once
+//! and its vector kept under a hash of its normalised text. The cache
+//! holds at most `max_cache_entries` vectors, dropping the least recently
+//! used beyond that, and is saved as one versioned JSON document (see
+//! [`crate::storage::migrations`]) naming the model that made the vectors:
+//! a cache of another model is discarded on load. Vectors are stored as
+//! base64 of their little-endian `f32`s.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::language;
+use crate::storage::migrations::{self, Artifact};
+
+/// Version of the embedding cache layout.
+pub const EMBEDDING_CACHE_VERSION: u32 = 1;
+
+/// Cache key of a question: a hash of its [`language::normalize`]d,
+/// lowercased text, so typographic variants share an entry.
+pub fn question_hash(question: &str) -> String {
+    let digest = Sha256::digest(language::normalize(question).to_lowercase().as_bytes());
+    digest[..16].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    schema_version: u32,
+    model: String,
+    entries: Vec<CacheEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    hash: String,
+    /// Base64 of the vector's little-endian `f32`s.
+    vector: String,
+    last_used: u64,
+}
+
+/// Cached vector and the lookup clock when it was last used.
+struct Cached {
+    vector: Vec<f32>,
+    last_used: u64,
+}
+
+/// Embeddings of one model keyed by [`question_hash`], least recently used
+/// evicted beyond `max_entries`.
+pub struct EmbeddingStore {
+    path: PathBuf,
+    model: String,
+    max_entries: usize,
+    entries: HashMap<String, Cached>,
+    /// Advances on every lookup and insert; orders entries by use.
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl EmbeddingStore {
+    /// An empty cache of `model`'s vectors saving to `path`.
+    pub fn new(path: impl Into<PathBuf>, model: &str, max_entries: usize) -> Self {
+        Self {
+            path: path.into(),
+            model: model.to_string(),
+            max_entries: max_entries.max(1),
+            entries: HashMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Load the cache at `path`. A missing file, or one written for a
+    /// different model, is an empty cache.
+    pub fn load(path: impl Into<PathBuf>, model: &str, max_entries: usize) -> Result<Self> {
+        let mut store = Self::new(path, model, max_entries);
+        if !store.path.exists() {
+            return Ok(store);
+        }
+        let what = store.path.display().to_string();
+        let text = std::fs::read_to_string(&store.path).with_context(|| format!("Failed to read {what}"))?;
+        let doc: serde_json::Value = serde_json::from_str(&text).with_context(|| format!("Failed to parse {what}"))?;
+        let doc = migrations::upgrade(Artifact::EmbeddingCache, doc, &what)?;
+        let file: CacheFile = serde_json::from_value(doc).with_context(|| format!("Failed to parse {what}"))?;
+        if file.model != model {
+            info!(path = %what, cached = %file.model, model, "Embedding cache is for another model — starting empty");
+            return Ok(store);
+        }
+        for entry in file.entries {
+            let vector = decode(&entry.vector).with_context(|| format!("Bad vector for {} in {what}", entry.hash))?;
+            store.clock = store.clock.max(entry.last_used);
+            store.entries.insert(entry.hash, Cached { vector, last_used: entry.last_used });
+        }
+        store.evict();
+        Ok(store)
+    }
+
+    /// Write the cache, replacing the file only once the new one is
+    /// complete.
+    pub fn save(&self) -> Result<()> {
+        let file = CacheFile {
+            schema_version: EMBEDDING_CACHE_VERSION,
+            model: self.model.clone(),
+            entries: self
+                .entries
+                .iter()
+                .map(|(hash, c)| CacheEntry { hash: hash.clone(), vector: encode(&c.vector), last_used: c.last_used })
+                .collect(),
+        };
+        let json = serde_json::to_string(&file).context("Failed to serialise embedding cache")?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lookups that found a vector.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups that didn't.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// The vector cached under `hash`, marking it used.
+    pub fn get(&mut self, hash: &str) -> Option<Vec<f32>> {
+        self.clock += 1;
+        match self.entries.get_mut(hash) {
+            Some(cached) => {
+                cached.last_used = self.clock;
+                self.hits += 1;
+                Some(cached.vector.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Cache `vector` under `hash`, evicting the least recently used
+    /// entries beyond the cap.
+    pub fn insert(&mut self, hash: String, vector: Vec<f32>) {
+        self.clock += 1;
+        self.entries.insert(hash, Cached { vector, last_used: self.clock });
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        let excess = self.entries.len().saturating_sub(self.max_entries);
+        if excess == 0 {
+            return;
+        }
+        let mut by_use: Vec<(u64, String)> = self.entries.iter().map(|(h, c)| (c.last_used, h.clone())).collect();
+        by_use.sort_unstable();
+        for (_, hash) in by_use.into_iter().take(excess) {
+            self.entries.remove(&hash);
+        }
+    }
+}
+
+fn encode(vector: &[f32]) -> String {
+    let bytes: Vec<u8> = vector.iter().flat_map(|x| x.to_le_bytes()).collect();
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn decode(text: &str) -> Result<Vec<f32>> {
+    let bytes = base64::engine::general_purpose::STANDARD.decode(text)?;
+    anyhow::ensure!(bytes.len() % 4 == 0, "{} bytes is not a whole number of f32s", bytes.len());
+    Ok(bytes.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("oracle_embeddings_{name}_{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_question_hash_ignores_typography_and_case() {
+        assert_eq!(question_hash("Will \u{201C}X\u{201D} happen?"), question_hash("will \"x\" happen?"));
+        assert_ne!(question_hash("Will X happen?"), question_hash("Will Y happen?"));
+        assert_eq!(question_hash("q").len(), 32);
+    }
+
+    #[test]
+    fn test_least_recently_used_evicted_beyond_cap() {
+        let mut store = EmbeddingStore::new(temp_file("lru"), "m", 2);
+        store.insert("a".into(), vec![1.0]);
+        store.insert("b".into(), vec![2.0]);
+        assert_eq!(store.get("a"), Some(vec![1.0]));
+        store.insert("c".into(), vec![3.0]);
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get("b"), None);
+        assert!(store.get("a").is_some() && store.get("c").is_some());
+        assert_eq!((store.hits(), store.misses()), (3, 1));
+    }
+
+    #[test]
+    fn test_round_trips_and_discards_other_models() {
+        let path = temp_file("persist");
+        let mut store = EmbeddingStore::new(&path, "local-hash-4", 10);
+        let vector = vec![0.5, -0.25, 1e-7, f32::MAX];
+        store.insert(question_hash("Will it rain?"), vector.clone());
+        store.save().unwrap();
+
+        let mut loaded = EmbeddingStore::load(&path, "local-hash-4", 10).unwrap();
+        assert_eq!(loaded.get(&question_hash("Will it rain?")), Some(vector));
+        let doc: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["schema_version"], EMBEDDING_CACHE_VERSION);
+
+        assert!(EmbeddingStore::load(&path, "text-embedding-3-small", 10).unwrap().is_empty());
+        assert!(EmbeddingStore::load(temp_file("missing"), "m", 10).unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,22 +2,40 @@
 //!
 //! Reconciles each scan cycle: deducts costs, records trade outcomes,
 //! updates bankroll, and checks if the agent is still alive.
+//!
+//! The bankroll books only realised P&L and costs. A mark-to-market pass
+//! ([`Accountant::mark_to_market`]) values the open AUD bets at current
+//! prices, giving `total_equity` (bankroll plus unrealized P&L) alongside
+//! `cash_bankroll` (bankroll less open stakes).
+
+use std::collections::{BTreeMap, HashMap};
 
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
-use tracing::{info, warn};
-
-use crate::engine::executor::{ExecutedTrade, ExecutionReport};
-use crate::types::{AgentState, AgentStatus};
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::engine::auto_exit::CloseResult;
+use crate::engine::daily;
+use crate::engine::executor::{ExecutedTrade, ExecutionReport, FailedTrade};
+use crate::engine::reconcile;
+use crate::platforms::manifold::ManifoldUserInfo;
+use crate::storage::metrics::{CycleMetrics, DecisionRow};
+use crate::strategy::links::link_key;
+use crate::types::{self, AgentState, AgentStatus, Market, Position, Side, Tier};
+
+/// How long an unconfirmed order may stay missing from its venue's
+/// positions before it is taken as never placed.
+pub const UNCONFIRMED_GRACE_HOURS: i64 = 2;
 
 // ---------------------------------------------------------------------------
 // Cycle cost breakdown
 // ---------------------------------------------------------------------------
 
 /// All costs incurred during a single scan cycle.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CycleCosts {
     /// LLM API cost (token usage).
     pub llm_cost: Decimal,
@@ -51,21 +69,70 @@ impl CycleCosts {
 // ---------------------------------------------------------------------------
 
 /// Summary of a complete scan→estimate→execute cycle.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CycleReport {
     pub cycle_number: u64,
     pub markets_scanned: usize,
     pub edges_found: usize,
     pub bets_placed: usize,
+    /// Failed bets, excluding markets that closed before execution.
     pub bets_failed: usize,
+    /// Markets that closed or were suspended between scan and execution.
+    pub closed_markets: Vec<String>,
+    /// Orders that may have reached the venue but were never confirmed.
+    pub bets_unconfirmed: usize,
     pub total_committed: Decimal,
     pub cycle_costs: CycleCosts,
     pub bankroll_before: Decimal,
     pub bankroll_after: Decimal,
+    /// Bankroll after the cycle less the stakes of open AUD bets.
+    pub cash_bankroll: Decimal,
+    /// Bankroll after the cycle plus the open AUD bets' unrealized P&L.
+    pub total_equity: Decimal,
     pub status: AgentStatus,
     pub timestamp: chrono::DateTime<Utc>,
     /// Executed trade details for dashboard and logging.
     pub executed_trades: Vec<ExecutedTrade>,
+    /// Failed bets, excluding markets that closed before execution.
+    pub failed_trades: Vec<FailedTrade>,
+    /// Per-market strategy decisions for the research history. Caller fills this in.
+    pub decisions: Vec<DecisionRow>,
+    /// LLM spend per estimation tier; empty when tiering is off. Caller
+    /// fills this in.
+    pub llm_cost_by_tier: BTreeMap<Tier, Decimal>,
+    /// LLM spend per serving model; empty unless routing or failover tags
+    /// estimates with their model. Caller fills this in.
+    pub llm_cost_by_model: BTreeMap<String, Decimal>,
+    /// Markets whose cached estimate was reused instead of an LLM call,
+    /// and those estimated afresh. Caller fills these in.
+    pub estimate_cache_hits: usize,
+    pub estimate_cache_misses: usize,
+    /// LLM spend counted against the cycle budget, and what was left of it
+    /// (`None` with no `[costs]` cap). Caller fills these in.
+    pub budget_used: Decimal,
+    pub budget_remaining: Option<Decimal>,
+    /// Markets the cost budget left unestimated. Caller fills this in.
+    pub budget_skipped: usize,
+}
+
+impl CycleReport {
+    /// Compact summary kept in the on-disk cycle history
+    /// (see [`crate::storage::append_cycle_report`]).
+    pub fn summary(&self, mana_bankroll: Decimal) -> types::CycleReport {
+        types::CycleReport {
+            cycle_number: self.cycle_number,
+            timestamp: self.timestamp,
+            markets_scanned: self.markets_scanned as u64,
+            edges_found: self.edges_found as u64,
+            bets_placed: self.bets_placed as u64,
+            cycle_cost: self.cycle_costs.total(),
+            cycle_pnl: self.bankroll_after - self.bankroll_before,
+            bankroll_after: self.bankroll_after,
+            bets_failed: self.bets_failed as u64,
+            mana_bankroll_after: mana_bankroll,
+            status: Some(self.status),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -86,11 +153,16 @@ impl Accountant {
         // Deduct costs with per-category breakdown for dashboard reporting
         let alive = state.deduct_costs(costs.llm_cost, costs.data_cost, costs.other, costs.ib_commissions);
 
-        // Record trades
+        // Record trades. Only confirmed fills count; unconfirmed orders
+        // wait for the next cycles' venue positions.
         state.trades_placed += execution.executed.len() as u64;
+        state
+            .unconfirmed_bets
+            .extend(execution.unconfirmed.iter().map(|u| u.receipt.clone()));
         state.cycle_count += 1;
 
-        // Update peak
+        // Update cash, equity and peaks
+        state.update_equity();
         state.update_peak();
 
         if !alive {
@@ -106,19 +178,34 @@ impl Accountant {
             markets_scanned: 0, // Caller fills this in
             edges_found: 0,     // Caller fills this in
             bets_placed: execution.executed.len(),
-            bets_failed: execution.failed.len(),
+            bets_failed: execution.error_count(),
+            closed_markets: execution.closed_markets(),
+            bets_unconfirmed: execution.unconfirmed.len(),
             total_committed: execution.total_committed,
             cycle_costs: costs.clone(),
             bankroll_before,
             bankroll_after: state.bankroll,
+            cash_bankroll: state.cash_bankroll,
+            total_equity: state.total_equity,
             status: state.status.clone(),
             timestamp: Utc::now(),
             executed_trades: execution.executed.clone(),
+            failed_trades: execution.errors().cloned().collect(),
+            decisions: Vec::new(),
+            llm_cost_by_tier: BTreeMap::new(),
+            llm_cost_by_model: BTreeMap::new(),
+            estimate_cache_hits: 0,
+            estimate_cache_misses: 0,
+            budget_used: Decimal::ZERO,
+            budget_remaining: None,
+            budget_skipped: 0,
         };
 
         info!(
             cycle = report.cycle_number,
             bankroll = format!("${:.2}", state.bankroll),
+            cash = format!("${:.2}", state.cash_bankroll),
+            equity = format!("${:.2}", state.total_equity),
             costs = format!("${:.4}", costs.total()),
             bets = report.bets_placed,
             status = ?state.status,
@@ -127,6 +214,143 @@ impl Accountant {
 
         report
     }
+
+    /// Settle orders left unconfirmed by earlier cycles against the venues'
+    /// reported positions. An order whose market side is now held is
+    /// booked as an open bet; one still missing [`UNCONFIRMED_GRACE_HOURS`]
+    /// after submission is taken as never placed. The grace period covers
+    /// a venue whose positions couldn't be read this cycle.
+    ///
+    /// Returns the number of orders confirmed and dropped.
+    pub fn settle_unconfirmed(state: &mut AgentState, positions: &[Position], now: DateTime<Utc>) -> (usize, usize) {
+        let (mut confirmed, mut dropped) = (0, 0);
+        let grace = Duration::hours(UNCONFIRMED_GRACE_HOURS);
+        for receipt in std::mem::take(&mut state.unconfirmed_bets) {
+            let held = positions.iter().any(|p| {
+                p.platform == receipt.platform && p.market_id == receipt.market_id && p.side == receipt.side
+            });
+            if held {
+                info!(market_id = %receipt.market_id, platform = %receipt.platform, order_id = %receipt.order_id, "Unconfirmed order found on venue — booked as open");
+                state.trades_placed += 1;
+                state.open_bets.push(receipt);
+                confirmed += 1;
+            } else if now - receipt.timestamp >= grace {
+                warn!(market_id = %receipt.market_id, platform = %receipt.platform, order_id = %receipt.order_id, "Unconfirmed order never appeared on venue — treated as not placed");
+                dropped += 1;
+            } else {
+                state.unconfirmed_bets.push(receipt);
+            }
+        }
+        (confirmed, dropped)
+    }
+
+    /// Mark the open AUD bets to market. Each bet is valued by
+    /// [`daily::position_value`] (the resolver's entry conventions: YES
+    /// prices by side, decimal odds as their implied probability) at the
+    /// market's current YES price in `markets`, else at the YES price
+    /// implied by the venue's reported position on that market side; a bet
+    /// with neither is valued at its stake. Stores the unrealized P&L,
+    /// refreshes cash and equity and raises the peaks; returns the
+    /// unrealized P&L.
+    pub fn mark_to_market(state: &mut AgentState, positions: &[Position], markets: &[Market]) -> Decimal {
+        let prices: HashMap<String, Decimal> =
+            markets.iter().map(|m| (link_key(&m.platform, &m.id), m.current_price_yes)).collect();
+        let mut unrealized = Decimal::ZERO;
+        for bet in state.open_bets.iter().filter(|b| b.currency == "AUD") {
+            let yes = prices.get(&link_key(&bet.platform, &bet.market_id)).copied().or_else(|| {
+                positions
+                    .iter()
+                    .find(|p| {
+                        p.platform == bet.platform
+                            && p.market_id == bet.market_id
+                            && p.side == bet.side
+                            && p.size > Decimal::ZERO
+                    })
+                    .map(|p| match bet.side {
+                        Side::Yes => p.current_value / p.size,
+                        Side::No => Decimal::ONE - p.current_value / p.size,
+                    })
+            });
+            unrealized += daily::position_value(bet, yes) - bet.amount;
+        }
+        state.unrealized_pnl = unrealized;
+        state.update_equity();
+        state.update_peak();
+        debug!(
+            unrealized = %unrealized.round_dp(2),
+            cash = %state.cash_bankroll.round_dp(2),
+            equity = %state.total_equity.round_dp(2),
+            "Open bets marked to market"
+        );
+        unrealized
+    }
+
+    /// Record venue balances fetched after a cycle. Platforms missing from
+    /// `balances` keep their last known figure.
+    pub fn record_balances(state: &mut AgentState, balances: BTreeMap<String, Decimal>) {
+        for (platform, balance) in balances {
+            let previous = state.balances.insert(platform.clone(), balance);
+            if previous != Some(balance) {
+                debug!(%platform, %balance, previous = ?previous, "Venue balance updated");
+            }
+        }
+    }
+
+    /// Record the Manifold account fetched at the start of a cycle. Only
+    /// its liquid Mana is a balance: Mana invested in open positions can't
+    /// fund new bets, so sizing and the risk checks never see it.
+    pub fn record_manifold_account(state: &mut AgentState, info: &ManifoldUserInfo) {
+        Self::record_balances(state, BTreeMap::from([("manifold".to_string(), info.liquid_balance)]));
+    }
+
+    /// Book a closed position: its realised P&L — in Mana on Manifold,
+    /// never touching the AUD survival bankroll — and its removal from the
+    /// open bets. A failed close leaves the position open. Returns whether
+    /// the close was booked.
+    pub fn book_close(state: &mut AgentState, result: &CloseResult, now: DateTime<Utc>) -> bool {
+        if !result.success {
+            return false;
+        }
+        let won = result.realized_pnl >= Decimal::ZERO;
+        if result.platform == "manifold" {
+            state.record_mana_resolution(result.realized_pnl, won);
+            // The sale shows up in the account history without a receipt.
+            reconcile::record_own_sale(&mut state.external_activity, &result.market_id, now);
+        } else {
+            state.record_resolution(result.realized_pnl, won);
+        }
+        state.open_bets.retain(|b| b.order_id != result.bet_id);
+        true
+    }
+
+    /// Build this cycle's row for the long-horizon metrics history.
+    ///
+    /// `realized_pnl` and `realized_mana_pnl` are the P&L realised since the
+    /// previous cycle (resolutions and auto-exits land between cycles).
+    pub fn cycle_metrics(
+        report: &CycleReport,
+        state: &AgentState,
+        realized_pnl: Decimal,
+        realized_mana_pnl: Decimal,
+    ) -> CycleMetrics {
+        let f = |d: Decimal| d.to_f64().unwrap_or(0.0);
+        let exposure: Decimal = state.open_bets.iter().map(|b| b.amount).sum();
+        CycleMetrics {
+            timestamp: report.timestamp,
+            bankroll: f(state.bankroll),
+            mana_bankroll: f(state.mana_bankroll),
+            realized_pnl: f(realized_pnl),
+            realized_mana_pnl: f(realized_mana_pnl),
+            llm_cost: f(report.cycle_costs.llm_cost),
+            data_cost: f(report.cycle_costs.data_cost),
+            commission_cost: f(report.cycle_costs.ib_commissions),
+            other_cost: f(report.cycle_costs.other),
+            bets_placed: report.bets_placed as i64,
+            bets_failed: report.bets_failed as i64,
+            markets_scanned: report.markets_scanned as i64,
+            exposure: f(exposure),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -136,6 +360,7 @@ impl Accountant {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::auto_exit::CloseReason;
     use crate::engine::executor::{ExecutedTrade, ExecutionReport};
     use crate::types::{Side, TradeReceipt};
 
@@ -159,14 +384,17 @@ mod tests {
                 receipt: TradeReceipt::dry_run(&format!("m{i}"), per_trade, "AUD"),
                 edge_pct: 10.0,
                 confidence: 0.8,
+                latency_ms: 0,
             })
             .collect();
 
         ExecutionReport {
             executed,
             failed: Vec::new(),
+            unconfirmed: Vec::new(),
             total_committed: total,
             total_commission: Decimal::ZERO,
+            duplicate_fills: 0,
         }
     }
 
@@ -244,6 +472,36 @@ mod tests {
         assert_eq!(state.total_api_costs, dec!(0.11));
     }
 
+    #[test]
+    fn test_unconfirmed_orders_wait_out_grace_then_drop() {
+        let mut state = make_state(dec!(100));
+        let now = Utc::now();
+        let mut recent = TradeReceipt::dry_run("m1", dec!(10), "AUD");
+        recent.platform = "betfair".to_string();
+        let mut stale = TradeReceipt { market_id: "m2".to_string(), ..recent.clone() };
+        stale.timestamp = now - Duration::hours(UNCONFIRMED_GRACE_HOURS);
+        state.unconfirmed_bets = vec![recent, stale];
+
+        // Positions on another side or venue don't confirm either order.
+        let elsewhere = Position {
+            market_id: "m1".to_string(),
+            platform: "betfair".to_string(),
+            side: Side::No,
+            size: dec!(10),
+            entry_price: dec!(0.5),
+            current_value: dec!(10),
+            cost: dec!(10),
+            category: None,
+        };
+        let settled = Accountant::settle_unconfirmed(&mut state, &[elsewhere], now);
+
+        assert_eq!(settled, (0, 1));
+        assert!(state.open_bets.is_empty());
+        assert_eq!(state.unconfirmed_bets.len(), 1);
+        assert_eq!(state.unconfirmed_bets[0].market_id, "m1");
+        assert_eq!(state.trades_placed, 0);
+    }
+
     #[test]
     fn test_cycle_costs_total() {
         let costs = CycleCosts {
@@ -260,4 +518,139 @@ mod tests {
         let costs = CycleCosts::default();
         assert_eq!(costs.total(), Decimal::ZERO);
     }
+
+    #[test]
+    fn test_cycle_metrics_row() {
+        let mut state = make_state(dec!(100));
+        state.open_bets.push(TradeReceipt::dry_run("m1", dec!(12), "Mana"));
+        state.open_bets.push(TradeReceipt::dry_run("m2", dec!(8), "Mana"));
+        let costs = CycleCosts { llm_cost: dec!(0.04), data_cost: dec!(0.01), ..Default::default() };
+        let mut report = Accountant::reconcile(&mut state, &make_execution(2, dec!(20)), &costs);
+        report.markets_scanned = 80;
+
+        let m = Accountant::cycle_metrics(&report, &state, dec!(1.5), dec!(-3));
+
+        assert_eq!(m.timestamp, report.timestamp);
+        assert!((m.bankroll - 99.95).abs() < 1e-9);
+        assert!((m.llm_cost - 0.04).abs() < 1e-9);
+        assert!((m.data_cost - 0.01).abs() < 1e-9);
+        assert_eq!(m.realized_pnl, 1.5);
+        assert_eq!(m.realized_mana_pnl, -3.0);
+        assert_eq!(m.bets_placed, 2);
+        assert_eq!(m.markets_scanned, 80);
+        assert_eq!(m.exposure, 20.0);
+    }
+
+    fn aud_bet(market_id: &str, side: Side, amount: Decimal, fill_price: Decimal) -> TradeReceipt {
+        TradeReceipt { platform: "betfair".into(), side, fill_price, ..TradeReceipt::dry_run(market_id, amount, "AUD") }
+    }
+
+    fn priced(market_id: &str, yes: Decimal) -> Market {
+        Market { id: market_id.into(), platform: "betfair".into(), current_price_yes: yes, ..Market::sample() }
+    }
+
+    #[test]
+    fn test_new_bet_moves_cash_not_equity() {
+        let mut state = make_state(dec!(100));
+        Accountant::mark_to_market(&mut state, &[], &[]);
+        assert_eq!((state.cash_bankroll, state.total_equity), (dec!(100), dec!(100)));
+
+        // A 10 AUD back placed at odds of 2.5 (0.40) this cycle
+        let mut execution = make_execution(1, dec!(10));
+        let receipt = aud_bet("m1", Side::Yes, dec!(10), dec!(2.5));
+        execution.executed[0].receipt = receipt.clone();
+        state.open_bets.push(receipt);
+        let report = Accountant::reconcile(&mut state, &execution, &CycleCosts::default());
+        assert_eq!((report.cash_bankroll, report.total_equity), (dec!(90), dec!(100)));
+
+        // Still priced at the fill next cycle: equity flat, no drawdown
+        Accountant::mark_to_market(&mut state, &[], &[priced("m1", dec!(0.40))]);
+        assert_eq!((state.cash_bankroll, state.total_equity), (dec!(90), dec!(100)));
+        assert_eq!(state.equity_drawdown(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_losing_open_bet_marks_equity_down_without_cash() {
+        let mut state = make_state(dec!(100));
+        // m1 backed at odds of 2.0; m2 a NO bought at 0.60, recorded as its
+        // YES price
+        state.open_bets.push(aud_bet("m1", Side::Yes, dec!(10), dec!(2.0)));
+        state.open_bets.push(aud_bet("m2", Side::No, dec!(6), dec!(0.40)));
+        // Mana and USD bets are not in the AUD books
+        state.open_bets.push(TradeReceipt::dry_run("m3", dec!(50), "Mana"));
+        state.open_bets.push(TradeReceipt { side: Side::No, fill_price: dec!(0.59), ..TradeReceipt::dry_run("m4", dec!(9), "USD") });
+        Accountant::mark_to_market(&mut state, &[], &[]);
+        assert_eq!((state.cash_bankroll, state.total_equity, state.peak_equity), (dec!(84), dec!(100), dec!(100)));
+
+        // Unmoved prices mark flat, whatever the entry convention
+        let unmoved = Accountant::mark_to_market(&mut state, &[], &[priced("m1", dec!(0.50)), priced("m2", dec!(0.40))]);
+        assert_eq!(unmoved, Decimal::ZERO);
+
+        // m1 falls to 0.30 (20 shares: -4); m2 is unpriced this cycle, so
+        // its venue position (10 NO shares worth 5.00) marks it: -1
+        let position = Position {
+            market_id: "m2".into(),
+            platform: "betfair".into(),
+            side: Side::No,
+            size: dec!(10),
+            entry_price: dec!(0.60),
+            current_value: dec!(5),
+            cost: dec!(6),
+            category: None,
+        };
+        let unrealized = Accountant::mark_to_market(&mut state, &[position], &[priced("m1", dec!(0.30))]);
+        assert_eq!(unrealized, dec!(-5));
+        assert_eq!(state.bankroll, dec!(100));
+        assert_eq!((state.cash_bankroll, state.total_equity, state.peak_equity), (dec!(84), dec!(95), dec!(100)));
+        assert_eq!(state.equity_drawdown(), dec!(0.05));
+        assert_eq!(state.drawdown(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_manifold_balance_is_liquid_mana_only() {
+        let mut state = make_state(dec!(100));
+        let info = ManifoldUserInfo {
+            user_id: "u1".into(),
+            liquid_balance: dec!(50),
+            investment_value: dec!(200),
+            resolved_profit: Decimal::ZERO,
+        };
+        Accountant::record_manifold_account(&mut state, &info);
+        assert_eq!(state.bankroll_for("manifold"), dec!(50));
+
+        // The after-cycle refresh agrees with it.
+        Accountant::record_balances(&mut state, BTreeMap::from([("manifold".to_string(), dec!(50))]));
+        assert_eq!(state.balances["manifold"], dec!(50));
+    }
+
+    #[test]
+    fn test_book_close_keeps_mana_out_of_the_bankroll() {
+        let mut state = make_state(dec!(100));
+        let mana = TradeReceipt { platform: "manifold".into(), ..TradeReceipt::dry_run("m1", dec!(10), "Mana") };
+        state.open_bets.push(mana.clone());
+        state.open_bets.push(aud_bet("m2", Side::Yes, dec!(10), dec!(2.0)));
+        let close = |bet: &TradeReceipt, pnl: Decimal, success: bool| CloseResult {
+            market_id: bet.market_id.clone(),
+            bet_id: bet.order_id.clone(),
+            platform: bet.platform.clone(),
+            reason: CloseReason::EdgeTakeProfit,
+            realized_pnl: pnl,
+            success,
+            message: String::new(),
+        };
+        let now = Utc::now();
+
+        // A failed close leaves the position open.
+        assert!(!Accountant::book_close(&mut state, &close(&mana, dec!(3), false), now));
+        assert_eq!(state.open_bets.len(), 2);
+
+        assert!(Accountant::book_close(&mut state, &close(&mana, dec!(3), true), now));
+        assert_eq!((state.total_mana_pnl, state.mana_trades_won, state.bankroll), (dec!(3), 1, dec!(100)));
+        assert_eq!(state.external_activity.own_sales.len(), 1);
+
+        let aud = state.open_bets[0].clone();
+        assert!(Accountant::book_close(&mut state, &close(&aud, dec!(-2), true), now));
+        assert_eq!((state.total_pnl, state.trades_lost, state.bankroll), (dec!(-2), 1, dec!(98)));
+        assert!(state.open_bets.is_empty());
+    }
 }
@@ -4,6 +4,12 @@
 //! configurable thresholds. Supports both Manifold (paper mode — sell shares)
 //! and Betfair (live AUD mode — hedge/green-up with an opposite bet).
 //!
+//! Positions inside a configured unwind window (e.g. Betfair sports markets
+//! minutes before going in-play) are closed regardless of P&L: via
+//! `close_position` where the venue supports it, otherwise by hedging.
+//! Positions the cycle's exit review finds with their edge spent or
+//! reversed (see [`crate::strategy::exit`]) are closed the same way.
+//!
 //! ## Betfair Australia minimum stake rules (AUD, March 2026)
 //! - Exchange back/lay bets via API: **AUD $1.00 absolute minimum**
 //! - BSP back bets: same AUD $1.00 minimum
@@ -19,14 +25,17 @@
 //! - Practical minimum: 1 Mana — no meaningful constraint for auto-exit
 
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
 use tracing::{info, warn};
 
+use crate::config::UnwindWindow;
 use crate::platforms::betfair::BetfairClient;
 use crate::platforms::manifold::ManifoldClient;
+use crate::platforms::PredictionPlatform;
+use crate::strategy::exit::ExitTrigger;
 use crate::types::{Side, TradeReceipt};
 
 // ---------------------------------------------------------------------------
@@ -50,6 +59,8 @@ pub struct AutoExitConfig {
     pub min_close_stake: Decimal,
     /// If true, log decisions without placing real orders.
     pub dry_run: bool,
+    /// Windows ahead of a market's deadline in which positions are unwound.
+    pub unwind_windows: Vec<UnwindWindow>,
 }
 
 impl Default for AutoExitConfig {
@@ -65,6 +76,7 @@ impl Default for AutoExitConfig {
             max_hold_hours: 168,  // 1 week — let markets move toward resolution
             min_close_stake: dec!(2.0),
             dry_run: false,
+            unwind_windows: Vec::new(),
         }
     }
 }
@@ -79,6 +91,22 @@ pub enum CloseReason {
     TakeProfit,
     StopLoss,
     MaxHoldTime,
+    /// Closed ahead of a liquidity cliff (inside the unwind window).
+    Unwound,
+    /// This cycle's estimate leaves the position (almost) no edge: the
+    /// price has caught up (see [`crate::strategy::exit`]).
+    EdgeTakeProfit,
+    /// This cycle's estimate has turned against the position.
+    EdgeCutLoss,
+}
+
+impl From<ExitTrigger> for CloseReason {
+    fn from(trigger: ExitTrigger) -> Self {
+        match trigger {
+            ExitTrigger::TakeProfit => CloseReason::EdgeTakeProfit,
+            ExitTrigger::CutLoss => CloseReason::EdgeCutLoss,
+        }
+    }
 }
 
 impl std::fmt::Display for CloseReason {
@@ -87,6 +115,9 @@ impl std::fmt::Display for CloseReason {
             CloseReason::TakeProfit => write!(f, "TakeProfit"),
             CloseReason::StopLoss => write!(f, "StopLoss"),
             CloseReason::MaxHoldTime => write!(f, "MaxHoldTime"),
+            CloseReason::Unwound => write!(f, "Unwound"),
+            CloseReason::EdgeTakeProfit => write!(f, "EdgeTakeProfit"),
+            CloseReason::EdgeCutLoss => write!(f, "EdgeCutLoss"),
         }
     }
 }
@@ -133,6 +164,7 @@ impl AutoExitEngine {
         }
 
         let mut results = Vec::new();
+        let now = Utc::now();
 
         for bet in open_bets {
             // Skip dry-run receipts — these are not real positions
@@ -140,6 +172,13 @@ impl AutoExitEngine {
                 continue;
             }
 
+            if self.in_unwind_window(bet, now) {
+                if let Some(r) = self.close(bet, CloseReason::Unwound).await {
+                    results.push(r);
+                }
+                continue;
+            }
+
             let result = match bet.platform.as_str() {
                 "manifold" => {
                     if let Some(ref client) = self.manifold {
@@ -341,26 +380,11 @@ impl AutoExitEngine {
             return None; // Invalid odds — skip
         }
 
-        // P&L% for greening up:
-        //   BACK (Side::Yes): win when odds shorten (current < entry).
-        //     locked_profit = stake × (entry_odds − current_odds) / current_odds
-        //     P&L% = (entry_odds − current_odds) / current_odds × 100
-        //   LAY (Side::No): win when odds lengthen (current > entry).
-        //     locked_profit = stake × (current_odds − entry_odds) / current_odds
-        //     P&L% = (current_odds − entry_odds) / current_odds × 100
-        let pnl_pct = match bet.side {
-            Side::Yes => (entry_odds - current_odds) / current_odds * dec!(100),
-            Side::No => (current_odds - entry_odds) / current_odds * dec!(100),
-        };
+        // P&L% and hedge/green-up stake (see `green_up`).
+        let (pnl_pct, close_stake) = green_up(bet, current_odds);
 
         let reason = self.check_triggers(bet, pnl_pct)?;
 
-        // Hedge/green-up stake:
-        //   For BACK: lay_stake = original_stake × entry_odds / current_odds
-        //   For LAY:  back_stake = original_stake × entry_odds / current_odds
-        //   (same formula — just the side flips)
-        let close_stake = bet.amount * entry_odds / current_odds;
-
         // Enforce Betfair AUD minimum stake ($1.00 official; $2.00 safety buffer)
         if close_stake < self.config.min_close_stake {
             warn!(
@@ -437,7 +461,6 @@ impl AutoExitEngine {
         }
 
         // Place the opposite (hedge) bet to fully green-up
-        use crate::platforms::PredictionPlatform;
         let hedge_side = match bet.side {
             Side::Yes => Side::No, // BACK → close by LAYing
             Side::No => Side::Yes, // LAY → close by BACKing
@@ -490,6 +513,61 @@ impl AutoExitEngine {
         }
     }
 
+    // -- Unwind windows ---------------------------------------------------
+
+    /// Whether `bet`'s market deadline is inside a configured unwind window.
+    fn in_unwind_window(&self, bet: &TradeReceipt, now: DateTime<Utc>) -> bool {
+        bet.deadline.is_some_and(|deadline| {
+            UnwindWindow::contains(
+                &self.config.unwind_windows,
+                &bet.platform,
+                bet.category,
+                deadline,
+                now,
+            )
+        })
+    }
+
+    /// Close the position of an exit decision (see
+    /// [`crate::strategy::exit`]): whatever its P&L, as when unwinding.
+    pub async fn close_on_exit(&self, bet: &TradeReceipt, trigger: ExitTrigger) -> Option<CloseResult> {
+        if !self.config.enabled {
+            return None;
+        }
+        self.close(bet, trigger.into()).await
+    }
+
+    /// Close a position unconditionally: ahead of its liquidity cliff, or
+    /// on an exit decision.
+    async fn close(&self, bet: &TradeReceipt, reason: CloseReason) -> Option<CloseResult> {
+        let venue: &dyn PredictionPlatform = match bet.platform.as_str() {
+            "manifold" => self.manifold.as_ref()?,
+            "betfair" => self.betfair.as_ref()?,
+            _ => return None,
+        };
+
+        // Hedging needs the current price; closing outright does not.
+        let hedge_odds = match &self.betfair {
+            Some(client) if !venue.supports_close() && bet.platform == "betfair" => {
+                match client.get_best_back_odds(&bet.market_id).await {
+                    Ok(odds) => odds,
+                    Err(e) => {
+                        warn!(
+                            market_id = %bet.market_id,
+                            error = %e,
+                            reason = %reason,
+                            "Close: failed to fetch Betfair market odds"
+                        );
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        Some(close_position(venue, bet, hedge_odds, &self.config, reason).await)
+    }
+
     // -- Trigger evaluation -----------------------------------------------
 
     /// Return the first trigger hit, or `None` if no threshold is crossed.
@@ -513,6 +591,127 @@ impl AutoExitEngine {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Unwinding
+// ---------------------------------------------------------------------------
+
+/// Green-up maths for a decimal-odds position: `(pnl_pct, close_stake)`.
+///
+/// P&L% for greening up:
+///   BACK (Side::Yes): win when odds shorten (current < entry).
+///     locked_profit = stake × (entry_odds − current_odds) / current_odds
+///     P&L% = (entry_odds − current_odds) / current_odds × 100
+///   LAY (Side::No): win when odds lengthen (current > entry).
+///     locked_profit = stake × (current_odds − entry_odds) / current_odds
+///     P&L% = (current_odds − entry_odds) / current_odds × 100
+///
+/// Hedge stake is `original_stake × entry_odds / current_odds` for both
+/// sides — only the hedge side flips.
+fn green_up(bet: &TradeReceipt, current_odds: Decimal) -> (Decimal, Decimal) {
+    let entry_odds = bet.fill_price;
+    let pnl_pct = match bet.side {
+        Side::Yes => (entry_odds - current_odds) / current_odds * dec!(100),
+        Side::No => (current_odds - entry_odds) / current_odds * dec!(100),
+    };
+    (pnl_pct, bet.amount * entry_odds / current_odds)
+}
+
+/// Close `bet` on `venue` unconditionally, for `reason`.
+///
+/// Uses `close_position` where the venue supports it; otherwise places the
+/// opposite bet sized from `hedge_odds` (decimal odds). Unlike a P&L
+/// trigger exit there is no liquidity check — when unwinding, the book is
+/// about to get worse, not better. A failed close leaves the position open
+/// for the next cycle.
+async fn close_position(
+    venue: &dyn PredictionPlatform,
+    bet: &TradeReceipt,
+    hedge_odds: Option<Decimal>,
+    config: &AutoExitConfig,
+    reason: CloseReason,
+) -> CloseResult {
+    let result = |realized_pnl: Decimal, success: bool, message: String| CloseResult {
+        market_id: bet.market_id.clone(),
+        bet_id: bet.order_id.clone(),
+        platform: bet.platform.clone(),
+        reason: reason.clone(),
+        realized_pnl,
+        success,
+        message,
+    };
+
+    if venue.supports_close() {
+        if config.dry_run {
+            info!(market_id = %bet.market_id, reason = %reason, "[DRY RUN] Would close position");
+            return result(Decimal::ZERO, true, "dry-run".to_string());
+        }
+        return match venue.close_position(&bet.market_id, bet.side).await {
+            Ok(returned) => {
+                // Venue did not report proceeds — book the close flat.
+                let pnl = returned.map(|a| a - bet.amount).unwrap_or(Decimal::ZERO);
+                info!(market_id = %bet.market_id, pnl = %pnl, reason = %reason, "Closed position");
+                result(pnl, true, String::new())
+            }
+            Err(e) => {
+                warn!(market_id = %bet.market_id, error = %e, reason = %reason, "Close failed");
+                result(Decimal::ZERO, false, e.to_string())
+            }
+        };
+    }
+
+    // No close support — hedge with the opposite side.
+    let current_odds = match hedge_odds {
+        Some(o) if o > Decimal::ONE && bet.fill_price > Decimal::ONE => o,
+        _ => {
+            warn!(market_id = %bet.market_id, reason = %reason, "Close: no valid odds to size hedge");
+            return result(Decimal::ZERO, false, "no odds to size hedge".to_string());
+        }
+    };
+    let (pnl_pct, close_stake) = green_up(bet, current_odds);
+    let realized_pnl = bet.amount * pnl_pct / dec!(100);
+
+    if close_stake < config.min_close_stake {
+        warn!(
+            market_id = %bet.market_id,
+            close_stake = %close_stake,
+            reason = %reason,
+            "Close: hedge stake below minimum — position left open"
+        );
+        return result(realized_pnl, false, "hedge stake below minimum".to_string());
+    }
+
+    if config.dry_run {
+        info!(
+            market_id = %bet.market_id,
+            close_stake = %close_stake,
+            reason = %reason,
+            "[DRY RUN] Would close position via hedge"
+        );
+        return result(realized_pnl, true, "dry-run".to_string());
+    }
+
+    let hedge_side = match bet.side {
+        Side::Yes => Side::No,
+        Side::No => Side::Yes,
+    };
+    match venue.place_bet(&bet.market_id, hedge_side, close_stake).await {
+        Ok(_) => {
+            info!(
+                market_id = %bet.market_id,
+                close_stake = %close_stake,
+                pnl = %realized_pnl,
+                reason = %reason,
+                "Closed position via hedge"
+            );
+            result(realized_pnl, true, String::new())
+        }
+        Err(e) => {
+            warn!(market_id = %bet.market_id, error = %e, reason = %reason, "Close: hedge bet failed");
+            result(realized_pnl, false, e.to_string())
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -534,6 +733,14 @@ mod tests {
             fees: Decimal::ZERO,
             timestamp: Utc::now() - Duration::hours(hours_ago),
             currency: if platform == "manifold" { "Mana".to_string() } else { "AUD".to_string() },
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         }
     }
 
@@ -626,6 +833,117 @@ mod tests {
         assert!(close_stake < config.min_close_stake, "close_stake {close_stake} should be < min {}", config.min_close_stake);
     }
 
+    // -- Unwind tests -----------------------------------------------------
+
+    /// Venue recording hedges and closes, with switchable close support.
+    struct MockVenue {
+        can_close: bool,
+        placed: std::sync::Mutex<Vec<(Side, Decimal)>>,
+        closed: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl MockVenue {
+        fn new(can_close: bool) -> Self {
+            Self { can_close, placed: Default::default(), closed: Default::default() }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for MockVenue {
+        async fn fetch_markets(&self) -> Result<Vec<crate::types::Market>> { Ok(Vec::new()) }
+        async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+            self.placed.lock().unwrap().push((side, amount));
+            let mut r = make_bet("betfair", side, amount, dec!(2.0), 0);
+            r.market_id = market_id.to_string();
+            Ok(r)
+        }
+        async fn get_positions(&self) -> Result<Vec<crate::types::Position>> { Ok(Vec::new()) }
+        async fn get_balance(&self) -> Result<Decimal> { Ok(Decimal::ZERO) }
+        async fn check_liquidity(&self, _: &str) -> Result<crate::types::LiquidityInfo> {
+            anyhow::bail!("unused")
+        }
+        fn is_real_money(&self) -> bool { false }
+        fn name(&self) -> &str { "mock" }
+        fn supports_close(&self) -> bool { self.can_close }
+        async fn close_position(&self, market_id: &str, _side: Side) -> Result<Option<Decimal>> {
+            self.closed.lock().unwrap().push(market_id.to_string());
+            Ok(Some(dec!(12)))
+        }
+    }
+
+    #[test]
+    fn test_in_unwind_window() {
+        let config = AutoExitConfig {
+            unwind_windows: vec![UnwindWindow {
+                platform: "betfair".into(),
+                category: Some(crate::types::MarketCategory::Sports),
+                minutes_before: 10,
+            }],
+            ..AutoExitConfig::default()
+        };
+        let engine = AutoExitEngine::new(None, None, config);
+        let now = Utc::now();
+        let mut bet = make_bet("betfair", Side::Yes, dec!(10), dec!(3.0), 1);
+        bet.category = Some(crate::types::MarketCategory::Sports);
+
+        // No deadline recorded (legacy receipt) — never unwound.
+        assert!(!engine.in_unwind_window(&bet, now));
+        bet.deadline = Some(now + Duration::minutes(8));
+        assert!(engine.in_unwind_window(&bet, now));
+        bet.deadline = Some(now + Duration::minutes(30));
+        assert!(!engine.in_unwind_window(&bet, now));
+    }
+
+    #[tokio::test]
+    async fn test_unwind_hedges_when_close_unsupported() {
+        let venue = MockVenue::new(false);
+        let config = AutoExitConfig::default();
+        // BACK 10 @ 3.0, now 2.0: lay 15 to green up, +50% locked.
+        let bet = make_bet("betfair", Side::Yes, dec!(10), dec!(3.0), 1);
+
+        let r = close_position(&venue, &bet, Some(dec!(2.0)), &config, CloseReason::Unwound).await;
+        assert!(r.success);
+        assert_eq!(r.reason, CloseReason::Unwound);
+        assert_eq!(r.realized_pnl, dec!(5));
+        assert_eq!(*venue.placed.lock().unwrap(), vec![(Side::No, dec!(15))]);
+        assert!(venue.closed.lock().unwrap().is_empty());
+
+        // Without a price there is nothing to size the hedge from.
+        let r = close_position(&venue, &bet, None, &config, CloseReason::Unwound).await;
+        assert!(!r.success);
+        assert_eq!(venue.placed.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unwind_closes_when_supported() {
+        let venue = MockVenue::new(true);
+        let bet = make_bet("manifold", Side::No, dec!(10), dec!(0.4), 1);
+
+        let r = close_position(&venue, &bet, None, &AutoExitConfig::default(), CloseReason::Unwound).await;
+        assert!(r.success);
+        assert_eq!(r.reason, CloseReason::Unwound);
+        assert_eq!(r.realized_pnl, dec!(2)); // 12 returned − 10 staked
+        assert_eq!(*venue.closed.lock().unwrap(), vec!["test-market".to_string()]);
+        assert!(venue.placed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_edge_exit_closes_with_its_trigger() {
+        let bet = make_bet("manifold", Side::Yes, dec!(10), dec!(0.4), 1);
+        let disabled = AutoExitEngine::new(None, None, AutoExitConfig { enabled: false, ..AutoExitConfig::default() });
+        assert!(disabled.close_on_exit(&bet, ExitTrigger::TakeProfit).await.is_none());
+
+        // A broken thesis is cut whatever the P&L, by hedging if need be.
+        let venue = MockVenue::new(false);
+        let bet = make_bet("betfair", Side::Yes, dec!(10), dec!(2.0), 1);
+        let r = close_position(&venue, &bet, Some(dec!(4.0)), &AutoExitConfig::default(), ExitTrigger::CutLoss.into())
+            .await;
+        assert!(r.success);
+        assert_eq!(r.reason, CloseReason::EdgeCutLoss);
+        assert!(r.realized_pnl < Decimal::ZERO);
+        assert_eq!(venue.placed.lock().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_max_hold_hours_zero_disabled() {
         let config = AutoExitConfig {
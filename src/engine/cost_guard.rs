@@ -0,0 +1,258 @@
+//! Per-cycle cost guard.
+//!
+//! Caps what one cycle may spend on enriching and estimating the scanned
+//! markets. Each market's marginal cost is its share of an LLM call plus
+//! the paid data fetches it would trigger (nothing for warm cache entries);
+//! the kept set maximises total priority score within the budget — a 0/1
+//! knapsack — rather than cutting the priority list at the first market
+//! that no longer fits.
+
+use rust_decimal::prelude::*;
+use tracing::{info, warn};
+
+/// Capacity resolution of the knapsack: the budget is split into this many
+/// units and each cost is rounded up to whole units, so the selection never
+/// overspends.
+const BUDGET_UNITS: usize = 10_000;
+
+/// A cycle's spend cap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CycleBudget {
+    pub max_cost: Decimal,
+    /// Estimation cost attributed to each market: one LLM call's cost
+    /// split across its batch.
+    pub llm_share: Decimal,
+}
+
+/// One market competing for the cycle budget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candidate {
+    /// Scanner priority score (higher is better).
+    pub priority: f64,
+    /// Marginal enrichment + estimation cost.
+    pub cost: Decimal,
+    /// Every data section the market needs is already cached.
+    pub warm: bool,
+}
+
+/// How a selection was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Everything fit; nothing was dropped.
+    All,
+    Knapsack,
+    /// Priority-order cut, used when the knapsack inputs are degenerate.
+    TopK,
+}
+
+/// Markets kept under the budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    /// Indices into the candidates, ascending.
+    pub kept: Vec<usize>,
+    /// Excluded markets that outrank a kept one: dropped for their cost,
+    /// not their priority.
+    pub excluded_for_cost: usize,
+    pub method: Method,
+}
+
+/// Candidates to keep within `budget`. A non-positive budget keeps none.
+pub fn select(candidates: &[Candidate], budget: Decimal) -> Selection {
+    let total: Decimal = candidates.iter().map(|c| c.cost.max(Decimal::ZERO)).sum();
+    let (kept, method) = if total <= budget && !degenerate(candidates) {
+        ((0..candidates.len()).collect(), Method::All)
+    } else if budget <= Decimal::ZERO || degenerate(candidates) {
+        (top_k(candidates, budget), Method::TopK)
+    } else {
+        (knapsack(candidates, budget), Method::Knapsack)
+    };
+    let lowest_kept = kept.iter().map(|&i| candidates[i].priority).fold(f64::INFINITY, f64::min);
+    let excluded_for_cost = (0..candidates.len())
+        .filter(|i| !kept.contains(i) && candidates[*i].priority > lowest_kept)
+        .count();
+    Selection { kept, excluded_for_cost, method }
+}
+
+/// Inputs the knapsack can't rank: non-finite or negative priorities, or
+/// negative costs.
+fn degenerate(candidates: &[Candidate]) -> bool {
+    candidates.iter().any(|c| !c.priority.is_finite() || c.priority < 0.0 || c.cost < Decimal::ZERO)
+}
+
+/// Walk candidates by descending priority (warm first on ties) and stop at
+/// the first that no longer fits.
+pub fn top_k(candidates: &[Candidate], budget: Decimal) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&candidates[a], &candidates[b]);
+        b.priority.partial_cmp(&a.priority).unwrap_or(std::cmp::Ordering::Equal).then(b.warm.cmp(&a.warm))
+    });
+    let mut spent = Decimal::ZERO;
+    let mut kept = Vec::new();
+    for i in order {
+        let cost = candidates[i].cost.max(Decimal::ZERO);
+        if spent + cost > budget {
+            break;
+        }
+        spent += cost;
+        kept.push(i);
+    }
+    kept.sort_unstable();
+    kept
+}
+
+/// Maximise total priority within `budget`; among equal totals, prefer
+/// more warm markets.
+pub fn knapsack(candidates: &[Candidate], budget: Decimal) -> Vec<usize> {
+    let unit = budget / Decimal::from(BUDGET_UNITS);
+    let weights: Vec<usize> = candidates
+        .iter()
+        .map(|c| (c.cost / unit).ceil().to_usize().unwrap_or(usize::MAX))
+        .collect();
+
+    // best[w] = (total priority, warm count) using at most w units;
+    // take[i][w] = candidate i is in the best set for capacity w.
+    let mut best = vec![(0.0f64, 0u32); BUDGET_UNITS + 1];
+    let mut take = vec![vec![false; BUDGET_UNITS + 1]; candidates.len()];
+    for (i, c) in candidates.iter().enumerate() {
+        let w = weights[i];
+        if w > BUDGET_UNITS {
+            continue;
+        }
+        for cap in (w..=BUDGET_UNITS).rev() {
+            let (value, warm) = best[cap - w];
+            let with = (value + c.priority, warm + u32::from(c.warm));
+            if better(with, best[cap]) {
+                best[cap] = with;
+                take[i][cap] = true;
+            }
+        }
+    }
+
+    let mut kept = Vec::new();
+    let mut cap = BUDGET_UNITS;
+    for i in (0..candidates.len()).rev() {
+        if take[i][cap] {
+            kept.push(i);
+            cap -= weights[i];
+        }
+    }
+    kept.reverse();
+    kept
+}
+
+fn better(a: (f64, u32), b: (f64, u32)) -> bool {
+    // Sums of the same priorities in a different order can differ in the
+    // last bits; treat those as ties.
+    let eps = 1e-9 * a.0.abs().max(b.0.abs()).max(1.0);
+    a.0 > b.0 + eps || ((a.0 - b.0).abs() <= eps && a.1 > b.1)
+}
+
+/// Log a selection that dropped markets.
+pub fn log_selection(selection: &Selection, total: usize, budget: Decimal) {
+    if selection.method == Method::All {
+        return;
+    }
+    if selection.method == Method::TopK {
+        warn!(%budget, "Cost guard inputs degenerate — fell back to a priority cut");
+    }
+    info!(
+        kept = selection.kept.len(),
+        excluded = total - selection.kept.len(),
+        excluded_for_cost = selection.excluded_for_cost,
+        method = ?selection.method,
+        %budget,
+        "Cost guard truncated the market list"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn c(priority: f64, cost: Decimal, warm: bool) -> Candidate {
+        Candidate { priority, cost, warm }
+    }
+
+    #[test]
+    fn test_everything_fits() {
+        let cands = [c(90.0, dec!(0.01), false), c(10.0, dec!(0.01), false)];
+        let s = select(&cands, dec!(0.05));
+        assert_eq!(s.kept, vec![0, 1]);
+        assert_eq!(s.method, Method::All);
+        assert_eq!(s.excluded_for_cost, 0);
+    }
+
+    #[test]
+    fn test_knapsack_beats_top_k_on_expensive_leader() {
+        // One expensive top market vs three cheap ones worth more together.
+        let cands = [
+            c(100.0, dec!(0.10), false),
+            c(60.0, dec!(0.03), false),
+            c(50.0, dec!(0.03), false),
+            c(40.0, dec!(0.03), false),
+        ];
+        // Top-K takes the leader and then can't afford the 0.03s.
+        assert_eq!(top_k(&cands, dec!(0.10)), vec![0]);
+        let s = select(&cands, dec!(0.10));
+        assert_eq!(s.method, Method::Knapsack);
+        assert_eq!(s.kept, vec![1, 2, 3]);
+        assert_eq!(s.excluded_for_cost, 1);
+    }
+
+    #[test]
+    fn test_knapsack_fills_past_a_misfit() {
+        // Top-K stops at the first misfit; the knapsack skips it and keeps
+        // filling.
+        let cands = [c(80.0, dec!(0.04), false), c(70.0, dec!(0.05), false), c(60.0, dec!(0.02), false), c(5.0, dec!(0.01), false)];
+        assert_eq!(top_k(&cands, dec!(0.08)), vec![0]);
+        let s = select(&cands, dec!(0.08));
+        assert_eq!(s.kept, vec![0, 2, 3]);
+        assert_eq!(s.excluded_for_cost, 1);
+    }
+
+    #[test]
+    fn test_warm_cache_preferred_on_ties() {
+        let cands = [c(50.0, dec!(0.02), false), c(50.0, dec!(0.02), true), c(50.0, dec!(0.02), false)];
+        assert_eq!(select(&cands, dec!(0.02)).kept, vec![1]);
+        assert_eq!(top_k(&cands, dec!(0.02)), vec![1]);
+        // A warm market costs nothing extra, so it rides along for free.
+        let cands = [c(70.0, dec!(0.05), false), c(20.0, Decimal::ZERO, true), c(60.0, dec!(0.05), false)];
+        assert_eq!(select(&cands, dec!(0.05)).kept, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_never_overspends() {
+        let cands: Vec<Candidate> = (0..30)
+            .map(|i| c(f64::from(i * 7 % 11 + 1), Decimal::new(i64::from(i % 5 + 1), 3), i % 4 == 0))
+            .collect();
+        let budget = dec!(0.037);
+        let s = select(&cands, budget);
+        let spent: Decimal = s.kept.iter().map(|&i| cands[i].cost).sum();
+        assert!(spent <= budget);
+        let knap: f64 = s.kept.iter().map(|&i| cands[i].priority).sum();
+        let naive: f64 = top_k(&cands, budget).iter().map(|&i| cands[i].priority).sum();
+        assert!(knap >= naive);
+    }
+
+    #[test]
+    fn test_degenerate_inputs_fall_back_to_top_k() {
+        let cands = [c(f64::NAN, dec!(0.01), false), c(90.0, dec!(0.01), false), c(10.0, dec!(0.01), false)];
+        let s = select(&cands, dec!(0.02));
+        assert_eq!(s.method, Method::TopK);
+        assert_eq!(s.kept.len(), 2);
+
+        let negative = [c(90.0, dec!(-0.01), false), c(10.0, dec!(0.05), false)];
+        assert_eq!(select(&negative, dec!(0.01)).method, Method::TopK);
+
+        let zero_budget = [c(90.0, dec!(0.01), false), c(10.0, Decimal::ZERO, true)];
+        let s = select(&zero_budget, Decimal::ZERO);
+        assert_eq!(s.method, Method::TopK);
+        assert!(s.kept.is_empty());
+    }
+}
@@ -0,0 +1,380 @@
+//! Per-cycle and per-day spending limits.
+//!
+//! One [`CostTracker`] is shared by the enricher and the estimation phase
+//! and checked before every paid call. LLM calls count against the cycle
+//! budget (`costs.max_llm_cost_per_cycle`); LLM and data calls together
+//! count against the daily one (`costs.max_total_cost_per_day`). A call
+//! goes ahead only when its expected cost fits what is left of both, so
+//! spend stops at a cap rather than one call past it; the call's actual
+//! cost is booked once it returns.
+//!
+//! The day is the UTC day: when it rolls over the daily spend starts from
+//! zero and paused estimation resumes. Unlike the [`cost_guard`], which
+//! trims the market list up front from estimated costs, the tracker holds
+//! the line on what is actually spent.
+//!
+//! [`cost_guard`]: super::cost_guard
+
+use std::future::Future;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::config::CostsConfig;
+use crate::types::{AgentState, Estimate};
+
+/// What a call is spent on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostKind {
+    /// Counts against the cycle and the daily budget.
+    Llm,
+    /// Counts against the daily budget only.
+    Data,
+}
+
+#[derive(Debug, Default)]
+struct Spend {
+    /// UTC day `today` belongs to.
+    day: Option<NaiveDate>,
+    today: Decimal,
+    /// LLM spend since [`CostTracker::begin_cycle`].
+    cycle: Decimal,
+    /// Whether the pause for `day` has been logged.
+    pause_logged: bool,
+}
+
+impl Spend {
+    fn roll_to(&mut self, day: NaiveDate) {
+        if self.day != Some(day) {
+            if self.pause_logged {
+                info!(%day, "New UTC day — daily cost budget reset, estimation resumes");
+            }
+            *self = Spend { day: Some(day), cycle: self.cycle, ..Spend::default() };
+        }
+    }
+}
+
+/// Running spend against the `[costs]` caps. A cap of zero is no cap.
+#[derive(Debug, Default)]
+pub struct CostTracker {
+    cycle_cap: Decimal,
+    daily_cap: Decimal,
+    spend: Mutex<Spend>,
+}
+
+impl CostTracker {
+    pub fn new(config: &CostsConfig) -> Self {
+        Self {
+            cycle_cap: config.max_llm_cost_per_cycle,
+            daily_cap: config.max_total_cost_per_day,
+            spend: Mutex::default(),
+        }
+    }
+
+    /// Either cap is set.
+    pub fn is_enabled(&self) -> bool {
+        self.cycle_cap > Decimal::ZERO || self.daily_cap > Decimal::ZERO
+    }
+
+    fn spend(&self) -> std::sync::MutexGuard<'_, Spend> {
+        self.spend.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Carry today's spend over a restart: the costs booked since the
+    /// end-of-day snapshot taken this morning. Without one, today's costs
+    /// can't be told apart from earlier days' and the day starts from zero.
+    pub fn resume_at(&self, state: &AgentState, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        let spent = state
+            .daily
+            .snapshots
+            .last()
+            .filter(|s| s.date == today - Duration::days(1))
+            .map(|s| (state.total_costs() - s.costs).max(Decimal::ZERO))
+            .unwrap_or(Decimal::ZERO);
+        let mut spend = self.spend();
+        spend.roll_to(today);
+        spend.today = spent;
+    }
+
+    /// Start a cycle's LLM budget afresh.
+    pub fn begin_cycle(&self) {
+        self.spend().cycle = Decimal::ZERO;
+    }
+
+    /// Reserve `expected` for a call at `now`, or refuse it when that would
+    /// overrun a cap. Free calls always go ahead. A reservation is settled
+    /// against the actual cost with [`Self::settle`].
+    pub fn reserve_at(&self, kind: CostKind, expected: Decimal, now: DateTime<Utc>) -> bool {
+        let mut spend = self.spend();
+        spend.roll_to(now.date_naive());
+        let expected = expected.max(Decimal::ZERO);
+        if expected.is_zero() {
+            return true;
+        }
+        if self.daily_cap > Decimal::ZERO && spend.today + expected > self.daily_cap {
+            if !spend.pause_logged {
+                warn!(
+                    spent = %spend.today,
+                    cap = %self.daily_cap,
+                    "Daily cost budget exhausted — estimation paused until the UTC day rolls over"
+                );
+                spend.pause_logged = true;
+            }
+            return false;
+        }
+        if kind == CostKind::Llm && self.cycle_cap > Decimal::ZERO && spend.cycle + expected > self.cycle_cap {
+            return false;
+        }
+        spend.today += expected;
+        if kind == CostKind::Llm {
+            spend.cycle += expected;
+        }
+        true
+    }
+
+    /// Replace a reservation of `expected` with the call's `actual` cost.
+    pub fn settle(&self, kind: CostKind, expected: Decimal, actual: Decimal) {
+        let delta = actual - expected.max(Decimal::ZERO);
+        let mut spend = self.spend();
+        spend.today = (spend.today + delta).max(Decimal::ZERO);
+        if kind == CostKind::Llm {
+            spend.cycle = (spend.cycle + delta).max(Decimal::ZERO);
+        }
+    }
+
+    /// Book a call that was made without a reservation.
+    pub fn record(&self, kind: CostKind, cost: Decimal) {
+        self.settle(kind, Decimal::ZERO, cost);
+    }
+
+    /// LLM spend this cycle.
+    pub fn cycle_used(&self) -> Decimal {
+        self.spend().cycle
+    }
+
+    /// LLM spend left this cycle under both caps at `now`; `None` when
+    /// neither cap is set.
+    pub fn cycle_remaining_at(&self, now: DateTime<Utc>) -> Option<Decimal> {
+        let mut spend = self.spend();
+        spend.roll_to(now.date_naive());
+        let cycle = (self.cycle_cap > Decimal::ZERO).then(|| self.cycle_cap - spend.cycle);
+        let daily = (self.daily_cap > Decimal::ZERO).then(|| self.daily_cap - spend.today);
+        match (cycle, daily) {
+            (Some(c), Some(d)) => Some(c.min(d).max(Decimal::ZERO)),
+            (c, d) => c.or(d).map(|r| r.max(Decimal::ZERO)),
+        }
+    }
+
+    /// Spend so far on the UTC day of `now`.
+    pub fn spent_today_at(&self, now: DateTime<Utc>) -> Decimal {
+        let mut spend = self.spend();
+        spend.roll_to(now.date_naive());
+        spend.today
+    }
+}
+
+/// Estimates of the leading markets, in order, and how many were skipped
+/// when the budget ran out.
+#[derive(Debug)]
+pub struct Budgeted {
+    pub estimates: Vec<Estimate>,
+    pub skipped: usize,
+}
+
+/// Estimate the markets of `batches` (consecutive index ranges, see
+/// [`crate::llm::budget::plan`]) one call each, reserving `expected` (one
+/// call's cost) from `tracker` before each and stopping at the first batch
+/// it refuses. `estimate` is called with each batch's range. With the
+/// tracker disabled the calls all go out at once.
+pub async fn estimate_within<F, Fut>(
+    tracker: &CostTracker,
+    batches: &[Range<usize>],
+    expected: Decimal,
+    now: DateTime<Utc>,
+    mut estimate: F,
+) -> Result<Budgeted>
+where
+    F: FnMut(Range<usize>) -> Fut,
+    Fut: Future<Output = Result<Vec<Estimate>>>,
+{
+    let total = batches.last().map_or(0, |r| r.end);
+    if !tracker.is_enabled() {
+        let results = futures::future::try_join_all(batches.iter().cloned().map(&mut estimate)).await?;
+        return Ok(Budgeted { estimates: results.into_iter().flatten().collect(), skipped: 0 });
+    }
+    let mut estimates = Vec::with_capacity(total);
+    let mut done = 0;
+    for range in batches {
+        if !tracker.reserve_at(CostKind::Llm, expected, now) {
+            break;
+        }
+        done = range.end;
+        match estimate(range.clone()).await {
+            Ok(batch) => {
+                tracker.settle(CostKind::Llm, expected, batch.iter().map(|e| e.cost).sum());
+                estimates.extend(batch);
+            }
+            Err(e) => {
+                tracker.settle(CostKind::Llm, expected, Decimal::ZERO);
+                return Err(e);
+            }
+        }
+    }
+    let skipped = total - done;
+    if skipped > 0 {
+        info!(
+            skipped,
+            estimated = done,
+            cycle_spend = %tracker.cycle_used(),
+            "Cost budget reached — remaining markets skipped until next cycle"
+        );
+    }
+    Ok(Budgeted { estimates, skipped })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::LlmEstimator;
+    use crate::types::{DataContext, Market, MarketCategory};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Every call costs the same, whatever the batch.
+    struct FixedCostLlm {
+        cost: Decimal,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LlmEstimator for FixedCostLlm {
+        async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+            Ok(self.batch_estimate(&[(market.clone(), context.clone())]).await?.remove(0))
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let share = self.cost / Decimal::from(markets.len());
+            Ok(markets
+                .iter()
+                .map(|_| Estimate {
+                    probability: dec!(0.5),
+                    confidence: dec!(0.5),
+                    reasoning: String::new(),
+                    tokens_used: 100,
+                    cost: share,
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                })
+                .collect())
+        }
+
+        fn cost_per_call(&self) -> Decimal {
+            self.cost
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    fn markets(n: usize) -> Vec<(Market, DataContext)> {
+        (0..n)
+            .map(|i| (Market { id: format!("m{i}"), ..Market::sample() }, DataContext::empty(MarketCategory::Other)))
+            .collect()
+    }
+
+    fn tracker(cycle: Decimal, daily: Decimal) -> CostTracker {
+        CostTracker::new(&CostsConfig { max_llm_cost_per_cycle: cycle, max_total_cost_per_day: daily })
+    }
+
+    async fn run(tracker: &CostTracker, llm: &FixedCostLlm, markets: &[(Market, DataContext)], now: DateTime<Utc>) -> Budgeted {
+        let batches = crate::llm::budget::plan(markets, 2, None).batches;
+        estimate_within(tracker, &batches, llm.cost_per_call(), now, |r| llm.batch_estimate(&markets[r]))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_estimation_stops_at_cycle_cap() {
+        let llm = FixedCostLlm { cost: dec!(0.10), calls: AtomicUsize::new(0) };
+        let markets = markets(10);
+        let tracker = tracker(dec!(0.35), Decimal::ZERO);
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+
+        // Three calls of two markets fit in 0.35; a fourth would overrun
+        let out = run(&tracker, &llm, &markets, now).await;
+        assert_eq!((out.estimates.len(), out.skipped), (6, 4));
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(tracker.cycle_used(), dec!(0.30));
+        assert_eq!(tracker.cycle_remaining_at(now), Some(dec!(0.05)));
+
+        // The next cycle starts afresh
+        tracker.begin_cycle();
+        assert_eq!(run(&tracker, &llm, &markets, now).await.estimates.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_daily_cap_pauses_until_utc_rollover() {
+        let llm = FixedCostLlm { cost: dec!(0.10), calls: AtomicUsize::new(0) };
+        let markets = markets(4);
+        let tracker = tracker(Decimal::ZERO, dec!(0.50));
+        let day1 = Utc.with_ymd_and_hms(2026, 3, 1, 20, 0, 0).unwrap();
+
+        // Data spend counts towards the day too
+        assert!(tracker.reserve_at(CostKind::Data, dec!(0.15), day1));
+        assert_eq!(run(&tracker, &llm, &markets, day1).await.estimates.len(), 4);
+        tracker.begin_cycle();
+        // 0.35 spent: one more call fits, the next is refused
+        let out = run(&tracker, &llm, &markets, day1).await;
+        assert_eq!((out.estimates.len(), out.skipped), (2, 2));
+        tracker.begin_cycle();
+        let out = run(&tracker, &llm, &markets, day1 + Duration::hours(3)).await;
+        assert_eq!((out.estimates.len(), out.skipped), (0, 4));
+        assert_eq!(tracker.spent_today_at(day1), dec!(0.45));
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 3);
+
+        // Past midnight UTC the budget is back
+        let day2 = Utc.with_ymd_and_hms(2026, 3, 2, 0, 5, 0).unwrap();
+        tracker.begin_cycle();
+        let out = run(&tracker, &llm, &markets, day2).await;
+        assert_eq!((out.estimates.len(), out.skipped), (4, 0));
+        assert_eq!(tracker.spent_today_at(day2), dec!(0.20));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_tracker_still_splits_batches() {
+        let llm = FixedCostLlm { cost: dec!(0.10), calls: AtomicUsize::new(0) };
+        let markets = markets(5);
+        let tracker = CostTracker::default();
+        let out = run(&tracker, &llm, &markets, Utc::now()).await;
+        assert_eq!((out.estimates.len(), out.skipped), (5, 0));
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(tracker.cycle_remaining_at(Utc::now()), None);
+    }
+
+    #[test]
+    fn test_settle_books_actual_cost() {
+        let tracker = tracker(dec!(1), Decimal::ZERO);
+        let now = Utc::now();
+        assert!(tracker.reserve_at(CostKind::Llm, dec!(0.10), now));
+        tracker.settle(CostKind::Llm, dec!(0.10), dec!(0.25));
+        tracker.record(CostKind::Llm, dec!(0.05));
+        assert_eq!(tracker.cycle_used(), dec!(0.30));
+        // Data never counts against the cycle
+        tracker.record(CostKind::Data, dec!(0.40));
+        assert_eq!((tracker.cycle_used(), tracker.spent_today_at(now)), (dec!(0.30), dec!(0.70)));
+    }
+}
@@ -0,0 +1,363 @@
+//! Resuming a failed cycle.
+//!
+//! A cycle pays for its scan and enrichment before the LLM sees a market,
+//! so when estimation then fails — a provider outage, say — starting the
+//! next cycle from the scan pays for both again. [`CycleRecovery`] keeps
+//! each stage's output in a [`CycleContext`] until the cycle completes:
+//!
+//! - while a failed cycle's enrichment is younger than
+//!   `recovery.max_age_mins`, the next cycle skips the scan and enrichment
+//!   and goes straight to estimation, accounting the data cost then;
+//! - bets approved but not placed because their orders never reached the
+//!   venue (rate limits, refused connections) are retried as approved by
+//!   the next cycle instead of being estimated again. The executor
+//!   re-validates each one against its venue's price within
+//!   `execution.slippage_tolerance` as it places it.
+//!
+//! The context lives in memory and, with `recovery.file` set, is saved
+//! there after each stage so a restart resumes too. It is only worth its
+//! max age, so an unreadable file is discarded rather than migrated.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::RecoveryConfig;
+use crate::engine::executor::ExecutionReport;
+use crate::strategy::kelly::SizedBet;
+use crate::types::{DataContext, Market};
+
+/// Markets a cycle scanned and enriched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichedStage {
+    pub at: DateTime<Utc>,
+    /// Markets the scan returned, before the cost guard.
+    pub markets_scanned: usize,
+    pub enriched: Vec<(Market, DataContext)>,
+    /// Data cost of the enrichment, accounted by the cycle that completes.
+    pub data_cost: Decimal,
+}
+
+/// Approved bets whose orders never reached their venue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovedStage {
+    pub at: DateTime<Utc>,
+    pub bets: Vec<SizedBet>,
+}
+
+/// Output of the stages of a cycle that has yet to complete.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CycleContext {
+    #[serde(default)]
+    pub enriched: Option<EnrichedStage>,
+    #[serde(default)]
+    pub approved: Option<ApprovedStage>,
+}
+
+/// The context carried between cycles, and where it is saved.
+#[derive(Debug)]
+pub struct CycleRecovery {
+    config: RecoveryConfig,
+    context: CycleContext,
+}
+
+impl CycleRecovery {
+    /// An empty context.
+    pub fn new(config: RecoveryConfig) -> Self {
+        Self { config, context: CycleContext::default() }
+    }
+
+    /// The context saved at `config.file`. Without a file, or when it is
+    /// missing or unreadable, the context is empty.
+    pub fn load(config: RecoveryConfig) -> Self {
+        let path = Path::new(&config.file);
+        if config.file.is_empty() || !path.exists() {
+            return Self::new(config);
+        }
+        let context = std::fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(serde_json::from_str::<CycleContext>(&text)?));
+        match context {
+            Ok(context) => Self { config, context },
+            Err(e) => {
+                warn!(file = %path.display(), error = %e, "Unreadable cycle context discarded");
+                Self::new(config)
+            }
+        }
+    }
+
+    /// Write the context to `config.file`, if set, replacing the file only
+    /// once the new one is complete.
+    pub fn save(&self) -> Result<()> {
+        if self.config.file.is_empty() {
+            return Ok(());
+        }
+        let json = serde_json::to_string(&self.context).context("Failed to serialise cycle context")?;
+        let path = Path::new(&self.config.file);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn context(&self) -> &CycleContext {
+        &self.context
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.max_age_mins > 0
+    }
+
+    fn is_fresh(&self, at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.is_enabled() && now - at < Duration::minutes(self.config.max_age_mins)
+    }
+
+    /// A failed cycle's enrichment to estimate from at `now`, while fresh.
+    pub fn enriched(&self, now: DateTime<Utc>) -> Option<&EnrichedStage> {
+        self.context.enriched.as_ref().filter(|s| self.is_fresh(s.at, now))
+    }
+
+    /// Bets a cycle approved but couldn't place, to retry at `now`, while
+    /// fresh.
+    pub fn approved(&self, now: DateTime<Utc>) -> Option<&ApprovedStage> {
+        self.context.approved.as_ref().filter(|s| self.is_fresh(s.at, now))
+    }
+
+    /// Keep a cycle's enrichment until it completes, in place of anything
+    /// left by an earlier one.
+    pub fn record_enriched(&mut self, stage: EnrichedStage) {
+        if self.is_enabled() {
+            self.context = CycleContext { enriched: Some(stage), approved: None };
+        }
+    }
+
+    /// The cycle's estimates and bets are done with: keep only the
+    /// approved bets its execution left unplaced (see [`unplaced`]).
+    pub fn record_unplaced(&mut self, bets: Vec<SizedBet>, at: DateTime<Utc>) {
+        let approved = (self.is_enabled() && !bets.is_empty()).then_some(ApprovedStage { at, bets });
+        self.context = CycleContext { enriched: None, approved };
+    }
+
+    /// Nothing left to resume.
+    pub fn clear(&mut self) {
+        self.context = CycleContext::default();
+    }
+}
+
+/// Bets of `bets` whose orders failed before reaching their venue, and so
+/// can be placed again as they are.
+pub fn unplaced(bets: &[SizedBet], report: &ExecutionReport) -> Vec<SizedBet> {
+    let failed = |bet: &SizedBet| {
+        report.failed.iter().any(|f| {
+            f.code.is_retryable()
+                && !f.code.may_have_submitted()
+                && f.platform == bet.edge.market.platform
+                && f.market_id == bet.edge.market.id
+        })
+    };
+    bets.iter().filter(|b| failed(b)).cloned().collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use rust_decimal_macros::dec;
+
+    use crate::engine::executor::{ExecutionFailure, FailedTrade};
+    use crate::llm::LlmEstimator;
+    use crate::strategy::edge::Edge;
+    use crate::types::{Estimate, MarketCategory, Side};
+
+    fn config(max_age_mins: i64, file: &str) -> RecoveryConfig {
+        RecoveryConfig { max_age_mins, file: file.to_string() }
+    }
+
+    fn temp_file(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("oracle_cycle_context_{name}_{}.json", uuid::Uuid::new_v4()));
+        path.to_str().unwrap().to_string()
+    }
+
+    fn estimate() -> Estimate {
+        Estimate {
+            probability: dec!(0.65),
+            confidence: dec!(0.8),
+            reasoning: String::new(),
+            tokens_used: 100,
+            cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    fn bet(platform: &str, market_id: &str) -> SizedBet {
+        SizedBet {
+            edge: Edge {
+                market: Market { id: market_id.to_string(), platform: platform.to_string(), ..Market::sample() },
+                estimate: estimate(),
+                side: Side::Yes,
+                edge: dec!(0.15),
+                signed_edge: dec!(0.15),
+                raw_edge: dec!(0.15),
+                effective_threshold: dec!(0.06),
+            },
+            kelly_fraction: dec!(0.10),
+            bet_fraction: dec!(0.05),
+            bet_amount: dec!(10),
+            expected_value: dec!(1.5),
+            net_expected_value: dec!(1.5),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.15),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: Some(format!("key-{market_id}")),
+        }
+    }
+
+    /// Fails its first call, then estimates every market at 65%.
+    #[derive(Default)]
+    struct FlakyLlm {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LlmEstimator for FlakyLlm {
+        async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+            let mut batch = self.batch_estimate(&[(market.clone(), context.clone())]).await?;
+            Ok(batch.remove(0))
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                anyhow::bail!("529 overloaded");
+            }
+            Ok(markets.iter().map(|_| estimate()).collect())
+        }
+
+        fn cost_per_call(&self) -> Decimal { dec!(0.01) }
+        fn model_name(&self) -> &str { "flaky" }
+    }
+
+    /// Scans and enriches `markets` unless `recovery` has a fresh
+    /// enrichment to resume from, then estimates them, as `run_cycle`
+    /// does. Returns the data cost the cycle accounts.
+    async fn cycle(recovery: &mut CycleRecovery, llm: &FlakyLlm, enrichments: &mut usize, now: DateTime<Utc>) -> Result<Decimal> {
+        let stage = match recovery.enriched(now) {
+            Some(stage) => stage.clone(),
+            None => {
+                *enrichments += 1;
+                let enriched: Vec<_> = (0..3)
+                    .map(|i| (Market { id: format!("m{i}"), ..Market::sample() }, DataContext::empty(MarketCategory::Economics)))
+                    .collect();
+                let stage = EnrichedStage { at: now, markets_scanned: 5, enriched, data_cost: dec!(0.05) };
+                recovery.record_enriched(stage.clone());
+                recovery.save()?;
+                stage
+            }
+        };
+        let estimates = llm.batch_estimate(&stage.enriched).await?;
+        assert_eq!(estimates.len(), stage.enriched.len());
+        recovery.record_unplaced(Vec::new(), now);
+        recovery.save()?;
+        Ok(stage.data_cost)
+    }
+
+    #[tokio::test]
+    async fn test_failed_estimation_resumes_without_rescanning() {
+        let now = Utc::now();
+        let llm = FlakyLlm::default();
+        let mut recovery = CycleRecovery::new(config(30, ""));
+        let mut enrichments = 0;
+
+        assert!(cycle(&mut recovery, &llm, &mut enrichments, now).await.is_err());
+        let cost = cycle(&mut recovery, &llm, &mut enrichments, now + Duration::minutes(10)).await.unwrap();
+        assert_eq!((enrichments, cost), (1, dec!(0.05)));
+        assert!(recovery.context().enriched.is_none());
+
+        // A completed cycle leaves nothing to resume: the next scans again.
+        cycle(&mut recovery, &llm, &mut enrichments, now + Duration::minutes(20)).await.unwrap();
+        assert_eq!(enrichments, 2);
+    }
+
+    #[tokio::test]
+    async fn test_stale_or_disabled_enrichment_is_not_resumed() {
+        let now = Utc::now();
+        let mut enrichments = 0;
+        let mut recovery = CycleRecovery::new(config(30, ""));
+        assert!(cycle(&mut recovery, &FlakyLlm::default(), &mut enrichments, now).await.is_err());
+        assert!(recovery.enriched(now + Duration::minutes(29)).is_some());
+        assert!(recovery.enriched(now + Duration::minutes(30)).is_none());
+
+        let mut off = CycleRecovery::new(config(0, ""));
+        assert!(cycle(&mut off, &FlakyLlm::default(), &mut enrichments, now).await.is_err());
+        assert!(off.context().enriched.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_context_survives_a_restart() {
+        let now = Utc::now();
+        let file = temp_file("restart");
+        let mut recovery = CycleRecovery::new(config(30, &file));
+        let mut enrichments = 0;
+        assert!(cycle(&mut recovery, &FlakyLlm::default(), &mut enrichments, now).await.is_err());
+
+        // The process restarts before the next cycle.
+        let mut reloaded = CycleRecovery::load(config(30, &file));
+        let llm = FlakyLlm { calls: AtomicUsize::new(1) };
+        let cost = cycle(&mut reloaded, &llm, &mut enrichments, now + Duration::minutes(5)).await.unwrap();
+        assert_eq!((enrichments, cost), (1, dec!(0.05)));
+        assert!(CycleRecovery::load(config(30, &file)).context().enriched.is_none());
+
+        std::fs::write(&file, "{not json").unwrap();
+        assert!(CycleRecovery::load(config(30, &file)).context().enriched.is_none());
+        std::fs::remove_file(&file).ok();
+    }
+
+    #[test]
+    fn test_only_bets_that_never_reached_the_venue_are_kept() {
+        let now = Utc::now();
+        let bets = vec![bet("betfair", "limited"), bet("betfair", "timed-out"), bet("betfair", "closed"), bet("betfair", "filled")];
+        let failure = |market_id: &str, code| FailedTrade {
+            market_id: market_id.to_string(),
+            platform: "betfair".to_string(),
+            reason: String::new(),
+            code,
+            attempts: 3,
+            latency_ms: 0,
+            observed_price: None,
+        };
+        let report = ExecutionReport {
+            executed: Vec::new(),
+            failed: vec![
+                failure("limited", ExecutionFailure::RateLimited),
+                failure("timed-out", ExecutionFailure::Timeout),
+                failure("closed", ExecutionFailure::MarketClosed),
+            ],
+            unconfirmed: Vec::new(),
+            total_committed: Decimal::ZERO,
+            total_commission: Decimal::ZERO,
+            duplicate_fills: 0,
+        };
+        let retry = unplaced(&bets, &report);
+        assert_eq!(retry.iter().map(|b| b.edge.market.id.as_str()).collect::<Vec<_>>(), ["limited"]);
+
+        let mut recovery = CycleRecovery::new(config(30, ""));
+        recovery.record_unplaced(retry, now);
+        let approved = recovery.approved(now + Duration::minutes(10)).unwrap();
+        // Retried as approved, under the same idempotency key.
+        assert_eq!(approved.bets[0].idempotency_key.as_deref(), Some("key-limited"));
+        assert!(recovery.approved(now + Duration::minutes(30)).is_none());
+    }
+}
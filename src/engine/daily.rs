@@ -0,0 +1,386 @@
+//! End-of-day snapshots and the daily P&L derived from them.
+//!
+//! Cycles run at arbitrary times relative to midnight, so accumulating a
+//! "today" figure as they go mixes realised results, costs and price drift
+//! from either side of the boundary. Instead the first cycle after UTC
+//! midnight closes the previous day: it values every open position at its
+//! last mark and appends a [`DailySnapshot`] to the state's ledger. A day's
+//! P&L is the difference between its snapshot and the one before, and
+//! "today" is the live books against the latest snapshot, so the daily
+//! report, the daily loss halt and the dashboard all agree.
+//!
+//! A resolution for a market that closed before the latest snapshot's
+//! cutoff belongs to that day. It is booked as a [`DailyCorrection`]
+//! against the snapshot, taking the bet's valuation out and its realised
+//! P&L in, rather than landing in today. Snapshots themselves are never
+//! rewritten.
+//!
+//! When the agent was down over one or more midnights, the first cycle
+//! closes only the latest day; the figure for that snapshot then spans
+//! every day since the previous one (`since`).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::types::{
+    AgentState, DailyBook, DailyCorrection, DailyLedger, DailySnapshot, Market, Side, TradeReceipt,
+};
+
+/// Currency the agent's running costs are paid in.
+const COST_CURRENCY: &str = "AUD";
+
+// ---------------------------------------------------------------------------
+// Valuation
+// ---------------------------------------------------------------------------
+
+/// Record the current YES price of every held market in `markets`, and
+/// forget marks of markets no longer held.
+pub fn observe_marks(ledger: &mut DailyLedger, markets: &[Market], open_bets: &[TradeReceipt]) {
+    let held: BTreeSet<&str> = open_bets.iter().map(|b| b.market_id.as_str()).collect();
+    ledger.marks.retain(|id, _| held.contains(id.as_str()));
+    for market in markets.iter().filter(|m| held.contains(m.id.as_str())) {
+        ledger.marks.insert(market.id.clone(), market.current_price_yes);
+    }
+}
+
+/// Value of `bet` at a YES price of `mark`. Entry prices are probabilities
+/// or, above 1, decimal odds; a bet without a usable entry price or mark is
+/// valued at its stake.
+pub fn position_value(bet: &TradeReceipt, mark: Option<Decimal>) -> Decimal {
+    let entry = match bet.fill_price {
+        p if p > Decimal::ZERO && p < Decimal::ONE => match bet.side {
+            Side::Yes => p,
+            Side::No => Decimal::ONE - p,
+        },
+        p if p > Decimal::ONE => Decimal::ONE / p,
+        _ => return bet.amount,
+    };
+    let Some(mark) = mark.filter(|m| *m >= Decimal::ZERO && *m <= Decimal::ONE) else {
+        return bet.amount;
+    };
+    let current = match bet.side {
+        Side::Yes => mark,
+        Side::No => Decimal::ONE - mark,
+    };
+    bet.amount * current / entry
+}
+
+/// Snapshot of the books as they stand, for the day `date`.
+pub fn snapshot(state: &AgentState, date: NaiveDate, now: DateTime<Utc>) -> DailySnapshot {
+    let mut books: BTreeMap<String, DailyBook> = BTreeMap::new();
+    books.insert(
+        "AUD".to_string(),
+        DailyBook { cash: state.bankroll, realized: state.total_pnl, ..Default::default() },
+    );
+    books.insert(
+        "Mana".to_string(),
+        DailyBook { cash: state.mana_bankroll, realized: state.total_mana_pnl, ..Default::default() },
+    );
+    let mut positions = BTreeMap::new();
+    for bet in &state.open_bets {
+        let value = position_value(bet, state.daily.marks.get(&bet.market_id).copied());
+        let book = books.entry(bet.currency.clone()).or_default();
+        book.staked += bet.amount;
+        book.at_market += value;
+        positions.insert(bet.order_id.clone(), value - bet.amount);
+    }
+    DailySnapshot { date, taken_at: now, books, costs: state.total_costs(), positions }
+}
+
+// ---------------------------------------------------------------------------
+// Closing days
+// ---------------------------------------------------------------------------
+
+/// Close the previous UTC day if it has no snapshot yet. Returns the P&L
+/// of the closed day (empty for the very first snapshot, which is only a
+/// baseline), or `None` when nothing was due.
+pub fn close_day(state: &mut AgentState, now: DateTime<Utc>) -> Option<Vec<DayPnl>> {
+    let date = now.date_naive() - Duration::days(1);
+    let last = state.daily.snapshots.last().map(|s| s.date);
+    if last.is_some_and(|d| d >= date) {
+        return None;
+    }
+    if let Some(since) = last.filter(|d| (date - *d).num_days() > 1) {
+        warn!(
+            date = %date,
+            since = %since,
+            missed = (date - since).num_days() - 1,
+            "Missed end-of-day snapshots — the closed day's P&L spans the gap"
+        );
+    }
+    let open_ids: BTreeSet<&str> = state.open_bets.iter().map(|b| b.market_id.as_str()).collect();
+    state.daily.marks.retain(|id, _| open_ids.contains(id.as_str()));
+    let snap = snapshot(state, date, now);
+    info!(date = %date, positions = snap.positions.len(), "End-of-day snapshot taken");
+    state.daily.snapshots.push(snap);
+    let n = state.daily.snapshots.len();
+    Some(match n {
+        1 => Vec::new(),
+        _ => diff(&state.daily, n - 2, &state.daily.snapshots[n - 1]),
+    })
+}
+
+/// Book a resolution. One for a market that closed before the latest
+/// snapshot's cutoff, and valued in it, is recorded against that day.
+pub fn record_resolution(state: &mut AgentState, bet: &TradeReceipt, pnl: Decimal, now: DateTime<Utc>) {
+    let Some(last) = state.daily.snapshots.last() else { return };
+    let cutoff = (last.date + Duration::days(1)).and_time(chrono::NaiveTime::MIN).and_utc();
+    if bet.deadline.is_none_or(|d| d >= cutoff) {
+        return;
+    }
+    let Some(valued) = last.positions.get(&bet.order_id) else { return };
+    let correction = DailyCorrection {
+        date: last.date,
+        recorded_at: now,
+        order_id: bet.order_id.clone(),
+        currency: bet.currency.clone(),
+        realized: pnl,
+        unrealized: -*valued,
+    };
+    info!(
+        date = %correction.date,
+        order_id = %correction.order_id,
+        realized = %pnl,
+        "Late resolution booked against the previous day"
+    );
+    state.daily.corrections.push(correction);
+}
+
+// ---------------------------------------------------------------------------
+// Daily P&L
+// ---------------------------------------------------------------------------
+
+/// P&L in one currency between two snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DayPnl {
+    /// Day closed (for "today", the current date).
+    pub date: NaiveDate,
+    /// Day of the previous snapshot; earlier than the day before `date`
+    /// when snapshots were missed.
+    pub since: NaiveDate,
+    pub currency: String,
+    pub realized: Decimal,
+    /// Change in the open positions' unrealised P&L.
+    pub unrealized: Decimal,
+    /// Running costs (AUD only).
+    pub costs: Decimal,
+    /// `realized + unrealized - costs`.
+    pub pnl: Decimal,
+}
+
+/// Realised and unrealised totals per currency, plus costs, of snapshot
+/// `snap` with the corrections recorded against its day.
+fn totals(ledger: &DailyLedger, snap: &DailySnapshot) -> (BTreeMap<String, (Decimal, Decimal)>, Decimal) {
+    let mut totals: BTreeMap<String, (Decimal, Decimal)> = snap
+        .books
+        .iter()
+        .map(|(currency, book)| (currency.clone(), (book.realized, book.unrealized())))
+        .collect();
+    for c in ledger.corrections.iter().filter(|c| c.date == snap.date) {
+        let t = totals.entry(c.currency.clone()).or_default();
+        t.0 += c.realized;
+        t.1 += c.unrealized;
+    }
+    (totals, snap.costs)
+}
+
+/// P&L from snapshot `prev` of the ledger to `snap`.
+fn diff(ledger: &DailyLedger, prev: usize, snap: &DailySnapshot) -> Vec<DayPnl> {
+    let base = &ledger.snapshots[prev];
+    let (from, from_costs) = totals(ledger, base);
+    let (to, to_costs) = totals(ledger, snap);
+    let currencies: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+    currencies
+        .into_iter()
+        .map(|currency| {
+            let (r0, u0) = from.get(currency).copied().unwrap_or_default();
+            let (r1, u1) = to.get(currency).copied().unwrap_or_default();
+            let costs = if currency == COST_CURRENCY { to_costs - from_costs } else { Decimal::ZERO };
+            let (realized, unrealized) = (r1 - r0, u1 - u0);
+            DayPnl {
+                date: snap.date,
+                since: base.date,
+                currency: currency.clone(),
+                realized,
+                unrealized,
+                costs,
+                pnl: realized + unrealized - costs,
+            }
+        })
+        .collect()
+}
+
+/// P&L of every closed day after the first snapshot, oldest first.
+pub fn history(ledger: &DailyLedger) -> Vec<DayPnl> {
+    (1..ledger.snapshots.len())
+        .flat_map(|i| diff(ledger, i - 1, &ledger.snapshots[i]))
+        .collect()
+}
+
+/// P&L since the latest snapshot; empty before the first one.
+pub fn today(state: &AgentState, now: DateTime<Utc>) -> Vec<DayPnl> {
+    let Some(last) = state.daily.snapshots.len().checked_sub(1) else {
+        return Vec::new();
+    };
+    diff(&state.daily, last, &snapshot(state, now.date_naive(), now))
+}
+
+/// Today's loss in AUD as a fraction of the bankroll at the latest
+/// snapshot; zero when up on the day or before the first snapshot.
+pub fn daily_loss_pct(state: &AgentState, now: DateTime<Utc>) -> Decimal {
+    let Some(base) = state.daily.snapshots.last().and_then(|s| s.books.get(COST_CURRENCY)).map(|b| b.cash) else {
+        return Decimal::ZERO;
+    };
+    let pnl = today(state, now)
+        .into_iter()
+        .find(|d| d.currency == COST_CURRENCY)
+        .map_or(Decimal::ZERO, |d| d.pnl);
+    if base <= Decimal::ZERO || pnl >= Decimal::ZERO {
+        Decimal::ZERO
+    } else {
+        -pnl / base
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn bet(id: &str, amount: Decimal, fill_price: Decimal, deadline: DateTime<Utc>) -> TradeReceipt {
+        TradeReceipt {
+            order_id: id.to_string(),
+            fill_price,
+            deadline: Some(deadline),
+            ..TradeReceipt::dry_run(&format!("m-{id}"), amount, "AUD")
+        }
+    }
+
+    fn mark(state: &mut AgentState, id: &str, price: Decimal) {
+        state.daily.marks.insert(format!("m-{id}"), price);
+    }
+
+    fn resolve(state: &mut AgentState, id: &str, pnl: Decimal, now: DateTime<Utc>) {
+        let at = state.open_bets.iter().position(|b| b.order_id == id).unwrap();
+        let bet = state.open_bets.remove(at);
+        state.total_pnl += pnl;
+        state.bankroll += bet.amount + pnl;
+        record_resolution(state, &bet, pnl, now);
+    }
+
+    fn aud(days: &[DayPnl]) -> DayPnl {
+        days.iter().find(|d| d.currency == "AUD").cloned().unwrap()
+    }
+
+    #[test]
+    fn test_position_value_at_mark() {
+        let b = bet("a", dec!(10), dec!(0.40), at(1, 0));
+        assert_eq!(position_value(&b, Some(dec!(0.50))), dec!(12.5));
+        assert_eq!(position_value(&b, None), dec!(10));
+        let no = TradeReceipt { side: Side::No, ..b.clone() };
+        assert_eq!(position_value(&no, Some(dec!(0.70))), dec!(5));
+        // Decimal odds of 2.5 → entry probability 0.4.
+        let odds = TradeReceipt { fill_price: dec!(2.5), ..b };
+        assert_eq!(position_value(&odds, Some(dec!(0.50))), dec!(12.5));
+    }
+
+    #[test]
+    fn test_days_with_overnight_resolution() {
+        let mut state = AgentState::new(dec!(100));
+        // Day 1: baseline snapshot, then a bet goes on.
+        assert_eq!(close_day(&mut state, at(1, 0)), Some(Vec::new()));
+        assert_eq!(close_day(&mut state, at(1, 12)), None);
+        state.bankroll -= dec!(10);
+        state.open_bets.push(bet("a", dec!(10), dec!(0.40), at(2, 22)));
+        mark(&mut state, "a", dec!(0.50));
+        state.total_api_costs += dec!(1);
+
+        // Day 2 closes (snapshot for 1 Mar, taken 2 Mar): +2.5 unrealised, 1 cost.
+        let closed = close_day(&mut state, at(2, 0)).unwrap();
+        let day = aud(&closed);
+        assert_eq!((day.date, day.realized, day.unrealized, day.costs), (at(1, 0).date_naive(), dec!(0), dec!(2.5), dec!(1)));
+        assert_eq!(day.pnl, dec!(1.5));
+
+        // Market closes 22:00 on 2 Mar valued at 0.60; the snapshot for
+        // 2 Mar is taken before the resolution arrives.
+        mark(&mut state, "a", dec!(0.60));
+        close_day(&mut state, at(3, 0)).unwrap();
+        assert_eq!(aud(&history(&state.daily)).pnl, dec!(1.5));
+        resolve(&mut state, "a", dec!(15), at(3, 1));
+        assert_eq!(state.daily.corrections.len(), 1);
+
+        // 2 Mar now carries the win: -2.5 valued on 1 Mar, +15 realised.
+        let days = history(&state.daily);
+        let second = days.iter().filter(|d| d.currency == "AUD").nth(1).unwrap();
+        assert_eq!((second.realized, second.unrealized, second.pnl), (dec!(15), dec!(-2.5), dec!(12.5)));
+        // And today is flat.
+        assert_eq!(aud(&today(&state, at(3, 6))).pnl, dec!(0));
+        let closed = close_day(&mut state, at(4, 0)).unwrap();
+        assert_eq!(aud(&closed).pnl, dec!(0));
+    }
+
+    #[test]
+    fn test_same_day_resolution_stays_in_today() {
+        let mut state = AgentState::new(dec!(100));
+        close_day(&mut state, at(1, 0));
+        state.open_bets.push(bet("a", dec!(10), dec!(0.50), at(1, 20)));
+        resolve(&mut state, "a", dec!(-10), at(1, 21));
+        assert!(state.daily.corrections.is_empty());
+        assert_eq!(aud(&today(&state, at(1, 22))).realized, dec!(-10));
+        assert_eq!(aud(&close_day(&mut state, at(2, 0)).unwrap()).realized, dec!(-10));
+    }
+
+    #[test]
+    fn test_missed_midnights_catch_up() {
+        let mut state = AgentState::new(dec!(100));
+        close_day(&mut state, at(1, 0));
+        state.total_pnl += dec!(4);
+        // Down from 1 Mar until 5 Mar: only 4 Mar is closed, spanning 1–4 Mar.
+        let closed = close_day(&mut state, at(5, 3)).unwrap();
+        let day = aud(&closed);
+        assert_eq!((day.since, day.date), (NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(), at(4, 0).date_naive()));
+        assert_eq!(day.realized, dec!(4));
+        assert_eq!(state.daily.snapshots.len(), 2);
+        assert_eq!(close_day(&mut state, at(5, 4)), None);
+    }
+
+    #[test]
+    fn test_daily_loss_pct() {
+        let mut state = AgentState::new(dec!(100));
+        assert_eq!(daily_loss_pct(&state, at(1, 0)), Decimal::ZERO);
+        close_day(&mut state, at(1, 0));
+        state.open_bets.push(bet("a", dec!(20), dec!(0.50), at(9, 0)));
+        mark(&mut state, "a", dec!(0.25));
+        state.total_api_costs += dec!(2);
+        assert_eq!(daily_loss_pct(&state, at(1, 8)), dec!(0.12));
+        mark(&mut state, "a", dec!(0.90));
+        assert_eq!(daily_loss_pct(&state, at(1, 9)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_observe_marks_tracks_held_markets() {
+        let mut ledger = DailyLedger::default();
+        ledger.marks.insert("gone".into(), dec!(0.3));
+        let held = vec![bet("a", dec!(1), dec!(0.5), at(9, 0))];
+        let mut market = crate::testkit::market("m-a", "manifold", "Q?", crate::types::MarketCategory::Other, 0.42, 1.0, 24);
+        observe_marks(&mut ledger, &[market.clone()], &held);
+        assert_eq!(ledger.marks.get("m-a"), Some(&market.current_price_yes));
+        assert!(!ledger.marks.contains_key("gone"));
+        market.id = "other".into();
+        observe_marks(&mut ledger, &[market], &held);
+        assert_eq!(ledger.marks.len(), 1);
+    }
+}
@@ -1,25 +1,39 @@
 //! Data enrichment pipeline.
 //!
 //! Routes markets to appropriate data providers based on category,
-//! aggregates contexts, and manages TTL-based caching to minimise
-//! API costs. Implements cross-market data sharing (e.g., one weather
+//! aggregates their contexts into one composite per market, and manages
+//! TTL-based caching to minimise API costs. A provider that fails only
+//! loses its own section: the rest still reach the LLM, and the failed
+//! source is named in the prompt. Implements cross-market data sharing (e.g., one weather
 //! fetch serves all weather markets in the same geographic area).
 //!
+//! Cache misses are fetched concurrently, up to
+//! `data_sources.max_concurrent_requests` at once. Each provider may hold
+//! at most half of those slots, so one slow API can't starve the others.
+//! Paid fetches the daily cost budget can't cover are not made (see
+//! [`super::cost_tracker`]); their sections show as unavailable.
+//!
 //! This is Phase 3E from the development plan.
 
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
-use tracing::{debug, info, warn};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tracing::{debug, info, info_span, warn, Instrument};
 
+use super::cost_tracker::{CostKind, CostTracker};
 use crate::config::EnricherConfig;
-use crate::data::economics::EconomicsProvider;
+use crate::diagnostics::{CollectionSize, SizedStore};
+use crate::data::economics::FredProvider;
+use crate::data::manifold_flow::summarize_flow;
 use crate::data::news::NewsProvider;
-use crate::data::sports::SportsProvider;
-use crate::data::weather::WeatherProvider;
+use crate::data::sports::ApiSportsProvider;
+use crate::data::weather::OpenMeteoProvider;
 use crate::data::DataProvider;
-use crate::types::{DataContext, Market, MarketCategory};
+use crate::platforms::manifold::ManifoldClient;
+use crate::types::{ContextSection, DataContext, Market, MarketCategory};
 
 // ---------------------------------------------------------------------------
 // Cache
@@ -75,6 +89,110 @@ impl ContextCache {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Providers
+// ---------------------------------------------------------------------------
+
+/// Section label for the Manifold trade-flow signal.
+const FLOW_SOURCE: &str = "manifold_flow";
+
+/// Default cap on in-flight provider requests during a batch.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// A data provider the enricher can route a market to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Provider {
+    Weather,
+    Sports,
+    Economics,
+    News,
+}
+
+impl Provider {
+    const ALL: [Provider; 4] = [Provider::Weather, Provider::Sports, Provider::Economics, Provider::News];
+
+    /// Section label and cache-key prefix.
+    fn label(self) -> &'static str {
+        match self {
+            Provider::Weather => "weather",
+            Provider::Sports => "sports",
+            Provider::Economics => "economics",
+            Provider::News => "news",
+        }
+    }
+
+    /// Providers consulted for `category`, primary first.
+    fn for_category(category: MarketCategory, supplementary_news: bool) -> Vec<Provider> {
+        let primary = match category {
+            MarketCategory::Weather => Provider::Weather,
+            MarketCategory::Sports => Provider::Sports,
+            MarketCategory::Economics => Provider::Economics,
+            MarketCategory::Politics | MarketCategory::Culture | MarketCategory::Other => Provider::News,
+        };
+        let mut providers = vec![primary];
+        if supplementary_news && primary != Provider::News {
+            providers.push(Provider::News);
+        }
+        providers
+    }
+}
+
+/// Combine per-provider results into one context. Successful providers
+/// each contribute a section; failed ones are listed as unavailable. The
+/// composite is as fresh as its oldest section and costs their sum.
+fn compose(category: MarketCategory, parts: Vec<(&str, Result<DataContext>)>) -> DataContext {
+    let mut ctx = DataContext::empty(category);
+    let mut raw = serde_json::Map::new();
+    let mut sources = Vec::new();
+    let mut oldest = None;
+    for (label, result) in parts {
+        match result {
+            Ok(part) => {
+                raw.insert(label.to_string(), part.raw_data);
+                sources.push(part.source);
+                ctx.cost += part.cost;
+                oldest = Some(oldest.map_or(part.freshness, |t: chrono::DateTime<Utc>| t.min(part.freshness)));
+                ctx.sections.push(ContextSection {
+                    source: label.to_string(),
+                    summary: part.summary,
+                    freshness: part.freshness,
+                    available: true,
+                });
+            }
+            Err(e) => {
+                warn!(source = label, error = %e, "Data provider failed — section unavailable this cycle");
+                ctx.sections.push(ContextSection::unavailable(label));
+            }
+        }
+    }
+    if !sources.is_empty() {
+        ctx.raw_data = serde_json::Value::Object(raw);
+        ctx.source = sources.join("+");
+    }
+    if let Some(t) = oldest {
+        ctx.freshness = t;
+    }
+    ctx.summary = ctx.render();
+    ctx
+}
+
+/// One provider section of a market being enriched.
+enum Section {
+    /// Served from the cache.
+    Cached(DataContext),
+    /// Fetched this batch under the given cache key, possibly on behalf of
+    /// an earlier market with the same topic.
+    Fetched(String),
+}
+
+/// A provider fetch for one cache key.
+struct Fetch {
+    provider: Provider,
+    cache_key: String,
+    /// Index of the first market that needed it.
+    market: usize,
+}
+
 // ---------------------------------------------------------------------------
 // Enricher
 // ---------------------------------------------------------------------------
@@ -82,11 +200,17 @@ impl ContextCache {
 /// Orchestrates data enrichment across all providers with caching.
 pub struct Enricher {
     config: EnricherConfig,
-    weather: WeatherProvider,
-    sports: SportsProvider,
-    economics: EconomicsProvider,
-    news: NewsProvider,
+    weather: Box<dyn DataProvider>,
+    sports: Box<dyn DataProvider>,
+    economics: Box<dyn DataProvider>,
+    news: Box<dyn DataProvider>,
+    /// In-flight provider requests allowed during a batch.
+    max_concurrent: usize,
+    /// Bet-feed client for the Manifold flow signal (None = disabled).
+    flow: Option<ManifoldClient>,
     cache: ContextCache,
+    /// Daily spend limit shared with the estimation phase.
+    costs: Arc<CostTracker>,
     total_cost: Decimal,
     total_calls: u64,
     cache_hits: u64,
@@ -109,28 +233,80 @@ impl Enricher {
         news_api_key: Option<String>,
         sports_api_key: Option<String>,
     ) -> Result<Self> {
-        Ok(Self {
+        Ok(Self::from_providers(
             config,
-            weather: WeatherProvider::new()
-                .context("Failed to initialise weather provider")?,
-            sports: SportsProvider::new(sports_api_key)
-                .context("Failed to initialise sports provider")?,
-            economics: EconomicsProvider::new(fred_api_key)
-                .context("Failed to initialise economics provider")?,
-            news: NewsProvider::new(news_api_key)
-                .context("Failed to initialise news provider")?,
+            Box::new(OpenMeteoProvider::new().context("Failed to initialise weather provider")?),
+            Box::new(ApiSportsProvider::new(sports_api_key).context("Failed to initialise sports provider")?),
+            Box::new(FredProvider::new(fred_api_key).context("Failed to initialise economics provider")?),
+            Box::new(NewsProvider::new(news_api_key).context("Failed to initialise news provider")?),
+        ))
+    }
+
+    fn from_providers(
+        config: EnricherConfig,
+        weather: Box<dyn DataProvider>,
+        sports: Box<dyn DataProvider>,
+        economics: Box<dyn DataProvider>,
+        news: Box<dyn DataProvider>,
+    ) -> Self {
+        Self {
+            config,
+            weather,
+            sports,
+            economics,
+            news,
+            max_concurrent: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            flow: None,
             cache: ContextCache::new(),
+            costs: Arc::default(),
             total_cost: Decimal::ZERO,
             total_calls: 0,
             cache_hits: 0,
-        })
+        }
+    }
+
+    /// Enable the Manifold trade-flow signal using the given client.
+    pub fn with_manifold_flow(mut self, client: ManifoldClient) -> Self {
+        self.flow = Some(client);
+        self
+    }
+
+    /// Replace the news provider, e.g. one configured for a paid NewsAPI plan.
+    pub fn with_news(mut self, news: NewsProvider) -> Self {
+        self.news = Box::new(news);
+        self
+    }
+
+    /// Replace the sports provider, e.g. one with a persisted request count.
+    pub fn with_sports(mut self, sports: ApiSportsProvider) -> Self {
+        self.sports = Box::new(sports);
+        self
+    }
+
+    /// Replace the economics provider, e.g. one with configured FRED series.
+    pub fn with_economics(mut self, economics: FredProvider) -> Self {
+        self.economics = Box::new(economics);
+        self
+    }
+
+    /// Check paid fetches against `costs` (no limit by default).
+    pub fn with_cost_tracker(mut self, costs: Arc<CostTracker>) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Cap in-flight provider requests per batch (minimum 1).
+    pub fn with_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent = max.max(1);
+        self
     }
 
     /// Enrich a batch of markets with context data.
     ///
     /// Markets sharing the same category benefit from caching —
     /// the first market triggers an API call, subsequent ones reuse
-    /// the cached context if the topic is similar enough.
+    /// its context if the topic is similar enough. Fetches run
+    /// concurrently; the output keeps the input order.
     pub async fn enrich_batch(
         &mut self,
         markets: &[Market],
@@ -139,23 +315,85 @@ impl Enricher {
 
         // Periodic cache cleanup
         self.cache.evict_expired();
+        for provider in Provider::ALL {
+            self.provider(provider).begin_cycle();
+        }
 
-        let mut results = Vec::with_capacity(markets.len());
+        // Plan every market's sections; each missed cache key is fetched once.
+        let mut plans = Vec::with_capacity(markets.len());
+        let mut fetches = Vec::new();
+        let mut pending = HashSet::new();
+        for (idx, market) in markets.iter().enumerate() {
+            let topic = Self::cache_key(market);
+            let mut sections = Vec::new();
+            for provider in Provider::for_category(market.category, self.config.supplementary_news) {
+                let cache_key = format!("{}:{topic}", provider.label());
+                let section = if let Some(cached) = self.cache.get(&cache_key) {
+                    debug!(market_id = %market.id, cache_key = %cache_key, "Cache hit");
+                    self.cache_hits += 1;
+                    Section::Cached(cached.clone())
+                } else {
+                    if pending.insert(cache_key.clone()) {
+                        fetches.push(Fetch { provider, cache_key: cache_key.clone(), market: idx });
+                    } else {
+                        // Shared with an earlier market's fetch this batch.
+                        self.cache_hits += 1;
+                    }
+                    Section::Fetched(cache_key)
+                };
+                sections.push((provider, section));
+            }
+            plans.push(sections);
+        }
 
-        for market in markets {
-            let context = self.enrich_one(market).await;
-            match context {
-                Ok(ctx) => results.push((market.clone(), ctx)),
-                Err(e) => {
-                    warn!(
-                        market_id = %market.id,
-                        error = %e,
-                        "Enrichment failed, using empty context"
-                    );
-                    results.push((market.clone(), DataContext::empty(market.category.clone())));
-                }
+        let now = Utc::now();
+        let (fetches, refused): (Vec<Fetch>, Vec<Fetch>) = fetches
+            .into_iter()
+            .partition(|f| self.costs.reserve_at(CostKind::Data, self.cost_of(f.provider), now));
+        let mut fetched = self.fetch_all(markets, &fetches).await;
+        for fetch in &fetches {
+            let expected = self.cost_of(fetch.provider);
+            if let Some(Ok(context)) = fetched.get(&fetch.cache_key) {
+                self.cache.insert(fetch.cache_key.clone(), context.clone(), self.ttl_for(fetch.provider));
+                self.total_calls += 1;
+                self.total_cost += context.cost;
+                self.costs.settle(CostKind::Data, expected, context.cost);
+            } else {
+                self.costs.settle(CostKind::Data, expected, Decimal::ZERO);
             }
         }
+        if !refused.is_empty() {
+            warn!(skipped = refused.len(), "Daily cost budget exhausted — paid data fetches skipped");
+        }
+        for fetch in refused {
+            fetched.insert(fetch.cache_key, Err("daily cost budget exhausted".to_string()));
+        }
+
+        let mut results = Vec::with_capacity(markets.len());
+        for (market, sections) in markets.iter().zip(plans) {
+            let parts = sections
+                .into_iter()
+                .map(|(provider, section)| {
+                    let result = match section {
+                        Section::Cached(context) => Ok(context),
+                        Section::Fetched(key) => match &fetched[&key] {
+                            Ok(context) => Ok(context.clone()),
+                            Err(e) => Err(anyhow::anyhow!("{e}")),
+                        },
+                    };
+                    (provider.label(), result)
+                })
+                .collect();
+
+            // Cross-refs are per market, not per cached topic.
+            let mut ctx = compose(market.category, parts);
+            ctx.metaculus_forecast = market.cross_refs.metaculus_prob;
+            ctx.metaculus_forecasters = market.cross_refs.metaculus_forecasters;
+            ctx.manifold_price = market.cross_refs.manifold_prob;
+            results.push((market.clone(), ctx));
+        }
+
+        self.append_manifold_flow(&mut results).await;
 
         info!(
             enriched = results.len(),
@@ -169,48 +407,130 @@ impl Enricher {
         Ok(results)
     }
 
-    /// Enrich a single market, checking cache first.
-    async fn enrich_one(&mut self, market: &Market) -> Result<DataContext> {
-        let cache_key = Self::cache_key(market);
-
-        // Check cache
-        if let Some(cached) = self.cache.get(&cache_key) {
-            debug!(
-                market_id = %market.id,
-                cache_key = %cache_key,
-                "Cache hit"
-            );
-            self.cache_hits += 1;
-            // Return cached but update cross-refs for this specific market
-            let mut ctx = cached.clone();
-            ctx.metaculus_forecast = market.cross_refs.metaculus_prob;
-            ctx.metaculus_forecasters = market.cross_refs.metaculus_forecasters;
-            ctx.manifold_price = market.cross_refs.manifold_prob;
-            return Ok(ctx);
+    /// Run `fetches` concurrently, keyed by cache key. A request holds a
+    /// slot of its provider's semaphore (half the batch limit, rounded up)
+    /// before taking one of the batch-wide slots, so requests queued
+    /// behind a slow provider never block the others. Errors are kept as
+    /// strings: several markets may share one failed fetch.
+    async fn fetch_all(
+        &self,
+        markets: &[Market],
+        fetches: &[Fetch],
+    ) -> HashMap<String, Result<DataContext, String>> {
+        let batch = Semaphore::new(self.max_concurrent);
+        let per_provider = self.max_concurrent.div_ceil(2);
+        // Indexed by discriminant, which follows `Provider::ALL`.
+        let limits = Provider::ALL.map(|_| Semaphore::new(per_provider));
+
+        let jobs = fetches.iter().map(|fetch| {
+            let market = &markets[fetch.market];
+            let provider_slot = &limits[fetch.provider as usize];
+            let batch = &batch;
+            async move {
+                let _provider_permit = provider_slot.acquire().await.expect("semaphore is never closed");
+                let _batch_permit = batch.acquire().await.expect("semaphore is never closed");
+                let span = info_span!("enrich_provider", provider = fetch.provider.label(), market_id = %market.id);
+                let result = self
+                    .fetch_from(fetch.provider, market)
+                    .instrument(span)
+                    .await
+                    .map_err(|e| format!("{e:#}"));
+                (fetch.cache_key.clone(), result)
+            }
+        });
+        futures::future::join_all(jobs).await.into_iter().collect()
+    }
+
+    /// Add the recent Manifold bet-flow section to the top N Manifold markets.
+    ///
+    /// `results` follows the caller's priority order. Feeds are cached for
+    /// the duration of this batch only — flow is stale by the next cycle.
+    async fn append_manifold_flow(&self, results: &mut [(Market, DataContext)]) {
+        let Some(client) = &self.flow else { return };
+        if !self.config.manifold_flow_enabled {
+            return;
+        }
+
+        let now = Utc::now();
+        let mut feeds: HashMap<String, Option<ContextSection>> = HashMap::new();
+        for idx in Self::flow_targets(results, self.config.manifold_flow_top_n) {
+            let (market, ctx) = &mut results[idx];
+            if !feeds.contains_key(&market.id) {
+                let section = match client
+                    .fetch_recent_bets(&market.id, self.config.manifold_flow_bets)
+                    .await
+                {
+                    Ok(bets) => summarize_flow(&bets, now, Duration::hours(24)).map(|f| ContextSection {
+                        source: FLOW_SOURCE.to_string(),
+                        summary: f.summary_line(),
+                        freshness: now,
+                        available: true,
+                    }),
+                    Err(e) => {
+                        warn!(market_id = %market.id, error = %e, "Manifold flow fetch failed");
+                        Some(ContextSection::unavailable(FLOW_SOURCE))
+                    }
+                };
+                feeds.insert(market.id.clone(), section);
+            }
+            if let Some(section) = &feeds[&market.id] {
+                ctx.push_section(section.clone());
+            }
         }
+        debug!(fetched = feeds.len(), "Manifold flow enrichment complete");
+    }
 
-        // Cache miss — fetch from provider
-        let context = self.fetch_from_provider(market).await?;
+    /// Indices of the first `top_n` distinct Manifold markets in `results`.
+    fn flow_targets(results: &[(Market, DataContext)], top_n: usize) -> Vec<usize> {
+        let mut seen: Vec<&str> = Vec::new();
+        let mut targets = Vec::new();
+        for (idx, (market, _)) in results.iter().enumerate() {
+            if market.platform != "manifold" {
+                continue;
+            }
+            if !seen.contains(&market.id.as_str()) {
+                if seen.len() >= top_n {
+                    continue;
+                }
+                seen.push(&market.id);
+            }
+            targets.push(idx);
+        }
+        targets
+    }
 
-        // Cache the result
-        let ttl = self.ttl_for_category(&market.category);
-        self.cache.insert(cache_key, context.clone(), ttl);
-        self.total_calls += 1;
-        self.total_cost += context.cost;
+    fn provider(&self, provider: Provider) -> &dyn DataProvider {
+        match provider {
+            Provider::Weather => self.weather.as_ref(),
+            Provider::Sports => self.sports.as_ref(),
+            Provider::Economics => self.economics.as_ref(),
+            Provider::News => self.news.as_ref(),
+        }
+    }
 
-        Ok(context)
+    /// Fetch a market's context from one provider.
+    async fn fetch_from(&self, provider: Provider, market: &Market) -> Result<DataContext> {
+        self.provider(provider).fetch_context(market).await
     }
 
-    /// Route to the appropriate provider based on market category.
-    async fn fetch_from_provider(&self, market: &Market) -> Result<DataContext> {
-        match &market.category {
-            MarketCategory::Weather => self.weather.fetch_context(market).await,
-            MarketCategory::Sports => self.sports.fetch_context(market).await,
-            MarketCategory::Economics => self.economics.fetch_context(market).await,
-            MarketCategory::Politics => self.news.fetch_context(market).await,
-            MarketCategory::Culture => self.news.fetch_context(market).await,
-            MarketCategory::Other => self.news.fetch_context(market).await,
+    /// Data cost enriching `market` would add now, and whether every
+    /// section it needs is already cached. Each market is costed on its
+    /// own, so markets sharing a topic are each charged the fetch.
+    pub fn marginal_cost(&self, market: &Market) -> (Decimal, bool) {
+        let topic = Self::cache_key(market);
+        let mut cost = Decimal::ZERO;
+        let mut warm = true;
+        for provider in Provider::for_category(market.category, self.config.supplementary_news) {
+            if self.cache.get(&format!("{}:{topic}", provider.label())).is_none() {
+                warm = false;
+                cost += self.cost_of(provider);
+            }
         }
+        (cost, warm)
+    }
+
+    fn cost_of(&self, provider: Provider) -> Decimal {
+        self.provider(provider).cost_per_call()
     }
 
     /// Generate a cache key that groups similar markets together.
@@ -240,6 +560,15 @@ impl Enricher {
         format!("{}:{}", category, keywords.join("+"))
     }
 
+    /// TTL of one provider's cached context.
+    fn ttl_for(&self, provider: Provider) -> Duration {
+        match provider {
+            Provider::Weather => self.ttl_for_category(&MarketCategory::Weather),
+            Provider::News => self.ttl_for_category(&MarketCategory::Politics),
+            Provider::Sports | Provider::Economics => self.ttl_for_category(&MarketCategory::Other),
+        }
+    }
+
     /// TTL varies by category — fast-moving categories expire sooner.
     fn ttl_for_category(&self, category: &MarketCategory) -> Duration {
         match category {
@@ -279,6 +608,12 @@ impl Enricher {
     }
 }
 
+impl SizedStore for Enricher {
+    fn collection_sizes(&self) -> Vec<CollectionSize> {
+        vec![CollectionSize::new("enricher.context_cache", self.cache.len(), None)]
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -287,6 +622,7 @@ impl Enricher {
 mod tests {
     use super::*;
     use crate::types::{CrossReferences, d};
+    use rust_decimal_macros::dec;
 
     fn make_market(
         id: &str,
@@ -307,6 +643,9 @@ mod tests {
             resolution_criteria: String::new(),
             url: "https://example.com".to_string(),
             cross_refs: CrossReferences::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         }
     }
 
@@ -404,6 +743,44 @@ mod tests {
         assert_eq!(e.cache_hit_rate(), 0.0);
     }
 
+    #[test]
+    fn test_marginal_cost_zero_when_cached() {
+        let mut enricher = Enricher::new(None, None, None).unwrap();
+        let m = make_market("1", "Will Sydney get rain?", MarketCategory::Weather);
+        let (_, warm) = enricher.marginal_cost(&m);
+        assert!(!warm);
+
+        let topic = Enricher::cache_key(&m);
+        enricher.cache.insert(format!("weather:{topic}"), DataContext::empty(MarketCategory::Weather), Duration::minutes(30));
+        let supplementary = Provider::for_category(MarketCategory::Weather, enricher.config.supplementary_news);
+        if supplementary.len() > 1 {
+            enricher.cache.insert(format!("news:{topic}"), DataContext::empty(MarketCategory::Weather), Duration::minutes(30));
+        }
+        assert_eq!(enricher.marginal_cost(&m), (Decimal::ZERO, true));
+    }
+
+    // -- Manifold flow tests ----------------------------------------------
+
+    #[test]
+    fn test_flow_targets_top_n_manifold_only() {
+        let mut markets = vec![
+            make_market("m1", "A?", MarketCategory::Other),
+            make_market("b1", "B?", MarketCategory::Other),
+            make_market("m2", "C?", MarketCategory::Other),
+            make_market("m1", "A?", MarketCategory::Other),
+            make_market("m3", "D?", MarketCategory::Other),
+        ];
+        markets[1].platform = "betfair".to_string();
+        let results: Vec<(Market, DataContext)> = markets
+            .into_iter()
+            .map(|m| (m, DataContext::empty(MarketCategory::Other)))
+            .collect();
+
+        // Duplicate m1 rides along with its first occurrence; m3 is past the cap.
+        assert_eq!(Enricher::flow_targets(&results, 2), vec![0, 2, 3]);
+        assert!(Enricher::flow_targets(&results, 0).is_empty());
+    }
+
     #[test]
     fn test_cache_hit_rate_calculation() {
         let mut enricher = Enricher::new(None, None, None).unwrap();
@@ -412,4 +789,291 @@ mod tests {
         let rate = enricher.cache_hit_rate();
         assert!((rate - 0.7).abs() < 1e-10);
     }
+
+    // -- Composite contexts ----------------------------------------------
+
+    fn part(source: &str, summary: &str, cost: Decimal, age_mins: i64) -> Result<DataContext> {
+        Ok(DataContext {
+            summary: summary.to_string(),
+            source: source.to_string(),
+            cost,
+            freshness: Utc::now() - Duration::minutes(age_mins),
+            raw_data: serde_json::json!({ "from": source }),
+            ..DataContext::empty(MarketCategory::Economics)
+        })
+    }
+
+    #[test]
+    fn test_compose_one_of_three_failed() {
+        let ctx = compose(
+            MarketCategory::Economics,
+            vec![
+                ("news", Err(anyhow::anyhow!("timed out"))),
+                ("economics", part("FRED", "CPI 3.1% y/y", d(0.01), 10)),
+                ("weather", part("open-meteo", "Sydney 25°C", d(0.0), 30)),
+            ],
+        );
+        assert_eq!(ctx.sections.len(), 3);
+        assert_eq!(ctx.source, "FRED+open-meteo");
+        assert_eq!(ctx.cost, d(0.01));
+        assert!(ctx.is_stale(Duration::minutes(29)), "freshness is the oldest section's");
+        assert_eq!(ctx.raw_data["economics"]["from"], "FRED");
+        assert!(ctx.raw_data.get("news").is_none());
+
+        // Available sections in source order, then the failed source.
+        let rendered = ctx.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[0].starts_with("[economics @ "));
+        assert_eq!(lines[1], "CPI 3.1% y/y");
+        assert!(lines[2].starts_with("[weather @ "));
+        assert_eq!(lines[3], "Sydney 25°C");
+        assert_eq!(lines[4], "news: unavailable this cycle");
+        assert_eq!(ctx.summary, rendered);
+    }
+
+    #[test]
+    fn test_compose_all_failed() {
+        let ctx = compose(
+            MarketCategory::Sports,
+            vec![
+                ("sports", Err(anyhow::anyhow!("503"))),
+                ("news", Err(anyhow::anyhow!("timed out"))),
+            ],
+        );
+        assert_eq!(ctx.source, "none");
+        assert_eq!(ctx.cost, Decimal::ZERO);
+        assert!(ctx.sections.iter().all(|s| !s.available));
+        assert_eq!(
+            ctx.render(),
+            "No enrichment data available.\nnews: unavailable this cycle\nsports: unavailable this cycle"
+        );
+    }
+
+    // -- Concurrent batches ------------------------------------------------
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// In-flight counter that remembers its peak.
+    #[derive(Default)]
+    struct Probe {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl Probe {
+        fn enter(&self) {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+        }
+
+        fn exit(&self) {
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        fn peak(&self) -> usize {
+            self.peak.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Provider that sleeps `delay_ms`, records concurrency on its own and
+    /// the shared probe, and logs its label on completion.
+    struct MockProvider {
+        label: &'static str,
+        delay_ms: u64,
+        fail_id: Option<&'static str>,
+        own: Arc<Probe>,
+        all: Arc<Probe>,
+        done: Arc<Mutex<Vec<&'static str>>>,
+        calls: Arc<AtomicUsize>,
+        cost: Decimal,
+    }
+
+    #[async_trait::async_trait]
+    impl DataProvider for MockProvider {
+        fn category(&self) -> MarketCategory {
+            MarketCategory::Other
+        }
+
+        async fn fetch_context(&self, market: &Market) -> Result<DataContext> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.own.enter();
+            self.all.enter();
+            tokio::time::sleep(std::time::Duration::from_millis(self.delay_ms)).await;
+            self.all.exit();
+            self.own.exit();
+            self.done.lock().unwrap().push(self.label);
+            if self.fail_id == Some(market.id.as_str()) {
+                anyhow::bail!("{} unavailable for {}", self.label, market.id);
+            }
+            Ok(DataContext {
+                summary: format!("{} for {}", self.label, market.id),
+                source: self.label.to_string(),
+                cost: self.cost,
+                ..DataContext::empty(market.category)
+            })
+        }
+
+        fn cost_per_call(&self) -> Decimal {
+            self.cost
+        }
+    }
+
+    struct Harness {
+        enricher: Enricher,
+        weather: Arc<Probe>,
+        news: Arc<Probe>,
+        all: Arc<Probe>,
+        done: Arc<Mutex<Vec<&'static str>>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    /// Enricher over mock weather and news providers (no supplementary news).
+    fn harness(weather_ms: u64, news_ms: u64, fail_id: Option<&'static str>, max_concurrent: usize) -> Harness {
+        let (weather, news, all) = (Arc::new(Probe::default()), Arc::new(Probe::default()), Arc::new(Probe::default()));
+        let done = Arc::new(Mutex::new(Vec::new()));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mock = |label, delay_ms, own: &Arc<Probe>| -> Box<dyn DataProvider> {
+            Box::new(MockProvider {
+                label,
+                delay_ms,
+                fail_id,
+                own: own.clone(),
+                all: all.clone(),
+                done: done.clone(),
+                calls: calls.clone(),
+                cost: Decimal::ZERO,
+            })
+        };
+        let config = EnricherConfig { supplementary_news: false, ..EnricherConfig::default() };
+        let enricher = Enricher::from_providers(
+            config,
+            mock("weather", weather_ms, &weather),
+            mock("sports", 0, &Arc::new(Probe::default())),
+            mock("economics", 0, &Arc::new(Probe::default())),
+            mock("news", news_ms, &news),
+        )
+        .with_max_concurrent_requests(max_concurrent);
+        Harness { enricher, weather, news, all, done, calls }
+    }
+
+    fn numbered(n: usize, category: MarketCategory) -> Market {
+        make_market(&format!("m{n}"), &format!("Will topic{n} happen?"), category)
+    }
+
+    #[tokio::test]
+    async fn test_enrich_batch_bounded_and_ordered() {
+        let mut h = harness(20, 5, None, 4);
+        let markets: Vec<Market> = (0..12)
+            .map(|n| numbered(n, if n % 2 == 0 { MarketCategory::Weather } else { MarketCategory::Politics }))
+            .collect();
+
+        let results = h.enricher.enrich_batch(&markets).await.unwrap();
+
+        assert_eq!(results.len(), 12);
+        for (n, (market, ctx)) in results.iter().enumerate() {
+            assert_eq!(market.id, format!("m{n}"));
+            assert!(ctx.summary.contains(&format!("for m{n}")), "{}", ctx.summary);
+        }
+        assert!(h.all.peak() <= 4 && h.all.peak() > 1, "peak {}", h.all.peak());
+        assert!(h.weather.peak() <= 2 && h.news.peak() <= 2);
+        assert_eq!(h.enricher.total_calls(), 12);
+    }
+
+    #[tokio::test]
+    async fn test_slow_provider_does_not_starve_others() {
+        let mut h = harness(200, 1, None, 4);
+        // Weather first: unbounded per provider, it would take every slot.
+        let markets: Vec<Market> = (0..4)
+            .map(|n| numbered(n, MarketCategory::Weather))
+            .chain((4..8).map(|n| numbered(n, MarketCategory::Politics)))
+            .collect();
+
+        h.enricher.enrich_batch(&markets).await.unwrap();
+
+        let done = h.done.lock().unwrap();
+        assert_eq!(done[..4], ["news"; 4], "completion order {done:?}");
+        assert_eq!(h.weather.peak(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_market_degrades_to_empty_context() {
+        let mut h = harness(0, 1, Some("m1"), 8);
+        let markets: Vec<Market> = (0..3).map(|n| numbered(n, MarketCategory::Politics)).collect();
+
+        let results = h.enricher.enrich_batch(&markets).await.unwrap();
+
+        assert_eq!(results.len(), 3);
+        let failed = &results[1].1;
+        assert_eq!(failed.source, "none");
+        assert!(failed.sections.iter().all(|s| !s.available));
+        assert!(results[0].1.sections[0].available && results[2].1.sections[0].available);
+        // Failures are not cached.
+        assert_eq!(h.enricher.total_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shared_topic_fetched_once_per_batch() {
+        let mut h = harness(0, 1, None, 8);
+        let markets = vec![
+            make_market("a", "Will the senate pass the bill?", MarketCategory::Politics),
+            make_market("b", "Will the senate pass the bill?", MarketCategory::Politics),
+        ];
+
+        let results = h.enricher.enrich_batch(&markets).await.unwrap();
+
+        assert_eq!(h.calls.load(Ordering::SeqCst), 1);
+        assert_eq!((h.enricher.total_calls(), h.enricher.cache_hits()), (1, 1));
+        assert_eq!(results[0].1.summary, results[1].1.summary);
+    }
+
+    #[tokio::test]
+    async fn test_daily_budget_skips_paid_fetches() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mock = |label, cost| -> Box<dyn DataProvider> {
+            Box::new(MockProvider {
+                label,
+                delay_ms: 0,
+                fail_id: None,
+                own: Arc::default(),
+                all: Arc::default(),
+                done: Arc::default(),
+                calls: calls.clone(),
+                cost,
+            })
+        };
+        let costs = Arc::new(CostTracker::new(&crate::config::CostsConfig {
+            max_llm_cost_per_cycle: Decimal::ZERO,
+            max_total_cost_per_day: dec!(0.25),
+        }));
+        let config = EnricherConfig { supplementary_news: false, ..EnricherConfig::default() };
+        let mut enricher = Enricher::from_providers(
+            config,
+            mock("weather", Decimal::ZERO),
+            mock("sports", Decimal::ZERO),
+            mock("economics", Decimal::ZERO),
+            mock("news", dec!(0.10)),
+        )
+        .with_max_concurrent_requests(1)
+        .with_cost_tracker(costs.clone());
+        let markets: Vec<Market> = (0..4).map(|n| numbered(n, MarketCategory::Politics)).collect();
+
+        let results = enricher.enrich_batch(&markets).await.unwrap();
+
+        // Two paid fetches fit in 0.25; the rest go without
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let available: Vec<bool> = results.iter().map(|(_, ctx)| ctx.sections[0].available).collect();
+        assert_eq!(available, [true, true, false, false]);
+        assert_eq!(costs.spent_today_at(Utc::now()), dec!(0.20));
+    }
+
+    #[test]
+    fn test_provider_routing() {
+        assert_eq!(
+            Provider::for_category(MarketCategory::Economics, true),
+            vec![Provider::Economics, Provider::News]
+        );
+        assert_eq!(Provider::for_category(MarketCategory::Economics, false), vec![Provider::Economics]);
+        assert_eq!(Provider::for_category(MarketCategory::Politics, true), vec![Provider::News]);
+    }
 }
@@ -1,35 +1,53 @@
 //! Trade executor.
 //!
 //! Places bets via platform clients and tracks execution results.
-//! IB ForecastEx executor is deferred (Phase 2A). Currently supports
-//! Manifold paper-trading for strategy validation.
+//! Manifold play-money and ForecastEx paper-account bets are placed in
+//! every mode for strategy validation; real-money venues only when live.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal_macros::dec;
-use tracing::{debug, info, warn};
+use serde::Serialize;
+use tracing::{debug, info, info_span, warn, Instrument};
 
+use crate::config::ExecutionConfig;
 use crate::platforms::betfair::BetfairClient;
 use crate::platforms::manifold::ManifoldClient;
+use crate::platforms::preflight::PreflightReport;
 use crate::platforms::PredictionPlatform;
+use crate::prometheus::Registry;
+use crate::strategy::constraints::BetConstraints;
+use crate::strategy::impact::PriceImpact;
 use crate::strategy::kelly::SizedBet;
-use crate::types::{Side, TradeReceipt};
+use crate::types::{OracleError, Position, Side, TradeReceipt};
 
 // ---------------------------------------------------------------------------
 // Execution result
 // ---------------------------------------------------------------------------
 
-/// Result of executing a batch of bets.
+/// Result of executing a batch of bets. Every placed bet ends in exactly
+/// one of `executed`, `failed` (permanently) or `unconfirmed`.
 #[derive(Debug, Clone)]
 pub struct ExecutionReport {
     pub executed: Vec<ExecutedTrade>,
     pub failed: Vec<FailedTrade>,
+    /// Orders that may have reached the venue but were never confirmed.
+    pub unconfirmed: Vec<UnconfirmedTrade>,
     pub total_committed: Decimal,
     pub total_commission: Decimal,
+    /// Fills ignored because their order id was already booked.
+    pub duplicate_fills: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ExecutedTrade {
     pub market_id: String,
     pub platform: String,
@@ -40,13 +58,290 @@ pub struct ExecutedTrade {
     pub edge_pct: f64,
     /// Estimate confidence at time of bet (for dashboard display).
     pub confidence: f64,
+    /// Wall-clock time spent placing the bet (0 for dry runs).
+    pub latency_ms: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FailedTrade {
     pub market_id: String,
     pub platform: String,
     pub reason: String,
+    pub code: ExecutionFailure,
+    /// Placement attempts made, retries included.
+    pub attempts: u32,
+    /// Wall-clock time spent before the bet was given up.
+    pub latency_ms: u64,
+    /// Venue price seen when the bet was skipped for moving past the
+    /// slippage tolerance.
+    pub observed_price: Option<Decimal>,
+}
+
+/// A bet whose order may have been submitted but never confirmed: a
+/// timeout or server error after which the venue couldn't rule out a
+/// fill. Reconciled against venue positions next cycle
+/// ([`crate::engine::accountant::Accountant::settle_unconfirmed`]).
+#[derive(Debug, Clone)]
+pub struct UnconfirmedTrade {
+    pub market_id: String,
+    pub platform: String,
+    /// Provisional receipt at the scanned price, with the order's
+    /// idempotency key as `order_id`. Booked once the venue confirms it.
+    pub receipt: TradeReceipt,
+    pub reason: String,
+    pub attempts: u32,
+    pub latency_ms: u64,
+}
+
+/// Classification of an execution failure; serialized as its
+/// [`Self::label`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionFailure {
+    /// The market closed or was suspended between scan and execution.
+    /// Expected churn, not an error — never retried.
+    MarketClosed,
+    /// Rate limited (HTTP 429) or the connection failed: the order never
+    /// reached the venue. Retried.
+    RateLimited,
+    /// Venue server error (HTTP 5xx). The order may have been processed.
+    ServerError,
+    /// The placement exceeded its latency budget and was abandoned. The
+    /// order may still have reached the venue.
+    Timeout,
+    /// The venue had already seen the order's idempotency key: an earlier
+    /// attempt reached it.
+    Duplicate,
+    /// Even the platform's minimum stake would move the price more than
+    /// `risk.max_price_impact_pct`. Never placed.
+    PriceImpact,
+    /// A market-order venue's price had moved past
+    /// `execution.slippage_tolerance` since the scan. Never placed.
+    PriceMoved,
+    /// Any other placement failure. Permanent — never retried.
+    Other,
+}
+
+/// Error recorded when a placement exceeds its latency budget.
+#[derive(Debug, thiserror::Error)]
+#[error("{platform} bet placement timed out after {budget:?}")]
+pub struct ExecutionTimeout {
+    pub platform: String,
+    pub budget: Duration,
+}
+
+/// Error recorded when a bet can't be scaled down to within the price
+/// impact limit.
+#[derive(Debug, thiserror::Error)]
+#[error("{platform} bet of {amount} would move the price by {impact:.4}, over the {limit} limit, and can't be scaled down")]
+pub struct PriceImpactExceeded {
+    pub platform: String,
+    pub amount: Decimal,
+    pub impact: Decimal,
+    pub limit: Decimal,
+}
+
+/// Error recorded when a market-order venue's price has moved past the
+/// slippage tolerance since the bet's edge was computed.
+#[derive(Debug, thiserror::Error)]
+#[error("{platform} price moved from {expected} to {observed}, past the {tolerance} slippage tolerance")]
+pub struct PriceMoved {
+    pub platform: String,
+    pub expected: Decimal,
+    pub observed: Decimal,
+    pub tolerance: Decimal,
+}
+
+impl ExecutionFailure {
+    /// Classify a placement error returned by a platform client. HTTP
+    /// statuses are read from a `reqwest` error in the chain, or from the
+    /// status line clients format into their messages.
+    pub fn classify(err: &anyhow::Error) -> Self {
+        if err.downcast_ref::<ExecutionTimeout>().is_some() {
+            return Self::Timeout;
+        }
+        if err.downcast_ref::<PriceImpactExceeded>().is_some() {
+            return Self::PriceImpact;
+        }
+        if err.downcast_ref::<PriceMoved>().is_some() {
+            return Self::PriceMoved;
+        }
+        match err.downcast_ref::<OracleError>() {
+            Some(OracleError::MarketClosed { .. }) => return Self::MarketClosed,
+            Some(OracleError::DuplicateOrder { .. }) => return Self::Duplicate,
+            _ => {}
+        }
+        let status = err.chain().find_map(|cause| {
+            let e = cause.downcast_ref::<reqwest::Error>()?;
+            if e.is_timeout() {
+                Some(Self::Timeout)
+            } else if e.is_connect() {
+                Some(Self::RateLimited)
+            } else {
+                e.status().map(Self::from_status)
+            }
+        });
+        status.unwrap_or_else(|| Self::from_message(&format!("{err:#}")))
+    }
+
+    fn from_status(status: reqwest::StatusCode) -> Self {
+        match status.as_u16() {
+            429 => Self::RateLimited,
+            500..=599 => Self::ServerError,
+            _ => Self::Other,
+        }
+    }
+
+    /// Transient status line ("503 Service Unavailable") in an error message.
+    fn from_message(message: &str) -> Self {
+        std::iter::once(429)
+            .chain(500..600)
+            .filter_map(|code| reqwest::StatusCode::from_u16(code).ok())
+            .find(|status| message.contains(&status.to_string()))
+            .map_or(Self::Other, Self::from_status)
+    }
+
+    /// Whether retrying the same order could succeed.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimited | Self::ServerError | Self::Timeout)
+    }
+
+    /// Whether the failed order may nonetheless have reached the venue.
+    pub fn may_have_submitted(self) -> bool {
+        matches!(self, Self::ServerError | Self::Timeout | Self::Duplicate)
+    }
+
+    /// Metric label.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MarketClosed => "market_closed",
+            Self::RateLimited => "rate_limited",
+            Self::ServerError => "server_error",
+            Self::Timeout => "timeout",
+            Self::Duplicate => "duplicate",
+            Self::PriceImpact => "price_impact",
+            Self::PriceMoved => "price_moved",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl ExecutionReport {
+    /// Market IDs whose orders failed because the market had closed.
+    pub fn closed_markets(&self) -> Vec<String> {
+        self.failed
+            .iter()
+            .filter(|f| f.code == ExecutionFailure::MarketClosed)
+            .map(|f| f.market_id.clone())
+            .collect()
+    }
+
+    /// Failures excluding closed markets.
+    pub fn errors(&self) -> impl Iterator<Item = &FailedTrade> {
+        self.failed.iter().filter(|f| f.code != ExecutionFailure::MarketClosed)
+    }
+
+    /// Number of failures excluding closed markets.
+    pub fn error_count(&self) -> usize {
+        self.errors().count()
+    }
+
+    fn record_fill(&mut self, bet: &SizedBet, platform: &str, mut receipt: TradeReceipt, latency_ms: u64) {
+        stamp(&mut receipt, bet);
+        self.executed.push(ExecutedTrade {
+            market_id: bet.edge.market.id.clone(),
+            platform: platform.to_string(),
+            side: bet.edge.side.clone(),
+            amount: bet.bet_amount,
+            receipt,
+            edge_pct: (bet.edge.edge * dec!(100)).to_f64().unwrap_or(0.0),
+            confidence: bet.edge.estimate.confidence.to_f64().unwrap_or(0.0),
+            latency_ms,
+        });
+        self.total_committed += bet.bet_amount;
+    }
+
+    fn record_failure(&mut self, market_id: &str, platform: &str, err: &anyhow::Error, attempts: u32, latency_ms: u64) {
+        let code = ExecutionFailure::classify(err);
+        if code == ExecutionFailure::MarketClosed {
+            info!(
+                market_id = %market_id,
+                platform = %platform,
+                "Market closed before execution — skipping"
+            );
+        } else if code == ExecutionFailure::PriceMoved {
+            info!(
+                market_id = %market_id,
+                platform = %platform,
+                reason = %err,
+                "Price moved since the scan — skipping"
+            );
+        } else {
+            warn!(
+                market_id = %market_id,
+                platform = %platform,
+                error = %err,
+                "Execution failed"
+            );
+        }
+        self.failed.push(FailedTrade {
+            market_id: market_id.to_string(),
+            platform: platform.to_string(),
+            reason: err.to_string(),
+            code,
+            attempts,
+            latency_ms,
+            observed_price: err.downcast_ref::<PriceMoved>().map(|moved| moved.observed),
+        });
+    }
+
+    fn record_unconfirmed(
+        &mut self,
+        bet: &SizedBet,
+        platform: &str,
+        key: String,
+        err: &anyhow::Error,
+        attempts: u32,
+        latency_ms: u64,
+    ) {
+        let market = &bet.edge.market;
+        warn!(
+            market_id = %market.id,
+            platform = %platform,
+            idempotency_key = %key,
+            error = %err,
+            "Order unconfirmed — may have reached the venue; reconciling next cycle"
+        );
+        let mut receipt = TradeReceipt {
+            order_id: key,
+            platform: platform.to_string(),
+            side: bet.edge.side,
+            fill_price: bet.entry_price(),
+            ..TradeReceipt::dry_run(&market.id, bet.bet_amount, if platform == "manifold" { "Mana" } else { "AUD" })
+        };
+        stamp(&mut receipt, bet);
+        self.unconfirmed.push(UnconfirmedTrade {
+            market_id: market.id.clone(),
+            platform: platform.to_string(),
+            receipt,
+            reason: format!("{err:#}"),
+            attempts,
+            latency_ms,
+        });
+    }
+}
+
+/// Stamp the bet's market timing, approval context and scanned price on its
+/// receipt, so the position monitor can unwind ahead of the deadline and
+/// the fill's slippage can be measured.
+fn stamp(receipt: &mut TradeReceipt, bet: &SizedBet) {
+    receipt.expected_price = Some(bet.entry_price());
+    receipt.deadline = Some(bet.edge.market.deadline);
+    receipt.category = Some(bet.edge.market.category);
+    receipt.edge = Some(bet.edge.edge);
+    receipt.tags = bet.edge.market.tags.clone();
+    receipt.correlation_key = bet.correlation_key.clone();
+    receipt.risk_context = bet.risk_context.clone();
 }
 
 // ---------------------------------------------------------------------------
@@ -54,19 +349,123 @@ pub struct FailedTrade {
 // ---------------------------------------------------------------------------
 
 pub struct Executor {
-    manifold: Option<ManifoldClient>,
-    betfair: Option<BetfairClient>,
-    // forecastex: Option<ForecastExClient>,  // Phase 2A
+    manifold: Option<Arc<ManifoldClient>>,
+    /// Venues that accept orders, keyed by platform name.
+    venues: HashMap<String, Arc<dyn PredictionPlatform>>,
+    limits: ExecutionConfig,
     dry_run: bool,
+    /// Order ids already booked. A venue returning the same order twice
+    /// (retried request, replayed response) must not be committed twice.
+    journal: Mutex<HashSet<String>>,
+    /// Warm standby: no orders are placed and no resolutions are polled
+    /// (see [`crate::engine::standby`]).
+    standby: AtomicBool,
+    /// Venues that failed their last preflight, with the failed checks.
+    /// Bets routed to them are not placed: the venue is scan-only.
+    blocked: Mutex<BTreeMap<String, String>>,
+    /// Prometheus registry counting fills and failures per venue.
+    metrics: Option<Arc<Registry>>,
+    /// Price impact allowed per bet; unchecked when `None`.
+    impact: Option<ImpactLimit>,
+}
+
+/// `risk.max_price_impact_pct` and the stakes each platform accepts, to
+/// which a bet scaled down for it is rounded.
+struct ImpactLimit {
+    max_impact: Decimal,
+    constraints: HashMap<String, BetConstraints>,
+}
+
+impl ImpactLimit {
+    /// The stake to place for `p`: the bet's own when its estimated impact
+    /// is within the limit or can't be estimated, otherwise the largest
+    /// stake within it that the platform accepts.
+    async fn stake_for(&self, p: &Placement<'_>, budget: Duration) -> Result<Decimal> {
+        let bet = p.bet;
+        let market = &bet.edge.market;
+        let liquidity = match tokio::time::timeout(budget, p.venue.check_liquidity(&market.id)).await {
+            Ok(Ok(liquidity)) => liquidity,
+            Ok(Err(e)) => {
+                warn!(error = %e, "Liquidity unavailable — placing without a price impact check");
+                return Ok(bet.bet_amount);
+            }
+            Err(_) => {
+                warn!("Liquidity check timed out — placing without a price impact check");
+                return Ok(bet.bet_amount);
+            }
+        };
+        let price = bet.entry_price();
+        let Some(impact) = PriceImpact::new(p.venue.liquidity_model(), &liquidity, bet.edge.side, price) else {
+            debug!(liquidity = %liquidity, "No liquidity to estimate price impact from");
+            return Ok(bet.bet_amount);
+        };
+        let estimated = impact.impact(bet.bet_amount);
+        if estimated <= self.max_impact {
+            return Ok(bet.bet_amount);
+        }
+        let within = impact.max_stake(self.max_impact).min(bet.bet_amount);
+        match BetConstraints::for_platform(&self.constraints, &market.platform).normalise(within) {
+            Ok(stake) => {
+                info!(
+                    original = %bet.bet_amount,
+                    scaled = %stake,
+                    impact = %estimated.round_dp(4),
+                    limit = %self.max_impact,
+                    "Bet scaled down to the price impact limit"
+                );
+                Ok(stake)
+            }
+            Err(_) => Err(PriceImpactExceeded {
+                platform: p.venue.name().to_string(),
+                amount: bet.bet_amount,
+                impact: estimated.round_dp(4),
+                limit: self.max_impact,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Outcome of one preflight pass over every venue.
+#[derive(Debug, Clone, Default)]
+pub struct PreflightRun {
+    /// One report per venue, sorted by platform.
+    pub reports: Vec<PreflightReport>,
+    /// Platforms whose go/no-go flipped in this pass.
+    pub changed: Vec<String>,
+}
+
+/// A bet routed to a venue for placement.
+struct Placement<'a> {
+    index: usize,
+    bet: &'a SizedBet,
+    venue: Arc<dyn PredictionPlatform>,
+}
+
+/// How a bet's placement ended, over all its attempts.
+enum Placed {
+    Filled(Box<TradeReceipt>),
+    Failed(anyhow::Error),
+    /// The order may have reached the venue but was never confirmed.
+    Unconfirmed(anyhow::Error),
+}
+
+/// Outcome of placing one bet.
+struct Attempt {
+    index: usize,
+    result: Placed,
+    /// Idempotency key every attempt was submitted under.
+    key: String,
+    /// Stake placed: the bet's own, or scaled down for price impact.
+    amount: Decimal,
+    attempts: u32,
+    latency_ms: u64,
+    real_money: bool,
 }
 
 impl Executor {
     pub fn new(manifold: Option<ManifoldClient>, dry_run: bool) -> Self {
-        Self {
-            manifold,
-            betfair: None,
-            dry_run,
-        }
+        Self::with_betfair(manifold, None, dry_run)
     }
 
     pub fn with_betfair(
@@ -74,11 +473,160 @@ impl Executor {
         betfair: Option<BetfairClient>,
         dry_run: bool,
     ) -> Self {
-        Self {
-            manifold,
-            betfair,
+        let manifold = manifold.map(Arc::new);
+        let mut executor = Self {
+            manifold: manifold.clone(),
+            venues: HashMap::new(),
+            limits: ExecutionConfig::default(),
             dry_run,
+            journal: Mutex::new(HashSet::new()),
+            standby: AtomicBool::new(false),
+            blocked: Mutex::new(BTreeMap::new()),
+            metrics: None,
+            impact: None,
+        };
+        if let Some(m) = manifold {
+            executor = executor.with_venue(m);
+        }
+        if let Some(b) = betfair {
+            executor = executor.with_venue(Arc::new(b));
         }
+        executor
+    }
+
+    /// Register an order-accepting venue under its platform name.
+    pub fn with_venue(mut self, venue: Arc<dyn PredictionPlatform>) -> Self {
+        self.venues.insert(venue.name().to_string(), venue);
+        self
+    }
+
+    /// Replace every registered venue with `wrap(venue)` (e.g. fault injection).
+    pub fn map_venues(
+        mut self,
+        wrap: impl Fn(Arc<dyn PredictionPlatform>) -> Arc<dyn PredictionPlatform>,
+    ) -> Self {
+        self.venues = self
+            .venues
+            .into_iter()
+            .map(|(name, venue)| (name, wrap(venue)))
+            .collect();
+        self
+    }
+
+    /// Platforms with a registered order-accepting venue.
+    pub fn venue_names(&self) -> Vec<String> {
+        self.venues.keys().cloned().collect()
+    }
+
+    /// The order-accepting venue registered for `platform`.
+    pub fn venue(&self, platform: &str) -> Option<Arc<dyn PredictionPlatform>> {
+        self.venues.get(platform).cloned()
+    }
+
+    /// Every registered order-accepting venue.
+    pub fn venues(&self) -> Vec<Arc<dyn PredictionPlatform>> {
+        self.venues.values().cloned().collect()
+    }
+
+    /// Enter or leave warm standby.
+    pub fn set_standby(&self, standby: bool) {
+        self.standby.store(standby, Ordering::SeqCst);
+    }
+
+    pub fn is_standby(&self) -> bool {
+        self.standby.load(Ordering::SeqCst)
+    }
+
+    /// Run every venue's preflight and gate execution on the results:
+    /// a failing venue becomes scan-only until a later pass succeeds.
+    pub async fn preflight(&self) -> PreflightRun {
+        let venues = self.venues();
+        let checks = venues.iter().map(|v| {
+            let limits = self.limits.preflight_limits(v.name());
+            async move { v.preflight(&limits).await }
+        });
+        let mut reports = join_all(checks).await;
+        reports.sort_by(|a, b| a.platform.cmp(&b.platform));
+
+        let mut blocked = self.blocked.lock().unwrap();
+        let mut changed = Vec::new();
+        for report in &reports {
+            if report.go() {
+                if blocked.remove(&report.platform).is_some() {
+                    info!(platform = %report.platform, "Preflight passed — execution re-enabled");
+                    changed.push(report.platform.clone());
+                }
+                continue;
+            }
+            let summary = report.summary();
+            warn!(platform = %report.platform, failed = %summary, "[SCAN ONLY] Preflight failed — venue will not be traded");
+            if blocked.insert(report.platform.clone(), summary).is_none() {
+                changed.push(report.platform.clone());
+            }
+        }
+        PreflightRun { reports, changed }
+    }
+
+    /// Scan-only venues and why.
+    pub fn blocked_venues(&self) -> BTreeMap<String, String> {
+        self.blocked.lock().unwrap().clone()
+    }
+
+    /// Count each batch's fills and failures in `registry`.
+    pub fn with_metrics(mut self, registry: Arc<Registry>) -> Self {
+        self.metrics = Some(registry);
+        self
+    }
+
+    /// Apply concurrency and latency limits.
+    pub fn with_limits(mut self, limits: ExecutionConfig) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Check each bet's price impact before placing it
+    /// (`risk.max_price_impact_pct`), scaling it down to a stake in
+    /// `constraints` that stays within `max_impact` or skipping it.
+    pub fn with_max_price_impact(mut self, max_impact: Option<Decimal>, constraints: HashMap<String, BetConstraints>) -> Self {
+        self.impact = max_impact.map(|max_impact| ImpactLimit { max_impact, constraints });
+        self
+    }
+
+    /// Funds available on every registered venue, keyed by platform. A
+    /// venue whose balance can't be read is left out, so its last known
+    /// balance stands.
+    pub async fn fetch_balances(&self) -> BTreeMap<String, Decimal> {
+        let fetches = self
+            .venues
+            .iter()
+            .map(|(name, venue)| async move { (name.clone(), venue.get_balance().await) });
+        let mut balances = BTreeMap::new();
+        for (platform, fetched) in join_all(fetches).await {
+            match fetched {
+                Ok(balance) => {
+                    balances.insert(platform, balance);
+                }
+                Err(e) => debug!(%platform, error = %e, "Venue balance unavailable"),
+            }
+        }
+        balances
+    }
+
+    /// Positions held on every registered venue. A venue whose positions
+    /// can't be read is left out.
+    pub async fn fetch_positions(&self) -> Vec<Position> {
+        let fetches = self
+            .venues
+            .iter()
+            .map(|(name, venue)| async move { (name.clone(), venue.get_positions().await) });
+        let mut positions = Vec::new();
+        for (platform, fetched) in join_all(fetches).await {
+            match fetched {
+                Ok(held) => positions.extend(held),
+                Err(e) => debug!(%platform, error = %e, "Venue positions unavailable"),
+            }
+        }
+        positions
     }
 
     /// Fetch the live Mana account snapshot from the Manifold API (best-effort).
@@ -90,76 +638,61 @@ impl Executor {
         self.manifold.as_ref()?.get_user_info().await
     }
 
-    /// Check which open Manifold bets have resolved and return outcomes.
-    ///
-    /// Returns an empty vec when no Manifold client is configured or
-    /// `open_bets` is empty.
-    pub async fn check_manifold_resolutions(
+    /// Recent bets on the Manifold account `user_id` (newest first).
+    pub async fn fetch_manifold_user_bets(
         &self,
-        open_bets: &[crate::types::TradeReceipt],
-    ) -> Vec<crate::platforms::manifold::ManifoldResolution> {
-        if open_bets.is_empty() {
-            return Vec::new();
-        }
-        match &self.manifold {
-            Some(client) => client.check_resolutions(open_bets).await,
-            None => Vec::new(),
-        }
+        user_id: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::platforms::manifold::ManifoldFeedBet>> {
+        let client = self.manifold.as_ref().context("No Manifold client configured")?;
+        client.fetch_user_bets(user_id, limit).await
     }
 
     /// Execute a batch of sized bets.
     ///
     /// In dry-run mode, logs but doesn't place real bets.
     /// With Manifold enabled, places play-money bets for validation.
-    /// IB ForecastEx execution comes in Phase 2A.
+    /// A ForecastEx paper account is traded the same way.
+    ///
+    /// Platforms are placed concurrently, each with at most
+    /// `max_parallel_per_platform` bets in flight (one for sequential
+    /// venues). Every attempt has a latency budget; attempts that exceed
+    /// it are abandoned as [`ExecutionFailure::Timeout`]. Transient
+    /// failures are retried (see [`Self::place`]). Results are folded into
+    /// the report on this task, in batch order.
     pub async fn execute_batch(&self, bets: &[SizedBet]) -> Result<ExecutionReport> {
         let mut report = ExecutionReport {
             executed: Vec::new(),
             failed: Vec::new(),
+            unconfirmed: Vec::new(),
             total_committed: Decimal::ZERO,
             total_commission: Decimal::ZERO,
+            duplicate_fills: 0,
         };
 
         if bets.is_empty() {
             return Ok(report);
         }
+        if self.is_standby() {
+            info!(count = bets.len(), "[STANDBY] Execution disabled — approved bets not placed");
+            return Ok(report);
+        }
 
         info!(count = bets.len(), dry_run = self.dry_run, "Executing batch");
 
-        for bet in bets {
+        let mut by_platform: BTreeMap<&str, Vec<Placement>> = BTreeMap::new();
+
+        for (index, bet) in bets.iter().enumerate() {
             let platform = bet.edge.market.platform.as_str();
-            let edge_pct = (bet.edge.edge * dec!(100)).to_f64().unwrap_or(0.0);
-            let confidence = bet.edge.estimate.confidence.to_f64().unwrap_or(0.0);
 
             // Manifold paper execution: always attempt regardless of dry_run (play money).
             if platform == "manifold" {
-                if let Some(ref manifold) = self.manifold {
-                    match self.execute_on_manifold(manifold, bet).await {
-                        Ok(receipt) => {
-                            report.executed.push(ExecutedTrade {
-                                market_id: bet.edge.market.id.clone(),
-                                platform: "manifold".to_string(),
-                                side: bet.edge.side.clone(),
-                                amount: bet.bet_amount,
-                                receipt,
-                                edge_pct,
-                                confidence,
-                            });
-                            report.total_committed += bet.bet_amount;
-                        }
-                        Err(e) => {
-                            warn!(
-                                market_id = %bet.edge.market.id,
-                                error = %e,
-                                "Manifold execution failed"
-                            );
-                            report.failed.push(FailedTrade {
-                                market_id: bet.edge.market.id.clone(),
-                                platform: "manifold".to_string(),
-                                reason: e.to_string(),
-                            });
-                        }
-                    }
+                if let Some(venue) = self.venues.get(platform) {
+                    by_platform.entry(platform).or_default().push(Placement {
+                        index,
+                        bet,
+                        venue: Arc::clone(venue),
+                    });
                 } else {
                     // No Manifold client available — log as dry-run
                     info!(
@@ -169,20 +702,24 @@ impl Executor {
                         edge = format!("{:.1}%", bet.edge.edge * dec!(100)),
                         "[DRY RUN] No Manifold client — would place paper bet"
                     );
-                    report.executed.push(ExecutedTrade {
-                        market_id: bet.edge.market.id.clone(),
-                        platform: "dry-run".to_string(),
-                        side: bet.edge.side.clone(),
-                        amount: bet.bet_amount,
-                        receipt: TradeReceipt::dry_run(&bet.edge.market.id, bet.bet_amount, "Mana"),
-                        edge_pct,
-                        confidence,
-                    });
-                    report.total_committed += bet.bet_amount;
+                    let receipt = TradeReceipt::dry_run(&bet.edge.market.id, bet.bet_amount, "Mana");
+                    report.record_fill(bet, "dry-run", receipt, 0);
                 }
                 continue;
             }
 
+            // ForecastEx paper account: play money too, so also regardless of dry_run.
+            if platform == "forecastex" {
+                if let Some(venue) = self.venues.get(platform).filter(|v| !v.is_real_money()) {
+                    by_platform.entry(platform).or_default().push(Placement {
+                        index,
+                        bet,
+                        venue: Arc::clone(venue),
+                    });
+                    continue;
+                }
+            }
+
             // For all other platforms, respect the dry_run flag.
             if self.dry_run {
                 info!(
@@ -193,95 +730,294 @@ impl Executor {
                     kelly = format!("{:.2}%", bet.kelly_fraction * dec!(100)),
                     "[DRY RUN] Would place bet"
                 );
-                report.executed.push(ExecutedTrade {
-                    market_id: bet.edge.market.id.clone(),
-                    platform: "dry-run".to_string(),
-                    side: bet.edge.side.clone(),
-                    amount: bet.bet_amount,
-                    receipt: TradeReceipt::dry_run(&bet.edge.market.id, bet.bet_amount, "AUD"),
-                    edge_pct,
-                    confidence,
-                });
-                report.total_committed += bet.bet_amount;
+                let receipt = TradeReceipt::dry_run(&bet.edge.market.id, bet.bet_amount, "AUD");
+                report.record_fill(bet, "dry-run", receipt, 0);
                 continue;
             }
 
-            // Betfair real-money execution
-            if let Some(ref betfair) = self.betfair {
-                if platform == "betfair" {
-                    match self.execute_on_betfair(betfair, bet).await {
-                        Ok(receipt) => {
-                            report.total_commission += receipt.fees;
-                            report.executed.push(ExecutedTrade {
-                                market_id: bet.edge.market.id.clone(),
-                                platform: "betfair".to_string(),
-                                side: bet.edge.side.clone(),
-                                amount: bet.bet_amount,
-                                receipt,
-                                edge_pct,
-                                confidence,
-                            });
-                            report.total_committed += bet.bet_amount;
-                        }
-                        Err(e) => {
-                            warn!(
-                                market_id = %bet.edge.market.id,
-                                error = %e,
-                                "Betfair execution failed"
-                            );
-                            report.failed.push(FailedTrade {
-                                market_id: bet.edge.market.id.clone(),
-                                platform: "betfair".to_string(),
-                                reason: e.to_string(),
-                            });
-                        }
+            // Real-money execution (Betfair, Kalshi, ForecastEx)
+            if let Some(venue) = self.venues.get(platform) {
+                by_platform.entry(platform).or_default().push(Placement {
+                    index,
+                    bet,
+                    venue: Arc::clone(venue),
+                });
+            }
+        }
+
+        let blocked = self.blocked_venues();
+        by_platform.retain(|platform, placements| match blocked.get(*platform) {
+            Some(failed) => {
+                info!(platform, count = placements.len(), failed = %failed, "[SCAN ONLY] Preflight failed — approved bets not placed");
+                false
+            }
+            None => true,
+        });
+
+        let lanes = by_platform.into_iter().map(|(platform, placements)| {
+            let parallelism = self.limits.parallelism_for(platform);
+            stream::iter(placements)
+                .map(|p| Self::place(p, &self.limits, self.impact.as_ref()))
+                .buffer_unordered(parallelism)
+                .collect::<Vec<_>>()
+        });
+        let mut attempts: Vec<Attempt> = join_all(lanes).await.into_iter().flatten().collect();
+        attempts.sort_by_key(|a| a.index);
+
+        for attempt in attempts {
+            let scaled;
+            let mut bet = &bets[attempt.index];
+            if attempt.amount != bet.bet_amount {
+                let mut restaked = bet.clone();
+                restaked.restake(attempt.amount);
+                scaled = restaked;
+                bet = &scaled;
+            }
+            let platform = bet.edge.market.platform.as_str();
+            match attempt.result {
+                Placed::Filled(receipt) => {
+                    let mut receipt = *receipt;
+                    retain_raw_response(&mut receipt, attempt.real_money, &self.limits);
+                    if !self.journal.lock().unwrap().insert(receipt.order_id.clone()) {
+                        warn!(
+                            market_id = %bet.edge.market.id,
+                            order_id = %receipt.order_id,
+                            "Duplicate fill ignored — order already booked"
+                        );
+                        report.duplicate_fills += 1;
+                        continue;
                     }
+                    report.total_commission += receipt.fees;
+                    report.record_fill(bet, platform, receipt, attempt.latency_ms);
+                }
+                Placed::Failed(e) => {
+                    report.record_failure(&bet.edge.market.id, platform, &e, attempt.attempts, attempt.latency_ms)
+                }
+                Placed::Unconfirmed(e) => {
+                    report.record_unconfirmed(bet, platform, attempt.key, &e, attempt.attempts, attempt.latency_ms)
                 }
             }
-
-            // TODO (Phase 2A): Execute on IB ForecastEx
         }
 
         info!(
             executed = report.executed.len(),
-            failed = report.failed.len(),
+            failed = report.error_count(),
+            closed = report.failed.len() - report.error_count(),
+            unconfirmed = report.unconfirmed.len(),
             committed = format!("${:.2}", report.total_committed),
             "Batch execution complete"
         );
+        if let Some(metrics) = &self.metrics {
+            metrics.record_execution(&report);
+        }
 
         Ok(report)
     }
 
-    async fn execute_on_betfair(
-        &self,
-        client: &BetfairClient,
-        bet: &SizedBet,
+    /// Place one bet, retrying transient failures with exponential backoff.
+    ///
+    /// Orders pay at most the bet's entry price plus the slippage
+    /// tolerance: as their limit on venues with limit orders, and on the
+    /// rest by re-reading the price first and skipping the bet when it has
+    /// moved past that (see [`Self::check_price`]).
+    ///
+    /// With an `impact` limit, the venue's liquidity is read first and the
+    /// stake scaled down to stay within it (see [`ImpactLimit::stake_for`]).
+    ///
+    /// Every attempt carries the bet's idempotency key. A failure that may
+    /// have reached the venue (a server error or timeout) is only retried
+    /// when the venue deduplicates on the key, or when its positions show
+    /// nothing held on the bet's market side; otherwise, and when the last
+    /// attempt fails that way, the bet is left unconfirmed.
+    async fn place(p: Placement<'_>, limits: &ExecutionConfig, impact: Option<&ImpactLimit>) -> Attempt {
+        let started = Instant::now();
+        let platform = p.venue.name().to_string();
+        let key = p.bet.idempotency_key.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let budget = Duration::from_secs(limits.bet_timeout_secs);
+        let max_attempts = limits.max_bet_attempts.max(1);
+        let span = info_span!(
+            "bet",
+            market_id = %p.bet.edge.market.id,
+            platform = %platform,
+            amount = %p.bet.bet_amount,
+        );
+        let mut attempts = 0;
+        let mut amount = p.bet.bet_amount;
+        let max_price = (p.bet.entry_price() + limits.slippage_tolerance).min(Decimal::ONE);
+        let result = async {
+            if !p.venue.supports_limit_orders() {
+                if let Err(e) = Self::check_price(&p, max_price, limits.slippage_tolerance, budget).await {
+                    return Placed::Failed(e);
+                }
+            }
+            if let Some(limit) = impact {
+                match limit.stake_for(&p, budget).await {
+                    Ok(stake) => amount = stake,
+                    Err(e) => return Placed::Failed(e),
+                }
+            }
+            loop {
+                attempts += 1;
+                let err = match Self::attempt(&p, amount, max_price, &key, budget).await {
+                    Ok(receipt) => return Placed::Filled(Box::new(receipt)),
+                    Err(e) => e,
+                };
+                let code = ExecutionFailure::classify(&err);
+                if !code.is_retryable() || attempts >= max_attempts {
+                    return if code.may_have_submitted() { Placed::Unconfirmed(err) } else { Placed::Failed(err) };
+                }
+                if code.may_have_submitted() && !p.venue.supports_idempotency() {
+                    match tokio::time::timeout(budget, Self::holds_position(&p)).await {
+                        Ok(Ok(false)) => {}
+                        Ok(Ok(true)) => return Placed::Unconfirmed(err.context("position held on the venue after the failure")),
+                        _ => return Placed::Unconfirmed(err.context("venue positions unavailable to rule out a fill")),
+                    }
+                }
+                let delay_ms = limits.retry_backoff_ms.saturating_mul(1 << (attempts - 1).min(16));
+                warn!(attempt = attempts, error = %err, delay_ms, "Transient placement failure — retrying");
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            }
+        }
+        .instrument(span)
+        .await;
+        Attempt {
+            index: p.index,
+            result,
+            key,
+            amount,
+            attempts,
+            latency_ms: started.elapsed().as_millis() as u64,
+            real_money: p.venue.is_real_money(),
+        }
+    }
+
+    /// One placement attempt of `amount`, paying at most `max_price`,
+    /// within the latency budget.
+    async fn attempt(
+        p: &Placement<'_>,
+        amount: Decimal,
+        max_price: Decimal,
+        key: &str,
+        budget: Duration,
     ) -> Result<TradeReceipt> {
-        if bet.edge.market.platform != "betfair" {
-            anyhow::bail!("Market {} is not a Betfair market", bet.edge.market.id);
+        let bet = p.bet;
+        let platform = p.venue.name();
+        let placed = tokio::time::timeout(
+            budget,
+            p.venue.place_limit_bet(&bet.edge.market.id, bet.edge.side, amount, max_price, key),
+        )
+        .await;
+        match placed {
+            Ok(r) => r.with_context(|| format!("{platform} bet placement failed")),
+            Err(_) => Err(ExecutionTimeout { platform: platform.to_string(), budget }.into()),
         }
+    }
 
-        client
-            .place_bet(&bet.edge.market.id, bet.edge.side.clone(), bet.bet_amount)
-            .await
-            .context("Betfair bet placement failed")
+    /// Fail with [`PriceMoved`] when the venue's current price of the bet's
+    /// side is above `max_price`. A price the venue can't quote in time is
+    /// let through, as for the liquidity check.
+    async fn check_price(p: &Placement<'_>, max_price: Decimal, tolerance: Decimal, budget: Duration) -> Result<()> {
+        let market = &p.bet.edge.market;
+        let observed = match tokio::time::timeout(budget, p.venue.current_price(&market.id, p.bet.edge.side)).await {
+            Ok(Ok(Some(price))) => price,
+            Ok(Ok(None)) => return Ok(()),
+            Ok(Err(e)) => {
+                warn!(error = %e, "Current price unavailable — placing without a slippage check");
+                return Ok(());
+            }
+            Err(_) => {
+                warn!("Current price timed out — placing without a slippage check");
+                return Ok(());
+            }
+        };
+        if observed > max_price {
+            return Err(PriceMoved {
+                platform: p.venue.name().to_string(),
+                expected: p.bet.entry_price(),
+                observed,
+                tolerance,
+            }
+            .into());
+        }
+        Ok(())
     }
 
-    async fn execute_on_manifold(
-        &self,
-        client: &ManifoldClient,
-        bet: &SizedBet,
-    ) -> Result<TradeReceipt> {
-        // Only execute on Manifold markets
-        if bet.edge.market.platform != "manifold" {
-            anyhow::bail!("Market {} is not a Manifold market", bet.edge.market.id);
+    /// Whether the venue reports a position on the bet's market side.
+    async fn holds_position(p: &Placement<'_>) -> Result<bool> {
+        let positions = p.venue.get_positions().await?;
+        Ok(positions
+            .iter()
+            .any(|pos| pos.market_id == p.bet.edge.market.id && pos.side == p.bet.edge.side))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Raw response retention
+// ---------------------------------------------------------------------------
+
+/// Object keys whose values are replaced before a response is retained.
+const SENSITIVE_KEYS: &[&str] = &[
+    "token", "secret", "password", "passwd", "api_key", "apikey",
+    "authorization", "session", "signature", "cookie",
+];
+
+const REDACTED: &str = "[redacted]";
+
+/// Apply the retention policy to a fresh receipt's raw platform response:
+/// drop it when disabled for this kind of venue, otherwise redact
+/// credential-like fields and cap its serialized size.
+fn retain_raw_response(receipt: &mut TradeReceipt, real_money: bool, limits: &ExecutionConfig) {
+    let keep = if real_money { limits.raw_response_real_money } else { limits.raw_response_paper };
+    if !keep {
+        receipt.raw_response = None;
+        return;
+    }
+    if let Some(raw) = receipt.raw_response.take() {
+        receipt.raw_response = Some(cap_size(redact(raw), limits.raw_response_max_bytes));
+    }
+}
+
+/// Recursively blank out values under sensitive keys and bearer-style
+/// strings anywhere in the document.
+fn redact(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let key = k.to_ascii_lowercase();
+                    if SENSITIVE_KEYS.iter().any(|s| key.contains(s)) {
+                        (k, Value::String(REDACTED.to_string()))
+                    } else {
+                        (k, redact(v))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        Value::String(s) if s.starts_with("Bearer ") || s.starts_with("sk-") => {
+            Value::String(REDACTED.to_string())
         }
+        other => other,
+    }
+}
 
-        client
-            .place_bet(&bet.edge.market.id, bet.edge.side.clone(), bet.bet_amount)
-            .await
-            .context("Manifold bet placement failed")
+/// Replace a response larger than `max_bytes` (serialized) with a marker
+/// holding a prefix of its text.
+fn cap_size(value: serde_json::Value, max_bytes: usize) -> serde_json::Value {
+    let text = value.to_string();
+    if text.len() <= max_bytes {
+        return value;
     }
+    let mut cut = max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    serde_json::json!({
+        "truncated": true,
+        "original_bytes": text.len(),
+        "prefix": &text[..cut],
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -302,6 +1038,14 @@ impl TradeReceipt {
             fees: Decimal::ZERO,
             timestamp: chrono::Utc::now(),
             currency: currency.to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         }
     }
 }
@@ -314,6 +1058,10 @@ impl TradeReceipt {
 mod tests {
     use super::*;
     use crate::strategy::edge::Edge;
+    use crate::platforms::preflight::{CheckKind, PreflightLimits};
+    use crate::telemetry::attr;
+    use opentelemetry::trace::SpanId;
+    use opentelemetry::Value;
     use crate::types::*;
     use chrono::{Duration, Utc};
 
@@ -334,6 +1082,9 @@ mod tests {
                     resolution_criteria: String::new(),
                     url: String::new(),
                     cross_refs: Default::default(),
+                    event_group: None,
+                    facts: None,
+                    tags: Vec::new(),
                 },
                 estimate: Estimate {
                     probability: dec!(0.65),
@@ -341,15 +1092,27 @@ mod tests {
                     reasoning: String::new(),
                     tokens_used: 100,
                     cost: dec!(0.01),
+                    critique: None,
+                    served_by: None,
+                    tier: None,
                 },
                 side: Side::Yes,
                 edge: dec!(0.15),
                 signed_edge: dec!(0.15),
+                raw_edge: dec!(0.15),
+                effective_threshold: dec!(0.06),
             },
             kelly_fraction: dec!(0.10),
             bet_fraction: dec!(0.05),
             bet_amount: amount,
             expected_value: amount * dec!(0.15),
+            net_expected_value: amount * dec!(0.15),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.15),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
         }
     }
 
@@ -382,6 +1145,611 @@ mod tests {
         assert_eq!(receipt.currency, "AUD");
     }
 
+    #[test]
+    fn test_classify_market_closed_through_context() {
+        // Shape returned by execute_on_* : platform error wrapped in context.
+        let err = anyhow::Error::from(OracleError::MarketClosed {
+            platform: "manifold".into(),
+            market_id: "m1".into(),
+            reason: r#"{"message":"Trading is closed."}"#.into(),
+        })
+        .context("Manifold bet placement failed");
+        assert_eq!(ExecutionFailure::classify(&err), ExecutionFailure::MarketClosed);
+        assert!(!ExecutionFailure::MarketClosed.is_retryable());
+
+        let err = anyhow::anyhow!("Betfair placeOrders error: INSUFFICIENT_FUNDS")
+            .context("Betfair bet placement failed");
+        assert_eq!(ExecutionFailure::classify(&err), ExecutionFailure::Other);
+        assert!(!ExecutionFailure::Other.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_transient_statuses() {
+        let classify = |msg: &str| ExecutionFailure::classify(&anyhow::anyhow!(msg.to_string()).context("bet placement failed"));
+        assert_eq!(classify("Manifold bet failed 429 Too Many Requests: slow down"), ExecutionFailure::RateLimited);
+        assert_eq!(classify("Kalshi error 503 Service Unavailable: {}"), ExecutionFailure::ServerError);
+        assert_eq!(classify("Manifold bet failed 400 Bad Request: insufficient balance"), ExecutionFailure::Other);
+        assert_eq!(classify("Bought 500 shares"), ExecutionFailure::Other);
+
+        let duplicate = anyhow::Error::from(OracleError::DuplicateOrder { platform: "kalshi".into(), key: "k".into() });
+        assert_eq!(ExecutionFailure::classify(&duplicate), ExecutionFailure::Duplicate);
+        assert!(ExecutionFailure::ServerError.is_retryable() && ExecutionFailure::ServerError.may_have_submitted());
+        assert!(ExecutionFailure::RateLimited.is_retryable() && !ExecutionFailure::RateLimited.may_have_submitted());
+        assert!(!ExecutionFailure::Duplicate.is_retryable() && ExecutionFailure::Duplicate.may_have_submitted());
+    }
+
+    #[test]
+    fn test_closed_failures_excluded_from_error_count() {
+        let mut report = ExecutionReport {
+            executed: Vec::new(),
+            failed: Vec::new(),
+            unconfirmed: Vec::new(),
+            total_committed: Decimal::ZERO,
+            total_commission: Decimal::ZERO,
+            duplicate_fills: 0,
+        };
+        let closed = anyhow::Error::from(OracleError::MarketClosed {
+            platform: "betfair".into(),
+            market_id: "1.1".into(),
+            reason: "MARKET_SUSPENDED".into(),
+        });
+        report.record_failure("1.1", "betfair", &closed, 1, 5);
+        report.record_failure("1.2", "betfair", &anyhow::anyhow!("timeout"), 1, 5);
+
+        assert_eq!(report.failed.len(), 2);
+        assert_eq!(report.error_count(), 1);
+        assert_eq!(report.closed_markets(), vec!["1.1".to_string()]);
+    }
+
+    // -- Concurrent execution --------------------------------------------
+
+    /// Venue that takes `delay` to fill every order.
+    struct MockVenue {
+        name: &'static str,
+        delay: std::time::Duration,
+        real_money: bool,
+        placed: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for MockVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+            self.placed.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            Ok(TradeReceipt {
+                order_id: format!("{}-{market_id}", self.name),
+                market_id: market_id.to_string(),
+                platform: self.name.to_string(),
+                side,
+                amount,
+                fill_price: dec!(0.5),
+                fees: Decimal::ZERO,
+                timestamp: Utc::now(),
+                currency: "AUD".to_string(),
+                deadline: None,
+                category: None,
+                edge: None,
+                raw_response: Some(serde_json::json!({
+                    "betId": format!("{}-{market_id}", self.name),
+                    "status": "SUCCESS",
+                    "sessionToken": "tok-123",
+                })),
+                risk_context: None,
+                tags: Vec::new(),
+                correlation_key: None,
+                expected_price: None,
+            })
+        }
+
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+
+        async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+            anyhow::bail!("not supported")
+        }
+
+        fn is_real_money(&self) -> bool {
+            self.real_money
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    /// Venue whose preflight fails `failing` (passes when `None`).
+    struct PreflightVenue {
+        failing: Mutex<Option<CheckKind>>,
+        placed: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for PreflightVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+            self.placed.fetch_add(1, Ordering::SeqCst);
+            let mut receipt = TradeReceipt::dry_run(market_id, amount, "AUD");
+            receipt.order_id = format!("pf-{market_id}-{}", self.placed.load(Ordering::SeqCst));
+            receipt.side = side;
+            Ok(receipt)
+        }
+
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(dec!(100))
+        }
+
+        async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+            anyhow::bail!("not supported")
+        }
+
+        fn is_real_money(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            "alpha"
+        }
+
+        async fn preflight(&self, _limits: &PreflightLimits) -> PreflightReport {
+            let mut report = PreflightReport::new("alpha");
+            report.pass(CheckKind::Auth, "ok");
+            if let Some(kind) = *self.failing.lock().unwrap() {
+                report.fail(kind, "mock failure");
+            }
+            report
+        }
+    }
+
+    fn make_bet_on(platform: &str, market_id: &str) -> SizedBet {
+        let mut bet = make_sized_bet(market_id, dec!(10));
+        bet.edge.market.platform = platform.to_string();
+        bet
+    }
+
+    fn venue(name: &'static str, delay_ms: u64) -> Arc<dyn PredictionPlatform> {
+        Arc::new(MockVenue { name, delay: std::time::Duration::from_millis(delay_ms), real_money: true, placed: Default::default() })
+    }
+
+    fn paper_venue(name: &'static str) -> Arc<dyn PredictionPlatform> {
+        Arc::new(MockVenue { name, delay: std::time::Duration::ZERO, real_money: false, placed: Default::default() })
+    }
+
+    #[tokio::test]
+    async fn test_standby_places_nothing() {
+        let alpha = Arc::new(MockVenue { name: "alpha", delay: std::time::Duration::ZERO, real_money: true, placed: Default::default() });
+        let executor = Executor::new(None, false).with_venue(alpha.clone());
+        let bets = vec![make_bet_on("alpha", "a1"), make_bet_on("alpha", "a2")];
+
+        executor.set_standby(true);
+        let report = executor.execute_batch(&bets).await.unwrap();
+        assert!(report.executed.is_empty() && report.failed.is_empty());
+        assert_eq!(alpha.placed.load(Ordering::SeqCst), 0);
+        assert!(crate::engine::resolver::Resolver::check(&executor, &[TradeReceipt {
+            platform: "alpha".to_string(),
+            ..TradeReceipt::dry_run("a0", dec!(5), "AUD")
+        }]).await.is_empty());
+
+        executor.set_standby(false);
+        let report = executor.execute_batch(&bets).await.unwrap();
+        assert_eq!(report.executed.len(), 2);
+        assert_eq!(alpha.placed.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_preflight_keeps_venue_scan_only() {
+        for kind in [CheckKind::Auth, CheckKind::Balance, CheckKind::Allowance, CheckKind::ClockSkew] {
+            let venue = Arc::new(PreflightVenue { failing: Mutex::new(Some(kind)), placed: Default::default() });
+            let executor = Executor::new(None, false).with_venue(venue.clone());
+            let bets = vec![make_bet_on("alpha", "a1")];
+
+            let run = executor.preflight().await;
+            assert_eq!(run.changed, vec!["alpha"]);
+            assert!(!run.reports[0].go());
+            assert!(executor.blocked_venues()["alpha"].contains("mock failure"));
+            let report = executor.execute_batch(&bets).await.unwrap();
+            assert!(report.executed.is_empty() && report.failed.is_empty(), "{kind:?}");
+            assert_eq!(venue.placed.load(Ordering::SeqCst), 0);
+
+            // Still failing: no change to alert on.
+            assert!(executor.preflight().await.changed.is_empty());
+
+            // Fixed: execution resumes on the next pass.
+            *venue.failing.lock().unwrap() = None;
+            let run = executor.preflight().await;
+            assert_eq!(run.changed, vec!["alpha"]);
+            assert!(executor.blocked_venues().is_empty());
+            assert_eq!(executor.execute_batch(&bets).await.unwrap().executed.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetched_balances_recorded_per_platform() {
+        let alpha = Arc::new(PreflightVenue { failing: Mutex::new(None), placed: Default::default() });
+        let executor = Executor::new(None, false).with_venue(alpha).with_venue(venue("beta", 0));
+        let balances = executor.fetch_balances().await;
+        assert_eq!(balances, BTreeMap::from([("alpha".to_string(), dec!(100)), ("beta".to_string(), Decimal::ZERO)]));
+
+        let mut state = crate::types::AgentState::new(dec!(500));
+        state.balances.insert("gamma".to_string(), dec!(42));
+        crate::engine::accountant::Accountant::record_balances(&mut state, balances);
+        assert_eq!(state.bankroll_for("alpha"), dec!(100));
+        assert_eq!(state.bankroll_for("beta"), Decimal::ZERO);
+        // Not fetched this time: last known balance stands.
+        assert_eq!(state.bankroll_for("gamma"), dec!(42));
+    }
+
+    #[tokio::test]
+    async fn test_default_preflight_checks_balance_floor() {
+        let limits = ExecutionConfig {
+            min_balance: HashMap::from([("alpha".to_string(), dec!(10))]),
+            ..ExecutionConfig::default()
+        };
+        // MockVenue reports a zero balance.
+        let executor = Executor::new(None, false).with_venue(venue("alpha", 0)).with_venue(venue("beta", 0)).with_limits(limits);
+        let run = executor.preflight().await;
+        assert_eq!(run.changed, vec!["alpha"]);
+        let failed: Vec<_> = run.reports[0].failures().map(|c| c.kind).collect();
+        assert_eq!(failed, vec![CheckKind::Balance]);
+        assert!(run.reports[1].go());
+
+        let report = executor
+            .execute_batch(&[make_bet_on("alpha", "a1"), make_bet_on("beta", "b1")])
+            .await
+            .unwrap();
+        let placed: Vec<_> = report.executed.iter().map(|t| t.market_id.as_str()).collect();
+        assert_eq!(placed, vec!["b1"]);
+    }
+
+    #[tokio::test]
+    async fn test_bet_spans_nest_under_cycle() {
+        use tracing::Instrument;
+        let (_guard, exported) = crate::telemetry::capturing(1.0);
+        let executor = Executor::new(None, false).with_venue(venue("alpha", 0));
+        let bets = vec![make_bet_on("alpha", "a1"), make_bet_on("alpha", "a2")];
+
+        async {
+            executor.execute_batch(&bets).instrument(tracing::info_span!("execute")).await.unwrap();
+        }
+        .instrument(tracing::info_span!("cycle", cycle = 7))
+        .await;
+
+        let spans = exported.get_finished_spans().unwrap();
+        let find = |name: &str| spans.iter().filter(|s| s.name == name).collect::<Vec<_>>();
+        let (cycle, execute, placed) = (find("cycle"), find("execute"), find("bet"));
+        assert_eq!((cycle.len(), execute.len(), placed.len()), (1, 1, 2));
+        assert_eq!(cycle[0].parent_span_id, SpanId::INVALID);
+        assert_eq!(execute[0].parent_span_id, cycle[0].span_context.span_id());
+        assert!(spans.iter().all(|s| s.span_context.trace_id() == cycle[0].span_context.trace_id()));
+        let mut ids: Vec<_> = placed
+            .iter()
+            .map(|s| {
+                assert_eq!(s.parent_span_id, execute[0].span_context.span_id());
+                assert_eq!(attr(s, "platform"), Some(&Value::from("alpha")));
+                match attr(s, "market_id") {
+                    Some(id) => id.as_str().into_owned(),
+                    None => panic!("market_id missing"),
+                }
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["a1", "a2"]);
+    }
+
+    #[tokio::test]
+    async fn test_platforms_execute_concurrently() {
+        let executor = Executor::new(None, false)
+            .with_venue(venue("alpha", 100))
+            .with_venue(venue("beta", 200))
+            .with_venue(venue("gamma", 300));
+        let bets = vec![
+            make_bet_on("gamma", "g1"),
+            make_bet_on("alpha", "a1"),
+            make_bet_on("beta", "b1"),
+            make_bet_on("alpha", "a2"),
+            make_bet_on("gamma", "g2"),
+        ];
+
+        let started = Instant::now();
+        let report = executor.execute_batch(&bets).await.unwrap();
+        let elapsed = started.elapsed();
+
+        // Sequential would be 100+200+300+100+300 = 1000 ms; with two
+        // in flight per venue the slowest lane (gamma) takes ~300 ms.
+        assert!(elapsed < std::time::Duration::from_millis(600), "took {elapsed:?}");
+        assert_eq!(report.executed.len(), 5);
+        assert_eq!(report.total_committed, dec!(50));
+        // Report order follows the batch, not completion order.
+        let ids: Vec<_> = report.executed.iter().map(|t| t.market_id.as_str()).collect();
+        assert_eq!(ids, vec!["g1", "a1", "b1", "a2", "g2"]);
+        assert!(report.executed.iter().all(|t| t.latency_ms >= 100));
+    }
+
+    #[tokio::test]
+    async fn test_sequential_platform_places_one_at_a_time() {
+        let limits = ExecutionConfig {
+            sequential_platforms: vec!["alpha".to_string()],
+            ..ExecutionConfig::default()
+        };
+        let executor = Executor::new(None, false)
+            .with_venue(venue("alpha", 100))
+            .with_limits(limits);
+        let bets = vec![make_bet_on("alpha", "a1"), make_bet_on("alpha", "a2")];
+
+        let started = Instant::now();
+        let report = executor.execute_batch(&bets).await.unwrap();
+
+        assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+        assert_eq!(report.executed.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hung_platform_times_out_without_blocking_others() {
+        let limits = ExecutionConfig { bet_timeout_secs: 1, max_bet_attempts: 1, ..ExecutionConfig::default() };
+        let executor = Executor::new(None, false)
+            .with_venue(venue("alpha", 50))
+            .with_venue(venue("stuck", 60_000))
+            .with_limits(limits);
+        let bets = vec![make_bet_on("stuck", "s1"), make_bet_on("alpha", "a1")];
+
+        let started = Instant::now();
+        let report = executor.execute_batch(&bets).await.unwrap();
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(3), "hung venue blocked the batch");
+        assert_eq!(report.executed.len(), 1);
+        assert_eq!(report.executed[0].market_id, "a1");
+        assert!(report.executed[0].latency_ms < 1000);
+        // The timed-out order may have reached the venue.
+        assert!(report.failed.is_empty());
+        assert_eq!(report.unconfirmed.len(), 1);
+        assert_eq!(report.unconfirmed[0].market_id, "s1");
+        assert!(report.unconfirmed[0].latency_ms >= 1000);
+    }
+
+    // -- Retries ---------------------------------------------------------
+
+    /// Venue that fails its first placements with the queued errors, then
+    /// fills. An order that "hangs" is still booked as a position.
+    struct FlakyVenue {
+        errors: Mutex<Vec<&'static str>>,
+        /// Hang (past any budget) on this attempt number, after booking.
+        hang_on: Option<usize>,
+        idempotent: bool,
+        keys: Mutex<Vec<String>>,
+        positions: Mutex<Vec<Position>>,
+    }
+
+    impl FlakyVenue {
+        fn new(errors: &[&'static str]) -> Self {
+            Self {
+                errors: Mutex::new(errors.iter().rev().copied().collect()),
+                hang_on: None,
+                idempotent: false,
+                keys: Mutex::default(),
+                positions: Mutex::default(),
+            }
+        }
+
+        fn attempts(&self) -> usize {
+            self.keys.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for FlakyVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+            self.place_bet_keyed(market_id, side, amount, "unkeyed").await
+        }
+
+        fn supports_idempotency(&self) -> bool {
+            self.idempotent
+        }
+
+        async fn place_bet_keyed(&self, market_id: &str, side: Side, amount: Decimal, key: &str) -> Result<TradeReceipt> {
+            let attempt = {
+                let mut keys = self.keys.lock().unwrap();
+                keys.push(key.to_string());
+                keys.len()
+            };
+            if self.hang_on == Some(attempt) {
+                self.positions.lock().unwrap().push(Position {
+                    market_id: market_id.to_string(),
+                    platform: "flaky".to_string(),
+                    side,
+                    size: amount,
+                    entry_price: dec!(0.5),
+                    current_value: amount,
+                    cost: amount,
+                    category: None,
+                });
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            }
+            if let Some(err) = self.errors.lock().unwrap().pop() {
+                anyhow::bail!("Flaky bet failed {err}");
+            }
+            let mut receipt = TradeReceipt::dry_run(market_id, amount, "AUD");
+            receipt.order_id = format!("flaky-{key}");
+            receipt.platform = "flaky".to_string();
+            receipt.side = side;
+            Ok(receipt)
+        }
+
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(self.positions.lock().unwrap().clone())
+        }
+
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(dec!(100))
+        }
+
+        async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+            anyhow::bail!("not supported")
+        }
+
+        fn is_real_money(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+    }
+
+    fn retry_limits() -> ExecutionConfig {
+        ExecutionConfig { bet_timeout_secs: 1, retry_backoff_ms: 1, ..ExecutionConfig::default() }
+    }
+
+    fn approved_bet(market_id: &str) -> SizedBet {
+        let mut bet = make_bet_on("flaky", market_id);
+        bet.idempotency_key = Some(format!("key-{market_id}"));
+        bet
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_retried_under_one_key() {
+        let flaky = Arc::new(FlakyVenue::new(&["429 Too Many Requests", "503 Service Unavailable"]));
+        let executor = Executor::new(None, false).with_venue(flaky.clone()).with_limits(retry_limits());
+
+        let report = executor.execute_batch(&[approved_bet("f1")]).await.unwrap();
+
+        assert_eq!(report.executed.len(), 1);
+        assert!(report.failed.is_empty() && report.unconfirmed.is_empty());
+        assert_eq!(report.executed[0].receipt.order_id, "flaky-key-f1");
+        assert_eq!(*flaky.keys.lock().unwrap(), vec!["key-f1"; 3]);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failure_not_retried() {
+        let flaky = Arc::new(FlakyVenue::new(&["400 Bad Request: insufficient balance"]));
+        let executor = Executor::new(None, false).with_venue(flaky.clone()).with_limits(retry_limits());
+
+        let report = executor.execute_batch(&[approved_bet("f1")]).await.unwrap();
+
+        assert!(report.executed.is_empty() && report.unconfirmed.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!((report.failed[0].code, report.failed[0].attempts), (ExecutionFailure::Other, 1));
+        assert_eq!(flaky.attempts(), 1);
+        assert_eq!(report.total_committed, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_exhausting_retries_fail() {
+        let flaky = Arc::new(FlakyVenue::new(&["429 Too Many Requests"; 3]));
+        let executor = Executor::new(None, false).with_venue(flaky.clone()).with_limits(retry_limits());
+
+        let report = executor.execute_batch(&[approved_bet("f1")]).await.unwrap();
+
+        // A rate-limited order never reached the venue: a plain failure.
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!((report.failed[0].code, report.failed[0].attempts), (ExecutionFailure::RateLimited, 3));
+        assert!(report.unconfirmed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_then_timeout_is_unconfirmed() {
+        let flaky = Arc::new(FlakyVenue { hang_on: Some(1), ..FlakyVenue::new(&[]) });
+        let executor = Executor::new(None, false).with_venue(flaky.clone()).with_limits(retry_limits());
+
+        let report = executor.execute_batch(&[approved_bet("f1")]).await.unwrap();
+
+        // The venue holds the position, so the order is not re-sent.
+        assert_eq!(flaky.attempts(), 1);
+        assert!(report.executed.is_empty() && report.failed.is_empty());
+        assert_eq!(report.total_committed, Decimal::ZERO);
+        let unconfirmed = &report.unconfirmed[0];
+        assert_eq!((unconfirmed.market_id.as_str(), unconfirmed.attempts), ("f1", 1));
+        assert_eq!(unconfirmed.receipt.order_id, "key-f1");
+        assert!(unconfirmed.reason.contains("position held"));
+
+        // Next cycle: the accountant books it once the venue reports it.
+        let mut state = crate::types::AgentState::new(dec!(500));
+        let cycle = crate::engine::accountant::Accountant::reconcile(&mut state, &report, &Default::default());
+        assert_eq!((cycle.bets_placed, cycle.bets_unconfirmed), (0, 1));
+        assert!(state.open_bets.is_empty());
+        let positions = flaky.get_positions().await.unwrap();
+        let settled = crate::engine::accountant::Accountant::settle_unconfirmed(&mut state, &positions, Utc::now());
+        assert_eq!(settled, (1, 0));
+        assert_eq!(state.open_bets[0].order_id, "key-f1");
+        assert!(state.unconfirmed_bets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_venue_retries_timeout_with_same_key() {
+        let flaky = Arc::new(FlakyVenue { hang_on: Some(1), idempotent: true, ..FlakyVenue::new(&[]) });
+        let executor = Executor::new(None, false).with_venue(flaky.clone()).with_limits(retry_limits());
+
+        let report = executor.execute_batch(&[approved_bet("f1")]).await.unwrap();
+
+        // The venue deduplicates on the key, so the retry is safe.
+        assert_eq!(report.executed.len(), 1);
+        assert_eq!(*flaky.keys.lock().unwrap(), vec!["key-f1"; 2]);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_order_id_booked_once() {
+        // MockVenue derives the order id from the market id, so a second
+        // placement on the same market replays the first order's receipt.
+        let executor = Executor::new(None, false).with_venue(venue("alpha", 0));
+        let bets = vec![make_bet_on("alpha", "a1"), make_bet_on("alpha", "a1")];
+
+        let report = executor.execute_batch(&bets).await.unwrap();
+        assert_eq!(report.executed.len(), 1);
+        assert_eq!(report.duplicate_fills, 1);
+        assert_eq!(report.total_committed, dec!(10));
+
+        // The journal outlives the batch.
+        let report = executor.execute_batch(&bets[..1]).await.unwrap();
+        assert!(report.executed.is_empty());
+        assert_eq!(report.duplicate_fills, 1);
+    }
+
+    #[tokio::test]
+    async fn test_clustered_bet_fills_on_selected_venue() {
+        use crate::config::VenueSelectionConfig;
+        use crate::strategy::kelly::{KellyCalculator, KellyConfig};
+        use crate::strategy::venue::VenueSelector;
+
+        let executor = Executor::new(None, false)
+            .with_venue(venue("betfair", 0))
+            .with_venue(venue("polymarket", 0));
+        let bet = make_bet_on("betfair", "1.5");
+        let mut polymarket = bet.edge.market.clone();
+        polymarket.platform = "polymarket".into();
+        polymarket.id = "pm-5".into();
+        polymarket.current_price_yes = dec!(0.45);
+        polymarket.current_price_no = dec!(0.56);
+
+        let kelly = KellyCalculator::new(KellyConfig { commission_per_trade: Decimal::ZERO, ..KellyConfig::default() });
+        let mut venues = VenueSelector::new(VenueSelectionConfig::default(), executor.venue_names());
+        venues.set_clusters(vec![vec![bet.edge.market.clone(), polymarket]]);
+        let routed = venues.route(bet, &kelly, dec!(1000), Utc::now());
+
+        let report = executor.execute_batch(std::slice::from_ref(&routed)).await.unwrap();
+        assert_eq!(report.executed.len(), 1);
+        let trade = &report.executed[0];
+        assert_eq!((trade.platform.as_str(), trade.market_id.as_str()), ("polymarket", "pm-5"));
+        assert_eq!(trade.amount, routed.bet_amount);
+    }
+
     #[tokio::test]
     async fn test_no_manifold_logs_dry_run() {
         // No Manifold client, not global dry-run — Manifold markets still get
@@ -393,4 +1761,268 @@ mod tests {
         assert_eq!(report.executed[0].platform, "dry-run");
         assert_eq!(report.failed.len(), 0);
     }
+
+    // -- Price impact -----------------------------------------------------
+
+    /// Manifold-like CPMM venue with YES/NO `pools`, quoting YES at `quote`
+    /// when set, filling at 0.55 and recording the stakes placed.
+    struct PoolVenue {
+        pools: (Decimal, Decimal),
+        quote: Option<Decimal>,
+        stakes: Mutex<Vec<Decimal>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for PoolVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+            self.stakes.lock().unwrap().push(amount);
+            Ok(TradeReceipt {
+                order_id: format!("pool-{market_id}"),
+                platform: "manifold".into(),
+                side,
+                fill_price: dec!(0.55),
+                ..TradeReceipt::dry_run(market_id, amount, "Mana")
+            })
+        }
+
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+
+        async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+            Ok(LiquidityInfo { bid_depth: self.pools.0, ask_depth: self.pools.1, volume_24h: Decimal::ZERO })
+        }
+
+        async fn current_price(&self, _market_id: &str, side: Side) -> Result<Option<Decimal>> {
+            Ok(self.quote.map(|yes| if side == Side::Yes { yes } else { Decimal::ONE - yes }))
+        }
+
+        fn liquidity_model(&self) -> LiquidityModel {
+            LiquidityModel::Cpmm
+        }
+
+        fn is_real_money(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            "manifold"
+        }
+    }
+
+    fn pool_executor(pools: (Decimal, Decimal)) -> (Executor, Arc<PoolVenue>) {
+        let venue = Arc::new(PoolVenue { pools, quote: None, stakes: Mutex::new(Vec::new()) });
+        let executor = Executor::new(None, false)
+            .with_venue(venue.clone())
+            .with_max_price_impact(Some(dec!(0.10)), crate::strategy::constraints::BetConstraints::defaults());
+        (executor, venue)
+    }
+
+    #[tokio::test]
+    async fn test_bet_scaled_down_to_price_impact_limit() {
+        // Even 100/100 pools at 50%: 50 mana would lift YES to 69%, while
+        // 22.47 lifts it exactly 10 points — 22 in whole mana. 5 mana moves
+        // it 2.4 points and is placed as sized.
+        let (executor, venue) = pool_executor((dec!(100), dec!(100)));
+        let bets = [make_sized_bet("big", dec!(50)), make_sized_bet("small", dec!(5))];
+        let report = executor.execute_batch(&bets).await.unwrap();
+
+        assert_eq!(*venue.stakes.lock().unwrap(), [dec!(22), dec!(5)]);
+        assert_eq!(report.executed.iter().map(|t| t.amount).collect::<Vec<_>>(), [dec!(22), dec!(5)]);
+        assert_eq!(report.total_committed, dec!(27));
+        let receipt = &report.executed[0].receipt;
+        assert_eq!(receipt.expected_price, Some(dec!(0.50)));
+        assert_eq!(receipt.slippage(), Some(dec!(0.05)));
+    }
+
+    #[tokio::test]
+    async fn test_bet_over_price_impact_limit_skipped() {
+        // 2/2 pools: 0.45 mana already lifts YES 10 points, and rounds to
+        // nothing under the 1 mana minimum stake.
+        let (executor, venue) = pool_executor((dec!(2), dec!(2)));
+        let report = executor.execute_batch(&[make_sized_bet("thin", dec!(50))]).await.unwrap();
+
+        assert!(venue.stakes.lock().unwrap().is_empty());
+        assert!(report.executed.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].code, ExecutionFailure::PriceImpact);
+        assert_eq!(report.failed[0].attempts, 0);
+        assert!(report.failed[0].reason.contains("over the 0.10 limit"), "{}", report.failed[0].reason);
+    }
+
+    // -- Slippage protection -----------------------------------------------
+
+    #[tokio::test]
+    async fn test_moved_price_skips_market_order_bet() {
+        // Scanned at 0.50 with the default 0.02 tolerance: a YES quote of
+        // 0.55 is past it, 0.51 within it.
+        let moved = Arc::new(PoolVenue { pools: (dec!(100), dec!(100)), quote: Some(dec!(0.55)), stakes: Mutex::new(Vec::new()) });
+        let executor = Executor::new(None, false).with_venue(moved.clone());
+        let report = executor.execute_batch(&[make_sized_bet("moved", dec!(5))]).await.unwrap();
+
+        assert!(moved.stakes.lock().unwrap().is_empty());
+        assert!(report.executed.is_empty());
+        let failed = &report.failed[0];
+        assert_eq!((failed.code, failed.attempts), (ExecutionFailure::PriceMoved, 0));
+        assert_eq!(failed.observed_price, Some(dec!(0.55)));
+        assert!(failed.reason.contains("from 0.50 to 0.55"), "{}", failed.reason);
+
+        let steady = Arc::new(PoolVenue { pools: (dec!(100), dec!(100)), quote: Some(dec!(0.51)), stakes: Mutex::new(Vec::new()) });
+        let executor = Executor::new(None, false).with_venue(steady.clone());
+        let report = executor.execute_batch(&[make_sized_bet("steady", dec!(5))]).await.unwrap();
+        assert_eq!(*steady.stakes.lock().unwrap(), [dec!(5)]);
+        assert!(report.failed.is_empty());
+    }
+
+    /// Limit-order venue recording each order's price limit. Its quote is
+    /// far off, and never read.
+    #[derive(Default)]
+    struct LimitVenue {
+        limits: Mutex<Vec<Decimal>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for LimitVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_bet(&self, _market_id: &str, _side: Side, _amount: Decimal) -> Result<TradeReceipt> {
+            anyhow::bail!("placed without a limit")
+        }
+
+        fn supports_limit_orders(&self) -> bool {
+            true
+        }
+
+        async fn place_limit_bet(
+            &self,
+            market_id: &str,
+            _side: Side,
+            amount: Decimal,
+            max_price: Decimal,
+            key: &str,
+        ) -> Result<TradeReceipt> {
+            self.limits.lock().unwrap().push(max_price);
+            Ok(TradeReceipt { order_id: key.to_string(), ..TradeReceipt::dry_run(market_id, amount, "AUD") })
+        }
+
+        async fn current_price(&self, _market_id: &str, _side: Side) -> Result<Option<Decimal>> {
+            Ok(Some(dec!(0.99)))
+        }
+
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+
+        async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+            anyhow::bail!("not supported")
+        }
+
+        fn is_real_money(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            "alpha"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limit_venue_gets_entry_price_plus_tolerance() {
+        let venue = Arc::new(LimitVenue::default());
+        let limits = ExecutionConfig { slippage_tolerance: dec!(0.03), ..ExecutionConfig::default() };
+        let executor = Executor::new(None, false).with_venue(venue.clone()).with_limits(limits);
+        let mut no_bet = make_bet_on("alpha", "a2");
+        no_bet.edge.side = Side::No;
+        no_bet.edge.market.current_price_no = dec!(0.985);
+        let report = executor.execute_batch(&[make_bet_on("alpha", "a1"), no_bet]).await.unwrap();
+
+        assert_eq!(report.executed.len(), 2);
+        // YES scanned at 0.50; NO at 0.985, whose limit stops at certainty.
+        let mut sent = venue.limits.lock().unwrap().clone();
+        sent.sort();
+        assert_eq!(sent, [dec!(0.53), Decimal::ONE]);
+    }
+
+    // -- Raw response retention ------------------------------------------
+
+    #[tokio::test]
+    async fn test_raw_response_retained_and_redacted_for_real_money() {
+        let executor = Executor::new(None, false).with_venue(venue("alpha", 0));
+        let report = executor.execute_batch(&[make_bet_on("alpha", "a1")]).await.unwrap();
+
+        let raw = report.executed[0].receipt.raw_response.as_ref().expect("retained");
+        assert_eq!(raw["betId"], "alpha-a1");
+        assert_eq!(raw["status"], "SUCCESS");
+        assert_eq!(raw["sessionToken"], "[redacted]");
+    }
+
+    #[tokio::test]
+    async fn test_raw_response_paper_retention_follows_config() {
+        let executor = Executor::new(None, false).with_venue(paper_venue("alpha"));
+        let report = executor.execute_batch(&[make_bet_on("alpha", "a1")]).await.unwrap();
+        assert!(report.executed[0].receipt.raw_response.is_none());
+
+        let limits = ExecutionConfig { raw_response_paper: true, ..ExecutionConfig::default() };
+        let executor = Executor::new(None, false).with_venue(paper_venue("alpha")).with_limits(limits);
+        let report = executor.execute_batch(&[make_bet_on("alpha", "a2")]).await.unwrap();
+        assert!(report.executed[0].receipt.raw_response.is_some());
+
+        let limits = ExecutionConfig { raw_response_real_money: false, ..ExecutionConfig::default() };
+        let executor = Executor::new(None, false).with_venue(venue("beta", 0)).with_limits(limits);
+        let report = executor.execute_batch(&[make_bet_on("beta", "b1")]).await.unwrap();
+        assert!(report.executed[0].receipt.raw_response.is_none());
+    }
+
+    #[test]
+    fn test_redact_nested_fields_and_bearer_strings() {
+        let raw = serde_json::json!({
+            "Authorization": "Key abc",
+            "order": {"api_key": "k", "id": "42"},
+            "headers": ["Bearer xyz", "application/json"],
+            "note": "sk-live-123",
+            "size": 10.0,
+        });
+        let redacted = redact(raw);
+        assert_eq!(redacted["Authorization"], "[redacted]");
+        assert_eq!(redacted["order"]["api_key"], "[redacted]");
+        assert_eq!(redacted["order"]["id"], "42");
+        assert_eq!(redacted["headers"][0], "[redacted]");
+        assert_eq!(redacted["headers"][1], "application/json");
+        assert_eq!(redacted["note"], "[redacted]");
+        assert_eq!(redacted["size"], 10.0);
+    }
+
+    #[test]
+    fn test_oversized_raw_response_truncated() {
+        let mut receipt = TradeReceipt::dry_run("m1", dec!(10), "AUD");
+        receipt.raw_response = Some(serde_json::json!({"report": "é".repeat(100)}));
+        let limits = ExecutionConfig { raw_response_max_bytes: 25, ..ExecutionConfig::default() };
+        retain_raw_response(&mut receipt, true, &limits);
+
+        let raw = receipt.raw_response.unwrap();
+        assert_eq!(raw["truncated"], true);
+        assert_eq!(raw["original_bytes"], 213);
+        let prefix = raw["prefix"].as_str().unwrap();
+        assert!(prefix.len() <= 25 && prefix.starts_with("{\"report\":\"é"));
+
+        // Within the cap, the response is kept verbatim.
+        let mut receipt = TradeReceipt::dry_run("m1", dec!(10), "AUD");
+        receipt.raw_response = Some(serde_json::json!({"ok": true}));
+        retain_raw_response(&mut receipt, true, &ExecutionConfig::default());
+        assert_eq!(receipt.raw_response.unwrap(), serde_json::json!({"ok": true}));
+    }
 }
@@ -2,6 +2,18 @@
 
 pub mod scanner;
 pub mod enricher;
+pub mod cost_guard;
+pub mod cost_tracker;
+pub mod cycle_context;
 pub mod executor;
 pub mod accountant;
 pub mod auto_exit;
+pub mod reconcile;
+pub mod resolver;
+pub mod standby;
+pub mod pause;
+pub mod shutdown;
+pub mod watchlist;
+pub mod tags;
+pub mod daily;
+pub mod state;
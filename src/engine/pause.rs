@@ -0,0 +1,120 @@
+//! Pause.
+//!
+//! A paused agent keeps ticking — state is saved, resolutions, auto-exits
+//! and reconciliation run, the dashboard is served — but runs no cycle:
+//! nothing is scanned, enriched, estimated or placed. Unlike standby, no
+//! API spend is incurred.
+//!
+//! The operator pauses through the dashboard API or SIGUSR1; the risk
+//! manager's drawdown halt pauses on its own, instead of rejecting every
+//! bet. Resuming requires the drawdown to have recovered below
+//! `risk.drawdown_resume_pct` unless forced. Pause is persisted as
+//! [`AgentStatus::Paused`] with its reason, so a restarted agent comes
+//! back paused.
+
+use rust_decimal_macros::dec;
+use tracing::{info, warn};
+
+use crate::types::{AgentState, AgentStatus};
+
+/// Whether this tick runs a cycle: alive, or in standby (which runs the
+/// cycle with execution disabled in the executor).
+pub fn should_trade(state: &AgentState) -> bool {
+    matches!(state.status, AgentStatus::Alive | AgentStatus::Standby)
+}
+
+/// Pause a live agent. Returns whether the mode changed.
+pub fn pause(state: &mut AgentState, reason: &str) -> bool {
+    if state.status != AgentStatus::Alive {
+        return false;
+    }
+    state.status = AgentStatus::Paused;
+    state.pause_reason = Some(reason.to_string());
+    info!(reason, "PAUSED — no cycles until resumed");
+    true
+}
+
+/// Resume a paused agent if its drawdown has `recovered` below the resume
+/// threshold, or when `force`d. `None` when not paused, else whether it
+/// resumed.
+pub fn resume(state: &mut AgentState, recovered: bool, force: bool) -> Option<bool> {
+    if state.status != AgentStatus::Paused {
+        return None;
+    }
+    if !recovered && !force {
+        warn!(
+            drawdown = %format!("{:.1}%", state.drawdown() * dec!(100)),
+            "Resume refused — drawdown has not recovered below the resume threshold; staying PAUSED"
+        );
+        return Some(false);
+    }
+    state.status = AgentStatus::Alive;
+    let reason = state.pause_reason.take();
+    info!(forced = force, was = ?reason, "Resumed from PAUSED");
+    Some(true)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_trade_by_status() {
+        let mut state = AgentState::new(dec!(100));
+        for (status, trades) in [
+            (AgentStatus::Alive, true),
+            (AgentStatus::Standby, true),
+            (AgentStatus::Paused, false),
+            (AgentStatus::Died, false),
+        ] {
+            state.status = status;
+            assert_eq!(should_trade(&state), trades, "{status:?}");
+        }
+    }
+
+    #[test]
+    fn test_pause_only_from_alive() {
+        let mut state = AgentState::new(dec!(100));
+        assert!(pause(&mut state, "operator"));
+        assert_eq!(state.status, AgentStatus::Paused);
+        assert_eq!(state.pause_reason.as_deref(), Some("operator"));
+        assert!(!pause(&mut state, "again"));
+        assert_eq!(state.pause_reason.as_deref(), Some("operator"));
+
+        state.status = AgentStatus::Standby;
+        assert!(!pause(&mut state, "operator"));
+        assert_eq!(state.status, AgentStatus::Standby);
+    }
+
+    #[test]
+    fn test_resume_needs_recovery_or_force() {
+        let mut state = AgentState::new(dec!(100));
+        assert_eq!(resume(&mut state, true, false), None);
+
+        pause(&mut state, "drawdown halt");
+        assert_eq!(resume(&mut state, false, false), Some(false));
+        assert_eq!(state.status, AgentStatus::Paused);
+        assert_eq!(resume(&mut state, false, true), Some(true));
+        assert_eq!(state.status, AgentStatus::Alive);
+        assert!(state.pause_reason.is_none());
+
+        pause(&mut state, "operator");
+        assert_eq!(resume(&mut state, true, false), Some(true));
+        assert!(should_trade(&state));
+    }
+
+    #[test]
+    fn test_paused_status_round_trips() {
+        let mut state = AgentState::new(dec!(100));
+        pause(&mut state, "Drawdown halt: 42.0% from peak");
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: AgentState = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.status, AgentStatus::Paused);
+        assert_eq!(loaded.pause_reason, state.pause_reason);
+        assert!(!should_trade(&loaded));
+    }
+}
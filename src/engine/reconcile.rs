@@ -0,0 +1,538 @@
+//! Shared-account reconciliation.
+//!
+//! When an account is also traded by hand, internal arithmetic drifts from
+//! the platform's books. In reconciled accounting mode each cycle fetches
+//! the account's transaction history, attributes every new transaction to
+//! one of the agent's receipts (or auto-exit sales), and books the rest as
+//! external activity. Resolved P&L the agent's own resolutions don't
+//! explain is attributed to external activity as well.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+use crate::platforms::manifold::ManifoldFeedBet;
+use crate::types::{ExternalActivity, OwnSale, Side, TradeReceipt};
+
+/// Slack on amount matching beyond the transaction's own fees.
+const AMOUNT_TOLERANCE: Decimal = dec!(0.01);
+
+/// Most recent agent sales kept for attribution.
+pub const MAX_OWN_SALES: usize = 200;
+
+/// Kind of a platform transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxnKind {
+    Buy,
+    Sale,
+    /// Automatic YES/NO netting — nobody's trading decision.
+    Redemption,
+    Cancelled,
+}
+
+/// One transaction from a platform's account history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlatformTxn {
+    pub id: String,
+    pub market_id: String,
+    pub kind: TxnKind,
+    pub side: Option<Side>,
+    /// Amount spent; negative for sales.
+    pub amount: Decimal,
+    pub fees: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PlatformTxn {
+    pub fn from_manifold(bet: &ManifoldFeedBet) -> Self {
+        let kind = if bet.is_cancelled {
+            TxnKind::Cancelled
+        } else if bet.is_redemption {
+            TxnKind::Redemption
+        } else if bet.amount < 0.0 {
+            TxnKind::Sale
+        } else {
+            TxnKind::Buy
+        };
+        let side = match bet.outcome.as_str() {
+            "YES" => Some(Side::Yes),
+            "NO" => Some(Side::No),
+            _ => None,
+        };
+        Self {
+            id: bet.id.clone(),
+            market_id: bet.contract_id.clone(),
+            kind,
+            side,
+            amount: Decimal::from_f64(bet.amount).unwrap_or_default(),
+            fees: Decimal::from_f64(bet.fees.total()).unwrap_or_default(),
+            timestamp: DateTime::from_timestamp_millis(bet.created_time).unwrap_or_default(),
+        }
+    }
+}
+
+/// How a batch of transactions was attributed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Attribution {
+    /// `(transaction id, receipt order id)` pairs.
+    pub own: Vec<(String, String)>,
+    /// Transactions matched to agent sales.
+    pub own_sales: Vec<String>,
+    /// Transactions placed by someone else.
+    pub external: Vec<PlatformTxn>,
+    /// Redemptions and cancellations.
+    pub ignored: usize,
+}
+
+/// Attribute `txns` to `receipts` and `sales`; the rest is external.
+///
+/// A transaction whose id equals a receipt's order id is ours. Remaining
+/// buys are matched to unclaimed receipts on the same market and side
+/// within `window`, whose amounts agree to within the transaction's fees;
+/// sales are matched to agent sales on the same market within `window`.
+/// Where several pairings are possible (e.g. a manual bet placed seconds
+/// after ours), closest amount then closest time wins, so each receipt
+/// claims at most one transaction.
+pub fn attribute(
+    txns: &[PlatformTxn],
+    receipts: &[TradeReceipt],
+    sales: &[OwnSale],
+    window: Duration,
+) -> Attribution {
+    let mut out = Attribution::default();
+    let mut claimed_txn = vec![false; txns.len()];
+    let mut claimed_receipt = vec![false; receipts.len()];
+
+    for (i, t) in txns.iter().enumerate() {
+        if matches!(t.kind, TxnKind::Redemption | TxnKind::Cancelled) {
+            claimed_txn[i] = true;
+            out.ignored += 1;
+            continue;
+        }
+        if let Some(j) = receipts
+            .iter()
+            .enumerate()
+            .position(|(j, r)| !claimed_receipt[j] && !t.id.is_empty() && r.order_id == t.id)
+        {
+            claimed_txn[i] = true;
+            claimed_receipt[j] = true;
+            out.own.push((t.id.clone(), receipts[j].order_id.clone()));
+        }
+    }
+
+    // Fuzzy pass for buys: all plausible pairs, best first.
+    let mut pairs: Vec<(Decimal, Duration, usize, usize)> = Vec::new();
+    for (i, t) in txns.iter().enumerate() {
+        if claimed_txn[i] || t.kind != TxnKind::Buy {
+            continue;
+        }
+        for (j, r) in receipts.iter().enumerate() {
+            if claimed_receipt[j] || r.market_id != t.market_id || t.side != Some(r.side) {
+                continue;
+            }
+            let gap = (t.timestamp - r.timestamp).abs();
+            let diff = (t.amount - r.amount).abs();
+            if gap <= window && diff <= t.fees + AMOUNT_TOLERANCE {
+                pairs.push((diff, gap, i, j));
+            }
+        }
+    }
+    pairs.sort();
+    for (_, _, i, j) in pairs {
+        if !claimed_txn[i] && !claimed_receipt[j] {
+            claimed_txn[i] = true;
+            claimed_receipt[j] = true;
+            out.own.push((txns[i].id.clone(), receipts[j].order_id.clone()));
+        }
+    }
+
+    // Sales: an agent sale claims the closest sale on its market.
+    let mut claimed_sale = vec![false; sales.len()];
+    let mut pairs: Vec<(Duration, usize, usize)> = Vec::new();
+    for (i, t) in txns.iter().enumerate() {
+        if claimed_txn[i] || t.kind != TxnKind::Sale {
+            continue;
+        }
+        for (k, s) in sales.iter().enumerate() {
+            let gap = (t.timestamp - s.timestamp).abs();
+            if s.market_id == t.market_id && gap <= window {
+                pairs.push((gap, i, k));
+            }
+        }
+    }
+    pairs.sort();
+    for (_, i, k) in pairs {
+        if !claimed_txn[i] && !claimed_sale[k] {
+            claimed_txn[i] = true;
+            claimed_sale[k] = true;
+            out.own_sales.push(txns[i].id.clone());
+        }
+    }
+
+    out.external = txns
+        .iter()
+        .zip(&claimed_txn)
+        .filter(|(_, &claimed)| !claimed)
+        .map(|(t, _)| t.clone())
+        .collect();
+    out
+}
+
+/// Transactions in `history` not yet processed by `activity`.
+pub fn unseen(history: &[PlatformTxn], activity: &ExternalActivity) -> Vec<PlatformTxn> {
+    history
+        .iter()
+        .filter(|t| match activity.cursor {
+            Some(c) => t.timestamp > c || (t.timestamp == c && !activity.cursor_ids.contains(&t.id)),
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Process a history fetch: attribute unseen transactions and book the
+/// external ones.
+///
+/// The first call only sets the cursor — activity before reconciliation
+/// was enabled is not attributed.
+pub fn reconcile(
+    activity: &mut ExternalActivity,
+    history: &[PlatformTxn],
+    receipts: &[TradeReceipt],
+    window: Duration,
+) -> Attribution {
+    let fresh = unseen(history, activity);
+    let baseline = activity.cursor.is_none();
+    advance_cursor(activity, &fresh);
+    if baseline || fresh.is_empty() {
+        return Attribution::default();
+    }
+
+    let attribution = attribute(&fresh, receipts, &activity.own_sales, window);
+    for t in &attribution.external {
+        activity.trades += 1;
+        activity.staked += t.amount;
+        activity.fees += t.fees;
+    }
+
+    // Matched sales are done; unmatched ones older than the window never will be.
+    let horizon = activity.cursor.unwrap_or_else(Utc::now) - window;
+    let matched: Vec<&PlatformTxn> = fresh
+        .iter()
+        .filter(|t| attribution.own_sales.contains(&t.id))
+        .collect();
+    activity.own_sales.retain(|s| {
+        s.timestamp >= horizon
+            && !matched
+                .iter()
+                .any(|t| t.market_id == s.market_id && (t.timestamp - s.timestamp).abs() <= window)
+    });
+    attribution
+}
+
+fn advance_cursor(activity: &mut ExternalActivity, fresh: &[PlatformTxn]) {
+    let Some(newest) = fresh.iter().map(|t| t.timestamp).max() else {
+        return;
+    };
+    if activity.cursor != Some(newest) {
+        activity.cursor = Some(newest);
+        activity.cursor_ids.clear();
+    }
+    activity
+        .cursor_ids
+        .extend(fresh.iter().filter(|t| t.timestamp == newest).map(|t| t.id.clone()));
+}
+
+/// Record an agent-initiated sale so it isn't booked as external.
+pub fn record_own_sale(activity: &mut ExternalActivity, market_id: &str, timestamp: DateTime<Utc>) {
+    activity.own_sales.push(OwnSale { market_id: market_id.to_string(), timestamp });
+    let excess = activity.own_sales.len().saturating_sub(MAX_OWN_SALES);
+    activity.own_sales.drain(..excess);
+}
+
+/// Attribute the change in the platform's resolved profit: whatever the
+/// agent's own P&L change doesn't account for is external.
+///
+/// The first call records a baseline.
+pub fn split_profit(activity: &mut ExternalActivity, platform_profit: Decimal, agent_pnl: Decimal) {
+    if let Some(last) = activity.last_platform_profit {
+        activity.pnl += (platform_profit - last) - (agent_pnl - activity.last_agent_pnl);
+    }
+    activity.last_platform_profit = Some(platform_profit);
+    activity.last_agent_pnl = agent_pnl;
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Recorded `/v0/bets?userId=` response (trimmed), newest first:
+    /// two bets of ours (one with a synthesised receipt id), a manual bet
+    /// seconds after ours on the same market, an auto-exit sale, a manual
+    /// sale, a redemption and a cancelled limit order.
+    const HISTORY: &str = r#"[
+        {"id":"bx-cancel","contractId":"c3","userId":"u","amount":0,"outcome":"YES",
+         "createdTime":1760000300000,"isCancelled":true},
+        {"id":"bx-redeem","contractId":"c1","userId":"u","amount":-5,"outcome":"NO",
+         "createdTime":1760000250000,"isRedemption":true},
+        {"id":"bx-sale-manual","contractId":"c2","userId":"u","amount":-30,"outcome":"YES",
+         "createdTime":1760000200000},
+        {"id":"bx-sale-agent","contractId":"c1","userId":"u","amount":-18.5,"outcome":"YES",
+         "createdTime":1760000150000},
+        {"id":"bx-manual","contractId":"c1","userId":"u","amount":25,"outcome":"YES",
+         "createdTime":1760000010000,"fees":{"creatorFee":0,"platformFee":0.2,"liquidityFee":0}},
+        {"id":"bx-ours-2","contractId":"c1","userId":"u","amount":25.1,"outcome":"YES",
+         "createdTime":1760000005000,"fees":{"creatorFee":0.05,"platformFee":0.05,"liquidityFee":0}},
+        {"id":"b-ours-1","contractId":"c2","userId":"u","amount":40,"outcome":"NO",
+         "createdTime":1760000000000}
+    ]"#;
+
+    fn history() -> Vec<PlatformTxn> {
+        let bets: Vec<ManifoldFeedBet> = serde_json::from_str(HISTORY).unwrap();
+        bets.iter().map(PlatformTxn::from_manifold).collect()
+    }
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(ms).unwrap()
+    }
+
+    fn receipt(order_id: &str, market_id: &str, side: Side, amount: Decimal, ms: i64) -> TradeReceipt {
+        TradeReceipt {
+            order_id: order_id.to_string(),
+            market_id: market_id.to_string(),
+            platform: "manifold".to_string(),
+            side,
+            amount,
+            fill_price: dec!(0.5),
+            fees: Decimal::ZERO,
+            timestamp: at(ms),
+            currency: "Mana".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        }
+    }
+
+    fn receipts() -> Vec<TradeReceipt> {
+        vec![
+            receipt("b-ours-1", "c2", Side::No, dec!(40), 1_760_000_000_000),
+            // Bet response carried no id: matched on market/side/amount/time.
+            receipt("uuid-7f3a", "c1", Side::Yes, dec!(25.1), 1_760_000_004_000),
+        ]
+    }
+
+    fn window() -> Duration {
+        Duration::seconds(120)
+    }
+
+    fn external_ids(a: &Attribution) -> Vec<&str> {
+        a.external.iter().map(|t| t.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_from_manifold_kinds() {
+        let h = history();
+        let kinds: Vec<TxnKind> = h.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TxnKind::Cancelled,
+                TxnKind::Redemption,
+                TxnKind::Sale,
+                TxnKind::Sale,
+                TxnKind::Buy,
+                TxnKind::Buy,
+                TxnKind::Buy,
+            ]
+        );
+        assert_eq!(h[5].fees, dec!(0.1));
+        assert_eq!(h[5].side, Some(Side::Yes));
+        assert_eq!(h[6].timestamp, at(1_760_000_000_000));
+    }
+
+    #[test]
+    fn test_attribution_of_recorded_history() {
+        let sales = vec![OwnSale { market_id: "c1".into(), timestamp: at(1_760_000_149_000) }];
+        let a = attribute(&history(), &receipts(), &sales, window());
+
+        assert_eq!(
+            a.own,
+            vec![
+                ("b-ours-1".to_string(), "b-ours-1".to_string()),
+                ("bx-ours-2".to_string(), "uuid-7f3a".to_string()),
+            ]
+        );
+        assert_eq!(a.own_sales, vec!["bx-sale-agent"]);
+        assert_eq!(external_ids(&a), vec!["bx-sale-manual", "bx-manual"]);
+        assert_eq!(a.ignored, 2);
+    }
+
+    #[test]
+    fn test_overlapping_timestamps_prefer_closest_amount() {
+        // Manual and agent bets at the same instant, same market and side.
+        let txns = vec![
+            PlatformTxn {
+                id: "t-manual".into(),
+                market_id: "c1".into(),
+                kind: TxnKind::Buy,
+                side: Some(Side::Yes),
+                amount: dec!(10),
+                fees: dec!(0.5),
+                timestamp: at(1_000),
+            },
+            PlatformTxn {
+                id: "t-agent".into(),
+                market_id: "c1".into(),
+                kind: TxnKind::Buy,
+                side: Some(Side::Yes),
+                amount: dec!(10.4),
+                fees: dec!(0.5),
+                timestamp: at(1_000),
+            },
+        ];
+        let ours = vec![receipt("r1", "c1", Side::Yes, dec!(10.4), 1_000)];
+        let a = attribute(&txns, &ours, &[], window());
+        assert_eq!(a.own, vec![("t-agent".to_string(), "r1".to_string())]);
+        assert_eq!(external_ids(&a), vec!["t-manual"]);
+
+        // Equal amounts: the closer timestamp wins, the other is external.
+        let mut txns = txns;
+        txns[1].amount = dec!(10);
+        txns[1].timestamp = at(3_000);
+        let ours = vec![receipt("r1", "c1", Side::Yes, dec!(10), 2_500)];
+        let a = attribute(&txns, &ours, &[], window());
+        assert_eq!(a.own, vec![("t-agent".to_string(), "r1".to_string())]);
+        assert_eq!(external_ids(&a), vec!["t-manual"]);
+    }
+
+    #[test]
+    fn test_fees_bound_amount_matching() {
+        let txn = |amount: Decimal, fees: Decimal| PlatformTxn {
+            id: "t".into(),
+            market_id: "c1".into(),
+            kind: TxnKind::Buy,
+            side: Some(Side::Yes),
+            amount,
+            fees,
+            timestamp: at(0),
+        };
+        let ours = vec![receipt("r", "c1", Side::Yes, dec!(20), 0)];
+        // Amount differs by exactly the fees (+ tolerance): ours.
+        assert_eq!(attribute(&[txn(dec!(20.51), dec!(0.5))], &ours, &[], window()).own.len(), 1);
+        // Beyond fees: someone else's bet.
+        assert_eq!(attribute(&[txn(dec!(20.6), dec!(0.5))], &ours, &[], window()).external.len(), 1);
+        // Wrong side, or outside the window: external.
+        let mut t = txn(dec!(20), Decimal::ZERO);
+        t.side = Some(Side::No);
+        assert_eq!(attribute(&[t], &ours, &[], window()).external.len(), 1);
+        let mut t = txn(dec!(20), Decimal::ZERO);
+        t.timestamp = at(121_000);
+        assert_eq!(attribute(&[t], &ours, &[], window()).external.len(), 1);
+    }
+
+    #[test]
+    fn test_receipt_claims_one_transaction() {
+        // Two identical fills, one receipt (e.g. the user repeated our bet).
+        let t = PlatformTxn {
+            id: String::new(),
+            market_id: "c1".into(),
+            kind: TxnKind::Buy,
+            side: Some(Side::Yes),
+            amount: dec!(5),
+            fees: Decimal::ZERO,
+            timestamp: at(0),
+        };
+        let ours = vec![receipt("r", "c1", Side::Yes, dec!(5), 0)];
+        let a = attribute(&[t.clone(), t], &ours, &[], window());
+        assert_eq!(a.own.len(), 1);
+        assert_eq!(a.external.len(), 1);
+    }
+
+    #[test]
+    fn test_reconcile_baseline_then_incremental() {
+        let mut activity = ExternalActivity::default();
+        let h = history();
+
+        // First fetch: cursor only, nothing attributed.
+        assert_eq!(reconcile(&mut activity, &h, &receipts(), window()), Attribution::default());
+        assert_eq!(activity.cursor, Some(at(1_760_000_300_000)));
+        assert_eq!(activity.trades, 0);
+
+        // Same page again: nothing new.
+        assert_eq!(reconcile(&mut activity, &h, &receipts(), window()), Attribution::default());
+
+        // A manual bet sharing the cursor's timestamp, and one of ours after it.
+        let mut h2 = h.clone();
+        h2.insert(0, PlatformTxn {
+            id: "later-ours".into(),
+            market_id: "c9".into(),
+            kind: TxnKind::Buy,
+            side: Some(Side::Yes),
+            amount: dec!(12),
+            fees: Decimal::ZERO,
+            timestamp: at(1_760_000_400_000),
+        });
+        h2.insert(1, PlatformTxn {
+            id: "same-instant".into(),
+            market_id: "c9".into(),
+            kind: TxnKind::Buy,
+            side: Some(Side::No),
+            amount: dec!(7),
+            fees: dec!(0.25),
+            timestamp: at(1_760_000_300_000),
+        });
+        let ours = vec![receipt("later-ours", "c9", Side::Yes, dec!(12), 1_760_000_400_000)];
+        let a = reconcile(&mut activity, &h2, &ours, window());
+        assert_eq!(a.own.len(), 1);
+        assert_eq!(external_ids(&a), vec!["same-instant"]);
+        assert_eq!(activity.trades, 1);
+        assert_eq!(activity.staked, dec!(7));
+        assert_eq!(activity.fees, dec!(0.25));
+        assert_eq!(activity.cursor_ids, vec!["later-ours".to_string()]);
+
+        // Replaying the same page is idempotent.
+        reconcile(&mut activity, &h2, &ours, window());
+        assert_eq!(activity.trades, 1);
+    }
+
+    #[test]
+    fn test_reconcile_consumes_own_sales() {
+        let mut activity = ExternalActivity { cursor: Some(at(0)), ..ExternalActivity::default() };
+        record_own_sale(&mut activity, "c1", at(1_760_000_149_000));
+        record_own_sale(&mut activity, "c7", at(1_760_000_250_000));
+        // A stale sale that can no longer match anything.
+        record_own_sale(&mut activity, "c8", at(1_000));
+
+        let a = reconcile(&mut activity, &history(), &receipts(), window());
+        assert_eq!(a.own_sales, vec!["bx-sale-agent"]);
+        assert_eq!(activity.trades, 2);
+        assert_eq!(activity.staked, dec!(-5)); // manual +25 buy, -30 sale
+        assert_eq!(activity.fees, dec!(0.2));
+        // c1 matched, c8 expired; c7 is still within the window.
+        assert_eq!(activity.own_sales.len(), 1);
+        assert_eq!(activity.own_sales[0].market_id, "c7");
+    }
+
+    #[test]
+    fn test_split_profit() {
+        let mut activity = ExternalActivity::default();
+        split_profit(&mut activity, dec!(100), dec!(40));
+        assert_eq!(activity.pnl, Decimal::ZERO);
+        // Platform profit +50, of which the agent's resolutions explain +20.
+        split_profit(&mut activity, dec!(150), dec!(60));
+        assert_eq!(activity.pnl, dec!(30));
+        // Manual loss while the agent gains.
+        split_profit(&mut activity, dec!(140), dec!(75));
+        assert_eq!(activity.pnl, dec!(5));
+    }
+}
@@ -0,0 +1,371 @@
+//! Resolver — settles open bets once their markets resolve.
+//!
+//! The ledger is `AgentState::open_bets`, persisted with the rest of the
+//! state, so bets placed before a restart are still settled after it. Each
+//! tick the resolver asks every venue holding open bets how their markets
+//! resolved ([`PredictionPlatform::resolution`]) and prices each bet:
+//!
+//! - YES / NO / probabilistic resolutions pay the held side's share value
+//!   on the matched stake, so a partially filled order settles on what
+//!   was filled.
+//! - N/A, void and cancelled markets refund the stake: no P&L, and the
+//!   bet counts as neither a win nor a loss.
+
+use std::collections::BTreeSet;
+
+use futures::stream::{self, StreamExt};
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::engine::executor::Executor;
+use crate::types::{AgentState, MarketResolution, Side, TradeReceipt};
+
+/// Markets checked concurrently.
+const MAX_CONCURRENT_CHECKS: usize = 4;
+
+/// A settled bet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settlement {
+    pub bet_id: String,
+    pub market_id: String,
+    pub platform: String,
+    /// Currency of the stake and `pnl`.
+    pub currency: String,
+    pub resolution: MarketResolution,
+    pub won: bool,
+    /// Realised profit (negative for a loss, zero for a refund).
+    pub pnl: Decimal,
+}
+
+impl Settlement {
+    /// Stake refunded: carries no win, loss or outcome signal.
+    pub fn is_refund(&self) -> bool {
+        self.resolution == MarketResolution::Cancelled
+    }
+
+    /// Whether the market resolved YES, for outcome labelling. `None` for
+    /// refunds and probabilistic resolutions.
+    pub fn resolved_yes(&self) -> Option<bool> {
+        match self.resolution {
+            MarketResolution::Yes => Some(true),
+            MarketResolution::No => Some(false),
+            MarketResolution::Probability(_) | MarketResolution::Cancelled => None,
+        }
+    }
+}
+
+pub struct Resolver;
+
+impl Resolver {
+    /// Settle every open bet whose market has resolved on its venue.
+    ///
+    /// Bets on platforms without a registered venue are left open, as are
+    /// markets whose check fails. Nothing is checked in standby.
+    pub async fn check(executor: &Executor, open_bets: &[TradeReceipt]) -> Vec<Settlement> {
+        if open_bets.is_empty() || executor.is_standby() {
+            return Vec::new();
+        }
+        let markets: BTreeSet<(&str, &str)> =
+            open_bets.iter().map(|b| (b.platform.as_str(), b.market_id.as_str())).collect();
+        let resolved = Self::resolutions(executor, markets).await;
+
+        let mut settlements = Vec::new();
+        for (platform, market_id, resolution) in resolved {
+            settlements.extend(
+                open_bets
+                    .iter()
+                    .filter(|b| b.platform == platform && b.market_id == market_id)
+                    .map(|b| Self::settle(b, resolution)),
+            );
+        }
+        settlements
+    }
+
+    /// Resolutions of `markets` (`(platform, market_id)`) that have
+    /// resolved, for outcomes of markets we hold no bet on. Same rules as
+    /// [`Self::check`]: unknown platforms and failed checks are skipped,
+    /// and nothing is checked in standby.
+    pub async fn check_markets(executor: &Executor, markets: &[(String, String)]) -> Vec<(String, String, MarketResolution)> {
+        if executor.is_standby() {
+            return Vec::new();
+        }
+        let markets: BTreeSet<(&str, &str)> = markets.iter().map(|(p, m)| (p.as_str(), m.as_str())).collect();
+        Self::resolutions(executor, markets)
+            .await
+            .into_iter()
+            .map(|(platform, market_id, resolution)| (platform.to_string(), market_id.to_string(), resolution))
+            .collect()
+    }
+
+    /// Ask each market's venue for its resolution, a few at a time.
+    async fn resolutions<'a>(
+        executor: &Executor,
+        markets: BTreeSet<(&'a str, &'a str)>,
+    ) -> Vec<(&'a str, &'a str, MarketResolution)> {
+        let checks = markets.into_iter().filter_map(|(platform, market_id)| {
+            let venue = executor.venue(platform)?;
+            Some(async move { (platform, market_id, venue.resolution(market_id).await) })
+        });
+        let results: Vec<_> = stream::iter(checks).buffer_unordered(MAX_CONCURRENT_CHECKS).collect().await;
+        results
+            .into_iter()
+            .filter_map(|(platform, market_id, result)| match result {
+                Ok(resolution) => resolution.map(|r| (platform, market_id, r)),
+                Err(e) => {
+                    warn!(platform, market_id, error = %e, "Resolution check failed");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Price `bet` at `resolution`.
+    ///
+    /// `fill_price` is either the YES price (0–1, prediction markets) or
+    /// decimal odds (> 1, Betfair: a YES bet backs, a NO bet lays). Without
+    /// a usable price a winning bet returns its stake and a losing one loses it.
+    pub fn settle(bet: &TradeReceipt, resolution: MarketResolution) -> Settlement {
+        let (won, pnl) = match resolution.yes_value() {
+            None => (false, Decimal::ZERO),
+            Some(yes) => {
+                let held = match bet.side {
+                    Side::Yes => yes,
+                    Side::No => Decimal::ONE - yes,
+                };
+                let stake = bet.amount;
+                let pnl = match bet.fill_price {
+                    p if p > Decimal::ZERO && p < Decimal::ONE => {
+                        let entry = match bet.side {
+                            Side::Yes => p,
+                            Side::No => Decimal::ONE - p,
+                        };
+                        stake * held / entry - stake
+                    }
+                    odds if odds > Decimal::ONE => {
+                        let win = stake * (odds - Decimal::ONE);
+                        match bet.side {
+                            Side::Yes => win * held - stake * (Decimal::ONE - held),
+                            // A lay wins the backer's stake and risks the liability.
+                            Side::No => stake * held - win * (Decimal::ONE - held),
+                        }
+                    }
+                    _ => stake * held - stake,
+                };
+                (pnl > Decimal::ZERO || held == Decimal::ONE, pnl)
+            }
+        };
+        Settlement {
+            bet_id: bet.order_id.clone(),
+            market_id: bet.market_id.clone(),
+            platform: bet.platform.clone(),
+            currency: bet.currency.clone(),
+            resolution,
+            won,
+            pnl,
+        }
+    }
+
+    /// Book a settlement: Mana outcomes to the play-money ledger, everything
+    /// else to the bankroll. Refunds change neither (stakes are not
+    /// deducted at placement) and are not counted as wins or losses.
+    pub fn apply(state: &mut AgentState, settlement: &Settlement) {
+        if settlement.is_refund() {
+            info!(
+                market_id = %settlement.market_id,
+                bet_id = %settlement.bet_id,
+                platform = %settlement.platform,
+                "Market cancelled — stake refunded"
+            );
+            return;
+        }
+        if settlement.currency == "Mana" {
+            state.record_mana_resolution(settlement.pnl, settlement.won);
+        } else {
+            state.record_resolution(settlement.pnl, settlement.won);
+        }
+        info!(
+            market_id = %settlement.market_id,
+            bet_id = %settlement.bet_id,
+            platform = %settlement.platform,
+            resolution = ?settlement.resolution,
+            won = settlement.won,
+            pnl = %settlement.pnl.round_dp(2),
+            currency = %settlement.currency,
+            "Bet resolved"
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::PredictionPlatform;
+    use crate::types::{LiquidityInfo, Market, Position};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn bet(id: &str, platform: &str, market_id: &str, side: Side, amount: Decimal, fill_price: Decimal) -> TradeReceipt {
+        let currency = if platform == "manifold" { "Mana" } else { "AUD" };
+        TradeReceipt {
+            order_id: id.to_string(),
+            platform: platform.to_string(),
+            side,
+            fill_price,
+            ..TradeReceipt::dry_run(market_id, amount, currency)
+        }
+    }
+
+    /// Venue reporting fixed resolutions and counting the lookups.
+    struct ResolvedVenue {
+        name: &'static str,
+        resolutions: HashMap<&'static str, MarketResolution>,
+        lookups: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PredictionPlatform for ResolvedVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+        async fn place_bet(&self, _: &str, _: Side, _: Decimal) -> Result<TradeReceipt> {
+            anyhow::bail!("not used")
+        }
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(Vec::new())
+        }
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+        async fn check_liquidity(&self, _: &str) -> Result<LiquidityInfo> {
+            anyhow::bail!("not used")
+        }
+        fn is_real_money(&self) -> bool {
+            self.name != "manifold"
+        }
+        fn name(&self) -> &str {
+            self.name
+        }
+        async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            if market_id == "broken" {
+                anyhow::bail!("HTTP 500");
+            }
+            Ok(self.resolutions.get(market_id).copied())
+        }
+    }
+
+    #[test]
+    fn test_settle_prediction_market_prices() {
+        use MarketResolution::*;
+        // YES bought at 0.40: 10 stake buys 25 shares.
+        let yes = bet("a", "kalshi", "m", Side::Yes, dec!(10), dec!(0.40));
+        assert_eq!(Resolver::settle(&yes, Yes).pnl, dec!(15));
+        assert_eq!(Resolver::settle(&yes, No).pnl, dec!(-10));
+        // NO at a YES price of 0.40 costs 0.60 a share.
+        let no = bet("b", "kalshi", "m", Side::No, dec!(12), dec!(0.40));
+        let s = Resolver::settle(&no, No);
+        assert!(s.won);
+        assert_eq!(s.pnl, dec!(8));
+        // Probabilistic: 25 shares worth 0.60 each.
+        let s = Resolver::settle(&yes, Probability(dec!(0.60)));
+        assert_eq!((s.pnl, s.won), (dec!(5), true));
+    }
+
+    #[test]
+    fn test_settle_betfair_odds() {
+        use MarketResolution::*;
+        let back = bet("a", "betfair", "1.2", Side::Yes, dec!(10), dec!(3.5));
+        assert_eq!(Resolver::settle(&back, Yes).pnl, dec!(25));
+        assert_eq!(Resolver::settle(&back, No).pnl, dec!(-10));
+        let lay = bet("b", "betfair", "1.2", Side::No, dec!(10), dec!(3.5));
+        assert_eq!(Resolver::settle(&lay, No).pnl, dec!(10));
+        assert_eq!(Resolver::settle(&lay, Yes).pnl, dec!(-25));
+    }
+
+    #[test]
+    fn test_cancelled_market_refunds() {
+        let mut state = AgentState::new(dec!(100));
+        let s = Resolver::settle(&bet("a", "betfair", "1.2", Side::Yes, dec!(10), dec!(3.5)), MarketResolution::Cancelled);
+        assert!(s.is_refund() && !s.won);
+        assert_eq!((s.pnl, s.resolved_yes()), (Decimal::ZERO, None));
+        Resolver::apply(&mut state, &s);
+        assert_eq!((state.bankroll, state.trades_won, state.trades_lost), (dec!(100), 0, 0));
+    }
+
+    #[test]
+    fn test_partial_fill_settles_on_matched_stake() {
+        // Same order, one fully matched and one half matched.
+        let full = bet("a", "kalshi", "m", Side::Yes, dec!(20), dec!(0.50));
+        let half = bet("b", "kalshi", "m", Side::Yes, dec!(10), dec!(0.50));
+        for resolution in [MarketResolution::Yes, MarketResolution::No, MarketResolution::Probability(dec!(0.8))] {
+            let (f, h) = (Resolver::settle(&full, resolution), Resolver::settle(&half, resolution));
+            assert_eq!(h.pnl * dec!(2), f.pnl, "{resolution:?}");
+        }
+    }
+
+    #[test]
+    fn test_apply_books_by_currency() {
+        let mut state = AgentState::new(dec!(100));
+        state.mana_bankroll = dec!(1000);
+        let aud = Resolver::settle(&bet("a", "kalshi", "m", Side::Yes, dec!(10), dec!(0.40)), MarketResolution::Yes);
+        let mana = Resolver::settle(&bet("b", "manifold", "m", Side::Yes, dec!(50), dec!(0.50)), MarketResolution::No);
+        Resolver::apply(&mut state, &aud);
+        Resolver::apply(&mut state, &mana);
+        assert_eq!((state.bankroll, state.total_pnl, state.trades_won), (dec!(115), dec!(15), 1));
+        assert_eq!((state.mana_bankroll, state.total_mana_pnl, state.mana_trades_lost), (dec!(950), dec!(-50), 1));
+    }
+
+    #[tokio::test]
+    async fn test_check_queries_each_market_once_per_venue() {
+        let venue = Arc::new(ResolvedVenue {
+            name: "kalshi",
+            resolutions: HashMap::from([("done", MarketResolution::No), ("void", MarketResolution::Cancelled)]),
+            lookups: AtomicUsize::new(0),
+        });
+        let executor = Executor::new(None, false).with_venue(venue.clone());
+        let open = vec![
+            bet("a", "kalshi", "done", Side::Yes, dec!(10), dec!(0.40)),
+            bet("b", "kalshi", "done", Side::No, dec!(6), dec!(0.40)),
+            bet("c", "kalshi", "open", Side::Yes, dec!(10), dec!(0.40)),
+            bet("d", "kalshi", "void", Side::Yes, dec!(10), dec!(0.40)),
+            bet("e", "kalshi", "broken", Side::Yes, dec!(10), dec!(0.40)),
+            // No venue registered: left open, never queried.
+            bet("f", "betfair", "1.2", Side::Yes, dec!(10), dec!(2.0)),
+        ];
+
+        let mut settled = Resolver::check(&executor, &open).await;
+        settled.sort_by(|a, b| a.bet_id.cmp(&b.bet_id));
+        assert_eq!(venue.lookups.load(Ordering::SeqCst), 4);
+        assert_eq!(settled.iter().map(|s| s.bet_id.as_str()).collect::<Vec<_>>(), vec!["a", "b", "d"]);
+        assert_eq!((settled[0].pnl, settled[1].pnl), (dec!(-10), dec!(4)));
+        assert!(settled[2].is_refund());
+
+        executor.set_standby(true);
+        assert!(Resolver::check(&executor, &open).await.is_empty());
+        assert_eq!(venue.lookups.load(Ordering::SeqCst), 4);
+    }
+    #[tokio::test]
+    async fn test_check_markets_reports_resolved_markets_without_bets() {
+        let venue = Arc::new(ResolvedVenue {
+            name: "kalshi",
+            resolutions: HashMap::from([("done", MarketResolution::Yes)]),
+            lookups: AtomicUsize::new(0),
+        });
+        let executor = Executor::new(None, false).with_venue(venue.clone());
+        let markets: Vec<(String, String)> = [("kalshi", "done"), ("kalshi", "open"), ("kalshi", "done"), ("betfair", "1.2")]
+            .iter()
+            .map(|(p, m)| (p.to_string(), m.to_string()))
+            .collect();
+        let resolved = Resolver::check_markets(&executor, &markets).await;
+        assert_eq!(resolved, vec![("kalshi".to_string(), "done".to_string(), MarketResolution::Yes)]);
+        assert_eq!(venue.lookups.load(Ordering::SeqCst), 2);
+    }
+}
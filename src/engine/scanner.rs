@@ -5,58 +5,90 @@
 //! text similarity, attaches cross-references, and filters/sorts the
 //! unified market list for downstream processing.
 //!
+//! The sorted list is capped per category (`scanner.max_per_category`) and
+//! then overall (`scanner.max_markets_to_process`), so one busy category
+//! cannot crowd the rest out of the LLM batch. What the caps kept and
+//! dropped is reported in a [`ScanSummary`].
+//!
+//! Markets on different executable platforms that ask the same question
+//! are merged before the caps ([`merge_duplicates`]): only the most liquid
+//! stays bettable, with the others' prices attached as cross-references.
+//!
+//! Trading venues that keep producing no markets after filtering hibernate:
+//! they are scanned only every `hibernate_scan_every` cycles until a scan
+//! finds markets again or they are woken explicitly.
+//!
+//! A platform with `scan_every_n_cycles` above one is fetched only on every
+//! Nth scan; in between, its last fetched list (and when it was fetched) is
+//! reused, so slow-moving Metaculus forecasts still cross-reference every
+//! cycle. Reused lists are reported in [`ScanSummary::reused`].
+//!
+//! With `[scanner.embeddings]` enabled, cross-references are matched by the
+//! cosine similarity of question embeddings against
+//! `scanner.embeddings.match_threshold`
+//! ([`crate::embeddings::EmbeddingMatcher`]); if embedding fails, the scan
+//! falls back to word overlap for that cycle.
+//!
 //! This is the "2D: Market Router" from the development plan.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
-use tracing::{debug, info, warn};
+use serde::Serialize;
+use tracing::{debug, field, info, info_span, warn, Instrument};
 
 use crate::config::ScannerConfig;
+use crate::embeddings::{cosine, EmbeddingMatcher};
+use crate::engine::watchlist::{MarketLists, PatternList};
+use crate::language;
 use crate::platforms::betfair::BetfairClient;
 use crate::platforms::manifold::ManifoldClient;
 use crate::platforms::metaculus::MetaculusClient;
+use crate::platforms::forecastex::ForecastExClient;
+use crate::platforms::kalshi::KalshiClient;
 use crate::platforms::polymarket::PolymarketClient;
 use crate::platforms::PredictionPlatform;
-use crate::types::{CrossReferences, Market};
+use crate::question_parser;
+use crate::strategy::links::link_key;
+use crate::types::{CrossReferences, DuplicateListing, Market, MarketCategory, PlatformHibernation};
 
 // ---------------------------------------------------------------------------
 // Text similarity
 // ---------------------------------------------------------------------------
 
-/// Compute a normalised similarity score between two strings.
+/// Normalised token set for a question: lowercased alphanumeric words
+/// longer than two characters of its [`language::normalize`]d text.
+pub(crate) fn tokenize(s: &str) -> HashSet<String> {
+    language::normalize(s)
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2) // drop short words like "a", "in", "to"
+        .map(String::from)
+        .collect()
+}
+
+/// Compute a normalised similarity score between two tokenised questions.
 ///
 /// Uses a combination of:
 /// 1. Word overlap (Jaccard index on normalised tokens)
 /// 2. Substring containment bonus
 ///
 /// Returns 0.0 (no similarity) to 1.0 (identical after normalisation).
-fn text_similarity(a: &str, b: &str) -> f64 {
-    let norm = |s: &str| -> Vec<String> {
-        s.to_lowercase()
-            .split(|c: char| !c.is_alphanumeric())
-            .filter(|w| w.len() > 2) // drop short words like "a", "in", "to"
-            .map(String::from)
-            .collect()
-    };
-
-    let words_a = norm(a);
-    let words_b = norm(b);
-
-    if words_a.is_empty() || words_b.is_empty() {
+pub(crate) fn token_similarity(set_a: &HashSet<String>, set_b: &HashSet<String>) -> f64 {
+    if set_a.is_empty() || set_b.is_empty() {
         return 0.0;
     }
 
     // Jaccard index: |intersection| / |union|
-    let set_a: std::collections::HashSet<&str> =
-        words_a.iter().map(|s| s.as_str()).collect();
-    let set_b: std::collections::HashSet<&str> =
-        words_b.iter().map(|s| s.as_str()).collect();
-
-    let intersection = set_a.intersection(&set_b).count() as f64;
-    let union = set_a.union(&set_b).count() as f64;
+    let intersection = set_a.intersection(set_b).count() as f64;
+    let union = (set_a.len() + set_b.len()) as f64 - intersection;
 
     let jaccard = if union > 0.0 {
         intersection / union
@@ -76,10 +108,123 @@ fn text_similarity(a: &str, b: &str) -> f64 {
     (0.6 * jaccard + 0.4 * containment).min(1.0)
 }
 
+/// [`token_similarity`] on raw question strings.
+#[cfg(any(test, feature = "testkit"))]
+pub fn text_similarity(a: &str, b: &str) -> f64 {
+    token_similarity(&tokenize(a), &tokenize(b))
+}
+
+// ---------------------------------------------------------------------------
+// Candidate generation
+// ---------------------------------------------------------------------------
+
+/// Words that appear in most questions and say nothing about the topic.
+/// They still count towards the similarity score, but never make two
+/// questions candidates on their own.
+const STOPWORDS: &[&str] = &[
+    "will", "the", "and", "for", "with", "before", "after", "than", "more",
+    "less", "least", "most", "any", "this", "that", "from", "into", "end",
+    "are", "was", "has", "have", "what", "which", "who", "when", "how",
+    "does", "not", "its", "his", "her", "their", "over", "under", "above",
+    "below", "between", "during", "within", "there", "per", "out",
+];
+
+/// A token that appears in more than this fraction of indexed questions
+/// is too common to be a useful candidate signal (e.g. "2026").
+const MAX_POSTING_FRACTION: f64 = 0.1;
+
+/// Posting lists at or below this length are always kept, so small
+/// corpora are not starved of candidates by the fraction cap.
+const MIN_POSTING_CAP: usize = 10;
+
+/// Inverted index over a set of questions, tokenised once per cycle.
+struct QuestionIndex {
+    tokens: Vec<HashSet<String>>,
+    postings: HashMap<String, Vec<usize>>,
+}
+
+impl QuestionIndex {
+    fn build(markets: &[Market]) -> Self {
+        let tokens: Vec<HashSet<String>> =
+            markets.iter().map(|m| tokenize(&m.question)).collect();
+
+        let mut postings: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, set) in tokens.iter().enumerate() {
+            for tok in set {
+                if !STOPWORDS.contains(&tok.as_str()) {
+                    postings.entry(tok.clone()).or_default().push(i);
+                }
+            }
+        }
+
+        let cap = ((markets.len() as f64 * MAX_POSTING_FRACTION) as usize).max(MIN_POSTING_CAP);
+        postings.retain(|_, ids| ids.len() <= cap);
+
+        Self { tokens, postings }
+    }
+
+    /// Indices of questions sharing at least one informative token with
+    /// `query`, in ascending order.
+    fn candidates(&self, query: &HashSet<String>) -> Vec<usize> {
+        let mut ids: Vec<usize> = query
+            .iter()
+            .filter_map(|tok| self.postings.get(tok))
+            .flatten()
+            .copied()
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Market Router
 // ---------------------------------------------------------------------------
 
+/// Composition of one scan's processing set, by category name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScanSummary {
+    /// Markets left after filtering, before the caps.
+    pub filtered: usize,
+    /// Markets passed on for estimation.
+    pub kept: BTreeMap<String, usize>,
+    /// Markets dropped by the per-category or total cap.
+    pub dropped: BTreeMap<String, usize>,
+    /// Fetch time of each platform scanned, in seconds.
+    pub scan_seconds: BTreeMap<String, f64>,
+    /// Raw markets returned by each platform fetched successfully.
+    pub fetched: BTreeMap<String, usize>,
+    /// Platforms not due this cycle, served from their last fetch.
+    pub reused: BTreeMap<String, ReusedScan>,
+}
+
+/// A platform's cached market list served in place of a fetch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReusedScan {
+    /// Raw markets in the cached list.
+    pub markets: usize,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Last fetched market list of a platform scanned every few cycles.
+struct CachedScan {
+    markets: Vec<Market>,
+    fetched_at: DateTime<Utc>,
+    /// Scan number of the fetch.
+    cycle: u64,
+}
+
+/// One platform's part of a scan.
+struct PlatformFetch {
+    markets: Result<Vec<Market>>,
+    /// Fetch time; `None` when not fetched.
+    secs: Option<f64>,
+    /// Fetched successfully this scan.
+    scanned: bool,
+    reused: Option<ReusedScan>,
+}
+
 /// Unified market scanner that aggregates and cross-references markets
 /// from all enabled platforms.
 pub struct MarketRouter {
@@ -88,6 +233,70 @@ pub struct MarketRouter {
     metaculus: Option<MetaculusClient>,
     polymarket: Option<PolymarketClient>,
     betfair: Option<BetfairClient>,
+    kalshi: Option<KalshiClient>,
+    forecastex: Option<ForecastExClient>,
+    /// `(platform, market_id)` pairs seen closed at execution time; dropped
+    /// from every subsequent scan.
+    closed: Mutex<HashSet<(String, String)>>,
+    /// `(platform, market_id)` pairs the last cycle's cost budget left
+    /// unestimated; processed ahead of the rest next scan.
+    deferred: Mutex<HashSet<(String, String)>>,
+    /// Markets the last scan merged into a duplicate on another platform
+    /// (see [`merge_duplicates`]); still venues for routing and arbitrage.
+    merged: Mutex<Vec<Market>>,
+    /// Idle-scan tracking per trading venue. Metaculus is never tracked:
+    /// its value is cross-references, not markets of its own.
+    hibernation: Mutex<HashMap<String, PlatformHibernation>>,
+    /// Operator watchlist and question blocklist, swapped in by the agent
+    /// loop when the files change.
+    lists: Mutex<MarketLists>,
+    /// `[scanner]` question patterns dropped, and kept regardless of
+    /// liquidity and deadline.
+    blacklist: PatternList,
+    whitelist: PatternList,
+    /// Scan interval in cycles per platform; absent means every cycle.
+    scan_every: HashMap<String, u32>,
+    /// Last list of each platform with an interval above one.
+    scan_cache: Mutex<HashMap<String, CachedScan>>,
+    /// Scans started, numbering [`CachedScan::cycle`].
+    scan_cycle: AtomicU64,
+    /// Embedding matcher replacing word overlap in cross-referencing,
+    /// when `[scanner.embeddings]` is enabled.
+    embeddings: Option<EmbeddingMatcher>,
+}
+
+/// Whether two markets may be cross-referenced: same category, or either
+/// is "Other".
+fn comparable(a: &Market, b: &Market) -> bool {
+    a.category == b.category || a.category == MarketCategory::Other || b.category == MarketCategory::Other
+}
+
+/// Attach `metaculus`'s community forecast to `market`.
+fn attach_forecast(market: &mut Market, metaculus: &Market, score: f64) {
+    market.cross_refs.metaculus_prob = metaculus.cross_refs.metaculus_prob;
+    market.cross_refs.metaculus_forecasters = metaculus.cross_refs.metaculus_forecasters;
+    debug!(
+        manifold_q = %market.question,
+        metaculus_q = %metaculus.question,
+        score,
+        metaculus_prob = ?metaculus.cross_refs.metaculus_prob,
+        "Cross-platform match found"
+    );
+}
+
+/// The `[scanner]` blacklist and whitelist; patterns are validated with the
+/// config, so one that fails here is logged and the list left empty.
+fn patterns(config: &ScannerConfig) -> (PatternList, PatternList) {
+    let compile = |name: &str, patterns: &[String]| {
+        PatternList::new(patterns).unwrap_or_else(|e| {
+            warn!(list = name, error = %format!("{e:#}"), "Invalid scanner patterns — ignoring the list");
+            PatternList::default()
+        })
+    };
+    (
+        compile("blacklist_patterns", &config.blacklist_patterns),
+        compile("whitelist_patterns", &config.whitelist_patterns),
+    )
 }
 
 impl MarketRouter {
@@ -107,12 +316,26 @@ impl MarketRouter {
         manifold: Option<ManifoldClient>,
         metaculus: Option<MetaculusClient>,
     ) -> Self {
+        let (blacklist, whitelist) = patterns(&config);
         Self {
             config,
             manifold,
             metaculus,
             polymarket: None,
             betfair: None,
+            kalshi: None,
+            forecastex: None,
+            closed: Mutex::default(),
+            deferred: Mutex::default(),
+            merged: Mutex::default(),
+            hibernation: Mutex::default(),
+            lists: Mutex::default(),
+            blacklist,
+            whitelist,
+            scan_every: HashMap::new(),
+            scan_cache: Mutex::default(),
+            scan_cycle: AtomicU64::new(0),
+            embeddings: None,
         }
     }
 
@@ -128,6 +351,19 @@ impl MarketRouter {
             metaculus,
             polymarket: None,
             betfair: Some(betfair),
+            kalshi: None,
+            forecastex: None,
+            closed: Mutex::default(),
+            deferred: Mutex::default(),
+            merged: Mutex::default(),
+            hibernation: Mutex::default(),
+            lists: Mutex::default(),
+            blacklist: PatternList::default(),
+            whitelist: PatternList::default(),
+            scan_every: HashMap::new(),
+            scan_cache: Mutex::default(),
+            scan_cycle: AtomicU64::new(0),
+            embeddings: None,
         }
     }
 
@@ -138,12 +374,26 @@ impl MarketRouter {
         manifold: Option<ManifoldClient>,
         metaculus: Option<MetaculusClient>,
     ) -> Self {
+        let (blacklist, whitelist) = patterns(&config);
         Self {
             config,
             manifold,
             metaculus,
             polymarket: None,
             betfair: Some(betfair),
+            kalshi: None,
+            forecastex: None,
+            closed: Mutex::default(),
+            deferred: Mutex::default(),
+            merged: Mutex::default(),
+            hibernation: Mutex::default(),
+            lists: Mutex::default(),
+            blacklist,
+            whitelist,
+            scan_every: HashMap::new(),
+            scan_cache: Mutex::default(),
+            scan_cycle: AtomicU64::new(0),
+            embeddings: None,
         }
     }
 
@@ -159,72 +409,274 @@ impl MarketRouter {
             metaculus,
             polymarket: Some(polymarket),
             betfair: None,
+            kalshi: None,
+            forecastex: None,
+            closed: Mutex::default(),
+            deferred: Mutex::default(),
+            merged: Mutex::default(),
+            hibernation: Mutex::default(),
+            lists: Mutex::default(),
+            blacklist: PatternList::default(),
+            whitelist: PatternList::default(),
+            scan_every: HashMap::new(),
+            scan_cache: Mutex::default(),
+            scan_cycle: AtomicU64::new(0),
+            embeddings: None,
+        }
+    }
+
+    /// Also scan Kalshi as a real-money venue.
+    pub fn with_kalshi(mut self, kalshi: KalshiClient) -> Self {
+        self.kalshi = Some(kalshi);
+        self
+    }
+
+    /// Also scan IB ForecastEx event contracts.
+    pub fn with_forecastex(mut self, forecastex: ForecastExClient) -> Self {
+        self.forecastex = Some(forecastex);
+        self
+    }
+
+    /// Fetch each platform in `intervals` only every that many scans.
+    pub fn with_scan_intervals(mut self, intervals: HashMap<String, u32>) -> Self {
+        self.scan_every = intervals;
+        self
+    }
+
+    /// Cross-reference by embedding similarity at the matcher's threshold
+    /// instead of word overlap.
+    pub fn with_embeddings(mut self, matcher: EmbeddingMatcher) -> Self {
+        self.embeddings = Some(matcher);
+        self
+    }
+
+    /// Record that a market stopped accepting trades, so later scans drop it.
+    pub fn mark_closed(&self, platform: &str, market_id: &str) {
+        self.closed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((platform.to_string(), market_id.to_string()));
+    }
+
+    /// Markets skipped this cycle for cost, to be processed first next
+    /// scan. Replaces the previous cycle's set.
+    pub fn defer<'a>(&self, markets: impl IntoIterator<Item = &'a Market>) {
+        *self.deferred.lock().unwrap_or_else(|e| e.into_inner()) =
+            markets.into_iter().map(|m| (m.platform.clone(), m.id.clone())).collect();
+    }
+
+    /// Replace the watchlist and blocklist applied from the next scan.
+    pub fn set_lists(&self, lists: MarketLists) {
+        *self.lists.lock().unwrap_or_else(|e| e.into_inner()) = lists;
+    }
+
+    /// Restore hibernation state persisted from an earlier run.
+    pub fn restore_hibernation(&self, state: HashMap<String, PlatformHibernation>) {
+        *self.hibernation.lock().unwrap_or_else(|e| e.into_inner()) = state;
+    }
+
+    /// Current hibernation state, for persistence and display.
+    pub fn hibernation(&self) -> HashMap<String, PlatformHibernation> {
+        self.hibernation.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Wake a hibernating platform so it is scanned on the next cycle.
+    /// Returns false when it was not hibernating.
+    pub fn wake(&self, platform: &str) -> bool {
+        let mut hibernation = self.hibernation.lock().unwrap_or_else(|e| e.into_inner());
+        match hibernation.get_mut(platform) {
+            Some(h) if h.hibernating => {
+                *h = PlatformHibernation::default();
+                info!(platform, "Platform woken from hibernation");
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `platform` is scanned this cycle. A hibernating platform
+    /// counts down towards its next scan instead.
+    fn due_for_scan(&self, platform: &str) -> bool {
+        let mut hibernation = self.hibernation.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(h) = hibernation.get_mut(platform).filter(|h| h.hibernating) else {
+            return true;
+        };
+        if h.next_scan_in > 1 {
+            h.next_scan_in -= 1;
+            return false;
+        }
+        true
+    }
+
+    /// Update idle tracking for a platform that was scanned successfully
+    /// and yielded `markets` post-filter markets.
+    fn record_scan(&self, platform: &str, markets: usize) {
+        let mut hibernation = self.hibernation.lock().unwrap_or_else(|e| e.into_inner());
+        let h = hibernation.entry(platform.to_string()).or_default();
+        if markets > 0 {
+            if h.hibernating {
+                info!(platform, markets, "Platform producing markets again — leaving hibernation");
+            }
+            *h = PlatformHibernation::default();
+            return;
+        }
+        h.empty_scans += 1;
+        let every = self.config.hibernate_scan_every.max(1);
+        if h.hibernating {
+            h.next_scan_in = every;
+        } else if self.config.hibernate_after_empty_scans > 0
+            && h.empty_scans >= self.config.hibernate_after_empty_scans
+        {
+            h.hibernating = true;
+            h.next_scan_in = every;
+            info!(
+                platform,
+                empty_scans = h.empty_scans,
+                scan_every = every,
+                "No actionable markets — platform hibernating"
+            );
         }
     }
 
     /// Scan all enabled platforms, cross-reference markets, and return
-    /// a filtered, sorted list of actionable markets.
+    /// a filtered, sorted and capped list of actionable markets with its
+    /// composition.
     ///
     /// This is the main entry point called by the engine's scan cycle.
-    pub async fn scan_all(&self) -> Result<Vec<Market>> {
+    pub async fn scan_all(&self) -> Result<(Vec<Market>, ScanSummary)> {
         info!("Starting multi-platform market scan...");
 
-        // 1. Fetch from all platforms concurrently
-        let (manifold_markets, metaculus_markets, polymarket_markets, betfair_markets) = tokio::join!(
-            self.fetch_manifold(),
-            self.fetch_metaculus(),
-            self.fetch_polymarket(),
-            self.fetch_betfair(),
+        // 1. Fetch from all platforms concurrently, reusing the cached list
+        //    of platforms not due this cycle and skipping hibernating
+        //    venues that are not yet due
+        let cycle = self.scan_cycle.fetch_add(1, Ordering::Relaxed) + 1;
+        let (manifold, metaculus, polymarket, betfair, kalshi, forecastex) = tokio::join!(
+            self.fetch_scheduled("manifold", self.manifold.is_some(), true, cycle, self.fetch_manifold()),
+            self.fetch_scheduled("metaculus", self.metaculus.is_some(), false, cycle, self.fetch_metaculus()),
+            self.fetch_scheduled("polymarket", self.polymarket.is_some(), true, cycle, self.fetch_polymarket()),
+            self.fetch_scheduled("betfair", self.betfair.is_some(), true, cycle, self.fetch_betfair()),
+            self.fetch_scheduled("kalshi", self.kalshi.is_some(), true, cycle, self.fetch_kalshi()),
+            self.fetch_scheduled("forecastex", self.forecastex.is_some(), true, cycle, self.fetch_forecastex()),
         );
-
-        let mut manifold_markets = manifold_markets.unwrap_or_else(|e| {
+        let fetches = [
+            ("manifold", &manifold),
+            ("metaculus", &metaculus),
+            ("polymarket", &polymarket),
+            ("betfair", &betfair),
+            ("kalshi", &kalshi),
+            ("forecastex", &forecastex),
+        ];
+        // Metaculus is never tracked for hibernation
+        let scanned: Vec<&str> = fetches
+            .iter()
+            .filter(|(platform, f)| f.scanned && *platform != "metaculus")
+            .map(|(platform, _)| *platform)
+            .collect();
+        let scan_seconds: BTreeMap<String, f64> = fetches
+            .iter()
+            .filter_map(|(platform, f)| Some((platform.to_string(), f.secs?)))
+            .collect();
+        let reused: BTreeMap<String, ReusedScan> = fetches
+            .iter()
+            .filter_map(|(platform, f)| Some((platform.to_string(), f.reused.clone()?)))
+            .collect();
+        let fetched: BTreeMap<String, usize> = fetches
+            .iter()
+            .filter(|(_, f)| f.scanned)
+            .filter_map(|(platform, f)| Some((platform.to_string(), f.markets.as_ref().ok()?.len())))
+            .collect();
+
+        let mut manifold_markets = manifold.markets.unwrap_or_else(|e| {
             warn!(error = %e, "Manifold scan failed, continuing without");
             Vec::new()
         });
 
-        let metaculus_markets = metaculus_markets.unwrap_or_else(|e| {
+        let mut metaculus_markets = metaculus.markets.unwrap_or_else(|e| {
             warn!(error = %e, "Metaculus scan failed, continuing without");
             Vec::new()
         });
 
-        let polymarket_markets = polymarket_markets.unwrap_or_else(|e| {
+        let mut polymarket_markets = polymarket.markets.unwrap_or_else(|e| {
             warn!(error = %e, "Polymarket scan failed, continuing without");
             Vec::new()
         });
 
-        let betfair_markets = betfair_markets.unwrap_or_else(|e| {
+        let mut betfair_markets = betfair.markets.unwrap_or_else(|e| {
             warn!(error = %e, "Betfair scan failed, continuing without");
             Vec::new()
         });
 
+        let mut kalshi_markets = kalshi.markets.unwrap_or_else(|e| {
+            warn!(error = %e, "Kalshi scan failed, continuing without");
+            Vec::new()
+        });
+
+        let mut forecastex_markets = forecastex.markets.unwrap_or_else(|e| {
+            warn!(error = %e, "ForecastEx scan failed, continuing without");
+            Vec::new()
+        });
+
         info!(
             manifold = manifold_markets.len(),
             metaculus = metaculus_markets.len(),
             polymarket = polymarket_markets.len(),
             betfair = betfair_markets.len(),
+            kalshi = kalshi_markets.len(),
+            forecastex = forecastex_markets.len(),
+            hibernating = ?self.hibernation_notes(),
+            reused = ?reused.keys().collect::<Vec<_>>(),
             "Raw markets fetched"
         );
 
+        // 1b. Drop questions in languages the classifiers and prompt can't use
+        let foreign: usize = [
+            &mut manifold_markets,
+            &mut metaculus_markets,
+            &mut polymarket_markets,
+            &mut betfair_markets,
+            &mut kalshi_markets,
+            &mut forecastex_markets,
+        ]
+        .into_iter()
+        .map(|markets| self.drop_other_languages(markets))
+        .sum();
+        if foreign > 0 {
+            debug!(dropped = foreign, allowed = ?self.config.allowed_languages, "Markets in other languages dropped");
+        }
+
         // 2. Cross-reference: attach Metaculus forecasts to matching Manifold markets
-        Self::cross_reference(&mut manifold_markets, &metaculus_markets, self.config.match_threshold);
+        let matched = self.cross_reference_configured(&mut manifold_markets, &metaculus_markets).await;
 
         // 3. Merge all markets into a single list
-        //    Betfair & Polymarket markets are primary (real-money execution venues).
+        //    Betfair, Polymarket, Kalshi & ForecastEx markets are primary (real-money execution venues).
         //    Manifold markets are secondary (play-money validation).
         //    Metaculus-only markets are informational signals.
         let mut all_markets = betfair_markets;
         all_markets.extend(polymarket_markets);
+        all_markets.extend(kalshi_markets);
+        all_markets.extend(forecastex_markets);
         all_markets.extend(manifold_markets);
 
         // Add Metaculus markets that didn't match any Manifold market
         // (useful for discovering questions we might want to track)
-        for mc in &metaculus_markets {
-            let already_referenced = all_markets.iter().any(|m| {
-                m.cross_refs.metaculus_prob.is_some()
-                    && text_similarity(&m.question, &mc.question) >= self.config.match_threshold
-            });
+        let mut referenced: Vec<HashSet<String>> = all_markets
+            .iter()
+            .filter(|m| m.cross_refs.metaculus_prob.is_some())
+            .map(|m| tokenize(&m.question))
+            .collect();
+        for (i, mc) in metaculus_markets.iter().enumerate() {
+            if matched.contains(&i) {
+                continue;
+            }
+            let mc_tokens = tokenize(&mc.question);
+            let already_referenced = referenced
+                .iter()
+                .any(|t| token_similarity(t, &mc_tokens) >= self.config.match_threshold);
             if !already_referenced {
                 all_markets.push(mc.clone());
+                if mc.cross_refs.metaculus_prob.is_some() {
+                    referenced.push(mc_tokens);
+                }
             }
         }
 
@@ -236,33 +688,138 @@ impl MarketRouter {
             after = all_markets.len(),
             "Markets filtered"
         );
+        for platform in scanned {
+            self.record_scan(platform, all_markets.iter().filter(|m| m.platform == platform).count());
+        }
 
-        // 5. Sort by cross-reference richness, then by liquidity
+        // 5. One bettable market per question across executable platforms
+        let all_markets = if self.config.merge_duplicates {
+            let (kept, merged) = merge_duplicates(all_markets, self.config.duplicate_threshold);
+            *self.merged.lock().unwrap_or_else(|e| e.into_inner()) = merged;
+            kept
+        } else {
+            all_markets
+        };
+
+        // 6. Watched markets first, then by cross-reference richness and
+        //    liquidity
         let mut all_markets = all_markets;
-        all_markets.sort_by(|a, b| {
-            let score_a = Self::priority_score(a);
-            let score_b = Self::priority_score(b);
-            score_b
-                .partial_cmp(&score_a)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        // 6. Cap to top-N for downstream enrichment + LLM estimation.
-        //    Sorted by priority score above, so we drop the lowest-ranked markets.
-        let pre_cap = all_markets.len();
-        all_markets.truncate(self.config.max_markets_to_process);
+        self.sort_for_processing(&mut all_markets);
+
+        // 7. Cap per category, then to top-N, for downstream enrichment +
+        //    LLM estimation. Sorted by priority score above, so we drop the
+        //    lowest-ranked markets.
+        let (mut all_markets, mut summary) = self.apply_caps(all_markets);
+        summary.scan_seconds = scan_seconds;
+        summary.reused = reused;
+        summary.fetched = fetched;
+
+        // 8. Parse question facts and attach operator tags once for
+        //    downstream consumers.
+        {
+            let lists = self.lists.lock().unwrap_or_else(|e| e.into_inner());
+            for market in &mut all_markets {
+                market.facts = Some(question_parser::parse(&market.question));
+                market.tags = lists.tags_for(market);
+            }
+        }
 
         info!(
-            total = pre_cap,
+            total = summary.filtered,
             after_cap = all_markets.len(),
+            dropped = ?summary.dropped,
             "Market scan complete (capped for processing)"
         );
 
-        Ok(all_markets)
+        Ok((all_markets, summary))
+    }
+
+    /// Markets in `markets` grouped by underlying event across platforms
+    /// (see [`same_event_clusters`]), at the cross-reference threshold.
+    /// The last scan's merged duplicates of markets in `markets` are
+    /// clustered with them, so they can still be routed to.
+    pub fn event_clusters(&self, markets: &[Market]) -> Vec<Vec<Market>> {
+        let kept: HashSet<String> = markets.iter().map(|m| link_key(&m.platform, &m.id)).collect();
+        let merged = self.merged.lock().unwrap_or_else(|e| e.into_inner());
+        let markets: Vec<Market> = markets
+            .iter()
+            .chain(merged.iter().filter(|m| m.cross_refs.duplicate_of.as_ref().is_some_and(|k| kept.contains(k))))
+            .cloned()
+            .collect();
+        same_event_clusters(&markets, self.config.match_threshold)
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| markets[i].clone()).collect())
+            .collect()
+    }
+
+    /// Scan-stats notes for hibernating platforms, sorted by platform.
+    pub fn hibernation_notes(&self) -> Vec<String> {
+        let hibernation = self.hibernation.lock().unwrap_or_else(|e| e.into_inner());
+        let mut notes: Vec<String> = hibernation.iter().filter_map(|(p, h)| h.note(p)).collect();
+        notes.sort();
+        notes
     }
 
     // -- Platform fetch helpers ------------------------------------------
 
+    fn scan_interval(&self, platform: &str) -> u64 {
+        u64::from(self.scan_every.get(platform).copied().unwrap_or(1).max(1))
+    }
+
+    /// Fetch an enabled `platform` on scan `cycle` when its interval has
+    /// elapsed (and, for a venue that `hibernates`, it isn't hibernating),
+    /// caching the list; otherwise serve the cached list.
+    async fn fetch_scheduled(
+        &self,
+        platform: &'static str,
+        enabled: bool,
+        hibernates: bool,
+        cycle: u64,
+        fetch: impl std::future::Future<Output = Result<Vec<Market>>>,
+    ) -> PlatformFetch {
+        let every = self.scan_interval(platform);
+        if enabled && every > 1 {
+            let cache = self.scan_cache.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(cached) = cache.get(platform).filter(|c| cycle - c.cycle < every) {
+                debug!(platform, markets = cached.markets.len(), fetched_at = %cached.fetched_at, "Platform not due — reusing last scan");
+                return PlatformFetch {
+                    markets: Ok(cached.markets.clone()),
+                    secs: None,
+                    scanned: false,
+                    reused: Some(ReusedScan { markets: cached.markets.len(), fetched_at: cached.fetched_at }),
+                };
+            }
+        }
+        let scan = enabled && (!hibernates || self.due_for_scan(platform));
+        let (markets, secs) = Self::fetch_if(platform, scan, fetch).await;
+        if let (true, true, Ok(list)) = (scan, every > 1, &markets) {
+            self.scan_cache.lock().unwrap_or_else(|e| e.into_inner()).insert(
+                platform.to_string(),
+                CachedScan { markets: list.clone(), fetched_at: Utc::now(), cycle },
+            );
+        }
+        PlatformFetch { scanned: scan && markets.is_ok(), markets, secs, reused: None }
+    }
+
+    /// Run `fetch` inside a `scan_platform` span when `scan` is set, with
+    /// its duration in seconds (`None` when skipped).
+    async fn fetch_if(
+        platform: &'static str,
+        scan: bool,
+        fetch: impl std::future::Future<Output = Result<Vec<Market>>>,
+    ) -> (Result<Vec<Market>>, Option<f64>) {
+        if !scan {
+            return (Ok(Vec::new()), None);
+        }
+        let started = Instant::now();
+        let span = info_span!("scan_platform", platform, markets = field::Empty);
+        let fetched = fetch.instrument(span.clone()).await;
+        if let Ok(markets) = &fetched {
+            span.record("markets", markets.len() as i64);
+        }
+        (fetched, Some(started.elapsed().as_secs_f64()))
+    }
+
     async fn fetch_manifold(&self) -> Result<Vec<Market>> {
         match &self.manifold {
             Some(client) => client.fetch_markets().await,
@@ -291,52 +848,102 @@ impl MarketRouter {
         }
     }
 
+    async fn fetch_kalshi(&self) -> Result<Vec<Market>> {
+        match &self.kalshi {
+            Some(client) => client.fetch_markets().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn fetch_forecastex(&self) -> Result<Vec<Market>> {
+        match &self.forecastex {
+            Some(client) => client.fetch_markets().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
     // -- Cross-referencing -----------------------------------------------
 
     /// For each Manifold market, find the best-matching Metaculus question
     /// and attach its community forecast as a cross-reference.
-    fn cross_reference(manifold: &mut [Market], metaculus: &[Market], match_threshold: f64) {
+    #[cfg(any(test, feature = "testkit"))]
+    pub fn cross_reference(manifold: &mut [Market], metaculus: &[Market], match_threshold: f64) {
+        Self::cross_reference_capped(manifold, metaculus, match_threshold, usize::MAX);
+    }
+
+    /// [`Self::cross_reference`] with a cap on similarity comparisons.
+    ///
+    /// Each question is tokenised once, and only pairs sharing at least one
+    /// informative token are compared. When the cap binds, the most liquid
+    /// markets on both sides are compared first. Returns the number of
+    /// comparisons made.
+    fn cross_reference_capped(
+        manifold: &mut [Market],
+        metaculus: &[Market],
+        match_threshold: f64,
+        max_comparisons: usize,
+    ) -> usize {
         if metaculus.is_empty() {
-            return;
+            return 0;
+        }
+
+        let index = QuestionIndex::build(metaculus);
+
+        // Liquidity rank of each Metaculus question (0 = most liquid),
+        // used to choose which candidates to keep when the budget runs out.
+        let mut by_liquidity: Vec<usize> = (0..metaculus.len()).collect();
+        by_liquidity.sort_by(|&a, &b| metaculus[b].liquidity.cmp(&metaculus[a].liquidity));
+        let mut mc_rank = vec![0usize; metaculus.len()];
+        for (rank, &i) in by_liquidity.iter().enumerate() {
+            mc_rank[i] = rank;
         }
 
+        let mut mf_order: Vec<usize> = (0..manifold.len()).collect();
+        mf_order.sort_by(|&a, &b| manifold[b].liquidity.cmp(&manifold[a].liquidity));
+
         let mut match_count = 0u32;
+        let mut comparisons = 0usize;
+
+        for mf_idx in mf_order {
+            if comparisons >= max_comparisons {
+                debug!(
+                    max_comparisons,
+                    "Cross-reference comparison budget exhausted"
+                );
+                break;
+            }
+
+            let mf_market = &mut manifold[mf_idx];
+            let mf_tokens = tokenize(&mf_market.question);
+
+            let mut candidates: Vec<usize> = index
+                .candidates(&mf_tokens)
+                .into_iter()
+                .filter(|&i| comparable(mf_market, &metaculus[i]))
+                .collect();
+
+            let budget = max_comparisons - comparisons;
+            if candidates.len() > budget {
+                candidates.sort_by_key(|&i| mc_rank[i]);
+                candidates.truncate(budget);
+                candidates.sort_unstable();
+            }
+            comparisons += candidates.len();
 
-        for mf_market in manifold.iter_mut() {
             let mut best_score = 0.0f64;
             let mut best_match: Option<&Market> = None;
 
-            for mc_market in metaculus {
-                // Quick category pre-filter: only match within same category
-                // or if either is "Other"
-                if mf_market.category != mc_market.category
-                    && mf_market.category != crate::types::MarketCategory::Other
-                    && mc_market.category != crate::types::MarketCategory::Other
-                {
-                    continue;
-                }
-
-                let score = text_similarity(&mf_market.question, &mc_market.question);
+            for i in candidates {
+                let score = token_similarity(&mf_tokens, &index.tokens[i]);
                 if score > best_score {
                     best_score = score;
-                    best_match = Some(mc_market);
+                    best_match = Some(&metaculus[i]);
                 }
             }
 
             if best_score >= match_threshold {
                 if let Some(mc) = best_match {
-                    mf_market.cross_refs.metaculus_prob = mc.cross_refs.metaculus_prob;
-                    mf_market.cross_refs.metaculus_forecasters =
-                        mc.cross_refs.metaculus_forecasters;
-
-                    debug!(
-                        manifold_q = %mf_market.question,
-                        metaculus_q = %mc.question,
-                        score = best_score,
-                        metaculus_prob = ?mc.cross_refs.metaculus_prob,
-                        "Cross-platform match found"
-                    );
-
+                    attach_forecast(mf_market, mc, best_score);
                     match_count += 1;
                 }
             }
@@ -344,41 +951,225 @@ impl MarketRouter {
 
         info!(
             matches = match_count,
+            comparisons,
             manifold_total = manifold.len(),
             metaculus_total = metaculus.len(),
             "Cross-referencing complete"
         );
+
+        comparisons
+    }
+
+    /// Step 2 of [`Self::scan_all`]: cross-reference by embeddings when a
+    /// matcher is set, falling back to word overlap if embedding fails.
+    /// Returns the Metaculus questions matched by embedding, which word
+    /// overlap wouldn't recognise as already referenced.
+    async fn cross_reference_configured(&self, manifold: &mut [Market], metaculus: &[Market]) -> HashSet<usize> {
+        let max_comparisons = self.config.max_cross_ref_comparisons;
+        if let Some(matcher) = &self.embeddings {
+            let matched = Self::cross_reference_embedded(matcher, manifold, metaculus, max_comparisons).await;
+            matcher.save();
+            match matched {
+                Ok(matched) => return matched,
+                Err(e) => warn!(error = %format!("{e:#}"), "Embedding match failed — falling back to word overlap"),
+            }
+        }
+        Self::cross_reference_capped(manifold, metaculus, self.config.match_threshold, max_comparisons);
+        HashSet::new()
+    }
+
+    /// [`Self::cross_reference_capped`] by the cosine similarity of question
+    /// embeddings against the matcher's threshold. Every category-compatible
+    /// pair is a candidate; when the cap binds, the most liquid markets on
+    /// both sides are compared first. Returns the Metaculus questions
+    /// matched.
+    async fn cross_reference_embedded(
+        matcher: &EmbeddingMatcher,
+        manifold: &mut [Market],
+        metaculus: &[Market],
+        max_comparisons: usize,
+    ) -> Result<HashSet<usize>> {
+        let mut matched = HashSet::new();
+        if manifold.is_empty() || metaculus.is_empty() {
+            return Ok(matched);
+        }
+
+        let questions: Vec<&str> = manifold.iter().chain(metaculus).map(|m| m.question.as_str()).collect();
+        let vectors = matcher.embed_all(&questions).await?;
+        let (mf_vectors, mc_vectors) = vectors.split_at(manifold.len());
+
+        let mut mc_order: Vec<usize> = (0..metaculus.len()).collect();
+        mc_order.sort_by(|&a, &b| metaculus[b].liquidity.cmp(&metaculus[a].liquidity));
+        let mut mf_order: Vec<usize> = (0..manifold.len()).collect();
+        mf_order.sort_by(|&a, &b| manifold[b].liquidity.cmp(&manifold[a].liquidity));
+
+        let mut comparisons = 0usize;
+        for mf_idx in mf_order {
+            if comparisons >= max_comparisons {
+                debug!(max_comparisons, "Cross-reference comparison budget exhausted");
+                break;
+            }
+
+            let candidates: Vec<usize> = mc_order
+                .iter()
+                .copied()
+                .filter(|&i| comparable(&manifold[mf_idx], &metaculus[i]))
+                .take(max_comparisons - comparisons)
+                .collect();
+            comparisons += candidates.len();
+
+            let best = candidates
+                .into_iter()
+                .map(|i| (i, cosine(&mf_vectors[mf_idx], &mc_vectors[i])))
+                .fold(None, |best: Option<(usize, f64)>, (i, score)| match best {
+                    Some((_, top)) if top >= score => best,
+                    _ => Some((i, score)),
+                });
+            if let Some((i, score)) = best.filter(|&(_, score)| score >= matcher.threshold()) {
+                attach_forecast(&mut manifold[mf_idx], &metaculus[i], score);
+                matched.insert(i);
+            }
+        }
+
+        let (cache_hits, cache_misses) = matcher.cache_stats();
+        info!(
+            matches = matched.len(),
+            comparisons,
+            manifold_total = manifold.len(),
+            metaculus_total = metaculus.len(),
+            cache_hits,
+            cache_misses,
+            "Cross-referencing by embedding complete"
+        );
+
+        Ok(matched)
     }
 
     // -- Filtering -------------------------------------------------------
 
+    /// Steps 4 and 5 of [`Self::scan_all`] without the scan bookkeeping:
+    /// filter, then order for the per-cycle cap.
+    #[cfg(any(test, feature = "testkit"))]
+    pub fn filter_and_sort(&self, markets: Vec<Market>) -> Vec<Market> {
+        let mut markets = self.filter_markets(markets);
+        self.sort_for_processing(&mut markets);
+        markets
+    }
+
+    /// Keep the first markets of each capped category, then the first
+    /// `max_markets_to_process` overall. `markets` must be in processing
+    /// order; watched markets count toward their category's cap but are
+    /// never dropped by it.
+    fn apply_caps(&self, markets: Vec<Market>) -> (Vec<Market>, ScanSummary) {
+        let caps: HashMap<MarketCategory, usize> = self
+            .config
+            .max_per_category
+            .iter()
+            .filter_map(|(category, &cap)| Some((category.parse().ok()?, cap)))
+            .collect();
+        let lists = self.lists.lock().unwrap_or_else(|e| e.into_inner());
+        let mut summary = ScanSummary { filtered: markets.len(), ..ScanSummary::default() };
+        let mut taken: HashMap<MarketCategory, usize> = HashMap::new();
+        let mut kept = Vec::new();
+        for market in markets {
+            let count = taken.entry(market.category).or_default();
+            let within_cap = caps.get(&market.category).is_none_or(|&cap| *count < cap)
+                || lists.is_watched(&market.platform, &market.id);
+            let category = market.category.to_string();
+            if within_cap && kept.len() < self.config.max_markets_to_process {
+                *count += 1;
+                *summary.kept.entry(category).or_default() += 1;
+                kept.push(market);
+            } else {
+                *summary.dropped.entry(category).or_default() += 1;
+            }
+        }
+        (kept, summary)
+    }
+
     /// Filter out markets that are too illiquid, too far/close to deadline,
     /// or already resolved.
+    /// Order for the per-cycle cap: watched markets first, then those
+    /// deferred by the last cycle's cost budget, then by
+    /// [`Self::priority_score`].
+    fn sort_for_processing(&self, markets: &mut [Market]) {
+        let lists = self.lists.lock().unwrap_or_else(|e| e.into_inner());
+        let deferred = self.deferred.lock().unwrap_or_else(|e| e.into_inner());
+        let is_deferred = |m: &Market| deferred.contains(&(m.platform.clone(), m.id.clone()));
+        markets.sort_by(|a, b| {
+            let watched_a = lists.is_watched(&a.platform, &a.id);
+            let watched_b = lists.is_watched(&b.platform, &b.id);
+            watched_b.cmp(&watched_a).then_with(|| is_deferred(b).cmp(&is_deferred(a))).then_with(|| {
+                Self::priority_score(b)
+                    .partial_cmp(&Self::priority_score(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+    }
+
+    /// Drop the markets whose question is detected in a language outside
+    /// `scanner.allowed_languages`; returns how many were dropped.
+    fn drop_other_languages(&self, markets: &mut Vec<Market>) -> usize {
+        let allowed = &self.config.allowed_languages;
+        if allowed.is_empty() {
+            return 0;
+        }
+        let before = markets.len();
+        markets.retain(|m| {
+            language::detect(&language::normalize(&m.question)).is_none_or(|l| allowed.iter().any(|a| a == l))
+        });
+        before - markets.len()
+    }
+
     fn filter_markets(&self, markets: Vec<Market>) -> Vec<Market> {
         let now = Utc::now();
         let min_liquidity = self.config.min_liquidity;
         let min_hours = self.config.min_hours_to_deadline;
         let max_hours = self.config.max_hours_to_deadline;
+        let closed = self.closed.lock().unwrap_or_else(|e| e.into_inner());
+        let lists = self.lists.lock().unwrap_or_else(|e| e.into_inner());
 
         markets
             .into_iter()
             .filter(|m| {
-                // Liquidity check
-                if m.liquidity < min_liquidity {
+                // Closed at execution time in an earlier cycle
+                if closed.contains(&(m.platform.clone(), m.id.clone())) {
                     return false;
                 }
 
-                // Deadline checks
-                let hours_remaining =
-                    (m.deadline - now).num_minutes() as f64 / 60.0;
-
-                if hours_remaining < min_hours {
+                // Operator blocklist
+                if lists.is_blocked(&m.question) {
                     return false;
                 }
-                if hours_remaining > max_hours {
+
+                // Configured blacklist, which wins over the whitelist
+                if let Some(pattern) = self.blacklist.first_match(&m.question) {
+                    debug!(platform = %m.platform, market_id = %m.id, pattern, "Blacklisted market dropped");
                     return false;
                 }
 
+                // Whitelisted series skip the liquidity and deadline checks
+                let whitelisted = self.whitelist.first_match(&m.question).inspect(|pattern| {
+                    debug!(platform = %m.platform, market_id = %m.id, pattern, "Whitelisted market kept regardless of liquidity and deadline");
+                });
+                if whitelisted.is_none() {
+                    // Liquidity check
+                    if m.liquidity < min_liquidity {
+                        return false;
+                    }
+
+                    // Deadline checks
+                    let hours_remaining =
+                        (m.deadline - now).num_minutes() as f64 / 60.0;
+
+                    if hours_remaining < min_hours {
+                        return false;
+                    }
+                    if hours_remaining > max_hours {
+                        return false;
+                    }
+                }
+
                 // Price sanity: skip markets at extreme probabilities
                 // (very little edge to be found at 1% or 99%)
                 if m.current_price_yes < dec!(0.02) || m.current_price_yes > dec!(0.98) {
@@ -399,7 +1190,7 @@ impl MarketRouter {
     /// - Higher liquidity
     /// - More bettors / forecasters
     /// - Probability away from extremes (more room for edge)
-    fn priority_score(market: &Market) -> f64 {
+    pub fn priority_score(market: &Market) -> f64 {
         let mut score = 0.0;
 
         // Cross-reference bonus: markets with Metaculus data are richer
@@ -434,6 +1225,113 @@ impl MarketRouter {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Event clusters
+// ---------------------------------------------------------------------------
+
+/// Group markets on different platforms that ask about the same event.
+///
+/// Two markets match when they are on different platforms, share a
+/// category (or either is `Other`) and their questions score at least
+/// `match_threshold`; clusters are the connected components of matches.
+/// Returns clusters of two or more markets as ascending indices into
+/// `markets`.
+pub fn same_event_clusters(markets: &[Market], match_threshold: f64) -> Vec<Vec<usize>> {
+    let index = QuestionIndex::build(markets);
+    let mut parent: Vec<usize> = (0..markets.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..markets.len() {
+        for j in index.candidates(&index.tokens[i]).into_iter().filter(|&j| j > i) {
+            let (a, b) = (&markets[i], &markets[j]);
+            let same_category = a.category == b.category
+                || a.category == crate::types::MarketCategory::Other
+                || b.category == crate::types::MarketCategory::Other;
+            if a.platform == b.platform || !same_category {
+                continue;
+            }
+            if token_similarity(&index.tokens[i], &index.tokens[j]) >= match_threshold {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..markets.len() {
+        clusters.entry(root(&mut parent, i)).or_default().push(i);
+    }
+    let mut clusters: Vec<Vec<usize>> = clusters.into_values().filter(|c| c.len() > 1).collect();
+    clusters.sort();
+    clusters
+}
+
+/// Merge markets on different executable platforms that ask the same
+/// question (clusters at `threshold`, see [`same_event_clusters`]).
+///
+/// The most liquid market of each cluster is kept. The others are removed
+/// and listed in its `cross_refs.duplicates`, and point back at it through
+/// `cross_refs.duplicate_of`, so the agent holds one opinion per question
+/// and the risk manager sees bets on any of them as one exposure unit.
+/// Metaculus is informational and never merged. Returns the kept markets in
+/// their original order, and the merged ones.
+pub fn merge_duplicates(mut markets: Vec<Market>, threshold: f64) -> (Vec<Market>, Vec<Market>) {
+    let executable: Vec<usize> = (0..markets.len()).filter(|&i| markets[i].platform != "metaculus").collect();
+    let candidates: Vec<Market> = executable.iter().map(|&i| markets[i].clone()).collect();
+    let mut removed = HashSet::new();
+
+    for cluster in same_event_clusters(&candidates, threshold) {
+        let members: Vec<usize> = cluster.into_iter().map(|i| executable[i]).collect();
+        // Most liquid first; the earliest listed on a tie.
+        let primary = *members
+            .iter()
+            .max_by_key(|&&i| (markets[i].liquidity, std::cmp::Reverse(i)))
+            .expect("clusters have two or more members");
+        let key = link_key(&markets[primary].platform, &markets[primary].id);
+
+        for &i in members.iter().filter(|&&i| i != primary) {
+            let dup = &mut markets[i];
+            dup.cross_refs.duplicate_of = Some(key.clone());
+            let listing = DuplicateListing {
+                platform: dup.platform.clone(),
+                market_id: dup.id.clone(),
+                price_yes: dup.current_price_yes,
+                liquidity: dup.liquidity,
+            };
+            let (metaculus_prob, metaculus_forecasters) = (dup.cross_refs.metaculus_prob, dup.cross_refs.metaculus_forecasters);
+            info!(
+                kept = %key,
+                merged = %link_key(&listing.platform, &listing.market_id),
+                kept_price = %markets[primary].current_price_yes,
+                merged_price = %listing.price_yes,
+                "Duplicate market merged"
+            );
+
+            let refs = &mut markets[primary].cross_refs;
+            match listing.platform.as_str() {
+                "manifold" => refs.manifold_prob = refs.manifold_prob.or(Some(listing.price_yes)),
+                "forecastex" => refs.forecastex_price = refs.forecastex_price.or(Some(listing.price_yes)),
+                _ => {}
+            }
+            if refs.metaculus_prob.is_none() {
+                refs.metaculus_prob = metaculus_prob;
+                refs.metaculus_forecasters = metaculus_forecasters;
+            }
+            refs.duplicates.push(listing);
+            removed.insert(i);
+        }
+    }
+
+    let (merged, kept): (Vec<_>, Vec<_>) = markets.into_iter().enumerate().partition(|(i, _)| removed.contains(i));
+    (kept.into_iter().map(|(_, m)| m).collect(), merged.into_iter().map(|(_, m)| m).collect())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -441,7 +1339,11 @@ impl MarketRouter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::embeddings::store::EmbeddingStore;
+    use crate::embeddings::{Embedder, HashEmbedder};
     use crate::types::{d, MarketCategory};
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
     use chrono::Duration;
 
     // -- Text similarity tests -------------------------------------------
@@ -527,6 +1429,9 @@ mod tests {
             resolution_criteria: String::new(),
             url: format!("https://example.com/{id}"),
             cross_refs: CrossReferences::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         }
     }
 
@@ -543,6 +1448,68 @@ mod tests {
         m
     }
 
+    // -- Event cluster tests ---------------------------------------------
+
+    #[test]
+    fn test_same_event_clusters_span_platforms_only() {
+        let q = "Will the Lakers win the 2026 NBA Championship?";
+        let markets = [
+            make_market("bf", "betfair", q, MarketCategory::Sports, 0.30, 5000.0, 720.0),
+            make_market("pm", "polymarket", "Lakers win the 2026 NBA Championship?", MarketCategory::Sports, 0.32, 8000.0, 720.0),
+            make_market("mf", "manifold", q, MarketCategory::Sports, 0.35, 500.0, 720.0),
+            // Unrelated event.
+            make_market("bf2", "betfair", "Will Arsenal win the 2026 Premier League?", MarketCategory::Sports, 0.20, 5000.0, 720.0),
+            // Same wording, different category.
+            make_market("pol", "polymarket", q, MarketCategory::Politics, 0.30, 5000.0, 720.0),
+        ];
+        assert_eq!(same_event_clusters(&markets, 0.8), vec![vec![0, 1, 2]]);
+        assert!(same_event_clusters(&markets[..1], 0.8).is_empty());
+    }
+
+    #[test]
+    fn test_merge_duplicates_keeps_most_liquid_listing() {
+        let q = "Will the Fed cut rates at the December 2026 FOMC meeting?";
+        let markets = vec![
+            make_market("mf", "manifold", q, MarketCategory::Economics, 0.41, 900.0, 720.0),
+            make_market("pm", "polymarket", q, MarketCategory::Economics, 0.35, 25000.0, 720.0),
+            make_metaculus_market("mc", q, MarketCategory::Economics, 0.38, 150),
+            make_market("other", "manifold", "Will Bitcoin close 2026 above $150k?", MarketCategory::Economics, 0.2, 800.0, 720.0),
+        ];
+        let (kept, merged) = merge_duplicates(markets, 0.8);
+
+        // One bettable listing of the question survives; Metaculus is never merged.
+        let ids: Vec<&str> = kept.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["pm", "mc", "other"]);
+        let pm = &kept[0];
+        assert_eq!(pm.cross_refs.manifold_prob, Some(d(0.41)));
+        assert_eq!(pm.cross_refs.duplicates.len(), 1);
+        assert_eq!((pm.cross_refs.duplicates[0].platform.as_str(), pm.cross_refs.duplicates[0].market_id.as_str()), ("manifold", "mf"));
+        assert_eq!(pm.cross_refs.duplicates[0].price_yes, d(0.41));
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].cross_refs.duplicate_of.as_deref(), Some("polymarket:pm"));
+    }
+
+    #[test]
+    fn test_event_clusters_include_merged_duplicates() {
+        let q = "Will the Lakers win the 2026 NBA Championship?";
+        let router = MarketRouter::with_config(ScannerConfig::default(), None, None);
+        let (kept, merged) = merge_duplicates(
+            vec![
+                make_market("bf", "betfair", q, MarketCategory::Sports, 0.30, 5000.0, 720.0),
+                make_market("ks", "kalshi", q, MarketCategory::Sports, 0.33, 800.0, 720.0),
+            ],
+            0.8,
+        );
+        *router.merged.lock().unwrap() = merged;
+        let clusters = router.event_clusters(&kept);
+        assert_eq!(clusters.len(), 1);
+        let ids: Vec<&str> = clusters[0].iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["bf", "ks"]);
+        // Not when the kept market is gone from the scan.
+        assert!(router.event_clusters(&[]).is_empty());
+    }
+
     // -- Cross-referencing tests -----------------------------------------
 
     #[test]
@@ -655,6 +1622,270 @@ mod tests {
         assert!(manifold[0].cross_refs.metaculus_prob.is_none());
     }
 
+    // -- Candidate generation / comparison cap --------------------------
+
+    /// The pre-index implementation, kept verbatim as a regression oracle.
+    fn legacy_text_similarity(a: &str, b: &str) -> f64 {
+        let norm = |s: &str| -> Vec<String> {
+            s.to_lowercase()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| w.len() > 2)
+                .map(String::from)
+                .collect()
+        };
+        let words_a = norm(a);
+        let words_b = norm(b);
+        if words_a.is_empty() || words_b.is_empty() {
+            return 0.0;
+        }
+        let set_a: HashSet<&str> = words_a.iter().map(|s| s.as_str()).collect();
+        let set_b: HashSet<&str> = words_b.iter().map(|s| s.as_str()).collect();
+        let intersection = set_a.intersection(&set_b).count() as f64;
+        let union = set_a.union(&set_b).count() as f64;
+        let jaccard = if union > 0.0 { intersection / union } else { 0.0 };
+        let containment = if set_a.len() <= set_b.len() {
+            intersection / set_a.len() as f64
+        } else {
+            intersection / set_b.len() as f64
+        };
+        (0.6 * jaccard + 0.4 * containment).min(1.0)
+    }
+
+    const FIXTURE_QUESTIONS: &[&str] = &[
+        "Will Trump win the 2028 election?",
+        "Will Trump win the 2028 presidential election?",
+        "Will Donald Trump win the US 2028 presidential election?",
+        "Will US CPI exceed 3% in Q2 2026?",
+        "US inflation CPI above 3 percent Q2 2026",
+        "Will California experience an earthquake before 2030?",
+        "Will Trump finish his second term?",
+        "Will Trump finish his second presidential term in office?",
+        "Will Trump finish his second term as president?",
+        "Will the Thunder win the NBA finals?",
+        "TRUMP ELECTION",
+        "a in to",
+        "",
+    ];
+
+    #[test]
+    fn test_similarity_matches_legacy_on_fixtures() {
+        for a in FIXTURE_QUESTIONS {
+            for b in FIXTURE_QUESTIONS {
+                assert_eq!(
+                    text_similarity(a, b).to_bits(),
+                    legacy_text_similarity(a, b).to_bits(),
+                    "score drift for {a:?} vs {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cross_reference_matches_brute_force_on_fixtures() {
+        let metaculus: Vec<Market> = FIXTURE_QUESTIONS
+            .iter()
+            .enumerate()
+            .map(|(i, q)| make_metaculus_market(&format!("mc{i}"), q, MarketCategory::Other, 0.5, 10 + i as u32))
+            .collect();
+        let mut manifold: Vec<Market> = FIXTURE_QUESTIONS
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, q)| make_market(&format!("mf{i}"), "manifold", q, MarketCategory::Politics, 0.5, 100.0, 720.0))
+            .collect();
+
+        let expected: Vec<Option<u32>> = manifold
+            .iter()
+            .map(|mf| {
+                let mut best = (0.0f64, None);
+                for mc in &metaculus {
+                    let score = legacy_text_similarity(&mf.question, &mc.question);
+                    if score > best.0 {
+                        best = (score, mc.cross_refs.metaculus_forecasters);
+                    }
+                }
+                if best.0 >= 0.45 { best.1 } else { None }
+            })
+            .collect();
+
+        MarketRouter::cross_reference(&mut manifold, &metaculus, 0.45);
+
+        let got: Vec<Option<u32>> = manifold.iter().map(|m| m.cross_refs.metaculus_forecasters).collect();
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn test_stopwords_alone_do_not_make_candidates() {
+        let metaculus = vec![make_metaculus_market(
+            "mc1", "Will the Fed cut rates?", MarketCategory::Other, 0.5, 10,
+        )];
+        let index = QuestionIndex::build(&metaculus);
+        assert!(index.candidates(&tokenize("Will the Lakers win?")).is_empty());
+        assert_eq!(index.candidates(&tokenize("Fed rate cut this year?")), vec![0]);
+    }
+
+    /// Deterministic synthetic question: three topic words out of a 400-word
+    /// vocabulary plus the usual boilerplate ("will", "before", a year).
+    fn synthetic_question(seed: usize) -> String {
+        let w = |k: usize| format!("topic{}", (seed.wrapping_mul(2654435761).wrapping_add(k * 40503)) % 400);
+        format!("Will {} {} {} happen before {}?", w(1), w(2), w(3), 2026 + seed % 4)
+    }
+
+    #[test]
+    fn test_candidate_generation_reduces_comparisons_tenfold() {
+        let metaculus: Vec<Market> = (0..500)
+            .map(|i| make_metaculus_market(&format!("mc{i}"), &synthetic_question(i), MarketCategory::Other, 0.5, 10))
+            .collect();
+        let mut manifold: Vec<Market> = (0..500)
+            .map(|i| make_market(&format!("mf{i}"), "manifold", &synthetic_question(i + 10_000), MarketCategory::Other, 0.5, 100.0, 720.0))
+            .collect();
+
+        let full_cross_product = manifold.len() * metaculus.len();
+        let comparisons = MarketRouter::cross_reference_capped(&mut manifold, &metaculus, 0.45, usize::MAX);
+
+        assert!(comparisons > 0, "synthetic corpus should produce some candidates");
+        assert!(
+            comparisons * 10 <= full_cross_product,
+            "{comparisons} comparisons is not an order of magnitude below {full_cross_product}"
+        );
+    }
+
+    #[test]
+    fn test_comparison_cap_prioritises_liquid_markets() {
+        let metaculus = vec![make_metaculus_market(
+            "mc1", "Will Trump finish his second term?", MarketCategory::Politics, 0.68, 150,
+        )];
+        let mut manifold = vec![
+            make_market("thin", "manifold", "Will Trump finish his second term?", MarketCategory::Politics, 0.5, 10.0, 720.0),
+            make_market("deep", "manifold", "Will Trump finish his second term?", MarketCategory::Politics, 0.5, 1000.0, 720.0),
+        ];
+
+        let comparisons = MarketRouter::cross_reference_capped(&mut manifold, &metaculus, 0.45, 1);
+
+        assert_eq!(comparisons, 1);
+        assert!(manifold[1].cross_refs.metaculus_prob.is_some(), "liquid market compared first");
+        assert!(manifold[0].cross_refs.metaculus_prob.is_none(), "budget exhausted before thin market");
+    }
+
+    // -- Embedding cross-reference tests -----------------------------------
+
+    /// [`HashEmbedder`] counting the questions it is asked to embed.
+    struct CountingEmbedder {
+        inner: HashEmbedder,
+        embedded: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Embedder for CountingEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.embedded.fetch_add(texts.len(), Ordering::Relaxed);
+            self.inner.embed(texts).await
+        }
+
+        fn model_name(&self) -> &str {
+            self.inner.model_name()
+        }
+    }
+
+    struct FailingEmbedder;
+
+    #[async_trait::async_trait]
+    impl Embedder for FailingEmbedder {
+        async fn embed(&self, _: &[String]) -> Result<Vec<Vec<f32>>> {
+            anyhow::bail!("embeddings endpoint unavailable")
+        }
+
+        fn model_name(&self) -> &str {
+            "failing"
+        }
+    }
+
+    /// `embedder` behind an empty cache in a temporary file.
+    fn matcher(embedder: Box<dyn Embedder>, threshold: f64) -> EmbeddingMatcher {
+        let path = std::env::temp_dir().join(format!("oracle_scanner_embeddings_{}.json", uuid::Uuid::new_v4()));
+        let store = EmbeddingStore::new(path, embedder.model_name(), 100);
+        EmbeddingMatcher::new(embedder, store, threshold)
+    }
+
+    /// Router whose word-overlap threshold no reworded question reaches,
+    /// matching by `embedder` at a cosine of 0.5.
+    fn embedding_router(embedder: Box<dyn Embedder>) -> MarketRouter {
+        let config = ScannerConfig { match_threshold: 0.95, ..ScannerConfig::default() };
+        MarketRouter::with_config(config, None, None).with_embeddings(matcher(embedder, 0.5))
+    }
+
+    fn fed_markets() -> (Vec<Market>, Vec<Market>) {
+        let manifold = vec![
+            make_market("mf1", "manifold", "Will the Fed cut rates in March 2026?", MarketCategory::Economics, 0.4, 500.0, 720.0),
+            make_market("mf2", "manifold", "Will it snow in London on Christmas Day?", MarketCategory::Weather, 0.1, 300.0, 720.0),
+        ];
+        let metaculus = vec![
+            make_metaculus_market("mc1", "Will the Lakers win the NBA finals?", MarketCategory::Other, 0.2, 90),
+            make_metaculus_market("mc2", "Fed cuts interest rates at the March 2026 meeting?", MarketCategory::Economics, 0.55, 120),
+        ];
+        (manifold, metaculus)
+    }
+
+    #[tokio::test]
+    async fn test_embedding_match_uses_its_own_threshold() {
+        let router = embedding_router(Box::new(HashEmbedder::new(256)));
+        let (mut manifold, metaculus) = fed_markets();
+
+        let matched = router.cross_reference_configured(&mut manifold, &metaculus).await;
+
+        assert_eq!(matched, HashSet::from([1]));
+        assert_eq!(manifold[0].cross_refs.metaculus_prob, Some(d(0.55)));
+        assert!(manifold[1].cross_refs.metaculus_prob.is_none(), "unrelated question left unmatched");
+
+        // Word overlap at the same scanner threshold misses the rewording.
+        let (mut manifold, metaculus) = fed_markets();
+        MarketRouter::cross_reference(&mut manifold, &metaculus, 0.95);
+        assert!(manifold[0].cross_refs.metaculus_prob.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_are_cached_across_cycles() {
+        let embedded = Arc::new(AtomicUsize::new(0));
+        let embedder = CountingEmbedder { inner: HashEmbedder::new(256), embedded: embedded.clone() };
+        let router = embedding_router(Box::new(embedder));
+
+        for _ in 0..2 {
+            let (mut manifold, metaculus) = fed_markets();
+            router.cross_reference_configured(&mut manifold, &metaculus).await;
+            assert_eq!(manifold[0].cross_refs.metaculus_prob, Some(d(0.55)));
+        }
+
+        // Four questions embedded once; the second cycle is all cache hits.
+        assert_eq!(embedded.load(Ordering::Relaxed), 4);
+        let matcher = router.embeddings.as_ref().unwrap();
+        assert_eq!(matcher.cache_stats(), (4, 4));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_word_overlap_without_embeddings() {
+        let trump = |manifold_q: &str| {
+            let manifold = vec![make_market("mf1", "manifold", manifold_q, MarketCategory::Politics, 0.75, 200.0, 720.0)];
+            let metaculus = vec![make_metaculus_market(
+                "mc1", "Will Trump finish his second term as president?", MarketCategory::Politics, 0.68, 80,
+            )];
+            (manifold, metaculus)
+        };
+        let question = "Will Trump finish his second presidential term in office?";
+
+        // Embedding fails: the cycle still cross-references, by word overlap.
+        let failing = MarketRouter::with_config(ScannerConfig::default(), None, None)
+            .with_embeddings(matcher(Box::new(FailingEmbedder), 0.99));
+        let (mut manifold, metaculus) = trump(question);
+        assert!(failing.cross_reference_configured(&mut manifold, &metaculus).await.is_empty());
+        assert_eq!(manifold[0].cross_refs.metaculus_prob, Some(d(0.68)));
+
+        // Embeddings disabled: word overlap at `scanner.match_threshold`.
+        let plain = MarketRouter::with_config(ScannerConfig::default(), None, None);
+        let (mut manifold, metaculus) = trump(question);
+        plain.cross_reference_configured(&mut manifold, &metaculus).await;
+        assert_eq!(manifold[0].cross_refs.metaculus_prob, Some(d(0.68)));
+    }
+
     // -- Filter tests ----------------------------------------------------
 
     #[test]
@@ -694,6 +1925,148 @@ mod tests {
         assert_eq!(filtered[0].id, "ok");
     }
 
+    #[test]
+    fn test_filter_removes_markets_marked_closed() {
+        let router = MarketRouter::new(None, None);
+        router.mark_closed("manifold", "gone");
+        let markets = vec![
+            make_market("ok", "manifold", "Normal", MarketCategory::Politics, 0.5, 100.0, 720.0),
+            make_market("gone", "manifold", "Closed mid-cycle", MarketCategory::Politics, 0.5, 100.0, 720.0),
+            // Same ID on another platform is unaffected
+            make_market("gone", "betfair", "Other venue", MarketCategory::Politics, 0.5, 100.0, 720.0),
+        ];
+        let filtered = router.filter_markets(markets);
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|m| !(m.id == "gone" && m.platform == "manifold")));
+    }
+
+    #[test]
+    fn test_blocklist_filters_and_watchlist_leads() {
+        let router = MarketRouter::new(None, None);
+        router.set_lists(
+            MarketLists::parse(r#"markets = ["manifold:pinned"]"#, r#"patterns = ["(?i)\\btweet"]"#).unwrap(),
+        );
+        let markets = vec![
+            make_market("rich", "manifold", "Will X win?", MarketCategory::Politics, 0.5, 5000.0, 720.0),
+            make_market("noise", "manifold", "Will Elon tweet 50 times?", MarketCategory::Politics, 0.5, 5000.0, 720.0),
+            make_market("pinned", "manifold", "Will Y win?", MarketCategory::Politics, 0.5, 10.0, 720.0),
+        ];
+        let mut filtered = router.filter_markets(markets);
+        assert_eq!(filtered.len(), 2);
+        router.sort_for_processing(&mut filtered);
+        assert_eq!(filtered[0].id, "pinned");
+
+        // Reloaded lists apply from the next scan.
+        router.set_lists(MarketLists::default());
+        router.sort_for_processing(&mut filtered);
+        assert_eq!(filtered[0].id, "rich");
+    }
+
+    #[test]
+    fn test_markets_deferred_for_cost_lead_next_scan() {
+        let router = MarketRouter::new(None, None);
+        let mut markets = vec![
+            make_market("rich", "manifold", "Will X win?", MarketCategory::Politics, 0.5, 5000.0, 720.0),
+            make_market("thin", "manifold", "Will Y win?", MarketCategory::Politics, 0.5, 10.0, 720.0),
+        ];
+        router.defer(&markets[1..]);
+        router.sort_for_processing(&mut markets);
+        assert_eq!(markets[0].id, "thin");
+
+        // Each cycle replaces the set
+        router.defer(&[]);
+        router.sort_for_processing(&mut markets);
+        assert_eq!(markets[0].id, "rich");
+    }
+
+    #[test]
+    fn test_blacklist_beats_whitelist_and_whitelist_bypasses_filters() {
+        let config = ScannerConfig {
+            blacklist_patterns: vec!["resolve yes".into(), "/^will @\\w+ /".into()],
+            whitelist_patterns: vec!["/^asx 200 close/".into(), "resolve".into()],
+            ..ScannerConfig::default()
+        };
+        let router = MarketRouter::with_config(config, None, None);
+        let markets = vec![
+            // Whitelisted: thin and closing within the hour, still kept
+            make_market("thin", "manifold", "ASX 200 close above 8000?", MarketCategory::Economics, 0.5, 1.0, 0.5),
+            // ...but not at an extreme price
+            make_market("extreme", "manifold", "ASX 200 close above 9000?", MarketCategory::Economics, 0.99, 1.0, 720.0),
+            // Both lists match: the blacklist wins
+            make_market("self", "manifold", "Will this market RESOLVE YES?", MarketCategory::Other, 0.5, 5000.0, 720.0),
+            make_market("user", "manifold", "Will @alice finish her novel?", MarketCategory::Other, 0.5, 5000.0, 720.0),
+            // A substring is not a regex: "@alice" mid-question is not anchored
+            make_market("mention", "manifold", "Does Bob follow @alice now?", MarketCategory::Other, 0.5, 5000.0, 720.0),
+            // Unlisted and thin: dropped as usual
+            make_market("plain", "manifold", "Will it rain?", MarketCategory::Weather, 0.5, 1.0, 720.0),
+        ];
+        let kept: Vec<String> = router.filter_markets(markets).into_iter().map(|m| m.id).collect();
+        assert_eq!(kept, vec!["thin", "mention"]);
+    }
+
+    #[test]
+    fn test_other_languages_dropped_unless_allowed() {
+        let question = |id: &str, q: &str| make_market(id, "manifold", q, MarketCategory::Other, 0.5, 100.0, 720.0);
+        let markets = || {
+            vec![
+                question("en", "Will the Fed cut rates before the end of 2026?"),
+                question("es", "¿Ganará el Real Madrid la Liga antes del fin de la temporada?"),
+                question("ru", "Путин останется президентом до конца 2026 года?"),
+                question("short", "TRUMP ELECTION"),
+            ]
+        };
+        let kept = |config: ScannerConfig| {
+            let mut markets = markets();
+            let dropped = MarketRouter::with_config(config, None, None).drop_other_languages(&mut markets);
+            (dropped, markets.into_iter().map(|m| m.id).collect::<Vec<_>>())
+        };
+        assert_eq!(kept(ScannerConfig::default()), (2, vec!["en".to_string(), "short".to_string()]));
+        let spanish = ScannerConfig { allowed_languages: vec!["en".into(), "es".into()], ..ScannerConfig::default() };
+        assert_eq!(kept(spanish).0, 1);
+        assert_eq!(kept(ScannerConfig { allowed_languages: Vec::new(), ..ScannerConfig::default() }).0, 0);
+    }
+
+    #[test]
+    fn test_similarity_ignores_typography_and_tags() {
+        assert_eq!(text_similarity("Will \u{201C}Barbie\u{201D} top the box office?", "Will \"Barbie\" top the box office?"), 1.0);
+        assert_eq!(text_similarity("[Resolves to poll] Will Pok\u{0065}\u{0301}mon Legends sell 10M?", "Will Pok\u{00E9}mon Legends sell 10M?"), 1.0);
+    }
+
+    #[test]
+    fn test_category_caps_apply_after_priority_sort() {
+        let config = ScannerConfig {
+            max_markets_to_process: 3,
+            max_per_category: HashMap::from([("sports".to_string(), 2)]),
+            ..ScannerConfig::default()
+        };
+        let router = MarketRouter::with_config(config, None, None);
+        let market = |id: &str, category, liquidity| {
+            make_market(id, "manifold", &format!("{id}?"), category, 0.5, liquidity, 720.0)
+        };
+        let markets = vec![
+            market("s1", MarketCategory::Sports, 100.0),
+            market("s2", MarketCategory::Sports, 4000.0),
+            market("s3", MarketCategory::Sports, 200.0),
+            market("s4", MarketCategory::Sports, 3000.0),
+            market("p1", MarketCategory::Politics, 1000.0),
+            market("p2", MarketCategory::Politics, 50.0),
+        ];
+
+        // Sports keeps its two most liquid; the total cap then drops p2.
+        let (kept, summary) = router.apply_caps(router.filter_and_sort(markets.clone()));
+        let ids: Vec<&str> = kept.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["s2", "s4", "p1"]);
+        assert_eq!(summary.filtered, 6);
+        assert_eq!(summary.kept, BTreeMap::from([("Politics".to_string(), 1), ("Sports".to_string(), 2)]));
+        assert_eq!(summary.dropped, BTreeMap::from([("Politics".to_string(), 1), ("Sports".to_string(), 2)]));
+
+        // A watched market leads and takes one of the category's places.
+        router.set_lists(MarketLists::parse(r#"markets = ["manifold:s1"]"#, "").unwrap());
+        let (kept, _) = router.apply_caps(router.filter_and_sort(markets));
+        let ids: Vec<&str> = kept.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["s1", "s2", "p1"]);
+    }
+
     // -- Priority scoring tests ------------------------------------------
 
     #[test]
@@ -748,4 +2121,144 @@ mod tests {
         assert!(router.manifold.is_some());
         assert!(router.metaculus.is_some());
     }
+
+    // -- Hibernation tests -----------------------------------------------
+
+    fn hibernating_router(after: u32, every: u32) -> MarketRouter {
+        let config = ScannerConfig {
+            hibernate_after_empty_scans: after,
+            hibernate_scan_every: every,
+            ..ScannerConfig::default()
+        };
+        MarketRouter::with_config(config, None, None)
+    }
+
+    /// Run `cycles` scan cycles in which `platform` yields `markets` when
+    /// scanned; returns which cycles actually scanned it.
+    fn simulate(router: &MarketRouter, platform: &str, cycles: usize, markets: usize) -> Vec<bool> {
+        (0..cycles)
+            .map(|_| {
+                let due = router.due_for_scan(platform);
+                if due {
+                    router.record_scan(platform, markets);
+                }
+                due
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_consecutive_empty_scans_hibernate_platform() {
+        let router = hibernating_router(3, 4);
+        assert_eq!(simulate(&router, "betfair", 3, 0), vec![true; 3]);
+        assert!(router.hibernation()["betfair"].hibernating);
+        assert_eq!(router.hibernation_notes(), vec!["betfair: hibernating, next scan in 4 cycles"]);
+
+        // Scanned every 4th cycle while nothing turns up.
+        let scans = simulate(&router, "betfair", 8, 0);
+        assert_eq!(scans, vec![false, false, false, true, false, false, false, true]);
+        assert_eq!(router.hibernation_notes(), vec!["betfair: hibernating, next scan in 4 cycles"]);
+        simulate(&router, "betfair", 1, 0);
+        assert_eq!(router.hibernation_notes(), vec!["betfair: hibernating, next scan in 3 cycles"]);
+
+        // Other platforms are unaffected.
+        assert!(router.due_for_scan("manifold"));
+    }
+
+    #[test]
+    fn test_markets_found_resume_every_cycle_scans() {
+        let router = hibernating_router(2, 3);
+        simulate(&router, "betfair", 2, 0);
+        assert!(router.hibernation()["betfair"].hibernating);
+
+        // The scheduled scan finds markets: back to scanning every cycle.
+        assert_eq!(simulate(&router, "betfair", 3, 5), vec![false, false, true]);
+        assert_eq!(router.hibernation()["betfair"], PlatformHibernation::default());
+        assert_eq!(simulate(&router, "betfair", 3, 5), vec![true; 3]);
+
+        // A non-empty scan also resets the empty-scan count.
+        simulate(&router, "betfair", 1, 0);
+        simulate(&router, "betfair", 1, 5);
+        simulate(&router, "betfair", 1, 0);
+        assert!(!router.hibernation()["betfair"].hibernating);
+    }
+
+    #[test]
+    fn test_wake_scans_on_next_cycle() {
+        let router = hibernating_router(2, 10);
+        simulate(&router, "betfair", 2, 0);
+        assert!(!router.due_for_scan("betfair"));
+
+        assert!(router.wake("betfair"));
+        assert!(!router.wake("betfair"), "already awake");
+        assert!(!router.wake("manifold"), "never hibernated");
+        assert!(router.hibernation_notes().is_empty());
+        assert!(router.due_for_scan("betfair"));
+
+        // Woken platforms need a full run of empty scans to hibernate again.
+        router.record_scan("betfair", 0);
+        assert!(router.due_for_scan("betfair"));
+    }
+
+    #[test]
+    fn test_hibernation_state_round_trips() {
+        let router = hibernating_router(2, 5);
+        simulate(&router, "betfair", 3, 0);
+        let saved = router.hibernation();
+
+        let restored = hibernating_router(2, 5);
+        restored.restore_hibernation(saved.clone());
+        assert_eq!(restored.hibernation(), saved);
+        assert!(!restored.due_for_scan("betfair"));
+
+        // Disabled: never hibernates.
+        let off = hibernating_router(0, 5);
+        assert_eq!(simulate(&off, "betfair", 20, 0), vec![true; 20]);
+    }
+
+    #[tokio::test]
+    async fn test_platform_skipped_on_non_due_cycles_reuses_last_list() {
+        let router = MarketRouter::new(None, None).with_scan_intervals(HashMap::from([("metaculus".to_string(), 3)]));
+        let fetches = std::sync::atomic::AtomicUsize::new(0);
+        let mut fetched_at = None;
+        let mut log = Vec::new();
+        for cycle in 1..=7 {
+            let fetch = async {
+                // Each fetch returns one more market than the last.
+                let n = fetches.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok((0..n).map(|i| make_market(&format!("q{i}"), "metaculus", "Q?", MarketCategory::Politics, 0.5, 0.0, 48.0)).collect())
+            };
+            let scan = router.fetch_scheduled("metaculus", true, false, cycle, fetch).await;
+            let markets = scan.markets.unwrap().len();
+            match &scan.reused {
+                None => fetched_at = Some(router.scan_cache.lock().unwrap()["metaculus"].fetched_at),
+                Some(reused) => {
+                    assert_eq!(reused.markets, markets);
+                    assert_eq!(Some(reused.fetched_at), fetched_at);
+                    assert!(scan.secs.is_none() && !scan.scanned);
+                }
+            }
+            log.push((scan.reused.is_some(), markets));
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 3, "fetched on cycles 1, 4 and 7");
+        assert_eq!(
+            log,
+            vec![(false, 1), (true, 1), (true, 1), (false, 2), (true, 2), (true, 2), (false, 3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_every_cycle_platform_is_not_cached() {
+        let router = MarketRouter::new(None, None).with_scan_intervals(HashMap::from([("metaculus".to_string(), 3)]));
+        for cycle in 1..=2 {
+            let scan = router.fetch_scheduled("manifold", true, true, cycle, async { Ok(Vec::new()) }).await;
+            assert!(scan.scanned && scan.reused.is_none());
+        }
+        // A failed fetch is not cached either: the next cycle retries.
+        let failed = router.fetch_scheduled("metaculus", true, false, 1, async { anyhow::bail!("down") }).await;
+        assert!(failed.markets.is_err());
+        let retry = router.fetch_scheduled("metaculus", true, false, 2, async { Ok(Vec::new()) }).await;
+        assert!(retry.scanned);
+        assert!(router.scan_cache.lock().unwrap().keys().eq(["metaculus"]));
+    }
 }
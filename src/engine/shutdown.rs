@@ -0,0 +1,281 @@
+//! Graceful shutdown.
+//!
+//! Ctrl+C sets a [`Shutdown`] flag rather than dropping the loop where it
+//! stands. The cycle in flight checks the flag between pipeline stages and
+//! skips the ones that start new work (enrichment, estimation), but always
+//! runs its execution, reconciliation and save, so the books match the
+//! venues on exit. [`finish_within`] gives it `agent.shutdown_timeout_secs`
+//! to get there; past that the cycle is abandoned and the state saved
+//! with a dirty-shutdown marker, which makes the next startup [`repair`]
+//! the state from the venues' positions and balances.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::engine::accountant::Accountant;
+use crate::types::{AgentState, Position, TradeReceipt};
+
+/// Shared shutdown flag. Clones observe the same flag.
+#[derive(Clone)]
+pub struct Shutdown {
+    flag: Arc<watch::Sender<bool>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self { flag: Arc::new(watch::channel(false).0) }
+    }
+
+    /// A flag set by the first Ctrl+C.
+    pub fn on_ctrl_c() -> Self {
+        let shutdown = Self::new();
+        let handle = shutdown.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                info!("Shutdown signal received — finishing the current cycle");
+                handle.request();
+            }
+        });
+        shutdown
+    }
+
+    pub fn request(&self) {
+        self.flag.send_replace(true);
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.flag.borrow()
+    }
+
+    /// Resolves once shutdown has been requested.
+    pub async fn requested(&self) {
+        let mut rx = self.flag.subscribe();
+        // The sender lives in `self`, so the channel can't close under us.
+        let _ = rx.wait_for(|&requested| requested).await;
+    }
+
+    /// Check between pipeline stages: true (and logged) when `stage`
+    /// should be skipped because shutdown has been requested.
+    pub fn skips(&self, stage: &str) -> bool {
+        let requested = self.is_requested();
+        if requested {
+            info!(stage, "Shutdown requested — skipping");
+        }
+        requested
+    }
+}
+
+/// Run `work` to completion, unless shutdown is requested while it runs
+/// and it is still running `grace` later: then it is dropped and `None`
+/// returned.
+pub async fn finish_within<F: Future>(shutdown: &Shutdown, grace: Duration, work: F) -> Option<F::Output> {
+    let deadline = async {
+        shutdown.requested().await;
+        tokio::time::sleep(grace).await;
+    };
+    tokio::select! {
+        output = work => Some(output),
+        _ = deadline => {
+            warn!(grace_secs = grace.as_secs(), "Cycle did not finish within the shutdown timeout — abandoned");
+            None
+        }
+    }
+}
+
+/// Record an abandoned cycle: the next startup repairs the state before
+/// trading.
+pub fn mark_dirty(state: &mut AgentState, now: DateTime<Utc>) {
+    state.dirty_shutdown = Some(now);
+}
+
+/// What [`repair`] changed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Repair {
+    /// Unconfirmed orders found on a venue.
+    pub confirmed: usize,
+    /// Unconfirmed orders given up on.
+    pub dropped: usize,
+    /// Venue positions the state had no bet for.
+    pub adopted: usize,
+}
+
+/// Bring a state left by a dirty shutdown back in line with the venues:
+/// settle unconfirmed orders, book positions the abandoned cycle placed but
+/// never recorded as open bets, take the venues' balances, and clear the
+/// marker. Positions on accounts also traded outside the agent should be
+/// left out of `positions`, or they would be adopted as the agent's.
+pub fn repair(
+    state: &mut AgentState,
+    positions: &[Position],
+    balances: BTreeMap<String, Decimal>,
+    now: DateTime<Utc>,
+) -> Repair {
+    let (confirmed, dropped) = Accountant::settle_unconfirmed(state, positions, now);
+    let mut adopted = 0;
+    for position in positions {
+        let tracked = state.open_bets.iter().chain(&state.unconfirmed_bets).any(|b| {
+            b.platform == position.platform && b.market_id == position.market_id && b.side == position.side
+        });
+        if tracked {
+            continue;
+        }
+        warn!(market_id = %position.market_id, platform = %position.platform, "Untracked venue position — booked as open");
+        state.open_bets.push(recovered_receipt(position, now));
+        state.trades_placed += 1;
+        adopted += 1;
+    }
+    Accountant::record_balances(state, balances);
+    state.update_equity();
+    state.dirty_shutdown = None;
+    Repair { confirmed, dropped, adopted }
+}
+
+fn recovered_receipt(position: &Position, now: DateTime<Utc>) -> TradeReceipt {
+    let amount = if position.cost > Decimal::ZERO { position.cost } else { position.size * position.entry_price };
+    TradeReceipt {
+        order_id: format!("recovered-{}-{}", position.platform, position.market_id),
+        market_id: position.market_id.clone(),
+        platform: position.platform.clone(),
+        side: position.side,
+        amount,
+        fill_price: position.entry_price,
+        fees: Decimal::ZERO,
+        timestamp: now,
+        currency: if position.platform == "manifold" { "Mana".to_string() } else { TradeReceipt::default_currency() },
+        deadline: None,
+        category: position.category,
+        edge: None,
+        raw_response: None,
+        risk_context: None,
+        tags: Vec::new(),
+        correlation_key: None,
+        expected_price: None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::accountant::CycleCosts;
+    use crate::engine::executor::ExecutionReport;
+    use crate::engine::state::SharedState;
+    use crate::storage::backend::Storage;
+    use crate::storage::sqlite::SqliteStorage;
+    use crate::types::Side;
+    use rust_decimal_macros::dec;
+
+    fn no_execution() -> ExecutionReport {
+        ExecutionReport {
+            executed: Vec::new(),
+            failed: Vec::new(),
+            unconfirmed: Vec::new(),
+            total_committed: Decimal::ZERO,
+            total_commission: Decimal::ZERO,
+            duplicate_fills: 0,
+        }
+    }
+
+    /// A cycle shaped like the live one: a slow enrichment stage, then
+    /// estimation unless shutdown was requested, then the reconcile and
+    /// save that always run.
+    async fn simulated_cycle(state: &mut AgentState, shared: &SharedState, shutdown: &Shutdown, enrich: Duration) -> bool {
+        let enriched = !shutdown.skips("enrichment");
+        tokio::time::sleep(enrich).await;
+        let estimated = !shutdown.skips("estimation");
+        let costs = CycleCosts { data_cost: dec!(0.5), ..Default::default() };
+        Accountant::reconcile(state, &no_execution(), &costs);
+        state.last_cycle_time = Some(Utc::now());
+        shared.commit(state).await.unwrap();
+        enriched && estimated
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_during_enrichment_completes_cycle() {
+        let storage = Arc::new(SqliteStorage::open_in_memory().await.unwrap());
+        let mut state = AgentState::new(dec!(100));
+        let shared = SharedState::new(state.clone()).persisted(storage.clone());
+        let shutdown = Shutdown::new();
+
+        let signal = {
+            let shutdown = shutdown.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                shutdown.request();
+            }
+        };
+        let cycle = simulated_cycle(&mut state, &shared, &shutdown, Duration::from_millis(80));
+        let (finished, ()) = tokio::join!(finish_within(&shutdown, Duration::from_secs(5), cycle), signal);
+
+        // Enrichment was under way; estimation was skipped.
+        assert_eq!(finished, Some(false));
+        let saved = storage.load_state().await.unwrap().unwrap();
+        assert_eq!(saved.cycle_count, 1);
+        assert_eq!(saved.bankroll, dec!(99.5));
+        assert!(saved.last_cycle_time.is_some());
+        assert!(saved.dirty_shutdown.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cycle_past_timeout_is_abandoned() {
+        let shutdown = Shutdown::new();
+        shutdown.request();
+        let hung = tokio::time::sleep(Duration::from_secs(60));
+        let start = std::time::Instant::now();
+        assert!(finish_within(&shutdown, Duration::from_millis(20), hung).await.is_none());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // Without a request the work runs to the end.
+        let calm = Shutdown::new();
+        assert_eq!(finish_within(&calm, Duration::ZERO, async { 7 }).await, Some(7));
+    }
+
+    fn position(market_id: &str, cost: Decimal) -> Position {
+        Position {
+            market_id: market_id.to_string(),
+            platform: "betfair".to_string(),
+            side: Side::Yes,
+            size: cost * dec!(2),
+            entry_price: dec!(0.5),
+            current_value: cost,
+            cost,
+            category: None,
+        }
+    }
+
+    #[test]
+    fn test_repair_books_untracked_positions_and_clears_marker() {
+        let now = Utc::now();
+        let mut state = AgentState::new(dec!(100));
+        let mut known = TradeReceipt::dry_run("m1", dec!(5), "AUD");
+        known.platform = "betfair".to_string();
+        state.open_bets.push(known);
+        mark_dirty(&mut state, now);
+
+        let balances = BTreeMap::from([("betfair".to_string(), dec!(87))]);
+        let repair = repair(&mut state, &[position("m1", dec!(5)), position("m2", dec!(8))], balances, now);
+
+        assert_eq!(repair, Repair { confirmed: 0, dropped: 0, adopted: 1 });
+        assert_eq!(state.open_bets.len(), 2);
+        let adopted = &state.open_bets[1];
+        assert_eq!((adopted.market_id.as_str(), adopted.amount, adopted.currency.as_str()), ("m2", dec!(8), "AUD"));
+        assert_eq!(state.balances["betfair"], dec!(87));
+        assert!(state.dirty_shutdown.is_none());
+    }
+}
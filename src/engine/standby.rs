@@ -0,0 +1,393 @@
+//! Warm standby.
+//!
+//! A standby agent keeps running the scan → enrich → estimate pipeline
+//! every cycle, so caches, scan hibernation and the decision history stay
+//! warm, but the executor places nothing and the loop skips every step
+//! that changes the trading ledger: resolution processing, auto-exits and
+//! Mana reconciliation. API spend is still charged to the bankroll — it is
+//! real. Unlike pause, estimation continues.
+//!
+//! Standby is persisted as [`AgentStatus::Standby`], so a restarted agent
+//! comes back in standby. Resuming first re-checks every venue's balance
+//! and positions against the ledger and stays in standby if a venue can't
+//! be reached.
+
+use std::collections::BTreeSet;
+
+use futures::future::join_all;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::engine::executor::Executor;
+use crate::platforms::PredictionPlatform;
+use crate::types::{AgentState, AgentStatus, Position, TradeReceipt};
+
+/// Operator request to change mode, applied at the start of the next tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeRequest {
+    Standby,
+    /// Stop running cycles (see [`crate::engine::pause`]).
+    Pause,
+    /// Leave standby or pause.
+    Resume,
+    /// Leave pause even if the drawdown has not recovered.
+    ForceResume,
+}
+
+/// One venue's balance and positions against the ledger.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VenueCheck {
+    pub platform: String,
+    pub balance: Option<Decimal>,
+    /// What the ledger holds for that balance, where it tracks one
+    /// (Manifold's Mana bankroll).
+    pub ledger_balance: Option<Decimal>,
+    /// Markets with an open bet in the ledger but no venue position —
+    /// usually resolved during standby; picked up by the next resolution check.
+    pub ledger_only: Vec<String>,
+    /// Markets with a venue position but no open bet in the ledger.
+    pub venue_only: Vec<String>,
+    /// Why the venue couldn't be checked.
+    pub error: Option<String>,
+}
+
+/// Result of the pre-resume validation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ResumeCheck {
+    pub venues: Vec<VenueCheck>,
+}
+
+impl ResumeCheck {
+    /// Every venue answered. Position and balance discrepancies are
+    /// reported, not blocking.
+    pub fn ok(&self) -> bool {
+        self.venues.iter().all(|v| v.error.is_none())
+    }
+}
+
+/// Market ids held only in the ledger, and only on the venue, sorted.
+pub fn compare_positions(ledger: &[TradeReceipt], positions: &[Position]) -> (Vec<String>, Vec<String>) {
+    let held: BTreeSet<&str> = ledger.iter().map(|b| b.market_id.as_str()).collect();
+    let on_venue: BTreeSet<&str> = positions.iter().map(|p| p.market_id.as_str()).collect();
+    (
+        held.difference(&on_venue).map(|s| s.to_string()).collect(),
+        on_venue.difference(&held).map(|s| s.to_string()).collect(),
+    )
+}
+
+async fn check_venue(venue: &dyn PredictionPlatform, state: &AgentState) -> VenueCheck {
+    let platform = venue.name().to_string();
+    let ledger_balance = (platform == "manifold").then_some(state.mana_bankroll);
+    let (balance, positions) = match tokio::join!(venue.get_balance(), venue.get_positions()) {
+        (Ok(balance), Ok(positions)) => (balance, positions),
+        (Err(e), _) | (_, Err(e)) => {
+            return VenueCheck {
+                platform,
+                balance: None,
+                ledger_balance,
+                ledger_only: Vec::new(),
+                venue_only: Vec::new(),
+                error: Some(format!("{e:#}")),
+            };
+        }
+    };
+    let ledger: Vec<TradeReceipt> = state.open_bets.iter().filter(|b| b.platform == platform).cloned().collect();
+    let (ledger_only, venue_only) = compare_positions(&ledger, &positions);
+    VenueCheck { platform, balance: Some(balance), ledger_balance, ledger_only, venue_only, error: None }
+}
+
+/// Check every venue the executor places on against `state`.
+pub async fn check_venues(executor: &Executor, state: &AgentState) -> ResumeCheck {
+    let venues = executor.venues();
+    let mut checks = join_all(venues.iter().map(|v| check_venue(v.as_ref(), state))).await;
+    checks.sort_by(|a, b| a.platform.cmp(&b.platform));
+    ResumeCheck { venues: checks }
+}
+
+/// Match the executor to the persisted status, e.g. after a restart.
+pub fn restore(state: &AgentState, executor: &Executor) {
+    executor.set_standby(state.is_standby());
+    if state.is_standby() {
+        info!("Restored in STANDBY — estimating, execution disabled");
+    }
+}
+
+/// Enter standby from a live agent. Returns whether the mode changed.
+pub fn enter(state: &mut AgentState, executor: &Executor) -> bool {
+    if state.status != AgentStatus::Alive {
+        return false;
+    }
+    state.status = AgentStatus::Standby;
+    executor.set_standby(true);
+    info!("Entered STANDBY — scanning and estimating continue; execution and ledger updates disabled");
+    true
+}
+
+/// Leave standby once every venue checks out. `None` when not in standby.
+pub async fn resume(state: &mut AgentState, executor: &Executor) -> Option<ResumeCheck> {
+    if !state.is_standby() {
+        return None;
+    }
+    let check = check_venues(executor, state).await;
+    for v in &check.venues {
+        if let Some(e) = &v.error {
+            warn!(platform = %v.platform, error = %e, "Resume check: venue unreachable");
+        }
+        if !v.ledger_only.is_empty() || !v.venue_only.is_empty() {
+            warn!(
+                platform = %v.platform,
+                ledger_only = ?v.ledger_only,
+                venue_only = ?v.venue_only,
+                "Resume check: venue positions differ from the ledger"
+            );
+        }
+        if let (Some(balance), Some(ledger)) = (v.balance, v.ledger_balance) {
+            if balance != ledger {
+                warn!(platform = %v.platform, %balance, %ledger, "Resume check: venue balance differs from the ledger");
+            }
+        }
+    }
+    if check.ok() {
+        state.status = AgentStatus::Alive;
+        executor.set_standby(false);
+        info!(venues = check.venues.len(), "Resumed from STANDBY — execution enabled");
+    } else {
+        warn!("Resume refused — staying in STANDBY");
+    }
+    Some(check)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::accountant::{Accountant, CycleCosts};
+    use crate::engine::resolver::Resolver;
+    use crate::strategy::edge::Edge;
+    use crate::strategy::kelly::SizedBet;
+    use crate::types::{Estimate, LiquidityInfo, Market, MarketCategory, Side};
+    use anyhow::Result;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Venue that counts every call and holds `positions`.
+    struct CountingVenue {
+        name: &'static str,
+        positions: Vec<&'static str>,
+        reachable: bool,
+        placed: AtomicUsize,
+        checked: AtomicUsize,
+    }
+
+    impl CountingVenue {
+        fn new(name: &'static str, positions: Vec<&'static str>, reachable: bool) -> Arc<Self> {
+            Arc::new(Self { name, positions, reachable, placed: AtomicUsize::new(0), checked: AtomicUsize::new(0) })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PredictionPlatform for CountingVenue {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+            self.placed.fetch_add(1, Ordering::SeqCst);
+            Ok(receipt(self.name, market_id, side, amount))
+        }
+
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            self.checked.fetch_add(1, Ordering::SeqCst);
+            anyhow::ensure!(self.reachable, "connection refused");
+            Ok(self
+                .positions
+                .iter()
+                .map(|id| Position {
+                    market_id: id.to_string(),
+                    platform: self.name.to_string(),
+                    side: Side::Yes,
+                    size: dec!(10),
+                    entry_price: dec!(0.5),
+                    current_value: dec!(10),
+                    cost: dec!(5),
+                    category: None,
+                })
+                .collect())
+        }
+
+        async fn get_balance(&self) -> Result<Decimal> {
+            anyhow::ensure!(self.reachable, "connection refused");
+            Ok(dec!(900))
+        }
+
+        async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+            anyhow::bail!("not supported")
+        }
+
+        fn is_real_money(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn receipt(platform: &str, market_id: &str, side: Side, amount: Decimal) -> TradeReceipt {
+        let mut r = TradeReceipt::dry_run(market_id, amount, "Mana");
+        r.order_id = format!("{platform}-{market_id}");
+        r.platform = platform.to_string();
+        r.side = side;
+        r
+    }
+
+    fn sized_bet(platform: &str, market_id: &str) -> SizedBet {
+        SizedBet {
+            edge: Edge {
+                market: Market {
+                    id: market_id.to_string(),
+                    platform: platform.to_string(),
+                    question: "Test?".into(),
+                    description: String::new(),
+                    category: MarketCategory::Weather,
+                    current_price_yes: dec!(0.50),
+                    current_price_no: dec!(0.50),
+                    volume_24h: dec!(100),
+                    liquidity: dec!(500),
+                    deadline: chrono::Utc::now() + chrono::Duration::days(30),
+                    resolution_criteria: String::new(),
+                    url: String::new(),
+                    cross_refs: Default::default(),
+                    event_group: None,
+                    facts: None,
+                    tags: Vec::new(),
+                },
+                estimate: Estimate {
+                    probability: dec!(0.65),
+                    confidence: dec!(0.8),
+                    reasoning: String::new(),
+                    tokens_used: 100,
+                    cost: dec!(0.01),
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                },
+                side: Side::Yes,
+                edge: dec!(0.15),
+                signed_edge: dec!(0.15),
+                raw_edge: dec!(0.15),
+                effective_threshold: dec!(0.06),
+            },
+            kelly_fraction: dec!(0.10),
+            bet_fraction: dec!(0.05),
+            bet_amount: dec!(10),
+            expected_value: dec!(1.5),
+            net_expected_value: dec!(1.5),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.15),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_positions() {
+        let ledger = vec![receipt("manifold", "a", Side::Yes, dec!(5)), receipt("manifold", "b", Side::No, dec!(5))];
+        let pos = |id: &str| Position {
+            market_id: id.to_string(),
+            platform: "manifold".to_string(),
+            side: Side::Yes,
+            size: dec!(1),
+            entry_price: dec!(0.5),
+            current_value: dec!(1),
+            cost: dec!(5),
+            category: None,
+        };
+        let (ledger_only, venue_only) = compare_positions(&ledger, &[pos("b"), pos("c")]);
+        assert_eq!(ledger_only, vec!["a"]);
+        assert_eq!(venue_only, vec!["c"]);
+    }
+
+    #[tokio::test]
+    async fn test_standby_cycle_touches_neither_executor_nor_ledger() {
+        let venue = CountingVenue::new("manifold", vec![], true);
+        let executor = Executor::new(None, false).with_venue(venue.clone());
+        let mut state = AgentState::new(dec!(100));
+        state.mana_bankroll = dec!(900);
+        assert!(enter(&mut state, &executor));
+        assert!(!enter(&mut state, &executor));
+        let before = (state.open_bets.len(), state.trades_placed, state.mana_bankroll, state.total_mana_pnl);
+
+        // A standby cycle: approved bets go to the executor, costs are booked.
+        let execution = executor.execute_batch(&[sized_bet("manifold", "m1")]).await.unwrap();
+        let report = Accountant::reconcile(&mut state, &execution, &CycleCosts { llm_cost: dec!(0.02), ..Default::default() });
+        let resolutions = Resolver::check(&executor, &[receipt("manifold", "m0", Side::Yes, dec!(5))]).await;
+
+        assert_eq!(venue.placed.load(Ordering::SeqCst), 0);
+        assert!(resolutions.is_empty());
+        assert_eq!(report.bets_placed, 0);
+        assert_eq!((state.open_bets.len(), state.trades_placed, state.mana_bankroll, state.total_mana_pnl), before);
+        // The cycle itself still ran and its real spend was charged.
+        assert_eq!(state.cycle_count, 1);
+        assert_eq!(state.bankroll, dec!(99.98));
+        assert_eq!(state.status, AgentStatus::Standby);
+    }
+
+    #[tokio::test]
+    async fn test_resume_validates_venues() {
+        let up = CountingVenue::new("manifold", vec!["m2"], true);
+        let executor = Executor::new(None, false).with_venue(up.clone());
+        let mut state = AgentState::new(dec!(100));
+        state.mana_bankroll = dec!(850);
+        state.open_bets.push(receipt("manifold", "m1", Side::Yes, dec!(5)));
+
+        // Not in standby: nothing to do.
+        assert!(resume(&mut state, &executor).await.is_none());
+
+        enter(&mut state, &executor);
+        let check = resume(&mut state, &executor).await.unwrap();
+        assert!(check.ok());
+        assert_eq!(check.venues[0].ledger_only, vec!["m1"]);
+        assert_eq!(check.venues[0].venue_only, vec!["m2"]);
+        assert_eq!((check.venues[0].balance, check.venues[0].ledger_balance), (Some(dec!(900)), Some(dec!(850))));
+        assert_eq!(state.status, AgentStatus::Alive);
+        assert!(!executor.is_standby());
+
+        // Execution is live again.
+        executor.execute_batch(&[sized_bet("manifold", "m3")]).await.unwrap();
+        assert_eq!(up.placed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_venue_blocks_resume() {
+        let down = CountingVenue::new("betfair", vec![], false);
+        let executor = Executor::new(None, false).with_venue(down.clone());
+        let mut state = AgentState::new(dec!(100));
+        enter(&mut state, &executor);
+
+        let check = resume(&mut state, &executor).await.unwrap();
+        assert!(!check.ok());
+        assert!(check.venues[0].error.as_deref().unwrap().contains("connection refused"));
+        assert_eq!(state.status, AgentStatus::Standby);
+        assert!(executor.is_standby());
+        assert_eq!(down.checked.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_restore_matches_persisted_status() {
+        let executor = Executor::new(None, false);
+        let mut state = AgentState::new(dec!(100));
+        state.status = AgentStatus::Standby;
+        let state: AgentState = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        restore(&state, &executor);
+        assert!(executor.is_standby());
+    }
+}
@@ -0,0 +1,293 @@
+//! The one shared [`AgentState`], read by the dashboard and mutated by the
+//! engine and the control endpoints.
+//!
+//! The engine works on its own copy through each tick and hands it back at
+//! well-defined points — after resolutions, auto-exits, reviews and each
+//! cycle — with [`SharedState::commit`]. Control endpoints change the
+//! shared state through [`SharedState::apply`]: the change is visible to
+//! readers at once, persisted, and queued for the engine, which replays it
+//! onto its copy at the next sync, so an engine commit never overwrites it.
+//!
+//! No lock is ever held across an `.await`: readers and writers get the
+//! state inside a synchronous closure and never see the guard, so a network
+//! call made while "holding" the state cannot compile, and a slow venue
+//! can't stall the dashboard or a control request. Persisting goes through
+//! the [`Storage`] backend after the lock is released; each write carries
+//! the state's version, and one overtaken by a newer write is skipped.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{error, info};
+
+use crate::storage::backend::Storage;
+use crate::types::AgentState;
+
+/// A change made from outside the engine loop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum StateChange {
+    /// Funds added to (or, negative, withdrawn from) a bankroll. Not P&L:
+    /// the AUD peak moves with it so drawdown is unaffected.
+    Deposit { currency: String, amount: Decimal },
+}
+
+impl StateChange {
+    /// Reject a change that can't be applied to `state`.
+    pub fn validate(&self, state: &AgentState) -> Result<()> {
+        match self {
+            Self::Deposit { currency, amount } => {
+                anyhow::ensure!(!amount.is_zero(), "deposit amount must be non-zero");
+                let balance = match currency.as_str() {
+                    "AUD" => state.bankroll,
+                    "Mana" => state.mana_bankroll,
+                    other => anyhow::bail!("unknown currency {other:?} (expected AUD or Mana)"),
+                };
+                anyhow::ensure!(balance + amount >= Decimal::ZERO, "withdrawal exceeds the {currency} balance");
+                Ok(())
+            }
+        }
+    }
+
+    fn apply(&self, state: &mut AgentState) {
+        match self {
+            Self::Deposit { currency, amount } if currency == "AUD" => {
+                state.bankroll += amount;
+                state.peak_bankroll = (state.peak_bankroll + amount).max(Decimal::ZERO);
+            }
+            Self::Deposit { amount, .. } => state.mana_bankroll += amount,
+        }
+    }
+}
+
+struct Inner {
+    state: AgentState,
+    /// Changes applied here but not yet replayed onto the engine's copy.
+    pending: Vec<StateChange>,
+    /// Bumped on every change and commit.
+    version: u64,
+}
+
+/// Cheaply cloneable handle to the shared agent state. The engine's copy
+/// must start from the state the handle was created with and only move
+/// forward through [`SharedState::sync`] / [`SharedState::commit`].
+#[derive(Clone)]
+pub struct SharedState {
+    inner: Arc<RwLock<Inner>>,
+    /// Backend written on every commit and change; `None` keeps the state
+    /// in memory only.
+    storage: Option<Arc<dyn Storage>>,
+    /// Version of the last state written.
+    saved: Arc<Mutex<u64>>,
+}
+
+impl From<AgentState> for SharedState {
+    fn from(state: AgentState) -> Self {
+        Self::new(state)
+    }
+}
+
+impl SharedState {
+    /// In-memory shared state (nothing is persisted).
+    pub fn new(state: AgentState) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Inner { state, pending: Vec::new(), version: 0 })),
+            storage: None,
+            saved: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Persist to `storage`.
+    pub fn persisted(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Read the state. The closure is synchronous, so the lock is released
+    /// before the caller's next `.await`.
+    pub async fn read<R>(&self, f: impl FnOnce(&AgentState) -> R) -> R {
+        f(&self.inner.read().await.state)
+    }
+
+    /// [`Self::read`] without waiting; `None` while a writer holds the lock.
+    pub fn try_read<R>(&self, f: impl FnOnce(&AgentState) -> R) -> Option<R> {
+        self.inner.try_read().ok().map(|inner| f(&inner.state))
+    }
+
+    /// Copy of the current state.
+    pub async fn snapshot(&self) -> AgentState {
+        self.read(AgentState::clone).await
+    }
+
+    /// Apply a control change: validated, visible to readers immediately,
+    /// persisted, and replayed onto the engine's copy at its next sync.
+    /// Errs only when the change is invalid; a failed write is logged and
+    /// retried by the engine's next commit.
+    pub async fn apply(&self, change: StateChange) -> Result<()> {
+        let snapshot = {
+            let mut inner = self.inner.write().await;
+            change.validate(&inner.state)?;
+            change.apply(&mut inner.state);
+            info!(?change, "State change applied");
+            inner.pending.push(change);
+            self.versioned(&mut inner)
+        };
+        if let Err(e) = self.persist(snapshot).await {
+            error!(error = %e, "Failed to save state after state change");
+        }
+        Ok(())
+    }
+
+    /// Engine sync point: replay queued control changes onto `working`,
+    /// then publish it. Returns the number of changes replayed.
+    pub async fn sync(&self, working: &mut AgentState) -> usize {
+        let mut inner = self.inner.write().await;
+        Self::sync_locked(&mut inner, working)
+    }
+
+    /// [`Self::sync`], then persist.
+    pub async fn commit(&self, working: &mut AgentState) -> Result<usize> {
+        let (replayed, snapshot) = {
+            let mut inner = self.inner.write().await;
+            let replayed = Self::sync_locked(&mut inner, working);
+            (replayed, self.versioned(&mut inner))
+        };
+        self.persist(snapshot).await?;
+        Ok(replayed)
+    }
+
+    fn sync_locked(inner: &mut Inner, working: &mut AgentState) -> usize {
+        let pending = std::mem::take(&mut inner.pending);
+        for change in &pending {
+            change.apply(working);
+        }
+        inner.state = working.clone();
+        pending.len()
+    }
+
+    /// Bump the version and copy the state for [`Self::persist`] (no copy
+    /// when nothing is persisted).
+    fn versioned(&self, inner: &mut Inner) -> Option<(u64, AgentState)> {
+        inner.version += 1;
+        self.storage.as_ref().map(|_| (inner.version, inner.state.clone()))
+    }
+
+    async fn persist(&self, snapshot: Option<(u64, AgentState)>) -> Result<()> {
+        let (Some(storage), Some((version, state))) = (&self.storage, snapshot) else { return Ok(()) };
+        let mut saved = self.saved.lock().await;
+        if *saved >= version {
+            // A newer state has been written meanwhile.
+            return Ok(());
+        }
+        storage.save_state(&state).await?;
+        *saved = version;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+
+    fn deposit(currency: &str, amount: Decimal) -> StateChange {
+        StateChange::Deposit { currency: currency.to_string(), amount }
+    }
+
+    /// One engine tick: work on the engine's copy across a slow "network
+    /// call", then commit.
+    async fn simulated_cycle(shared: SharedState, mut working: AgentState, pause: Duration) -> AgentState {
+        working.cycle_count += 1;
+        working.bankroll -= dec!(2);
+        tokio::time::sleep(pause).await;
+        working.open_bets.push(crate::types::TradeReceipt::dry_run("m1", dec!(5), "Mana"));
+        shared.commit(&mut working).await.unwrap();
+        working
+    }
+
+    #[tokio::test]
+    async fn test_change_during_cycle_is_not_lost() {
+        let working = AgentState::new(dec!(100));
+        let shared = SharedState::new(working.clone());
+        let cycle = tokio::spawn(simulated_cycle(shared.clone(), working, Duration::from_millis(50)));
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let during = tokio::time::timeout(Duration::from_secs(1), async {
+            shared.apply(deposit("AUD", dec!(50))).await.unwrap();
+            shared.read(|s| (s.bankroll, s.cycle_count)).await
+        })
+        .await
+        .expect("control change blocked behind the cycle");
+        // Visible at once, on top of the last published state.
+        assert_eq!(during, (dec!(150), 0));
+
+        let working = tokio::time::timeout(Duration::from_secs(1), cycle).await.unwrap().unwrap();
+        assert_eq!((working.bankroll, working.cycle_count), (dec!(148), 1));
+        let published = shared.snapshot().await;
+        assert_eq!((published.bankroll, published.peak_bankroll), (dec!(148), dec!(150)));
+        assert_eq!(published.open_bets.len(), 1);
+        // Replayed once only.
+        let mut again = working.clone();
+        assert_eq!(shared.sync(&mut again).await, 0);
+        assert_eq!(again.bankroll, dec!(148));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_readers_and_changes_do_not_deadlock() {
+        let working = AgentState::new(dec!(100));
+        let shared = SharedState::new(working.clone());
+        let mut tasks = Vec::new();
+        for i in 0..20 {
+            let s = shared.clone();
+            tasks.push(tokio::spawn(async move {
+                if i % 2 == 0 {
+                    s.apply(deposit("Mana", dec!(1))).await.unwrap();
+                } else {
+                    s.read(|a| a.mana_bankroll).await;
+                }
+            }));
+        }
+        let cycles = tokio::spawn({
+            let s = shared.clone();
+            async move {
+                let mut working = working;
+                for _ in 0..5 {
+                    working = simulated_cycle(s.clone(), working, Duration::from_millis(2)).await;
+                }
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), async {
+            for t in tasks {
+                t.await.unwrap();
+            }
+            cycles.await.unwrap();
+        })
+        .await
+        .expect("deadlock");
+        let state = shared.snapshot().await;
+        assert_eq!(state.mana_bankroll, dec!(10));
+        assert_eq!(state.cycle_count, 5);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_change_rejected_and_changes_persisted() {
+        let storage = Arc::new(crate::storage::sqlite::SqliteStorage::open_in_memory().await.unwrap());
+        let shared = SharedState::new(AgentState::new(dec!(100))).persisted(storage.clone());
+        assert!(shared.apply(deposit("USD", dec!(5))).await.is_err());
+        assert!(shared.apply(deposit("AUD", dec!(-101))).await.is_err());
+        assert!(shared.apply(deposit("AUD", dec!(0))).await.is_err());
+        assert!(storage.load_state().await.unwrap().is_none());
+
+        shared.apply(deposit("AUD", dec!(-40))).await.unwrap();
+        let saved = storage.load_state().await.unwrap().unwrap();
+        assert_eq!((saved.bankroll, saved.peak_bankroll), (dec!(60), dec!(60)));
+    }
+}
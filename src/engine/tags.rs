@@ -0,0 +1,427 @@
+//! Operator-defined market tags, finer-grained than categories.
+//!
+//! Rules map question regexes, event-group slugs and platforms to tags.
+//! The scanner attaches every matching rule's tag to the market, and the
+//! tags then travel with its decisions, trades and resolutions, so risk
+//! caps (`[risk.tags]`) and `/api/tags` can work per tag:
+//!
+//! ```toml
+//! # tags.toml
+//! [[rules]]
+//! tag = "AU-politics"
+//! question = "(?i)\\b(albanese|dutton|labor|coalition)\\b"
+//! platforms = ["betfair", "polymarket"]
+//!
+//! [[rules]]
+//! tag = "f1"
+//! group = "(?i)^(f1|formula-1)-"
+//! stop = true
+//!
+//! [[rules]]
+//! tag = "motorsport"
+//! question = "(?i)grand prix|nascar|motogp"
+//! ```
+//!
+//! A rule matches when all of its conditions do and needs at least one.
+//! Rules are tried in file order and a market collects the tag of every
+//! rule that matches, once each, in that order; a matching rule with
+//! `stop = true` ends the search, so an earlier, more specific rule can
+//! keep later broad ones off its markets. The file is reloaded with the
+//! watchlist (see [`crate::engine::watchlist`]), and like it is rejected
+//! as a whole when any rule is invalid.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use regex_automata::meta::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::watchlist::PLATFORMS;
+use crate::types::{AgentState, Market, TagResults, TradeReceipt};
+
+/// Longest tag accepted.
+const MAX_TAG_LEN: usize = 64;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TagsFile {
+    #[serde(default)]
+    rules: Vec<TagRuleEntry>,
+}
+
+/// One rule as written in the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TagRuleEntry {
+    tag: String,
+    /// Regex matched against the question.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    question: Option<String>,
+    /// Regex matched against the market's event group slug; markets
+    /// without a group never match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    /// Platforms the rule is limited to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    platforms: Vec<String>,
+    /// Stop trying later rules once this one matches.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    stop: bool,
+}
+
+#[derive(Debug, Clone)]
+struct TagRule {
+    entry: TagRuleEntry,
+    question: Option<Regex>,
+    group: Option<Regex>,
+}
+
+impl TagRule {
+    fn compile(index: usize, entry: TagRuleEntry) -> Result<Self> {
+        let rule = format!("tag rule {} ({:?})", index + 1, entry.tag);
+        validate_tag(&entry.tag).with_context(|| rule.clone())?;
+        anyhow::ensure!(
+            entry.question.is_some() || entry.group.is_some() || !entry.platforms.is_empty(),
+            "{rule} has no question, group or platforms condition"
+        );
+        for platform in &entry.platforms {
+            anyhow::ensure!(PLATFORMS.contains(&platform.as_str()), "{rule}: unknown platform {platform:?}");
+        }
+        let compile = |pattern: &Option<String>, what: &str| -> Result<Option<Regex>> {
+            pattern
+                .as_deref()
+                .map(|p| Regex::new(p).with_context(|| format!("{rule}: {what} {p:?} is not a valid regex")))
+                .transpose()
+        };
+        let question = compile(&entry.question, "question")?;
+        let group = compile(&entry.group, "group")?;
+        Ok(Self { entry, question, group })
+    }
+
+    fn matches(&self, market: &Market) -> bool {
+        (self.entry.platforms.is_empty() || self.entry.platforms.contains(&market.platform))
+            && self.question.as_ref().is_none_or(|re| re.is_match(&market.question))
+            && self.group.as_ref().is_none_or(|re| market.event_group.as_deref().is_some_and(|g| re.is_match(g)))
+    }
+}
+
+/// Tags are shown in reports and config keys: letters, digits, `-` and `_`.
+fn validate_tag(tag: &str) -> Result<()> {
+    anyhow::ensure!(!tag.is_empty(), "empty tag");
+    anyhow::ensure!(tag.len() <= MAX_TAG_LEN, "tag longer than {MAX_TAG_LEN} characters");
+    anyhow::ensure!(
+        tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "tag may only contain letters, digits, '-' and '_'"
+    );
+    Ok(())
+}
+
+/// The tag rules in force.
+#[derive(Debug, Clone, Default)]
+pub struct TagRules {
+    rules: Vec<TagRule>,
+}
+
+impl TagRules {
+    /// Parse a tags file; any invalid rule rejects the file.
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: TagsFile = toml::from_str(text).context("Invalid tag rules")?;
+        let rules = file
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| TagRule::compile(i, entry))
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Tags for `market`, in rule order.
+    pub fn tags_for(&self, market: &Market) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.matches(market)) {
+            if !tags.contains(&rule.entry.tag) {
+                tags.push(rule.entry.tag.clone());
+            }
+            if rule.entry.stop {
+                break;
+            }
+        }
+        tags
+    }
+
+    /// Distinct tags the rules can assign.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        let mut seen: Vec<&str> = Vec::new();
+        for rule in &self.rules {
+            if !seen.contains(&rule.entry.tag.as_str()) {
+                seen.push(&rule.entry.tag);
+            }
+        }
+        seen.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether `other` holds the same rules in the same order.
+    pub fn same_rules(&self, other: &TagRules) -> bool {
+        self.rules.len() == other.rules.len() && self.rules.iter().zip(&other.rules).all(|(a, b)| a.entry == b.entry)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Results and reporting
+// ---------------------------------------------------------------------------
+
+/// Count a resolved bet against every tag it carries. Cancellations
+/// (zero-PnL losses) are neither a win nor a loss and are not counted.
+pub fn record_resolution(state: &mut AgentState, bet: &TradeReceipt, pnl: Decimal, won: bool) {
+    if !won && pnl.is_zero() {
+        return;
+    }
+    for tag in &bet.tags {
+        let at = match state.tag_results.iter().position(|r| r.tag == *tag && r.currency == bet.currency) {
+            Some(at) => at,
+            None => {
+                state.tag_results.push(TagResults {
+                    tag: tag.clone(),
+                    currency: bet.currency.clone(),
+                    ..Default::default()
+                });
+                state.tag_results.len() - 1
+            }
+        };
+        let results = &mut state.tag_results[at];
+        results.resolved += 1;
+        if won {
+            results.won += 1;
+        } else {
+            results.lost += 1;
+        }
+        results.staked += bet.amount;
+        results.pnl += pnl;
+    }
+}
+
+/// One tag's open and resolved bets in one currency, for `/api/tags`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TagStats {
+    pub tag: String,
+    pub currency: String,
+    pub open_bets: usize,
+    pub open_exposure: Decimal,
+    pub resolved: u64,
+    pub won: u64,
+    pub lost: u64,
+    /// Stake on resolved bets.
+    pub staked: Decimal,
+    pub realized_pnl: Decimal,
+    /// Realised PnL over resolved stake; `None` before any resolution.
+    pub roi: Option<Decimal>,
+}
+
+/// Per-tag statistics from the open bets and the recorded results. A bet
+/// counts once under each of its tags, so totals across tags can exceed
+/// the portfolio's. Sorted by tag, then currency.
+pub fn report(open_bets: &[TradeReceipt], results: &[TagResults]) -> Vec<TagStats> {
+    let mut stats: BTreeMap<(String, String), TagStats> = BTreeMap::new();
+    let mut entry = |tag: &str, currency: &str| {
+        stats.entry((tag.to_string(), currency.to_string())).or_insert_with(|| TagStats {
+            tag: tag.to_string(),
+            currency: currency.to_string(),
+            ..Default::default()
+        });
+    };
+    for bet in open_bets {
+        for tag in &bet.tags {
+            entry(tag, &bet.currency);
+        }
+    }
+    for r in results {
+        entry(&r.tag, &r.currency);
+    }
+
+    for bet in open_bets {
+        for tag in &bet.tags {
+            if let Some(s) = stats.get_mut(&(tag.clone(), bet.currency.clone())) {
+                s.open_bets += 1;
+                s.open_exposure += bet.amount;
+            }
+        }
+    }
+    for r in results {
+        if let Some(s) = stats.get_mut(&(r.tag.clone(), r.currency.clone())) {
+            s.resolved += r.resolved;
+            s.won += r.won;
+            s.lost += r.lost;
+            s.staked += r.staked;
+            s.realized_pnl += r.pnl;
+        }
+    }
+    stats
+        .into_values()
+        .map(|mut s| {
+            s.roi = (!s.staked.is_zero()).then(|| (s.realized_pnl / s.staked).round_dp(4));
+            s
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MarketCategory, Side};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn market(platform: &str, question: &str, group: Option<&str>) -> Market {
+        let mut m = crate::testkit::market("m1", platform, question, MarketCategory::Politics, 0.5, 1000.0, 48);
+        m.event_group = group.map(String::from);
+        m
+    }
+
+    fn receipt(tags: &[&str], currency: &str, amount: Decimal) -> TradeReceipt {
+        TradeReceipt {
+            order_id: "o1".into(),
+            market_id: "m1".into(),
+            platform: "manifold".into(),
+            side: Side::Yes,
+            amount,
+            fill_price: dec!(0.5),
+            fees: Decimal::ZERO,
+            timestamp: Utc::now(),
+            currency: currency.into(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            correlation_key: None,
+            expected_price: None,
+        }
+    }
+
+    const RULES: &str = r#"
+        [[rules]]
+        tag = "AU-politics"
+        question = "(?i)\\balbanese\\b"
+        platforms = ["betfair", "polymarket"]
+
+        [[rules]]
+        tag = "f1"
+        group = "^f1-"
+        stop = true
+
+        [[rules]]
+        tag = "motorsport"
+        question = "(?i)grand prix"
+
+        [[rules]]
+        tag = "AU-politics"
+        question = "(?i)\\bdutton\\b"
+    "#;
+
+    #[test]
+    fn test_rule_matching_precedence() {
+        let rules = TagRules::parse(RULES).unwrap();
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules.tags().collect::<Vec<_>>(), ["AU-politics", "f1", "motorsport"]);
+
+        // Platform condition limits the first rule; the last still applies.
+        let m = market("manifold", "Will Albanese and Dutton debate?", None);
+        assert_eq!(rules.tags_for(&m), ["AU-politics"]);
+        let m = market("betfair", "Will Albanese win?", None);
+        assert_eq!(rules.tags_for(&m), ["AU-politics"]);
+
+        // Several rules match: tags in rule order, each once.
+        let m = market("polymarket", "Will Albanese attend the Australian Grand Prix? Dutton?", None);
+        assert_eq!(rules.tags_for(&m), ["AU-politics", "motorsport"]);
+
+        // A matching stop rule keeps later rules off.
+        let m = market("polymarket", "Monaco Grand Prix winner?", Some("f1-monaco-2026"));
+        assert_eq!(rules.tags_for(&m), ["f1"]);
+        let m = market("polymarket", "Monaco Grand Prix winner?", Some("nascar-2026"));
+        assert_eq!(rules.tags_for(&m), ["motorsport"]);
+
+        assert!(rules.tags_for(&market("manifold", "Will it rain?", None)).is_empty());
+        assert!(TagRules::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_rules_rejected() {
+        for bad in [
+            r#"[[rules]]
+               tag = "x""#,
+            r#"[[rules]]
+               tag = ""
+               question = "a""#,
+            r#"[[rules]]
+               tag = "has space"
+               question = "a""#,
+            r#"[[rules]]
+               tag = "x"
+               question = "(unclosed""#,
+            r#"[[rules]]
+               tag = "x"
+               platforms = ["kalshi"]"#,
+            r#"[[rules]]
+               tag = "x"
+               question = "a"
+               typo = true"#,
+        ] {
+            assert!(TagRules::parse(bad).is_err(), "accepted: {bad}");
+        }
+        let err = TagRules::parse("[[rules]]\ntag = \"ok\"\nquestion = \"a\"\n[[rules]]\ntag = \"y\"\ngroup = \"[\"")
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("tag rule 2"));
+    }
+
+    #[test]
+    fn test_same_rules() {
+        let a = TagRules::parse(RULES).unwrap();
+        assert!(a.same_rules(&TagRules::parse(RULES).unwrap()));
+        assert!(!a.same_rules(&TagRules::parse("[[rules]]\ntag = \"f1\"\ngroup = \"^f1-\"").unwrap()));
+    }
+
+    #[test]
+    fn test_report_aggregates_per_tag_and_currency() {
+        let mut state = AgentState::new(dec!(100));
+        let won = receipt(&["f1", "motorsport"], "Mana", dec!(10));
+        let lost = receipt(&["f1"], "Mana", dec!(20));
+        let cancelled = receipt(&["f1"], "Mana", dec!(5));
+        let aud = receipt(&["f1"], "AUD", dec!(4));
+        record_resolution(&mut state, &won, dec!(8), true);
+        record_resolution(&mut state, &lost, dec!(-20), false);
+        record_resolution(&mut state, &cancelled, Decimal::ZERO, false);
+        record_resolution(&mut state, &aud, dec!(2), true);
+
+        let open = vec![
+            receipt(&["f1", "AU-politics"], "Mana", dec!(15)),
+            receipt(&[], "Mana", dec!(50)),
+        ];
+        let report = report(&open, &state.tag_results);
+        let keys: Vec<(&str, &str)> = report.iter().map(|s| (s.tag.as_str(), s.currency.as_str())).collect();
+        assert_eq!(keys, [("AU-politics", "Mana"), ("f1", "AUD"), ("f1", "Mana"), ("motorsport", "Mana")]);
+
+        let f1 = &report[2];
+        assert_eq!((f1.open_bets, f1.open_exposure), (1, dec!(15)));
+        assert_eq!((f1.resolved, f1.won, f1.lost), (2, 1, 1));
+        assert_eq!((f1.staked, f1.realized_pnl), (dec!(30), dec!(-12)));
+        assert_eq!(f1.roi, Some(dec!(-0.4)));
+
+        // A bet counts under each of its tags.
+        assert_eq!((report[3].resolved, report[3].realized_pnl), (1, dec!(8)));
+        assert_eq!((report[0].open_bets, report[0].roi), (1, None));
+        assert_eq!(report[1].realized_pnl, dec!(2));
+    }
+}
@@ -0,0 +1,537 @@
+//! Operator watchlist and question blocklist, reloaded while running.
+//!
+//! The watchlist pins markets, keyed `platform:market_id`, ahead of the
+//! scanner's per-cycle cap; a watched market still has to come back from
+//! its platform's scan. The blocklist drops every market whose question
+//! matches one of its regular expressions:
+//!
+//! ```toml
+//! # watchlist.toml
+//! markets = ["manifold:abc123", "betfair:1.23456789"]
+//!
+//! # blocklist.toml
+//! patterns = ["(?i)\\belon musk\\b.*\\btweet", "(?i)^will i "]
+//! ```
+//!
+//! The tag rules file (see [`crate::engine::tags`]) is reloaded alongside
+//! them. Every file is checked for a new modification time at the start of
+//! each cycle. A file that no longer parses — unknown platform, malformed
+//! key, regex or tag rule — is rejected as a whole and the previous lists
+//! stay in force. The files are the source of truth:
+//! `/api/control/watchlist` edits the watchlist and blocklist on disk and
+//! the running agent picks the edit up like any other change.
+//!
+//! `[scanner]` also takes fixed `blacklist_patterns` and
+//! `whitelist_patterns` ([`PatternList`]): case-insensitive substrings, or
+//! regexes when written `/…/`. `/api/blacklist` adds one of those to the
+//! blocklist file.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use regex_automata::meta::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::engine::tags::TagRules;
+use crate::strategy::links::link_key;
+use crate::types::Market;
+
+/// Platform names a watchlist key may start with.
+pub const PLATFORMS: &[&str] = &["manifold", "metaculus", "polymarket", "betfair", "forecastex"];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchlistFile {
+    #[serde(default)]
+    markets: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BlocklistFile {
+    #[serde(default)]
+    patterns: Vec<String>,
+}
+
+/// The watchlist, blocklist and tag rules in force.
+#[derive(Debug, Clone, Default)]
+pub struct MarketLists {
+    watch: BTreeSet<String>,
+    block: Vec<(String, Regex)>,
+    tags: TagRules,
+}
+
+impl MarketLists {
+    /// Parse both files' contents; any invalid entry rejects both.
+    pub fn parse(watchlist: &str, blocklist: &str) -> Result<Self> {
+        Self::parse_with_tags(watchlist, blocklist, "")
+    }
+
+    /// [`Self::parse`] plus the tag rules file; any invalid entry or rule
+    /// rejects all three.
+    pub fn parse_with_tags(watchlist: &str, blocklist: &str, tags: &str) -> Result<Self> {
+        let watch: WatchlistFile = toml::from_str(watchlist).context("Invalid watchlist")?;
+        let block: BlocklistFile = toml::from_str(blocklist).context("Invalid blocklist")?;
+        let tags = TagRules::parse(tags)?;
+        Ok(Self { tags, ..Self::from_entries(watch.markets, block.patterns)? })
+    }
+
+    fn from_entries(markets: Vec<String>, patterns: Vec<String>) -> Result<Self> {
+        for key in &markets {
+            let (platform, id) = key
+                .split_once(':')
+                .with_context(|| format!("watchlist entry {key:?} is not platform:market_id"))?;
+            anyhow::ensure!(PLATFORMS.contains(&platform), "watchlist entry {key:?}: unknown platform {platform:?}");
+            anyhow::ensure!(!id.trim().is_empty(), "watchlist entry {key:?} has no market id");
+        }
+        let mut block: Vec<(String, Regex)> = Vec::with_capacity(patterns.len());
+        for pattern in patterns {
+            if block.iter().any(|(p, _)| *p == pattern) {
+                continue;
+            }
+            let re = Regex::new(&pattern).with_context(|| format!("blocklist pattern {pattern:?} is not a valid regex"))?;
+            block.push((pattern, re));
+        }
+        Ok(Self { watch: markets.into_iter().collect(), block, tags: TagRules::default() })
+    }
+
+    pub fn is_watched(&self, platform: &str, market_id: &str) -> bool {
+        !self.watch.is_empty() && self.watch.contains(&link_key(platform, market_id))
+    }
+
+    pub fn is_blocked(&self, question: &str) -> bool {
+        self.block.iter().any(|(_, re)| re.is_match(question))
+    }
+
+    pub fn watched(&self) -> impl Iterator<Item = &str> {
+        self.watch.iter().map(String::as_str)
+    }
+
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.block.iter().map(|(p, _)| p.as_str())
+    }
+
+    pub fn tag_rules(&self) -> &TagRules {
+        &self.tags
+    }
+
+    /// Tags the rules in force give `market`.
+    pub fn tags_for(&self, market: &Market) -> Vec<String> {
+        self.tags.tags_for(market)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watch.is_empty() && self.block.is_empty() && self.tags.is_empty()
+    }
+
+    /// What changes going from `self` to `next`.
+    pub fn diff(&self, next: &MarketLists) -> ListDiff {
+        let (old_block, new_block): (BTreeSet<&str>, BTreeSet<&str>) =
+            (self.patterns().collect(), next.patterns().collect());
+        ListDiff {
+            watch_added: next.watch.difference(&self.watch).cloned().collect(),
+            watch_removed: self.watch.difference(&next.watch).cloned().collect(),
+            block_added: new_block.difference(&old_block).map(|p| p.to_string()).collect(),
+            block_removed: old_block.difference(&new_block).map(|p| p.to_string()).collect(),
+            tags_changed: !self.tags.same_rules(&next.tags),
+        }
+    }
+}
+
+/// Entries added and removed by a reload.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ListDiff {
+    pub watch_added: Vec<String>,
+    pub watch_removed: Vec<String>,
+    pub block_added: Vec<String>,
+    pub block_removed: Vec<String>,
+    /// The tag rules differ.
+    pub tags_changed: bool,
+}
+
+impl ListDiff {
+    pub fn is_empty(&self) -> bool {
+        self.watch_added.is_empty()
+            && self.watch_removed.is_empty()
+            && self.block_added.is_empty()
+            && self.block_removed.is_empty()
+            && !self.tags_changed
+    }
+}
+
+/// Case-insensitive question patterns from `[scanner]`: a pattern written
+/// `/…/` is a regex, anything else a literal substring.
+#[derive(Debug, Clone, Default)]
+pub struct PatternList {
+    patterns: Vec<(String, Regex)>,
+}
+
+impl PatternList {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|p| {
+                let re = Regex::new(&pattern_regex(p)).with_context(|| format!("pattern {p:?} is not a valid regex"))?;
+                Ok((p.clone(), re))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// The first pattern `question` matches, as configured.
+    pub fn first_match(&self, question: &str) -> Option<&str> {
+        self.patterns.iter().find(|(_, re)| re.is_match(question)).map(|(p, _)| p.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+}
+
+/// The case-insensitive regex a `[scanner]` pattern stands for — in the
+/// blocklist file's syntax.
+pub fn pattern_regex(pattern: &str) -> String {
+    match pattern.strip_prefix('/').and_then(|p| p.strip_suffix('/')) {
+        Some(re) if !re.is_empty() => format!("(?i){re}"),
+        _ => {
+            let mut re = String::from("(?i)");
+            for c in pattern.chars() {
+                if "\\.+*?()|[]{}^$#&-~".contains(c) {
+                    re.push('\\');
+                }
+                re.push(c);
+            }
+            re
+        }
+    }
+}
+
+/// Entries to add to or remove from the files, as posted to
+/// `/api/control/watchlist`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ListEdit {
+    #[serde(default)]
+    pub add_markets: Vec<String>,
+    #[serde(default)]
+    pub remove_markets: Vec<String>,
+    #[serde(default)]
+    pub add_patterns: Vec<String>,
+    #[serde(default)]
+    pub remove_patterns: Vec<String>,
+}
+
+/// Where the lists live on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListPaths {
+    pub watchlist: PathBuf,
+    pub blocklist: PathBuf,
+    /// Tag rules; none without a file.
+    pub tags: Option<PathBuf>,
+}
+
+impl ListPaths {
+    pub fn new(watchlist: impl Into<PathBuf>, blocklist: impl Into<PathBuf>) -> Self {
+        Self { watchlist: watchlist.into(), blocklist: blocklist.into(), tags: None }
+    }
+
+    /// Also load tag rules from `tags`.
+    pub fn with_tags(mut self, tags: impl Into<PathBuf>) -> Self {
+        self.tags = Some(tags.into());
+        self
+    }
+
+    fn read(&self) -> Result<(String, String)> {
+        Ok((read_or_empty(&self.watchlist)?, read_or_empty(&self.blocklist)?))
+    }
+
+    fn read_tags(&self) -> Result<String> {
+        self.tags.as_deref().map_or(Ok(String::new()), read_or_empty)
+    }
+
+    /// All three files, parsed.
+    fn parse(&self) -> Result<MarketLists> {
+        let (watch, block) = self.read()?;
+        MarketLists::parse_with_tags(&watch, &block, &self.read_tags()?)
+    }
+
+    fn stamps(&self) -> [Option<Stamp>; 3] {
+        [modified(&self.watchlist), modified(&self.blocklist), self.tags.as_deref().and_then(modified)]
+    }
+
+    /// Apply `edit` to the files: validate the result, then write each
+    /// changed file through a temporary file and a rename. Returns the
+    /// difference from what was on disk; nothing is written when the
+    /// result would not parse. Comments in a rewritten file are lost.
+    pub fn apply(&self, edit: &ListEdit) -> Result<ListDiff> {
+        let (watch_text, block_text) = self.read()?;
+        let current = MarketLists::parse(&watch_text, &block_text)?;
+        let mut watch: WatchlistFile = toml::from_str(&watch_text)?;
+        let mut block: BlocklistFile = toml::from_str(&block_text)?;
+
+        watch.markets.retain(|m| !edit.remove_markets.contains(m));
+        for m in &edit.add_markets {
+            if !watch.markets.contains(m) {
+                watch.markets.push(m.clone());
+            }
+        }
+        block.patterns.retain(|p| !edit.remove_patterns.contains(p));
+        for p in &edit.add_patterns {
+            if !block.patterns.contains(p) {
+                block.patterns.push(p.clone());
+            }
+        }
+
+        let next = MarketLists::from_entries(watch.markets.clone(), block.patterns.clone())?;
+        let diff = current.diff(&next);
+        if !diff.watch_added.is_empty() || !diff.watch_removed.is_empty() {
+            write_atomic(&self.watchlist, &toml::to_string(&watch)?)?;
+        }
+        if !diff.block_added.is_empty() || !diff.block_removed.is_empty() {
+            write_atomic(&self.blocklist, &toml::to_string(&block)?)?;
+        }
+        Ok(diff)
+    }
+}
+
+/// The lists in force plus the file state they were read from.
+#[derive(Debug)]
+pub struct ListFiles {
+    paths: ListPaths,
+    stamps: [Option<Stamp>; 3],
+    lists: MarketLists,
+}
+
+impl ListFiles {
+    /// Load the files. Missing files are empty lists; an invalid file is
+    /// an error, as at any startup.
+    pub fn load(paths: ListPaths) -> Result<Self> {
+        let stamps = paths.stamps();
+        let lists = paths.parse().with_context(|| {
+            let tags = paths.tags.as_ref().map(|t| format!(" / {}", t.display())).unwrap_or_default();
+            format!("Invalid {} / {}{tags}", paths.watchlist.display(), paths.blocklist.display())
+        })?;
+        Ok(Self { paths, stamps, lists })
+    }
+
+    pub fn paths(&self) -> &ListPaths {
+        &self.paths
+    }
+
+    pub fn lists(&self) -> &MarketLists {
+        &self.lists
+    }
+
+    /// Re-read the files if any modification time moved. Returns the
+    /// difference when the lists changed; an invalid file is logged and
+    /// the previous lists are kept until the file changes again.
+    pub fn reload_if_changed(&mut self) -> Option<ListDiff> {
+        let stamps = self.paths.stamps();
+        if stamps == self.stamps {
+            return None;
+        }
+        self.stamps = stamps;
+        let next = match self.paths.parse() {
+            Ok(next) => next,
+            Err(e) => {
+                error!(error = format!("{e:#}"), "Watchlist/blocklist/tags reload rejected — keeping previous lists");
+                return None;
+            }
+        };
+        let diff = self.lists.diff(&next);
+        self.lists = next;
+        if diff.is_empty() {
+            return None;
+        }
+        info!(
+            watch_added = diff.watch_added.len(),
+            watch_removed = diff.watch_removed.len(),
+            block_added = diff.block_added.len(),
+            block_removed = diff.block_removed.len(),
+            tags_changed = diff.tags_changed,
+            tag_rules = self.lists.tag_rules().len(),
+            "Watchlist/blocklist/tags reloaded"
+        );
+        Some(diff)
+    }
+}
+
+fn read_or_empty(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Modification time and length: a rewrite within the clock's resolution
+/// usually still changes the length.
+type Stamp = (SystemTime, u64);
+
+fn modified(path: &Path) -> Option<Stamp> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, contents).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tmp_paths(name: &str) -> ListPaths {
+        let dir = std::env::temp_dir().join(format!("oracle-lists-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        ListPaths::new(dir.join("watchlist.toml"), dir.join("blocklist.toml"))
+    }
+
+    /// Give the filesystem a new modification time to notice.
+    fn rewrite(path: &Path, contents: &str) {
+        let before = modified(path);
+        loop {
+            std::fs::write(path, contents).unwrap();
+            if modified(path) != before {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_parse_and_match() {
+        let lists = MarketLists::parse(
+            r#"markets = ["manifold:abc", "betfair:1.234"]"#,
+            r#"patterns = ["(?i)\\btweet", "^Will I "]"#,
+        )
+        .unwrap();
+        assert!(lists.is_watched("manifold", "abc"));
+        assert!(!lists.is_watched("manifold", "abd"));
+        assert!(lists.is_blocked("Will Elon TWEET about X?"));
+        assert!(lists.is_blocked("Will I finish my thesis?"));
+        assert!(!lists.is_blocked("Will it rain in Sydney?"));
+        assert!(MarketLists::parse("", "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pattern_list_substrings_and_regexes() {
+        let list = PatternList::new(&[
+            "this market".to_string(),
+            "/^will i\\b/".to_string(),
+            "(c++)".to_string(),
+        ])
+        .unwrap();
+        // Substrings match anywhere, ignoring case, with no regex meaning
+        assert_eq!(list.first_match("Will THIS MARKET resolve YES?"), Some("this market"));
+        assert_eq!(list.first_match("Is (C++) still popular?"), Some("(c++)"));
+        assert_eq!(list.first_match("Is C still popular?"), None);
+        // Regexes are anchored as written, also ignoring case
+        assert_eq!(list.first_match("WILL I finish my thesis?"), Some("/^will i\\b/"));
+        assert_eq!(list.first_match("Who will I vote for?"), None);
+
+        assert_eq!(pattern_regex("a.b"), "(?i)a\\.b");
+        assert_eq!(pattern_regex("/a.b/"), "(?i)a.b");
+        assert!(PatternList::new(&["/(broken/".to_string()]).is_err());
+        assert!(PatternList::new(&["(not a regex".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_entries_rejected() {
+        assert!(MarketLists::parse(r#"markets = ["kalshi:abc"]"#, "").is_err());
+        assert!(MarketLists::parse(r#"markets = ["abc"]"#, "").is_err());
+        assert!(MarketLists::parse(r#"markets = ["manifold:"]"#, "").is_err());
+        assert!(MarketLists::parse("", r#"patterns = ["(unclosed"]"#).is_err());
+        assert!(MarketLists::parse("markets = 3", "").is_err());
+    }
+
+    #[test]
+    fn test_reload_diffs_and_keeps_old_lists_on_error() {
+        let paths = tmp_paths("reload");
+        std::fs::write(&paths.watchlist, r#"markets = ["manifold:a", "manifold:b"]"#).unwrap();
+        let mut files = ListFiles::load(paths.clone()).unwrap();
+        assert_eq!(files.reload_if_changed(), None);
+
+        rewrite(&paths.watchlist, r#"markets = ["manifold:b", "betfair:1.5"]"#);
+        std::fs::write(&paths.blocklist, r#"patterns = ["(?i)crypto"]"#).unwrap();
+        let diff = files.reload_if_changed().unwrap();
+        assert_eq!(diff.watch_added, vec!["betfair:1.5".to_string()]);
+        assert_eq!(diff.watch_removed, vec!["manifold:a".to_string()]);
+        assert_eq!(diff.block_added, vec!["(?i)crypto".to_string()]);
+        assert!(diff.block_removed.is_empty());
+
+        // A bad regex is rejected whole; the old lists stay in force.
+        rewrite(&paths.blocklist, r#"patterns = ["(?i)crypto", "[bad"]"#);
+        assert_eq!(files.reload_if_changed(), None);
+        assert!(files.lists().is_blocked("Crypto up?"));
+        assert!(files.lists().is_watched("betfair", "1.5"));
+
+        // Fixing it applies on the next check.
+        rewrite(&paths.blocklist, r#"patterns = []"#);
+        let diff = files.reload_if_changed().unwrap();
+        assert_eq!(diff.block_removed, vec!["(?i)crypto".to_string()]);
+        assert!(!files.lists().is_blocked("Crypto up?"));
+    }
+
+    #[test]
+    fn test_tag_rules_reload_with_lists() {
+        let paths = tmp_paths("tags");
+        let paths = paths.clone().with_tags(paths.watchlist.with_file_name("tags.toml"));
+        let tags_path = paths.tags.clone().unwrap();
+        std::fs::write(&tags_path, "[[rules]]\ntag = \"f1\"\nquestion = \"(?i)grand prix\"").unwrap();
+        let mut files = ListFiles::load(paths.clone()).unwrap();
+        let market = crate::testkit::market("m", "manifold", "Monaco Grand Prix?", crate::types::MarketCategory::Sports, 0.5, 10.0, 48);
+        assert_eq!(files.lists().tags_for(&market), ["f1"]);
+
+        rewrite(&tags_path, "[[rules]]\ntag = \"motorsport\"\nquestion = \"(?i)grand prix\"");
+        let diff = files.reload_if_changed().unwrap();
+        assert!(diff.tags_changed && diff.watch_added.is_empty());
+        assert_eq!(files.lists().tags_for(&market), ["motorsport"]);
+
+        // An invalid rule is rejected whole; the old rules stay in force.
+        rewrite(&tags_path, "[[rules]]\ntag = \"bad tag\"\nquestion = \"x\"");
+        assert_eq!(files.reload_if_changed(), None);
+        assert_eq!(files.lists().tags_for(&market), ["motorsport"]);
+
+        // Editing the watchlist leaves the rules alone.
+        let edit = ListEdit { add_markets: vec!["manifold:m".into()], ..Default::default() };
+        assert!(!paths.apply(&edit).unwrap().tags_changed);
+        assert!(ListFiles::load(paths).is_err());
+    }
+
+    #[test]
+    fn test_apply_writes_back_and_round_trips() {
+        let paths = tmp_paths("apply");
+        std::fs::write(&paths.watchlist, r#"markets = ["manifold:a"]"#).unwrap();
+        let mut files = ListFiles::load(paths.clone()).unwrap();
+
+        let edit = ListEdit {
+            add_markets: vec!["polymarket:xyz".into()],
+            remove_markets: vec!["manifold:a".into()],
+            add_patterns: vec!["(?i)weather".into()],
+            ..Default::default()
+        };
+        let diff = paths.apply(&edit).unwrap();
+        assert_eq!(diff.watch_added, vec!["polymarket:xyz".to_string()]);
+        assert_eq!(diff.watch_removed, vec!["manifold:a".to_string()]);
+
+        // The agent sees the same change through the files.
+        let seen = files.reload_if_changed().unwrap();
+        assert_eq!(seen, diff);
+        assert!(files.lists().is_watched("polymarket", "xyz"));
+        assert!(files.lists().is_blocked("Weather in Perth?"));
+
+        // An invalid edit writes nothing.
+        let before = std::fs::read_to_string(&paths.watchlist).unwrap();
+        let bad = ListEdit { add_markets: vec!["nowhere:1".into()], ..Default::default() };
+        assert!(paths.apply(&bad).is_err());
+        assert_eq!(std::fs::read_to_string(&paths.watchlist).unwrap(), before);
+    }
+}
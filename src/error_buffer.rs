@@ -0,0 +1,146 @@
+//! Recent error-level log events.
+//!
+//! [`ErrorLayer`] sits in the subscriber next to the log formatter (see
+//! `init_logging`) and copies every `ERROR` event into a shared ring of the
+//! last [`RECENT_ERRORS`], which `/api/diagnostics` serves so an operator
+//! can see what went wrong without reading the logs on the host. Events
+//! pass the subscriber's filter first, so only what is logged is captured.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Error events kept.
+pub const RECENT_ERRORS: usize = 50;
+
+/// One captured error event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CapturedError {
+    pub timestamp: DateTime<Utc>,
+    /// Module the event was logged from.
+    pub target: String,
+    pub message: String,
+    /// The event's other fields, formatted.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Ring buffer of the latest error events, shared by the capturing layer
+/// and its readers.
+#[derive(Debug, Clone)]
+pub struct RecentErrors {
+    events: Arc<Mutex<VecDeque<CapturedError>>>,
+    capacity: usize,
+}
+
+impl Default for RecentErrors {
+    fn default() -> Self {
+        Self::new(RECENT_ERRORS)
+    }
+}
+
+impl RecentErrors {
+    pub fn new(capacity: usize) -> Self {
+        Self { events: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))), capacity: capacity.max(1) }
+    }
+
+    /// Add `event`, dropping the oldest when full.
+    pub fn push(&self, event: CapturedError) {
+        let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Captured events, oldest first.
+    pub fn snapshot(&self) -> Vec<CapturedError> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+
+    /// A layer capturing into this buffer.
+    pub fn layer(&self) -> ErrorLayer {
+        ErrorLayer { errors: self.clone() }
+    }
+}
+
+/// Subscriber layer copying `ERROR` events into [`RecentErrors`].
+pub struct ErrorLayer {
+    errors: RecentErrors,
+}
+
+/// Collects an event's message and fields.
+#[derive(Default)]
+struct EventFields {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl EventFields {
+    fn set(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for EventFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.set(field, format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ErrorLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::ERROR {
+            return;
+        }
+        let mut visitor = EventFields::default();
+        event.record(&mut visitor);
+        self.errors.push(CapturedError {
+            timestamp: Utc::now(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_layer_captures_errors_only_and_keeps_the_latest() {
+        let errors = RecentErrors::new(2);
+        let subscriber = tracing_subscriber::registry().with(errors.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!("not captured");
+            tracing::error!(platform = "betfair", attempts = 3, "first");
+            tracing::error!("second");
+            tracing::error!(error = %"boom", "third");
+        });
+
+        let captured = errors.snapshot();
+        assert_eq!(captured.iter().map(|e| e.message.as_str()).collect::<Vec<_>>(), ["second", "third"]);
+        assert_eq!(captured[1].fields["error"], "boom");
+        assert!(captured[1].target.starts_with("oracle"));
+    }
+}
@@ -0,0 +1,158 @@
+//! Captured API response corpus under `tests/fixtures/`.
+//!
+//! Every external response shape the platform and LLM clients deserialize
+//! has a fixture here, and each client's tests run its fixtures through
+//! the production types, so upstream schema drift fails a test instead of
+//! silently zeroing a platform's scan. Public read-only endpoints can be
+//! re-captured with [`refresh`] (feature `fixtures`):
+//!
+//! ```text
+//! cargo test --features fixtures -- --ignored refresh_fixtures
+//! ```
+//!
+//! Authenticated and order-placing endpoints are captured by hand from a
+//! paper account and their credentials scrubbed.
+//!
+//! `storage/` holds artifacts in superseded schema versions, one per
+//! migration step (see [`crate::storage::migrations`]).
+
+use std::path::PathBuf;
+
+/// One fixture file and where it comes from.
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture {
+    /// Path under `tests/fixtures/`.
+    pub path: &'static str,
+    /// Public GET endpoint [`refresh`] re-captures it from; `None` for
+    /// fixtures captured by hand.
+    pub url: Option<&'static str>,
+}
+
+/// Every fixture in the corpus.
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        path: "manifold/search-markets.json",
+        url: Some("https://api.manifold.markets/v0/search-markets?term=&filter=open&contractType=BINARY&sort=liquidity&limit=2"),
+    },
+    Fixture { path: "manifold/market.json", url: None },
+    Fixture { path: "manifold/bet.json", url: None },
+    Fixture { path: "manifold/bets.json", url: None },
+    Fixture { path: "manifold/me.json", url: None },
+    Fixture {
+        path: "metaculus/questions.json",
+        url: Some("https://www.metaculus.com/api2/questions/?limit=2&offset=0&status=open&type=binary&order_by=-nr_forecasters&has_group=false"),
+    },
+    Fixture {
+        path: "polymarket/gamma-markets.json",
+        url: Some("https://gamma-api.polymarket.com/markets?active=true&closed=false&limit=1"),
+    },
+    Fixture { path: "betfair/listMarketCatalogue.json", url: None },
+    Fixture { path: "betfair/listMarketBook.json", url: None },
+    Fixture { path: "betfair/placeOrders.json", url: None },
+    Fixture { path: "betfair/listCurrentOrders.json", url: None },
+    Fixture { path: "betfair/getAccountFunds.json", url: None },
+    Fixture {
+        path: "kalshi/markets.json",
+        url: Some("https://api.elections.kalshi.com/trade-api/v2/markets?status=open&limit=2"),
+    },
+    Fixture { path: "kalshi/orderbook.json", url: None },
+    Fixture { path: "kalshi/order.json", url: None },
+    Fixture { path: "kalshi/balance.json", url: None },
+    Fixture { path: "fred/series-observations.json", url: None },
+    Fixture { path: "fred/series-release.json", url: None },
+    Fixture { path: "fred/release-dates.json", url: None },
+    Fixture { path: "api-sports/fixtures-next.json", url: None },
+    Fixture { path: "api-sports/fixtures-last.json", url: None },
+    Fixture { path: "api-sports/odds.json", url: None },
+    Fixture { path: "api-sports/games.json", url: None },
+    Fixture { path: "kalshi/positions.json", url: None },
+    Fixture { path: "anthropic/messages.json", url: None },
+    Fixture { path: "openrouter/chat-completions.json", url: None },
+    Fixture { path: "openai/chat-completions.json", url: None },
+    Fixture { path: "openai/chat-completions-json.json", url: None },
+    Fixture { path: "xai/chat-completions.json", url: None },
+    Fixture { path: "storage/state-v0.json", url: None },
+    Fixture { path: "storage/state-v1.json", url: None },
+    Fixture { path: "storage/lifecycle-v0.json", url: None },
+    Fixture { path: "storage/archive-index-v0.json", url: None },
+];
+
+/// Root of the fixture corpus.
+pub fn dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+/// Contents of the fixture at `path` (relative to [`dir`]).
+///
+/// # Panics
+/// If the file is missing — fixtures are part of the source tree.
+pub fn read(path: &str) -> String {
+    let file = dir().join(path);
+    std::fs::read_to_string(&file).unwrap_or_else(|e| panic!("fixture {}: {e}", file.display()))
+}
+
+/// Re-download every fixture with a public URL, pretty-printed in place.
+/// Returns the paths written. Review the diff before committing: live
+/// data replaces the captured examples.
+#[cfg(feature = "fixtures")]
+pub async fn refresh() -> anyhow::Result<Vec<&'static str>> {
+    use anyhow::Context;
+
+    let http = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context("Failed to build fixture HTTP client")?;
+    let mut written = Vec::new();
+    for fixture in FIXTURES {
+        let Some(url) = fixture.url else { continue };
+        let body: serde_json::Value = http
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Fetching {url}"))?
+            .json()
+            .await
+            .with_context(|| format!("Parsing {url}"))?;
+        std::fs::write(dir().join(fixture.path), serde_json::to_string_pretty(&body)? + "\n")
+            .with_context(|| format!("Writing {}", fixture.path))?;
+        written.push(fixture.path);
+    }
+    Ok(written)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_corpus_matches_registry() {
+        let registered: BTreeSet<String> = FIXTURES.iter().map(|f| f.path.to_string()).collect();
+        let mut on_disk = BTreeSet::new();
+        for platform in std::fs::read_dir(dir()).unwrap() {
+            let platform = platform.unwrap();
+            for file in std::fs::read_dir(platform.path()).unwrap() {
+                let name = file.unwrap().file_name().into_string().unwrap();
+                on_disk.insert(format!("{}/{name}", platform.file_name().to_string_lossy()));
+            }
+        }
+        assert_eq!(registered, on_disk);
+        for fixture in FIXTURES {
+            serde_json::from_str::<serde_json::Value>(&read(fixture.path))
+                .unwrap_or_else(|e| panic!("{} is not valid JSON: {e}", fixture.path));
+        }
+    }
+
+    #[cfg(feature = "fixtures")]
+    #[tokio::test]
+    #[ignore = "hits live APIs; run by hand to refresh the corpus"]
+    async fn refresh_fixtures() {
+        let written = refresh().await.unwrap();
+        assert_eq!(written.len(), FIXTURES.iter().filter(|f| f.url.is_some()).count());
+    }
+}
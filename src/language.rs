@@ -0,0 +1,181 @@
+//! Question language detection and text normalisation.
+//!
+//! Manifold and Metaculus list questions in many languages; the keyword
+//! classifiers and the LLM prompt only work on English, so the scanner
+//! drops markets whose question is detected in a language outside
+//! `scanner.allowed_languages`. [`detect`] is a heuristic: questions in a
+//! non-Latin script are named by their script, Latin-script questions by
+//! which language's common function words they use most. Questions too
+//! short or plain to tell ("TRUMP ELECTION") are undetermined and kept.
+//!
+//! [`normalize`] folds the typographic variants that break keyword and
+//! token matching: it applies Unicode NFC, turns smart quotes and dashes
+//! into their ASCII forms, and strips emoji and bracketed tags such as
+//! "[Resolves to poll]".
+
+use unicode_normalization::UnicodeNormalization;
+
+// ---------------------------------------------------------------------------
+// Normalisation
+// ---------------------------------------------------------------------------
+
+/// `question` in NFC with quotes and dashes folded to ASCII, emoji and
+/// `[…]` tags removed and whitespace collapsed.
+pub fn normalize(question: &str) -> String {
+    let mut out = String::with_capacity(question.len());
+    let mut depth = 0usize;
+    for c in question.nfc() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth > 0 => depth -= 1,
+            _ if depth > 0 => {}
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => out.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}' | '\u{00BB}' => {
+                out.push('"')
+            }
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => out.push('-'),
+            c if is_emoji(c) => {}
+            c if c.is_whitespace() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Pictographs, their modifiers and the joiners between them.
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1F000}'..='\u{1FAFF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{FE0E}'..='\u{FE0F}'
+            | '\u{200D}'
+            | '\u{20E3}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Detection
+// ---------------------------------------------------------------------------
+
+/// Common function words of the Latin-script languages told apart, with
+/// their ISO 639-1 codes. Words shared between languages ("a", "de") count
+/// for each.
+const FUNCTION_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "will", "be", "by", "of", "in", "a", "an", "to", "before", "end", "is", "and", "or", "than", "at", "on", "win"]),
+    ("es", &["el", "la", "los", "las", "de", "del", "en", "un", "una", "antes", "fin", "y", "o", "que", "será", "ganará", "habrá", "más"]),
+    ("fr", &["le", "la", "les", "de", "du", "des", "un", "une", "avant", "fin", "et", "ou", "que", "sera", "est", "qui", "au", "plus"]),
+    ("de", &["der", "die", "das", "den", "dem", "des", "ein", "eine", "vor", "ende", "und", "oder", "wird", "ist", "im", "bis", "mehr", "als"]),
+    ("pt", &["o", "os", "a", "as", "de", "do", "da", "em", "um", "uma", "antes", "fim", "e", "ou", "que", "será", "vai", "até", "mais"]),
+    ("it", &["il", "lo", "la", "gli", "le", "di", "del", "della", "un", "una", "prima", "fine", "e", "o", "che", "sarà", "entro", "più"]),
+    ("nl", &["de", "het", "een", "van", "voor", "eind", "en", "of", "dat", "wordt", "zal", "is", "in", "meer", "dan"]),
+];
+
+/// Function words a Latin-script question needs before it is assigned a
+/// language.
+const MIN_FUNCTION_WORDS: usize = 2;
+
+/// ISO 639-1 code of the language `text` is most likely written in, or
+/// `None` when it can't be told.
+pub fn detect(text: &str) -> Option<&'static str> {
+    if let Some(language) = by_script(text) {
+        return Some(language);
+    }
+    let lowered = text.to_lowercase();
+    let words: Vec<&str> = lowered.split(|c: char| !c.is_alphabetic()).filter(|w| !w.is_empty()).collect();
+    let scores = FUNCTION_WORDS
+        .iter()
+        .map(|(language, vocabulary)| (*language, words.iter().filter(|w| vocabulary.contains(w)).count()));
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut tied = false;
+    for (language, score) in scores {
+        match best {
+            Some((_, top)) if score == top => tied = true,
+            Some((_, top)) if score < top => {}
+            _ => {
+                best = Some((language, score));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|&(_, score)| score >= MIN_FUNCTION_WORDS && !tied).map(|(language, _)| language)
+}
+
+/// Language of the non-Latin script most of the letters of `text` are in,
+/// if more than half of them are.
+fn by_script(text: &str) -> Option<&'static str> {
+    let mut letters = 0usize;
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        let Some(language) = script_language(c) else { continue };
+        match counts.iter_mut().find(|(l, _)| *l == language) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((language, 1)),
+        }
+    }
+    // Kana marks Japanese even among mostly Han characters.
+    if counts.iter().any(|&(l, _)| l == "ja") {
+        let cjk: usize = counts.iter().filter(|(l, _)| matches!(*l, "ja" | "zh")).map(|(_, n)| n).sum();
+        counts.retain(|(l, _)| *l != "zh");
+        if let Some((_, n)) = counts.iter_mut().find(|(l, _)| *l == "ja") {
+            *n = cjk;
+        }
+    }
+    counts.into_iter().max_by_key(|&(_, n)| n).filter(|&(_, n)| n * 2 > letters).map(|(language, _)| language)
+}
+
+/// Language a letter's script stands for, `None` for Latin letters.
+fn script_language(c: char) -> Option<&'static str> {
+    Some(match c {
+        '\u{0400}'..='\u{04FF}' => "ru",
+        '\u{0370}'..='\u{03FF}' => "el",
+        '\u{0590}'..='\u{05FF}' => "he",
+        '\u{0600}'..='\u{06FF}' => "ar",
+        '\u{0900}'..='\u{097F}' => "hi",
+        '\u{0E00}'..='\u{0E7F}' => "th",
+        '\u{3040}'..='\u{30FF}' => "ja",
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => "ko",
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => "zh",
+        _ => return None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_question_languages() {
+        let cases = [
+            ("Will the Fed cut rates before the end of 2026?", Some("en")),
+            ("¿Ganará el Real Madrid la Liga antes del fin de la temporada?", Some("es")),
+            ("Est-ce que le prix du pétrole sera plus de 100 dollars avant la fin de l'année ?", Some("fr")),
+            ("Wird die AfD bei der nächsten Bundestagswahl mehr als 25% der Stimmen erhalten?", Some("de")),
+            ("O Lula vai ganhar a eleição até o fim do ano?", Some("pt")),
+            ("Путин останется президентом до конца 2026 года?", Some("ru")),
+            ("2026年までに日本の首相は交代しますか？", Some("ja")),
+            ("中国的GDP增长率会超过5%吗？", Some("zh")),
+            ("윤석열 대통령이 탄핵될까요?", Some("ko")),
+            ("TRUMP ELECTION", None),
+        ];
+        for (question, language) in cases {
+            assert_eq!(detect(question), language, "{question}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_folds_quotes_dashes_and_tags() {
+        assert_eq!(normalize("Will \u{201C}X\u{201D} happen?"), normalize("Will \"X\" happen?"));
+        assert_eq!(normalize("Trump\u{2019}s 2025\u{2013}2026 term"), "Trump's 2025-2026 term");
+        assert_eq!(normalize("[Resolves to poll] Will BTC hit $100k? \u{1F680}\u{1F680}"), "Will BTC hit $100k?");
+        // Decomposed "é" composes, so it stays one word.
+        assert_eq!(normalize("Pok\u{0065}\u{0301}mon"), "Pok\u{00E9}mon");
+    }
+}
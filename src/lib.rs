@@ -5,6 +5,9 @@
 
 pub mod config;
 pub mod types;
+pub mod question_parser;
+pub mod language;
+pub mod embeddings;
 pub mod platforms;
 pub mod data;
 pub mod llm;
@@ -12,4 +15,17 @@ pub mod strategy;
 pub mod engine;
 pub mod storage;
 pub mod dashboard;
+pub mod ctl;
 pub mod backtest;
+pub mod diagnostics;
+pub mod error_buffer;
+pub mod alerts;
+pub mod notifications;
+pub mod telemetry;
+pub mod prometheus;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
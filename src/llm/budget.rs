@@ -0,0 +1,262 @@
+//! Prompt token budgets.
+//!
+//! A batch prompt grows with every market and context summary in it. Past
+//! the model's context window the request fails or, worse, the answer is
+//! cut short and the last markets silently go unestimated. With
+//! `llm.context_window` set, [`plan`] splits a cycle's markets into batches
+//! of at most `llm.max_markets_per_batch` and fits each one to the window:
+//! data summaries are cut to an even share of it, and if the batch still
+//! doesn't fit its lowest-priority markets are dropped and left for the
+//! next cycle.
+//!
+//! Token counts use the usual four-characters-per-token estimate, which
+//! runs slightly high for English prose; [`SYSTEM_PROMPT_TOKENS`] of the
+//! window are kept back for the system prompt.
+
+use std::ops::Range;
+
+use super::anthropic::AnthropicClient;
+use crate::types::{DataContext, Market};
+
+/// Characters per token in [`estimate_tokens`].
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Window reserved for the system prompt, whichever provider sends it.
+pub const SYSTEM_PROMPT_TOKENS: usize = 1_000;
+
+/// Marks a summary that was cut short.
+const ELLIPSIS: char = '…';
+
+/// Estimated tokens in `text`.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// `text` cut to at most `tokens`, ending at a line break or, failing one
+/// in the second half of the cut, a word boundary, and marked with `…`.
+/// Text that fits is returned whole.
+pub fn truncate_to_tokens(text: &str, tokens: usize) -> String {
+    let max_chars = tokens * CHARS_PER_TOKEN;
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let cut: String = text.chars().take(max_chars - 1).collect();
+    // A cut landing exactly on a space keeps the whole word before it.
+    let whole_word = text.chars().nth(max_chars - 1).is_some_and(char::is_whitespace);
+    let at = cut
+        .rfind('\n')
+        .filter(|&i| i >= cut.len() / 2)
+        .or_else(|| if whole_word { Some(cut.len()) } else { cut.rfind(char::is_whitespace) })
+        .unwrap_or(0);
+    format!("{}{ELLIPSIS}", cut[..at].trim_end())
+}
+
+/// Token limits of one request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptBudget {
+    /// Model context window: prompt and answer together.
+    pub context_window: usize,
+    /// Tokens reserved for the answer.
+    pub max_output_tokens: usize,
+}
+
+impl PromptBudget {
+    pub fn new(context_window: usize, max_output_tokens: usize) -> Self {
+        Self { context_window, max_output_tokens }
+    }
+
+    /// Tokens left for the user prompt.
+    pub fn input_tokens(&self) -> usize {
+        self.context_window.saturating_sub(self.max_output_tokens + SYSTEM_PROMPT_TOKENS)
+    }
+}
+
+/// A batch prompt and the markets it covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchPrompt {
+    pub prompt: String,
+    /// Positions of the markets in the prompt, in order.
+    pub included: Vec<usize>,
+    /// Positions of the markets left out to fit the budget.
+    pub dropped: Vec<usize>,
+    /// Data text sent for each included market, cut to fit.
+    pub data: Vec<String>,
+}
+
+/// Fit entries into `available(n)` tokens, `n` being the entries kept.
+/// Entry `i` costs `fixed[i]` plus its `data[i]`, which is cut to an even
+/// share of the space; entries are dropped from the end until the rest
+/// fit. Returns the data of the entries kept.
+pub fn fit(data: &[String], fixed: &[usize], available: impl Fn(usize) -> usize) -> Vec<String> {
+    for n in (1..=data.len()).rev() {
+        let space = available(n);
+        let share = space / n;
+        let capped: Vec<String> =
+            data[..n].iter().zip(fixed).map(|(d, &f)| truncate_to_tokens(d, share.saturating_sub(f))).collect();
+        let used: usize = fixed[..n].iter().sum::<usize>() + capped.iter().map(|d| estimate_tokens(d)).sum::<usize>();
+        if used <= space {
+            return capped;
+        }
+    }
+    Vec::new()
+}
+
+/// A cycle's markets split into batches that each fit the budget.
+#[derive(Debug, Clone, Default)]
+pub struct BatchPlan {
+    /// Markets to estimate, in order, contexts cut to what was sent.
+    pub markets: Vec<(Market, DataContext)>,
+    /// Range of `markets` in each call.
+    pub batches: Vec<Range<usize>>,
+    /// Positions in the input of the markets left out.
+    pub dropped: Vec<usize>,
+}
+
+/// Split `markets` (highest priority first) into batches of at most
+/// `max_per_batch`, each fitted to `budget` if there is one.
+pub fn plan(markets: &[(Market, DataContext)], max_per_batch: usize, budget: Option<&PromptBudget>) -> BatchPlan {
+    let mut out = BatchPlan::default();
+    for (chunk_index, chunk) in markets.chunks(max_per_batch.max(1)).enumerate() {
+        let offset = chunk_index * max_per_batch.max(1);
+        let fitted = AnthropicClient::build_batch_prompt(chunk, budget);
+        let start = out.markets.len();
+        for (&i, data) in fitted.included.iter().zip(fitted.data) {
+            let (market, context) = &chunk[i];
+            let context = if data == context.render() {
+                context.clone()
+            } else {
+                DataContext { summary: data, sections: Vec::new(), ..context.clone() }
+            };
+            out.markets.push((market.clone(), context));
+        }
+        if out.markets.len() > start {
+            out.batches.push(start..out.markets.len());
+        }
+        out.dropped.extend(fitted.dropped.iter().map(|i| offset + i));
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MarketCategory;
+
+    fn market(i: usize, summary: &str) -> (Market, DataContext) {
+        let mut context = DataContext::empty(MarketCategory::Weather);
+        context.summary = summary.to_string();
+        (Market { id: format!("m{i}"), question: format!("Will it rain in city {i}?"), ..Market::sample() }, context)
+    }
+
+    fn long_summary(words: usize) -> String {
+        (0..words).map(|i| format!("reading{i}")).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        // Characters, not bytes.
+        assert_eq!(estimate_tokens("ééééé"), 2);
+    }
+
+    #[test]
+    fn test_truncation_ends_on_word_boundary() {
+        let text = "rain expected over the weekend with heavy falls";
+        assert_eq!(truncate_to_tokens(text, 100), text);
+        // 19 characters kept, then back to the last space.
+        let cut = truncate_to_tokens(text, 5);
+        assert_eq!(cut, "rain expected over…");
+        assert!(estimate_tokens(&cut) <= 5);
+        // A cut landing just past a word keeps that word.
+        assert_eq!(truncate_to_tokens("abcdefg hij", 2), "abcdefg…");
+        // A single word too long for the budget is dropped entirely.
+        assert_eq!(truncate_to_tokens("Supercalifragilistic", 2), "…");
+        assert_eq!(truncate_to_tokens(text, 0), "");
+    }
+
+    #[test]
+    fn test_truncation_prefers_line_break() {
+        let text = "[weather @ 2026-10-16]\nRain 80%, wind 20 km/h\nFront arrives Sunday";
+        assert_eq!(truncate_to_tokens(text, 12), "[weather @ 2026-10-16]\nRain 80%, wind 20 km/h…");
+    }
+
+    #[test]
+    fn test_budget_reserves_output_and_system_prompt() {
+        let budget = PromptBudget::new(8_000, 2_048);
+        assert_eq!(budget.input_tokens(), 8_000 - 2_048 - SYSTEM_PROMPT_TOKENS);
+        assert_eq!(PromptBudget::new(1_000, 2_048).input_tokens(), 0);
+    }
+
+    #[test]
+    fn test_fit_shares_space_and_drops_from_end() {
+        let data = vec![long_summary(100), "short".to_string(), long_summary(100)];
+        // 300 tokens, 50 fixed each: 100 per entry, so 50 of data each.
+        let capped = fit(&data, &[50, 50, 50], |_| 300);
+        assert_eq!(capped.len(), 3);
+        assert_eq!(capped[1], "short");
+        assert!(capped.iter().all(|d| estimate_tokens(d) <= 50));
+        assert!(capped[0].ends_with('…'));
+
+        // Fixed costs alone overrun with three entries: the last goes.
+        let capped = fit(&data, &[120, 120, 120], |_| 300);
+        assert_eq!(capped.len(), 2);
+        assert!(capped.iter().all(|d| estimate_tokens(d) <= 30));
+        assert!(fit(&data, &[400, 400, 400], |_| 300).is_empty());
+    }
+
+    #[test]
+    fn test_batch_prompt_fits_budget_and_reports_dropped() {
+        let markets: Vec<_> = (0..4).map(|i| market(i, &long_summary(400))).collect();
+        let unbounded = AnthropicClient::build_batch_prompt(&markets, None);
+        assert_eq!((unbounded.included, unbounded.dropped), (vec![0, 1, 2, 3], vec![]));
+
+        let budget = PromptBudget::new(4_000, 1_000);
+        let fitted = AnthropicClient::build_batch_prompt(&markets, Some(&budget));
+        assert_eq!(fitted.included, vec![0, 1, 2, 3]);
+        assert!(estimate_tokens(&fitted.prompt) <= budget.input_tokens());
+        assert!(fitted.data.iter().all(|d| d.ends_with('…') && d.len() < markets[0].1.summary.len()));
+        assert!(fitted.prompt.contains("MARKET 4 (ID: m3)"));
+
+        // Long questions: the fixed lines of all four overrun the window.
+        let wordy: Vec<_> = (0..4)
+            .map(|i| {
+                let (mut m, c) = market(i, "dry");
+                m.question = long_summary(60);
+                (m, c)
+            })
+            .collect();
+        let tight = PromptBudget::new(800 + 1_000 + SYSTEM_PROMPT_TOKENS, 1_000);
+        let fitted = AnthropicClient::build_batch_prompt(&wordy, Some(&tight));
+        assert_eq!((fitted.included, fitted.dropped), (vec![0, 1, 2], vec![3]));
+        assert!(estimate_tokens(&fitted.prompt) <= tight.input_tokens());
+        assert!(fitted.prompt.contains("following 3 markets"));
+        assert!(!fitted.prompt.contains("ID: m3"));
+    }
+
+    #[test]
+    fn test_plan_splits_batches_and_cuts_contexts() {
+        let markets: Vec<_> = (0..5).map(|i| market(i, &long_summary(400))).collect();
+        let plan_all = plan(&markets, 2, None);
+        assert_eq!(plan_all.batches, vec![0..2, 2..4, 4..5]);
+        assert_eq!(plan_all.markets[0].1.summary, markets[0].1.summary);
+        assert!(plan_all.dropped.is_empty());
+
+        let budget = PromptBudget::new(2_500, 500);
+        let fitted = plan(&markets, 2, Some(&budget));
+        assert_eq!(fitted.markets.len(), 5);
+        assert_eq!(fitted.batches.len(), 3);
+        let (market, context) = &fitted.markets[4];
+        assert_eq!(market.id, "m4");
+        assert!(context.summary.ends_with('…'));
+        assert!(context.sections.is_empty());
+    }
+}
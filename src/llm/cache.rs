@@ -0,0 +1,258 @@
+//! Estimate cache.
+//!
+//! Markets rarely change between cycles, yet each cycle paid for a fresh
+//! estimate of every one. The cache keeps each market's last estimate with
+//! the YES price it was made at, and hands it back instead of a new LLM
+//! call while it is younger than the TTL and the price has moved less than
+//! the configured delta (see [`EstimateCacheConfig`]). Markets close to
+//! their deadline are always re-estimated: prices move fast near
+//! resolution.
+//!
+//! Cached estimates are the ones the model (and any self-critique)
+//! produced; cross-reference blending is applied afresh each cycle. A
+//! reused estimate costs nothing, so its `cost` and `tokens_used` are zero.
+//!
+//! Saved as one versioned JSON document (see
+//! [`crate::storage::migrations`]); expired entries are dropped on save.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config::EstimateCacheConfig;
+use crate::storage::migrations::{self, Artifact};
+use crate::types::{Estimate, Market};
+
+/// Version of the estimate cache layout.
+pub const ESTIMATE_CACHE_VERSION: u32 = 1;
+
+/// One market's last estimate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedEstimate {
+    pub platform: String,
+    pub market_id: String,
+    pub estimate: Estimate,
+    /// YES price when the estimate was made.
+    pub price_at_estimate: Decimal,
+    pub estimated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    schema_version: u32,
+    entries: Vec<CachedEstimate>,
+}
+
+/// Last estimate per market, keyed by `platform:market_id`.
+#[derive(Debug)]
+pub struct EstimateCache {
+    config: EstimateCacheConfig,
+    entries: BTreeMap<String, CachedEstimate>,
+}
+
+impl EstimateCache {
+    /// An empty cache saving to `config.file`.
+    pub fn new(config: EstimateCacheConfig) -> Self {
+        Self { config, entries: BTreeMap::new() }
+    }
+
+    /// Load the cache at `config.file`. A missing file is an empty cache.
+    pub fn load(config: EstimateCacheConfig) -> Result<Self> {
+        let path = PathBuf::from(&config.file);
+        if !path.exists() {
+            return Ok(Self::new(config));
+        }
+        let what = path.display().to_string();
+        let text = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {what}"))?;
+        let doc: serde_json::Value = serde_json::from_str(&text).with_context(|| format!("Failed to parse {what}"))?;
+        let doc = migrations::upgrade(Artifact::EstimateCache, doc, &what)?;
+        let file: CacheFile = serde_json::from_value(doc).with_context(|| format!("Failed to parse {what}"))?;
+        let entries = file
+            .entries
+            .into_iter()
+            .map(|e| (key(&e.platform, &e.market_id), e))
+            .collect();
+        Ok(Self { config, entries })
+    }
+
+    /// Drop expired entries and write the cache, replacing the file only
+    /// once the new one is complete.
+    pub fn save(&mut self, now: DateTime<Utc>) -> Result<()> {
+        let ttl = Duration::minutes(self.config.ttl_mins);
+        self.entries.retain(|_, e| now - e.estimated_at < ttl);
+        let file = CacheFile {
+            schema_version: ESTIMATE_CACHE_VERSION,
+            entries: self.entries.values().cloned().collect(),
+        };
+        let json = serde_json::to_string(&file).context("Failed to serialise estimate cache")?;
+        let path = self.path();
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        Path::new(&self.config.file)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The cached estimate for `market` if it may stand in for a new one
+    /// at `now`: younger than the TTL, the price within the allowed move,
+    /// and the deadline beyond the re-estimation horizon. Returned with
+    /// zero cost and tokens.
+    pub fn lookup(&self, market: &Market, now: DateTime<Utc>) -> Option<Estimate> {
+        let entry = self.entries.get(&key(&market.platform, &market.id))?;
+        let fresh = now - entry.estimated_at < Duration::minutes(self.config.ttl_mins);
+        let steady = (market.current_price_yes - entry.price_at_estimate).abs() < self.config.max_price_move;
+        let distant = market.deadline - now > Duration::hours(self.config.min_hours_to_deadline);
+        (fresh && steady && distant).then(|| Estimate {
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            ..entry.estimate.clone()
+        })
+    }
+
+    /// Remember `estimate` of `market`, made at `now`.
+    pub fn insert(&mut self, market: &Market, estimate: &Estimate, now: DateTime<Utc>) {
+        self.entries.insert(
+            key(&market.platform, &market.id),
+            CachedEstimate {
+                platform: market.platform.clone(),
+                market_id: market.id.clone(),
+                estimate: estimate.clone(),
+                price_at_estimate: market.current_price_yes,
+                estimated_at: now,
+            },
+        );
+    }
+}
+
+fn key(platform: &str, market_id: &str) -> String {
+    format!("{platform}:{market_id}")
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MarketCategory;
+    use rust_decimal_macros::dec;
+
+    fn market(price: Decimal, deadline: DateTime<Utc>) -> Market {
+        Market {
+            id: "m1".into(),
+            platform: "manifold".into(),
+            question: "Will it rain?".into(),
+            description: String::new(),
+            category: MarketCategory::Weather,
+            current_price_yes: price,
+            current_price_no: Decimal::ONE - price,
+            volume_24h: dec!(100),
+            liquidity: dec!(500),
+            deadline,
+            resolution_criteria: String::new(),
+            url: String::new(),
+            cross_refs: Default::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn estimate() -> Estimate {
+        Estimate {
+            probability: dec!(0.62),
+            confidence: dec!(0.8),
+            reasoning: "cached".into(),
+            tokens_used: 900,
+            cost: dec!(0.004),
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    fn config(file: &str) -> EstimateCacheConfig {
+        EstimateCacheConfig {
+            enabled: true,
+            ttl_mins: 60,
+            max_price_move: dec!(0.03),
+            min_hours_to_deadline: 24,
+            file: file.to_string(),
+        }
+    }
+
+    fn temp_file(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("oracle_estimate_cache_{name}_{}.json", uuid::Uuid::new_v4()));
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_hit_is_free_and_expires_after_ttl() {
+        let now = Utc::now();
+        let mut cache = EstimateCache::new(config(&temp_file("ttl")));
+        let m = market(dec!(0.50), now + Duration::days(10));
+        assert!(cache.lookup(&m, now).is_none());
+        cache.insert(&m, &estimate(), now);
+
+        let hit = cache.lookup(&m, now + Duration::minutes(59)).unwrap();
+        assert_eq!((hit.probability, hit.cost, hit.tokens_used), (dec!(0.62), Decimal::ZERO, 0));
+        assert!(cache.lookup(&m, now + Duration::minutes(60)).is_none());
+    }
+
+    #[test]
+    fn test_price_move_invalidates() {
+        let now = Utc::now();
+        let mut cache = EstimateCache::new(config(&temp_file("price")));
+        let deadline = now + Duration::days(10);
+        cache.insert(&market(dec!(0.50), deadline), &estimate(), now);
+        assert!(cache.lookup(&market(dec!(0.52), deadline), now).is_some());
+        assert!(cache.lookup(&market(dec!(0.47), deadline), now).is_none());
+        assert!(cache.lookup(&market(dec!(0.53), deadline), now).is_none());
+    }
+
+    #[test]
+    fn test_near_deadline_always_misses() {
+        let now = Utc::now();
+        let mut cache = EstimateCache::new(config(&temp_file("deadline")));
+        let m = market(dec!(0.50), now + Duration::hours(30));
+        cache.insert(&m, &estimate(), now);
+        assert!(cache.lookup(&m, now).is_some());
+        // Seven hours on, the market is inside the 24h horizon.
+        assert!(cache.lookup(&m, now + Duration::hours(7)).is_none());
+    }
+
+    #[test]
+    fn test_save_drops_expired_and_round_trips() {
+        let now = Utc::now();
+        let file = temp_file("persist");
+        let mut cache = EstimateCache::new(config(&file));
+        let m = market(dec!(0.50), now + Duration::days(10));
+        let mut stale = market(dec!(0.50), now + Duration::days(10));
+        stale.id = "stale".into();
+        cache.insert(&m, &estimate(), now);
+        cache.insert(&stale, &estimate(), now - Duration::hours(2));
+        cache.save(now).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        let loaded = EstimateCache::load(config(&file)).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.lookup(&m, now).unwrap().reasoning, "cached");
+        let _ = std::fs::remove_file(&file);
+    }
+}
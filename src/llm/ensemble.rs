@@ -0,0 +1,345 @@
+//! Multi-provider ensemble estimation.
+//!
+//! [`EnsembleEstimator`] sends every estimate or batch to all of its
+//! members concurrently and combines their answers market by market:
+//!
+//! - probability: the mean of the members' probabilities, weighted by each
+//!   member's confidence (a plain mean if every confidence is zero);
+//! - confidence: the members' mean confidence, cut by
+//!   `disagreement_penalty × spread`, where spread is the gap between the
+//!   highest and lowest member probability — two providers that disagree
+//!   leave the edge detector less sure than either alone;
+//! - reasoning: each member's reasoning under its model name;
+//! - tokens and cost: summed, since every member was paid for.
+//!
+//! A member that fails (or returns the wrong number of estimates) is left
+//! out of the combination; the call only fails when every member does.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::future::join_all;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use super::{CallParams, LlmEstimator};
+use crate::types::{DataContext, Estimate, Market};
+
+pub struct EnsembleEstimator {
+    members: Vec<Box<dyn LlmEstimator>>,
+    disagreement_penalty: Decimal,
+    name: String,
+}
+
+impl EnsembleEstimator {
+    /// `members` must not be empty; the first also serves critiques.
+    pub fn new(members: Vec<Box<dyn LlmEstimator>>, disagreement_penalty: Decimal) -> Self {
+        assert!(!members.is_empty(), "ensemble needs at least one member");
+        let names: Vec<&str> = members.iter().map(|m| m.model_name()).collect();
+        let name = format!("ensemble({})", names.join("+"));
+        Self { members, disagreement_penalty, name }
+    }
+
+    /// Combine one market's member estimates, given as `(model, estimate)`.
+    fn combine(&self, estimates: &[(&str, Estimate)]) -> Estimate {
+        let n = Decimal::from(estimates.len());
+        let weight: Decimal = estimates.iter().map(|(_, e)| e.confidence).sum();
+        let probability = if weight > Decimal::ZERO {
+            estimates.iter().map(|(_, e)| e.probability * e.confidence).sum::<Decimal>() / weight
+        } else {
+            estimates.iter().map(|(_, e)| e.probability).sum::<Decimal>() / n
+        };
+
+        let max = estimates.iter().map(|(_, e)| e.probability).max().unwrap_or_default();
+        let min = estimates.iter().map(|(_, e)| e.probability).min().unwrap_or_default();
+        let penalty = (Decimal::ONE - self.disagreement_penalty * (max - min)).max(Decimal::ZERO);
+        let confidence = weight / n * penalty;
+
+        let reasoning = estimates
+            .iter()
+            .map(|(model, e)| format!("[{model}] {}", e.reasoning))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Estimate {
+            probability,
+            confidence,
+            reasoning,
+            tokens_used: estimates.iter().map(|(_, e)| e.tokens_used).sum(),
+            cost: estimates.iter().map(|(_, e)| e.cost).sum(),
+            critique: None,
+            served_by: None,
+            tier: estimates[0].1.tier,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmEstimator for EnsembleEstimator {
+    async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(&self, market: &Market, context: &DataContext, params: &CallParams) -> Result<Estimate> {
+        let outcomes = join_all(self.members.iter().map(|m| m.estimate_with(market, context, params))).await;
+        let mut served = Vec::with_capacity(self.members.len());
+        let mut last_err = None;
+        for (member, outcome) in self.members.iter().zip(outcomes) {
+            match outcome {
+                Ok(e) => served.push((member.model_name(), e)),
+                Err(e) => {
+                    warn!(market_id = %market.id, model = %member.model_name(), error = %e, "Ensemble member failed — dropping it");
+                    last_err = Some(e);
+                }
+            }
+        }
+        match last_err {
+            Some(e) if served.is_empty() => Err(e.context("every ensemble member failed")),
+            _ => Ok(self.combine(&served)),
+        }
+    }
+
+    async fn batch_estimate_with(
+        &self,
+        markets: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let outcomes = join_all(self.members.iter().map(|m| m.batch_estimate_with(markets, params))).await;
+        let mut served = Vec::with_capacity(self.members.len());
+        let mut last_err = None;
+        for (member, outcome) in self.members.iter().zip(outcomes) {
+            let outcome = outcome.and_then(|estimates| {
+                anyhow::ensure!(
+                    estimates.len() == markets.len(),
+                    "{} returned {} estimates for {} markets",
+                    member.model_name(),
+                    estimates.len(),
+                    markets.len()
+                );
+                Ok(estimates)
+            });
+            match outcome {
+                Ok(estimates) => served.push((member.model_name(), estimates)),
+                Err(e) => {
+                    warn!(model = %member.model_name(), batch_size = markets.len(), error = %e, "Ensemble member failed batch — dropping it");
+                    last_err = Some(e);
+                }
+            }
+        }
+        if let Some(e) = last_err.filter(|_| served.is_empty()) {
+            return Err(e.context("every ensemble member failed"));
+        }
+
+        let mut columns: Vec<_> = served.into_iter().map(|(model, estimates)| (model, estimates.into_iter())).collect();
+        Ok((0..markets.len())
+            .map(|_| {
+                let row: Vec<_> = columns
+                    .iter_mut()
+                    .filter_map(|(model, estimates)| estimates.next().map(|e| (*model, e)))
+                    .collect();
+                self.combine(&row)
+            })
+            .collect())
+    }
+
+    /// Critique with the first member; the critique sees the combined
+    /// estimate as the one to revise.
+    async fn critique(&self, market: &Market, context: &DataContext, initial: &Estimate) -> Result<Estimate> {
+        self.members[0].critique(market, context, initial).await
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        self.members.iter().map(|m| m.cost_per_call()).sum()
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    /// Answers every market with a fixed probability and confidence, or
+    /// fails every call when `fails` is set.
+    struct MockLlm {
+        name: &'static str,
+        probability: Decimal,
+        confidence: Decimal,
+        cost: Decimal,
+        fails: bool,
+    }
+
+    impl MockLlm {
+        fn new(name: &'static str, probability: Decimal, confidence: Decimal) -> Self {
+            Self { name, probability, confidence, cost: dec!(0.01), fails: false }
+        }
+
+        fn failing(name: &'static str) -> Self {
+            Self { fails: true, ..Self::new(name, dec!(0.5), dec!(0.5)) }
+        }
+    }
+
+    #[async_trait]
+    impl LlmEstimator for MockLlm {
+        async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+            let mut batch = self.batch_estimate(&[(market.clone(), context.clone())]).await?;
+            Ok(batch.remove(0))
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            if self.fails {
+                anyhow::bail!("{}: 529 overloaded", self.name);
+            }
+            Ok(markets
+                .iter()
+                .map(|_| Estimate {
+                    probability: self.probability,
+                    confidence: self.confidence,
+                    reasoning: format!("{} thinks so", self.name),
+                    tokens_used: 100,
+                    cost: self.cost,
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                })
+                .collect())
+        }
+
+        fn cost_per_call(&self) -> Decimal { self.cost }
+        fn model_name(&self) -> &str { self.name }
+    }
+
+    fn markets(n: usize) -> Vec<(Market, DataContext)> {
+        (0..n)
+            .map(|i| {
+                let m = Market {
+                    id: format!("m{i}"),
+                    platform: "manifold".to_string(),
+                    question: format!("Will m{i} happen?"),
+                    description: String::new(),
+                    category: crate::types::MarketCategory::Weather,
+                    current_price_yes: dec!(0.40),
+                    current_price_no: dec!(0.60),
+                    volume_24h: dec!(1000),
+                    liquidity: dec!(5000),
+                    deadline: Utc::now() + chrono::Duration::days(3),
+                    resolution_criteria: String::new(),
+                    url: String::new(),
+                    cross_refs: Default::default(),
+                    event_group: None,
+                    facts: None,
+                    tags: Vec::new(),
+                };
+                let ctx = DataContext {
+                    category: m.category,
+                    raw_data: serde_json::Value::Null,
+                    summary: String::new(),
+                    freshness: Utc::now(),
+                    source: "test".to_string(),
+                    cost: Decimal::ZERO,
+                    metaculus_forecast: None,
+                    metaculus_forecasters: None,
+                    manifold_price: None,
+                    sections: Vec::new(),
+                };
+                (m, ctx)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_probability_is_confidence_weighted() {
+        let llm = EnsembleEstimator::new(
+            vec![
+                Box::new(MockLlm::new("claude", dec!(0.60), dec!(0.8))),
+                Box::new(MockLlm::new("gpt", dec!(0.70), dec!(0.2))),
+            ],
+            Decimal::ZERO,
+        );
+        let (m, ctx) = &markets(1)[0];
+        let e = llm.estimate_probability(m, ctx).await.unwrap();
+        // (0.60 × 0.8 + 0.70 × 0.2) / 1.0
+        assert_eq!(e.probability, dec!(0.62));
+        assert_eq!(e.confidence, dec!(0.5));
+        assert_eq!((e.tokens_used, e.cost), (200, dec!(0.02)));
+        assert_eq!(e.reasoning, "[claude] claude thinks so\n\n[gpt] gpt thinks so");
+        assert_eq!(llm.model_name(), "ensemble(claude+gpt)");
+        assert_eq!(llm.cost_per_call(), dec!(0.02));
+    }
+
+    #[tokio::test]
+    async fn test_disagreement_cuts_confidence() {
+        let agree = EnsembleEstimator::new(
+            vec![
+                Box::new(MockLlm::new("claude", dec!(0.60), dec!(0.8))),
+                Box::new(MockLlm::new("gpt", dec!(0.60), dec!(0.6))),
+            ],
+            dec!(1.0),
+        );
+        let disagree = EnsembleEstimator::new(
+            vec![
+                Box::new(MockLlm::new("claude", dec!(0.50), dec!(0.8))),
+                Box::new(MockLlm::new("gpt", dec!(0.80), dec!(0.6))),
+                Box::new(MockLlm::new("grok", dec!(0.70), dec!(0.7))),
+            ],
+            dec!(2.0),
+        );
+        let (m, ctx) = &markets(1)[0];
+        assert_eq!(agree.estimate_probability(m, ctx).await.unwrap().confidence, dec!(0.7));
+        // Spread 0.30 at penalty 2.0: 0.7 × (1 − 0.6).
+        assert_eq!(disagree.estimate_probability(m, ctx).await.unwrap().confidence, dec!(0.28));
+
+        let opposed = EnsembleEstimator::new(
+            vec![
+                Box::new(MockLlm::new("claude", dec!(0.10), dec!(0.9))),
+                Box::new(MockLlm::new("gpt", dec!(0.90), dec!(0.9))),
+            ],
+            dec!(2.0),
+        );
+        let e = opposed.estimate_probability(m, ctx).await.unwrap();
+        assert_eq!((e.probability, e.confidence), (dec!(0.5), Decimal::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_failed_member_is_dropped() {
+        let llm = EnsembleEstimator::new(
+            vec![
+                Box::new(MockLlm::new("claude", dec!(0.60), dec!(0.8))),
+                Box::new(MockLlm::failing("gpt")),
+            ],
+            dec!(1.0),
+        );
+        let estimates = llm.batch_estimate(&markets(3)).await.unwrap();
+        assert_eq!(estimates.len(), 3);
+        for e in &estimates {
+            assert_eq!((e.probability, e.confidence, e.cost), (dec!(0.60), dec!(0.8), dec!(0.01)));
+            assert_eq!(e.reasoning, "[claude] claude thinks so");
+        }
+
+        let (m, ctx) = &markets(1)[0];
+        assert_eq!(llm.estimate_probability(m, ctx).await.unwrap().probability, dec!(0.60));
+    }
+
+    #[tokio::test]
+    async fn test_all_members_failing_is_an_error() {
+        let llm = EnsembleEstimator::new(
+            vec![Box::new(MockLlm::failing("claude")), Box::new(MockLlm::failing("gpt"))],
+            dec!(1.0),
+        );
+        let err = llm.batch_estimate(&markets(2)).await.unwrap_err();
+        assert!(format!("{err:#}").contains("every ensemble member failed"));
+        let (m, ctx) = &markets(1)[0];
+        assert!(llm.estimate_probability(m, ctx).await.is_err());
+    }
+}
@@ -0,0 +1,322 @@
+//! Provider failover for LLM estimation.
+//!
+//! [`FailoverEstimator`] splits each batch into `batch_size` chunks and sends
+//! them to the primary estimator. A chunk the primary fails (after its own
+//! retries) is re-issued to the secondary; if that fails too, the chunk
+//! degrades to echo estimates — the market price at low confidence, which
+//! the edge detector skips — so one overloaded provider never costs the
+//! cycle its scan and enrichment work. Every served estimate records which
+//! estimator produced it in [`Estimate::served_by`], and carries that
+//! estimator's cost.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::future::join_all;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::{error, warn};
+
+use super::{CallParams, LlmEstimator};
+use crate::types::{DataContext, Estimate, Market};
+
+pub struct FailoverEstimator {
+    primary: Box<dyn LlmEstimator>,
+    secondary: Box<dyn LlmEstimator>,
+    batch_size: usize,
+}
+
+impl FailoverEstimator {
+    pub fn new(primary: Box<dyn LlmEstimator>, secondary: Box<dyn LlmEstimator>, batch_size: usize) -> Self {
+        Self { primary, secondary, batch_size: batch_size.max(1) }
+    }
+
+    /// Run one chunk on `est`, tagging the results with its model name.
+    async fn serve(
+        est: &dyn LlmEstimator,
+        chunk: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let mut estimates = est.batch_estimate_with(chunk, params).await?;
+        anyhow::ensure!(
+            estimates.len() == chunk.len(),
+            "{} returned {} estimates for {} markets",
+            est.model_name(),
+            estimates.len(),
+            chunk.len()
+        );
+        for e in &mut estimates {
+            e.served_by = Some(est.model_name().to_string());
+        }
+        Ok(estimates)
+    }
+}
+
+/// Placeholder estimate for a market no provider could serve: the market
+/// price itself, so no edge is detected.
+fn echo(market: &Market, err: &anyhow::Error) -> Estimate {
+    Estimate {
+        probability: market.current_price_yes,
+        confidence: dec!(0.1),
+        reasoning: format!("All LLM providers failed: {err}"),
+        tokens_used: 0,
+        cost: Decimal::ZERO,
+        critique: None,
+        served_by: None,
+        tier: None,
+    }
+}
+
+/// LLM spend per serving estimator. Echo estimates cost nothing and are
+/// not listed.
+pub fn cost_by_provider<'a>(estimates: impl IntoIterator<Item = &'a Estimate>) -> BTreeMap<String, Decimal> {
+    let mut costs = BTreeMap::new();
+    for e in estimates {
+        if let Some(provider) = &e.served_by {
+            *costs.entry(provider.clone()).or_insert(Decimal::ZERO) += e.cost;
+        }
+    }
+    costs
+}
+
+#[async_trait]
+impl LlmEstimator for FailoverEstimator {
+    async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(&self, market: &Market, context: &DataContext, params: &CallParams) -> Result<Estimate> {
+        let (est, mut estimate) = match self.primary.estimate_with(market, context, params).await {
+            Ok(e) => (&self.primary, e),
+            Err(e) => {
+                warn!(market_id = %market.id, error = %e, secondary = %self.secondary.model_name(), "Primary LLM failed — failing over");
+                (&self.secondary, self.secondary.estimate_with(market, context, params).await?)
+            }
+        };
+        estimate.served_by = Some(est.model_name().to_string());
+        Ok(estimate)
+    }
+
+    async fn batch_estimate_with(
+        &self,
+        markets: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let chunks: Vec<_> = markets.chunks(self.batch_size).collect();
+        let primary = join_all(chunks.iter().map(|c| Self::serve(&*self.primary, c, params))).await;
+
+        let mut results = Vec::with_capacity(markets.len());
+        for (chunk, outcome) in chunks.into_iter().zip(primary) {
+            let err = match outcome {
+                Ok(estimates) => {
+                    results.extend(estimates);
+                    continue;
+                }
+                Err(e) => e,
+            };
+            warn!(
+                error = %err,
+                chunk_size = chunk.len(),
+                secondary = %self.secondary.model_name(),
+                "Primary LLM failed batch — failing over"
+            );
+            match Self::serve(&*self.secondary, chunk, params).await {
+                Ok(estimates) => results.extend(estimates),
+                Err(e) => {
+                    error!(error = %e, chunk_size = chunk.len(), "Secondary LLM failed batch — using market prices");
+                    results.extend(chunk.iter().map(|(m, _)| echo(m, &e)));
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Critique with the estimator that served `initial`, so the pass stays
+    /// same-model after a failover.
+    async fn critique(&self, market: &Market, context: &DataContext, initial: &Estimate) -> Result<Estimate> {
+        let est = if initial.served_by.as_deref() == Some(self.secondary.model_name()) {
+            &self.secondary
+        } else {
+            &self.primary
+        };
+        let mut revised = est.critique(market, context, initial).await?;
+        revised.served_by = Some(est.model_name().to_string());
+        Ok(revised)
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        self.primary.cost_per_call()
+    }
+
+    fn model_name(&self) -> &str {
+        self.primary.model_name()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers every market with a fixed probability, failing any batch
+    /// that contains one of `fail_on`. Records the batches it was sent.
+    struct MockLlm {
+        name: &'static str,
+        probability: Decimal,
+        cost: Decimal,
+        fail_on: Vec<&'static str>,
+        batches: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl MockLlm {
+        fn new(name: &'static str, probability: Decimal, cost: Decimal, fail_on: &[&'static str]) -> Self {
+            Self { name, probability, cost, fail_on: fail_on.to_vec(), batches: Arc::default() }
+        }
+    }
+
+    #[async_trait]
+    impl LlmEstimator for MockLlm {
+        async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+            let mut batch = self.batch_estimate(&[(market.clone(), context.clone())]).await?;
+            Ok(batch.remove(0))
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            let ids: Vec<String> = markets.iter().map(|(m, _)| m.id.clone()).collect();
+            self.batches.lock().unwrap().push(ids.clone());
+            if let Some(id) = ids.iter().find(|id| self.fail_on.contains(&id.as_str())) {
+                anyhow::bail!("529 overloaded (batch with {id})");
+            }
+            Ok(markets
+                .iter()
+                .map(|_| Estimate {
+                    probability: self.probability,
+                    confidence: dec!(0.8),
+                    reasoning: String::new(),
+                    tokens_used: 100,
+                    cost: self.cost,
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                })
+                .collect())
+        }
+
+        async fn critique(&self, _: &Market, _: &DataContext, initial: &Estimate) -> Result<Estimate> {
+            Ok(Estimate { reasoning: format!("critiqued by {}", self.name), ..initial.clone() })
+        }
+
+        fn cost_per_call(&self) -> Decimal { self.cost }
+        fn model_name(&self) -> &str { self.name }
+    }
+
+    fn markets(n: usize) -> Vec<(Market, DataContext)> {
+        (0..n)
+            .map(|i| {
+                let m = Market {
+                    id: format!("m{i}"),
+                    platform: "manifold".to_string(),
+                    question: format!("Will m{i} happen?"),
+                    description: String::new(),
+                    category: crate::types::MarketCategory::Weather,
+                    current_price_yes: dec!(0.40),
+                    current_price_no: dec!(0.60),
+                    volume_24h: dec!(1000),
+                    liquidity: dec!(5000),
+                    deadline: Utc::now() + chrono::Duration::days(3),
+                    resolution_criteria: String::new(),
+                    url: String::new(),
+                    cross_refs: Default::default(),
+                    event_group: None,
+                    facts: None,
+                    tags: Vec::new(),
+                };
+                let ctx = DataContext {
+                    category: m.category,
+                    raw_data: serde_json::Value::Null,
+                    summary: String::new(),
+                    freshness: Utc::now(),
+                    source: "test".to_string(),
+                    cost: Decimal::ZERO,
+                    metaculus_forecast: None,
+                    metaculus_forecasters: None,
+                    manifold_price: None,
+                    sections: Vec::new(),
+                };
+                (m, ctx)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_failed_batch_is_served_by_secondary() {
+        let primary = MockLlm::new("primary", dec!(0.70), dec!(0.01), &["m3"]);
+        let secondary = MockLlm::new("secondary", dec!(0.60), dec!(0.03), &[]);
+        let secondary_batches = secondary.batches.clone();
+        let llm = FailoverEstimator::new(Box::new(primary), Box::new(secondary), 2);
+
+        let estimates = llm.batch_estimate(&markets(6)).await.unwrap();
+        assert_eq!(estimates.len(), 6);
+        let served: Vec<_> = estimates.iter().map(|e| e.served_by.as_deref().unwrap()).collect();
+        assert_eq!(served, ["primary", "primary", "secondary", "secondary", "primary", "primary"]);
+        assert_eq!(estimates[2].probability, dec!(0.60));
+        assert_eq!(estimates[0].probability, dec!(0.70));
+        // Only the failed chunk was re-issued; the secondary is idle otherwise.
+        assert_eq!(*secondary_batches.lock().unwrap(), vec![vec!["m2".to_string(), "m3".to_string()]]);
+
+        let costs = cost_by_provider(&estimates);
+        assert_eq!(costs["primary"], dec!(0.04));
+        assert_eq!(costs["secondary"], dec!(0.06));
+        assert_eq!(llm.model_name(), "primary");
+    }
+
+    #[tokio::test]
+    async fn test_both_failing_degrades_to_echo() {
+        let primary = MockLlm::new("primary", dec!(0.70), dec!(0.01), &["m3"]);
+        let secondary = MockLlm::new("secondary", dec!(0.60), dec!(0.03), &["m3"]);
+        let llm = FailoverEstimator::new(Box::new(primary), Box::new(secondary), 2);
+
+        let input = markets(6);
+        let estimates = llm.batch_estimate(&input).await.unwrap();
+        assert_eq!(estimates.len(), 6);
+        for i in [2, 3] {
+            let e = &estimates[i];
+            assert_eq!(e.probability, input[i].0.current_price_yes);
+            assert_eq!((e.cost, e.served_by.as_deref()), (Decimal::ZERO, None));
+            assert!(e.reasoning.contains("529 overloaded"));
+        }
+        assert_eq!(estimates[4].served_by.as_deref(), Some("primary"));
+
+        let costs = cost_by_provider(&estimates);
+        assert_eq!(costs.len(), 1);
+        assert_eq!(costs["primary"], dec!(0.04));
+    }
+
+    #[tokio::test]
+    async fn test_single_estimate_and_critique_follow_serving_provider() {
+        let primary = MockLlm::new("primary", dec!(0.70), dec!(0.01), &["m0"]);
+        let secondary = MockLlm::new("secondary", dec!(0.60), dec!(0.03), &[]);
+        let llm = FailoverEstimator::new(Box::new(primary), Box::new(secondary), 5);
+
+        let input = markets(2);
+        let (m0, c0) = &input[0];
+        let (m1, c1) = &input[1];
+        let e0 = llm.estimate_probability(m0, c0).await.unwrap();
+        let e1 = llm.estimate_probability(m1, c1).await.unwrap();
+        assert_eq!(e0.served_by.as_deref(), Some("secondary"));
+        assert_eq!(e1.served_by.as_deref(), Some("primary"));
+
+        assert_eq!(llm.critique(m0, c0, &e0).await.unwrap().reasoning, "critiqued by secondary");
+        assert_eq!(llm.critique(m1, c1, &e1).await.unwrap().reasoning, "critiqued by primary");
+    }
+}
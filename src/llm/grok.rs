@@ -0,0 +1,494 @@
+//! Grok (xAI) LLM integration.
+//!
+//! Implements the `LlmEstimator` trait against xAI's OpenAI-compatible
+//! Chat Completions API at `api.x.ai`. Uses the same prompt templates and
+//! PROBABILITY/CONFIDENCE parsing as Anthropic.
+//!
+//! Grok has live knowledge of posts on X. With `llm.cite_x_posts` the
+//! prompt for Politics and Culture markets asks it to cite the recent
+//! posts that moved its estimate, so the reasoning can be checked.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info_span, warn, Instrument};
+
+use super::{critique, prompts, CallParams, LlmEstimator};
+use crate::llm::anthropic::AnthropicClient; // Reuse parsing utilities
+use crate::types::{d, DataContext, Estimate, Market, MarketCategory};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+const API_URL: &str = "https://api.x.ai/v1/chat/completions";
+const DEFAULT_MODEL: &str = "grok-3";
+const DEFAULT_MAX_TOKENS: u32 = 1024;
+
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_MS: u64 = 1000;
+
+/// USD per 1K (input, output) tokens by model family, most specific first.
+const PRICING: &[(&str, f64, f64)] = &[
+    ("grok-3-mini", 0.0003, 0.0005),
+    ("grok-3", 0.003, 0.015),
+    ("grok-2", 0.002, 0.010),
+];
+
+/// Appended to the prompt of Politics and Culture markets when citations
+/// are on.
+const X_CITATIONS_SECTION: &str = "\n\n=== X POSTS ===\n\
+     Before answering, consider recent posts on X about this question. In your reasoning, \
+     cite up to three that moved your estimate (author handle, date, one-line summary). \
+     Treat posts as noisy signals, not facts, and say so when none are relevant.";
+
+// ---------------------------------------------------------------------------
+// API types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    model: String,
+    max_tokens: u32,
+    messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    #[serde(default)]
+    message: Option<ResponseMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: u32,
+    #[serde(default)]
+    completion_tokens: u32,
+    #[serde(default)]
+    total_tokens: u32,
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+pub struct GrokClient {
+    http: Client,
+    api_key: String,
+    model: String,
+    max_tokens: u32,
+    x_citations: bool,
+    input_cost_per_1k: f64,
+    output_cost_per_1k: f64,
+    total_cost: std::sync::atomic::AtomicU64,
+    total_calls: std::sync::atomic::AtomicU64,
+}
+
+impl GrokClient {
+    pub fn new(api_key: String, model: Option<String>, max_tokens: Option<u32>) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .context("Failed to build Grok HTTP client")?;
+
+        let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+        let (input_cost_per_1k, output_cost_per_1k) = model_pricing(&model);
+        Ok(Self {
+            http,
+            api_key,
+            model,
+            max_tokens: max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            x_citations: false,
+            input_cost_per_1k,
+            output_cost_per_1k,
+            total_cost: std::sync::atomic::AtomicU64::new(0),
+            total_calls: std::sync::atomic::AtomicU64::new(0),
+        })
+    }
+
+    /// Ask for recent X posts to be cited on Politics and Culture markets.
+    pub fn with_x_citations(mut self, enabled: bool) -> Self {
+        self.x_citations = enabled;
+        self
+    }
+
+    /// Whether `market` gets the X citations section.
+    fn cites_x(&self, market: &Market) -> bool {
+        self.x_citations && matches!(market.category, MarketCategory::Politics | MarketCategory::Culture)
+    }
+
+    fn single_prompt(&self, market: &Market, context: &DataContext) -> String {
+        let prompt = AnthropicClient::build_single_prompt(market, context);
+        if self.cites_x(market) {
+            format!("{prompt}{X_CITATIONS_SECTION}")
+        } else {
+            prompt
+        }
+    }
+
+    fn batch_prompt(&self, markets: &[(Market, DataContext)]) -> String {
+        let prompt = AnthropicClient::build_batch_prompt(markets, None).prompt;
+        if markets.iter().any(|(m, _)| self.cites_x(m)) {
+            format!("{prompt}{X_CITATIONS_SECTION}")
+        } else {
+            prompt
+        }
+    }
+
+    fn build_request(&self, system: &str, user_message: &str, max_tokens: u32) -> ChatRequest {
+        ChatRequest {
+            model: self.model.clone(),
+            max_tokens,
+            messages: vec![
+                ChatMessage {
+                    role: "system".to_string(),
+                    content: system.to_string(),
+                },
+                ChatMessage {
+                    role: "user".to_string(),
+                    content: user_message.to_string(),
+                },
+            ],
+        }
+    }
+
+    /// USD cost of a call's token usage at the model's prices.
+    fn usage_cost(&self, usage: &ChatUsage) -> f64 {
+        (usage.prompt_tokens as f64 / 1000.0) * self.input_cost_per_1k
+            + (usage.completion_tokens as f64 / 1000.0) * self.output_cost_per_1k
+    }
+
+    async fn call_api(&self, system: &str, user_message: &str, max_tokens: u32) -> Result<(String, u32, f64)> {
+        let request = self.build_request(system, user_message, max_tokens);
+
+        let mut last_error = None;
+
+        for attempt in 0..=MAX_RETRIES {
+            if attempt > 0 {
+                let delay = BASE_BACKOFF_MS * 2u64.pow(attempt - 1);
+                tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            }
+
+            let resp = self.http
+                .post(API_URL)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .send()
+                .await;
+
+            match resp {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        let body: ChatResponse = response.json().await
+                            .context("Failed to parse Grok response")?;
+
+                        let text = response_text(&body);
+
+                        let usage = body.usage.unwrap_or(ChatUsage {
+                            prompt_tokens: 0,
+                            completion_tokens: 0,
+                            total_tokens: 0,
+                        });
+
+                        let cost = self.usage_cost(&usage);
+
+                        let cost_micro = (cost * 1_000_000.0) as u64;
+                        self.total_cost.fetch_add(cost_micro, std::sync::atomic::Ordering::Relaxed);
+                        self.total_calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+                        return Ok((text, usage.total_tokens, cost));
+                    }
+
+                    if status.as_u16() == 429 || status.as_u16() >= 500 {
+                        let error_text = response.text().await.unwrap_or_default();
+                        warn!(status = %status, attempt, "Retryable Grok error");
+                        last_error = Some(format!("HTTP {status}: {error_text}"));
+                        continue;
+                    }
+
+                    let error_text = response.text().await.unwrap_or_default();
+                    anyhow::bail!("Grok API error {status}: {error_text}");
+                }
+                Err(e) => {
+                    last_error = Some(format!("Request error: {e}"));
+                    continue;
+                }
+            }
+        }
+
+        anyhow::bail!("Grok API failed after {MAX_RETRIES} retries: {}", last_error.unwrap_or_default())
+    }
+
+    pub fn cumulative_cost(&self) -> f64 {
+        self.total_cost.load(std::sync::atomic::Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+
+    pub fn total_calls(&self) -> u64 {
+        self.total_calls.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl LlmEstimator for GrokClient {
+    async fn estimate_probability(
+        &self,
+        market: &Market,
+        context: &DataContext,
+    ) -> Result<Estimate> {
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(
+        &self,
+        markets: &[(Market, DataContext)],
+    ) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        params: &CallParams,
+    ) -> Result<Estimate> {
+        let user_msg = self.single_prompt(market, context);
+
+        debug!(market_id = %market.id, model = %self.model, "Grok single estimate");
+
+        let max_tokens = params.max_tokens.unwrap_or(self.max_tokens);
+        let (response_text, tokens, cost) =
+            self.call_api(AnthropicClient::system_prompt(), &user_msg, max_tokens).await?;
+        let (prob_f64, conf_f64, reasoning) = AnthropicClient::parse_estimate(&response_text)?;
+        prompts::refuse_planted(market, prob_f64)?;
+
+        Ok(Estimate {
+            probability: d(prob_f64),
+            confidence: d(conf_f64),
+            reasoning,
+            tokens_used: tokens,
+            cost: d(cost),
+            critique: None,
+            served_by: None,
+            tier: None,
+        })
+    }
+
+    async fn batch_estimate_with(
+        &self,
+        markets: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let max_tokens = params.max_tokens.unwrap_or(self.max_tokens);
+        if markets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if markets.len() <= 2 {
+            let mut results = Vec::with_capacity(markets.len());
+            for (market, context) in markets {
+                results.push(self.estimate_with(market, context, params).await?);
+            }
+            return Ok(results);
+        }
+
+        let user_msg = self.batch_prompt(markets);
+
+        let span = info_span!("estimate_batch", markets = markets.len() as i64);
+        let (response_text, tokens, cost) = self
+            .call_api(AnthropicClient::system_prompt(), &user_msg, max_tokens)
+            .instrument(span)
+            .await?;
+
+        let expected_ids: Vec<&str> = markets.iter().map(|(m, _)| m.id.as_str()).collect();
+        let mut parsed = AnthropicClient::parse_batch_response(&response_text, &expected_ids);
+        prompts::drop_planted(&mut parsed, markets);
+
+        let cost_per = cost / markets.len() as f64;
+        let tokens_per = tokens / markets.len() as u32;
+
+        let mut results = Vec::with_capacity(markets.len());
+
+        for (i, (market, context)) in markets.iter().enumerate() {
+            match parsed.get(i).and_then(|p| p.as_ref()) {
+                Some((prob, conf)) => {
+                    results.push(Estimate {
+                        probability: d(*prob),
+                        confidence: d(*conf),
+                        reasoning: format!("(batch estimate for {})", market.id),
+                        tokens_used: tokens_per,
+                        cost: d(cost_per),
+                        critique: None,
+                        served_by: None,
+                        tier: None,
+                    });
+                }
+                None => {
+                    match self.estimate_with(market, context, params).await {
+                        Ok(est) => results.push(est),
+                        Err(e) => {
+                            results.push(Estimate {
+                                probability: market.current_price_yes,
+                                confidence: dec!(0.1),
+                                reasoning: format!("Estimation failed: {e}"),
+                                tokens_used: 0,
+                                cost: Decimal::ZERO,
+                                critique: None,
+                                served_by: None,
+                                tier: None,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn critique(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        initial: &Estimate,
+    ) -> Result<Estimate> {
+        let user_msg = AnthropicClient::build_critique_prompt(market, context, initial);
+        let (response_text, tokens, cost) = self
+            .call_api(AnthropicClient::system_prompt(), &user_msg, self.max_tokens)
+            .await
+            .context("Grok critique call failed")?;
+        critique::parse_response(&response_text, market, tokens, cost)
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        d((500.0 / 1000.0) * self.input_cost_per_1k + (300.0 / 1000.0) * self.output_cost_per_1k)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Response helpers
+// ---------------------------------------------------------------------------
+
+/// USD per 1K (input, output) tokens for `model`; unknown models are priced
+/// as grok-3.
+fn model_pricing(model: &str) -> (f64, f64) {
+    let name = model.rsplit('/').next().unwrap_or(model);
+    PRICING
+        .iter()
+        .find(|(prefix, _, _)| name.starts_with(prefix))
+        .map(|&(_, input, output)| (input, output))
+        .unwrap_or((0.003, 0.015))
+}
+
+/// Text of the first choice; empty when the model returned no content.
+fn response_text(body: &ChatResponse) -> String {
+    body.choices
+        .first()
+        .and_then(|c| c.message.as_ref())
+        .and_then(|m| m.content.clone())
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(category: MarketCategory) -> Market {
+        Market { category, ..Market::sample() }
+    }
+
+    #[test]
+    fn test_client_construction() {
+        let client = GrokClient::new("test-key".into(), None, None).unwrap();
+        assert_eq!(client.model_name(), DEFAULT_MODEL);
+        assert_eq!(client.cumulative_cost(), 0.0);
+        assert_eq!(client.total_calls(), 0);
+    }
+
+    #[test]
+    fn test_model_pricing() {
+        assert_eq!(model_pricing("grok-3-mini"), (0.0003, 0.0005));
+        assert_eq!(model_pricing("grok-3"), (0.003, 0.015));
+        assert_eq!(model_pricing("grok-2-1212"), (0.002, 0.010));
+        assert_eq!(model_pricing("x-ai/grok-3-mini-beta"), (0.0003, 0.0005));
+
+        let client = GrokClient::new("key".into(), Some("grok-3-mini".into()), None).unwrap();
+        let usage = ChatUsage { prompt_tokens: 2000, completion_tokens: 1000, total_tokens: 3000 };
+        assert!((client.usage_cost(&usage) - 0.0011).abs() < 1e-12);
+        assert!(client.cost_per_call() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_request_construction() {
+        let client = GrokClient::new("key".into(), Some("grok-2".into()), None).unwrap();
+        let body = serde_json::to_value(client.build_request(AnthropicClient::system_prompt(), "Will it rain?", 512)).unwrap();
+        assert_eq!(body["model"], "grok-2");
+        assert_eq!(body["max_tokens"], 512);
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][0]["content"], AnthropicClient::system_prompt());
+        assert_eq!(body["messages"][1]["role"], "user");
+        assert_eq!(body["messages"][1]["content"], "Will it rain?");
+    }
+
+    #[test]
+    fn test_x_citations_only_for_politics_and_culture() {
+        let context = DataContext::empty(MarketCategory::Other);
+        let off = GrokClient::new("key".into(), None, None).unwrap();
+        assert!(!off.single_prompt(&market(MarketCategory::Politics), &context).contains("=== X POSTS ==="));
+
+        let on = off.with_x_citations(true);
+        assert!(on.single_prompt(&market(MarketCategory::Politics), &context).contains("=== X POSTS ==="));
+        assert!(on.single_prompt(&market(MarketCategory::Culture), &context).contains("=== X POSTS ==="));
+        assert!(!on.single_prompt(&market(MarketCategory::Weather), &context).contains("=== X POSTS ==="));
+
+        let batch = vec![(market(MarketCategory::Sports), context.clone()), (market(MarketCategory::Culture), context)];
+        assert!(on.batch_prompt(&batch).contains("=== X POSTS ==="));
+        assert!(!on.batch_prompt(&batch[..1]).contains("=== X POSTS ==="));
+    }
+
+    #[test]
+    fn test_fixture_chat_completion() {
+        let body: ChatResponse =
+            serde_json::from_str(&crate::fixtures::read("xai/chat-completions.json")).unwrap();
+        let usage = body.usage.as_ref().unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.total_tokens), (714, 61, 775));
+
+        let (prob, conf, reasoning) = AnthropicClient::parse_estimate(&response_text(&body)).unwrap();
+        assert_eq!((prob, conf), (0.58, 0.55));
+        assert!(reasoning.contains("@"));
+    }
+}
@@ -1,11 +1,23 @@
 //! LLM integration for fair-value probability estimation.
 //!
 //! Defines the `LlmEstimator` trait and provides implementations for
-//! Claude (Anthropic), GPT-4 (OpenAI), and OpenRouter (multi-provider).
+//! Claude (Anthropic), OpenAI-compatible endpoints, Grok (xAI) and OpenRouter
+//! (multi-provider).
 
 pub mod anthropic;
+pub mod budget;
+pub mod cache;
+pub mod critique;
+pub mod ensemble;
+pub mod failover;
+pub mod grok;
 pub mod openai;
 pub mod openrouter;
+pub mod prompts;
+pub mod routing;
+pub mod shadow;
+pub mod structured;
+pub mod tiers;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -13,6 +25,24 @@ use rust_decimal::Decimal;
 
 use crate::types::{DataContext, Estimate, Market};
 
+/// Env var read for a provider's API key when `llm.api_key_env` is unset.
+pub fn default_api_key_env(provider: &str) -> &'static str {
+    match provider {
+        "anthropic" => "ANTHROPIC_API_KEY",
+        "openai" => "OPENAI_API_KEY",
+        "grok" => "XAI_API_KEY",
+        _ => "OPENROUTER_API_KEY",
+    }
+}
+
+/// Per-call request overrides, set by the estimation tier of the markets
+/// being estimated. Unset fields fall back to the client's configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallParams {
+    /// Output token cap for the request.
+    pub max_tokens: Option<u32>,
+}
+
 /// Abstraction over LLM probability estimators.
 ///
 /// Implementors send enriched market data to an LLM and parse
@@ -33,6 +63,41 @@ pub trait LlmEstimator: Send + Sync {
         markets: &[(Market, DataContext)],
     ) -> Result<Vec<Estimate>>;
 
+    /// [`Self::estimate_probability`] with per-call request overrides.
+    /// Estimators without request controls ignore `params`.
+    async fn estimate_with(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        params: &CallParams,
+    ) -> Result<Estimate> {
+        let _ = params;
+        self.estimate_probability(market, context).await
+    }
+
+    /// [`Self::batch_estimate`] with per-call request overrides.
+    async fn batch_estimate_with(
+        &self,
+        markets: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let _ = params;
+        self.batch_estimate(markets).await
+    }
+
+    /// Ask the model to critique its own `initial` estimate: the three
+    /// strongest reasons it could be wrong, then a revised probability.
+    /// Returns the revised estimate with the critique as `reasoning`.
+    async fn critique(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        initial: &Estimate,
+    ) -> Result<Estimate> {
+        let _ = (market, context, initial);
+        anyhow::bail!("{} does not support self-critique", self.model_name())
+    }
+
     /// Cost per individual API call in USD.
     fn cost_per_call(&self) -> Decimal;
 
@@ -1,8 +1,14 @@
-//! OpenAI GPT-4 LLM integration.
+//! OpenAI-compatible LLM integration.
 //!
-//! Implements the `LlmEstimator` trait as an alternative provider.
-//! Uses the same prompt templates as Anthropic but targets the
-//! OpenAI Chat Completions API.
+//! Implements the `LlmEstimator` trait against the Chat Completions API.
+//! Uses the same prompt templates and PROBABILITY/CONFIDENCE parsing as
+//! Anthropic. The base URL is configurable (`llm.base_url`), so the same
+//! client serves OpenAI, Azure OpenAI's v1 endpoint, vLLM and LM Studio.
+//!
+//! With `llm.json_mode` the request sets `response_format: json_object`
+//! and the prompt asks for a JSON object instead of the trailing answer
+//! lines (see [`super::structured`]); replies that still use the line
+//! format parse as usual.
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -10,9 +16,9 @@ use reqwest::Client;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, warn};
+use tracing::{debug, info_span, warn, Instrument};
 
-use super::LlmEstimator;
+use super::{critique, prompts, structured, CallParams, LlmEstimator};
 use crate::llm::anthropic::AnthropicClient; // Reuse parsing utilities
 use crate::types::{d, DataContext, Estimate, Market};
 
@@ -20,18 +26,24 @@ use crate::types::{d, DataContext, Estimate, Market};
 // Configuration
 // ---------------------------------------------------------------------------
 
-const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
 const DEFAULT_MODEL: &str = "gpt-4o";
 const DEFAULT_MAX_TOKENS: u32 = 1024;
 
 const MAX_RETRIES: u32 = 3;
 const BASE_BACKOFF_MS: u64 = 1000;
 
-/// Approximate cost per 1K input tokens (GPT-4o).
+/// Default cost per 1K input tokens (GPT-4o); see [`OpenAiClient::with_pricing`].
 const INPUT_COST_PER_1K: f64 = 0.005;
-/// Approximate cost per 1K output tokens (GPT-4o).
+/// Default cost per 1K output tokens (GPT-4o).
 const OUTPUT_COST_PER_1K: f64 = 0.015;
 
+/// JSON-mode counterpart of the batch prompt's MARKET_ID lines; JSON mode
+/// only allows an object at the top level, so the array is wrapped.
+const JSON_BATCH_FORMAT: &str = "\n\nRESPONSE FORMAT: reply with a single JSON object and nothing else, \
+     in place of the MARKET_ID lines:\n\
+     {\"estimates\": [{\"market_id\": \"<id>\", \"probability\": 0.XX, \"confidence\": 0.XX}]}";
+
 // ---------------------------------------------------------------------------
 // API types
 // ---------------------------------------------------------------------------
@@ -41,14 +53,22 @@ struct ChatRequest {
     model: String,
     max_tokens: u32,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 struct ChatMessage {
     role: String,
     content: String,
 }
 
+#[derive(Debug, Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
 #[derive(Debug, Deserialize)]
 struct ChatResponse {
     #[serde(default)]
@@ -60,7 +80,14 @@ struct ChatResponse {
 #[derive(Debug, Deserialize)]
 struct Choice {
     #[serde(default)]
-    message: Option<ChatMessage>,
+    message: Option<ResponseMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    /// Null on refusals and tool calls.
+    #[serde(default)]
+    content: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -82,6 +109,11 @@ pub struct OpenAiClient {
     api_key: String,
     model: String,
     max_tokens: u32,
+    /// Full Chat Completions URL, derived from the base URL.
+    endpoint: String,
+    json_mode: bool,
+    input_cost_per_1k: f64,
+    output_cost_per_1k: f64,
     total_cost: std::sync::atomic::AtomicU64,
     total_calls: std::sync::atomic::AtomicU64,
 }
@@ -98,15 +130,51 @@ impl OpenAiClient {
             api_key,
             model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
             max_tokens: max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            endpoint: chat_completions_url(DEFAULT_BASE_URL),
+            json_mode: false,
+            input_cost_per_1k: INPUT_COST_PER_1K,
+            output_cost_per_1k: OUTPUT_COST_PER_1K,
             total_cost: std::sync::atomic::AtomicU64::new(0),
             total_calls: std::sync::atomic::AtomicU64::new(0),
         })
     }
 
-    async fn call_api(&self, system: &str, user_message: &str) -> Result<(String, u32, f64)> {
-        let request = ChatRequest {
+    /// Target an OpenAI-compatible server instead of api.openai.com, e.g.
+    /// `http://localhost:8000/v1` for vLLM. `/chat/completions` is appended.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.endpoint = chat_completions_url(base_url);
+        self
+    }
+
+    /// Request `response_format: json_object` and JSON-shaped answers.
+    /// Critique calls keep the line format.
+    pub fn with_json_mode(mut self, enabled: bool) -> Self {
+        self.json_mode = enabled;
+        self
+    }
+
+    /// USD per 1K input and output tokens, for models other than GPT-4o.
+    /// Local servers can set both to zero.
+    pub fn with_pricing(mut self, input_per_1k: f64, output_per_1k: f64) -> Self {
+        self.input_cost_per_1k = input_per_1k;
+        self.output_cost_per_1k = output_per_1k;
+        self
+    }
+
+    /// System prompt, with the JSON answer format appended in JSON mode.
+    fn system_prompt(&self, batch: bool) -> String {
+        let base = AnthropicClient::system_prompt();
+        match (self.json_mode, batch) {
+            (false, _) => base.to_string(),
+            (true, false) => format!("{base}{}", structured::SINGLE_FORMAT),
+            (true, true) => format!("{base}{JSON_BATCH_FORMAT}"),
+        }
+    }
+
+    fn build_request(&self, system: &str, user_message: &str, max_tokens: u32, json: bool) -> ChatRequest {
+        ChatRequest {
             model: self.model.clone(),
-            max_tokens: self.max_tokens,
+            max_tokens,
             messages: vec![
                 ChatMessage {
                     role: "system".to_string(),
@@ -117,7 +185,46 @@ impl OpenAiClient {
                     content: user_message.to_string(),
                 },
             ],
-        };
+            response_format: json.then_some(ResponseFormat { kind: "json_object" }),
+        }
+    }
+
+    /// USD cost of a call's token usage at the configured prices.
+    fn usage_cost(&self, usage: &ChatUsage) -> f64 {
+        (usage.prompt_tokens as f64 / 1000.0) * self.input_cost_per_1k
+            + (usage.completion_tokens as f64 / 1000.0) * self.output_cost_per_1k
+    }
+
+    /// Parse a single-market reply: JSON first in JSON mode, then the
+    /// PROBABILITY/CONFIDENCE lines.
+    fn parse_single(&self, text: &str) -> Result<(f64, f64, String)> {
+        if self.json_mode {
+            if let Some(parsed) = structured::parse_estimate(text) {
+                return Ok(parsed);
+            }
+            debug!("OpenAI reply was not a JSON estimate; falling back to line parsing");
+        }
+        AnthropicClient::parse_estimate(text)
+    }
+
+    fn parse_batch(&self, text: &str, expected_ids: &[&str]) -> Vec<Option<(f64, f64)>> {
+        if self.json_mode {
+            if let Some(parsed) = structured::parse_batch(text, expected_ids) {
+                return parsed;
+            }
+            debug!("OpenAI batch reply was not JSON; falling back to line parsing");
+        }
+        AnthropicClient::parse_batch_response(text, expected_ids)
+    }
+
+    async fn call_api(
+        &self,
+        system: &str,
+        user_message: &str,
+        max_tokens: u32,
+        json: bool,
+    ) -> Result<(String, u32, f64)> {
+        let request = self.build_request(system, user_message, max_tokens, json);
 
         let mut last_error = None;
 
@@ -128,7 +235,7 @@ impl OpenAiClient {
             }
 
             let resp = self.http
-                .post(OPENAI_API_URL)
+                .post(&self.endpoint)
                 .header("Authorization", format!("Bearer {}", self.api_key))
                 .header("Content-Type", "application/json")
                 .json(&request)
@@ -143,10 +250,7 @@ impl OpenAiClient {
                         let body: ChatResponse = response.json().await
                             .context("Failed to parse OpenAI response")?;
 
-                        let text = body.choices.first()
-                            .and_then(|c| c.message.as_ref())
-                            .map(|m| m.content.clone())
-                            .unwrap_or_default();
+                        let text = response_text(&body);
 
                         let usage = body.usage.unwrap_or(ChatUsage {
                             prompt_tokens: 0,
@@ -154,8 +258,7 @@ impl OpenAiClient {
                             total_tokens: 0,
                         });
 
-                        let cost = (usage.prompt_tokens as f64 / 1000.0) * INPUT_COST_PER_1K
-                            + (usage.completion_tokens as f64 / 1000.0) * OUTPUT_COST_PER_1K;
+                        let cost = self.usage_cost(&usage);
 
                         let cost_micro = (cost * 1_000_000.0) as u64;
                         self.total_cost.fetch_add(cost_micro, std::sync::atomic::Ordering::Relaxed);
@@ -200,13 +303,32 @@ impl LlmEstimator for OpenAiClient {
         market: &Market,
         context: &DataContext,
     ) -> Result<Estimate> {
-        let system = AnthropicClient::system_prompt();
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(
+        &self,
+        markets: &[(Market, DataContext)],
+    ) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        params: &CallParams,
+    ) -> Result<Estimate> {
+        let system = self.system_prompt(false);
         let user_msg = AnthropicClient::build_single_prompt(market, context);
 
         debug!(market_id = %market.id, model = %self.model, "OpenAI single estimate");
 
-        let (response_text, tokens, cost) = self.call_api(system, &user_msg).await?;
-        let (prob_f64, conf_f64, reasoning) = AnthropicClient::parse_estimate(&response_text)?;
+        let max_tokens = params.max_tokens.unwrap_or(self.max_tokens);
+        let (response_text, tokens, cost) =
+            self.call_api(&system, &user_msg, max_tokens, self.json_mode).await?;
+        let (prob_f64, conf_f64, reasoning) = self.parse_single(&response_text)?;
+        prompts::refuse_planted(market, prob_f64)?;
 
         Ok(Estimate {
             probability: d(prob_f64),
@@ -214,13 +336,18 @@ impl LlmEstimator for OpenAiClient {
             reasoning,
             tokens_used: tokens,
             cost: d(cost),
+            critique: None,
+            served_by: None,
+            tier: None,
         })
     }
 
-    async fn batch_estimate(
+    async fn batch_estimate_with(
         &self,
         markets: &[(Market, DataContext)],
+        params: &CallParams,
     ) -> Result<Vec<Estimate>> {
+        let max_tokens = params.max_tokens.unwrap_or(self.max_tokens);
         // Same batch logic as Anthropic — reuse prompt + parsing
         if markets.is_empty() {
             return Ok(Vec::new());
@@ -229,18 +356,23 @@ impl LlmEstimator for OpenAiClient {
         if markets.len() <= 2 {
             let mut results = Vec::with_capacity(markets.len());
             for (market, context) in markets {
-                results.push(self.estimate_probability(market, context).await?);
+                results.push(self.estimate_with(market, context, params).await?);
             }
             return Ok(results);
         }
 
-        let system = AnthropicClient::system_prompt();
-        let user_msg = AnthropicClient::build_batch_prompt(markets);
+        let system = self.system_prompt(true);
+        let user_msg = AnthropicClient::build_batch_prompt(markets, None).prompt;
 
-        let (response_text, tokens, cost) = self.call_api(system, &user_msg).await?;
+        let span = info_span!("estimate_batch", markets = markets.len() as i64);
+        let (response_text, tokens, cost) = self
+            .call_api(&system, &user_msg, max_tokens, self.json_mode)
+            .instrument(span)
+            .await?;
 
         let expected_ids: Vec<&str> = markets.iter().map(|(m, _)| m.id.as_str()).collect();
-        let parsed = AnthropicClient::parse_batch_response(&response_text, &expected_ids);
+        let mut parsed = self.parse_batch(&response_text, &expected_ids);
+        prompts::drop_planted(&mut parsed, markets);
 
         let cost_per = cost / markets.len() as f64;
         let tokens_per = tokens / markets.len() as u32;
@@ -256,10 +388,13 @@ impl LlmEstimator for OpenAiClient {
                         reasoning: format!("(batch estimate for {})", market.id),
                         tokens_used: tokens_per,
                         cost: d(cost_per),
+                        critique: None,
+                        served_by: None,
+                        tier: None,
                     });
                 }
                 None => {
-                    match self.estimate_probability(market, context).await {
+                    match self.estimate_with(market, context, params).await {
                         Ok(est) => results.push(est),
                         Err(e) => {
                             results.push(Estimate {
@@ -268,6 +403,9 @@ impl LlmEstimator for OpenAiClient {
                                 reasoning: format!("Estimation failed: {e}"),
                                 tokens_used: 0,
                                 cost: Decimal::ZERO,
+                                critique: None,
+                                served_by: None,
+                                tier: None,
                             });
                         }
                     }
@@ -278,8 +416,22 @@ impl LlmEstimator for OpenAiClient {
         Ok(results)
     }
 
+    async fn critique(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        initial: &Estimate,
+    ) -> Result<Estimate> {
+        let user_msg = AnthropicClient::build_critique_prompt(market, context, initial);
+        let (response_text, tokens, cost) = self
+            .call_api(AnthropicClient::system_prompt(), &user_msg, self.max_tokens, false)
+            .await
+            .context("OpenAI critique call failed")?;
+        critique::parse_response(&response_text, market, tokens, cost)
+    }
+
     fn cost_per_call(&self) -> Decimal {
-        d((500.0 / 1000.0) * INPUT_COST_PER_1K + (300.0 / 1000.0) * OUTPUT_COST_PER_1K)
+        d((500.0 / 1000.0) * self.input_cost_per_1k + (300.0 / 1000.0) * self.output_cost_per_1k)
     }
 
     fn model_name(&self) -> &str {
@@ -287,6 +439,24 @@ impl LlmEstimator for OpenAiClient {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Response helpers
+// ---------------------------------------------------------------------------
+
+/// `{base_url}/chat/completions`, tolerating a trailing slash.
+fn chat_completions_url(base_url: &str) -> String {
+    format!("{}/chat/completions", base_url.trim_end_matches('/'))
+}
+
+/// Text of the first choice; empty when the model returned no content.
+fn response_text(body: &ChatResponse) -> String {
+    body.choices
+        .first()
+        .and_then(|c| c.message.as_ref())
+        .and_then(|m| m.content.clone())
+        .unwrap_or_default()
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -313,4 +483,94 @@ mod tests {
         let client = OpenAiClient::new("key".into(), None, None).unwrap();
         assert!(client.cost_per_call() > Decimal::ZERO);
     }
+
+    #[test]
+    fn test_base_url() {
+        let client = OpenAiClient::new("key".into(), None, None).unwrap();
+        assert_eq!(client.endpoint, "https://api.openai.com/v1/chat/completions");
+        let client = client.with_base_url("http://localhost:1234/v1/");
+        assert_eq!(client.endpoint, "http://localhost:1234/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_request_construction() {
+        let client = OpenAiClient::new("key".into(), Some("llama-3.1-70b".into()), None).unwrap();
+        let system = client.system_prompt(false);
+        let body = serde_json::to_value(client.build_request(&system, "Will it rain?", 512, false)).unwrap();
+        assert_eq!(body["model"], "llama-3.1-70b");
+        assert_eq!(body["max_tokens"], 512);
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][0]["content"], AnthropicClient::system_prompt());
+        assert_eq!(body["messages"][1]["role"], "user");
+        assert_eq!(body["messages"][1]["content"], "Will it rain?");
+        assert!(body.get("response_format").is_none());
+    }
+
+    #[test]
+    fn test_json_mode_request() {
+        let client = OpenAiClient::new("key".into(), None, None).unwrap().with_json_mode(true);
+        let single = client.system_prompt(false);
+        let batch = client.system_prompt(true);
+        // The API rejects json_object requests whose messages never mention JSON.
+        assert!(single.starts_with(AnthropicClient::system_prompt()) && single.contains("JSON"));
+        assert!(batch.contains("\"estimates\""));
+
+        let body = serde_json::to_value(client.build_request(&single, "Q?", 256, true)).unwrap();
+        assert_eq!(body["response_format"], serde_json::json!({"type": "json_object"}));
+    }
+
+    #[test]
+    fn test_configured_pricing() {
+        let client = OpenAiClient::new("key".into(), None, None).unwrap().with_pricing(0.0005, 0.0015);
+        let usage = ChatUsage { prompt_tokens: 2000, completion_tokens: 1000, total_tokens: 3000 };
+        assert!((client.usage_cost(&usage) - 0.0025).abs() < 1e-12);
+
+        let local = OpenAiClient::new("key".into(), None, None).unwrap().with_pricing(0.0, 0.0);
+        assert_eq!(local.cost_per_call(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_fixture_chat_completion() {
+        let body: ChatResponse =
+            serde_json::from_str(&crate::fixtures::read("openai/chat-completions.json")).unwrap();
+        let usage = body.usage.as_ref().unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens), (652, 38));
+
+        let client = OpenAiClient::new("key".into(), None, None).unwrap();
+        let (prob, conf, reasoning) = client.parse_single(&response_text(&body)).unwrap();
+        assert_eq!((prob, conf), (0.64, 0.60));
+        assert!(reasoning.contains("payrolls"));
+    }
+
+    #[test]
+    fn test_fixture_json_mode_completion() {
+        let body: ChatResponse =
+            serde_json::from_str(&crate::fixtures::read("openai/chat-completions-json.json")).unwrap();
+        let text = response_text(&body);
+
+        let client = OpenAiClient::new("key".into(), None, None).unwrap().with_json_mode(true);
+        let (prob, conf, reasoning) = client.parse_single(&text).unwrap();
+        assert_eq!((prob, conf), (0.64, 0.6));
+        assert!(reasoning.starts_with("The September payrolls"));
+
+        // Models that ignore the format still parse via the answer lines.
+        let (prob, _, _) = client.parse_single("PROBABILITY: 0.30\nCONFIDENCE: 0.5").unwrap();
+        assert_eq!(prob, 0.30);
+    }
+
+    #[test]
+    fn test_null_content() {
+        let body: ChatResponse = serde_json::from_str(
+            r#"{"choices": [{"message": {"role": "assistant", "content": null, "refusal": "no"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(response_text(&body), "");
+    }
+
+    #[test]
+    fn test_json_mode_batch_falls_back_to_lines() {
+        let client = OpenAiClient::new("key".into(), None, None).unwrap().with_json_mode(true);
+        let parsed = client.parse_batch("MARKET_ID: m1 | PROBABILITY: 0.40 | CONFIDENCE: 0.60", &["m1"]);
+        assert_eq!(parsed, vec![Some((0.4, 0.6))]);
+    }
 }
@@ -15,9 +15,9 @@ use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{debug, info, info_span, warn, Instrument};
 
-use super::LlmEstimator;
+use super::{critique, prompts, CallParams, LlmEstimator};
 use crate::llm::anthropic::AnthropicClient; // Reuse prompt templates + parsing
 use crate::types::{d, DataContext, Estimate, Market};
 
@@ -179,10 +179,11 @@ impl OpenRouterClient {
         model: &str,
         system: &str,
         user_message: &str,
+        max_tokens: u32,
     ) -> Result<(String, u32, f64)> {
         let request = ChatRequest {
             model: model.to_string(),
-            max_tokens: self.max_tokens,
+            max_tokens,
             messages: vec![
                 ChatMessage {
                     role: "system".to_string(),
@@ -291,8 +292,13 @@ impl OpenRouterClient {
 
     /// Call the primary model, falling back to the secondary if configured
     /// and the primary fails.
-    async fn call_api(&self, system: &str, user_message: &str) -> Result<(String, u32, f64)> {
-        match self.call_model(&self.primary_model, system, user_message).await {
+    async fn call_api(
+        &self,
+        system: &str,
+        user_message: &str,
+        max_tokens: u32,
+    ) -> Result<(String, u32, f64)> {
+        match self.call_model(&self.primary_model, system, user_message, max_tokens).await {
             Ok(result) => Ok(result),
             Err(primary_err) => {
                 if let Some(ref fallback) = self.fallback_model {
@@ -302,7 +308,7 @@ impl OpenRouterClient {
                         error = %primary_err,
                         "Primary model failed, falling back"
                     );
-                    self.call_model(fallback, system, user_message)
+                    self.call_model(fallback, system, user_message, max_tokens)
                         .await
                         .with_context(|| {
                             format!(
@@ -341,6 +347,22 @@ impl LlmEstimator for OpenRouterClient {
         &self,
         market: &Market,
         context: &DataContext,
+    ) -> Result<Estimate> {
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(
+        &self,
+        markets: &[(Market, DataContext)],
+    ) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        params: &CallParams,
     ) -> Result<Estimate> {
         let system = AnthropicClient::system_prompt();
         let user_msg = AnthropicClient::build_single_prompt(market, context);
@@ -351,13 +373,15 @@ impl LlmEstimator for OpenRouterClient {
             "Requesting single probability estimate via OpenRouter"
         );
 
+        let max_tokens = params.max_tokens.unwrap_or(self.max_tokens);
         let (response_text, tokens, cost) = self
-            .call_api(system, &user_msg)
+            .call_api(system, &user_msg, max_tokens)
             .await
             .context("OpenRouter API call failed")?;
 
         let (prob_f64, conf_f64, reasoning) = AnthropicClient::parse_estimate(&response_text)
             .context("Failed to parse estimate from LLM response")?;
+        prompts::refuse_planted(market, prob_f64)?;
 
         let probability = d(prob_f64);
         let confidence = d(conf_f64);
@@ -389,13 +413,18 @@ impl LlmEstimator for OpenRouterClient {
             reasoning,
             tokens_used: tokens,
             cost,
+            critique: None,
+            served_by: None,
+            tier: None,
         })
     }
 
-    async fn batch_estimate(
+    async fn batch_estimate_with(
         &self,
         markets: &[(Market, DataContext)],
+        params: &CallParams,
     ) -> Result<Vec<Estimate>> {
+        let max_tokens = params.max_tokens.unwrap_or(self.max_tokens);
         if markets.is_empty() {
             return Ok(Vec::new());
         }
@@ -404,7 +433,7 @@ impl LlmEstimator for OpenRouterClient {
         if markets.len() <= 2 {
             let mut results = Vec::with_capacity(markets.len());
             for (market, context) in markets {
-                results.push(self.estimate_probability(market, context).await?);
+                results.push(self.estimate_with(market, context, params).await?);
             }
             return Ok(results);
         }
@@ -423,7 +452,7 @@ impl LlmEstimator for OpenRouterClient {
         let system = AnthropicClient::system_prompt();
         let prompts: Vec<String> = markets
             .chunks(batch_size)
-            .map(|chunk| AnthropicClient::build_batch_prompt(chunk))
+            .map(|chunk| AnthropicClient::build_batch_prompt(chunk, None).prompt)
             .collect();
 
         // Phase 2: Dispatch all chunks concurrently.
@@ -433,7 +462,11 @@ impl LlmEstimator for OpenRouterClient {
         // With MAX_MARKETS_TO_PROCESS=80 and batch_size=5 there are at most 16
         // concurrent requests, well within OpenRouter's rate limits.
         let chunk_futures: Vec<_> = prompts.iter()
-            .map(|msg| self.call_api(&system, msg))
+            .zip(markets.chunks(batch_size))
+            .map(|(msg, chunk)| {
+                let span = info_span!("estimate_batch", markets = chunk.len() as i64);
+                self.call_api(system, msg, max_tokens).instrument(span)
+            })
             .collect();
         let api_results: Vec<Result<(String, u32, f64)>> = join_all(chunk_futures).await;
 
@@ -457,6 +490,9 @@ impl LlmEstimator for OpenRouterClient {
                             reasoning: format!("API call failed: {e}"),
                             tokens_used: 0,
                             cost: Decimal::ZERO,
+                            critique: None,
+                            served_by: None,
+                            tier: None,
                         });
                     }
                     continue;
@@ -464,7 +500,8 @@ impl LlmEstimator for OpenRouterClient {
             };
 
             let expected_ids: Vec<&str> = chunk.iter().map(|(m, _)| m.id.as_str()).collect();
-            let parsed = AnthropicClient::parse_batch_response(&response_text, &expected_ids);
+            let mut parsed = AnthropicClient::parse_batch_response(&response_text, &expected_ids);
+            prompts::drop_planted(&mut parsed, chunk);
 
             let cost_per_market = cost / chunk.len() as f64;
             let tokens_per_market = tokens / chunk.len() as u32;
@@ -480,6 +517,9 @@ impl LlmEstimator for OpenRouterClient {
                             reasoning: format!("(batch estimate for {})", market.id),
                             tokens_used: tokens_per_market,
                             cost: d(cost_per_market),
+                            critique: None,
+                            served_by: None,
+                            tier: None,
                         });
                     }
                     None => {
@@ -489,7 +529,7 @@ impl LlmEstimator for OpenRouterClient {
                         );
                         chunk_fallbacks += 1;
                         total_fallbacks += 1;
-                        match self.estimate_probability(market, context).await {
+                        match self.estimate_with(market, context, params).await {
                             Ok(est) => all_results.push(est),
                             Err(e) => {
                                 warn!(
@@ -503,6 +543,9 @@ impl LlmEstimator for OpenRouterClient {
                                     reasoning: format!("Estimation failed: {e}"),
                                     tokens_used: 0,
                                     cost: Decimal::ZERO,
+                                    critique: None,
+                                    served_by: None,
+                                    tier: None,
                                 });
                             }
                         }
@@ -532,6 +575,20 @@ impl LlmEstimator for OpenRouterClient {
         Ok(all_results)
     }
 
+    async fn critique(
+        &self,
+        market: &Market,
+        context: &DataContext,
+        initial: &Estimate,
+    ) -> Result<Estimate> {
+        let user_msg = AnthropicClient::build_critique_prompt(market, context, initial);
+        let (response_text, tokens, cost) = self
+            .call_api(AnthropicClient::system_prompt(), &user_msg, self.max_tokens)
+            .await
+            .context("OpenRouter critique call failed")?;
+        critique::parse_response(&response_text, market, tokens, cost)
+    }
+
     fn cost_per_call(&self) -> Decimal {
         let (input_cost, output_cost) = model_costs(&self.primary_model);
         // Approximate: ~500 input tokens + ~300 output tokens
@@ -630,4 +687,17 @@ mod tests {
         assert!((input - 0.015).abs() < 1e-10);
         assert!((output - 0.075).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_fixture_chat_completion() {
+        let body: ChatResponse =
+            serde_json::from_str(&crate::fixtures::read("openrouter/chat-completions.json")).unwrap();
+        assert_eq!(body.model.as_deref(), Some("x-ai/grok-4.1-fast"));
+        let usage = body.usage.unwrap();
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens), (644, 41));
+
+        let text = &body.choices[0].message.as_ref().unwrap().content;
+        let (prob, conf, _) = AnthropicClient::parse_estimate(text).unwrap();
+        assert_eq!((prob, conf), (0.62, 0.65));
+    }
 }
@@ -0,0 +1,292 @@
+//! Per-category model routing.
+//!
+//! [`RoutingEstimator`] sends each market to the estimator configured for
+//! its category in `[llm.category_models]` and every other market to the
+//! default estimator, so an expensive model can be kept for the
+//! categories that justify it. A batch is split by target model and each
+//! group batched separately, the groups running at once; estimates come
+//! back in input order. Each is tagged in [`Estimate::served_by`] with the
+//! model that served it, unless its estimator already tagged it (a
+//! failover default names whichever provider answered), so
+//! [`super::failover::cost_by_provider`] gives the spend per model.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::future::join_all;
+use rust_decimal::Decimal;
+
+use super::{CallParams, LlmEstimator};
+use crate::types::{DataContext, Estimate, Market, MarketCategory};
+
+pub struct RoutingEstimator {
+    default: Box<dyn LlmEstimator>,
+    models: Vec<Box<dyn LlmEstimator>>,
+    /// Index into `models` of each routed category.
+    routes: HashMap<MarketCategory, usize>,
+}
+
+impl RoutingEstimator {
+    pub fn new(default: Box<dyn LlmEstimator>) -> Self {
+        Self { default, models: Vec::new(), routes: HashMap::new() }
+    }
+
+    /// Route markets of `categories` to `est`. A category routed twice
+    /// goes to the later estimator.
+    pub fn with_route(mut self, categories: &[MarketCategory], est: Box<dyn LlmEstimator>) -> Self {
+        for &category in categories {
+            self.routes.insert(category, self.models.len());
+        }
+        self.models.push(est);
+        self
+    }
+
+    /// Routed models, not counting the default.
+    pub fn route_count(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Estimator for markets of `category`.
+    fn target(&self, category: MarketCategory) -> &dyn LlmEstimator {
+        match self.routes.get(&category) {
+            Some(&i) => &*self.models[i],
+            None => &*self.default,
+        }
+    }
+
+    /// Positions of `markets` grouped by target model, each group in input
+    /// order; groups in order of first appearance.
+    fn groups(&self, markets: &[(Market, DataContext)]) -> Vec<(Option<usize>, Vec<usize>)> {
+        let mut groups: Vec<(Option<usize>, Vec<usize>)> = Vec::new();
+        for (i, (market, _)) in markets.iter().enumerate() {
+            let model = self.routes.get(&market.category).copied();
+            match groups.iter_mut().find(|(m, _)| *m == model) {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((model, vec![i])),
+            }
+        }
+        groups
+    }
+
+    /// Run one group's batch on `est`, tagging the results with its model
+    /// name where the estimator left them untagged.
+    async fn serve(
+        est: &dyn LlmEstimator,
+        batch: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let mut estimates = est.batch_estimate_with(batch, params).await?;
+        anyhow::ensure!(
+            estimates.len() == batch.len(),
+            "{} returned {} estimates for {} markets",
+            est.model_name(),
+            estimates.len(),
+            batch.len()
+        );
+        for e in &mut estimates {
+            e.served_by.get_or_insert_with(|| est.model_name().to_string());
+        }
+        Ok(estimates)
+    }
+}
+
+#[async_trait]
+impl LlmEstimator for RoutingEstimator {
+    async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(&self, market: &Market, context: &DataContext, params: &CallParams) -> Result<Estimate> {
+        let est = self.target(market.category);
+        let mut estimate = est.estimate_with(market, context, params).await?;
+        estimate.served_by.get_or_insert_with(|| est.model_name().to_string());
+        Ok(estimate)
+    }
+
+    async fn batch_estimate_with(
+        &self,
+        markets: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let groups = self.groups(markets);
+        let batches: Vec<Vec<(Market, DataContext)>> =
+            groups.iter().map(|(_, indices)| indices.iter().map(|&i| markets[i].clone()).collect()).collect();
+        let served = join_all(groups.iter().zip(&batches).map(|((model, _), batch)| {
+            let est = model.map_or(&*self.default, |i| &*self.models[i]);
+            Self::serve(est, batch, params)
+        }))
+        .await;
+
+        let mut slots: Vec<Option<Estimate>> = vec![None; markets.len()];
+        for ((_, indices), estimates) in groups.iter().zip(served) {
+            for (&i, e) in indices.iter().zip(estimates?) {
+                slots[i] = Some(e);
+            }
+        }
+        Ok(slots.into_iter().map(|e| e.expect("every market is routed")).collect())
+    }
+
+    /// Critique with the model the market's category routes to, so the
+    /// pass stays same-model.
+    async fn critique(&self, market: &Market, context: &DataContext, initial: &Estimate) -> Result<Estimate> {
+        let est = self.target(market.category);
+        let mut revised = est.critique(market, context, initial).await?;
+        revised.served_by.get_or_insert_with(|| est.model_name().to_string());
+        Ok(revised)
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        self.default.cost_per_call()
+    }
+
+    fn model_name(&self) -> &str {
+        self.default.model_name()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::failover::cost_by_provider;
+    use rust_decimal_macros::dec;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers every market with a fixed probability at a fixed cost per
+    /// market. Records the batches it was sent.
+    struct MockLlm {
+        name: &'static str,
+        probability: Decimal,
+        cost: Decimal,
+        batches: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl MockLlm {
+        fn new(name: &'static str, probability: Decimal, cost: Decimal) -> Self {
+            Self { name, probability, cost, batches: Arc::default() }
+        }
+    }
+
+    #[async_trait]
+    impl LlmEstimator for MockLlm {
+        async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+            let mut batch = self.batch_estimate(&[(market.clone(), context.clone())]).await?;
+            Ok(batch.remove(0))
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            self.batches.lock().unwrap().push(markets.iter().map(|(m, _)| m.id.clone()).collect());
+            Ok(markets
+                .iter()
+                .map(|_| Estimate {
+                    probability: self.probability,
+                    confidence: dec!(0.8),
+                    reasoning: String::new(),
+                    tokens_used: 100,
+                    cost: self.cost,
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                })
+                .collect())
+        }
+
+        async fn critique(&self, _: &Market, _: &DataContext, initial: &Estimate) -> Result<Estimate> {
+            Ok(Estimate { reasoning: format!("critiqued by {}", self.name), ..initial.clone() })
+        }
+
+        fn cost_per_call(&self) -> Decimal { self.cost }
+        fn model_name(&self) -> &str { self.name }
+    }
+
+    fn markets(categories: &[MarketCategory]) -> Vec<(Market, DataContext)> {
+        categories
+            .iter()
+            .enumerate()
+            .map(|(i, &category)| {
+                (Market { id: format!("m{i}"), category, ..Market::sample() }, DataContext::empty(category))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_batch_is_grouped_by_model_and_reassembled_in_order() {
+        use MarketCategory::*;
+        let cheap = MockLlm::new("cheap", dec!(0.50), dec!(0.001));
+        let reasoning = MockLlm::new("reasoning", dec!(0.70), dec!(0.02));
+        let (cheap_batches, reasoning_batches) = (cheap.batches.clone(), reasoning.batches.clone());
+        let llm = RoutingEstimator::new(Box::new(cheap)).with_route(&[Politics, Economics], Box::new(reasoning));
+        assert_eq!(llm.route_count(), 1);
+
+        let input = markets(&[Sports, Politics, Weather, Economics, Sports]);
+        let estimates = llm.batch_estimate(&input).await.unwrap();
+
+        // One call per model, each with its markets in input order.
+        assert_eq!(*cheap_batches.lock().unwrap(), vec![vec!["m0", "m2", "m4"]]);
+        assert_eq!(*reasoning_batches.lock().unwrap(), vec![vec!["m1", "m3"]]);
+        let served: Vec<_> = estimates.iter().map(|e| e.served_by.as_deref().unwrap()).collect();
+        assert_eq!(served, ["cheap", "reasoning", "cheap", "reasoning", "cheap"]);
+        assert_eq!(estimates[1].probability, dec!(0.70));
+        assert_eq!(estimates[4].probability, dec!(0.50));
+
+        // Per-model spend adds up to the cycle's LLM cost.
+        let costs = cost_by_provider(&estimates);
+        assert_eq!((costs["cheap"], costs["reasoning"]), (dec!(0.003), dec!(0.04)));
+        assert_eq!(costs.values().sum::<Decimal>(), estimates.iter().map(|e| e.cost).sum::<Decimal>());
+        assert_eq!(llm.model_name(), "cheap");
+    }
+
+    #[tokio::test]
+    async fn test_single_estimates_and_critiques_follow_the_route() {
+        use MarketCategory::*;
+        let reasoning = MockLlm::new("reasoning", dec!(0.70), dec!(0.02));
+        let llm = RoutingEstimator::new(Box::new(MockLlm::new("cheap", dec!(0.50), dec!(0.001))))
+            .with_route(&[Politics], Box::new(reasoning));
+
+        let input = markets(&[Politics, Culture]);
+        let (politics, culture) = (&input[0], &input[1]);
+        let e0 = llm.estimate_probability(&politics.0, &politics.1).await.unwrap();
+        let e1 = llm.estimate_probability(&culture.0, &culture.1).await.unwrap();
+        assert_eq!((e0.served_by.as_deref(), e0.probability), (Some("reasoning"), dec!(0.70)));
+        assert_eq!((e1.served_by.as_deref(), e1.probability), (Some("cheap"), dec!(0.50)));
+
+        assert_eq!(llm.critique(&politics.0, &politics.1, &e0).await.unwrap().reasoning, "critiqued by reasoning");
+        assert_eq!(llm.critique(&culture.0, &culture.1, &e1).await.unwrap().reasoning, "critiqued by cheap");
+    }
+
+    #[tokio::test]
+    async fn test_tag_from_wrapped_estimator_is_kept() {
+        // A failover default names the provider that actually answered.
+        struct Tagged(MockLlm);
+
+        #[async_trait]
+        impl LlmEstimator for Tagged {
+            async fn estimate_probability(&self, m: &Market, c: &DataContext) -> Result<Estimate> {
+                self.0.estimate_probability(m, c).await
+            }
+            async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+                let mut estimates = self.0.batch_estimate(markets).await?;
+                for e in &mut estimates {
+                    e.served_by = Some("secondary".to_string());
+                }
+                Ok(estimates)
+            }
+            fn cost_per_call(&self) -> Decimal { self.0.cost_per_call() }
+            fn model_name(&self) -> &str { "primary" }
+        }
+
+        let default = Tagged(MockLlm::new("primary", dec!(0.50), dec!(0.01)));
+        let llm = RoutingEstimator::new(Box::new(default))
+            .with_route(&[MarketCategory::Politics], Box::new(MockLlm::new("reasoning", dec!(0.70), dec!(0.02))));
+        let estimates = llm.batch_estimate(&markets(&[MarketCategory::Weather])).await.unwrap();
+        assert_eq!(estimates[0].served_by.as_deref(), Some("secondary"));
+    }
+}
@@ -0,0 +1,510 @@
+//! Shadow-mode model canary.
+//!
+//! Runs a second ("shadow") LLM over a bounded random sample of each
+//! cycle's markets alongside the primary estimator. Shadow estimates are
+//! recorded for comparison only — they never reach the strategy pipeline.
+//!
+//! The accumulated [`ComparisonReport`] answers "is the new model at least
+//! as good as the current one?" before it is switched in: mean absolute
+//! difference, directional agreement against the market price, relative
+//! cost and latency, and — once markets resolve — comparative Brier scores.
+//! Samples are kept in a [`ShadowStore`] so the comparison survives
+//! restarts and sampled markets can be checked for outcomes whether or not
+//! we bet on them.
+
+use std::time::Instant;
+
+use anyhow::Result;
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use super::LlmEstimator;
+use crate::diagnostics::{CollectionSize, SizedStore};
+use crate::storage::shadow::{ShadowRecord, ShadowStore, DEFAULT_SHADOW_FILE, MAX_RECORDS};
+use crate::types::{DataContext, Estimate, Market, MarketResolution};
+
+// ---------------------------------------------------------------------------
+// Sampling
+// ---------------------------------------------------------------------------
+
+/// Minimal xorshift PRNG — enough for market sampling without pulling in
+/// a dependency, and seedable for deterministic tests.
+#[derive(Debug, Clone)]
+struct XorShift(u64);
+
+impl XorShift {
+    fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x9E37_79B9_7F4A_7C15);
+        Self::seeded(nanos)
+    }
+
+    fn seeded(seed: u64) -> Self {
+        // xorshift must never hold zero.
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Uniform in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Snapshot of the primary vs shadow comparison.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonReport {
+    pub primary_model: String,
+    pub shadow_model: String,
+    /// Markets estimated by both models.
+    pub sample_size: usize,
+    /// Of those, how many have resolved (Brier sample size).
+    pub resolved_count: usize,
+    pub mean_abs_diff: f64,
+    /// Fraction of samples where both models pick the same side vs market.
+    pub directional_agreement: f64,
+    /// Shadow spend / primary spend on the sampled markets (1.0 = same cost).
+    pub relative_cost: Option<f64>,
+    pub primary_latency_ms: f64,
+    pub shadow_latency_ms: f64,
+    pub primary_brier: Option<f64>,
+    pub shadow_brier: Option<f64>,
+}
+
+impl ComparisonReport {
+    /// One-line summary for logs / the daily report.
+    pub fn summary(&self) -> String {
+        let brier = match (self.primary_brier, self.shadow_brier) {
+            (Some(p), Some(s)) => {
+                format!("Brier {p:.3} vs {s:.3} over {}/{} resolved", self.resolved_count, self.sample_size)
+            }
+            _ => format!("Brier pending (0/{} resolved)", self.sample_size),
+        };
+        format!(
+            "{} vs {} over n={}: |Δp|={:.3}, agree={:.0}%, cost×{}, latency {:.0}ms vs {:.0}ms, {}",
+            self.primary_model,
+            self.shadow_model,
+            self.sample_size,
+            self.mean_abs_diff,
+            self.directional_agreement * 100.0,
+            self.relative_cost.map(|c| format!("{c:.2}")).unwrap_or_else(|| "n/a".into()),
+            self.primary_latency_ms,
+            self.shadow_latency_ms,
+            brier,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shadow runner
+// ---------------------------------------------------------------------------
+
+/// Owns the shadow estimator and the accumulated comparison.
+pub struct ShadowRunner {
+    shadow: Box<dyn LlmEstimator>,
+    primary_model: String,
+    /// Percentage (0–100) of each cycle's markets to double-estimate.
+    sample_pct: f64,
+    /// Hard cap on sampled markets per cycle.
+    max_per_cycle: usize,
+    rng: XorShift,
+    /// Samples and their outcomes; re-sampling a market replaces its record.
+    store: ShadowStore,
+    primary_cost: Decimal,
+    shadow_cost: Decimal,
+    primary_latency_ms: f64,
+    shadow_latency_ms: f64,
+    latency_samples: usize,
+}
+
+impl ShadowRunner {
+    pub fn new(
+        shadow: Box<dyn LlmEstimator>,
+        primary_model: &str,
+        sample_pct: f64,
+        max_per_cycle: usize,
+    ) -> Self {
+        Self {
+            shadow,
+            primary_model: primary_model.to_string(),
+            sample_pct: sample_pct.clamp(0.0, 100.0),
+            max_per_cycle,
+            rng: XorShift::from_time(),
+            store: ShadowStore::new(DEFAULT_SHADOW_FILE),
+            primary_cost: Decimal::ZERO,
+            shadow_cost: Decimal::ZERO,
+            primary_latency_ms: 0.0,
+            shadow_latency_ms: 0.0,
+            latency_samples: 0,
+        }
+    }
+
+    /// Replace the sampling RNG with a fixed seed (tests).
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = XorShift::seeded(seed);
+        self
+    }
+
+    /// Keep samples in `store` (e.g. one loaded from disk) instead of the
+    /// empty default.
+    pub fn with_store(mut self, store: ShadowStore) -> Self {
+        self.store = store;
+        self
+    }
+
+    pub fn store(&self) -> &ShadowStore {
+        &self.store
+    }
+
+    pub fn store_mut(&mut self) -> &mut ShadowStore {
+        &mut self.store
+    }
+
+    /// Write the samples to the store's file.
+    pub fn save(&self) -> Result<()> {
+        self.store.save()
+    }
+
+    pub fn shadow_model(&self) -> &str {
+        self.shadow.model_name()
+    }
+
+    /// Pick the indices of markets to double-estimate this cycle.
+    ///
+    /// Each market is included with probability `sample_pct / 100`; when
+    /// more than `max_per_cycle` are, that many are kept uniformly at random
+    /// (a partial Fisher-Yates shuffle) rather than the first ones, which
+    /// would favour the highest-priority markets. Returned in ascending order.
+    pub fn sample_indices(&mut self, n: usize) -> Vec<usize> {
+        let p = self.sample_pct / 100.0;
+        let mut picked: Vec<usize> = (0..n).filter(|_| self.rng.next_f64() < p).collect();
+        let keep = picked.len().min(self.max_per_cycle);
+        for i in 0..keep {
+            let j = i + (self.rng.next_u64() % (picked.len() - i) as u64) as usize;
+            picked.swap(i, j);
+        }
+        picked.truncate(keep);
+        picked.sort_unstable();
+        picked
+    }
+
+    /// Run the shadow model over a sample of this cycle's markets.
+    ///
+    /// `primary` holds the primary estimates aligned with `markets`;
+    /// `primary_latency_ms` is the primary's per-market latency for this
+    /// cycle. Returns the shadow spend so the caller can account for it.
+    /// Failures are logged and swallowed — the canary must never break a cycle.
+    pub async fn run(
+        &mut self,
+        markets: &[(Market, DataContext)],
+        primary: &[Estimate],
+        primary_latency_ms: f64,
+    ) -> Decimal {
+        let n = markets.len().min(primary.len());
+        let indices = self.sample_indices(n);
+        if indices.is_empty() {
+            return Decimal::ZERO;
+        }
+
+        let sample: Vec<(Market, DataContext)> = indices.iter().map(|&i| markets[i].clone()).collect();
+        let started = Instant::now();
+        let shadow_estimates: Result<Vec<Estimate>> = self.shadow.batch_estimate(&sample).await;
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        let shadow_estimates = match shadow_estimates {
+            Ok(e) => e,
+            Err(e) => {
+                warn!(model = %self.shadow.model_name(), error = %e, "Shadow estimation failed — skipping this cycle");
+                return Decimal::ZERO;
+            }
+        };
+
+        let spent: Decimal = shadow_estimates.iter().map(|e| e.cost).sum();
+        let per_market_ms = elapsed_ms / sample.len() as f64;
+
+        let now = chrono::Utc::now();
+        for (&i, shadow_est) in indices.iter().zip(&shadow_estimates) {
+            let (market, _) = &markets[i];
+            let primary_est = &primary[i];
+            self.primary_cost += primary_est.cost;
+            self.shadow_cost += shadow_est.cost;
+            self.primary_latency_ms += primary_latency_ms;
+            self.shadow_latency_ms += per_market_ms;
+            self.latency_samples += 1;
+            self.store.record(ShadowRecord {
+                platform: market.platform.clone(),
+                market_id: market.id.clone(),
+                market_price: market.current_price_yes.to_f64().unwrap_or(0.5),
+                primary_prob: primary_est.probability.to_f64().unwrap_or(0.5),
+                shadow_prob: shadow_est.probability.to_f64().unwrap_or(0.5),
+                sampled_at: now,
+                deadline: market.deadline,
+                resolved_yes: None,
+            });
+        }
+
+        debug!(
+            sampled = shadow_estimates.len(),
+            shadow_cost = %spent,
+            "Shadow estimates recorded"
+        );
+        spent
+    }
+
+    /// Record a market resolution. Ignored for markets that were never
+    /// sampled; returns whether the store changed.
+    pub fn record_resolution(&mut self, platform: &str, market_id: &str, resolution: MarketResolution) -> bool {
+        let changed = self.store.resolve(platform, market_id, resolution);
+        if changed {
+            info!(platform, market_id, ?resolution, "Shadow-sampled market resolved");
+        }
+        changed
+    }
+
+    /// Build the current comparison snapshot.
+    pub fn report(&self) -> ComparisonReport {
+        let n = self.store.len();
+        let (mut diff_sum, mut agree) = (0.0, 0usize);
+        let (mut p_brier, mut s_brier, mut resolved) = (0.0, 0.0, 0usize);
+
+        for r in self.store.records() {
+            diff_sum += (r.primary_prob - r.shadow_prob).abs();
+            if r.agrees() {
+                agree += 1;
+            }
+            if let Some(yes) = r.resolved_yes {
+                let outcome = if yes { 1.0 } else { 0.0 };
+                p_brier += (r.primary_prob - outcome).powi(2);
+                s_brier += (r.shadow_prob - outcome).powi(2);
+                resolved += 1;
+            }
+        }
+
+        let mean = |sum: f64, count: usize| if count > 0 { sum / count as f64 } else { 0.0 };
+        let relative_cost = if self.primary_cost > Decimal::ZERO {
+            (self.shadow_cost / self.primary_cost).to_f64()
+        } else {
+            None
+        };
+
+        ComparisonReport {
+            primary_model: self.primary_model.clone(),
+            shadow_model: self.shadow.model_name().to_string(),
+            sample_size: n,
+            resolved_count: resolved,
+            mean_abs_diff: mean(diff_sum, n),
+            directional_agreement: mean(agree as f64, n),
+            relative_cost,
+            primary_latency_ms: mean(self.primary_latency_ms, self.latency_samples),
+            shadow_latency_ms: mean(self.shadow_latency_ms, self.latency_samples),
+            primary_brier: (resolved > 0).then(|| p_brier / resolved as f64),
+            shadow_brier: (resolved > 0).then(|| s_brier / resolved as f64),
+        }
+    }
+}
+
+impl SizedStore for ShadowRunner {
+    fn collection_sizes(&self) -> Vec<CollectionSize> {
+        vec![CollectionSize::new("shadow.records", self.store.len(), Some(MAX_RECORDS))]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MarketCategory;
+    use async_trait::async_trait;
+    use rust_decimal_macros::dec;
+
+    /// Returns a fixed probability for every market.
+    struct FixedEstimator {
+        name: &'static str,
+        prob: Decimal,
+        cost: Decimal,
+    }
+
+    #[async_trait]
+    impl LlmEstimator for FixedEstimator {
+        async fn estimate_probability(&self, _m: &Market, _c: &DataContext) -> Result<Estimate> {
+            Ok(Estimate {
+                probability: self.prob,
+                confidence: dec!(0.7),
+                reasoning: String::new(),
+                tokens_used: 10,
+                cost: self.cost,
+                critique: None,
+                served_by: None,
+                tier: None,
+            })
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            let mut out = Vec::new();
+            for (m, c) in markets {
+                out.push(self.estimate_probability(m, c).await?);
+            }
+            Ok(out)
+        }
+
+        fn cost_per_call(&self) -> Decimal {
+            self.cost
+        }
+
+        fn model_name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn markets(n: usize, price: Decimal) -> Vec<(Market, DataContext)> {
+        (0..n)
+            .map(|i| {
+                let mut m = Market::sample();
+                m.id = format!("m{i}");
+                m.current_price_yes = price;
+                m.current_price_no = Decimal::ONE - price;
+                (m, DataContext::empty(MarketCategory::Economics))
+            })
+            .collect()
+    }
+
+    async fn primary_estimates(mk: &[(Market, DataContext)], prob: Decimal) -> Vec<Estimate> {
+        let primary = FixedEstimator { name: "primary", prob, cost: dec!(0.010) };
+        primary.batch_estimate(mk).await.unwrap()
+    }
+
+    fn runner(shadow_prob: Decimal, pct: f64, cap: usize) -> ShadowRunner {
+        let shadow = FixedEstimator { name: "shadow", prob: shadow_prob, cost: dec!(0.020) };
+        ShadowRunner::new(Box::new(shadow), "primary", pct, cap).with_seed(42)
+    }
+
+    #[test]
+    fn test_sampling_respects_cap_and_pct() {
+        let mut r = runner(dec!(0.5), 100.0, 3);
+        let picked = r.sample_indices(10);
+        assert_eq!(picked.len(), 3);
+        assert!(picked.windows(2).all(|w| w[0] < w[1]) && picked.iter().all(|&i| i < 10));
+
+        let mut none = runner(dec!(0.5), 0.0, 10);
+        assert!(none.sample_indices(50).is_empty());
+
+        let mut half = runner(dec!(0.5), 50.0, 1000);
+        let picked = half.sample_indices(1000).len();
+        assert!((350..650).contains(&picked), "picked {picked}");
+    }
+
+    #[test]
+    fn test_capped_sample_covers_every_position() {
+        // 3 of 10 per cycle: each index should be kept ~30% of the time,
+        // not just the first three.
+        let mut r = runner(dec!(0.5), 100.0, 3);
+        let mut hits = [0usize; 10];
+        for _ in 0..10_000 {
+            for i in r.sample_indices(10) {
+                hits[i] += 1;
+            }
+        }
+        for (i, &h) in hits.iter().enumerate() {
+            assert!((2_700..3_300).contains(&h), "index {i} kept {h} times");
+        }
+    }
+
+    #[test]
+    fn test_sampling_is_deterministic_for_seed() {
+        let a = runner(dec!(0.5), 30.0, 100).sample_indices(100);
+        let b = runner(dec!(0.5), 30.0, 100).sample_indices(100);
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn test_run_records_comparison_stats() {
+        let mk = markets(4, dec!(0.50));
+        let primary = primary_estimates(&mk, dec!(0.60)).await;
+        let mut r = runner(dec!(0.70), 100.0, 10);
+
+        let spent = r.run(&mk, &primary, 120.0).await;
+        assert_eq!(spent, dec!(0.080));
+
+        let report = r.report();
+        assert_eq!(report.sample_size, 4);
+        assert!((report.mean_abs_diff - 0.10).abs() < 1e-9);
+        assert!((report.directional_agreement - 1.0).abs() < 1e-9);
+        assert!((report.relative_cost.unwrap() - 2.0).abs() < 1e-9);
+        assert!((report.primary_latency_ms - 120.0).abs() < 1e-9);
+        assert!(report.primary_brier.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_directional_disagreement() {
+        let mk = markets(2, dec!(0.50));
+        let primary = primary_estimates(&mk, dec!(0.60)).await;
+        let mut r = runner(dec!(0.40), 100.0, 10);
+        r.run(&mk, &primary, 0.0).await;
+        assert_eq!(r.report().directional_agreement, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_brier_after_scripted_resolutions() {
+        let mk = markets(2, dec!(0.50));
+        let primary = primary_estimates(&mk, dec!(0.80)).await;
+        let mut r = runner(dec!(0.60), 100.0, 10);
+        r.run(&mk, &primary, 0.0).await;
+
+        assert!(r.record_resolution("forecastex", "m0", MarketResolution::Yes));
+        assert!(r.record_resolution("forecastex", "m1", MarketResolution::No));
+        assert!(!r.record_resolution("forecastex", "never-sampled", MarketResolution::Yes));
+
+        let report = r.report();
+        assert_eq!((report.resolved_count, report.sample_size), (2, 2));
+        // primary: (0.8-1)^2=0.04, (0.8-0)^2=0.64 → 0.34
+        assert!((report.primary_brier.unwrap() - 0.34).abs() < 1e-9);
+        // shadow: (0.6-1)^2=0.16, (0.6-0)^2=0.36 → 0.26
+        assert!((report.shadow_brier.unwrap() - 0.26).abs() < 1e-9);
+        assert!(report.summary().contains("over 2/2 resolved"), "{}", report.summary());
+    }
+
+    #[tokio::test]
+    async fn test_samples_survive_a_restart() {
+        let path = std::env::temp_dir().join(format!("oracle_shadow_runner_{}.json", uuid::Uuid::new_v4()));
+        let mk = markets(3, dec!(0.50));
+        let primary = primary_estimates(&mk, dec!(0.60)).await;
+        let mut r = runner(dec!(0.70), 100.0, 10).with_store(ShadowStore::load(&path).unwrap());
+        r.run(&mk, &primary, 0.0).await;
+        r.record_resolution("forecastex", "m1", MarketResolution::No);
+        r.save().unwrap();
+
+        let restarted = runner(dec!(0.70), 100.0, 10).with_store(ShadowStore::load(&path).unwrap());
+        let report = restarted.report();
+        assert_eq!((report.sample_size, report.resolved_count), (3, 1));
+        assert!((report.shadow_brier.unwrap() - 0.49).abs() < 1e-9);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_empty_sample_costs_nothing() {
+        let mk = markets(3, dec!(0.50));
+        let primary = primary_estimates(&mk, dec!(0.60)).await;
+        let mut r = runner(dec!(0.70), 0.0, 10);
+        assert_eq!(r.run(&mk, &primary, 0.0).await, Decimal::ZERO);
+        assert_eq!(r.report().sample_size, 0);
+        assert!(r.report().relative_cost.is_none());
+    }
+}
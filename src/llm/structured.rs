@@ -0,0 +1,216 @@
+//! Structured (JSON) answers.
+//!
+//! Instead of the trailing `PROBABILITY:` / `CONFIDENCE:` lines, the model
+//! answers with a JSON object — or, for a batch, a JSON array keyed by
+//! `market_id` — parsed with serde. Replies may wrap the JSON in a
+//! markdown fence or surround it with prose. A reply that isn't valid
+//! JSON (truncated, or a model ignoring the format) yields `None`, and the
+//! caller falls back to the line parser in [`super::anthropic`].
+//!
+//! Values are checked the way the line parser checks them: a probability
+//! written as a percentage (`72`) is read as 0.72, and both numbers are
+//! clamped — probability to [0.01, 0.99], confidence to [0.1, 0.99]. A
+//! probability that is neither a fraction nor a percentage (a year, a
+//! negative number) makes the estimate invalid.
+
+use serde::Deserialize;
+use tracing::warn;
+
+/// Appended to the system prompt for single estimates, replacing the
+/// final-line format.
+pub const SINGLE_FORMAT: &str = "\n\nRESPONSE FORMAT: reply with a single JSON object and nothing else, \
+     in place of the PROBABILITY/CONFIDENCE lines:\n\
+     {\"probability\": 0.XX, \"confidence\": 0.XX, \"key_factors\": [\"<factor>\", ...], \
+     \"reasoning\": \"<brief step-by-step reasoning>\"}";
+
+/// Appended to the system prompt for batches, replacing the MARKET_ID lines.
+pub const BATCH_FORMAT: &str = "\n\nRESPONSE FORMAT: reply with a single JSON array and nothing else, \
+     in place of the MARKET_ID lines, with one object per market:\n\
+     [{\"market_id\": \"<id>\", \"probability\": 0.XX, \"confidence\": 0.XX}]";
+
+/// Model name fragments of models known to follow the JSON formats
+/// reliably; `llm.structured_output` defaults on for these.
+const SUPPORTED_MODELS: &[&str] = &[
+    "claude-3-5", "claude-3-7", "claude-sonnet-4", "claude-opus-4", "claude-haiku-4",
+    "gpt-4o", "gpt-4.1", "gpt-5",
+];
+
+/// Whether `model` is known to follow the JSON answer formats.
+pub fn supports(model: &str) -> bool {
+    let model = model.to_lowercase();
+    SUPPORTED_MODELS.iter().any(|m| model.contains(m))
+}
+
+/// Single estimate.
+#[derive(Debug, Deserialize)]
+struct JsonEstimate {
+    probability: f64,
+    #[serde(default)]
+    confidence: Option<f64>,
+    #[serde(default)]
+    key_factors: Vec<String>,
+    #[serde(default)]
+    reasoning: String,
+}
+
+/// One market of a batch.
+#[derive(Debug, Deserialize)]
+struct JsonBatchEntry {
+    market_id: String,
+    probability: f64,
+    #[serde(default)]
+    confidence: Option<f64>,
+}
+
+/// A batch reply: a bare array, or `{"estimates": [...]}` where the API
+/// only allows objects (OpenAI's `json_object` mode).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonBatch {
+    Array(Vec<JsonBatchEntry>),
+    Object { estimates: Vec<JsonBatchEntry> },
+}
+
+/// JSON body of a reply: the contents of a ```json fence if there is one,
+/// else the text from the first `{` or `[` to the last `}` or `]`.
+pub fn json_body(text: &str) -> &str {
+    let text = text.trim();
+    if let Some(start) = text.find("```") {
+        let fenced = &text[start + 3..];
+        let fenced = fenced.strip_prefix("json").unwrap_or(fenced);
+        let end = fenced.find("```").unwrap_or(fenced.len());
+        return fenced[..end].trim();
+    }
+    let start = text.find(['{', '[']).unwrap_or(0);
+    let end = text.rfind(['}', ']']).map_or(text.len(), |i| i + 1);
+    if start < end { &text[start..end] } else { text }
+}
+
+/// Probability as a fraction, clamped to [0.01, 0.99]; `None` outside
+/// [0, 100].
+fn probability(raw: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&raw) {
+        warn!(raw_prob = raw, "LLM returned an invalid probability");
+        return None;
+    }
+    let p = if raw > 1.0 { raw / 100.0 } else { raw };
+    if !(0.01..=0.99).contains(&p) {
+        warn!(raw_prob = raw, "LLM returned out-of-bounds probability — clamping to [0.01, 0.99]");
+    }
+    Some(p.clamp(0.01, 0.99))
+}
+
+/// Confidence clamped to [0.1, 0.99]; 0.5 when missing.
+fn confidence(raw: Option<f64>) -> f64 {
+    raw.unwrap_or(0.5).clamp(0.1, 0.99)
+}
+
+/// Parse a single estimate as `(probability, confidence, reasoning)`, the
+/// key factors appended to the reasoning. `None` when the reply is not a
+/// JSON estimate.
+pub fn parse_estimate(text: &str) -> Option<(f64, f64, String)> {
+    let est: JsonEstimate = serde_json::from_str(json_body(text)).ok()?;
+    let mut reasoning = est.reasoning;
+    if !est.key_factors.is_empty() {
+        if !reasoning.is_empty() {
+            reasoning.push('\n');
+        }
+        reasoning.push_str("Key factors: ");
+        reasoning.push_str(&est.key_factors.join("; "));
+    }
+    Some((probability(est.probability)?, confidence(est.confidence), reasoning))
+}
+
+/// Parse a batch reply into `expected_ids` order. `None` when the reply is
+/// not a JSON batch at all; markets missing from it or with an invalid
+/// probability are `None` within it.
+pub fn parse_batch(text: &str, expected_ids: &[&str]) -> Option<Vec<Option<(f64, f64)>>> {
+    let entries = match serde_json::from_str(json_body(text)).ok()? {
+        JsonBatch::Array(entries) | JsonBatch::Object { estimates: entries } => entries,
+    };
+    let mut results: Vec<Option<(f64, f64)>> = vec![None; expected_ids.len()];
+    for entry in entries {
+        let id = entry.market_id.trim();
+        if let Some(idx) = expected_ids.iter().position(|eid| id.eq_ignore_ascii_case(eid)) {
+            results[idx] = probability(entry.probability).map(|p| (p, confidence(entry.confidence)));
+        }
+    }
+    Some(results)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_estimate() {
+        let text = r#"{"probability": 0.64, "confidence": 0.7, "key_factors": ["payrolls beat", "Fed on hold"],
+            "reasoning": "Labour market still tight."}"#;
+        let (prob, conf, reasoning) = parse_estimate(text).unwrap();
+        assert_eq!((prob, conf), (0.64, 0.7));
+        assert_eq!(reasoning, "Labour market still tight.\nKey factors: payrolls beat; Fed on hold");
+    }
+
+    #[test]
+    fn test_parse_fenced_estimate() {
+        let text = "Here is my estimate:\n```json\n{\"probability\": 0.2, \"confidence\": 0.8}\n```\nThanks.";
+        assert_eq!(parse_estimate(text).unwrap(), (0.2, 0.8, String::new()));
+        let bare_fence = "```\n{\"probability\": 0.35}\n```";
+        assert_eq!(parse_estimate(bare_fence).unwrap().0, 0.35);
+        let with_prose = "After weighing it up: {\"probability\": 0.41, \"confidence\": 0.6} — final.";
+        assert_eq!(parse_estimate(with_prose).unwrap().0, 0.41);
+    }
+
+    #[test]
+    fn test_truncated_or_line_replies_are_not_json() {
+        let truncated = r#"{"probability": 0.62, "confidence": 0.7, "reasoning": "Polls tight"#;
+        assert!(parse_estimate(truncated).is_none());
+        assert!(parse_estimate("PROBABILITY: 0.4\nCONFIDENCE: 0.6").is_none());
+        assert!(parse_estimate(r#"{"confidence": 0.6}"#).is_none());
+    }
+
+    #[test]
+    fn test_bounds_are_clamped() {
+        assert_eq!(parse_estimate(r#"{"probability": 1.0}"#).unwrap().0, 0.99);
+        let (prob, conf, _) = parse_estimate(r#"{"probability": 0.0, "confidence": 0.0}"#).unwrap();
+        assert_eq!((prob, conf), (0.01, 0.1));
+        let (prob, conf, _) = parse_estimate(r#"{"probability": 72, "confidence": 1.5}"#).unwrap();
+        assert!((prob - 0.72).abs() < 1e-12);
+        assert_eq!(conf, 0.99);
+        // Neither a fraction nor a percentage: invalid, not clamped.
+        assert!(parse_estimate(r#"{"probability": 2026}"#).is_none());
+        assert!(parse_estimate(r#"{"probability": -0.2}"#).is_none());
+    }
+
+    #[test]
+    fn test_parse_batch_array_and_object() {
+        let array = r#"[
+            {"market_id": "M2", "probability": 0.3, "confidence": 0.8},
+            {"market_id": "m1", "probability": 0.7},
+            {"market_id": "m3", "probability": 2026},
+            {"market_id": "other", "probability": 0.5}
+        ]"#;
+        let parsed = parse_batch(array, &["m1", "m2", "m3"]).unwrap();
+        assert_eq!(parsed, vec![Some((0.7, 0.5)), Some((0.3, 0.8)), None]);
+
+        let object = r#"```json
+{"estimates": [{"market_id": "m1", "probability": 0.05, "confidence": 0.05}]}
+```"#;
+        assert_eq!(parse_batch(object, &["m1"]).unwrap(), vec![Some((0.05, 0.1))]);
+        assert!(parse_batch("MARKET_ID: m1 | PROBABILITY: 0.4", &["m1"]).is_none());
+        assert!(parse_batch(r#"[{"market_id": "m1", "probability": 0.4}, {"market_id": "m2", "prob"#, &["m1"]).is_none());
+    }
+
+    #[test]
+    fn test_supported_models() {
+        assert!(supports("claude-sonnet-4-6-20250514"));
+        assert!(supports("anthropic/claude-3-5-haiku"));
+        assert!(supports("GPT-4o-mini"));
+        assert!(!supports("claude-2.1"));
+        assert!(!supports("llama-3.1-70b"));
+    }
+}
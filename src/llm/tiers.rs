@@ -0,0 +1,318 @@
+//! Estimation budget tiers.
+//!
+//! Markets are assigned tier A, B or C from their scan priority score and
+//! the largest bet they could size, so LLM spend follows the money: a
+//! market that can only ever take a few dollars gets a short batched call
+//! on a summary of its context, while one that could size a large bet gets
+//! its own calls on the full context, several samples and a critique. See
+//! [`TiersConfig`] for the per-tier budget.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use futures::future::join_all;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::warn;
+
+use super::{CallParams, LlmEstimator};
+use crate::config::{TierParams, TiersConfig};
+use crate::types::{DataContext, Estimate, Market, Tier};
+
+/// Largest bet `market` could size, in AUD: `max_bet_pct` of `bankroll`,
+/// the balance its platform draws on ([`crate::types::AgentState::bankroll_for`]).
+/// Manifold stakes are in Mana and are converted at `mana_aud`.
+pub fn max_stake(market: &Market, bankroll: Decimal, max_bet_pct: Decimal, cfg: &TiersConfig) -> Decimal {
+    if market.platform == "manifold" {
+        bankroll * max_bet_pct * cfg.mana_aud
+    } else {
+        bankroll * max_bet_pct
+    }
+}
+
+/// Highest tier whose priority and stake minimums are both met.
+pub fn assign(priority: f64, max_stake: Decimal, cfg: &TiersConfig) -> Tier {
+    let meets = |t: &TierParams| priority >= t.min_priority && max_stake >= t.min_stake;
+    if meets(&cfg.a) {
+        Tier::A
+    } else if meets(&cfg.b) {
+        Tier::B
+    } else {
+        Tier::C
+    }
+}
+
+/// Estimate each market under its tier's budget. `tiers` is index-aligned
+/// with `markets`, and so are the returned estimates, each tagged with its
+/// tier. Batched tiers propagate estimator errors as `batch_estimate` does;
+/// an individually estimated market whose samples all fail is echoed.
+pub async fn estimate(
+    llm: &dyn LlmEstimator,
+    cfg: &TiersConfig,
+    markets: &[(Market, DataContext)],
+    tiers: &[Tier],
+) -> Result<Vec<Estimate>> {
+    let mut slots: Vec<Option<Estimate>> = vec![None; markets.len()];
+    for tier in [Tier::A, Tier::B, Tier::C] {
+        let indices: Vec<usize> = (0..markets.len()).filter(|&i| tiers[i] == tier).collect();
+        if indices.is_empty() {
+            continue;
+        }
+        let params = cfg.params(tier);
+        let call = CallParams { max_tokens: Some(params.max_tokens) };
+        let group: Vec<(Market, DataContext)> = indices
+            .iter()
+            .map(|&i| {
+                let (m, c) = &markets[i];
+                let ctx = if params.full_context { c.clone() } else { c.summary_only() };
+                (m.clone(), ctx)
+            })
+            .collect();
+
+        let estimates = if params.individual {
+            join_all(group.iter().map(|(m, c)| sample(llm, m, c, &call, params.samples))).await
+        } else {
+            let mut samples: Vec<Vec<Estimate>> = vec![Vec::new(); group.len()];
+            for _ in 0..params.samples {
+                for (s, e) in samples.iter_mut().zip(llm.batch_estimate_with(&group, &call).await?) {
+                    s.push(e);
+                }
+            }
+            samples.into_iter().map(|s| average(s).expect("samples ≥ 1")).collect()
+        };
+
+        for (i, mut e) in indices.into_iter().zip(estimates) {
+            e.tier = Some(tier);
+            slots[i] = Some(e);
+        }
+    }
+    Ok(slots.into_iter().map(|e| e.expect("every market has a tier")).collect())
+}
+
+/// `samples` individual estimates of one market, averaged.
+async fn sample(
+    llm: &dyn LlmEstimator,
+    market: &Market,
+    context: &DataContext,
+    params: &CallParams,
+    samples: u32,
+) -> Estimate {
+    let mut drawn = Vec::new();
+    for _ in 0..samples {
+        match llm.estimate_with(market, context, params).await {
+            Ok(e) => drawn.push(e),
+            Err(e) => warn!(market_id = %market.id, error = %e, "Tiered estimate sample failed"),
+        }
+    }
+    average(drawn).unwrap_or_else(|| Estimate {
+        probability: market.current_price_yes,
+        confidence: dec!(0.1),
+        reasoning: "All estimate samples failed".to_string(),
+        tokens_used: 0,
+        cost: Decimal::ZERO,
+        critique: None,
+        served_by: None,
+        tier: None,
+    })
+}
+
+/// Mean probability and confidence of `samples`, with their summed cost
+/// and tokens. Reasoning and provider come from the first sample.
+fn average(samples: Vec<Estimate>) -> Option<Estimate> {
+    let n = Decimal::from(samples.len());
+    let mut iter = samples.into_iter();
+    let mut merged = iter.next()?;
+    if n == Decimal::ONE {
+        return Some(merged);
+    }
+    for e in iter {
+        merged.probability += e.probability;
+        merged.confidence += e.confidence;
+        merged.tokens_used += e.tokens_used;
+        merged.cost += e.cost;
+    }
+    merged.probability /= n;
+    merged.confidence /= n;
+    merged.reasoning = format!("(mean of {n} samples) {}", merged.reasoning);
+    Some(merged)
+}
+
+/// LLM spend per tier. Untiered estimates are not listed.
+pub fn cost_by_tier<'a>(estimates: impl IntoIterator<Item = &'a Estimate>) -> BTreeMap<Tier, Decimal> {
+    let mut costs = BTreeMap::new();
+    for e in estimates {
+        if let Some(tier) = e.tier {
+            *costs.entry(tier).or_insert(Decimal::ZERO) += e.cost;
+        }
+    }
+    costs
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    /// One request the mock was sent.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Request {
+        ids: Vec<String>,
+        max_tokens: Option<u32>,
+        summaries: Vec<String>,
+    }
+
+    /// Answers with `0.50 + 0.10 × call number` and records every request.
+    #[derive(Default)]
+    struct CapturingLlm {
+        requests: Mutex<Vec<Request>>,
+    }
+
+    impl CapturingLlm {
+        fn answer(&self, markets: &[(Market, DataContext)], params: &CallParams) -> Vec<Estimate> {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(Request {
+                ids: markets.iter().map(|(m, _)| m.id.clone()).collect(),
+                max_tokens: params.max_tokens,
+                summaries: markets.iter().map(|(_, c)| c.render()).collect(),
+            });
+            let probability = dec!(0.40) + dec!(0.10) * Decimal::from(requests.len());
+            markets
+                .iter()
+                .map(|_| Estimate {
+                    probability,
+                    confidence: dec!(0.8),
+                    reasoning: String::new(),
+                    tokens_used: 100,
+                    cost: dec!(0.01),
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                })
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl LlmEstimator for CapturingLlm {
+        async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+            self.estimate_with(market, context, &CallParams::default()).await
+        }
+
+        async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+            self.batch_estimate_with(markets, &CallParams::default()).await
+        }
+
+        async fn estimate_with(&self, market: &Market, context: &DataContext, params: &CallParams) -> Result<Estimate> {
+            Ok(self.answer(&[(market.clone(), context.clone())], params).remove(0))
+        }
+
+        async fn batch_estimate_with(
+            &self,
+            markets: &[(Market, DataContext)],
+            params: &CallParams,
+        ) -> Result<Vec<Estimate>> {
+            Ok(self.answer(markets, params))
+        }
+
+        fn cost_per_call(&self) -> Decimal { dec!(0.01) }
+        fn model_name(&self) -> &str { "capturing" }
+    }
+
+    fn market(id: &str, platform: &str) -> (Market, DataContext) {
+        let m = Market {
+            id: id.to_string(),
+            platform: platform.to_string(),
+            question: format!("Will {id} happen?"),
+            description: String::new(),
+            category: crate::types::MarketCategory::Weather,
+            current_price_yes: dec!(0.40),
+            current_price_no: dec!(0.60),
+            volume_24h: dec!(1000),
+            liquidity: dec!(5000),
+            deadline: Utc::now() + chrono::Duration::days(3),
+            resolution_criteria: String::new(),
+            url: String::new(),
+            cross_refs: Default::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        };
+        let ctx = DataContext {
+            category: m.category,
+            raw_data: serde_json::Value::Null,
+            summary: "Headline\nDetail line one\nDetail line two".to_string(),
+            freshness: Utc::now(),
+            source: "test".to_string(),
+            cost: Decimal::ZERO,
+            metaculus_forecast: None,
+            metaculus_forecasters: None,
+            manifold_price: None,
+            sections: Vec::new(),
+        };
+        (m, ctx)
+    }
+
+    #[test]
+    fn test_tier_boundaries() {
+        let cfg = TiersConfig::default();
+        // Both minimums are inclusive and both must be met.
+        assert_eq!(assign(80.0, dec!(20), &cfg), Tier::A);
+        assert_eq!(assign(79.9, dec!(20), &cfg), Tier::B);
+        assert_eq!(assign(200.0, dec!(19.99), &cfg), Tier::B);
+        assert_eq!(assign(40.0, dec!(5), &cfg), Tier::B);
+        assert_eq!(assign(39.9, dec!(100), &cfg), Tier::C);
+        assert_eq!(assign(100.0, dec!(4.99), &cfg), Tier::C);
+    }
+
+    #[test]
+    fn test_max_stake_converts_mana() {
+        let cfg = TiersConfig::default();
+        let (betfair, _) = market("b", "betfair");
+        let (manifold, _) = market("m", "manifold");
+        // $500 AUD bankroll at 6% → $30; 5000 Mana at 6% → 300 Mana → $3.
+        assert_eq!(max_stake(&betfair, dec!(500), dec!(0.06), &cfg), dec!(30));
+        assert_eq!(max_stake(&manifold, dec!(5000), dec!(0.06), &cfg), dec!(3));
+    }
+
+    #[tokio::test]
+    async fn test_requests_follow_tier_params() {
+        let cfg = TiersConfig::default();
+        let llm = CapturingLlm::default();
+        let markets = vec![market("a1", "betfair"), market("c1", "manifold"), market("c2", "manifold")];
+        let tiers = [Tier::A, Tier::C, Tier::C];
+
+        let estimates = estimate(&llm, &cfg, &markets, &tiers).await.unwrap();
+
+        let requests = llm.requests.lock().unwrap().clone();
+        // Tier A: three individual full-context samples at 1500 tokens.
+        assert_eq!(requests.len(), 4);
+        for r in &requests[..3] {
+            assert_eq!(r.ids, vec!["a1"]);
+            assert_eq!(r.max_tokens, Some(1500));
+            assert!(r.summaries[0].contains("Detail line two"));
+        }
+        // Tier C: one summary-only batch at 300 tokens.
+        assert_eq!(requests[3].ids, vec!["c1", "c2"]);
+        assert_eq!(requests[3].max_tokens, Some(300));
+        assert_eq!(requests[3].summaries, vec!["Headline", "Headline"]);
+
+        // Estimates stay aligned with the input and carry their tier; the
+        // tier A estimate averages 0.50, 0.60 and 0.70.
+        assert_eq!(estimates[0].tier, Some(Tier::A));
+        assert_eq!(estimates[0].probability, dec!(0.60));
+        assert_eq!(estimates[0].cost, dec!(0.03));
+        assert_eq!(estimates[1].tier, Some(Tier::C));
+        assert_eq!(estimates[2].probability, dec!(0.80));
+
+        let costs = cost_by_tier(&estimates);
+        assert_eq!(costs.get(&Tier::A), Some(&dec!(0.03)));
+        assert_eq!(costs.get(&Tier::C), Some(&dec!(0.02)));
+        assert_eq!(costs.get(&Tier::B), None);
+    }
+}
@@ -4,34 +4,83 @@
 //! restores state from disk (or creates fresh), and runs the main
 //! scan→estimate→bet loop with graceful shutdown.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use futures::TryStreamExt;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, error, info, warn};
-
-use oracle::dashboard::routes::{AppState, BalancePoint, CycleLogEntry, DashboardState, ErrorLogEntry, EvaluationProgress, TradeLogEntry};
-use oracle::dashboard::spawn_dashboard;
-
-use oracle::config;
+use tracing::{debug, error, field, info, info_span, warn, Instrument};
+
+use oracle::dashboard::routes::{AppState, CategoryThresholdView, CycleEvent, CycleLogEntry, DashboardState, EnrichmentAvailability, ErrorLogEntry, EvaluationProgress, TradeLogEntry, MAX_CYCLE_LOG};
+use oracle::dashboard::{spawn_dashboard, spawn_public_dashboard};
+use oracle::alerts::Webhook;
+use oracle::notifications::{DailySchedule, DrawdownTiers, Event, Notifications};
+use oracle::diagnostics::{Diagnostics, SizedStore};
+use oracle::error_buffer::RecentErrors;
+use oracle::telemetry;
+use oracle::prometheus::{self, TimedEstimator};
+
+use oracle::config::{self, CoolDownConfig, EdgeExitConfig, ReferenceWeightsConfig, SelfCritiqueConfig, TiersConfig};
 use oracle::engine::accountant::{Accountant, CycleCosts, CycleReport};
+use oracle::engine::resolver::Resolver;
 use oracle::engine::auto_exit::{AutoExitConfig, AutoExitEngine, CloseResult};
-use oracle::engine::enricher::Enricher;
-use oracle::engine::executor::Executor;
+use oracle::engine::cost_guard::{self, CycleBudget};
+use oracle::engine::cost_tracker::{self, CostKind, CostTracker};
+use oracle::engine::cycle_context::{self, ApprovedStage, CycleRecovery, EnrichedStage};
+use oracle::data::economics::FredProvider;
+use oracle::data::news::{NewsProvider, FREE_TIER_REQUESTS_PER_DAY};
+use oracle::data::sports::{ApiSportsProvider, DailyCounter, FREE_TIER_REQUESTS_PER_DAY as SPORTS_FREE_TIER_REQUESTS_PER_DAY};
+use oracle::engine::enricher::{Enricher, DEFAULT_MAX_CONCURRENT_REQUESTS};
+use oracle::engine::executor::{ExecutionFailure, ExecutionReport, Executor};
+use oracle::engine::reconcile;
+use oracle::engine::pause;
+use oracle::engine::shutdown::{self, Shutdown};
+use oracle::engine::standby::{self, ModeRequest};
+use oracle::engine::state::SharedState;
+use oracle::engine::{daily, tags};
+use oracle::engine::watchlist::{ListFiles, ListPaths};
+use oracle::embeddings::EmbeddingMatcher;
 use oracle::engine::scanner::MarketRouter;
 use oracle::llm::anthropic::AnthropicClient;
+use oracle::llm::grok::GrokClient;
 use oracle::llm::openai::OpenAiClient;
 use oracle::llm::openrouter::OpenRouterClient;
+use oracle::llm::routing::RoutingEstimator;
+use oracle::llm::budget::{self, PromptBudget};
+use oracle::llm::cache::EstimateCache;
+use oracle::llm::critique;
+use oracle::llm::ensemble::EnsembleEstimator;
+use oracle::llm::failover::{self, FailoverEstimator};
+use oracle::llm::shadow::ShadowRunner;
+use oracle::llm::tiers;
+use oracle::llm::structured;
 use oracle::llm::LlmEstimator;
 use oracle::platforms::betfair::BetfairClient;
+use oracle::platforms::forecastex::ForecastExClient;
+use oracle::platforms::kalshi::KalshiClient;
 use oracle::platforms::manifold::ManifoldClient;
 use oracle::platforms::metaculus::MetaculusClient;
 use oracle::storage;
+use oracle::storage::backend::{BalanceSample, Storage, TradeQuery};
+use oracle::storage::archive::{Archive, ArchiveReason};
+use oracle::storage::calibration::{CalibrationStore, DEFAULT_CALIBRATION_FILE};
+use oracle::storage::journal::DecisionJournal;
+use oracle::storage::metrics::MetricsStore;
+use oracle::storage::migrations::NewerSchema;
+use oracle::storage::shadow::{ShadowStore, DEFAULT_SHADOW_FILE};
+use oracle::storage::research;
+use oracle::backtest::replay::{self, Recording, ReplayReport};
+use oracle::backtest::sensitivity::{self, SensitivityParams};
+use oracle::strategy::links::{self, MarketLinks};
+use oracle::strategy::performance::{Attribution, PerformanceReport, MIN_SUGGESTION_TRADES};
+use oracle::strategy::{adaptive, cooldown, exit, references};
 use oracle::strategy::edge::{EdgeConfig, EdgeDetector};
-use oracle::strategy::kelly::{KellyCalculator, KellyConfig};
-use oracle::strategy::risk::{RiskConfig, RiskManager};
-use oracle::strategy::StrategyOrchestrator;
+use oracle::strategy::kelly::KellyCalculator;
+use oracle::strategy::risk::{self, RiskConfig, RiskManager};
+use oracle::strategy::arbitrage::ArbitrageDetector;
+use oracle::strategy::venue::VenueSelector;
+use oracle::strategy::{DecisionRecord, StrategyOrchestrator, StrategyParams};
 use oracle::types::{AgentState, AgentStatus};
 
 const BANNER: &str = r#"
@@ -51,10 +100,89 @@ async fn main() -> Result<()> {
     let _ = dotenv::dotenv();
 
     // Load configuration from TOML
-    let cfg = config::AppConfig::load("config.toml")?;
+    let (cfg, effective_config) = config::AppConfig::load_effective("config.toml")?;
 
     // Initialise structured logging
-    init_logging(&cfg);
+    let recent_errors = init_logging(&cfg);
+
+    // `oracle rebuild-rollups` — regenerate hourly metrics rollups from the
+    // raw per-cycle history, then exit.
+    if std::env::args().nth(1).as_deref() == Some("rebuild-rollups") {
+        let store = MetricsStore::open(&cfg.dashboard.metrics_db).await?;
+        let hours = store.rebuild_rollups().await?;
+        println!("Rebuilt {hours} hourly rollups in {}", cfg.dashboard.metrics_db);
+        return Ok(());
+    }
+
+    // `oracle export --research [--out PATH] [--salt SALT]` — write the
+    // anonymised decision history as CSV, then exit. Without a salt the
+    // market keys are unlinkable across exports.
+    if std::env::args().nth(1).as_deref() == Some("export") {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        let flag = |name: &str| args.iter().position(|a| a == name).and_then(|i| args.get(i + 1)).cloned();
+        anyhow::ensure!(args.iter().any(|a| a == "--research"), "usage: oracle export --research [--out PATH] [--salt SALT]");
+        let out = flag("--out").unwrap_or_else(|| "research.csv".to_string());
+        let salt = flag("--salt").unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let store = MetricsStore::open(&cfg.dashboard.metrics_db).await?;
+        let file = std::fs::File::create(&out).with_context(|| format!("Failed to create {out}"))?;
+        let rows = research::export_csv(&store, std::io::BufWriter::new(file), &salt).await?;
+        println!("Exported {rows} decisions to {out} (schema v{})", research::SCHEMA_VERSION);
+        return Ok(());
+    }
+
+    // `oracle archive --rebuild-index` — regenerate the market archive's
+    // lookup index from the archive directory, then exit.
+    if std::env::args().nth(1).as_deref() == Some("archive") {
+        anyhow::ensure!(std::env::args().any(|a| a == "--rebuild-index"), "usage: oracle archive --rebuild-index");
+        let markets = Archive::new(&cfg.archive.dir).rebuild_index()?;
+        println!("Indexed {markets} archived markets in {}", cfg.archive.dir);
+        return Ok(());
+    }
+
+    // `oracle analyze --sensitivity` — replay the stored decision history
+    // under the parameter grid and print bets added/removed per variant.
+    if std::env::args().nth(1).as_deref() == Some("analyze") {
+        anyhow::ensure!(std::env::args().any(|a| a == "--sensitivity"), "usage: oracle analyze --sensitivity");
+        let store = MetricsStore::open(&cfg.dashboard.metrics_db).await?;
+        let rows: Vec<_> = store.decisions().try_collect().await?;
+        let bankroll = cfg.agent.initial_bankroll.to_f64().unwrap_or(0.0);
+        let report = sensitivity::analyze(&rows, &sensitivity_base(&cfg), bankroll);
+        println!("{} decisions over {} cycles; baseline {} bets, PnL {:+.2}", report.decisions, report.cycles, report.baseline.bets, report.baseline_pnl);
+        println!("{:<26} {:>5} {:>6} {:>8} {:>10} {:>10} {:>11}", "variant", "bets", "added", "removed", "committed", "pnl_delta", "unresolved");
+        for v in &report.variants {
+            println!(
+                "{:<26} {:>5} {:>6} {:>8} {:>10.2} {:>+10.2} {:>11}",
+                v.label, v.bets, v.bets_added.len(), v.bets_removed.len(), v.committed, v.pnl_delta, v.unresolved_excluded
+            );
+        }
+        println!("Note: {}", report.note);
+        return Ok(());
+    }
+
+    // `oracle report` — print realised performance of the settled trades
+    // by category and platform, with threshold suggestions.
+    if std::env::args().nth(1).as_deref() == Some("report") {
+        let backend = storage::backend::open(&cfg.storage).await?;
+        let trades = backend.query_trades(&TradeQuery { settled: Some(true), ..TradeQuery::default() }).await?;
+        let edge_config = StrategyParams::edge_config(&cfg);
+        let report = PerformanceReport::build(&trades, |category| edge_config.threshold_for(&category).to_f64());
+        print_performance_report(&report);
+        return Ok(());
+    }
+
+    // `oracle backtest [DIR]` — replay cycles recorded with
+    // ORACLE_RECORD_CYCLES=1 through the configured strategy and print the
+    // simulated results. No LLM or platform calls.
+    if std::env::args().nth(1).as_deref() == Some("backtest") {
+        let dir = std::env::args().nth(2).unwrap_or_else(|| replay::DEFAULT_RECORD_DIR.to_string());
+        let recording = Recording::load(std::path::Path::new(&dir))?;
+        let mut orchestrator = build_orchestrator(&cfg, MarketLinks::load(&cfg.risk.links.file)?);
+        let mut state = AgentState::new(cfg.agent.initial_bankroll);
+        state.mana_bankroll = cfg.platforms.manifold.mana_bankroll.unwrap_or_default();
+        let report = replay::run(&recording, &mut orchestrator, state, &cfg.agent.currency, &cfg.risk.cool_down);
+        print_backtest_report(&dir, &report);
+        return Ok(());
+    }
 
     // Print startup banner
     println!("{BANNER}");
@@ -68,7 +196,9 @@ async fn main() -> Result<()> {
 
     // -- Restore or create state -----------------------------------------
 
-    let mut state = match storage::load_state(None)? {
+    let backend = storage::backend::open(&cfg.storage).await?;
+    info!(backend = backend.name(), "Storage opened");
+    let mut state = match backend.load_state().await? {
         Some(mut s) => {
             // A restart is an explicit user action — if the bankroll is still
             // above the survival threshold, reset a persisted Died status so
@@ -97,6 +227,8 @@ async fn main() -> Result<()> {
         }
     };
     state.survival_threshold = cfg.agent.survival_threshold;
+    log_config_changes(&state, &effective_config);
+    state.effective_config = effective_config.values();
 
     // Seed Mana bankroll from config if this is a fresh state or an existing state
     // that predates the mana_bankroll field (backward compat: default is 0).
@@ -111,12 +243,89 @@ async fn main() -> Result<()> {
 
     // Shared state for the web dashboard (Arc so both the server and the
     // main loop can hold a reference).
-    let dashboard_state: AppState = Arc::new(DashboardState::new(state.clone()));
+    let metrics_store = match MetricsStore::open(&cfg.dashboard.metrics_db).await {
+        Ok(store) => Some(store),
+        // Running without history would hide the refusal; stop instead.
+        Err(e) if e.is::<NewerSchema>() => return Err(e),
+        Err(e) => {
+            warn!(error = %e, "Metrics history disabled — could not open database");
+            None
+        }
+    };
+    let archive = Archive::new(&cfg.archive.dir);
+    let market_links = MarketLinks::load(&cfg.risk.links.file)?;
+    if !market_links.is_empty() {
+        info!(sets = market_links.sets().len(), file = %cfg.risk.links.file, "Market links loaded");
+    }
+    let mut list_files = ListFiles::load(
+        ListPaths::new(&cfg.scanner.watchlist_file, &cfg.scanner.blocklist_file).with_tags(&cfg.scanner.tags_file),
+    )?;
+    if !list_files.lists().is_empty() {
+        info!(
+            watched = list_files.lists().watched().count(),
+            blocked_patterns = list_files.lists().patterns().count(),
+            tag_rules = list_files.lists().tag_rules().len(),
+            "Watchlist/blocklist/tags loaded"
+        );
+    }
+    for tag in cfg.risk.tags.keys() {
+        if !list_files.lists().tag_rules().tags().any(|t| t == tag) {
+            warn!(tag = %tag, file = %cfg.scanner.tags_file, "risk.tags caps a tag no rule assigns");
+        }
+    }
+    let api_token = cfg.dashboard.api_token_env.as_deref()
+        .and_then(|env| std::env::var(env).ok());
+    // The one agent state the dashboard and control endpoints share with
+    // the loop below, which commits its copy at each save point.
+    let shared_state = SharedState::new(state.clone()).persisted(Arc::clone(&backend));
+    let cycle_history = backend.recent_cycles(MAX_CYCLE_LOG).await.unwrap_or_else(|e| {
+        warn!(error = %e, "Failed to load cycle history, starting with an empty cycle log");
+        Vec::new()
+    });
+    let mut dashboard = DashboardState::new(shared_state.clone())
+        .with_cycle_history(&cycle_history)
+        .with_effective_config(effective_config)
+        .with_recent_errors(recent_errors)
+        .with_enrichment(enrichment_availability(&cfg))
+        .with_api_token(api_token)
+        .with_request_budget(cfg.dashboard.response_cache, cfg.dashboard.max_expensive_requests)
+        .with_archive(archive.clone())
+        .with_sensitivity_base(sensitivity_base(&cfg))
+        .with_links(market_links.clone())
+        .with_list_paths(list_files.paths().clone())
+        .with_storage(Arc::clone(&backend));
+    let decision_journal = cfg.journal.enabled.then(|| DecisionJournal::new(&cfg.journal));
+    if let Some(journal) = &decision_journal {
+        dashboard = dashboard.with_decision_journal(journal.clone());
+    }
+    if let Some(store) = &metrics_store {
+        dashboard = dashboard.with_metrics(store.clone());
+    }
+    let dashboard_state: AppState = Arc::new(dashboard);
+
+    // Every estimate, labelled with its market's outcome once known.
+    let mut calibration = match CalibrationStore::load(DEFAULT_CALIBRATION_FILE) {
+        Ok(store) => {
+            *dashboard_state.estimate_calibration.write().await = Some(store.report());
+            Some(store)
+        }
+        // Starting empty would overwrite the newer file on the first save.
+        Err(e) if e.is::<NewerSchema>() => return Err(e),
+        Err(e) => {
+            warn!(error = %e, "Estimate calibration disabled — could not load store");
+            None
+        }
+    };
 
     if cfg.dashboard.enabled {
         if let Err(e) = spawn_dashboard(Arc::clone(&dashboard_state), cfg.dashboard.port).await {
             tracing::warn!(error = %e, "Dashboard disabled — could not start");
         }
+        if cfg.dashboard.public_mode {
+            if let Err(e) = spawn_public_dashboard(Arc::clone(&dashboard_state), cfg.dashboard.public_port).await {
+                tracing::warn!(error = %e, "Public dashboard disabled — could not start");
+            }
+        }
     }
 
     // -- Initialise components -------------------------------------------
@@ -142,7 +351,7 @@ async fn main() -> Result<()> {
         match BetfairClient::new() {
             Ok(client) => {
                 info!("Betfair Exchange enabled");
-                Some(client)
+                Some(keep_alive(client, &cfg.platforms.betfair))
             }
             Err(e) => {
                 warn!(error = %e, "Betfair init failed (credentials missing?), continuing without");
@@ -158,6 +367,33 @@ async fn main() -> Result<()> {
         Some(bf) => MarketRouter::with_betfair_config(cfg.scanner.clone(), bf, manifold, metaculus),
         None => MarketRouter::with_config(cfg.scanner.clone(), manifold, metaculus),
     };
+    let router = match kalshi_client(&cfg) {
+        Some(kalshi) => router.with_kalshi(kalshi),
+        None => router,
+    };
+    // One client (one TWS connection per client id) for scanning and execution.
+    let forecastex = forecastex_client(&cfg);
+    let router = match forecastex.clone() {
+        Some(forecastex) => router.with_forecastex(forecastex),
+        None => router,
+    };
+    let router = router.with_scan_intervals(cfg.platforms.scan_intervals());
+    let router = if cfg.scanner.embeddings.enabled {
+        match EmbeddingMatcher::from_config(&cfg.scanner.embeddings, &cfg.llm) {
+            Ok(matcher) => {
+                info!(provider = %cfg.scanner.embeddings.provider, "Cross-referencing by embedding similarity");
+                router.with_embeddings(matcher)
+            }
+            Err(e) => {
+                warn!(error = %format!("{e:#}"), "Embeddings unavailable — cross-referencing by word overlap");
+                router
+            }
+        }
+    } else {
+        router
+    };
+    router.restore_hibernation(state.hibernation.clone());
+    router.set_lists(list_files.lists().clone());
 
     // Data enricher
     let fred_key = cfg.data_sources.fred_api_key_env.as_deref()
@@ -166,83 +402,229 @@ async fn main() -> Result<()> {
         .and_then(|env| std::env::var(env).ok());
     let sports_key = cfg.data_sources.api_sports_key_env.as_deref()
         .and_then(|env| std::env::var(env).ok());
-    let mut enricher = Enricher::with_config(cfg.enricher.clone(), fred_key, news_key, sports_key)?;
+    // Spending limits shared by enrichment and estimation
+    let cost_tracker = Arc::new(CostTracker::new(&cfg.costs));
+    if cost_tracker.is_enabled() {
+        cost_tracker.resume_at(&state, chrono::Utc::now());
+        info!(
+            max_llm_cost_per_cycle = %cfg.costs.max_llm_cost_per_cycle,
+            max_total_cost_per_day = %cfg.costs.max_total_cost_per_day,
+            spent_today = %cost_tracker.spent_today_at(chrono::Utc::now()),
+            "Cost budget enabled"
+        );
+    }
+    let mut enricher = Enricher::with_config(cfg.enricher.clone(), fred_key.clone(), news_key.clone(), sports_key.clone())?
+        .with_max_concurrent_requests(
+            cfg.data_sources.max_concurrent_requests.unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS),
+        )
+        .with_cost_tracker(Arc::clone(&cost_tracker));
+    if cfg.data_sources.news_requests_per_day.is_some() || cfg.data_sources.news_cost_per_call.is_some() {
+        let news = NewsProvider::new(news_key)?
+            .with_requests_per_day(cfg.data_sources.news_requests_per_day.unwrap_or(FREE_TIER_REQUESTS_PER_DAY))
+            .with_cost_per_call(cfg.data_sources.news_cost_per_call.unwrap_or_default());
+        enricher = enricher.with_news(news);
+    }
+    let sports_usage = cfg.data_sources.sports_usage_file.as_deref().unwrap_or("oracle_api_sports_usage.json");
+    let sports_requests = DailyCounter::load(
+        sports_usage,
+        cfg.data_sources.sports_requests_per_day.unwrap_or(SPORTS_FREE_TIER_REQUESTS_PER_DAY),
+    )?;
+    enricher = enricher.with_sports(ApiSportsProvider::new(sports_key)?.with_request_counter(sports_requests));
+    if !cfg.data_sources.fred_series.is_empty() {
+        enricher = enricher.with_economics(FredProvider::new(fred_key)?.with_series(cfg.data_sources.fred_series.clone()));
+    }
+    if cfg.enricher.manifold_flow_enabled {
+        // Public bets feed — no API key needed.
+        enricher = enricher.with_manifold_flow(ManifoldClient::new(None)?);
+        info!(top_n = cfg.enricher.manifold_flow_top_n, "Manifold flow enrichment enabled");
+    }
 
     // LLM estimator
-    let llm_api_key = std::env::var(&cfg.llm.api_key_env).unwrap_or_default();
+    let llm_api_key = std::env::var(cfg.llm.key_env()).unwrap_or_default();
 
     let llm: Box<dyn LlmEstimator> = if llm_api_key.is_empty() {
         warn!("No LLM API key configured — running in dry-run/scan-only mode");
         Box::new(AnthropicClient::new("dummy".into(), Some("dummy".to_string()), None)?)
     } else {
-        match cfg.llm.provider.as_str() {
-            "openrouter" => {
-                info!(
-                    model = %cfg.llm.model,
-                    fallback = ?cfg.llm.fallback_model,
-                    "Using OpenRouter LLM provider"
-                );
-                Box::new(OpenRouterClient::new(
-                    llm_api_key,
-                    Some(cfg.llm.model.clone()),
-                    cfg.llm.fallback_model.clone(),
-                    Some(cfg.llm.max_tokens),
-                    Some(cfg.llm.batch_size),
-                )?)
-            }
-            "anthropic" => {
-                info!(model = %cfg.llm.model, "Using Anthropic LLM provider");
-                Box::new(AnthropicClient::new(
-                    llm_api_key,
-                    Some(cfg.llm.model.clone()),
-                    Some(cfg.llm.max_tokens),
-                )?)
-            }
-            "openai" => {
-                info!(model = %cfg.llm.model, "Using OpenAI LLM provider");
-                Box::new(OpenAiClient::new(
-                    llm_api_key,
-                    Some(cfg.llm.model.clone()),
-                    Some(cfg.llm.max_tokens),
-                )?)
-            }
-            other => {
-                anyhow::bail!(
-                    "Unknown LLM provider '{}' in config.toml. \
-                     Valid values are: openrouter, anthropic, openai",
-                    other
-                );
+        build_estimator(&cfg.llm.provider, &cfg.llm.model, llm_api_key, &cfg.llm, &dashboard_state.prometheus)?
+    };
+
+    // Optional ensemble: the primary plus every member with a key.
+    let llm = match &cfg.llm.ensemble {
+        Some(ec) if llm.model_name() != "dummy" => {
+            let mut members = vec![llm];
+            for mc in &ec.members {
+                match std::env::var(&mc.api_key_env) {
+                    Ok(key) if !key.is_empty() => {
+                        members.push(build_estimator(&mc.provider, &mc.model, key, &cfg.llm, &dashboard_state.prometheus)?);
+                    }
+                    _ => warn!(env = %mc.api_key_env, model = %mc.model, "Ensemble member API key missing — leaving it out"),
+                }
+            }
+            if members.len() > 1 {
+                let ensemble = EnsembleEstimator::new(members, ec.disagreement_penalty);
+                info!(model = %ensemble.model_name(), "LLM ensemble enabled");
+                Box::new(ensemble)
+            } else {
+                members.remove(0)
             }
         }
+        _ => llm,
     };
 
+    // Optional failover provider: idle unless a primary batch fails.
+    let llm = match &cfg.llm.secondary {
+        Some(sc) if llm.model_name() != "dummy" => match std::env::var(&sc.api_key_env) {
+            Ok(key) if !key.is_empty() => {
+                let secondary = build_estimator(&sc.provider, &sc.model, key, &cfg.llm, &dashboard_state.prometheus)?;
+                info!(secondary_model = %sc.model, "LLM provider failover enabled");
+                Box::new(FailoverEstimator::new(llm, secondary, cfg.llm.batch_size as usize))
+            }
+            _ => {
+                warn!(env = %sc.api_key_env, "Secondary LLM configured but API key missing — failover disabled");
+                llm
+            }
+        },
+        _ => llm,
+    };
+
+    // Optional per-category models: one client per provider/model, shared by
+    // the categories routed to it; the rest go to the chain above.
+    let llm = if cfg.llm.category_models.is_empty() || llm.model_name() == "dummy" {
+        llm
+    } else {
+        let mut targets: std::collections::BTreeMap<(&str, &str, &str), Vec<oracle::types::MarketCategory>> = Default::default();
+        for (category, mc) in &cfg.llm.category_models {
+            targets.entry((&mc.provider, &mc.model, &mc.api_key_env)).or_default().push(category.parse()?);
+        }
+        let mut routing = RoutingEstimator::new(llm);
+        for ((provider, model, key_env), categories) in targets {
+            match std::env::var(key_env) {
+                Ok(key) if !key.is_empty() => {
+                    info!(model = %model, categories = ?categories, "LLM category routing enabled");
+                    let est = build_estimator(provider, model, key, &cfg.llm, &dashboard_state.prometheus)?;
+                    routing = routing.with_route(&categories, est);
+                }
+                _ => warn!(env = %key_env, model = %model, "Category model API key missing — its categories use the primary"),
+            }
+        }
+        Box::new(routing)
+    };
+
+    // Chaos mode (dev-only): fault injection around the estimator and venues.
+    let chaos = cfg.chaos.resolved();
+    #[cfg(feature = "chaos")]
+    let llm = oracle::chaos::wrap_estimator(llm, &chaos);
+    if chaos.enabled {
+        if cfg!(feature = "chaos") {
+            warn!(scenario = ?chaos.scenario, seed = chaos.seed, "CHAOS MODE — injecting faults");
+        } else {
+            warn!("[chaos] enabled but this build lacks the `chaos` feature — ignoring");
+        }
+    }
+
+    // Reused estimates of markets that have barely moved since.
+    let mut estimate_cache = if cfg.llm.cache.enabled {
+        match EstimateCache::load(cfg.llm.cache.clone()) {
+            Ok(cache) => {
+                info!(entries = cache.len(), file = %cfg.llm.cache.file, "Estimate cache loaded");
+                Some(cache)
+            }
+            // Starting empty would overwrite the newer file on the first save.
+            Err(e) if e.is::<NewerSchema>() => return Err(e),
+            Err(e) => {
+                warn!(error = %e, "Estimate cache disabled — could not load it");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // A failed cycle's paid-for stages, resumed by the next one.
+    let mut recovery = CycleRecovery::load(cfg.recovery.clone());
+
+    // Optional shadow-model canary: double-estimates a sample of markets,
+    // records the comparison, never bets on the shadow estimates.
+    let mut shadow = match &cfg.llm.shadow {
+        Some(sc) if llm.model_name() != "dummy" => {
+            let provider = sc.provider.as_deref().unwrap_or(&cfg.llm.provider);
+            let key_env = sc.api_key_env.as_deref().unwrap_or(cfg.llm.key_env());
+            match std::env::var(key_env) {
+                Ok(key) if !key.is_empty() => match ShadowStore::load(DEFAULT_SHADOW_FILE) {
+                    Ok(store) => {
+                        let est = build_estimator(provider, &sc.model, key, &cfg.llm, &dashboard_state.prometheus)?;
+                        info!(
+                            shadow_model = %sc.model,
+                            sample_pct = sc.sample_pct,
+                            max_per_cycle = sc.max_per_cycle,
+                            samples = store.len(),
+                            "Shadow model canary enabled"
+                        );
+                        Some(ShadowRunner::new(est, llm.model_name(), sc.sample_pct, sc.max_per_cycle).with_store(store))
+                    }
+                    // Starting empty would overwrite the newer file on the first save.
+                    Err(e) if e.is::<NewerSchema>() => return Err(e),
+                    Err(e) => {
+                        warn!(error = %e, "Shadow model canary disabled — could not load its samples");
+                        None
+                    }
+                },
+                _ => {
+                    warn!(env = %key_env, "Shadow model configured but API key missing — canary disabled");
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let self_critique = cfg.llm.self_critique.enabled.then_some(&cfg.llm.self_critique);
+    if let Some(sc) = self_critique {
+        info!(
+            min_bet_pct = %sc.min_bet_pct,
+            max_per_cycle = sc.max_per_cycle,
+            "Self-critique enabled for high-stake estimates"
+        );
+    }
+    let tiers = cfg.llm.tiers.enabled.then_some(&cfg.llm.tiers);
+    if let Some(t) = tiers {
+        info!(
+            a_tokens = t.a.max_tokens,
+            b_tokens = t.b.max_tokens,
+            c_tokens = t.c.max_tokens,
+            "Estimation budget tiers enabled"
+        );
+    }
+    let cycle_budget = (cfg.llm.max_cycle_cost > Decimal::ZERO && llm.model_name() != "dummy").then(|| CycleBudget {
+        max_cost: cfg.llm.max_cycle_cost,
+        llm_share: llm.cost_per_call() / Decimal::from(cfg.llm.batch_size.max(1)),
+    });
+    if let Some(b) = cycle_budget {
+        info!(max_cost = %b.max_cost, llm_share = %b.llm_share, "Cycle cost guard enabled");
+    }
+    // Set when a day closes; the model comparison is reported once a day.
+    let mut daily_report_due = false;
+    // Cumulative realised P&L at the end of the previous cycle, for the
+    // per-cycle deltas in the metrics history.
+    let mut last_realized = (state.total_pnl, state.total_mana_pnl);
+    let mut diagnostics = Diagnostics::new(cfg.diagnostics.clone());
+
     // Store active model name and trading mode in dashboard for display
     *dashboard_state.active_model.write().await = llm.model_name().to_string();
     *dashboard_state.trading_mode.write().await = cfg.agent.trading_mode.clone();
 
     // Strategy orchestrator (edge detection → Kelly sizing → risk approval)
-    let dec_006 = rust_decimal_macros::dec!(0.06);
-    let dec_008 = rust_decimal_macros::dec!(0.08);
-    let dec_010 = rust_decimal_macros::dec!(0.10);
-    let dec_012 = rust_decimal_macros::dec!(0.12);
-    let mut orchestrator = StrategyOrchestrator::new(
-        EdgeDetector::new(EdgeConfig {
-            weather_threshold: *cfg.risk.category_thresholds.get("weather").unwrap_or(&dec_006),
-            sports_threshold: *cfg.risk.category_thresholds.get("sports").unwrap_or(&dec_008),
-            economics_threshold: *cfg.risk.category_thresholds.get("economics").unwrap_or(&dec_010),
-            politics_threshold: *cfg.risk.category_thresholds.get("politics").unwrap_or(&dec_012),
-            ..EdgeConfig::default()
-        }),
-        KellyCalculator::new(KellyConfig {
-            multiplier: cfg.risk.kelly_multiplier,
-            max_bet_pct: cfg.risk.max_bet_pct,
-            ..KellyConfig::default()
-        }),
-        RiskManager::new(RiskConfig {
-            max_exposure_pct: cfg.risk.max_exposure_pct,
-            ..RiskConfig::default()
-        }),
-    );
+    let mut edge_config = StrategyParams::edge_config(&cfg);
+    let mut orchestrator = build_orchestrator(&cfg, market_links);
+    let mut config_watcher = config::ConfigWatcher::new("config.toml", cfg.clone());
+
+    let adaptive_cfg = &cfg.risk.adaptive_thresholds;
+    let cool_down_cfg = &cfg.risk.cool_down;
+    let references_cfg = &cfg.strategy.references;
+    if adaptive_cfg.enabled {
+        orchestrator.set_category_thresholds(&state.thresholds.current);
+    }
+    *dashboard_state.edge_thresholds.write().await = threshold_views(&state, &edge_config, adaptive_cfg);
 
     // Executor — create platform clients based on trading_mode
     let (executor_manifold, executor_betfair, dry_run) =
@@ -259,7 +641,7 @@ async fn main() -> Result<()> {
             }
             "live" => {
                 info!("Trading mode: LIVE (Betfair real-money)");
-                let betfair = BetfairClient::new().ok();
+                let betfair = BetfairClient::new().ok().map(|c| keep_alive(c, &cfg.platforms.betfair));
                 if betfair.is_none() {
                     warn!("Betfair client unavailable — credentials may be missing");
                 }
@@ -270,7 +652,55 @@ async fn main() -> Result<()> {
                 (None, None, true)
             }
         };
-    let executor = Executor::with_betfair(executor_manifold, executor_betfair, dry_run);
+    let mut executor = Executor::with_betfair(executor_manifold, executor_betfair, dry_run)
+        .with_limits(cfg.execution.clone())
+        .with_max_price_impact(cfg.risk.max_price_impact_pct, cfg.risk.bet_constraints.clone())
+        .with_metrics(Arc::clone(&dashboard_state.prometheus));
+    if cfg.agent.trading_mode == "live" {
+        if let Some(kalshi) = kalshi_client(&cfg).filter(|k| k.can_trade()) {
+            info!("Kalshi registered as a live venue");
+            executor = executor.with_venue(Arc::new(kalshi));
+        }
+    }
+    if let Some(forecastex) = forecastex.filter(|f| f.is_executable()) {
+        if forecastex.is_paper() {
+            info!("ForecastEx registered as a paper venue");
+            executor = executor.with_venue(Arc::new(forecastex));
+        } else if cfg.agent.trading_mode == "live" {
+            info!("ForecastEx registered as a live venue");
+            executor = executor.with_venue(Arc::new(forecastex));
+        }
+    }
+    let budgets = cfg.platforms.request_budgets();
+    let executor = executor.map_venues(|v| oracle::platforms::rate_limit::wrap_platform(v, &budgets));
+    #[cfg(feature = "chaos")]
+    let executor = executor.map_venues(|v| oracle::chaos::wrap_platform(v, &chaos));
+    orchestrator.set_venue_selector(VenueSelector::new(cfg.strategy.venues.clone(), executor.venue_names()));
+    if cfg.strategy.arbitrage.enabled {
+        orchestrator.set_arbitrage_detector(ArbitrageDetector::new(
+            cfg.strategy.arbitrage.clone(),
+            cfg.strategy.venues.fees.clone(),
+            executor.venue_names(),
+        ));
+        info!(min_margin = %cfg.strategy.arbitrage.min_margin, "Cross-venue arbitrage enabled");
+    }
+    standby::restore(&state, &executor);
+    if state.status == AgentStatus::Paused {
+        info!(reason = ?state.pause_reason, "Restored PAUSED — no cycles until resumed");
+    }
+    #[cfg(unix)]
+    spawn_pause_signal(Arc::clone(&dashboard_state));
+
+    // Venue preflight: a venue that can't take orders stays scan-only.
+    let alert_webhook = Webhook::from_config(&cfg.alerts);
+    if cfg.execution.preflight {
+        run_preflight(&executor, &dashboard_state, alert_webhook.as_ref()).await;
+    }
+
+    // Event notifications (bets, drawdown tiers, death, daily summary, budget)
+    let notifications = Notifications::from_config(&cfg.notifications);
+    let mut drawdown_tiers = DrawdownTiers::new(state.drawdown());
+    let mut daily_summary = DailySchedule::new(cfg.notifications.daily_summary_hour, chrono::Utc::now());
 
     // Auto-exit engine — create fresh clients (executor took ownership of the first set)
     let auto_exit_config = AutoExitConfig {
@@ -280,6 +710,7 @@ async fn main() -> Result<()> {
         max_hold_hours: cfg.strategy.max_hold_hours,
         min_close_stake: cfg.strategy.min_close_stake,
         dry_run: cfg.strategy.auto_exit_dry_run || dry_run,
+        unwind_windows: cfg.strategy.unwind_windows.clone(),
     };
     let ae_manifold = if cfg.agent.trading_mode == "paper" {
         let api_key = cfg.platforms.manifold.api_key_env.as_deref()
@@ -289,12 +720,33 @@ async fn main() -> Result<()> {
         None
     };
     let ae_betfair = if cfg.agent.trading_mode == "live" {
-        BetfairClient::new().ok()
+        BetfairClient::new().ok().map(|c| keep_alive(c, &cfg.platforms.betfair))
     } else {
         None
     };
     let auto_exit_engine = AutoExitEngine::new(ae_manifold, ae_betfair, auto_exit_config);
 
+    // Shared-account reconciliation (Manifold only).
+    let reconciled = cfg.platforms.manifold.accounting == config::AccountingMode::Reconciled
+        && cfg.agent.trading_mode == "paper";
+    let match_window = chrono::Duration::seconds(cfg.platforms.manifold.match_window_secs);
+    if reconciled {
+        info!("Manifold accounting: RECONCILED (account may be traded outside the agent)");
+    }
+
+    // The last session abandoned a cycle part-way: orders may have been
+    // placed that the state never recorded.
+    if let Some(at) = state.dirty_shutdown {
+        warn!(since = %at, "Dirty shutdown marker found — repairing state from venue positions and balances");
+        let positions: Vec<_> = executor.fetch_positions().await
+            .into_iter()
+            .filter(|p| !(reconciled && p.platform == "manifold"))
+            .collect();
+        let repair = shutdown::repair(&mut state, &positions, executor.fetch_balances().await, chrono::Utc::now());
+        info!(confirmed = repair.confirmed, dropped = repair.dropped, adopted = repair.adopted, "State repaired after dirty shutdown");
+        shared_state.commit(&mut state).await?;
+    }
+
     // -- Main loop -------------------------------------------------------
 
     let scan_interval = Duration::from_secs(cfg.agent.scan_interval_secs);
@@ -313,8 +765,8 @@ async fn main() -> Result<()> {
     let mut interval = tokio::time::interval_at(next_tick, scan_interval);
     interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
-    let shutdown = tokio::signal::ctrl_c();
-    tokio::pin!(shutdown);
+    let shutdown = Shutdown::on_ctrl_c();
+    let shutdown_grace = Duration::from_secs(cfg.agent.shutdown_timeout_secs);
 
     if initial_delay.is_zero() {
         info!(
@@ -329,38 +781,165 @@ async fn main() -> Result<()> {
         );
     }
 
+    // Events recorded with the next completed cycle.
+    let mut cycle_events = Vec::new();
+
+    let cycle_deps = CycleDeps {
+        router: &router,
+        llm: &*llm,
+        executor: &executor,
+        storage: &*backend,
+        dash: Some(&dashboard_state),
+        journal: decision_journal.as_ref(),
+        cost_budget: &cost_tracker,
+        auto_exit: Some(&auto_exit_engine),
+        shutdown: &shutdown,
+    };
+    let cycle_config = CycleConfig {
+        self_critique,
+        tiers,
+        cool_down: cool_down_cfg,
+        references: references_cfg,
+        cycle_budget,
+        batch_size: cfg.llm.batch_size as usize,
+        prompt_budget: prompt_budget(&cfg),
+        exits: &cfg.strategy.exits,
+    };
+
     loop {
         tokio::select! {
             _ = interval.tick() => {
-                if !state.is_alive() {
+                if state.status == AgentStatus::Died {
                     info!("Agent is dead. Shutting down.");
                     break;
                 }
 
-                // Check if any previously placed bets have resolved.
-                if !state.open_bets.is_empty() {
-                    let resolutions = executor.check_manifold_resolutions(&state.open_bets).await;
-                    if !resolutions.is_empty() {
-                        let mut resolved_ids = std::collections::HashSet::new();
-                        for r in &resolutions {
-                            // Manifold PnL is in Mana — update mana state only,
-                            // never the AUD bankroll or survival check.
-                            state.record_mana_resolution(r.pnl, r.won);
-                            info!(
-                                market_id = %r.market_id,
-                                pnl_mana = %r.pnl,
-                                won = r.won,
-                                mana_bankroll = %state.mana_bankroll,
-                                "Manifold bet resolved"
-                            );
-                            resolved_ids.insert(r.bet_id.clone());
+                // Operator standby / pause / resume request.
+                let mode_request = dashboard_state.mode_request.write().await.take();
+                match mode_request {
+                    Some(ModeRequest::Standby) if standby::enter(&mut state, &executor) => {
+                        if let Err(e) = shared_state.commit(&mut state).await {
+                            error!(error = %e, "Failed to save state after entering standby");
                         }
-                        state.open_bets.retain(|b| !resolved_ids.contains(&b.order_id));
-                        // Persist updated state after resolutions
-                        if let Err(e) = storage::save_state(&state, None) {
-                            error!(error = %e, "Failed to save state after resolution");
+                    }
+                    Some(ModeRequest::Pause) if pause::pause(&mut state, "Paused by operator") => {
+                        if let Err(e) = shared_state.commit(&mut state).await {
+                            error!(error = %e, "Failed to save state after pausing");
+                        }
+                    }
+                    Some(request @ (ModeRequest::Resume | ModeRequest::ForceResume)) => {
+                        let recovered = orchestrator.risk().drawdown_recovered(&state);
+                        let force = request == ModeRequest::ForceResume;
+                        if let Some(resumed) = pause::resume(&mut state, recovered, force) {
+                            if resumed {
+                                if let Err(e) = shared_state.commit(&mut state).await {
+                                    error!(error = %e, "Failed to save state after resume");
+                                }
+                            }
+                        } else if let Some(check) = standby::resume(&mut state, &executor).await {
+                            *dashboard_state.last_resume_check.write().await = Some(check);
+                            if let Err(e) = shared_state.commit(&mut state).await {
+                                error!(error = %e, "Failed to save state after resume");
+                            }
                         }
                     }
+                    _ => {}
+                }
+
+                // The drawdown halt pauses the agent rather than leaving the
+                // risk manager to reject every bet.
+                if state.is_alive() && orchestrator.risk().drawdown_halted(&state) {
+                    let reason = format!("Drawdown halt: {:.1}% from peak", state.drawdown() * rust_decimal_macros::dec!(100));
+                    pause::pause(&mut state, &reason);
+                    if let Some(webhook) = &alert_webhook {
+                        webhook.send(&format!("ORACLE: paused — {reason}")).await;
+                    }
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state after drawdown pause");
+                    }
+                }
+                let in_standby = state.is_standby();
+                if in_standby {
+                    info!("STANDBY — scanning and estimating; execution and ledger updates disabled");
+                }
+
+                // Receipts as of the start of the tick — resolutions and
+                // auto-exits below remove bets whose fills are still to be
+                // attributed in reconciled accounting.
+                let tick_receipts = if reconciled { state.open_bets.clone() } else { Vec::new() };
+
+                // Operator requests to wake hibernating platforms.
+                let wakes = std::mem::take(&mut *dashboard_state.wake_requests.write().await);
+                for platform in wakes {
+                    if !router.wake(&platform) {
+                        info!(platform = %platform, "Wake requested for a platform that is not hibernating");
+                    }
+                }
+                state.hibernation = router.hibernation();
+
+                // Watchlist / blocklist / tag rule edits, by hand or through the API.
+                if let Some(diff) = list_files.reload_if_changed() {
+                    router.set_lists(list_files.lists().clone());
+                    cycle_events.push(CycleEvent::WatchlistChanged(diff));
+                }
+
+                // Strategy limits edited in config.toml, applied between cycles.
+                if let Some(reloaded) = config_watcher.reload_if_changed() {
+                    edge_config = StrategyParams::edge_config(reloaded);
+                    orchestrator.update_config(StrategyParams::from_config(reloaded, orchestrator.links().clone()));
+                    if adaptive_cfg.enabled {
+                        orchestrator.set_category_thresholds(&state.thresholds.current);
+                    }
+                    *dashboard_state.edge_thresholds.write().await =
+                        threshold_views(&state, &edge_config, adaptive_cfg);
+                    let period = Duration::from_secs(reloaded.agent.scan_interval_secs);
+                    if period != interval.period() {
+                        info!(interval_secs = period.as_secs(), "Scan interval changed");
+                        interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                    }
+                }
+
+                // Close yesterday's books before this tick's resolutions, so
+                // late ones for markets that closed yesterday are booked there.
+                if let Some(days) = daily::close_day(&mut state, chrono::Utc::now()) {
+                    for day in &days {
+                        info!(
+                            date = %day.date,
+                            since = %day.since,
+                            currency = %day.currency,
+                            realized = %day.realized.round_dp(2),
+                            unrealized = %day.unrealized.round_dp(2),
+                            costs = %day.costs.round_dp(2),
+                            pnl = %day.pnl.round_dp(2),
+                            "Daily P&L"
+                        );
+                    }
+                    daily_report_due = true;
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state after end-of-day snapshot");
+                    }
+                }
+
+                // Check if any previously placed bets have resolved.
+                if !state.open_bets.is_empty() && !in_standby {
+                    let bets = state.open_bets.clone();
+                    process_resolutions(&executor, &mut state, &bets, shadow.as_mut(), calibration.as_mut(), metrics_store.as_ref(), &*backend, &archive, cool_down_cfg).await;
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state after resolution");
+                    }
+                }
+
+                // Outcomes of estimated markets we passed on.
+                if let Some(store) = calibration.as_mut() {
+                    if !in_standby {
+                        resolve_due_estimates(&executor, store).await;
+                    }
+                    *dashboard_state.estimate_calibration.write().await = Some(store.report());
+                }
+                // Outcomes of shadow-sampled markets, bet on or not.
+                if let Some(sr) = shadow.as_mut().filter(|_| !in_standby) {
+                    resolve_due_shadow(&executor, sr).await;
                 }
 
                 // Reconcile mana_bankroll and total_mana_pnl against the actual Manifold
@@ -373,13 +952,24 @@ async fn main() -> Result<()> {
                 //                  P&L; NOT affected by bet placements moving money from
                 //                  liquid to invested — those are NOT losses).
                 //
-                // mana_gross_equity → liquid balance + current value of open positions.
-                //                     This is the true bankroll for Kelly sizing.
-                let mut mana_gross_equity: Option<Decimal> = None;
-                if cfg.agent.trading_mode == "paper" {
+                // balances["manifold"] → liquid balance only, as fetched after every cycle:
+                //                        Mana invested in open positions can't fund new bets.
+                if cfg.agent.trading_mode == "paper" && !in_standby {
                     if let Some(info) = executor.get_mana_info().await {
+                        if reconciled {
+                            // Shared account: split the platform's books into our
+                            // activity and everyone else's instead of adopting its
+                            // all-time profit as ours.
+                            reconcile_manifold_history(&executor, &mut state, &info.user_id, &tick_receipts, match_window).await;
+                            reconcile::split_profit(&mut state.external_activity, info.resolved_profit, state.total_mana_pnl);
+                        }
                         let bal_drift = info.liquid_balance  - state.mana_bankroll;
-                        let pnl_drift = info.resolved_profit - state.total_mana_pnl;
+                        // Reconciled: the profit gap is external P&L, not drift.
+                        let pnl_drift = if reconciled {
+                            Decimal::ZERO
+                        } else {
+                            info.resolved_profit - state.total_mana_pnl
+                        };
                         if bal_drift.abs() > rust_decimal_macros::dec!(0.5)
                             || pnl_drift.abs() > rust_decimal_macros::dec!(0.5)
                         {
@@ -393,44 +983,136 @@ async fn main() -> Result<()> {
                                 "Reconciling Mana state with Manifold API"
                             );
                             state.mana_bankroll  = info.liquid_balance;
-                            state.total_mana_pnl = info.resolved_profit;
+                            if !reconciled {
+                                state.total_mana_pnl = info.resolved_profit;
+                            }
                         }
-                        // Always capture gross equity for this cycle's Kelly sizing,
-                        // even when drift is below the log threshold.
-                        mana_gross_equity = Some(info.liquid_balance + info.investment_value);
+                        // Always refresh the balance for this cycle's sizing and risk
+                        // checks, even when drift is below the log threshold.
+                        Accountant::record_manifold_account(&mut state, &info);
                     }
                 }
 
                 // Auto-exit: check open positions for take-profit / stop-loss / time limits.
-                if !state.open_bets.is_empty() {
+                if !state.open_bets.is_empty() && !in_standby {
                     let close_results = auto_exit_engine.check_and_close(&state.open_bets).await;
                     if !close_results.is_empty() {
                         process_auto_exits(
                             &close_results,
                             &mut state,
-                            &dashboard_state,
+                            Some(&dashboard_state),
                         ).await;
-                        if let Err(e) = storage::save_state(&state, None) {
+                        if let Err(e) = shared_state.commit(&mut state).await {
                             error!(error = %e, "Failed to save state after auto-exit");
                         }
                     }
                 }
 
-                // Use gross equity (liquid balance + open position value) for Kelly sizing
-                // so bet sizes reflect the true bankroll, not just available cash.
-                // Falls back to state.mana_bankroll (liquid only) when the API is unavailable.
-                let mana_for_sizing = mana_gross_equity
-                    .or_else(|| if state.mana_bankroll > Decimal::ZERO { Some(state.mana_bankroll) } else { None });
-                match run_cycle(
-                    &router, &mut enricher, &*llm, &mut orchestrator,
-                    &executor, &mut state, Some(&dashboard_state), mana_for_sizing,
-                ).await {
+                // Adaptive edge thresholds: periodic review of realised edge performance.
+                let adjustments = adaptive::review(&mut state, &edge_config, adaptive_cfg, chrono::Utc::now());
+                if !adjustments.is_empty() {
+                    orchestrator.set_category_thresholds(&state.thresholds.current);
+                    *dashboard_state.edge_thresholds.write().await =
+                        threshold_views(&state, &edge_config, adaptive_cfg);
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state after threshold review");
+                    }
+                }
+
+                // Cross-reference weights: periodic re-derivation from reference accuracy.
+                if references::review(&mut state, references_cfg, chrono::Utc::now()) > 0 {
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state after reference weight review");
+                    }
+                }
+
+                // Category cool-downs that have run their time lift before selection.
+                if !cooldown::lift_expired(&mut state, cool_down_cfg, chrono::Utc::now()).is_empty() {
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state after cool-down lift");
+                    }
+                }
+
+                // Paused: the books above are kept, but no cycle runs.
+                if !pause::should_trade(&state) {
+                    info!(reason = ?state.pause_reason, "PAUSED — skipping cycle");
+                    if let Err(e) = shared_state.commit(&mut state).await {
+                        error!(error = %e, "Failed to save state");
+                    }
+                    continue;
+                }
+
+                // Live venues are re-checked every cycle: sessions expire and
+                // balances drain between startups.
+                if cfg.execution.preflight && cfg.agent.trading_mode == "live" && !in_standby {
+                    run_preflight(&executor, &dashboard_state, alert_webhook.as_ref()).await;
+                }
+
+                let cycle_span = info_span!("cycle", cycle = (state.cycle_count + 1) as i64);
+                let deps = CycleDeps { auto_exit: cycle_deps.auto_exit.filter(|_| !in_standby), ..cycle_deps };
+                let parts = CycleParts {
+                    enricher: &mut enricher,
+                    orchestrator: &mut orchestrator,
+                    shadow: shadow.as_mut(),
+                    calibration: calibration.as_mut(),
+                    estimate_cache: estimate_cache.as_mut(),
+                };
+                let cycle = run_cycle(deps, cycle_config, parts, &mut state, &mut recovery).instrument(cycle_span);
+                // A cycle in flight at Ctrl+C finishes, within the timeout.
+                let Some(outcome) = shutdown::finish_within(&shutdown, shutdown_grace, cycle).await else {
+                    shutdown::mark_dirty(&mut state, chrono::Utc::now());
+                    break;
+                };
+                match outcome {
                     Ok(report) => {
                         log_cycle_report(&report);
-                        update_dashboard(&dashboard_state, &state, &report).await;
-                        *dashboard_state.progress.write().await = EvaluationProgress::Idle;
+                        notify_cycle(&notifications, &report, &state, &mut drawdown_tiers, &cost_tracker);
+
+                        if let Some(store) = &metrics_store {
+                            let metrics = Accountant::cycle_metrics(
+                                &report,
+                                &state,
+                                state.total_pnl - last_realized.0,
+                                state.total_mana_pnl - last_realized.1,
+                            );
+                            if let Err(e) = store.record_cycle(&metrics).await {
+                                warn!(error = %e, "Failed to record cycle metrics");
+                            }
+                            if let Err(e) = store.record_decisions(&report.decisions).await {
+                                warn!(error = %e, "Failed to record cycle decisions");
+                            }
+                            archive_expired(store, &archive, &state, cfg.archive.expire_after_days).await;
+                        }
+                        last_realized = (state.total_pnl, state.total_mana_pnl);
+
+                        // A market we already hold closed mid-cycle — poll its
+                        // resolution now rather than waiting for the next tick.
+                        let held_closed: Vec<_> = state.open_bets.iter()
+                            .filter(|b| report.closed_markets.contains(&b.market_id))
+                            .cloned()
+                            .collect();
+                        if !held_closed.is_empty() && !in_standby {
+                            info!(count = held_closed.len(), "Held market closed — polling resolution early");
+                            process_resolutions(&executor, &mut state, &held_closed, shadow.as_mut(), calibration.as_mut(), metrics_store.as_ref(), &*backend, &archive, cool_down_cfg).await;
+                        }
+
+                        update_dashboard(&dashboard_state, &state, &report, std::mem::take(&mut cycle_events)).await;
+                        if let Err(e) = backend.append_cycle(&report.summary(state.mana_bankroll)).await {
+                            warn!(error = %e, "Failed to append cycle history");
+                        }
+                        if let Err(e) = backend.append_balance(&BalanceSample::of(&state, report.timestamp)).await {
+                            warn!(error = %e, "Failed to append balance history");
+                        }
+                        if let Some(sr) = &shadow {
+                            let comparison = sr.report();
+                            if std::mem::take(&mut daily_report_due) {
+                                info!(report = %comparison.summary(), "Daily model comparison");
+                            }
+                            *dashboard_state.model_comparison.write().await = Some(comparison);
+                        }
+                        dashboard_state.set_progress(EvaluationProgress::Idle).await;
                         state.last_cycle_time = Some(chrono::Utc::now());
-                        if let Err(e) = storage::save_state(&state, None) {
+                        if let Err(e) = shared_state.commit(&mut state).await {
                             error!(error = %e, "Failed to save state");
                         }
                         if state.status == AgentStatus::Died {
@@ -440,25 +1122,43 @@ async fn main() -> Result<()> {
                     }
                     Err(e) => {
                         error!(error = %e, "Cycle failed — continuing to next");
-                        {
-                            let mut log = dashboard_state.error_log.write().await;
-                            log.push(ErrorLogEntry {
+                        dashboard_state
+                            .push_error(ErrorLogEntry {
                                 timestamp: chrono::Utc::now().to_rfc3339(),
                                 cycle_number: state.cycle_count + 1,
                                 error: e.to_string(),
-                            });
-                            if log.len() > 50 {
-                                let excess = log.len() - 50;
-                                log.drain(0..excess);
-                            }
-                        }
-                        *dashboard_state.progress.write().await = EvaluationProgress::Idle;
+                            })
+                            .await;
+                        dashboard_state.set_progress(EvaluationProgress::Idle).await;
                         state.cycle_count += 1;
                         state.last_cycle_time = Some(chrono::Utc::now());
                     }
                 }
+
+                let now = chrono::Utc::now();
+                if notifications.is_enabled() && daily_summary.due(now) {
+                    notifications.send(Event::DailySummary {
+                        date: now.date_naive(),
+                        bankroll: state.bankroll,
+                        total_pnl: state.total_pnl,
+                        open_bets: state.open_bets.len(),
+                        trades_placed: state.trades_placed,
+                        trades_won: state.trades_won,
+                        trades_lost: state.trades_lost,
+                        spent_today: cost_tracker.spent_today_at(now),
+                    });
+                }
+
+                if diagnostics.due(state.cycle_count) {
+                    let mut stores: Vec<&dyn SizedStore> = vec![&state, &enricher, &*dashboard_state];
+                    if let Some(sr) = &shadow {
+                        stores.push(sr);
+                    }
+                    let sample = diagnostics.sample(state.cycle_count, &stores);
+                    *dashboard_state.diagnostics.write().await = Some(sample);
+                }
             }
-            _ = &mut shutdown => {
+            _ = shutdown.requested() => {
                 info!("Shutdown signal received.");
                 break;
             }
@@ -466,7 +1166,13 @@ async fn main() -> Result<()> {
     }
 
     // Save final state
-    storage::save_state(&state, None)?;
+    shared_state.commit(&mut state).await?;
+    notifications.drain(std::time::Duration::from_secs(5)).await;
+    telemetry::shutdown().await;
+    if state.dirty_shutdown.is_some() {
+        warn!("ORACLE shut down mid-cycle — state will be repaired on next start.");
+        return Ok(());
+    }
     info!(
         bankroll = %format!("${}", state.bankroll.round_dp(2)),
         cycles = state.cycle_count,
@@ -478,103 +1184,950 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Services a cycle uses, built once in `main`.
+#[derive(Clone, Copy)]
+struct CycleDeps<'a> {
+    router: &'a MarketRouter,
+    llm: &'a dyn LlmEstimator,
+    executor: &'a Executor,
+    storage: &'a dyn Storage,
+    dash: Option<&'a AppState>,
+    journal: Option<&'a DecisionJournal>,
+    cost_budget: &'a CostTracker,
+    /// Unset in standby: positions are left alone.
+    auto_exit: Option<&'a AutoExitEngine>,
+    shutdown: &'a Shutdown,
+}
+
+/// Cycle settings from the config, fixed for the run.
+#[derive(Clone, Copy)]
+struct CycleConfig<'a> {
+    self_critique: Option<&'a SelfCritiqueConfig>,
+    tiers: Option<&'a TiersConfig>,
+    cool_down: &'a CoolDownConfig,
+    references: &'a ReferenceWeightsConfig,
+    cycle_budget: Option<CycleBudget>,
+    batch_size: usize,
+    prompt_budget: Option<PromptBudget>,
+    exits: &'a EdgeExitConfig,
+}
+
+/// Components a cycle updates, lent to it for its duration.
+struct CycleParts<'a> {
+    enricher: &'a mut Enricher,
+    orchestrator: &'a mut StrategyOrchestrator,
+    shadow: Option<&'a mut ShadowRunner>,
+    calibration: Option<&'a mut CalibrationStore>,
+    estimate_cache: Option<&'a mut EstimateCache>,
+}
+
 /// Run a single scan→enrich→estimate→edge→size→risk→execute cycle.
 async fn run_cycle(
-    router: &MarketRouter,
-    enricher: &mut Enricher,
-    llm: &dyn LlmEstimator,
-    orchestrator: &mut StrategyOrchestrator,
-    executor: &Executor,
+    deps: CycleDeps<'_>,
+    settings: CycleConfig<'_>,
+    parts: CycleParts<'_>,
     state: &mut AgentState,
-    dash: Option<&AppState>,
-    mana_bankroll: Option<Decimal>,
+    recovery: &mut CycleRecovery,
 ) -> Result<CycleReport> {
+    let CycleDeps { router, llm, executor, storage, dash, journal, cost_budget, auto_exit, shutdown } = deps;
+    let CycleConfig {
+        self_critique,
+        tiers: tier_cfg,
+        cool_down,
+        references: references_cfg,
+        cycle_budget,
+        batch_size,
+        prompt_budget,
+        exits,
+    } = settings;
+    let CycleParts { enricher, orchestrator, shadow, calibration, estimate_cache } = parts;
     info!(cycle = state.cycle_count + 1, "Starting cycle");
+    cost_budget.begin_cycle();
+
+    // 0. A failed cycle's bets left unplaced, or its enrichment, are
+    // picked up where it stopped.
+    let started_at = chrono::Utc::now();
+    if let Some(stage) = recovery.approved(started_at).cloned() {
+        return retry_unplaced(stage, router, executor, state, storage, dash, recovery).await;
+    }
+    let resumed = recovery.enriched(started_at).cloned();
 
     // 1. Scan markets
-    if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Scanning; }
-    let markets = router.scan_all().await?;
-    let markets_scanned = markets.len();
+    let (markets, markets_scanned) = match &resumed {
+        Some(stage) => {
+            info!(
+                markets = stage.enriched.len(),
+                enriched_at = %stage.at,
+                "Resuming the failed cycle's enrichment — scan and enrichment skipped"
+            );
+            (stage.enriched.iter().map(|(m, _)| m.clone()).collect(), stage.markets_scanned)
+        }
+        None => {
+            if let Some(d) = dash { d.set_progress(EvaluationProgress::Scanning).await; }
+            let scan_span = info_span!("scan", markets = field::Empty);
+            let (markets, scan_summary) = router.scan_all().instrument(scan_span.clone()).await?;
+            scan_span.record("markets", markets.len() as i64);
+            if let Some(d) = dash {
+                d.prometheus.record_scan(&scan_summary);
+                d.record_scan(scan_summary, chrono::Utc::now()).await;
+            }
+            state.hibernation = router.hibernation();
+            daily::observe_marks(&mut state.daily, &markets, &state.open_bets);
+            if let Some(d) = dash {
+                *d.link_suggestions.write().await = links::suggest(&markets, orchestrator.links());
+            }
+            orchestrator.set_clusters(router.event_clusters(&markets));
+            let scanned = markets.len();
+            (markets, scanned)
+        }
+    };
+    // Positions already held on the platforms count towards the risk caps,
+    // including any the state has lost track of
+    let mut positions = executor.fetch_positions().await;
+    if !state.unconfirmed_bets.is_empty() {
+        let (confirmed, dropped) = Accountant::settle_unconfirmed(state, &positions, chrono::Utc::now());
+        info!(confirmed, dropped, pending = state.unconfirmed_bets.len(), "Unconfirmed orders reconciled");
+    }
+    risk::categorise_positions(&mut positions, &markets, &state.open_bets);
+    orchestrator.set_open_positions(&positions);
+    // Value open bets at current prices ahead of the drawdown checks
+    Accountant::mark_to_market(state, &positions, &markets);
     info!(count = markets_scanned, "Markets scanned");
 
+    // Sync exposure counters to actual open positions before making new decisions.
+    // Without this, the risk manager's internal totals accumulate indefinitely
+    // (resolved/auto-exited positions are never subtracted), causing progressive
+    // rejection of new bets even when real exposure is well within limits.
+    orchestrator.sync_exposure_from_state(state);
+    orchestrator.reset_cycle();
+
+    // 1a. Cross-venue arbitrage — priced off the venues, so ahead of the
+    // estimates; its legs count against the risk caps of the bets after it.
+    // Resumed prices are too old to lock in a spread.
+    let (arbitrage_bets, arbitrage_decisions) = match resumed {
+        Some(_) => Default::default(),
+        None => orchestrator.select_arbitrage_at(state, chrono::Utc::now()),
+    };
+
     // Snapshot enricher cost before enrichment so we can compute the per-cycle delta.
     let data_cost_before = enricher.total_cost();
+    // A resumed enrichment was paid for by the failed cycle but not yet accounted.
+    let resumed_data_cost = resumed.as_ref().map_or(Decimal::ZERO, |s| s.data_cost);
 
     if markets.is_empty() {
         let costs = CycleCosts {
             data_cost: enricher.total_cost() - data_cost_before,
             ..Default::default()
         };
-        let exec = oracle::engine::executor::ExecutionReport {
+        let exec = ExecutionReport {
             executed: Vec::new(),
             failed: Vec::new(),
+            unconfirmed: Vec::new(),
             total_committed: Decimal::ZERO,
             total_commission: Decimal::ZERO,
+            duplicate_fills: 0,
         };
         let mut report = Accountant::reconcile(state, &exec, &costs);
         report.markets_scanned = markets_scanned;
         return Ok(report);
     }
 
+    // 1b. Cost guard: keep the markets worth most within the cycle budget.
+    // A resumed enrichment already went through it.
+    let markets = match cycle_budget.filter(|_| resumed.is_none()) {
+        Some(budget) => {
+            let candidates: Vec<_> = markets.iter()
+                .map(|m| {
+                    let (data_cost, warm) = enricher.marginal_cost(m);
+                    cost_guard::Candidate {
+                        priority: MarketRouter::priority_score(m),
+                        cost: data_cost + budget.llm_share,
+                        warm,
+                    }
+                })
+                .collect();
+            let selection = cost_guard::select(&candidates, budget.max_cost);
+            cost_guard::log_selection(&selection, markets.len(), budget.max_cost);
+            selection.kept.iter().map(|&i| markets[i].clone()).collect()
+        }
+        None => markets,
+    };
+
     // 2. Enrich with data
-    if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Enriching { markets_total: markets_scanned }; }
-    let enriched = enricher.enrich_batch(&markets).await?;
+    if let Some(d) = dash { d.set_progress(EvaluationProgress::Enriching { markets_total: markets_scanned }).await; }
+    let enrich_span = info_span!("enrich", markets = markets.len() as i64, cost = field::Empty);
+    // Stages that start new work are skipped once shutdown is requested;
+    // execution, reconciliation and the save below always run.
+    let mut enriched = match resumed {
+        Some(stage) => stage.enriched,
+        None if shutdown.skips("enrichment") => Vec::new(),
+        None => {
+            let enriched = enricher.enrich_batch(&markets).instrument(enrich_span.clone()).await?;
+            // Kept until the cycle completes, so a failed estimation
+            // doesn't cost the next cycle a scan and enrichment.
+            recovery.record_enriched(EnrichedStage {
+                at: chrono::Utc::now(),
+                markets_scanned,
+                enriched: enriched.clone(),
+                data_cost: enricher.total_cost() - data_cost_before,
+            });
+            save_recovery(recovery);
+            enriched
+        }
+    };
+    enrich_span.record("cost", field::display(enricher.total_cost() - data_cost_before));
     // data_cost_before was captured before the empty-markets early return above.
 
     // 3. LLM estimation
-    let estimates: Vec<_> = if llm.model_name() != "dummy" {
-        let market_contexts: Vec<_> = enriched.iter()
-            .map(|(m, c)| (m.clone(), c.clone()))
+    let mut shadow_cost = Decimal::ZERO;
+    let mut cache_hits = 0;
+    let mut cache_misses = 0;
+    let mut budget_skipped = 0;
+    let estimates: Vec<_> = if llm.model_name() != "dummy" && !shutdown.skips("estimation") {
+        // 3a. Reuse cached estimates of markets that have barely moved;
+        // only the rest go to the model.
+        let estimated_at = chrono::Utc::now();
+        let mut cached: Vec<_> = enriched.iter()
+            .map(|(m, _)| estimate_cache.as_deref().and_then(|c| c.lookup(m, estimated_at)))
             .collect();
-        if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Estimating { markets_total: markets_scanned, markets_done: 0 }; }
-        let ests = llm.batch_estimate(&market_contexts).await?;
-        if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Estimating { markets_total: markets_scanned, markets_done: markets_scanned }; }
+        let mut market_contexts: Vec<_> = enriched.iter()
+            .zip(&cached)
+            .filter(|(_, hit)| hit.is_none())
+            .map(|((m, c), _)| (m.clone(), c.clone()))
+            .collect();
+        cache_hits = enriched.len() - market_contexts.len();
+        cache_misses = market_contexts.len();
+        if cache_hits > 0 {
+            info!(hits = cache_hits, misses = cache_misses, "Reusing cached estimates");
+        }
+        // Batches of at most llm.batch_size markets, each fitted to the
+        // context window; markets that don't fit lead the next scan.
+        let budget::BatchPlan { markets: planned, batches, dropped } =
+            budget::plan(&market_contexts, batch_size, prompt_budget.as_ref());
+        let mut deferred: Vec<_> = dropped.iter().map(|&i| market_contexts[i].0.clone()).collect();
+        if !deferred.is_empty() {
+            info!(dropped = deferred.len(), "Batch prompts over the context window — markets deferred to next cycle");
+        }
+        market_contexts = planned;
+        if let Some(d) = dash { d.set_progress(EvaluationProgress::Estimating { markets_total: markets_scanned, markets_done: 0 }).await; }
+        let started = std::time::Instant::now();
+        let estimate_span = info_span!("estimate", markets = market_contexts.len() as i64, cost = field::Empty);
+        let assigned: Option<Vec<_>> = tier_cfg.map(|tc| {
+            market_contexts.iter()
+                .map(|(m, _)| {
+                    let stake = tiers::max_stake(m, state.bankroll_for(&m.platform), orchestrator.max_bet_pct(), tc);
+                    tiers::assign(MarketRouter::priority_score(m), stake, tc)
+                })
+                .collect()
+        });
+        // Batches go out while the cost budget lasts
+        let (contexts, assigned) = (&market_contexts, assigned.as_deref());
+        let budgeted = cost_tracker::estimate_within(
+            cost_budget, &batches, llm.cost_per_call(), estimated_at,
+            move |r: std::ops::Range<usize>| {
+                let batch = &contexts[r.clone()];
+                let batch_tiers = assigned.map(|a| &a[r]);
+                async move {
+                    match (tier_cfg, batch_tiers) {
+                        (Some(tc), Some(t)) => tiers::estimate(llm, tc, batch, t).await,
+                        _ => llm.batch_estimate(batch).await,
+                    }
+                }
+            },
+        )
+        .instrument(estimate_span.clone())
+        .await?;
+        let mut ests = budgeted.estimates;
+        // Markets the budget didn't reach are left out of this cycle and
+        // lead the next scan.
+        budget_skipped = budgeted.skipped;
+        let skipped = market_contexts.split_off(market_contexts.len() - budgeted.skipped);
+        deferred.extend(skipped.into_iter().map(|(m, _)| m));
+        router.defer(&deferred);
+        if !deferred.is_empty() {
+            let skip: std::collections::HashSet<(&str, &str)> =
+                deferred.iter().map(|m| (m.platform.as_str(), m.id.as_str())).collect();
+            (enriched, cached) = enriched.into_iter()
+                .zip(cached)
+                .filter(|((m, _), _)| !skip.contains(&(m.platform.as_str(), m.id.as_str())))
+                .unzip();
+            cache_misses = market_contexts.len();
+        }
+        estimate_span.record("cost", field::display(ests.iter().map(|e| e.cost).sum::<Decimal>()));
+        let primary_latency_ms = started.elapsed().as_secs_f64() * 1000.0 / market_contexts.len().max(1) as f64;
+        if let Some(d) = dash { d.set_progress(EvaluationProgress::Estimating { markets_total: markets_scanned, markets_done: markets_scanned }).await; }
+        // 3b. Shadow canary (recorded only — never feeds the strategy).
+        if let Some(sr) = shadow.filter(|_| !market_contexts.is_empty()) {
+            shadow_cost = sr.run(&market_contexts, &ests, primary_latency_ms).await;
+            cost_budget.record(CostKind::Llm, shadow_cost);
+            if let Err(e) = sr.save() {
+                warn!(error = %e, "Failed to save shadow samples");
+            }
+        }
+        // 3c. Self-critique of estimates that would size large bets. Its
+        // cost is folded into each critiqued estimate.
+        if let Some(sc) = self_critique {
+            let tentative: Vec<_> = market_contexts.iter()
+                .map(|(m, _)| m.clone())
+                .zip(ests.iter().cloned())
+                .collect();
+            let mut stakes = orchestrator.tentative_bet_fractions(&tentative, state);
+            // With tiering on, only tiers that budget a critique get one.
+            if let Some(tc) = tier_cfg {
+                stakes.retain(|&(i, _)| ests[i].tier.is_some_and(|t| tc.params(t).critique));
+            }
+            let summary = critique::run(llm, sc, &market_contexts, &mut ests, &stakes).await;
+            cost_budget.record(CostKind::Llm, summary.cost);
+            if summary.calls > 0 {
+                info!(
+                    calls = summary.calls,
+                    revised = summary.revised,
+                    failed = summary.failed,
+                    cost = %format!("${:.4}", summary.cost),
+                    "Self-critique pass complete"
+                );
+            }
+        }
+        if let Some(cache) = estimate_cache {
+            for ((m, _), e) in market_contexts.iter().zip(&ests) {
+                cache.insert(m, e, estimated_at);
+            }
+            if let Err(e) = cache.save(estimated_at) {
+                warn!(error = %e, "Failed to save estimate cache");
+            }
+        }
+        let mut fresh = ests.into_iter();
+        let mut ests: Vec<_> = cached.into_iter().filter_map(|hit| hit.or_else(|| fresh.next())).collect();
+        // 3d. Pull estimates towards cross-reference prices by their
+        // learned trust weights.
+        let mut blended = 0;
+        for ((m, _), e) in enriched.iter().zip(ests.iter_mut()) {
+            blended += usize::from(references::blend(e, m, state, references_cfg));
+        }
+        if blended > 0 {
+            info!(blended, "Estimates blended with cross-references");
+        }
         enriched.iter().zip(ests).map(|((m, _), e)| (m.clone(), e)).collect()
     } else {
-        Vec::new() // No LLM key — skip estimation
+        Vec::new() // No LLM key, or shutting down — skip estimation
     };
 
     // 4-5. Edge detection → Kelly sizing → risk approval (via orchestrator)
-    if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Selecting { markets_total: markets_scanned }; }
-    // Sync exposure counters to actual open positions before making new decisions.
-    // Without this, the risk manager's internal totals accumulate indefinitely
-    // (resolved/auto-exited positions are never subtracted), causing progressive
-    // rejection of new bets even when real exposure is well within limits.
-    orchestrator.sync_exposure_from_state(state);
-    orchestrator.reset_cycle();
-    let (approved_bets, decisions) = orchestrator.select_bets(&estimates, state, mana_bankroll);
+    if let Some(d) = dash { d.set_progress(EvaluationProgress::Selecting { markets_total: markets_scanned }).await; }
+    let decided_at = chrono::Utc::now();
+    if replay::recording_enabled() {
+        let root = std::path::Path::new(replay::DEFAULT_RECORD_DIR);
+        if let Err(e) = replay::record_cycle(root, state.cycle_count + 1, decided_at, &enriched, &estimates) {
+            warn!(error = %e, "Failed to record cycle for replay");
+        }
+    }
+    let strategy_span = info_span!("strategy", edges = field::Empty, approved = field::Empty);
+    let entered = strategy_span.enter();
+    let (approved_bets, decisions) = orchestrator.select_bets_at(&estimates, state, decided_at);
+    let approved_bets: Vec<_> = arbitrage_bets.into_iter().chain(approved_bets).collect();
+    let mut decisions: Vec<_> = arbitrage_decisions.into_iter().chain(decisions).collect();
+    strategy_span.record("edges", decisions.len() as i64);
+    strategy_span.record("approved", approved_bets.len() as i64);
+    drop(entered);
     // decisions contains KellyRejected + RiskRejected + Selected — all edges
-    // above threshold — so its length equals the raw edge count.
+    // above threshold — plus one Arbitrage per pair found, so its length
+    // equals the raw edge count.
     let edges_found = decisions.len();
 
+    // 5b. Exit review: close held positions whose edge this cycle's
+    // estimates show spent or reversed. Reviews are journaled with the rest.
+    if let Some(engine) = auto_exit {
+        let reviews = exit::review_positions(&state.open_bets, &estimates, exits);
+        let mut closed = Vec::new();
+        for review in &reviews {
+            if let DecisionRecord::Exit { review, trigger } = review {
+                closed.extend(engine.close_on_exit(&review.position, *trigger).await);
+            }
+        }
+        process_auto_exits(&closed, state, dash).await;
+        decisions.extend(reviews);
+    }
+    cooldown::record_skips(state, &decisions, cool_down);
+    if let Some(d) = dash { d.prometheus.record_decisions(&decisions); }
+    if let Some(journal) = journal {
+        if let Err(e) = journal.append(state.cycle_count + 1, decided_at, &decisions) {
+            warn!(error = %e, "Failed to journal cycle decisions");
+        }
+    }
+    if let Err(e) = storage.append_decisions(state.cycle_count + 1, decided_at, &decisions).await {
+        warn!(error = %e, "Failed to store cycle decisions");
+    }
+    let decision_rows = research::decision_rows(
+        state.cycle_count + 1,
+        decided_at,
+        &estimates,
+        &decisions,
+        state,
+    );
+
     // 6. Execute
-    if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Executing { bets_total: approved_bets.len() }; }
-    let execution = executor.execute_batch(&approved_bets).await?;
+    if let Some(d) = dash { d.set_progress(EvaluationProgress::Executing { bets_total: approved_bets.len() }).await; }
+    let execute_span = info_span!("execute", bets = approved_bets.len() as i64, executed = field::Empty, failed = field::Empty);
+    let execution = executor.execute_batch(&approved_bets).instrument(execute_span.clone()).await?;
+    execute_span.record("executed", execution.executed.len() as i64);
+    execute_span.record("failed", execution.error_count() as i64);
+    // Bets whose orders never reached their venue are retried next cycle.
+    recovery.record_unplaced(cycle_context::unplaced(&approved_bets, &execution), decided_at);
+    save_recovery(recovery);
 
     // 7. Track open bets (for resolution checking on next cycles)
-    for trade in &execution.executed {
-        if trade.platform != "dry-run" {
-            state.open_bets.push(trade.receipt.clone());
+    track_execution(router, state, storage, &execution).await;
+
+    // 7b. Keep every estimate for calibration, flagging the ones we bet on.
+    if let Some(store) = calibration {
+        for (market, estimate) in &estimates {
+            let acted_on = execution.executed.iter().any(|t| t.market_id == market.id);
+            store.record_estimate(market, estimate, acted_on, decided_at);
+        }
+        if let Err(e) = store.save() {
+            warn!(error = %e, "Failed to save estimate calibration");
         }
     }
 
     // 8. Reconcile
-    if let Some(d) = dash { *d.progress.write().await = EvaluationProgress::Reconciling; }
+    if let Some(d) = dash { d.set_progress(EvaluationProgress::Reconciling).await; }
     let costs = CycleCosts {
-        llm_cost: estimates.iter().map(|(_, e)| e.cost).sum(),
+        llm_cost: estimates.iter().map(|(_, e)| e.cost).sum::<Decimal>() + shadow_cost,
         // Use delta from before enrichment to avoid double-counting cumulative enricher cost.
-        data_cost: enricher.total_cost() - data_cost_before,
+        data_cost: enricher.total_cost() - data_cost_before + resumed_data_cost,
         ..Default::default()
     };
 
     let mut report = Accountant::reconcile(state, &execution, &costs);
+    Accountant::record_balances(state, executor.fetch_balances().await);
     report.markets_scanned = markets_scanned;
     report.edges_found = edges_found;
+    report.decisions = decision_rows;
+    report.llm_cost_by_tier = tiers::cost_by_tier(estimates.iter().map(|(_, e)| e));
+    report.llm_cost_by_model = failover::cost_by_provider(estimates.iter().map(|(_, e)| e));
+    report.estimate_cache_hits = cache_hits;
+    report.estimate_cache_misses = cache_misses;
+    report.budget_used = cost_budget.cycle_used();
+    report.budget_remaining = cost_budget.cycle_remaining_at(chrono::Utc::now());
+    report.budget_skipped = budget_skipped;
 
     Ok(report)
 }
 
+/// Place the bets a failed cycle approved but couldn't place, as approved,
+/// in place of a full cycle. The executor re-checks each against its
+/// venue's price as it places it.
+async fn retry_unplaced(
+    stage: ApprovedStage,
+    router: &MarketRouter,
+    executor: &Executor,
+    state: &mut AgentState,
+    storage: &dyn Storage,
+    dash: Option<&AppState>,
+    recovery: &mut CycleRecovery,
+) -> Result<CycleReport> {
+    info!(
+        bets = stage.bets.len(),
+        approved_at = %stage.at,
+        "Retrying bets left unplaced last cycle — scan, enrichment and estimation skipped"
+    );
+    if let Some(d) = dash { d.set_progress(EvaluationProgress::Executing { bets_total: stage.bets.len() }).await; }
+    let execution = executor.execute_batch(&stage.bets).await?;
+    // Still unreachable: tried again while the approval is fresh.
+    recovery.record_unplaced(cycle_context::unplaced(&stage.bets, &execution), stage.at);
+    save_recovery(recovery);
+    track_execution(router, state, storage, &execution).await;
+
+    if let Some(d) = dash { d.set_progress(EvaluationProgress::Reconciling).await; }
+    let report = Accountant::reconcile(state, &execution, &CycleCosts::default());
+    Accountant::record_balances(state, executor.fetch_balances().await);
+    Ok(report)
+}
+
+/// Drop markets found closed from the scan and book the fills as open
+/// bets (for resolution checking on next cycles).
+async fn track_execution(router: &MarketRouter, state: &mut AgentState, storage: &dyn Storage, execution: &ExecutionReport) {
+    for f in execution.failed.iter().filter(|f| f.code == ExecutionFailure::MarketClosed) {
+        router.mark_closed(&f.platform, &f.market_id);
+    }
+    for trade in &execution.executed {
+        if trade.platform != "dry-run" {
+            state.open_bets.push(trade.receipt.clone());
+            if let Err(e) = storage.append_trade(&trade.receipt).await {
+                warn!(error = %e, order_id = %trade.receipt.order_id, "Failed to store trade");
+            }
+        }
+    }
+}
+
+fn save_recovery(recovery: &CycleRecovery) {
+    if let Err(e) = recovery.save() {
+        warn!(error = %e, "Failed to save cycle context");
+    }
+}
+
+/// Label estimates whose market deadline has passed with their outcome,
+/// a few per tick, and drop the ones overdue too long to expect one.
+async fn resolve_due_estimates(executor: &Executor, store: &mut CalibrationStore) {
+    const CHECKS_PER_TICK: usize = 20;
+    let now = chrono::Utc::now();
+    let mut changed = store.expire(now) > 0;
+    for (platform, market_id, resolution) in Resolver::check_markets(executor, &store.due(now, CHECKS_PER_TICK)).await {
+        changed |= store.resolve(&platform, &market_id, resolution, now);
+    }
+    if changed {
+        if let Err(e) = store.save() {
+            warn!(error = %e, "Failed to save estimate calibration");
+        }
+    }
+}
+
+/// Label shadow-sampled markets whose deadline has passed with their
+/// outcome, a few per tick, and drop the ones overdue too long.
+async fn resolve_due_shadow(executor: &Executor, sr: &mut ShadowRunner) {
+    const CHECKS_PER_TICK: usize = 20;
+    let now = chrono::Utc::now();
+    let mut changed = sr.store_mut().expire(now) > 0;
+    for (platform, market_id, resolution) in Resolver::check_markets(executor, &sr.store().due(now, CHECKS_PER_TICK)).await {
+        changed |= sr.record_resolution(&platform, &market_id, resolution);
+    }
+    if changed {
+        if let Err(e) = sr.save() {
+            warn!(error = %e, "Failed to save shadow samples");
+        }
+    }
+}
+
+/// Check `bets` for resolution and apply any outcomes to `state`.
+async fn process_resolutions(
+    executor: &Executor,
+    state: &mut AgentState,
+    bets: &[oracle::types::TradeReceipt],
+    shadow: Option<&mut ShadowRunner>,
+    calibration: Option<&mut CalibrationStore>,
+    store: Option<&MetricsStore>,
+    storage: &dyn Storage,
+    archive: &Archive,
+    cool_down: &CoolDownConfig,
+) {
+    let settlements = Resolver::check(executor, bets).await;
+    if settlements.is_empty() {
+        return;
+    }
+    if let Some(calibration) = calibration {
+        let now = chrono::Utc::now();
+        let mut changed = false;
+        for s in &settlements {
+            changed |= calibration.resolve(&s.platform, &s.market_id, s.resolution, now);
+        }
+        if changed {
+            if let Err(e) = calibration.save() {
+                warn!(error = %e, "Failed to save estimate calibration");
+            }
+        }
+    }
+    if let Some(sr) = shadow {
+        let mut changed = false;
+        for s in &settlements {
+            changed |= sr.record_resolution(&s.platform, &s.market_id, s.resolution);
+        }
+        if changed {
+            if let Err(e) = sr.save() {
+                warn!(error = %e, "Failed to save shadow samples");
+            }
+        }
+    }
+    // Filed under the next cycle: known before its decisions.
+    if replay::recording_enabled() {
+        let root = std::path::Path::new(replay::DEFAULT_RECORD_DIR);
+        if let Err(e) = replay::record_resolutions(root, state.cycle_count + 1, &settlements) {
+            warn!(error = %e, "Failed to record resolutions for replay");
+        }
+    }
+    let mut resolved_ids = std::collections::HashSet::new();
+    let mut labelled = Vec::new();
+    for s in &settlements {
+        // Mana outcomes go to the play-money ledger only, never the AUD
+        // bankroll or survival check; refunds are booked as neither.
+        Resolver::apply(state, s);
+        resolved_ids.insert(s.bet_id.clone());
+        if let Err(e) = storage.settle_trade(&s.bet_id, s.pnl, chrono::Utc::now()).await {
+            warn!(error = %e, bet_id = %s.bet_id, "Failed to settle stored trade");
+        }
+        let Some(bet) = state.open_bets.iter().find(|b| b.order_id == s.bet_id).cloned() else {
+            continue;
+        };
+        // Refunds carry no win/loss or outcome signal.
+        if s.is_refund() {
+            continue;
+        }
+
+        // Feed the category's losing streak.
+        if let Some(category) = bet.category {
+            cooldown::record_resolution(state, category, s.won, cool_down, chrono::Utc::now());
+        }
+
+        // Per-tag results for `/api/tags`, and late resolutions booked
+        // against the day their market closed.
+        tags::record_resolution(state, &bet, s.pnl, s.won);
+        daily::record_resolution(state, &bet, s.pnl, chrono::Utc::now());
+
+        // Record detected edge vs realised return for the adaptive thresholds.
+        if let (Some(category), Some(edge)) = (bet.category, bet.edge) {
+            adaptive::record_realization(state, oracle::types::EdgeRealization {
+                category,
+                edge,
+                stake: bet.amount,
+                pnl: s.pnl,
+                resolved_at: chrono::Utc::now(),
+            });
+        }
+
+        let Some(resolved_yes) = s.resolved_yes() else { continue };
+
+        // Label this market's decision history with its outcome.
+        if let Some(store) = store {
+            score_references(store, state, &s.platform, &s.market_id, resolved_yes).await;
+            match store.record_resolution(&s.platform, &s.market_id, resolved_yes).await {
+                Ok(_) => labelled.push((s.platform.clone(), s.market_id.clone())),
+                Err(e) => warn!(error = %e, market_id = %s.market_id, "Failed to record decision outcome"),
+            }
+        }
+    }
+
+    // Move each labelled market's lifecycle into the archive.
+    if let Some(store) = store {
+        labelled.sort();
+        labelled.dedup();
+        for (platform, market_id) in labelled {
+            let receipts = state.open_bets.iter()
+                .filter(|b| b.market_id == market_id && resolved_ids.contains(&b.order_id))
+                .cloned()
+                .collect();
+            if let Err(e) = archive.archive(store, &platform, &market_id, ArchiveReason::Resolved, receipts).await {
+                warn!(error = %e, market_id = %market_id, "Failed to archive resolved market");
+            }
+        }
+    }
+    state.open_bets.retain(|b| !resolved_ids.contains(&b.order_id));
+}
+
+/// Score the reference prices of a resolved market's latest decision for
+/// the cross-reference weights. The decision log is the only record of
+/// what the references said, so this runs before the market is archived.
+async fn score_references(store: &MetricsStore, state: &mut AgentState, platform: &str, market_id: &str, resolved_yes: bool) {
+    let rows = match store.market_decisions(platform, market_id).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!(error = %e, market_id = %market_id, "Decision lookup failed — references not scored");
+            return;
+        }
+    };
+    let Some(row) = rows.last() else { return };
+    let Ok(category) = row.category.parse() else { return };
+    let refs: Vec<(&str, f64)> = [("metaculus", row.metaculus_prob), ("manifold", row.manifold_prob)]
+        .into_iter()
+        .filter(|(platform, _)| *platform != row.platform)
+        .filter_map(|(platform, p)| Some((platform, p?)))
+        .collect();
+    references::record_resolution(state, category, &refs, resolved_yes);
+}
+
+/// Archive markets that never resolved and have had no decision for
+/// `expire_after_days`. Markets we still hold a bet on are kept hot.
+async fn archive_expired(store: &MetricsStore, archive: &Archive, state: &AgentState, expire_after_days: i64) {
+    if expire_after_days <= 0 {
+        return;
+    }
+    let before = chrono::Utc::now() - chrono::Duration::days(expire_after_days);
+    let stale = match store.stale_markets(before).await {
+        Ok(stale) => stale,
+        Err(e) => {
+            warn!(error = %e, "Stale market query failed — archival skipped this cycle");
+            return;
+        }
+    };
+    for (platform, market_id) in stale {
+        if state.open_bets.iter().any(|b| b.platform == platform && b.market_id == market_id) {
+            continue;
+        }
+        if let Err(e) = archive.archive(store, &platform, &market_id, ArchiveReason::Expired, Vec::new()).await {
+            warn!(error = %e, market_id = %market_id, "Failed to archive expired market");
+        }
+    }
+}
+
+/// Attribute new Manifold account activity to our receipts or to external
+/// trading. Best-effort: a failed fetch is retried next cycle.
+async fn reconcile_manifold_history(
+    executor: &Executor,
+    state: &mut AgentState,
+    user_id: &str,
+    receipts: &[oracle::types::TradeReceipt],
+    window: chrono::Duration,
+) {
+    const HISTORY_LIMIT: usize = 1000;
+    let bets = match executor.fetch_manifold_user_bets(user_id, HISTORY_LIMIT).await {
+        Ok(bets) => bets,
+        Err(e) => {
+            warn!(error = %e, "Manifold history fetch failed — reconciliation skipped this cycle");
+            return;
+        }
+    };
+    let history: Vec<_> = bets.iter().map(reconcile::PlatformTxn::from_manifold).collect();
+    if history.len() == HISTORY_LIMIT && reconcile::unseen(&history, &state.external_activity).len() == HISTORY_LIMIT {
+        warn!(limit = HISTORY_LIMIT, "Manifold history page is entirely new — older activity may be missed");
+    }
+    let attribution = reconcile::reconcile(&mut state.external_activity, &history, receipts, window);
+    if !attribution.external.is_empty() {
+        let staked: Decimal = attribution.external.iter().map(|t| t.amount).sum();
+        info!(
+            count = attribution.external.len(),
+            staked = %staked,
+            own = attribution.own.len() + attribution.own_sales.len(),
+            "External Manifold activity detected"
+        );
+    }
+}
+
+/// Log every effective-config key that changed since the previous session.
+fn log_config_changes(state: &AgentState, current: &config::EffectiveConfig) {
+    if state.effective_config.is_empty() {
+        debug!("No previous effective config recorded — skipping config diff");
+        return;
+    }
+    let changes = current.diff(&state.effective_config);
+    for change in &changes {
+        let show = |v: &Option<serde_json::Value>| v.as_ref().map_or("<none>".to_string(), |v| v.to_string());
+        info!(
+            key = %change.key,
+            from = %show(&change.previous),
+            to = %show(&change.current),
+            "Config changed since last session"
+        );
+    }
+    info!(changed = changes.len(), "Effective config compared with last session");
+}
+
+/// Edge thresholds from `[risk.category_thresholds]`, falling back to the
+/// built-in defaults per category.
+/// Kalshi client when `[platforms.kalshi]` is enabled; a failed init is
+/// logged and the venue skipped.
+fn kalshi_client(cfg: &config::AppConfig) -> Option<KalshiClient> {
+    if !cfg.platforms.kalshi.enabled {
+        return None;
+    }
+    match KalshiClient::from_config(&cfg.platforms.kalshi) {
+        Ok(client) => Some(client),
+        Err(e) => {
+            warn!(error = %e, "Kalshi init failed, continuing without");
+            None
+        }
+    }
+}
+
+/// ForecastEx client when `[platforms.forecastex]` is enabled. TWS is
+/// connected on first use.
+fn forecastex_client(cfg: &config::AppConfig) -> Option<ForecastExClient> {
+    cfg.platforms.forecastex.enabled.then(|| ForecastExClient::from_config(&cfg.platforms.forecastex))
+}
+
+/// Edge detection → Kelly sizing → risk approval, as configured. Shared by
+/// the live loop and `oracle backtest`.
+fn build_orchestrator(cfg: &config::AppConfig, market_links: MarketLinks) -> StrategyOrchestrator {
+    let params = StrategyParams::from_config(cfg, market_links);
+    StrategyOrchestrator::new(
+        EdgeDetector::new(params.edge),
+        KellyCalculator::new(params.kelly),
+        RiskManager::new(params.risk),
+    )
+}
+
+/// The configured strategy parameters the sensitivity grid varies around.
+fn sensitivity_base(cfg: &config::AppConfig) -> SensitivityParams {
+    SensitivityParams {
+        edge: StrategyParams::edge_config(cfg),
+        kelly_multiplier: cfg.risk.kelly_multiplier,
+        max_bet_pct: cfg.risk.max_bet_pct,
+        max_bets_per_cycle: RiskConfig::default().max_bets_per_cycle,
+    }
+}
+
+/// Per-category thresholds for `/api/risk`.
+fn threshold_views(
+    state: &AgentState,
+    base: &EdgeConfig,
+    cfg: &config::AdaptiveThresholdConfig,
+) -> Vec<CategoryThresholdView> {
+    oracle::types::MarketCategory::ALL
+        .iter()
+        .map(|&category| {
+            let effective = if cfg.enabled {
+                adaptive::effective_threshold(state, base, category)
+            } else {
+                base.threshold_for(&category)
+            };
+            CategoryThresholdView {
+                category: category.to_string(),
+                configured: base.threshold_for(&category).to_f64().unwrap_or(0.0),
+                effective: effective.to_f64().unwrap_or(0.0),
+                frozen: cfg.frozen.contains(&category),
+            }
+        })
+        .collect()
+}
+
+/// Token budget of a batch prompt, when `llm.context_window` is set. The
+/// answer's share is the largest output cap any estimation tier asks for.
+fn prompt_budget(cfg: &config::AppConfig) -> Option<PromptBudget> {
+    let tiers = &cfg.llm.tiers;
+    let tier_max = if tiers.enabled { [&tiers.a, &tiers.b, &tiers.c].map(|t| t.max_tokens).into_iter().max() } else { None };
+    let max_output = tier_max.unwrap_or(0).max(cfg.llm.max_tokens);
+    cfg.llm.context_window.map(|window| PromptBudget::new(window as usize, max_output as usize))
+}
+
+/// `client` with the background session keep-alive of `cfg`, if any.
+fn keep_alive(client: BetfairClient, cfg: &config::BetfairConfig) -> BetfairClient {
+    match cfg.keep_alive_mins {
+        0 => client,
+        mins => client.with_keep_alive(Duration::from_secs(mins * 60)),
+    }
+}
+
+/// Construct an LLM estimator for `provider`/`model`, its call latency
+/// observed in `metrics`.
+fn build_estimator(
+    provider: &str,
+    model: &str,
+    api_key: String,
+    llm_cfg: &config::LlmConfig,
+    metrics: &Arc<prometheus::Registry>,
+) -> Result<Box<dyn LlmEstimator>> {
+    let est: Box<dyn LlmEstimator> = match provider {
+        "openrouter" => {
+            info!(
+                model = %model,
+                fallback = ?llm_cfg.fallback_model,
+                "Using OpenRouter LLM provider"
+            );
+            Box::new(OpenRouterClient::new(
+                api_key,
+                Some(model.to_string()),
+                llm_cfg.fallback_model.clone(),
+                Some(llm_cfg.max_tokens),
+                Some(llm_cfg.batch_size),
+            )?)
+        }
+        "anthropic" => {
+            let structured = llm_cfg.structured_output.unwrap_or_else(|| structured::supports(model));
+            info!(model = %model, structured, "Using Anthropic LLM provider");
+            Box::new(
+                AnthropicClient::new(api_key, Some(model.to_string()), Some(llm_cfg.max_tokens))?
+                    .with_structured_output(structured),
+            )
+        }
+        "openai" => {
+            info!(
+                model = %model,
+                base_url = ?llm_cfg.base_url,
+                json_mode = llm_cfg.json_mode,
+                "Using OpenAI-compatible LLM provider"
+            );
+            let mut client = OpenAiClient::new(
+                api_key,
+                Some(model.to_string()),
+                Some(llm_cfg.max_tokens),
+            )?
+            .with_json_mode(llm_cfg.json_mode);
+            if let Some(url) = &llm_cfg.base_url {
+                client = client.with_base_url(url);
+            }
+            match (llm_cfg.input_cost_per_1k, llm_cfg.output_cost_per_1k) {
+                (Some(input), Some(output)) => client = client.with_pricing(input, output),
+                (None, None) => {}
+                _ => warn!("Set both llm.input_cost_per_1k and llm.output_cost_per_1k; using default pricing"),
+            }
+            Box::new(client)
+        }
+        "grok" => {
+            info!(model = %model, cite_x_posts = llm_cfg.cite_x_posts, "Using Grok (xAI) LLM provider");
+            Box::new(
+                GrokClient::new(api_key, Some(model.to_string()), Some(llm_cfg.max_tokens))?
+                    .with_x_citations(llm_cfg.cite_x_posts),
+            )
+        }
+        other => {
+            anyhow::bail!(
+                "Unknown LLM provider '{}' in config.toml. \
+                 Valid values are: openrouter, anthropic, openai, grok",
+                other
+            );
+        }
+    };
+    Ok(Box::new(TimedEstimator::new(est, Arc::clone(metrics))))
+}
+
 /// Log a human-readable cycle summary.
+/// Print a [`PerformanceReport`] as tables for `oracle report`.
+fn print_performance_report(report: &PerformanceReport) {
+    let percent = |x: Option<f64>| x.map_or_else(|| "-".to_string(), |x| format!("{:+.1}%", x * 100.0));
+    let table = |title: &str, rows: &[Attribution]| {
+        println!("{:<12} {:<5} {:>6} {:>5} {:>10} {:>10} {:>8} {:>8} {:>9} {:>6}", title, "ccy", "trades", "won", "staked", "pnl", "roi", "edge", "realised", "brier");
+        for a in rows {
+            println!(
+                "{:<12} {:<5} {:>6} {:>5} {:>10.2} {:>+10.2} {:>8} {:>8} {:>9} {:>6}",
+                a.key, a.currency, a.trades, a.won, a.staked, a.pnl, percent(Some(a.roi)),
+                percent(a.avg_entry_edge), percent(a.avg_realised_edge),
+                a.brier.map_or_else(|| "-".to_string(), |b| format!("{b:.3}"))
+            );
+        }
+        println!();
+    };
+    if report.by_category.is_empty() {
+        println!("No settled trades yet.");
+        return;
+    }
+    table("category", &report.by_category);
+    table("platform", &report.by_platform);
+    if report.suggestions.is_empty() {
+        println!("No category's realised edge is significantly below its threshold (tested from {MIN_SUGGESTION_TRADES} resolved bets).");
+        return;
+    }
+    println!("Suggested thresholds (realised edge significantly below threshold):");
+    println!("{:<12} {:>6} {:>9} {:>8} {:>9} {:>8} {:>9}", "category", "trades", "threshold", "edge", "realised", "upper", "suggested");
+    for s in &report.suggestions {
+        println!(
+            "{:<12} {:>6} {:>9.3} {:>8.3} {:>9.3} {:>8.3} {:>9.3}",
+            s.category, s.trades, s.threshold, s.avg_entry_edge, s.avg_realised_edge, s.upper_bound, s.suggested
+        );
+    }
+}
+
+fn print_backtest_report(dir: &str, report: &ReplayReport) {
+    let pnl = |by_currency: &std::collections::BTreeMap<String, Decimal>| {
+        by_currency.iter().map(|(c, p)| format!("{c} {:+.2}", p)).collect::<Vec<_>>().join(", ")
+    };
+    println!(
+        "{dir}: {} cycles, {} estimates, {} bets — {} won, {} lost, {} refunded, {} unresolved; win rate {:.1}%",
+        report.cycles, report.estimates, report.bets, report.wins, report.losses, report.refunds, report.unresolved,
+        report.win_rate() * 100.0
+    );
+    println!("{:<8} {:>10} {:>10} {:>10} {:>12} {:>8}", "currency", "start", "final", "pnl", "max_drawdown", "max_dd%");
+    for (currency, l) in &report.ledgers {
+        println!(
+            "{:<8} {:>10.2} {:>10.2} {:>+10.2} {:>12.2} {:>7.1}%",
+            currency, l.start, l.balance, l.pnl(), l.max_drawdown, l.max_drawdown_pct * Decimal::ONE_HUNDRED
+        );
+    }
+    println!("{:<10} {:>5} {:>4} {:>5} {:>9} {:>11}  pnl", "category", "bets", "won", "lost", "refunded", "unresolved");
+    for (category, c) in &report.by_category {
+        println!(
+            "{:<10} {:>5} {:>4} {:>5} {:>9} {:>11}  {}",
+            category, c.bets, c.wins, c.losses, c.refunds, c.unresolved, pnl(&c.pnl)
+        );
+    }
+}
+
 fn log_cycle_report(report: &CycleReport) {
     info!(
         cycle = report.cycle_number,
@@ -588,12 +2141,146 @@ fn log_cycle_report(report: &CycleReport) {
         status = ?report.status,
         "Cycle complete"
     );
+    if !report.llm_cost_by_tier.is_empty() {
+        let by_tier: Vec<String> = report.llm_cost_by_tier.iter()
+            .map(|(tier, cost)| format!("{tier}=${}", cost.round_dp(4)))
+            .collect();
+        info!(cycle = report.cycle_number, costs = %by_tier.join(" "), "LLM cost by tier");
+    }
+    if !report.llm_cost_by_model.is_empty() {
+        let by_model: Vec<String> = report.llm_cost_by_model.iter()
+            .map(|(model, cost)| format!("{model}=${}", cost.round_dp(4)))
+            .collect();
+        info!(cycle = report.cycle_number, costs = %by_model.join(" "), "LLM cost by model");
+    }
+    if report.estimate_cache_hits > 0 {
+        info!(
+            cycle = report.cycle_number,
+            hits = report.estimate_cache_hits,
+            misses = report.estimate_cache_misses,
+            "Estimate cache"
+        );
+    }
+    if let Some(remaining) = report.budget_remaining {
+        info!(
+            cycle = report.cycle_number,
+            used = %format!("${}", report.budget_used.round_dp(4)),
+            remaining = %format!("${}", remaining.round_dp(4)),
+            "Cost budget"
+        );
+    }
 }
 
 /// Push cycle results into the shared dashboard state.
-async fn update_dashboard(dash: &AppState, state: &AgentState, report: &CycleReport) {
-    // Mirror the latest agent state snapshot
-    *dash.agent.write().await = state.clone();
+/// Queue notifications for a finished cycle: its bets, a budget shortfall,
+/// a newly crossed drawdown tier and the agent's death.
+fn notify_cycle(
+    notifications: &Notifications,
+    report: &CycleReport,
+    state: &AgentState,
+    drawdown_tiers: &mut DrawdownTiers,
+    cost_tracker: &CostTracker,
+) {
+    if !notifications.is_enabled() {
+        return;
+    }
+    for t in &report.executed_trades {
+        notifications.send(Event::BetPlaced {
+            platform: t.platform.clone(),
+            market_id: t.market_id.clone(),
+            side: t.side,
+            amount: t.amount,
+            edge_pct: t.edge_pct,
+        });
+    }
+    for f in &report.failed_trades {
+        notifications.send(Event::BetFailed {
+            platform: f.platform.clone(),
+            market_id: f.market_id.clone(),
+            reason: f.reason.clone(),
+        });
+    }
+    if report.budget_skipped > 0 {
+        notifications.send(Event::LlmBudget {
+            skipped_markets: report.budget_skipped,
+            spent_today: cost_tracker.spent_today_at(report.timestamp),
+        });
+    }
+    if let Some(tier_pct) = drawdown_tiers.crossed(state.drawdown()) {
+        notifications.send(Event::Drawdown {
+            tier_pct,
+            drawdown_pct: state.drawdown() * Decimal::ONE_HUNDRED,
+            bankroll: state.bankroll,
+        });
+    }
+    if state.status == AgentStatus::Died {
+        notifications.send(Event::AgentDeath { bankroll: state.bankroll, survival_threshold: state.survival_threshold });
+    }
+}
+
+/// Preflight every venue, publish the reports and alert on go/no-go flips.
+/// SIGUSR1 pauses a running agent, or asks a paused one to resume.
+#[cfg(unix)]
+fn spawn_pause_signal(dash: AppState) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut usr1 = match signal(SignalKind::user_defined1()) {
+        Ok(s) => s,
+        Err(e) => {
+            warn!(error = %e, "SIGUSR1 pause toggle unavailable");
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        while usr1.recv().await.is_some() {
+            let paused = dash.agent.read(|a| a.status == AgentStatus::Paused).await;
+            let request = if paused { ModeRequest::Resume } else { ModeRequest::Pause };
+            info!(?request, "SIGUSR1 received");
+            *dash.mode_request.write().await = Some(request);
+        }
+    });
+}
+
+/// Enrichment providers and whether the API keys they need are set.
+fn enrichment_availability(cfg: &config::AppConfig) -> Vec<EnrichmentAvailability> {
+    let keyed = |provider: &str, env: &Option<String>| EnrichmentAvailability {
+        provider: provider.to_string(),
+        key_env: env.clone(),
+        available: env.as_deref().is_some_and(|e| std::env::var(e).is_ok_and(|v| !v.is_empty())),
+    };
+    let keyless = |provider: &str, available: bool| EnrichmentAvailability {
+        provider: provider.to_string(),
+        key_env: None,
+        available,
+    };
+    let sources = &cfg.data_sources;
+    vec![
+        keyless("weather", true),
+        keyed("sports", &sources.api_sports_key_env),
+        keyed("economics", &sources.fred_api_key_env),
+        keyed("news", &sources.news_api_key_env),
+        keyless("manifold_flow", cfg.enricher.manifold_flow_enabled),
+    ]
+}
+
+async fn run_preflight(executor: &Executor, dash: &AppState, webhook: Option<&Webhook>) {
+    let run = executor.preflight().await;
+    if let Some(webhook) = webhook {
+        for report in run.reports.iter().filter(|r| run.changed.contains(&r.platform)) {
+            let text = if report.go() {
+                format!("ORACLE: {} preflight passed — execution re-enabled", report.platform)
+            } else {
+                format!("ORACLE: {} preflight failed — scan-only ({})", report.platform, report.summary())
+            };
+            webhook.send(&text).await;
+        }
+    }
+    *dash.preflight.write().await = run.reports;
+}
+
+async fn update_dashboard(dash: &AppState, state: &AgentState, report: &CycleReport, events: Vec<CycleEvent>) {
+    dash.prometheus.record_cycle(report, state);
+    *dash.last_cycle.write().await = Some(report.clone());
 
     // Append cycle log entry (cap at 100 on the write side)
     {
@@ -608,48 +2295,30 @@ async fn update_dashboard(dash: &AppState, state: &AgentState, report: &CycleRep
             cycle_cost: report.cycle_costs.total().to_f64().unwrap_or(0.0),
             bankroll_after: report.bankroll_after.to_f64().unwrap_or(0.0),
             status: format!("{}", report.status),
+            events,
         });
-        if log.len() > 100 {
-            let excess = log.len() - 100;
+        if log.len() > MAX_CYCLE_LOG {
+            let excess = log.len() - MAX_CYCLE_LOG;
             log.drain(0..excess);
         }
     }
 
     // Append executed trades to recent trades log (cap at 200)
     if !report.executed_trades.is_empty() {
-        let mut trades = dash.recent_trades.write().await;
-        for t in &report.executed_trades {
-            trades.push(TradeLogEntry {
-                timestamp: t.receipt.timestamp.to_rfc3339(),
-                market_id: t.market_id.clone(),
-                platform: t.platform.clone(),
-                side: format!("{}", t.side),
-                amount: t.amount.to_f64().unwrap_or(0.0),
-                currency: t.receipt.currency.clone(),
-                edge_pct: t.edge_pct,
-                confidence: t.confidence,
-                close_reason: None,
-                final_pnl: None,
-            });
-        }
-        if trades.len() > 200 {
-            let excess = trades.len() - 200;
-            trades.drain(0..excess);
-        }
-    }
-
-    // Append balance history point (cap at 500 on the write side)
-    {
-        let mut history = dash.balance_history.write().await;
-        history.push(BalancePoint {
-            timestamp: report.timestamp.to_rfc3339(),
-            bankroll: report.bankroll_after.to_f64().unwrap_or(0.0),
-            mana_bankroll: state.mana_bankroll.to_f64().unwrap_or(0.0),
-        });
-        if history.len() > 500 {
-            let excess = history.len() - 500;
-            history.drain(0..excess);
-        }
+        dash.push_trades(report.executed_trades.iter().map(|t| TradeLogEntry {
+            timestamp: t.receipt.timestamp.to_rfc3339(),
+            market_id: t.market_id.clone(),
+            platform: t.platform.clone(),
+            side: format!("{}", t.side),
+            amount: t.amount.to_f64().unwrap_or(0.0),
+            currency: t.receipt.currency.clone(),
+            edge_pct: t.edge_pct,
+            confidence: t.confidence,
+            category: t.receipt.category,
+            close_reason: None,
+            final_pnl: None,
+        }))
+        .await;
     }
 }
 
@@ -657,29 +2326,18 @@ async fn update_dashboard(dash: &AppState, state: &AgentState, report: &CycleRep
 async fn process_auto_exits(
     results: &[CloseResult],
     state: &mut oracle::types::AgentState,
-    dash: &AppState,
+    dash: Option<&AppState>,
 ) {
-    let mut closed_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
-
     for result in results {
-        if !result.success {
-            continue;
-        }
-
+        let category = state.open_bets.iter().find(|b| b.order_id == result.bet_id).and_then(|b| b.category);
         // Record the closed position's P&L in agent state.
-        // Manifold is Mana (paper money) — never touch the AUD survival bankroll.
-        let won = result.realized_pnl >= Decimal::ZERO;
-        if result.platform == "manifold" {
-            state.record_mana_resolution(result.realized_pnl, won);
-        } else {
-            state.record_resolution(result.realized_pnl, won);
+        if !Accountant::book_close(state, result, chrono::Utc::now()) {
+            continue;
         }
-        closed_ids.insert(result.bet_id.clone());
 
         // Push a "closed" trade entry to the dashboard
-        {
-            let mut trades = dash.recent_trades.write().await;
-            trades.push(TradeLogEntry {
+        if let Some(dash) = dash {
+            dash.push_trades([TradeLogEntry {
                 timestamp: chrono::Utc::now().to_rfc3339(),
                 market_id: result.market_id.clone(),
                 platform: format!("{}-closed", result.platform),
@@ -688,26 +2346,19 @@ async fn process_auto_exits(
                 currency: if result.platform == "betfair" { "AUD".to_string() } else { "Mana".to_string() },
                 edge_pct: 0.0,
                 confidence: 0.0,
+                category,
                 close_reason: Some(result.reason.to_string()),
                 final_pnl: Some(result.realized_pnl.to_f64().unwrap_or(0.0)),
-            });
-            if trades.len() > 200 {
-                let excess = trades.len() - 200;
-                trades.drain(0..excess);
-            }
+            }])
+            .await;
         }
     }
-
-    // Remove successfully closed bets from open_bets
-    if !closed_ids.is_empty() {
-        state.open_bets.retain(|b| !closed_ids.contains(&b.order_id));
-        // Mirror updated state to dashboard
-        *dash.agent.write().await = state.clone();
-    }
 }
 
-/// Initialise the `tracing` subscriber.
-fn init_logging(cfg: &config::AppConfig) {
+/// Initialise the `tracing` subscriber. Returns the buffer its error
+/// events are captured into, for `/api/diagnostics`.
+fn init_logging(cfg: &config::AppConfig) -> RecentErrors {
+    use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
 
     let env_filter = EnvFilter::try_from_default_env()
@@ -715,19 +2366,33 @@ fn init_logging(cfg: &config::AppConfig) {
 
     let json_logging = std::env::var("ORACLE_LOG_JSON").is_ok();
 
+    // Spans go to the OTLP exporter only when telemetry is enabled.
+    let provider = if cfg.telemetry.enabled {
+        telemetry::init(&cfg.telemetry)
+            .map_err(|e| eprintln!("Span export disabled: {e:#}"))
+            .ok()
+    } else {
+        None
+    };
+    let spans = provider.as_ref().map(telemetry::layer);
+
+    let recent_errors = RecentErrors::default();
+    let registry = tracing_subscriber::registry().with(env_filter).with(spans).with(recent_errors.layer());
     if json_logging {
-        fmt()
-            .json()
-            .with_env_filter(env_filter)
-            .with_target(true)
-            .with_thread_ids(true)
+        registry
+            .with(fmt::layer().json().with_target(true).with_thread_ids(true))
             .init();
     } else {
-        fmt()
-            .with_env_filter(env_filter)
-            .with_target(true)
-            .init();
+        registry.with(fmt::layer().with_target(true)).init();
     }
 
-    let _ = cfg;
+    if provider.is_some() {
+        info!(
+            endpoint = %cfg.telemetry.endpoint,
+            service = %cfg.telemetry.service_name,
+            sample_ratio = cfg.telemetry.sample_ratio,
+            "OTLP span export enabled"
+        );
+    }
+    recent_errors
 }
@@ -0,0 +1,707 @@
+//! Event notifications.
+//!
+//! Where [`crate::alerts`] posts one-line operator alerts, this module
+//! reports what the agent does: bets placed and failed, drawdown crossing
+//! each 10% tier, the agent's death, a daily summary and the LLM budget
+//! running out. Events go to a [`Notifier`] — a JSON webhook, plain or
+//! Discord-formatted — configured under `[notifications]`.
+//!
+//! [`Notifications::send`] never waits: events are queued for a background
+//! worker, which delivers each with a short retry. The worker holds them
+//! to `max_per_minute`; events over that, or repeating one already sent in
+//! the last `coalesce_secs`, are coalesced per kind into a single notice
+//! carrying the latest event and a count, sent once the budget allows. A
+//! bad cycle thus costs a handful of messages, not fifty.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinSet;
+use tracing::{debug, warn};
+
+use crate::config::NotificationsConfig;
+use crate::platforms::rate_limit::TokenBucket;
+use crate::types::Side;
+
+/// Events queued for the worker before further ones are dropped.
+const QUEUE_CAPACITY: usize = 256;
+
+/// Delivery attempts per notice, and the wait before the first retry
+/// (doubled for each further one).
+const ATTEMPTS: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How often the worker looks for coalesced notices it may now send.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+// ---------------------------------------------------------------------------
+// Events
+// ---------------------------------------------------------------------------
+
+/// Kinds of event, as listed in `[notifications].events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    BetPlaced,
+    BetFailed,
+    Drawdown,
+    AgentDeath,
+    DailySummary,
+    LlmBudget,
+}
+
+impl EventKind {
+    pub fn all() -> Vec<Self> {
+        vec![Self::BetPlaced, Self::BetFailed, Self::Drawdown, Self::AgentDeath, Self::DailySummary, Self::LlmBudget]
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BetPlaced => "bet_placed",
+            Self::BetFailed => "bet_failed",
+            Self::Drawdown => "drawdown",
+            Self::AgentDeath => "agent_death",
+            Self::DailySummary => "daily_summary",
+            Self::LlmBudget => "llm_budget",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::BetPlaced => "Bet placed",
+            Self::BetFailed => "Bet failed",
+            Self::Drawdown => "Drawdown",
+            Self::AgentDeath => "Agent died",
+            Self::DailySummary => "Daily summary",
+            Self::LlmBudget => "LLM budget exhausted",
+        }
+    }
+
+    /// Discord embed colour.
+    fn colour(self) -> u32 {
+        match self {
+            Self::BetPlaced => 0x2ecc71,
+            Self::BetFailed | Self::AgentDeath => 0xe74c3c,
+            Self::Drawdown | Self::LlmBudget => 0xe67e22,
+            Self::DailySummary => 0x3498db,
+        }
+    }
+}
+
+/// Something worth telling the operator about.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    BetPlaced { platform: String, market_id: String, side: Side, amount: Decimal, edge_pct: f64 },
+    BetFailed { platform: String, market_id: String, reason: String },
+    /// Drawdown from peak reached `tier_pct` (a multiple of 10).
+    Drawdown { tier_pct: u32, drawdown_pct: Decimal, bankroll: Decimal },
+    AgentDeath { bankroll: Decimal, survival_threshold: Decimal },
+    DailySummary {
+        date: NaiveDate,
+        bankroll: Decimal,
+        total_pnl: Decimal,
+        open_bets: usize,
+        trades_placed: u64,
+        trades_won: u64,
+        trades_lost: u64,
+        spent_today: Decimal,
+    },
+    /// Markets left unestimated because the cost budget ran out.
+    LlmBudget { skipped_markets: usize, spent_today: Decimal },
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::BetPlaced { .. } => EventKind::BetPlaced,
+            Self::BetFailed { .. } => EventKind::BetFailed,
+            Self::Drawdown { .. } => EventKind::Drawdown,
+            Self::AgentDeath { .. } => EventKind::AgentDeath,
+            Self::DailySummary { .. } => EventKind::DailySummary,
+            Self::LlmBudget { .. } => EventKind::LlmBudget,
+        }
+    }
+
+    /// Identity for repeat suppression: the same bet failing on the same
+    /// market every cycle is one event repeating.
+    fn key(&self) -> String {
+        match self {
+            Self::BetPlaced { platform, market_id, .. } | Self::BetFailed { platform, market_id, .. } => {
+                format!("{}:{platform}:{market_id}", self.kind().as_str())
+            }
+            Self::Drawdown { tier_pct, .. } => format!("drawdown:{tier_pct}"),
+            Self::DailySummary { date, .. } => format!("daily_summary:{date}"),
+            _ => self.kind().as_str().to_string(),
+        }
+    }
+
+    /// One-line description.
+    pub fn text(&self) -> String {
+        match self {
+            Self::BetPlaced { platform, market_id, side, amount, edge_pct } => {
+                format!("Bet placed: {side:?} {:.2} on {platform}/{market_id} (edge {edge_pct:.1}%)", amount)
+            }
+            Self::BetFailed { platform, market_id, reason } => format!("Bet failed on {platform}/{market_id}: {reason}"),
+            Self::Drawdown { tier_pct, drawdown_pct, bankroll } => {
+                format!("Drawdown passed {tier_pct}%: {:.1}% from peak, bankroll {:.2}", drawdown_pct, bankroll)
+            }
+            Self::AgentDeath { bankroll, survival_threshold } => {
+                format!("Agent died: bankroll {:.2} below survival threshold {:.2}", bankroll, survival_threshold)
+            }
+            Self::DailySummary { date, bankroll, total_pnl, open_bets, trades_placed, trades_won, trades_lost, spent_today } => {
+                format!(
+                    "Daily summary {date}: bankroll {:.2}, P&L {:+.2}, {open_bets} open bets, \
+                     {trades_placed} trades ({trades_won} won / {trades_lost} lost), {:.2} spent today",
+                    bankroll, total_pnl, spent_today
+                )
+            }
+            Self::LlmBudget { skipped_markets, spent_today } => {
+                format!("LLM budget exhausted: {skipped_markets} markets left unestimated, {:.2} spent today", spent_today)
+            }
+        }
+    }
+
+    /// Name/value pairs for the Discord embed.
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::BetPlaced { platform, market_id, side, amount, edge_pct } => vec![
+                ("Market", format!("{platform}/{market_id}")),
+                ("Side", format!("{side:?}")),
+                ("Amount", format!("{:.2}", amount)),
+                ("Edge", format!("{edge_pct:.1}%")),
+            ],
+            Self::BetFailed { platform, market_id, reason } => {
+                vec![("Market", format!("{platform}/{market_id}")), ("Reason", reason.clone())]
+            }
+            Self::Drawdown { drawdown_pct, bankroll, .. } => {
+                vec![("Drawdown", format!("{:.1}%", drawdown_pct)), ("Bankroll", format!("{:.2}", bankroll))]
+            }
+            Self::AgentDeath { bankroll, .. } => vec![("Bankroll", format!("{:.2}", bankroll))],
+            Self::DailySummary { bankroll, total_pnl, open_bets, .. } => vec![
+                ("Bankroll", format!("{:.2}", bankroll)),
+                ("P&L", format!("{:+.2}", total_pnl)),
+                ("Open bets", open_bets.to_string()),
+            ],
+            Self::LlmBudget { skipped_markets, spent_today } => vec![
+                ("Skipped markets", skipped_markets.to_string()),
+                ("Spent today", format!("{:.2}", spent_today)),
+            ],
+        }
+    }
+}
+
+/// An event as delivered: `repeats` counts the events it stands for,
+/// itself included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notice {
+    pub event: Event,
+    pub repeats: u32,
+    pub at: DateTime<Utc>,
+}
+
+impl Notice {
+    pub fn text(&self) -> String {
+        match self.repeats {
+            0 | 1 => self.event.text(),
+            n => format!("{} (+{} more {} since the last notice)", self.event.text(), n - 1, self.event.kind().as_str()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Notifiers
+// ---------------------------------------------------------------------------
+
+/// Delivers notices somewhere.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn deliver(&self, notice: &Notice) -> Result<()>;
+}
+
+/// Body layout of a webhook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// `{event, text, repeats, at, data}`.
+    #[default]
+    Json,
+    /// A Discord message with one embed.
+    Discord,
+}
+
+/// JSON POST to a webhook URL.
+pub struct WebhookNotifier {
+    http: Client,
+    url: String,
+    format: WebhookFormat,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, format: WebhookFormat) -> Self {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { http, url, format }
+    }
+
+    /// Body posted for `notice`.
+    pub fn payload(&self, notice: &Notice) -> serde_json::Value {
+        let kind = notice.event.kind();
+        match self.format {
+            WebhookFormat::Json => serde_json::json!({
+                "event": kind.as_str(),
+                "text": notice.text(),
+                "repeats": notice.repeats,
+                "at": notice.at.to_rfc3339(),
+                "data": notice.event,
+            }),
+            WebhookFormat::Discord => {
+                let mut fields: Vec<_> = notice
+                    .event
+                    .fields()
+                    .into_iter()
+                    .map(|(name, value)| serde_json::json!({ "name": name, "value": value, "inline": true }))
+                    .collect();
+                if notice.repeats > 1 {
+                    fields.push(serde_json::json!({ "name": "Repeats", "value": notice.repeats.to_string(), "inline": true }));
+                }
+                serde_json::json!({
+                    "username": "ORACLE",
+                    "embeds": [{
+                        "title": kind.title(),
+                        "description": notice.text(),
+                        "color": kind.colour(),
+                        "timestamp": notice.at.to_rfc3339(),
+                        "fields": fields,
+                    }],
+                })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn deliver(&self, notice: &Notice) -> Result<()> {
+        self.http.post(&self.url).json(&self.payload(notice)).send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Deliver `notice`, retrying a failure twice with a growing pause.
+async fn deliver_with_retry(notifier: &dyn Notifier, notice: &Notice) {
+    let mut backoff = RETRY_BACKOFF;
+    for attempt in 1..=ATTEMPTS {
+        match notifier.deliver(notice).await {
+            Ok(()) => {
+                debug!(event = notice.event.kind().as_str(), attempt, "Notification delivered");
+                return;
+            }
+            Err(e) if attempt < ATTEMPTS => {
+                debug!(error = %e, attempt, "Notification failed — retrying");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            Err(e) => warn!(error = %e, event = notice.event.kind().as_str(), "Notification dropped after retries"),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rate limiting
+// ---------------------------------------------------------------------------
+
+/// Per-minute budget and repeat coalescing, on explicit instants.
+pub struct Throttle {
+    budget: TokenBucket,
+    window: Duration,
+    /// When each event key was last sent.
+    sent: HashMap<String, Instant>,
+    /// Held events per kind, oldest kind first: the latest event and how
+    /// many it stands for.
+    pending: Vec<(Event, u32)>,
+}
+
+impl Throttle {
+    pub fn new(max_per_minute: u32, window: Duration) -> Self {
+        Self { budget: TokenBucket::per_minute(max_per_minute), window, sent: HashMap::new(), pending: Vec::new() }
+    }
+
+    fn recently_sent(&self, event: &Event, now: Instant) -> bool {
+        self.sent.get(&event.key()).is_some_and(|&at| now.saturating_duration_since(at) < self.window)
+    }
+
+    /// Pass `event` on now, or hold it coalesced with its kind.
+    pub fn offer(&mut self, event: Event, now: Instant) -> Option<(Event, u32)> {
+        let kind = event.kind();
+        let held = self.pending.iter().position(|(e, _)| e.kind() == kind);
+        if held.is_none() && !self.recently_sent(&event, now) && self.budget.try_take_at(now) {
+            self.sent.insert(event.key(), now);
+            return Some((event, 1));
+        }
+        match held {
+            Some(i) => {
+                let (latest, count) = &mut self.pending[i];
+                *latest = event;
+                *count += 1;
+            }
+            None => self.pending.push((event, 1)),
+        }
+        None
+    }
+
+    /// Held notices whose repeat window has passed, as far as the budget
+    /// allows; with `force`, all of them regardless.
+    pub fn flush(&mut self, now: Instant, force: bool) -> Vec<(Event, u32)> {
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < self.pending.len() {
+            let ready = force || (!self.recently_sent(&self.pending[i].0, now) && self.budget.try_take_at(now));
+            if ready {
+                let (event, count) = self.pending.remove(i);
+                self.sent.insert(event.key(), now);
+                out.push((event, count));
+            } else {
+                i += 1;
+            }
+        }
+        out
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dispatcher
+// ---------------------------------------------------------------------------
+
+enum Message {
+    Event(Event),
+    /// Send everything held and wait for deliveries in flight.
+    Drain(oneshot::Sender<()>),
+}
+
+/// Handle to the notification worker. Clones share it; a disabled handle
+/// drops everything.
+#[derive(Clone, Default)]
+pub struct Notifications {
+    tx: Option<mpsc::Sender<Message>>,
+    events: Vec<EventKind>,
+}
+
+impl Notifications {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Notifications as configured: enabled, with a webhook URL given
+    /// directly or through `url_env`.
+    pub fn from_config(cfg: &NotificationsConfig) -> Self {
+        if !cfg.enabled {
+            return Self::disabled();
+        }
+        let url = cfg.url.clone().filter(|u| !u.trim().is_empty()).or_else(|| {
+            std::env::var(cfg.url_env.as_deref()?).ok().filter(|u| !u.trim().is_empty())
+        });
+        let Some(url) = url else {
+            warn!("[notifications] enabled without a webhook URL — notifications off");
+            return Self::disabled();
+        };
+        Self::spawn(Arc::new(WebhookNotifier::new(url, cfg.format)), cfg)
+    }
+
+    /// Start a worker delivering to `notifier`.
+    pub fn spawn(notifier: Arc<dyn Notifier>, cfg: &NotificationsConfig) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        let throttle = Throttle::new(cfg.max_per_minute.max(1), Duration::from_secs(cfg.coalesce_secs));
+        tokio::spawn(run_worker(notifier, throttle, rx));
+        Self { tx: Some(tx), events: cfg.events.clone() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.tx.is_some()
+    }
+
+    /// Queue `event` if its kind is wanted. Never waits; a full queue
+    /// drops it.
+    pub fn send(&self, event: Event) {
+        let Some(tx) = &self.tx else { return };
+        if !self.events.contains(&event.kind()) {
+            return;
+        }
+        if let Err(e) = tx.try_send(Message::Event(event)) {
+            debug!(error = %e, "Notification queue full — event dropped");
+        }
+    }
+
+    /// Send everything held back, waiting at most `grace` for delivery.
+    /// Call before exiting.
+    pub async fn drain(&self, grace: Duration) {
+        let Some(tx) = &self.tx else { return };
+        let (done, wait) = oneshot::channel();
+        if tx.send(Message::Drain(done)).await.is_err() {
+            return;
+        }
+        if tokio::time::timeout(grace, wait).await.is_err() {
+            warn!("Notifications still in flight at exit — abandoned");
+        }
+    }
+}
+
+async fn run_worker(notifier: Arc<dyn Notifier>, mut throttle: Throttle, mut rx: mpsc::Receiver<Message>) {
+    let mut deliveries = JoinSet::new();
+    let mut tick = tokio::time::interval(FLUSH_INTERVAL);
+    let send = |deliveries: &mut JoinSet<()>, (event, repeats): (Event, u32)| {
+        let notifier = Arc::clone(&notifier);
+        let notice = Notice { event, repeats, at: Utc::now() };
+        deliveries.spawn(async move { deliver_with_retry(&*notifier, &notice).await });
+    };
+    loop {
+        tokio::select! {
+            message = rx.recv() => match message {
+                Some(Message::Event(event)) => {
+                    if let Some(ready) = throttle.offer(event, Instant::now()) {
+                        send(&mut deliveries, ready);
+                    }
+                }
+                Some(Message::Drain(done)) => {
+                    for held in throttle.flush(Instant::now(), true) {
+                        send(&mut deliveries, held);
+                    }
+                    while deliveries.join_next().await.is_some() {}
+                    let _ = done.send(());
+                }
+                None => break,
+            },
+            _ = tick.tick() => {
+                for held in throttle.flush(Instant::now(), false) {
+                    send(&mut deliveries, held);
+                }
+            }
+            Some(_) = deliveries.join_next(), if !deliveries.is_empty() => {}
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scheduled events
+// ---------------------------------------------------------------------------
+
+/// Drawdown tier last notified, so each 10% tier is reported once on the
+/// way down and again only after a recovery below it.
+#[derive(Debug, Clone, Copy)]
+pub struct DrawdownTiers {
+    tier: u32,
+}
+
+impl DrawdownTiers {
+    /// Starting at `drawdown`'s tier, so a restart doesn't re-report it.
+    pub fn new(drawdown: Decimal) -> Self {
+        Self { tier: Self::tier_of(drawdown) }
+    }
+
+    fn tier_of(drawdown: Decimal) -> u32 {
+        (drawdown.max(Decimal::ZERO) * dec!(10)).floor().to_u32().unwrap_or(0)
+    }
+
+    /// The tier (in percent) `drawdown` newly reached, if it passed one.
+    pub fn crossed(&mut self, drawdown: Decimal) -> Option<u32> {
+        let tier = Self::tier_of(drawdown);
+        let crossed = tier > self.tier;
+        self.tier = tier;
+        (crossed && tier > 0).then_some(tier * 10)
+    }
+}
+
+/// Daily summary due once per UTC day, from `hour` on.
+#[derive(Debug, Clone, Copy)]
+pub struct DailySchedule {
+    hour: u32,
+    last: Option<NaiveDate>,
+}
+
+impl DailySchedule {
+    /// A schedule that starts tomorrow when `now` is already past `hour`.
+    pub fn new(hour: u32, now: DateTime<Utc>) -> Self {
+        let last = (now.hour() >= hour).then(|| now.date_naive());
+        Self { hour, last }
+    }
+
+    /// True once per day, at the first call from `hour` on.
+    pub fn due(&mut self, now: DateTime<Utc>) -> bool {
+        let today = now.date_naive();
+        if now.hour() < self.hour || self.last == Some(today) {
+            return false;
+        }
+        self.last = Some(today);
+        true
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use std::sync::Mutex;
+
+    fn failed(market: &str) -> Event {
+        Event::BetFailed { platform: "betfair".into(), market_id: market.into(), reason: "INSUFFICIENT_FUNDS".into() }
+    }
+
+    fn placed() -> Event {
+        Event::BetPlaced { platform: "manifold".into(), market_id: "m1".into(), side: Side::Yes, amount: dec!(4), edge_pct: 12.5 }
+    }
+
+    /// Records the bodies posted to it; answers 500 to the first `failures`.
+    #[derive(Clone, Default)]
+    struct Received {
+        bodies: Arc<Mutex<Vec<serde_json::Value>>>,
+        failures: Arc<Mutex<u32>>,
+    }
+
+    async fn mock_server(received: Received) -> String {
+        async fn hook(State(r): State<Received>, Json(body): Json<serde_json::Value>) -> StatusCode {
+            let mut failures = r.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return StatusCode::INTERNAL_SERVER_ERROR;
+            }
+            r.bodies.lock().unwrap().push(body);
+            StatusCode::NO_CONTENT
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route("/hook", post(hook)).with_state(received);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/hook")
+    }
+
+    fn config(format: WebhookFormat) -> NotificationsConfig {
+        NotificationsConfig { enabled: true, format, ..NotificationsConfig::default() }
+    }
+
+    #[tokio::test]
+    async fn test_json_and_discord_payloads_posted() {
+        let received = Received::default();
+        *received.failures.lock().unwrap() = 1;
+        let url = mock_server(received.clone()).await;
+
+        let json = Notifications::spawn(Arc::new(WebhookNotifier::new(url.clone(), WebhookFormat::Json)), &config(WebhookFormat::Json));
+        json.send(placed());
+        json.drain(Duration::from_secs(5)).await;
+        let discord = Notifications::spawn(Arc::new(WebhookNotifier::new(url, WebhookFormat::Discord)), &config(WebhookFormat::Discord));
+        discord.send(Event::Drawdown { tier_pct: 20, drawdown_pct: dec!(21.4), bankroll: dec!(78.6) });
+        discord.drain(Duration::from_secs(5)).await;
+
+        let bodies = received.bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 2, "first post retried after the 500");
+        let body = &bodies[0];
+        assert_eq!(body["event"], "bet_placed");
+        assert_eq!(body["repeats"], 1);
+        assert_eq!(body["data"]["market_id"], "m1");
+        assert_eq!(body["data"]["side"], "Yes");
+        assert_eq!(body["data"]["edge_pct"], 12.5);
+        assert_eq!(body["text"], "Bet placed: Yes 4.00 on manifold/m1 (edge 12.5%)");
+
+        let embed = &bodies[1]["embeds"][0];
+        assert_eq!(embed["title"], "Drawdown");
+        assert_eq!(embed["description"], "Drawdown passed 20%: 21.4% from peak, bankroll 78.60");
+        assert_eq!(embed["fields"][0]["name"], "Drawdown");
+        assert_eq!(embed["fields"][0]["value"], "21.4%");
+    }
+
+    #[tokio::test]
+    async fn test_bad_cycle_coalesced_under_rate_limit() {
+        let received = Received::default();
+        let url = mock_server(received.clone()).await;
+        let cfg = NotificationsConfig { max_per_minute: 2, ..config(WebhookFormat::Json) };
+        let notifications = Notifications::spawn(Arc::new(WebhookNotifier::new(url, WebhookFormat::Json)), &cfg);
+
+        for i in 0..50 {
+            notifications.send(failed(&format!("m{i}")));
+        }
+        notifications.drain(Duration::from_secs(5)).await;
+
+        let bodies = received.bodies.lock().unwrap();
+        let mut repeats: Vec<_> = bodies.iter().map(|b| b["repeats"].as_u64().unwrap()).collect();
+        repeats.sort();
+        assert_eq!(repeats, [1, 1, 48]);
+        let coalesced = bodies.iter().find(|b| b["repeats"] == 48).unwrap();
+        assert_eq!(coalesced["data"]["market_id"], "m49");
+        assert!(coalesced["text"].as_str().unwrap().ends_with("(+47 more bet_failed since the last notice)"));
+    }
+
+    #[tokio::test]
+    async fn test_filtered_events_not_sent() {
+        let received = Received::default();
+        let url = mock_server(received.clone()).await;
+        let cfg = NotificationsConfig { events: vec![EventKind::AgentDeath], ..config(WebhookFormat::Json) };
+        let notifications = Notifications::spawn(Arc::new(WebhookNotifier::new(url, WebhookFormat::Json)), &cfg);
+        notifications.send(placed());
+        notifications.send(Event::AgentDeath { bankroll: dec!(4), survival_threshold: dec!(5) });
+        notifications.drain(Duration::from_secs(5)).await;
+
+        let bodies = received.bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["event"], "agent_death");
+    }
+
+    #[test]
+    fn test_repeat_held_until_window_passes() {
+        let mut throttle = Throttle::new(60, Duration::from_secs(600));
+        let start = Instant::now();
+        assert_eq!(throttle.offer(failed("m1"), start), Some((failed("m1"), 1)));
+        // The same market failing again is held, as are further failures.
+        assert_eq!(throttle.offer(failed("m1"), start + Duration::from_secs(60)), None);
+        assert_eq!(throttle.offer(failed("m2"), start + Duration::from_secs(61)), None);
+        // Other kinds are unaffected.
+        assert!(throttle.offer(placed(), start + Duration::from_secs(62)).is_some());
+
+        // Latest held event is m2, whose key was never sent.
+        assert_eq!(throttle.flush(start + Duration::from_secs(120), false), [(failed("m2"), 2)]);
+        assert!(throttle.flush(start + Duration::from_secs(130), false).is_empty());
+    }
+
+    #[test]
+    fn test_drawdown_tiers_reported_once() {
+        let mut tiers = DrawdownTiers::new(dec!(0.05));
+        assert_eq!(tiers.crossed(dec!(0.08)), None);
+        assert_eq!(tiers.crossed(dec!(0.12)), Some(10));
+        assert_eq!(tiers.crossed(dec!(0.15)), None);
+        assert_eq!(tiers.crossed(dec!(0.31)), Some(30));
+        // Recovering below a tier re-arms it.
+        assert_eq!(tiers.crossed(dec!(0.25)), None);
+        assert_eq!(tiers.crossed(dec!(0.30)), Some(30));
+    }
+
+    #[test]
+    fn test_daily_schedule_once_per_day_from_hour() {
+        let at = |d: u32, h: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap().and_hms_opt(h, 0, 0).unwrap().and_utc();
+        let mut schedule = DailySchedule::new(21, at(16, 9));
+        assert!(!schedule.due(at(16, 20)));
+        assert!(schedule.due(at(16, 21)));
+        assert!(!schedule.due(at(16, 23)));
+        assert!(schedule.due(at(17, 22)));
+        // Started past the hour: nothing until tomorrow.
+        let mut late = DailySchedule::new(21, at(16, 22));
+        assert!(!late.due(at(16, 23)));
+        assert!(late.due(at(17, 21)));
+    }
+}
@@ -7,10 +7,17 @@
 //! Betting API base: https://api.betfair.com/exchange/betting/rest/v1.0/
 //! Account API base: https://api.betfair.com/exchange/account/rest/v1.0/
 //! Auth: https://identitysso.betfair.com/api/login
+//! Session keep-alive: https://identitysso.betfair.com/api/keepAlive
 //!
 //! Auth requires: App Key + session token (obtained via username/password login).
 //! Headers: `X-Application: {app_key}`, `X-Authentication: {session_token}`
 //!
+//! Sessions expire after [`SESSION_TIMEOUT`] unless kept alive. The client
+//! logs in on first use, extends a session close to expiry before using it,
+//! and can keep it alive on an interval in the background
+//! ([`BetfairClient::with_keep_alive`]). A session Betfair rejects with a
+//! 401 is replaced by a single login however many calls hit the 401.
+//!
 //! Betfair uses decimal odds and a back/lay model:
 //! - Back = bet FOR an outcome (like YES)
 //! - Lay = bet AGAINST an outcome (like NO)
@@ -19,6 +26,9 @@
 //! Commission: Betfair charges a market rate commission on net winnings
 //! (typically 5%, varies by market and jurisdiction).
 
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -30,9 +40,12 @@ use secrecy::{ExposeSecret, Secret};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
+use super::ladder::{PriceLadder, Rounding};
+use super::preflight::{CheckKind, PreflightLimits, PreflightReport};
 use super::PredictionPlatform;
 use crate::types::{
-    d, CrossReferences, LiquidityInfo, Market, MarketCategory, Position, Side, TradeReceipt,
+    d, CrossReferences, LiquidityInfo, Market, MarketCategory, OracleError, Position, Side,
+    TradeReceipt,
 };
 
 // ---------------------------------------------------------------------------
@@ -40,10 +53,17 @@ use crate::types::{
 // ---------------------------------------------------------------------------
 
 const AUTH_URL: &str = "https://identitysso.betfair.com/api/login";
+const KEEP_ALIVE_URL: &str = "https://identitysso.betfair.com/api/keepAlive";
 const BETTING_URL: &str = "https://api.betfair.com/exchange/betting/rest/v1.0";
 const ACCOUNT_URL: &str = "https://api.betfair.com/exchange/account/rest/v1.0";
 const PLATFORM_NAME: &str = "betfair";
 
+/// Session lifetime on the international exchange. A keep-alive restarts it.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// A session in use is extended once it is this close to expiry.
+const SESSION_REFRESH_MARGIN: Duration = Duration::from_secs(60 * 60);
+
 /// Maximum markets to fetch per catalogue request.
 const DEFAULT_FETCH_LIMIT: u32 = 200;
 
@@ -53,6 +73,12 @@ const DEFAULT_COMMISSION_RATE: Decimal = dec!(0.05);
 /// Minimum total matched on a market for it to be considered liquid.
 const MIN_TOTAL_MATCHED: f64 = 100.0;
 
+/// placeOrders error codes meaning the market is not accepting bets.
+const CLOSED_MARKET_ERRORS: &[&str] = &["MARKET_SUSPENDED", "MARKET_NOT_OPEN_FOR_BETTING"];
+
+/// placeOrders error code for a repeated `customerRef`.
+const DUPLICATE_ORDER_ERROR: &str = "DUPLICATE_TRANSACTION";
+
 // ---------------------------------------------------------------------------
 // Betfair API types
 // ---------------------------------------------------------------------------
@@ -66,6 +92,14 @@ struct LoginResponse {
     login_status: String,
 }
 
+/// Response from the SSO keep-alive endpoint.
+#[derive(Debug, Deserialize)]
+struct KeepAliveResponse {
+    status: String,
+    #[serde(default)]
+    error: String,
+}
+
 /// Event type (top-level sport/category).
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -321,99 +355,120 @@ struct TimeRange {
 // Client
 // ---------------------------------------------------------------------------
 
-/// Betfair Exchange platform client.
-pub struct BetfairClient {
-    http: Client,
-    app_key: Secret<String>,
-    session_token: tokio::sync::RwLock<Option<Secret<String>>>,
-    username: Secret<String>,
-    password: Secret<String>,
+/// Betfair API locations.
+#[derive(Debug, Clone)]
+struct Endpoints {
+    auth: String,
+    keep_alive: String,
+    betting: String,
+    account: String,
 }
 
-impl BetfairClient {
-    /// Create a new Betfair client.
-    ///
-    /// Reads credentials from environment variables:
-    /// - `BETFAIR_APP_KEY` — application key
-    /// - `BETFAIR_USERNAME` — account username
-    /// - `BETFAIR_PASSWORD` — account password
-    pub fn new() -> Result<Self> {
-        let app_key = std::env::var("BETFAIR_APP_KEY")
-            .context("BETFAIR_APP_KEY environment variable not set")?;
-        let username = std::env::var("BETFAIR_USERNAME")
-            .context("BETFAIR_USERNAME environment variable not set")?;
-        let password = std::env::var("BETFAIR_PASSWORD")
-            .context("BETFAIR_PASSWORD environment variable not set")?;
-
-        let http = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .user_agent("ORACLE/0.1.0 (prediction-market-agent)")
-            .build()
-            .context("Failed to build HTTP client for Betfair")?;
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            auth: AUTH_URL.to_string(),
+            keep_alive: KEEP_ALIVE_URL.to_string(),
+            betting: BETTING_URL.to_string(),
+            account: ACCOUNT_URL.to_string(),
+        }
+    }
+}
 
-        Ok(Self {
-            http,
-            app_key: Secret::new(app_key),
-            session_token: tokio::sync::RwLock::new(None),
-            username: Secret::new(username),
-            password: Secret::new(password),
-        })
+impl Endpoints {
+    /// The same paths on another host.
+    fn at(base: &str) -> Self {
+        let base = base.trim_end_matches('/');
+        Self {
+            auth: format!("{base}/api/login"),
+            keep_alive: format!("{base}/api/keepAlive"),
+            betting: format!("{base}/exchange/betting/rest/v1.0"),
+            account: format!("{base}/exchange/account/rest/v1.0"),
+        }
     }
+}
 
-    /// Create a client with explicit credentials (for testing).
-    pub fn with_credentials(
-        app_key: String,
-        username: String,
-        password: String,
-    ) -> Result<Self> {
-        let http = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .user_agent("ORACLE/0.1.0 (prediction-market-agent)")
-            .build()
-            .context("Failed to build HTTP client for Betfair")?;
+/// A logged-in session and when Betfair will drop it.
+struct Session {
+    token: Secret<String>,
+    expires_at: Instant,
+}
 
-        Ok(Self {
-            http,
-            app_key: Secret::new(app_key),
-            session_token: tokio::sync::RwLock::new(None),
-            username: Secret::new(username),
-            password: Secret::new(password),
-        })
+impl Session {
+    fn new(token: String, now: Instant) -> Self {
+        Self { token: Secret::new(token), expires_at: now + SESSION_TIMEOUT }
+    }
+
+    /// Close enough to expiry that it should be extended before use.
+    fn needs_refresh(&self, now: Instant) -> bool {
+        now + SESSION_REFRESH_MARGIN >= self.expires_at
     }
+}
 
-    // -- Authentication ----------------------------------------------------
+/// Credentials and the current session, shared with the keep-alive task.
+struct Auth {
+    http: Client,
+    endpoints: Endpoints,
+    app_key: Secret<String>,
+    username: Secret<String>,
+    password: Secret<String>,
+    session: tokio::sync::RwLock<Option<Session>>,
+}
 
+impl Auth {
     /// Get a valid session token, logging in if necessary.
     ///
-    /// Uses double-checked locking with a tokio async RwLock to ensure only
-    /// one concurrent caller triggers a login request.
+    /// Single-flight: the caller that finds the session missing or near
+    /// expiry extends it or logs in while holding the write lock, and
+    /// every concurrent caller waits on that lock and then uses the
+    /// session it left, rather than logging in again.
     async fn ensure_session(&self) -> Result<String> {
-        // Fast path: token already present
+        // Fast path: a session with time left
         {
-            let guard = self.session_token.read().await;
-            if let Some(ref token) = *guard {
-                return Ok(token.expose_secret().clone());
+            let guard = self.session.read().await;
+            if let Some(session) = guard.as_ref().filter(|s| !s.needs_refresh(Instant::now())) {
+                return Ok(session.token.expose_secret().clone());
             }
         }
-        // Slow path: acquire write lock, recheck, then login if still absent
-        let mut guard = self.session_token.write().await;
-        if let Some(ref token) = *guard {
-            return Ok(token.expose_secret().clone());
+        // Slow path: acquire write lock, recheck, then refresh if still needed
+        let mut guard = self.session.write().await;
+        if let Some(session) = guard.as_mut() {
+            if !session.needs_refresh(Instant::now()) {
+                return Ok(session.token.expose_secret().clone());
+            }
+            // Near expiry: extend it rather than log in again.
+            let token = session.token.expose_secret().clone();
+            match self.keep_alive(&token).await {
+                Ok(()) => {
+                    *session = Session::new(token.clone(), Instant::now());
+                    debug!("Betfair session extended");
+                    return Ok(token);
+                }
+                Err(e) => warn!(error = %e, "Betfair session could not be extended, re-authenticating..."),
+            }
         }
-        // Only one caller reaches here — perform login while holding the lock
         let token = self.fetch_session_token().await?;
-        *guard = Some(Secret::new(token.clone()));
+        *guard = Some(Session::new(token.clone(), Instant::now()));
         info!("Betfair authentication successful");
         Ok(token)
     }
 
+    /// Drop the session if it is still the one Betfair rejected. A
+    /// concurrent caller may already have replaced it.
+    async fn invalidate(&self, rejected: &str) {
+        let mut guard = self.session.write().await;
+        if guard.as_ref().is_some_and(|s| s.token.expose_secret() == rejected) {
+            *guard = None;
+        }
+    }
+
     /// Perform the raw HTTP login and return the session token string.
     async fn fetch_session_token(&self) -> Result<String> {
         info!("Authenticating with Betfair...");
 
         let resp = self
             .http
-            .post(AUTH_URL)
+            .post(&self.endpoints.auth)
             .header("X-Application", self.app_key.expose_secret().as_str())
             .header("Accept", "application/json")
             .form(&[
@@ -444,71 +499,183 @@ impl BetfairClient {
             .context("Betfair login succeeded but no session token returned")
     }
 
-    // -- API helpers -------------------------------------------------------
-
-    /// Make an authenticated POST to the Betfair Betting API.
-    async fn betting_api<T: serde::de::DeserializeOwned>(
-        &self,
-        endpoint: &str,
-        body: &serde_json::Value,
-    ) -> Result<T> {
-        let token = self.ensure_session().await?;
-        let url = format!("{BETTING_URL}/{endpoint}/");
-
-        debug!(url = %url, "Betfair API request");
-
+    /// Ask Betfair to restart the session's timeout.
+    async fn keep_alive(&self, token: &str) -> Result<()> {
         let resp = self
             .http
-            .post(&url)
+            .post(&self.endpoints.keep_alive)
             .header("X-Application", self.app_key.expose_secret().as_str())
-            .header("X-Authentication", &token)
-            .header("Content-Type", "application/json")
-            .json(body)
+            .header("X-Authentication", token)
+            .header("Accept", "application/json")
             .send()
             .await
-            .with_context(|| format!("Betfair {endpoint} request failed"))?;
+            .context("Betfair keepAlive request failed")?;
+        let alive: KeepAliveResponse = resp
+            .json()
+            .await
+            .context("Failed to parse Betfair keepAlive response")?;
+        anyhow::ensure!(alive.status == "SUCCESS", "Betfair keepAlive rejected: {}", alive.error);
+        Ok(())
+    }
 
-        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-            // Session expired — clear token and retry once
-            {
-                let mut guard = self.session_token.write().await;
-                *guard = None;
+    /// Extend the current session, logging in again if Betfair no longer
+    /// accepts it (expired or revoked).
+    async fn refresh_session(&self) -> Result<()> {
+        let token = self.ensure_session().await?;
+        match self.keep_alive(&token).await {
+            Ok(()) => {
+                let mut guard = self.session.write().await;
+                if let Some(session) = guard.as_mut().filter(|s| s.token.expose_secret() == &token) {
+                    session.expires_at = Instant::now() + SESSION_TIMEOUT;
+                }
+                Ok(())
             }
-            warn!("Betfair session expired, re-authenticating...");
-            let token = self.ensure_session().await?;
+            Err(e) => {
+                warn!(error = %e, "Betfair session rejected, re-authenticating...");
+                self.invalidate(&token).await;
+                self.ensure_session().await.map(|_| ())
+            }
+        }
+    }
+}
+
+/// Betfair Exchange platform client.
+pub struct BetfairClient {
+    auth: Arc<Auth>,
+}
+
+impl BetfairClient {
+    /// Create a new Betfair client.
+    ///
+    /// Reads credentials from environment variables:
+    /// - `BETFAIR_APP_KEY` — application key
+    /// - `BETFAIR_USERNAME` — account username
+    /// - `BETFAIR_PASSWORD` — account password
+    pub fn new() -> Result<Self> {
+        let app_key = std::env::var("BETFAIR_APP_KEY")
+            .context("BETFAIR_APP_KEY environment variable not set")?;
+        let username = std::env::var("BETFAIR_USERNAME")
+            .context("BETFAIR_USERNAME environment variable not set")?;
+        let password = std::env::var("BETFAIR_PASSWORD")
+            .context("BETFAIR_PASSWORD environment variable not set")?;
+
+        Self::with_credentials(app_key, username, password)
+    }
+
+    /// Create a client with explicit credentials (for testing).
+    pub fn with_credentials(
+        app_key: String,
+        username: String,
+        password: String,
+    ) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .user_agent("ORACLE/0.1.0 (prediction-market-agent)")
+            .build()
+            .context("Failed to build HTTP client for Betfair")?;
 
-            let resp = self
+        Ok(Self {
+            auth: Arc::new(Auth {
+                http,
+                endpoints: Endpoints::default(),
+                app_key: Secret::new(app_key),
+                username: Secret::new(username),
+                password: Secret::new(password),
+                session: tokio::sync::RwLock::new(None),
+            }),
+        })
+    }
+
+    /// Send every request to `base` (a local server in tests) instead of
+    /// Betfair's hosts.
+    pub fn with_base_url(self, base: &str) -> Self {
+        let Auth { http, app_key, username, password, session, .. } =
+            Arc::into_inner(self.auth).expect("no keep-alive task before the base URL is set");
+        let endpoints = Endpoints::at(base);
+        Self { auth: Arc::new(Auth { http, endpoints, app_key, username, password, session }) }
+    }
+
+    /// Keep the session alive every `interval` in the background, for as
+    /// long as the client is alive. Must be called inside a tokio runtime.
+    /// A client that has not logged in yet is left alone.
+    pub fn with_keep_alive(self, interval: Duration) -> Self {
+        let auth = Arc::downgrade(&self.auth);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(auth) = auth.upgrade() else { break };
+                if auth.session.read().await.is_none() {
+                    continue;
+                }
+                if let Err(e) = auth.refresh_session().await {
+                    warn!(error = %e, "Betfair keep-alive failed");
+                }
+            }
+        });
+        self
+    }
+
+    // -- API helpers -------------------------------------------------------
+
+    /// POST `body` to `url` with the session token, re-authenticating and
+    /// retrying once if Betfair rejects the session. `what` names the call
+    /// in errors.
+    async fn authenticated_post<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        what: &str,
+    ) -> Result<T> {
+        let send = |token: String| {
+            self.auth
                 .http
-                .post(&url)
-                .header("X-Application", self.app_key.expose_secret().as_str())
-                .header("X-Authentication", &token)
+                .post(url)
+                .header("X-Application", self.auth.app_key.expose_secret().as_str())
+                .header("X-Authentication", token)
                 .header("Content-Type", "application/json")
                 .json(body)
                 .send()
-                .await
-                .with_context(|| format!("Betfair {endpoint} retry failed"))?;
+        };
 
-            if !resp.status().is_success() {
-                let status = resp.status();
-                let body_text = resp.text().await.unwrap_or_default();
-                anyhow::bail!("Betfair {endpoint} error {status}: {body_text}");
-            }
+        let token = self.auth.ensure_session().await?;
+        let mut resp = send(token.clone())
+            .await
+            .with_context(|| format!("Betfair {what} request failed"))?;
 
-            return resp
-                .json()
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+            // Session expired — drop it (unless a concurrent call already
+            // has) and retry once on the next one
+            warn!("Betfair session expired, re-authenticating...");
+            self.auth.invalidate(&token).await;
+            let token = self.auth.ensure_session().await?;
+            resp = send(token)
                 .await
-                .with_context(|| format!("Failed to parse Betfair {endpoint} response"));
+                .with_context(|| format!("Betfair {what} retry failed"))?;
         }
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body_text = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Betfair {endpoint} error {status}: {body_text}");
+            anyhow::bail!("Betfair {what} error {status}: {body_text}");
         }
 
         resp.json()
             .await
-            .with_context(|| format!("Failed to parse Betfair {endpoint} response"))
+            .with_context(|| format!("Failed to parse Betfair {what} response"))
+    }
+
+    /// Make an authenticated POST to the Betfair Betting API.
+    async fn betting_api<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &serde_json::Value,
+    ) -> Result<T> {
+        let url = format!("{}/{endpoint}/", self.auth.endpoints.betting);
+        debug!(url = %url, "Betfair API request");
+        self.authenticated_post(&url, body, endpoint).await
     }
 
     /// Make an authenticated POST to the Betfair Account API.
@@ -517,29 +684,8 @@ impl BetfairClient {
         endpoint: &str,
         body: &serde_json::Value,
     ) -> Result<T> {
-        let token = self.ensure_session().await?;
-        let url = format!("{ACCOUNT_URL}/{endpoint}/");
-
-        let resp = self
-            .http
-            .post(&url)
-            .header("X-Application", self.app_key.expose_secret().as_str())
-            .header("X-Authentication", &token)
-            .header("Content-Type", "application/json")
-            .json(body)
-            .send()
-            .await
-            .with_context(|| format!("Betfair account {endpoint} request failed"))?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body_text = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Betfair account {endpoint} error {status}: {body_text}");
-        }
-
-        resp.json()
-            .await
-            .with_context(|| format!("Failed to parse Betfair account {endpoint} response"))
+        let url = format!("{}/{endpoint}/", self.auth.endpoints.account);
+        self.authenticated_post(&url, body, &format!("account {endpoint}")).await
     }
 
     // -- Market fetching ---------------------------------------------------
@@ -576,7 +722,8 @@ impl BetfairClient {
             "sort": "MAXIMUM_TRADED"
         });
 
-        self.betting_api("listMarketCatalogue", &body).await
+        let entries = self.betting_api("listMarketCatalogue", &body).await?;
+        Ok(super::parse_entries("betfair/listMarketCatalogue", entries).0)
     }
 
     /// Fetch live prices for a list of market IDs.
@@ -591,7 +738,8 @@ impl BetfairClient {
             "matchProjection": "ROLLED_UP_BY_AVG_PRICE"
         });
 
-        self.betting_api("listMarketBook", &body).await
+        let entries = self.betting_api("listMarketBook", &body).await?;
+        Ok(super::parse_entries("betfair/listMarketBook", entries).0)
     }
 
     // -- Conversion helpers ------------------------------------------------
@@ -709,6 +857,15 @@ impl BetfairClient {
             resolution_criteria: String::new(),
             url,
             cross_refs: CrossReferences::default(),
+            // All markets on one fixture (Match Odds, Over/Under, BTTS, ...)
+            // share the event id and move together.
+            event_group: catalogue
+                .event
+                .as_ref()
+                .and_then(|e| e.id.as_deref())
+                .map(|id| format!("betfair:{id}")),
+                facts: None,
+                tags: Vec::new(),
         })
     }
 
@@ -780,149 +937,98 @@ impl BetfairClient {
 
         best_id
     }
-}
 
-// ---------------------------------------------------------------------------
-// Auto-exit helpers (non-trait, public methods)
-// ---------------------------------------------------------------------------
+    fn rounding_for(side: Side) -> Rounding {
+        match side {
+            Side::Yes => Rounding::Up,
+            Side::No => Rounding::Down,
+        }
+    }
 
-impl BetfairClient {
-    /// Get the current best available back price (decimal odds) for the
-    /// favourite runner in a market. Used by the auto-exit engine to compute
-    /// unrealized P&L on open positions.
-    ///
-    /// Returns `None` if the market has no active runners or no back offers.
-    pub async fn get_best_back_odds(&self, market_id: &str) -> Result<Option<Decimal>> {
-        let books = self.fetch_market_books(&[market_id.to_string()]).await?;
-        let book = match books.first() {
-            Some(b) => b,
-            None => return Ok(None),
+    /// Snap a limit price onto the odds ladder toward the passive side.
+    /// Off-ladder prices are rejected by Betfair, so none are ever sent.
+    fn order_price(side: Side, price: f64) -> Result<f64> {
+        let price = Decimal::from_f64(price)
+            .map(|p| p.round_dp(4))
+            .with_context(|| format!("Invalid limit price {price}"))?;
+        let snapped = PriceLadder::BETFAIR.snap(price, Self::rounding_for(side));
+        anyhow::ensure!(PriceLadder::BETFAIR.contains(snapped), "Limit price {snapped} is off the Betfair ladder");
+        if snapped != price {
+            debug!(from = %price, to = %snapped, side = ?side, "Snapped limit price to Betfair ladder");
+        }
+        snapped.to_f64().context("Limit price out of range")
+    }
+
+    /// Bound the best available `odds` by `max_price`, the most the bet may
+    /// pay for its outcome's probability: a BACK at no shorter than
+    /// `1 / max_price`, a LAY at no longer than `1 / (1 − max_price)`. A
+    /// bound past the best price leaves the order unmatched until it lapses.
+    fn protected_odds(side: Side, odds: f64, max_price: Option<Decimal>) -> f64 {
+        let Some(limit) = max_price.and_then(|p| p.to_f64()) else {
+            return odds;
         };
+        match side {
+            Side::Yes if limit > 0.0 => odds.max(1.0 / limit),
+            Side::No if limit < 1.0 => odds.min(1.0 / (1.0 - limit)),
+            _ => odds,
+        }
+    }
 
-        let mut best_back: Option<f64> = None;
-        for runner in &book.runners {
-            if runner.status.as_deref() != Some("ACTIVE") && runner.status.is_some() {
-                continue;
-            }
-            if let Some(ref ex) = runner.ex {
-                if let Some(back) = ex.available_to_back.first() {
-                    if best_back.is_none() || back.price < best_back.unwrap() {
-                        best_back = Some(back.price);
-                    }
-                }
-            }
+    /// An idempotency key as a `customerRef`: hyphens dropped to fit
+    /// Betfair's 32 characters.
+    fn customer_ref(key: &str) -> String {
+        key.chars().filter(|c| *c != '-').take(32).collect()
+    }
+
+    /// Book status if the market is not open for betting (SUSPENDED/CLOSED).
+    fn closed_book_status(book: &MarketBook) -> Option<&str> {
+        match book.status.as_deref() {
+            Some(s @ ("SUSPENDED" | "CLOSED")) => Some(s),
+            _ => None,
         }
+    }
 
-        Ok(best_back.map(d))
+    /// Closed-market error code from a placeOrders response, checking the
+    /// report-level code first and then the first instruction report.
+    fn closed_market_error(resp: &PlaceOrdersResponse) -> Option<&str> {
+        resp.error_code
+            .as_deref()
+            .or_else(|| resp.instruction_reports.first().and_then(|r| r.error_code.as_deref()))
+            .filter(|code| CLOSED_MARKET_ERRORS.contains(code))
     }
 
-    /// Get the total AUD liquidity available on the lay side for a market.
+    /// Place a bet on a Betfair market.
     ///
-    /// The auto-exit engine requires available lay liquidity of at least 80%
-    /// of the closing stake before placing a hedge bet, preventing partial
-    /// fills that would leave an unbalanced position.
-    pub async fn get_available_liquidity(&self, market_id: &str) -> Result<Decimal> {
+    /// Converts ORACLE Side::Yes/No to Betfair BACK/LAY on the favourite runner.
+    /// Uses a LIMIT order at the current best available price, bounded by
+    /// `max_price` (see [`Self::protected_odds`]). A `customer_ref` makes
+    /// the order idempotent: Betfair rejects a repeat of it as
+    /// `DUPLICATE_TRANSACTION`.
+    async fn place_order(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        customer_ref: Option<&str>,
+        max_price: Option<Decimal>,
+    ) -> Result<TradeReceipt> {
+        // Get current market book to find the best price and selection
         let books = self.fetch_market_books(&[market_id.to_string()]).await?;
-        let book = match books.first() {
-            Some(b) => b,
-            None => return Ok(Decimal::ZERO),
-        };
-
-        let total_lay: f64 = book
-            .runners
-            .iter()
-            .filter(|r| r.status.as_deref() != Some("REMOVED"))
-            .filter_map(|r| r.ex.as_ref())
-            .flat_map(|ex| &ex.available_to_lay)
-            .map(|p| p.size)
-            .sum();
+        let book = books
+            .first()
+            .context("No market book returned for order placement")?;
 
-        Ok(d(total_lay))
-    }
-}
+        if let Some(status) = Self::closed_book_status(book) {
+            return Err(OracleError::MarketClosed {
+                platform: PLATFORM_NAME.to_string(),
+                market_id: market_id.to_string(),
+                reason: format!("market {status}"),
+            }
+            .into());
+        }
 
-// ---------------------------------------------------------------------------
-// PredictionPlatform trait implementation
-// ---------------------------------------------------------------------------
-
-#[async_trait]
-impl PredictionPlatform for BetfairClient {
-    /// Fetch active markets from Betfair Exchange.
-    ///
-    /// Queries market catalogues for target event types, then fetches
-    /// live prices for the most liquid markets.
-    async fn fetch_markets(&self) -> Result<Vec<Market>> {
-        info!("Scanning Betfair Exchange for active markets...");
-
-        // 1. Fetch market catalogues across target event types
-        let event_type_ids = Self::target_event_type_ids();
-        let catalogues = self.fetch_market_catalogues(&event_type_ids).await?;
-
-        info!(count = catalogues.len(), "Betfair market catalogues fetched");
-
-        if catalogues.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // 2. Filter to markets with meaningful liquidity
-        let liquid_catalogues: Vec<_> = catalogues
-            .into_iter()
-            .filter(|c| c.total_matched.unwrap_or(0.0) >= MIN_TOTAL_MATCHED)
-            .collect();
-
-        // 3. Fetch market books (prices) in batches of 40 (API limit)
-        let market_ids: Vec<String> = liquid_catalogues
-            .iter()
-            .map(|c| c.market_id.clone())
-            .collect();
-
-        let mut all_books = Vec::new();
-        for chunk in market_ids.chunks(40) {
-            match self.fetch_market_books(&chunk.to_vec()).await {
-                Ok(books) => all_books.extend(books),
-                Err(e) => {
-                    warn!(error = %e, "Failed to fetch market book batch, continuing");
-                }
-            }
-        }
-
-        // 4. Build index of books by market ID for fast lookup
-        let book_index: std::collections::HashMap<String, &MarketBook> = all_books
-            .iter()
-            .map(|b| (b.market_id.clone(), b))
-            .collect();
-
-        // 5. Convert to ORACLE Market type
-        let markets: Vec<Market> = liquid_catalogues
-            .iter()
-            .filter_map(|c| {
-                let book = book_index.get(&c.market_id).copied();
-                Self::to_oracle_market(c, book)
-            })
-            .collect();
-
-        info!(total = markets.len(), "Betfair scan complete");
-        Ok(markets)
-    }
-
-    /// Place a bet on a Betfair market.
-    ///
-    /// Converts ORACLE Side::Yes/No to Betfair BACK/LAY on the favourite runner.
-    /// Uses a LIMIT order at the current best available price.
-    async fn place_bet(
-        &self,
-        market_id: &str,
-        side: Side,
-        amount: Decimal,
-    ) -> Result<TradeReceipt> {
-        // Get current market book to find the best price and selection
-        let books = self.fetch_market_books(&[market_id.to_string()]).await?;
-        let book = books
-            .first()
-            .context("No market book returned for order placement")?;
-
-        let selection_id = Self::favourite_selection_id(book)
-            .context("No active runner found for order placement")?;
+        let selection_id = Self::favourite_selection_id(book)
+            .context("No active runner found for order placement")?;
 
         // Determine Betfair side and price
         let (bf_side, price) = match side {
@@ -952,9 +1058,10 @@ impl PredictionPlatform for BetfairClient {
             }
         };
 
+        let price = Self::order_price(side, Self::protected_odds(side, price, max_price))?;
         let amount_f64 = amount.to_f64().unwrap_or(0.0);
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "marketId": market_id,
             "instructions": [{
                 "orderType": "LIMIT",
@@ -967,8 +1074,26 @@ impl PredictionPlatform for BetfairClient {
                 }
             }]
         });
+        if let Some(customer_ref) = customer_ref {
+            body["customerRef"] = customer_ref.into();
+        }
 
-        let resp: PlaceOrdersResponse = self.betting_api("placeOrders", &body).await?;
+        let raw: serde_json::Value = self.betting_api("placeOrders", &body).await?;
+        let resp: PlaceOrdersResponse = serde_json::from_value(raw.clone())
+            .context("Failed to parse placeOrders response")?;
+
+        if let Some(code) = Self::closed_market_error(&resp) {
+            return Err(OracleError::MarketClosed {
+                platform: PLATFORM_NAME.to_string(),
+                market_id: market_id.to_string(),
+                reason: code.to_string(),
+            }
+            .into());
+        }
+
+        if let (Some(key), Some(DUPLICATE_ORDER_ERROR)) = (customer_ref, resp.error_code.as_deref()) {
+            return Err(OracleError::DuplicateOrder { platform: PLATFORM_NAME.to_string(), key: key.to_string() }.into());
+        }
 
         // Check for API-level errors
         if let Some(ref error_code) = resp.error_code {
@@ -1025,8 +1150,181 @@ impl PredictionPlatform for BetfairClient {
             fees,
             timestamp,
             currency: "AUD".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: Some(raw),
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         })
     }
+}
+
+// ---------------------------------------------------------------------------
+// Auto-exit helpers (non-trait, public methods)
+// ---------------------------------------------------------------------------
+
+impl BetfairClient {
+    /// Get the current best available back price (decimal odds) for the
+    /// favourite runner in a market. Used by the auto-exit engine to compute
+    /// unrealized P&L on open positions.
+    ///
+    /// Returns `None` if the market has no active runners or no back offers.
+    pub async fn get_best_back_odds(&self, market_id: &str) -> Result<Option<Decimal>> {
+        let books = self.fetch_market_books(&[market_id.to_string()]).await?;
+        let book = match books.first() {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+
+        let mut best_back: Option<f64> = None;
+        for runner in &book.runners {
+            if runner.status.as_deref() != Some("ACTIVE") && runner.status.is_some() {
+                continue;
+            }
+            if let Some(ref ex) = runner.ex {
+                if let Some(back) = ex.available_to_back.first() {
+                    if best_back.is_none() || back.price < best_back.unwrap() {
+                        best_back = Some(back.price);
+                    }
+                }
+            }
+        }
+
+        Ok(best_back.map(d))
+    }
+
+    /// Get the total AUD liquidity available on the lay side for a market.
+    ///
+    /// The auto-exit engine requires available lay liquidity of at least 80%
+    /// of the closing stake before placing a hedge bet, preventing partial
+    /// fills that would leave an unbalanced position.
+    pub async fn get_available_liquidity(&self, market_id: &str) -> Result<Decimal> {
+        let books = self.fetch_market_books(&[market_id.to_string()]).await?;
+        let book = match books.first() {
+            Some(b) => b,
+            None => return Ok(Decimal::ZERO),
+        };
+
+        let total_lay: f64 = book
+            .runners
+            .iter()
+            .filter(|r| r.status.as_deref() != Some("REMOVED"))
+            .filter_map(|r| r.ex.as_ref())
+            .flat_map(|ex| &ex.available_to_lay)
+            .map(|p| p.size)
+            .sum();
+
+        Ok(d(total_lay))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PredictionPlatform trait implementation
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl PredictionPlatform for BetfairClient {
+    /// Fetch active markets from Betfair Exchange.
+    ///
+    /// Queries market catalogues for target event types, then fetches
+    /// live prices for the most liquid markets.
+    async fn fetch_markets(&self) -> Result<Vec<Market>> {
+        info!("Scanning Betfair Exchange for active markets...");
+
+        // 1. Fetch market catalogues across target event types
+        let event_type_ids = Self::target_event_type_ids();
+        let catalogues = self.fetch_market_catalogues(&event_type_ids).await?;
+
+        info!(count = catalogues.len(), "Betfair market catalogues fetched");
+
+        if catalogues.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 2. Filter to markets with meaningful liquidity
+        let liquid_catalogues: Vec<_> = catalogues
+            .into_iter()
+            .filter(|c| c.total_matched.unwrap_or(0.0) >= MIN_TOTAL_MATCHED)
+            .collect();
+
+        // 3. Fetch market books (prices) in batches of 40 (API limit)
+        let market_ids: Vec<String> = liquid_catalogues
+            .iter()
+            .map(|c| c.market_id.clone())
+            .collect();
+
+        let mut all_books = Vec::new();
+        for chunk in market_ids.chunks(40) {
+            match self.fetch_market_books(&chunk.to_vec()).await {
+                Ok(books) => all_books.extend(books),
+                Err(e) => {
+                    warn!(error = %e, "Failed to fetch market book batch, continuing");
+                }
+            }
+        }
+
+        // 4. Build index of books by market ID for fast lookup
+        let book_index: std::collections::HashMap<String, &MarketBook> = all_books
+            .iter()
+            .map(|b| (b.market_id.clone(), b))
+            .collect();
+
+        // 5. Convert to ORACLE Market type
+        let markets: Vec<Market> = liquid_catalogues
+            .iter()
+            .filter_map(|c| {
+                let book = book_index.get(&c.market_id).copied();
+                Self::to_oracle_market(c, book)
+            })
+            .collect();
+
+        info!(total = markets.len(), "Betfair scan complete");
+        Ok(markets)
+    }
+
+    /// Place a bet on a Betfair market (see [`BetfairClient::place_order`]).
+    async fn place_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+    ) -> Result<TradeReceipt> {
+        self.place_order(market_id, side, amount, None, None).await
+    }
+
+    fn supports_idempotency(&self) -> bool {
+        true
+    }
+
+    /// Place under `key` as the order's `customerRef`.
+    async fn place_bet_keyed(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        key: &str,
+    ) -> Result<TradeReceipt> {
+        self.place_order(market_id, side, amount, Some(&Self::customer_ref(key)), None).await
+    }
+
+    fn supports_limit_orders(&self) -> bool {
+        true
+    }
+
+    /// [`Self::place_bet_keyed`] at odds bounded by `max_price`.
+    async fn place_limit_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        max_price: Decimal,
+        key: &str,
+    ) -> Result<TradeReceipt> {
+        self.place_order(market_id, side, amount, Some(&Self::customer_ref(key)), Some(max_price)).await
+    }
 
     /// Get current open positions on Betfair.
     ///
@@ -1058,6 +1356,8 @@ impl PredictionPlatform for BetfairClient {
                     size,
                     entry_price,
                     current_value: size, // Would need market book for accurate valuation
+                    cost: size,
+                    category: None,
                 }
             })
             .collect();
@@ -1100,9 +1400,34 @@ impl PredictionPlatform for BetfairClient {
         true
     }
 
+    fn price_increment(&self, _market: &Market) -> PriceLadder {
+        PriceLadder::BETFAIR
+    }
+
+    /// Backs ask for higher odds, lays offer lower.
+    fn passive_rounding(&self, side: Side) -> Rounding {
+        Self::rounding_for(side)
+    }
+
     fn name(&self) -> &str {
         PLATFORM_NAME
     }
+
+    /// Refresh the session before reading funds, so a dead session reads
+    /// as an auth failure rather than a balance one.
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        let mut report = PreflightReport::new(PLATFORM_NAME);
+        if let Err(e) = self.auth.refresh_session().await {
+            report.fail(CheckKind::Auth, format!("{e:#}"));
+            return report;
+        }
+        report.pass(CheckKind::Auth, "session valid");
+        match self.get_balance().await {
+            Ok(balance) => report.check_floor(balance, limits),
+            Err(e) => report.fail(CheckKind::Balance, format!("{e:#}")),
+        }
+        report
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1353,6 +1678,7 @@ mod tests {
         assert_eq!(market.platform, "betfair");
         assert_eq!(market.question, "Liverpool v Chelsea — Match Odds");
         assert_eq!(market.category, MarketCategory::Sports);
+        assert_eq!(market.event_group.as_deref(), Some("betfair:12345"));
     }
 
     #[test]
@@ -1381,6 +1707,8 @@ mod tests {
 
         assert_eq!(market.current_price_yes, d(0.5));
         assert!(market.liquidity > Decimal::ZERO);
+        // No event id, no group.
+        assert!(market.event_group.is_none());
     }
 
     // -- Event type ID tests --
@@ -1393,12 +1721,282 @@ mod tests {
         assert!(ids.contains(&"2378961")); // Politics
     }
 
+    // -- Closed-market detection --
+
+    #[test]
+    fn test_closed_book_status() {
+        let mut book = make_test_book(2.0, 100.0, 2.02, 100.0);
+        assert_eq!(BetfairClient::closed_book_status(&book), None);
+
+        book.status = Some("SUSPENDED".to_string());
+        assert_eq!(BetfairClient::closed_book_status(&book), Some("SUSPENDED"));
+
+        book.status = Some("CLOSED".to_string());
+        assert_eq!(BetfairClient::closed_book_status(&book), Some("CLOSED"));
+    }
+
+    #[test]
+    fn test_closed_market_error_report_level() {
+        let resp: PlaceOrdersResponse = serde_json::from_str(
+            r#"{"status":"FAILURE","marketId":"1.234","errorCode":"MARKET_SUSPENDED","instructionReports":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(BetfairClient::closed_market_error(&resp), Some("MARKET_SUSPENDED"));
+    }
+
+    #[test]
+    fn test_closed_market_error_instruction_level() {
+        let resp: PlaceOrdersResponse = serde_json::from_str(
+            r#"{"status":"FAILURE","marketId":"1.234","instructionReports":[{"status":"FAILURE","errorCode":"MARKET_NOT_OPEN_FOR_BETTING"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            BetfairClient::closed_market_error(&resp),
+            Some("MARKET_NOT_OPEN_FOR_BETTING")
+        );
+    }
+
+    #[test]
+    fn test_closed_market_error_ignores_other_failures() {
+        let resp: PlaceOrdersResponse = serde_json::from_str(
+            r#"{"status":"FAILURE","errorCode":"INSUFFICIENT_FUNDS","instructionReports":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(BetfairClient::closed_market_error(&resp), None);
+    }
+
     // -- is_real_money --
 
+    #[test]
+    fn test_order_price_snaps_toward_passive_side() {
+        // Back asks for higher odds, lay offers lower.
+        assert_eq!(BetfairClient::order_price(Side::Yes, 2.01).unwrap(), 2.02);
+        assert_eq!(BetfairClient::order_price(Side::No, 2.01).unwrap(), 2.0);
+        assert_eq!(BetfairClient::order_price(Side::Yes, 3.52).unwrap(), 3.55);
+        assert_eq!(BetfairClient::order_price(Side::No, 3.52).unwrap(), 3.5);
+        // On-ladder prices pass through unchanged.
+        assert_eq!(BetfairClient::order_price(Side::Yes, 1.83).unwrap(), 1.83);
+        assert_eq!(BetfairClient::order_price(Side::No, 46.0).unwrap(), 46.0);
+        assert!(BetfairClient::order_price(Side::Yes, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_protected_odds_bound_the_price_paid() {
+        // Paying at most 0.52 for YES backs at no shorter than 1.923.
+        let backed = BetfairClient::protected_odds(Side::Yes, 1.8, Some(dec!(0.52)));
+        assert!((backed - 1.0 / 0.52).abs() < 1e-9);
+        assert_eq!(BetfairClient::order_price(Side::Yes, backed).unwrap(), 1.93);
+        // Better odds than the bound are taken as they are.
+        assert_eq!(BetfairClient::protected_odds(Side::Yes, 2.1, Some(dec!(0.52))), 2.1);
+        // Paying at most 0.5 for NO lays at no longer than evens.
+        assert_eq!(BetfairClient::protected_odds(Side::No, 2.2, Some(dec!(0.5))), 2.0);
+        assert_eq!(BetfairClient::protected_odds(Side::No, 1.9, Some(dec!(0.5))), 1.9);
+        assert_eq!(BetfairClient::protected_odds(Side::No, 2.2, None), 2.2);
+    }
+
     #[test]
     fn test_is_real_money() {
         // Can't create a real client without env vars, but we can test
         // that the trait method returns true by checking the const intent
         assert_eq!(PLATFORM_NAME, "betfair");
     }
+
+    // -- Fixture contract tests --
+
+    fn fixture_entries(path: &str) -> Vec<serde_json::Value> {
+        serde_json::from_str(&crate::fixtures::read(path)).unwrap()
+    }
+
+    #[test]
+    fn test_fixture_catalogue_and_book() {
+        let entries = fixture_entries("betfair/listMarketCatalogue.json");
+        let (catalogues, skipped) = crate::platforms::parse_entries::<MarketCatalogue>("test", entries);
+        assert_eq!((catalogues.len(), skipped), (1, 0));
+        let entries = fixture_entries("betfair/listMarketBook.json");
+        let (books, skipped) = crate::platforms::parse_entries::<MarketBook>("test", entries);
+        assert_eq!((books.len(), skipped), (1, 0));
+
+        let (catalogue, book) = (&catalogues[0], &books[0]);
+        assert_eq!(catalogue.runners.len(), 3);
+        assert_eq!(book.status.as_deref(), Some("OPEN"));
+        assert_eq!(BetfairClient::favourite_selection_id(book), Some(1096));
+
+        let m = BetfairClient::to_oracle_market(catalogue, Some(book)).unwrap();
+        assert_eq!(m.id, "1.231456789");
+        assert_eq!(m.question, "Arsenal v Chelsea — Match Odds");
+        assert_eq!(m.category, MarketCategory::Sports);
+        assert_eq!(m.event_group.as_deref(), Some("betfair:34812901"));
+        assert_eq!(m.volume_24h, d(184233.71));
+        assert!(m.current_price_yes > dec!(0.4) && m.current_price_yes < dec!(0.5));
+        assert!(m.liquidity > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_fixture_drifted_catalogue_is_skipped() {
+        let mut entries = fixture_entries("betfair/listMarketCatalogue.json");
+        entries.push(entries[0].clone());
+        entries[1]["runners"][0]["selectionId"] = serde_json::json!("1096");
+        let (catalogues, skipped) = crate::platforms::parse_entries::<MarketCatalogue>("test", entries);
+        assert_eq!((catalogues.len(), skipped), (1, 1));
+    }
+
+    #[test]
+    fn test_fixture_place_orders() {
+        let resp: PlaceOrdersResponse =
+            serde_json::from_str(&crate::fixtures::read("betfair/placeOrders.json")).unwrap();
+        assert_eq!(resp.status.as_deref(), Some("SUCCESS"));
+        let report = &resp.instruction_reports[0];
+        assert_eq!(report.bet_id.as_deref(), Some("356781234567"));
+        assert_eq!(report.size_matched, Some(10.0));
+        assert_eq!(report.average_price_matched, Some(2.12));
+        assert!(BetfairClient::closed_market_error(&resp).is_none());
+    }
+
+    #[test]
+    fn test_fixture_current_orders() {
+        let resp: CurrentOrdersResponse =
+            serde_json::from_str(&crate::fixtures::read("betfair/listCurrentOrders.json")).unwrap();
+        assert_eq!(resp.more_available, Some(false));
+        let order = &resp.current_orders[0];
+        assert_eq!(order.bet_id, "356781234567");
+        assert_eq!(order.selection_id, 1096);
+        assert_eq!(order.side, "BACK");
+        assert_eq!(order.price_size.as_ref().map(|p| p.price), Some(2.12));
+    }
+
+    #[test]
+    fn test_fixture_account_funds() {
+        let funds: AccountFunds =
+            serde_json::from_str(&crate::fixtures::read("betfair/getAccountFunds.json")).unwrap();
+        assert_eq!(funds.available_to_bet_balance, Some(412.58));
+        assert_eq!(funds.exposure, Some(-10.0));
+    }
+
+    #[test]
+    fn test_keep_alive_response() {
+        let ok: KeepAliveResponse =
+            serde_json::from_str(r#"{"token":"t","product":"app","status":"SUCCESS","error":""}"#).unwrap();
+        assert_eq!(ok.status, "SUCCESS");
+        let expired: KeepAliveResponse =
+            serde_json::from_str(r#"{"token":"","product":"app","status":"FAIL","error":"NO_SESSION"}"#).unwrap();
+        assert_eq!((expired.status.as_str(), expired.error.as_str()), ("FAIL", "NO_SESSION"));
+    }
+
+    // -- Session lifecycle tests --
+
+    /// Calls the mock Betfair server has answered.
+    #[derive(Clone, Default)]
+    struct Calls {
+        logins: Arc<std::sync::atomic::AtomicUsize>,
+        keep_alives: Arc<std::sync::atomic::AtomicUsize>,
+        /// Keep-alives are refused when set.
+        refuse_keep_alive: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl Calls {
+        fn logins(&self) -> usize {
+            self.logins.load(std::sync::atomic::Ordering::SeqCst)
+        }
+
+        fn keep_alives(&self) -> usize {
+            self.keep_alives.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    /// Serves login, keepAlive and getAccountFunds, rejecting the session
+    /// token "stale" with a 401. Logins are slow enough for concurrent
+    /// callers to pile up behind them.
+    async fn mock_betfair(calls: Calls) -> BetfairClient {
+        use axum::extract::State;
+        use axum::http::{HeaderMap, StatusCode};
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use std::sync::atomic::Ordering;
+
+        async fn login(State(calls): State<Calls>) -> Json<serde_json::Value> {
+            let n = calls.logins.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Json(serde_json::json!({ "sessionToken": format!("token{n}"), "loginStatus": "SUCCESS" }))
+        }
+        async fn keep_alive(State(calls): State<Calls>) -> Json<serde_json::Value> {
+            calls.keep_alives.fetch_add(1, Ordering::SeqCst);
+            let status = if calls.refuse_keep_alive.load(Ordering::SeqCst) { "FAIL" } else { "SUCCESS" };
+            Json(serde_json::json!({ "token": "", "product": "app", "status": status, "error": "" }))
+        }
+        async fn funds(headers: HeaderMap) -> Result<Json<serde_json::Value>, StatusCode> {
+            match headers.get("X-Authentication").and_then(|t| t.to_str().ok()) {
+                Some("stale") | None => Err(StatusCode::UNAUTHORIZED),
+                Some(_) => Ok(Json(serde_json::json!({ "availableToBetBalance": 100.0 }))),
+            }
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/api/login", post(login))
+            .route("/api/keepAlive", post(keep_alive))
+            .route("/exchange/account/rest/v1.0/getAccountFunds/", post(funds))
+            .with_state(calls);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        BetfairClient::with_credentials("key".into(), "user".into(), "pass".into())
+            .unwrap()
+            .with_base_url(&format!("http://{addr}"))
+    }
+
+    async fn set_session(client: &BetfairClient, token: &str, expires_in: Duration) {
+        let session = Session { token: Secret::new(token.to_string()), expires_at: Instant::now() + expires_in };
+        *client.auth.session.write().await = Some(session);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_401s_share_one_login() {
+        let calls = Calls::default();
+        let client = mock_betfair(calls.clone()).await;
+        // A session Betfair has since dropped.
+        set_session(&client, "stale", SESSION_TIMEOUT).await;
+
+        let balances = futures::future::join_all((0..5).map(|_| client.get_balance())).await;
+        for balance in balances {
+            assert_eq!(balance.unwrap(), dec!(100));
+        }
+        assert_eq!(calls.logins(), 1);
+        assert_eq!(client.auth.ensure_session().await.unwrap(), "token1");
+    }
+
+    #[tokio::test]
+    async fn test_session_near_expiry_is_extended_before_use() {
+        let calls = Calls::default();
+        let client = mock_betfair(calls.clone()).await;
+        set_session(&client, "token0", SESSION_REFRESH_MARGIN / 2).await;
+
+        client.get_balance().await.unwrap();
+        client.get_balance().await.unwrap();
+        assert_eq!((calls.keep_alives(), calls.logins()), (1, 0));
+
+        // A session Betfair won't extend is replaced.
+        calls.refuse_keep_alive.store(true, std::sync::atomic::Ordering::SeqCst);
+        set_session(&client, "token0", SESSION_REFRESH_MARGIN / 2).await;
+        client.get_balance().await.unwrap();
+        assert_eq!((calls.keep_alives(), calls.logins()), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_background_keep_alive_runs_while_client_lives() {
+        let calls = Calls::default();
+        let client = mock_betfair(calls.clone()).await.with_keep_alive(Duration::from_millis(20));
+        // Not logged in yet: nothing to keep alive.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!((calls.keep_alives(), calls.logins()), (0, 0));
+
+        set_session(&client, "token0", SESSION_TIMEOUT).await;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(calls.keep_alives() >= 2, "keep-alives: {}", calls.keep_alives());
+
+        drop(client);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let after_drop = calls.keep_alives();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(calls.keep_alives(), after_drop);
+        assert_eq!(calls.logins(), 0);
+    }
 }
@@ -1,11 +1,1543 @@
 //! IB ForecastEx platform integration.
 //!
-//! Real-money execution via Interactive Brokers TWS API.
+//! Real-money execution via Interactive Brokers TWS / IB Gateway.
 //! As of February 2026, this is the sole AU-compliant real-money
 //! prediction market execution venue. Connection reliability is
 //! mission-critical.
 //!
-//! TODO (Phase 2): Implement IB TWS API connection, market scanning,
-//! and order placement.
+//! ForecastEx event contracts trade on IB as options on exchange
+//! `FORECASTX`: each underlying (e.g. `FF`, the fed funds target) lists
+//! one strike per threshold and expiry, whose call is the YES contract
+//! ("above the strike") and whose put is the NO. Both pay $1, are priced
+//! 0.01–0.99 and are only ever bought — a position is exited by buying
+//! the other side. Market ids are `SYMBOL-YYYYMMDD-STRIKE`, from which the
+//! contract for either side is rebuilt.
+//!
+//! The client speaks the TWS socket protocol itself (length-prefixed
+//! messages of NUL-terminated fields, pinned to server version
+//! [`SERVER_VERSION`]) behind the [`TwsApi`] trait, so contract mapping
+//! and order construction are testable without a Gateway. The socket is
+//! opened on first use and re-opened after a drop; reads are retried once
+//! on the new connection, orders never are.
+//!
+//! With `paper = true` (the default) only a paper account (`DU...`, IB
+//! Gateway port 4002) is traded; paper orders are play money and are
+//! placed in every trading mode.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use futures::future::BoxFuture;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use super::preflight::{CheckKind, PreflightLimits, PreflightReport};
+use super::PredictionPlatform;
+use crate::config::ForecastExConfig;
+use crate::types::{CrossReferences, LiquidityInfo, Market, MarketCategory, Position, Side, TradeReceipt};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+const PLATFORM_NAME: &str = "forecastex";
+const EXCHANGE: &str = "FORECASTX";
+const MARKET_URL: &str = "https://forecasttrader.interactivebrokers.com/";
+
+/// TWS protocol version spoken. Message layouts below are this version's.
+pub const SERVER_VERSION: i64 = 151;
+
+/// Time allowed for a request's replies.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time a snapshot quote may take; a partial quote is used after it.
+const QUOTE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Time a resting order is given to fill before it is cancelled.
+const FILL_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time depth updates are collected for.
+const DEPTH_WINDOW: Duration = Duration::from_millis(1500);
+/// Book levels requested per side.
+const DEPTH_ROWS: u32 = 10;
+
+// ---------------------------------------------------------------------------
+// TWS data types
+// ---------------------------------------------------------------------------
+
+/// An IB contract, with the fields the requests here send.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Contract {
+    pub con_id: i64,
+    pub symbol: String,
+    pub sec_type: String,
+    /// `YYYYMMDD`.
+    pub expiry: String,
+    /// Zero when unset.
+    pub strike: Decimal,
+    /// "C" (YES) or "P" (NO); empty for both.
+    pub right: String,
+    pub multiplier: String,
+    pub exchange: String,
+    pub currency: String,
+    pub local_symbol: String,
+    pub trading_class: String,
+}
+
+impl Contract {
+    /// Every ForecastEx contract on `symbol`, or one side when `right` is set.
+    pub fn forecastex(symbol: &str, right: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            sec_type: "OPT".to_string(),
+            right: right.to_string(),
+            exchange: EXCHANGE.to_string(),
+            currency: "USD".to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The contract block shared by contract, market data, depth and
+    /// order requests.
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.con_id.to_string(),
+            self.symbol.clone(),
+            self.sec_type.clone(),
+            self.expiry.clone(),
+            self.strike.normalize().to_string(),
+            self.right.clone(),
+            self.multiplier.clone(),
+            self.exchange.clone(),
+            String::new(), // primary exchange
+            self.currency.clone(),
+            self.local_symbol.clone(),
+            self.trading_class.clone(),
+        ]
+    }
+
+    fn expiry_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.expiry.get(..8)?, "%Y%m%d").ok()
+    }
+}
+
+/// A `reqContractDetails` result.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ContractDetails {
+    pub contract: Contract,
+    pub market_name: String,
+    pub min_tick: Decimal,
+    /// Description of the underlying event, e.g. "Fed Funds Target Rate".
+    pub long_name: String,
+}
+
+/// A snapshot quote. Sizes in contracts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Quote {
+    pub bid: Option<Decimal>,
+    pub ask: Option<Decimal>,
+    pub last: Option<Decimal>,
+    pub bid_size: Decimal,
+    pub ask_size: Decimal,
+    pub volume: Decimal,
+}
+
+/// A limit order as sent to TWS.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Order {
+    /// "BUY" — ForecastEx contracts are never sold.
+    pub action: String,
+    pub quantity: Decimal,
+    pub limit_price: Decimal,
+    /// Time in force.
+    pub tif: String,
+    pub account: String,
+}
+
+/// How an order ended, from its status, execution and commission reports.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Fill {
+    pub order_id: i64,
+    pub perm_id: i64,
+    /// Last reported status ("Filled", "Cancelled", ...).
+    pub status: String,
+    pub filled: Decimal,
+    pub avg_price: Decimal,
+    /// Sum over the order's executions.
+    pub commission: Decimal,
+    pub exec_ids: Vec<String>,
+}
+
+/// A row of the account's portfolio.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PortfolioPosition {
+    pub account: String,
+    pub contract: Contract,
+    pub position: Decimal,
+    /// Per contract, including the multiplier.
+    pub avg_cost: Decimal,
+}
+
+/// A value of the account summary.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccountValue {
+    pub account: String,
+    pub tag: String,
+    pub value: String,
+    pub currency: String,
+}
+
+/// Side of a book level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepthLevel {
+    pub side: BookSide,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// The TWS calls the client makes; [`TwsSocket`] is the real one.
+#[async_trait]
+pub trait TwsApi: Send + Sync {
+    async fn contract_details(&self, contract: &Contract) -> Result<Vec<ContractDetails>>;
+    async fn quote(&self, contract: &Contract) -> Result<Quote>;
+    /// Place `order` and wait for it to fill, be cancelled or time out
+    /// (then cancelled).
+    async fn place_order(&self, contract: &Contract, order: &Order) -> Result<Fill>;
+    async fn positions(&self) -> Result<Vec<PortfolioPosition>>;
+    async fn account_summary(&self, tags: &[&str]) -> Result<Vec<AccountValue>>;
+    async fn market_depth(&self, contract: &Contract, rows: u32) -> Result<Vec<DepthLevel>>;
+}
+
+// ---------------------------------------------------------------------------
+// Wire protocol
+// ---------------------------------------------------------------------------
+
+/// Outgoing message ids.
+mod out {
+    pub const REQ_MKT_DATA: &str = "1";
+    pub const CANCEL_MKT_DATA: &str = "2";
+    pub const PLACE_ORDER: &str = "3";
+    pub const CANCEL_ORDER: &str = "4";
+    pub const REQ_CONTRACT_DATA: &str = "9";
+    pub const REQ_MKT_DEPTH: &str = "10";
+    pub const CANCEL_MKT_DEPTH: &str = "11";
+    pub const REQ_MARKET_DATA_TYPE: &str = "59";
+    pub const REQ_POSITIONS: &str = "61";
+    pub const REQ_ACCOUNT_SUMMARY: &str = "62";
+    pub const CANCEL_ACCOUNT_SUMMARY: &str = "63";
+    pub const CANCEL_POSITIONS: &str = "64";
+    pub const START_API: &str = "71";
+}
+
+/// Incoming message ids.
+mod inc {
+    pub const TICK_PRICE: i64 = 1;
+    pub const TICK_SIZE: i64 = 2;
+    pub const ORDER_STATUS: i64 = 3;
+    pub const ERR_MSG: i64 = 4;
+    pub const NEXT_VALID_ID: i64 = 9;
+    pub const CONTRACT_DATA: i64 = 10;
+    pub const EXECUTION_DATA: i64 = 11;
+    pub const MARKET_DEPTH: i64 = 12;
+    pub const MARKET_DEPTH_L2: i64 = 13;
+    pub const MANAGED_ACCTS: i64 = 15;
+    pub const CONTRACT_DATA_END: i64 = 52;
+    pub const TICK_SNAPSHOT_END: i64 = 57;
+    pub const COMMISSION_REPORT: i64 = 59;
+    pub const POSITION_DATA: i64 = 61;
+    pub const POSITION_END: i64 = 62;
+    pub const ACCOUNT_SUMMARY: i64 = 63;
+    pub const ACCOUNT_SUMMARY_END: i64 = 64;
+}
+
+/// One message: a big-endian length, then each field NUL-terminated.
+fn frame(fields: &[String]) -> Vec<u8> {
+    let body: Vec<u8> = fields.iter().flat_map(|f| f.bytes().chain([0])).collect();
+    let mut msg = (body.len() as u32).to_be_bytes().to_vec();
+    msg.extend(body);
+    msg
+}
+
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> std::io::Result<Vec<String>> {
+    let len = reader.read_u32().await? as usize;
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).await?;
+    let mut fields: Vec<String> = body.split(|&b| b == 0).map(|f| String::from_utf8_lossy(f).into_owned()).collect();
+    // The trailing NUL leaves an empty last piece.
+    if body.last() == Some(&0) {
+        fields.pop();
+    }
+    Ok(fields)
+}
+
+/// `placeOrder` at [`SERVER_VERSION`]: the contract, then every order
+/// attribute TWS expects, unset ones empty.
+pub fn encode_place_order(order_id: i64, contract: &Contract, order: &Order) -> Vec<String> {
+    let s = |v: &str| v.to_string();
+    let mut msg = vec![s(out::PLACE_ORDER), order_id.to_string()];
+    msg.extend(contract.fields());
+    msg.extend([s(""), s("")]); // secIdType, secId
+    msg.extend([
+        order.action.clone(),
+        order.quantity.normalize().to_string(),
+        s("LMT"),
+        order.limit_price.normalize().to_string(),
+        s(""), // auxPrice
+        order.tif.clone(),
+        s(""), // ocaGroup
+        order.account.clone(),
+        s(""), // openClose
+        s("0"), // origin: customer
+        s(""), // orderRef
+        s("1"), // transmit
+        s("0"), // parentId
+        s("0"), // blockOrder
+        s("0"), // sweepToFill
+        s("0"), // displaySize
+        s("0"), // triggerMethod
+        s("0"), // outsideRth
+        s("0"), // hidden
+        s(""), // deprecated sharesAllocation
+        s("0"), // discretionaryAmt
+        s(""), // goodAfterTime
+        s(""), // goodTillDate
+        s(""), s(""), s(""), s(""), // faGroup, faMethod, faPercentage, faProfile
+        s(""), // modelCode
+        s("0"), // shortSaleSlot
+        s(""), // designatedLocation
+        s("-1"), // exemptCode
+        s("0"), // ocaType
+        s(""), // rule80A
+        s(""), // settlingFirm
+        s("0"), // allOrNone
+        s(""), // minQty
+        s(""), // percentOffset
+        s("0"), s("0"), s(""), // eTradeOnly, firmQuoteOnly, nbboPriceCap
+        s("0"), // auctionStrategy
+        s(""), s(""), s(""), s(""), s(""), // startingPrice, stockRefPrice, delta, stockRangeLower, stockRangeUpper
+        s("0"), // overridePercentageConstraints
+        s(""), s(""), // volatility, volatilityType
+        s(""), s(""), // deltaNeutralOrderType, deltaNeutralAuxPrice
+        s("0"), s(""), // continuousUpdate, referencePriceType
+        s(""), s(""), // trailStopPrice, trailingPercent
+        s(""), s(""), s(""), // scaleInitLevelSize, scaleSubsLevelSize, scalePriceIncrement
+        s(""), s(""), s(""), // scaleTable, activeStartTime, activeStopTime
+        s(""), // hedgeType
+        s("0"), // optOutSmartRouting
+        s(""), s(""), // clearingAccount, clearingIntent
+        s("0"), // notHeld
+        s("0"), // deltaNeutralContract
+        s(""), // algoStrategy
+        s(""), // algoId
+        s("0"), // whatIf
+        s(""), // orderMiscOptions
+        s("0"), // solicited
+        s("0"), s("0"), // randomizeSize, randomizePrice
+        s("0"), // conditions
+        s(""), s(""), s(""), s(""), s(""), s(""), s("0"), // adjusted order fields
+        s(""), // extOperator
+        s(""), s(""), // softDollarTier name, value
+        s(""), // cashQty
+        s(""), s(""), // mifid2DecisionMaker, mifid2DecisionAlgo
+        s(""), s(""), // mifid2ExecutionTrader, mifid2ExecutionAlgo
+        s("0"), // dontUseAutoPriceForHedge
+        s("0"), // isOmsContainer
+        s("0"), // discretionaryUpToLimitPrice
+        s(""), // usePriceMgmtAlgo
+    ]);
+    msg
+}
+
+/// Cursor over an incoming message's fields.
+struct Fields<'a> {
+    fields: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Fields<'a> {
+    fn new(fields: &'a [String]) -> Self {
+        Self { fields: fields.iter() }
+    }
+
+    fn skip(&mut self, n: usize) -> &mut Self {
+        for _ in 0..n {
+            self.fields.next();
+        }
+        self
+    }
+
+    fn string(&mut self) -> String {
+        self.fields.next().cloned().unwrap_or_default()
+    }
+
+    fn int(&mut self) -> i64 {
+        self.fields.next().and_then(|f| f.parse().ok()).unwrap_or_default()
+    }
+
+    /// Decimal, or None when empty or unset (TWS sends `1.7976931348623157E308`).
+    fn decimal(&mut self) -> Option<Decimal> {
+        let field = self.fields.next()?;
+        Decimal::from_str_exact(field).ok().or_else(|| Decimal::from_scientific(field).ok())
+    }
+}
+
+/// The socket dropped or the handshake failed; the connection is
+/// discarded and the next call reconnects.
+#[derive(Debug, thiserror::Error)]
+#[error("TWS connection lost: {0}")]
+pub struct Disconnected(String);
+
+/// An error reported by TWS for a request.
+#[derive(Debug, thiserror::Error)]
+#[error("TWS error {code}: {message}")]
+pub struct TwsError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Informational TWS codes: farm status (2100s), order warnings (399),
+/// delayed data notices (10167), and the cancellation we asked for (202).
+fn is_notice(code: i64) -> bool {
+    matches!(code, 202 | 399 | 2100..=2199 | 10167)
+}
+
+/// One open, handshaken connection.
+struct Connection {
+    stream: TcpStream,
+    next_order_id: i64,
+    accounts: Vec<String>,
+}
+
+impl Connection {
+    async fn open(host: &str, port: u16, client_id: i64, delayed_data: bool) -> Result<Self> {
+        let handshake = async {
+            let mut stream = TcpStream::connect((host, port)).await?;
+            let version = format!("v{SERVER_VERSION}..{SERVER_VERSION}");
+            let mut hello = b"API\0".to_vec();
+            hello.extend((version.len() as u32).to_be_bytes());
+            hello.extend(version.as_bytes());
+            stream.write_all(&hello).await?;
+            let reply = read_frame(&mut stream).await?;
+            Ok::<_, std::io::Error>((stream, reply))
+        };
+        let (stream, reply) = tokio::time::timeout(REQUEST_TIMEOUT, handshake)
+            .await
+            .map_err(|_| Disconnected(format!("no handshake from {host}:{port}")))?
+            .map_err(|e| Disconnected(format!("{host}:{port}: {e}")))?;
+        let version: i64 = reply.first().and_then(|v| v.parse().ok()).unwrap_or_default();
+        anyhow::ensure!(version == SERVER_VERSION, Disconnected(format!("TWS offered server version {version}, need {SERVER_VERSION}")));
+
+        let mut conn = Self { stream, next_order_id: -1, accounts: Vec::new() };
+        conn.send(&[out::START_API.into(), "2".into(), client_id.to_string(), String::new()]).await?;
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        while conn.next_order_id < 0 || conn.accounts.is_empty() {
+            let msg = conn.recv(deadline).await?;
+            let mut f = Fields::new(&msg);
+            match f.int() {
+                inc::NEXT_VALID_ID => conn.next_order_id = f.skip(1).int(),
+                inc::MANAGED_ACCTS => {
+                    conn.accounts = f.skip(1).string().split(',').filter(|a| !a.is_empty()).map(String::from).collect();
+                }
+                inc::ERR_MSG => {
+                    let (code, message) = (f.skip(2).int(), f.string());
+                    if !is_notice(code) {
+                        return Err(Disconnected(format!("TWS refused the session: {code} {message}")).into());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if delayed_data {
+            // Live where subscribed, delayed otherwise.
+            conn.send(&[out::REQ_MARKET_DATA_TYPE.into(), "1".into(), "3".into()]).await?;
+        }
+        info!(host, port, client_id, accounts = ?conn.accounts, "Connected to TWS");
+        Ok(conn)
+    }
+
+    async fn send(&mut self, fields: &[String]) -> Result<()> {
+        self.stream.write_all(&frame(fields)).await.map_err(|e| Disconnected(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Next message, or an error at `deadline`.
+    async fn recv(&mut self, deadline: Instant) -> Result<Vec<String>> {
+        match tokio::time::timeout_at(deadline, read_frame(&mut self.stream)).await {
+            Ok(Ok(msg)) => Ok(msg),
+            Ok(Err(e)) => Err(Disconnected(e.to_string()).into()),
+            Err(_) => anyhow::bail!("TWS request timed out"),
+        }
+    }
+
+    /// The error in `msg` when it is one for request `id`.
+    fn error_for(msg: &[String], id: i64) -> Option<TwsError> {
+        let mut f = Fields::new(msg);
+        if f.int() != inc::ERR_MSG || f.skip(1).int() != id {
+            return None;
+        }
+        let (code, message) = (f.int(), f.string());
+        (!is_notice(code)).then_some(TwsError { code, message })
+    }
+
+    async fn contract_details(&mut self, req_id: i64, contract: &Contract) -> Result<Vec<ContractDetails>> {
+        let mut msg = vec![out::REQ_CONTRACT_DATA.into(), "8".into(), req_id.to_string()];
+        msg.extend(contract.fields());
+        msg.extend(["0".into(), String::new(), String::new()]); // includeExpired, secIdType, secId
+        self.send(&msg).await?;
+
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        let mut details = Vec::new();
+        loop {
+            let msg = self.recv(deadline).await?;
+            if let Some(e) = Self::error_for(&msg, req_id) {
+                // "No security definition" is an empty result.
+                if e.code == 200 {
+                    return Ok(details);
+                }
+                return Err(e.into());
+            }
+            let mut f = Fields::new(&msg);
+            match f.int() {
+                inc::CONTRACT_DATA if f.skip(1).int() == req_id => details.push(parse_contract_data(&mut f)),
+                inc::CONTRACT_DATA_END if f.skip(1).int() == req_id => return Ok(details),
+                _ => {}
+            }
+        }
+    }
+
+    async fn quote(&mut self, req_id: i64, contract: &Contract) -> Result<Quote> {
+        let mut msg = vec![out::REQ_MKT_DATA.into(), "11".into(), req_id.to_string()];
+        msg.extend(contract.fields());
+        // deltaNeutral, genericTicks, snapshot, regulatorySnapshot, options
+        msg.extend(["0".into(), String::new(), "1".into(), "0".into(), String::new()]);
+        self.send(&msg).await?;
+
+        let deadline = Instant::now() + QUOTE_TIMEOUT;
+        let mut quote = Quote::default();
+        loop {
+            let msg = match self.recv(deadline).await {
+                Ok(msg) => msg,
+                Err(e) if e.is::<Disconnected>() => return Err(e),
+                // Snapshots can take seconds to complete; use what arrived.
+                Err(_) => {
+                    self.send(&[out::CANCEL_MKT_DATA.into(), "2".into(), req_id.to_string()]).await?;
+                    return Ok(quote);
+                }
+            };
+            if let Some(e) = Self::error_for(&msg, req_id) {
+                return Err(e.into());
+            }
+            let mut f = Fields::new(&msg);
+            match f.int() {
+                inc::TICK_PRICE if f.skip(1).int() == req_id => {
+                    let (tick, price) = (f.int(), f.decimal().filter(|p| *p > Decimal::ZERO));
+                    match tick {
+                        1 | 66 => quote.bid = price,
+                        2 | 67 => quote.ask = price,
+                        4 | 68 => quote.last = price,
+                        _ => {}
+                    }
+                }
+                inc::TICK_SIZE if f.skip(1).int() == req_id => {
+                    let (tick, size) = (f.int(), f.decimal().unwrap_or_default());
+                    match tick {
+                        0 | 69 => quote.bid_size = size,
+                        3 | 70 => quote.ask_size = size,
+                        8 | 74 => quote.volume = size,
+                        _ => {}
+                    }
+                }
+                inc::TICK_SNAPSHOT_END if f.skip(1).int() == req_id => return Ok(quote),
+                _ => {}
+            }
+        }
+    }
+
+    async fn place_order(&mut self, contract: &Contract, order: &Order) -> Result<Fill> {
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+        self.send(&encode_place_order(order_id, contract, order)).await?;
+
+        let mut fill = Fill { order_id, ..Fill::default() };
+        let mut commissions: HashMap<String, Decimal> = HashMap::new();
+        let mut deadline = Instant::now() + FILL_TIMEOUT;
+        let mut cancelled = false;
+        let mut done = false;
+        loop {
+            if done && fill.exec_ids.iter().all(|id| commissions.contains_key(id)) {
+                break;
+            }
+            let msg = match self.recv(deadline).await {
+                Ok(msg) => msg,
+                Err(e) if e.is::<Disconnected>() => return Err(e),
+                // Late commission reports: book the fill without them.
+                Err(_) if done => break,
+                Err(_) if !cancelled => {
+                    warn!(order_id, "ForecastEx order unfilled at timeout — cancelling");
+                    self.send(&[out::CANCEL_ORDER.into(), "1".into(), order_id.to_string()]).await?;
+                    cancelled = true;
+                    deadline = Instant::now() + REQUEST_TIMEOUT;
+                    continue;
+                }
+                Err(e) => return Err(e.context(format!("ForecastEx order {order_id} did not confirm its cancellation"))),
+            };
+            if let Some(e) = Self::error_for(&msg, order_id) {
+                return Err(anyhow::Error::new(e).context(format!("ForecastEx order {order_id} rejected")));
+            }
+            let mut f = Fields::new(&msg);
+            match f.int() {
+                inc::ORDER_STATUS if f.int() == order_id => {
+                    fill.status = f.string();
+                    fill.filled = f.decimal().unwrap_or_default();
+                    f.skip(1); // remaining
+                    fill.avg_price = f.decimal().unwrap_or_default();
+                    fill.perm_id = f.int();
+                    if matches!(fill.status.as_str(), "Filled" | "Cancelled" | "ApiCancelled" | "Inactive") && !done {
+                        done = true;
+                        // Commission reports trail the final status.
+                        deadline = Instant::now() + Duration::from_secs(2);
+                    }
+                }
+                inc::EXECUTION_DATA if f.skip(1).int() == order_id => {
+                    let exec_id = f.skip(11).string();
+                    if !fill.exec_ids.contains(&exec_id) {
+                        fill.exec_ids.push(exec_id);
+                    }
+                }
+                inc::COMMISSION_REPORT => {
+                    let exec_id = f.skip(1).string();
+                    let commission = f.decimal().unwrap_or_default();
+                    commissions.insert(exec_id, commission);
+                }
+                _ => {}
+            }
+        }
+        fill.commission = fill.exec_ids.iter().filter_map(|id| commissions.get(id)).sum();
+        Ok(fill)
+    }
+
+    async fn positions(&mut self) -> Result<Vec<PortfolioPosition>> {
+        self.send(&[out::REQ_POSITIONS.into(), "1".into()]).await?;
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        let mut positions = Vec::new();
+        loop {
+            let msg = self.recv(deadline).await?;
+            let mut f = Fields::new(&msg);
+            match f.int() {
+                inc::POSITION_DATA => {
+                    let account = f.skip(1).string();
+                    let contract = Contract {
+                        con_id: f.int(),
+                        symbol: f.string(),
+                        sec_type: f.string(),
+                        expiry: f.string(),
+                        strike: f.decimal().unwrap_or_default(),
+                        right: f.string(),
+                        multiplier: f.string(),
+                        exchange: f.string(),
+                        currency: f.string(),
+                        local_symbol: f.string(),
+                        trading_class: f.string(),
+                    };
+                    let position = f.decimal().unwrap_or_default();
+                    let avg_cost = f.decimal().unwrap_or_default();
+                    positions.push(PortfolioPosition { account, contract, position, avg_cost });
+                }
+                inc::POSITION_END => break,
+                _ => {}
+            }
+        }
+        self.send(&[out::CANCEL_POSITIONS.into(), "1".into()]).await?;
+        Ok(positions)
+    }
+
+    async fn account_summary(&mut self, req_id: i64, tags: &[&str]) -> Result<Vec<AccountValue>> {
+        self.send(&[out::REQ_ACCOUNT_SUMMARY.into(), "1".into(), req_id.to_string(), "All".into(), tags.join(",")]).await?;
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        let mut values = Vec::new();
+        loop {
+            let msg = self.recv(deadline).await?;
+            if let Some(e) = Self::error_for(&msg, req_id) {
+                return Err(e.into());
+            }
+            let mut f = Fields::new(&msg);
+            match f.int() {
+                inc::ACCOUNT_SUMMARY if f.skip(1).int() == req_id => values.push(AccountValue {
+                    account: f.string(),
+                    tag: f.string(),
+                    value: f.string(),
+                    currency: f.string(),
+                }),
+                inc::ACCOUNT_SUMMARY_END if f.skip(1).int() == req_id => break,
+                _ => {}
+            }
+        }
+        self.send(&[out::CANCEL_ACCOUNT_SUMMARY.into(), "1".into(), req_id.to_string()]).await?;
+        Ok(values)
+    }
+
+    async fn market_depth(&mut self, req_id: i64, contract: &Contract, rows: u32) -> Result<Vec<DepthLevel>> {
+        let mut msg = vec![out::REQ_MKT_DEPTH.into(), "5".into(), req_id.to_string()];
+        msg.extend(contract.fields());
+        msg.extend([rows.to_string(), "0".into(), String::new()]); // numRows, isSmartDepth, options
+        self.send(&msg).await?;
+
+        // Depth streams: apply updates for a short window, then cancel.
+        let deadline = Instant::now() + DEPTH_WINDOW;
+        let mut book: [Vec<(Decimal, Decimal)>; 2] = [Vec::new(), Vec::new()];
+        loop {
+            let msg = match self.recv(deadline).await {
+                Ok(msg) => msg,
+                Err(e) if e.is::<Disconnected>() => return Err(e),
+                Err(_) => break,
+            };
+            if let Some(e) = Self::error_for(&msg, req_id) {
+                return Err(e.into());
+            }
+            let mut f = Fields::new(&msg);
+            let kind = f.int();
+            if !matches!(kind, inc::MARKET_DEPTH | inc::MARKET_DEPTH_L2) || f.skip(1).int() != req_id {
+                continue;
+            }
+            let position = f.int().max(0) as usize;
+            if kind == inc::MARKET_DEPTH_L2 {
+                f.skip(1); // market maker
+            }
+            let (operation, side) = (f.int(), f.int());
+            let level = (f.decimal().unwrap_or_default(), f.decimal().unwrap_or_default());
+            let levels = &mut book[(side == 1) as usize];
+            match operation {
+                0 => levels.insert(position.min(levels.len()), level),
+                1 if position < levels.len() => levels[position] = level,
+                2 if position < levels.len() => {
+                    levels.remove(position);
+                }
+                _ => {}
+            }
+        }
+        self.send(&[out::CANCEL_MKT_DEPTH.into(), "1".into(), req_id.to_string(), "0".into()]).await?;
+        let [asks, bids] = book;
+        Ok(bids
+            .into_iter()
+            .map(|(price, size)| DepthLevel { side: BookSide::Bid, price, size })
+            .chain(asks.into_iter().map(|(price, size)| DepthLevel { side: BookSide::Ask, price, size }))
+            .collect())
+    }
+}
+
+/// The rest of a `contractData` message, after its request id.
+fn parse_contract_data(f: &mut Fields) -> ContractDetails {
+    let mut contract = Contract {
+        symbol: f.string(),
+        sec_type: f.string(),
+        // "20261210 16:00 US/Eastern" on some versions.
+        expiry: f.string().split_whitespace().next().unwrap_or_default().to_string(),
+        strike: f.decimal().unwrap_or_default(),
+        right: f.string(),
+        exchange: f.string(),
+        currency: f.string(),
+        local_symbol: f.string(),
+        ..Contract::default()
+    };
+    let market_name = f.string();
+    contract.trading_class = f.string();
+    contract.con_id = f.int();
+    let min_tick = f.decimal().unwrap_or_default();
+    f.skip(1); // mdSizeMultiplier
+    contract.multiplier = f.string();
+    f.skip(4); // orderTypes, validExchanges, priceMagnifier, underConId
+    ContractDetails { contract, market_name, min_tick, long_name: f.string() }
+}
+
+/// [`TwsApi`] over a TWS / IB Gateway socket.
+pub struct TwsSocket {
+    host: String,
+    port: u16,
+    client_id: i64,
+    /// Ask for delayed data where there's no subscription (paper accounts).
+    delayed_data: bool,
+    conn: tokio::sync::Mutex<Option<Connection>>,
+    next_req_id: AtomicI64,
+}
+
+impl TwsSocket {
+    pub fn new(host: &str, port: u16, client_id: i64, delayed_data: bool) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            client_id,
+            delayed_data,
+            conn: tokio::sync::Mutex::new(None),
+            next_req_id: AtomicI64::new(1),
+        }
+    }
+
+    fn req_id(&self) -> i64 {
+        self.next_req_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Run `call` on the connection, opening it first if needed. A lost
+    /// connection is dropped, and `call` retried once on a new one when
+    /// `retry` (never for orders: the first may have reached TWS).
+    async fn with_connection<T>(
+        &self,
+        retry: bool,
+        call: impl for<'c> Fn(&'c mut Connection) -> BoxFuture<'c, Result<T>>,
+    ) -> Result<T> {
+        let mut guard = self.conn.lock().await;
+        let attempts = if retry { 2 } else { 1 };
+        let mut last = None;
+        for _ in 0..attempts {
+            if guard.is_none() {
+                *guard = Some(Connection::open(&self.host, self.port, self.client_id, self.delayed_data).await?);
+            }
+            let conn = guard.as_mut().expect("connected above");
+            match call(conn).await {
+                Err(e) if e.is::<Disconnected>() => {
+                    warn!(error = %e, "TWS connection dropped — reconnecting");
+                    *guard = None;
+                    last = Some(e);
+                }
+                result => return result,
+            }
+        }
+        Err(last.expect("at least one attempt"))
+    }
+}
+
+#[async_trait]
+impl TwsApi for TwsSocket {
+    async fn contract_details(&self, contract: &Contract) -> Result<Vec<ContractDetails>> {
+        let req_id = self.req_id();
+        self.with_connection(true, |c| {
+            let contract = contract.clone();
+            Box::pin(async move { c.contract_details(req_id, &contract).await })
+        })
+        .await
+    }
+
+    async fn quote(&self, contract: &Contract) -> Result<Quote> {
+        let req_id = self.req_id();
+        self.with_connection(true, |c| {
+            let contract = contract.clone();
+            Box::pin(async move { c.quote(req_id, &contract).await })
+        })
+        .await
+    }
+
+    async fn place_order(&self, contract: &Contract, order: &Order) -> Result<Fill> {
+        self.with_connection(false, |c| {
+            let (contract, order) = (contract.clone(), order.clone());
+            Box::pin(async move { c.place_order(&contract, &order).await })
+        })
+        .await
+    }
+
+    async fn positions(&self) -> Result<Vec<PortfolioPosition>> {
+        self.with_connection(true, |c| Box::pin(c.positions())).await
+    }
+
+    async fn account_summary(&self, tags: &[&str]) -> Result<Vec<AccountValue>> {
+        let req_id = self.req_id();
+        let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+        self.with_connection(true, |c| {
+            let tags = tags.clone();
+            Box::pin(async move {
+                let tags: Vec<&str> = tags.iter().map(String::as_str).collect();
+                c.account_summary(req_id, &tags).await
+            })
+        })
+        .await
+    }
+
+    async fn market_depth(&self, contract: &Contract, rows: u32) -> Result<Vec<DepthLevel>> {
+        let req_id = self.req_id();
+        self.with_connection(true, |c| {
+            let contract = contract.clone();
+            Box::pin(async move { c.market_depth(req_id, &contract, rows).await })
+        })
+        .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// ForecastEx venue over a [`TwsApi`]. Clones share the connection.
+#[derive(Clone)]
+pub struct ForecastExClient {
+    api: Arc<dyn TwsApi>,
+    /// IB account orders are placed for (`None`: scan-only).
+    account: Option<String>,
+    paper: bool,
+    /// Underlyings scanned.
+    symbols: Vec<String>,
+    /// Contracts quoted per scan, nearest expiry first.
+    max_markets: usize,
+}
+
+impl ForecastExClient {
+    pub fn new(api: Arc<dyn TwsApi>, account: Option<String>, paper: bool) -> Self {
+        Self { api, account, paper, symbols: vec!["FF".to_string()], max_markets: 40 }
+    }
+
+    /// Client for `[platforms.forecastex]`. Connects on first use.
+    pub fn from_config(cfg: &ForecastExConfig) -> Self {
+        let account = std::env::var(&cfg.account_id_env).ok().filter(|v| !v.is_empty());
+        if account.is_none() {
+            info!("IB account not set ({}) — ForecastEx scan only", cfg.account_id_env);
+        }
+        let socket = TwsSocket::new(&cfg.ib_host, cfg.ib_port, cfg.ib_client_id as i64, cfg.paper);
+        Self::new(Arc::new(socket), account, cfg.paper)
+            .with_symbols(cfg.symbols.clone())
+            .with_max_markets(cfg.max_markets)
+    }
+
+    pub fn with_symbols(mut self, symbols: Vec<String>) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    pub fn with_max_markets(mut self, max_markets: usize) -> Self {
+        self.max_markets = max_markets;
+        self
+    }
+
+    /// Whether orders can be placed: an account is configured, and in
+    /// paper mode it is a paper account.
+    pub fn is_executable(&self) -> bool {
+        match &self.account {
+            Some(account) => !self.paper || Self::is_paper_account(account),
+            None => false,
+        }
+    }
+
+    /// Trading a paper account (play money).
+    pub fn is_paper(&self) -> bool {
+        self.paper
+    }
+
+    /// IB paper accounts are `DU...` (individual) or `DF...` (advisor).
+    fn is_paper_account(account: &str) -> bool {
+        account.starts_with("DU") || account.starts_with("DF")
+    }
+
+    fn executable_account(&self) -> Result<&str> {
+        let account = self.account.as_deref().context("IB account not configured")?;
+        anyhow::ensure!(
+            !self.paper || Self::is_paper_account(account),
+            "paper = true but {account} is not a paper account"
+        );
+        Ok(account)
+    }
+
+    // -- Mapping -----------------------------------------------------------
+
+    /// `SYMBOL-YYYYMMDD-STRIKE` for either side's contract.
+    pub fn market_id(contract: &Contract) -> String {
+        format!("{}-{}-{}", contract.symbol, contract.expiry, contract.strike.normalize())
+    }
+
+    /// The contract bought for `side` of `market_id`: the call for YES,
+    /// the put for NO.
+    pub fn contract_for(market_id: &str, side: Side) -> Result<Contract> {
+        let mut parts = market_id.rsplitn(3, '-');
+        let (strike, expiry, symbol) = (parts.next(), parts.next(), parts.next());
+        let (Some(strike), Some(expiry), Some(symbol)) = (strike, expiry, symbol) else {
+            anyhow::bail!("Not a ForecastEx market id: {market_id}");
+        };
+        let strike: Decimal = strike.parse().with_context(|| format!("Bad strike in {market_id}"))?;
+        anyhow::ensure!(
+            expiry.len() == 8 && expiry.bytes().all(|b| b.is_ascii_digit()),
+            "Bad expiry in {market_id}"
+        );
+        let right = match side {
+            Side::Yes => "C",
+            Side::No => "P",
+        };
+        Ok(Contract { expiry: expiry.to_string(), strike, ..Contract::forecastex(symbol, right) })
+    }
+
+    fn categorize(symbol: &str, long_name: &str) -> MarketCategory {
+        let text = format!("{symbol} {long_name}").to_lowercase();
+        const WEATHER: &[&str] = &["temperature", "rain", "snow", "hurricane", "climate"];
+        const POLITICS: &[&str] = &["election", "president", "senate", "house", "congress", "governor"];
+        if WEATHER.iter().any(|w| text.contains(w)) {
+            MarketCategory::Weather
+        } else if POLITICS.iter().any(|w| text.contains(w)) {
+            MarketCategory::Politics
+        } else {
+            MarketCategory::Economics
+        }
+    }
+
+    /// A YES contract and its quote as a market. `None` without a price.
+    pub fn to_oracle_market(details: &ContractDetails, quote: &Quote) -> Option<Market> {
+        let c = &details.contract;
+        let price_yes = match (quote.bid, quote.ask) {
+            (Some(bid), Some(ask)) => (bid + ask) / dec!(2),
+            (Some(p), None) | (None, Some(p)) => quote.last.unwrap_or(p),
+            (None, None) => quote.last?,
+        }
+        .round_dp(4);
+        if price_yes <= Decimal::ZERO || price_yes >= Decimal::ONE {
+            return None;
+        }
+        let expiry = c.expiry_date()?;
+        let name = if details.long_name.is_empty() { c.symbol.as_str() } else { details.long_name.as_str() };
+        let strike = c.strike.normalize();
+        let date = expiry.format("%b %-d, %Y");
+        let liquidity = quote.bid.unwrap_or_default() * quote.bid_size + quote.ask.unwrap_or_default() * quote.ask_size;
+        Some(Market {
+            id: Self::market_id(c),
+            platform: PLATFORM_NAME.to_string(),
+            question: format!("Will {name} be above {strike} on {date}?"),
+            description: format!("ForecastEx {} event contract, strike {strike}, expiring {date}.", c.symbol),
+            category: Self::categorize(&c.symbol, &details.long_name),
+            current_price_yes: price_yes,
+            current_price_no: Decimal::ONE - price_yes,
+            volume_24h: quote.volume,
+            liquidity,
+            deadline: expiry.and_hms_opt(23, 59, 59)?.and_utc(),
+            resolution_criteria: format!(
+                "A YES contract pays $1 if {name} is above {strike} at expiry on {date}; a NO contract pays $1 otherwise."
+            ),
+            url: MARKET_URL.to_string(),
+            cross_refs: CrossReferences::default(),
+            event_group: Some(format!("{}-{}", c.symbol, c.expiry)),
+            facts: None,
+            tags: Vec::new(),
+        })
+    }
+
+    /// A limit buy of as many whole contracts as `amount` pays for at
+    /// `price`.
+    pub fn order_for(amount: Decimal, price: Decimal, account: &str) -> Result<Order> {
+        anyhow::ensure!(price > Decimal::ZERO && price < Decimal::ONE, "ForecastEx price {price} out of range");
+        let quantity = (amount / price).floor();
+        anyhow::ensure!(quantity >= Decimal::ONE, "Stake {amount} buys no contract at {price}");
+        Ok(Order {
+            action: "BUY".to_string(),
+            quantity,
+            limit_price: price.round_dp(2),
+            tif: "DAY".to_string(),
+            account: account.to_string(),
+        })
+    }
+
+    /// Limit buy of `side`'s contract at its current ask, capped at
+    /// `max_price` (whole cents, rounded down).
+    async fn buy(&self, market_id: &str, side: Side, amount: Decimal, max_price: Option<Decimal>) -> Result<TradeReceipt> {
+        let account = self.executable_account()?;
+        let wanted = Self::contract_for(market_id, side)?;
+        let contract = self
+            .api
+            .contract_details(&wanted)
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| format!("No ForecastEx contract for {market_id}"))?
+            .contract;
+        let quote = self.api.quote(&contract).await?;
+        let ask = quote.ask.with_context(|| format!("No ask on ForecastEx {market_id} {side:?}"))?;
+        let price = match max_price {
+            Some(max) => ask.min(max.round_dp_with_strategy(2, RoundingStrategy::ToZero)),
+            None => ask,
+        };
+        let order = Self::order_for(amount, price, account)?;
+        info!(market_id, ?side, quantity = %order.quantity, price = %order.limit_price, %ask, paper = self.paper, "Placing ForecastEx order");
+        let fill = self.api.place_order(&contract, &order).await?;
+        Self::receipt(market_id, side, &fill)
+    }
+
+    /// Receipt for a fill. Amount is the filled cost and `fill_price` the
+    /// YES price, as for every platform.
+    pub fn receipt(market_id: &str, side: Side, fill: &Fill) -> Result<TradeReceipt> {
+        anyhow::ensure!(fill.filled > Decimal::ZERO, "ForecastEx order {} not filled ({})", fill.order_id, fill.status);
+        let fill_price = match side {
+            Side::Yes => fill.avg_price,
+            Side::No => Decimal::ONE - fill.avg_price,
+        };
+        let order_id = if fill.perm_id != 0 { fill.perm_id } else { fill.order_id };
+        Ok(TradeReceipt {
+            order_id: order_id.to_string(),
+            market_id: market_id.to_string(),
+            platform: PLATFORM_NAME.to_string(),
+            side,
+            amount: fill.filled * fill.avg_price,
+            fill_price,
+            fees: fill.commission,
+            timestamp: Utc::now(),
+            currency: "USD".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: serde_json::to_value(fill).ok(),
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        })
+    }
+
+    /// A ForecastEx holding on one of `symbols` as a position.
+    fn to_position(p: &PortfolioPosition, symbols: &[String]) -> Option<Position> {
+        let c = &p.contract;
+        if c.sec_type != "OPT" || !symbols.contains(&c.symbol) || p.position <= Decimal::ZERO {
+            return None;
+        }
+        let side = match c.right.as_str() {
+            "C" => Side::Yes,
+            "P" => Side::No,
+            _ => return None,
+        };
+        let multiplier = c.multiplier.parse::<Decimal>().ok().filter(|m| *m > Decimal::ZERO).unwrap_or(Decimal::ONE);
+        let entry_price = p.avg_cost / multiplier;
+        let cost = p.position * entry_price;
+        Some(Position {
+            market_id: Self::market_id(c),
+            platform: PLATFORM_NAME.to_string(),
+            side,
+            size: p.position,
+            entry_price,
+            current_value: cost, // Would need a quote for a mark
+            cost,
+            category: None,
+        })
+    }
+
+    /// Dollar depth of the YES book.
+    fn depth(levels: &[DepthLevel]) -> (Decimal, Decimal) {
+        let sum = |side: BookSide| levels.iter().filter(|l| l.side == side).map(|l| l.price * l.size).sum();
+        (sum(BookSide::Bid), sum(BookSide::Ask))
+    }
+
+    /// YES contracts on the scanned underlyings expiring on or after
+    /// `today`, nearest expiry first, capped at `max_markets`.
+    async fn listed_contracts(&self, today: NaiveDate) -> Result<Vec<ContractDetails>> {
+        let mut listed = Vec::new();
+        for symbol in &self.symbols {
+            match self.api.contract_details(&Contract::forecastex(symbol, "C")).await {
+                Ok(details) => listed.extend(details),
+                Err(e) if e.is::<Disconnected>() => return Err(e),
+                Err(e) => warn!(symbol = %symbol, error = %e, "ForecastEx contract lookup failed"),
+            }
+        }
+        listed.retain(|d| d.contract.expiry_date().is_some_and(|e| e >= today));
+        listed.sort_by(|a, b| (&a.contract.expiry, a.contract.strike).cmp(&(&b.contract.expiry, b.contract.strike)));
+        listed.truncate(self.max_markets);
+        Ok(listed)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PredictionPlatform trait implementation
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl PredictionPlatform for ForecastExClient {
+    async fn fetch_markets(&self) -> Result<Vec<Market>> {
+        let listed = self.listed_contracts(Utc::now().date_naive()).await?;
+        let mut markets = Vec::new();
+        for details in &listed {
+            match self.api.quote(&details.contract).await {
+                Ok(quote) => markets.extend(Self::to_oracle_market(details, &quote)),
+                Err(e) if e.is::<Disconnected>() => return Err(e),
+                Err(e) => debug!(contract = %Self::market_id(&details.contract), error = %e, "ForecastEx quote failed"),
+            }
+        }
+        info!(listed = listed.len(), priced = markets.len(), "ForecastEx markets fetched");
+        Ok(markets)
+    }
+
+    /// Limit buy of `side`'s contract at its current ask.
+    async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+        self.buy(market_id, side, amount, None).await
+    }
+
+    fn supports_limit_orders(&self) -> bool {
+        true
+    }
+
+    /// Limit buy at the ask, or at `max_price` when the ask is above it.
+    async fn place_limit_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        max_price: Decimal,
+        _key: &str,
+    ) -> Result<TradeReceipt> {
+        self.buy(market_id, side, amount, Some(max_price)).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        let account = self.executable_account()?;
+        Ok(self
+            .api
+            .positions()
+            .await?
+            .iter()
+            .filter(|p| p.account == account)
+            .filter_map(|p| Self::to_position(p, &self.symbols))
+            .collect())
+    }
+
+    /// Available funds in USD.
+    async fn get_balance(&self) -> Result<Decimal> {
+        let account = self.executable_account()?;
+        let values = self.api.account_summary(&["AvailableFunds"]).await?;
+        let value = values
+            .iter()
+            .find(|v| v.account == account && v.tag == "AvailableFunds")
+            .with_context(|| format!("No AvailableFunds for IB account {account}"))?;
+        value.value.parse().with_context(|| format!("Bad AvailableFunds value {:?}", value.value))
+    }
+
+    async fn check_liquidity(&self, market_id: &str) -> Result<LiquidityInfo> {
+        let contract = Self::contract_for(market_id, Side::Yes)?;
+        let levels = self.api.market_depth(&contract, DEPTH_ROWS).await?;
+        let (bid_depth, ask_depth) = Self::depth(&levels);
+        let volume_24h = self.api.quote(&contract).await.map(|q| q.volume).unwrap_or_default();
+        Ok(LiquidityInfo { bid_depth, ask_depth, volume_24h })
+    }
+
+    /// Real money unless trading a paper account.
+    fn is_real_money(&self) -> bool {
+        !self.paper
+    }
+
+    fn name(&self) -> &str {
+        PLATFORM_NAME
+    }
+
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        let mut report = PreflightReport::new(PLATFORM_NAME);
+        match self.executable_account() {
+            Ok(_) => report.balance(self.get_balance().await, limits),
+            Err(e) => report.fail(CheckKind::Auth, e.to_string()),
+        }
+        report
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    fn details(expiry: &str, strike: Decimal) -> ContractDetails {
+        ContractDetails {
+            contract: Contract {
+                con_id: 7001,
+                expiry: expiry.to_string(),
+                strike,
+                multiplier: "1".to_string(),
+                local_symbol: format!("FF {expiry} {strike} C"),
+                trading_class: "FF".to_string(),
+                ..Contract::forecastex("FF", "C")
+            },
+            market_name: "FF".to_string(),
+            min_tick: dec!(0.01),
+            long_name: "the Fed Funds Target Rate".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_market_id_round_trips_to_either_side() {
+        let c = details("20261210", dec!(4.125)).contract;
+        let id = ForecastExClient::market_id(&c);
+        assert_eq!(id, "FF-20261210-4.125");
+
+        let yes = ForecastExClient::contract_for(&id, Side::Yes).unwrap();
+        assert_eq!((yes.symbol.as_str(), yes.expiry.as_str(), yes.strike, yes.right.as_str()), ("FF", "20261210", dec!(4.125), "C"));
+        assert_eq!((yes.sec_type.as_str(), yes.exchange.as_str()), ("OPT", "FORECASTX"));
+        assert_eq!(ForecastExClient::contract_for(&id, Side::No).unwrap().right, "P");
+        // Symbols may contain dashes; the strike and expiry are the last two parts.
+        assert_eq!(ForecastExClient::contract_for("US-CPI-20261115-3", Side::Yes).unwrap().symbol, "US-CPI");
+        assert!(ForecastExClient::contract_for("FF-2026-4.125", Side::Yes).is_err());
+        assert!(ForecastExClient::contract_for("nonsense", Side::Yes).is_err());
+    }
+
+    #[test]
+    fn test_contract_maps_to_market() {
+        let quote = Quote {
+            bid: Some(dec!(0.36)),
+            ask: Some(dec!(0.38)),
+            last: Some(dec!(0.40)),
+            bid_size: dec!(500),
+            ask_size: dec!(250),
+            volume: dec!(1200),
+        };
+        let m = ForecastExClient::to_oracle_market(&details("20261210", dec!(4.125)), &quote).unwrap();
+        assert_eq!(m.id, "FF-20261210-4.125");
+        assert_eq!(m.platform, "forecastex");
+        assert_eq!(m.question, "Will the Fed Funds Target Rate be above 4.125 on Dec 10, 2026?");
+        assert_eq!((m.current_price_yes, m.current_price_no), (dec!(0.37), dec!(0.63)));
+        assert_eq!(m.liquidity, dec!(275));
+        assert_eq!(m.volume_24h, dec!(1200));
+        assert_eq!(m.category, MarketCategory::Economics);
+        assert_eq!(m.event_group.as_deref(), Some("FF-20261210"));
+        assert_eq!(m.deadline.to_rfc3339(), "2026-12-10T23:59:59+00:00");
+
+        // One-sided book: last trade; no price at all: skipped.
+        let one_sided = Quote { bid: None, ..quote.clone() };
+        assert_eq!(ForecastExClient::to_oracle_market(&details("20261210", dec!(4.125)), &one_sided).unwrap().current_price_yes, dec!(0.40));
+        assert!(ForecastExClient::to_oracle_market(&details("20261210", dec!(4.125)), &Quote::default()).is_none());
+    }
+
+    #[test]
+    fn test_order_construction() {
+        let order = ForecastExClient::order_for(dec!(10), dec!(0.37), "DU1234567").unwrap();
+        assert_eq!(order.quantity, dec!(27));
+        assert_eq!((order.action.as_str(), order.limit_price, order.account.as_str()), ("BUY", dec!(0.37), "DU1234567"));
+        assert!(ForecastExClient::order_for(dec!(0.30), dec!(0.37), "DU1234567").is_err());
+        assert!(ForecastExClient::order_for(dec!(10), dec!(1), "DU1234567").is_err());
+
+        let contract = ForecastExClient::contract_for("FF-20261210-4.125", Side::No).unwrap();
+        let msg = encode_place_order(42, &contract, &order);
+        assert_eq!(&msg[..2], ["3", "42"]);
+        // Contract block: conId, symbol, secType, expiry, strike, right, multiplier, exchange.
+        assert_eq!(&msg[3..10], ["FF", "OPT", "20261210", "4.125", "P", "", "FORECASTX"]);
+        // After secIdType/secId: action, quantity, type, limit, aux, tif, oca group, account.
+        assert_eq!(&msg[16..24], ["BUY", "27", "LMT", "0.37", "", "DAY", "", "DU1234567"]);
+        assert_eq!(msg.len(), 110);
+    }
+
+    #[tokio::test]
+    async fn test_frame_round_trip() {
+        let fields = vec!["9".to_string(), "1".to_string(), String::new(), "FF".to_string()];
+        let bytes = frame(&fields);
+        assert_eq!(&bytes[..4], &(8u32).to_be_bytes());
+        assert_eq!(read_frame(&mut bytes.as_slice()).await.unwrap(), fields);
+    }
+
+    /// TWS stand-in: canned contracts and quotes, fills every order at
+    /// its limit with a flat commission.
+    #[derive(Default)]
+    struct MockTws {
+        orders: Mutex<Vec<(Contract, Order)>>,
+        positions: Vec<PortfolioPosition>,
+    }
+
+    #[async_trait]
+    impl TwsApi for MockTws {
+        async fn contract_details(&self, contract: &Contract) -> Result<Vec<ContractDetails>> {
+            let mut listed = vec![details("20261210", dec!(4.125)), details("20261210", dec!(3.875)), details("20250101", dec!(4))];
+            for d in &mut listed {
+                d.contract.right = if contract.right.is_empty() { "C".into() } else { contract.right.clone() };
+            }
+            if !contract.expiry.is_empty() {
+                listed.retain(|d| d.contract.expiry == contract.expiry && d.contract.strike == contract.strike);
+            }
+            Ok(listed)
+        }
+
+        async fn quote(&self, contract: &Contract) -> Result<Quote> {
+            let ask = if contract.right == "C" { dec!(0.38) } else { dec!(0.64) };
+            Ok(Quote { bid: Some(ask - dec!(0.02)), ask: Some(ask), volume: dec!(10), ..Quote::default() })
+        }
+
+        async fn place_order(&self, contract: &Contract, order: &Order) -> Result<Fill> {
+            self.orders.lock().unwrap().push((contract.clone(), order.clone()));
+            Ok(Fill {
+                order_id: 5,
+                perm_id: 90210,
+                status: "Filled".to_string(),
+                filled: order.quantity,
+                avg_price: order.limit_price,
+                commission: dec!(0.25),
+                exec_ids: vec!["0001".to_string()],
+            })
+        }
+
+        async fn positions(&self) -> Result<Vec<PortfolioPosition>> {
+            Ok(self.positions.clone())
+        }
+
+        async fn account_summary(&self, _tags: &[&str]) -> Result<Vec<AccountValue>> {
+            let value = |account: &str, value: &str| AccountValue {
+                account: account.to_string(),
+                tag: "AvailableFunds".to_string(),
+                value: value.to_string(),
+                currency: "USD".to_string(),
+            };
+            Ok(vec![value("DU999", "1.00"), value("DU1234567", "2500.75")])
+        }
+
+        async fn market_depth(&self, _contract: &Contract, _rows: u32) -> Result<Vec<DepthLevel>> {
+            Ok(vec![
+                DepthLevel { side: BookSide::Bid, price: dec!(0.36), size: dec!(100) },
+                DepthLevel { side: BookSide::Bid, price: dec!(0.35), size: dec!(200) },
+                DepthLevel { side: BookSide::Ask, price: dec!(0.38), size: dec!(50) },
+            ])
+        }
+    }
+
+    fn client(mock: MockTws, account: &str) -> (Arc<MockTws>, ForecastExClient) {
+        let mock = Arc::new(mock);
+        let client = ForecastExClient::new(mock.clone(), Some(account.to_string()), true);
+        (mock, client)
+    }
+
+    #[tokio::test]
+    async fn test_scan_skips_expired_contracts() {
+        let (_, client) = client(MockTws::default(), "DU1234567");
+        let markets = client.fetch_markets().await.unwrap();
+        let ids: Vec<&str> = markets.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["FF-20261210-3.875", "FF-20261210-4.125"]);
+        assert_eq!(markets[0].current_price_yes, dec!(0.37));
+    }
+
+    #[tokio::test]
+    async fn test_place_bet_buys_side_contract_with_commission() {
+        let (mock, client) = client(MockTws::default(), "DU1234567");
+        let receipt = client.place_bet("FF-20261210-4.125", Side::No, dec!(10)).await.unwrap();
+
+        let orders = mock.orders.lock().unwrap();
+        let (contract, order) = &orders[0];
+        assert_eq!((contract.right.as_str(), contract.strike), ("P", dec!(4.125)));
+        assert_eq!((order.quantity, order.limit_price), (dec!(15), dec!(0.64)));
+
+        assert_eq!(receipt.order_id, "90210");
+        assert_eq!(receipt.amount, dec!(9.60));
+        assert_eq!(receipt.fill_price, dec!(0.36)); // YES-equivalent price
+        assert_eq!(receipt.fees, dec!(0.25));
+        assert_eq!(receipt.currency, "USD");
+    }
+
+    #[tokio::test]
+    async fn test_limit_bet_caps_order_price() {
+        let (mock, client) = client(MockTws::default(), "DU1234567");
+        assert!(client.supports_limit_orders());
+        // The NO ask of 0.64 is above the 0.625 limit: the order rests at 0.62.
+        client.place_limit_bet("FF-20261210-4.125", Side::No, dec!(10), dec!(0.625), "key").await.unwrap();
+        // A limit above the ask buys at the ask.
+        client.place_limit_bet("FF-20261210-4.125", Side::No, dec!(10), dec!(0.70), "key").await.unwrap();
+
+        let orders = mock.orders.lock().unwrap();
+        assert_eq!((orders[0].1.quantity, orders[0].1.limit_price), (dec!(16), dec!(0.62)));
+        assert_eq!(orders[1].1.limit_price, dec!(0.64));
+    }
+
+    #[tokio::test]
+    async fn test_positions_balance_and_liquidity() {
+        let held = |account: &str, right: &str, position: Decimal| PortfolioPosition {
+            account: account.to_string(),
+            contract: Contract { right: right.to_string(), multiplier: "1".into(), ..details("20261210", dec!(4.125)).contract },
+            position,
+            avg_cost: dec!(0.40),
+        };
+        let mock = MockTws {
+            positions: vec![
+                held("DU1234567", "C", dec!(25)),
+                held("DU1234567", "P", dec!(0)),
+                held("DU999", "C", dec!(3)),
+                PortfolioPosition { account: "DU1234567".into(), contract: Contract { symbol: "AAPL".into(), sec_type: "STK".into(), ..Contract::default() }, position: dec!(10), avg_cost: dec!(180) },
+            ],
+            ..MockTws::default()
+        };
+        let (_, client) = client(mock, "DU1234567");
+
+        let positions = client.get_positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!((positions[0].market_id.as_str(), positions[0].side), ("FF-20261210-4.125", Side::Yes));
+        assert_eq!((positions[0].size, positions[0].cost), (dec!(25), dec!(10.00)));
+
+        assert_eq!(client.get_balance().await.unwrap(), dec!(2500.75));
+
+        let liquidity = client.check_liquidity("FF-20261210-4.125").await.unwrap();
+        assert_eq!((liquidity.bid_depth, liquidity.ask_depth, liquidity.volume_24h), (dec!(106), dec!(19), dec!(10)));
+    }
+
+    #[tokio::test]
+    async fn test_paper_flag_gates_execution() {
+        let (_, paper) = client(MockTws::default(), "DU1234567");
+        assert!(paper.is_executable() && paper.is_paper() && !paper.is_real_money());
+
+        // A live account is refused while paper is set.
+        let (mock, live_account) = client(MockTws::default(), "U7654321");
+        assert!(!live_account.is_executable());
+        let err = live_account.place_bet("FF-20261210-4.125", Side::Yes, dec!(10)).await.unwrap_err();
+        assert!(err.to_string().contains("not a paper account"));
+        assert!(mock.orders.lock().unwrap().is_empty());
+
+        let live = ForecastExClient::new(Arc::new(MockTws::default()), Some("U7654321".into()), false);
+        assert!(live.is_executable() && live.is_real_money());
+        let scan_only = ForecastExClient::new(Arc::new(MockTws::default()), None, true);
+        assert!(!scan_only.is_executable());
+        assert!(scan_only.get_balance().await.is_err());
+    }
+
+    /// Minimal TWS: handshake, then an account summary per request. The
+    /// first connection is closed on its first request.
+    async fn fake_gateway(listener: TcpListener, connections: Arc<AtomicI64>) {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else { return };
+            let n = connections.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut prefix = [0u8; 4];
+                stream.read_exact(&mut prefix).await.unwrap();
+                assert_eq!(&prefix, b"API\0");
+                let len = stream.read_u32().await.unwrap() as usize;
+                let mut version = vec![0; len];
+                stream.read_exact(&mut version).await.unwrap();
+                assert_eq!(version, b"v151..151");
+                let s = |v: &[&str]| v.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+                stream.write_all(&frame(&s(&["151", "20261016 09:00:00 AEST"]))).await.unwrap();
+
+                let start = read_frame(&mut stream).await.unwrap();
+                assert_eq!(start[..3], s(&["71", "2", "7"]));
+                stream.write_all(&frame(&s(&["15", "1", "DU1234567"]))).await.unwrap();
+                stream.write_all(&frame(&s(&["4", "2", "-1", "2104", "Market data farm connection is OK:usfarm"]))).await.unwrap();
+                stream.write_all(&frame(&s(&["9", "1", "100"]))).await.unwrap();
+
+                while let Ok(req) = read_frame(&mut stream).await {
+                    if req[0] != "62" {
+                        continue;
+                    }
+                    if n == 0 {
+                        return; // Drop the connection mid-request.
+                    }
+                    let id = req[2].as_str();
+                    stream.write_all(&frame(&s(&["63", "1", id, "DU1234567", "AvailableFunds", "812.40", "USD"]))).await.unwrap();
+                    stream.write_all(&frame(&s(&["64", "1", id]))).await.unwrap();
+                }
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_socket_reconnects_after_drop() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let connections = Arc::new(AtomicI64::new(0));
+        tokio::spawn(fake_gateway(listener, connections.clone()));
 
-// Placeholder for Phase 2 implementation
+        let socket = Arc::new(TwsSocket::new("127.0.0.1", port, 7, false));
+        let client = ForecastExClient::new(socket.clone(), Some("DU1234567".into()), true);
+        assert_eq!(client.get_balance().await.unwrap(), dec!(812.40));
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+        // The new connection is kept for the next call.
+        assert_eq!(client.get_balance().await.unwrap(), dec!(812.40));
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,909 @@
+//! Kalshi integration.
+//!
+//! Regulated US event exchange with a REST API (Trade API v2). Market
+//! data (`/markets`, `/markets/{ticker}/orderbook`) is public; portfolio
+//! and order endpoints need an API key id and RSA-PSS signed headers:
+//!
+//! - `KALSHI-ACCESS-KEY` — the key id
+//! - `KALSHI-ACCESS-TIMESTAMP` — request time in unix milliseconds
+//! - `KALSHI-ACCESS-SIGNATURE` — base64 RSA-PSS/SHA-256 signature of
+//!   `timestamp + METHOD + path` (path without the query string)
+//!
+//! API docs: https://trading-api.readme.io/reference/getting-started
+//!
+//! Prices are integer cents (1–99) per contract paying $1, so a YES price
+//! of 57 is a 0.57 probability. Balances and costs are in USD cents.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::pss::BlindedSigningKey;
+use rsa::signature::{RandomizedSigner, SignatureEncoding};
+use rsa::RsaPrivateKey;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::Sha256;
+use tracing::{debug, info};
+
+use super::preflight::{CheckKind, PreflightLimits, PreflightReport};
+use super::PredictionPlatform;
+use crate::config::KalshiConfig;
+use crate::types::{
+    CrossReferences, LiquidityInfo, Market, MarketCategory, MarketResolution, OracleError, Position, Side,
+    TradeReceipt,
+};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+const PROD_HOST: &str = "https://api.elections.kalshi.com";
+const DEMO_HOST: &str = "https://demo-api.kalshi.co";
+/// Prefix of every endpoint path; part of the signed message.
+const API_PATH: &str = "/trade-api/v2";
+const PLATFORM_NAME: &str = "kalshi";
+
+/// Markets per `/markets` page (the API maximum is 1000).
+const PAGE_LIMIT: u32 = 200;
+/// Pages fetched per scan; Kalshi lists tens of thousands of markets.
+const MAX_PAGES: usize = 5;
+/// Contracts traded in the last 24h.
+const MIN_VOLUME_24H: Decimal = dec!(100);
+/// Resting order value in USD.
+const MIN_LIQUIDITY: Decimal = dec!(500);
+
+/// Error codes meaning the market is not accepting orders.
+const CLOSED_MARKET_ERRORS: &[&str] = &["market_closed", "market_not_active", "trading_is_paused"];
+
+// ---------------------------------------------------------------------------
+// Kalshi API types
+// ---------------------------------------------------------------------------
+
+/// One page of `/markets`.
+#[derive(Debug, Deserialize)]
+struct MarketsPage {
+    #[serde(default)]
+    markets: Vec<serde_json::Value>,
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+/// A market from `/markets` or `/markets/{ticker}`. Prices in cents.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KalshiMarket {
+    pub ticker: String,
+    #[serde(default)]
+    pub event_ticker: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: String,
+    #[serde(default)]
+    pub yes_sub_title: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub close_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub yes_bid: i64,
+    #[serde(default)]
+    pub yes_ask: i64,
+    #[serde(default)]
+    pub no_bid: i64,
+    #[serde(default)]
+    pub no_ask: i64,
+    #[serde(default)]
+    pub last_price: i64,
+    #[serde(default)]
+    pub volume_24h: i64,
+    /// Value of resting orders, in cents.
+    #[serde(default)]
+    pub liquidity: i64,
+    /// Deprecated upstream and often empty; see [`KalshiClient::categorize`].
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub rules_primary: String,
+    #[serde(default)]
+    pub rules_secondary: String,
+    /// "yes" / "no" once determined; "void" for a voided market.
+    #[serde(default)]
+    pub result: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarketResponse {
+    market: KalshiMarket,
+}
+
+/// Bids only: a YES bid at `p` is a NO offer at `100 - p` and vice versa.
+/// Each level is `[price_cents, contracts]`, best (highest) last.
+#[derive(Debug, Default, Deserialize)]
+struct Orderbook {
+    #[serde(default)]
+    yes: Option<Vec<[i64; 2]>>,
+    #[serde(default)]
+    no: Option<Vec<[i64; 2]>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderbookResponse {
+    #[serde(default)]
+    orderbook: Orderbook,
+}
+
+#[derive(Debug, Deserialize)]
+struct KalshiOrder {
+    order_id: String,
+    #[serde(default)]
+    status: String,
+    /// YES price of the order in cents, whichever side it buys.
+    #[serde(default)]
+    yes_price: i64,
+    #[serde(default)]
+    fill_count: Option<i64>,
+    #[serde(default)]
+    taker_fill_cost: i64,
+    #[serde(default)]
+    maker_fill_cost: i64,
+    #[serde(default)]
+    taker_fees: i64,
+    #[serde(default)]
+    maker_fees: i64,
+    #[serde(default)]
+    created_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderResponse {
+    order: KalshiOrder,
+}
+
+#[derive(Debug, Deserialize)]
+struct BalanceResponse {
+    /// Available balance in cents.
+    balance: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarketPosition {
+    ticker: String,
+    /// Contracts held: positive YES, negative NO.
+    #[serde(default)]
+    position: i64,
+    /// Cost of the open contracts, in cents.
+    #[serde(default)]
+    market_exposure: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PositionsResponse {
+    #[serde(default)]
+    market_positions: Vec<MarketPosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ApiError,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+// ---------------------------------------------------------------------------
+// Request signing
+// ---------------------------------------------------------------------------
+
+/// Signs requests with the account's RSA key. The key never leaves here.
+pub struct RequestSigner {
+    key_id: String,
+    key: BlindedSigningKey<Sha256>,
+}
+
+impl RequestSigner {
+    /// Load a PKCS#1 (`BEGIN RSA PRIVATE KEY`, as Kalshi issues them) or
+    /// PKCS#8 PEM key.
+    pub fn from_pem(key_id: impl Into<String>, pem: &str) -> Result<Self> {
+        let key = RsaPrivateKey::from_pkcs1_pem(pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs8_pem(pem))
+            .context("Kalshi private key is not a PKCS#1 or PKCS#8 RSA PEM")?;
+        Ok(Self { key_id: key_id.into(), key: BlindedSigningKey::new(key) })
+    }
+
+    /// The string that is signed: `timestamp + METHOD + path`.
+    pub fn message(timestamp_ms: i64, method: &Method, path: &str) -> String {
+        let path = path.split('?').next().unwrap_or(path);
+        format!("{timestamp_ms}{}{path}", method.as_str())
+    }
+
+    /// Base64 RSA-PSS (SHA-256, digest-length salt) signature of `message`.
+    pub fn sign(&self, message: &str) -> String {
+        let signature = self.key.sign_with_rng(&mut rsa::rand_core::OsRng, message.as_bytes());
+        base64::engine::general_purpose::STANDARD.encode(signature.to_bytes())
+    }
+
+    /// Auth headers for a request to `path` (including [`API_PATH`]).
+    pub fn headers(&self, method: &Method, path: &str, timestamp_ms: i64) -> [(&'static str, String); 3] {
+        [
+            ("KALSHI-ACCESS-KEY", self.key_id.clone()),
+            ("KALSHI-ACCESS-TIMESTAMP", timestamp_ms.to_string()),
+            ("KALSHI-ACCESS-SIGNATURE", self.sign(&Self::message(timestamp_ms, method, path))),
+        ]
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+pub struct KalshiClient {
+    http: Client,
+    host: &'static str,
+    /// `None`: scan-only (market data needs no auth).
+    signer: Option<RequestSigner>,
+}
+
+impl KalshiClient {
+    /// Client against `host` ([`PROD_HOST`] or [`DEMO_HOST`]).
+    pub fn new(host: &'static str, signer: Option<RequestSigner>) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .user_agent("ORACLE/0.1.0 (prediction-market-agent)")
+            .build()
+            .context("Failed to build HTTP client for Kalshi")?;
+        Ok(Self { http, host, signer })
+    }
+
+    /// Client from `[platforms.kalshi]`. Without both credential env vars
+    /// set the client is scan-only; a key that can't be read is an error.
+    pub fn from_config(cfg: &KalshiConfig) -> Result<Self> {
+        let host = if cfg.demo { DEMO_HOST } else { PROD_HOST };
+        let key_id = std::env::var(&cfg.api_key_id_env).ok().filter(|v| !v.is_empty());
+        let key_path = std::env::var(&cfg.private_key_path_env).ok().filter(|v| !v.is_empty());
+        let signer = match (key_id, key_path) {
+            (Some(id), Some(path)) => {
+                let pem = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read Kalshi private key {path}"))?;
+                Some(RequestSigner::from_pem(id, &pem)?)
+            }
+            _ => {
+                info!(
+                    "Kalshi credentials not set ({} / {}) — scan only",
+                    cfg.api_key_id_env, cfg.private_key_path_env
+                );
+                None
+            }
+        };
+        Self::new(host, signer)
+    }
+
+    /// Whether orders, balance and positions are available.
+    pub fn can_trade(&self) -> bool {
+        self.signer.is_some()
+    }
+
+    // -- HTTP --------------------------------------------------------------
+
+    /// Request `path` (relative to [`API_PATH`]), signed when credentials
+    /// are configured; `auth` requires them.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<&serde_json::Value>,
+        auth: bool,
+    ) -> Result<T> {
+        let full_path = format!("{API_PATH}{path}");
+        let mut req = self.http.request(method.clone(), format!("{}{full_path}", self.host)).query(query);
+        match &self.signer {
+            Some(signer) => {
+                for (name, value) in signer.headers(&method, &full_path, Utc::now().timestamp_millis()) {
+                    req = req.header(name, value);
+                }
+            }
+            None if auth => anyhow::bail!("Kalshi credentials required for {method} {path}"),
+            None => {}
+        }
+        if let Some(body) = body {
+            req = req.json(body);
+        }
+
+        let resp = req.send().await.with_context(|| format!("Kalshi {method} {path} failed"))?;
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            // A reused client_order_id is rejected as a conflict.
+            if let (reqwest::StatusCode::CONFLICT, Some(key)) = (status, body.and_then(|b| b["client_order_id"].as_str())) {
+                return Err(OracleError::DuplicateOrder { platform: PLATFORM_NAME.to_string(), key: key.to_string() }.into());
+            }
+            return Err(Self::api_error(status, &text, path));
+        }
+        serde_json::from_str(&text).with_context(|| format!("Failed to parse Kalshi {path} response"))
+    }
+
+    /// Error for a non-2xx response: [`OracleError::MarketClosed`] when
+    /// the venue says the market isn't trading.
+    fn api_error(status: reqwest::StatusCode, body: &str, path: &str) -> anyhow::Error {
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(ErrorBody { error }) if CLOSED_MARKET_ERRORS.contains(&error.code.as_str()) => OracleError::MarketClosed {
+                platform: PLATFORM_NAME.to_string(),
+                market_id: path.rsplit('/').next().unwrap_or_default().to_string(),
+                reason: error.code,
+            }
+            .into(),
+            Ok(ErrorBody { error }) => anyhow::anyhow!("Kalshi error {status} ({}): {}", error.code, error.message),
+            Err(_) => anyhow::anyhow!("Kalshi error {status}: {body}"),
+        }
+    }
+
+    async fn fetch_market(&self, ticker: &str) -> Result<KalshiMarket> {
+        let resp: MarketResponse = self.request(Method::GET, &format!("/markets/{ticker}"), &[], None, false).await?;
+        Ok(resp.market)
+    }
+
+    /// Open markets, up to [`MAX_PAGES`] pages.
+    pub async fn fetch_open_markets(&self) -> Result<Vec<KalshiMarket>> {
+        let mut markets = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![("status", "open".to_string()), ("limit", PAGE_LIMIT.to_string())];
+            if let Some(c) = &cursor {
+                query.push(("cursor", c.clone()));
+            }
+            let page: MarketsPage = self.request(Method::GET, "/markets", &query, None, false).await?;
+            let (parsed, _) = super::parse_entries::<KalshiMarket>("kalshi/markets", page.markets);
+            markets.extend(parsed);
+            cursor = page.cursor.filter(|c| !c.is_empty());
+            if cursor.is_none() {
+                break;
+            }
+        }
+        info!(count = markets.len(), "Fetched raw Kalshi markets");
+        Ok(markets)
+    }
+
+    // -- Conversion --------------------------------------------------------
+
+    /// Cents to a USD amount or probability.
+    pub fn cents(c: i64) -> Decimal {
+        Decimal::new(c, 2)
+    }
+
+    /// YES probability: the bid/ask midpoint when both sides are quoted,
+    /// else the last trade. `None` for a market that never traded.
+    pub fn yes_probability(m: &KalshiMarket) -> Option<Decimal> {
+        let quoted = |c: i64| (1..=99).contains(&c);
+        if quoted(m.yes_bid) && quoted(m.yes_ask) && m.yes_bid <= m.yes_ask {
+            Some(Self::cents(m.yes_bid + m.yes_ask) / dec!(2))
+        } else if quoted(m.last_price) {
+            Some(Self::cents(m.last_price))
+        } else {
+            None
+        }
+    }
+
+    /// Category from Kalshi's own label, falling back to the series
+    /// ticker and the title.
+    pub fn categorize(category: &str, event_ticker: &str, title: &str) -> MarketCategory {
+        match category.to_lowercase().as_str() {
+            "politics" | "elections" | "world" => return MarketCategory::Politics,
+            "economics" | "financials" | "crypto" | "companies" => return MarketCategory::Economics,
+            "climate and weather" | "weather" | "climate" => return MarketCategory::Weather,
+            "sports" => return MarketCategory::Sports,
+            "entertainment" | "culture" | "science and technology" | "social" => return MarketCategory::Culture,
+            _ => {}
+        }
+
+        let series = event_ticker.split('-').next().unwrap_or_default().to_uppercase();
+        let series = series.strip_prefix("KX").unwrap_or(&series);
+        const SERIES: &[(&[&str], MarketCategory)] = &[
+            (&["HIGH", "LOW", "RAIN", "SNOW", "HURR", "TEMP"], MarketCategory::Weather),
+            (&["NBA", "NFL", "MLB", "NHL", "NCAA", "WNBA", "EPL", "UCL", "MLS", "UFC", "PGA", "ATP", "WTA", "F1", "NASCAR"], MarketCategory::Sports),
+            (&["FED", "CPI", "GDP", "PAYROLLS", "U3", "JOBLESS", "INX", "NASDAQ", "BTC", "ETH", "GAS", "TNOTE", "RECESS"], MarketCategory::Economics),
+            (&["PRES", "SENATE", "HOUSE", "GOV", "TRUMP", "APPROVAL", "SCOTUS", "ELECT"], MarketCategory::Politics),
+            (&["OSCAR", "GRAMMY", "EMMY", "BILLBOARD", "SPOTIFY", "RT", "NETFLIX", "BOXOFFICE"], MarketCategory::Culture),
+        ];
+        for (prefixes, category) in SERIES {
+            if prefixes.iter().any(|p| series.starts_with(p)) {
+                return *category;
+            }
+        }
+
+        let t = title.to_lowercase();
+        let any = |words: &[&str]| words.iter().any(|w| t.contains(w));
+        if any(&["election", "president", "senate", "congress", "governor", "nominee", "supreme court"]) {
+            MarketCategory::Politics
+        } else if any(&["temperature", "hurricane", "rain", "snow", "weather"]) {
+            MarketCategory::Weather
+        } else if any(&["fed ", "rate cut", "inflation", "cpi", "gdp", "bitcoin", "s&p", "recession", "unemployment"]) {
+            MarketCategory::Economics
+        } else if any(&[" winner?", "championship", "super bowl", "world series", "playoffs", "grand prix"]) {
+            MarketCategory::Sports
+        } else if any(&["oscar", "grammy", "box office", "album", "movie", "billboard"]) {
+            MarketCategory::Culture
+        } else {
+            MarketCategory::Other
+        }
+    }
+
+    /// Convert a Kalshi market into an ORACLE market. `None` for markets
+    /// not trading or without a price.
+    pub fn to_oracle_market(m: &KalshiMarket) -> Option<Market> {
+        if m.ticker.is_empty() || m.title.is_empty() || !matches!(m.status.as_str(), "active" | "open") {
+            return None;
+        }
+        let price_yes = Self::yes_probability(m)?;
+        // Multi-outcome events share a title; the YES subtitle names the outcome.
+        let question = if m.yes_sub_title.is_empty() || m.title.contains(&m.yes_sub_title) {
+            m.title.clone()
+        } else {
+            format!("{} — {}", m.title, m.yes_sub_title)
+        };
+        let series = m.event_ticker.split('-').next().unwrap_or_default().to_lowercase();
+
+        Some(Market {
+            id: m.ticker.clone(),
+            platform: PLATFORM_NAME.to_string(),
+            question,
+            description: if m.subtitle.is_empty() { m.rules_secondary.clone() } else { m.subtitle.clone() },
+            category: Self::categorize(&m.category, &m.event_ticker, &m.title),
+            current_price_yes: price_yes,
+            current_price_no: Decimal::ONE - price_yes,
+            volume_24h: Decimal::from(m.volume_24h),
+            liquidity: Self::cents(m.liquidity),
+            deadline: m.close_time.unwrap_or_else(|| Utc::now() + chrono::Duration::days(365)),
+            resolution_criteria: m.rules_primary.clone(),
+            url: format!("https://kalshi.com/markets/{series}"),
+            cross_refs: CrossReferences::default(),
+            event_group: (!m.event_ticker.is_empty()).then(|| format!("kalshi:{}", m.event_ticker)),
+            facts: None,
+            tags: Vec::new(),
+        })
+    }
+
+    /// Drop thin, expired and near-certain markets.
+    pub fn filter_markets(markets: Vec<Market>) -> Vec<Market> {
+        let now = Utc::now();
+        markets
+            .into_iter()
+            .filter(|m| {
+                m.volume_24h >= MIN_VOLUME_24H
+                    && m.liquidity >= MIN_LIQUIDITY
+                    && m.deadline > now
+                    && m.current_price_yes > dec!(0.02)
+                    && m.current_price_yes < dec!(0.98)
+            })
+            .collect()
+    }
+
+    /// Resolution of a determined market; `None` while it is undecided.
+    pub fn market_resolution(m: &KalshiMarket) -> Result<Option<MarketResolution>> {
+        if !matches!(m.status.as_str(), "determined" | "finalized" | "settled") {
+            return Ok(None);
+        }
+        Ok(Some(match m.result.as_str() {
+            "yes" => MarketResolution::Yes,
+            "no" => MarketResolution::No,
+            "void" => MarketResolution::Cancelled,
+            "" => return Ok(None),
+            other => anyhow::bail!("Unknown Kalshi result {other:?} on {}", m.ticker),
+        }))
+    }
+
+    // -- Orders ------------------------------------------------------------
+
+    /// Ask in cents for buying `side`; `None` when nothing is offered.
+    pub fn ask_cents(m: &KalshiMarket, side: Side) -> Option<i64> {
+        let ask = match side {
+            Side::Yes => m.yes_ask,
+            Side::No => m.no_ask,
+        };
+        (1..=99).contains(&ask).then_some(ask)
+    }
+
+    /// Limit buy at the current ask for `side`, under `client_order_id`.
+    async fn place_order(&self, market_id: &str, side: Side, amount: Decimal, client_order_id: &str) -> Result<TradeReceipt> {
+        anyhow::ensure!(self.can_trade(), "Kalshi credentials required to place orders");
+        let market = self.fetch_market(market_id).await?;
+        if market.status != "active" {
+            return Err(OracleError::MarketClosed {
+                platform: PLATFORM_NAME.to_string(),
+                market_id: market_id.to_string(),
+                reason: format!("market {}", market.status),
+            }
+            .into());
+        }
+        let price = Self::ask_cents(&market, side).with_context(|| format!("No {side} ask on {market_id}"))?;
+        let body = Self::order_payload(market_id, side, price, amount, client_order_id)?;
+        debug!(%market_id, %side, price, "Submitting Kalshi order");
+
+        let raw: serde_json::Value = self.request(Method::POST, "/portfolio/orders", &[], Some(&body), true).await?;
+        let resp: OrderResponse = serde_json::from_value(raw.clone()).context("Failed to parse Kalshi order response")?;
+        let receipt = Self::receipt(&resp.order, market_id, side, raw)?;
+        info!(
+            order_id = %receipt.order_id,
+            market_id = %market_id,
+            side = %side,
+            amount = %receipt.amount,
+            price = %receipt.fill_price,
+            "Kalshi order placed"
+        );
+        Ok(receipt)
+    }
+
+    /// `POST /portfolio/orders` body: an immediate-or-cancel limit buy of
+    /// as many whole contracts as `amount` (USD) covers at `price_cents`.
+    pub fn order_payload(
+        ticker: &str,
+        side: Side,
+        price_cents: i64,
+        amount: Decimal,
+        client_order_id: &str,
+    ) -> Result<serde_json::Value> {
+        anyhow::ensure!((1..=99).contains(&price_cents), "Kalshi price {price_cents}¢ out of range");
+        let count = (amount / Self::cents(price_cents)).floor();
+        let count = i64::try_from(count).unwrap_or(0);
+        anyhow::ensure!(count >= 1, "${amount} buys no whole contract at {price_cents}¢");
+        let (side, price_key) = match side {
+            Side::Yes => ("yes", "yes_price"),
+            Side::No => ("no", "no_price"),
+        };
+        let mut body = serde_json::json!({
+            "ticker": ticker,
+            "client_order_id": client_order_id,
+            "action": "buy",
+            "side": side,
+            "type": "limit",
+            "count": count,
+            "time_in_force": "immediate_or_cancel",
+        });
+        body[price_key] = price_cents.into();
+        Ok(body)
+    }
+
+    /// Receipt for a placed order. Amount is the filled cost and
+    /// `fill_price` the YES price, as for every platform.
+    fn receipt(order: &KalshiOrder, market_id: &str, side: Side, raw: serde_json::Value) -> Result<TradeReceipt> {
+        let filled = order.fill_count.unwrap_or(0);
+        anyhow::ensure!(filled > 0, "Kalshi order {} not filled ({})", order.order_id, order.status);
+        Ok(TradeReceipt {
+            order_id: order.order_id.clone(),
+            market_id: market_id.to_string(),
+            platform: PLATFORM_NAME.to_string(),
+            side,
+            amount: Self::cents(order.taker_fill_cost + order.maker_fill_cost),
+            fill_price: Self::cents(order.yes_price),
+            fees: Self::cents(order.taker_fees + order.maker_fees),
+            timestamp: order.created_time.unwrap_or_else(Utc::now),
+            currency: "USD".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: Some(raw),
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        })
+    }
+
+    /// Dollar depth of the book: YES bids, and YES offers (NO bids seen
+    /// from the other side).
+    fn depth(book: &Orderbook) -> (Decimal, Decimal) {
+        let sum = |levels: &Option<Vec<[i64; 2]>>, price: fn(i64) -> i64| {
+            levels.iter().flatten().map(|[p, qty]| Self::cents(price(*p) * qty)).sum::<Decimal>()
+        };
+        (sum(&book.yes, |p| p), sum(&book.no, |p| 100 - p))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PredictionPlatform trait implementation
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl PredictionPlatform for KalshiClient {
+    async fn fetch_markets(&self) -> Result<Vec<Market>> {
+        let raw = self.fetch_open_markets().await?;
+        let markets: Vec<Market> = raw.iter().filter_map(Self::to_oracle_market).collect();
+        let filtered = Self::filter_markets(markets);
+        info!(count = filtered.len(), "Kalshi markets after filtering");
+        Ok(filtered)
+    }
+
+    /// Limit buy at the current ask for `side`.
+    async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+        self.place_order(market_id, side, amount, &uuid::Uuid::new_v4().to_string()).await
+    }
+
+    fn supports_idempotency(&self) -> bool {
+        true
+    }
+
+    /// Place under `key` as the order's `client_order_id`.
+    async fn place_bet_keyed(&self, market_id: &str, side: Side, amount: Decimal, key: &str) -> Result<TradeReceipt> {
+        self.place_order(market_id, side, amount, key).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        let resp: PositionsResponse = self
+            .request(Method::GET, "/portfolio/positions", &[("limit", "1000".to_string())], None, true)
+            .await?;
+        Ok(resp
+            .market_positions
+            .into_iter()
+            .filter(|p| p.position != 0)
+            .map(|p| {
+                let size = Self::cents(p.market_exposure);
+                Position {
+                    market_id: p.ticker,
+                    platform: PLATFORM_NAME.to_string(),
+                    side: if p.position > 0 { Side::Yes } else { Side::No },
+                    size,
+                    entry_price: size / Decimal::from(p.position.abs()),
+                    current_value: size, // Would need the market price for a mark
+                    cost: size,
+                    category: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Available balance in USD.
+    async fn get_balance(&self) -> Result<Decimal> {
+        let resp: BalanceResponse = self.request(Method::GET, "/portfolio/balance", &[], None, true).await?;
+        Ok(Self::cents(resp.balance))
+    }
+
+    async fn check_liquidity(&self, market_id: &str) -> Result<LiquidityInfo> {
+        let book_path = format!("/markets/{market_id}/orderbook");
+        let (book, market) = tokio::try_join!(
+            self.request::<OrderbookResponse>(Method::GET, &book_path, &[], None, false),
+            self.fetch_market(market_id),
+        )?;
+        let (bid_depth, ask_depth) = Self::depth(&book.orderbook);
+        Ok(LiquidityInfo { bid_depth, ask_depth, volume_24h: Decimal::from(market.volume_24h) })
+    }
+
+    async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+        Self::market_resolution(&self.fetch_market(market_id).await?)
+    }
+
+    /// Real money once credentials are configured.
+    fn is_real_money(&self) -> bool {
+        self.can_trade()
+    }
+
+    fn name(&self) -> &str {
+        PLATFORM_NAME
+    }
+
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        let mut report = PreflightReport::new(PLATFORM_NAME);
+        if self.can_trade() {
+            report.balance(self.get_balance().await, limits);
+        } else {
+            report.fail(CheckKind::Auth, "Kalshi credentials not configured");
+        }
+        report
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture<T: DeserializeOwned>(path: &str) -> T {
+        serde_json::from_str(&crate::fixtures::read(path)).unwrap()
+    }
+
+    fn fixture_markets() -> Vec<KalshiMarket> {
+        let page: MarketsPage = fixture("kalshi/markets.json");
+        let (markets, skipped) = crate::platforms::parse_entries::<KalshiMarket>("test", page.markets);
+        assert_eq!(skipped, 0);
+        markets
+    }
+
+    #[test]
+    fn test_price_conversion_from_cents() {
+        assert_eq!(KalshiClient::cents(57), dec!(0.57));
+        assert_eq!(KalshiClient::cents(48213), dec!(482.13));
+
+        let mut m = fixture_markets().remove(0);
+        // Midpoint of 57 / 59.
+        assert_eq!(KalshiClient::yes_probability(&m), Some(dec!(0.58)));
+        // One-sided book: last trade.
+        m.yes_ask = 0;
+        assert_eq!(KalshiClient::yes_probability(&m), Some(dec!(0.58)));
+        m.last_price = 0;
+        assert_eq!(KalshiClient::yes_probability(&m), None);
+    }
+
+    #[test]
+    fn test_fixture_markets_convert_and_filter() {
+        let markets = fixture_markets();
+        assert_eq!(markets.len(), 2);
+
+        let fed = KalshiClient::to_oracle_market(&markets[0]).unwrap();
+        assert_eq!(fed.id, "KXFEDDECISION-26DEC-C25");
+        assert_eq!(fed.platform, "kalshi");
+        assert_eq!(fed.question, "Will the Fed cut rates at the December 2026 meeting? — Cut 25bps");
+        assert_eq!(fed.category, MarketCategory::Economics);
+        assert_eq!((fed.current_price_yes, fed.current_price_no), (dec!(0.58), dec!(0.42)));
+        assert_eq!(fed.liquidity, dec!(123456.78));
+        assert_eq!(fed.volume_24h, dec!(48210));
+        assert_eq!(fed.event_group.as_deref(), Some("kalshi:KXFEDDECISION-26DEC"));
+        assert_eq!(fed.url, "https://kalshi.com/markets/kxfeddecision");
+
+        // Unquoted game market: priced off the last trade, then filtered as thin.
+        let game = KalshiClient::to_oracle_market(&markets[1]).unwrap();
+        assert_eq!(game.current_price_yes, dec!(0.44));
+        assert_eq!(game.category, MarketCategory::Sports);
+        let kept = KalshiClient::filter_markets(vec![fed, game]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].id, "KXFEDDECISION-26DEC-C25");
+
+        let mut closed = markets[0].clone();
+        closed.status = "settled".to_string();
+        assert!(KalshiClient::to_oracle_market(&closed).is_none());
+    }
+
+    #[test]
+    fn test_category_classification() {
+        use MarketCategory::*;
+        let cases = [
+            ("Politics", "KXPRES-28", "Who will win?", Politics),
+            ("Climate and Weather", "X-1", "", Weather),
+            ("Financials", "X-1", "", Economics),
+            ("Science and Technology", "X-1", "", Culture),
+            ("", "KXHIGHNY-26OCT17", "Highest temperature in NYC today?", Weather),
+            ("", "KXNFLGAME-26OCT19BUFKC", "Buffalo at Kansas City Winner?", Sports),
+            ("", "KXCPIYOY-26SEP", "CPI year over year in Sep 2026?", Economics),
+            ("", "OTHER-1", "Will the Senate confirm the nominee?", Politics),
+            ("", "OTHER-1", "Will a hurricane make landfall in Florida?", Weather),
+            ("", "OTHER-1", "Top album on Billboard this week?", Culture),
+            ("", "OTHER-1", "Will the bridge reopen?", Other),
+        ];
+        for (category, ticker, title, expected) in cases {
+            assert_eq!(KalshiClient::categorize(category, ticker, title), expected, "{category:?} {ticker} {title:?}");
+        }
+    }
+
+    #[test]
+    fn test_order_payload_construction() {
+        let body = KalshiClient::order_payload("KXFED-26DEC-C25", Side::Yes, 59, dec!(10), "cid-1").unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "ticker": "KXFED-26DEC-C25",
+                "client_order_id": "cid-1",
+                "action": "buy",
+                "side": "yes",
+                "type": "limit",
+                "count": 16,
+                "time_in_force": "immediate_or_cancel",
+                "yes_price": 59,
+            })
+        );
+
+        let no = KalshiClient::order_payload("T", Side::No, 43, dec!(5), "cid-2").unwrap();
+        assert_eq!((no["side"].as_str(), no["no_price"].as_i64(), no["count"].as_i64()), (Some("no"), Some(43), Some(11)));
+        assert!(no.get("yes_price").is_none());
+
+        // Less than one contract, or a price off the 1–99¢ range.
+        assert!(KalshiClient::order_payload("T", Side::Yes, 59, dec!(0.50), "c").is_err());
+        assert!(KalshiClient::order_payload("T", Side::Yes, 0, dec!(10), "c").is_err());
+        assert!(KalshiClient::order_payload("T", Side::Yes, 100, dec!(10), "c").is_err());
+    }
+
+    #[test]
+    fn test_market_resolution() {
+        let mut m = fixture_markets().remove(0);
+        assert_eq!(KalshiClient::market_resolution(&m).unwrap(), None);
+        m.status = "finalized".to_string();
+        for (result, expected) in [
+            ("yes", Some(MarketResolution::Yes)),
+            ("no", Some(MarketResolution::No)),
+            ("void", Some(MarketResolution::Cancelled)),
+            ("", None),
+        ] {
+            m.result = result.to_string();
+            assert_eq!(KalshiClient::market_resolution(&m).unwrap(), expected, "{result:?}");
+        }
+        m.result = "scalar".to_string();
+        assert!(KalshiClient::market_resolution(&m).is_err());
+    }
+
+    #[test]
+    fn test_ask_for_side() {
+        let mut m = fixture_markets().remove(0);
+        assert_eq!(KalshiClient::ask_cents(&m, Side::Yes), Some(59));
+        assert_eq!(KalshiClient::ask_cents(&m, Side::No), Some(43));
+        m.no_ask = 100;
+        assert_eq!(KalshiClient::ask_cents(&m, Side::No), None);
+    }
+
+    #[test]
+    fn test_fixture_order_receipt() {
+        let raw: serde_json::Value = fixture("kalshi/order.json");
+        let resp: OrderResponse = serde_json::from_value(raw.clone()).unwrap();
+        let receipt = KalshiClient::receipt(&resp.order, "KXFEDDECISION-26DEC-C25", Side::Yes, raw).unwrap();
+        assert_eq!(receipt.order_id, "ee5a2ba3-3b25-4b2f-a3c6-1b0c8a2f7d41");
+        assert_eq!((receipt.amount, receipt.fill_price, receipt.fees), (dec!(9.44), dec!(0.59), dec!(0.28)));
+        assert_eq!(receipt.currency, "USD");
+
+        let mut unfilled: OrderResponse = fixture("kalshi/order.json");
+        unfilled.order.fill_count = Some(0);
+        unfilled.order.status = "canceled".to_string();
+        let err = KalshiClient::receipt(&unfilled.order, "T", Side::Yes, serde_json::Value::Null).unwrap_err();
+        assert!(err.to_string().contains("not filled (canceled)"));
+    }
+
+    #[test]
+    fn test_fixture_balance_positions_and_book() {
+        let balance: BalanceResponse = fixture("kalshi/balance.json");
+        assert_eq!(KalshiClient::cents(balance.balance), dec!(482.13));
+
+        let positions: PositionsResponse = fixture("kalshi/positions.json");
+        assert_eq!(positions.market_positions.len(), 3);
+        assert_eq!(positions.market_positions[1].position, -30);
+
+        let book: OrderbookResponse = fixture("kalshi/orderbook.json");
+        // YES bids: 55×1200 + 56×340 + 57×815 cents; offers: NO bids at 100 − p.
+        let (bids, asks) = KalshiClient::depth(&book.orderbook);
+        assert_eq!(bids, dec!(660) + dec!(190.40) + dec!(464.55));
+        assert_eq!(asks, dec!(305) + dec!(750) + dec!(365.80));
+        let empty: OrderbookResponse = serde_json::from_str(r#"{"orderbook":{"yes":null,"no":null}}"#).unwrap();
+        assert_eq!(KalshiClient::depth(&empty.orderbook), (Decimal::ZERO, Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_closed_market_error() {
+        let body = r#"{"error":{"code":"market_closed","message":"market is closed"}}"#;
+        let err = KalshiClient::api_error(reqwest::StatusCode::BAD_REQUEST, body, "/markets/KXFED-26DEC-C25");
+        assert!(matches!(err.downcast_ref::<OracleError>(), Some(OracleError::MarketClosed { .. })));
+        let other = KalshiClient::api_error(reqwest::StatusCode::UNAUTHORIZED, r#"{"error":{"code":"unauthorized","message":"bad signature"}}"#, "/portfolio/orders");
+        assert!(other.downcast_ref::<OracleError>().is_none());
+        assert!(other.to_string().contains("bad signature"));
+    }
+
+    #[test]
+    fn test_request_signing() {
+        use rsa::pkcs1::{EncodeRsaPrivateKey, LineEnding};
+        use rsa::signature::Verifier;
+
+        let key = RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 1024).unwrap();
+        let pem = key.to_pkcs1_pem(LineEnding::LF).unwrap();
+        let signer = RequestSigner::from_pem("key-id", &pem).unwrap();
+        assert!(RequestSigner::from_pem("key-id", "not a key").is_err());
+
+        // The query string is not part of the signed path.
+        let message = RequestSigner::message(1_792_117_267_318, &Method::GET, "/trade-api/v2/portfolio/orders?limit=5");
+        assert_eq!(message, "1792117267318GET/trade-api/v2/portfolio/orders");
+
+        let headers = signer.headers(&Method::POST, "/trade-api/v2/portfolio/orders", 1_792_117_267_318);
+        assert_eq!(headers[0], ("KALSHI-ACCESS-KEY", "key-id".to_string()));
+        assert_eq!(headers[1], ("KALSHI-ACCESS-TIMESTAMP", "1792117267318".to_string()));
+        let signature = base64::engine::general_purpose::STANDARD.decode(&headers[2].1).unwrap();
+        let verifier = rsa::pss::VerifyingKey::<Sha256>::new(key.to_public_key());
+        let signature = rsa::pss::Signature::try_from(signature.as_slice()).unwrap();
+        assert!(verifier.verify(b"1792117267318POST/trade-api/v2/portfolio/orders", &signature).is_ok());
+        assert!(verifier.verify(b"1792117267318GET/trade-api/v2/portfolio/orders", &signature).is_err());
+    }
+}
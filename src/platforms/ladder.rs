@@ -0,0 +1,185 @@
+//! Valid price increments per venue.
+//!
+//! Every limit price we send must sit on the venue's tick ladder:
+//!
+//! | Venue      | Quoted as                | Ladder                                  |
+//! |------------|--------------------------|-----------------------------------------|
+//! | Betfair    | decimal odds             | non-uniform, 1.01–1000 ([`BETFAIR`])     |
+//! | Polymarket | outcome-token price      | 0.01 or 0.001, reported per market       |
+//! | Manifold   | `limitProb` (YES terms)  | 0.01 steps, 0.01–0.99                    |
+//!
+//! [`PriceLadder::snap`] rounds an off-ladder price to a neighbouring tick.
+//! Callers round toward the passive side of the book, so that snapping
+//! never makes an order more aggressive than the computed limit:
+//! a Betfair back asks for higher odds and a lay for lower, a Polymarket buy
+//! pays less, and a Manifold YES limit sits lower while a NO limit (a floor
+//! on the YES probability) sits higher.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Direction to round an off-ladder price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// One band of a ladder: prices in `[from, to]` move in `step`s from `from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Band {
+    pub from: Decimal,
+    pub to: Decimal,
+    pub step: Decimal,
+}
+
+/// Betfair's decimal-odds price ladder.
+pub const BETFAIR: &[Band] = &[
+    Band { from: dec!(1.01), to: dec!(2), step: dec!(0.01) },
+    Band { from: dec!(2), to: dec!(3), step: dec!(0.02) },
+    Band { from: dec!(3), to: dec!(4), step: dec!(0.05) },
+    Band { from: dec!(4), to: dec!(6), step: dec!(0.1) },
+    Band { from: dec!(6), to: dec!(10), step: dec!(0.2) },
+    Band { from: dec!(10), to: dec!(20), step: dec!(0.5) },
+    Band { from: dec!(20), to: dec!(30), step: dec!(1) },
+    Band { from: dec!(30), to: dec!(50), step: dec!(2) },
+    Band { from: dec!(50), to: dec!(100), step: dec!(5) },
+    Band { from: dec!(100), to: dec!(1000), step: dec!(10) },
+];
+
+/// Probability ladder in cents (Manifold, most Polymarket markets).
+pub const CENTS: &[Band] = &[Band { from: dec!(0.01), to: dec!(0.99), step: dec!(0.01) }];
+
+/// Probability ladder in tenths of a cent (Polymarket markets near 0 or 1).
+pub const MILLS: &[Band] = &[Band { from: dec!(0.001), to: dec!(0.999), step: dec!(0.001) }];
+
+/// The set of prices a venue accepts for one market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceLadder {
+    bands: &'static [Band],
+}
+
+impl PriceLadder {
+    pub const BETFAIR: Self = Self { bands: BETFAIR };
+    pub const CENTS: Self = Self { bands: CENTS };
+    pub const MILLS: Self = Self { bands: MILLS };
+
+    /// Ladder for a venue-reported minimum tick size. Unknown sizes fall
+    /// back to the coarser cent ladder, which is valid on either.
+    pub fn for_tick(tick: Decimal) -> Self {
+        if tick == dec!(0.001) {
+            Self::MILLS
+        } else {
+            Self::CENTS
+        }
+    }
+
+    pub fn min(&self) -> Decimal {
+        self.bands[0].from
+    }
+
+    pub fn max(&self) -> Decimal {
+        self.bands[self.bands.len() - 1].to
+    }
+
+    /// Whether `price` is a tick on this ladder.
+    pub fn contains(&self, price: Decimal) -> bool {
+        self.bands
+            .iter()
+            .any(|b| price >= b.from && price <= b.to && ((price - b.from) % b.step).is_zero())
+    }
+
+    /// Round `price` to the nearest tick in `direction`, clamped to the
+    /// ladder's range.
+    pub fn snap(&self, price: Decimal, direction: Rounding) -> Decimal {
+        if price <= self.min() {
+            return self.min();
+        }
+        if price >= self.max() {
+            return self.max();
+        }
+        // A price on a band boundary belongs to the band above it (and is
+        // a tick of both), so the lower band is never consulted for it.
+        let band = self
+            .bands
+            .iter()
+            .find(|b| price >= b.from && price < b.to)
+            .expect("price within ladder range");
+        let steps = (price - band.from) / band.step;
+        let steps = match direction {
+            Rounding::Down => steps.floor(),
+            Rounding::Up => steps.ceil(),
+        };
+        (band.from + steps * band.step).normalize()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_betfair_ladder_boundaries() {
+        let l = PriceLadder::BETFAIR;
+        // (price, down, up) across every band change.
+        let cases = [
+            (dec!(1.005), dec!(1.01), dec!(1.01)),
+            (dec!(1.995), dec!(1.99), dec!(2)),
+            (dec!(2), dec!(2), dec!(2)),
+            (dec!(2.01), dec!(2), dec!(2.02)),
+            (dec!(2.99), dec!(2.98), dec!(3)),
+            (dec!(3.01), dec!(3), dec!(3.05)),
+            (dec!(3.99), dec!(3.95), dec!(4)),
+            (dec!(4.05), dec!(4), dec!(4.1)),
+            (dec!(5.95), dec!(5.9), dec!(6)),
+            (dec!(6.1), dec!(6), dec!(6.2)),
+            (dec!(9.9), dec!(9.8), dec!(10)),
+            (dec!(10.2), dec!(10), dec!(10.5)),
+            (dec!(19.7), dec!(19.5), dec!(20)),
+            (dec!(20.5), dec!(20), dec!(21)),
+            (dec!(29.5), dec!(29), dec!(30)),
+            (dec!(31), dec!(30), dec!(32)),
+            (dec!(49), dec!(48), dec!(50)),
+            (dec!(52), dec!(50), dec!(55)),
+            (dec!(99), dec!(95), dec!(100)),
+            (dec!(105), dec!(100), dec!(110)),
+            (dec!(995), dec!(990), dec!(1000)),
+            (dec!(1500), dec!(1000), dec!(1000)),
+        ];
+        for (price, down, up) in cases {
+            assert_eq!(l.snap(price, Rounding::Down), down, "{price} down");
+            assert_eq!(l.snap(price, Rounding::Up), up, "{price} up");
+            assert!(l.contains(down) && l.contains(up), "{price}");
+        }
+
+        for on in [dec!(1.01), dec!(1.5), dec!(2.5), dec!(3.55), dec!(4.7), dec!(7.4), dec!(15.5), dec!(26), dec!(44), dec!(85), dec!(460), dec!(1000)] {
+            assert!(l.contains(on), "{on}");
+            assert_eq!(l.snap(on, Rounding::Up), on);
+        }
+        for off in [dec!(1.001), dec!(2.01), dec!(3.02), dec!(4.05), dec!(6.1), dec!(10.2), dec!(21.5), dec!(31), dec!(52), dec!(105), dec!(1010)] {
+            assert!(!l.contains(off), "{off}");
+        }
+    }
+
+    #[test]
+    fn test_probability_ladders_round_toward_passive_side() {
+        // Polymarket buys (either outcome token) round the price paid down.
+        let cents = PriceLadder::for_tick(dec!(0.01));
+        assert_eq!(cents.snap(dec!(0.437), Rounding::Down), dec!(0.43));
+        let mills = PriceLadder::for_tick(dec!(0.001));
+        assert_eq!(mills.snap(dec!(0.0276), Rounding::Down), dec!(0.027));
+        assert!(mills.contains(dec!(0.027)) && !cents.contains(dec!(0.027)));
+        assert_eq!(PriceLadder::for_tick(dec!(0.05)), PriceLadder::CENTS);
+
+        // Manifold limitProb: YES limits sit lower, NO limits higher.
+        let manifold = PriceLadder::CENTS;
+        assert_eq!(manifold.snap(dec!(0.437), Rounding::Down), dec!(0.43));
+        assert_eq!(manifold.snap(dec!(0.431), Rounding::Up), dec!(0.44));
+        assert_eq!(manifold.snap(dec!(0.995), Rounding::Up), dec!(0.99));
+        assert_eq!(manifold.snap(dec!(0.004), Rounding::Down), dec!(0.01));
+    }
+}
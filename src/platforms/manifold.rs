@@ -17,9 +17,13 @@ use rust_decimal::prelude::*;
 use serde::Deserialize;
 use tracing::{debug, info, warn};
 
+use super::ladder::Rounding;
+use super::preflight::{CheckKind, PreflightLimits, PreflightReport};
 use super::PredictionPlatform;
+use crate::language;
 use crate::types::{
-    d, CrossReferences, LiquidityInfo, Market, MarketCategory, Position, Side, TradeReceipt,
+    d, CrossReferences, LiquidityInfo, LiquidityModel, Market, MarketCategory, MarketResolution,
+    OracleError, Position, Side, TradeReceipt,
 };
 
 // ---------------------------------------------------------------------------
@@ -35,6 +39,12 @@ const DEFAULT_FETCH_LIMIT: u32 = 200;
 /// Minimum unique bettors for a market to be considered meaningful.
 const MIN_BETTORS: u32 = 3;
 
+/// Bets per `/v0/bets` page (API max is 1000).
+const BETS_PAGE_LIMIT: usize = 1000;
+
+/// Share counts below this are treated as a closed position.
+const DUST_SHARES: f64 = 1e-6;
+
 // ---------------------------------------------------------------------------
 // API response types (Manifold JSON → Rust)
 // ---------------------------------------------------------------------------
@@ -136,6 +146,8 @@ struct ManifoldProfitCached {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ManifoldUser {
+    #[serde(default)]
+    id: String,
     /// Liquid Mana available to bet immediately.
     #[serde(default)]
     balance: f64,
@@ -149,6 +161,8 @@ struct ManifoldUser {
 
 /// Ground-truth snapshot returned by [`ManifoldClient::get_user_info`].
 pub struct ManifoldUserInfo {
+    /// Manifold user id of the account owning the API key.
+    pub user_id: String,
     /// Liquid Mana balance (available to place new bets).
     pub liquid_balance: rust_decimal::Decimal,
     /// Current market value of all open (unresolved) share positions.
@@ -173,6 +187,9 @@ struct ManifoldMarketDetail {
     /// Current market probability (0.0–1.0). Present on open markets.
     #[serde(default)]
     probability: Option<f64>,
+    /// "BINARY", "MULTIPLE_CHOICE", etc.
+    #[serde(default)]
+    outcome_type: Option<String>,
 }
 
 /// Response from `/v0/market/{id}/sell` POST — sell shares.
@@ -186,14 +203,61 @@ struct ManifoldSellResponse {
     status: Option<String>,
 }
 
-/// A resolved Manifold bet outcome, returned by `check_resolutions()`.
-#[derive(Debug, Clone)]
-pub struct ManifoldResolution {
-    pub market_id: String,
-    pub bet_id: String,
-    pub won: bool,
-    /// Net Mana PnL: positive = profit, negative = loss, zero = cancelled.
-    pub pnl: Decimal,
+/// One entry from the public `/v0/bets` feed (by market or by user).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifoldFeedBet {
+    /// Bet id — the `betId` returned when the bet was placed.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub contract_id: String,
+    #[serde(default)]
+    pub user_id: String,
+    /// Mana spent; negative for share sales.
+    #[serde(default)]
+    pub amount: f64,
+    /// "YES" or "NO".
+    #[serde(default)]
+    pub outcome: String,
+    /// Shares bought; negative for sales and redemptions.
+    #[serde(default)]
+    pub shares: f64,
+    /// Answer bet on in a multiple-choice market; absent on binary markets.
+    #[serde(default)]
+    pub answer_id: Option<String>,
+    #[serde(default)]
+    pub prob_before: f64,
+    #[serde(default)]
+    pub prob_after: f64,
+    /// Milliseconds since epoch.
+    #[serde(default)]
+    pub created_time: i64,
+    #[serde(default)]
+    pub is_cancelled: bool,
+    /// Automatic YES/NO share netting — not a trading decision.
+    #[serde(default)]
+    pub is_redemption: bool,
+    #[serde(default)]
+    pub fees: ManifoldBetFees,
+}
+
+/// Fee breakdown on a Manifold bet.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifoldBetFees {
+    #[serde(default)]
+    pub creator_fee: f64,
+    #[serde(default)]
+    pub platform_fee: f64,
+    #[serde(default)]
+    pub liquidity_fee: f64,
+}
+
+impl ManifoldBetFees {
+    pub fn total(&self) -> f64 {
+        self.creator_fee + self.platform_fee + self.liquidity_fee
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -253,12 +317,26 @@ impl ManifoldClient {
             anyhow::bail!("Manifold API error {status}: {body}");
         }
 
-        let markets: Vec<ManifoldLiteMarket> = resp
+        let entries: Vec<serde_json::Value> = resp
             .json()
             .await
             .context("Failed to parse Manifold search-markets response")?;
 
-        Ok(markets)
+        Ok(super::parse_entries("manifold/search-markets", entries).0)
+    }
+
+    /// Whether a failed bet response means the market no longer accepts
+    /// trades. Manifold answers with a 4xx and a message such as
+    /// `{"message":"Trading is closed."}` or "...market is resolved".
+    fn is_market_closed_error(status: reqwest::StatusCode, body: &str) -> bool {
+        if !status.is_client_error() {
+            return false;
+        }
+        let body = body.to_lowercase();
+        body.contains("trading is closed")
+            || body.contains("market is closed")
+            || body.contains("market closed")
+            || body.contains("is resolved")
     }
 
     /// Convert a Manifold API timestamp (ms since epoch) to `DateTime<Utc>`.
@@ -270,7 +348,7 @@ impl ManifoldClient {
     /// heuristics. Manifold doesn't provide structured categories on
     /// `LiteMarket`, so we infer from question text and topic slugs.
     fn classify(market: &ManifoldLiteMarket) -> MarketCategory {
-        let q = market.question.to_lowercase();
+        let q = language::normalize(&market.question).to_lowercase();
         let slugs: Vec<String> = market
             .group_slugs
             .as_ref()
@@ -377,6 +455,9 @@ impl ManifoldClient {
                 manifold_prob: Some(prob_dec),
                 ..CrossReferences::default()
             },
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         }
     }
 }
@@ -386,143 +467,94 @@ impl ManifoldClient {
 // ---------------------------------------------------------------------------
 
 impl ManifoldClient {
-    /// Check which of the supplied open bets have resolved and compute their PnL.
+    /// Fetch the most recent `limit` bets on a market (newest first).
     ///
-    /// Queries `GET /v0/market/{id}` for each unique market in `open_bets`.
-    /// Markets that are still open are silently skipped.
-    /// Markets resolved as "CANCEL" yield zero PnL (full refund).
-    pub async fn check_resolutions(
+    /// Public endpoint — no API key needed.
+    pub async fn fetch_recent_bets(
         &self,
-        open_bets: &[crate::types::TradeReceipt],
-    ) -> Vec<ManifoldResolution> {
-        use std::collections::HashSet;
+        market_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ManifoldFeedBet>> {
+        let resp = self
+            .http
+            .get(format!("{BASE_URL}/bets"))
+            .query(&[("contractId", market_id), ("limit", &limit.to_string())])
+            .send()
+            .await
+            .context("Manifold bets feed request failed")?;
 
-        let market_ids: HashSet<&str> =
-            open_bets.iter().map(|b| b.market_id.as_str()).collect();
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Manifold bets feed failed {status}: {body}");
+        }
 
-        let mut results = Vec::new();
+        resp.json()
+            .await
+            .context("Failed to parse Manifold bets feed")
+    }
 
-        for market_id in market_ids {
-            let url = format!("{BASE_URL}/market/{market_id}");
-            let resp = match self.http.get(&url).send().await {
-                Ok(r) => r,
-                Err(e) => {
-                    warn!(market_id, error = %e, "Resolution check: HTTP request failed");
-                    continue;
-                }
-            };
+    /// Fetch the most recent `limit` bets placed by `user_id` (newest first).
+    ///
+    /// Public endpoint — no API key needed. Includes sales and redemptions.
+    pub async fn fetch_user_bets(
+        &self,
+        user_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ManifoldFeedBet>> {
+        self.fetch_user_bets_before(user_id, limit, None).await
+    }
 
-            if !resp.status().is_success() {
-                warn!(market_id, status = %resp.status(), "Resolution check: non-OK response");
-                continue;
+    /// Every bet placed by `user_id`, oldest first, following the `before`
+    /// cursor one page at a time.
+    pub async fn fetch_all_user_bets(&self, user_id: &str) -> Result<Vec<ManifoldFeedBet>> {
+        let mut bets: Vec<ManifoldFeedBet> = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let page = self.fetch_user_bets_before(user_id, BETS_PAGE_LIMIT, before.as_deref()).await?;
+            before = next_cursor(&page, BETS_PAGE_LIMIT);
+            bets.extend(page);
+            if before.is_none() {
+                break;
             }
+        }
+        bets.reverse();
+        Ok(bets)
+    }
 
-            let detail: ManifoldMarketDetail = match resp.json().await {
-                Ok(d) => d,
-                Err(e) => {
-                    warn!(market_id, error = %e, "Resolution check: JSON parse failed");
-                    continue;
-                }
-            };
-
-            if !detail.is_resolved {
-                continue;
-            }
+    /// One page of `user_id`'s bets, newest first, placed before the bet
+    /// with id `before` when given.
+    async fn fetch_user_bets_before(
+        &self,
+        user_id: &str,
+        limit: usize,
+        before: Option<&str>,
+    ) -> Result<Vec<ManifoldFeedBet>> {
+        let mut query = vec![("userId", user_id.to_string()), ("limit", limit.to_string())];
+        if let Some(before) = before {
+            query.push(("before", before.to_string()));
+        }
+        let resp = self
+            .http
+            .get(format!("{BASE_URL}/bets"))
+            .query(&query)
+            .send()
+            .await
+            .context("Manifold user bets request failed")?;
 
-            let resolution = detail.resolution.as_deref().unwrap_or("");
-
-            for bet in open_bets.iter().filter(|b| b.market_id == market_id) {
-                let (won, pnl) = match resolution {
-                    "CANCEL" => (false, Decimal::ZERO), // refund — no gain or loss
-                    "YES" => {
-                        if bet.side == crate::types::Side::Yes {
-                            let profit = if bet.fill_price > Decimal::ZERO
-                                && bet.fill_price < Decimal::ONE
-                            {
-                                bet.amount * (Decimal::ONE - bet.fill_price) / bet.fill_price
-                            } else {
-                                Decimal::ZERO
-                            };
-                            (true, profit)
-                        } else {
-                            (false, -bet.amount)
-                        }
-                    }
-                    "NO" => {
-                        if bet.side == crate::types::Side::No {
-                            let fill_complement =
-                                Decimal::ONE - bet.fill_price;
-                            let profit = if fill_complement > Decimal::ZERO
-                                && fill_complement < Decimal::ONE
-                            {
-                                bet.amount * bet.fill_price / fill_complement
-                            } else {
-                                Decimal::ZERO
-                            };
-                            (true, profit)
-                        } else {
-                            (false, -bet.amount)
-                        }
-                    }
-                    "MKT" => {
-                        // Probabilistic resolution
-                        let res_prob = detail
-                            .resolution_probability
-                            .map(|p| {
-                                rust_decimal::Decimal::from_f64(p)
-                                    .unwrap_or(Decimal::ZERO)
-                            })
-                            .unwrap_or(Decimal::ZERO);
-                        let yes_frac = match &bet.side {
-                            crate::types::Side::Yes => res_prob,
-                            crate::types::Side::No => Decimal::ONE - res_prob,
-                        };
-                        let payout = if bet.fill_price > Decimal::ZERO {
-                            bet.amount * yes_frac / bet.fill_price
-                        } else {
-                            Decimal::ZERO
-                        };
-                        let won = payout >= bet.amount;
-                        (won, payout - bet.amount)
-                    }
-                    _ => {
-                        warn!(market_id, resolution, "Unknown Manifold resolution type");
-                        continue;
-                    }
-                };
-
-                info!(
-                    market_id,
-                    bet_id = %bet.order_id,
-                    resolution,
-                    won,
-                    pnl = %format!("{:.0} Mana", pnl),
-                    "Manifold bet resolved"
-                );
-
-                results.push(ManifoldResolution {
-                    market_id: market_id.to_string(),
-                    bet_id: bet.order_id.clone(),
-                    won,
-                    pnl,
-                });
-            }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Manifold user bets failed {status}: {body}");
         }
 
-        results
+        resp.json()
+            .await
+            .context("Failed to parse Manifold user bets")
     }
-}
-
-// ---------------------------------------------------------------------------
-// Auto-exit helpers (non-trait, public methods)
-// ---------------------------------------------------------------------------
 
-impl ManifoldClient {
-    /// Fetch the current implied probability for a Manifold market.
-    ///
-    /// Returns a value in [0, 1]. Used by the auto-exit engine to compute
-    /// unrealized P&L on open positions.
-    pub async fn get_market_probability(&self, market_id: &str) -> Result<Decimal> {
+    /// `GET /v0/market/{id}`.
+    async fn fetch_market_detail(&self, market_id: &str) -> Result<ManifoldMarketDetail> {
         let url = format!("{BASE_URL}/market/{market_id}");
 
         let resp = self
@@ -538,10 +570,40 @@ impl ManifoldClient {
             anyhow::bail!("Manifold market detail failed {status}: {body}");
         }
 
-        let detail: ManifoldMarketDetail = resp
-            .json()
+        resp.json()
             .await
-            .context("Failed to parse Manifold market detail")?;
+            .context("Failed to parse Manifold market detail")
+    }
+
+    /// Resolution of a market detail; `None` while it is open.
+    fn detail_resolution(detail: &ManifoldMarketDetail) -> Result<Option<MarketResolution>> {
+        if !detail.is_resolved {
+            return Ok(None);
+        }
+        let resolution = match detail.resolution.as_deref().unwrap_or("") {
+            "YES" => MarketResolution::Yes,
+            "NO" => MarketResolution::No,
+            "MKT" => MarketResolution::Probability(
+                detail.resolution_probability.and_then(Decimal::from_f64).unwrap_or(Decimal::ZERO),
+            ),
+            "CANCEL" => MarketResolution::Cancelled,
+            other => anyhow::bail!("Unknown Manifold resolution {other:?} on {}", detail.id),
+        };
+        Ok(Some(resolution))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Auto-exit helpers (non-trait, public methods)
+// ---------------------------------------------------------------------------
+
+impl ManifoldClient {
+    /// Fetch the current implied probability for a Manifold market.
+    ///
+    /// Returns a value in [0, 1]. Used by the auto-exit engine to compute
+    /// unrealized P&L on open positions.
+    pub async fn get_market_probability(&self, market_id: &str) -> Result<Decimal> {
+        let detail = self.fetch_market_detail(market_id).await?;
 
         // Use live probability for open markets; resolved markets use resolution_probability
         let prob = if detail.is_resolved {
@@ -642,6 +704,7 @@ impl ManifoldClient {
         }
         let user: ManifoldUser = resp.json().await.ok()?;
         Some(ManifoldUserInfo {
+            user_id:          user.id,
             liquid_balance:   Decimal::from_f64(user.balance).unwrap_or_default(),
             investment_value: Decimal::from_f64(user.investment_value).unwrap_or_default(),
             resolved_profit:  Decimal::from_f64(user.profit_cached.all_time).unwrap_or_default(),
@@ -649,6 +712,87 @@ impl ManifoldClient {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Position aggregation
+// ---------------------------------------------------------------------------
+
+/// Cursor for the page after `page`: the id of its oldest bet, or `None`
+/// when the page came back short (no more bets).
+fn next_cursor(page: &[ManifoldFeedBet], limit: usize) -> Option<String> {
+    if page.len() < limit {
+        return None;
+    }
+    page.last().map(|b| b.id.clone())
+}
+
+/// Net holding on one binary market.
+#[derive(Debug, Clone, PartialEq)]
+struct NetPosition {
+    contract_id: String,
+    side: Side,
+    shares: f64,
+    /// Mana cost of the shares still held (average cost).
+    cost: f64,
+}
+
+/// Shares and average cost held on one outcome.
+#[derive(Debug, Default, Clone, Copy)]
+struct Holding {
+    shares: f64,
+    cost: f64,
+}
+
+impl Holding {
+    /// Apply a fill: buys add their stake, sales and redemptions release
+    /// cost in proportion to the shares sold.
+    fn fill(&mut self, shares: f64, amount: f64) {
+        if shares >= 0.0 {
+            self.shares += shares;
+            self.cost += amount;
+        } else if self.shares > 0.0 {
+            let sold = (-shares).min(self.shares);
+            self.cost -= self.cost * sold / self.shares;
+            self.shares -= sold;
+        }
+    }
+}
+
+/// Net positions from a bet history in time order, one per market.
+///
+/// Cancelled bets and bets on multiple-choice answers are skipped. YES and
+/// NO shares held on the same market redeem in pairs, so only the larger
+/// side remains, carrying its share of the cost.
+fn aggregate_positions(bets: &[ManifoldFeedBet]) -> Vec<NetPosition> {
+    let mut holdings: Vec<(String, Holding, Holding)> = Vec::new();
+    for bet in bets {
+        if bet.is_cancelled || bet.answer_id.is_some() {
+            continue;
+        }
+        let index = match holdings.iter().position(|(id, _, _)| *id == bet.contract_id) {
+            Some(i) => i,
+            None => {
+                holdings.push((bet.contract_id.clone(), Holding::default(), Holding::default()));
+                holdings.len() - 1
+            }
+        };
+        let (_, yes, no) = &mut holdings[index];
+        match bet.outcome.as_str() {
+            "YES" => yes.fill(bet.shares, bet.amount),
+            "NO" => no.fill(bet.shares, bet.amount),
+            _ => {}
+        }
+    }
+
+    holdings
+        .into_iter()
+        .filter_map(|(contract_id, yes, no)| {
+            let (side, held, other) = if yes.shares >= no.shares { (Side::Yes, yes, no) } else { (Side::No, no, yes) };
+            let shares = held.shares - other.shares;
+            (shares > DUST_SHARES).then(|| NetPosition { contract_id, side, shares, cost: held.cost * shares / held.shares })
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // PredictionPlatform trait implementation
 // ---------------------------------------------------------------------------
@@ -758,13 +902,23 @@ impl PredictionPlatform for ManifoldClient {
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
+            if Self::is_market_closed_error(status, &body) {
+                return Err(OracleError::MarketClosed {
+                    platform: PLATFORM_NAME.to_string(),
+                    market_id: market_id.to_string(),
+                    reason: body,
+                }
+                .into());
+            }
             anyhow::bail!("Manifold bet failed {status}: {body}");
         }
 
-        let bet: ManifoldBetResponse = resp
+        let raw: serde_json::Value = resp
             .json()
             .await
             .context("Failed to parse Manifold bet response")?;
+        let bet: ManifoldBetResponse = serde_json::from_value(raw.clone())
+            .context("Failed to parse Manifold bet response")?;
 
         let order_id = bet
             .bet_id
@@ -795,17 +949,56 @@ impl PredictionPlatform for ManifoldClient {
             fees: Decimal::ZERO, // Manifold doesn't charge explicit fees on bets
             timestamp,
             currency: "Mana".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: Some(raw),
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         })
     }
 
     /// Get current positions on Manifold.
     ///
-    /// TODO: Implement using `/v0/bets` filtered by user ID. Requires
-    /// tracking the authenticated user's ID and aggregating open positions.
+    /// Nets the account's full bet history (`/v0/bets?userId=`) per
+    /// market and values each open position at the market's current
+    /// probability. Resolved and non-binary markets are left out.
     async fn get_positions(&self) -> Result<Vec<Position>> {
-        // Phase 6 will implement full position tracking.
-        // For now return empty — scanning is the priority.
-        Ok(Vec::new())
+        let user = self
+            .get_user_info()
+            .await
+            .context("Manifold /v0/me unavailable (API key required for positions)")?;
+        let bets = self.fetch_all_user_bets(&user.user_id).await?;
+
+        let mut positions = Vec::new();
+        for net in aggregate_positions(&bets) {
+            let detail = self
+                .fetch_market_detail(&net.contract_id)
+                .await
+                .with_context(|| format!("Manifold market {} for positions", net.contract_id))?;
+            if detail.is_resolved || detail.outcome_type.as_deref().is_some_and(|t| t != "BINARY") {
+                continue;
+            }
+            let prob_yes = detail.probability.unwrap_or(0.5).clamp(0.0, 1.0);
+            let price = match net.side {
+                Side::Yes => prob_yes,
+                Side::No => 1.0 - prob_yes,
+            };
+            positions.push(Position {
+                market_id: net.contract_id,
+                platform: PLATFORM_NAME.to_string(),
+                side: net.side,
+                size: d(net.shares),
+                entry_price: d((net.cost / net.shares).clamp(0.0, 1.0)),
+                current_value: d(net.shares * price),
+                cost: d(net.cost),
+                category: None,
+            });
+        }
+        debug!(bets = bets.len(), positions = positions.len(), "Manifold positions aggregated");
+        Ok(positions)
     }
 
     /// Get Mana balance for the authenticated user.
@@ -875,14 +1068,70 @@ impl PredictionPlatform for ManifoldClient {
         })
     }
 
+    /// Binary markets are CPMM pools; [`Self::check_liquidity`] reports them.
+    fn liquidity_model(&self) -> LiquidityModel {
+        LiquidityModel::Cpmm
+    }
+
+    /// Live probability of `side` from the market detail. Bets are market
+    /// orders, so the executor checks this before placing.
+    async fn current_price(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        let detail = self.fetch_market_detail(market_id).await?;
+        Ok(detail.probability.map(|p| {
+            let yes = d(p.clamp(0.0, 1.0));
+            match side {
+                Side::Yes => yes,
+                Side::No => Decimal::ONE - yes,
+            }
+        }))
+    }
+
     /// Manifold is play-money only — not a real-money execution venue.
     fn is_real_money(&self) -> bool {
         false
     }
 
+    /// `limitProb` is in YES terms: a YES limit is a ceiling and a NO limit
+    /// a floor on the probability, so NO limits round up.
+    fn passive_rounding(&self, side: Side) -> Rounding {
+        match side {
+            Side::Yes => Rounding::Down,
+            Side::No => Rounding::Up,
+        }
+    }
+
     fn name(&self) -> &str {
         PLATFORM_NAME
     }
+
+    /// From `isResolved` / `resolution` on `GET /v0/market/{id}`.
+    async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+        let detail = self.fetch_market_detail(market_id).await?;
+        Self::detail_resolution(&detail)
+    }
+
+    /// Betting needs a key; without one there is nothing to ask the API.
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        let mut report = PreflightReport::new(PLATFORM_NAME);
+        if self.api_key.is_none() {
+            report.fail(CheckKind::Auth, "no API key configured");
+            return report;
+        }
+        report.balance(self.get_balance().await, limits);
+        report
+    }
+
+    fn supports_close(&self) -> bool {
+        true
+    }
+
+    async fn close_position(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        let outcome = match side {
+            Side::Yes => "YES",
+            Side::No => "NO",
+        };
+        self.sell_shares(market_id, outcome).await
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1031,6 +1280,32 @@ mod tests {
         assert_eq!(dt.year(), 1970);
     }
 
+    // -- Closed-market detection --
+
+    #[test]
+    fn test_is_market_closed_error() {
+        use reqwest::StatusCode;
+
+        assert!(ManifoldClient::is_market_closed_error(
+            StatusCode::FORBIDDEN,
+            r#"{"message":"Trading is closed."}"#,
+        ));
+        assert!(ManifoldClient::is_market_closed_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"message":"Cannot bet: market is resolved"}"#,
+        ));
+        // Other client errors are ordinary failures
+        assert!(!ManifoldClient::is_market_closed_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"message":"Insufficient balance."}"#,
+        ));
+        // Server errors are never treated as closed
+        assert!(!ManifoldClient::is_market_closed_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "market closed",
+        ));
+    }
+
     // -- Client construction --
 
     #[test]
@@ -1049,4 +1324,172 @@ mod tests {
         assert!(client.is_ok());
         assert!(client.unwrap().api_key.is_some());
     }
+
+    // -- Fixture contract tests --
+
+    fn fixture_entries(path: &str) -> Vec<serde_json::Value> {
+        serde_json::from_str(&crate::fixtures::read(path)).unwrap()
+    }
+
+    #[test]
+    fn test_fixture_search_markets() {
+        let entries = fixture_entries("manifold/search-markets.json");
+        let (markets, skipped) = crate::platforms::parse_entries::<ManifoldLiteMarket>("test", entries);
+        assert_eq!((markets.len(), skipped), (2, 0));
+
+        let m = ManifoldClient::to_oracle_market(markets.into_iter().next().unwrap());
+        assert_eq!(m.id, "kX0fRmWzQcN9pL2aT4vB");
+        assert_eq!(m.category, MarketCategory::Weather);
+        assert_eq!(m.current_price_yes, d(0.3864));
+        assert_eq!(m.volume_24h, d(412.5));
+        assert_eq!(m.liquidity, d(1000.0));
+        assert_eq!(m.deadline.timestamp_millis(), 1761091200000);
+        assert!(m.url.starts_with("https://manifold.markets/"));
+    }
+
+    #[test]
+    fn test_fixture_drifted_search_entry_is_skipped() {
+        let mut entries = fixture_entries("manifold/search-markets.json");
+        entries[0]["probability"] = serde_json::json!("0.3864");
+        let (markets, skipped) = crate::platforms::parse_entries::<ManifoldLiteMarket>("test", entries);
+        assert_eq!(skipped, 1);
+        assert_eq!(markets[0].id, "Pz7yHc2LmQ4rV8nB1xWe");
+    }
+
+    #[test]
+    fn test_fixture_market_detail() {
+        let detail: ManifoldMarketDetail =
+            serde_json::from_str(&crate::fixtures::read("manifold/market.json")).unwrap();
+        assert_eq!(detail.id, "kX0fRmWzQcN9pL2aT4vB");
+        assert!(detail.is_resolved);
+        assert_eq!(detail.resolution.as_deref(), Some("NO"));
+        assert_eq!(detail.probability, Some(0.0004));
+        assert_eq!(detail.outcome_type.as_deref(), Some("BINARY"));
+        assert_eq!(ManifoldClient::detail_resolution(&detail).unwrap(), Some(MarketResolution::No));
+    }
+
+    #[test]
+    fn test_detail_resolution_mapping() {
+        let mut detail: ManifoldMarketDetail =
+            serde_json::from_str(&crate::fixtures::read("manifold/market.json")).unwrap();
+        for (resolution, expected) in [
+            ("YES", MarketResolution::Yes),
+            ("CANCEL", MarketResolution::Cancelled),
+            ("MKT", MarketResolution::Probability(Decimal::new(35, 2))),
+        ] {
+            detail.resolution = Some(resolution.to_string());
+            detail.resolution_probability = Some(0.35);
+            assert_eq!(ManifoldClient::detail_resolution(&detail).unwrap(), Some(expected));
+        }
+        detail.resolution = Some("CHOOSE_MULTIPLE".to_string());
+        assert!(ManifoldClient::detail_resolution(&detail).is_err());
+        detail.is_resolved = false;
+        assert_eq!(ManifoldClient::detail_resolution(&detail).unwrap(), None);
+    }
+
+    #[test]
+    fn test_fixture_bet_response() {
+        let bet: ManifoldBetResponse =
+            serde_json::from_str(&crate::fixtures::read("manifold/bet.json")).unwrap();
+        assert_eq!(bet.bet_id.as_deref(), Some("e91Hd0qLs2"));
+        assert_eq!(bet.amount, 25.0);
+        assert_eq!(bet.shares, 61.8143);
+        assert_eq!(bet.prob_after, 0.3991);
+    }
+
+    #[test]
+    fn test_fixture_bets_feed() {
+        let entries = fixture_entries("manifold/bets.json");
+        let (bets, skipped) = crate::platforms::parse_entries::<ManifoldFeedBet>("test", entries);
+        assert_eq!((bets.len(), skipped), (3, 0));
+        assert_eq!(bets[0].contract_id, "kX0fRmWzQcN9pL2aT4vB");
+        assert_eq!(bets[0].outcome, "YES");
+        assert_eq!(bets[0].shares, 61.8143);
+        assert_eq!(bets[0].fees.total(), 0.25);
+        assert!(bets[0].answer_id.is_none());
+        assert!(bets[1].amount < 0.0 && bets[1].shares < 0.0);
+        assert_eq!(bets[2].answer_id.as_deref(), Some("a7Fx2LqPz1"));
+    }
+
+    fn feed_bet(contract_id: &str, outcome: &str, shares: f64, amount: f64) -> ManifoldFeedBet {
+        ManifoldFeedBet {
+            id: format!("{contract_id}-{outcome}-{shares}"),
+            contract_id: contract_id.to_string(),
+            user_id: "u".to_string(),
+            amount,
+            outcome: outcome.to_string(),
+            shares,
+            answer_id: None,
+            prob_before: 0.5,
+            prob_after: 0.5,
+            created_time: 0,
+            is_cancelled: false,
+            is_redemption: false,
+            fees: ManifoldBetFees::default(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_nets_buy_and_partial_sell() {
+        let mut bets = fixture_entries("manifold/bets.json")
+            .into_iter()
+            .map(|e| serde_json::from_value::<ManifoldFeedBet>(e).unwrap())
+            .collect::<Vec<_>>();
+        bets.reverse(); // the feed is newest first
+        // Sell 30 of the 61.8143 YES shares bought for 25 Mana.
+        bets.push(feed_bet("kX0fRmWzQcN9pL2aT4vB", "YES", -30.0, -13.1));
+
+        let positions = aggregate_positions(&bets);
+        let yes = positions.iter().find(|p| p.contract_id == "kX0fRmWzQcN9pL2aT4vB").unwrap();
+        assert_eq!(yes.side, Side::Yes);
+        assert!((yes.shares - 31.8143).abs() < 1e-9);
+        // Average cost: the remaining shares keep their share of the 25 Mana.
+        assert!((yes.cost - 25.0 * 31.8143 / 61.8143).abs() < 1e-9);
+        // A sale with nothing held, and the multiple-choice bet, leave no position.
+        assert_eq!(positions.len(), 1);
+    }
+
+    #[test]
+    fn test_aggregate_redeems_pairs_and_skips_cancelled() {
+        let mut cancelled = feed_bet("c", "YES", 10.0, 5.0);
+        cancelled.is_cancelled = true;
+        let bets = vec![
+            feed_bet("m", "YES", 40.0, 20.0),
+            feed_bet("m", "NO", 10.0, 5.0),
+            feed_bet("flat", "NO", 20.0, 8.0),
+            feed_bet("flat", "NO", -20.0, -9.0),
+            cancelled,
+        ];
+        let positions = aggregate_positions(&bets);
+        assert_eq!(
+            positions,
+            vec![NetPosition { contract_id: "m".to_string(), side: Side::Yes, shares: 30.0, cost: 15.0 }]
+        );
+    }
+
+    #[test]
+    fn test_next_cursor_follows_full_pages() {
+        let page: Vec<ManifoldFeedBet> = (0..3).map(|i| feed_bet("m", "YES", i as f64, 1.0)).collect();
+        assert_eq!(next_cursor(&page, 3), Some(page[2].id.clone()));
+        assert_eq!(next_cursor(&page, 4), None);
+        assert_eq!(next_cursor(&[], 1000), None);
+    }
+
+    #[test]
+    fn test_fixture_me() {
+        let user: ManifoldUser = serde_json::from_str(&crate::fixtures::read("manifold/me.json")).unwrap();
+        assert_eq!(user.id, "uR4nD0mUs3rId00000001");
+        assert_eq!(user.balance, 4312.77);
+        assert_eq!(user.investment_value, 688.4);
+        assert_eq!(user.profit_cached.all_time, 1.17);
+    }
+
+    #[tokio::test]
+    async fn test_preflight_without_key_fails_auth() {
+        let client = ManifoldClient::new(None).unwrap();
+        let report = client.preflight(&PreflightLimits::default()).await;
+        assert!(!report.go());
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].kind, CheckKind::Auth);
+    }
 }
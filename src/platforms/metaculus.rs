@@ -18,8 +18,10 @@ use serde::Deserialize;
 use tracing::{debug, info, warn};
 
 use super::PredictionPlatform;
+use crate::language;
 use crate::types::{
-    d, CrossReferences, LiquidityInfo, Market, MarketCategory, Position, Side, TradeReceipt,
+    d, CrossReferences, LiquidityInfo, Market, MarketCategory, MarketResolution, Position, Side,
+    TradeReceipt,
 };
 
 // ---------------------------------------------------------------------------
@@ -42,12 +44,13 @@ const MIN_FORECASTERS: u32 = 5;
 // API response types (Metaculus JSON -> Rust)
 // ---------------------------------------------------------------------------
 
-/// Top-level paginated response from `/api2/questions/`.
+/// Top-level paginated response from `/api2/questions/`. Fetched with raw
+/// `results` first so malformed posts can be skipped one by one.
 #[derive(Debug, Deserialize)]
-struct MetaculusPage {
+struct MetaculusPage<T = MetaculusPost> {
     count: u32,
     next: Option<String>,
-    results: Vec<MetaculusPost>,
+    results: Vec<T>,
 }
 
 /// A Metaculus "post" — the top-level object wrapping a question.
@@ -97,6 +100,10 @@ struct MetaculusQuestion {
     #[serde(default)]
     scheduled_resolve_time: Option<String>,
 
+    /// "yes", "no", "annulled" or "ambiguous" once resolved.
+    #[serde(default)]
+    resolution: Option<String>,
+
     /// Resolution criteria text.
     #[serde(default)]
     resolution_criteria: Option<String>,
@@ -207,12 +214,34 @@ impl MetaculusClient {
             anyhow::bail!("Metaculus API error {status}: {body}");
         }
 
-        let page: MetaculusPage = resp
+        let page: MetaculusPage<serde_json::Value> = resp
             .json()
             .await
             .context("Failed to parse Metaculus response")?;
 
-        Ok(page)
+        Ok(Self::parse_page(page))
+    }
+
+    /// Parse a raw page's posts, skipping any that no longer fit.
+    fn parse_page(page: MetaculusPage<serde_json::Value>) -> MetaculusPage {
+        let (results, _) = super::parse_entries("metaculus/questions", page.results);
+        MetaculusPage { count: page.count, next: page.next, results }
+    }
+
+    /// Resolution of a binary question; `None` while unresolved.
+    fn post_resolution(post: &MetaculusPost) -> Option<MarketResolution> {
+        if !post.resolved {
+            return None;
+        }
+        match post.question.as_ref()?.resolution.as_deref()? {
+            "yes" => Some(MarketResolution::Yes),
+            "no" => Some(MarketResolution::No),
+            "annulled" | "ambiguous" => Some(MarketResolution::Cancelled),
+            other => {
+                warn!(post_id = post.id, resolution = other, "Unknown Metaculus resolution");
+                None
+            }
+        }
     }
 
     /// Extract the community median probability from a post's aggregations.
@@ -278,7 +307,7 @@ impl MetaculusClient {
             .map(|cats| cats.iter().map(|c| c.slug.to_lowercase()).collect())
             .unwrap_or_default();
 
-        let title = post.title.to_lowercase();
+        let title = language::normalize(&post.title).to_lowercase();
         let has_slug = |pattern: &str| slugs.iter().any(|s| s.contains(pattern));
         let has_word = |pattern: &str| title.contains(pattern);
 
@@ -394,6 +423,9 @@ impl MetaculusClient {
                 metaculus_forecasters: Some(forecasters),
                 ..CrossReferences::default()
             },
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         })
     }
 }
@@ -493,6 +525,22 @@ impl PredictionPlatform for MetaculusClient {
         })
     }
 
+    /// From the question's `resolution` on `GET /api2/questions/{id}/`.
+    async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+        let mut req = self.http.get(format!("{BASE_URL}/{market_id}/"));
+        if let Some(token) = &self.api_key {
+            req = req.header("Authorization", format!("Token {token}"));
+        }
+        let resp = req.send().await.context("Metaculus question request failed")?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Metaculus question {market_id} error {status}: {body}");
+        }
+        let post: MetaculusPost = resp.json().await.context("Failed to parse Metaculus question")?;
+        Ok(Self::post_resolution(&post))
+    }
+
     /// Metaculus is read-only — not an execution venue.
     fn is_real_money(&self) -> bool {
         false
@@ -533,6 +581,7 @@ mod tests {
             question_type: "binary".to_string(),
             scheduled_close_time: Some("2027-01-01T00:00:00Z".to_string()),
             scheduled_resolve_time: Some("2027-01-01T00:00:00Z".to_string()),
+            resolution: None,
             resolution_criteria: Some("Test criteria".to_string()),
             fine_print: None,
             description: Some("Test description".to_string()),
@@ -793,4 +842,56 @@ mod tests {
         assert!(!client.is_real_money());
         assert_eq!(client.name(), "metaculus");
     }
+
+    // -- Fixture contract tests --
+
+    fn fixture_page() -> MetaculusPage<serde_json::Value> {
+        serde_json::from_str(&crate::fixtures::read("metaculus/questions.json")).unwrap()
+    }
+
+    #[test]
+    fn test_fixture_questions_page() {
+        let page = MetaculusClient::parse_page(fixture_page());
+        assert_eq!(page.count, 412);
+        assert!(page.next.is_some());
+        assert_eq!(page.results.len(), 2);
+
+        let mut posts = page.results.into_iter();
+        let m = MetaculusClient::to_oracle_market(posts.next().unwrap()).unwrap();
+        assert_eq!(m.id, "28311");
+        assert_eq!(m.category, MarketCategory::Economics);
+        assert_eq!(m.current_price_yes, d(0.57));
+        assert_eq!(m.cross_refs.metaculus_forecasters, Some(188));
+        assert_eq!(m.deadline, MetaculusClient::parse_datetime("2026-11-04T03:30:00Z"));
+        assert!(m.resolution_criteria.starts_with("Resolves YES if the RBA"));
+
+        // No revealed community prediction yet: parsed, but not a market.
+        assert!(MetaculusClient::to_oracle_market(posts.next().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_post_resolution() {
+        let mut post = MetaculusClient::parse_page(fixture_page()).results.remove(0);
+        assert_eq!(MetaculusClient::post_resolution(&post), None);
+        post.resolved = true;
+        for (resolution, expected) in [
+            ("yes", Some(MarketResolution::Yes)),
+            ("no", Some(MarketResolution::No)),
+            ("annulled", Some(MarketResolution::Cancelled)),
+            ("ambiguous", Some(MarketResolution::Cancelled)),
+            ("42.5", None),
+        ] {
+            post.question.as_mut().unwrap().resolution = Some(resolution.to_string());
+            assert_eq!(MetaculusClient::post_resolution(&post), expected, "{resolution}");
+        }
+    }
+
+    #[test]
+    fn test_fixture_drifted_post_is_skipped() {
+        let mut raw = fixture_page();
+        raw.results[0]["status"] = serde_json::Value::Null;
+        let page = MetaculusClient::parse_page(raw);
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.results[0].id, 29002);
+    }
 }
@@ -1,21 +1,29 @@
 //! Platform integrations.
 //!
 //! Defines the `PredictionPlatform` trait and provides implementations for:
-//! - ForecastEx (IB TWS API) — real-money execution (sole AU-compliant venue)
+//! - ForecastEx (IB TWS API) — real-money execution (sole AU-compliant venue), paper-account first
 //! - Metaculus — read-only crowd forecast cross-reference
 //! - Manifold — play-money validation and sentiment signal
 
 pub mod betfair;
 pub mod forecastex;
+pub mod kalshi;
+pub mod ladder;
 pub mod metaculus;
 pub mod manifold;
 pub mod polymarket;
+pub mod preflight;
+pub mod rate_limit;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use tracing::{debug, warn};
 
-use crate::types::{LiquidityInfo, Market, Position, Side, TradeReceipt};
+use crate::types::{LiquidityInfo, LiquidityModel, Market, MarketResolution, Position, Side, TradeReceipt};
+use ladder::{PriceLadder, Rounding};
+use preflight::{PreflightLimits, PreflightReport};
 
 /// Abstraction over prediction market platforms.
 ///
@@ -35,6 +43,55 @@ pub trait PredictionPlatform: Send + Sync {
         amount: Decimal,
     ) -> Result<TradeReceipt>;
 
+    /// Whether [`Self::place_bet_keyed`] deduplicates on its key: a repeated
+    /// key is rejected with [`crate::types::OracleError::DuplicateOrder`]
+    /// instead of being placed twice, so a lost response can be retried.
+    fn supports_idempotency(&self) -> bool {
+        false
+    }
+
+    /// [`Self::place_bet`] under a client idempotency key. Venues without
+    /// idempotency support ignore the key.
+    async fn place_bet_keyed(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        key: &str,
+    ) -> Result<TradeReceipt> {
+        let _ = key;
+        self.place_bet(market_id, side, amount).await
+    }
+
+    /// Whether [`Self::place_limit_bet`] sends its price limit with the
+    /// order. Venues without limit orders are checked against
+    /// [`Self::current_price`] before placing instead.
+    fn supports_limit_orders(&self) -> bool {
+        false
+    }
+
+    /// [`Self::place_bet_keyed`] paying at most `max_price` for `side`'s
+    /// probability; an order that can't fill within it goes unmatched.
+    /// Venues without limit orders ignore the limit.
+    async fn place_limit_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        max_price: Decimal,
+        key: &str,
+    ) -> Result<TradeReceipt> {
+        let _ = max_price;
+        self.place_bet_keyed(market_id, side, amount, key).await
+    }
+
+    /// Current price of `side` on `market_id`; `None` when the venue
+    /// can't quote one market on its own.
+    async fn current_price(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        let _ = (market_id, side);
+        Ok(None)
+    }
+
     /// Get current open positions on this platform.
     async fn get_positions(&self) -> Result<Vec<Position>>;
 
@@ -44,9 +101,107 @@ pub trait PredictionPlatform: Send + Sync {
     /// Check liquidity for a specific market.
     async fn check_liquidity(&self, market_id: &str) -> Result<LiquidityInfo>;
 
+    /// What [`Self::check_liquidity`] reports: an order book unless the
+    /// venue is a market maker.
+    fn liquidity_model(&self) -> LiquidityModel {
+        LiquidityModel::OrderBook
+    }
+
     /// Whether this platform supports real-money execution.
     fn is_real_money(&self) -> bool;
 
     /// Platform name for logging and identification.
     fn name(&self) -> &str;
+
+    /// Tick ladder that limit prices on `market` must land on.
+    fn price_increment(&self, market: &Market) -> PriceLadder {
+        let _ = market;
+        PriceLadder::CENTS
+    }
+
+    /// Direction that keeps a snapped `side` limit price passive
+    /// (see [`ladder`]). Defaults to paying less for the bought outcome.
+    fn passive_rounding(&self, side: Side) -> Rounding {
+        let _ = side;
+        Rounding::Down
+    }
+
+    /// Snap a computed limit price onto the market's ladder, toward the
+    /// passive side of the book.
+    fn limit_price(&self, market: &Market, side: Side, price: Decimal) -> Decimal {
+        self.price_increment(market).snap(price, self.passive_rounding(side))
+    }
+
+    /// Whether open positions can be closed outright (sold back) here.
+    /// Venues without close support are exited by hedging instead.
+    fn supports_close(&self) -> bool {
+        false
+    }
+
+    /// Close an open position outright.
+    /// Returns the amount credited back, if the venue reports it.
+    async fn close_position(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        let _ = (market_id, side);
+        anyhow::bail!("{} does not support closing positions", self.name())
+    }
+
+    /// How `market_id` resolved; `None` while it is still open or when
+    /// the platform doesn't report resolutions.
+    async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+        let _ = market_id;
+        Ok(None)
+    }
+
+    /// Check the venue can take an order right now (see [`preflight`]).
+    /// Defaults to auth and balance via [`Self::get_balance`].
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        let mut report = PreflightReport::new(self.name());
+        report.balance(self.get_balance().await, limits);
+        report
+    }
+}
+
+/// Deserialize each entry of a JSON array response on its own, skipping
+/// entries that don't fit `T`. A drifted field then costs the entries it
+/// appears in rather than the whole scan. Returns the parsed entries and
+/// the number skipped; skips are logged under `source`.
+pub fn parse_entries<T: DeserializeOwned>(source: &str, entries: Vec<serde_json::Value>) -> (Vec<T>, usize) {
+    let mut parsed = Vec::with_capacity(entries.len());
+    let mut skipped = 0;
+    for entry in entries {
+        match serde_json::from_value(entry) {
+            Ok(t) => parsed.push(t),
+            Err(e) => {
+                skipped += 1;
+                debug!(source, error = %e, "Skipping malformed API entry");
+            }
+        }
+    }
+    if skipped > 0 {
+        warn!(source, skipped, parsed = parsed.len(), "Skipped malformed API entries");
+    }
+    (parsed, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct Entry {
+        id: u32,
+    }
+
+    #[test]
+    fn test_parse_entries_skips_and_counts_malformed() {
+        let entries = vec![
+            serde_json::json!({ "id": 1 }),
+            serde_json::json!({ "id": "two" }),
+            serde_json::json!({ "name": "three" }),
+            serde_json::json!({ "id": 4, "extra": true }),
+        ];
+        let (parsed, skipped) = parse_entries::<Entry>("test", entries);
+        assert_eq!(parsed, vec![Entry { id: 1 }, Entry { id: 4 }]);
+        assert_eq!(skipped, 2);
+    }
 }
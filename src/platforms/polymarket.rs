@@ -11,12 +11,19 @@
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use reqwest::Client;
+use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::Deserialize;
 use tracing::{debug, info, warn};
 
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::platforms::ladder::PriceLadder;
+use crate::platforms::preflight::{CheckKind, PreflightLimits, PreflightReport};
 use crate::platforms::PredictionPlatform;
 use crate::types::{
     d, CrossReferences, LiquidityInfo, Market, MarketCategory, Position, Side, TradeReceipt,
@@ -28,6 +35,8 @@ use crate::types::{
 
 const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
 const CLOB_API_URL: &str = "https://clob.polymarket.com";
+/// Polygon wallet private key used for EIP-712 order signing.
+const WALLET_KEY_ENV: &str = "POLYMARKET_PRIVATE_KEY";
 const DEFAULT_LIMIT: u32 = 100;
 const MIN_VOLUME_24H: f64 = 1000.0;
 const MIN_LIQUIDITY: f64 = 500.0;
@@ -60,12 +69,15 @@ pub struct GammaMarket {
     pub outcome_prices: Option<String>,
     #[serde(default, rename = "clobTokenIds")]
     pub clob_token_ids: Option<String>,
-    #[serde(default)]
+    /// Sent as a JSON string; see [`number_or_string`].
+    #[serde(default, deserialize_with = "number_or_string")]
     pub volume: Option<f64>,
     #[serde(default, rename = "volumeNum")]
     pub volume_num: Option<f64>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "number_or_string")]
     pub liquidity: Option<f64>,
+    #[serde(default, rename = "liquidityNum")]
+    pub liquidity_num: Option<f64>,
     #[serde(default)]
     pub tags: Option<Vec<GammaTag>>,
     #[serde(default, rename = "bestBid")]
@@ -76,6 +88,35 @@ pub struct GammaMarket {
     pub spread: Option<f64>,
     #[serde(default, rename = "lastTradePrice")]
     pub last_trade_price: Option<f64>,
+    /// Minimum price increment on the CLOB (0.01, or 0.001 near 0 and 1).
+    #[serde(default, rename = "orderPriceMinTickSize")]
+    pub order_price_min_tick_size: Option<f64>,
+    /// Parent event(s); markets sharing an event are correlated.
+    #[serde(default)]
+    pub events: Option<Vec<GammaEvent>>,
+}
+
+/// Gamma sends some amounts as JSON strings (`"liquidity": "1234.5"`)
+/// and their `*Num` twins as numbers; accept either form. Unparseable
+/// strings read as absent.
+fn number_or_string<'de, D: serde::Deserializer<'de>>(de: D) -> std::result::Result<Option<f64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(f64),
+        Text(String),
+    }
+    Ok(match Option::<Amount>::deserialize(de)? {
+        Some(Amount::Number(n)) => Some(n),
+        Some(Amount::Text(s)) => s.parse().ok(),
+        None => None,
+    })
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GammaEvent {
+    #[serde(default)]
+    pub slug: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -95,6 +136,8 @@ pub struct PolymarketClient {
     http: Client,
     min_volume: f64,
     min_liquidity: f64,
+    /// Tick size reported per market (condition id) at the last scan.
+    tick_sizes: Mutex<HashMap<String, Decimal>>,
 }
 
 impl PolymarketClient {
@@ -108,6 +151,7 @@ impl PolymarketClient {
             http,
             min_volume: MIN_VOLUME_24H,
             min_liquidity: MIN_LIQUIDITY,
+            tick_sizes: Mutex::default(),
         })
     }
 
@@ -133,13 +177,24 @@ impl PolymarketClient {
             anyhow::bail!("Gamma API error {status}: {body}");
         }
 
-        let markets: Vec<GammaMarket> = resp.json().await
+        let entries: Vec<serde_json::Value> = resp.json().await
             .context("Failed to parse Gamma markets response")?;
+        let (markets, _) = super::parse_entries::<GammaMarket>("polymarket/gamma-markets", entries);
 
         info!(count = markets.len(), "Fetched raw Gamma markets");
         Ok(markets)
     }
 
+    /// Remember each market's reported tick size for limit pricing.
+    fn record_tick_sizes(&self, markets: &[GammaMarket]) {
+        let mut ticks = self.tick_sizes.lock().unwrap();
+        for gm in markets {
+            if let Some(tick) = gm.order_price_min_tick_size.and_then(Decimal::from_f64) {
+                ticks.insert(gm.condition_id.clone(), tick.normalize());
+            }
+        }
+    }
+
     /// Convert a Gamma market into our internal Market type.
     pub fn convert_market(gm: &GammaMarket) -> Option<Market> {
         if gm.condition_id.is_empty() || gm.question.is_empty() {
@@ -166,7 +221,7 @@ impl PolymarketClient {
             .unwrap_or_else(|| chrono::Utc::now() + chrono::Duration::days(365));
 
         let volume = d(gm.volume.or(gm.volume_num).unwrap_or(0.0));
-        let liquidity = d(gm.liquidity.unwrap_or(0.0));
+        let liquidity = d(gm.liquidity.or(gm.liquidity_num).unwrap_or(0.0));
 
         // Categorize from tags
         let category = gm.tags.as_ref()
@@ -189,6 +244,12 @@ impl PolymarketClient {
             resolution_criteria: gm.description.clone(),
             url,
             cross_refs: CrossReferences::default(),
+            event_group: gm.events.as_ref()
+                .and_then(|events| events.first())
+                .filter(|e| !e.slug.is_empty())
+                .map(|e| format!("polymarket:{}", e.slug)),
+                facts: None,
+                tags: Vec::new(),
         })
     }
 
@@ -273,6 +334,48 @@ impl PolymarketClient {
         }
     }
 
+    /// CLOB server time; signed requests outside its tolerance are rejected.
+    async fn fetch_server_time(&self) -> Result<DateTime<Utc>> {
+        let body = self.http
+            .get(format!("{CLOB_API_URL}/time"))
+            .send()
+            .await
+            .context("Polymarket CLOB time request failed")?
+            .error_for_status()
+            .context("Polymarket CLOB time request rejected")?
+            .text()
+            .await
+            .context("Failed to read Polymarket CLOB time")?;
+        Self::parse_server_time(&body).with_context(|| format!("Unparseable CLOB time: {body}"))
+    }
+
+    /// `/time` answers with bare unix seconds.
+    pub fn parse_server_time(body: &str) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(body.trim().trim_matches('"').parse().ok()?, 0)
+    }
+
+    /// Preflight from its inputs. USDC and CTF approvals can only be read
+    /// once order signing is wired, so until then the venue is never a go.
+    pub fn preflight_report(
+        wallet_configured: bool,
+        server_time: Result<DateTime<Utc>>,
+        local: DateTime<Utc>,
+        limits: &PreflightLimits,
+    ) -> PreflightReport {
+        let mut report = PreflightReport::new("polymarket");
+        if wallet_configured {
+            report.pass(CheckKind::Auth, "wallet key configured");
+        } else {
+            report.fail(CheckKind::Auth, format!("{WALLET_KEY_ENV} not set"));
+        }
+        report.fail(CheckKind::Allowance, "USDC/CTF approvals unverifiable until CLOB signing is wired");
+        match server_time {
+            Ok(server) => report.clock(server, local, limits),
+            Err(e) => report.fail(CheckKind::ClockSkew, format!("{e:#}")),
+        }
+        report
+    }
+
     /// Filter markets by volume and liquidity thresholds.
     pub fn filter_markets(&self, markets: Vec<Market>) -> Vec<Market> {
         let min_vol = d(self.min_volume);
@@ -295,6 +398,7 @@ impl PolymarketClient {
 impl PredictionPlatform for PolymarketClient {
     async fn fetch_markets(&self) -> Result<Vec<Market>> {
         let gamma_markets = self.fetch_gamma_markets().await?;
+        self.record_tick_sizes(&gamma_markets);
 
         let markets: Vec<Market> = gamma_markets.iter()
             .filter_map(|gm| Self::convert_market(gm))
@@ -330,6 +434,31 @@ impl PredictionPlatform for PolymarketClient {
         Ok(TradeReceipt::dry_run(market_id, amount, "USDC"))
     }
 
+    /// CLOB orders are limit orders: `max_price` becomes the order's price.
+    fn supports_limit_orders(&self) -> bool {
+        true
+    }
+
+    async fn place_limit_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        max_price: Decimal,
+        _key: &str,
+    ) -> Result<TradeReceipt> {
+        // TODO: Send as the signed order's limit price, snapped down to the
+        // market's tick size, once CLOB placement is wired.
+        warn!(
+            market_id = %market_id,
+            side = ?side,
+            amount = %amount,
+            max_price = %max_price,
+            "Polymarket execution not yet wired — returning dry-run receipt"
+        );
+        Ok(TradeReceipt::dry_run(market_id, amount, "USDC"))
+    }
+
     async fn get_positions(&self) -> Result<Vec<Position>> {
         // TODO: Query CLOB API /positions with L2 auth
         Ok(Vec::new())
@@ -353,9 +482,24 @@ impl PredictionPlatform for PolymarketClient {
         false // Until CLOB signing is wired up
     }
 
+    /// The market's reported tick size; cents when it hasn't been seen.
+    fn price_increment(&self, market: &Market) -> PriceLadder {
+        self.tick_sizes
+            .lock()
+            .unwrap()
+            .get(&market.id)
+            .map_or(PriceLadder::CENTS, |&tick| PriceLadder::for_tick(tick))
+    }
+
     fn name(&self) -> &str {
         "polymarket"
     }
+
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        let wallet_configured = std::env::var(WALLET_KEY_ENV).is_ok_and(|k| !k.is_empty());
+        let server_time = self.fetch_server_time().await;
+        Self::preflight_report(wallet_configured, server_time, Utc::now(), limits)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -480,12 +624,15 @@ mod tests {
             clob_token_ids: None,
             volume: Some(5000.0),
             volume_num: None,
+            liquidity_num: None,
             liquidity: Some(2000.0),
             tags: None,
             best_bid: None,
             best_ask: None,
             spread: None,
             last_trade_price: None,
+            events: None,
+            order_price_min_tick_size: None,
         };
         assert!(PolymarketClient::convert_market(&gm).is_none());
     }
@@ -505,15 +652,19 @@ mod tests {
             clob_token_ids: Some("[\"token1\",\"token2\"]".into()),
             volume: Some(50000.0),
             volume_num: None,
+            liquidity_num: None,
             liquidity: Some(10000.0),
             tags: Some(vec![GammaTag { label: "Crypto".into(), slug: "crypto".into() }]),
             best_bid: Some(0.71),
             best_ask: Some(0.73),
             spread: Some(0.02),
             last_trade_price: Some(0.72),
+            events: Some(vec![GammaEvent { slug: "bitcoin-price-2026".into() }]),
+            order_price_min_tick_size: Some(0.001),
         };
 
         let market = PolymarketClient::convert_market(&gm).unwrap();
+        assert_eq!(market.event_group.as_deref(), Some("polymarket:bitcoin-price-2026"));
         assert_eq!(market.id, "0xabc123");
         assert_eq!(market.platform, "polymarket");
         assert_eq!(market.current_price_yes, dec!(0.72));
@@ -527,6 +678,7 @@ mod tests {
             http: Client::new(),
             min_volume: 1000.0,
             min_liquidity: 500.0,
+            tick_sizes: Mutex::default(),
         };
 
         let markets = vec![
@@ -544,6 +696,9 @@ mod tests {
                 resolution_criteria: String::new(),
                 url: String::new(),
                 cross_refs: Default::default(),
+                event_group: None,
+                facts: None,
+                tags: Vec::new(),
             },
             Market {
                 id: "low_volume".into(),
@@ -559,6 +714,9 @@ mod tests {
                 resolution_criteria: String::new(),
                 url: String::new(),
                 cross_refs: Default::default(),
+                event_group: None,
+                facts: None,
+                tags: Vec::new(),
             },
             Market {
                 id: "nearly_resolved".into(),
@@ -574,6 +732,9 @@ mod tests {
                 resolution_criteria: String::new(),
                 url: String::new(),
                 cross_refs: Default::default(),
+                event_group: None,
+                facts: None,
+                tags: Vec::new(),
             },
         ];
 
@@ -582,10 +743,101 @@ mod tests {
         assert_eq!(filtered[0].id, "good");
     }
 
+    #[test]
+    fn test_limit_price_uses_reported_tick_size() {
+        let client = PolymarketClient::new().unwrap();
+        let gm = |id: &str, tick: Option<f64>| GammaMarket {
+            condition_id: id.into(),
+            order_price_min_tick_size: tick,
+            ..serde_json::from_str("{}").unwrap()
+        };
+        client.record_tick_sizes(&[gm("fine", Some(0.001)), gm("coarse", Some(0.01)), gm("unreported", None)]);
+
+        let mut market = PolymarketClient::convert_market(&GammaMarket {
+            question: "Q?".into(),
+            ..gm("fine", None)
+        })
+        .unwrap();
+        // Buys of either outcome token round the price paid down.
+        assert_eq!(client.limit_price(&market, Side::Yes, dec!(0.0276)), dec!(0.027));
+        assert_eq!(client.limit_price(&market, Side::No, dec!(0.9724)), dec!(0.972));
+        market.id = "coarse".into();
+        assert_eq!(client.limit_price(&market, Side::Yes, dec!(0.0276)), dec!(0.02));
+        market.id = "unreported".into();
+        assert_eq!(client.price_increment(&market), PriceLadder::CENTS);
+        assert_eq!(client.limit_price(&market, Side::No, dec!(0.437)), dec!(0.43));
+    }
+
     #[test]
     fn test_client_construction() {
         let client = PolymarketClient::new().unwrap();
         assert_eq!(client.name(), "polymarket");
         assert!(!client.is_real_money()); // until CLOB is wired
     }
+
+    #[test]
+    fn test_parse_server_time() {
+        let t = PolymarketClient::parse_server_time("1760000000\n").unwrap();
+        assert_eq!(t.timestamp(), 1_760_000_000);
+        assert!(PolymarketClient::parse_server_time("\"1760000000\"").is_some());
+        assert!(PolymarketClient::parse_server_time("<html>").is_none());
+    }
+
+    #[test]
+    fn test_preflight_failure_modes() {
+        let local = Utc::now();
+        let limits = PreflightLimits::default();
+        let failed = |r: &PreflightReport| r.failures().map(|c| c.kind).collect::<Vec<_>>();
+
+        // Wallet configured, clock in sync: still blocked on approvals.
+        let r = PolymarketClient::preflight_report(true, Ok(local), local, &limits);
+        assert!(!r.go());
+        assert_eq!(failed(&r), vec![CheckKind::Allowance]);
+
+        // No wallet, clock 30 s off.
+        let r = PolymarketClient::preflight_report(false, Ok(local - chrono::Duration::seconds(30)), local, &limits);
+        assert_eq!(failed(&r), vec![CheckKind::Auth, CheckKind::Allowance, CheckKind::ClockSkew]);
+        assert!(r.summary().contains("POLYMARKET_PRIVATE_KEY not set"));
+
+        // CLOB unreachable: skew can't be confirmed.
+        let r = PolymarketClient::preflight_report(true, Err(anyhow::anyhow!("timed out")), local, &limits);
+        assert!(failed(&r).contains(&CheckKind::ClockSkew));
+    }
+
+    // -- Fixture contract tests --
+
+    fn fixture_entries() -> Vec<serde_json::Value> {
+        serde_json::from_str(&crate::fixtures::read("polymarket/gamma-markets.json")).unwrap()
+    }
+
+    #[test]
+    fn test_fixture_gamma_markets() {
+        let (markets, skipped) =
+            crate::platforms::parse_entries::<GammaMarket>("test", fixture_entries());
+        assert_eq!((markets.len(), skipped), (1, 0));
+        let gm = &markets[0];
+        // Gamma sends volume and liquidity as strings.
+        assert_eq!(gm.volume, Some(2048812.19));
+        assert_eq!(gm.liquidity, Some(184230.55));
+        assert_eq!(gm.order_price_min_tick_size, Some(0.001));
+
+        let m = PolymarketClient::convert_market(gm).unwrap();
+        assert_eq!(m.id, "0x9b1f6f1c2a0f4f5d8c3e2b1a09f8e7d6c5b4a3928171605f4e3d2c1b0a998877");
+        assert_eq!(m.current_price_yes, dec!(0.615));
+        assert_eq!(m.current_price_no, dec!(0.385));
+        assert_eq!(m.liquidity, d(184230.55));
+        assert_eq!(m.event_group.as_deref(), Some("polymarket:fed-decision-in-december-2026"));
+    }
+
+    #[test]
+    fn test_fixture_unparseable_amount_reads_as_absent() {
+        let mut entries = fixture_entries();
+        entries[0]["liquidity"] = serde_json::json!("n/a");
+        let (markets, skipped) = crate::platforms::parse_entries::<GammaMarket>("test", entries);
+        assert_eq!(skipped, 0);
+        assert_eq!(markets[0].liquidity, None);
+        // The numeric twin still carries it through conversion.
+        let m = PolymarketClient::convert_market(&markets[0]).unwrap();
+        assert_eq!(m.liquidity, d(184230.55));
+    }
 }
@@ -0,0 +1,196 @@
+//! Venue preflight checks.
+//!
+//! Before the agent trades on a venue it confirms the venue can take an
+//! order: credentials are accepted, the balance is above a floor, any
+//! on-chain approvals are in place and, for venues that sign requests, the
+//! local clock agrees with the venue's. A venue that fails stays scan-only
+//! (see [`crate::engine::executor::Executor::preflight`]) instead of
+//! failing bets mid-cycle.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// Thresholds a venue is checked against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreflightLimits {
+    /// Balance below which the venue is not traded.
+    pub min_balance: Decimal,
+    /// Largest tolerated difference between local and venue clocks.
+    pub max_clock_skew: chrono::Duration,
+}
+
+impl Default for PreflightLimits {
+    fn default() -> Self {
+        Self { min_balance: Decimal::ZERO, max_clock_skew: chrono::Duration::seconds(5) }
+    }
+}
+
+/// What a check verifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    Auth,
+    Balance,
+    Allowance,
+    ClockSkew,
+}
+
+/// One check's outcome.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PreflightCheck {
+    pub kind: CheckKind,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// Every check run against one venue.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PreflightReport {
+    pub platform: String,
+    pub checked_at: DateTime<Utc>,
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    pub fn new(platform: &str) -> Self {
+        Self { platform: platform.to_string(), checked_at: Utc::now(), checks: Vec::new() }
+    }
+
+    pub fn pass(&mut self, kind: CheckKind, detail: impl Into<String>) {
+        self.checks.push(PreflightCheck { kind, ok: true, detail: detail.into() });
+    }
+
+    pub fn fail(&mut self, kind: CheckKind, detail: impl Into<String>) {
+        self.checks.push(PreflightCheck { kind, ok: false, detail: detail.into() });
+    }
+
+    /// Go: every check passed. A report with no checks is a no-go.
+    pub fn go(&self) -> bool {
+        !self.checks.is_empty() && self.checks.iter().all(|c| c.ok)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &PreflightCheck> {
+        self.checks.iter().filter(|c| !c.ok)
+    }
+
+    /// One line naming the failed checks, e.g. "auth: session expired".
+    pub fn summary(&self) -> String {
+        if self.go() {
+            return "all checks passed".to_string();
+        }
+        if self.checks.is_empty() {
+            return "no checks ran".to_string();
+        }
+        self.failures()
+            .map(|c| format!("{}: {}", kind_label(c.kind), c.detail))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Record auth and balance from one authenticated balance request:
+    /// an error means the credentials were not accepted.
+    pub fn balance(&mut self, fetched: anyhow::Result<Decimal>, limits: &PreflightLimits) {
+        match fetched {
+            Ok(balance) => {
+                self.pass(CheckKind::Auth, "credentials accepted");
+                self.check_floor(balance, limits);
+            }
+            Err(e) => self.fail(CheckKind::Auth, format!("{e:#}")),
+        }
+    }
+
+    /// Balance against the configured floor.
+    pub fn check_floor(&mut self, balance: Decimal, limits: &PreflightLimits) {
+        if balance >= limits.min_balance {
+            self.pass(CheckKind::Balance, format!("{balance} available"));
+        } else {
+            self.fail(CheckKind::Balance, format!("{balance} below floor {}", limits.min_balance));
+        }
+    }
+
+    /// Venue clock `server` against the local clock at `local`.
+    pub fn clock(&mut self, server: DateTime<Utc>, local: DateTime<Utc>, limits: &PreflightLimits) {
+        let skew = (server - local).num_milliseconds();
+        let detail = format!("{skew} ms from venue clock");
+        if skew.abs() <= limits.max_clock_skew.num_milliseconds() {
+            self.pass(CheckKind::ClockSkew, detail);
+        } else {
+            self.fail(CheckKind::ClockSkew, detail);
+        }
+    }
+}
+
+fn kind_label(kind: CheckKind) -> &'static str {
+    match kind {
+        CheckKind::Auth => "auth",
+        CheckKind::Balance => "balance",
+        CheckKind::Allowance => "allowance",
+        CheckKind::ClockSkew => "clock skew",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn limits(min_balance: Decimal) -> PreflightLimits {
+        PreflightLimits { min_balance, ..PreflightLimits::default() }
+    }
+
+    #[test]
+    fn test_healthy_venue_is_go() {
+        let mut r = PreflightReport::new("betfair");
+        r.balance(Ok(dec!(250)), &limits(dec!(100)));
+        assert!(r.go());
+        assert_eq!(r.summary(), "all checks passed");
+        assert_eq!(r.checks.len(), 2);
+    }
+
+    #[test]
+    fn test_auth_failure_is_no_go() {
+        let mut r = PreflightReport::new("betfair");
+        r.balance(Err(anyhow::anyhow!("Betfair login rejected: INVALID_USERNAME_OR_PASSWORD")), &limits(dec!(0)));
+        assert!(!r.go());
+        let failed: Vec<_> = r.failures().map(|c| c.kind).collect();
+        assert_eq!(failed, vec![CheckKind::Auth]);
+        assert!(r.summary().starts_with("auth: Betfair login rejected"));
+    }
+
+    #[test]
+    fn test_balance_below_floor_is_no_go() {
+        let mut r = PreflightReport::new("manifold");
+        r.balance(Ok(dec!(40)), &limits(dec!(50)));
+        assert!(!r.go());
+        assert_eq!(r.summary(), "balance: 40 below floor 50");
+        // The floor itself is enough.
+        let mut r = PreflightReport::new("manifold");
+        r.balance(Ok(dec!(50)), &limits(dec!(50)));
+        assert!(r.go());
+    }
+
+    #[test]
+    fn test_clock_skew_tolerance() {
+        let local = Utc::now();
+        let l = PreflightLimits::default();
+        let mut r = PreflightReport::new("polymarket");
+        r.clock(local + chrono::Duration::seconds(3), local, &l);
+        r.clock(local - chrono::Duration::seconds(4), local, &l);
+        assert!(r.go());
+        r.clock(local - chrono::Duration::seconds(9), local, &l);
+        assert!(!r.go());
+        assert_eq!(r.summary(), "clock skew: -9000 ms from venue clock");
+    }
+
+    #[test]
+    fn test_empty_report_is_no_go() {
+        let r = PreflightReport::new("polymarket");
+        assert!(!r.go());
+        assert_eq!(r.summary(), "no checks ran");
+    }
+}
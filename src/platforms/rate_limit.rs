@@ -0,0 +1,268 @@
+//! Request budgets for platform clients.
+//!
+//! [`TokenBucket`] is the shared limiter: it holds up to a burst of tokens
+//! and refills at a steady rate. [`RateLimited`] wraps any
+//! [`PredictionPlatform`] and takes a token before every call that reaches
+//! the venue, waiting when the bucket is empty, so a burst of
+//! `check_liquidity` calls in one cycle stays within the platform's
+//! per-minute budget (`platforms.<name>.requests_per_minute`).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tracing::debug;
+
+use super::ladder::{PriceLadder, Rounding};
+use super::preflight::{PreflightLimits, PreflightReport};
+use super::PredictionPlatform;
+use crate::types::{LiquidityInfo, LiquidityModel, Market, MarketResolution, Position, Side, TradeReceipt};
+
+/// Token bucket holding at most `capacity` tokens, refilled continuously.
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    /// Tokens available, as of the instant.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    /// A full bucket of `capacity` tokens.
+    pub fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self { capacity, refill_per_sec, state: Mutex::new((capacity, Instant::now())) }
+    }
+
+    /// `requests` per minute, all of which may be spent back to back.
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests as f64, requests as f64 / 60.0)
+    }
+
+    /// Take one token if one is available at `now`; otherwise how long
+    /// until one is.
+    pub fn take_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, last) = *state;
+        let elapsed = now.saturating_duration_since(last).as_secs_f64();
+        let tokens = (tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        if tokens >= 1.0 {
+            *state = (tokens - 1.0, now);
+            return Ok(());
+        }
+        *state = (tokens, now);
+        if self.refill_per_sec <= 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - tokens) / self.refill_per_sec))
+    }
+
+    /// Take one token if one is available at `now`.
+    pub fn try_take_at(&self, now: Instant) -> bool {
+        self.take_at(now).is_ok()
+    }
+
+    pub fn try_take(&self) -> bool {
+        self.try_take_at(Instant::now())
+    }
+
+    /// Take one token, waiting for the refill when the bucket is empty.
+    pub async fn take(&self) {
+        while let Err(wait) = self.take_at(Instant::now()) {
+            tokio::time::sleep(wait.min(Duration::from_secs(60))).await;
+        }
+    }
+}
+
+/// A platform whose venue calls are held to a request budget.
+pub struct RateLimited {
+    inner: Arc<dyn PredictionPlatform>,
+    budget: TokenBucket,
+}
+
+impl RateLimited {
+    pub fn new(inner: Arc<dyn PredictionPlatform>, requests_per_minute: u32) -> Self {
+        Self { inner, budget: TokenBucket::per_minute(requests_per_minute) }
+    }
+
+    async fn acquire(&self, call: &str) {
+        if self.budget.try_take() {
+            return;
+        }
+        debug!(platform = self.inner.name(), call, "Request budget spent — waiting");
+        self.budget.take().await;
+    }
+}
+
+/// Wrap `venue` in its budget from `budgets` (by platform name); venues
+/// without one, or with 0, are returned unchanged.
+pub fn wrap_platform(venue: Arc<dyn PredictionPlatform>, budgets: &HashMap<String, u32>) -> Arc<dyn PredictionPlatform> {
+    match budgets.get(venue.name()).copied() {
+        Some(requests_per_minute) if requests_per_minute > 0 => Arc::new(RateLimited::new(venue, requests_per_minute)),
+        _ => venue,
+    }
+}
+
+#[async_trait]
+impl PredictionPlatform for RateLimited {
+    async fn fetch_markets(&self) -> Result<Vec<Market>> {
+        self.acquire("markets").await;
+        self.inner.fetch_markets().await
+    }
+
+    async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+        self.acquire("bet").await;
+        self.inner.place_bet(market_id, side, amount).await
+    }
+
+    fn supports_idempotency(&self) -> bool {
+        self.inner.supports_idempotency()
+    }
+
+    async fn place_bet_keyed(&self, market_id: &str, side: Side, amount: Decimal, key: &str) -> Result<TradeReceipt> {
+        self.acquire("bet").await;
+        self.inner.place_bet_keyed(market_id, side, amount, key).await
+    }
+
+    fn supports_limit_orders(&self) -> bool {
+        self.inner.supports_limit_orders()
+    }
+
+    async fn place_limit_bet(
+        &self,
+        market_id: &str,
+        side: Side,
+        amount: Decimal,
+        max_price: Decimal,
+        key: &str,
+    ) -> Result<TradeReceipt> {
+        self.acquire("bet").await;
+        self.inner.place_limit_bet(market_id, side, amount, max_price, key).await
+    }
+
+    async fn current_price(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        self.acquire("price").await;
+        self.inner.current_price(market_id, side).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        self.acquire("positions").await;
+        self.inner.get_positions().await
+    }
+
+    async fn get_balance(&self) -> Result<Decimal> {
+        self.acquire("balance").await;
+        self.inner.get_balance().await
+    }
+
+    async fn check_liquidity(&self, market_id: &str) -> Result<LiquidityInfo> {
+        self.acquire("liquidity").await;
+        self.inner.check_liquidity(market_id).await
+    }
+
+    fn liquidity_model(&self) -> LiquidityModel {
+        self.inner.liquidity_model()
+    }
+
+    fn is_real_money(&self) -> bool {
+        self.inner.is_real_money()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn price_increment(&self, market: &Market) -> PriceLadder {
+        self.inner.price_increment(market)
+    }
+
+    fn passive_rounding(&self, side: Side) -> Rounding {
+        self.inner.passive_rounding(side)
+    }
+
+    fn supports_close(&self) -> bool {
+        self.inner.supports_close()
+    }
+
+    async fn close_position(&self, market_id: &str, side: Side) -> Result<Option<Decimal>> {
+        self.acquire("close").await;
+        self.inner.close_position(market_id, side).await
+    }
+
+    async fn resolution(&self, market_id: &str) -> Result<Option<MarketResolution>> {
+        self.acquire("resolution").await;
+        self.inner.resolution(market_id).await
+    }
+
+    async fn preflight(&self, limits: &PreflightLimits) -> PreflightReport {
+        self.acquire("preflight").await;
+        self.inner.preflight(limits).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_per_minute_bucket_refills_at_rate() {
+        let bucket = TokenBucket::per_minute(60);
+        let start = Instant::now();
+        assert_eq!((0..100).filter(|_| bucket.try_take_at(start)).count(), 60);
+        // One token a second
+        assert_eq!(bucket.take_at(start), Err(Duration::from_secs(1)));
+        assert!(bucket.try_take_at(start + Duration::from_secs(1)));
+        assert!(!bucket.try_take_at(start + Duration::from_secs(1)));
+    }
+
+    struct Counting {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PredictionPlatform for Counting {
+        async fn fetch_markets(&self) -> Result<Vec<Market>> {
+            Ok(Vec::new())
+        }
+        async fn place_bet(&self, market_id: &str, _: Side, _: Decimal) -> Result<TradeReceipt> {
+            anyhow::bail!("no bets on {market_id}")
+        }
+        async fn get_positions(&self) -> Result<Vec<Position>> {
+            Ok(Vec::new())
+        }
+        async fn get_balance(&self) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+        async fn check_liquidity(&self, _: &str) -> Result<LiquidityInfo> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(LiquidityInfo { bid_depth: Decimal::ZERO, ask_depth: Decimal::ZERO, volume_24h: Decimal::ZERO })
+        }
+        fn is_real_money(&self) -> bool {
+            false
+        }
+        fn name(&self) -> &str {
+            "manifold"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_burst_waits_for_budget() {
+        let inner = Arc::new(Counting { calls: AtomicUsize::new(0) });
+        let budgets = HashMap::from([("manifold".to_string(), 6000)]);
+        let venue = wrap_platform(inner.clone(), &budgets);
+
+        let start = Instant::now();
+        for i in 0..6005 {
+            venue.check_liquidity(&format!("m{i}")).await.unwrap();
+        }
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 6005);
+        // 6000 back to back, then five more at 100 a second
+        assert!(start.elapsed() >= Duration::from_millis(45), "{:?}", start.elapsed());
+    }
+}
@@ -0,0 +1,467 @@
+//! Prometheus metrics for cycle and trading telemetry.
+//!
+//! A small in-process [`Registry`] of counters, gauges and histograms,
+//! rendered in the Prometheus text exposition format by the dashboard's
+//! `/metrics` route. The agent loop records each completed cycle, its
+//! strategy rejections and per-platform scan times; the executor counts
+//! placements and failures per venue; [`TimedEstimator`] observes LLM call
+//! latency. Every metric is declared up front in [`METRICS`], so a scrape
+//! lists all families from the first cycle and unlabelled series start at
+//! zero.
+//!
+//! Values are kept as `f64` — this is monitoring, not accounting.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::engine::accountant::CycleReport;
+use crate::engine::executor::ExecutionReport;
+use crate::engine::scanner::ScanSummary;
+use crate::llm::{CallParams, LlmEstimator};
+use crate::strategy::DecisionRecord;
+use crate::types::{AgentState, DataContext, Estimate, Market};
+
+/// Content type of the text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Bucket bounds (seconds) for LLM call latency.
+const LLM_LATENCY_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0];
+/// Bucket bounds (seconds) for one platform's market fetch.
+const SCAN_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Kind of a metric family, with the bucket bounds of a histogram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Counter,
+    Gauge,
+    Histogram(&'static [f64]),
+}
+
+/// A declared metric family.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metric {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: Kind,
+    /// Label names every series carries.
+    pub labels: &'static [&'static str],
+}
+
+pub const CYCLES: Metric = Metric {
+    name: "oracle_cycles_total",
+    help: "Completed trading cycles.",
+    kind: Kind::Counter,
+    labels: &[],
+};
+pub const MARKETS_SCANNED: Metric = Metric {
+    name: "oracle_markets_scanned_total",
+    help: "Markets passed on for estimation after filtering and caps.",
+    kind: Kind::Counter,
+    labels: &[],
+};
+pub const EDGES_FOUND: Metric = Metric {
+    name: "oracle_edges_found_total",
+    help: "Estimates with an edge above the category threshold.",
+    kind: Kind::Counter,
+    labels: &[],
+};
+pub const BETS_PLACED: Metric = Metric {
+    name: "oracle_bets_placed_total",
+    help: "Bets filled, by platform (\"dry-run\" for simulated fills).",
+    kind: Kind::Counter,
+    labels: &["platform"],
+};
+pub const BETS_FAILED: Metric = Metric {
+    name: "oracle_bets_failed_total",
+    help: "Bet placements that failed, by platform and failure class.",
+    kind: Kind::Counter,
+    labels: &["platform", "code"],
+};
+pub const BETS_UNCONFIRMED: Metric = Metric {
+    name: "oracle_bets_unconfirmed_total",
+    help: "Orders that may have reached the venue but were never confirmed, by platform.",
+    kind: Kind::Counter,
+    labels: &["platform"],
+};
+pub const BETS_REJECTED: Metric = Metric {
+    name: "oracle_bets_rejected_total",
+    help: "Edges not bet on, by the strategy check that rejected them.",
+    kind: Kind::Counter,
+    labels: &["reason"],
+};
+pub const LLM_COST: Metric = Metric {
+    name: "oracle_llm_cost_usd_total",
+    help: "LLM API spend in USD.",
+    kind: Kind::Counter,
+    labels: &[],
+};
+pub const DATA_COST: Metric = Metric {
+    name: "oracle_data_cost_usd_total",
+    help: "Data provider API spend in USD.",
+    kind: Kind::Counter,
+    labels: &[],
+};
+pub const BANKROLL: Metric = Metric {
+    name: "oracle_bankroll_current",
+    help: "AUD bankroll after the latest cycle.",
+    kind: Kind::Gauge,
+    labels: &[],
+};
+pub const DRAWDOWN: Metric = Metric {
+    name: "oracle_drawdown_current",
+    help: "Drawdown from peak bankroll as a fraction.",
+    kind: Kind::Gauge,
+    labels: &[],
+};
+pub const LLM_LATENCY: Metric = Metric {
+    name: "oracle_llm_latency_seconds",
+    help: "LLM call latency, by model and call.",
+    kind: Kind::Histogram(LLM_LATENCY_BUCKETS),
+    labels: &["model", "call"],
+};
+pub const SCAN_DURATION: Metric = Metric {
+    name: "oracle_scan_duration_seconds",
+    help: "Market fetch time per platform scan.",
+    kind: Kind::Histogram(SCAN_BUCKETS),
+    labels: &["platform"],
+};
+
+/// Every metric, in exposition order. Families are listed even before
+/// their first sample; unlabelled counters render as zero until then.
+pub const METRICS: &[Metric] = &[
+    CYCLES, MARKETS_SCANNED, EDGES_FOUND, BETS_PLACED, BETS_FAILED, BETS_UNCONFIRMED, BETS_REJECTED,
+    LLM_COST, DATA_COST, BANKROLL, DRAWDOWN, LLM_LATENCY, SCAN_DURATION,
+];
+
+/// Label pairs of one series, in the order given when first recorded.
+type Labels = Vec<(String, String)>;
+
+#[derive(Debug, Clone)]
+enum Series {
+    Value(f64),
+    /// Per-bucket (non-cumulative) counts, then the `+Inf` overflow.
+    Histogram { counts: Vec<u64>, sum: f64, count: u64 },
+}
+
+/// Shared metric registry. Cheap to update from any task; all methods take
+/// `&self`.
+#[derive(Debug, Default)]
+pub struct Registry {
+    series: Mutex<BTreeMap<&'static str, BTreeMap<Labels, Series>>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `by` to a counter (or gauge).
+    pub fn inc_by(&self, metric: &Metric, labels: &[(&str, &str)], by: f64) {
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = series
+            .entry(metric.name)
+            .or_default()
+            .entry(owned(labels))
+            .or_insert(Series::Value(0.0));
+        if let Series::Value(v) = entry {
+            *v += by;
+        }
+    }
+
+    /// Add one to a counter.
+    pub fn inc(&self, metric: &Metric, labels: &[(&str, &str)]) {
+        self.inc_by(metric, labels, 1.0);
+    }
+
+    /// Set a gauge.
+    pub fn set(&self, metric: &Metric, labels: &[(&str, &str)], value: f64) {
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        series.entry(metric.name).or_default().insert(owned(labels), Series::Value(value));
+    }
+
+    /// Record one histogram observation. Ignored for non-histograms.
+    pub fn observe(&self, metric: &Metric, labels: &[(&str, &str)], value: f64) {
+        let Kind::Histogram(bounds) = metric.kind else { return };
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = series
+            .entry(metric.name)
+            .or_default()
+            .entry(owned(labels))
+            .or_insert_with(|| Series::Histogram { counts: vec![0; bounds.len() + 1], sum: 0.0, count: 0 });
+        if let Series::Histogram { counts, sum, count } = entry {
+            let bucket = bounds.iter().position(|&b| value <= b).unwrap_or(bounds.len());
+            counts[bucket] += 1;
+            *sum += value;
+            *count += 1;
+        }
+    }
+
+    /// Fold a completed cycle into the cycle counters, costs and gauges.
+    pub fn record_cycle(&self, report: &CycleReport, state: &AgentState) {
+        self.inc(&CYCLES, &[]);
+        self.inc_by(&MARKETS_SCANNED, &[], report.markets_scanned as f64);
+        self.inc_by(&EDGES_FOUND, &[], report.edges_found as f64);
+        self.inc_by(&LLM_COST, &[], f64_of(report.cycle_costs.llm_cost));
+        self.inc_by(&DATA_COST, &[], f64_of(report.cycle_costs.data_cost));
+        self.set(&BANKROLL, &[], f64_of(report.bankroll_after));
+        self.set(&DRAWDOWN, &[], f64_of(state.drawdown()));
+    }
+
+    /// Count the cycle's rejected edges by reason: the decision's
+    /// [`rejection_code`](DecisionRecord::rejection_code), or `kelly` when
+    /// sizing found no bet.
+    pub fn record_decisions(&self, decisions: &[DecisionRecord]) {
+        for decision in decisions {
+            let reason = match decision {
+                DecisionRecord::KellyRejected { .. } => "kelly",
+                _ => match decision.rejection_code() {
+                    Some(code) => code,
+                    None => continue,
+                },
+            };
+            self.inc(&BETS_REJECTED, &[("reason", reason)]);
+        }
+    }
+
+    /// Observe the fetch time of every platform scanned.
+    pub fn record_scan(&self, summary: &ScanSummary) {
+        for (platform, secs) in &summary.scan_seconds {
+            self.observe(&SCAN_DURATION, &[("platform", platform)], *secs);
+        }
+    }
+
+    /// Count a batch's fills, failures and unconfirmed orders per platform.
+    pub fn record_execution(&self, report: &ExecutionReport) {
+        for trade in &report.executed {
+            self.inc(&BETS_PLACED, &[("platform", &trade.platform)]);
+        }
+        for failed in &report.failed {
+            self.inc(&BETS_FAILED, &[("platform", &failed.platform), ("code", failed.code.label())]);
+        }
+        for unconfirmed in &report.unconfirmed {
+            self.inc(&BETS_UNCONFIRMED, &[("platform", &unconfirmed.platform)]);
+        }
+    }
+
+    /// Current value of one counter or gauge series, if recorded.
+    pub fn value(&self, metric: &Metric, labels: &[(&str, &str)]) -> Option<f64> {
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        match series.get(metric.name)?.get(&owned(labels))? {
+            Series::Value(v) => Some(*v),
+            Series::Histogram { .. } => None,
+        }
+    }
+
+    /// Render every family in the text exposition format.
+    pub fn render(&self) -> String {
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+        for metric in METRICS {
+            let kind = match metric.kind {
+                Kind::Counter => "counter",
+                Kind::Gauge => "gauge",
+                Kind::Histogram(_) => "histogram",
+            };
+            let _ = writeln!(out, "# HELP {} {}", metric.name, escape_help(metric.help));
+            let _ = writeln!(out, "# TYPE {} {kind}", metric.name);
+            let recorded = series.get(metric.name);
+            if recorded.is_none() && metric.kind == Kind::Counter && metric.labels.is_empty() {
+                let _ = writeln!(out, "{} 0", metric.name);
+            }
+            for (labels, value) in recorded.into_iter().flatten() {
+                match (value, metric.kind) {
+                    (Series::Value(v), _) => {
+                        let _ = writeln!(out, "{}{} {}", metric.name, label_set(labels, None), number(*v));
+                    }
+                    (Series::Histogram { counts, sum, count }, Kind::Histogram(bounds)) => {
+                        let mut cumulative = 0;
+                        for (i, n) in counts.iter().enumerate() {
+                            cumulative += n;
+                            let le = bounds.get(i).map_or_else(|| "+Inf".to_string(), |b| number(*b));
+                            let _ = writeln!(out, "{}_bucket{} {cumulative}", metric.name, label_set(labels, Some(&le)));
+                        }
+                        let _ = writeln!(out, "{}_sum{} {}", metric.name, label_set(labels, None), number(*sum));
+                        let _ = writeln!(out, "{}_count{} {count}", metric.name, label_set(labels, None));
+                    }
+                    (Series::Histogram { .. }, _) => {}
+                }
+            }
+        }
+        out
+    }
+}
+
+fn owned(labels: &[(&str, &str)]) -> Labels {
+    labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+fn f64_of(d: Decimal) -> f64 {
+    d.to_f64().unwrap_or(0.0)
+}
+
+/// `{a="x",le="0.5"}`, or nothing for an unlabelled series.
+fn label_set(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels.iter().map(|(k, v)| format!("{k}=\"{}\"", escape_label(v))).collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    if pairs.is_empty() { String::new() } else { format!("{{{}}}", pairs.join(",")) }
+}
+
+/// Label values escape backslash, double quote and newline.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', r"\\").replace('"', "\\\"").replace('\n', r"\n")
+}
+
+/// HELP text escapes backslash and newline.
+fn escape_help(help: &str) -> String {
+    help.replace('\\', r"\\").replace('\n', r"\n")
+}
+
+/// Sample value in the exposition float syntax.
+fn number(v: f64) -> String {
+    if v.is_nan() {
+        "NaN".into()
+    } else if v.is_infinite() {
+        if v > 0.0 { "+Inf".into() } else { "-Inf".into() }
+    } else {
+        v.to_string()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// LLM latency
+// ---------------------------------------------------------------------------
+
+/// Estimator wrapper observing each call's wall-clock time in
+/// [`LLM_LATENCY`], labelled by model and call (`single`, `batch`,
+/// `critique`). Failed calls are observed too.
+pub struct TimedEstimator {
+    inner: Box<dyn LlmEstimator>,
+    registry: Arc<Registry>,
+}
+
+impl TimedEstimator {
+    pub fn new(inner: Box<dyn LlmEstimator>, registry: Arc<Registry>) -> Self {
+        Self { inner, registry }
+    }
+
+    fn observe(&self, call: &str, started: Instant) {
+        let labels = [("model", self.inner.model_name()), ("call", call)];
+        self.registry.observe(&LLM_LATENCY, &labels, started.elapsed().as_secs_f64());
+    }
+}
+
+#[async_trait]
+impl LlmEstimator for TimedEstimator {
+    async fn estimate_probability(&self, market: &Market, context: &DataContext) -> Result<Estimate> {
+        self.estimate_with(market, context, &CallParams::default()).await
+    }
+
+    async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+        self.batch_estimate_with(markets, &CallParams::default()).await
+    }
+
+    async fn estimate_with(&self, market: &Market, context: &DataContext, params: &CallParams) -> Result<Estimate> {
+        let started = Instant::now();
+        let result = self.inner.estimate_with(market, context, params).await;
+        self.observe("single", started);
+        result
+    }
+
+    async fn batch_estimate_with(
+        &self,
+        markets: &[(Market, DataContext)],
+        params: &CallParams,
+    ) -> Result<Vec<Estimate>> {
+        let started = Instant::now();
+        let result = self.inner.batch_estimate_with(markets, params).await;
+        self.observe("batch", started);
+        result
+    }
+
+    async fn critique(&self, market: &Market, context: &DataContext, initial: &Estimate) -> Result<Estimate> {
+        let started = Instant::now();
+        let result = self.inner.critique(market, context, initial).await;
+        self.observe("critique", started);
+        result
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        self.inner.cost_per_call()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrecorded_families_render_at_zero() {
+        let text = Registry::new().render();
+        assert!(text.contains("# TYPE oracle_cycles_total counter\noracle_cycles_total 0\n"));
+        assert!(text.contains("# TYPE oracle_llm_latency_seconds histogram\n"));
+        assert!(!text.contains("oracle_llm_latency_seconds_count"));
+        // Gauges have no meaningful value before the first cycle.
+        assert!(!text.lines().any(|l| l.starts_with("oracle_bankroll_current")));
+    }
+
+    #[test]
+    fn test_label_values_are_escaped() {
+        let registry = Registry::new();
+        registry.inc(&BETS_FAILED, &[("platform", "we\"ird\\pl\natform"), ("code", "other")]);
+        let text = registry.render();
+        assert!(text.contains(r#"oracle_bets_failed_total{platform="we\"ird\\pl\natform",code="other"} 1"#), "{text}");
+        assert!(!text.contains("pl\natform"));
+    }
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let registry = Registry::new();
+        let labels = [("platform", "kalshi")];
+        for secs in [0.2, 0.7, 0.7, 100.0] {
+            registry.observe(&SCAN_DURATION, &labels, secs);
+        }
+        let text = registry.render();
+        for line in [
+            r#"oracle_scan_duration_seconds_bucket{platform="kalshi",le="0.25"} 1"#,
+            r#"oracle_scan_duration_seconds_bucket{platform="kalshi",le="0.5"} 1"#,
+            r#"oracle_scan_duration_seconds_bucket{platform="kalshi",le="1"} 3"#,
+            r#"oracle_scan_duration_seconds_bucket{platform="kalshi",le="60"} 3"#,
+            r#"oracle_scan_duration_seconds_bucket{platform="kalshi",le="+Inf"} 4"#,
+            r#"oracle_scan_duration_seconds_sum{platform="kalshi"} 101.6"#,
+            r#"oracle_scan_duration_seconds_count{platform="kalshi"} 4"#,
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {line} in\n{text}");
+        }
+    }
+
+    #[test]
+    fn test_counters_accumulate_and_gauges_replace() {
+        let registry = Registry::new();
+        registry.inc_by(&LLM_COST, &[], 0.25);
+        registry.inc_by(&LLM_COST, &[], 0.5);
+        registry.set(&DRAWDOWN, &[], 0.1);
+        registry.set(&DRAWDOWN, &[], 0.05);
+        assert_eq!(registry.value(&LLM_COST, &[]), Some(0.75));
+        assert_eq!(registry.value(&DRAWDOWN, &[]), Some(0.05));
+        // Observations on a non-histogram are ignored.
+        registry.observe(&CYCLES, &[], 3.0);
+        assert_eq!(registry.value(&CYCLES, &[]), None);
+    }
+}
@@ -0,0 +1,729 @@
+//! Rule-based extraction of [`QuestionFacts`] from market questions.
+//!
+//! Parsed once per market by the scanner and shared by everything that
+//! needs more than the raw text (data provider routing, summaries). Passes,
+//! in order, over whitespace tokens:
+//!
+//! 1. Dates — "March 15, 2026", "15 March", "Q2 2026", "2026-03-15",
+//!    "by June", "in 2027". Month- and year-only phrases resolve to the end
+//!    of the period; a missing year is the next occurrence.
+//! 2. Thresholds — a number with a unit ("$100k", "3%", "45°C", "1.5
+//!    billion dollars") or governed by a comparison ("above 6,000", "at
+//!    least 10", "10 or more"). Bare numbers elsewhere ("S&P 500", "the
+//!    2028 election") are not thresholds.
+//! 3. Geography — whole-word, case-sensitive match against [`PLACES`].
+//! 4. Entities — remaining runs of capitalised words ("Federal Reserve",
+//!    "Bank of England"), minus question words and leading place names.
+
+use std::borrow::Cow;
+
+use chrono::{Datelike, NaiveDate, Utc};
+
+use crate::types::{Comparison, Market, QuestionFacts};
+
+// ---------------------------------------------------------------------------
+// Vocabulary
+// ---------------------------------------------------------------------------
+
+/// Canonical place names and the spellings that refer to them. Longer
+/// aliases win over shorter ones they contain ("New York City" vs "New York").
+pub const PLACES: &[(&str, &[&str])] = &[
+    ("United States", &["United States", "USA", "U.S.A.", "U.S.", "US", "America"]),
+    ("United Kingdom", &["United Kingdom", "Great Britain", "Britain", "UK", "U.K."]),
+    ("England", &["England"]),
+    ("European Union", &["European Union", "EU", "E.U."]),
+    ("China", &["China"]),
+    ("Russia", &["Russia"]),
+    ("Ukraine", &["Ukraine"]),
+    ("Japan", &["Japan"]),
+    ("India", &["India"]),
+    ("Germany", &["Germany"]),
+    ("France", &["France"]),
+    ("Italy", &["Italy"]),
+    ("Spain", &["Spain"]),
+    ("Canada", &["Canada"]),
+    ("Mexico", &["Mexico"]),
+    ("Brazil", &["Brazil"]),
+    ("Argentina", &["Argentina"]),
+    ("Venezuela", &["Venezuela"]),
+    ("Australia", &["Australia"]),
+    ("New Zealand", &["New Zealand"]),
+    ("Israel", &["Israel"]),
+    ("Gaza", &["Gaza"]),
+    ("Iran", &["Iran"]),
+    ("Taiwan", &["Taiwan"]),
+    ("South Korea", &["South Korea"]),
+    ("North Korea", &["North Korea"]),
+    ("California", &["California"]),
+    ("Texas", &["Texas"]),
+    ("Florida", &["Florida"]),
+    ("New York", &["New York"]),
+    ("Pennsylvania", &["Pennsylvania"]),
+    ("Arizona", &["Arizona"]),
+    ("Michigan", &["Michigan"]),
+    ("New South Wales", &["New South Wales", "NSW"]),
+    ("Queensland", &["Queensland", "QLD"]),
+    ("Victoria", &["Victoria"]),
+    ("Western Australia", &["Western Australia"]),
+    ("New York City", &["New York City", "NYC", "Manhattan"]),
+    ("Los Angeles", &["Los Angeles", "L.A."]),
+    ("Washington DC", &["Washington, D.C.", "Washington DC", "Washington D.C.", "Washington", "D.C."]),
+    ("Chicago", &["Chicago"]),
+    ("Miami", &["Miami"]),
+    ("Houston", &["Houston"]),
+    ("London", &["London"]),
+    ("Paris", &["Paris"]),
+    ("Berlin", &["Berlin"]),
+    ("Moscow", &["Moscow"]),
+    ("Kyiv", &["Kyiv", "Kiev"]),
+    ("Beijing", &["Beijing"]),
+    ("Hong Kong", &["Hong Kong"]),
+    ("Tokyo", &["Tokyo"]),
+    ("Singapore", &["Singapore"]),
+    ("Dubai", &["Dubai"]),
+    ("Toronto", &["Toronto"]),
+    ("Sydney", &["Sydney"]),
+    ("Melbourne", &["Melbourne"]),
+    ("Brisbane", &["Brisbane"]),
+    ("Perth", &["Perth"]),
+];
+
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+const WEEKDAYS: &[&str] = &["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+/// Words after which a bare month or year is a date ("by June", "in 2027").
+const DATE_PREPOSITIONS: &[&str] = &["in", "by", "before", "until", "till", "through", "during", "of", "after", "end"];
+
+/// Capitalised words that never start an entity.
+const STOP_CAPS: &[&str] = &[
+    "will", "would", "does", "do", "did", "is", "are", "was", "were", "can", "could", "should",
+    "has", "have", "had", "who", "what", "when", "where", "which", "how", "why", "the", "a",
+    "an", "if", "by", "in", "on", "at", "before", "after", "between", "any", "yes", "no",
+];
+
+/// Lowercase connectors allowed inside an entity ("Bank of England").
+const CONNECTORS: &[&str] = &["of", "de", "del", "la", "von", "van"];
+
+/// Single-word comparators and the direction they imply.
+const COMPARATORS: &[(&str, Comparison)] = &[
+    ("above", Comparison::Above),
+    ("over", Comparison::Above),
+    ("exceed", Comparison::Above),
+    ("exceeds", Comparison::Above),
+    ("exceeding", Comparison::Above),
+    ("surpass", Comparison::Above),
+    ("surpasses", Comparison::Above),
+    ("top", Comparison::Above),
+    ("tops", Comparison::Above),
+    ("reach", Comparison::Above),
+    ("reaches", Comparison::Above),
+    ("hit", Comparison::Above),
+    ("hits", Comparison::Above),
+    ("beyond", Comparison::Above),
+    ("below", Comparison::Below),
+    ("under", Comparison::Below),
+    ("beneath", Comparison::Below),
+];
+
+/// Two-word comparators ("more than", "at least").
+const COMPARATOR_PAIRS: &[(&str, &str, Comparison)] = &[
+    ("more", "than", Comparison::Above),
+    ("greater", "than", Comparison::Above),
+    ("higher", "than", Comparison::Above),
+    ("larger", "than", Comparison::Above),
+    ("at", "least", Comparison::Above),
+    ("less", "than", Comparison::Below),
+    ("fewer", "than", Comparison::Below),
+    ("lower", "than", Comparison::Below),
+    ("smaller", "than", Comparison::Below),
+    ("at", "most", Comparison::Below),
+];
+
+/// Unit words after a number, and the unit they stand for.
+const UNIT_WORDS: &[(&str, &str)] = &[
+    ("percent", "%"),
+    ("bps", "bps"),
+    ("degrees", "°"),
+    ("degree", "°"),
+    ("inches", "in"),
+    ("inch", "in"),
+    ("mm", "mm"),
+    ("millimeters", "mm"),
+    ("millimetres", "mm"),
+    ("cm", "cm"),
+    ("km", "km"),
+    ("mph", "mph"),
+    ("dollars", "$"),
+    ("usd", "$"),
+    ("points", "points"),
+    ("pts", "points"),
+    ("seats", "seats"),
+    ("votes", "votes"),
+    ("goals", "goals"),
+    ("runs", "runs"),
+];
+
+// ---------------------------------------------------------------------------
+// Entry points
+// ---------------------------------------------------------------------------
+
+/// Parse `question` relative to today's date (for year inference).
+pub fn parse(question: &str) -> QuestionFacts {
+    parse_at(question, Utc::now().date_naive())
+}
+
+/// The market's attached facts, or a fresh parse when the market did not
+/// come through the scanner.
+pub fn facts(market: &Market) -> Cow<'_, QuestionFacts> {
+    match &market.facts {
+        Some(f) => Cow::Borrowed(f),
+        None => Cow::Owned(parse(&market.question)),
+    }
+}
+
+/// One-line description of the question's terms for provider summaries,
+/// e.g. "above 3%; by 2026-06-30". `None` when nothing was parsed.
+pub fn describe(facts: &QuestionFacts) -> Option<String> {
+    let mut parts = Vec::new();
+    if !facts.numeric_thresholds.is_empty() {
+        let values: Vec<String> = facts.numeric_thresholds.iter().map(|(v, u)| format_quantity(*v, u)).collect();
+        let direction = match facts.comparison {
+            Some(Comparison::Above) => "above ",
+            Some(Comparison::Below) => "below ",
+            Some(Comparison::Between) => "between ",
+            None => "",
+        };
+        let joiner = if facts.comparison == Some(Comparison::Between) { " and " } else { ", " };
+        parts.push(format!("{direction}{}", values.join(joiner)));
+    }
+    if !facts.dates.is_empty() {
+        let dates: Vec<String> = facts.dates.iter().map(|d| d.to_string()).collect();
+        parts.push(format!("by {}", dates.join(", ")));
+    }
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+fn format_quantity(value: f64, unit: &str) -> String {
+    match unit {
+        "" => format!("{value}"),
+        "$" | "€" | "£" => format!("{unit}{value}"),
+        u if u.starts_with('%') || u.starts_with('°') => format!("{value}{u}"),
+        u => format!("{value} {u}"),
+    }
+}
+
+/// Parse `question`, inferring missing years relative to `today`.
+pub fn parse_at(question: &str, today: NaiveDate) -> QuestionFacts {
+    let tokens = tokenize(question);
+    let mut used = vec![false; tokens.len()];
+
+    let dates = extract_dates(&tokens, &mut used, today);
+    let (numeric_thresholds, comparison) = extract_thresholds(&tokens, &mut used);
+    let geography = extract_geography(question);
+    let entities = extract_entities(&tokens, &used);
+
+    QuestionFacts { entities, dates, numeric_thresholds, comparison, geography }
+}
+
+// ---------------------------------------------------------------------------
+// Tokens
+// ---------------------------------------------------------------------------
+
+struct Token {
+    /// Word with surrounding punctuation and possessive "'s" removed.
+    word: String,
+    lower: String,
+    /// Punctuation after this token ends a phrase.
+    brk: bool,
+}
+
+fn tokenize(question: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    for raw in question.split_whitespace() {
+        let start = raw.trim_start_matches(['(', '[', '"', '\'', '“', '‘']);
+        if start.len() < raw.len() {
+            if let Some(prev) = tokens.last_mut() {
+                prev.brk = true;
+            }
+        }
+        let mut word = start.trim_end_matches(['?', ',', '!', ';', ':', ')', ']', '"', '\'', '”', '’']);
+        let mut brk = word.len() < start.len();
+        // A trailing full stop ends a sentence unless the word is an
+        // abbreviation with inner dots ("U.S.").
+        if let Some(stem) = word.strip_suffix('.') {
+            if !stem.contains('.') {
+                word = stem;
+                brk = true;
+            }
+        }
+        let word = word.strip_suffix("'s").or_else(|| word.strip_suffix("’s")).unwrap_or(word);
+        if !word.chars().any(|c| c.is_alphanumeric()) {
+            if let Some(prev) = tokens.last_mut() {
+                prev.brk = true;
+            }
+            continue;
+        }
+        tokens.push(Token { word: word.to_string(), lower: word.to_lowercase(), brk });
+    }
+    tokens
+}
+
+fn is_capitalised(word: &str) -> bool {
+    word.chars().next().is_some_and(|c| c.is_uppercase())
+}
+
+// ---------------------------------------------------------------------------
+// Dates
+// ---------------------------------------------------------------------------
+
+/// Month number for a capitalised month name or abbreviation.
+fn month_of(word: &str) -> Option<u32> {
+    if !is_capitalised(word) || word.len() < 3 {
+        return None;
+    }
+    let lower = word.to_lowercase();
+    MONTHS
+        .iter()
+        .position(|m| *m == lower || (lower.len() <= 4 && m.starts_with(&lower) && lower != "marc"))
+        .map(|i| i as u32 + 1)
+}
+
+fn day_of(word: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"].iter().find_map(|s| word.strip_suffix(s)).unwrap_or(word);
+    if digits.is_empty() || digits.len() > 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+fn year_of(word: &str) -> Option<i32> {
+    if word.len() != 4 || !word.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    word.parse().ok().filter(|y| (1900..=2100).contains(y))
+}
+
+fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    let (y, m) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(y, m, 1)?.pred_opt()
+}
+
+/// `make(year)` for this year, or next year if that is already past.
+fn next_occurrence(today: NaiveDate, make: impl Fn(i32) -> Option<NaiveDate>) -> Option<NaiveDate> {
+    make(today.year()).filter(|d| *d >= today).or_else(|| make(today.year() + 1))
+}
+
+fn extract_dates(tokens: &[Token], used: &mut [bool], today: NaiveDate) -> Vec<NaiveDate> {
+    let word = |i: usize| tokens.get(i).map(|t| t.word.as_str()).unwrap_or("");
+    let after_preposition = |i: usize| i > 0 && DATE_PREPOSITIONS.contains(&tokens[i - 1].lower.as_str());
+
+    let mut dates = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let w = word(i);
+        let found: Option<(NaiveDate, usize)> = if let Ok(d) = NaiveDate::parse_from_str(w, "%Y-%m-%d") {
+            Some((d, 1))
+        } else if let Some(m) = month_of(w) {
+            match (day_of(word(i + 1)), year_of(word(i + 1)), year_of(word(i + 2))) {
+                (Some(d), _, Some(y)) => NaiveDate::from_ymd_opt(y, m, d).map(|date| (date, 3)),
+                (Some(d), _, None) => next_occurrence(today, |y| NaiveDate::from_ymd_opt(y, m, d)).map(|date| (date, 2)),
+                (None, Some(y), _) => last_day_of_month(y, m).map(|date| (date, 2)),
+                (None, None, _) if after_preposition(i) => {
+                    next_occurrence(today, |y| last_day_of_month(y, m)).map(|date| (date, 1))
+                }
+                _ => None,
+            }
+        } else if let (Some(d), Some(m)) = (day_of(w), month_of(word(i + 1))) {
+            match year_of(word(i + 2)) {
+                Some(y) => NaiveDate::from_ymd_opt(y, m, d).map(|date| (date, 3)),
+                None => next_occurrence(today, |y| NaiveDate::from_ymd_opt(y, m, d)).map(|date| (date, 2)),
+            }
+        } else if let (Some(q), Some(y)) = (quarter_of(w), year_of(word(i + 1))) {
+            last_day_of_month(y, q * 3).map(|date| (date, 2))
+        } else if let Some(y) = year_of(w).filter(|_| after_preposition(i)) {
+            NaiveDate::from_ymd_opt(y, 12, 31).map(|date| (date, 1))
+        } else {
+            None
+        };
+        match found {
+            Some((date, len)) => {
+                dates.push(date);
+                used[i..i + len].iter_mut().for_each(|u| *u = true);
+                i += len;
+            }
+            None => i += 1,
+        }
+    }
+    dates
+}
+
+fn quarter_of(word: &str) -> Option<u32> {
+    let q = word.strip_prefix('Q')?.parse().ok()?;
+    (1..=4).contains(&q).then_some(q)
+}
+
+// ---------------------------------------------------------------------------
+// Thresholds
+// ---------------------------------------------------------------------------
+
+/// A number token: value (scaled by any suffix), unit, trailing "+".
+fn quantity(word: &str) -> Option<(f64, String, bool)> {
+    let (word, plus) = match word.strip_suffix('+') {
+        Some(w) => (w, true),
+        None => (word, false),
+    };
+    let (negative, word) = match word.strip_prefix('-') {
+        Some(w) => (true, w),
+        None => (false, word),
+    };
+    let currency = ['$', '€', '£'].into_iter().find(|c| word.starts_with(*c));
+    let rest = currency.map_or(word, |c| &word[c.len_utf8()..]);
+    if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',')).unwrap_or(rest.len());
+    let value: f64 = rest[..end].trim_end_matches(['.', ',']).replace(',', "").parse().ok()?;
+    let (scale, unit) = match &rest[end..] {
+        "" => (1.0, ""),
+        "k" | "K" => (1e3, ""),
+        "M" | "mn" => (1e6, ""),
+        "m" if currency.is_some() => (1e6, ""),
+        "B" | "bn" | "b" => (1e9, ""),
+        "T" | "tn" => (1e12, ""),
+        "%" => (1.0, "%"),
+        "C" | "°C" => (1.0, "°C"),
+        "F" | "°F" => (1.0, "°F"),
+        "°" => (1.0, "°"),
+        "bps" => (1.0, "bps"),
+        s @ ("mm" | "cm" | "km" | "m" | "mph" | "x") => (1.0, s),
+        _ => return None,
+    };
+    let unit = currency.map_or_else(|| unit.to_string(), |c| c.to_string());
+    let value = if negative { -value * scale } else { value * scale };
+    Some((value, unit, plus))
+}
+
+fn scale_word(word: &str) -> Option<f64> {
+    match word {
+        "thousand" => Some(1e3),
+        "million" => Some(1e6),
+        "billion" => Some(1e9),
+        "trillion" => Some(1e12),
+        _ => None,
+    }
+}
+
+/// Comparator governing the number at `i`, from the words before it.
+fn comparator_before(tokens: &[Token], i: usize) -> Option<Comparison> {
+    let lower = |j: usize| tokens[j].lower.as_str();
+    if i >= 2 {
+        if let Some((_, _, dir)) = COMPARATOR_PAIRS.iter().find(|(a, b, _)| lower(i - 2) == *a && lower(i - 1) == *b) {
+            // "no more than" caps rather than floors.
+            let negated = i >= 3 && lower(i - 3) == "no";
+            return Some(match (negated, dir) {
+                (true, Comparison::Above) => Comparison::Below,
+                _ => *dir,
+            });
+        }
+    }
+    if i >= 1 {
+        if lower(i - 1) == "between" {
+            return Some(Comparison::Between);
+        }
+        return COMPARATORS.iter().find(|(w, _)| lower(i - 1) == *w).map(|(_, dir)| *dir);
+    }
+    None
+}
+
+fn extract_thresholds(tokens: &[Token], used: &mut [bool]) -> (Vec<(f64, String)>, Option<Comparison>) {
+    let mut thresholds = Vec::new();
+    let mut comparison = None;
+    let mut between_pending = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let Some((mut value, mut unit, plus)) = (!used[i]).then(|| quantity(&tokens[i].word)).flatten() else {
+            i += 1;
+            continue;
+        };
+        let lower = |j: usize| tokens.get(j).map(|t| t.lower.as_str()).unwrap_or("");
+        let mut end = i + 1;
+        if let Some(scale) = scale_word(lower(end)) {
+            value *= scale;
+            end += 1;
+        }
+        if unit.is_empty() || unit == "°" {
+            if lower(end) == "per" && lower(end + 1) == "cent" {
+                unit = "%".to_string();
+                end += 2;
+            } else if lower(end) == "percentage" && lower(end + 1) == "points" {
+                unit = "pp".to_string();
+                end += 2;
+            } else if lower(end) == "basis" && lower(end + 1) == "points" {
+                unit = "bps".to_string();
+                end += 2;
+            } else if let Some((_, u)) = UNIT_WORDS.iter().find(|(w, _)| lower(end) == *w) {
+                unit = u.to_string();
+                end += 1;
+            }
+            if unit == "°" {
+                match lower(end) {
+                    "celsius" | "c" => { unit = "°C".to_string(); end += 1; }
+                    "fahrenheit" | "f" => { unit = "°F".to_string(); end += 1; }
+                    _ => {}
+                }
+            }
+        }
+        let mut direction = if between_pending && i >= 1 && lower(i - 1) == "and" {
+            between_pending = false;
+            Some(Comparison::Between)
+        } else {
+            comparator_before(tokens, i)
+        };
+        if lower(end) == "or" {
+            let open = match lower(end + 1) {
+                "more" | "higher" | "above" | "greater" => Some(Comparison::Above),
+                "less" | "fewer" | "lower" | "below" => Some(Comparison::Below),
+                _ => None,
+            };
+            if open.is_some() {
+                direction = open;
+                end += 2;
+                // "3 or more goals"
+                if let Some((_, u)) = UNIT_WORDS.iter().find(|(w, _)| unit.is_empty() && lower(end) == *w) {
+                    unit = u.to_string();
+                    end += 1;
+                }
+            }
+        }
+        if plus && direction.is_none() {
+            direction = Some(Comparison::Above);
+        }
+        if direction.is_none() && unit.is_empty() {
+            i += 1;
+            continue;
+        }
+        if direction == Some(Comparison::Between) {
+            between_pending = !between_pending || lower(i - 1) == "between";
+        }
+        thresholds.push((value, unit));
+        comparison = comparison.or(direction);
+        used[i..end].iter_mut().for_each(|u| *u = true);
+        i = end;
+    }
+    (thresholds, comparison)
+}
+
+// ---------------------------------------------------------------------------
+// Geography and entities
+// ---------------------------------------------------------------------------
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+/// Canonical places named in `question`, in order of first mention.
+fn extract_geography(question: &str) -> Vec<String> {
+    let mut aliases: Vec<(&str, &str)> = PLACES
+        .iter()
+        .flat_map(|(canonical, names)| names.iter().map(move |n| (*n, *canonical)))
+        .collect();
+    aliases.sort_by_key(|(alias, _)| std::cmp::Reverse(alias.len()));
+
+    let mut taken: Vec<(usize, usize)> = Vec::new();
+    let mut found: Vec<(usize, &str)> = Vec::new();
+    for (alias, canonical) in aliases {
+        for (start, _) in question.match_indices(alias) {
+            let end = start + alias.len();
+            let boundary_before = !question[..start].chars().next_back().is_some_and(is_word_char);
+            let boundary_after = !question[end..].chars().next().is_some_and(is_word_char);
+            let overlaps = taken.iter().any(|&(s, e)| start < e && s < end);
+            if boundary_before && boundary_after && !overlaps {
+                taken.push((start, end));
+                found.push((start, canonical));
+            }
+        }
+    }
+    found.sort_by_key(|(start, _)| *start);
+    let mut places: Vec<String> = Vec::new();
+    for (_, canonical) in found {
+        if !places.iter().any(|p| p == canonical) {
+            places.push(canonical.to_string());
+        }
+    }
+    places
+}
+
+fn is_place_alias(phrase: &str) -> bool {
+    PLACES.iter().any(|(_, names)| names.contains(&phrase))
+}
+
+fn extract_entities(tokens: &[Token], used: &[bool]) -> Vec<String> {
+    let starts_phrase = |i: usize| !used[i] && is_capitalised(&tokens[i].word) && !WEEKDAYS.contains(&tokens[i].lower.as_str());
+    let continues_phrase = |i: usize| {
+        !used[i] && (starts_phrase(i) || tokens[i].word.starts_with(|c: char| c.is_ascii_digit()))
+    };
+
+    let mut entities: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !starts_phrase(i) {
+            i += 1;
+            continue;
+        }
+        let mut phrase = vec![tokens[i].word.as_str()];
+        let mut j = i;
+        while !tokens[j].brk && j + 1 < tokens.len() {
+            if continues_phrase(j + 1) {
+                j += 1;
+            } else if CONNECTORS.contains(&tokens[j + 1].lower.as_str())
+                && !tokens[j + 1].brk
+                && j + 2 < tokens.len()
+                && starts_phrase(j + 2)
+            {
+                phrase.push(tokens[j + 1].word.as_str());
+                j += 2;
+            } else {
+                break;
+            }
+            phrase.push(tokens[j].word.as_str());
+        }
+        i = j + 1;
+
+        let skip = phrase.iter().take_while(|w| STOP_CAPS.contains(&w.to_lowercase().as_str())).count();
+        let mut words = &phrase[skip..];
+        if is_place_alias(&words.join(" ")) {
+            continue;
+        }
+        // A leading place is already in geography: "US CPI" → "CPI".
+        if let Some(n) = (1..words.len()).rev().find(|&n| is_place_alias(&words[..n].join(" "))) {
+            words = &words[n..];
+        }
+        let phrase = words.join(" ");
+        if phrase.chars().count() < 2 || entities.contains(&phrase) {
+            continue;
+        }
+        entities.push(phrase);
+    }
+    entities
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Labelled corpus: (question, entities, dates, thresholds, comparison,
+    /// geography), parsed as of 2026-01-15. Shared infrastructure — extend
+    /// this rather than loosening a case.
+    #[allow(clippy::type_complexity)]
+    fn corpus() -> Vec<(&'static str, Vec<&'static str>, Vec<NaiveDate>, Vec<(f64, &'static str)>, Option<Comparison>, Vec<&'static str>)> {
+        use Comparison::*;
+        vec![
+            ("Will US CPI exceed 3% in Q2 2026?", vec!["CPI"], vec![ymd(2026, 6, 30)], vec![(3.0, "%")], Some(Above), vec!["United States"]),
+            ("Will Bitcoin reach $100k by March 31, 2026?", vec!["Bitcoin"], vec![ymd(2026, 3, 31)], vec![(100_000.0, "$")], Some(Above), vec![]),
+            ("Will Sydney get more than 100mm rainfall?", vec![], vec![], vec![(100.0, "mm")], Some(Above), vec!["Sydney"]),
+            ("California heat wave above 45C in 2026?", vec![], vec![ymd(2026, 12, 31)], vec![(45.0, "°C")], Some(Above), vec!["California"]),
+            ("Will there be a major hurricane in the US this year?", vec![], vec![], vec![], None, vec!["United States"]),
+            ("Will it rain on Mars?", vec!["Mars"], vec![], vec![], None, vec![]),
+            ("Will Trump win the 2028 presidential election?", vec!["Trump"], vec![], vec![], None, vec![]),
+            ("Will OpenAI release GPT-5 before July 2026?", vec!["OpenAI", "GPT-5"], vec![ymd(2026, 7, 31)], vec![], None, vec![]),
+            ("Will the Fed cut interest rates in 2026?", vec!["Fed"], vec![ymd(2026, 12, 31)], vec![], None, vec![]),
+            ("Will the S&P 500 close above 6,000 on December 31, 2026?", vec!["S&P 500"], vec![ymd(2026, 12, 31)], vec![(6000.0, "")], Some(Above), vec![]),
+            ("Will unemployment in the U.S. fall below 4% by June?", vec![], vec![ymd(2026, 6, 30)], vec![(4.0, "%")], Some(Below), vec!["United States"]),
+            ("Will the Bank of England raise rates before 1 March 2026?", vec!["Bank of England"], vec![ymd(2026, 3, 1)], vec![], None, vec!["England"]),
+            ("Will Elon Musk tweet more than 100 times on Feb 3?", vec!["Elon Musk"], vec![ymd(2026, 2, 3)], vec![(100.0, "")], Some(Above), vec![]),
+            ("Will Tesla deliver at least 500,000 vehicles in Q1 2026?", vec!["Tesla"], vec![ymd(2026, 3, 31)], vec![(500_000.0, "")], Some(Above), vec![]),
+            ("Will NVIDIA market cap exceed $5 trillion by end of 2026?", vec!["NVIDIA"], vec![ymd(2026, 12, 31)], vec![(5e12, "$")], Some(Above), vec![]),
+            ("Will the temperature in New York City hit 100°F this summer?", vec![], vec![], vec![(100.0, "°F")], Some(Above), vec!["New York City"]),
+            ("Will Russia and Ukraine sign a ceasefire by 2026-06-30?", vec![], vec![ymd(2026, 6, 30)], vec![], None, vec!["Russia", "Ukraine"]),
+            ("Will China's GDP growth be between 4% and 5% in 2026?", vec!["GDP"], vec![ymd(2026, 12, 31)], vec![(4.0, "%"), (5.0, "%")], Some(Between), vec!["China"]),
+            ("Will the Lakers win the NBA Finals?", vec!["Lakers", "NBA Finals"], vec![], vec![], None, vec![]),
+            ("Will Taylor Swift announce a new album before September 2026?", vec!["Taylor Swift"], vec![ymd(2026, 9, 30)], vec![], None, vec![]),
+            ("Will gas prices fall under $3 per gallon?", vec![], vec![], vec![(3.0, "$")], Some(Below), vec![]),
+            ("Will Apple stock close above $250 on March 15?", vec!["Apple"], vec![ymd(2026, 3, 15)], vec![(250.0, "$")], Some(Above), vec![]),
+            ("Will Melbourne record a day above 40 degrees Celsius in February?", vec![], vec![ymd(2026, 2, 28)], vec![(40.0, "°C")], Some(Above), vec!["Melbourne"]),
+            ("Will the Federal Reserve cut rates by 50 basis points in March 2026?", vec!["Federal Reserve"], vec![ymd(2026, 3, 31)], vec![(50.0, "bps")], None, vec![]),
+            ("Will the EU fine Google more than €1B in 2026?", vec!["Google"], vec![ymd(2026, 12, 31)], vec![(1e9, "€")], Some(Above), vec!["European Union"]),
+            ("Will Manchester United finish in the top 4 of the Premier League?", vec!["Manchester United", "Premier League"], vec![], vec![(4.0, "")], Some(Above), vec![]),
+            ("Will Israel and Iran exchange strikes before April?", vec![], vec![ymd(2026, 4, 30)], vec![], None, vec!["Israel", "Iran"]),
+            ("Will the UK unemployment rate be 5 percent or higher in 2026?", vec![], vec![ymd(2026, 12, 31)], vec![(5.0, "%")], Some(Above), vec!["United Kingdom"]),
+            ("Will Democrats win at least 218 seats in the House?", vec!["Democrats", "House"], vec![], vec![(218.0, "seats")], Some(Above), vec![]),
+            ("Will Brisbane get 200 mm of rain in January 2026?", vec![], vec![ymd(2026, 1, 31)], vec![(200.0, "mm")], None, vec!["Brisbane"]),
+            ("Will the Chiefs win Super Bowl LXI?", vec!["Chiefs", "Super Bowl LXI"], vec![], vec![], None, vec![]),
+            ("Will Ethereum trade below $2,000 at any point in 2026?", vec!["Ethereum"], vec![ymd(2026, 12, 31)], vec![(2000.0, "$")], Some(Below), vec![]),
+            ("Will Tokyo see snow on 25 December 2026?", vec![], vec![ymd(2026, 12, 25)], vec![], None, vec!["Tokyo"]),
+            ("Will Netflix have 300M+ subscribers by Q4 2026?", vec!["Netflix"], vec![ymd(2026, 12, 31)], vec![(3e8, "")], Some(Above), vec![]),
+            ("Will Houston see wind gusts over 80 mph during hurricane season?", vec![], vec![], vec![(80.0, "mph")], Some(Above), vec!["Houston"]),
+            ("Will Microsoft acquire a company for $10 billion or more in 2026?", vec!["Microsoft"], vec![ymd(2026, 12, 31)], vec![(1e10, "$")], Some(Above), vec![]),
+            ("Will Novak Djokovic win Wimbledon in 2026?", vec!["Novak Djokovic", "Wimbledon"], vec![ymd(2026, 12, 31)], vec![], None, vec![]),
+            ("Will the 10-year Treasury yield exceed 5% by May 2026?", vec!["Treasury"], vec![ymd(2026, 5, 31)], vec![(5.0, "%")], Some(Above), vec![]),
+            ("Will Perth have fewer than 10 days of rain in March?", vec![], vec![ymd(2026, 3, 31)], vec![(10.0, "")], Some(Below), vec!["Perth"]),
+            ("Will the Prime Minister of Japan resign before 2027?", vec!["Prime Minister of Japan"], vec![ymd(2027, 12, 31)], vec![], None, vec!["Japan"]),
+            ("Will inflation in Australia drop below 2.5% in Q3 2026?", vec![], vec![ymd(2026, 9, 30)], vec![(2.5, "%")], Some(Below), vec!["Australia"]),
+            ("Will a magnitude 7+ earthquake hit California in 2026?", vec![], vec![ymd(2026, 12, 31)], vec![(7.0, "")], Some(Above), vec!["California"]),
+            ("Will SpaceX launch Starship to orbit before June 1?", vec!["SpaceX", "Starship"], vec![ymd(2026, 6, 1)], vec![], None, vec![]),
+            ("Will Real Madrid score 3 or more goals against Barcelona?", vec!["Real Madrid", "Barcelona"], vec![], vec![(3.0, "goals")], Some(Above), vec![]),
+            ("Will the population of India exceed 1.5 billion by 2030?", vec![], vec![ymd(2030, 12, 31)], vec![(1.5e9, "")], Some(Above), vec!["India"]),
+            ("Will Amazon's quarterly revenue top $200B in Q4 2026?", vec!["Amazon"], vec![ymd(2026, 12, 31)], vec![(2e11, "$")], Some(Above), vec![]),
+            ("Will the Dow Jones close at most 40,000 on Friday?", vec!["Dow Jones"], vec![], vec![(40_000.0, "")], Some(Below), vec![]),
+            ("Will London have a white Christmas in 2026?", vec!["Christmas"], vec![ymd(2026, 12, 31)], vec![], None, vec!["London"]),
+            ("Will Theresa May return to the cabinet?", vec!["Theresa May"], vec![], vec![], None, vec![]),
+            ("Will Washington, D.C. get more than 5 inches of snow in January?", vec![], vec![ymd(2026, 1, 31)], vec![(5.0, "in")], Some(Above), vec!["Washington DC"]),
+        ]
+    }
+
+    #[test]
+    fn test_corpus() {
+        let today = ymd(2026, 1, 15);
+        let cases = corpus();
+        assert!(cases.len() >= 50, "corpus has {} questions", cases.len());
+        for (question, entities, dates, thresholds, comparison, geography) in cases {
+            let facts = parse_at(question, today);
+            let expected = QuestionFacts {
+                entities: entities.iter().map(|s| s.to_string()).collect(),
+                dates,
+                numeric_thresholds: thresholds.iter().map(|(v, u)| (*v, u.to_string())).collect(),
+                comparison,
+                geography: geography.iter().map(|s| s.to_string()).collect(),
+            };
+            assert_eq!(facts, expected, "question: {question}");
+        }
+    }
+
+    #[test]
+    fn test_missing_year_rolls_forward() {
+        let today = ymd(2026, 10, 1);
+        assert_eq!(parse_at("Will it snow by March 3?", today).dates, vec![ymd(2027, 3, 3)]);
+        assert_eq!(parse_at("Will it snow by December 3?", today).dates, vec![ymd(2026, 12, 3)]);
+        assert_eq!(parse_at("Will it snow in May?", today).dates, vec![ymd(2027, 5, 31)]);
+    }
+
+    #[test]
+    fn test_describe() {
+        let today = ymd(2026, 1, 15);
+        let facts = parse_at("Will US CPI exceed 3% in Q2 2026?", today);
+        assert_eq!(describe(&facts).as_deref(), Some("above 3%; by 2026-06-30"));
+        let facts = parse_at("Will China's GDP growth be between 4% and 5%?", today);
+        assert_eq!(describe(&facts).as_deref(), Some("between 4% and 5%"));
+        assert_eq!(describe(&parse_at("Will it rain on Mars?", today)), None);
+    }
+
+    #[test]
+    fn test_facts_prefers_attached() {
+        let mut market = Market::sample();
+        assert_eq!(facts(&market).numeric_thresholds, vec![(3.0, "%".to_string())]);
+        market.facts = Some(QuestionFacts { entities: vec!["Attached".into()], ..Default::default() });
+        assert_eq!(facts(&market).entities, vec!["Attached"]);
+    }
+}
@@ -0,0 +1,383 @@
+//! Lifecycle archive for finished markets.
+//!
+//! Once a tracked market resolves — or has gone unresolved for
+//! `expire_after_days` after we last saw it — its scattered hot data (the
+//! per-cycle `decisions` rows with their estimates, and its bet receipts)
+//! is consolidated into one JSON document under `{dir}/{market_key}.json`.
+//! The document is read back and checked before the hot rows are deleted,
+//! so a failed write never loses data.
+//!
+//! `{dir}/index.json` maps `platform:market_id` to archive file names; it
+//! is updated on every archive and can be regenerated from the directory
+//! with `oracle archive --rebuild-index`. Both documents are versioned
+//! (see [`super::migrations`]).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::metrics::{DecisionRow, MetricsStore};
+use super::migrations::{self, Artifact, NewerSchema};
+use crate::types::TradeReceipt;
+
+/// Version of the lifecycle document layout.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Index file name inside the archive directory.
+const INDEX_FILE: &str = "index.json";
+
+/// On-disk layout of the index.
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexFile {
+    schema_version: u32,
+    markets: BTreeMap<String, String>,
+}
+
+/// Why a market was archived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveReason {
+    Resolved,
+    /// Never resolved; archived after going unseen for the expiry period.
+    Expired,
+}
+
+/// Everything recorded about one market, from first estimate to outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketLifecycle {
+    pub version: u32,
+    pub platform: String,
+    pub market_id: String,
+    /// Set once archived; `None` for a lifecycle assembled from hot data.
+    pub reason: Option<ArchiveReason>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub resolved_yes: Option<bool>,
+    /// Per-cycle estimates and strategy decisions, oldest first.
+    pub decisions: Vec<DecisionRow>,
+    /// Bets placed on the market.
+    pub receipts: Vec<TradeReceipt>,
+}
+
+impl MarketLifecycle {
+    /// Gather a market's hot data. `receipts` are its bets from agent state.
+    pub async fn assemble(
+        store: &MetricsStore,
+        platform: &str,
+        market_id: &str,
+        receipts: Vec<TradeReceipt>,
+    ) -> Result<Self> {
+        let decisions = store.market_decisions(platform, market_id).await?;
+        let resolved_yes = decisions.iter().rev().find_map(|d| d.resolved_yes);
+        Ok(Self {
+            version: ARCHIVE_VERSION,
+            platform: platform.to_string(),
+            market_id: market_id.to_string(),
+            reason: None,
+            archived_at: None,
+            resolved_yes,
+            decisions,
+            receipts,
+        })
+    }
+}
+
+/// File-name-safe key for a market: `{platform}_{market_id}` with anything
+/// outside `[A-Za-z0-9._-]` replaced by `-`.
+pub fn market_key(platform: &str, market_id: &str) -> String {
+    format!("{platform}_{market_id}")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '-' })
+        .collect()
+}
+
+fn index_key(platform: &str, market_id: &str) -> String {
+    format!("{platform}:{market_id}")
+}
+
+/// The on-disk archive directory.
+#[derive(Debug, Clone)]
+pub struct Archive {
+    dir: PathBuf,
+}
+
+impl Archive {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Archive a market and prune its hot rows. Returns the archive path.
+    pub async fn archive(
+        &self,
+        store: &MetricsStore,
+        platform: &str,
+        market_id: &str,
+        reason: ArchiveReason,
+        receipts: Vec<TradeReceipt>,
+    ) -> Result<PathBuf> {
+        let mut lifecycle = MarketLifecycle::assemble(store, platform, market_id, receipts).await?;
+        lifecycle.reason = Some(reason);
+        lifecycle.archived_at = Some(Utc::now());
+        let path = self.write(&lifecycle)?;
+
+        let pruned = store.delete_market_decisions(platform, market_id).await?;
+        info!(
+            platform,
+            market_id,
+            reason = ?reason,
+            decisions = pruned,
+            receipts = lifecycle.receipts.len(),
+            path = %path.display(),
+            "Market archived"
+        );
+        Ok(path)
+    }
+
+    /// Write `lifecycle` atomically, verify it parses back identically and
+    /// record it in the index.
+    fn write(&self, lifecycle: &MarketLifecycle) -> Result<PathBuf> {
+        let mut index = self.load_index()?;
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create archive dir {}", self.dir.display()))?;
+        let name = format!("{}.json", market_key(&lifecycle.platform, &lifecycle.market_id));
+        let path = self.dir.join(&name);
+        let tmp = self.dir.join(format!("{name}.tmp"));
+        std::fs::write(&tmp, serde_json::to_vec(lifecycle)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("Failed to move archive into {}", path.display()))?;
+
+        let back = Self::read_file(&path)?;
+        anyhow::ensure!(&back == lifecycle, "Archive {} did not read back identically", path.display());
+
+        index.insert(index_key(&lifecycle.platform, &lifecycle.market_id), name);
+        self.save_index(&index)?;
+        Ok(path)
+    }
+
+    /// An archived market's lifecycle, if it has been archived.
+    pub fn read(&self, platform: &str, market_id: &str) -> Result<Option<MarketLifecycle>> {
+        let name = self
+            .load_index()?
+            .remove(&index_key(platform, market_id))
+            .unwrap_or_else(|| format!("{}.json", market_key(platform, market_id)));
+        let path = self.dir.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::read_file(&path).map(Some)
+    }
+
+    fn read_file(path: &Path) -> Result<MarketLifecycle> {
+        let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let doc = serde_json::from_slice(&bytes).with_context(|| format!("Failed to parse archive {}", path.display()))?;
+        let doc = migrations::upgrade(Artifact::Lifecycle, doc, &path.display().to_string())?;
+        serde_json::from_value(doc).with_context(|| format!("Failed to parse archive {}", path.display()))
+    }
+
+    /// The index, upgraded to the current layout. A missing or corrupt
+    /// index reads as empty (it can be rebuilt); one from a newer binary
+    /// is refused so it is never overwritten with less.
+    fn load_index(&self) -> Result<BTreeMap<String, String>> {
+        let path = self.dir.join(INDEX_FILE);
+        let Some(doc) = std::fs::read(&path).ok().and_then(|b| serde_json::from_slice(&b).ok()) else {
+            return Ok(BTreeMap::new());
+        };
+        match migrations::upgrade(Artifact::ArchiveIndex, doc, &path.display().to_string()) {
+            Ok(doc) => Ok(serde_json::from_value::<IndexFile>(doc).map(|f| f.markets).unwrap_or_default()),
+            Err(e) if e.is::<NewerSchema>() => Err(e),
+            Err(e) => {
+                warn!(error = %e, "Archive index unreadable — treating as empty");
+                Ok(BTreeMap::new())
+            }
+        }
+    }
+
+    fn save_index(&self, index: &BTreeMap<String, String>) -> Result<()> {
+        let path = self.dir.join(INDEX_FILE);
+        let file = IndexFile { schema_version: migrations::INDEX_VERSION, markets: index.clone() };
+        std::fs::write(&path, serde_json::to_vec_pretty(&file)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Regenerate the index from the archive files. Unreadable files are
+    /// skipped with a warning. Returns the number of markets indexed.
+    pub fn rebuild_index(&self) -> Result<usize> {
+        // Refuses an index from a newer binary.
+        self.load_index()?;
+        let mut index = BTreeMap::new();
+        let entries = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read archive dir {}", self.dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                continue;
+            };
+            if !name.ends_with(".json") || name == INDEX_FILE {
+                continue;
+            }
+            match Self::read_file(&path) {
+                Ok(l) => {
+                    index.insert(index_key(&l.platform, &l.market_id), name);
+                }
+                Err(e) => warn!(error = %e, "Skipping unreadable archive file"),
+            }
+        }
+        self.save_index(&index)?;
+        Ok(index.len())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Side;
+    use chrono::Duration;
+    use futures::TryStreamExt;
+    use rust_decimal_macros::dec;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("oracle_archive_{name}_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn row(market_id: &str, cycle: u64, decision: &str, resolved_yes: Option<bool>) -> DecisionRow {
+        DecisionRow {
+            timestamp: Utc::now() - Duration::hours(10 - cycle as i64),
+            cycle,
+            platform: "manifold".into(),
+            market_id: market_id.into(),
+            category: "weather".into(),
+            price_yes: 0.40,
+            metaculus_prob: Some(0.5),
+            manifold_prob: None,
+            estimate: 0.55 + cycle as f64 / 100.0,
+            confidence: 0.8,
+            edge: (decision != "no_edge").then_some(0.15),
+            side: (decision != "no_edge").then(|| "YES".to_string()),
+            decision: decision.into(),
+            bet_fraction: (decision == "selected").then_some(0.02),
+            resolved_yes,
+            tags: Vec::new(),
+        }
+    }
+
+    async fn seeded_store() -> MetricsStore {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        store
+            .record_decisions(&[
+                row("m1", 1, "no_edge", None),
+                row("m2", 1, "no_edge", None),
+                row("m1", 2, "selected", None),
+                row("m1", 3, "risk_rejected", None),
+            ])
+            .await
+            .unwrap();
+        store.record_resolution("manifold", "m1", true).await.unwrap();
+        store
+    }
+
+    fn receipt(market_id: &str) -> TradeReceipt {
+        let mut r = TradeReceipt::dry_run(market_id, dec!(25), "Mana");
+        r.side = Side::Yes;
+        r
+    }
+
+    #[tokio::test]
+    async fn test_archive_assembles_full_lifecycle_and_prunes() {
+        let store = seeded_store().await;
+        let archive = Archive::new(temp_dir("assemble"));
+
+        let path = archive
+            .archive(&store, "manifold", "m1", ArchiveReason::Resolved, vec![receipt("m1")])
+            .await
+            .unwrap();
+        assert!(path.ends_with("manifold_m1.json"));
+
+        let l = archive.read("manifold", "m1").unwrap().unwrap();
+        assert_eq!(l.version, ARCHIVE_VERSION);
+        assert_eq!((l.reason, l.resolved_yes), (Some(ArchiveReason::Resolved), Some(true)));
+        assert!(l.archived_at.is_some());
+        let cycles: Vec<_> = l.decisions.iter().map(|d| (d.cycle, d.decision.as_str())).collect();
+        assert_eq!(cycles, [(1, "no_edge"), (2, "selected"), (3, "risk_rejected")]);
+        assert!(l.decisions.iter().all(|d| d.market_id == "m1" && d.resolved_yes == Some(true)));
+        assert_eq!(l.receipts.len(), 1);
+        assert_eq!(l.receipts[0].amount, dec!(25));
+
+        // Hot rows for m1 are gone; other markets are untouched.
+        assert!(store.market_decisions("manifold", "m1").await.unwrap().is_empty());
+        let remaining: Vec<DecisionRow> = store.decisions().try_collect().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].market_id, "m2");
+
+        assert!(archive.read("manifold", "m2").unwrap().is_none());
+        std::fs::remove_dir_all(archive.dir()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_unresolved_markets_expire() {
+        let store = seeded_store().await;
+        // Only m2 is unresolved; its last decision is 9 hours old.
+        let stale = store.stale_markets(Utc::now() - Duration::hours(1)).await.unwrap();
+        assert_eq!(stale, vec![("manifold".to_string(), "m2".to_string())]);
+        assert!(store.stale_markets(Utc::now() - Duration::days(1)).await.unwrap().is_empty());
+
+        let archive = Archive::new(temp_dir("expire"));
+        archive.archive(&store, "manifold", "m2", ArchiveReason::Expired, Vec::new()).await.unwrap();
+        let l = archive.read("manifold", "m2").unwrap().unwrap();
+        assert_eq!((l.reason, l.resolved_yes, l.decisions.len()), (Some(ArchiveReason::Expired), None, 1));
+        std::fs::remove_dir_all(archive.dir()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_index_scans_directory() {
+        let store = seeded_store().await;
+        let archive = Archive::new(temp_dir("index"));
+        archive.archive(&store, "manifold", "m1", ArchiveReason::Resolved, Vec::new()).await.unwrap();
+        archive.archive(&store, "manifold", "m2", ArchiveReason::Expired, Vec::new()).await.unwrap();
+        std::fs::write(archive.dir().join("junk.json"), "not json").unwrap();
+
+        std::fs::remove_file(archive.dir().join(INDEX_FILE)).unwrap();
+        assert_eq!(archive.rebuild_index().unwrap(), 2);
+        let index = archive.load_index().unwrap();
+        assert_eq!(index["manifold:m1"], "manifold_m1.json");
+        assert_eq!(index["manifold:m2"], "manifold_m2.json");
+        assert!(archive.read("manifold", "m1").unwrap().is_some());
+        std::fs::remove_dir_all(archive.dir()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_newer_index_blocks_archiving() {
+        let store = seeded_store().await;
+        let archive = Archive::new(temp_dir("newer"));
+        let newer = serde_json::json!({ "schema_version": migrations::INDEX_VERSION + 1, "markets": {}, "shards": 4 });
+        std::fs::write(archive.dir().join(INDEX_FILE), newer.to_string()).unwrap();
+
+        let err = archive.archive(&store, "manifold", "m1", ArchiveReason::Resolved, Vec::new()).await.unwrap_err();
+        assert!(err.is::<NewerSchema>());
+        // Nothing pruned, newer index untouched.
+        assert_eq!(store.market_decisions("manifold", "m1").await.unwrap().len(), 3);
+        let on_disk: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(archive.dir().join(INDEX_FILE)).unwrap()).unwrap();
+        assert_eq!(on_disk, newer);
+        assert!(archive.rebuild_index().is_err());
+        std::fs::remove_dir_all(archive.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_market_key_is_file_safe() {
+        assert_eq!(market_key("betfair", "1.234567"), "betfair_1.234567");
+        assert_eq!(market_key("polymarket", "0xab/../cd"), "polymarket_0xab-..-cd");
+    }
+}
@@ -0,0 +1,799 @@
+//! Pluggable persistence behind the [`Storage`] trait.
+//!
+//! Two backends implement it: [`JsonStorage`], the original state file and
+//! JSON-lines histories, and [`SqliteStorage`](super::sqlite::SqliteStorage),
+//! one database the dashboard can query by time range and category.
+//! `[storage] backend` picks one at startup ([`open`]); the first SQLite
+//! open of a deployment imports the JSON files so nothing is lost by
+//! switching.
+//!
+//! Besides agent state and cycle summaries, a backend keeps every executed
+//! trade (settled with its realised P&L when it resolves), every strategy
+//! decision by cycle and the balances after each cycle, which the
+//! dashboard chart reads [`downsample`]d.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::sqlite::SqliteStorage;
+use crate::config::{StorageBackend, StorageConfig};
+use crate::strategy::DecisionRecord;
+use crate::types::{AgentState, CycleReport, MarketCategory, TradeReceipt};
+
+/// Default trade ledger path of the JSON backend.
+pub const DEFAULT_TRADES_FILE: &str = "oracle_trades.jsonl";
+
+/// Default per-cycle decision log of the JSON backend (the rotating
+/// [`journal`](super::journal) is kept separately).
+pub const DEFAULT_CYCLE_DECISIONS_FILE: &str = "oracle_cycle_decisions.jsonl";
+
+/// Decisions kept by the JSON backend; older lines are dropped on append.
+pub const MAX_DECISION_HISTORY: usize = 50_000;
+
+/// Default balance series of the JSON backend.
+pub const DEFAULT_BALANCE_HISTORY_FILE: &str = "oracle_balance_history.jsonl";
+
+/// Balance samples kept by the JSON backend, about a year of 5-minute
+/// cycles.
+pub const MAX_BALANCE_HISTORY: usize = 100_000;
+
+/// An executed trade and, once its market resolved, the realised P&L.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeRecord {
+    #[serde(flatten)]
+    pub receipt: TradeReceipt,
+    /// Realised P&L in the trade's currency; `None` while open.
+    pub pnl: Option<Decimal>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+/// Filter for [`Storage::query_trades`]. Every field is optional.
+#[derive(Debug, Clone, Default)]
+pub struct TradeQuery {
+    /// Placed at or after.
+    pub from: Option<DateTime<Utc>>,
+    /// Placed before.
+    pub to: Option<DateTime<Utc>>,
+    pub platform: Option<String>,
+    /// Only settled (`true`) or only open (`false`) trades.
+    pub settled: Option<bool>,
+}
+
+impl TradeQuery {
+    pub fn matches(&self, trade: &TradeRecord) -> bool {
+        let at = trade.receipt.timestamp;
+        self.from.is_none_or(|from| at >= from)
+            && self.to.is_none_or(|to| at < to)
+            && self.platform.as_ref().is_none_or(|p| *p == trade.receipt.platform)
+            && self.settled.is_none_or(|settled| trade.pnl.is_some() == settled)
+    }
+}
+
+/// Realised results of settled trades in one category and currency.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryPnl {
+    pub category: String,
+    pub currency: String,
+    pub trades: u64,
+    pub won: u64,
+    pub staked: Decimal,
+    pub pnl: Decimal,
+}
+
+/// One strategy decision as stored, with the full record as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredDecision {
+    pub cycle: u64,
+    pub at: DateTime<Utc>,
+    pub platform: String,
+    pub market_id: String,
+    /// `selected`, `kelly_rejected`, `stake_rejected`, `risk_rejected` or
+    /// `arbitrage`.
+    pub decision: String,
+    /// Risk rejection reason, if any.
+    pub reason: Option<String>,
+    pub record: serde_json::Value,
+}
+
+impl StoredDecision {
+    pub fn new(cycle: u64, at: DateTime<Utc>, record: &DecisionRecord) -> Result<Self> {
+        let market = record.market();
+        Ok(Self {
+            cycle,
+            at,
+            platform: market.platform.clone(),
+            market_id: market.id.clone(),
+            decision: record.kind().to_string(),
+            reason: record.rejection().map(ToString::to_string),
+            record: serde_json::to_value(record).context("Failed to serialise decision")?,
+        })
+    }
+}
+
+/// Balances at the end of a cycle: one point of the dashboard chart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceSample {
+    pub at: DateTime<Utc>,
+    /// AUD bankroll less the stake of open bets.
+    pub cash: Decimal,
+    /// AUD bankroll plus the unrealised P&L of open bets.
+    pub equity: Decimal,
+    /// Fall of equity from its peak, as a fraction.
+    pub drawdown: Decimal,
+    pub bankroll: Decimal,
+    pub mana_bankroll: Decimal,
+}
+
+impl BalanceSample {
+    /// The balances of `state` at `at`.
+    pub fn of(state: &AgentState, at: DateTime<Utc>) -> Self {
+        Self {
+            at,
+            cash: state.cash_bankroll,
+            equity: state.total_equity,
+            drawdown: state.equity_drawdown(),
+            bankroll: state.bankroll,
+            mana_bankroll: state.mana_bankroll,
+        }
+    }
+}
+
+/// Bucket width of a [`downsample`]d balance series.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    /// Every sample.
+    #[default]
+    Raw,
+    /// The last sample of each UTC hour.
+    Hourly,
+    /// The last sample of each UTC day.
+    Daily,
+}
+
+impl Resolution {
+    fn bucket_secs(self) -> Option<i64> {
+        match self {
+            Self::Raw => None,
+            Self::Hourly => Some(3_600),
+            Self::Daily => Some(86_400),
+        }
+    }
+}
+
+/// The last sample of each bucket of `samples` (oldest first). Buckets are
+/// aligned to the UTC epoch, so hourly and daily ones start on the hour and
+/// at midnight.
+pub fn downsample(samples: Vec<BalanceSample>, resolution: Resolution) -> Vec<BalanceSample> {
+    let Some(width) = resolution.bucket_secs() else { return samples };
+    let bucket = |s: &BalanceSample| s.at.timestamp().div_euclid(width);
+    let mut out: Vec<BalanceSample> = Vec::new();
+    for sample in samples {
+        match out.last_mut() {
+            Some(last) if bucket(last) == bucket(&sample) => *last = sample,
+            _ => out.push(sample),
+        }
+    }
+    out
+}
+
+/// Group settled trades by category and currency, largest stake first.
+/// Trades without a category count as `Other`.
+pub fn summarise_by_category(trades: &[TradeRecord]) -> Vec<CategoryPnl> {
+    let mut groups: BTreeMap<(String, String), CategoryPnl> = BTreeMap::new();
+    for trade in trades {
+        let Some(pnl) = trade.pnl else { continue };
+        let category = trade.receipt.category.unwrap_or(MarketCategory::Other).to_string();
+        let currency = trade.receipt.currency.clone();
+        let group = groups.entry((category.clone(), currency.clone())).or_insert(CategoryPnl {
+            category,
+            currency,
+            trades: 0,
+            won: 0,
+            staked: Decimal::ZERO,
+            pnl: Decimal::ZERO,
+        });
+        group.trades += 1;
+        group.won += u64::from(pnl > Decimal::ZERO);
+        group.staked += trade.receipt.amount;
+        group.pnl += pnl;
+    }
+    let mut out: Vec<CategoryPnl> = groups.into_values().collect();
+    out.sort_by_key(|g| std::cmp::Reverse(g.staked));
+    out
+}
+
+/// Persistence of agent state, trades, cycle summaries and decisions.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Backend name for logs.
+    fn name(&self) -> &'static str;
+
+    /// Replace the saved agent state.
+    async fn save_state(&self, state: &AgentState) -> Result<()>;
+
+    /// The saved agent state, or `None` on a fresh start.
+    async fn load_state(&self) -> Result<Option<AgentState>>;
+
+    /// Record an executed trade. A trade already recorded under the same
+    /// `order_id` is left as it is.
+    async fn append_trade(&self, trade: &TradeReceipt) -> Result<()>;
+
+    /// Record the realised P&L of a trade. Unknown and already settled
+    /// orders are ignored.
+    async fn settle_trade(&self, order_id: &str, pnl: Decimal, at: DateTime<Utc>) -> Result<()>;
+
+    /// Trades matching `query`, oldest first.
+    async fn query_trades(&self, query: &TradeQuery) -> Result<Vec<TradeRecord>>;
+
+    async fn append_cycle(&self, report: &CycleReport) -> Result<()>;
+
+    /// The newest `limit` cycle summaries, oldest first.
+    async fn recent_cycles(&self, limit: usize) -> Result<Vec<CycleReport>>;
+
+    /// Record the strategy decisions made in `cycle` at `at`.
+    async fn append_decisions(&self, cycle: u64, at: DateTime<Utc>, decisions: &[DecisionRecord]) -> Result<()>;
+
+    /// Decisions of `cycle`, in the order they were made.
+    async fn decisions_in_cycle(&self, cycle: u64) -> Result<Vec<StoredDecision>>;
+
+    /// Record the balances at the end of a cycle.
+    async fn append_balance(&self, sample: &BalanceSample) -> Result<()>;
+
+    /// Balance samples taken in `[from, to)`, oldest first. A missing bound
+    /// is open.
+    async fn balance_history(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Result<Vec<BalanceSample>>;
+
+    /// Trades placed in `[from, to)`, oldest first.
+    async fn trades_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<TradeRecord>> {
+        self.query_trades(&TradeQuery { from: Some(from), to: Some(to), ..TradeQuery::default() }).await
+    }
+
+    /// Realised P&L of settled trades per category and currency.
+    async fn pnl_by_category(&self) -> Result<Vec<CategoryPnl>> {
+        let settled = self.query_trades(&TradeQuery { settled: Some(true), ..TradeQuery::default() }).await?;
+        Ok(summarise_by_category(&settled))
+    }
+}
+
+/// Open the configured backend. A new SQLite database is seeded from the
+/// JSON files in the working directory, and an empty balance series from
+/// the cycle history.
+pub async fn open(config: &StorageConfig) -> Result<Arc<dyn Storage>> {
+    let storage: Arc<dyn Storage> = match config.backend {
+        StorageBackend::Json => Arc::new(JsonStorage::default().with_state_backups(config.state_backups)),
+        StorageBackend::Sqlite => {
+            let db = SqliteStorage::open(&config.sqlite_path).await?;
+            import(&JsonStorage::default(), &db).await?;
+            Arc::new(db)
+        }
+    };
+    backfill_balances(&*storage).await?;
+    Ok(storage)
+}
+
+/// Seed an empty balance series from the bankroll after each stored cycle,
+/// so the chart keeps its history across the upgrade that introduced it.
+/// Cash and equity were not recorded then and are taken as the bankroll.
+/// Returns the number of samples written.
+pub async fn backfill_balances(storage: &dyn Storage) -> Result<usize> {
+    if !storage.balance_history(None, None).await?.is_empty() {
+        return Ok(0);
+    }
+    let cycles = storage.recent_cycles(super::MAX_CYCLE_HISTORY).await?;
+    let mut peak = Decimal::ZERO;
+    for report in &cycles {
+        peak = peak.max(report.bankroll_after);
+        let drawdown = if peak > Decimal::ZERO { Decimal::ONE - report.bankroll_after / peak } else { Decimal::ZERO };
+        storage
+            .append_balance(&BalanceSample {
+                at: report.timestamp,
+                cash: report.bankroll_after,
+                equity: report.bankroll_after,
+                drawdown,
+                bankroll: report.bankroll_after,
+                mana_bankroll: report.mana_bankroll_after,
+            })
+            .await?;
+    }
+    if !cycles.is_empty() {
+        info!(storage = storage.name(), samples = cycles.len(), "Balance history backfilled from cycles");
+    }
+    Ok(cycles.len())
+}
+
+/// Copy state, cycle history and trades from `from` into `into` when `into`
+/// has no state yet. The state is written last, so an interrupted import
+/// runs again. Returns whether anything was imported.
+pub async fn import(from: &dyn Storage, into: &dyn Storage) -> Result<bool> {
+    if into.load_state().await?.is_some() {
+        return Ok(false);
+    }
+    let Some(state) = from.load_state().await? else { return Ok(false) };
+
+    let cycles = from.recent_cycles(super::MAX_CYCLE_HISTORY).await?;
+    for report in &cycles {
+        into.append_cycle(report).await?;
+    }
+    for sample in from.balance_history(None, None).await? {
+        into.append_balance(&sample).await?;
+    }
+    let mut trades = from.query_trades(&TradeQuery::default()).await?;
+    // Bets placed before trades were recorded are only in the state.
+    trades.extend(state.open_bets.iter().map(|receipt| TradeRecord {
+        receipt: receipt.clone(),
+        pnl: None,
+        settled_at: None,
+    }));
+    for trade in &trades {
+        into.append_trade(&trade.receipt).await?;
+        if let (Some(pnl), Some(at)) = (trade.pnl, trade.settled_at) {
+            into.settle_trade(&trade.receipt.order_id, pnl, at).await?;
+        }
+    }
+    into.save_state(&state).await?;
+    info!(
+        from = from.name(),
+        into = into.name(),
+        cycles = cycles.len(),
+        trades = trades.len(),
+        "Imported existing state"
+    );
+    Ok(true)
+}
+
+// ---------------------------------------------------------------------------
+// JSON backend
+// ---------------------------------------------------------------------------
+
+/// One line of the JSON trade ledger.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum TradeEvent {
+    Placed { trade: Box<TradeReceipt> },
+    Settled { order_id: String, pnl: Decimal, at: DateTime<Utc> },
+}
+
+/// The state file plus JSON-lines files for cycles, trades, decisions and
+/// balances.
+/// Trades are an append-only event log folded on read.
+#[derive(Debug, Clone)]
+pub struct JsonStorage {
+    state_file: String,
+    /// Rolling backups kept of the state file.
+    state_backups: usize,
+    cycles_file: String,
+    trades_file: String,
+    decisions_file: String,
+    balances_file: String,
+}
+
+impl Default for JsonStorage {
+    /// The default file names in the working directory.
+    fn default() -> Self {
+        Self {
+            state_file: super::DEFAULT_STATE_FILE.to_string(),
+            state_backups: super::DEFAULT_STATE_BACKUPS,
+            cycles_file: super::DEFAULT_CYCLE_HISTORY_FILE.to_string(),
+            trades_file: DEFAULT_TRADES_FILE.to_string(),
+            decisions_file: DEFAULT_CYCLE_DECISIONS_FILE.to_string(),
+            balances_file: DEFAULT_BALANCE_HISTORY_FILE.to_string(),
+        }
+    }
+}
+
+impl JsonStorage {
+    /// The default file names inside `dir`.
+    pub fn in_dir(dir: &Path) -> Self {
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+        Self {
+            state_file: path(super::DEFAULT_STATE_FILE),
+            state_backups: super::DEFAULT_STATE_BACKUPS,
+            cycles_file: path(super::DEFAULT_CYCLE_HISTORY_FILE),
+            trades_file: path(DEFAULT_TRADES_FILE),
+            decisions_file: path(DEFAULT_CYCLE_DECISIONS_FILE),
+            balances_file: path(DEFAULT_BALANCE_HISTORY_FILE),
+        }
+    }
+
+    /// Keep `backups` rolling copies of the state file (default 3).
+    pub fn with_state_backups(mut self, backups: usize) -> Self {
+        self.state_backups = backups;
+        self
+    }
+
+    fn append_line(path: &str, line: &str) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .context(format!("Failed to open {path}"))?;
+        writeln!(file, "{line}").context(format!("Failed to append to {path}"))
+    }
+
+    /// Parsed lines of a JSON-lines file; a missing file has none and
+    /// malformed lines are skipped with a warning.
+    fn read_lines<T: serde::de::DeserializeOwned>(path: &str) -> Result<Vec<T>> {
+        if !Path::new(path).exists() {
+            return Ok(Vec::new());
+        }
+        let contents = std::fs::read_to_string(path).context(format!("Failed to read {path}"))?;
+        let mut items = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(item) => items.push(item),
+                Err(e) => warn!(path, line = i + 1, error = %e, "Skipping malformed line"),
+            }
+        }
+        Ok(items)
+    }
+}
+
+#[async_trait]
+impl Storage for JsonStorage {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    async fn save_state(&self, state: &AgentState) -> Result<()> {
+        super::save_state_with_backups(state, Some(&self.state_file), self.state_backups)
+    }
+
+    async fn load_state(&self) -> Result<Option<AgentState>> {
+        super::load_state(Some(&self.state_file))
+    }
+
+    async fn append_trade(&self, trade: &TradeReceipt) -> Result<()> {
+        // Duplicates are dropped when the log is folded.
+        let event = TradeEvent::Placed { trade: Box::new(trade.clone()) };
+        let line = serde_json::to_string(&event).context("Failed to serialise trade")?;
+        Self::append_line(&self.trades_file, &line)
+    }
+
+    async fn settle_trade(&self, order_id: &str, pnl: Decimal, at: DateTime<Utc>) -> Result<()> {
+        let event = TradeEvent::Settled { order_id: order_id.to_string(), pnl, at };
+        let line = serde_json::to_string(&event).context("Failed to serialise settlement")?;
+        Self::append_line(&self.trades_file, &line)
+    }
+
+    async fn query_trades(&self, query: &TradeQuery) -> Result<Vec<TradeRecord>> {
+        let mut trades: Vec<TradeRecord> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for event in Self::read_lines::<TradeEvent>(&self.trades_file)? {
+            match event {
+                TradeEvent::Placed { trade } => {
+                    if !index.contains_key(&trade.order_id) {
+                        index.insert(trade.order_id.clone(), trades.len());
+                        trades.push(TradeRecord { receipt: *trade, pnl: None, settled_at: None });
+                    }
+                }
+                TradeEvent::Settled { order_id, pnl, at } => {
+                    if let Some(trade) = index.get(&order_id).map(|&i| &mut trades[i]) {
+                        if trade.pnl.is_none() {
+                            trade.pnl = Some(pnl);
+                            trade.settled_at = Some(at);
+                        }
+                    }
+                }
+            }
+        }
+        trades.retain(|t| query.matches(t));
+        trades.sort_by_key(|t| t.receipt.timestamp);
+        Ok(trades)
+    }
+
+    async fn append_cycle(&self, report: &CycleReport) -> Result<()> {
+        super::append_cycle_report(report, Some(&self.cycles_file))
+    }
+
+    async fn recent_cycles(&self, limit: usize) -> Result<Vec<CycleReport>> {
+        super::load_cycle_history(Some(&self.cycles_file), limit)
+    }
+
+    async fn append_decisions(&self, cycle: u64, at: DateTime<Utc>, decisions: &[DecisionRecord]) -> Result<()> {
+        if decisions.is_empty() {
+            return Ok(());
+        }
+        let mut lines = Vec::with_capacity(decisions.len());
+        for record in decisions {
+            let stored = StoredDecision::new(cycle, at, record)?;
+            lines.push(serde_json::to_string(&stored).context("Failed to serialise decision")?);
+        }
+        super::append_lines_capped(&self.decisions_file, &lines, MAX_DECISION_HISTORY)
+    }
+
+    async fn decisions_in_cycle(&self, cycle: u64) -> Result<Vec<StoredDecision>> {
+        let mut decisions = Self::read_lines::<StoredDecision>(&self.decisions_file)?;
+        decisions.retain(|d| d.cycle == cycle);
+        Ok(decisions)
+    }
+
+    async fn append_balance(&self, sample: &BalanceSample) -> Result<()> {
+        let line = serde_json::to_string(sample).context("Failed to serialise balance sample")?;
+        super::append_lines_capped(&self.balances_file, &[line], MAX_BALANCE_HISTORY)
+    }
+
+    async fn balance_history(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Result<Vec<BalanceSample>> {
+        let mut samples = Self::read_lines::<BalanceSample>(&self.balances_file)?;
+        samples.retain(|s| from.is_none_or(|from| s.at >= from) && to.is_none_or(|to| s.at < to));
+        samples.sort_by_key(|s| s.at);
+        Ok(samples)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::edge::Edge;
+    use crate::strategy::kelly::SizedBet;
+    use crate::strategy::risk::RejectionReason;
+    use crate::types::{AgentStatus, Estimate, Market, Side};
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+
+    fn temp_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("oracle_test_storage_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn receipt(order_id: &str, category: MarketCategory, amount: Decimal, at: DateTime<Utc>) -> TradeReceipt {
+        TradeReceipt {
+            order_id: order_id.into(),
+            market_id: format!("m-{order_id}"),
+            platform: "betfair".into(),
+            side: Side::Yes,
+            amount,
+            fill_price: dec!(0.5),
+            fees: Decimal::ZERO,
+            timestamp: at,
+            currency: "AUD".into(),
+            deadline: None,
+            category: Some(category),
+            edge: Some(dec!(0.08)),
+            raw_response: None,
+            risk_context: None,
+            tags: vec!["nightly".into()],
+            correlation_key: None,
+            expected_price: None,
+        }
+    }
+
+    fn cycle(n: u64) -> CycleReport {
+        CycleReport {
+            cycle_number: n,
+            timestamp: Utc::now(),
+            markets_scanned: 40,
+            edges_found: 3,
+            bets_placed: 1,
+            cycle_cost: dec!(0.05),
+            cycle_pnl: dec!(-0.05),
+            bankroll_after: Decimal::from(100 + n),
+            bets_failed: 0,
+            mana_bankroll_after: dec!(1000),
+            status: Some(AgentStatus::Alive),
+        }
+    }
+
+    fn balance(at: DateTime<Utc>, bankroll: Decimal) -> BalanceSample {
+        BalanceSample { at, cash: bankroll - dec!(10), equity: bankroll + dec!(2), drawdown: dec!(0.05), bankroll, mana_bankroll: dec!(1000) }
+    }
+
+    fn edge(id: &str) -> Edge {
+        let market = Market { id: id.into(), ..Market::sample() };
+        let estimate = Estimate {
+            probability: dec!(0.6),
+            confidence: dec!(0.8),
+            reasoning: "test".into(),
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
+        };
+        Edge { market, estimate, side: Side::Yes, edge: dec!(0.1), signed_edge: dec!(0.1), raw_edge: dec!(0.1), effective_threshold: dec!(0.1) }
+    }
+
+    fn kelly_rejected(id: &str) -> DecisionRecord {
+        DecisionRecord::KellyRejected { edge: edge(id) }
+    }
+
+    fn risk_rejected(id: &str) -> DecisionRecord {
+        let bet = SizedBet {
+            edge: edge(id),
+            kelly_fraction: dec!(0.05),
+            bet_fraction: dec!(0.02),
+            bet_amount: dec!(10),
+            expected_value: dec!(1),
+            net_expected_value: dec!(1),
+            days_to_resolution: 3.0,
+            discounted_edge: dec!(0.1),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
+        };
+        DecisionRecord::RiskRejected { bet, reason: RejectionReason::MaxPositionsReached { current: 5, limit: 5 } }
+    }
+
+    /// The behaviour every backend must share.
+    async fn exercise(storage: &dyn Storage) {
+        // State
+        assert!(storage.load_state().await.unwrap().is_none());
+        let mut state = AgentState::new(dec!(500));
+        state.cycle_count = 42;
+        state.total_pnl = dec!(25);
+        storage.save_state(&state).await.unwrap();
+        state.cycle_count = 43;
+        storage.save_state(&state).await.unwrap();
+        let loaded = storage.load_state().await.unwrap().unwrap();
+        assert_eq!((loaded.cycle_count, loaded.bankroll, loaded.total_pnl), (43, dec!(500), dec!(25)));
+
+        // Trades: dedupe by order id, settle once, query by range
+        let t0 = Utc::now() - Duration::days(3);
+        storage.append_trade(&receipt("a", MarketCategory::Sports, dec!(10), t0)).await.unwrap();
+        storage.append_trade(&receipt("b", MarketCategory::Politics, dec!(20), t0 + Duration::days(1))).await.unwrap();
+        storage.append_trade(&receipt("c", MarketCategory::Sports, dec!(30), t0 + Duration::days(2))).await.unwrap();
+        storage.append_trade(&receipt("a", MarketCategory::Sports, dec!(99), t0)).await.unwrap();
+        storage.settle_trade("a", dec!(9.5), t0 + Duration::days(1)).await.unwrap();
+        storage.settle_trade("a", dec!(-10), t0 + Duration::days(2)).await.unwrap();
+        storage.settle_trade("c", dec!(-30), t0 + Duration::days(2)).await.unwrap();
+        storage.settle_trade("unknown", dec!(1), t0).await.unwrap();
+
+        let all = storage.query_trades(&TradeQuery::default()).await.unwrap();
+        assert_eq!(all.iter().map(|t| t.receipt.order_id.as_str()).collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(all[0].receipt, receipt("a", MarketCategory::Sports, dec!(10), t0));
+        assert_eq!((all[0].pnl, all[0].settled_at), (Some(dec!(9.5)), Some(t0 + Duration::days(1))));
+        assert_eq!(all[1].pnl, None);
+
+        let between = storage.trades_between(t0 + Duration::hours(1), t0 + Duration::days(2)).await.unwrap();
+        assert_eq!(between.iter().map(|t| t.receipt.order_id.as_str()).collect::<Vec<_>>(), ["b"]);
+        let open = storage.query_trades(&TradeQuery { settled: Some(false), ..TradeQuery::default() }).await.unwrap();
+        assert_eq!(open.len(), 1);
+        let elsewhere = TradeQuery { platform: Some("polymarket".into()), ..TradeQuery::default() };
+        assert!(storage.query_trades(&elsewhere).await.unwrap().is_empty());
+
+        let by_category = storage.pnl_by_category().await.unwrap();
+        assert_eq!(
+            by_category,
+            vec![CategoryPnl {
+                category: "Sports".into(),
+                currency: "AUD".into(),
+                trades: 2,
+                won: 1,
+                staked: dec!(40),
+                pnl: dec!(-20.5),
+            }]
+        );
+
+        // Cycles: newest `limit`, oldest first
+        for n in 1..=3 {
+            storage.append_cycle(&cycle(n)).await.unwrap();
+        }
+        let cycles = storage.recent_cycles(2).await.unwrap();
+        assert_eq!(cycles.iter().map(|c| c.cycle_number).collect::<Vec<_>>(), [2, 3]);
+        assert_eq!((cycles[1].bankroll_after, cycles[1].status), (dec!(103), Some(AgentStatus::Alive)));
+
+        // Decisions by cycle
+        let at = Utc::now();
+        storage.append_decisions(7, at, &[kelly_rejected("x"), risk_rejected("z")]).await.unwrap();
+        storage.append_decisions(8, at, &[kelly_rejected("y")]).await.unwrap();
+        let decisions = storage.decisions_in_cycle(7).await.unwrap();
+        assert_eq!(decisions.len(), 2);
+        assert_eq!((decisions[0].market_id.as_str(), decisions[0].decision.as_str()), ("x", "kelly_rejected"));
+        assert_eq!(decisions[0].at, at);
+        assert_eq!(decisions[1].decision, "risk_rejected");
+        assert!(decisions[1].reason.is_some());
+        assert_eq!(decisions[1].record["decision"], "risk_rejected");
+        assert!(storage.decisions_in_cycle(9).await.unwrap().is_empty());
+
+        // Balances by range, oldest first
+        let t0 = Utc::now() - Duration::hours(3);
+        for h in [2, 0, 1] {
+            let sample = BalanceSample { at: t0 + Duration::hours(h), ..balance(t0, Decimal::from(100 + h)) };
+            storage.append_balance(&sample).await.unwrap();
+        }
+        let all = storage.balance_history(None, None).await.unwrap();
+        assert_eq!(all.iter().map(|s| s.bankroll).collect::<Vec<_>>(), [dec!(100), dec!(101), dec!(102)]);
+        assert_eq!(all[0], balance(t0, dec!(100)));
+        let later = storage.balance_history(Some(t0 + Duration::hours(1)), Some(t0 + Duration::hours(2))).await.unwrap();
+        assert_eq!(later.iter().map(|s| s.bankroll).collect::<Vec<_>>(), [dec!(101)]);
+    }
+
+    #[tokio::test]
+    async fn test_json_backend() {
+        let dir = temp_dir();
+        exercise(&JsonStorage::in_dir(&dir)).await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_backend() {
+        exercise(&SqliteStorage::open_in_memory().await.unwrap()).await;
+    }
+
+    #[test]
+    fn test_downsample_keeps_last_sample_per_bucket() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let series: Vec<BalanceSample> = [
+            ("2026-03-01T22:59:59Z", 1),
+            ("2026-03-01T23:00:00Z", 2),
+            ("2026-03-01T23:59:59Z", 3),
+            ("2026-03-02T00:00:00Z", 4),
+            ("2026-03-02T00:30:00Z", 5),
+            ("2026-03-02T02:00:00Z", 6),
+        ]
+        .into_iter()
+        .map(|(t, n)| balance(at(t), Decimal::from(n)))
+        .collect();
+        let bankrolls = |samples: Vec<BalanceSample>| samples.iter().map(|s| s.bankroll).collect::<Vec<_>>();
+
+        assert_eq!(downsample(series.clone(), Resolution::Raw), series);
+        assert_eq!(bankrolls(downsample(series.clone(), Resolution::Hourly)), [dec!(1), dec!(3), dec!(5), dec!(6)]);
+        let daily = downsample(series, Resolution::Daily);
+        assert_eq!(bankrolls(daily.clone()), [dec!(3), dec!(6)]);
+        assert_eq!(daily[0].at, at("2026-03-01T23:59:59Z"));
+        assert!(downsample(Vec::new(), Resolution::Daily).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_balances_from_cycles_once() {
+        let db = SqliteStorage::open_in_memory().await.unwrap();
+        assert_eq!(backfill_balances(&db).await.unwrap(), 0);
+        for n in [3, 1, 2] {
+            db.append_cycle(&cycle(n)).await.unwrap();
+        }
+        assert_eq!(backfill_balances(&db).await.unwrap(), 3);
+        let samples = db.balance_history(None, None).await.unwrap();
+        assert_eq!(samples[2].equity, dec!(102));
+        assert_eq!(samples[2].drawdown, Decimal::ONE - dec!(102) / dec!(103));
+        assert_eq!(backfill_balances(&db).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_import_json_into_sqlite_once() {
+        let dir = temp_dir();
+        let json = JsonStorage::in_dir(&dir);
+        let db = SqliteStorage::open_in_memory().await.unwrap();
+        assert!(!import(&json, &db).await.unwrap(), "nothing to import");
+
+        let mut state = AgentState::new(dec!(500));
+        state.cycle_count = 2;
+        state.open_bets.push(receipt("open", MarketCategory::Weather, dec!(5), Utc::now()));
+        json.save_state(&state).await.unwrap();
+        json.append_cycle(&cycle(1)).await.unwrap();
+        json.append_cycle(&cycle(2)).await.unwrap();
+        json.append_trade(&receipt("won", MarketCategory::Sports, dec!(10), Utc::now())).await.unwrap();
+        json.settle_trade("won", dec!(8), Utc::now()).await.unwrap();
+        json.append_balance(&balance(Utc::now(), dec!(500))).await.unwrap();
+
+        assert!(import(&json, &db).await.unwrap());
+        assert_eq!(db.balance_history(None, None).await.unwrap().len(), 1);
+        assert_eq!(db.load_state().await.unwrap().unwrap().cycle_count, 2);
+        assert_eq!(db.recent_cycles(10).await.unwrap().len(), 2);
+        let trades = db.query_trades(&TradeQuery::default()).await.unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades.iter().find(|t| t.receipt.order_id == "won").unwrap().pnl, Some(dec!(8)));
+
+        // The database now has state of its own
+        assert!(!import(&json, &db).await.unwrap());
+        assert_eq!(db.recent_cycles(10).await.unwrap().len(), 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
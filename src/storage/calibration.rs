@@ -0,0 +1,374 @@
+//! Estimate calibration store.
+//!
+//! Keeps one record per market the LLM estimated — its probability,
+//! confidence and the market price at the time — and labels it with the
+//! outcome once the market resolves, so the
+//! [`Calibrator`](crate::backtest::calibration::Calibrator) can score the
+//! estimates. Markets we hold are labelled from the resolver's
+//! settlements; estimates we passed on are checked once their deadline
+//! has passed ([`CalibrationStore::due`]).
+//!
+//! A later estimate of the same market replaces the record, except that
+//! an estimate we bet on is never replaced by one we didn't: the acted-on
+//! estimate is the one worth scoring. Refunded and probabilistic
+//! resolutions carry no YES/NO outcome, so their records are dropped.
+//!
+//! Saved as one versioned JSON document (see [`super::migrations`]),
+//! capped at [`MAX_RECORDS`].
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::migrations::{self, Artifact};
+use crate::backtest::calibration::{CalibrationPoint, CalibrationReport, Calibrator};
+use crate::types::{Estimate, Market, MarketCategory, MarketResolution};
+
+/// Version of the calibration store layout.
+pub const CALIBRATION_VERSION: u32 = 1;
+
+/// Default store path, next to the state file.
+pub const DEFAULT_CALIBRATION_FILE: &str = "oracle_calibration.json";
+
+/// Records kept; the oldest estimates are dropped beyond this.
+pub const MAX_RECORDS: usize = 20_000;
+
+/// Days past its deadline an unresolved market is still checked; after
+/// that its record is dropped so it stops heading the [`due`] queue.
+///
+/// [`due`]: CalibrationStore::due
+pub const UNRESOLVED_EXPIRY_DAYS: i64 = 30;
+
+/// One market's scored estimate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationRecord {
+    pub platform: String,
+    pub market_id: String,
+    pub category: MarketCategory,
+    pub estimated_prob: f64,
+    pub confidence: f64,
+    /// YES price when the estimate was made.
+    pub market_price_at_estimate: f64,
+    pub estimated_at: DateTime<Utc>,
+    /// Market deadline; unbet markets are checked for an outcome after it.
+    pub deadline: DateTime<Utc>,
+    /// A bet was placed on this estimate.
+    pub acted_on: bool,
+    /// `Some(true)` = resolved YES.
+    pub resolved_outcome: Option<bool>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// On-disk layout.
+#[derive(Debug, Serialize, Deserialize)]
+struct CalibrationFile {
+    schema_version: u32,
+    records: Vec<CalibrationRecord>,
+}
+
+/// Estimates and their outcomes, keyed `platform:market_id`.
+#[derive(Debug, Clone)]
+pub struct CalibrationStore {
+    path: PathBuf,
+    records: BTreeMap<String, CalibrationRecord>,
+}
+
+fn key(platform: &str, market_id: &str) -> String {
+    format!("{platform}:{market_id}")
+}
+
+impl CalibrationStore {
+    /// An empty store saving to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), records: BTreeMap::new() }
+    }
+
+    /// Load the store at `path`. A missing file is an empty store.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if !path.exists() {
+            return Ok(Self::new(path));
+        }
+        let what = path.display().to_string();
+        let text = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {what}"))?;
+        let doc: serde_json::Value = serde_json::from_str(&text).with_context(|| format!("Failed to parse {what}"))?;
+        let doc = migrations::upgrade(Artifact::Calibration, doc, &what)?;
+        let file: CalibrationFile = serde_json::from_value(doc).with_context(|| format!("Failed to parse {what}"))?;
+        let records = file
+            .records
+            .into_iter()
+            .map(|r| (key(&r.platform, &r.market_id), r))
+            .collect();
+        Ok(Self { path, records })
+    }
+
+    /// Write the store, replacing the file only once the new one is complete.
+    pub fn save(&self) -> Result<()> {
+        let file = CalibrationFile {
+            schema_version: CALIBRATION_VERSION,
+            records: self.records.values().cloned().collect(),
+        };
+        let json = serde_json::to_string(&file).context("Failed to serialise calibration store")?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &CalibrationRecord> {
+        self.records.values()
+    }
+
+    /// Record an estimate of `market` made at `at`. Resolved markets are
+    /// left as they are.
+    pub fn record_estimate(&mut self, market: &Market, estimate: &Estimate, acted_on: bool, at: DateTime<Utc>) {
+        let key = key(&market.platform, &market.id);
+        if let Some(existing) = self.records.get(&key) {
+            if existing.resolved_outcome.is_some() || (existing.acted_on && !acted_on) {
+                return;
+            }
+        }
+        self.records.insert(
+            key,
+            CalibrationRecord {
+                platform: market.platform.clone(),
+                market_id: market.id.clone(),
+                category: market.category,
+                estimated_prob: estimate.probability.to_f64().unwrap_or(0.5),
+                confidence: estimate.confidence.to_f64().unwrap_or(0.0),
+                market_price_at_estimate: market.current_price_yes.to_f64().unwrap_or(0.5),
+                estimated_at: at,
+                deadline: market.deadline,
+                acted_on,
+                resolved_outcome: None,
+                resolved_at: None,
+            },
+        );
+        self.prune();
+    }
+
+    /// Label a market's record with its resolution. Returns whether the
+    /// store changed.
+    pub fn resolve(&mut self, platform: &str, market_id: &str, resolution: MarketResolution, at: DateTime<Utc>) -> bool {
+        let key = key(platform, market_id);
+        let Some(record) = self.records.get_mut(&key) else { return false };
+        if record.resolved_outcome.is_some() {
+            return false;
+        }
+        match resolution {
+            MarketResolution::Yes | MarketResolution::No => {
+                record.resolved_outcome = Some(resolution == MarketResolution::Yes);
+                record.resolved_at = Some(at);
+            }
+            MarketResolution::Probability(_) | MarketResolution::Cancelled => {
+                self.records.remove(&key);
+            }
+        }
+        true
+    }
+
+    /// Unresolved markets past their deadline at `now`, as `(platform,
+    /// market_id)`, earliest deadline first, at most `limit`.
+    pub fn due(&self, now: DateTime<Utc>, limit: usize) -> Vec<(String, String)> {
+        let mut due: Vec<&CalibrationRecord> = self
+            .records
+            .values()
+            .filter(|r| r.resolved_outcome.is_none() && r.deadline <= now)
+            .collect();
+        due.sort_by_key(|r| r.deadline);
+        due.into_iter().take(limit).map(|r| (r.platform.clone(), r.market_id.clone())).collect()
+    }
+
+    /// Drop unresolved records more than [`UNRESOLVED_EXPIRY_DAYS`] past
+    /// their deadline. Returns how many were dropped.
+    pub fn expire(&mut self, now: DateTime<Utc>) -> usize {
+        let cutoff = now - chrono::Duration::days(UNRESOLVED_EXPIRY_DAYS);
+        let before = self.records.len();
+        self.records.retain(|_, r| r.resolved_outcome.is_some() || r.deadline >= cutoff);
+        before - self.records.len()
+    }
+
+    /// Brier score, log loss and calibration curve over the resolved records.
+    pub fn report(&self) -> CalibrationReport {
+        let mut calibrator = Calibrator::new();
+        calibrator.add_points(
+            self.records
+                .values()
+                .filter_map(|r| {
+                    Some(CalibrationPoint {
+                        market_id: r.market_id.clone(),
+                        category: r.category,
+                        estimated_probability: r.estimated_prob,
+                        resolved_yes: r.resolved_outcome?,
+                    })
+                })
+                .collect(),
+        );
+        calibrator.report()
+    }
+
+    /// Drop the oldest estimates beyond [`MAX_RECORDS`].
+    fn prune(&mut self) {
+        let excess = self.records.len().saturating_sub(MAX_RECORDS);
+        if excess == 0 {
+            return;
+        }
+        let mut by_age: Vec<(DateTime<Utc>, String)> =
+            self.records.iter().map(|(k, r)| (r.estimated_at, k.clone())).collect();
+        by_age.sort();
+        for (_, key) in by_age.into_iter().take(excess) {
+            self.records.remove(&key);
+        }
+        debug!(dropped = excess, "Calibration store pruned");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn market(id: &str, category: MarketCategory, price: Decimal, deadline: DateTime<Utc>) -> Market {
+        Market {
+            id: id.into(),
+            platform: "manifold".into(),
+            question: format!("{id}?"),
+            description: String::new(),
+            category,
+            current_price_yes: price,
+            current_price_no: Decimal::ONE - price,
+            volume_24h: dec!(100),
+            liquidity: dec!(500),
+            deadline,
+            resolution_criteria: String::new(),
+            url: String::new(),
+            cross_refs: Default::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn estimate(probability: Decimal) -> Estimate {
+        Estimate {
+            probability,
+            confidence: dec!(0.7),
+            reasoning: String::new(),
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("oracle_calibration_{name}_{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_record_resolve_and_persist() {
+        let path = temp_path("round_trip");
+        let now = Utc::now();
+        let mut store = CalibrationStore::load(&path).unwrap();
+        assert!(store.is_empty());
+
+        let rain = market("rain", MarketCategory::Weather, dec!(0.40), now + Duration::days(1));
+        store.record_estimate(&rain, &estimate(dec!(0.80)), false, now);
+        store.record_estimate(&market("cup", MarketCategory::Sports, dec!(0.5), now), &estimate(dec!(0.3)), true, now);
+        assert!(store.resolve("manifold", "rain", MarketResolution::Yes, now));
+        assert!(!store.resolve("manifold", "rain", MarketResolution::No, now), "already resolved");
+        assert!(!store.resolve("manifold", "unknown", MarketResolution::Yes, now));
+        // A resolved record is not replaced by a late estimate.
+        store.record_estimate(&rain, &estimate(dec!(0.10)), true, now);
+        store.save().unwrap();
+
+        let loaded = CalibrationStore::load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let rain = loaded.records().find(|r| r.market_id == "rain").unwrap();
+        assert_eq!((rain.estimated_prob, rain.market_price_at_estimate, rain.confidence), (0.8, 0.4, 0.7));
+        assert_eq!(rain.resolved_outcome, Some(true));
+        assert!(rain.resolved_at.is_some() && !rain.acted_on);
+
+        let report = loaded.report();
+        assert_eq!(report.total_predictions, 1);
+        assert!((report.overall_brier - 0.04).abs() < 1e-12);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_acted_on_estimate_is_kept() {
+        let now = Utc::now();
+        let mut store = CalibrationStore::new(temp_path("acted"));
+        let m = market("m", MarketCategory::Politics, dec!(0.5), now);
+        store.record_estimate(&m, &estimate(dec!(0.60)), false, now);
+        store.record_estimate(&m, &estimate(dec!(0.70)), true, now);
+        store.record_estimate(&m, &estimate(dec!(0.20)), false, now + Duration::hours(1));
+        let record = store.records().next().unwrap();
+        assert_eq!((record.estimated_prob, record.acted_on), (0.7, true));
+    }
+
+    #[test]
+    fn test_unscorable_resolutions_drop_the_record() {
+        let now = Utc::now();
+        let mut store = CalibrationStore::new(temp_path("drop"));
+        for id in ["void", "mkt"] {
+            store.record_estimate(&market(id, MarketCategory::Other, dec!(0.5), now), &estimate(dec!(0.5)), false, now);
+        }
+        assert!(store.resolve("manifold", "void", MarketResolution::Cancelled, now));
+        assert!(store.resolve("manifold", "mkt", MarketResolution::Probability(dec!(0.4)), now));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_due_lists_unresolved_past_deadline() {
+        let now = Utc::now();
+        let mut store = CalibrationStore::new(temp_path("due"));
+        for (id, hours) in [("late", -2), ("later", -1), ("open", 5), ("done", -3)] {
+            let m = market(id, MarketCategory::Other, dec!(0.5), now + Duration::hours(hours));
+            store.record_estimate(&m, &estimate(dec!(0.5)), false, now);
+        }
+        store.resolve("manifold", "done", MarketResolution::No, now);
+        let due = store.due(now, 10);
+        assert_eq!(due, vec![("manifold".to_string(), "late".to_string()), ("manifold".to_string(), "later".to_string())]);
+        assert_eq!(store.due(now, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_expire_drops_long_overdue_unresolved() {
+        let now = Utc::now();
+        let mut store = CalibrationStore::new(temp_path("expire"));
+        for (id, days) in [("stuck", -40), ("recent", -2), ("scored", -60)] {
+            let m = market(id, MarketCategory::Other, dec!(0.5), now + Duration::days(days));
+            store.record_estimate(&m, &estimate(dec!(0.5)), false, now);
+        }
+        store.resolve("manifold", "scored", MarketResolution::Yes, now);
+        assert_eq!(store.expire(now), 1);
+        let mut left: Vec<_> = store.records().map(|r| r.market_id.as_str()).collect();
+        left.sort();
+        assert_eq!(left, vec!["recent", "scored"]);
+    }
+}
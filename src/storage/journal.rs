@@ -0,0 +1,456 @@
+//! Append-only decision journal.
+//!
+//! Every [`DecisionRecord`] of a cycle — selected, Kelly-rejected,
+//! risk-rejected, and the exit or hold of each reviewed position — is
+//! written as one JSON line with the cycle number, the decision time, the
+//! market snapshot, the estimate and any rejection reason, so a decision can be explained long after the cycle. A
+//! blocked bet also carries the reason's code as `reason_code`, which
+//! [`DecisionJournal::rejection_summary`] counts. Markets
+//! without an edge make no decision and are not journalled (the metrics
+//! `decisions` table has every estimated market).
+//!
+//! The file rotates once it exceeds `max_file_mb` or its first line is
+//! older than `max_age_days`: `oracle_decisions.jsonl` becomes `.1`, `.1`
+//! becomes `.2`, and so on up to `keep_files`; older files are deleted.
+//! Lines repeat [`JOURNAL_VERSION`] and are read back as plain JSON, so a
+//! reader tolerates lines from other versions.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::config::JournalConfig;
+use crate::strategy::DecisionRecord;
+
+/// Default decision journal path.
+pub const DEFAULT_JOURNAL_FILE: &str = "oracle_decisions.jsonl";
+
+/// Version of the journal line layout.
+pub const JOURNAL_VERSION: u32 = 1;
+
+/// One journal line.
+#[derive(Serialize)]
+struct Entry<'a> {
+    schema_version: u32,
+    at: DateTime<Utc>,
+    cycle: u64,
+    platform: &'a str,
+    market_id: &'a str,
+    /// Rejection reason in words, for reading the file by eye.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason_text: Option<String>,
+    /// Code of the check that blocked the bet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason_code: Option<&'static str>,
+    #[serde(flatten)]
+    record: &'a DecisionRecord,
+}
+
+/// Filter for [`DecisionJournal::recent`].
+#[derive(Debug, Clone, Default)]
+pub struct JournalQuery {
+    pub market_id: Option<String>,
+    pub cycle: Option<u64>,
+    /// Most entries returned.
+    pub limit: usize,
+}
+
+impl JournalQuery {
+    fn matches(&self, line: &Value) -> bool {
+        self.market_id.as_deref().is_none_or(|id| line["market_id"] == id)
+            && self.cycle.is_none_or(|cycle| line["cycle"] == cycle)
+    }
+}
+
+/// Blocked bets by rejection code over the latest cycles of the journal.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RejectionSummary {
+    /// Oldest and newest cycle with journalled decisions in the window;
+    /// `None` when the journal is empty.
+    pub from_cycle: Option<u64>,
+    pub to_cycle: Option<u64>,
+    pub total: usize,
+    pub by_code: BTreeMap<String, usize>,
+}
+
+/// The journal file and its rotation policy.
+#[derive(Debug, Clone)]
+pub struct DecisionJournal {
+    path: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    keep_files: usize,
+}
+
+impl DecisionJournal {
+    pub fn new(config: &JournalConfig) -> Self {
+        Self {
+            path: PathBuf::from(&config.file),
+            max_bytes: config.max_file_mb * 1024 * 1024,
+            max_age: Duration::days(config.max_age_days),
+            keep_files: config.keep_files,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `decisions` made in `cycle` at `at`, rotating first if due.
+    pub fn append(&self, cycle: u64, at: DateTime<Utc>, decisions: &[DecisionRecord]) -> Result<()> {
+        if decisions.is_empty() {
+            return Ok(());
+        }
+        self.rotate_if_due(at)?;
+        let mut lines = String::new();
+        for record in decisions {
+            let market = record.market();
+            let reason_text = record.rejection().map(ToString::to_string);
+            let reason_code = record.rejection_code();
+            let entry = Entry {
+                schema_version: JOURNAL_VERSION,
+                at,
+                cycle,
+                platform: &market.platform,
+                market_id: &market.id,
+                reason_text,
+                reason_code,
+                record,
+            };
+            lines.push_str(&serde_json::to_string(&entry).context("Failed to serialise decision")?);
+            lines.push('\n');
+        }
+        let what = self.path.display();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open decision journal {what}"))?;
+        file.write_all(lines.as_bytes()).with_context(|| format!("Failed to append to decision journal {what}"))?;
+        debug!(path = %what, cycle, decisions = decisions.len(), "Decisions journalled");
+        Ok(())
+    }
+
+    /// The newest entries matching `query`, newest first, across the
+    /// current and rotated files. Malformed lines are skipped.
+    pub fn recent(&self, query: &JournalQuery) -> Result<Vec<Value>> {
+        let mut found = Vec::new();
+        for n in 0..=self.keep_files {
+            if found.len() >= query.limit {
+                break;
+            }
+            let path = self.rotated(n);
+            if !path.exists() {
+                continue;
+            }
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read decision journal {}", path.display()))?;
+            let matching = contents
+                .lines()
+                .rev()
+                .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                .filter(|line| query.matches(line));
+            found.extend(matching.take(query.limit - found.len()));
+        }
+        Ok(found)
+    }
+
+    /// Count the blocked bets of the latest `cycles` journalled cycles
+    /// (at least one) by `reason_code`, across the current and rotated
+    /// files. Lines written before codes were journalled aren't counted.
+    pub fn rejection_summary(&self, cycles: u64) -> Result<RejectionSummary> {
+        let mut summary = RejectionSummary::default();
+        let mut window_start = None;
+        for n in 0..=self.keep_files {
+            let path = self.rotated(n);
+            if !path.exists() {
+                continue;
+            }
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read decision journal {}", path.display()))?;
+            for line in contents.lines().rev().filter_map(|line| serde_json::from_str::<Value>(line).ok()) {
+                let Some(cycle) = line["cycle"].as_u64() else { continue };
+                let start = *window_start.get_or_insert_with(|| {
+                    summary.to_cycle = Some(cycle);
+                    cycle.saturating_sub(cycles.max(1) - 1)
+                });
+                if cycle < start {
+                    return Ok(summary);
+                }
+                summary.from_cycle = Some(cycle);
+                if let Some(code) = line["reason_code"].as_str() {
+                    summary.total += 1;
+                    *summary.by_code.entry(code.to_string()).or_insert(0) += 1;
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// `n`th rotated file; 0 is the live one.
+    fn rotated(&self, n: usize) -> PathBuf {
+        if n == 0 {
+            return self.path.clone();
+        }
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    /// Rotate when the live file is over size or its first entry is older
+    /// than the age limit. Returns whether it rotated.
+    fn rotate_if_due(&self, now: DateTime<Utc>) -> Result<bool> {
+        let Ok(meta) = fs::metadata(&self.path) else {
+            return Ok(false);
+        };
+        let oversize = meta.len() >= self.max_bytes;
+        let expired = || first_entry_at(&self.path).is_some_and(|at| now - at >= self.max_age);
+        if !oversize && !expired() {
+            return Ok(false);
+        }
+        let oldest = self.rotated(self.keep_files);
+        if self.keep_files == 0 || oldest.exists() {
+            fs::remove_file(&oldest).with_context(|| format!("Failed to delete {}", oldest.display()))?;
+        }
+        for n in (1..self.keep_files).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated(n + 1)).with_context(|| format!("Failed to rotate {}", from.display()))?;
+            }
+        }
+        if self.keep_files > 0 {
+            fs::rename(&self.path, self.rotated(1))
+                .with_context(|| format!("Failed to rotate {}", self.path.display()))?;
+        }
+        info!(path = %self.path.display(), oversize, "Decision journal rotated");
+        Ok(true)
+    }
+}
+
+/// Decision time of the first line in `path`.
+fn first_entry_at(path: &Path) -> Option<DateTime<Utc>> {
+    let file = fs::File::open(path).ok()?;
+    let line = BufReader::new(file).lines().next()?.ok()?;
+    match serde_json::from_str::<Value>(&line).ok().and_then(|v| v["at"].as_str().map(str::to_string)) {
+        Some(at) => at.parse().ok(),
+        None => {
+            warn!(path = %path.display(), "Decision journal starts with a malformed line");
+            None
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::edge::Edge;
+    use crate::strategy::kelly::SizedBet;
+    use crate::strategy::risk::RejectionReason;
+    use crate::types::{Estimate, Market, MarketCategory, Side};
+    use rust_decimal_macros::dec;
+
+    fn edge(id: &str) -> Edge {
+        Edge {
+            market: Market {
+                id: id.into(),
+                platform: "manifold".into(),
+                question: "Will it rain?".into(),
+                description: String::new(),
+                category: MarketCategory::Weather,
+                current_price_yes: dec!(0.40),
+                current_price_no: dec!(0.60),
+                volume_24h: dec!(100),
+                liquidity: dec!(500),
+                deadline: Utc::now() + Duration::days(10),
+                resolution_criteria: String::new(),
+                url: String::new(),
+                cross_refs: Default::default(),
+                event_group: None,
+                facts: None,
+                tags: Vec::new(),
+            },
+            estimate: Estimate {
+                probability: dec!(0.55),
+                confidence: dec!(0.8),
+                reasoning: "wet season".into(),
+                tokens_used: 100,
+                cost: dec!(0.01),
+                critique: None,
+                served_by: None,
+                tier: None,
+            },
+            side: Side::Yes,
+            edge: dec!(0.15),
+            signed_edge: dec!(0.15),
+            raw_edge: dec!(0.15),
+            effective_threshold: dec!(0.06),
+        }
+    }
+
+    fn bet(id: &str) -> SizedBet {
+        SizedBet {
+            edge: edge(id),
+            kelly_fraction: dec!(0.2),
+            bet_fraction: dec!(0.05),
+            bet_amount: dec!(50),
+            expected_value: dec!(7.5),
+            net_expected_value: dec!(7.5),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.15),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
+        }
+    }
+
+    fn decisions() -> Vec<DecisionRecord> {
+        vec![
+            DecisionRecord::Selected { bet: bet("m1"), adjusted_amount: dec!(45), drawdown_multiplier: dec!(1) },
+            DecisionRecord::KellyRejected { edge: edge("m2") },
+            DecisionRecord::RiskRejected {
+                bet: bet("m3"),
+                reason: RejectionReason::MaxPositionsReached { current: 20, limit: 20 },
+            },
+        ]
+    }
+
+    fn journal(name: &str, max_file_mb: u64, keep_files: usize) -> DecisionJournal {
+        let file = std::env::temp_dir().join(format!("oracle_journal_{name}_{}.jsonl", uuid::Uuid::new_v4()));
+        DecisionJournal::new(&JournalConfig {
+            enabled: true,
+            file: file.to_str().unwrap().to_string(),
+            max_file_mb,
+            max_age_days: 7,
+            keep_files,
+        })
+    }
+
+    fn cleanup(journal: &DecisionJournal) {
+        for n in 0..=journal.keep_files {
+            let _ = fs::remove_file(journal.rotated(n));
+        }
+    }
+
+    #[test]
+    fn test_each_variant_serialises() {
+        let j = journal("variants", 10, 2);
+        let at = Utc::now();
+        j.append(7, at, &decisions()).unwrap();
+        let lines = j.recent(&JournalQuery { limit: 10, ..Default::default() }).unwrap();
+        assert_eq!(lines.len(), 3);
+
+        // Newest first: the last decision of the cycle leads.
+        let rejected = &lines[0];
+        assert_eq!(rejected["decision"], "risk_rejected");
+        assert_eq!(rejected["market_id"], "m3");
+        assert_eq!(rejected["reason"]["kind"], "max_positions_reached");
+        assert_eq!(rejected["reason"]["limit"], 20);
+        assert_eq!(rejected["reason_text"], "20 positions at 20 limit");
+        assert_eq!(rejected["reason_code"], "max_positions_reached");
+        assert_eq!(rejected["bet"]["edge"]["market"]["question"], "Will it rain?");
+
+        let kelly = &lines[1];
+        assert_eq!(kelly["decision"], "kelly_rejected");
+        assert_eq!(kelly["edge"]["estimate"]["reasoning"], "wet season");
+        assert!(kelly.get("reason_text").is_none() && kelly.get("reason_code").is_none());
+
+        let selected = &lines[2];
+        assert_eq!(selected["decision"], "selected");
+        assert_eq!(selected["adjusted_amount"], 45.0);
+        assert_eq!(selected["cycle"], 7);
+        assert_eq!(selected["schema_version"], JOURNAL_VERSION);
+        assert_eq!(selected["at"].as_str().unwrap().parse::<DateTime<Utc>>().unwrap(), at);
+        cleanup(&j);
+    }
+
+    #[test]
+    fn test_query_filters_and_limits() {
+        let j = journal("filters", 10, 2);
+        for cycle in 1..=3 {
+            j.append(cycle, Utc::now(), &decisions()).unwrap();
+        }
+        let by_market = j.recent(&JournalQuery { market_id: Some("m2".into()), limit: 10, ..Default::default() }).unwrap();
+        assert_eq!(by_market.iter().map(|l| l["cycle"].as_u64().unwrap()).collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        let by_cycle = j.recent(&JournalQuery { cycle: Some(2), limit: 10, ..Default::default() }).unwrap();
+        assert_eq!(by_cycle.len(), 3);
+        assert!(by_cycle.iter().all(|l| l["cycle"] == 2));
+
+        let both = JournalQuery { market_id: Some("m1".into()), cycle: Some(1), limit: 10 };
+        assert_eq!(j.recent(&both).unwrap().len(), 1);
+        assert_eq!(j.recent(&JournalQuery { limit: 4, ..Default::default() }).unwrap().len(), 4);
+        cleanup(&j);
+    }
+
+    #[test]
+    fn test_rotates_by_age_and_reads_across_files() {
+        let j = journal("age", 10, 2);
+        let start = Utc::now() - Duration::days(20);
+        j.append(1, start, &decisions()).unwrap();
+        j.append(2, start + Duration::days(8), &decisions()).unwrap();
+        j.append(3, start + Duration::days(16), &decisions()).unwrap();
+        assert!(j.rotated(1).exists() && j.rotated(2).exists());
+
+        // A fourth rotation drops the oldest file.
+        j.append(4, start + Duration::days(24), &decisions()).unwrap();
+        let cycles: Vec<u64> = j
+            .recent(&JournalQuery { market_id: Some("m1".into()), limit: 10, ..Default::default() })
+            .unwrap()
+            .iter()
+            .map(|l| l["cycle"].as_u64().unwrap())
+            .collect();
+        assert_eq!(cycles, vec![4, 3, 2]);
+        cleanup(&j);
+    }
+
+    #[test]
+    fn test_rejection_summary_counts_latest_cycles() {
+        let j = journal("summary", 10, 2);
+        assert_eq!(j.rejection_summary(5).unwrap(), RejectionSummary::default());
+
+        let start = Utc::now() - Duration::days(20);
+        let stake = DecisionRecord::StakeRejected {
+            bet: bet("m4"),
+            reason: crate::strategy::constraints::StakeRejection::BelowMinStake { stake: dec!(0.5), min_stake: dec!(1) },
+        };
+        j.append(1, start, &decisions()).unwrap();
+        // Cycle 3 is old enough a week later to rotate cycles 1 and 2 out.
+        j.append(2, start + Duration::days(1), &[decisions().remove(2), stake]).unwrap();
+        j.append(3, start + Duration::days(9), &decisions()).unwrap();
+
+        let latest_two = j.rejection_summary(2).unwrap();
+        assert_eq!((latest_two.from_cycle, latest_two.to_cycle), (Some(2), Some(3)));
+        assert_eq!(latest_two.total, 3);
+        assert_eq!(latest_two.by_code["max_positions_reached"], 2);
+        assert_eq!(latest_two.by_code["below_min_stake"], 1);
+
+        let latest = j.rejection_summary(0).unwrap();
+        assert_eq!((latest.from_cycle, latest.to_cycle, latest.total), (Some(3), Some(3), 1));
+        assert_eq!(j.rejection_summary(10).unwrap().total, 4);
+        cleanup(&j);
+    }
+
+    #[test]
+    fn test_rotates_by_size() {
+        let j = journal("size", 0, 1);
+        j.append(1, Utc::now(), &decisions()).unwrap();
+        j.append(2, Utc::now(), &decisions()).unwrap();
+        let live = fs::read_to_string(j.path()).unwrap();
+        assert_eq!(live.lines().count(), 3);
+        assert!(live.lines().all(|l| l.contains("\"cycle\":2")));
+        assert!(j.rotated(1).exists());
+        cleanup(&j);
+    }
+}
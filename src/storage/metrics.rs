@@ -0,0 +1,807 @@
+//! Cycle metrics history and hourly rollups (SQLite).
+//!
+//! Every cycle appends one raw row to `cycle_metrics` and, in the same
+//! transaction, folds it into the matching `hourly_rollups` row. Long-range
+//! dashboard charts read one row per hour instead of every cycle.
+//!
+//! The rollup table is derived data: [`MetricsStore::rebuild_rollups`]
+//! regenerates it from the raw rows (`oracle rebuild-rollups`).
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use futures::stream::{BoxStream, StreamExt};
+use sqlx::Row;
+use tracing::info;
+
+/// Seconds per rollup bucket.
+pub const HOUR_SECS: i64 = 3600;
+
+/// Schema version 1 (see [`super::migrations::METRICS_STEPS`]).
+pub(super) const SCHEMA_V1: &str = r#"
+CREATE TABLE IF NOT EXISTS cycle_metrics (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts INTEGER NOT NULL,
+    bankroll REAL NOT NULL,
+    mana_bankroll REAL NOT NULL,
+    realized_pnl REAL NOT NULL,
+    realized_mana_pnl REAL NOT NULL,
+    llm_cost REAL NOT NULL,
+    data_cost REAL NOT NULL,
+    commission_cost REAL NOT NULL,
+    other_cost REAL NOT NULL,
+    bets_placed INTEGER NOT NULL,
+    bets_failed INTEGER NOT NULL,
+    markets_scanned INTEGER NOT NULL,
+    exposure REAL NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_cycle_metrics_ts ON cycle_metrics(ts);
+
+CREATE TABLE IF NOT EXISTS hourly_rollups (
+    hour INTEGER PRIMARY KEY,
+    cycles INTEGER NOT NULL,
+    last_ts INTEGER NOT NULL,
+    last_id INTEGER NOT NULL,
+    bankroll_close REAL NOT NULL,
+    mana_bankroll_close REAL NOT NULL,
+    realized_pnl REAL NOT NULL,
+    realized_mana_pnl REAL NOT NULL,
+    llm_cost REAL NOT NULL,
+    data_cost REAL NOT NULL,
+    commission_cost REAL NOT NULL,
+    other_cost REAL NOT NULL,
+    bets_placed INTEGER NOT NULL,
+    bets_failed INTEGER NOT NULL,
+    markets_scanned INTEGER NOT NULL,
+    exposure_hwm REAL NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS decisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts INTEGER NOT NULL,
+    cycle INTEGER NOT NULL,
+    platform TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    category TEXT NOT NULL,
+    price_yes REAL NOT NULL,
+    metaculus_prob REAL,
+    manifold_prob REAL,
+    estimate REAL NOT NULL,
+    confidence REAL NOT NULL,
+    edge REAL,
+    side TEXT,
+    decision TEXT NOT NULL,
+    bet_fraction REAL,
+    resolved_yes INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_decisions_market ON decisions(platform, market_id);
+"#;
+
+/// Schema version 2: operator tags on decisions, comma-separated.
+pub(super) const SCHEMA_V2: &str = r#"
+ALTER TABLE decisions ADD COLUMN tags TEXT NOT NULL DEFAULT '';
+"#;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One cycle's contribution to the metrics history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleMetrics {
+    pub timestamp: DateTime<Utc>,
+    /// AUD bankroll at cycle end.
+    pub bankroll: f64,
+    /// Mana bankroll at cycle end.
+    pub mana_bankroll: f64,
+    /// AUD P&L realised since the previous cycle.
+    pub realized_pnl: f64,
+    /// Mana P&L realised since the previous cycle.
+    pub realized_mana_pnl: f64,
+    pub llm_cost: f64,
+    pub data_cost: f64,
+    pub commission_cost: f64,
+    pub other_cost: f64,
+    pub bets_placed: i64,
+    pub bets_failed: i64,
+    pub markets_scanned: i64,
+    /// Total stake in open bets at cycle end.
+    pub exposure: f64,
+}
+
+/// A bucket of aggregated cycle metrics (an hourly rollup, or a merge of
+/// several rollups / raw cycles for a chart resolution).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsPoint {
+    /// Bucket start (RFC 3339).
+    pub timestamp: String,
+    #[serde(skip)]
+    pub bucket: i64,
+    pub cycles: i64,
+    pub bankroll_close: f64,
+    pub mana_bankroll_close: f64,
+    pub realized_pnl: f64,
+    pub realized_mana_pnl: f64,
+    pub llm_cost: f64,
+    pub data_cost: f64,
+    pub commission_cost: f64,
+    pub other_cost: f64,
+    pub bets_placed: i64,
+    pub bets_failed: i64,
+    pub markets_scanned: i64,
+    pub exposure_hwm: f64,
+}
+
+impl MetricsPoint {
+    fn from_cycle(m: &CycleMetrics, bucket: i64) -> Self {
+        Self {
+            timestamp: bucket_timestamp(bucket),
+            bucket,
+            cycles: 1,
+            bankroll_close: m.bankroll,
+            mana_bankroll_close: m.mana_bankroll,
+            realized_pnl: m.realized_pnl,
+            realized_mana_pnl: m.realized_mana_pnl,
+            llm_cost: m.llm_cost,
+            data_cost: m.data_cost,
+            commission_cost: m.commission_cost,
+            other_cost: m.other_cost,
+            bets_placed: m.bets_placed,
+            bets_failed: m.bets_failed,
+            markets_scanned: m.markets_scanned,
+            exposure_hwm: m.exposure,
+        }
+    }
+
+    /// Fold a later point into this one (closes take the later value).
+    fn absorb(&mut self, later: &MetricsPoint) {
+        self.cycles += later.cycles;
+        self.bankroll_close = later.bankroll_close;
+        self.mana_bankroll_close = later.mana_bankroll_close;
+        self.realized_pnl += later.realized_pnl;
+        self.realized_mana_pnl += later.realized_mana_pnl;
+        self.llm_cost += later.llm_cost;
+        self.data_cost += later.data_cost;
+        self.commission_cost += later.commission_cost;
+        self.other_cost += later.other_cost;
+        self.bets_placed += later.bets_placed;
+        self.bets_failed += later.bets_failed;
+        self.markets_scanned += later.markets_scanned;
+        self.exposure_hwm = self.exposure_hwm.max(later.exposure_hwm);
+    }
+}
+
+/// One strategy decision for one market in one cycle (the `decisions`
+/// table). Stakes are stored only as a fraction of bankroll.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRow {
+    pub timestamp: DateTime<Utc>,
+    pub cycle: u64,
+    pub platform: String,
+    pub market_id: String,
+    pub category: String,
+    pub price_yes: f64,
+    pub metaculus_prob: Option<f64>,
+    pub manifold_prob: Option<f64>,
+    pub estimate: f64,
+    pub confidence: f64,
+    /// Actionable edge, when one was detected.
+    pub edge: Option<f64>,
+    pub side: Option<String>,
+    /// `no_edge`, `kelly_rejected`, `stake_rejected`, `risk_rejected` or
+    /// `selected`.
+    pub decision: String,
+    /// Sized stake as a fraction of the bankroll it was sized against.
+    pub bet_fraction: Option<f64>,
+    /// Filled in once the market resolves (`Some(true)` = YES).
+    pub resolved_yes: Option<bool>,
+    /// Operator tags of the market.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Which table a history response was served from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySource {
+    Rollup,
+    Raw,
+}
+
+/// Metrics history over a time range at a chart resolution.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsHistory {
+    pub source: HistorySource,
+    pub resolution_secs: i64,
+    pub points: Vec<MetricsPoint>,
+}
+
+fn bucket_timestamp(bucket: i64) -> String {
+    Utc.timestamp_opt(bucket, 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339()
+}
+
+/// Merge chronologically ordered points into buckets of `resolution_secs`.
+/// A resolution of 0 keeps the points as they are.
+fn bucketize(points: Vec<MetricsPoint>, resolution_secs: i64) -> Vec<MetricsPoint> {
+    if resolution_secs <= 0 {
+        return points;
+    }
+    let mut out: Vec<MetricsPoint> = Vec::new();
+    for mut p in points {
+        let bucket = p.bucket - p.bucket.rem_euclid(resolution_secs);
+        match out.last_mut() {
+            Some(last) if last.bucket == bucket => last.absorb(&p),
+            _ => {
+                p.bucket = bucket;
+                p.timestamp = bucket_timestamp(bucket);
+                out.push(p);
+            }
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Store
+// ---------------------------------------------------------------------------
+
+/// SQLite-backed metrics history. Cheap to clone (wraps a pool).
+#[derive(Debug, Clone)]
+pub struct MetricsStore {
+    pool: SqlitePool,
+}
+
+impl MetricsStore {
+    /// Open (creating if needed) the metrics database at `path`.
+    pub async fn open(path: &str) -> Result<Self> {
+        let opts = SqliteConnectOptions::from_str(&format!("sqlite://{path}"))
+            .with_context(|| format!("Invalid metrics database path: {path}"))?
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(opts)
+            .await
+            .with_context(|| format!("Failed to open metrics database {path}"))?;
+        Self::init(pool, path).await
+    }
+
+    /// In-memory store (single connection, so every query sees the same DB).
+    pub async fn open_in_memory() -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .context("Failed to open in-memory metrics database")?;
+        Self::init(pool, "in-memory metrics database").await
+    }
+
+    async fn init(pool: SqlitePool, what: &str) -> Result<Self> {
+        super::migrations::migrate_metrics(&pool, what).await?;
+        Ok(Self { pool })
+    }
+
+    /// Append one cycle and fold it into its hourly rollup.
+    pub async fn record_cycle(&self, m: &CycleMetrics) -> Result<()> {
+        let ts = m.timestamp.timestamp();
+        let hour = ts - ts.rem_euclid(HOUR_SECS);
+        let mut tx = self.pool.begin().await?;
+
+        let id = sqlx::query(
+            "INSERT INTO cycle_metrics (ts, bankroll, mana_bankroll, realized_pnl, realized_mana_pnl,
+                llm_cost, data_cost, commission_cost, other_cost,
+                bets_placed, bets_failed, markets_scanned, exposure)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(ts)
+        .bind(m.bankroll)
+        .bind(m.mana_bankroll)
+        .bind(m.realized_pnl)
+        .bind(m.realized_mana_pnl)
+        .bind(m.llm_cost)
+        .bind(m.data_cost)
+        .bind(m.commission_cost)
+        .bind(m.other_cost)
+        .bind(m.bets_placed)
+        .bind(m.bets_failed)
+        .bind(m.markets_scanned)
+        .bind(m.exposure)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert cycle metrics")?
+        .last_insert_rowid();
+
+        // Closes follow the latest (ts, id) in the hour so that an
+        // out-of-order insert cannot overwrite a later close.
+        sqlx::query(
+            "INSERT INTO hourly_rollups (hour, cycles, last_ts, last_id, bankroll_close, mana_bankroll_close,
+                realized_pnl, realized_mana_pnl, llm_cost, data_cost, commission_cost, other_cost,
+                bets_placed, bets_failed, markets_scanned, exposure_hwm)
+             VALUES (?, 1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(hour) DO UPDATE SET
+                cycles = cycles + 1,
+                bankroll_close = CASE WHEN (excluded.last_ts, excluded.last_id) > (last_ts, last_id)
+                    THEN excluded.bankroll_close ELSE bankroll_close END,
+                mana_bankroll_close = CASE WHEN (excluded.last_ts, excluded.last_id) > (last_ts, last_id)
+                    THEN excluded.mana_bankroll_close ELSE mana_bankroll_close END,
+                last_id = CASE WHEN (excluded.last_ts, excluded.last_id) > (last_ts, last_id)
+                    THEN excluded.last_id ELSE last_id END,
+                last_ts = MAX(last_ts, excluded.last_ts),
+                realized_pnl = realized_pnl + excluded.realized_pnl,
+                realized_mana_pnl = realized_mana_pnl + excluded.realized_mana_pnl,
+                llm_cost = llm_cost + excluded.llm_cost,
+                data_cost = data_cost + excluded.data_cost,
+                commission_cost = commission_cost + excluded.commission_cost,
+                other_cost = other_cost + excluded.other_cost,
+                bets_placed = bets_placed + excluded.bets_placed,
+                bets_failed = bets_failed + excluded.bets_failed,
+                markets_scanned = markets_scanned + excluded.markets_scanned,
+                exposure_hwm = MAX(exposure_hwm, excluded.exposure_hwm)",
+        )
+        .bind(hour)
+        .bind(ts)
+        .bind(id)
+        .bind(m.bankroll)
+        .bind(m.mana_bankroll)
+        .bind(m.realized_pnl)
+        .bind(m.realized_mana_pnl)
+        .bind(m.llm_cost)
+        .bind(m.data_cost)
+        .bind(m.commission_cost)
+        .bind(m.other_cost)
+        .bind(m.bets_placed)
+        .bind(m.bets_failed)
+        .bind(m.markets_scanned)
+        .bind(m.exposure)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update hourly rollup")?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Regenerate `hourly_rollups` from `cycle_metrics`. Idempotent.
+    /// Returns the number of hourly rows written.
+    pub async fn rebuild_rollups(&self) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM hourly_rollups")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear hourly rollups")?;
+        let written = sqlx::query(
+            "INSERT INTO hourly_rollups (hour, cycles, last_ts, last_id, bankroll_close, mana_bankroll_close,
+                realized_pnl, realized_mana_pnl, llm_cost, data_cost, commission_cost, other_cost,
+                bets_placed, bets_failed, markets_scanned, exposure_hwm)
+             SELECT g.hour, g.cycles, c.ts, c.id, c.bankroll, c.mana_bankroll,
+                g.realized_pnl, g.realized_mana_pnl, g.llm_cost, g.data_cost, g.commission_cost, g.other_cost,
+                g.bets_placed, g.bets_failed, g.markets_scanned, g.exposure_hwm
+             FROM (
+                SELECT ts - (ts % 3600) AS hour, COUNT(*) AS cycles,
+                    SUM(realized_pnl) AS realized_pnl, SUM(realized_mana_pnl) AS realized_mana_pnl,
+                    SUM(llm_cost) AS llm_cost, SUM(data_cost) AS data_cost,
+                    SUM(commission_cost) AS commission_cost, SUM(other_cost) AS other_cost,
+                    SUM(bets_placed) AS bets_placed, SUM(bets_failed) AS bets_failed,
+                    SUM(markets_scanned) AS markets_scanned, MAX(exposure) AS exposure_hwm
+                FROM cycle_metrics GROUP BY hour
+             ) g
+             JOIN cycle_metrics c ON c.id = (
+                SELECT id FROM cycle_metrics
+                WHERE ts - (ts % 3600) = g.hour
+                ORDER BY ts DESC, id DESC LIMIT 1
+             )",
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to rebuild hourly rollups")?
+        .rows_affected();
+        tx.commit().await?;
+
+        info!(hours = written, "Hourly metrics rollups rebuilt");
+        Ok(written)
+    }
+
+    /// Hourly rollups with `hour` in `[from, to)`, oldest first.
+    pub async fn rollups(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<MetricsPoint>> {
+        let rows = sqlx::query(
+            "SELECT hour, cycles, bankroll_close, mana_bankroll_close, realized_pnl, realized_mana_pnl,
+                llm_cost, data_cost, commission_cost, other_cost,
+                bets_placed, bets_failed, markets_scanned, exposure_hwm
+             FROM hourly_rollups WHERE hour >= ? AND hour < ? ORDER BY hour",
+        )
+        .bind(from.timestamp() - from.timestamp().rem_euclid(HOUR_SECS))
+        .bind(to.timestamp())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to query hourly rollups")?;
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let bucket: i64 = r.get("hour");
+                MetricsPoint {
+                    timestamp: bucket_timestamp(bucket),
+                    bucket,
+                    cycles: r.get("cycles"),
+                    bankroll_close: r.get("bankroll_close"),
+                    mana_bankroll_close: r.get("mana_bankroll_close"),
+                    realized_pnl: r.get("realized_pnl"),
+                    realized_mana_pnl: r.get("realized_mana_pnl"),
+                    llm_cost: r.get("llm_cost"),
+                    data_cost: r.get("data_cost"),
+                    commission_cost: r.get("commission_cost"),
+                    other_cost: r.get("other_cost"),
+                    bets_placed: r.get("bets_placed"),
+                    bets_failed: r.get("bets_failed"),
+                    markets_scanned: r.get("markets_scanned"),
+                    exposure_hwm: r.get("exposure_hwm"),
+                }
+            })
+            .collect())
+    }
+
+    /// Raw cycle rows with `ts` in `[from, to)`, oldest first.
+    pub async fn raw(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<CycleMetrics>> {
+        let rows = sqlx::query(
+            "SELECT ts, bankroll, mana_bankroll, realized_pnl, realized_mana_pnl,
+                llm_cost, data_cost, commission_cost, other_cost,
+                bets_placed, bets_failed, markets_scanned, exposure
+             FROM cycle_metrics WHERE ts >= ? AND ts < ? ORDER BY ts, id",
+        )
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to query cycle metrics")?;
+
+        Ok(rows
+            .iter()
+            .map(|r| CycleMetrics {
+                timestamp: Utc.timestamp_opt(r.get("ts"), 0).single().unwrap_or_else(Utc::now),
+                bankroll: r.get("bankroll"),
+                mana_bankroll: r.get("mana_bankroll"),
+                realized_pnl: r.get("realized_pnl"),
+                realized_mana_pnl: r.get("realized_mana_pnl"),
+                llm_cost: r.get("llm_cost"),
+                data_cost: r.get("data_cost"),
+                commission_cost: r.get("commission_cost"),
+                other_cost: r.get("other_cost"),
+                bets_placed: r.get("bets_placed"),
+                bets_failed: r.get("bets_failed"),
+                markets_scanned: r.get("markets_scanned"),
+                exposure: r.get("exposure"),
+            })
+            .collect())
+    }
+
+    /// Metrics over `[from, to)` at `resolution_secs` per point.
+    ///
+    /// Resolutions of an hour or more are served from the rollup table;
+    /// finer resolutions aggregate raw cycle rows (0 = one point per cycle).
+    pub async fn history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        resolution_secs: i64,
+    ) -> Result<MetricsHistory> {
+        let (source, points) = if resolution_secs >= HOUR_SECS {
+            (HistorySource::Rollup, self.rollups(from, to).await?)
+        } else {
+            let points = self
+                .raw(from, to)
+                .await?
+                .iter()
+                .map(|m| MetricsPoint::from_cycle(m, m.timestamp.timestamp()))
+                .collect();
+            (HistorySource::Raw, points)
+        };
+        Ok(MetricsHistory {
+            source,
+            resolution_secs,
+            points: bucketize(points, resolution_secs),
+        })
+    }
+
+    /// Append one cycle's decisions.
+    pub async fn record_decisions(&self, rows: &[DecisionRow]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for d in rows {
+            sqlx::query(
+                "INSERT INTO decisions (ts, cycle, platform, market_id, category, price_yes,
+                    metaculus_prob, manifold_prob, estimate, confidence, edge, side, decision,
+                    bet_fraction, resolved_yes, tags)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(d.timestamp.timestamp())
+            .bind(d.cycle as i64)
+            .bind(&d.platform)
+            .bind(&d.market_id)
+            .bind(&d.category)
+            .bind(d.price_yes)
+            .bind(d.metaculus_prob)
+            .bind(d.manifold_prob)
+            .bind(d.estimate)
+            .bind(d.confidence)
+            .bind(d.edge)
+            .bind(&d.side)
+            .bind(&d.decision)
+            .bind(d.bet_fraction)
+            .bind(d.resolved_yes)
+            .bind(d.tags.join(","))
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert decision")?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Set the outcome on every unresolved decision for a market.
+    /// Returns the number of rows updated.
+    pub async fn record_resolution(&self, platform: &str, market_id: &str, resolved_yes: bool) -> Result<u64> {
+        let updated = sqlx::query(
+            "UPDATE decisions SET resolved_yes = ?
+             WHERE platform = ? AND market_id = ? AND resolved_yes IS NULL",
+        )
+        .bind(resolved_yes)
+        .bind(platform)
+        .bind(market_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record decision resolution")?
+        .rows_affected();
+        Ok(updated)
+    }
+
+    /// All decisions, oldest first, streamed row by row.
+    pub fn decisions(&self) -> BoxStream<'_, Result<DecisionRow>> {
+        sqlx::query(
+            "SELECT ts, cycle, platform, market_id, category, price_yes, metaculus_prob,
+                manifold_prob, estimate, confidence, edge, side, decision, bet_fraction, resolved_yes, tags
+             FROM decisions ORDER BY id",
+        )
+        .fetch(&self.pool)
+        .map(|row| Ok(decision_from_row(&row.context("Failed to read decision")?)))
+        .boxed()
+    }
+
+    /// One market's decisions, oldest first.
+    pub async fn market_decisions(&self, platform: &str, market_id: &str) -> Result<Vec<DecisionRow>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {DECISION_COLUMNS} FROM decisions WHERE platform = ? AND market_id = ? ORDER BY id"
+        ))
+        .bind(platform)
+        .bind(market_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read market decisions")?;
+        Ok(rows.iter().map(decision_from_row).collect())
+    }
+
+    /// Delete one market's decisions (after archiving them).
+    /// Returns the number of rows deleted.
+    pub async fn delete_market_decisions(&self, platform: &str, market_id: &str) -> Result<u64> {
+        let deleted = sqlx::query("DELETE FROM decisions WHERE platform = ? AND market_id = ?")
+            .bind(platform)
+            .bind(market_id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete market decisions")?
+            .rows_affected();
+        Ok(deleted)
+    }
+
+    /// Markets with no resolved decision whose latest decision is older
+    /// than `before`, as (platform, market_id).
+    pub async fn stale_markets(&self, before: DateTime<Utc>) -> Result<Vec<(String, String)>> {
+        let rows = sqlx::query(
+            "SELECT platform, market_id FROM decisions
+             GROUP BY platform, market_id
+             HAVING MAX(ts) < ? AND COUNT(resolved_yes) = 0
+             ORDER BY platform, market_id",
+        )
+        .bind(before.timestamp())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to query stale markets")?;
+        Ok(rows.iter().map(|r| (r.get("platform"), r.get("market_id"))).collect())
+    }
+}
+
+const DECISION_COLUMNS: &str = "ts, cycle, platform, market_id, category, price_yes, metaculus_prob,
+    manifold_prob, estimate, confidence, edge, side, decision, bet_fraction, resolved_yes, tags";
+
+fn decision_from_row(r: &sqlx::sqlite::SqliteRow) -> DecisionRow {
+    DecisionRow {
+        timestamp: Utc.timestamp_opt(r.get("ts"), 0).single().unwrap_or_else(Utc::now),
+        cycle: r.get::<i64, _>("cycle") as u64,
+        platform: r.get("platform"),
+        market_id: r.get("market_id"),
+        category: r.get("category"),
+        price_yes: r.get("price_yes"),
+        metaculus_prob: r.get("metaculus_prob"),
+        manifold_prob: r.get("manifold_prob"),
+        estimate: r.get("estimate"),
+        confidence: r.get("confidence"),
+        edge: r.get("edge"),
+        side: r.get("side"),
+        decision: r.get("decision"),
+        bet_fraction: r.get("bet_fraction"),
+        resolved_yes: r.get("resolved_yes"),
+        tags: r.get::<String, _>("tags").split(',').filter(|t| !t.is_empty()).map(String::from).collect(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// Deterministic synthetic history: ~7 cycles/hour for 3 days with
+    /// irregular spacing, drifting bankroll and sporadic bets.
+    fn synthetic_history() -> Vec<CycleMetrics> {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let mut t = start;
+        let mut bankroll = 100.0;
+        let mut out = Vec::new();
+        for i in 0..500i64 {
+            t += Duration::seconds(300 + (i * 137) % 600);
+            bankroll += ((i % 7) as f64 - 3.0) * 0.25;
+            out.push(CycleMetrics {
+                timestamp: t,
+                bankroll,
+                mana_bankroll: 1000.0 + i as f64,
+                realized_pnl: if i % 11 == 0 { 1.5 } else { 0.0 },
+                realized_mana_pnl: if i % 13 == 0 { -4.0 } else { 0.0 },
+                llm_cost: 0.01 * (1 + i % 3) as f64,
+                data_cost: 0.002,
+                commission_cost: if i % 5 == 0 { 0.05 } else { 0.0 },
+                other_cost: 0.0,
+                bets_placed: i % 4,
+                bets_failed: i64::from(i % 9 == 0),
+                markets_scanned: 80,
+                exposure: ((i * 31) % 200) as f64,
+            });
+        }
+        out
+    }
+
+    fn assert_points_eq(a: &[MetricsPoint], b: &[MetricsPoint]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert_eq!(x.bucket, y.bucket);
+            assert_eq!(x.cycles, y.cycles);
+            assert_eq!(x.bets_placed, y.bets_placed);
+            assert_eq!(x.bets_failed, y.bets_failed);
+            assert_eq!(x.markets_scanned, y.markets_scanned);
+            for (l, r) in [
+                (x.bankroll_close, y.bankroll_close),
+                (x.mana_bankroll_close, y.mana_bankroll_close),
+                (x.realized_pnl, y.realized_pnl),
+                (x.realized_mana_pnl, y.realized_mana_pnl),
+                (x.llm_cost, y.llm_cost),
+                (x.data_cost, y.data_cost),
+                (x.commission_cost, y.commission_cost),
+                (x.other_cost, y.other_cost),
+                (x.exposure_hwm, y.exposure_hwm),
+            ] {
+                assert!((l - r).abs() < 1e-9, "{l} != {r} at bucket {}", x.bucket);
+            }
+        }
+    }
+
+    fn whole_range() -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_incremental_and_rebuilt_rollups_agree() {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        for m in synthetic_history() {
+            store.record_cycle(&m).await.unwrap();
+        }
+        let (from, to) = whole_range();
+        let incremental = store.rollups(from, to).await.unwrap();
+        assert!(incremental.len() > 48, "expected several days of hourly rows");
+
+        let written = store.rebuild_rollups().await.unwrap();
+        assert_eq!(written as usize, incremental.len());
+        let rebuilt = store.rollups(from, to).await.unwrap();
+        assert_points_eq(&incremental, &rebuilt);
+
+        // Idempotent: a second rebuild changes nothing.
+        store.rebuild_rollups().await.unwrap();
+        assert_points_eq(&rebuilt, &store.rollups(from, to).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rollups_match_raw_aggregation() {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let history = synthetic_history();
+        for m in &history {
+            store.record_cycle(m).await.unwrap();
+        }
+        let (from, to) = whole_range();
+
+        // Aggregating raw cycles in Rust gives the same hourly points.
+        let from_raw = store.history(from, to, HOUR_SECS - 1).await.unwrap();
+        assert_eq!(from_raw.source, HistorySource::Raw);
+        let expected = bucketize(
+            history
+                .iter()
+                .map(|m| MetricsPoint::from_cycle(m, m.timestamp.timestamp()))
+                .collect(),
+            HOUR_SECS,
+        );
+        let from_rollup = store.history(from, to, HOUR_SECS).await.unwrap();
+        assert_eq!(from_rollup.source, HistorySource::Rollup);
+        assert_points_eq(&expected, &from_rollup.points);
+    }
+
+    #[tokio::test]
+    async fn test_history_merges_rollups_to_coarser_resolution() {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let history = synthetic_history();
+        for m in &history {
+            store.record_cycle(m).await.unwrap();
+        }
+        let (from, to) = whole_range();
+        let daily = store.history(from, to, 24 * HOUR_SECS).await.unwrap();
+
+        assert_eq!(daily.source, HistorySource::Rollup);
+        assert_eq!(daily.points.iter().map(|p| p.cycles).sum::<i64>(), history.len() as i64);
+        let last = daily.points.last().unwrap();
+        assert_eq!(last.bankroll_close, history.last().unwrap().bankroll);
+        assert_eq!(
+            daily.points.iter().map(|p| p.exposure_hwm).fold(0.0, f64::max),
+            history.iter().map(|m| m.exposure).fold(0.0, f64::max)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_short_range_served_from_raw() {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let history = synthetic_history();
+        for m in history.iter().take(10) {
+            store.record_cycle(m).await.unwrap();
+        }
+        let (from, to) = whole_range();
+        let raw = store.history(from, to, 0).await.unwrap();
+        assert_eq!(raw.source, HistorySource::Raw);
+        assert_eq!(raw.points.len(), 10);
+        assert!(raw.points.iter().all(|p| p.cycles == 1));
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_insert_keeps_latest_close() {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let mut history = synthetic_history();
+        history.truncate(3);
+        let t0 = Utc.with_ymd_and_hms(2026, 3, 1, 5, 0, 0).unwrap();
+        history[0].timestamp = t0 + Duration::minutes(50);
+        history[1].timestamp = t0 + Duration::minutes(10);
+        history[2].timestamp = t0 + Duration::minutes(30);
+        for m in &history {
+            store.record_cycle(m).await.unwrap();
+        }
+        let (from, to) = whole_range();
+        let rows = store.rollups(from, to).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].bankroll_close, history[0].bankroll);
+
+        store.rebuild_rollups().await.unwrap();
+        assert_eq!(store.rollups(from, to).await.unwrap()[0].bankroll_close, history[0].bankroll);
+    }
+}
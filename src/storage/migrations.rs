@@ -0,0 +1,431 @@
+//! Schema versions of persisted artifacts and the upgrades between them.
+//!
+//! Every artifact records the layout version it was written with: JSON
+//! documents in a version field, the metrics and storage databases in
+//! SQLite's `user_version`. Loading upgrades an older artifact one registered step
+//! at a time, and files from before versioning read as version 0. An
+//! artifact from a newer binary is refused with [`NewerSchema`] rather
+//! than read and later re-saved without the fields this binary doesn't
+//! know. Saves always write the current version.
+//!
+//! To change a layout: bump the artifact's version, append a step from
+//! the previous version to [`STEPS`] (or [`METRICS_STEPS`] /
+//! [`STORAGE_STEPS`]), and add a
+//! fixture of the previous version under `tests/fixtures/storage/` with a
+//! test asserting the upgraded result.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tracing::info;
+
+use super::archive::ARCHIVE_VERSION;
+use super::calibration::CALIBRATION_VERSION;
+use super::shadow::SHADOW_VERSION;
+use crate::embeddings::store::EMBEDDING_CACHE_VERSION;
+use crate::llm::cache::ESTIMATE_CACHE_VERSION;
+use crate::types::DEFAULT_PLATFORM;
+
+/// Version of the agent state file layout.
+pub const STATE_VERSION: u32 = 2;
+
+/// Version of the archive index layout.
+pub const INDEX_VERSION: u32 = 1;
+
+/// A persisted JSON document with its own version line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+    /// `oracle_state.json`, including every ledger in [`crate::types::AgentState`].
+    State,
+    /// One archived market lifecycle.
+    Lifecycle,
+    /// The archive's `index.json`.
+    ArchiveIndex,
+    /// The estimate calibration store.
+    Calibration,
+    /// The LLM estimate cache.
+    EstimateCache,
+    /// The question embedding cache.
+    EmbeddingCache,
+    /// The shadow canary store.
+    Shadow,
+}
+
+impl Artifact {
+    /// Newest version this binary reads and the one it writes.
+    pub fn current(self) -> u32 {
+        match self {
+            Artifact::State => STATE_VERSION,
+            Artifact::Lifecycle => ARCHIVE_VERSION,
+            Artifact::ArchiveIndex => INDEX_VERSION,
+            Artifact::Calibration => CALIBRATION_VERSION,
+            Artifact::EstimateCache => ESTIMATE_CACHE_VERSION,
+            Artifact::EmbeddingCache => EMBEDDING_CACHE_VERSION,
+            Artifact::Shadow => SHADOW_VERSION,
+        }
+    }
+
+    /// Field holding the version. Lifecycles carried `version` before the
+    /// other artifacts were versioned.
+    pub fn version_key(self) -> &'static str {
+        match self {
+            Artifact::Lifecycle => "version",
+            Artifact::State
+            | Artifact::ArchiveIndex
+            | Artifact::Calibration
+            | Artifact::EstimateCache
+            | Artifact::EmbeddingCache
+            | Artifact::Shadow => "schema_version",
+        }
+    }
+}
+
+/// Refusal to open an artifact written by a newer binary.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "{artifact} has schema v{found} but this binary supports up to v{supported} — \
+     it was written by a newer ORACLE; upgrade the binary before opening it"
+)]
+pub struct NewerSchema {
+    pub artifact: String,
+    pub found: u32,
+    pub supported: u32,
+}
+
+/// One upgrade of a JSON document from `from` to `from + 1`.
+struct Step {
+    artifact: Artifact,
+    from: u32,
+    apply: fn(Value) -> Result<Value>,
+}
+
+/// Every JSON upgrade, in order.
+const STEPS: &[Step] = &[
+    Step { artifact: Artifact::State, from: 0, apply: state_v0_to_v1 },
+    Step { artifact: Artifact::State, from: 1, apply: state_v1_to_v2 },
+    Step { artifact: Artifact::Lifecycle, from: 0, apply: lifecycle_v0_to_v1 },
+    Step { artifact: Artifact::ArchiveIndex, from: 0, apply: index_v0_to_v1 },
+    Step { artifact: Artifact::Calibration, from: 0, apply: calibration_v0_to_v1 },
+    Step { artifact: Artifact::EstimateCache, from: 0, apply: estimate_cache_v0_to_v1 },
+    Step { artifact: Artifact::EmbeddingCache, from: 0, apply: embedding_cache_v0_to_v1 },
+    Step { artifact: Artifact::Shadow, from: 0, apply: shadow_v0_to_v1 },
+];
+
+/// Unversioned state files. Fields added before versioning already
+/// deserialise to their defaults, so the layout is unchanged.
+fn state_v0_to_v1(doc: Value) -> Result<Value> {
+    anyhow::ensure!(doc.is_object(), "state is not a JSON object");
+    Ok(doc)
+}
+
+/// v2 tracks funds per venue. Until then the single AUD bankroll was all
+/// on the default platform; Mana needs no entry, sizing falls back to
+/// `mana_bankroll` for Manifold.
+fn state_v1_to_v2(mut doc: Value) -> Result<Value> {
+    let Value::Object(map) = &mut doc else { anyhow::bail!("state is not a JSON object") };
+    if !map.contains_key("balances") {
+        let mut balances = serde_json::Map::new();
+        if let Some(bankroll) = map.get("bankroll") {
+            balances.insert(DEFAULT_PLATFORM.to_string(), bankroll.clone());
+        }
+        map.insert("balances".to_string(), Value::Object(balances));
+    }
+    Ok(doc)
+}
+
+/// Lifecycles without a version field: same layout as v1.
+fn lifecycle_v0_to_v1(doc: Value) -> Result<Value> {
+    anyhow::ensure!(doc.is_object(), "archive lifecycle is not a JSON object");
+    Ok(doc)
+}
+
+/// The v0 index was a bare `{"platform:id": "file.json"}` map; v1 nests
+/// it under `markets` next to the version.
+fn index_v0_to_v1(doc: Value) -> Result<Value> {
+    let Value::Object(markets) = doc else { anyhow::bail!("archive index is not a JSON object") };
+    Ok(serde_json::json!({ "markets": markets }))
+}
+
+/// The calibration store was versioned from the start; an unstamped
+/// document is read as v1.
+fn calibration_v0_to_v1(doc: Value) -> Result<Value> {
+    anyhow::ensure!(doc.is_object(), "calibration store is not a JSON object");
+    Ok(doc)
+}
+
+/// Same for the estimate cache.
+fn estimate_cache_v0_to_v1(doc: Value) -> Result<Value> {
+    anyhow::ensure!(doc.is_object(), "estimate cache is not a JSON object");
+    Ok(doc)
+}
+
+/// Same for the embedding cache.
+fn embedding_cache_v0_to_v1(doc: Value) -> Result<Value> {
+    anyhow::ensure!(doc.is_object(), "embedding cache is not a JSON object");
+    Ok(doc)
+}
+
+/// Same for the shadow store.
+fn shadow_v0_to_v1(doc: Value) -> Result<Value> {
+    anyhow::ensure!(doc.is_object(), "shadow store is not a JSON object");
+    Ok(doc)
+}
+
+/// Version `doc` was written with; 0 when it predates versioning.
+pub fn version_of(artifact: Artifact, doc: &Value) -> Result<u32> {
+    match doc.get(artifact.version_key()) {
+        None | Some(Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .with_context(|| format!("{} is not a version number: {v}", artifact.version_key())),
+    }
+}
+
+/// Refuse `found` when it is newer than `supported`.
+pub fn ensure_supported(what: &str, found: u32, supported: u32) -> Result<()> {
+    if found > supported {
+        return Err(NewerSchema { artifact: what.to_string(), found, supported }.into());
+    }
+    Ok(())
+}
+
+/// Bring `doc` (read from `what`, for messages) up to the current
+/// version, stamped.
+pub fn upgrade(artifact: Artifact, mut doc: Value, what: &str) -> Result<Value> {
+    let found = version_of(artifact, &doc)?;
+    let current = artifact.current();
+    ensure_supported(what, found, current)?;
+    for from in found..current {
+        let step = STEPS
+            .iter()
+            .find(|s| s.artifact == artifact && s.from == from)
+            .with_context(|| format!("No migration for {artifact:?} from v{from}"))?;
+        doc = (step.apply)(doc).with_context(|| format!("Migrating {what} from v{from}"))?;
+    }
+    if found < current {
+        info!(what, from = found, to = current, "Upgraded storage schema");
+    }
+    stamp(artifact, &mut doc);
+    Ok(doc)
+}
+
+/// Record the current version in `doc`.
+pub fn stamp(artifact: Artifact, doc: &mut Value) {
+    if let Value::Object(map) = doc {
+        map.insert(artifact.version_key().to_string(), artifact.current().into());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metrics database
+// ---------------------------------------------------------------------------
+
+/// SQL taking the metrics database from version `i` to `i + 1`. Version 0
+/// is both a new file and one created before versioning; the v1 schema
+/// only creates what is missing, so either upgrades the same way.
+pub const METRICS_STEPS: &[&str] = &[super::metrics::SCHEMA_V1, super::metrics::SCHEMA_V2];
+
+/// Version of the metrics database layout.
+pub fn metrics_version() -> u32 {
+    METRICS_STEPS.len() as u32
+}
+
+/// Upgrade the metrics database to the current version, or refuse one
+/// from a newer binary. Each step and its version bump commit together.
+pub async fn migrate_metrics(pool: &SqlitePool, what: &str) -> Result<()> {
+    migrate_sqlite(pool, METRICS_STEPS, what).await
+}
+
+/// SQL taking the storage database ([`super::sqlite`]) from version `i` to
+/// `i + 1`.
+pub const STORAGE_STEPS: &[&str] = &[super::sqlite::SCHEMA_V1, super::sqlite::SCHEMA_V2];
+
+/// Version of the storage database layout.
+pub fn storage_version() -> u32 {
+    STORAGE_STEPS.len() as u32
+}
+
+/// Upgrade the storage database to the current version, or refuse one from
+/// a newer binary.
+pub async fn migrate_storage(pool: &SqlitePool, what: &str) -> Result<()> {
+    migrate_sqlite(pool, STORAGE_STEPS, what).await
+}
+
+/// Run the `steps` a database's `user_version` hasn't seen yet.
+async fn migrate_sqlite(pool: &SqlitePool, steps: &[&str], what: &str) -> Result<()> {
+    let found: i64 = sqlx::query_scalar("PRAGMA user_version")
+        .fetch_one(pool)
+        .await
+        .with_context(|| format!("Failed to read {what} schema version"))?;
+    let found = u32::try_from(found).with_context(|| format!("Negative {what} schema version"))?;
+    let current = steps.len() as u32;
+    ensure_supported(what, found, current)?;
+    for from in found..current {
+        let mut tx = pool.begin().await?;
+        sqlx::raw_sql(steps[from as usize])
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Migrating {what} from v{from}"))?;
+        sqlx::raw_sql(&format!("PRAGMA user_version = {}", from + 1))
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+    if found < current {
+        info!(what, from = found, to = current, "Upgraded storage schema");
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> Value {
+        serde_json::from_str(&crate::fixtures::read(&format!("storage/{name}"))).unwrap()
+    }
+
+    #[test]
+    fn test_every_version_has_a_path_from_zero() {
+        for artifact in [
+            Artifact::State,
+            Artifact::Lifecycle,
+            Artifact::ArchiveIndex,
+            Artifact::Calibration,
+            Artifact::EstimateCache,
+            Artifact::EmbeddingCache,
+            Artifact::Shadow,
+        ] {
+            for from in 0..artifact.current() {
+                assert!(
+                    STEPS.iter().any(|s| s.artifact == artifact && s.from == from),
+                    "{artifact:?} has no step from v{from}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_v0_upgrades() {
+        let v0 = fixture("state-v0.json");
+        assert_eq!(version_of(Artifact::State, &v0).unwrap(), 0);
+        let upgraded = upgrade(Artifact::State, v0.clone(), "state").unwrap();
+        assert_eq!(upgraded["schema_version"], STATE_VERSION);
+
+        let state: crate::types::AgentState = serde_json::from_value(upgraded).unwrap();
+        assert_eq!(state.bankroll.to_string(), "93.41");
+        assert_eq!(state.cycle_count, 212);
+        assert_eq!(state.open_bets.len(), 1);
+        assert_eq!(state.open_bets[0].market_id, "kX0fRmWzQcN9pL2aT4vB");
+        // Ledgers added since default to empty.
+        assert!(state.edge_realizations.is_empty());
+        assert!(state.hibernation.is_empty());
+    }
+
+    #[test]
+    fn test_state_v1_upgrades_to_per_platform_balances() {
+        let v1 = fixture("state-v1.json");
+        assert_eq!(version_of(Artifact::State, &v1).unwrap(), 1);
+        let v2 = upgrade(Artifact::State, v1, "state").unwrap();
+        assert_eq!(v2["schema_version"], 2);
+
+        // The whole AUD bankroll lands on the default platform.
+        let state: crate::types::AgentState = serde_json::from_value(v2).unwrap();
+        assert_eq!(state.balances.len(), 1);
+        assert_eq!(state.balances[DEFAULT_PLATFORM].to_string(), "187.62");
+        assert_eq!(state.bankroll_for("betfair"), state.bankroll);
+        assert_eq!(state.bankroll_for("manifold").to_string(), "1312.07");
+
+        // Balances already present are kept as they are.
+        let doc = serde_json::json!({ "schema_version": 1, "bankroll": 50, "balances": { "manifold": 900 } });
+        let v2 = upgrade(Artifact::State, doc, "state").unwrap();
+        assert_eq!(v2["balances"], serde_json::json!({ "manifold": 900 }));
+    }
+
+    #[test]
+    fn test_lifecycle_v0_upgrades() {
+        let v0 = fixture("lifecycle-v0.json");
+        let v1 = upgrade(Artifact::Lifecycle, v0, "lifecycle").unwrap();
+        assert_eq!(v1["version"], 1);
+        let lifecycle: crate::storage::archive::MarketLifecycle = serde_json::from_value(v1).unwrap();
+        assert_eq!(lifecycle.market_id, "kX0fRmWzQcN9pL2aT4vB");
+        assert_eq!(lifecycle.decisions.len(), 1);
+        assert_eq!(lifecycle.resolved_yes, Some(true));
+    }
+
+    #[test]
+    fn test_index_v0_upgrades() {
+        let v1 = upgrade(Artifact::ArchiveIndex, fixture("archive-index-v0.json"), "index").unwrap();
+        assert_eq!(v1["schema_version"], 1);
+        assert_eq!(v1["markets"]["manifold:kX0fRmWzQcN9pL2aT4vB"], "manifold_kX0fRmWzQcN9pL2aT4vB.json");
+        assert_eq!(v1["markets"].as_object().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_newer_version_is_refused() {
+        let doc = serde_json::json!({ "schema_version": STATE_VERSION + 1, "bankroll": "1" });
+        let err = upgrade(Artifact::State, doc, "oracle_state.json").unwrap_err();
+        let newer = err.downcast_ref::<NewerSchema>().expect("NewerSchema");
+        assert_eq!((newer.found, newer.supported), (STATE_VERSION + 1, STATE_VERSION));
+        assert!(err.to_string().contains("upgrade the binary"));
+
+        let bad = serde_json::json!({ "schema_version": "two" });
+        assert!(upgrade(Artifact::State, bad, "state").is_err());
+    }
+
+    #[test]
+    fn test_current_version_passes_through() {
+        let mut doc = serde_json::json!({ "markets": {} });
+        stamp(Artifact::ArchiveIndex, &mut doc);
+        assert_eq!(upgrade(Artifact::ArchiveIndex, doc.clone(), "index").unwrap(), doc);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_database_versioning() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        // An unversioned database from before user_version was set, with data.
+        sqlx::raw_sql(super::super::metrics::SCHEMA_V1).execute(&pool).await.unwrap();
+        sqlx::raw_sql("INSERT INTO hourly_rollups VALUES (3600, 1, 3600, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        migrate_metrics(&pool, "metrics").await.unwrap();
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version").fetch_one(&pool).await.unwrap();
+        assert_eq!(version, i64::from(metrics_version()));
+        let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM hourly_rollups").fetch_one(&pool).await.unwrap();
+        assert_eq!(rows, 1);
+        // Idempotent at the current version.
+        migrate_metrics(&pool, "metrics").await.unwrap();
+
+        sqlx::raw_sql("PRAGMA user_version = 99").execute(&pool).await.unwrap();
+        let err = migrate_metrics(&pool, "metrics").await.unwrap_err();
+        assert!(err.downcast_ref::<NewerSchema>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_storage_database_versioning() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        migrate_storage(&pool, "storage").await.unwrap();
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version").fetch_one(&pool).await.unwrap();
+        assert_eq!(version, i64::from(storage_version()));
+        migrate_storage(&pool, "storage").await.unwrap();
+
+        sqlx::raw_sql("PRAGMA user_version = 99").execute(&pool).await.unwrap();
+        let err = migrate_storage(&pool, "storage").await.unwrap_err();
+        assert!(err.downcast_ref::<NewerSchema>().is_some());
+    }
+}
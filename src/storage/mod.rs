@@ -1,68 +1,282 @@
 //! Persistence layer.
 //!
-//! Saves and loads agent state to/from a JSON file.
-//! SQLite integration can be added later for trade history and calibration
-//! data, but JSON is sufficient for the core state persistence requirement.
+//! Saves and loads agent state to/from a JSON file, and keeps a capped
+//! JSON-lines history of cycle summaries beside it for the dashboard.
+//! The state file is replaced atomically, carries a SHA-256 checksum of
+//! its contents, and rotates into rolling backups (`oracle_state.json.1`
+//! ... `.N`) that loading falls back to when it is missing or damaged.
+//! The agent persists through the [`backend::Storage`] trait: these files
+//! (plus trade and decision logs) or one [`sqlite`] database.
+//! Per-cycle metrics history and hourly rollups live in SQLite
+//! (see [`metrics`]).
+//! Finished markets are moved out of the hot stores into [`archive`].
+//! LLM estimates and their outcomes are kept for scoring in [`calibration`],
+//! the shadow model canary's samples in [`shadow`].
+//! Every strategy decision is logged to a rotating JSONL [`journal`].
+//! Every persisted artifact is versioned; see [`migrations`].
+
+pub mod archive;
+pub mod backend;
+pub mod calibration;
+pub mod journal;
+pub mod metrics;
+pub mod migrations;
+pub mod research;
+pub mod shadow;
+pub mod sqlite;
 
 use anyhow::{Context, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::io::Write;
 use std::path::Path;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::types::AgentState;
+use crate::types::{AgentState, CycleReport};
+use migrations::{Artifact, NewerSchema};
 
 /// Default state file path.
-const DEFAULT_STATE_FILE: &str = "oracle_state.json";
+pub const DEFAULT_STATE_FILE: &str = "oracle_state.json";
+
+/// Default cycle history path, next to the state file.
+pub const DEFAULT_CYCLE_HISTORY_FILE: &str = "oracle_cycles.jsonl";
+
+/// Cycle summaries kept on disk; older lines are dropped on append.
+pub const MAX_CYCLE_HISTORY: usize = 5_000;
+
+/// Rolling backups kept beside the state file.
+pub const DEFAULT_STATE_BACKUPS: usize = 3;
 
-/// Save agent state to a JSON file.
+/// Field of the state file holding the SHA-256 of the rest of it.
+const CHECKSUM_KEY: &str = "checksum";
+
+/// Save agent state to a JSON file, keeping [`DEFAULT_STATE_BACKUPS`].
 pub fn save_state(state: &AgentState, path: Option<&str>) -> Result<()> {
+    save_state_with_backups(state, path, DEFAULT_STATE_BACKUPS)
+}
+
+/// Save agent state to a JSON file, stamped with the current schema
+/// version and checksummed. The file is written beside the old one,
+/// synced and renamed over it, so a crash mid-save leaves the previous
+/// state whole; the previous state becomes `{path}.1`, shifting older
+/// backups up to `{path}.{backups}`.
+pub fn save_state_with_backups(state: &AgentState, path: Option<&str>, backups: usize) -> Result<()> {
     let path = path.unwrap_or(DEFAULT_STATE_FILE);
-    let json = serde_json::to_string_pretty(state)
+    let mut doc = serde_json::to_value(state)
+        .context("Failed to serialise agent state")?;
+    migrations::stamp(Artifact::State, &mut doc);
+    let sum = checksum(&doc);
+    if let Value::Object(map) = &mut doc {
+        map.insert(CHECKSUM_KEY.to_string(), sum.into());
+    }
+    let json = serde_json::to_string_pretty(&doc)
         .context("Failed to serialise agent state")?;
 
-    std::fs::write(path, &json)
-        .context(format!("Failed to write state to {path}"))?;
+    if backups > 0 && Path::new(path).exists() {
+        // A failed rotation costs a backup, not the save.
+        if let Err(e) = rotate_backups(path, backups) {
+            warn!(path, error = %e, "Failed to rotate state backups");
+        }
+    }
+    write_atomic(path, json.as_bytes())?;
 
     debug!(path, bankroll = %state.bankroll, "State saved");
     Ok(())
 }
 
-/// Load agent state from a JSON file.
-/// Returns None if the file doesn't exist (fresh start).
+/// Hex SHA-256 of `doc`'s compact serialisation.
+fn checksum(doc: &Value) -> String {
+    Sha256::digest(doc.to_string().as_bytes()).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The `n`th backup of the state file at `path`.
+pub fn state_backup_path(path: &str, n: usize) -> String {
+    format!("{path}.{n}")
+}
+
+/// Shift `{path}.1..` up one (dropping `{path}.{backups}`) and copy the
+/// current file to `{path}.1`. Copied rather than moved, so the state
+/// file never goes missing between rotation and the new write.
+fn rotate_backups(path: &str, backups: usize) -> std::io::Result<()> {
+    for n in (1..backups).rev() {
+        let from = state_backup_path(path, n);
+        if Path::new(&from).exists() {
+            std::fs::rename(&from, state_backup_path(path, n + 1))?;
+        }
+    }
+    std::fs::copy(path, state_backup_path(path, 1))?;
+    Ok(())
+}
+
+/// Replace `path` with `bytes` via a synced temp file in the same
+/// directory. On failure the temp file is removed and `path` untouched.
+fn write_atomic(path: &str, bytes: &[u8]) -> Result<()> {
+    let tmp = format!("{path}.tmp");
+    let written = (|| -> Result<()> {
+        let mut file = std::fs::File::create(&tmp).context(format!("Failed to create {tmp}"))?;
+        file.write_all(bytes).context(format!("Failed to write {tmp}"))?;
+        file.sync_all().context(format!("Failed to sync {tmp}"))?;
+        std::fs::rename(&tmp, path).context(format!("Failed to replace {path}"))
+    })();
+    if written.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+        return written;
+    }
+    // Persist the rename itself; not every platform can sync a directory.
+    let dir = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    if let Ok(dir) = std::fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Load agent state from a JSON file, upgrading an older schema. When
+/// the file is missing, fails its checksum or doesn't parse, the newest
+/// valid backup is used instead. Returns None if there is neither (fresh
+/// start); refuses a file written by a newer binary.
 pub fn load_state(path: Option<&str>) -> Result<Option<AgentState>> {
     let path = path.unwrap_or(DEFAULT_STATE_FILE);
+    let backups = (1..).map(|n| state_backup_path(path, n)).take_while(|p| Path::new(p).exists());
+    let candidates: Vec<String> = std::iter::once(path.to_string())
+        .filter(|p| Path::new(p).exists())
+        .chain(backups)
+        .collect();
 
-    if !Path::new(path).exists() {
-        info!(path, "No saved state found, starting fresh");
-        return Ok(None);
+    let mut first_error = None;
+    for file in &candidates {
+        match read_state_file(file) {
+            Ok(state) => {
+                if file != path {
+                    warn!(path, backup = %file, "State file missing or invalid — recovered from backup");
+                }
+                info!(
+                    path = %file,
+                    bankroll = %state.bankroll,
+                    cycle_count = state.cycle_count,
+                    trades = state.trades_placed,
+                    "State loaded from disk"
+                );
+                return Ok(Some(state));
+            }
+            // Falling back would drop what the newer binary recorded.
+            Err(e) if e.downcast_ref::<NewerSchema>().is_some() => return Err(e),
+            Err(e) => {
+                warn!(file = %file, error = %format!("{e:#}"), "Invalid state file — trying the next backup");
+                first_error.get_or_insert(e);
+            }
+        }
     }
 
+    match first_error {
+        Some(e) => Err(e.context(format!("No valid state in {path} or its backups"))),
+        None => {
+            info!(path, "No saved state found, starting fresh");
+            Ok(None)
+        }
+    }
+}
+
+/// Read one state file, verifying its checksum when it has one (files
+/// from before checksums don't).
+fn read_state_file(path: &str) -> Result<AgentState> {
     let json = std::fs::read_to_string(path)
         .context(format!("Failed to read state from {path}"))?;
 
-    let state: AgentState = serde_json::from_str(&json)
+    let mut doc: Value = serde_json::from_str(&json)
         .context(format!("Failed to parse state from {path}"))?;
-
-    info!(
-        path,
-        bankroll = %state.bankroll,
-        cycle_count = state.cycle_count,
-        trades = state.trades_placed,
-        "State loaded from disk"
-    );
-
-    Ok(Some(state))
+    let found = migrations::version_of(Artifact::State, &doc)?;
+    migrations::ensure_supported(path, found, migrations::STATE_VERSION)?;
+    if let Some(expected) = doc.as_object_mut().and_then(|map| map.remove(CHECKSUM_KEY)) {
+        let actual = checksum(&doc);
+        anyhow::ensure!(expected.as_str() == Some(actual.as_str()), "State file {path} failed its checksum");
+    }
+    let doc = migrations::upgrade(Artifact::State, doc, path)?;
+    serde_json::from_value(doc).context(format!("Failed to parse state from {path}"))
 }
 
-/// Delete the state file (for testing or reset).
+/// Delete the state file and its backups (for testing or reset).
 pub fn delete_state(path: Option<&str>) -> Result<()> {
     let path = path.unwrap_or(DEFAULT_STATE_FILE);
-    if Path::new(path).exists() {
-        std::fs::remove_file(path)
-            .context(format!("Failed to delete state file {path}"))?;
+    let backups: Vec<String> = (1..).map(|n| state_backup_path(path, n)).take_while(|p| Path::new(p).exists()).collect();
+    for file in std::iter::once(path.to_string()).chain(backups).chain([format!("{path}.tmp")]) {
+        if Path::new(&file).exists() {
+            std::fs::remove_file(&file)
+                .context(format!("Failed to delete state file {file}"))?;
+        }
     }
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Cycle history
+// ---------------------------------------------------------------------------
+
+/// Append a cycle summary to the history file, keeping the newest
+/// [`MAX_CYCLE_HISTORY`] lines.
+pub fn append_cycle_report(report: &CycleReport, path: Option<&str>) -> Result<()> {
+    append_cycle_report_capped(report, path, MAX_CYCLE_HISTORY)
+}
+
+/// [`append_cycle_report`] with an explicit `max_history` cap.
+pub fn append_cycle_report_capped(report: &CycleReport, path: Option<&str>, max_history: usize) -> Result<()> {
+    let path = path.unwrap_or(DEFAULT_CYCLE_HISTORY_FILE);
+    let line = serde_json::to_string(report).context("Failed to serialise cycle report")?;
+    append_lines_capped(path, &[line], max_history)
+}
+
+/// Append `new_lines` to a JSON-lines file, keeping the newest `max_lines`.
+pub(crate) fn append_lines_capped(path: &str, new_lines: &[String], max_lines: usize) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context(format!("Failed to open {path}"))?;
+    for line in new_lines {
+        writeln!(file, "{line}").context(format!("Failed to append to {path}"))?;
+    }
+    drop(file);
+
+    let contents = std::fs::read_to_string(path)
+        .context(format!("Failed to read {path}"))?;
+    let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() > max_lines {
+        let kept = &lines[lines.len() - max_lines..];
+        // Write-then-rename so a crash mid-truncation keeps the old file.
+        let tmp = format!("{path}.tmp");
+        std::fs::write(&tmp, kept.join("\n") + "\n")
+            .context(format!("Failed to write {tmp}"))?;
+        std::fs::rename(&tmp, path).context(format!("Failed to replace {path}"))?;
+        debug!(path, dropped = lines.len() - max_lines, "JSON-lines history truncated");
+    }
+    Ok(())
+}
+
+/// The newest `limit` cycle summaries, oldest first. A missing file is an
+/// empty history; malformed lines are skipped with a warning.
+pub fn load_cycle_history(path: Option<&str>, limit: usize) -> Result<Vec<CycleReport>> {
+    let path = path.unwrap_or(DEFAULT_CYCLE_HISTORY_FILE);
+    if !Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(path)
+        .context(format!("Failed to read cycle history {path}"))?;
+
+    let mut reports = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<CycleReport>(line) {
+            Ok(report) => reports.push(report),
+            Err(e) => warn!(path, line = i + 1, error = %e, "Skipping malformed cycle history line"),
+        }
+    }
+    let start = reports.len().saturating_sub(limit);
+    reports.drain(..start);
+    info!(path, cycles = reports.len(), "Cycle history loaded");
+    Ok(reports)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -137,6 +351,188 @@ mod tests {
         assert!(!Path::new(&path).exists());
     }
 
+    #[test]
+    fn test_save_writes_current_version() {
+        let path = temp_path();
+        save_state(&AgentState::new(dec!(50)), Some(&path)).unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["schema_version"], migrations::STATE_VERSION);
+        delete_state(Some(&path)).unwrap();
+    }
+
+    #[test]
+    fn test_load_refuses_newer_state() {
+        let path = temp_path();
+        save_state(&AgentState::new(dec!(50)), Some(&path)).unwrap();
+        let mut doc: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        doc["schema_version"] = (migrations::STATE_VERSION + 1).into();
+        std::fs::write(&path, doc.to_string()).unwrap();
+
+        let err = load_state(Some(&path)).unwrap_err();
+        assert!(err.downcast_ref::<migrations::NewerSchema>().is_some());
+        // Refusing leaves the file untouched.
+        assert!(std::fs::read_to_string(&path).unwrap().contains(&format!("\"schema_version\":{}", migrations::STATE_VERSION + 1)));
+        delete_state(Some(&path)).unwrap();
+    }
+
+    fn state_at_cycle(n: u64) -> AgentState {
+        let mut state = AgentState::new(dec!(100));
+        state.cycle_count = n;
+        state
+    }
+
+    fn loaded_cycle(path: &str) -> u64 {
+        load_state(Some(path)).unwrap().unwrap().cycle_count
+    }
+
+    #[test]
+    fn test_save_rotates_backups() {
+        let path = temp_path();
+        for n in 1..=4 {
+            save_state_with_backups(&state_at_cycle(n), Some(&path), 2).unwrap();
+        }
+        assert_eq!(loaded_cycle(&path), 4);
+        assert_eq!(loaded_cycle(&state_backup_path(&path, 1)), 3);
+        assert_eq!(loaded_cycle(&state_backup_path(&path, 2)), 2);
+        assert!(!Path::new(&state_backup_path(&path, 3)).exists());
+        // Written through a temp file that was renamed into place.
+        assert!(!Path::new(&format!("{path}.tmp")).exists());
+
+        delete_state(Some(&path)).unwrap();
+        assert!(!Path::new(&path).exists());
+        assert!(!Path::new(&state_backup_path(&path, 1)).exists());
+        assert!(!Path::new(&state_backup_path(&path, 2)).exists());
+    }
+
+    #[test]
+    fn test_load_recovers_from_backup() {
+        let path = temp_path();
+        for n in 1..=3 {
+            save_state(&state_at_cycle(n), Some(&path)).unwrap();
+        }
+
+        // Cut short, as by a reboot mid-write.
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, &json[..json.len() / 2]).unwrap();
+        assert_eq!(loaded_cycle(&path), 2);
+
+        // Valid JSON whose contents no longer match the checksum.
+        std::fs::write(&path, json.replace("\"cycle_count\": 3", "\"cycle_count\": 30")).unwrap();
+        assert_eq!(loaded_cycle(&path), 2);
+
+        // A damaged first backup falls through to the second.
+        std::fs::write(state_backup_path(&path, 1), "{").unwrap();
+        assert_eq!(loaded_cycle(&path), 1);
+
+        // No primary at all.
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded_cycle(&path), 1);
+        delete_state(Some(&path)).unwrap();
+    }
+
+    #[test]
+    fn test_load_fails_without_valid_state() {
+        let path = temp_path();
+        std::fs::write(&path, "{\"bankroll\": ").unwrap();
+        let err = load_state(Some(&path)).unwrap_err();
+        assert!(format!("{err:#}").contains("No valid state"));
+        // A file from before checksums still loads.
+        std::fs::write(&path, serde_json::to_string(&AgentState::new(dec!(70))).unwrap()).unwrap();
+        assert_eq!(load_state(Some(&path)).unwrap().unwrap().bankroll, dec!(70));
+        delete_state(Some(&path)).unwrap();
+    }
+
+    #[test]
+    fn test_failed_write_leaves_previous_state() {
+        let path = temp_path();
+        save_state_with_backups(&state_at_cycle(1), Some(&path), 0).unwrap();
+        let before = std::fs::read_to_string(&path).unwrap();
+
+        // The temp file can't be created: the save fails, the state file is untouched.
+        let tmp = format!("{path}.tmp");
+        std::fs::create_dir(&tmp).unwrap();
+        assert!(save_state_with_backups(&state_at_cycle(2), Some(&path), 0).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+        std::fs::remove_dir(&tmp).unwrap();
+
+        // A partial temp file left by a crash is ignored, then replaced.
+        std::fs::write(&tmp, &before[..20]).unwrap();
+        assert_eq!(loaded_cycle(&path), 1);
+        save_state_with_backups(&state_at_cycle(2), Some(&path), 0).unwrap();
+        assert_eq!(loaded_cycle(&path), 2);
+        assert!(!Path::new(&tmp).exists());
+        delete_state(Some(&path)).unwrap();
+    }
+
+    fn cycle(n: u64) -> CycleReport {
+        CycleReport {
+            cycle_number: n,
+            timestamp: chrono::Utc::now(),
+            markets_scanned: 40,
+            edges_found: 3,
+            bets_placed: 1,
+            cycle_cost: dec!(0.05),
+            cycle_pnl: dec!(-0.05),
+            bankroll_after: Decimal::from(100 + n),
+            bets_failed: 0,
+            mana_bankroll_after: dec!(1000),
+            status: Some(AgentStatus::Alive),
+        }
+    }
+
+    fn temp_history() -> String {
+        std::env::temp_dir()
+            .join(format!("oracle_test_cycles_{}.jsonl", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn test_cycle_history_round_trip() {
+        let path = temp_history();
+        assert!(load_cycle_history(Some(&path), 10).unwrap().is_empty());
+        for n in 1..=3 {
+            append_cycle_report(&cycle(n), Some(&path)).unwrap();
+        }
+
+        let loaded = load_cycle_history(Some(&path), 10).unwrap();
+        assert_eq!(loaded.iter().map(|r| r.cycle_number).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(loaded[2].bankroll_after, dec!(103));
+        assert_eq!(loaded[2].mana_bankroll_after, dec!(1000));
+        assert_eq!(loaded[2].status, Some(AgentStatus::Alive));
+        // The limit keeps the newest.
+        let newest = load_cycle_history(Some(&path), 2).unwrap();
+        assert_eq!(newest.iter().map(|r| r.cycle_number).collect::<Vec<_>>(), vec![2, 3]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_cycle_history_truncates_oldest() {
+        let path = temp_history();
+        for n in 1..=7 {
+            append_cycle_report_capped(&cycle(n), Some(&path), 4).unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+        let loaded = load_cycle_history(Some(&path), 100).unwrap();
+        assert_eq!(loaded.iter().map(|r| r.cycle_number).collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+        assert!(!Path::new(&format!("{path}.tmp")).exists());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_cycle_history_skips_malformed_line() {
+        let path = temp_history();
+        append_cycle_report(&cycle(1), Some(&path)).unwrap();
+        // A line cut short by a crash mid-write.
+        std::fs::OpenOptions::new().append(true).open(&path).unwrap()
+            .write_all(b"{\"cycle_number\":2,\"timest\n").unwrap();
+        append_cycle_report(&cycle(3), Some(&path)).unwrap();
+
+        let loaded = load_cycle_history(Some(&path), 10).unwrap();
+        assert_eq!(loaded.iter().map(|r| r.cycle_number).collect::<Vec<_>>(), vec![1, 3]);
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_delete_nonexistent_ok() {
         let result = delete_state(Some("/tmp/oracle_does_not_exist_xyz.json"));
@@ -0,0 +1,366 @@
+//! Anonymised research export of the decision history.
+//!
+//! `oracle export --research` writes one CSV row per (cycle, market) from
+//! the `decisions` table for offline analysis. The output carries no
+//! identifiers or absolute amounts: market keys are salted hashes, stakes
+//! are fractions of bankroll, timestamps are truncated to the hour, and no
+//! free text (questions, reasoning) is included.
+//!
+//! # Columns (schema version 1)
+//!
+//! See [`COLUMNS`]. Any change to the column set or its meaning must bump
+//! [`SCHEMA_VERSION`]; every row repeats the version so mixed exports can
+//! be told apart.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use futures::TryStreamExt;
+use rust_decimal::prelude::*;
+
+use super::metrics::{DecisionRow, MetricsStore};
+use crate::strategy::links::link_key;
+use crate::strategy::DecisionRecord;
+use crate::types::{AgentState, Estimate, Market};
+
+/// Version of the exported column schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Exported columns, in order, with their meaning.
+pub const COLUMNS: &[(&str, &str)] = &[
+    ("schema_version", "Column schema version (see SCHEMA_VERSION)"),
+    ("hour", "Decision time truncated to the hour, RFC 3339 UTC"),
+    ("cycle", "Agent cycle number"),
+    ("market_key", "Salted hash of platform + market id; stable within one export"),
+    ("platform", "Venue (manifold, betfair, ...)"),
+    ("category", "Market category"),
+    ("price_yes", "Market YES price at decision time"),
+    ("metaculus_prob", "Metaculus community forecast, if cross-referenced"),
+    ("manifold_prob", "Manifold price, if cross-referenced"),
+    ("estimate", "LLM probability estimate (YES)"),
+    ("confidence", "LLM self-reported confidence"),
+    ("edge", "Detected actionable edge; empty when below threshold"),
+    ("side", "YES / NO for detected edges"),
+    ("decision", "no_edge | kelly_rejected | stake_rejected | risk_rejected | selected"),
+    ("bet_fraction", "Sized stake as a fraction of bankroll; empty when unsized"),
+    ("resolved_yes", "1 / 0 once the market resolved, empty otherwise"),
+];
+
+/// Build one cycle's decision rows from the estimates and the strategy's
+/// decision log. Markets without an entry in `decisions` had no edge. A bet
+/// routed to another venue is recorded against the market whose edge
+/// triggered it. Arbitrage pairs, priced off the venues rather than an
+/// estimate, are left out.
+/// Stakes are divided by the bankroll they were sized against (the
+/// platform's balance, as in `select_bets`).
+pub fn decision_rows(
+    cycle: u64,
+    timestamp: DateTime<Utc>,
+    estimates: &[(Market, Estimate)],
+    decisions: &[DecisionRecord],
+    state: &AgentState,
+) -> Vec<DecisionRow> {
+    let mut by_market: HashMap<String, &DecisionRecord> = HashMap::new();
+    for d in decisions {
+        let key = match d {
+            DecisionRecord::Selected { bet, .. }
+            | DecisionRecord::RiskRejected { bet, .. }
+            | DecisionRecord::StakeRejected { bet, .. } => match &bet.venue {
+                Some(choice) => choice.origin.clone(),
+                None => link_key(&bet.edge.market.platform, &bet.edge.market.id),
+            },
+            DecisionRecord::KellyRejected { edge } => link_key(&edge.market.platform, &edge.market.id),
+            // Position reviews aren't about the market's edge.
+            DecisionRecord::Arbitrage { .. } | DecisionRecord::Exit { .. } | DecisionRecord::Hold { .. } => continue,
+        };
+        by_market.insert(key, d);
+    }
+    let fraction = |market: &Market, amount: Decimal| {
+        let base = state.bankroll_for(&market.platform);
+        (base > Decimal::ZERO).then(|| (amount / base).to_f64().unwrap_or(0.0))
+    };
+
+    estimates
+        .iter()
+        .map(|(market, estimate)| {
+            let decision = by_market.get(&link_key(&market.platform, &market.id));
+            let (label, edge, bet_fraction) = match decision {
+                None
+                | Some(DecisionRecord::Arbitrage { .. } | DecisionRecord::Exit { .. } | DecisionRecord::Hold { .. }) => {
+                    ("no_edge", None, None)
+                }
+                Some(DecisionRecord::KellyRejected { edge }) => ("kelly_rejected", Some(edge), None),
+                Some(DecisionRecord::StakeRejected { bet, .. }) => ("stake_rejected", Some(&bet.edge), None),
+                Some(DecisionRecord::RiskRejected { bet, .. }) => {
+                    ("risk_rejected", Some(&bet.edge), fraction(market, bet.bet_amount))
+                }
+                Some(DecisionRecord::Selected { bet, adjusted_amount, .. }) => {
+                    ("selected", Some(&bet.edge), fraction(market, *adjusted_amount))
+                }
+            };
+            DecisionRow {
+                timestamp,
+                cycle,
+                platform: market.platform.clone(),
+                market_id: market.id.clone(),
+                category: market.category.to_string(),
+                price_yes: market.current_price_yes.to_f64().unwrap_or(0.0),
+                metaculus_prob: market.cross_refs.metaculus_prob.and_then(|p| p.to_f64()),
+                manifold_prob: market.cross_refs.manifold_prob.and_then(|p| p.to_f64()),
+                estimate: estimate.probability.to_f64().unwrap_or(0.0),
+                confidence: estimate.confidence.to_f64().unwrap_or(0.0),
+                edge: edge.and_then(|e| e.edge.to_f64()),
+                side: edge.map(|e| e.side.to_string()),
+                decision: label.to_string(),
+                bet_fraction,
+                resolved_yes: None,
+                tags: market.tags.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Salted, non-reversible-without-the-salt key for a market.
+pub fn market_key(salt: &str, platform: &str, market_id: &str) -> String {
+    let mut h = DefaultHasher::new();
+    (salt, platform, market_id).hash(&mut h);
+    format!("{:016x}", h.finish())
+}
+
+fn hour(ts: DateTime<Utc>) -> String {
+    ts.duration_trunc(TimeDelta::hours(1)).unwrap_or(ts).to_rfc3339()
+}
+
+fn num(v: Option<f64>) -> String {
+    v.map(|x| format!("{x:.6}")).unwrap_or_default()
+}
+
+/// Quote a field if it would break the row.
+fn field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_line(row: &DecisionRow, salt: &str) -> String {
+    let cells = [
+        SCHEMA_VERSION.to_string(),
+        hour(row.timestamp),
+        row.cycle.to_string(),
+        market_key(salt, &row.platform, &row.market_id),
+        field(&row.platform),
+        field(&row.category),
+        num(Some(row.price_yes)),
+        num(row.metaculus_prob),
+        num(row.manifold_prob),
+        num(Some(row.estimate)),
+        num(Some(row.confidence)),
+        num(row.edge),
+        field(row.side.as_deref().unwrap_or_default()),
+        field(&row.decision),
+        num(row.bet_fraction),
+        row.resolved_yes.map(|y| if y { "1" } else { "0" }).unwrap_or_default().to_string(),
+    ];
+    cells.join(",")
+}
+
+/// Stream every stored decision to `out` as anonymised CSV. Rows are
+/// written as they are read; nothing is buffered beyond one row.
+/// Returns the number of data rows written.
+pub async fn export_csv<W: Write>(store: &MetricsStore, mut out: W, salt: &str) -> Result<usize> {
+    let header: Vec<&str> = COLUMNS.iter().map(|(name, _)| *name).collect();
+    writeln!(out, "{}", header.join(",")).context("Failed to write export header")?;
+
+    let mut rows = store.decisions();
+    let mut written = 0;
+    while let Some(row) = rows.try_next().await? {
+        writeln!(out, "{}", csv_line(&row, salt)).context("Failed to write export row")?;
+        written += 1;
+    }
+    out.flush().context("Failed to flush export")?;
+    Ok(written)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::edge::Edge;
+    use crate::strategy::kelly::SizedBet;
+    use crate::strategy::risk::RejectionReason;
+    use crate::types::{CrossReferences, MarketCategory, Side};
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    const SECRET_REASONING: &str = "key sk-or-v1-0123456789abcdef leaked into reasoning";
+
+    fn market(platform: &str, id: &str, price: Decimal) -> Market {
+        Market {
+            id: id.to_string(),
+            platform: platform.to_string(),
+            question: "Will the secret account-holder question resolve YES?".to_string(),
+            description: String::new(),
+            category: MarketCategory::Weather,
+            current_price_yes: price,
+            current_price_no: Decimal::ONE - price,
+            volume_24h: dec!(1000),
+            liquidity: dec!(5000),
+            deadline: Utc::now(),
+            resolution_criteria: String::new(),
+            url: format!("https://manifold.markets/someuser/{id}"),
+            cross_refs: CrossReferences { metaculus_prob: Some(dec!(0.55)), ..Default::default() },
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn estimate(p: Decimal) -> Estimate {
+        Estimate {
+            probability: p,
+            confidence: dec!(0.8),
+            reasoning: SECRET_REASONING.to_string(),
+            tokens_used: 100,
+            cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    fn sized(m: &Market, e: &Estimate, amount: Decimal) -> SizedBet {
+        SizedBet {
+            edge: Edge {
+                market: m.clone(),
+                estimate: e.clone(),
+                side: Side::Yes,
+                edge: dec!(0.2),
+                signed_edge: dec!(0.2),
+                raw_edge: dec!(0.2),
+                effective_threshold: dec!(0.10),
+            },
+            kelly_fraction: dec!(0.1),
+            bet_fraction: dec!(0.05),
+            bet_amount: amount,
+            expected_value: dec!(1),
+            net_expected_value: dec!(1),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.2),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Three markets over two cycles: one selected, one risk-rejected, one
+    /// without an edge; the selected one later resolves YES.
+    async fn seeded_store() -> MetricsStore {
+        let store = MetricsStore::open_in_memory().await.unwrap();
+        let ts = Utc.with_ymd_and_hms(2026, 5, 4, 13, 47, 12).unwrap();
+        for cycle in [41, 42] {
+            let estimates = vec![
+                (market("manifold", "mkt-AbC123", dec!(0.40)), estimate(dec!(0.60))),
+                (market("betfair", "1.234567890", dec!(0.40)), estimate(dec!(0.62))),
+                (market("manifold", "mkt-Zz9", dec!(0.50)), estimate(dec!(0.51))),
+            ];
+            let decisions = vec![
+                DecisionRecord::Selected {
+                    bet: sized(&estimates[0].0, &estimates[0].1, dec!(37.5)),
+                    adjusted_amount: dec!(37.5),
+                    drawdown_multiplier: dec!(1),
+                },
+                DecisionRecord::RiskRejected {
+                    bet: sized(&estimates[1].0, &estimates[1].1, dec!(12.34)),
+                    reason: RejectionReason::MaxPositionsReached { current: 20, limit: 20 },
+                },
+            ];
+            let mut state = AgentState::new(dec!(250));
+            state.mana_bankroll = dec!(1000);
+            let rows = decision_rows(cycle, ts, &estimates, &decisions, &state);
+            store.record_decisions(&rows).await.unwrap();
+        }
+        assert_eq!(store.record_resolution("manifold", "mkt-AbC123", true).await.unwrap(), 2);
+        store
+    }
+
+    async fn export(store: &MetricsStore, salt: &str) -> String {
+        let mut buf = Vec::new();
+        let n = export_csv(store, &mut buf, salt).await.unwrap();
+        assert_eq!(n, 6);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_contains_no_forbidden_fields() {
+        let store = seeded_store().await;
+        let csv = export(&store, "s3").await;
+
+        for forbidden in [
+            "mkt-AbC123", "1.234567890", "mkt-Zz9", // market ids
+            "someuser", "https://", "account-holder", // urls, question text
+            "sk-or-v1", "leaked", // reasoning / key-like strings
+            ":47", // sub-hour timestamps
+        ] {
+            assert!(!csv.contains(forbidden), "export leaked {forbidden:?}:\n{csv}");
+        }
+        // Absolute amounts and bankrolls: compared by value, since digits
+        // like "1000" legitimately occur inside "0.510000".
+        for cell in csv.lines().skip(1).flat_map(|l| l.split(',')) {
+            if let Ok(v) = cell.parse::<f64>() {
+                assert!(![37.5, 12.34, 250.0, 1000.0].contains(&v), "export leaked amount {cell}");
+            }
+        }
+        let header = csv.lines().next().unwrap();
+        for column in ["order_id", "amount", "bankroll", "question", "reasoning", "market_id"] {
+            assert!(!header.split(',').any(|c| c == column), "column {column} exported");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_rows_and_schema() {
+        let store = seeded_store().await;
+        let csv = export(&store, "s3").await;
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0].split(',').count(), COLUMNS.len());
+
+        let first: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(first.len(), COLUMNS.len());
+        assert_eq!(first[0], "1");
+        assert_eq!(first[1], "2026-05-04T13:00:00+00:00");
+        assert_eq!(first[2], "41");
+        assert_eq!(first[3], market_key("s3", "manifold", "mkt-AbC123"));
+        assert_eq!(first[7], "0.550000");
+        assert_eq!(first[12], "YES");
+        assert_eq!(first[13], "selected");
+        // 37.5 Mana of a 1000 Mana bankroll.
+        assert_eq!(first[14], "0.037500");
+        assert_eq!(first[15], "1");
+
+        let rejected: Vec<&str> = lines[2].split(',').collect();
+        assert_eq!(rejected[13], "risk_rejected");
+        assert_eq!(rejected[15], "");
+        let no_edge: Vec<&str> = lines[3].split(',').collect();
+        assert_eq!((no_edge[11], no_edge[13], no_edge[14]), ("", "no_edge", ""));
+
+        // Same market, same key across cycles; different salt, different key.
+        assert_eq!(lines[1].split(',').nth(3), lines[4].split(',').nth(3));
+        assert_ne!(market_key("s3", "manifold", "mkt-AbC123"), market_key("other", "manifold", "mkt-AbC123"));
+    }
+
+    #[test]
+    fn test_field_quoting() {
+        assert_eq!(field("plain"), "plain");
+        assert_eq!(field("a,b"), "\"a,b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
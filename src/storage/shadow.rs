@@ -0,0 +1,281 @@
+//! Shadow canary store.
+//!
+//! Keeps one record per market the shadow model double-estimated — both
+//! models' probabilities and the market price at the time — and labels it
+//! with the outcome once the market resolves, so the
+//! [`ShadowRunner`](crate::llm::shadow::ShadowRunner) can compare Brier
+//! scores. Sampled markets are mostly ones we never bet on, so outcomes
+//! come from checking each market once its deadline has passed
+//! ([`ShadowStore::due`]), not from bet settlements alone.
+//!
+//! Re-sampling a market replaces its record until it resolves. Refunded
+//! and probabilistic resolutions carry no YES/NO outcome, so their
+//! records are dropped.
+//!
+//! Saved as one versioned JSON document (see [`super::migrations`]),
+//! capped at [`MAX_RECORDS`].
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::migrations::{self, Artifact};
+use crate::types::MarketResolution;
+
+/// Version of the shadow store layout.
+pub const SHADOW_VERSION: u32 = 1;
+
+/// Default store path, next to the state file.
+pub const DEFAULT_SHADOW_FILE: &str = "oracle_shadow.json";
+
+/// Records kept; the oldest samples are dropped beyond this.
+pub const MAX_RECORDS: usize = 5_000;
+
+/// Days past its deadline an unresolved market is still checked; after
+/// that its record is dropped.
+pub const UNRESOLVED_EXPIRY_DAYS: i64 = 30;
+
+/// One market estimated by both models.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowRecord {
+    pub platform: String,
+    pub market_id: String,
+    /// YES price when the market was sampled.
+    pub market_price: f64,
+    pub primary_prob: f64,
+    pub shadow_prob: f64,
+    pub sampled_at: DateTime<Utc>,
+    /// Market deadline; the market is checked for an outcome after it.
+    pub deadline: DateTime<Utc>,
+    /// Filled once the market resolves (`Some(true)` = YES).
+    pub resolved_yes: Option<bool>,
+}
+
+impl ShadowRecord {
+    /// Both models lean the same way relative to the market price.
+    pub fn agrees(&self) -> bool {
+        let p = self.primary_prob - self.market_price;
+        let s = self.shadow_prob - self.market_price;
+        p.signum() == s.signum()
+    }
+}
+
+/// On-disk layout.
+#[derive(Debug, Serialize, Deserialize)]
+struct ShadowFile {
+    schema_version: u32,
+    records: Vec<ShadowRecord>,
+}
+
+/// Shadow samples and their outcomes, keyed `platform:market_id`.
+#[derive(Debug, Clone)]
+pub struct ShadowStore {
+    path: PathBuf,
+    records: BTreeMap<String, ShadowRecord>,
+}
+
+fn key(platform: &str, market_id: &str) -> String {
+    format!("{platform}:{market_id}")
+}
+
+impl ShadowStore {
+    /// An empty store saving to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), records: BTreeMap::new() }
+    }
+
+    /// Load the store at `path`. A missing file is an empty store.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if !path.exists() {
+            return Ok(Self::new(path));
+        }
+        let what = path.display().to_string();
+        let text = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {what}"))?;
+        let doc: serde_json::Value = serde_json::from_str(&text).with_context(|| format!("Failed to parse {what}"))?;
+        let doc = migrations::upgrade(Artifact::Shadow, doc, &what)?;
+        let file: ShadowFile = serde_json::from_value(doc).with_context(|| format!("Failed to parse {what}"))?;
+        let records = file
+            .records
+            .into_iter()
+            .map(|r| (key(&r.platform, &r.market_id), r))
+            .collect();
+        Ok(Self { path, records })
+    }
+
+    /// Write the store, replacing the file only once the new one is complete.
+    pub fn save(&self) -> Result<()> {
+        let file = ShadowFile {
+            schema_version: SHADOW_VERSION,
+            records: self.records.values().cloned().collect(),
+        };
+        let json = serde_json::to_string(&file).context("Failed to serialise shadow store")?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &ShadowRecord> {
+        self.records.values()
+    }
+
+    /// Record a sample. A resolved market's record is left as it is.
+    pub fn record(&mut self, record: ShadowRecord) {
+        let key = key(&record.platform, &record.market_id);
+        if self.records.get(&key).is_some_and(|r| r.resolved_yes.is_some()) {
+            return;
+        }
+        self.records.insert(key, record);
+        self.prune();
+    }
+
+    /// Label a market's record with its resolution. Returns whether the
+    /// store changed.
+    pub fn resolve(&mut self, platform: &str, market_id: &str, resolution: MarketResolution) -> bool {
+        let key = key(platform, market_id);
+        let Some(record) = self.records.get_mut(&key) else { return false };
+        if record.resolved_yes.is_some() {
+            return false;
+        }
+        match resolution {
+            MarketResolution::Yes | MarketResolution::No => {
+                record.resolved_yes = Some(resolution == MarketResolution::Yes);
+            }
+            MarketResolution::Probability(_) | MarketResolution::Cancelled => {
+                self.records.remove(&key);
+            }
+        }
+        true
+    }
+
+    /// Unresolved markets past their deadline at `now`, as `(platform,
+    /// market_id)`, earliest deadline first, at most `limit`.
+    pub fn due(&self, now: DateTime<Utc>, limit: usize) -> Vec<(String, String)> {
+        let mut due: Vec<&ShadowRecord> = self
+            .records
+            .values()
+            .filter(|r| r.resolved_yes.is_none() && r.deadline <= now)
+            .collect();
+        due.sort_by_key(|r| r.deadline);
+        due.into_iter().take(limit).map(|r| (r.platform.clone(), r.market_id.clone())).collect()
+    }
+
+    /// Drop unresolved records more than [`UNRESOLVED_EXPIRY_DAYS`] past
+    /// their deadline. Returns how many were dropped.
+    pub fn expire(&mut self, now: DateTime<Utc>) -> usize {
+        let cutoff = now - chrono::Duration::days(UNRESOLVED_EXPIRY_DAYS);
+        let before = self.records.len();
+        self.records.retain(|_, r| r.resolved_yes.is_some() || r.deadline >= cutoff);
+        before - self.records.len()
+    }
+
+    /// Drop the oldest samples beyond [`MAX_RECORDS`].
+    fn prune(&mut self) {
+        let excess = self.records.len().saturating_sub(MAX_RECORDS);
+        if excess == 0 {
+            return;
+        }
+        let mut by_age: Vec<(DateTime<Utc>, String)> =
+            self.records.iter().map(|(k, r)| (r.sampled_at, k.clone())).collect();
+        by_age.sort();
+        for (_, key) in by_age.into_iter().take(excess) {
+            self.records.remove(&key);
+        }
+        debug!(dropped = excess, "Shadow store pruned");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(id: &str, sampled_at: DateTime<Utc>, deadline: DateTime<Utc>) -> ShadowRecord {
+        ShadowRecord {
+            platform: "manifold".into(),
+            market_id: id.into(),
+            market_price: 0.5,
+            primary_prob: 0.6,
+            shadow_prob: 0.7,
+            sampled_at,
+            deadline,
+            resolved_yes: None,
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("oracle_shadow_{name}_{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_record_resolve_and_persist() {
+        let path = temp_path("round_trip");
+        let now = Utc::now();
+        let mut store = ShadowStore::load(&path).unwrap();
+        assert!(store.is_empty());
+
+        store.record(record("rain", now, now));
+        store.record(record("cup", now, now));
+        store.record(record("void", now, now));
+        assert!(store.resolve("manifold", "rain", MarketResolution::Yes));
+        assert!(!store.resolve("manifold", "rain", MarketResolution::No), "already resolved");
+        assert!(!store.resolve("manifold", "unknown", MarketResolution::Yes));
+        assert!(store.resolve("manifold", "void", MarketResolution::Cancelled));
+        // A resolved record is not replaced by a later sample.
+        store.record(ShadowRecord { primary_prob: 0.1, ..record("rain", now, now) });
+        store.save().unwrap();
+
+        let loaded = ShadowStore::load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let rain = loaded.records().find(|r| r.market_id == "rain").unwrap();
+        assert_eq!((rain.primary_prob, rain.resolved_yes), (0.6, Some(true)));
+        let doc: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["schema_version"], SHADOW_VERSION);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_due_expire_and_cap() {
+        let now = Utc::now();
+        let mut store = ShadowStore::new(temp_path("due"));
+        store.record(record("later", now, now + Duration::days(1)));
+        store.record(record("late", now, now - Duration::days(2)));
+        store.record(record("early", now, now - Duration::days(5)));
+        store.record(record("stale", now, now - Duration::days(UNRESOLVED_EXPIRY_DAYS + 1)));
+        store.resolve("manifold", "early", MarketResolution::No);
+
+        let due: Vec<String> = store.due(now, 10).into_iter().map(|(_, id)| id).collect();
+        assert_eq!(due, ["stale", "late"]);
+        assert_eq!(store.due(now, 1).len(), 1);
+        assert_eq!(store.expire(now), 1);
+        assert_eq!(store.len(), 3);
+
+        let mut capped = ShadowStore::new(temp_path("cap"));
+        for i in 0..=MAX_RECORDS {
+            capped.record(record(&format!("m{i}"), now + Duration::seconds(i as i64), now));
+        }
+        assert_eq!(capped.len(), MAX_RECORDS);
+        assert!(capped.records().all(|r| r.market_id != "m0"), "oldest sample dropped");
+    }
+}
@@ -0,0 +1,399 @@
+//! SQLite [`Storage`] backend.
+//!
+//! One database holds the agent state (a single row), every trade, every
+//! cycle summary, every strategy decision and the balances after each
+//! cycle. Money is stored as decimal
+//! TEXT and times as fixed-width RFC 3339 TEXT, so both round-trip exactly
+//! and time ranges compare as strings. The state row keeps the whole
+//! versioned state document beside a few columns for ad-hoc queries.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::Row;
+use tracing::debug;
+
+use super::backend::{BalanceSample, Storage, StoredDecision, TradeQuery, TradeRecord};
+use super::migrations::{self, Artifact};
+use crate::strategy::DecisionRecord;
+use crate::types::{AgentState, AgentStatus, CycleReport, TradeReceipt};
+
+/// Default database path.
+pub const DEFAULT_STORAGE_DB: &str = "oracle.db";
+
+/// Schema version 1 (see [`super::migrations::STORAGE_STEPS`]).
+pub(super) const SCHEMA_V1: &str = r#"
+CREATE TABLE IF NOT EXISTS agent_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    updated_at TEXT NOT NULL,
+    bankroll TEXT NOT NULL,
+    total_pnl TEXT NOT NULL,
+    cycle_count INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    doc TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS trades (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    order_id TEXT NOT NULL UNIQUE,
+    ts TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    category TEXT,
+    currency TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    fill_price TEXT NOT NULL,
+    fees TEXT NOT NULL,
+    pnl TEXT,
+    settled_at TEXT,
+    receipt TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_trades_ts ON trades(ts);
+
+CREATE TABLE IF NOT EXISTS cycles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle_number INTEGER NOT NULL,
+    ts TEXT NOT NULL,
+    markets_scanned INTEGER NOT NULL,
+    edges_found INTEGER NOT NULL,
+    bets_placed INTEGER NOT NULL,
+    bets_failed INTEGER NOT NULL,
+    cycle_cost TEXT NOT NULL,
+    cycle_pnl TEXT NOT NULL,
+    bankroll_after TEXT NOT NULL,
+    mana_bankroll_after TEXT NOT NULL,
+    status TEXT
+);
+
+CREATE TABLE IF NOT EXISTS decisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle INTEGER NOT NULL,
+    ts TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    decision TEXT NOT NULL,
+    reason TEXT,
+    record TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_storage_decisions_cycle ON decisions(cycle);
+"#;
+
+/// Schema version 2: the balance series.
+pub(super) const SCHEMA_V2: &str = r#"
+CREATE TABLE IF NOT EXISTS balances (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts TEXT NOT NULL,
+    cash TEXT NOT NULL,
+    equity TEXT NOT NULL,
+    drawdown TEXT NOT NULL,
+    bankroll TEXT NOT NULL,
+    mana_bankroll TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_balances_ts ON balances(ts);
+"#;
+
+/// Sortable text form of a timestamp.
+fn ts(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn parse_ts(s: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(s).with_context(|| format!("Bad timestamp {s}"))?.with_timezone(&Utc))
+}
+
+fn parse_decimal(s: &str) -> Result<Decimal> {
+    Decimal::from_str(s).with_context(|| format!("Bad decimal {s}"))
+}
+
+/// A status as its serde name (`"alive"` etc.).
+fn status_text(status: AgentStatus) -> Result<String> {
+    match serde_json::to_value(status)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => anyhow::bail!("Unexpected status encoding {other}"),
+    }
+}
+
+/// SQLite-backed [`Storage`].
+#[derive(Clone)]
+pub struct SqliteStorage {
+    pool: SqlitePool,
+}
+
+impl SqliteStorage {
+    /// Open (or create) the database at `path` and migrate it.
+    pub async fn open(path: &str) -> Result<Self> {
+        let opts = SqliteConnectOptions::from_str(&format!("sqlite://{path}"))
+            .with_context(|| format!("Invalid storage database path: {path}"))?
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(opts)
+            .await
+            .with_context(|| format!("Failed to open storage database {path}"))?;
+        Self::init(pool, path).await
+    }
+
+    /// In-memory database (single connection, so every query sees the same DB).
+    pub async fn open_in_memory() -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .context("Failed to open in-memory storage database")?;
+        Self::init(pool, "in-memory storage database").await
+    }
+
+    async fn init(pool: SqlitePool, what: &str) -> Result<Self> {
+        migrations::migrate_storage(&pool, what).await?;
+        Ok(Self { pool })
+    }
+
+    fn trade_from_row(row: &SqliteRow) -> Result<TradeRecord> {
+        let receipt: TradeReceipt =
+            serde_json::from_str(row.get("receipt")).context("Failed to parse stored trade")?;
+        let pnl: Option<String> = row.get("pnl");
+        let settled_at: Option<String> = row.get("settled_at");
+        Ok(TradeRecord {
+            receipt,
+            pnl: pnl.as_deref().map(parse_decimal).transpose()?,
+            settled_at: settled_at.as_deref().map(parse_ts).transpose()?,
+        })
+    }
+
+    fn cycle_from_row(row: &SqliteRow) -> Result<CycleReport> {
+        let status: Option<String> = row.get("status");
+        Ok(CycleReport {
+            cycle_number: row.get::<i64, _>("cycle_number") as u64,
+            timestamp: parse_ts(row.get("ts"))?,
+            markets_scanned: row.get::<i64, _>("markets_scanned") as u64,
+            edges_found: row.get::<i64, _>("edges_found") as u64,
+            bets_placed: row.get::<i64, _>("bets_placed") as u64,
+            cycle_cost: parse_decimal(row.get("cycle_cost"))?,
+            cycle_pnl: parse_decimal(row.get("cycle_pnl"))?,
+            bankroll_after: parse_decimal(row.get("bankroll_after"))?,
+            bets_failed: row.get::<i64, _>("bets_failed") as u64,
+            mana_bankroll_after: parse_decimal(row.get("mana_bankroll_after"))?,
+            status: status
+                .map(|s| serde_json::from_value(serde_json::Value::String(s)))
+                .transpose()
+                .context("Bad stored cycle status")?,
+        })
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStorage {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    async fn save_state(&self, state: &AgentState) -> Result<()> {
+        let mut doc = serde_json::to_value(state).context("Failed to serialise agent state")?;
+        migrations::stamp(Artifact::State, &mut doc);
+        sqlx::query(
+            "INSERT INTO agent_state (id, updated_at, bankroll, total_pnl, cycle_count, status, doc)
+             VALUES (1, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET updated_at = excluded.updated_at, bankroll = excluded.bankroll,
+                total_pnl = excluded.total_pnl, cycle_count = excluded.cycle_count,
+                status = excluded.status, doc = excluded.doc",
+        )
+        .bind(ts(Utc::now()))
+        .bind(state.bankroll.to_string())
+        .bind(state.total_pnl.to_string())
+        .bind(state.cycle_count as i64)
+        .bind(status_text(state.status)?)
+        .bind(doc.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save agent state")?;
+        debug!(bankroll = %state.bankroll, "State saved");
+        Ok(())
+    }
+
+    async fn load_state(&self) -> Result<Option<AgentState>> {
+        let doc: Option<String> = sqlx::query_scalar("SELECT doc FROM agent_state WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to load agent state")?;
+        let Some(doc) = doc else { return Ok(None) };
+        let doc: serde_json::Value = serde_json::from_str(&doc).context("Failed to parse stored agent state")?;
+        let doc = migrations::upgrade(Artifact::State, doc, "agent_state")?;
+        Ok(Some(serde_json::from_value(doc).context("Failed to parse stored agent state")?))
+    }
+
+    async fn append_trade(&self, trade: &TradeReceipt) -> Result<()> {
+        let receipt = serde_json::to_string(trade).context("Failed to serialise trade")?;
+        sqlx::query(
+            "INSERT INTO trades (order_id, ts, platform, market_id, side, category, currency,
+                amount, fill_price, fees, receipt)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(order_id) DO NOTHING",
+        )
+        .bind(&trade.order_id)
+        .bind(ts(trade.timestamp))
+        .bind(&trade.platform)
+        .bind(&trade.market_id)
+        .bind(trade.side.to_string())
+        .bind(trade.category.map(|c| c.to_string()))
+        .bind(&trade.currency)
+        .bind(trade.amount.to_string())
+        .bind(trade.fill_price.to_string())
+        .bind(trade.fees.to_string())
+        .bind(receipt)
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("Failed to record trade {}", trade.order_id))?;
+        Ok(())
+    }
+
+    async fn settle_trade(&self, order_id: &str, pnl: Decimal, at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE trades SET pnl = ?, settled_at = ? WHERE order_id = ? AND pnl IS NULL")
+            .bind(pnl.to_string())
+            .bind(ts(at))
+            .bind(order_id)
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to settle trade {order_id}"))?;
+        Ok(())
+    }
+
+    async fn query_trades(&self, query: &TradeQuery) -> Result<Vec<TradeRecord>> {
+        let rows = sqlx::query(
+            "SELECT receipt, pnl, settled_at FROM trades
+             WHERE (?1 IS NULL OR ts >= ?1) AND (?2 IS NULL OR ts < ?2)
+               AND (?3 IS NULL OR platform = ?3) AND (?4 IS NULL OR (pnl IS NOT NULL) = ?4)
+             ORDER BY ts, id",
+        )
+        .bind(query.from.map(ts))
+        .bind(query.to.map(ts))
+        .bind(query.platform.as_deref())
+        .bind(query.settled)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to query trades")?;
+        rows.iter().map(Self::trade_from_row).collect()
+    }
+
+    async fn append_cycle(&self, report: &CycleReport) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO cycles (cycle_number, ts, markets_scanned, edges_found, bets_placed, bets_failed,
+                cycle_cost, cycle_pnl, bankroll_after, mana_bankroll_after, status)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(report.cycle_number as i64)
+        .bind(ts(report.timestamp))
+        .bind(report.markets_scanned as i64)
+        .bind(report.edges_found as i64)
+        .bind(report.bets_placed as i64)
+        .bind(report.bets_failed as i64)
+        .bind(report.cycle_cost.to_string())
+        .bind(report.cycle_pnl.to_string())
+        .bind(report.bankroll_after.to_string())
+        .bind(report.mana_bankroll_after.to_string())
+        .bind(report.status.map(status_text).transpose()?)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record cycle")?;
+        Ok(())
+    }
+
+    async fn recent_cycles(&self, limit: usize) -> Result<Vec<CycleReport>> {
+        let rows = sqlx::query("SELECT * FROM cycles ORDER BY id DESC LIMIT ?")
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to load cycle history")?;
+        rows.iter().rev().map(Self::cycle_from_row).collect()
+    }
+
+    async fn append_decisions(&self, cycle: u64, at: DateTime<Utc>, decisions: &[DecisionRecord]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for record in decisions {
+            let d = StoredDecision::new(cycle, at, record)?;
+            sqlx::query(
+                "INSERT INTO decisions (cycle, ts, platform, market_id, decision, reason, record)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(cycle as i64)
+            .bind(ts(at))
+            .bind(&d.platform)
+            .bind(&d.market_id)
+            .bind(&d.decision)
+            .bind(&d.reason)
+            .bind(d.record.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record decision")?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn decisions_in_cycle(&self, cycle: u64) -> Result<Vec<StoredDecision>> {
+        let rows = sqlx::query(
+            "SELECT cycle, ts, platform, market_id, decision, reason, record FROM decisions
+             WHERE cycle = ? ORDER BY id",
+        )
+        .bind(cycle as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load decisions")?;
+        rows.iter()
+            .map(|row| {
+                Ok(StoredDecision {
+                    cycle: row.get::<i64, _>("cycle") as u64,
+                    at: parse_ts(row.get("ts"))?,
+                    platform: row.get("platform"),
+                    market_id: row.get("market_id"),
+                    decision: row.get("decision"),
+                    reason: row.get("reason"),
+                    record: serde_json::from_str(row.get("record")).context("Failed to parse stored decision")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn append_balance(&self, sample: &BalanceSample) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO balances (ts, cash, equity, drawdown, bankroll, mana_bankroll) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(ts(sample.at))
+        .bind(sample.cash.to_string())
+        .bind(sample.equity.to_string())
+        .bind(sample.drawdown.to_string())
+        .bind(sample.bankroll.to_string())
+        .bind(sample.mana_bankroll.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record balances")?;
+        Ok(())
+    }
+
+    async fn balance_history(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Result<Vec<BalanceSample>> {
+        let rows = sqlx::query(
+            "SELECT * FROM balances WHERE (?1 IS NULL OR ts >= ?1) AND (?2 IS NULL OR ts < ?2) ORDER BY ts, id",
+        )
+        .bind(from.map(ts))
+        .bind(to.map(ts))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load balance history")?;
+        rows.iter()
+            .map(|row| {
+                Ok(BalanceSample {
+                    at: parse_ts(row.get("ts"))?,
+                    cash: parse_decimal(row.get("cash"))?,
+                    equity: parse_decimal(row.get("equity"))?,
+                    drawdown: parse_decimal(row.get("drawdown"))?,
+                    bankroll: parse_decimal(row.get("bankroll"))?,
+                    mana_bankroll: parse_decimal(row.get("mana_bankroll"))?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,292 @@
+//! Adaptive category thresholds.
+//!
+//! The static per-category edge thresholds are priors. Once enough bets have
+//! resolved, the realised return of each detected-edge bucket shows where
+//! edges stop being false positives; `review` periodically moves each
+//! category's threshold towards that point, one bounded step at a time.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::*;
+use tracing::info;
+
+use crate::config::AdaptiveThresholdConfig;
+use crate::strategy::edge::EdgeConfig;
+use crate::types::{AgentState, EdgeRealization, MarketCategory, ThresholdAdjustment};
+
+/// Most recent realizations kept in state.
+pub const MAX_REALIZATIONS: usize = 5_000;
+
+/// Realised performance of one detected-edge bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeBucket {
+    /// Inclusive lower edge of the bucket.
+    pub lower: Decimal,
+    pub trades: usize,
+    /// Mean return on stake.
+    pub mean_return: f64,
+    /// Standard error of `mean_return` (0 for a single trade).
+    pub std_error: f64,
+}
+
+/// Group realizations into `width`-wide buckets by detected edge, ascending.
+pub fn bucket_realizations(rows: &[EdgeRealization], width: Decimal) -> Vec<EdgeBucket> {
+    let mut groups: Vec<(Decimal, Vec<f64>)> = Vec::new();
+    for r in rows {
+        let lower = (r.edge / width).floor() * width;
+        match groups.iter_mut().find(|(l, _)| *l == lower) {
+            Some((_, returns)) => returns.push(r.return_pct()),
+            None => groups.push((lower, vec![r.return_pct()])),
+        }
+    }
+    groups.sort_by_key(|g| g.0);
+
+    groups
+        .into_iter()
+        .map(|(lower, returns)| {
+            let n = returns.len() as f64;
+            let mean = returns.iter().sum::<f64>() / n;
+            let std_error = if returns.len() > 1 {
+                let var = returns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                (var / n).sqrt()
+            } else {
+                0.0
+            };
+            EdgeBucket { lower, trades: returns.len(), mean_return: mean, std_error }
+        })
+        .collect()
+}
+
+/// New threshold for one category, or `None` to leave it unchanged.
+///
+/// The target is the lower edge of the smallest bucket (with at least
+/// `min_bucket_trades`) whose mean return clears zero by `confidence_z`
+/// standard errors; if no such bucket exists the target is
+/// `max_threshold`. Fewer than `min_resolutions` rows, or no bucket with
+/// enough trades, is insufficient data and changes nothing.
+pub fn recompute_threshold(
+    rows: &[EdgeRealization],
+    current: Decimal,
+    cfg: &AdaptiveThresholdConfig,
+) -> Option<Decimal> {
+    if rows.len() < cfg.min_resolutions {
+        return None;
+    }
+    let buckets: Vec<EdgeBucket> = bucket_realizations(rows, cfg.bucket_width)
+        .into_iter()
+        .filter(|b| b.trades >= cfg.min_bucket_trades)
+        .collect();
+    if buckets.is_empty() {
+        return None;
+    }
+
+    let target = buckets
+        .iter()
+        .find(|b| b.mean_return - cfg.confidence_z * b.std_error > 0.0)
+        .map(|b| b.lower)
+        .unwrap_or(cfg.max_threshold)
+        .clamp(cfg.min_threshold, cfg.max_threshold);
+    let step = (target - current).clamp(-cfg.max_step, cfg.max_step);
+    let next = (current + step).clamp(cfg.min_threshold, cfg.max_threshold);
+
+    (next != current).then_some(next)
+}
+
+/// Record a resolved bet's detected edge and return, trimming old rows.
+pub fn record_realization(state: &mut AgentState, realization: EdgeRealization) {
+    state.edge_realizations.push(realization);
+    let excess = state.edge_realizations.len().saturating_sub(MAX_REALIZATIONS);
+    state.edge_realizations.drain(..excess);
+}
+
+/// Threshold in effect for `category`: the adaptive override if any,
+/// otherwise the configured value.
+pub fn effective_threshold(state: &AgentState, base: &EdgeConfig, category: MarketCategory) -> Decimal {
+    state
+        .thresholds
+        .current
+        .get(&category)
+        .copied()
+        .unwrap_or_else(|| base.threshold_for(&category))
+}
+
+/// Run the periodic review if one is due, updating `state.thresholds`.
+///
+/// Returns the adjustments made (empty when disabled, not yet due, or
+/// nothing changed).
+pub fn review(
+    state: &mut AgentState,
+    base: &EdgeConfig,
+    cfg: &AdaptiveThresholdConfig,
+    now: DateTime<Utc>,
+) -> Vec<ThresholdAdjustment> {
+    if !cfg.enabled {
+        return Vec::new();
+    }
+    if let Some(last) = state.thresholds.last_review {
+        if now - last < Duration::days(cfg.review_days) {
+            return Vec::new();
+        }
+    }
+    state.thresholds.last_review = Some(now);
+
+    let mut adjustments = Vec::new();
+    for &category in MarketCategory::ALL {
+        if cfg.frozen.contains(&category) {
+            continue;
+        }
+        let rows: Vec<EdgeRealization> = state
+            .edge_realizations
+            .iter()
+            .filter(|r| r.category == category)
+            .cloned()
+            .collect();
+        let current = effective_threshold(state, base, category);
+        let Some(threshold) = recompute_threshold(&rows, current, cfg) else {
+            continue;
+        };
+        info!(
+            category = %category,
+            from = %current,
+            to = %threshold,
+            resolutions = rows.len(),
+            "Adaptive edge threshold adjusted"
+        );
+        state.thresholds.current.insert(category, threshold);
+        adjustments.push(ThresholdAdjustment {
+            category,
+            previous: current,
+            threshold,
+            effective_at: now,
+            resolutions: rows.len(),
+        });
+    }
+    state.thresholds.adjustments.extend(adjustments.iter().cloned());
+    adjustments
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// `n` bets at `edge` on a 10-unit stake, cycling through `returns`.
+    fn rows(category: MarketCategory, edge: Decimal, n: usize, returns: &[Decimal]) -> Vec<EdgeRealization> {
+        (0..n)
+            .map(|i| EdgeRealization {
+                category,
+                edge,
+                stake: dec!(10),
+                pnl: dec!(10) * returns[i % returns.len()],
+                resolved_at: Utc::now(),
+            })
+            .collect()
+    }
+
+    fn cfg() -> AdaptiveThresholdConfig {
+        AdaptiveThresholdConfig {
+            enabled: true,
+            min_resolutions: 40,
+            min_bucket_trades: 20,
+            ..AdaptiveThresholdConfig::default()
+        }
+    }
+
+    /// Losing 6–8% bucket, winning 8–10% bucket.
+    fn synthetic_table() -> Vec<EdgeRealization> {
+        let mut t = rows(MarketCategory::Weather, dec!(0.07), 30, &[dec!(-1), dec!(0.5)]);
+        t.extend(rows(MarketCategory::Weather, dec!(0.09), 30, &[dec!(0.8), dec!(-1), dec!(0.9)]));
+        t
+    }
+
+    #[test]
+    fn test_bucket_realizations() {
+        let buckets = bucket_realizations(&synthetic_table(), dec!(0.02));
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].lower, dec!(0.06));
+        assert_eq!(buckets[0].trades, 30);
+        assert!((buckets[0].mean_return - -0.25).abs() < 1e-9);
+        assert_eq!(buckets[1].lower, dec!(0.08));
+        assert!(buckets[1].mean_return > 0.2);
+        assert!(buckets[1].std_error > 0.0);
+    }
+
+    #[test]
+    fn test_threshold_moves_up_by_one_step() {
+        // Target is 8% (first profitable bucket); from 6% one 1-point step.
+        assert_eq!(recompute_threshold(&synthetic_table(), dec!(0.06), &cfg()), Some(dec!(0.07)));
+        assert_eq!(recompute_threshold(&synthetic_table(), dec!(0.07), &cfg()), Some(dec!(0.08)));
+        // Already at target.
+        assert_eq!(recompute_threshold(&synthetic_table(), dec!(0.08), &cfg()), None);
+    }
+
+    #[test]
+    fn test_threshold_moves_down_towards_profitable_bucket() {
+        let t = rows(MarketCategory::Sports, dec!(0.05), 60, &[dec!(0.3), dec!(0.1)]);
+        assert_eq!(recompute_threshold(&t, dec!(0.08), &cfg()), Some(dec!(0.07)));
+        // Never below the configured minimum.
+        let c = AdaptiveThresholdConfig { min_threshold: dec!(0.075), ..cfg() };
+        assert_eq!(recompute_threshold(&t, dec!(0.08), &c), Some(dec!(0.075)));
+    }
+
+    #[test]
+    fn test_no_profitable_bucket_targets_max() {
+        let t = rows(MarketCategory::Politics, dec!(0.13), 60, &[dec!(-1), dec!(0.2)]);
+        let c = AdaptiveThresholdConfig { max_threshold: dec!(0.125), ..cfg() };
+        assert_eq!(recompute_threshold(&t, dec!(0.12), &c), Some(dec!(0.125)));
+    }
+
+    #[test]
+    fn test_confidence_margin_blocks_noisy_bucket() {
+        // Slightly positive mean, huge variance: not significant at z = 1.
+        // Counted as unprofitable, so the threshold climbs.
+        let t = rows(MarketCategory::Culture, dec!(0.11), 60, &[dec!(1.0), dec!(-0.95)]);
+        assert_eq!(recompute_threshold(&t, dec!(0.10), &cfg()), Some(dec!(0.11)));
+        // Without the margin the 10–12% bucket is accepted as profitable.
+        let lax = AdaptiveThresholdConfig { confidence_z: 0.0, ..cfg() };
+        assert_eq!(recompute_threshold(&t, dec!(0.10), &lax), None);
+    }
+
+    #[test]
+    fn test_insufficient_data_no_change() {
+        let few = rows(MarketCategory::Weather, dec!(0.09), 39, &[dec!(1)]);
+        assert_eq!(recompute_threshold(&few, dec!(0.06), &cfg()), None);
+        // Enough rows overall, but spread too thin for any bucket.
+        let mut thin = Vec::new();
+        for i in 0..10 {
+            thin.extend(rows(MarketCategory::Weather, Decimal::new(5 + 2 * i, 2), 5, &[dec!(1)]));
+        }
+        assert_eq!(recompute_threshold(&thin, dec!(0.06), &cfg()), None);
+        assert_eq!(recompute_threshold(&[], dec!(0.06), &cfg()), None);
+    }
+
+    #[test]
+    fn test_review_schedule_and_freeze() {
+        let mut state = AgentState::new(dec!(100));
+        state.edge_realizations = synthetic_table();
+        state.edge_realizations.extend(rows(MarketCategory::Sports, dec!(0.11), 60, &[dec!(0.5)]));
+        let base = EdgeConfig::default();
+        let c = AdaptiveThresholdConfig { frozen: vec![MarketCategory::Sports], ..cfg() };
+        let now = Utc::now();
+
+        let adj = review(&mut state, &base, &c, now);
+        assert_eq!(adj.len(), 1);
+        assert_eq!(adj[0].category, MarketCategory::Weather);
+        assert_eq!((adj[0].previous, adj[0].threshold), (dec!(0.06), dec!(0.07)));
+        assert_eq!(effective_threshold(&state, &base, MarketCategory::Weather), dec!(0.07));
+        assert_eq!(effective_threshold(&state, &base, MarketCategory::Sports), dec!(0.08));
+
+        // Not due again until a week later.
+        assert!(review(&mut state, &base, &c, now + Duration::days(6)).is_empty());
+        let adj = review(&mut state, &base, &c, now + Duration::days(7));
+        assert_eq!(adj[0].threshold, dec!(0.08));
+        assert_eq!(state.thresholds.adjustments.len(), 2);
+
+        // Disabled: never reviews.
+        let off = AdaptiveThresholdConfig { enabled: false, ..cfg() };
+        assert!(review(&mut state, &base, &off, now + Duration::days(30)).is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+//! Cross-venue price arbitrage.
+//!
+//! The scanner matches markets on different platforms that ask about the
+//! same event ([`same_event_clusters`](crate::engine::scanner::same_event_clusters)).
+//! Buying YES on one member and NO on another pays out one share whichever
+//! way the event resolves, so when
+//!
+//! ```text
+//! price_yes(a) + price_no(b) < payout after fees
+//! ```
+//!
+//! the pair locks in a profit with no forecast at all. The payout is one,
+//! less the winning venue's commission on its leg's winnings
+//! (`[strategy.venues].fees`); the worse of the two outcomes is used, and
+//! a pair must clear `min_margin` of its combined stake after it.
+//!
+//! Both legs buy the same number of shares. That number is sized by depth —
+//! at most `max_depth_pct` of either venue's liquidity — and capped at
+//! `max_pair_pct` of the smaller venue bankroll, not by Kelly. Only
+//! executable venues are paired, and Mana venues (Manifold) only with each
+//! other.
+//!
+//! The legs are approved together by
+//! [`RiskManager::approve_pair_at`](super::risk::RiskManager::approve_pair_at):
+//! a rejection of either cancels the pair.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+
+use super::edge::Edge;
+use super::kelly::SizedBet;
+use super::links::link_key;
+use crate::config::ArbitrageConfig;
+use crate::types::{AgentState, Estimate, Market, Side};
+
+/// Smallest stake placed on a leg.
+const MIN_LEG_STAKE: Decimal = dec!(1.0);
+
+/// Two legs that together pay out whichever way the event resolves.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArbitragePair {
+    /// Cluster id, as in [`VenueChoice::cluster`](super::venue::VenueChoice::cluster).
+    pub cluster: String,
+    /// YES on one venue.
+    pub yes_leg: SizedBet,
+    /// NO on the other.
+    pub no_leg: SizedBet,
+    /// Price of one share of each leg.
+    pub cost: Decimal,
+    /// Guaranteed profit as a fraction of the combined stake, after fees.
+    pub margin: Decimal,
+}
+
+impl ArbitragePair {
+    pub fn legs(&self) -> [&SizedBet; 2] {
+        [&self.yes_leg, &self.no_leg]
+    }
+
+    /// Combined stake of both legs.
+    pub fn stake(&self) -> Decimal {
+        self.yes_leg.bet_amount + self.no_leg.bet_amount
+    }
+}
+
+/// Finds arbitrage pairs within the same-event clusters.
+pub struct ArbitrageDetector {
+    config: ArbitrageConfig,
+    /// Commission on net winnings per platform.
+    fees: HashMap<String, Decimal>,
+    /// Platforms the executor can place orders on.
+    executable: HashSet<String>,
+    clusters: Vec<Vec<Market>>,
+}
+
+impl ArbitrageDetector {
+    pub fn new(
+        config: ArbitrageConfig,
+        fees: HashMap<String, Decimal>,
+        executable: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            config,
+            fees,
+            executable: executable.into_iter().collect(),
+            clusters: Vec::new(),
+        }
+    }
+
+    /// Replace the clusters (call once per cycle after the scan).
+    pub fn set_clusters(&mut self, clusters: Vec<Vec<Market>>) {
+        self.clusters = clusters;
+    }
+
+    /// The best pair in each cluster that clears `min_margin`, sized against
+    /// the platform balances in `state`, best margin first.
+    pub fn find_at(&self, state: &AgentState, now: DateTime<Utc>) -> Vec<ArbitragePair> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        let mut pairs: Vec<ArbitragePair> = self
+            .clusters
+            .iter()
+            .filter_map(|cluster| {
+                let members: Vec<&Market> = cluster.iter().filter(|m| self.executable.contains(&m.platform)).collect();
+                let id = format!("cluster:{}", link_key(&cluster.first()?.platform, &cluster.first()?.id));
+                let mut best: Option<ArbitragePair> = None;
+                for yes in &members {
+                    for no in &members {
+                        if link_key(&yes.platform, &yes.id) == link_key(&no.platform, &no.id)
+                            || (yes.platform == "manifold") != (no.platform == "manifold")
+                        {
+                            continue;
+                        }
+                        let Some(pair) = self.price(&id, yes, no, state, now) else { continue };
+                        if best.as_ref().is_none_or(|b| pair.margin > b.margin) {
+                            best = Some(pair);
+                        }
+                    }
+                }
+                best
+            })
+            .collect();
+        pairs.sort_by_key(|p| std::cmp::Reverse(p.margin));
+        pairs
+    }
+
+    /// Price YES on `yes` against NO on `no`, with both legs sized when the
+    /// pair clears `min_margin` and the minimum stake.
+    fn price(
+        &self,
+        cluster: &str,
+        yes: &Market,
+        no: &Market,
+        state: &AgentState,
+        now: DateTime<Utc>,
+    ) -> Option<ArbitragePair> {
+        let (yes_price, no_price) = (yes.current_price_yes, no.current_price_no);
+        let tradeable = |p: Decimal| p > Decimal::ZERO && p < Decimal::ONE;
+        if !tradeable(yes_price) || !tradeable(no_price) {
+            return None;
+        }
+        let fee = |platform: &str| self.fees.get(platform).copied().unwrap_or(Decimal::ZERO);
+
+        // One share of each leg: the winning leg pays one, less its venue's
+        // commission on the winnings.
+        let cost = yes_price + no_price;
+        let payout = (Decimal::ONE - fee(&yes.platform) * (Decimal::ONE - yes_price))
+            .min(Decimal::ONE - fee(&no.platform) * (Decimal::ONE - no_price));
+        let margin = (payout - cost) / cost;
+        if margin <= Decimal::ZERO || margin < self.config.min_margin {
+            return None;
+        }
+
+        let (yes_bankroll, no_bankroll) = (state.bankroll_for(&yes.platform), state.bankroll_for(&no.platform));
+        let shares = (yes.liquidity * self.config.max_depth_pct / yes_price)
+            .min(no.liquidity * self.config.max_depth_pct / no_price)
+            .min(yes_bankroll.min(no_bankroll) * self.config.max_pair_pct / cost);
+        if shares * yes_price < MIN_LEG_STAKE || shares * no_price < MIN_LEG_STAKE {
+            return None;
+        }
+
+        let gap = Decimal::ONE - cost;
+        let leg = |market: &Market, side: Side, price: Decimal, other: &Market, bankroll: Decimal| {
+            let stake = shares * price;
+            let (probability, against) = match side {
+                Side::Yes => (Decimal::ONE - no_price, format!("NO at {no_price}")),
+                Side::No => (yes_price, format!("YES at {yes_price}")),
+            };
+            let estimate = Estimate {
+                probability,
+                confidence: Decimal::ONE,
+                reasoning: format!(
+                    "Arbitrage against {} ({against}); margin {:.2}% after fees",
+                    link_key(&other.platform, &other.id),
+                    margin * dec!(100)
+                ),
+                tokens_used: 0,
+                cost: Decimal::ZERO,
+                critique: None,
+                served_by: None,
+                tier: None,
+            };
+            SizedBet {
+                edge: Edge {
+                    market: market.clone(),
+                    estimate,
+                    side,
+                    edge: gap,
+                    signed_edge: probability - market.current_price_yes,
+                    raw_edge: gap,
+                    // The pair clears `min_margin`, not an edge threshold.
+                    effective_threshold: Decimal::ZERO,
+                },
+                kelly_fraction: Decimal::ZERO,
+                bet_fraction: if bankroll > Decimal::ZERO { stake / bankroll } else { Decimal::ZERO },
+                bet_amount: stake,
+                expected_value: stake * margin,
+                net_expected_value: stake * margin,
+                days_to_resolution: (market.deadline - now).num_seconds().max(0) as f64 / 86_400.0,
+                discounted_edge: margin,
+                risk_context: None,
+                venue: None,
+                // Hedged legs add no correlated exposure
+                correlation_key: None,
+                idempotency_key: None,
+            }
+        };
+
+        Some(ArbitragePair {
+            cluster: cluster.to_string(),
+            yes_leg: leg(yes, Side::Yes, yes_price, no, yes_bankroll),
+            no_leg: leg(no, Side::No, no_price, yes, no_bankroll),
+            cost,
+            margin,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MarketCategory;
+
+    fn market(platform: &str, id: &str, yes: Decimal, no: Decimal, liquidity: Decimal) -> Market {
+        Market {
+            id: id.into(),
+            platform: platform.into(),
+            category: MarketCategory::Politics,
+            current_price_yes: yes,
+            current_price_no: no,
+            liquidity,
+            ..Market::sample()
+        }
+    }
+
+    fn detector(clusters: Vec<Vec<Market>>) -> ArbitrageDetector {
+        let config = ArbitrageConfig { enabled: true, ..ArbitrageConfig::default() };
+        let fees = HashMap::from([("betfair".to_string(), dec!(0.05))]);
+        let mut d = ArbitrageDetector::new(config, fees, ["betfair".to_string(), "polymarket".to_string()]);
+        d.set_clusters(clusters);
+        d
+    }
+
+    fn state() -> AgentState {
+        AgentState::new(dec!(10000))
+    }
+
+    #[test]
+    fn test_finds_gap_and_sizes_by_depth() {
+        // 42¢ YES on Polymarket, 45¢ NO on Betfair (55% YES): 13¢ under par,
+        // with Betfair's 5% on a NO win still leaving ~10%.
+        let pm = market("polymarket", "pm", dec!(0.42), dec!(0.60), dec!(2000));
+        let bf = market("betfair", "1.1", dec!(0.55), dec!(0.45), dec!(10000));
+        let pairs = detector(vec![vec![pm, bf]]).find_at(&state(), Utc::now());
+
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs[0];
+        assert_eq!(pair.yes_leg.edge.market.id, "pm");
+        assert_eq!((pair.no_leg.edge.market.id.as_str(), pair.no_leg.edge.side), ("1.1", Side::No));
+        assert_eq!(pair.cost, dec!(0.87));
+        assert!(pair.margin > dec!(0.09) && pair.margin < dec!(0.15), "{}", pair.margin);
+        // Polymarket's depth binds: 5% of 2000 = $100 on YES, the same
+        // number of shares on NO
+        assert_eq!(pair.yes_leg.bet_amount.round_dp(6), dec!(100));
+        let shares = pair.yes_leg.bet_amount / dec!(0.42);
+        assert_eq!(pair.no_leg.bet_amount.round_dp(6), (shares * dec!(0.45)).round_dp(6));
+    }
+
+    #[test]
+    fn test_gap_eaten_by_fees() {
+        // 2¢ under par, but a YES win on Betfair pays 5% of 0.70.
+        let bf = market("betfair", "1.1", dec!(0.30), dec!(0.72), dec!(10000));
+        let pm = market("polymarket", "pm", dec!(0.30), dec!(0.68), dec!(10000));
+        assert!(detector(vec![vec![bf, pm]]).find_at(&state(), Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_skips_non_executable_leg() {
+        let pm = market("polymarket", "pm", dec!(0.42), dec!(0.60), dec!(2000));
+        let mc = market("metaculus", "mc", dec!(0.55), dec!(0.45), dec!(10000));
+        assert!(detector(vec![vec![pm, mc]]).find_at(&state(), Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_disabled_finds_nothing() {
+        let pm = market("polymarket", "pm", dec!(0.42), dec!(0.60), dec!(2000));
+        let bf = market("betfair", "1.1", dec!(0.55), dec!(0.45), dec!(10000));
+        let mut d = detector(vec![vec![pm, bf]]);
+        d.config.enabled = false;
+        assert!(d.find_at(&state(), Utc::now()).is_empty());
+    }
+}
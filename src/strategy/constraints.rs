@@ -0,0 +1,141 @@
+//! Per-platform stake granularity.
+//!
+//! Kelly sizes in continuous amounts (3.4721), but venues take stakes in
+//! fixed steps: whole mana on Manifold, at least 2.00 a bet on Betfair,
+//! whole-dollar stakes on ForecastEx. [`BetConstraints::normalise`] caps a
+//! sized stake at the platform's maximum, rounds it down to its increment,
+//! and refuses it when that leaves less than the minimum, so the amount the
+//! risk manager approves is the one the venue will take.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Stakes a platform accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BetConstraints {
+    /// Smallest stake accepted.
+    #[serde(default)]
+    pub min_stake: Decimal,
+    /// Stake step; amounts are rounded down to a multiple of it.
+    #[serde(default = "BetConstraints::default_increment")]
+    pub increment: Decimal,
+    /// Largest stake placed in one bet (`None` = uncapped).
+    #[serde(default)]
+    pub max_stake: Option<Decimal>,
+}
+
+impl Default for BetConstraints {
+    /// Whole cents, no minimum, no cap — for platforms not listed.
+    fn default() -> Self {
+        Self { min_stake: Decimal::ZERO, increment: Self::default_increment(), max_stake: None }
+    }
+}
+
+/// Why a sized stake can't be placed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StakeRejection {
+    /// Rounded down to the increment, the stake is under the minimum.
+    BelowMinStake { stake: Decimal, min_stake: Decimal },
+}
+
+impl StakeRejection {
+    /// Stable code of the rejection (the serialised `kind` tag).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BelowMinStake { .. } => "below_min_stake",
+        }
+    }
+}
+
+impl std::fmt::Display for StakeRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BelowMinStake { stake, min_stake } => {
+                write!(f, "Stake {stake} below platform minimum {min_stake}")
+            }
+        }
+    }
+}
+
+impl BetConstraints {
+    fn default_increment() -> Decimal { dec!(0.01) }
+
+    /// Built-in constraints: integer mana on Manifold, 2.00 minimum on
+    /// Betfair, whole-dollar stakes on ForecastEx.
+    pub fn defaults() -> HashMap<String, BetConstraints> {
+        HashMap::from([
+            ("manifold".to_string(), Self { min_stake: dec!(1), increment: dec!(1), max_stake: None }),
+            ("betfair".to_string(), Self { min_stake: dec!(2.00), increment: dec!(0.01), max_stake: None }),
+            ("forecastex".to_string(), Self { min_stake: dec!(1), increment: dec!(1), max_stake: None }),
+        ])
+    }
+
+    /// Constraints for `platform` in `table`, or the unconstrained default.
+    pub fn for_platform(table: &HashMap<String, BetConstraints>, platform: &str) -> Self {
+        table.get(platform).copied().unwrap_or_default()
+    }
+
+    /// `amount` capped at the maximum and rounded down to the increment,
+    /// or why it can't be bet.
+    pub fn normalise(&self, amount: Decimal) -> Result<Decimal, StakeRejection> {
+        let capped = self.max_stake.map_or(amount, |max| amount.min(max));
+        let stake = if self.increment > Decimal::ZERO {
+            (capped / self.increment).floor() * self.increment
+        } else {
+            capped
+        };
+        if stake < self.min_stake || stake <= Decimal::ZERO {
+            return Err(StakeRejection::BelowMinStake { stake, min_stake: self.min_stake });
+        }
+        Ok(stake)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(platform: &str) -> BetConstraints {
+        BetConstraints::for_platform(&BetConstraints::defaults(), platform)
+    }
+
+    #[test]
+    fn test_rounds_down_to_increment() {
+        assert_eq!(profile("manifold").normalise(dec!(3.4721)), Ok(dec!(3)));
+        assert_eq!(profile("betfair").normalise(dec!(3.4721)), Ok(dec!(3.47)));
+        assert_eq!(profile("forecastex").normalise(dec!(12.999)), Ok(dec!(12)));
+        // Unlisted platforms trade in cents.
+        assert_eq!(profile("kalshi").normalise(dec!(0.4721)), Ok(dec!(0.47)));
+    }
+
+    #[test]
+    fn test_rejects_below_minimum() {
+        assert_eq!(
+            profile("manifold").normalise(dec!(0.99)),
+            Err(StakeRejection::BelowMinStake { stake: dec!(0), min_stake: dec!(1) })
+        );
+        assert_eq!(
+            profile("betfair").normalise(dec!(1.999)),
+            Err(StakeRejection::BelowMinStake { stake: dec!(1.99), min_stake: dec!(2.00) })
+        );
+        assert_eq!(profile("betfair").normalise(dec!(2.001)), Ok(dec!(2)));
+    }
+
+    #[test]
+    fn test_clamps_to_maximum() {
+        let manifold = BetConstraints { max_stake: Some(dec!(250)), ..profile("manifold") };
+        assert_eq!(manifold.normalise(dec!(731.6)), Ok(dec!(250)));
+        // A cap off the increment still lands on it.
+        let betfair = BetConstraints { max_stake: Some(dec!(50.005)), ..profile("betfair") };
+        assert_eq!(betfair.normalise(dec!(80)), Ok(dec!(50)));
+    }
+}
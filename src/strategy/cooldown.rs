@@ -0,0 +1,290 @@
+//! Category cool-downs after losing streaks.
+//!
+//! A run of resolved losses in one category usually means the model is
+//! mis-calibrated there right now. After `losing_streak` consecutive losses
+//! the category cools down: the risk manager blocks new bets in it, or
+//! holds them to a multiple of the category's edge threshold. A cool-down
+//! lifts after a win, after `lift_after_hours`, or after `lift_after_skips`
+//! rejected opportunities.
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::info;
+
+use super::risk::RejectionReason;
+use super::DecisionRecord;
+use crate::config::CoolDownConfig;
+use crate::types::{AgentState, CategoryStreak, MarketCategory};
+
+/// Record a resolved bet in `category`. Returns true when this loss
+/// started a cool-down.
+pub fn record_resolution(
+    state: &mut AgentState,
+    category: MarketCategory,
+    won: bool,
+    cfg: &CoolDownConfig,
+    now: DateTime<Utc>,
+) -> bool {
+    let streak = state.cool_downs.categories.entry(category).or_default();
+    if won {
+        if streak.cooling_down() {
+            info!(category = %category, "Category cool-down lifted after a win");
+        }
+        *streak = CategoryStreak::default();
+        return false;
+    }
+
+    streak.consecutive_losses += 1;
+    if cfg.losing_streak == 0 || streak.cooling_down() || streak.consecutive_losses < cfg.losing_streak {
+        return false;
+    }
+    streak.cool_down_since = Some(now);
+    streak.skipped = 0;
+    info!(
+        category = %category,
+        losses = streak.consecutive_losses,
+        mode = ?cfg.mode,
+        "Category cool-down started after losing streak"
+    );
+    true
+}
+
+/// Count cool-down rejections from a strategy pass, lifting any cool-down
+/// that has now skipped `lift_after_skips` opportunities.
+pub fn record_skips(state: &mut AgentState, decisions: &[DecisionRecord], cfg: &CoolDownConfig) -> Vec<MarketCategory> {
+    for d in decisions {
+        if let DecisionRecord::RiskRejected { reason: RejectionReason::CategoryCoolDown { category, .. }, .. } = d {
+            if let Some(streak) = state.cool_downs.categories.get_mut(category) {
+                streak.skipped += 1;
+            }
+        }
+    }
+    lift_where(state, "skipped opportunities", |s| {
+        cfg.lift_after_skips > 0 && s.skipped >= cfg.lift_after_skips
+    })
+}
+
+/// Lift cool-downs older than `lift_after_hours`.
+pub fn lift_expired(state: &mut AgentState, cfg: &CoolDownConfig, now: DateTime<Utc>) -> Vec<MarketCategory> {
+    if cfg.lift_after_hours <= 0 {
+        return Vec::new();
+    }
+    let period = Duration::hours(cfg.lift_after_hours);
+    lift_where(state, "time period", |s| s.cool_down_since.is_some_and(|t| now - t >= period))
+}
+
+fn lift_where(state: &mut AgentState, cause: &str, due: impl Fn(&CategoryStreak) -> bool) -> Vec<MarketCategory> {
+    let mut lifted: Vec<MarketCategory> = state
+        .cool_downs
+        .categories
+        .iter_mut()
+        .filter(|(_, s)| s.cooling_down() && due(s))
+        .map(|(&category, s)| {
+            *s = CategoryStreak::default();
+            category
+        })
+        .collect();
+    lifted.sort_by_key(|c| c.to_string());
+    for category in &lifted {
+        info!(category = %category, cause, "Category cool-down lifted");
+    }
+    lifted
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CoolDownMode;
+    use crate::strategy::risk::{RiskConfig, RiskManager};
+    use crate::strategy::edge::Edge;
+    use crate::strategy::kelly::SizedBet;
+    use crate::types::{Estimate, Market, Side};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn cfg(mode: CoolDownMode) -> CoolDownConfig {
+        CoolDownConfig {
+            losing_streak: 4,
+            mode,
+            edge_multiplier: dec!(1.5),
+            lift_after_hours: 48,
+            lift_after_skips: 3,
+        }
+    }
+
+    /// Apply a resolution sequence (true = win) in `category`.
+    fn resolve(state: &mut AgentState, category: MarketCategory, outcomes: &[bool], cfg: &CoolDownConfig, now: DateTime<Utc>) {
+        for &won in outcomes {
+            record_resolution(state, category, won, cfg, now);
+        }
+    }
+
+    fn bet(category: MarketCategory, edge: Decimal) -> SizedBet {
+        SizedBet {
+            edge: Edge {
+                market: Market {
+                    id: "m1".into(),
+                    platform: "manifold".into(),
+                    question: "Test?".into(),
+                    description: String::new(),
+                    category,
+                    current_price_yes: dec!(0.50),
+                    current_price_no: dec!(0.50),
+                    volume_24h: dec!(100),
+                    liquidity: dec!(500),
+                    deadline: Utc::now() + Duration::days(30),
+                    resolution_criteria: String::new(),
+                    url: String::new(),
+                    cross_refs: Default::default(),
+                    event_group: None,
+                    facts: None,
+                    tags: Vec::new(),
+                },
+                estimate: Estimate {
+                    probability: dec!(0.50) + edge,
+                    confidence: dec!(0.8),
+                    reasoning: String::new(),
+                    tokens_used: 100,
+                    cost: dec!(0.01),
+                    critique: None,
+                    served_by: None,
+                    tier: None,
+                },
+                side: Side::Yes,
+                edge,
+                signed_edge: edge,
+                raw_edge: edge,
+                effective_threshold: dec!(0.06),
+            },
+            kelly_fraction: dec!(0.10),
+            bet_fraction: dec!(0.05),
+            bet_amount: dec!(10),
+            expected_value: dec!(1),
+            net_expected_value: dec!(1),
+            days_to_resolution: 30.0,
+            discounted_edge: edge,
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
+        }
+    }
+
+    fn check(cfg: &CoolDownConfig, state: &AgentState, bet: &SizedBet) -> Result<(), RejectionReason> {
+        let rm = RiskManager::new(RiskConfig { cool_down: cfg.clone(), ..RiskConfig::default() });
+        rm.check_cool_down(bet, state, dec!(0.12))
+    }
+
+    #[test]
+    fn test_streak_triggers_only_on_consecutive_losses() {
+        let cfg = cfg(CoolDownMode::Block);
+        let mut state = AgentState::new(dec!(1000));
+        let now = Utc::now();
+
+        // A win inside the run resets the count.
+        resolve(&mut state, MarketCategory::Politics, &[false, false, false, true, false, false, false], &cfg, now);
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_none());
+        assert_eq!(state.cool_downs.categories[&MarketCategory::Politics].consecutive_losses, 3);
+
+        assert!(record_resolution(&mut state, MarketCategory::Politics, false, &cfg, now));
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_some());
+        // Streaks are per category.
+        assert!(state.cool_downs.active(MarketCategory::Sports).is_none());
+        // Further losses extend the streak without restarting the clock.
+        assert!(!record_resolution(&mut state, MarketCategory::Politics, false, &cfg, now + Duration::hours(1)));
+        assert_eq!(state.cool_downs.categories[&MarketCategory::Politics].cool_down_since, Some(now));
+
+        // Disabled: never triggers.
+        let off = CoolDownConfig { losing_streak: 0, ..cfg };
+        let mut state = AgentState::new(dec!(1000));
+        resolve(&mut state, MarketCategory::Politics, &[false; 10], &off, now);
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_none());
+    }
+
+    #[test]
+    fn test_block_mode_rejects_every_bet_in_category() {
+        let cfg = cfg(CoolDownMode::Block);
+        let mut state = AgentState::new(dec!(1000));
+        resolve(&mut state, MarketCategory::Politics, &[false; 4], &cfg, Utc::now());
+
+        let err = check(&cfg, &state, &bet(MarketCategory::Politics, dec!(0.40))).unwrap_err();
+        assert!(matches!(
+            err,
+            RejectionReason::CategoryCoolDown { category: MarketCategory::Politics, losses: 4, required_edge: None }
+        ));
+        assert!(check(&cfg, &state, &bet(MarketCategory::Sports, dec!(0.13))).is_ok());
+    }
+
+    #[test]
+    fn test_multiplier_mode_requires_larger_edge() {
+        let cfg = cfg(CoolDownMode::Multiplier);
+        let mut state = AgentState::new(dec!(1000));
+        resolve(&mut state, MarketCategory::Politics, &[false; 4], &cfg, Utc::now());
+
+        // Threshold 12% × 1.5 = 18%.
+        let err = check(&cfg, &state, &bet(MarketCategory::Politics, dec!(0.15))).unwrap_err();
+        assert!(matches!(err, RejectionReason::CategoryCoolDown { required_edge: Some(r), .. } if r == dec!(0.18)));
+        assert!(check(&cfg, &state, &bet(MarketCategory::Politics, dec!(0.18))).is_ok());
+    }
+
+    #[test]
+    fn test_win_lifts_cool_down() {
+        let cfg = cfg(CoolDownMode::Block);
+        let mut state = AgentState::new(dec!(1000));
+        let now = Utc::now();
+        resolve(&mut state, MarketCategory::Politics, &[false, false, false, false, true], &cfg, now);
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_none());
+        assert_eq!(state.cool_downs.categories[&MarketCategory::Politics], CategoryStreak::default());
+        assert!(check(&cfg, &state, &bet(MarketCategory::Politics, dec!(0.13))).is_ok());
+    }
+
+    #[test]
+    fn test_time_period_lifts_cool_down() {
+        let cfg = cfg(CoolDownMode::Block);
+        let mut state = AgentState::new(dec!(1000));
+        let start = Utc::now();
+        resolve(&mut state, MarketCategory::Politics, &[false; 4], &cfg, start);
+
+        assert!(lift_expired(&mut state, &cfg, start + Duration::hours(47)).is_empty());
+        assert_eq!(lift_expired(&mut state, &cfg, start + Duration::hours(48)), vec![MarketCategory::Politics]);
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_none());
+
+        // A fresh streak is needed to cool down again.
+        record_resolution(&mut state, MarketCategory::Politics, false, &cfg, start);
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_none());
+
+        let never = CoolDownConfig { lift_after_hours: 0, ..cfg.clone() };
+        let mut state = AgentState::new(dec!(1000));
+        resolve(&mut state, MarketCategory::Politics, &[false; 4], &never, start);
+        assert!(lift_expired(&mut state, &never, start + Duration::days(365)).is_empty());
+    }
+
+    #[test]
+    fn test_skipped_opportunities_lift_cool_down() {
+        let cfg = cfg(CoolDownMode::Block);
+        let mut state = AgentState::new(dec!(1000));
+        resolve(&mut state, MarketCategory::Politics, &[false; 4], &cfg, Utc::now());
+
+        let rejected = |category| DecisionRecord::RiskRejected {
+            bet: bet(category, dec!(0.20)),
+            reason: RejectionReason::CategoryCoolDown { category, losses: 4, required_edge: None },
+        };
+        let other = DecisionRecord::RiskRejected {
+            bet: bet(MarketCategory::Politics, dec!(0.20)),
+            reason: RejectionReason::MaxBetsPerCycleReached { current: 5, limit: 5 },
+        };
+
+        assert!(record_skips(&mut state, &[rejected(MarketCategory::Politics), other.clone()], &cfg).is_empty());
+        assert_eq!(state.cool_downs.categories[&MarketCategory::Politics].skipped, 1);
+        assert!(record_skips(&mut state, &[rejected(MarketCategory::Politics)], &cfg).is_empty());
+        assert_eq!(
+            record_skips(&mut state, &[rejected(MarketCategory::Politics), other], &cfg),
+            vec![MarketCategory::Politics]
+        );
+        assert!(state.cool_downs.active(MarketCategory::Politics).is_none());
+        assert!(check(&cfg, &state, &bet(MarketCategory::Politics, dec!(0.13))).is_ok());
+    }
+}
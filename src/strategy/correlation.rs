@@ -0,0 +1,108 @@
+//! Correlation groups of markets that are effectively the same bet.
+//!
+//! Category caps don't see that "Fed cuts in March", "Fed funds below 4.5%
+//! by April" and "Recession starts in Q2" all move on the same news. The
+//! operator declares keyword clusters under `[risk.correlation.groups]`:
+//!
+//! ```toml
+//! [risk.correlation.groups]
+//! fed = ["fed", "fomc", "fed funds", "rate cut", "recession"]
+//! ```
+//!
+//! A question joins the group whose keywords it mentions most, and the
+//! risk manager caps the combined stake on each group. Keywords match
+//! whole words, case-insensitively; a multi-word keyword must appear as a
+//! phrase.
+
+use std::collections::BTreeMap;
+
+/// Declared keyword clusters, in name order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorrelationGroups {
+    /// Group name and its keywords, each split into words.
+    groups: Vec<(String, Vec<Vec<String>>)>,
+}
+
+impl CorrelationGroups {
+    pub fn new(groups: &BTreeMap<String, Vec<String>>) -> Self {
+        let groups = groups
+            .iter()
+            .map(|(name, keywords)| {
+                let keywords = keywords.iter().map(|k| words(k)).filter(|k| !k.is_empty()).collect();
+                (name.clone(), keywords)
+            })
+            .collect();
+        Self { groups }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Group `question` belongs to: the one with the most distinct keyword
+    /// hits, ties going to the first by name. `None` when no keyword hits.
+    pub fn key_for(&self, question: &str) -> Option<String> {
+        let question = words(question);
+        let mut best: Option<(&str, usize)> = None;
+        for (name, keywords) in &self.groups {
+            let hits = keywords
+                .iter()
+                .filter(|k| question.windows(k.len()).any(|w| w == k.as_slice()))
+                .count();
+            if hits > 0 && best.is_none_or(|(_, most)| hits > most) {
+                best = Some((name, hits));
+            }
+        }
+        best.map(|(name, _)| name.to_string())
+    }
+}
+
+/// Lowercase alphanumeric words of `text`.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> CorrelationGroups {
+        CorrelationGroups::new(&BTreeMap::from([
+            ("fed".to_string(), vec!["Fed".into(), "FOMC".into(), "fed funds".into(), "rate cut".into(), "recession".into()]),
+            ("oil".to_string(), vec!["oil".into(), "OPEC".into(), "crude".into()]),
+        ]))
+    }
+
+    #[test]
+    fn test_key_for_matches_whole_words_and_phrases() {
+        let g = groups();
+        assert_eq!(g.key_for("Will the Fed cut rates in March?").as_deref(), Some("fed"));
+        assert_eq!(g.key_for("Fed funds below 4.5% by April?").as_deref(), Some("fed"));
+        assert_eq!(g.key_for("Will a US recession start in Q2?").as_deref(), Some("fed"));
+        // "federal" is not "fed"; "funds" alone is not "fed funds".
+        assert_eq!(g.key_for("Will the federal budget pass?"), None);
+        assert_eq!(g.key_for("Will index funds outperform?"), None);
+    }
+
+    #[test]
+    fn test_key_for_picks_group_with_most_hits() {
+        let g = groups();
+        assert_eq!(g.key_for("Will OPEC cut crude output before the Fed meets?").as_deref(), Some("oil"));
+        // One hit each: the tie goes to the first group by name.
+        assert_eq!(g.key_for("Oil above $90 after the FOMC?").as_deref(), Some("fed"));
+    }
+
+    #[test]
+    fn test_empty_groups_assign_nothing() {
+        let g = CorrelationGroups::new(&BTreeMap::from([("blank".to_string(), vec![" ".into()])]));
+        assert_eq!(g.key_for("Anything at all?"), None);
+        assert!(CorrelationGroups::default().is_empty());
+    }
+}
@@ -6,8 +6,10 @@
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+use crate::config::CrossRefDampeningConfig;
 use crate::types::{Estimate, Market, MarketCategory, Side};
 
 // ---------------------------------------------------------------------------
@@ -17,6 +19,7 @@ use crate::types::{Estimate, Market, MarketCategory, Side};
 /// Default mispricing thresholds per category.
 /// Markets must exceed these to be considered actionable.
 /// More uncertain categories require larger edges.
+#[derive(Debug, Clone)]
 pub struct EdgeConfig {
     pub weather_threshold: Decimal,
     pub sports_threshold: Decimal,
@@ -26,6 +29,16 @@ pub struct EdgeConfig {
     pub other_threshold: Decimal,
     /// Minimum absolute edge to consider (noise floor).
     pub min_edge: Decimal,
+    /// Pull towards a Metaculus consensus before the threshold checks.
+    pub cross_ref: CrossRefDampeningConfig,
+    /// Scale the category threshold by the estimate's confidence:
+    /// `threshold × (1 + confidence_k × (1 − confidence))`.
+    pub confidence_scaling: bool,
+    /// Curve steepness `k`: how much stricter a zero-confidence estimate
+    /// is than a certain one (k = 2 → three times the threshold).
+    pub confidence_k: Decimal,
+    /// Estimates below this confidence are skipped whatever their edge.
+    pub min_confidence: Decimal,
 }
 
 impl Default for EdgeConfig {
@@ -38,6 +51,10 @@ impl Default for EdgeConfig {
             culture_threshold: dec!(0.10),
             other_threshold: dec!(0.10),
             min_edge: dec!(0.03),
+            cross_ref: CrossRefDampeningConfig::default(),
+            confidence_scaling: false,
+            confidence_k: dec!(1),
+            min_confidence: dec!(0.10),
         }
     }
 }
@@ -54,6 +71,51 @@ impl EdgeConfig {
             MarketCategory::Other => self.other_threshold,
         }
     }
+
+    /// Threshold an estimate of `confidence` must clear in `category`:
+    /// the category threshold, scaled up for low confidence when enabled.
+    pub fn effective_threshold(&self, category: &MarketCategory, confidence: Decimal) -> Decimal {
+        let base = self.threshold_for(category);
+        if !self.confidence_scaling {
+            return base;
+        }
+        let doubt = (Decimal::ONE - confidence).clamp(Decimal::ZERO, Decimal::ONE);
+        base * (Decimal::ONE + self.confidence_k * doubt)
+    }
+
+    /// Set the threshold for a given category.
+    pub fn set_threshold(&mut self, category: MarketCategory, threshold: Decimal) {
+        let slot = match category {
+            MarketCategory::Weather => &mut self.weather_threshold,
+            MarketCategory::Sports => &mut self.sports_threshold,
+            MarketCategory::Economics => &mut self.economics_threshold,
+            MarketCategory::Politics => &mut self.politics_threshold,
+            MarketCategory::Culture => &mut self.culture_threshold,
+            MarketCategory::Other => &mut self.other_threshold,
+        };
+        *slot = threshold;
+    }
+
+    /// Weight given to the market's Metaculus forecast, if it counts:
+    /// `weight` scaled by the share of `full_weight_forecasters` behind it.
+    /// Markets listed on Metaculus itself are not checked against it.
+    pub fn cross_ref_weight(&self, market: &Market) -> Option<(Decimal, Decimal)> {
+        let cfg = &self.cross_ref;
+        if !cfg.enabled || market.platform == "metaculus" {
+            return None;
+        }
+        let consensus = market.cross_refs.metaculus_prob?;
+        let forecasters = market.cross_refs.metaculus_forecasters.unwrap_or(0);
+        if forecasters == 0 || forecasters < cfg.min_forecasters {
+            return None;
+        }
+        let scale = if forecasters >= cfg.full_weight_forecasters {
+            Decimal::ONE
+        } else {
+            Decimal::from(forecasters) / Decimal::from(cfg.full_weight_forecasters)
+        };
+        Some((consensus, cfg.weight * scale))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -61,13 +123,19 @@ impl EdgeConfig {
 // ---------------------------------------------------------------------------
 
 /// Detected edge (mispricing) in a market.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Edge {
     pub market: Market,
     pub estimate: Estimate,
     pub side: Side,
     pub edge: Decimal,      // absolute edge (always positive)
     pub signed_edge: Decimal, // positive = YES underpriced, negative = NO underpriced
+    /// Absolute edge of the estimate alone, before any cross-reference
+    /// dampening; equal to `edge` when none applied.
+    pub raw_edge: Decimal,
+    /// Threshold `edge` had to clear: the category threshold, scaled for
+    /// confidence when enabled (see [`EdgeConfig::effective_threshold`]).
+    pub effective_threshold: Decimal,
 }
 
 /// Detect mispricings by comparing LLM estimates to market prices.
@@ -85,6 +153,11 @@ impl EdgeDetector {
         &self.config
     }
 
+    /// Override one category's threshold (adaptive thresholds).
+    pub fn set_threshold(&mut self, category: MarketCategory, threshold: Decimal) {
+        self.config.set_threshold(category, threshold);
+    }
+
     /// Find all markets with actionable edges.
     pub fn find_edges(&self, estimates: &[(Market, Estimate)]) -> Vec<Edge> {
         let mut edges = Vec::new();
@@ -105,7 +178,39 @@ impl EdgeDetector {
     fn detect_edge(&self, market: &Market, estimate: &Estimate) -> Option<Edge> {
         let threshold = self.config.threshold_for(&market.category);
         let market_price = market.current_price_yes;
-        let fair_value = estimate.probability;
+        let raw_edge = (estimate.probability - market_price).abs();
+
+        // A well-attended Metaculus forecast pulls the fair value towards it:
+        // an LLM far from both the market and the crowd is more likely wrong
+        // than early.
+        let fair_value = match self.config.cross_ref_weight(market) {
+            Some((consensus, weight)) => {
+                let dampened = estimate.probability + weight * (consensus - estimate.probability);
+                let raw_signed = estimate.probability - market_price;
+                let dampened_signed = dampened - market_price;
+                if dampened_signed.is_zero() || dampened_signed.is_sign_negative() != raw_signed.is_sign_negative() {
+                    debug!(
+                        market_id = %market.id,
+                        raw_edge = %format!("{:.1}%", (raw_edge * dec!(100)).to_f64().unwrap_or(0.0)),
+                        metaculus = %format!("{:.1}%", (consensus * dec!(100)).to_f64().unwrap_or(0.0)),
+                        "Edge neutralised by Metaculus consensus"
+                    );
+                    return None;
+                }
+                if dampened_signed.abs() < threshold && raw_edge >= threshold {
+                    debug!(
+                        market_id = %market.id,
+                        raw_edge = %format!("{:.1}%", (raw_edge * dec!(100)).to_f64().unwrap_or(0.0)),
+                        edge = %format!("{:.1}%", (dampened_signed.abs() * dec!(100)).to_f64().unwrap_or(0.0)),
+                        metaculus = %format!("{:.1}%", (consensus * dec!(100)).to_f64().unwrap_or(0.0)),
+                        "Edge dampened below category threshold by Metaculus consensus"
+                    );
+                    return None;
+                }
+                dampened
+            }
+            None => estimate.probability,
+        };
 
         // Signed edge: positive means YES is underpriced, negative means overpriced
         let signed_edge = fair_value - market_price;
@@ -127,28 +232,27 @@ impl EdgeDetector {
             return None;
         }
 
-        // Absolute confidence floor — the LLM has no useful signal below this.
-        if estimate.confidence < dec!(0.10) {
+        // Confidence floor — the LLM has no useful signal below this.
+        if estimate.confidence < self.config.min_confidence {
             debug!(
                 market_id = %market.id,
                 confidence = %estimate.confidence,
-                "Confidence below absolute floor (0.10) — rejected"
+                min_confidence = %self.config.min_confidence,
+                "Confidence below floor — rejected"
             );
             return None;
         }
 
-        // Graduated confidence gate: low confidence requires a proportionally
+        // Confidence scaling: low confidence requires a proportionally
         // larger edge before we act.
         //
-        // effective_threshold = base × (0.5 + 0.5 × confidence)
-        //   confidence = 1.0 → multiplier = 1.00 → full base threshold
-        //   confidence = 0.5 → multiplier = 0.75 → 25% stricter
-        //   confidence = 0.1 → multiplier = 0.55 → 45% stricter
+        // effective_threshold = base × (1 + k × (1 − confidence))
+        //   k = 1, confidence = 1.0 → 1.0× base threshold
+        //   k = 1, confidence = 0.5 → 1.5× base
+        //   k = 1, confidence = 0.2 → 1.8× base
         //
-        // This replaces the previous binary cliff at 0.30 (which jumped from
-        // 1× to 2× threshold at a single rounding boundary).
-        let confidence_multiplier = dec!(0.5) + dec!(0.5) * estimate.confidence;
-        let effective_threshold = threshold * confidence_multiplier;
+        // Disabled, the effective threshold is the base one.
+        let effective_threshold = self.config.effective_threshold(&market.category, estimate.confidence);
         if abs_edge < effective_threshold {
             debug!(
                 market_id = %market.id,
@@ -178,6 +282,8 @@ impl EdgeDetector {
             side,
             edge: abs_edge,
             signed_edge,
+            raw_edge,
+            effective_threshold,
         })
     }
 }
@@ -206,6 +312,9 @@ mod tests {
             resolution_criteria: String::new(),
             url: String::new(),
             cross_refs: Default::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         }
     }
 
@@ -216,6 +325,9 @@ mod tests {
             reasoning: "test reasoning".to_string(),
             tokens_used: 100,
             cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
         }
     }
 
@@ -268,22 +380,59 @@ mod tests {
     }
 
     #[test]
-    fn test_low_confidence_graduated_threshold() {
+    fn test_low_confidence_unscaled_threshold() {
         let detector = EdgeDetector::new(EdgeConfig::default());
-        // Weather base threshold = 6%.
-        // At confidence=0.20: multiplier = 0.5 + 0.5*0.20 = 0.60
-        // effective_threshold = 0.06 * 0.60 = 0.036
+        // Weather base threshold = 6%; scaling is off by default, so
+        // confidence=0.20 leaves it at 6%.
         let market = make_market("m1", MarketCategory::Weather, dec!(0.40));
 
-        // 3.5% edge — above effective threshold (3.6%) → but check below noise floor first.
-        // min_edge = 0.03: 3.5% > 3%, so noise floor passes.
-        // 3.5% < 3.6% effective threshold → should be rejected.
+        // 3.5% edge clears the 3% noise floor but not the 6% threshold.
         let estimate_small = make_estimate(dec!(0.435), dec!(0.20));
         assert!(detector.detect_edge(&market, &estimate_small).is_none());
 
-        // 10% edge with confidence=0.20 → effective_threshold=3.6% → 10% > 3.6% → accepted.
+        // 10% edge with confidence=0.20 → 10% > 6% → accepted.
         let estimate_large = make_estimate(dec!(0.50), dec!(0.20));
-        assert!(detector.detect_edge(&market, &estimate_large).is_some());
+        let edge = detector.detect_edge(&market, &estimate_large).unwrap();
+        assert_eq!(edge.effective_threshold, dec!(0.06));
+    }
+
+    fn scaled(k: Decimal) -> EdgeDetector {
+        EdgeDetector::new(EdgeConfig { confidence_scaling: true, confidence_k: k, ..EdgeConfig::default() })
+    }
+
+    #[test]
+    fn test_confidence_scaling_filters_low_confidence_large_edge() {
+        // Economics base 10%; k=3 at confidence 0.30 → 10% × (1 + 3 × 0.7) = 31%.
+        let detector = scaled(dec!(3));
+        let market = make_market("m1", MarketCategory::Economics, dec!(0.40));
+        let estimate = make_estimate(dec!(0.65), dec!(0.30)); // 25% edge
+        assert!(detector.detect_edge(&market, &estimate).is_none());
+
+        // Unscaled, the same estimate clears the 10% threshold.
+        assert!(EdgeDetector::new(EdgeConfig::default()).detect_edge(&market, &estimate).is_some());
+    }
+
+    #[test]
+    fn test_confidence_scaling_passes_high_confidence_moderate_edge() {
+        // k=3 at confidence 0.90 → 10% × (1 + 3 × 0.1) = 13%.
+        let detector = scaled(dec!(3));
+        let market = make_market("m1", MarketCategory::Economics, dec!(0.40));
+        let estimate = make_estimate(dec!(0.55), dec!(0.90)); // 15% edge
+        let edge = detector.detect_edge(&market, &estimate).unwrap();
+        assert_eq!(edge.effective_threshold, dec!(0.13));
+        assert_eq!(edge.edge, dec!(0.15));
+
+        // 12% edge falls short of the scaled 13%.
+        assert!(detector.detect_edge(&market, &make_estimate(dec!(0.52), dec!(0.90))).is_none());
+    }
+
+    #[test]
+    fn test_min_confidence_cutoff() {
+        let detector = EdgeDetector::new(EdgeConfig { min_confidence: dec!(0.40), ..EdgeConfig::default() });
+        let market = make_market("m1", MarketCategory::Weather, dec!(0.40));
+        // A 50% edge is skipped at confidence 0.35, taken at 0.40.
+        assert!(detector.detect_edge(&market, &make_estimate(dec!(0.90), dec!(0.35))).is_none());
+        assert!(detector.detect_edge(&market, &make_estimate(dec!(0.90), dec!(0.40))).is_some());
     }
 
     #[test]
@@ -343,4 +492,58 @@ mod tests {
         assert_eq!(config.politics_threshold, dec!(0.12));
         assert_eq!(config.min_edge, dec!(0.03));
     }
+
+    fn dampening_detector() -> EdgeDetector {
+        EdgeDetector::new(EdgeConfig {
+            cross_ref: CrossRefDampeningConfig { enabled: true, ..CrossRefDampeningConfig::default() },
+            ..EdgeConfig::default()
+        })
+    }
+
+    fn with_metaculus(mut market: Market, prob: Decimal, forecasters: u32) -> Market {
+        market.cross_refs.metaculus_prob = Some(prob);
+        market.cross_refs.metaculus_forecasters = Some(forecasters);
+        market
+    }
+
+    #[test]
+    fn test_metaculus_consensus_neutralises_edge() {
+        let detector = dampening_detector();
+        // LLM 70%, market 50%, 300 forecasters at 52%: fair value pulled
+        // to 56.5%, a 6.5% edge against the 12% politics threshold.
+        let market = with_metaculus(make_market("m1", MarketCategory::Politics, dec!(0.50)), dec!(0.52), 300);
+        let estimate = make_estimate(dec!(0.70), dec!(0.9));
+        assert!(detector.detect_edge(&market, &estimate).is_none());
+
+        // Disabled, the same gap trades on its raw 20%.
+        let plain = EdgeDetector::new(EdgeConfig::default());
+        assert_eq!(plain.detect_edge(&market, &estimate).unwrap().edge, dec!(0.20));
+    }
+
+    #[test]
+    fn test_metaculus_weight_scales_with_forecasters() {
+        let detector = dampening_detector();
+        let estimate = make_estimate(dec!(0.70), dec!(0.9));
+
+        // 100 of 200 forecasters: half of the 0.75 weight.
+        let market = with_metaculus(make_market("m1", MarketCategory::Weather, dec!(0.50)), dec!(0.52), 100);
+        let edge = detector.detect_edge(&market, &estimate).unwrap();
+        assert_eq!(edge.raw_edge, dec!(0.20));
+        assert_eq!(edge.edge, dec!(0.1325));
+        assert!(matches!(edge.side, Side::Yes));
+
+        // Below min_forecasters the forecast is ignored.
+        let market = with_metaculus(make_market("m2", MarketCategory::Politics, dec!(0.50)), dec!(0.52), 10);
+        assert_eq!(detector.detect_edge(&market, &estimate).unwrap().edge, dec!(0.20));
+    }
+
+    #[test]
+    fn test_edge_untouched_without_cross_ref() {
+        let detector = dampening_detector();
+        let market = make_market("m1", MarketCategory::Politics, dec!(0.50));
+        let edge = detector.detect_edge(&market, &make_estimate(dec!(0.70), dec!(0.9))).unwrap();
+        assert_eq!(edge.edge, dec!(0.20));
+        assert_eq!(edge.raw_edge, edge.edge);
+        assert_eq!(edge.signed_edge, dec!(0.20));
+    }
 }
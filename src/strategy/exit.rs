@@ -0,0 +1,182 @@
+//! Exits on a spent or reversed edge.
+//!
+//! A position is opened because the estimate put its side above the
+//! market price. Each cycle re-estimates the markets it scans (or reuses a
+//! recent estimate), so the edge left on every held side is known again:
+//! the estimate of the held side less its current price. Once that falls
+//! below `strategy.exits.take_profit_edge` the price has caught up with
+//! the estimate and holding on only carries risk, so the position is
+//! closed to bank the move; once it is negative by more than
+//! `cut_loss_edge` the thesis has broken and the position is cut. Other
+//! positions are held. Closing itself is the auto-exit engine's: a sale
+//! where the venue supports one, an opposing bet otherwise (see
+//! [`crate::engine::auto_exit`]).
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use super::links::link_key;
+use super::DecisionRecord;
+use crate::config::EdgeExitConfig;
+use crate::types::{Estimate, Market, Side, TradeReceipt};
+
+/// Why a position is exited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitTrigger {
+    /// The price has caught up with the estimate.
+    TakeProfit,
+    /// The estimate has turned against the position.
+    CutLoss,
+}
+
+/// An open position re-evaluated against this cycle's estimate.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExitReview {
+    pub position: TradeReceipt,
+    pub market: Market,
+    pub estimate: Estimate,
+    /// Estimate of the held side less its current price.
+    pub remaining_edge: Decimal,
+}
+
+impl ExitReview {
+    /// Review `position` against `estimate` of its market at the market's
+    /// current price.
+    pub fn new(position: &TradeReceipt, market: &Market, estimate: &Estimate) -> Self {
+        let remaining_edge = match position.side {
+            Side::Yes => estimate.probability - market.current_price_yes,
+            Side::No => (Decimal::ONE - estimate.probability) - market.current_price_no,
+        };
+        Self { position: position.clone(), market: market.clone(), estimate: estimate.clone(), remaining_edge }
+    }
+
+    /// The exit the remaining edge calls for, if any.
+    pub fn trigger(&self, config: &EdgeExitConfig) -> Option<ExitTrigger> {
+        if self.remaining_edge < -config.cut_loss_edge {
+            Some(ExitTrigger::CutLoss)
+        } else if self.remaining_edge < config.take_profit_edge {
+            Some(ExitTrigger::TakeProfit)
+        } else {
+            None
+        }
+    }
+
+    /// The review as a decision: an exit or a hold.
+    pub fn decide(self, config: &EdgeExitConfig) -> DecisionRecord {
+        match self.trigger(config) {
+            Some(trigger) => DecisionRecord::Exit { review: self, trigger },
+            None => DecisionRecord::Hold { review: self },
+        }
+    }
+}
+
+/// Review every open position whose market was estimated this cycle.
+/// Positions on markets without an estimate are left for a later cycle.
+pub fn review_positions(
+    open_bets: &[TradeReceipt],
+    estimates: &[(Market, Estimate)],
+    config: &EdgeExitConfig,
+) -> Vec<DecisionRecord> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    let by_market: HashMap<String, &(Market, Estimate)> =
+        estimates.iter().map(|pair| (link_key(&pair.0.platform, &pair.0.id), pair)).collect();
+    open_bets
+        .iter()
+        .filter(|bet| bet.platform != "dry-run")
+        .filter_map(|bet| {
+            let (market, estimate) = by_market.get(&link_key(&bet.platform, &bet.market_id))?;
+            Some(ExitReview::new(bet, market, estimate).decide(config))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn config() -> EdgeExitConfig {
+        EdgeExitConfig { enabled: true, take_profit_edge: dec!(0.01), cut_loss_edge: dec!(0.05) }
+    }
+
+    fn market(price_yes: Decimal) -> Market {
+        Market {
+            id: "m1".to_string(),
+            platform: "manifold".to_string(),
+            current_price_yes: price_yes,
+            current_price_no: Decimal::ONE - price_yes,
+            ..Market::sample()
+        }
+    }
+
+    fn estimate(probability: Decimal) -> Estimate {
+        Estimate {
+            probability,
+            confidence: dec!(0.8),
+            reasoning: String::new(),
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    fn position(side: Side) -> TradeReceipt {
+        TradeReceipt { market_id: "m1".to_string(), platform: "manifold".to_string(), side, ..TradeReceipt::dry_run("m1", dec!(10), "Mana") }
+    }
+
+    fn decision(side: Side, probability: Decimal, price_yes: Decimal) -> DecisionRecord {
+        let estimates = [(market(price_yes), estimate(probability))];
+        let mut decisions = review_positions(&[position(side)], &estimates, &config());
+        assert_eq!(decisions.len(), 1);
+        decisions.remove(0)
+    }
+
+    #[test]
+    fn test_price_catching_up_takes_profit() {
+        // Bought YES at 0.50 on a 0.65 estimate; YES now trades at 0.645.
+        let DecisionRecord::Exit { review, trigger } = decision(Side::Yes, dec!(0.65), dec!(0.645)) else {
+            panic!("expected an exit")
+        };
+        assert_eq!(trigger, ExitTrigger::TakeProfit);
+        assert_eq!(review.remaining_edge, dec!(0.005));
+    }
+
+    #[test]
+    fn test_reversed_estimate_cuts_loss() {
+        // Holding NO at YES 0.40, but the estimate has moved to 0.70 YES:
+        // NO is worth 0.30 and trades at 0.60.
+        let DecisionRecord::Exit { review, trigger } = decision(Side::No, dec!(0.70), dec!(0.40)) else {
+            panic!("expected an exit")
+        };
+        assert_eq!(trigger, ExitTrigger::CutLoss);
+        assert_eq!(review.remaining_edge, dec!(-0.30));
+    }
+
+    #[test]
+    fn test_remaining_edge_holds() {
+        let held = decision(Side::Yes, dec!(0.65), dec!(0.55));
+        assert!(matches!(held, DecisionRecord::Hold { ref review } if review.remaining_edge == dec!(0.10)));
+        assert_eq!(held.kind(), "hold");
+    }
+
+    #[test]
+    fn test_unestimated_or_disabled_positions_are_not_reviewed() {
+        let estimates = [(Market { id: "other".to_string(), ..market(dec!(0.5)) }, estimate(dec!(0.9)))];
+        assert!(review_positions(&[position(Side::Yes)], &estimates, &config()).is_empty());
+
+        let estimates = [(market(dec!(0.64)), estimate(dec!(0.65)))];
+        let off = EdgeExitConfig { enabled: false, ..config() };
+        assert!(review_positions(&[position(Side::Yes)], &estimates, &off).is_empty());
+    }
+}
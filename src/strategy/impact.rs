@@ -0,0 +1,161 @@
+//! Price impact of a stake.
+//!
+//! On a thin market the bet itself moves the price: it fills worse than
+//! the scanned price and shows the move to everyone watching. The executor
+//! estimates that move from the venue's [`LiquidityInfo`] before placing
+//! and scales a bet down to the stake that stays within
+//! `risk.max_price_impact_pct` (see [`crate::engine::executor`]).
+//!
+//! Manifold's CPMM markets price from their YES and NO pools, so the move
+//! is exact up to fees: the pool weight `p` follows from the pools and the
+//! current price, and buying a side multiplies its odds by
+//! `(1 + amount / other_pool)^(1 / weight)`. Order books are modelled as
+//! their visible depth spread evenly from the current price to certainty,
+//! which is rough but monotone in the stake.
+
+use rust_decimal::prelude::*;
+
+use crate::types::{LiquidityInfo, LiquidityModel, Side};
+
+/// A stake's estimated price move and the largest stake within a limit.
+#[derive(Debug, Clone, Copy)]
+pub struct PriceImpact {
+    model: LiquidityModel,
+    /// Pool (CPMM) or visible depth (order book) of the bought side.
+    own: f64,
+    /// Pool of the other side (CPMM) or the rest of the visible depth.
+    other: f64,
+    /// Current probability of the bought side.
+    price: f64,
+}
+
+impl PriceImpact {
+    /// The market behind `liquidity` with `side` trading at `price`.
+    /// `None` when the venue reports no liquidity to estimate from or the
+    /// price is already at 0 or 1.
+    pub fn new(model: LiquidityModel, liquidity: &LiquidityInfo, side: Side, price: Decimal) -> Option<Self> {
+        let (yes, no) = (liquidity.bid_depth.to_f64()?, liquidity.ask_depth.to_f64()?);
+        let (own, other) = match side {
+            Side::Yes => (yes, no),
+            Side::No => (no, yes),
+        };
+        let price = price.to_f64()?;
+        let usable = match model {
+            LiquidityModel::Cpmm => own > 0.0 && other > 0.0,
+            LiquidityModel::OrderBook => own + other > 0.0,
+        };
+        (usable && price > 0.0 && price < 1.0).then_some(Self { model, own, other, price })
+    }
+
+    /// Weight of the bought side's pool in the CPMM invariant, implied by
+    /// the pools and the price.
+    fn weight(&self) -> f64 {
+        let (own, other) = (self.price * self.own, (1.0 - self.price) * self.other);
+        own / (own + other)
+    }
+
+    /// Rise of the bought side's probability from staking `amount`.
+    pub fn impact(&self, amount: Decimal) -> Decimal {
+        let amount = amount.to_f64().unwrap_or(0.0).max(0.0);
+        let moved = match self.model {
+            LiquidityModel::Cpmm => {
+                let odds = self.price / (1.0 - self.price) * (1.0 + amount / self.other).powf(1.0 / self.weight());
+                odds / (1.0 + odds) - self.price
+            }
+            LiquidityModel::OrderBook => (amount / (self.own + self.other)).min(1.0) * (1.0 - self.price),
+        };
+        Decimal::from_f64(moved).unwrap_or(Decimal::ONE)
+    }
+
+    /// Largest stake whose impact is at most `max_impact`.
+    pub fn max_stake(&self, max_impact: Decimal) -> Decimal {
+        let max_impact = max_impact.to_f64().unwrap_or(0.0).max(0.0);
+        let stake = match self.model {
+            LiquidityModel::Cpmm => {
+                let target = self.price + max_impact;
+                if target >= 1.0 {
+                    return Decimal::MAX;
+                }
+                let ratio = (target / (1.0 - target)) / (self.price / (1.0 - self.price));
+                self.other * (ratio.powf(self.weight()) - 1.0)
+            }
+            LiquidityModel::OrderBook => {
+                if max_impact >= 1.0 - self.price {
+                    return Decimal::MAX;
+                }
+                max_impact * (self.own + self.other) / (1.0 - self.price)
+            }
+        };
+        Decimal::from_f64(stake).unwrap_or(Decimal::ZERO)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn pools(yes: Decimal, no: Decimal) -> LiquidityInfo {
+        LiquidityInfo { bid_depth: yes, ask_depth: no, volume_24h: Decimal::ZERO }
+    }
+
+    fn close(a: Decimal, b: Decimal) -> bool {
+        (a - b).abs() < dec!(0.000001)
+    }
+
+    #[test]
+    fn test_cpmm_impact_matches_pool_arithmetic() {
+        // Even pools at 50%: 50 on YES leaves NO 150, YES 10000/150 = 66.67,
+        // so YES = 150 / 216.67 = 0.692308.
+        let even = PriceImpact::new(LiquidityModel::Cpmm, &pools(dec!(100), dec!(100)), Side::Yes, dec!(0.5)).unwrap();
+        assert!(close(even.impact(dec!(50)), dec!(0.192308)));
+        assert!(close(even.impact(Decimal::ZERO), Decimal::ZERO));
+
+        // YES 200 / NO 50 at 20%: 50 on NO leaves YES 250, NO 10000/250 = 40,
+        // so NO = 250 / 290 = 0.862069.
+        let skewed = PriceImpact::new(LiquidityModel::Cpmm, &pools(dec!(200), dec!(50)), Side::No, dec!(0.8)).unwrap();
+        assert!(close(skewed.impact(dec!(50)), dec!(0.062069)));
+
+        // Pool weight p = 0.8 with YES 25 / NO 400 prices YES at
+        // 0.8·400 / (0.8·400 + 0.2·25). Buying 100 YES leaves NO 500 and
+        // YES 25·(400/500)^(0.2/0.8) under the invariant YES^p·NO^(1−p).
+        let price = dec!(320) / dec!(325);
+        let weighted = PriceImpact::new(LiquidityModel::Cpmm, &pools(dec!(25), dec!(400)), Side::Yes, price).unwrap();
+        let yes_after = 25.0 * (400.0f64 / 500.0).powf(0.25);
+        let after = 0.8 * 500.0 / (0.8 * 500.0 + 0.2 * yes_after);
+        assert!(close(weighted.impact(dec!(100)), Decimal::from_f64(after).unwrap() - price));
+    }
+
+    #[test]
+    fn test_cpmm_max_stake_inverts_impact() {
+        // Even pools: 10 points up from 50% is odds 1.5 = (1 + A/100)², so
+        // A = 100·(√1.5 − 1) = 22.474487.
+        let even = PriceImpact::new(LiquidityModel::Cpmm, &pools(dec!(100), dec!(100)), Side::Yes, dec!(0.5)).unwrap();
+        let stake = even.max_stake(dec!(0.10));
+        assert!(close(stake, dec!(22.474487)));
+        assert!(close(even.impact(stake), dec!(0.10)));
+        // A limit past certainty never binds.
+        assert_eq!(even.max_stake(dec!(0.6)), Decimal::MAX);
+    }
+
+    #[test]
+    fn test_order_book_impact_is_linear_in_depth() {
+        let book = PriceImpact::new(LiquidityModel::OrderBook, &pools(dec!(600), dec!(400)), Side::Yes, dec!(0.6)).unwrap();
+        // A tenth of the visible depth moves the price a tenth of the way to 1.
+        assert!(close(book.impact(dec!(100)), dec!(0.04)));
+        assert!(close(book.max_stake(dec!(0.02)), dec!(50)));
+    }
+
+    #[test]
+    fn test_no_estimate_without_liquidity() {
+        let empty = pools(Decimal::ZERO, Decimal::ZERO);
+        assert!(PriceImpact::new(LiquidityModel::OrderBook, &empty, Side::Yes, dec!(0.5)).is_none());
+        let one_sided = pools(dec!(100), Decimal::ZERO);
+        assert!(PriceImpact::new(LiquidityModel::Cpmm, &one_sided, Side::Yes, dec!(0.5)).is_none());
+        assert!(PriceImpact::new(LiquidityModel::Cpmm, &pools(dec!(1), dec!(1)), Side::Yes, Decimal::ONE).is_none());
+    }
+}
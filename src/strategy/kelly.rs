@@ -2,19 +2,71 @@
 //!
 //! Computes optimal bet sizes using fractional Kelly with configurable
 //! multiplier, caps, and commission-adjusted edge calculations.
+//!
+//! Fees are modelled per platform ([`FeeModel`]): Betfair takes a share of
+//! net winnings, Manifold nothing, others a flat commission per trade. The
+//! Kelly fraction is computed on the fee-adjusted odds, i.e. against the
+//! break-even price the fee implies, so the same edge sizes smaller on a
+//! venue that charges more.
+//!
+//! Capital locked in a long-dated market can't be turned over, so the edge
+//! is also discounted by an annual hurdle rate over the time left to
+//! resolution ([`KellyConfig::capital_hurdle_annual_pct`]): the same edge
+//! sizes larger on a market resolving next week than on one resolving next
+//! year, and an edge the hurdle consumes entirely is not bet.
 
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+use super::constraints::BetConstraints;
 use super::edge::Edge;
-use crate::types::Side;
+use super::venue::VenueChoice;
+use crate::types::{RiskContext, Side};
 
 // ---------------------------------------------------------------------------
 // Configuration
 // ---------------------------------------------------------------------------
 
+/// What a platform charges on a trade.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeModel {
+    /// Fixed commission per trade, in the stake's currency.
+    FlatPerTrade(Decimal),
+    /// Share of net winnings (0.05 = 5%), nothing on a loss.
+    PercentOfWinnings(Decimal),
+    None,
+}
+
+impl FeeModel {
+    /// Built-in models: Betfair's 5% market base rate, fee-free Manifold.
+    pub fn defaults() -> HashMap<String, FeeModel> {
+        HashMap::from([
+            ("betfair".to_string(), FeeModel::PercentOfWinnings(dec!(0.05))),
+            ("manifold".to_string(), FeeModel::None),
+        ])
+    }
+
+    /// Price at which a bet bought at `price` breaks even after fees, for
+    /// a stake against `bankroll`. Equal to `price` when fee-free.
+    pub fn effective_price(&self, price: Decimal, bankroll: Decimal) -> Decimal {
+        match *self {
+            FeeModel::None => price,
+            FeeModel::FlatPerTrade(commission) => (price + commission / bankroll).min(dec!(0.99)),
+            FeeModel::PercentOfWinnings(fee) => {
+                let payout_ratio = (Decimal::ONE - price) / price * (Decimal::ONE - fee);
+                Decimal::ONE / (Decimal::ONE + payout_ratio)
+            }
+        }
+    }
+}
+
 /// Kelly sizing configuration.
 #[derive(Debug, Clone)]
 pub struct KellyConfig {
@@ -24,8 +76,35 @@ pub struct KellyConfig {
     pub max_bet_pct: Decimal,
     /// Minimum bet size in dollars (below this, don't bother).
     pub min_bet_size: Decimal,
-    /// Estimated round-trip commission per trade (IB ForecastEx).
+    /// Estimated round-trip commission per trade (IB ForecastEx), for
+    /// platforms without an entry in `platform_fees`.
     pub commission_per_trade: Decimal,
+    /// Fee model per platform.
+    pub platform_fees: HashMap<String, FeeModel>,
+    /// Return required of locked capital per year (0.10 = 10%). The edge
+    /// is reduced by this times the years left to resolution before
+    /// sizing.
+    pub capital_hurdle_annual_pct: Decimal,
+    /// Stake granularity per platform, applied after sizing.
+    pub bet_constraints: HashMap<String, BetConstraints>,
+}
+
+impl KellyConfig {
+    /// Fee model for `platform`: its own, or the flat commission.
+    pub fn fee_model(&self, platform: &str) -> FeeModel {
+        self.platform_fees
+            .get(platform)
+            .copied()
+            .unwrap_or(FeeModel::FlatPerTrade(self.commission_per_trade))
+    }
+
+    /// Days from `now` to `deadline` (zero once passed) and the edge the
+    /// capital hurdle takes over that time.
+    pub fn time_discount(&self, deadline: DateTime<Utc>, now: DateTime<Utc>) -> (f64, Decimal) {
+        let secs = (deadline - now).num_seconds().max(0);
+        let years = Decimal::from(secs) / dec!(31557600); // 365.25 days
+        (secs as f64 / 86_400.0, self.capital_hurdle_annual_pct * years)
+    }
 }
 
 impl Default for KellyConfig {
@@ -35,6 +114,9 @@ impl Default for KellyConfig {
             max_bet_pct: dec!(0.06),    // Max 6% of bankroll per trade
             min_bet_size: dec!(1.0),    // $1 minimum
             commission_per_trade: dec!(0.50), // IB estimated round-trip
+            platform_fees: FeeModel::defaults(),
+            capital_hurdle_annual_pct: dec!(0.10), // 10% a year
+            bet_constraints: BetConstraints::defaults(),
         }
     }
 }
@@ -44,13 +126,43 @@ impl Default for KellyConfig {
 // ---------------------------------------------------------------------------
 
 /// Sized bet recommendation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SizedBet {
     pub edge: Edge,
     pub kelly_fraction: Decimal,    // Raw Kelly fraction
     pub bet_fraction: Decimal,      // After multiplier + caps
     pub bet_amount: Decimal,        // Dollar amount
     pub expected_value: Decimal,    // Edge * bet_amount
+    pub net_expected_value: Decimal, // Discounted edge * bet_amount; ranks bets
+    pub days_to_resolution: f64,     // Capital lock-up the edge was discounted for
+    pub discounted_edge: Decimal,    // Edge after fees and the capital hurdle
+    pub risk_context: Option<RiskContext>, // Set by the risk manager on approval
+    pub venue: Option<VenueChoice>,        // Set when routed within an event cluster
+    pub correlation_key: Option<String>,   // Keyword cluster of the question, if any
+    pub idempotency_key: Option<String>,   // Set on approval; identifies the order across retries
+}
+
+impl SizedBet {
+    /// Price of the bet's side in the market snapshot its edge was
+    /// computed against.
+    pub fn entry_price(&self) -> Decimal {
+        match self.edge.side {
+            Side::Yes => self.edge.market.current_price_yes,
+            Side::No => self.edge.market.current_price_no,
+        }
+    }
+
+    /// Change the stake, scaling its bankroll fraction and expected values
+    /// with it.
+    pub fn restake(&mut self, amount: Decimal) {
+        if self.bet_amount > Decimal::ZERO {
+            let ratio = amount / self.bet_amount;
+            self.bet_fraction *= ratio;
+            self.expected_value *= ratio;
+            self.net_expected_value *= ratio;
+        }
+        self.bet_amount = amount;
+    }
 }
 
 pub struct KellyCalculator {
@@ -71,10 +183,16 @@ impl KellyCalculator {
     ///
     /// Kelly formula: f* = (bp - q) / b
     /// where:
-    ///   b = net odds (payout ratio)
-    ///   p = estimated win probability
+    ///   b = net odds (payout ratio) after the market platform's fees
+    ///   p = estimated win probability, less the capital hurdle over the
+    ///       time to resolution
     ///   q = 1 - p
     pub fn size_bet(&self, edge: &Edge, bankroll: Decimal) -> Option<SizedBet> {
+        self.size_bet_at(edge, bankroll, Utc::now())
+    }
+
+    /// [`Self::size_bet`] with the time to resolution measured from `now`.
+    pub fn size_bet_at(&self, edge: &Edge, bankroll: Decimal, now: DateTime<Utc>) -> Option<SizedBet> {
         if bankroll <= Decimal::ZERO {
             return None;
         }
@@ -84,10 +202,12 @@ impl KellyCalculator {
             Side::Yes => (edge.estimate.probability, edge.market.current_price_yes),
             Side::No => (Decimal::ONE - edge.estimate.probability, edge.market.current_price_no),
         };
+        if market_price <= Decimal::ZERO || market_price >= Decimal::ONE {
+            return None;
+        }
 
-        // Commission-adjusted market price
-        let effective_price = market_price + self.config.commission_per_trade / bankroll;
-        let effective_price = effective_price.min(dec!(0.99)); // can't exceed 1.0
+        // Fee-adjusted market price
+        let effective_price = self.config.fee_model(&edge.market.platform).effective_price(market_price, bankroll);
 
         // Net odds: what you win per dollar risked
         // Buy YES at price p, win (1-p) if YES, lose p if NO
@@ -97,6 +217,20 @@ impl KellyCalculator {
             return None;
         }
 
+        // Time value of the locked stake
+        let (days_to_resolution, discount) = self.config.time_discount(edge.market.deadline, now);
+        let win_prob = win_prob - discount;
+        let discounted_edge = win_prob - effective_price;
+        if discounted_edge <= Decimal::ZERO {
+            debug!(
+                market_id = %edge.market.id,
+                days = format!("{days_to_resolution:.0}"),
+                discount = %discount,
+                "Edge consumed by capital hurdle — no bet"
+            );
+            return None;
+        }
+
         let lose_prob = Decimal::ONE - win_prob;
 
         // Raw Kelly fraction
@@ -133,6 +267,7 @@ impl KellyCalculator {
         }
 
         let expected_value = edge.edge * bet_amount;
+        let net_expected_value = discounted_edge * bet_amount;
 
         debug!(
             market_id = %edge.market.id,
@@ -140,6 +275,7 @@ impl KellyCalculator {
             fractional = %format!("{:.2}%", (capped * dec!(100)).to_f64().unwrap_or(0.0)),
             bet_amount = %format!("${:.2}", bet_amount.to_f64().unwrap_or(0.0)),
             ev = %format!("${:.4}", expected_value.to_f64().unwrap_or(0.0)),
+            net_ev = %format!("${:.4}", net_expected_value.to_f64().unwrap_or(0.0)),
             "Bet sized"
         );
 
@@ -149,6 +285,13 @@ impl KellyCalculator {
             bet_fraction: capped,
             bet_amount,
             expected_value,
+            net_expected_value,
+            days_to_resolution,
+            discounted_edge,
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
         })
     }
 }
@@ -181,6 +324,9 @@ mod tests {
                 resolution_criteria: String::new(),
                 url: String::new(),
                 cross_refs: Default::default(),
+                event_group: None,
+                facts: None,
+                tags: Vec::new(),
             },
             estimate: Estimate {
                 probability: fair_value,
@@ -188,10 +334,15 @@ mod tests {
                 reasoning: String::new(),
                 tokens_used: 100,
                 cost: dec!(0.01),
+                critique: None,
+                served_by: None,
+                tier: None,
             },
             side,
             edge: edge_val,
             signed_edge: fair_value - market_price,
+            raw_edge: edge_val,
+            effective_threshold: dec!(0.06),
         }
     }
 
@@ -335,6 +486,46 @@ mod tests {
         assert!(sized.expected_value > Decimal::ZERO);
     }
 
+    #[test]
+    fn test_fee_venue_sizes_smaller_than_fee_free() {
+        let calc = KellyCalculator::new(KellyConfig {
+            max_bet_pct: dec!(0.50),
+            capital_hurdle_annual_pct: Decimal::ZERO,
+            ..Default::default()
+        });
+        // 8% edge: 48% market, 56% estimate.
+        let free = make_edge(dec!(0.48), dec!(0.56), dec!(0.8));
+        let mut betfair = free.clone();
+        betfair.market.platform = "betfair".into();
+
+        let free = calc.size_bet(&free, dec!(1000)).unwrap();
+        let fee = calc.size_bet(&betfair, dec!(1000)).unwrap();
+        assert!(fee.bet_amount < free.bet_amount, "fee {} vs free {}", fee.bet_amount, free.bet_amount);
+        assert_eq!(free.net_expected_value, free.expected_value);
+        assert!(fee.net_expected_value < fee.expected_value);
+        assert!(fee.net_expected_value > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_negative_net_edge_no_bet() {
+        let calc = KellyCalculator::new(KellyConfig { capital_hurdle_annual_pct: Decimal::ZERO, ..Default::default() });
+        // 1% gross edge at 50¢; a 5% cut of winnings moves break-even to ~51.3%.
+        let mut edge = make_edge(dec!(0.50), dec!(0.51), dec!(0.8));
+        assert!(calc.size_bet(&edge, dec!(1000)).is_some());
+        edge.market.platform = "betfair".into();
+        assert!(calc.size_bet(&edge, dec!(1000)).is_none());
+    }
+
+    #[test]
+    fn test_fee_model_fallback_and_effective_price() {
+        let config = KellyConfig::default();
+        assert_eq!(config.fee_model("manifold"), FeeModel::None);
+        assert_eq!(config.fee_model("forecastex"), FeeModel::FlatPerTrade(dec!(0.50)));
+        assert_eq!(FeeModel::None.effective_price(dec!(0.40), dec!(100)), dec!(0.40));
+        assert_eq!(FeeModel::FlatPerTrade(dec!(1)).effective_price(dec!(0.40), dec!(100)), dec!(0.41));
+        assert!(FeeModel::PercentOfWinnings(dec!(0.05)).effective_price(dec!(0.50), dec!(100)) > dec!(0.51));
+    }
+
     #[test]
     fn test_kelly_config_default() {
         let config = KellyConfig::default();
@@ -342,4 +533,40 @@ mod tests {
         assert_eq!(config.max_bet_pct, dec!(0.06));
         assert_eq!(config.min_bet_size, dec!(1.0));
     }
+
+    #[test]
+    fn test_near_dated_edge_sizes_larger() {
+        let calc = KellyCalculator::new(KellyConfig::default());
+        let now = Utc::now();
+        let mut near = make_edge(dec!(0.40), dec!(0.50), dec!(0.8));
+        near.market.deadline = now + Duration::days(1);
+        let mut far = near.clone();
+        far.market.deadline = now + Duration::days(330);
+
+        let near = calc.size_bet_at(&near, dec!(1000), now).unwrap();
+        let far = calc.size_bet_at(&far, dec!(1000), now).unwrap();
+        assert_eq!(near.edge.edge, far.edge.edge);
+        assert!(near.bet_amount > far.bet_amount, "near {} vs far {}", near.bet_amount, far.bet_amount);
+        assert!(near.discounted_edge > far.discounted_edge);
+        assert!(near.net_expected_value > far.net_expected_value);
+        assert_eq!((near.days_to_resolution, far.days_to_resolution), (1.0, 330.0));
+        // 10% a year over 330 days takes ~9 of the 10 points.
+        assert!((far.discounted_edge - dec!(0.0097)).abs() < dec!(0.0001), "{}", far.discounted_edge);
+    }
+
+    #[test]
+    fn test_hurdle_skips_long_dated_marginal_edge() {
+        let calc = KellyCalculator::new(KellyConfig::default());
+        let now = Utc::now();
+        let mut edge = make_edge(dec!(0.50), dec!(0.55), dec!(0.8));
+        edge.market.deadline = now + Duration::days(300);
+        assert!(calc.size_bet_at(&edge, dec!(1000), now).is_none());
+        // Still bet once it is near-dated, or with no hurdle.
+        assert!(calc.size_bet_at(&edge, dec!(1000), edge.market.deadline - Duration::days(7)).is_some());
+        let no_hurdle = KellyCalculator::new(KellyConfig { capital_hurdle_annual_pct: Decimal::ZERO, ..Default::default() });
+        let sized = no_hurdle.size_bet_at(&edge, dec!(1000), now).unwrap();
+        assert_eq!(sized.discounted_edge, dec!(0.05));
+        // A passed deadline is not discounted.
+        assert!(calc.size_bet_at(&edge, dec!(1000), edge.market.deadline + Duration::days(1)).is_some());
+    }
 }
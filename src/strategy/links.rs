@@ -0,0 +1,294 @@
+//! Operator-declared links between logically related markets.
+//!
+//! Markets such as "X wins the primary" and "X wins the presidency" are
+//! not independent: the second implies the first, so a YES on the
+//! presidency market next to a NO on the primary is a position that loses
+//! on every consistent outcome we bet on. Links are declared by hand in
+//! `links.toml`, with markets keyed `platform:market_id`:
+//!
+//! ```toml
+//! [[implies]]
+//! market = "polymarket:x-wins-presidency"
+//! implies = "polymarket:x-wins-primary"
+//!
+//! [[exclusive]]
+//! name = "gop-nominee"
+//! markets = ["polymarket:a-nominee", "polymarket:b-nominee", "polymarket:c-nominee"]
+//! ```
+//!
+//! Opposing positions:
+//!
+//! - `implies` A → B: YES on A with NO on B.
+//! - `exclusive`: YES on two members (at most one can resolve YES).
+//!
+//! [`suggest`] proposes candidate links from entity overlap in the
+//! questions' [`QuestionFacts`](crate::types::QuestionFacts). Suggestions
+//! are only listed for the operator; they never take effect until copied
+//! into `links.toml`.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::question_parser;
+use crate::types::{Market, Side};
+
+/// Most suggestions kept per scan.
+pub const MAX_SUGGESTIONS: usize = 50;
+
+/// Key a market is declared under: `platform:market_id`.
+pub fn link_key(platform: &str, market_id: &str) -> String {
+    format!("{platform}:{market_id}")
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LinksFile {
+    #[serde(default)]
+    implies: Vec<Implication>,
+    #[serde(default)]
+    exclusive: Vec<ExclusiveGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Implication {
+    market: String,
+    implies: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExclusiveGroup {
+    name: Option<String>,
+    markets: Vec<String>,
+}
+
+/// How the members of a linked set constrain each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// `members[0]` resolving YES implies `members[1]` resolves YES.
+    Implies,
+    /// At most one member resolves YES.
+    MutuallyExclusive,
+}
+
+/// One declared set of linked markets.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LinkSet {
+    pub name: String,
+    pub kind: LinkKind,
+    pub members: Vec<String>,
+}
+
+impl LinkSet {
+    /// Whether holding `a` and `b` in this set is logically inconsistent.
+    fn opposes(&self, a: (&str, Side), b: (&str, Side)) -> bool {
+        if a.0 == b.0 {
+            return false;
+        }
+        match self.kind {
+            LinkKind::Implies => {
+                let (antecedent, consequent) = (self.members[0].as_str(), self.members[1].as_str());
+                let against = |x: (&str, Side), y: (&str, Side)| {
+                    x == (antecedent, Side::Yes) && y == (consequent, Side::No)
+                };
+                against(a, b) || against(b, a)
+            }
+            LinkKind::MutuallyExclusive => a.1 == Side::Yes && b.1 == Side::Yes,
+        }
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.members.iter().any(|m| m == key)
+    }
+}
+
+/// All declared links.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MarketLinks {
+    sets: Vec<LinkSet>,
+}
+
+impl MarketLinks {
+    /// Load links from `path`. A missing file declares no links.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("Invalid links file {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: LinksFile = toml::from_str(text)?;
+        let mut sets = Vec::new();
+        for i in file.implies {
+            anyhow::ensure!(i.market != i.implies, "{} cannot imply itself", i.market);
+            sets.push(LinkSet {
+                name: format!("{} => {}", i.market, i.implies),
+                kind: LinkKind::Implies,
+                members: vec![i.market, i.implies],
+            });
+        }
+        for (n, g) in file.exclusive.into_iter().enumerate() {
+            let distinct: BTreeSet<&String> = g.markets.iter().collect();
+            anyhow::ensure!(distinct.len() >= 2, "exclusive group needs at least two distinct markets");
+            sets.push(LinkSet {
+                name: g.name.unwrap_or_else(|| format!("exclusive#{}", n + 1)),
+                kind: LinkKind::MutuallyExclusive,
+                members: g.markets,
+            });
+        }
+        for key in sets.iter().flat_map(|s| &s.members) {
+            anyhow::ensure!(key.contains(':'), "market key {key:?} is not platform:market_id");
+        }
+        Ok(Self { sets })
+    }
+
+    pub fn sets(&self) -> &[LinkSet] {
+        &self.sets
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Sets `key` belongs to.
+    pub fn sets_for<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a LinkSet> + 'a {
+        self.sets.iter().filter(move |s| s.contains(key))
+    }
+
+    /// First held position that a new `side` bet on `key` would logically
+    /// oppose, with the set that links them.
+    pub fn opposing<'a>(
+        &'a self,
+        key: &str,
+        side: Side,
+        held: impl IntoIterator<Item = (&'a str, Side)>,
+    ) -> Option<(&'a LinkSet, &'a str)> {
+        let held: Vec<_> = held.into_iter().collect();
+        self.sets.iter().filter(|s| s.contains(key)).find_map(|set| {
+            held.iter()
+                .find(|&&(other, other_side)| set.contains(other) && set.opposes((key, side), (other, other_side)))
+                .map(|&(other, _)| (set, other))
+        })
+    }
+}
+
+/// A candidate link for the operator to review.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LinkSuggestion {
+    pub markets: [String; 2],
+    pub questions: [String; 2],
+    /// Entities both questions mention.
+    pub shared_entities: Vec<String>,
+}
+
+/// Propose links between markets in the same category whose questions
+/// share at least one entity and that are not already linked. Strongest
+/// overlaps first, at most [`MAX_SUGGESTIONS`].
+pub fn suggest(markets: &[Market], declared: &MarketLinks) -> Vec<LinkSuggestion> {
+    let entities: Vec<BTreeSet<String>> = markets
+        .iter()
+        .map(|m| question_parser::facts(m).entities.iter().map(|e| e.to_lowercase()).collect())
+        .collect();
+    let mut suggestions = Vec::new();
+    for i in 0..markets.len() {
+        for j in i + 1..markets.len() {
+            let (a, b) = (&markets[i], &markets[j]);
+            if a.category != b.category || (a.event_group.is_some() && a.event_group == b.event_group) {
+                continue;
+            }
+            let shared: Vec<String> = entities[i].intersection(&entities[j]).cloned().collect();
+            if shared.is_empty() {
+                continue;
+            }
+            let (ka, kb) = (link_key(&a.platform, &a.id), link_key(&b.platform, &b.id));
+            if declared.sets_for(&ka).any(|s| s.contains(&kb)) {
+                continue;
+            }
+            suggestions.push(LinkSuggestion {
+                markets: [ka, kb],
+                questions: [a.question.clone(), b.question.clone()],
+                shared_entities: shared,
+            });
+        }
+    }
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.shared_entities.len()));
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MarketCategory;
+
+    const LINKS: &str = r#"
+        [[implies]]
+        market = "polymarket:pres"
+        implies = "polymarket:primary"
+
+        [[exclusive]]
+        name = "nominee"
+        markets = ["polymarket:a", "polymarket:b", "polymarket:c"]
+    "#;
+
+    #[test]
+    fn test_parse_and_opposition_rules() {
+        let links = MarketLinks::parse(LINKS).unwrap();
+        assert_eq!(links.sets().len(), 2);
+        assert_eq!(links.sets()[0].name, "polymarket:pres => polymarket:primary");
+
+        // Implication: YES on the antecedent vs NO on the consequent only.
+        let held = [("polymarket:primary", Side::No)];
+        let (set, other) = links.opposing("polymarket:pres", Side::Yes, held).unwrap();
+        assert_eq!((set.kind, other), (LinkKind::Implies, "polymarket:primary"));
+        assert!(links.opposing("polymarket:pres", Side::No, held).is_none());
+        assert!(links.opposing("polymarket:primary", Side::No, [("polymarket:pres", Side::Yes)]).is_some());
+        assert!(links.opposing("polymarket:primary", Side::Yes, [("polymarket:pres", Side::Yes)]).is_none());
+
+        // Exclusive: two YESes oppose, NOs do not.
+        assert_eq!(links.opposing("polymarket:b", Side::Yes, [("polymarket:a", Side::Yes)]).unwrap().0.name, "nominee");
+        assert!(links.opposing("polymarket:b", Side::No, [("polymarket:a", Side::Yes)]).is_none());
+        assert!(links.opposing("polymarket:z", Side::Yes, [("polymarket:a", Side::Yes)]).is_none());
+
+        assert!(MarketLinks::parse("[[exclusive]]\nmarkets = [\"p:a\", \"p:a\"]").is_err());
+        assert!(MarketLinks::parse("[[implies]]\nmarket = \"a\"\nimplies = \"p:b\"").is_err());
+        assert!(MarketLinks::load("/nonexistent/links.toml").unwrap().is_empty());
+    }
+
+    fn market(id: &str, question: &str, category: MarketCategory) -> Market {
+        Market {
+            id: id.into(),
+            platform: "polymarket".into(),
+            question: question.into(),
+            category,
+            ..Market::sample()
+        }
+    }
+
+    #[test]
+    fn test_suggestions_from_entity_overlap() {
+        let markets = [
+            market("primary", "Will Jane Doe win the Iowa caucus?", MarketCategory::Politics),
+            market("pres", "Will Jane Doe win the presidency in 2028?", MarketCategory::Politics),
+            market("other", "Will John Roe win the Senate race in Ohio?", MarketCategory::Politics),
+            market("sports", "Will Jane Doe attend the Super Bowl?", MarketCategory::Sports),
+        ];
+        let suggestions = suggest(&markets, &MarketLinks::default());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].markets, ["polymarket:primary".to_string(), "polymarket:pres".to_string()]);
+        assert!(suggestions[0].shared_entities.contains(&"jane doe".to_string()));
+
+        // Already-declared links are not suggested again.
+        let declared = MarketLinks::parse(LINKS).unwrap();
+        assert!(suggest(&markets, &declared).is_empty());
+    }
+}
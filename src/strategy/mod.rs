@@ -1,18 +1,39 @@
 //! Strategy engine — edge detection, Kelly sizing, and risk management.
 
+pub mod adaptive;
+pub mod arbitrage;
+pub mod constraints;
+pub mod cooldown;
+pub mod correlation;
 pub mod edge;
+pub mod exit;
+pub mod impact;
 pub mod kelly;
+pub mod links;
+pub mod performance;
+pub mod references;
 pub mod risk;
+pub mod venue;
 
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use serde::Serialize;
 use tracing::{debug, info, warn};
 
-use crate::types::{AgentState, BetDecision, Estimate, Market};
-use edge::{Edge, EdgeDetector};
-use kelly::{KellyCalculator, SizedBet};
-use risk::{RejectionReason, RiskManager};
+use std::collections::HashMap;
+
+use crate::config::AppConfig;
+use crate::types::{AgentState, BetDecision, Estimate, Market, MarketCategory, Position};
+use arbitrage::{ArbitrageDetector, ArbitragePair};
+use constraints::{BetConstraints, StakeRejection};
+use correlation::CorrelationGroups;
+use edge::{Edge, EdgeConfig, EdgeDetector};
+use exit::{ExitReview, ExitTrigger};
+use kelly::{KellyCalculator, KellyConfig, SizedBet};
+use risk::{Approval, RejectionReason, RiskConfig, RiskManager};
+use venue::VenueSelector;
 
 // ---------------------------------------------------------------------------
 // Decision log
@@ -20,29 +41,164 @@ use risk::{RejectionReason, RiskManager};
 
 /// Record of every decision made (or skipped) during a strategy pass.
 /// Kept for analysis and transparency — including opportunities that were
-/// passed on and the reason why.
-#[derive(Debug, Clone)]
+/// passed on and the reason why. Journalled per cycle (see
+/// [`crate::storage::journal`]).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
 pub enum DecisionRecord {
     /// Bet selected and queued for execution.
     Selected {
         bet: SizedBet,
         /// Final amount after drawdown adjustment.
         adjusted_amount: Decimal,
+        /// Drawdown tier multiplier that scaled the approved amount.
+        drawdown_multiplier: Decimal,
     },
-    /// Edge detected but Kelly sizing returned None (negative or zero Kelly).
+    /// Edge detected but Kelly sizing returned None (negative or zero Kelly,
+    /// or the edge consumed by fees and the capital hurdle).
     KellyRejected { edge: Edge },
+    /// Sized bet the platform can't take at its stake granularity.
+    StakeRejected {
+        bet: SizedBet,
+        reason: StakeRejection,
+    },
     /// Sized bet blocked by the risk manager.
     RiskRejected {
         bet: SizedBet,
         reason: RejectionReason,
     },
+    /// Cross-venue arbitrage pair: both legs queued for execution, or the
+    /// pair cancelled because the risk manager rejected a leg.
+    Arbitrage {
+        pair: Box<ArbitragePair>,
+        rejection: Option<RejectionReason>,
+    },
+    /// Open position closed: its remaining edge is spent or reversed.
+    Exit {
+        review: ExitReview,
+        trigger: ExitTrigger,
+    },
+    /// Open position re-evaluated and kept.
+    Hold { review: ExitReview },
+}
+
+impl DecisionRecord {
+    /// Market the decision is about (the YES leg's for an arbitrage pair).
+    pub fn market(&self) -> &Market {
+        match self {
+            DecisionRecord::Selected { bet, .. }
+            | DecisionRecord::RiskRejected { bet, .. }
+            | DecisionRecord::StakeRejected { bet, .. } => &bet.edge.market,
+            DecisionRecord::KellyRejected { edge } => &edge.market,
+            DecisionRecord::Arbitrage { pair, .. } => &pair.yes_leg.edge.market,
+            DecisionRecord::Exit { review, .. } | DecisionRecord::Hold { review } => &review.market,
+        }
+    }
+
+    /// Why the risk manager refused the bet or pair, if it did.
+    pub fn rejection(&self) -> Option<&RejectionReason> {
+        match self {
+            DecisionRecord::RiskRejected { reason, .. } | DecisionRecord::Arbitrage { rejection: Some(reason), .. } => {
+                Some(reason)
+            }
+            _ => None,
+        }
+    }
+
+    /// Code of the check that blocked the bet or pair: the risk
+    /// rejection's or the stake rejection's. `None` for decisions that
+    /// weren't blocked, and for edges Kelly sizing declined.
+    pub fn rejection_code(&self) -> Option<&'static str> {
+        match self {
+            DecisionRecord::StakeRejected { reason, .. } => Some(reason.code()),
+            _ => self.rejection().map(RejectionReason::code),
+        }
+    }
+
+    /// The `decision` tag the record serialises with.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DecisionRecord::Selected { .. } => "selected",
+            DecisionRecord::KellyRejected { .. } => "kelly_rejected",
+            DecisionRecord::StakeRejected { .. } => "stake_rejected",
+            DecisionRecord::RiskRejected { .. } => "risk_rejected",
+            DecisionRecord::Arbitrage { .. } => "arbitrage",
+            DecisionRecord::Exit { .. } => "exit",
+            DecisionRecord::Hold { .. } => "hold",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parameters
+// ---------------------------------------------------------------------------
+
+/// Edge, sizing and risk parameters, as configured.
+#[derive(Debug, Clone)]
+pub struct StrategyParams {
+    pub edge: EdgeConfig,
+    pub kelly: KellyConfig,
+    pub risk: RiskConfig,
+}
+
+impl StrategyParams {
+    /// Parameters from `cfg`, enforcing the declared market `links`.
+    pub fn from_config(cfg: &AppConfig, links: links::MarketLinks) -> Self {
+        Self {
+            edge: Self::edge_config(cfg),
+            kelly: KellyConfig {
+                multiplier: cfg.risk.kelly_multiplier,
+                max_bet_pct: cfg.risk.max_bet_pct,
+                platform_fees: cfg.risk.fees.clone(),
+                capital_hurdle_annual_pct: cfg.risk.capital_hurdle_annual_pct,
+                bet_constraints: cfg.risk.bet_constraints.clone(),
+                ..KellyConfig::default()
+            },
+            risk: RiskConfig {
+                max_exposure_pct: cfg.risk.max_exposure_pct,
+                drawdown_tiers: cfg.risk.drawdown_tiers.clone(),
+                drawdown_basis: cfg.risk.drawdown_basis,
+                unwind_windows: cfg.strategy.unwind_windows.clone(),
+                cool_down: cfg.risk.cool_down.clone(),
+                links,
+                link_rules: cfg.risk.links.clone(),
+                daily_loss_halt_pct: cfg.risk.max_daily_loss_pct,
+                drawdown_resume_pct: cfg.risk.drawdown_resume_pct,
+                tag_limits: cfg.risk.tags.clone().into_iter().collect(),
+                correlation_groups: CorrelationGroups::new(&cfg.risk.correlation.groups),
+                max_exposure_per_group_pct: cfg.risk.correlation.max_exposure_per_group_pct,
+                max_holding_pct: cfg.risk.max_holding_pct,
+                ..RiskConfig::default()
+            },
+        }
+    }
+
+    /// Configured category edge thresholds, with the built-in defaults for
+    /// categories the config leaves out.
+    pub fn edge_config(cfg: &AppConfig) -> EdgeConfig {
+        let threshold = |category: &str, default: Decimal| {
+            cfg.risk.category_thresholds.get(category).copied().unwrap_or(default)
+        };
+        EdgeConfig {
+            weather_threshold: threshold("weather", dec!(0.06)),
+            sports_threshold: threshold("sports", dec!(0.08)),
+            economics_threshold: threshold("economics", dec!(0.10)),
+            politics_threshold: threshold("politics", dec!(0.12)),
+            cross_ref: cfg.risk.cross_ref.clone(),
+            confidence_scaling: cfg.risk.confidence_scaling.enabled,
+            confidence_k: cfg.risk.confidence_scaling.k,
+            min_confidence: cfg.risk.confidence_scaling.min_confidence,
+            ..EdgeConfig::default()
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Orchestrator
 // ---------------------------------------------------------------------------
 
-/// Pipelines edge detection -> Kelly sizing -> risk approval -> bet selection.
+/// Pipelines edge detection -> Kelly sizing -> venue selection -> risk
+/// approval -> bet selection.
 ///
 /// Instantiate once per agent; call `reset_cycle` at the start of each scan
 /// cycle, then `select_bets` with the LLM estimates for that cycle.
@@ -50,6 +206,8 @@ pub struct StrategyOrchestrator {
     edge_detector: EdgeDetector,
     kelly: KellyCalculator,
     risk: RiskManager,
+    venues: Option<VenueSelector>,
+    arbitrage: Option<ArbitrageDetector>,
 }
 
 impl StrategyOrchestrator {
@@ -58,6 +216,38 @@ impl StrategyOrchestrator {
             edge_detector,
             kelly,
             risk,
+            venues: None,
+            arbitrage: None,
+        }
+    }
+
+    /// Swap in new parameters between cycles. Exposure counters are kept;
+    /// adaptive thresholds must be re-applied with
+    /// [`Self::set_category_thresholds`].
+    pub fn update_config(&mut self, params: StrategyParams) {
+        self.edge_detector = EdgeDetector::new(params.edge);
+        self.kelly = KellyCalculator::new(params.kelly);
+        self.risk.set_config(params.risk);
+    }
+
+    /// Route clustered bets to their best execution venue.
+    pub fn set_venue_selector(&mut self, selector: VenueSelector) {
+        self.venues = Some(selector);
+    }
+
+    /// Look for arbitrage across the venues of each same-event cluster.
+    pub fn set_arbitrage_detector(&mut self, detector: ArbitrageDetector) {
+        self.arbitrage = Some(detector);
+    }
+
+    /// Replace this cycle's same-event clusters (no-op without a venue
+    /// selector or arbitrage detector).
+    pub fn set_clusters(&mut self, clusters: Vec<Vec<Market>>) {
+        if let Some(arbitrage) = &mut self.arbitrage {
+            arbitrage.set_clusters(clusters.clone());
+        }
+        if let Some(venues) = &mut self.venues {
+            venues.set_clusters(clusters);
         }
     }
 
@@ -71,26 +261,78 @@ impl StrategyOrchestrator {
     /// sync the counters drift upward indefinitely, progressively rejecting new
     /// bets even when real exposure is well within limits.
     ///
-    /// Because `TradeReceipt` does not store `MarketCategory`, all open
-    /// positions are bucketed under `Other` for the per-category check. This
-    /// is conservative: the category limit caps *all* exposure together rather
-    /// than per-domain. A future improvement should add `category` to
-    /// `TradeReceipt` and populate it at bet-placement time.
+    /// Open bets count against their market's category; receipts from before
+    /// categories were recorded are bucketed under `Other`.
+    ///
+    /// Per-tag exposure is synced from each open bet's tags.
+    ///
+    /// Positions held on the platforms are set separately with
+    /// [`Self::set_open_positions`].
     pub fn sync_exposure_from_state(&mut self, state: &crate::types::AgentState) {
-        use std::collections::HashMap;
-        use crate::types::MarketCategory;
-
         let mut total = Decimal::ZERO;
         let mut by_category: HashMap<MarketCategory, Decimal> = HashMap::new();
+        let mut by_tag: HashMap<String, risk::TagExposure> = HashMap::new();
 
         for bet in &state.open_bets {
             total += bet.amount;
             *by_category
-                .entry(MarketCategory::Other)
+                .entry(bet.category.unwrap_or(MarketCategory::Other))
                 .or_insert(Decimal::ZERO) += bet.amount;
+            for tag in &bet.tags {
+                let held = by_tag.entry(tag.clone()).or_default();
+                held.exposure += bet.amount;
+                held.open_bets += 1;
+            }
         }
 
         self.risk.update_exposure(total, by_category, state.open_bets.len());
+        self.risk.update_tag_exposure(by_tag);
+    }
+
+    /// Positions the platforms report holding, counted on top of the open
+    /// bets (see [`RiskManager::set_open_positions`]).
+    pub fn set_open_positions(&mut self, positions: &[Position]) {
+        self.risk.set_open_positions(positions);
+    }
+
+    /// The risk manager, for the halt checks run outside bet selection.
+    pub fn risk(&self) -> &RiskManager {
+        &self.risk
+    }
+
+    /// Declared market links the risk manager enforces.
+    pub fn links(&self) -> &links::MarketLinks {
+        self.risk.links()
+    }
+
+    /// Largest fraction of a bankroll Kelly sizing stakes on one bet.
+    pub fn max_bet_pct(&self) -> Decimal {
+        self.kelly.config().max_bet_pct
+    }
+
+    /// Apply adaptive per-category edge thresholds.
+    pub fn set_category_thresholds(&mut self, thresholds: &HashMap<MarketCategory, Decimal>) {
+        for (&category, &threshold) in thresholds {
+            self.edge_detector.set_threshold(category, threshold);
+        }
+    }
+
+    /// Tentative Kelly bet fraction for each estimate with an actionable
+    /// edge, as `(index into estimates, fraction of bankroll)`. No risk
+    /// checks and no state change — used to pick estimates for a
+    /// self-critique before `select_bets`.
+    pub fn tentative_bet_fractions(
+        &self,
+        estimates: &[(Market, Estimate)],
+        state: &AgentState,
+    ) -> Vec<(usize, Decimal)> {
+        (0..estimates.len())
+            .filter_map(|i| {
+                let edge = self.edge_detector.find_edges(&estimates[i..=i]).pop()?;
+                let bankroll = state.bankroll_for(&edge.market.platform);
+                self.kelly.size_bet(&edge, bankroll).map(|bet| (i, bet.bet_fraction))
+            })
+            .collect()
     }
 
     /// Reset per-cycle counters (call once at the start of every scan cycle).
@@ -102,10 +344,13 @@ impl StrategyOrchestrator {
     ///
     /// Steps:
     /// 1. Detect actionable edges (above category thresholds).
-    /// 2. Kelly-size each edge.
-    /// 3. Rank survivors by composite score: `expected_value * confidence`.
-    /// 4. Approve in rank order through the risk manager (enforces cycle
-    ///    limit, exposure caps, drawdown halt, etc.).
+    /// 2. Kelly-size each edge, then route bets on clustered markets to
+    ///    their best venue (re-sized at that venue) and tag each with its
+    ///    correlation group.
+    /// 3. Rank survivors by composite score: `net_expected_value * confidence`
+    ///    (expected value after the venue's fees and the capital hurdle).
+    /// 4. Approve in rank order through the risk manager (enforces category
+    ///    cool-downs, cycle limit, exposure caps, drawdown halt, etc.).
     ///
     /// Returns the approved bets (ready for `Executor::execute_batch`) and a
     /// complete decision log including all rejected opportunities.
@@ -113,7 +358,18 @@ impl StrategyOrchestrator {
         &mut self,
         estimates: &[(Market, Estimate)],
         state: &AgentState,
-        mana_bankroll: Option<Decimal>,
+    ) -> (Vec<SizedBet>, Vec<DecisionRecord>) {
+        self.select_bets_at(estimates, state, Utc::now())
+    }
+
+    /// [`Self::select_bets`] as of `now` (time to resolution and the
+    /// time-dependent risk checks), so a replay of recorded cycles is
+    /// independent of when it runs.
+    pub fn select_bets_at(
+        &mut self,
+        estimates: &[(Market, Estimate)],
+        state: &AgentState,
+        now: DateTime<Utc>,
     ) -> (Vec<SizedBet>, Vec<DecisionRecord>) {
         let mut decisions: Vec<DecisionRecord> = Vec::new();
 
@@ -126,16 +382,11 @@ impl StrategyOrchestrator {
         );
 
         // Step 2 – Kelly sizing
-        // Manifold uses Mana (play currency), so size against mana_bankroll rather
-        // than the real AUD bankroll to get meaningful paper-trade sizes.
+        // Each bet is sized against the balance on its own platform (Mana on
+        // Manifold), not the aggregate bankroll.
         let mut sized: Vec<SizedBet> = Vec::new();
         for edge in edges {
-            let bankroll = if edge.market.platform == "manifold" {
-                mana_bankroll.unwrap_or(state.bankroll)
-            } else {
-                state.bankroll
-            };
-            match self.kelly.size_bet(&edge, bankroll) {
+            match self.kelly.size_bet_at(&edge, state.bankroll_for(&edge.market.platform), now) {
                 Some(bet) => sized.push(bet),
                 None => {
                     debug!(
@@ -148,47 +399,106 @@ impl StrategyOrchestrator {
             }
         }
 
-        // Step 3 – rank by composite score (expected value * confidence)
+        // Step 2b – venue selection within same-event clusters
+        if let Some(venues) = &self.venues {
+            sized = sized
+                .into_iter()
+                .map(|bet| {
+                    let bankroll = state.bankroll_for(&bet.edge.market.platform);
+                    let routed = venues.route(bet, &self.kelly, bankroll, now);
+                    if let Some(choice) = routed.venue.as_ref().filter(|c| c.chosen != c.origin) {
+                        info!(
+                            origin = %choice.origin,
+                            venue = %choice.chosen,
+                            considered = choice.considered.len(),
+                            "Bet routed to better venue"
+                        );
+                    }
+                    routed
+                })
+                .collect();
+        }
+
+        // Step 2c – correlation group from the question's keyword cluster
+        for bet in &mut sized {
+            bet.correlation_key = self.risk.correlation_groups().key_for(&bet.edge.market.question);
+        }
+
+        // Step 2d – round to the stake the (routed) venue accepts
+        let constraints = &self.kelly.config().bet_constraints;
+        let mut placeable = Vec::with_capacity(sized.len());
+        for mut bet in sized {
+            match BetConstraints::for_platform(constraints, &bet.edge.market.platform).normalise(bet.bet_amount) {
+                Ok(stake) => {
+                    bet.restake(stake);
+                    placeable.push(bet);
+                }
+                Err(reason) => {
+                    debug!(market_id = %bet.edge.market.id, reason = %reason, "Stake rejected");
+                    decisions.push(DecisionRecord::StakeRejected { bet, reason });
+                }
+            }
+        }
+        sized = placeable;
+
+        // Step 3 – rank by composite score (net expected value * confidence)
         // Higher score -> higher priority for scarce risk budget.
         sized.sort_by(|a, b| {
-            let score_a = a.expected_value * a.edge.estimate.confidence;
-            let score_b = b.expected_value * b.edge.estimate.confidence;
+            let score_a = a.net_expected_value * a.edge.estimate.confidence;
+            let score_b = b.net_expected_value * b.edge.estimate.confidence;
             score_b.cmp(&score_a)
         });
 
-        // Step 4 – risk approval in rank order
-        // For Manifold bets, override the exposure bankroll with mana_bankroll
-        // so that exposure caps are evaluated in Mana, not AUD.
+        // Step 4 – risk approval in rank order (exposure caps are evaluated
+        // against the bet's platform balance); the drawdown-scaled amount is
+        // rounded to the venue's stake again before it is recorded
         let mut selected: Vec<SizedBet> = Vec::new();
         for bet in sized {
-            let exposure_override = if bet.edge.market.platform == "manifold" {
-                mana_bankroll
-            } else {
-                None
-            };
-            match self.risk.approve(&bet, state, exposure_override) {
-                Ok(adjusted_amount) => {
+            let threshold = self.edge_detector.config().threshold_for(&bet.edge.market.category);
+            let approval = self
+                .risk
+                .check_cool_down(&bet, state, threshold)
+                .and_then(|()| self.risk.approve_at(&bet, state, None, now));
+            let approval = approval.map(|approval| {
+                let stake = BetConstraints::for_platform(&self.kelly.config().bet_constraints, &bet.edge.market.platform)
+                    .normalise(approval.amount);
+                (approval, stake)
+            });
+            match approval {
+                Ok((_, Err(reason))) => {
+                    debug!(market_id = %bet.edge.market.id, reason = %reason, "Stake rejected after drawdown scaling");
+                    decisions.push(DecisionRecord::StakeRejected { bet, reason });
+                }
+                Ok((Approval { context, .. }, Ok(adjusted_amount))) => {
                     info!(
                         market_id = %bet.edge.market.id,
                         side = ?bet.edge.side,
                         original = %format!("${:.2}", bet.bet_amount.to_f64().unwrap_or(0.0)),
                         adjusted = %format!("${:.2}", adjusted_amount.to_f64().unwrap_or(0.0)),
+                        drawdown_multiplier = %context.drawdown_factor,
                         ev = %format!("${:.4}", bet.expected_value.to_f64().unwrap_or(0.0)),
+                        net_ev = %format!("${:.4}", bet.net_expected_value.to_f64().unwrap_or(0.0)),
+                        days = %format!("{:.0}", bet.days_to_resolution),
                         confidence = %format!("{:.0}%", (bet.edge.estimate.confidence * dec!(100)).to_f64().unwrap_or(0.0)),
                         "Bet approved"
                     );
                     self.risk.record_approval(&bet, adjusted_amount);
+                    let drawdown_multiplier = context.drawdown_factor;
                     let mut approved = bet.clone();
                     approved.bet_amount = adjusted_amount;
+                    approved.risk_context = Some(context);
+                    approved.idempotency_key = Some(uuid::Uuid::new_v4().to_string());
                     decisions.push(DecisionRecord::Selected {
                         bet: approved.clone(),
                         adjusted_amount,
+                        drawdown_multiplier,
                     });
                     selected.push(approved);
                 }
                 Err(reason) => {
                     warn!(
                         market_id = %bet.edge.market.id,
+                        code = reason.code(),
                         reason = %reason,
                         "Bet rejected by risk manager"
                     );
@@ -206,6 +516,53 @@ impl StrategyOrchestrator {
         (selected, decisions)
     }
 
+    /// Find arbitrage pairs in this cycle's clusters and approve each, best
+    /// margin first, through [`RiskManager::approve_pair_at`]. Needs no
+    /// estimates, so it runs before estimation; call it after
+    /// `reset_cycle` and before `select_bets`, which then sees the legs as
+    /// approved this cycle.
+    ///
+    /// Returns both legs of every approved pair (ready for
+    /// `Executor::execute_batch`) and a decision per pair found.
+    pub fn select_arbitrage_at(
+        &mut self,
+        state: &AgentState,
+        now: DateTime<Utc>,
+    ) -> (Vec<SizedBet>, Vec<DecisionRecord>) {
+        let Some(detector) = &self.arbitrage else {
+            return (Vec::new(), Vec::new());
+        };
+        let pairs = detector.find_at(state, now);
+        let mut selected = Vec::new();
+        let mut decisions = Vec::new();
+        for mut pair in pairs {
+            match self.risk.approve_pair_at(pair.legs(), state, now) {
+                Ok(approvals) => {
+                    for (leg, approval) in [&mut pair.yes_leg, &mut pair.no_leg].into_iter().zip(approvals) {
+                        leg.bet_amount = approval.amount;
+                        leg.risk_context = Some(approval.context);
+                        leg.idempotency_key = Some(uuid::Uuid::new_v4().to_string());
+                        selected.push(leg.clone());
+                    }
+                    info!(
+                        cluster = %pair.cluster,
+                        yes = %pair.yes_leg.edge.market.platform,
+                        no = %pair.no_leg.edge.market.platform,
+                        stake = %format!("${:.2}", pair.stake().to_f64().unwrap_or(0.0)),
+                        margin = %format!("{:.2}%", (pair.margin * dec!(100)).to_f64().unwrap_or(0.0)),
+                        "Arbitrage pair approved"
+                    );
+                    decisions.push(DecisionRecord::Arbitrage { pair: Box::new(pair), rejection: None });
+                }
+                Err(reason) => {
+                    warn!(cluster = %pair.cluster, code = reason.code(), reason = %reason, "Arbitrage pair rejected by risk manager");
+                    decisions.push(DecisionRecord::Arbitrage { pair: Box::new(pair), rejection: Some(reason) });
+                }
+            }
+        }
+        (selected, decisions)
+    }
+
     /// Convert a slice of approved bets to `BetDecision`s for logging or
     /// persistence.  Data sources are not tracked at the strategy layer so
     /// that field is left empty; callers may populate it if desired.
@@ -221,6 +578,7 @@ impl StrategyOrchestrator {
                 confidence: b.edge.estimate.confidence,
                 rationale: b.edge.estimate.reasoning.clone(),
                 data_sources_used: Vec::new(),
+                risk_context: b.risk_context.clone(),
             })
             .collect()
     }
@@ -236,7 +594,7 @@ mod tests {
     use crate::strategy::edge::EdgeConfig;
     use crate::strategy::kelly::KellyConfig;
     use crate::strategy::risk::RiskConfig;
-    use crate::types::{AgentState, AgentStatus, Estimate, Market, MarketCategory};
+    use crate::types::{AgentState, AgentStatus, Estimate, Market, MarketCategory, Side};
     use chrono::{Duration, Utc};
 
     // ---- helpers -----------------------------------------------------------
@@ -256,6 +614,9 @@ mod tests {
             resolution_criteria: String::new(),
             url: String::new(),
             cross_refs: Default::default(),
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         }
     }
 
@@ -266,6 +627,9 @@ mod tests {
             reasoning: "test reasoning".to_string(),
             tokens_used: 100,
             cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
         }
     }
 
@@ -283,14 +647,31 @@ mod tests {
             total_ib_commissions: Decimal::ZERO,
             start_time: Utc::now(),
             peak_bankroll: bankroll,
+            cash_bankroll: bankroll,
+            total_equity: bankroll,
+            peak_equity: bankroll,
+            unrealized_pnl: Decimal::ZERO,
             status: AgentStatus::Alive,
+            pause_reason: None,
             survival_threshold: Decimal::ZERO,
             mana_bankroll: Decimal::ZERO,
             total_mana_pnl: Decimal::ZERO,
             mana_trades_won: 0,
             mana_trades_lost: 0,
+            balances: Default::default(),
             open_bets: Vec::new(),
+            unconfirmed_bets: Vec::new(),
             last_cycle_time: None,
+            edge_realizations: Vec::new(),
+            thresholds: Default::default(),
+            external_activity: Default::default(),
+            effective_config: Default::default(),
+            dirty_shutdown: None,
+            cool_downs: Default::default(),
+            tag_results: Vec::new(),
+            daily: Default::default(),
+            references: Default::default(),
+            hibernation: Default::default(),
         }
     }
 
@@ -311,7 +692,7 @@ mod tests {
     fn test_no_estimates_returns_empty() {
         let mut orc = make_orchestrator();
         let state = make_state(dec!(1000));
-        let (bets, decisions) = orc.select_bets(&[], &state, None);
+        let (bets, decisions) = orc.select_bets(&[], &state);
         assert!(bets.is_empty());
         assert!(decisions.is_empty());
     }
@@ -325,7 +706,7 @@ mod tests {
             make_market("m1", MarketCategory::Weather, dec!(0.50)),
             make_estimate(dec!(0.54), dec!(0.9)),
         )];
-        let (bets, decisions) = orc.select_bets(&estimates, &state, None);
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
         assert!(bets.is_empty());
         // No decisions logged because edge was filtered before the decision log
         assert!(decisions.is_empty());
@@ -340,7 +721,7 @@ mod tests {
             make_market("m1", MarketCategory::Weather, dec!(0.40)),
             make_estimate(dec!(0.60), dec!(0.8)),
         )];
-        let (bets, decisions) = orc.select_bets(&estimates, &state, None);
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
         assert_eq!(bets.len(), 1);
         assert!(bets[0].bet_amount > Decimal::ZERO);
         assert!(matches!(decisions[0], DecisionRecord::Selected { .. }));
@@ -370,11 +751,40 @@ mod tests {
             ),
         ];
 
-        let (bets, _) = orc.select_bets(&estimates, &state, None);
+        let (bets, _) = orc.select_bets(&estimates, &state);
         assert!(!bets.is_empty());
         assert_eq!(bets[0].edge.market.id, "high_score");
     }
 
+    #[test]
+    fn test_stakes_rounded_to_platform_before_approval() {
+        let mut orc = make_orchestrator();
+        let mut state = make_state(dec!(77));
+        state.balances.insert("betfair".to_string(), dec!(30));
+        let mut betfair = make_market("bf", MarketCategory::Weather, dec!(0.40));
+        betfair.platform = "betfair".to_string();
+        let estimates = vec![
+            (make_market("mf", MarketCategory::Weather, dec!(0.40)), make_estimate(dec!(0.60), dec!(0.8))),
+            (betfair, make_estimate(dec!(0.60), dec!(0.8))),
+        ];
+
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
+
+        // 6% of 77 mana = 4.62, placed as 4; 6% of 30 on Betfair is under its 2.00 minimum.
+        assert_eq!(bets.len(), 1);
+        assert_eq!(bets[0].bet_amount, dec!(4));
+        assert_eq!((bets[0].bet_fraction * dec!(77)).round_dp(6), dec!(4));
+        assert!(decisions.iter().any(|d| matches!(
+            d,
+            DecisionRecord::StakeRejected { bet, reason: StakeRejection::BelowMinStake { stake, .. } }
+                if bet.edge.market.id == "bf" && *stake == dec!(1.80)
+        )));
+        assert!(decisions.iter().any(|d| matches!(
+            d,
+            DecisionRecord::Selected { adjusted_amount, .. } if *adjusted_amount == dec!(4)
+        )));
+    }
+
     #[test]
     fn test_kelly_rejection_logged() {
         // Floor so high that no bet survives Kelly sizing
@@ -392,7 +802,7 @@ mod tests {
             make_market("m1", MarketCategory::Weather, dec!(0.40)),
             make_estimate(dec!(0.60), dec!(0.8)),
         )];
-        let (bets, decisions) = orc.select_bets(&estimates, &state, None);
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
         assert!(bets.is_empty());
         assert!(decisions
             .iter()
@@ -414,13 +824,129 @@ mod tests {
             })
             .collect();
 
-        let (bets, decisions) = orc.select_bets(&estimates, &state, None);
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
         assert!(bets.len() <= 5);
         assert!(decisions
             .iter()
             .any(|d| matches!(d, DecisionRecord::RiskRejected { .. })));
     }
 
+    #[test]
+    fn test_same_event_markets_keep_only_top_ranked_bet() {
+        let mut orc = make_orchestrator();
+        let state = make_state(dec!(10_000));
+
+        // Match Odds, Over/Under 2.5 and BTTS on one fixture: differently
+        // worded questions, one underlying event.
+        let estimates: Vec<_> = [
+            ("1.201", dec!(0.58), dec!(0.8)),
+            ("1.202", dec!(0.70), dec!(0.9)), // strongest
+            ("1.203", dec!(0.62), dec!(0.7)),
+        ]
+        .into_iter()
+        .map(|(id, p, conf)| {
+            let mut m = make_market(id, MarketCategory::Sports, dec!(0.40));
+            m.platform = "betfair".into();
+            m.event_group = Some("betfair:34120567".into());
+            (m, make_estimate(p, conf))
+        })
+        .collect();
+
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
+        assert_eq!(bets.len(), 1);
+        assert_eq!(bets[0].edge.market.id, "1.202");
+        let capped = decisions
+            .iter()
+            .filter(|d| matches!(
+                d,
+                DecisionRecord::RiskRejected { reason: RejectionReason::EventGroupLimitReached { .. }, .. }
+            ))
+            .count();
+        assert_eq!(capped, 2);
+    }
+
+    #[test]
+    fn test_cluster_bets_routed_and_counted_once() {
+        let mut orc = make_orchestrator();
+        orc.set_venue_selector(VenueSelector::new(
+            crate::config::VenueSelectionConfig::default(),
+            ["betfair".to_string(), "polymarket".to_string()],
+        ));
+        let state = make_state(dec!(10_000));
+
+        // One event on two venues at the same price; Betfair takes 5% of
+        // winnings, so both edges route to Polymarket.
+        let on = |platform: &str| {
+            let mut m = make_market(&format!("{platform}-final"), MarketCategory::Sports, dec!(0.40));
+            m.platform = platform.into();
+            m
+        };
+        let (betfair, polymarket) = (on("betfair"), on("polymarket"));
+        orc.set_clusters(vec![vec![betfair.clone(), polymarket.clone()]]);
+        let estimates = vec![
+            (betfair, make_estimate(dec!(0.60), dec!(0.9))),
+            (polymarket, make_estimate(dec!(0.60), dec!(0.8))),
+        ];
+
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
+        assert_eq!(bets.len(), 1);
+        assert_eq!(bets[0].edge.market.platform, "polymarket");
+        let choice = bets[0].venue.as_ref().unwrap();
+        assert_eq!((choice.origin.as_str(), choice.chosen.as_str()), ("betfair:betfair-final", "polymarket:polymarket-final"));
+        assert_eq!(choice.considered.len(), 2);
+
+        // The Polymarket-triggered bet is the same position: the cluster's
+        // slot is taken whichever venue filled it.
+        let rejected = decisions.iter().find_map(|d| match d {
+            DecisionRecord::RiskRejected { bet, reason: RejectionReason::EventGroupLimitReached { group, .. } } => {
+                Some((bet.venue.as_ref().unwrap().origin.clone(), group.clone()))
+            }
+            _ => None,
+        });
+        assert_eq!(rejected, Some(("polymarket:polymarket-final".into(), choice.cluster.clone())));
+    }
+
+    #[test]
+    fn test_bets_sized_against_platform_balance() {
+        let mut orc = make_orchestrator();
+        let mut state = make_state(dec!(1000));
+        state.balances.insert("betfair".to_string(), dec!(200));
+        let mut betfair = make_market("bf", MarketCategory::Sports, dec!(0.40));
+        betfair.platform = "betfair".to_string();
+        let estimates = vec![
+            (make_market("mf", MarketCategory::Weather, dec!(0.40)), make_estimate(dec!(0.60), dec!(0.8))),
+            (betfair, make_estimate(dec!(0.60), dec!(0.8))),
+        ];
+
+        let (bets, _) = orc.select_bets(&estimates, &state);
+        let amount = |id: &str| bets.iter().find(|b| b.edge.market.id == id).unwrap().bet_amount;
+        // Same edge: Betfair sizes against its 200 balance, Manifold (no
+        // balance, no Mana) against the 1000 aggregate.
+        assert_eq!(bets.len(), 2);
+        assert_eq!(amount("mf"), amount("bf") * dec!(5));
+
+        // Once Manifold reports a balance, that is what it sizes against.
+        state.balances.insert("manifold".to_string(), dec!(200));
+        let (bets, _) = make_orchestrator().select_bets(&estimates, &state);
+        let amount = |id: &str| bets.iter().find(|b| b.edge.market.id == id).unwrap().bet_amount;
+        assert_eq!(amount("mf"), amount("bf"));
+    }
+
+    #[test]
+    fn test_tentative_bet_fractions() {
+        let orc = make_orchestrator();
+        let state = make_state(dec!(1000));
+        let estimates = vec![
+            (make_market("none", MarketCategory::Weather, dec!(0.50)), make_estimate(dec!(0.52), dec!(0.9))),
+            (make_market("small", MarketCategory::Weather, dec!(0.40)), make_estimate(dec!(0.50), dec!(0.8))),
+            (make_market("large", MarketCategory::Weather, dec!(0.40)), make_estimate(dec!(0.70), dec!(0.8))),
+        ];
+        let fractions = orc.tentative_bet_fractions(&estimates, &state);
+        let indices: Vec<usize> = fractions.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![1, 2]);
+        assert!(fractions[1].1 > fractions[0].1);
+    }
+
     #[test]
     fn test_to_bet_decisions_conversion() {
         let mut orc = make_orchestrator();
@@ -429,13 +955,15 @@ mod tests {
             make_market("m1", MarketCategory::Weather, dec!(0.40)),
             make_estimate(dec!(0.60), dec!(0.8)),
         )];
-        let (bets, _) = orc.select_bets(&estimates, &state, None);
+        let (bets, _) = orc.select_bets(&estimates, &state);
         let decisions = StrategyOrchestrator::to_bet_decisions(&bets);
         assert_eq!(decisions.len(), bets.len());
         if let Some(d) = decisions.first() {
             assert_eq!(d.market.id, "m1");
             assert_eq!(d.fair_value, dec!(0.60));
             assert!(d.bet_amount > Decimal::ZERO);
+            let ctx = d.risk_context.as_ref().expect("approved bets carry a risk context");
+            assert_eq!((ctx.bankroll, ctx.exposure, ctx.bets_today), (dec!(1000), Decimal::ZERO, 0));
         }
     }
 
@@ -464,7 +992,7 @@ mod tests {
             .collect();
 
         // Fill the cycle limit (5 bets)
-        let (bets_first, _) = orc.select_bets(&estimates, &state, None);
+        let (bets_first, _) = orc.select_bets(&estimates, &state);
         assert_eq!(bets_first.len(), 5);
 
         // After reset, a new cycle can approve bets again
@@ -473,7 +1001,7 @@ mod tests {
             make_market("new", MarketCategory::Weather, dec!(0.40)),
             make_estimate(dec!(0.60), dec!(0.8)),
         )];
-        let (bets_second, _) = orc.select_bets(&estimates2, &state, None);
+        let (bets_second, _) = orc.select_bets(&estimates2, &state);
         assert_eq!(bets_second.len(), 1);
     }
 
@@ -487,10 +1015,136 @@ mod tests {
             make_market("m1", MarketCategory::Weather, dec!(0.40)),
             make_estimate(dec!(0.60), dec!(0.8)),
         )];
-        let (bets, decisions) = orc.select_bets(&estimates, &state, None);
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
         assert!(bets.is_empty());
         assert!(decisions
             .iter()
             .any(|d| matches!(d, DecisionRecord::RiskRejected { .. })));
     }
+
+    /// Coarse guard against order-of-magnitude regressions in selection,
+    /// for contributors who don't run `cargo bench`. The bound is ~100x
+    /// a release-build run, so it holds in debug builds on slow machines.
+    #[test]
+    fn test_select_bets_200_estimates_within_time_bound() {
+        let estimates = crate::testkit::estimates(200, 2026);
+        let mut orc = make_orchestrator();
+        let mut state = make_state(dec!(1000));
+        state.mana_bankroll = dec!(1000);
+
+        let start = std::time::Instant::now();
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
+        let elapsed = start.elapsed();
+
+        assert!(!bets.is_empty());
+        assert!(!decisions.is_empty());
+        assert!(
+            elapsed < std::time::Duration::from_secs(2),
+            "select_bets over 200 estimates took {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn test_config_reload_applies_new_thresholds_next_select() {
+        let original = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/config.toml")).unwrap();
+        let path = std::env::temp_dir().join(format!("oracle_config_reload_{}.toml", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap().to_string();
+        std::fs::write(&path, &original).unwrap();
+
+        let cfg = AppConfig::load(&path).unwrap();
+        let mut watcher = crate::config::ConfigWatcher::new(&path, cfg.clone());
+        let params = StrategyParams::from_config(&cfg, links::MarketLinks::default());
+        let mut orc = StrategyOrchestrator::new(
+            EdgeDetector::new(params.edge),
+            KellyCalculator::new(params.kelly),
+            RiskManager::new(params.risk),
+        );
+        let state = make_state(dec!(1000));
+        // 15% edge: above the configured 6% weather threshold.
+        let estimates = vec![(
+            make_market("m1", MarketCategory::Weather, dec!(0.40)),
+            make_estimate(dec!(0.55), dec!(0.9)),
+        )];
+        assert_eq!(orc.select_bets(&estimates, &state).0.len(), 1);
+        assert!(watcher.reload_if_changed().is_none(), "unchanged file");
+
+        // Raise the weather threshold past the edge; the name change needs a restart.
+        let edited = original
+            .replacen("weather = 0.06", "weather = 0.200", 1)
+            .replacen("name = \"ORACLE-001\"", "name = \"ORACLE-002\"", 1);
+        assert_ne!(edited, original);
+        std::fs::write(&path, &edited).unwrap();
+        let reloaded = watcher.reload_if_changed().expect("threshold change applied");
+        assert_eq!(reloaded.risk.category_thresholds["weather"], dec!(0.2));
+        assert_eq!(reloaded.agent.name, cfg.agent.name);
+
+        orc.update_config(StrategyParams::from_config(reloaded, orc.links().clone()));
+        orc.reset_cycle();
+        let (bets, decisions) = orc.select_bets(&estimates, &state);
+        assert!(bets.is_empty() && decisions.is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    fn venue_market(platform: &str, id: &str, yes: Decimal, no: Decimal) -> Market {
+        Market {
+            platform: platform.to_string(),
+            current_price_no: no,
+            liquidity: dec!(2000),
+            ..make_market(id, MarketCategory::Politics, yes)
+        }
+    }
+
+    fn arbitrage_orchestrator(risk: RiskConfig) -> StrategyOrchestrator {
+        let mut orc = StrategyOrchestrator::new(
+            EdgeDetector::new(EdgeConfig::default()),
+            KellyCalculator::new(KellyConfig::default()),
+            RiskManager::new(risk),
+        );
+        let config = crate::config::ArbitrageConfig { enabled: true, ..Default::default() };
+        let fees = HashMap::from([("betfair".to_string(), dec!(0.05))]);
+        orc.set_arbitrage_detector(ArbitrageDetector::new(config, fees, ["betfair".to_string(), "polymarket".to_string()]));
+        orc.set_clusters(vec![
+            // Genuine gap: 42¢ YES + 45¢ NO
+            vec![venue_market("polymarket", "pm-gap", dec!(0.42), dec!(0.60)), venue_market("betfair", "bf-gap", dec!(0.55), dec!(0.45))],
+            // 2¢ gap, eaten by Betfair's 5% on a YES win
+            vec![venue_market("betfair", "bf-fee", dec!(0.30), dec!(0.72)), venue_market("polymarket", "pm-fee", dec!(0.30), dec!(0.68))],
+            // Wide gap, but Metaculus takes no orders
+            vec![venue_market("polymarket", "pm-ref", dec!(0.30), dec!(0.72)), venue_market("metaculus", "mc-ref", dec!(0.60), dec!(0.40))],
+        ]);
+        orc
+    }
+
+    #[test]
+    fn test_arbitrage_orders_only_for_executable_gap_after_fees() {
+        let mut orc = arbitrage_orchestrator(RiskConfig::default());
+        let state = make_state(dec!(10_000));
+        let (bets, decisions) = orc.select_arbitrage_at(&state, Utc::now());
+
+        let legs: Vec<_> = bets.iter().map(|b| (b.edge.market.id.as_str(), b.edge.side)).collect();
+        assert_eq!(legs, [("pm-gap", Side::Yes), ("bf-gap", Side::No)]);
+        assert!(bets.iter().all(|b| b.risk_context.is_some() && b.idempotency_key.is_some()));
+        // Equal shares on both legs
+        assert_eq!((bets[0].bet_amount / dec!(0.42)).round_dp(6), (bets[1].bet_amount / dec!(0.45)).round_dp(6));
+        assert_eq!(decisions.len(), 1);
+        assert!(matches!(&decisions[0], DecisionRecord::Arbitrage { rejection: None, .. }));
+    }
+
+    #[test]
+    fn test_arbitrage_leg_rejection_cancels_pair() {
+        // Room for one bet this cycle: the NO leg is rejected, so the YES
+        // leg must not go out alone — nor use up the slot
+        let risk = RiskConfig { max_bets_per_cycle: 1, ..RiskConfig::default() };
+        let mut orc = arbitrage_orchestrator(risk);
+        let state = make_state(dec!(10_000));
+        let (bets, decisions) = orc.select_arbitrage_at(&state, Utc::now());
+
+        assert!(bets.is_empty());
+        assert!(matches!(
+            &decisions[0],
+            DecisionRecord::Arbitrage { rejection: Some(RejectionReason::MaxBetsPerCycleReached { .. }), .. }
+        ));
+        let estimates = vec![(make_market("m1", MarketCategory::Weather, dec!(0.40)), make_estimate(dec!(0.60), dec!(0.8)))];
+        let (bets, _) = orc.select_bets(&estimates, &state);
+        assert_eq!(bets.len(), 1);
+    }
 }
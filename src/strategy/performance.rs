@@ -0,0 +1,336 @@
+//! Performance attribution of settled bets.
+//!
+//! Groups settled trades by category and by platform, each split by
+//! currency since AUD and mana stakes don't add up, and reports what each
+//! group earned: ROI, the mean edge detected at entry against the mean edge
+//! realised (outcome minus fill price), and the Brier score of the
+//! estimates behind the bets. An estimate is reconstructed as its side's
+//! price at approval plus the detected edge. A bet counts as won when its
+//! P&L is positive, so a profitable early exit scores as a win; refunds
+//! (zero P&L) carry no outcome and are left out of the edge and Brier
+//! figures.
+//!
+//! [`PerformanceReport::build`] also flags categories whose realised edge
+//! is significantly below their edge threshold — the upper end of a
+//! one-sided 95% interval on the mean falls short of it — and suggests
+//! raising the threshold by the category's shortfall between detected and
+//! realised edge.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rust_decimal::prelude::*;
+use serde::Serialize;
+
+use crate::storage::backend::TradeRecord;
+use crate::types::MarketCategory;
+
+/// Scored bets a category needs before it is tested against its threshold;
+/// the interval uses the normal approximation, which needs the sample.
+pub const MIN_SUGGESTION_TRADES: usize = 20;
+
+/// One-sided 95% critical value.
+const SIGNIFICANCE_Z: f64 = 1.645;
+
+/// Realised results of one category or platform in one currency.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Attribution {
+    /// Category or platform name.
+    pub key: String,
+    pub currency: String,
+    pub trades: u64,
+    pub won: u64,
+    pub staked: Decimal,
+    pub pnl: Decimal,
+    /// P&L over stake.
+    pub roi: f64,
+    /// Mean edge detected at placement, over bets that recorded one.
+    pub avg_entry_edge: Option<f64>,
+    /// Mean of outcome (1 won, 0 lost) minus fill price.
+    pub avg_realised_edge: Option<f64>,
+    /// Mean squared error of the estimates behind the bets.
+    pub brier: Option<f64>,
+}
+
+/// A category whose realised edge is significantly below its threshold.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThresholdSuggestion {
+    pub category: String,
+    /// Bets with an outcome.
+    pub trades: usize,
+    pub threshold: f64,
+    pub avg_entry_edge: f64,
+    pub avg_realised_edge: f64,
+    /// Upper end of the one-sided 95% interval on `avg_realised_edge`.
+    pub upper_bound: f64,
+    /// `threshold` raised by the entry-to-realised edge shortfall.
+    pub suggested: f64,
+}
+
+/// Settled trades attributed by category and by platform.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PerformanceReport {
+    pub by_category: Vec<Attribution>,
+    pub by_platform: Vec<Attribution>,
+    pub suggestions: Vec<ThresholdSuggestion>,
+}
+
+/// What one bet contributes to the edge and Brier means.
+struct Score {
+    entry_edge: Option<f64>,
+    /// Realised edge and squared estimate error; `None` for a refund.
+    outcome: Option<(f64, Option<f64>)>,
+}
+
+fn score(trade: &TradeRecord, pnl: Decimal) -> Score {
+    let receipt = &trade.receipt;
+    let entry_edge = receipt.edge.and_then(|e| e.to_f64());
+    let outcome = (!pnl.is_zero()).then(|| {
+        let won = if pnl > Decimal::ZERO { 1.0 } else { 0.0 };
+        let fill = receipt.fill_price.to_f64().unwrap_or(0.0);
+        let price = receipt.expected_price.unwrap_or(receipt.fill_price).to_f64().unwrap_or(fill);
+        let brier = entry_edge.map(|edge| ((price + edge).clamp(0.0, 1.0) - won).powi(2));
+        (won - fill, brier)
+    });
+    Score { entry_edge, outcome }
+}
+
+#[derive(Default)]
+struct Tally {
+    trades: u64,
+    won: u64,
+    staked: Decimal,
+    pnl: Decimal,
+    entry_edges: Vec<f64>,
+    realised: Vec<f64>,
+    brier: Vec<f64>,
+}
+
+impl Tally {
+    fn add(&mut self, trade: &TradeRecord, pnl: Decimal, score: &Score) {
+        self.trades += 1;
+        self.won += u64::from(pnl > Decimal::ZERO);
+        self.staked += trade.receipt.amount;
+        self.pnl += pnl;
+        self.entry_edges.extend(score.entry_edge);
+        if let Some((realised, brier)) = score.outcome {
+            self.realised.push(realised);
+            self.brier.extend(brier);
+        }
+    }
+
+    fn attribution(self, key: String, currency: String) -> Attribution {
+        let roi = if self.staked.is_zero() { 0.0 } else { (self.pnl / self.staked).to_f64().unwrap_or(0.0) };
+        Attribution {
+            key,
+            currency,
+            trades: self.trades,
+            won: self.won,
+            staked: self.staked,
+            pnl: self.pnl,
+            roi,
+            avg_entry_edge: mean(&self.entry_edges),
+            avg_realised_edge: mean(&self.realised),
+            brier: mean(&self.brier),
+        }
+    }
+}
+
+fn mean(xs: &[f64]) -> Option<f64> {
+    (!xs.is_empty()).then(|| xs.iter().sum::<f64>() / xs.len() as f64)
+}
+
+/// Standard error of the mean of `xs` (at least two values).
+fn std_error(xs: &[f64], mean: f64) -> f64 {
+    let n = xs.len() as f64;
+    let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (var / n).sqrt()
+}
+
+impl PerformanceReport {
+    /// Attribute the settled trades in `trades` (open ones are skipped),
+    /// testing each category against `threshold`, its configured edge
+    /// threshold. Groups are ordered by name, then currency.
+    pub fn build(trades: &[TradeRecord], threshold: impl Fn(MarketCategory) -> Option<f64>) -> Self {
+        let mut by_category: BTreeMap<(String, String), Tally> = BTreeMap::new();
+        let mut by_platform: BTreeMap<(String, String), Tally> = BTreeMap::new();
+        let mut pooled: HashMap<MarketCategory, Tally> = HashMap::new();
+        for trade in trades {
+            let Some(pnl) = trade.pnl else { continue };
+            let receipt = &trade.receipt;
+            let category = receipt.category.unwrap_or(MarketCategory::Other);
+            let score = score(trade, pnl);
+            by_category.entry((category.to_string(), receipt.currency.clone())).or_default().add(trade, pnl, &score);
+            by_platform.entry((receipt.platform.clone(), receipt.currency.clone())).or_default().add(trade, pnl, &score);
+            pooled.entry(category).or_default().add(trade, pnl, &score);
+        }
+
+        let suggestions = MarketCategory::ALL
+            .iter()
+            .filter_map(|&category| suggest(category, pooled.get(&category)?, threshold(category)?))
+            .collect();
+        let attribute = |groups: BTreeMap<(String, String), Tally>| {
+            groups.into_iter().map(|((key, currency), tally)| tally.attribution(key, currency)).collect()
+        };
+        Self { by_category: attribute(by_category), by_platform: attribute(by_platform), suggestions }
+    }
+}
+
+/// A suggestion for `category` if its realised edge is significantly below
+/// `threshold`.
+fn suggest(category: MarketCategory, tally: &Tally, threshold: f64) -> Option<ThresholdSuggestion> {
+    if tally.realised.len() < MIN_SUGGESTION_TRADES {
+        return None;
+    }
+    let realised = mean(&tally.realised)?;
+    let upper_bound = realised + SIGNIFICANCE_Z * std_error(&tally.realised, realised);
+    if upper_bound >= threshold {
+        return None;
+    }
+    let entry = mean(&tally.entry_edges).unwrap_or(threshold);
+    Some(ThresholdSuggestion {
+        category: category.to_string(),
+        trades: tally.realised.len(),
+        threshold,
+        avg_entry_edge: entry,
+        avg_realised_edge: realised,
+        upper_bound,
+        suggested: (threshold + (entry - realised).max(0.0)).min(1.0),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    use crate::types::{Side, TradeReceipt};
+
+    fn trade(
+        category: MarketCategory,
+        platform: &str,
+        currency: &str,
+        amount: Decimal,
+        fill: Decimal,
+        edge: Option<Decimal>,
+        pnl: Option<Decimal>,
+    ) -> TradeRecord {
+        TradeRecord {
+            receipt: TradeReceipt {
+                order_id: uuid::Uuid::new_v4().to_string(),
+                market_id: "m".into(),
+                platform: platform.into(),
+                side: Side::Yes,
+                amount,
+                fill_price: fill,
+                fees: Decimal::ZERO,
+                timestamp: Utc::now(),
+                currency: currency.into(),
+                deadline: None,
+                category: Some(category),
+                edge,
+                raw_response: None,
+                risk_context: None,
+                tags: Vec::new(),
+                correlation_key: None,
+                expected_price: None,
+            },
+            pnl,
+            settled_at: pnl.map(|_| Utc::now()),
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn synthetic() -> Vec<TradeRecord> {
+        let mut politics = trade(MarketCategory::Politics, "manifold", "Mana", dec!(100), dec!(0.25), Some(dec!(0.05)), Some(dec!(300)));
+        // Approved at 0.20, so the estimate behind it was 0.25.
+        politics.receipt.expected_price = Some(dec!(0.20));
+        vec![
+            trade(MarketCategory::Sports, "betfair", "AUD", dec!(10), dec!(0.5), Some(dec!(0.1)), Some(dec!(10))),
+            trade(MarketCategory::Sports, "betfair", "AUD", dec!(20), dec!(0.4), Some(dec!(0.2)), Some(dec!(-20))),
+            politics,
+            // Refunded: counted, but no outcome to score.
+            trade(MarketCategory::Sports, "manifold", "Mana", dec!(50), dec!(0.5), None, Some(Decimal::ZERO)),
+            // Still open.
+            trade(MarketCategory::Sports, "betfair", "AUD", dec!(99), dec!(0.5), Some(dec!(0.1)), None),
+        ]
+    }
+
+    #[test]
+    fn test_attribution_by_category() {
+        let report = PerformanceReport::build(&synthetic(), |_| None);
+        let keys: Vec<_> = report.by_category.iter().map(|a| (a.key.as_str(), a.currency.as_str())).collect();
+        assert_eq!(keys, vec![("Politics", "Mana"), ("Sports", "AUD"), ("Sports", "Mana")]);
+
+        let sports = &report.by_category[1];
+        assert_eq!((sports.trades, sports.won), (2, 1));
+        assert_eq!((sports.staked, sports.pnl), (dec!(30), dec!(-10)));
+        assert!(close(sports.roi, -1.0 / 3.0));
+        assert!(close(sports.avg_entry_edge.unwrap(), 0.15));
+        // Won at 0.5 (+0.5), lost at 0.4 (−0.4).
+        assert!(close(sports.avg_realised_edge.unwrap(), 0.05));
+        // Estimates 0.6 and 0.6: (0.4² + 0.6²) / 2.
+        assert!(close(sports.brier.unwrap(), 0.26));
+
+        let politics = &report.by_category[0];
+        assert!(close(politics.roi, 3.0));
+        assert!(close(politics.avg_realised_edge.unwrap(), 0.75));
+        assert!(close(politics.brier.unwrap(), 0.5625));
+
+        let refunded = &report.by_category[2];
+        assert_eq!((refunded.trades, refunded.won, refunded.staked), (1, 0, dec!(50)));
+        assert_eq!((refunded.avg_entry_edge, refunded.avg_realised_edge, refunded.brier), (None, None, None));
+        assert_eq!(refunded.roi, 0.0);
+    }
+
+    #[test]
+    fn test_attribution_by_platform() {
+        let report = PerformanceReport::build(&synthetic(), |_| None);
+        assert_eq!(report.by_platform.len(), 2);
+        let betfair = &report.by_platform[0];
+        assert_eq!((betfair.key.as_str(), betfair.trades, betfair.pnl), ("betfair", 2, dec!(-10)));
+        let manifold = &report.by_platform[1];
+        assert_eq!((manifold.trades, manifold.won), (2, 1));
+        assert_eq!((manifold.staked, manifold.pnl), (dec!(150), dec!(300)));
+        assert!(close(manifold.roi, 2.0));
+        // The refund contributes to stake but not to the scored means.
+        assert!(close(manifold.avg_entry_edge.unwrap(), 0.05));
+        assert!(close(manifold.avg_realised_edge.unwrap(), 0.75));
+        assert!(report.suggestions.is_empty());
+    }
+
+    fn run(category: MarketCategory, wins: usize, losses: usize) -> Vec<TradeRecord> {
+        let bet = |pnl| trade(category, "betfair", "AUD", dec!(10), dec!(0.5), Some(dec!(0.1)), Some(pnl));
+        (0..wins).map(|_| bet(dec!(10))).chain((0..losses).map(|_| bet(dec!(-10)))).collect()
+    }
+
+    #[test]
+    fn test_suggests_threshold_for_underperforming_category() {
+        let mut trades = run(MarketCategory::Economics, 6, 14);
+        // 8 of 20 at 0.5 is −0.1 ± 0.112: not significantly below 0.08.
+        trades.extend(run(MarketCategory::Weather, 8, 12));
+        // Too few bets to test.
+        trades.extend(run(MarketCategory::Culture, 0, 10));
+        let report = PerformanceReport::build(&trades, |_| Some(0.08));
+
+        assert_eq!(report.suggestions.len(), 1);
+        let s = &report.suggestions[0];
+        assert_eq!((s.category.as_str(), s.trades), ("Economics", 20));
+        // Mean (6·0.5 − 14·0.5) / 20 = −0.2; sample variance 4.2 / 19.
+        assert!(close(s.avg_realised_edge, -0.2));
+        let se = (4.2f64 / 19.0 / 20.0).sqrt();
+        assert!(close(s.upper_bound, -0.2 + 1.645 * se));
+        // 0.08 plus the 0.1 − (−0.2) shortfall.
+        assert!(close(s.suggested, 0.38));
+
+        // Categories without a configured threshold are not tested.
+        assert!(PerformanceReport::build(&trades, |_| None).suggestions.is_empty());
+    }
+}
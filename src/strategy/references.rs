@@ -0,0 +1,319 @@
+//! Cross-reference trust weights.
+//!
+//! Metaculus and Manifold prices on the same question are evidence the LLM
+//! estimate can be pulled towards, but how much each deserves varies by
+//! platform and category. Every resolution scores the reference prices the
+//! agent saw against the outcome; `review` periodically turns each
+//! (platform, category) Brier score into a blending weight, shrunk towards
+//! the configured default until enough resolutions have accumulated.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::*;
+use tracing::{debug, info};
+
+use crate::config::ReferenceWeightsConfig;
+use crate::types::{AgentState, Estimate, Market, MarketCategory, ReferenceAccuracy};
+
+/// Brier score of always answering 50%: a reference scoring this or worse
+/// has learned weight 0.
+pub const UNINFORMED_BRIER: f64 = 0.25;
+
+/// Blending weight for a reference with `resolutions` scored resolutions
+/// at mean Brier score `brier`.
+///
+/// The learned weight scales `max_weight` by the reference's skill over an
+/// uninformed forecaster (`1 − brier / 0.25`, floored at 0). It is averaged
+/// with `default_weight` as if the default had `prior_resolutions`
+/// resolutions of its own, so a reference with little history stays near
+/// the default. Always within `[0, max_weight]`.
+pub fn derive_weight(resolutions: u32, brier: f64, cfg: &ReferenceWeightsConfig) -> f64 {
+    let max = cfg.max_weight.max(0.0);
+    let skill = if brier.is_finite() { (1.0 - brier / UNINFORMED_BRIER).clamp(0.0, 1.0) } else { 0.0 };
+    let learned = max * skill;
+    let n = f64::from(resolutions);
+    let k = f64::from(cfg.prior_resolutions);
+    if n + k == 0.0 {
+        return cfg.default_weight.clamp(0.0, max);
+    }
+    ((n * learned + k * cfg.default_weight) / (n + k)).clamp(0.0, max)
+}
+
+/// Reference prices on `market`, by platform name. A market's own
+/// platform is never its own reference.
+pub fn references(market: &Market) -> Vec<(&'static str, Decimal)> {
+    [("metaculus", market.cross_refs.metaculus_prob), ("manifold", market.cross_refs.manifold_prob)]
+        .into_iter()
+        .filter(|(platform, _)| *platform != market.platform)
+        .filter_map(|(platform, p)| Some((platform, p?)))
+        .collect()
+}
+
+/// Score each reference probability in `references` against a resolved
+/// outcome in `category`.
+pub fn record_resolution(
+    state: &mut AgentState,
+    category: MarketCategory,
+    references: &[(&str, f64)],
+    resolved_yes: bool,
+) {
+    let outcome = if resolved_yes { 1.0 } else { 0.0 };
+    for &(platform, p) in references {
+        let error = (p.clamp(0.0, 1.0) - outcome).powi(2);
+        let accuracy = &mut state.references.accuracy;
+        match accuracy.iter_mut().find(|a| a.platform == platform && a.category == category) {
+            Some(a) => {
+                a.resolutions += 1;
+                a.brier_sum += error;
+            }
+            None => accuracy.push(ReferenceAccuracy {
+                platform: platform.to_string(),
+                category,
+                resolutions: 1,
+                brier_sum: error,
+                weight: None,
+            }),
+        }
+    }
+}
+
+/// Weight in effect for `platform` in `category`: the reviewed weight if
+/// any, otherwise the configured default.
+pub fn weight(state: &AgentState, cfg: &ReferenceWeightsConfig, platform: &str, category: MarketCategory) -> f64 {
+    state
+        .references
+        .accuracy
+        .iter()
+        .find(|a| a.platform == platform && a.category == category)
+        .and_then(|a| a.weight)
+        .unwrap_or(cfg.default_weight)
+}
+
+/// Pull `estimate` towards `market`'s reference prices:
+/// `(1 − Σw)·p + Σ w·ref`. Returns whether the probability changed.
+pub fn blend(estimate: &mut Estimate, market: &Market, state: &AgentState, cfg: &ReferenceWeightsConfig) -> bool {
+    if !cfg.enabled {
+        return false;
+    }
+    let refs = references(market);
+    if refs.is_empty() {
+        return false;
+    }
+    let mut total_weight = Decimal::ZERO;
+    let mut pull = Decimal::ZERO;
+    for (platform, p) in refs {
+        let w = Decimal::from_f64(weight(state, cfg, platform, market.category)).unwrap_or(Decimal::ZERO);
+        total_weight += w;
+        pull += w * p;
+    }
+    let blended = ((Decimal::ONE - total_weight) * estimate.probability + pull).clamp(Decimal::ZERO, Decimal::ONE);
+    if blended == estimate.probability {
+        return false;
+    }
+    debug!(
+        market_id = %market.id,
+        from = %estimate.probability.round_dp(4),
+        to = %blended.round_dp(4),
+        weight = %total_weight.round_dp(3),
+        "Estimate blended with cross-references"
+    );
+    estimate.probability = blended;
+    true
+}
+
+/// Run the periodic review if one is due, re-deriving every weight.
+///
+/// Returns the number of weights that changed (0 when disabled or not
+/// yet due).
+pub fn review(state: &mut AgentState, cfg: &ReferenceWeightsConfig, now: DateTime<Utc>) -> usize {
+    if !cfg.enabled {
+        return 0;
+    }
+    if let Some(last) = state.references.last_review {
+        if now - last < Duration::days(cfg.review_days) {
+            return 0;
+        }
+    }
+    state.references.last_review = Some(now);
+
+    let mut changed = 0;
+    for a in &mut state.references.accuracy {
+        let Some(brier) = a.brier() else { continue };
+        let w = derive_weight(a.resolutions, brier, cfg);
+        if a.weight == Some(w) {
+            continue;
+        }
+        info!(
+            platform = %a.platform,
+            category = %a.category,
+            brier = format!("{brier:.4}"),
+            resolutions = a.resolutions,
+            weight = format!("{w:.3}"),
+            "Cross-reference weight updated"
+        );
+        a.weight = Some(w);
+        changed += 1;
+    }
+    changed
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CrossReferences;
+    use rust_decimal_macros::dec;
+
+    fn cfg() -> ReferenceWeightsConfig {
+        ReferenceWeightsConfig { enabled: true, ..ReferenceWeightsConfig::default() }
+    }
+
+    fn market(platform: &str, metaculus: Option<Decimal>, manifold: Option<Decimal>) -> Market {
+        Market {
+            id: "m1".to_string(),
+            platform: platform.to_string(),
+            question: "Will it rain?".to_string(),
+            description: String::new(),
+            category: MarketCategory::Weather,
+            current_price_yes: dec!(0.50),
+            current_price_no: dec!(0.50),
+            volume_24h: dec!(1000),
+            liquidity: dec!(5000),
+            deadline: Utc::now() + Duration::days(3),
+            resolution_criteria: String::new(),
+            url: String::new(),
+            cross_refs: CrossReferences {
+                metaculus_prob: metaculus,
+                metaculus_forecasters: Some(50),
+                manifold_prob: manifold,
+                forecastex_price: None,
+                ..Default::default()
+            },
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn estimate(p: Decimal) -> Estimate {
+        Estimate {
+            probability: p,
+            confidence: dec!(0.8),
+            reasoning: String::new(),
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
+        }
+    }
+
+    #[test]
+    fn test_derive_weight_from_brier() {
+        let c = ReferenceWeightsConfig { prior_resolutions: 0, ..cfg() };
+        // Perfect reference earns the cap; coin-flip or worse earns nothing.
+        assert!((derive_weight(100, 0.0, &c) - 0.40).abs() < 1e-12);
+        assert!((derive_weight(100, 0.125, &c) - 0.20).abs() < 1e-12);
+        assert_eq!(derive_weight(100, 0.25, &c), 0.0);
+        assert_eq!(derive_weight(100, 0.60, &c), 0.0);
+        assert_eq!(derive_weight(100, f64::NAN, &c), 0.0);
+    }
+
+    #[test]
+    fn test_derive_weight_shrinks_low_samples_to_default() {
+        let c = cfg();
+        // No history: exactly the default.
+        assert!((derive_weight(0, 0.0, &c) - 0.10).abs() < 1e-12);
+        // Three perfect resolutions barely move it: (3·0.40 + 30·0.10) / 33.
+        assert!((derive_weight(3, 0.0, &c) - 4.2 / 33.0).abs() < 1e-12);
+        // A long perfect record approaches the cap from below.
+        let long = derive_weight(3000, 0.0, &c);
+        assert!(long > 0.39 && long < 0.40);
+        // A long useless record approaches 0 from above.
+        let bad = derive_weight(3000, 0.30, &c);
+        assert!(bad > 0.0 && bad < 0.01);
+    }
+
+    #[test]
+    fn test_derive_weight_bounded() {
+        // A default above the cap is still clamped to it.
+        let c = ReferenceWeightsConfig { default_weight: 0.9, max_weight: 0.3, ..cfg() };
+        for n in [0, 1, 10, 1000] {
+            for brier in [0.0, 0.1, 0.25, 1.0] {
+                let w = derive_weight(n, brier, &c);
+                assert!((0.0..=0.3).contains(&w), "n={n} brier={brier} → {w}");
+            }
+        }
+        let negative = ReferenceWeightsConfig { max_weight: -1.0, ..cfg() };
+        assert_eq!(derive_weight(50, 0.0, &negative), 0.0);
+    }
+
+    #[test]
+    fn test_record_and_review_synthetic_table() {
+        let mut state = AgentState::new(dec!(100));
+        // Metaculus is sharp on weather (0.9 on YES), Manifold is noise.
+        for i in 0..60 {
+            let yes = i % 3 != 0;
+            let sharp = if yes { 0.9 } else { 0.1 };
+            record_resolution(&mut state, MarketCategory::Weather, &[("metaculus", sharp), ("manifold", 0.5)], yes);
+        }
+        record_resolution(&mut state, MarketCategory::Sports, &[("metaculus", 0.7)], false);
+        assert_eq!(state.references.accuracy.len(), 3);
+        let metaculus = &state.references.accuracy[0];
+        assert_eq!(metaculus.resolutions, 60);
+        assert!((metaculus.brier().unwrap() - 0.01).abs() < 1e-9);
+
+        // Before review every reference carries the default.
+        let c = cfg();
+        assert_eq!(weight(&state, &c, "metaculus", MarketCategory::Weather), 0.10);
+
+        let now = Utc::now();
+        assert_eq!(review(&mut state, &c, now), 3);
+        let sharp = weight(&state, &c, "metaculus", MarketCategory::Weather);
+        let noise = weight(&state, &c, "manifold", MarketCategory::Weather);
+        let thin = weight(&state, &c, "metaculus", MarketCategory::Sports);
+        assert!((sharp - (60.0 * 0.384 + 3.0) / 90.0).abs() < 1e-9);
+        assert!((noise - 1.0 / 30.0).abs() < 1e-9);
+        // One bad resolution leaves Sports close to the default.
+        assert!(thin < 0.10 && thin > 0.09);
+        // Unseen pairs keep the default.
+        assert_eq!(weight(&state, &c, "manifold", MarketCategory::Politics), 0.10);
+
+        // Weekly: not due after six days; nothing changed after seven.
+        assert_eq!(review(&mut state, &c, now + Duration::days(6)), 0);
+        assert_eq!(state.references.last_review, Some(now));
+        assert_eq!(review(&mut state, &c, now + Duration::days(7)), 0);
+        assert_eq!(state.references.last_review, Some(now + Duration::days(7)));
+
+        let off = ReferenceWeightsConfig { enabled: false, ..cfg() };
+        assert_eq!(review(&mut state, &off, now + Duration::days(30)), 0);
+    }
+
+    #[test]
+    fn test_blend_pulls_towards_references() {
+        let state = AgentState::new(dec!(100));
+        let c = cfg();
+
+        // Two references at the 0.10 default: 0.8·0.50 + 0.1·0.80 + 0.1·0.40.
+        let m = market("betfair", Some(dec!(0.80)), Some(dec!(0.40)));
+        let mut e = estimate(dec!(0.50));
+        assert!(blend(&mut e, &m, &state, &c));
+        assert_eq!(e.probability, dec!(0.52));
+        let m = market("betfair", Some(dec!(0.80)), None);
+        let mut e = estimate(dec!(0.60));
+        assert!(blend(&mut e, &m, &state, &c));
+        assert_eq!(e.probability, dec!(0.62));
+
+        // A Manifold market's own price is not a reference.
+        let m = market("manifold", None, Some(dec!(0.90)));
+        assert!(references(&m).is_empty());
+        assert!(!blend(&mut estimate(dec!(0.60)), &m, &state, &c));
+
+        // Disabled: untouched.
+        let m = market("betfair", Some(dec!(0.80)), None);
+        let off = ReferenceWeightsConfig { enabled: false, ..cfg() };
+        assert!(!blend(&mut estimate(dec!(0.60)), &m, &state, &off));
+    }
+}
@@ -3,14 +3,31 @@
 //! Enforces position limits, category exposure caps, drawdown-adjusted
 //! Kelly multiplier, and aggregate exposure limits. Acts as the final
 //! gate before trade execution.
+//!
+//! Approved stakes are scaled by the drawdown tier the AUD bankroll is in
+//! (`[[risk.drawdown_tiers]]`), down to the halt tier, which rejects every
+//! bet.
+//!
+//! Exposure is counted from the agent's open bets, bets approved this
+//! cycle, and the positions the platforms report holding (see
+//! [`RiskManager::set_open_positions`]), so stakes the agent's state does
+//! not know about still count against the caps. Where a platform reports
+//! its balance, a stake must also fit in what is left of it after this
+//! cycle's earlier approvals.
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use serde::Serialize;
 
+use super::correlation::CorrelationGroups;
 use super::kelly::SizedBet;
-use crate::types::{AgentState, MarketCategory};
+use super::links::{link_key, MarketLinks};
+use crate::config::{CoolDownConfig, CoolDownMode, DrawdownBasis, DrawdownTier, LinkRulesConfig, TagLimit, UnwindWindow};
+use crate::engine::daily;
+use crate::types::{AgentState, Market, MarketCategory, Position, RiskContext, Side, TradeReceipt};
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -27,10 +44,37 @@ pub struct RiskConfig {
     pub max_positions: usize,
     /// Maximum bets per single scan cycle.
     pub max_bets_per_cycle: usize,
-    /// Drawdown threshold to start reducing bets (fraction from peak).
-    pub drawdown_warning_pct: Decimal,
-    /// Drawdown threshold to halt all betting (fraction from peak).
-    pub drawdown_halt_pct: Decimal,
+    /// Maximum bets per cycle on markets sharing one `event_group`
+    /// (e.g. Match Odds, Over/Under and BTTS on the same fixture), or one
+    /// cross-platform event cluster for venue-routed bets.
+    pub max_bets_per_event_group: usize,
+    /// Stake multiplier by drawdown from peak; a tier with multiplier 0
+    /// halts all betting.
+    pub drawdown_tiers: Vec<DrawdownTier>,
+    /// Bankroll or mark-to-market equity, for every drawdown check.
+    pub drawdown_basis: DrawdownBasis,
+    /// Drawdown a halted agent must recover below before it may resume.
+    pub drawdown_resume_pct: Decimal,
+    /// Today's AUD loss, as a fraction of the last end-of-day bankroll,
+    /// that halts betting for the rest of the day (`None` = no halt).
+    pub daily_loss_halt_pct: Option<Decimal>,
+    /// Windows ahead of a market's deadline in which new bets are refused.
+    pub unwind_windows: Vec<UnwindWindow>,
+    /// Cool-down applied to categories on a losing streak.
+    pub cool_down: CoolDownConfig,
+    /// Operator-declared links between related markets.
+    pub links: MarketLinks,
+    /// Rules enforced over `links`.
+    pub link_rules: LinkRulesConfig,
+    /// Caps per operator tag, in addition to the category caps.
+    pub tag_limits: HashMap<String, TagLimit>,
+    /// Keyword clusters that assign each bet its correlation group.
+    pub correlation_groups: CorrelationGroups,
+    /// Combined stake cap per correlation group, as a fraction of bankroll.
+    pub max_exposure_per_group_pct: Decimal,
+    /// Stake cap on one side of one market, as a fraction of bankroll;
+    /// adding to a held position beyond it is refused.
+    pub max_holding_pct: Decimal,
 }
 
 impl Default for RiskConfig {
@@ -40,8 +84,19 @@ impl Default for RiskConfig {
             max_category_exposure_pct: dec!(0.25),  // 25% per category
             max_positions: 20,
             max_bets_per_cycle: 5,
-            drawdown_warning_pct: dec!(0.20),       // 20% from peak
-            drawdown_halt_pct: dec!(0.40),          // 40% from peak
+            max_bets_per_event_group: 1,
+            drawdown_tiers: DrawdownTier::defaults(), // Halt at 40% from peak
+            drawdown_basis: DrawdownBasis::Bankroll,
+            drawdown_resume_pct: dec!(0.30),        // Resume below 30% from peak
+            daily_loss_halt_pct: None,
+            unwind_windows: Vec::new(),
+            cool_down: CoolDownConfig::default(),
+            links: MarketLinks::default(),
+            link_rules: LinkRulesConfig::default(),
+            tag_limits: HashMap::new(),
+            correlation_groups: CorrelationGroups::default(),
+            max_exposure_per_group_pct: dec!(0.10),  // 10% per correlation group
+            max_holding_pct: dec!(0.06),             // One max-size bet per market side
         }
     }
 }
@@ -50,14 +105,95 @@ impl Default for RiskConfig {
 // Risk manager
 // ---------------------------------------------------------------------------
 
-/// Reason a bet was rejected by the risk manager.
-#[derive(Debug, Clone)]
+/// Fill in the category of platform positions from this cycle's scanned
+/// markets, falling back to the open bets on the same market.
+pub fn categorise_positions(positions: &mut [Position], markets: &[Market], open_bets: &[TradeReceipt]) {
+    let known: HashMap<String, MarketCategory> = open_bets
+        .iter()
+        .filter_map(|b| Some((link_key(&b.platform, &b.market_id), b.category?)))
+        .chain(markets.iter().map(|m| (link_key(&m.platform, &m.id), m.category)))
+        .collect();
+    for position in positions.iter_mut().filter(|p| p.category.is_none()) {
+        position.category = known.get(&link_key(&position.platform, &position.market_id)).copied();
+    }
+}
+
+/// Event a bet counts against for the per-event cap: its venue cluster when
+/// routed (whichever venue it landed on), else the market's event group.
+fn event_key(bet: &SizedBet) -> Option<&String> {
+    bet.venue.as_ref().map(|v| &v.cluster).or(bet.edge.market.event_group.as_ref())
+}
+
+/// Events a bet counts against: its [`event_key`], and the duplicate group
+/// of a market merged with listings on other platforms (keyed by the kept
+/// market), so the same question is bet once per cycle.
+fn event_keys(bet: &SizedBet) -> Vec<String> {
+    let market = &bet.edge.market;
+    let duplicate_group = match &market.cross_refs.duplicate_of {
+        Some(primary) => Some(primary.clone()),
+        None if !market.cross_refs.duplicates.is_empty() => Some(link_key(&market.platform, &market.id)),
+        None => None,
+    };
+    let mut keys: Vec<String> = event_key(bet).into_iter().cloned().collect();
+    keys.extend(duplicate_group.filter(|g| !keys.contains(g)));
+    keys
+}
+
+/// `(platform, key)` of each listing of a market's question: the market
+/// itself, its merged duplicates and the market it was merged into.
+fn exposure_unit(market: &Market) -> Vec<(String, String)> {
+    let refs = &market.cross_refs;
+    let mut unit = vec![(market.platform.clone(), link_key(&market.platform, &market.id))];
+    unit.extend(refs.duplicates.iter().map(|d| (d.platform.clone(), link_key(&d.platform, &d.market_id))));
+    if let Some(primary) = &refs.duplicate_of {
+        let platform = primary.split_once(':').map_or(primary.as_str(), |(p, _)| p);
+        unit.push((platform.to_string(), primary.clone()));
+    }
+    unit
+}
+
+/// Open stake and bet count carrying one tag.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TagExposure {
+    pub exposure: Decimal,
+    pub open_bets: usize,
+}
+
+/// Reason a bet was rejected by the risk manager. Each carries the figures
+/// the check compared; percentages are of the bankroll the check used.
+/// [`Self::code`] names the check for the journal, metrics and dashboard;
+/// a stake below the venue minimum is a
+/// [`StakeRejection`](super::constraints::StakeRejection) instead, as it is
+/// found before risk approval.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum RejectionReason {
     ExposureLimitExceeded { current: Decimal, limit: Decimal },
     CategoryLimitExceeded { category: MarketCategory, current: Decimal, limit: Decimal },
     MaxPositionsReached { current: usize, limit: usize },
     MaxBetsPerCycleReached { current: usize, limit: usize },
-    DrawdownHalt { drawdown_pct: Decimal },
+    EventGroupLimitReached { group: String, limit: usize },
+    /// Drawdown from peak has reached the halt tier starting at `limit`.
+    DrawdownHalt { drawdown_pct: Decimal, limit: Decimal },
+    DailyLossHalt { loss_pct: Decimal, limit: Decimal },
+    InsideUnwindWindow { minutes_to_deadline: i64 },
+    /// Category is cooling down after a losing streak. `required_edge` is
+    /// the raised edge bar in multiplier mode, `None` when blocked outright.
+    CategoryCoolDown { category: MarketCategory, losses: u32, required_edge: Option<Decimal> },
+    /// The bet logically opposes a held position on a linked market.
+    LinkedOpposition { set: String, other: String },
+    LinkedExposureExceeded { set: String, current: Decimal, limit: Decimal },
+    TagLimitExceeded { tag: String, current: Decimal, limit: Decimal },
+    TagOpenBetsReached { tag: String, current: usize, limit: usize },
+    /// The bet's correlation group is at its cap; `with` is the largest
+    /// position already held in the group.
+    CorrelatedExposure { group: String, with: String, current: Decimal, limit: Decimal },
+    /// The same side of this market is already held; `market` is its link
+    /// key.
+    AlreadyHolding { market: String, side: Side, current: Decimal, limit: Decimal },
+    /// The platform's cash balance (liquid Mana on Manifold), less stake
+    /// approved there earlier this cycle, can't cover the stake.
+    InsufficientBalance { platform: String, stake: Decimal, available: Decimal },
 }
 
 impl std::fmt::Display for RejectionReason {
@@ -71,22 +207,103 @@ impl std::fmt::Display for RejectionReason {
                 write!(f, "{current} positions at {limit} limit"),
             Self::MaxBetsPerCycleReached { current, limit } =>
                 write!(f, "{current} bets this cycle at {limit} limit"),
-            Self::DrawdownHalt { drawdown_pct } =>
-                write!(f, "Drawdown halt: {drawdown_pct:.1}% from peak"),
+            Self::EventGroupLimitReached { group, limit } =>
+                write!(f, "Event {group} already has {limit} bet(s) this cycle"),
+            Self::DrawdownHalt { drawdown_pct, limit } =>
+                write!(f, "Drawdown halt: {drawdown_pct:.1}% from peak, halt at {limit:.1}%"),
+            Self::DailyLossHalt { loss_pct, limit } =>
+                write!(f, "Daily loss halt: down {loss_pct:.1}% today, limit {limit:.1}%"),
+            Self::InsideUnwindWindow { minutes_to_deadline } =>
+                write!(f, "Inside unwind window: {minutes_to_deadline}m to deadline"),
+            Self::CategoryCoolDown { category, losses, required_edge: Some(required) } =>
+                write!(f, "{category:?} cooling down after {losses} losses: edge below {:.1}%", required * dec!(100)),
+            Self::CategoryCoolDown { category, losses, required_edge: None } =>
+                write!(f, "{category:?} cooling down after {losses} losses: blocked"),
+            Self::LinkedOpposition { set, other } =>
+                write!(f, "Opposes held position on {other} (link {set})"),
+            Self::LinkedExposureExceeded { set, current, limit } =>
+                write!(f, "Linked set {set} exposure {current:.0}% exceeds {limit:.0}% limit"),
+            Self::TagLimitExceeded { tag, current, limit } =>
+                write!(f, "Tag {tag} exposure {current:.0}% exceeds {limit:.0}% limit"),
+            Self::TagOpenBetsReached { tag, current, limit } =>
+                write!(f, "Tag {tag} has {current} open bets at {limit} limit"),
+            Self::CorrelatedExposure { group, with, current, limit } =>
+                write!(f, "Correlated with {with} (group {group}): exposure {current:.0}% exceeds {limit:.0}% limit"),
+            Self::AlreadyHolding { market, side, current, limit } =>
+                write!(f, "Already holding {side} on {market}: {current:.0}% exceeds {limit:.0}% limit"),
+            Self::InsufficientBalance { platform, stake, available } =>
+                write!(f, "Stake {stake:.2} exceeds {available:.2} available on {platform}"),
         }
     }
 }
 
+impl RejectionReason {
+    /// Stable snake_case code of the check that failed (the serialised
+    /// `kind` tag).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ExposureLimitExceeded { .. } => "exposure_limit_exceeded",
+            Self::CategoryLimitExceeded { .. } => "category_limit_exceeded",
+            Self::MaxPositionsReached { .. } => "max_positions_reached",
+            Self::MaxBetsPerCycleReached { .. } => "max_bets_per_cycle_reached",
+            Self::EventGroupLimitReached { .. } => "event_group_limit_reached",
+            Self::DrawdownHalt { .. } => "drawdown_halt",
+            Self::DailyLossHalt { .. } => "daily_loss_halt",
+            Self::InsideUnwindWindow { .. } => "inside_unwind_window",
+            Self::CategoryCoolDown { .. } => "category_cool_down",
+            Self::LinkedOpposition { .. } => "linked_opposition",
+            Self::LinkedExposureExceeded { .. } => "linked_exposure_exceeded",
+            Self::TagLimitExceeded { .. } => "tag_limit_exceeded",
+            Self::TagOpenBetsReached { .. } => "tag_open_bets_reached",
+            Self::CorrelatedExposure { .. } => "correlated_exposure",
+            Self::AlreadyHolding { .. } => "already_holding",
+            Self::InsufficientBalance { .. } => "insufficient_balance",
+        }
+    }
+}
+
+/// An approved bet: its drawdown-adjusted amount and the risk posture it
+/// was approved in.
+#[derive(Debug, Clone)]
+pub struct Approval {
+    pub amount: Decimal,
+    pub context: RiskContext,
+}
+
+/// Platform-reported exposure beyond the agent's open bets.
+#[derive(Debug, Default)]
+struct Untracked {
+    total: Decimal,
+    by_category: HashMap<MarketCategory, Decimal>,
+    /// Markets held on a platform with no open bet of ours.
+    markets: usize,
+}
+
+#[derive(Clone)]
 pub struct RiskManager {
     config: RiskConfig,
     /// Currently tracked exposure per category (updated as bets are approved).
     category_exposure: HashMap<MarketCategory, Decimal>,
+    /// Exposure and open bets per operator tag, on the same basis.
+    tag_exposure: HashMap<String, TagExposure>,
     /// Total current exposure.
     total_exposure: Decimal,
     /// Number of open positions.
     position_count: usize,
     /// Bets approved this cycle.
     cycle_bets: usize,
+    /// Bets approved this cycle per event group.
+    cycle_event_groups: HashMap<String, usize>,
+    /// Positions approved this cycle, keyed for link checks.
+    cycle_positions: Vec<(String, Side, Decimal)>,
+    /// Positions approved this cycle with a correlation group, as
+    /// `(group, link key, amount)`.
+    cycle_groups: Vec<(String, String, Decimal)>,
+    /// Stake approved this cycle per platform.
+    cycle_staked: HashMap<String, Decimal>,
+    /// Stake the platforms report per `(link key, side)`, with the
+    /// market's category.
+    platform_positions: HashMap<(String, Side), (Decimal, MarketCategory)>,
 }
 
 impl RiskManager {
@@ -94,15 +311,50 @@ impl RiskManager {
         Self {
             config,
             category_exposure: HashMap::new(),
+            tag_exposure: HashMap::new(),
             total_exposure: Decimal::ZERO,
             position_count: 0,
             cycle_bets: 0,
+            cycle_event_groups: HashMap::new(),
+            cycle_positions: Vec::new(),
+            cycle_groups: Vec::new(),
+            cycle_staked: HashMap::new(),
+            platform_positions: HashMap::new(),
         }
     }
 
+    /// Replace the limits, keeping the tracked exposure.
+    pub fn set_config(&mut self, config: RiskConfig) {
+        self.config = config;
+    }
+
+    /// Whether the AUD drawdown from peak has reached the halt tier.
+    pub fn drawdown_halted(&self, state: &AgentState) -> bool {
+        DrawdownTier::halt_pct(&self.config.drawdown_tiers).is_some_and(|halt| self.drawdown_from_peak(state) >= halt)
+    }
+
+    /// Whether the AUD drawdown from peak is back below the resume threshold.
+    pub fn drawdown_recovered(&self, state: &AgentState) -> bool {
+        self.drawdown_from_peak(state) < self.config.drawdown_resume_pct
+    }
+
+    /// Declared market links.
+    pub fn links(&self) -> &MarketLinks {
+        &self.config.links
+    }
+
+    /// Keyword clusters that assign correlation groups.
+    pub fn correlation_groups(&self) -> &CorrelationGroups {
+        &self.config.correlation_groups
+    }
+
     /// Reset cycle counter (call at start of each scan cycle).
     pub fn reset_cycle(&mut self) {
         self.cycle_bets = 0;
+        self.cycle_event_groups.clear();
+        self.cycle_positions.clear();
+        self.cycle_groups.clear();
+        self.cycle_staked.clear();
     }
 
     /// Update current exposure state from agent state.
@@ -118,39 +370,141 @@ impl RiskManager {
         self.position_count = position_count;
     }
 
+    /// Replace the per-tag exposure, alongside [`Self::update_exposure`].
+    pub fn update_tag_exposure(&mut self, tag_exposure: HashMap<String, TagExposure>) {
+        self.tag_exposure = tag_exposure;
+    }
+
+    /// Replace the positions the platforms report holding. Call once per
+    /// cycle; whatever they hold beyond the open bets on the same market
+    /// and side counts towards the exposure caps and position count.
+    /// Positions without a category count as `Other`.
+    pub fn set_open_positions(&mut self, positions: &[Position]) {
+        self.platform_positions.clear();
+        for p in positions {
+            let held = self
+                .platform_positions
+                .entry((link_key(&p.platform, &p.market_id), p.side))
+                .or_insert((Decimal::ZERO, p.category.unwrap_or(MarketCategory::Other)));
+            held.0 += p.cost;
+        }
+    }
+
+    /// Platform stake the open bets don't account for.
+    fn untracked(&self, state: &AgentState) -> Untracked {
+        let mut untracked = Untracked::default();
+        let mut new_markets = std::collections::HashSet::new();
+        for ((key, side), (stake, category)) in &self.platform_positions {
+            let mut ours = Decimal::ZERO;
+            let mut held = false;
+            for bet in state.open_bets.iter().filter(|b| link_key(&b.platform, &b.market_id) == *key) {
+                held = true;
+                if bet.side == *side {
+                    ours += bet.amount;
+                }
+            }
+            if !held {
+                new_markets.insert(key);
+            }
+            let extra = *stake - ours;
+            if extra > Decimal::ZERO {
+                untracked.total += extra;
+                *untracked.by_category.entry(*category).or_insert(Decimal::ZERO) += extra;
+            }
+        }
+        untracked.markets = new_markets.len();
+        untracked
+    }
+
     /// Check if a sized bet passes all risk checks.
     ///
-    /// Returns Ok(drawdown-adjusted bet amount) or Err(reason).
+    /// Returns Ok(drawdown-adjusted amount plus a [`RiskContext`] snapshot)
+    /// or Err(reason).
     ///
-    /// `bankroll_override` replaces `state.bankroll` for the exposure cap
-    /// calculations only (checks 4 and 5). Pass `Some(mana_bankroll)` for
-    /// Manifold bets so exposure is evaluated against Mana, not AUD.
-    /// The drawdown check always uses `state.bankroll` (real money health).
+    /// Exposure caps (checks 6–10) are evaluated against the balance on the
+    /// bet's platform ([`AgentState::bankroll_for`]) unless
+    /// `bankroll_override` is given. The drawdown and daily loss checks
+    /// always use the AUD books (real money health).
     pub fn approve(
         &self,
         bet: &SizedBet,
         state: &AgentState,
         bankroll_override: Option<Decimal>,
-    ) -> Result<Decimal, RejectionReason> {
-        let exposure_bankroll = bankroll_override.unwrap_or(state.bankroll);
+    ) -> Result<Approval, RejectionReason> {
+        self.approve_at(bet, state, bankroll_override, Utc::now())
+    }
+
+    /// [`Self::approve`] as of `now`, for replaying recorded cycles.
+    pub fn approve_at(
+        &self,
+        bet: &SizedBet,
+        state: &AgentState,
+        bankroll_override: Option<Decimal>,
+        now: DateTime<Utc>,
+    ) -> Result<Approval, RejectionReason> {
+        let exposure_bankroll =
+            bankroll_override.unwrap_or_else(|| state.bankroll_for(&bet.edge.market.platform));
 
-        // 1. Drawdown check (always against real AUD bankroll)
+        // 1. Drawdown and daily loss halts (always against real AUD bankroll)
         let drawdown = self.drawdown_from_peak(state);
-        if drawdown >= self.config.drawdown_halt_pct {
+        let multiplier = self.drawdown_multiplier(drawdown);
+        if multiplier <= Decimal::ZERO {
             return Err(RejectionReason::DrawdownHalt {
                 drawdown_pct: drawdown * dec!(100),
+                limit: DrawdownTier::halt_pct(&self.config.drawdown_tiers).unwrap_or_default() * dec!(100),
+            });
+        }
+
+        if let Some(limit) = self.config.daily_loss_halt_pct {
+            let loss = daily::daily_loss_pct(state, now);
+            if loss >= limit {
+                return Err(RejectionReason::DailyLossHalt {
+                    loss_pct: loss * dec!(100),
+                    limit: limit * dec!(100),
+                });
+            }
+        }
+
+        // 1b. The platform's cash, where reported, must cover the scaled
+        // stake after this cycle's earlier approvals
+        let platform = &bet.edge.market.platform;
+        if let Some(&balance) = state.balances.get(platform) {
+            let stake = bet.bet_amount * multiplier;
+            let available = balance - self.cycle_staked.get(platform).copied().unwrap_or(Decimal::ZERO);
+            if stake > available {
+                return Err(RejectionReason::InsufficientBalance {
+                    platform: platform.clone(),
+                    stake,
+                    available: available.max(Decimal::ZERO),
+                });
+            }
+        }
+
+        // 2. Unwind window — liquidity is about to go away on this market
+        let market = &bet.edge.market;
+        if UnwindWindow::contains(
+            &self.config.unwind_windows,
+            &market.platform,
+            Some(market.category),
+            market.deadline,
+            now,
+        ) {
+            return Err(RejectionReason::InsideUnwindWindow {
+                minutes_to_deadline: (market.deadline - now).num_minutes(),
             });
         }
 
-        // 2. Max positions
-        if self.position_count >= self.config.max_positions {
+        // 3. Max positions, including markets held only on the platforms
+        let untracked = self.untracked(state);
+        let positions = self.position_count + untracked.markets;
+        if positions >= self.config.max_positions {
             return Err(RejectionReason::MaxPositionsReached {
-                current: self.position_count,
+                current: positions,
                 limit: self.config.max_positions,
             });
         }
 
-        // 3. Max bets per cycle
+        // 4. Max bets per cycle
         if self.cycle_bets >= self.config.max_bets_per_cycle {
             return Err(RejectionReason::MaxBetsPerCycleReached {
                 current: self.cycle_bets,
@@ -158,8 +512,30 @@ impl RiskManager {
             });
         }
 
-        // 4. Total exposure check (uses exposure_bankroll for correct currency)
-        let new_total = self.total_exposure + bet.bet_amount;
+        // 5. One correlated cluster per event — bets are approved in rank
+        // order, so the best-ranked market on the event keeps the slot
+        for group in event_keys(bet) {
+            let placed = self.cycle_event_groups.get(&group).copied().unwrap_or(0);
+            if placed >= self.config.max_bets_per_event_group {
+                return Err(RejectionReason::EventGroupLimitReached {
+                    group,
+                    limit: self.config.max_bets_per_event_group,
+                });
+            }
+        }
+
+        // 6. No piling onto a side of a market already held
+        self.check_holding(bet, state, exposure_bankroll)?;
+
+        // 7. Declared links — no logically opposing positions, and one
+        // exposure cap across each linked set
+        self.check_links(bet, state, exposure_bankroll)?;
+
+        // 8. Correlation group cap — near-duplicate questions share one budget
+        self.check_correlation(bet, state, exposure_bankroll)?;
+
+        // 9. Total exposure check (uses exposure_bankroll for correct currency)
+        let new_total = self.total_exposure + untracked.total + bet.bet_amount;
         let max_exposure = exposure_bankroll * self.config.max_exposure_pct;
         if new_total > max_exposure {
             return Err(RejectionReason::ExposureLimitExceeded {
@@ -168,9 +544,10 @@ impl RiskManager {
             });
         }
 
-        // 5. Category exposure check (uses exposure_bankroll for correct currency)
+        // 10. Category exposure check (uses exposure_bankroll for correct currency)
         let category = &bet.edge.market.category;
-        let current_cat = self.category_exposure.get(category).copied().unwrap_or(Decimal::ZERO);
+        let current_cat = self.category_exposure.get(category).copied().unwrap_or(Decimal::ZERO)
+            + untracked.by_category.get(category).copied().unwrap_or(Decimal::ZERO);
         let new_cat = current_cat + bet.bet_amount;
         let max_cat = exposure_bankroll * self.config.max_category_exposure_pct;
         if new_cat > max_cat {
@@ -181,10 +558,237 @@ impl RiskManager {
             });
         }
 
-        // 6. Drawdown-adjusted sizing
-        let adjusted_amount = self.drawdown_adjust(bet.bet_amount, drawdown);
+        // 11. Tag caps — the bet counts against every tag it carries
+        self.check_tags(bet, exposure_bankroll)?;
+
+        // 12. Drawdown-tiered sizing
+        let adjusted_amount = bet.bet_amount * multiplier;
+
+        Ok(Approval {
+            amount: adjusted_amount,
+            context: self.snapshot(state, drawdown, now),
+        })
+    }
+
+    /// Approve both legs of an arbitrage pair as of `now`, recording them
+    /// on success. Each leg passes every check of [`Self::approve_at`],
+    /// the second with the first already counted; a rejection of either
+    /// cancels the pair and leaves the counters untouched. Drawdown scaling
+    /// shrinks both legs by the same factor so they stay balanced.
+    pub fn approve_pair_at(
+        &mut self,
+        legs: [&SizedBet; 2],
+        state: &AgentState,
+        now: DateTime<Utc>,
+    ) -> Result<[Approval; 2], RejectionReason> {
+        let mut trial = self.clone();
+        let first = trial.approve_at(legs[0], state, None, now)?;
+        trial.record_approval(legs[0], first.amount);
+        let second = trial.approve_at(legs[1], state, None, now)?;
+
+        let factor = (first.amount / legs[0].bet_amount).min(second.amount / legs[1].bet_amount);
+        let scale = |leg: &SizedBet, approval: Approval| Approval { amount: leg.bet_amount * factor, ..approval };
+        let approvals = [scale(legs[0], first), scale(legs[1], second)];
+        for (leg, approval) in legs.iter().zip(&approvals) {
+            self.record_approval(leg, approval.amount);
+        }
+        Ok(approvals)
+    }
+
+    /// Check a bet against what is already held on the same side of its
+    /// market: the larger of our open bets and the platform's reported
+    /// position, plus bets approved earlier this cycle. Listings of the
+    /// same question on other platforms (see [`exposure_unit`]) count too,
+    /// each as a share of its own platform's bankroll. The first bet on a
+    /// market is left to the other caps.
+    fn check_holding(&self, bet: &SizedBet, state: &AgentState, exposure_bankroll: Decimal) -> Result<(), RejectionReason> {
+        let market = &bet.edge.market;
+        let key = link_key(&market.platform, &market.id);
+        let side = bet.edge.side;
+        let mut held_share = Decimal::ZERO;
+        for (i, (platform, unit_key)) in exposure_unit(market).iter().enumerate() {
+            let held = self.held_on(unit_key, side, state);
+            let bankroll = if i == 0 { exposure_bankroll } else { state.bankroll_for(platform) };
+            if held > Decimal::ZERO && bankroll > Decimal::ZERO {
+                held_share += held / bankroll;
+            }
+        }
+        if held_share <= Decimal::ZERO || exposure_bankroll <= Decimal::ZERO {
+            return Ok(());
+        }
+        let new_share = held_share + bet.bet_amount / exposure_bankroll;
+        if new_share > self.config.max_holding_pct {
+            return Err(RejectionReason::AlreadyHolding {
+                market: key,
+                side,
+                current: new_share * dec!(100),
+                limit: self.config.max_holding_pct * dec!(100),
+            });
+        }
+        Ok(())
+    }
+
+    /// Stake held on `side` of the market keyed `key`: the larger of our
+    /// open bets and the platform's reported position, plus bets approved
+    /// earlier this cycle.
+    fn held_on(&self, key: &str, side: Side, state: &AgentState) -> Decimal {
+        let ours: Decimal = state
+            .open_bets
+            .iter()
+            .filter(|b| b.side == side && link_key(&b.platform, &b.market_id) == key)
+            .map(|b| b.amount)
+            .sum();
+        let reported = self.platform_positions.get(&(key.to_string(), side)).map_or(Decimal::ZERO, |(stake, _)| *stake);
+        let this_cycle: Decimal =
+            self.cycle_positions.iter().filter(|(k, s, _)| k == key && *s == side).map(|(_, _, a)| *a).sum();
+        ours.max(reported) + this_cycle
+    }
+
+    /// Check a bet against the declared links of its market. Held positions
+    /// are the open bets plus bets approved earlier this cycle.
+    fn check_links(&self, bet: &SizedBet, state: &AgentState, exposure_bankroll: Decimal) -> Result<(), RejectionReason> {
+        let links = &self.config.links;
+        let market = &bet.edge.market;
+        let key = link_key(&market.platform, &market.id);
+        if links.sets_for(&key).next().is_none() {
+            return Ok(());
+        }
+        let held: Vec<(String, Side, Decimal)> = state
+            .open_bets
+            .iter()
+            .map(|b| (link_key(&b.platform, &b.market_id), b.side, b.amount))
+            .chain(self.cycle_positions.iter().cloned())
+            .collect();
+
+        if self.config.link_rules.block_opposing {
+            let positions = held.iter().map(|(k, side, _)| (k.as_str(), *side));
+            if let Some((set, other)) = links.opposing(&key, bet.edge.side, positions) {
+                return Err(RejectionReason::LinkedOpposition { set: set.name.clone(), other: other.to_string() });
+            }
+        }
 
-        Ok(adjusted_amount)
+        let limit = exposure_bankroll * self.config.link_rules.max_set_exposure_pct;
+        for set in links.sets_for(&key) {
+            let in_set: Decimal = held.iter().filter(|(k, _, _)| set.members.contains(k)).map(|(_, _, a)| *a).sum();
+            let new_total = in_set + bet.bet_amount;
+            if new_total > limit {
+                return Err(RejectionReason::LinkedExposureExceeded {
+                    set: set.name.clone(),
+                    current: (new_total / exposure_bankroll) * dec!(100),
+                    limit: self.config.link_rules.max_set_exposure_pct * dec!(100),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a bet against the cap on its correlation group. Held positions
+    /// are the open bets placed in the group plus bets approved earlier this
+    /// cycle; the first bet in a group is left to the other caps.
+    fn check_correlation(&self, bet: &SizedBet, state: &AgentState, exposure_bankroll: Decimal) -> Result<(), RejectionReason> {
+        let Some(group) = &bet.correlation_key else {
+            return Ok(());
+        };
+        let held: Vec<(String, Decimal)> = state
+            .open_bets
+            .iter()
+            .filter(|b| b.correlation_key.as_ref() == Some(group))
+            .map(|b| (link_key(&b.platform, &b.market_id), b.amount))
+            .chain(self.cycle_groups.iter().filter(|(g, _, _)| g == group).map(|(_, k, a)| (k.clone(), *a)))
+            .collect();
+        let Some((with, _)) = held.iter().max_by_key(|(_, a)| *a) else {
+            return Ok(());
+        };
+        let new_total = held.iter().map(|(_, a)| *a).sum::<Decimal>() + bet.bet_amount;
+        if new_total > exposure_bankroll * self.config.max_exposure_per_group_pct {
+            return Err(RejectionReason::CorrelatedExposure {
+                group: group.clone(),
+                with: with.clone(),
+                current: (new_total / exposure_bankroll) * dec!(100),
+                limit: self.config.max_exposure_per_group_pct * dec!(100),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check a bet against the caps of each tag on its market.
+    fn check_tags(&self, bet: &SizedBet, exposure_bankroll: Decimal) -> Result<(), RejectionReason> {
+        for tag in &bet.edge.market.tags {
+            let Some(limit) = self.config.tag_limits.get(tag) else { continue };
+            let held = self.tag_exposure.get(tag).copied().unwrap_or_default();
+            if let Some(max) = limit.max_open_bets {
+                if held.open_bets >= max {
+                    return Err(RejectionReason::TagOpenBetsReached { tag: tag.clone(), current: held.open_bets, limit: max });
+                }
+            }
+            if let Some(pct) = limit.max_exposure_pct {
+                let new_total = held.exposure + bet.bet_amount;
+                if new_total > exposure_bankroll * pct {
+                    return Err(RejectionReason::TagLimitExceeded {
+                        tag: tag.clone(),
+                        current: (new_total / exposure_bankroll) * dec!(100),
+                        limit: pct * dec!(100),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Capture the risk posture a bet is being approved in. Exposure and
+    /// bet counts include bets approved earlier in this cycle.
+    fn snapshot(&self, state: &AgentState, drawdown: Decimal, now: DateTime<Utc>) -> RiskContext {
+        let today = now.date_naive();
+        let placed_today = state.open_bets.iter().filter(|b| b.timestamp.date_naive() == today).count();
+        let mut cool_downs: Vec<MarketCategory> = state
+            .cool_downs
+            .categories
+            .iter()
+            .filter(|(_, s)| s.cooling_down())
+            .map(|(&c, _)| c)
+            .collect();
+        cool_downs.sort_by_key(|c| c.to_string());
+        RiskContext {
+            version: RiskContext::VERSION,
+            bankroll: state.bankroll,
+            drawdown_pct: drawdown,
+            exposure: self.total_exposure,
+            category_exposure: self.category_exposure.clone(),
+            bets_today: placed_today + self.cycle_bets,
+            drawdown_factor: self.drawdown_multiplier(drawdown),
+            cool_downs,
+        }
+    }
+
+    /// Check a bet against its category's losing-streak cool-down.
+    ///
+    /// `threshold` is the category's edge threshold currently in effect; in
+    /// multiplier mode the bet's edge must reach `edge_multiplier` times it.
+    pub fn check_cool_down(
+        &self,
+        bet: &SizedBet,
+        state: &AgentState,
+        threshold: Decimal,
+    ) -> Result<(), RejectionReason> {
+        let category = bet.edge.market.category;
+        let Some(streak) = state.cool_downs.active(category) else {
+            return Ok(());
+        };
+        let required_edge = match self.config.cool_down.mode {
+            CoolDownMode::Block => None,
+            CoolDownMode::Multiplier => {
+                let required = threshold * self.config.cool_down.edge_multiplier;
+                if bet.edge.edge >= required {
+                    return Ok(());
+                }
+                Some(required)
+            }
+        };
+        Err(RejectionReason::CategoryCoolDown {
+            category,
+            losses: streak.consecutive_losses,
+            required_edge,
+        })
     }
 
     /// Record that a bet was approved (updates internal counters).
@@ -192,12 +796,30 @@ impl RiskManager {
         self.total_exposure += amount;
         let cat = &bet.edge.market.category;
         *self.category_exposure.entry(cat.clone()).or_insert(Decimal::ZERO) += amount;
+        for tag in &bet.edge.market.tags {
+            let held = self.tag_exposure.entry(tag.clone()).or_default();
+            held.exposure += amount;
+            held.open_bets += 1;
+        }
         self.position_count += 1;
         self.cycle_bets += 1;
+        *self.cycle_staked.entry(bet.edge.market.platform.clone()).or_insert(Decimal::ZERO) += amount;
+        for group in event_keys(bet) {
+            *self.cycle_event_groups.entry(group).or_insert(0) += 1;
+        }
+        let market = &bet.edge.market;
+        self.cycle_positions.push((link_key(&market.platform, &market.id), bet.edge.side, amount));
+        if let Some(group) = &bet.correlation_key {
+            self.cycle_groups.push((group.clone(), link_key(&market.platform, &market.id), amount));
+        }
     }
 
-    /// Compute drawdown from peak as a fraction (0.0 = at peak, 0.5 = 50% below).
+    /// Compute drawdown from peak as a fraction (0.0 = at peak, 0.5 = 50% below),
+    /// on the configured basis.
     fn drawdown_from_peak(&self, state: &AgentState) -> Decimal {
+        if self.config.drawdown_basis == DrawdownBasis::Equity {
+            return state.equity_drawdown();
+        }
         if state.peak_bankroll <= Decimal::ZERO {
             return Decimal::ZERO;
         }
@@ -205,26 +827,10 @@ impl RiskManager {
         dd.max(Decimal::ZERO)
     }
 
-    /// Reduce bet size proportionally to drawdown severity.
-    ///
-    /// At no drawdown -> full bet. At drawdown_warning -> 50% bet.
-    /// Linear interpolation between.
-    fn drawdown_adjust(&self, amount: Decimal, drawdown: Decimal) -> Decimal {
-        if drawdown <= Decimal::ZERO {
-            return amount;
-        }
-
-        let warning = self.config.drawdown_warning_pct;
-        if drawdown >= warning {
-            // Scale from 50% at warning to 10% at halt
-            let halt = self.config.drawdown_halt_pct;
-            let ratio = ((halt - drawdown) / (halt - warning)).max(Decimal::ZERO).min(Decimal::ONE);
-            amount * (dec!(0.1) + dec!(0.4) * ratio)
-        } else {
-            // Scale from 100% at 0 to 50% at warning
-            let ratio = Decimal::ONE - (drawdown / warning) * dec!(0.5);
-            amount * ratio
-        }
+    /// Stake multiplier of the drawdown tier `drawdown` falls in (0 in the
+    /// halt tier).
+    fn drawdown_multiplier(&self, drawdown: Decimal) -> Decimal {
+        DrawdownTier::multiplier_at(&self.config.drawdown_tiers, drawdown)
     }
 }
 
@@ -254,14 +860,31 @@ mod tests {
             total_ib_commissions: dec!(0.5),
             start_time: Utc::now() - Duration::days(7),
             peak_bankroll: peak,
+            cash_bankroll: bankroll,
+            total_equity: bankroll,
+            peak_equity: peak,
+            unrealized_pnl: Decimal::ZERO,
             status: AgentStatus::Alive,
+            pause_reason: None,
             survival_threshold: Decimal::ZERO,
             mana_bankroll: Decimal::ZERO,
             total_mana_pnl: Decimal::ZERO,
             mana_trades_won: 0,
             mana_trades_lost: 0,
+            balances: Default::default(),
             open_bets: Vec::new(),
+            unconfirmed_bets: Vec::new(),
             last_cycle_time: None,
+            edge_realizations: Vec::new(),
+            thresholds: Default::default(),
+            external_activity: Default::default(),
+            effective_config: Default::default(),
+            dirty_shutdown: None,
+            cool_downs: Default::default(),
+            tag_results: Vec::new(),
+            daily: Default::default(),
+            references: Default::default(),
+            hibernation: Default::default(),
         }
     }
 
@@ -282,6 +905,9 @@ mod tests {
                     resolution_criteria: String::new(),
                     url: String::new(),
                     cross_refs: Default::default(),
+                    event_group: None,
+                    facts: None,
+                    tags: Vec::new(),
                 },
                 estimate: Estimate {
                     probability: dec!(0.65),
@@ -289,15 +915,27 @@ mod tests {
                     reasoning: String::new(),
                     tokens_used: 100,
                     cost: dec!(0.01),
+                    critique: None,
+                    served_by: None,
+                    tier: None,
                 },
                 side: Side::Yes,
                 edge: dec!(0.15),
                 signed_edge: dec!(0.15),
+                raw_edge: dec!(0.15),
+                effective_threshold: dec!(0.06),
             },
             kelly_fraction: dec!(0.10),
             bet_fraction: dec!(0.05),
             bet_amount: amount,
             expected_value: amount * dec!(0.15),
+            net_expected_value: amount * dec!(0.15),
+            days_to_resolution: 30.0,
+            discounted_edge: dec!(0.15),
+            risk_context: None,
+            venue: None,
+            correlation_key: None,
+            idempotency_key: None,
         }
     }
 
@@ -308,7 +946,7 @@ mod tests {
         let bet = make_sized_bet(MarketCategory::Weather, dec!(50));
         let result = rm.approve(&bet, &state, None);
         assert!(result.is_ok());
-        assert!(result.unwrap() > Decimal::ZERO);
+        assert!(result.unwrap().amount > Decimal::ZERO);
     }
 
     #[test]
@@ -318,8 +956,10 @@ mod tests {
         let state = make_agent_state(dec!(1000), dec!(1000));
         let bet = make_sized_bet(MarketCategory::Weather, dec!(60)); // Would push to 61%
         let result = rm.approve(&bet, &state, None);
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), RejectionReason::ExposureLimitExceeded { .. }));
+        assert!(matches!(
+            result.unwrap_err(),
+            RejectionReason::ExposureLimitExceeded { current, limit } if current == dec!(61) && limit == dec!(60)
+        ));
     }
 
     #[test]
@@ -329,8 +969,11 @@ mod tests {
         let state = make_agent_state(dec!(1000), dec!(1000));
         let bet = make_sized_bet(MarketCategory::Weather, dec!(20)); // Would push to 26%
         let result = rm.approve(&bet, &state, None);
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), RejectionReason::CategoryLimitExceeded { .. }));
+        assert!(matches!(
+            result.unwrap_err(),
+            RejectionReason::CategoryLimitExceeded { category: MarketCategory::Weather, current, limit }
+                if current == dec!(26) && limit == dec!(25)
+        ));
     }
 
     #[test]
@@ -351,8 +994,19 @@ mod tests {
         let state = make_agent_state(dec!(1000), dec!(1000));
         let bet = make_sized_bet(MarketCategory::Weather, dec!(50));
         let result = rm.approve(&bet, &state, None);
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), RejectionReason::MaxBetsPerCycleReached { .. }));
+        assert!(matches!(result.unwrap_err(), RejectionReason::MaxBetsPerCycleReached { current: 5, limit: 5 }));
+    }
+
+    #[test]
+    fn test_drawdown_halt_and_recovery_thresholds() {
+        let rm = RiskManager::new(RiskConfig::default());
+        let halted = make_agent_state(dec!(600), dec!(1000)); // 40% drawdown
+        assert!(rm.drawdown_halted(&halted));
+        assert!(!rm.drawdown_recovered(&halted));
+        let between = make_agent_state(dec!(650), dec!(1000)); // 35%
+        assert!(!rm.drawdown_halted(&between));
+        assert!(!rm.drawdown_recovered(&between));
+        assert!(rm.drawdown_recovered(&make_agent_state(dec!(750), dec!(1000))));
     }
 
     #[test]
@@ -361,25 +1015,452 @@ mod tests {
         let state = make_agent_state(dec!(550), dec!(1000)); // 45% drawdown, above 40% halt
         let bet = make_sized_bet(MarketCategory::Weather, dec!(10));
         let result = rm.approve(&bet, &state, None);
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), RejectionReason::DrawdownHalt { .. }));
+        assert!(matches!(
+            result.unwrap_err(),
+            RejectionReason::DrawdownHalt { drawdown_pct, limit } if drawdown_pct == dec!(45) && limit == dec!(40)
+        ));
+    }
+
+    #[test]
+    fn test_reject_daily_loss_halt() {
+        let rm = RiskManager::new(RiskConfig { daily_loss_halt_pct: Some(dec!(0.05)), ..RiskConfig::default() });
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        let bet = make_sized_bet(MarketCategory::Weather, dec!(10));
+        // No snapshot yet: nothing to measure today against.
+        state.total_api_costs += dec!(60);
+        assert!(rm.approve(&bet, &state, None).is_ok());
+
+        daily::close_day(&mut state, Utc::now());
+        state.total_api_costs += dec!(40);
+        assert!(rm.approve(&bet, &state, None).is_ok());
+        state.total_api_costs += dec!(20);
+        let err = rm.approve(&bet, &state, None).unwrap_err();
+        assert!(matches!(err, RejectionReason::DailyLossHalt { loss_pct, .. } if loss_pct == dec!(6)));
+    }
+
+    #[test]
+    fn test_reject_inside_unwind_window() {
+        let rm = RiskManager::new(RiskConfig {
+            unwind_windows: vec![UnwindWindow {
+                platform: "betfair".into(),
+                category: Some(MarketCategory::Sports),
+                minutes_before: 10,
+            }],
+            ..RiskConfig::default()
+        });
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        let mut bet = make_sized_bet(MarketCategory::Sports, dec!(10));
+        bet.edge.market.platform = "betfair".into();
+        bet.edge.market.deadline = Utc::now() + Duration::minutes(5);
+        let result = rm.approve(&bet, &state, None);
+        assert!(matches!(result.unwrap_err(), RejectionReason::InsideUnwindWindow { .. }));
+
+        // Outside the window, and on platforms without one, bets pass.
+        bet.edge.market.deadline = Utc::now() + Duration::minutes(30);
+        assert!(rm.approve(&bet, &state, None).is_ok());
+        bet.edge.market.deadline = Utc::now() + Duration::minutes(5);
+        bet.edge.market.platform = "manifold".into();
+        assert!(rm.approve(&bet, &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_reject_second_bet_on_same_event_group() {
+        let mut rm = RiskManager::new(RiskConfig::default());
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        let mut bet = make_sized_bet(MarketCategory::Sports, dec!(10));
+        bet.edge.market.event_group = Some("betfair:32001".into());
+        assert!(rm.approve(&bet, &state, None).is_ok());
+        rm.record_approval(&bet, dec!(10));
+
+        let result = rm.approve(&bet, &state, None);
+        assert!(matches!(result, Err(RejectionReason::EventGroupLimitReached { limit: 1, .. })));
+
+        // A different event, or no event at all, is unaffected.
+        bet.edge.market.event_group = Some("betfair:32002".into());
+        assert!(rm.approve(&bet, &state, None).is_ok());
+        bet.edge.market.event_group = None;
+        assert!(rm.approve(&bet, &state, None).is_ok());
+
+        // The cap is per cycle.
+        rm.reset_cycle();
+        bet.edge.market.event_group = Some("betfair:32001".into());
+        assert!(rm.approve(&bet, &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_tag_caps_count_a_bet_against_every_tag() {
+        let tag_limits = HashMap::from([
+            ("f1".to_string(), TagLimit { max_exposure_pct: Some(dec!(0.05)), max_open_bets: None }),
+            ("motorsport".to_string(), TagLimit { max_exposure_pct: None, max_open_bets: Some(2) }),
+        ]);
+        let mut rm = RiskManager::new(RiskConfig { tag_limits, ..RiskConfig::default() });
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        let tagged = |tags: &[&str], amount: Decimal| {
+            let mut bet = make_sized_bet(MarketCategory::Sports, amount);
+            bet.edge.market.id = tags.join("+");
+            bet.edge.market.tags = tags.iter().map(|t| t.to_string()).collect();
+            bet
+        };
+
+        // One bet carrying both tags counts against both.
+        let both = tagged(&["f1", "motorsport"], dec!(30));
+        assert!(rm.approve(&both, &state, None).is_ok());
+        rm.record_approval(&both, dec!(30));
+        assert_eq!(rm.tag_exposure["f1"], TagExposure { exposure: dec!(30), open_bets: 1 });
+        assert_eq!(rm.tag_exposure["motorsport"], TagExposure { exposure: dec!(30), open_bets: 1 });
+
+        // f1 exposure: 30 + 25 > 5% of 1000.
+        let result = rm.approve(&tagged(&["f1"], dec!(25)), &state, None);
+        assert!(matches!(&result, Err(RejectionReason::TagLimitExceeded { tag, .. }) if tag == "f1"));
+        assert!(rm.approve(&tagged(&["f1"], dec!(20)), &state, None).is_ok());
+
+        // motorsport's open-bet cap is reached through bets on either tag set.
+        let other = tagged(&["motorsport"], dec!(5));
+        rm.record_approval(&other, dec!(5));
+        let result = rm.approve(&tagged(&["motorsport", "untracked"], dec!(1)), &state, None);
+        assert!(matches!(&result, Err(RejectionReason::TagOpenBetsReached { current: 2, limit: 2, .. })));
+
+        // Untagged bets and tags without caps are unaffected.
+        assert!(rm.approve(&tagged(&[], dec!(50)), &state, None).is_ok());
+        assert!(rm.approve(&tagged(&["untracked"], dec!(50)), &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_linked_markets_block_opposition_and_cap_exposure() {
+        let links = MarketLinks::parse(
+            r#"
+            [[implies]]
+            market = "manifold:pres"
+            implies = "manifold:primary"
+
+            [[exclusive]]
+            name = "nominee"
+            markets = ["manifold:a", "manifold:b"]
+            "#,
+        )
+        .unwrap();
+        let mut rm = RiskManager::new(RiskConfig { links, ..RiskConfig::default() });
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        let mut held = TradeReceipt::dry_run("primary", dec!(50), "Mana");
+        held.platform = "manifold".into();
+        held.side = Side::No;
+        state.open_bets.push(held);
+
+        // YES on the presidency against a held NO on the primary.
+        let mut pres = make_sized_bet(MarketCategory::Politics, dec!(20));
+        pres.edge.market.id = "pres".into();
+        let result = rm.approve(&pres, &state, None);
+        assert!(matches!(&result, Err(RejectionReason::LinkedOpposition { other, .. }) if other == "manifold:primary"));
+        // NO on the presidency is consistent, but the set's combined stake
+        // (50 held + 120) would exceed 15% of 1000.
+        pres.edge.side = Side::No;
+        pres.bet_amount = dec!(120);
+        assert!(matches!(rm.approve(&pres, &state, None), Err(RejectionReason::LinkedExposureExceeded { .. })));
+        pres.bet_amount = dec!(100);
+        assert!(rm.approve(&pres, &state, None).is_ok());
+
+        // Bets approved earlier in the cycle count as held.
+        let mut a = make_sized_bet(MarketCategory::Politics, dec!(10));
+        a.edge.market.id = "a".into();
+        assert!(rm.approve(&a, &state, None).is_ok());
+        rm.record_approval(&a, dec!(10));
+        let mut b = make_sized_bet(MarketCategory::Politics, dec!(10));
+        b.edge.market.id = "b".into();
+        assert!(matches!(rm.approve(&b, &state, None), Err(RejectionReason::LinkedOpposition { .. })));
+
+        // Opposition is allowed when the rule is off; unlinked markets are unaffected.
+        rm.config.link_rules.block_opposing = false;
+        assert!(rm.approve(&b, &state, None).is_ok());
+        rm.config.link_rules.block_opposing = true;
+        b.edge.market.id = "unlinked".into();
+        assert!(rm.approve(&b, &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_correlated_questions_share_group_cap() {
+        let groups = std::collections::BTreeMap::from([(
+            "fed".to_string(),
+            vec!["fed".into(), "fomc".into(), "fed funds".into(), "recession".into()],
+        )]);
+        let groups = CorrelationGroups::new(&groups);
+        let mut rm = RiskManager::new(RiskConfig { correlation_groups: groups.clone(), ..RiskConfig::default() });
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        let bet = |id: &str, question: &str, amount: Decimal| {
+            let mut bet = make_sized_bet(MarketCategory::Economics, amount);
+            bet.edge.market.id = id.into();
+            bet.edge.market.question = question.into();
+            bet.correlation_key = groups.key_for(question);
+            bet
+        };
+        let march = bet("fed-march", "Will the Fed cut rates in March?", dec!(60));
+        let april = bet("fed-april", "Fed funds rate below 4.5% by April?", dec!(60));
+        let jobs = bet("au-jobs", "Will Australian unemployment exceed 4.5%?", dec!(60));
+        assert_eq!(march.correlation_key.as_deref(), Some("fed"));
+        assert!(jobs.correlation_key.is_none());
+
+        assert!(rm.approve(&march, &state, None).is_ok());
+        rm.record_approval(&march, dec!(60));
+        // 60 + 60 exceeds 10% of 1000; the held March bet is named.
+        let result = rm.approve(&april, &state, None);
+        assert!(matches!(
+            &result,
+            Err(RejectionReason::CorrelatedExposure { group, with, current, limit })
+                if group == "fed" && with == "manifold:fed-march" && *current == dec!(12) && *limit == dec!(10)
+        ));
+        // Same category, different question: unaffected.
+        assert!(rm.approve(&jobs, &state, None).is_ok());
+
+        // Open bets count through the group stamped on their receipt.
+        rm.reset_cycle();
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        let mut held = TradeReceipt::dry_run("recession", dec!(80), "Mana");
+        held.platform = "manifold".into();
+        held.correlation_key = Some("fed".into());
+        state.open_bets.push(held);
+        let result = rm.approve(&bet("fed-april", "Fed funds rate below 4.5% by April?", dec!(30)), &state, None);
+        assert!(matches!(&result, Err(RejectionReason::CorrelatedExposure { with, .. }) if with == "manifold:recession"));
+        assert!(rm.approve(&bet("fed-april", "Fed funds rate below 4.5% by April?", dec!(20)), &state, None).is_ok());
+    }
+
+    fn platform_position(market_id: &str, side: Side, cost: Decimal) -> Position {
+        Position {
+            market_id: market_id.into(),
+            platform: "manifold".into(),
+            side,
+            size: cost * dec!(2),
+            entry_price: dec!(0.5),
+            current_value: cost,
+            cost,
+            category: None,
+        }
     }
 
     #[test]
-    fn test_drawdown_reduces_bet() {
+    fn test_platform_positions_count_against_category_cap() {
+        let mut rm = RiskManager::new(RiskConfig::default());
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        // $200 on a politics market the state knows nothing about (e.g. a
+        // restart on a fresh state file); its category comes from the scan.
+        let mut positions = vec![platform_position("senate", Side::Yes, dec!(200))];
+        let mut scanned = make_sized_bet(MarketCategory::Politics, dec!(0)).edge.market;
+        scanned.id = "senate".into();
+        categorise_positions(&mut positions, &[scanned], &state.open_bets);
+        assert_eq!(positions[0].category, Some(MarketCategory::Politics));
+        rm.set_open_positions(&positions);
+
+        let result = rm.approve(&make_sized_bet(MarketCategory::Politics, dec!(60)), &state, None);
+        assert!(matches!(
+            result,
+            Err(RejectionReason::CategoryLimitExceeded { category: MarketCategory::Politics, .. })
+        ));
+        assert!(rm.approve(&make_sized_bet(MarketCategory::Weather, dec!(60)), &state, None).is_ok());
+
+        // Held by an open bet too: counted once, through the synced exposure.
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        let mut receipt = TradeReceipt::dry_run("senate", dec!(200), "Mana");
+        receipt.platform = "manifold".into();
+        receipt.category = Some(MarketCategory::Politics);
+        state.open_bets.push(receipt);
+        rm.update_exposure(dec!(200), HashMap::from([(MarketCategory::Politics, dec!(200))]), 1);
+        assert!(rm.approve(&make_sized_bet(MarketCategory::Politics, dec!(40)), &state, None).is_ok());
+        assert!(rm.approve(&make_sized_bet(MarketCategory::Politics, dec!(60)), &state, None).is_err());
+    }
+
+    #[test]
+    fn test_platform_positions_count_toward_max_positions() {
+        let mut rm = RiskManager::new(RiskConfig { max_positions: 2, ..RiskConfig::default() });
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        rm.set_open_positions(&[
+            platform_position("a", Side::Yes, dec!(5)),
+            platform_position("a", Side::No, dec!(5)),
+            platform_position("b", Side::Yes, dec!(5)),
+        ]);
+        let result = rm.approve(&make_sized_bet(MarketCategory::Weather, dec!(10)), &state, None);
+        assert!(matches!(result, Err(RejectionReason::MaxPositionsReached { current: 2, limit: 2 })));
+    }
+
+    #[test]
+    fn test_reject_adding_to_held_side() {
+        let mut rm = RiskManager::new(RiskConfig::default());
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        rm.set_open_positions(&[platform_position("test", Side::Yes, dec!(40))]);
+
+        // 40 held + 30 exceeds 6% of 1000.
+        let result = rm.approve(&make_sized_bet(MarketCategory::Weather, dec!(30)), &state, None);
+        assert!(matches!(
+            &result,
+            Err(RejectionReason::AlreadyHolding { market, side: Side::Yes, current, limit })
+                if market == "manifold:test" && *current == dec!(7) && *limit == dec!(6)
+        ));
+        assert!(rm.approve(&make_sized_bet(MarketCategory::Weather, dec!(15)), &state, None).is_ok());
+        let mut other_side = make_sized_bet(MarketCategory::Weather, dec!(30));
+        other_side.edge.side = Side::No;
+        assert!(rm.approve(&other_side, &state, None).is_ok());
+
+        // Bets approved this cycle add to the holding.
+        rm.set_open_positions(&[]);
+        let bet = make_sized_bet(MarketCategory::Weather, dec!(40));
+        assert!(rm.approve(&bet, &state, None).is_ok());
+        rm.record_approval(&bet, dec!(40));
+        assert!(matches!(
+            rm.approve(&make_sized_bet(MarketCategory::Weather, dec!(30)), &state, None),
+            Err(RejectionReason::AlreadyHolding { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merged_duplicates_are_one_exposure_unit() {
+        let mut rm = RiskManager::new(RiskConfig::default());
+        let state = make_agent_state(dec!(1000), dec!(1000));
+        let listing = |platform: &str, market_id: &str| DuplicateListing {
+            platform: platform.into(),
+            market_id: market_id.into(),
+            price_yes: dec!(0.45),
+            liquidity: dec!(200),
+        };
+        let mut kept = make_sized_bet(MarketCategory::Politics, dec!(10));
+        kept.edge.market.platform = "polymarket".into();
+        kept.edge.market.cross_refs.duplicates = vec![listing("manifold", "test")];
+        let mut merged = make_sized_bet(MarketCategory::Politics, dec!(10));
+        merged.edge.market.cross_refs.duplicate_of = Some("polymarket:test".into());
+
+        // Betting the kept market uses the question's slot for the cycle.
+        assert!(rm.approve(&kept, &state, None).is_ok());
+        rm.record_approval(&kept, dec!(10));
+        assert!(matches!(
+            rm.approve(&merged, &state, None),
+            Err(RejectionReason::EventGroupLimitReached { group, .. }) if group == "polymarket:test"
+        ));
+
+        // Across cycles, a holding on either listing counts against the
+        // other: 6% held on Manifold plus 1% more on Polymarket exceeds 6%.
+        rm.reset_cycle();
+        rm.set_open_positions(&[platform_position("test", Side::Yes, dec!(60))]);
+        assert!(matches!(
+            rm.approve(&kept, &state, None),
+            Err(RejectionReason::AlreadyHolding { market, .. }) if market == "polymarket:test"
+        ));
+        let mut unrelated = kept.clone();
+        unrelated.edge.market.cross_refs.duplicates.clear();
+        assert!(rm.approve(&unrelated, &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_approval_captures_risk_context() {
+        let mut rm = RiskManager::new(RiskConfig::default());
+        rm.update_exposure(dec!(100), HashMap::from([(MarketCategory::Weather, dec!(100))]), 2);
+        let mut state = make_agent_state(dec!(900), dec!(1000)); // 10% drawdown
+        let receipt = |days_ago: i64| TradeReceipt {
+            order_id: "o".into(),
+            market_id: "m".into(),
+            platform: "manifold".into(),
+            side: Side::Yes,
+            amount: dec!(50),
+            fill_price: dec!(0.50),
+            fees: Decimal::ZERO,
+            timestamp: Utc::now() - Duration::days(days_ago),
+            currency: "AUD".into(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        };
+        state.open_bets = vec![receipt(0), receipt(2)];
+        state.cool_downs.categories.insert(
+            MarketCategory::Politics,
+            CategoryStreak { consecutive_losses: 4, cool_down_since: Some(Utc::now()), skipped: 0 },
+        );
+
+        let sports = make_sized_bet(MarketCategory::Sports, dec!(20));
+        let first = rm.approve(&sports, &state, None).unwrap();
+        assert_eq!(first.amount, dec!(15));
+        let ctx = &first.context;
+        assert_eq!(ctx.version, RiskContext::VERSION);
+        assert_eq!((ctx.bankroll, ctx.drawdown_pct, ctx.drawdown_factor), (dec!(900), dec!(0.1), dec!(0.75)));
+        assert_eq!(ctx.exposure, dec!(100));
+        assert_eq!(ctx.category_exposure, HashMap::from([(MarketCategory::Weather, dec!(100))]));
+        assert_eq!(ctx.bets_today, 1);
+        assert_eq!(ctx.cool_downs, vec![MarketCategory::Politics]);
+        rm.record_approval(&sports, first.amount);
+
+        // Later approvals in the cycle see the exposure earlier ones added.
+        let weather = make_sized_bet(MarketCategory::Weather, dec!(20));
+        let second = rm.approve(&weather, &state, None).unwrap();
+        assert_eq!(second.context.exposure, dec!(115));
+        assert_eq!(
+            second.context.category_exposure,
+            HashMap::from([(MarketCategory::Weather, dec!(100)), (MarketCategory::Sports, dec!(15))])
+        );
+        assert_eq!(second.context.bets_today, 2);
+        rm.record_approval(&weather, second.amount);
+        assert_eq!(rm.approve(&sports, &state, None).unwrap().context.exposure, dec!(130));
+
+        let json = serde_json::to_value(&second.context).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(serde_json::from_value::<RiskContext>(json).unwrap(), second.context);
+    }
+
+    #[test]
+    fn test_drawdown_tier_boundaries() {
         let rm = RiskManager::new(RiskConfig::default());
-        // No drawdown: full amount
-        let full = rm.drawdown_adjust(dec!(100), Decimal::ZERO);
-        assert_eq!(full, dec!(100));
+        let bet = make_sized_bet(MarketCategory::Weather, dec!(10));
+        // (bankroll against a 1000 peak, approved amount)
+        for (bankroll, amount) in [
+            (dec!(1000), dec!(10)),
+            (dec!(901), dec!(10)),
+            (dec!(900), dec!(7.5)),
+            (dec!(801), dec!(7.5)),
+            (dec!(800), dec!(5)),
+            (dec!(701), dec!(5)),
+            (dec!(700), dec!(2.5)),
+            (dec!(601), dec!(2.5)),
+        ] {
+            let approval = rm.approve(&bet, &make_agent_state(bankroll, dec!(1000)), None).unwrap();
+            assert_eq!(approval.amount, amount, "bankroll {bankroll}");
+            assert_eq!(approval.context.drawdown_factor, amount / dec!(10));
+        }
+        // The halt tier rejects rather than sizing to zero
+        let halted = rm.approve(&bet, &make_agent_state(dec!(600), dec!(1000)), None);
+        assert!(matches!(halted, Err(RejectionReason::DrawdownHalt { .. })));
+    }
+
+    #[test]
+    fn test_equity_basis_drawdown() {
+        // Bankroll at peak, but open bets marked 45% down
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        state.unrealized_pnl = dec!(-450);
+        state.update_equity();
+        let bet = make_sized_bet(MarketCategory::Weather, dec!(10));
+
+        let on_bankroll = RiskManager::new(RiskConfig::default());
+        assert_eq!(on_bankroll.approve(&bet, &state, None).unwrap().amount, dec!(10));
+        assert!(!on_bankroll.drawdown_halted(&state));
 
-        // At warning threshold (20%): 50% of amount
-        let at_warning = rm.drawdown_adjust(dec!(100), dec!(0.20));
-        assert!((at_warning - dec!(50)).abs() < Decimal::ONE);
+        let on_equity = RiskManager::new(RiskConfig { drawdown_basis: DrawdownBasis::Equity, ..RiskConfig::default() });
+        assert!(matches!(on_equity.approve(&bet, &state, None), Err(RejectionReason::DrawdownHalt { .. })));
+        assert!(on_equity.drawdown_halted(&state));
+        state.unrealized_pnl = dec!(-150);
+        state.update_equity();
+        assert_eq!(on_equity.approve(&bet, &state, None).unwrap().amount, dec!(7.5));
+    }
 
-        // Halfway between warning and halt: ~30%
-        let mid = rm.drawdown_adjust(dec!(100), dec!(0.30));
-        assert!(mid < at_warning);
-        assert!(mid > dec!(10));
+    #[test]
+    fn test_custom_drawdown_tiers() {
+        let tiers = vec![
+            DrawdownTier { from_pct: dec!(0), multiplier: dec!(1) },
+            DrawdownTier { from_pct: dec!(0.05), multiplier: dec!(0.6) },
+            DrawdownTier { from_pct: dec!(0.25), multiplier: dec!(0) },
+        ];
+        let rm = RiskManager::new(RiskConfig { drawdown_tiers: tiers, ..RiskConfig::default() });
+        let bet = make_sized_bet(MarketCategory::Weather, dec!(10));
+        let approval = rm.approve(&bet, &make_agent_state(dec!(940), dec!(1000)), None).unwrap();
+        assert_eq!(approval.amount, dec!(6));
+        assert!(rm.drawdown_halted(&make_agent_state(dec!(750), dec!(1000))));
+        assert!(!rm.drawdown_halted(&make_agent_state(dec!(751), dec!(1000))));
     }
 
     #[test]
@@ -426,4 +1507,71 @@ mod tests {
         assert_eq!(config.max_positions, 20);
         assert_eq!(config.max_bets_per_cycle, 5);
     }
+
+    #[test]
+    fn test_reject_stake_beyond_platform_balance() {
+        let mut rm = RiskManager::new(RiskConfig::default());
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        state.balances.insert("manifold".into(), dec!(80));
+        let first = make_sized_bet(MarketCategory::Weather, dec!(20));
+        let approval = rm.approve(&first, &state, None).unwrap();
+        rm.record_approval(&first, approval.amount);
+
+        // 60 of the 80 is left after the first bet this cycle.
+        let second = make_sized_bet(MarketCategory::Sports, dec!(70));
+        let err = rm.approve(&second, &state, None).unwrap_err();
+        assert!(matches!(
+            err,
+            RejectionReason::InsufficientBalance { ref platform, stake, available }
+                if platform == "manifold" && stake == dec!(70) && available == dec!(60)
+        ));
+        assert_eq!(err.code(), "insufficient_balance");
+
+        // A new cycle starts from the reported balance again (the stake
+        // then falls to the exposure cap); an unreported balance isn't
+        // checked.
+        rm.reset_cycle();
+        assert!(matches!(rm.approve(&second, &state, None), Err(RejectionReason::ExposureLimitExceeded { .. })));
+        state.balances.clear();
+        assert!(rm.approve(&make_sized_bet(MarketCategory::Sports, dec!(200)), &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_reject_manifold_stake_beyond_liquid_mana() {
+        use crate::engine::accountant::Accountant;
+        use crate::platforms::manifold::ManifoldUserInfo;
+
+        let mut rm = RiskManager::new(RiskConfig::default());
+        let mut state = make_agent_state(dec!(1000), dec!(1000));
+        // 250 Mana of equity, but 200 of it sits in open positions.
+        let info = ManifoldUserInfo {
+            user_id: "u1".into(),
+            liquid_balance: dec!(50),
+            investment_value: dec!(200),
+            resolved_profit: Decimal::ZERO,
+        };
+        Accountant::record_manifold_account(&mut state, &info);
+
+        let err = rm.approve(&make_sized_bet(MarketCategory::Sports, dec!(70)), &state, None).unwrap_err();
+        assert!(matches!(
+            err,
+            RejectionReason::InsufficientBalance { ref platform, stake, available }
+                if platform == "manifold" && stake == dec!(70) && available == dec!(50)
+        ));
+        assert!(rm.approve(&make_sized_bet(MarketCategory::Sports, dec!(10)), &state, None).is_ok());
+    }
+
+    #[test]
+    fn test_rejection_code_matches_serialised_tag() {
+        let reasons = [
+            RejectionReason::MaxBetsPerCycleReached { current: 5, limit: 5 },
+            RejectionReason::CategoryCoolDown { category: MarketCategory::Sports, losses: 3, required_edge: None },
+            RejectionReason::AlreadyHolding { market: "m".into(), side: Side::Yes, current: dec!(7), limit: dec!(6) },
+            RejectionReason::DrawdownHalt { drawdown_pct: dec!(45), limit: dec!(40) },
+            RejectionReason::InsufficientBalance { platform: "betfair".into(), stake: dec!(5), available: dec!(2) },
+        ];
+        for reason in reasons {
+            assert_eq!(serde_json::to_value(&reason).unwrap()["kind"], reason.code());
+        }
+    }
 }
@@ -0,0 +1,319 @@
+//! Execution venue selection.
+//!
+//! The scanner matches markets on different platforms that ask about the
+//! same event ([`same_event_clusters`](crate::engine::scanner::same_event_clusters)).
+//! When an approved-size bet's market belongs to such a cluster, every
+//! executable member is scored and the bet is routed to the best one,
+//! re-sized against that venue's price:
+//!
+//! - **price** — the executable price of the side being bought
+//!   (`current_price_yes` for YES, `current_price_no` for NO), so a wide
+//!   book costs what it really costs;
+//! - **fees** — commission on net winnings (`[strategy.venues].fees`);
+//! - **depth** — the stake is capped at `max_depth_pct` of the venue's
+//!   liquidity;
+//! - **reliability** — the expected profit is weighted by the venue's fill
+//!   reliability.
+//!
+//! A venue's score is the expected profit of the re-sized bet. Mana venues
+//! (Manifold) are never substituted for real-money venues or vice versa,
+//! since their stakes are sized against different bankrolls.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::edge::Edge;
+use super::kelly::{KellyCalculator, SizedBet};
+use super::links::link_key;
+use crate::config::VenueSelectionConfig;
+use crate::types::{Market, Side};
+
+/// One venue's score for a routed bet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueScore {
+    /// `platform:market_id` of the venue's market.
+    pub market: String,
+    /// Executable price of the bet's side.
+    pub price: Decimal,
+    /// Commission on net winnings.
+    pub fee: Decimal,
+    /// Liquidity available on the venue.
+    pub depth: Decimal,
+    pub reliability: Decimal,
+    /// Stake re-derived at this venue (zero when it would not bet).
+    pub amount: Decimal,
+    /// Reliability-weighted expected profit of `amount`.
+    pub score: Decimal,
+}
+
+/// Venues considered for a bet and the one it was routed to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueChoice {
+    /// Cluster id, used as the bet's event key in risk accounting.
+    pub cluster: String,
+    /// Market whose edge triggered the bet.
+    pub origin: String,
+    pub chosen: String,
+    pub considered: Vec<VenueScore>,
+}
+
+/// Routes bets to the best venue in their event cluster.
+pub struct VenueSelector {
+    config: VenueSelectionConfig,
+    /// Platforms the executor can place orders on.
+    executable: HashSet<String>,
+    clusters: Vec<Vec<Market>>,
+    /// Cluster index per member `platform:market_id`.
+    by_market: HashMap<String, usize>,
+}
+
+impl VenueSelector {
+    pub fn new(config: VenueSelectionConfig, executable: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            config,
+            executable: executable.into_iter().collect(),
+            clusters: Vec::new(),
+            by_market: HashMap::new(),
+        }
+    }
+
+    /// Replace the clusters (call once per cycle after the scan).
+    pub fn set_clusters(&mut self, clusters: Vec<Vec<Market>>) {
+        self.by_market = clusters
+            .iter()
+            .enumerate()
+            .flat_map(|(i, c)| c.iter().map(move |m| (link_key(&m.platform, &m.id), i)))
+            .collect();
+        self.clusters = clusters;
+    }
+
+    /// Route `bet` to the best-scoring venue in its cluster, re-sized at
+    /// that venue against `bankroll`. Bets outside any cluster are returned
+    /// unchanged; clustered bets always carry the [`VenueChoice`], even
+    /// when they stay on their own market. Re-sizing discounts for each
+    /// venue's time to resolution from `now`.
+    pub fn route(&self, bet: SizedBet, kelly: &KellyCalculator, bankroll: Decimal, now: DateTime<Utc>) -> SizedBet {
+        let origin = &bet.edge.market;
+        let origin_key = link_key(&origin.platform, &origin.id);
+        let Some(&cluster) = self.by_market.get(&origin_key).filter(|_| self.config.enabled) else {
+            return bet;
+        };
+        let members = &self.clusters[cluster];
+        let paper = origin.platform == "manifold";
+
+        // The origin first, so it keeps ties.
+        let candidates = std::iter::once(origin).chain(members.iter().filter(|m| {
+            link_key(&m.platform, &m.id) != origin_key
+                && self.executable.contains(&m.platform)
+                && (m.platform == "manifold") == paper
+        }));
+        let mut considered = Vec::new();
+        let mut best: Option<(Decimal, SizedBet)> = None;
+        for market in candidates {
+            let (score, resized) = self.score(&bet.edge, market, kelly, bankroll, now);
+            if let Some(resized) = resized {
+                if best.as_ref().is_none_or(|(s, _)| score.score > *s) {
+                    best = Some((score.score, resized));
+                }
+            }
+            considered.push(score);
+        }
+
+        let mut routed = best.map(|(_, b)| b).unwrap_or(bet);
+        routed.venue = Some(VenueChoice {
+            cluster: format!("cluster:{}", link_key(&members[0].platform, &members[0].id)),
+            origin: origin_key,
+            chosen: link_key(&routed.edge.market.platform, &routed.edge.market.id),
+            considered,
+        });
+        routed
+    }
+
+    /// Score `market` as the venue for `edge`'s bet, with the re-sized bet
+    /// when the venue would take one.
+    fn score(
+        &self,
+        edge: &Edge,
+        market: &Market,
+        kelly: &KellyCalculator,
+        bankroll: Decimal,
+        now: DateTime<Utc>,
+    ) -> (VenueScore, Option<SizedBet>) {
+        let (win_prob, price) = match edge.side {
+            Side::Yes => (edge.estimate.probability, market.current_price_yes),
+            Side::No => (Decimal::ONE - edge.estimate.probability, market.current_price_no),
+        };
+        let fee = self.config.fees.get(&market.platform).copied().unwrap_or(Decimal::ZERO);
+        let reliability = self.config.reliability.get(&market.platform).copied().unwrap_or(Decimal::ONE);
+        let mut score = VenueScore {
+            market: link_key(&market.platform, &market.id),
+            price,
+            fee,
+            depth: market.liquidity,
+            reliability,
+            amount: Decimal::ZERO,
+            score: Decimal::ZERO,
+        };
+        if price <= Decimal::ZERO || price >= Decimal::ONE {
+            return (score, None);
+        }
+
+        // Expected profit per unit staked, after commission on winnings.
+        let per_unit = win_prob * (Decimal::ONE - price) / price * (Decimal::ONE - fee) - (Decimal::ONE - win_prob);
+        if per_unit <= Decimal::ZERO {
+            return (score, None);
+        }
+        let venue_edge = Edge {
+            market: market.clone(),
+            estimate: edge.estimate.clone(),
+            side: edge.side,
+            edge: win_prob - price,
+            signed_edge: edge.estimate.probability - market.current_price_yes,
+            raw_edge: win_prob - price,
+            effective_threshold: edge.effective_threshold,
+        };
+        let Some(mut resized) = kelly.size_bet_at(&venue_edge, bankroll, now) else {
+            return (score, None);
+        };
+        let amount = resized.bet_amount.min(market.liquidity * self.config.max_depth_pct);
+        if amount < kelly.config().min_bet_size {
+            return (score, None);
+        }
+        resized.net_expected_value = resized.net_expected_value * amount / resized.bet_amount;
+        resized.bet_amount = amount;
+        resized.bet_fraction = amount / bankroll;
+        resized.expected_value = venue_edge.edge * amount;
+
+        score.amount = amount;
+        score.score = amount * per_unit * reliability;
+        (score, Some(resized))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::kelly::KellyConfig;
+    use crate::types::{Estimate, MarketCategory};
+    use rust_decimal_macros::dec;
+
+    fn market(platform: &str, id: &str, yes: Decimal, no: Decimal, liquidity: Decimal) -> Market {
+        Market {
+            id: id.into(),
+            platform: platform.into(),
+            category: MarketCategory::Sports,
+            current_price_yes: yes,
+            current_price_no: no,
+            liquidity,
+            ..Market::sample()
+        }
+    }
+
+    fn bet_on(m: &Market, probability: Decimal, kelly: &KellyCalculator) -> SizedBet {
+        let estimate = Estimate {
+            probability,
+            confidence: dec!(0.8),
+            reasoning: String::new(),
+            tokens_used: 0,
+            cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
+        };
+        let edge = Edge {
+            market: m.clone(),
+            estimate,
+            side: Side::Yes,
+            edge: probability - m.current_price_yes,
+            signed_edge: probability - m.current_price_yes,
+            raw_edge: probability - m.current_price_yes,
+            effective_threshold: dec!(0.06),
+        };
+        kelly.size_bet(&edge, dec!(1000)).unwrap()
+    }
+
+    fn kelly() -> KellyCalculator {
+        KellyCalculator::new(KellyConfig { commission_per_trade: Decimal::ZERO, ..KellyConfig::default() })
+    }
+
+    fn selector(config: VenueSelectionConfig) -> VenueSelector {
+        VenueSelector::new(config, ["betfair".to_string(), "polymarket".to_string()])
+    }
+
+    #[test]
+    fn test_routes_to_best_venue_after_fees() {
+        let kelly = kelly();
+        // Betfair is cheaper but charges 5% on winnings; Polymarket is 1¢
+        // dearer and free. Metaculus is in the cluster but not executable.
+        let betfair = market("betfair", "1.1", dec!(0.40), dec!(0.62), dec!(10000));
+        let polymarket = market("polymarket", "pm", dec!(0.41), dec!(0.60), dec!(10000));
+        let metaculus = market("metaculus", "mc", dec!(0.38), dec!(0.62), dec!(10000));
+        let mut venues = selector(VenueSelectionConfig::default());
+        venues.set_clusters(vec![vec![betfair.clone(), polymarket.clone(), metaculus]]);
+
+        let routed = venues.route(bet_on(&betfair, dec!(0.55), &kelly), &kelly, dec!(1000), Utc::now());
+        let choice = routed.venue.as_ref().unwrap();
+        assert_eq!(routed.edge.market.id, "pm");
+        assert_eq!((choice.origin.as_str(), choice.chosen.as_str()), ("betfair:1.1", "polymarket:pm"));
+        assert_eq!(choice.cluster, "cluster:betfair:1.1");
+        assert_eq!(choice.considered.len(), 2);
+        assert!(choice.considered[1].score > choice.considered[0].score);
+        // Re-derived at Polymarket's price: a smaller edge, a smaller stake.
+        assert_eq!(routed.edge.edge, dec!(0.14));
+        assert_eq!(routed.bet_amount, kelly.size_bet(&routed.edge, dec!(1000)).unwrap().bet_amount);
+
+        // Without Betfair's commission it wins on price.
+        let mut venues = selector(VenueSelectionConfig { fees: HashMap::new(), ..VenueSelectionConfig::default() });
+        venues.set_clusters(vec![vec![betfair.clone(), polymarket.clone()]]);
+        let routed = venues.route(bet_on(&betfair, dec!(0.55), &kelly), &kelly, dec!(1000), Utc::now());
+        assert_eq!(routed.edge.market.id, "1.1");
+        assert_eq!(routed.venue.unwrap().chosen, "betfair:1.1");
+    }
+
+    #[test]
+    fn test_depth_and_reliability_weigh_in() {
+        let kelly = kelly();
+        let betfair = market("betfair", "1.1", dec!(0.40), dec!(0.62), dec!(10000));
+        // Best price, but too thin to take more than a token stake.
+        let thin = market("polymarket", "pm", dec!(0.35), dec!(0.66), dec!(50));
+        let config = VenueSelectionConfig { fees: HashMap::new(), ..VenueSelectionConfig::default() };
+        let mut venues = selector(config.clone());
+        venues.set_clusters(vec![vec![betfair.clone(), thin.clone()]]);
+        let routed = venues.route(bet_on(&betfair, dec!(0.55), &kelly), &kelly, dec!(1000), Utc::now());
+        assert_eq!(routed.edge.market.id, "1.1");
+        assert_eq!(routed.venue.as_ref().unwrap().considered[1].amount, dec!(5.0));
+
+        // Deep enough, but filled half the time.
+        let deep = Market { liquidity: dec!(10000), ..thin };
+        let reliability = HashMap::from([("polymarket".to_string(), dec!(0.5))]);
+        let mut venues = selector(VenueSelectionConfig { reliability, ..config });
+        venues.set_clusters(vec![vec![betfair.clone(), deep]]);
+        assert_eq!(venues.route(bet_on(&betfair, dec!(0.55), &kelly), &kelly, dec!(1000), Utc::now()).edge.market.id, "1.1");
+    }
+
+    #[test]
+    fn test_unclustered_and_paper_bets_stay_put() {
+        let kelly = kelly();
+        let betfair = market("betfair", "1.1", dec!(0.40), dec!(0.62), dec!(10000));
+        let manifold = market("manifold", "mf", dec!(0.30), dec!(0.70), dec!(10000));
+        let mut venues = VenueSelector::new(
+            VenueSelectionConfig::default(),
+            ["betfair".to_string(), "manifold".to_string()],
+        );
+        let lone = venues.route(bet_on(&betfair, dec!(0.55), &kelly), &kelly, dec!(1000), Utc::now());
+        assert!(lone.venue.is_none());
+
+        // Mana prices never stand in for a real-money bet.
+        venues.set_clusters(vec![vec![betfair.clone(), manifold]]);
+        let routed = venues.route(bet_on(&betfair, dec!(0.55), &kelly), &kelly, dec!(1000), Utc::now());
+        assert_eq!(routed.edge.market.id, "1.1");
+        assert_eq!(routed.venue.unwrap().considered.len(), 1);
+    }
+}
@@ -0,0 +1,202 @@
+//! Span export for cycle timing.
+//!
+//! The engine opens a `cycle` span per cycle with one child per phase —
+//! `scan` (→ `scan_platform`), `enrich` (→ `enrich_provider`), `estimate`
+//! (→ `estimate_batch`), `strategy` and `execute` (→ `bet`) — carrying
+//! market counts, costs and, on per-bet spans, the market id. Counts are
+//! recorded as `i64`: OTLP has no unsigned integers and the layer exports
+//! `u64` fields as strings. With
+//! `[telemetry]` enabled, [`layer`] puts `tracing-opentelemetry` next to
+//! the log formatter in the subscriber, over an OpenTelemetry SDK tracer
+//! provider whose batch processor exports to an OTLP/HTTP collector using
+//! the JSON encoding.
+//!
+//! Sampling is decided per root span (parent-based trace-id ratio), so a
+//! cycle is exported whole or not at all. Only spans from this crate are
+//! exported; a crate span nested in another crate's span hangs off its
+//! nearest exported ancestor. Batching, queue limits and export retries
+//! are the SDK's; [`shutdown`] flushes what is still buffered.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{Protocol, WithExportConfig};
+use opentelemetry_sdk::trace::{Sampler, TracerProvider};
+use opentelemetry_sdk::{runtime, Resource};
+use tracing::level_filters::LevelFilter;
+use tracing::Subscriber;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::Layer;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::TelemetryConfig;
+
+/// Spans from targets outside this prefix are not exported.
+const TARGET_PREFIX: &str = "oracle";
+/// Longest one export request may take.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sample `sample_ratio` of root spans; children follow their root.
+fn sampler(sample_ratio: f64) -> Sampler {
+    Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(sample_ratio)))
+}
+
+/// Tracer provider batching sampled spans to the collector in `cfg`, set
+/// as the global provider so [`shutdown`] can flush it. Must be called
+/// inside the Tokio runtime.
+pub fn init(cfg: &TelemetryConfig) -> Result<TracerProvider> {
+    let url = format!("{}/v1/traces", cfg.endpoint.trim_end_matches('/'));
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_protocol(Protocol::HttpJson)
+        .with_endpoint(url)
+        .with_timeout(EXPORT_TIMEOUT)
+        .build()
+        .context("Failed to build the OTLP span exporter")?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_sampler(sampler(cfg.sample_ratio))
+        .with_resource(Resource::new([KeyValue::new("service.name", cfg.service_name.clone())]))
+        .build();
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    Ok(provider)
+}
+
+/// Subscriber layer exporting this crate's spans through `provider`.
+pub fn layer<S>(provider: &TracerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer(TARGET_PREFIX))
+        .with_filter(Targets::new().with_target(TARGET_PREFIX, LevelFilter::TRACE))
+}
+
+/// Flush and stop the global provider; a no-op when telemetry is off.
+pub async fn shutdown() {
+    // The batch processor blocks until its export task has flushed.
+    let _ = tokio::task::spawn_blocking(opentelemetry::global::shutdown_tracer_provider).await;
+}
+
+/// Install a subscriber with only the export [`layer`] on this thread
+/// until the guard drops; finished spans land in the returned exporter,
+/// which is emptied when the guard drops.
+#[cfg(test)]
+pub(crate) fn capturing(
+    sample_ratio: f64,
+) -> (tracing::subscriber::DefaultGuard, opentelemetry_sdk::testing::trace::InMemorySpanExporter) {
+    use tracing_subscriber::prelude::*;
+    let exporter = opentelemetry_sdk::testing::trace::InMemorySpanExporter::default();
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .with_sampler(sampler(sample_ratio))
+        .build();
+    (tracing::subscriber::set_default(tracing_subscriber::registry().with(layer(&provider))), exporter)
+}
+
+/// Value of the span attribute `key`.
+#[cfg(test)]
+pub(crate) fn attr<'a>(
+    span: &'a opentelemetry_sdk::export::trace::SpanData,
+    key: &str,
+) -> Option<&'a opentelemetry::Value> {
+    span.attributes.iter().find(|kv| kv.key.as_str() == key).map(|kv| &kv.value)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::SpanId;
+    use opentelemetry::Value;
+    use opentelemetry_sdk::export::trace::SpanData;
+    use tracing::info_span;
+
+    /// Spans finished while `f` runs.
+    fn capture(sample_ratio: f64, f: impl FnOnce()) -> Vec<SpanData> {
+        let (_guard, exporter) = capturing(sample_ratio);
+        f();
+        // Read before the guard drops: shutting the provider down clears them.
+        exporter.get_finished_spans().unwrap()
+    }
+
+    fn find<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+        spans.iter().find(|s| s.name == name).unwrap_or_else(|| panic!("no {name} span"))
+    }
+
+    #[test]
+    fn test_span_hierarchy_for_one_cycle() {
+        let spans = capture(1.0, || {
+            let cycle = info_span!("cycle", cycle = 7i64);
+            let _c = cycle.enter();
+            {
+                let scan = info_span!("scan", markets = tracing::field::Empty);
+                let _s = scan.enter();
+                info_span!("scan_platform", platform = "manifold").in_scope(|| {});
+                scan.record("markets", 12i64);
+            }
+            info_span!("execute", bets = 1i64).in_scope(|| {
+                info_span!("bet", market_id = "m1", amount = 2.5).in_scope(|| {});
+            });
+        });
+        assert_eq!(spans.len(), 5);
+
+        let cycle = find(&spans, "cycle");
+        let scan = find(&spans, "scan");
+        let platform = find(&spans, "scan_platform");
+        let execute = find(&spans, "execute");
+        let bet = find(&spans, "bet");
+        let id = |s: &SpanData| s.span_context.span_id();
+        assert_eq!(cycle.parent_span_id, SpanId::INVALID);
+        assert_eq!(scan.parent_span_id, id(cycle));
+        assert_eq!(platform.parent_span_id, id(scan));
+        assert_eq!(execute.parent_span_id, id(cycle));
+        assert_eq!(bet.parent_span_id, id(execute));
+        assert!(spans.iter().all(|s| s.span_context.trace_id() == cycle.span_context.trace_id()));
+
+        assert_eq!(attr(cycle, "cycle"), Some(&Value::I64(7)));
+        assert_eq!(attr(scan, "markets"), Some(&Value::I64(12)));
+        assert_eq!(attr(bet, "market_id"), Some(&Value::from("m1")));
+        assert_eq!(attr(bet, "amount"), Some(&Value::F64(2.5)));
+        assert!(cycle.end_time >= scan.end_time && scan.start_time >= cycle.start_time);
+    }
+
+    #[test]
+    fn test_sampling_is_per_cycle() {
+        let run = || {
+            for _ in 0..3 {
+                info_span!("cycle").in_scope(|| info_span!("scan").in_scope(|| {}));
+            }
+        };
+        assert!(capture(0.0, run).is_empty());
+        let spans = capture(1.0, run);
+        assert_eq!(spans.len(), 6);
+        let traces: std::collections::HashSet<_> = spans.iter().map(|s| s.span_context.trace_id()).collect();
+        assert_eq!(traces.len(), 3);
+    }
+
+    #[test]
+    fn test_other_crates_are_not_exported() {
+        let spans = capture(1.0, || {
+            let foreign = info_span!(target: "hyper::client", "request");
+            foreign.in_scope(|| info_span!("bet").in_scope(|| {}));
+        });
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, "bet");
+        assert_eq!(spans[0].parent_span_id, SpanId::INVALID);
+    }
+
+    // The batch processor's shutdown blocks on its export task, which needs
+    // a second worker thread to run.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_exporter_builds_from_config() {
+        let cfg = TelemetryConfig { enabled: true, ..TelemetryConfig::default() };
+        let provider = init(&cfg).unwrap();
+        assert!(provider.shutdown().is_ok());
+    }
+}
@@ -0,0 +1,222 @@
+//! Deterministic synthetic corpora for benchmarks and load-shaped tests.
+//!
+//! The hot paths (similarity matching, cross-referencing, market filtering
+//! and bet selection) run over hundreds of markets per cycle, far more
+//! than the hand-built markets in unit tests. These generators produce
+//! corpora of that size from a seed, so a benchmark, a timing guard in a
+//! unit test and the integration harness all measure the same inputs.
+//! Enabled for tests and by feature `testkit`:
+//!
+//! ```text
+//! cargo bench --features testkit
+//! ```
+
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+
+use crate::types::{CrossReferences, Estimate, Market, MarketCategory};
+
+/// Small xorshift generator: reproducible corpora without a `rand`
+/// dependency.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift.
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Uniform in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Uniform in `[lo, hi)`.
+    pub fn range(&mut self, lo: f64, hi: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        lo + unit * (hi - lo)
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+}
+
+const TEMPLATES: &[(&str, MarketCategory)] = &[
+    ("Will {s} win the {y} {e}?", MarketCategory::Sports),
+    ("Will {s} reach the {e} final in {y}?", MarketCategory::Sports),
+    ("Will {s} be elected {e} in {y}?", MarketCategory::Politics),
+    ("Will {s} resign as {e} before {y}?", MarketCategory::Politics),
+    ("Will {s} report {e} above forecast in {y}?", MarketCategory::Economics),
+    ("Will {s} cut {e} before the end of {y}?", MarketCategory::Economics),
+    ("Will {s} exceed {e} in {y}?", MarketCategory::Weather),
+    ("Will {s} release {e} before {y}?", MarketCategory::Culture),
+    ("Will {s} announce {e} by {y}?", MarketCategory::Other),
+];
+
+const SUBJECTS: &[&str] = &[
+    "Arsenal", "Lakers", "Djokovic", "Verstappen", "Trump", "Starmer",
+    "Macron", "Albanese", "the Federal Reserve", "the RBA", "Nvidia", "Apple",
+    "Sydney", "London", "Phoenix", "Taylor Swift", "OpenAI", "SpaceX",
+    "Tesla", "the ECB", "Brazil", "India", "Kenya", "Canada",
+];
+
+const EVENTS: &[&str] = &[
+    "championship", "Premier League", "NBA Finals", "World Cup", "Grand Slam",
+    "president", "prime minister", "chancellor", "interest rates",
+    "quarterly revenue", "inflation", "record temperatures", "40C",
+    "a new album", "a merger", "a product launch", "an IPO", "Starship orbit",
+];
+
+/// `n` distinct-looking prediction-market questions with their category.
+/// Subjects and events recur, so the corpus has realistic near-duplicates
+/// alongside unrelated questions.
+pub fn questions(n: usize, seed: u64) -> Vec<(String, MarketCategory)> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|_| {
+            let (template, category) = rng.pick(TEMPLATES);
+            let year = 2026 + rng.below(4);
+            let q = template
+                .replace("{s}", rng.pick(SUBJECTS))
+                .replace("{e}", rng.pick(EVENTS))
+                .replace("{y}", &year.to_string());
+            (q, category)
+        })
+        .collect()
+}
+
+/// A market with the fields the hot paths read; everything else empty.
+pub fn market(
+    id: &str,
+    platform: &str,
+    question: &str,
+    category: MarketCategory,
+    price_yes: f64,
+    liquidity: f64,
+    hours_to_deadline: i64,
+) -> Market {
+    let price_yes = decimal(price_yes);
+    Market {
+        id: id.to_string(),
+        platform: platform.to_string(),
+        question: question.to_string(),
+        description: String::new(),
+        category,
+        current_price_yes: price_yes,
+        current_price_no: Decimal::ONE - price_yes,
+        volume_24h: decimal(liquidity / 10.0),
+        liquidity: decimal(liquidity),
+        deadline: Utc::now() + Duration::hours(hours_to_deadline),
+        resolution_criteria: String::new(),
+        url: format!("https://example.com/{id}"),
+        cross_refs: CrossReferences::default(),
+        event_group: None,
+        facts: None,
+        tags: Vec::new(),
+    }
+}
+
+/// `n` markets on `platform`. Liquidity and deadlines straddle the default
+/// scanner filters, so a filter pass keeps some and drops the rest.
+pub fn markets(n: usize, platform: &str, seed: u64) -> Vec<Market> {
+    let mut rng = Rng::new(seed ^ 0x6d61_726b);
+    questions(n, seed)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (q, category))| {
+            let liquidity = rng.range(0.0, 20_000.0);
+            let hours = rng.range(0.0, 24.0 * 400.0) as i64;
+            market(&format!("{platform}-{i}"), platform, &q, category, rng.range(0.03, 0.97), liquidity, hours)
+        })
+        .collect()
+}
+
+/// `n` Metaculus questions carrying community forecasts, as the scanner
+/// receives them for cross-referencing.
+pub fn metaculus(n: usize, seed: u64) -> Vec<Market> {
+    let mut rng = Rng::new(seed ^ 0x6d63);
+    markets(n, "metaculus", seed)
+        .into_iter()
+        .map(|mut m| {
+            let forecasters = 10 + rng.below(900) as u32;
+            m.liquidity = Decimal::from(forecasters);
+            m.cross_refs.metaculus_prob = Some(m.current_price_yes);
+            m.cross_refs.metaculus_forecasters = Some(forecasters);
+            m
+        })
+        .collect()
+}
+
+/// `n` estimated markets across platforms and categories. Edges range from
+/// none to well past every category threshold, so strategy selection sees
+/// rejections at each stage as well as approvals.
+pub fn estimates(n: usize, seed: u64) -> Vec<(Market, Estimate)> {
+    const PLATFORMS: &[&str] = &["manifold", "polymarket", "betfair"];
+    let mut rng = Rng::new(seed ^ 0x6573);
+    questions(n, seed)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (q, category))| {
+            let platform = rng.pick(PLATFORMS);
+            let price = rng.range(0.05, 0.95);
+            let market = market(&format!("{platform}-{i}"), platform, &q, category, price, rng.range(500.0, 50_000.0), 24 * 30);
+            let probability = (price + rng.range(-0.25, 0.25)).clamp(0.01, 0.99);
+            let estimate = Estimate {
+                probability: decimal(probability),
+                confidence: decimal(rng.range(0.4, 0.95)),
+                reasoning: String::new(),
+                tokens_used: 0,
+                cost: Decimal::ZERO,
+                critique: None,
+                served_by: None,
+                tier: None,
+            };
+            (market, estimate)
+        })
+        .collect()
+}
+
+fn decimal(x: f64) -> Decimal {
+    Decimal::try_from(x).unwrap_or_default().round_dp(4)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corpora_are_deterministic() {
+        let a = estimates(50, 7);
+        let b = estimates(50, 7);
+        let key = |v: &[(Market, Estimate)]| -> Vec<(String, String, Decimal)> {
+            v.iter().map(|(m, e)| (m.id.clone(), m.question.clone(), e.probability)).collect()
+        };
+        assert_eq!(key(&a), key(&b));
+        assert_ne!(key(&a), key(&estimates(50, 8)));
+    }
+
+    #[test]
+    fn test_markets_are_well_formed() {
+        for m in markets(200, "manifold", 1).iter().chain(&metaculus(200, 2)) {
+            assert!(m.current_price_yes > Decimal::ZERO && m.current_price_yes < Decimal::ONE);
+            assert_eq!(m.current_price_yes + m.current_price_no, Decimal::ONE);
+            assert!(!m.question.contains('{'));
+        }
+        assert!(metaculus(10, 3).iter().all(|m| m.cross_refs.metaculus_prob.is_some()));
+    }
+}
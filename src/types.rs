@@ -4,10 +4,12 @@
 //! They are designed to be stable so that platform, strategy,
 //! and engine modules can depend on them without circular references.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 /// Convert an f64 to Decimal at API boundaries.
@@ -43,6 +45,19 @@ pub struct Market {
     pub url: String,
     /// Cross-platform probability references
     pub cross_refs: CrossReferences,
+    /// Underlying event shared by related markets (Betfair event id,
+    /// Polymarket event slug). Markets in one group are one correlated cluster.
+    #[serde(default)]
+    pub event_group: Option<String>,
+    /// Structured facts parsed from the question, attached by the scanner.
+    /// Use [`question_parser::facts`](crate::question_parser::facts) to read
+    /// them with a parse-on-demand fallback.
+    #[serde(default)]
+    pub facts: Option<QuestionFacts>,
+    /// Operator tags, attached by the scanner from the tag rules (see
+    /// [`crate::engine::tags`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl fmt::Display for Market {
@@ -102,11 +117,41 @@ impl Market {
                 metaculus_forecasters: Some(314),
                 manifold_prob: Some(dec!(0.48)),
                 forecastex_price: Some(dec!(0.45)),
+                ..Default::default()
             },
+            event_group: None,
+            facts: None,
+            tags: Vec::new(),
         }
     }
 }
 
+/// Entities, dates and numeric thresholds extracted from a market question
+/// by [`question_parser`](crate::question_parser).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestionFacts {
+    /// Capitalised phrases: people, organisations, products, events.
+    pub entities: Vec<String>,
+    /// Dates in question order. Month- and year-only phrases resolve to
+    /// the last day of the period ("by March 2026" → 2026-03-31).
+    pub dates: Vec<NaiveDate>,
+    /// Threshold values with their unit ("%", "$", "°C", "mm", or "" for a
+    /// plain count), scaled ("$1.5B" → 1.5e9), in question order.
+    pub numeric_thresholds: Vec<(f64, String)>,
+    /// Direction the question compares against the thresholds.
+    pub comparison: Option<Comparison>,
+    /// Places mentioned, as canonical names ("US" → "United States").
+    pub geography: Vec<String>,
+}
+
+/// Comparison direction of a threshold question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    Above,
+    Below,
+    Between,
+}
+
 /// Cross-platform reference probabilities.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CrossReferences {
@@ -114,6 +159,22 @@ pub struct CrossReferences {
     pub metaculus_forecasters: Option<u32>,
     pub manifold_prob: Option<Decimal>,
     pub forecastex_price: Option<Decimal>,
+    /// The same question on other executable platforms, merged into this
+    /// market by the scanner. Bets on any of them are one exposure unit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateListing>,
+    /// `platform:market_id` of the market this one was merged into.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+}
+
+/// A listing of the same question on another platform.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateListing {
+    pub platform: String,
+    pub market_id: String,
+    pub price_yes: Decimal,
+    pub liquidity: Decimal,
 }
 
 impl fmt::Display for CrossReferences {
@@ -129,6 +190,9 @@ impl fmt::Display for CrossReferences {
         if let Some(p) = self.forecastex_price {
             parts.push(format!("ForecastEx: {}¢", (p * dec!(100)).round()));
         }
+        for dup in &self.duplicates {
+            parts.push(format!("{} (same question): {}%", dup.platform, (dup.price_yes * dec!(100)).round()));
+        }
         if parts.is_empty() {
             write!(f, "No cross-references")
         } else {
@@ -226,6 +290,8 @@ pub enum AgentStatus {
     Alive,
     Died,
     Paused,
+    /// Scanning and estimating with execution and ledger updates disabled.
+    Standby,
 }
 
 impl fmt::Display for AgentStatus {
@@ -234,6 +300,7 @@ impl fmt::Display for AgentStatus {
             AgentStatus::Alive => write!(f, "🟢 ALIVE"),
             AgentStatus::Died => write!(f, "🔴 DIED"),
             AgentStatus::Paused => write!(f, "🟡 PAUSED"),
+            AgentStatus::Standby => write!(f, "🔵 STANDBY"),
         }
     }
 }
@@ -243,7 +310,7 @@ impl fmt::Display for AgentStatus {
 // ---------------------------------------------------------------------------
 
 /// Receipt returned after a trade is executed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TradeReceipt {
     pub order_id: String,
     pub market_id: String,
@@ -256,12 +323,71 @@ pub struct TradeReceipt {
     /// Currency of the bet amount: "AUD", "Mana", etc.
     #[serde(default = "TradeReceipt::default_currency")]
     pub currency: String,
+    /// Deadline of the underlying market (event start for Betfair).
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
+    /// Category of the underlying market.
+    #[serde(default)]
+    pub category: Option<MarketCategory>,
+    /// Absolute edge detected when the bet was placed.
+    #[serde(default)]
+    pub edge: Option<Decimal>,
+    /// Platform's raw placement response, kept for dispute resolution.
+    /// Redacted and size-capped by the executor; omitted from list views.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_response: Option<serde_json::Value>,
+    /// Risk posture when the bet was approved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_context: Option<RiskContext>,
+    /// Operator tags of the underlying market.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Correlation group the question fell into when the bet was placed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_key: Option<String>,
+    /// Price of the bet's side when it was approved, against which the
+    /// fill's slippage is measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_price: Option<Decimal>,
 }
 
 impl TradeReceipt {
     pub fn default_currency() -> String {
         "AUD".to_string()
     }
+
+    /// How much worse than expected the bet filled (positive = paid more).
+    pub fn slippage(&self) -> Option<Decimal> {
+        self.expected_price.map(|expected| self.fill_price - expected)
+    }
+}
+
+/// The agent's own risk posture at the moment a bet was approved, kept on
+/// the bet so outcomes can later be grouped by the conditions they were
+/// taken in (e.g. bets approved above 15% drawdown).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskContext {
+    /// Layout version; bump when fields change meaning.
+    pub version: u32,
+    /// Real-money bankroll.
+    pub bankroll: Decimal,
+    /// Drawdown from peak bankroll as a fraction.
+    pub drawdown_pct: Decimal,
+    /// Open exposure before this bet, including bets approved earlier in
+    /// the same cycle.
+    pub exposure: Decimal,
+    /// Open exposure per category, on the same basis as `exposure`.
+    pub category_exposure: HashMap<MarketCategory, Decimal>,
+    /// Bets already placed or approved today (UTC).
+    pub bets_today: usize,
+    /// Multiplier applied to the Kelly size for drawdown (1 = full size).
+    pub drawdown_factor: Decimal,
+    /// Categories cooling down after a losing streak.
+    pub cool_downs: Vec<MarketCategory>,
+}
+
+impl RiskContext {
+    pub const VERSION: u32 = 1;
 }
 
 impl fmt::Display for TradeReceipt {
@@ -288,6 +414,30 @@ impl TradeReceipt {
     }
 }
 
+/// How a market resolved, as reported by its platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketResolution {
+    Yes,
+    No,
+    /// Resolved to a YES probability (Manifold "MKT").
+    Probability(Decimal),
+    /// N/A, void or cancelled: stakes are refunded.
+    Cancelled,
+}
+
+impl MarketResolution {
+    /// Value of one YES share at resolution; `None` when cancelled.
+    pub fn yes_value(&self) -> Option<Decimal> {
+        match self {
+            Self::Yes => Some(Decimal::ONE),
+            Self::No => Some(Decimal::ZERO),
+            Self::Probability(p) => Some((*p).clamp(Decimal::ZERO, Decimal::ONE)),
+            Self::Cancelled => None,
+        }
+    }
+}
+
 /// An open position on a platform.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
@@ -297,6 +447,14 @@ pub struct Position {
     pub size: Decimal,
     pub entry_price: Decimal,
     pub current_value: Decimal,
+    /// Amount staked, in the platform's currency. Counted as exposure by
+    /// the risk manager.
+    #[serde(default)]
+    pub cost: Decimal,
+    /// Category of the market, when known. Platforms don't report it; the
+    /// agent fills it in from its scanned markets and open bets.
+    #[serde(default)]
+    pub category: Option<MarketCategory>,
 }
 
 impl fmt::Display for Position {
@@ -355,6 +513,17 @@ impl LiquidityInfo {
     }
 }
 
+/// How a venue prices against a stake, which decides what its
+/// [`LiquidityInfo`] means (see [`crate::strategy::impact`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityModel {
+    /// Resting orders; the depths are the visible book on each side.
+    OrderBook,
+    /// A constant-product market maker; `bid_depth` and `ask_depth` are
+    /// its YES and NO pools.
+    Cpmm,
+}
+
 // ---------------------------------------------------------------------------
 // Strategy types
 // ---------------------------------------------------------------------------
@@ -378,6 +547,9 @@ pub struct BetDecision {
     pub rationale: String,
     /// List of data sources used for the estimate
     pub data_sources_used: Vec<String>,
+    /// Risk posture when the bet was approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_context: Option<RiskContext>,
 }
 
 impl fmt::Display for BetDecision {
@@ -426,6 +598,50 @@ pub struct Estimate {
     pub reasoning: String,
     pub tokens_used: u32,
     pub cost: Decimal,
+    /// Self-critique pass, when this estimate was selected for one.
+    #[serde(default)]
+    pub critique: Option<Critique>,
+    /// Estimator that served this estimate under provider failover;
+    /// `None` outside failover and for echo estimates.
+    #[serde(default)]
+    pub served_by: Option<String>,
+    /// Estimation tier the market was assigned; `None` when tiering is off.
+    #[serde(default)]
+    pub tier: Option<Tier>,
+}
+
+/// Estimation tier: how much LLM effort a market's estimate is worth.
+/// `A` is the most valuable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Tier {
+    A,
+    B,
+    C,
+}
+
+impl std::fmt::Display for Tier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tier::A => write!(f, "A"),
+            Tier::B => write!(f, "B"),
+            Tier::C => write!(f, "C"),
+        }
+    }
+}
+
+/// Same-model self-critique of an estimate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Critique {
+    /// Probability before the critique.
+    pub initial_probability: Decimal,
+    /// Probability stated after the critique.
+    pub revised_probability: Decimal,
+    /// The model's strongest reasons its first estimate could be wrong.
+    pub reasons: String,
+    /// Whether the revised probability replaced the initial one.
+    pub applied: bool,
+    /// Cost of the critique call (already included in `Estimate::cost`).
+    pub cost: Decimal,
 }
 
 impl fmt::Display for Estimate {
@@ -475,6 +691,34 @@ pub struct DataContext {
     pub metaculus_forecast: Option<Decimal>,
     pub metaculus_forecasters: Option<u32>,
     pub manifold_price: Option<Decimal>,
+    /// Per-source parts of a composite context. Empty for single-source
+    /// contexts (and contexts stored before sections existed), whose
+    /// `summary` is the whole story.
+    #[serde(default)]
+    pub sections: Vec<ContextSection>,
+}
+
+/// One data source's part of a composite [`DataContext`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContextSection {
+    /// Source label, e.g. "weather", "news".
+    pub source: String,
+    /// Empty when the source was unavailable.
+    pub summary: String,
+    pub freshness: DateTime<Utc>,
+    /// False when the source failed this cycle.
+    #[serde(default = "ContextSection::default_available")]
+    pub available: bool,
+}
+
+impl ContextSection {
+    fn default_available() -> bool {
+        true
+    }
+
+    pub fn unavailable(source: &str) -> Self {
+        Self { source: source.to_string(), summary: String::new(), freshness: Utc::now(), available: false }
+    }
 }
 
 impl fmt::Display for DataContext {
@@ -512,14 +756,348 @@ impl DataContext {
             metaculus_forecast: None,
             metaculus_forecasters: None,
             manifold_price: None,
+            sections: Vec::new(),
+        }
+    }
+
+    /// Prompt text for this context. Sections render in source order,
+    /// with unavailable sources listed last so the model knows what it is
+    /// not seeing; contexts without sections render their summary.
+    pub fn render(&self) -> String {
+        if self.sections.is_empty() {
+            return self.summary.clone();
+        }
+        let mut sections: Vec<&ContextSection> = self.sections.iter().collect();
+        sections.sort_by(|a, b| (!a.available, &a.source).cmp(&(!b.available, &b.source)));
+
+        let mut lines = Vec::new();
+        if !sections.iter().any(|s| s.available) {
+            lines.push("No enrichment data available.".to_string());
+        }
+        for s in sections {
+            if s.available {
+                lines.push(format!("[{} @ {}]", s.source, s.freshness.format("%Y-%m-%d %H:%M UTC")));
+                lines.push(s.summary.clone());
+            } else {
+                lines.push(format!("{}: unavailable this cycle", s.source));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Summary-only copy for cheap estimation: the first line of each
+    /// available section (or of the summary), without the raw data.
+    /// Cross-reference forecasts are kept.
+    pub fn summary_only(&self) -> DataContext {
+        let first_line = |s: &str| s.lines().next().unwrap_or_default().to_string();
+        let mut ctx = DataContext {
+            raw_data: serde_json::Value::Null,
+            summary: first_line(&self.summary),
+            sections: self
+                .sections
+                .iter()
+                .map(|s| ContextSection { summary: first_line(&s.summary), ..s.clone() })
+                .collect(),
+            ..self.clone()
+        };
+        if !ctx.sections.is_empty() {
+            ctx.summary = ctx.render();
+        }
+        ctx
+    }
+
+    /// Add a section and refresh `summary` to match. A single-source
+    /// context's summary becomes its first section.
+    pub fn push_section(&mut self, section: ContextSection) {
+        if self.sections.is_empty() && self.source != "none" {
+            self.sections.push(ContextSection {
+                source: self.source.clone(),
+                summary: std::mem::take(&mut self.summary),
+                freshness: self.freshness,
+                available: true,
+            });
+        }
+        self.sections.push(section);
+        self.summary = self.render();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Edge realization
+// ---------------------------------------------------------------------------
+
+/// A resolved bet's detected edge against what it actually returned.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EdgeRealization {
+    pub category: MarketCategory,
+    /// Absolute edge at placement.
+    pub edge: Decimal,
+    pub stake: Decimal,
+    pub pnl: Decimal,
+    pub resolved_at: DateTime<Utc>,
+}
+
+impl EdgeRealization {
+    /// Return on stake (pnl / stake).
+    pub fn return_pct(&self) -> f64 {
+        if self.stake.is_zero() {
+            return 0.0;
+        }
+        (self.pnl / self.stake).to_f64().unwrap_or(0.0)
+    }
+}
+
+/// One change to a category's edge threshold by the adaptive review.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ThresholdAdjustment {
+    pub category: MarketCategory,
+    pub previous: Decimal,
+    pub threshold: Decimal,
+    /// When the new threshold took effect.
+    pub effective_at: DateTime<Utc>,
+    /// Resolved bets in the category the review was based on.
+    pub resolutions: usize,
+}
+
+/// Adaptive edge thresholds currently in effect.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThresholdState {
+    /// Per-category overrides of the configured thresholds.
+    #[serde(default)]
+    pub current: HashMap<MarketCategory, Decimal>,
+    /// Adjustment history, oldest first.
+    #[serde(default)]
+    pub adjustments: Vec<ThresholdAdjustment>,
+    #[serde(default)]
+    pub last_review: Option<DateTime<Utc>>,
+}
+
+/// Losing-streak tracking for one category.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CategoryStreak {
+    /// Resolved losses since the last win (or lifted cool-down).
+    #[serde(default)]
+    pub consecutive_losses: u32,
+    /// Start of the active cool-down, if any.
+    #[serde(default)]
+    pub cool_down_since: Option<DateTime<Utc>>,
+    /// Opportunities rejected during the active cool-down.
+    #[serde(default)]
+    pub skipped: u32,
+}
+
+impl CategoryStreak {
+    pub fn cooling_down(&self) -> bool {
+        self.cool_down_since.is_some()
+    }
+}
+
+/// Resolved bets carrying one tag, in one currency.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TagResults {
+    pub tag: String,
+    pub currency: String,
+    pub resolved: u64,
+    pub won: u64,
+    pub lost: u64,
+    /// Total stake of the resolved bets.
+    pub staked: Decimal,
+    pub pnl: Decimal,
+}
+
+/// Per-category losing streaks and cool-downs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoolDownState {
+    #[serde(default)]
+    pub categories: HashMap<MarketCategory, CategoryStreak>,
+}
+
+impl CoolDownState {
+    /// Streak for `category`, if one is active (cooling down).
+    pub fn active(&self, category: MarketCategory) -> Option<&CategoryStreak> {
+        self.categories.get(&category).filter(|s| s.cooling_down())
+    }
+}
+
+/// How well one reference platform's price has predicted resolutions in
+/// one category.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReferenceAccuracy {
+    /// Reference platform name ("metaculus", "manifold").
+    pub platform: String,
+    pub category: MarketCategory,
+    /// Resolved markets the reference was scored on.
+    pub resolutions: u32,
+    /// Sum of the reference's squared errors against the outcomes.
+    pub brier_sum: f64,
+    /// Blending weight set by the last review; `None` until first reviewed.
+    #[serde(default)]
+    pub weight: Option<f64>,
+}
+
+impl ReferenceAccuracy {
+    /// Mean Brier score, `None` before the first resolution.
+    pub fn brier(&self) -> Option<f64> {
+        (self.resolutions > 0).then(|| self.brier_sum / f64::from(self.resolutions))
+    }
+}
+
+/// Cross-reference trust weights (see [`crate::strategy::references`]).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReferenceState {
+    #[serde(default)]
+    pub accuracy: Vec<ReferenceAccuracy>,
+    #[serde(default)]
+    pub last_review: Option<DateTime<Utc>>,
+}
+
+/// Scan hibernation of one platform.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PlatformHibernation {
+    /// Consecutive scans that produced no post-filter markets.
+    #[serde(default)]
+    pub empty_scans: u32,
+    #[serde(default)]
+    pub hibernating: bool,
+    /// Cycles until the next scan while hibernating (1 = next cycle).
+    #[serde(default)]
+    pub next_scan_in: u32,
+}
+
+impl PlatformHibernation {
+    /// Scan-stats note for a hibernating platform, e.g.
+    /// "betfair: hibernating, next scan in 4 cycles".
+    pub fn note(&self, platform: &str) -> Option<String> {
+        if !self.hibernating {
+            return None;
         }
+        Some(match self.next_scan_in {
+            0 | 1 => format!("{platform}: hibernating, next scan next cycle"),
+            n => format!("{platform}: hibernating, next scan in {n} cycles"),
+        })
     }
 }
 
+// ---------------------------------------------------------------------------
+// External activity
+// ---------------------------------------------------------------------------
+
+/// Activity on a shared account that the agent did not place (e.g. manual
+/// trades), tracked when a platform uses reconciled accounting.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExternalActivity {
+    /// External bets and sales seen in the platform history.
+    #[serde(default)]
+    pub trades: u64,
+    /// Net amount staked externally (sales count negative).
+    #[serde(default)]
+    pub staked: Decimal,
+    #[serde(default)]
+    pub fees: Decimal,
+    /// Resolved platform P&L not explained by the agent's own resolutions.
+    #[serde(default)]
+    pub pnl: Decimal,
+    /// Timestamp of the newest platform transaction processed.
+    #[serde(default)]
+    pub cursor: Option<DateTime<Utc>>,
+    /// Processed transaction ids at `cursor`, so transactions sharing the
+    /// cursor's timestamp are not counted twice.
+    #[serde(default)]
+    pub cursor_ids: Vec<String>,
+    /// Platform-reported all-time profit at the last reconciliation.
+    #[serde(default)]
+    pub last_platform_profit: Option<Decimal>,
+    /// Agent's own P&L at the last reconciliation.
+    #[serde(default)]
+    pub last_agent_pnl: Decimal,
+    /// Sales placed by the agent (auto-exit), awaiting attribution.
+    #[serde(default)]
+    pub own_sales: Vec<OwnSale>,
+}
+
+/// A position sale placed by the agent, which has no receipt of its own.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OwnSale {
+    pub market_id: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Daily snapshots
+// ---------------------------------------------------------------------------
+
+/// One currency's books at a daily snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DailyBook {
+    /// Liquid balance (`bankroll` / `mana_bankroll`).
+    pub cash: Decimal,
+    /// Stake of the open positions.
+    pub staked: Decimal,
+    /// Open positions valued at their last mark.
+    pub at_market: Decimal,
+    /// Cumulative realised P&L.
+    pub realized: Decimal,
+}
+
+impl DailyBook {
+    pub fn unrealized(&self) -> Decimal {
+        self.at_market - self.staked
+    }
+}
+
+/// Books at the close of a UTC day, taken by the first cycle after midnight.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailySnapshot {
+    /// The day this snapshot closes.
+    pub date: NaiveDate,
+    pub taken_at: DateTime<Utc>,
+    /// Books per currency.
+    pub books: BTreeMap<String, DailyBook>,
+    /// Cumulative AUD costs (API + commissions).
+    pub costs: Decimal,
+    /// Unrealised P&L per open bet (order id), so a late resolution can be
+    /// taken back out of the day it was valued in.
+    #[serde(default)]
+    pub positions: BTreeMap<String, Decimal>,
+}
+
+/// A resolution that arrived after the snapshot of the day its market
+/// closed in, booked against that day instead of the current one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyCorrection {
+    /// Day of the snapshot being corrected.
+    pub date: NaiveDate,
+    pub recorded_at: DateTime<Utc>,
+    pub order_id: String,
+    pub currency: String,
+    /// Realised P&L of the resolution.
+    pub realized: Decimal,
+    /// Change to the snapshot's unrealised P&L (the bet's valuation, negated).
+    pub unrealized: Decimal,
+}
+
+/// Append-only daily snapshot series (see [`crate::engine::daily`]).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyLedger {
+    /// Oldest first; one per closed day the agent was up for.
+    #[serde(default)]
+    pub snapshots: Vec<DailySnapshot>,
+    /// Oldest first.
+    #[serde(default)]
+    pub corrections: Vec<DailyCorrection>,
+    /// Last seen YES price per held market id.
+    #[serde(default)]
+    pub marks: HashMap<String, Decimal>,
+}
+
 // ---------------------------------------------------------------------------
 // Agent state
 // ---------------------------------------------------------------------------
 
+/// Platform the single AUD bankroll was attributed to before balances were
+/// tracked per venue.
+pub const DEFAULT_PLATFORM: &str = "betfair";
+
 /// Persistent agent state, saved to JSON after each cycle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentState {
@@ -542,7 +1120,24 @@ pub struct AgentState {
     pub total_ib_commissions: Decimal,
     pub start_time: DateTime<Utc>,
     pub peak_bankroll: Decimal,
+    /// AUD bankroll less the stakes of open AUD bets.
+    #[serde(default)]
+    pub cash_bankroll: Decimal,
+    /// AUD bankroll plus the open AUD bets' unrealized P&L at the last
+    /// mark-to-market pass.
+    #[serde(default)]
+    pub total_equity: Decimal,
+    /// Highest `total_equity` reached.
+    #[serde(default)]
+    pub peak_equity: Decimal,
+    /// Unrealized P&L of the open AUD bets at the last mark-to-market pass
+    /// (see [`crate::engine::accountant::Accountant::mark_to_market`]).
+    #[serde(default)]
+    pub unrealized_pnl: Decimal,
     pub status: AgentStatus,
+    /// Why the agent is paused, while it is.
+    #[serde(default)]
+    pub pause_reason: Option<String>,
     /// Bankroll floor: agent dies when bankroll falls to or below this value.
     /// Defaults to 0.0 (die at $0). Set via config `survival_threshold`.
     #[serde(default)]
@@ -560,15 +1155,57 @@ pub struct AgentState {
     pub mana_trades_won: u64,
     #[serde(default)]
     pub mana_trades_lost: u64,
+    /// Funds available on each execution venue, keyed by platform name and
+    /// in the venue's own currency. Refreshed from the venues after every
+    /// cycle; see [`Self::bankroll_for`].
+    #[serde(default)]
+    pub balances: HashMap<String, Decimal>,
     /// Bets placed but not yet resolved. Persisted so resolution can be
     /// checked across restarts. `#[serde(default)]` handles old JSON files.
     #[serde(default)]
     pub open_bets: Vec<TradeReceipt>,
+    /// Orders that may have reached their venue but were never confirmed,
+    /// as provisional receipts keyed by idempotency key. Settled against
+    /// venue positions on later cycles.
+    #[serde(default)]
+    pub unconfirmed_bets: Vec<TradeReceipt>,
     /// Timestamp of the last completed cycle. Used on restart to compute
     /// how much of the scan interval has already elapsed, so the agent
     /// waits the remainder rather than firing immediately.
     #[serde(default)]
     pub last_cycle_time: Option<DateTime<Utc>>,
+    /// Detected edge vs realised return for resolved bets, oldest first.
+    #[serde(default)]
+    pub edge_realizations: Vec<EdgeRealization>,
+    /// Adaptive per-category edge thresholds.
+    #[serde(default)]
+    pub thresholds: ThresholdState,
+    /// Per-category losing streaks and cool-downs.
+    #[serde(default)]
+    pub cool_downs: CoolDownState,
+    /// Resolved results per operator tag and currency.
+    #[serde(default)]
+    pub tag_results: Vec<TagResults>,
+    /// End-of-day snapshots that daily P&L is derived from.
+    #[serde(default)]
+    pub daily: DailyLedger,
+    /// Historical accuracy and blending weights of cross-reference prices.
+    #[serde(default)]
+    pub references: ReferenceState,
+    /// Scan hibernation per platform name.
+    #[serde(default)]
+    pub hibernation: HashMap<String, PlatformHibernation>,
+    /// Activity on shared accounts not placed by the agent.
+    #[serde(default)]
+    pub external_activity: ExternalActivity,
+    /// Redacted effective configuration of the last session, diffed against
+    /// the current one on startup.
+    #[serde(default)]
+    pub effective_config: BTreeMap<String, serde_json::Value>,
+    /// Set when a shutdown abandoned a cycle part-way; the next startup
+    /// repairs the state from the venues before trading.
+    #[serde(default)]
+    pub dirty_shutdown: Option<DateTime<Utc>>,
 }
 
 impl fmt::Display for AgentState {
@@ -606,14 +1243,31 @@ impl AgentState {
             total_ib_commissions: Decimal::ZERO,
             start_time: Utc::now(),
             peak_bankroll: initial_bankroll,
+            cash_bankroll: initial_bankroll,
+            total_equity: initial_bankroll,
+            peak_equity: initial_bankroll,
+            unrealized_pnl: Decimal::ZERO,
             status: AgentStatus::Alive,
+            pause_reason: None,
             survival_threshold: Decimal::ZERO,
             mana_bankroll: Decimal::ZERO,
             total_mana_pnl: Decimal::ZERO,
             mana_trades_won: 0,
             mana_trades_lost: 0,
+            balances: HashMap::new(),
             open_bets: Vec::new(),
+            unconfirmed_bets: Vec::new(),
             last_cycle_time: None,
+            edge_realizations: Vec::new(),
+            thresholds: ThresholdState::default(),
+            cool_downs: CoolDownState::default(),
+            tag_results: Vec::new(),
+            daily: DailyLedger::default(),
+            references: ReferenceState::default(),
+            hibernation: HashMap::new(),
+            external_activity: ExternalActivity::default(),
+            effective_config: BTreeMap::new(),
+            dirty_shutdown: None,
         }
     }
 
@@ -627,6 +1281,17 @@ impl AgentState {
         }
     }
 
+    /// Bankroll to size bets on `platform` against: the venue's own balance
+    /// when known, else the aggregate — Mana for Manifold once there is
+    /// any, the AUD bankroll otherwise.
+    pub fn bankroll_for(&self, platform: &str) -> Decimal {
+        match self.balances.get(platform) {
+            Some(&balance) => balance,
+            None if platform == "manifold" && self.mana_bankroll > Decimal::ZERO => self.mana_bankroll,
+            None => self.bankroll,
+        }
+    }
+
     /// Current drawdown from peak as a fraction (0.0 = at peak).
     pub fn drawdown(&self) -> Decimal {
         if self.peak_bankroll <= Decimal::ZERO {
@@ -656,11 +1321,40 @@ impl AgentState {
         self.status == AgentStatus::Alive
     }
 
-    /// Update peak bankroll if current is higher.
+    /// Whether the agent is in warm standby (running, not trading).
+    pub fn is_standby(&self) -> bool {
+        self.status == AgentStatus::Standby
+    }
+
+    /// Update peak bankroll and peak equity if current is higher.
     pub fn update_peak(&mut self) {
         if self.bankroll > self.peak_bankroll {
             self.peak_bankroll = self.bankroll;
         }
+        if self.total_equity > self.peak_equity {
+            self.peak_equity = self.total_equity;
+        }
+    }
+
+    /// Stake of the open bets held in the AUD bankroll's currency.
+    pub fn committed(&self) -> Decimal {
+        self.open_bets.iter().filter(|b| b.currency == "AUD").map(|b| b.amount).sum()
+    }
+
+    /// Recompute `cash_bankroll` and `total_equity` from the bankroll, the
+    /// open bets and the last marked unrealized P&L.
+    pub fn update_equity(&mut self) {
+        self.cash_bankroll = self.bankroll - self.committed();
+        self.total_equity = self.bankroll + self.unrealized_pnl;
+    }
+
+    /// Current drawdown of `total_equity` from `peak_equity` as a fraction.
+    pub fn equity_drawdown(&self) -> Decimal {
+        if self.peak_equity <= Decimal::ZERO {
+            Decimal::ZERO
+        } else {
+            (Decimal::ONE - self.total_equity / self.peak_equity).max(Decimal::ZERO)
+        }
     }
 
     /// Deduct costs from the bankroll and track them by category.
@@ -734,6 +1428,26 @@ impl AgentState {
     }
 }
 
+impl crate::diagnostics::SizedStore for AgentState {
+    fn collection_sizes(&self) -> Vec<crate::diagnostics::CollectionSize> {
+        use crate::diagnostics::CollectionSize;
+        vec![
+            CollectionSize::new("state.open_bets", self.open_bets.len(), None),
+            CollectionSize::new(
+                "state.edge_realizations",
+                self.edge_realizations.len(),
+                Some(crate::strategy::adaptive::MAX_REALIZATIONS),
+            ),
+            CollectionSize::new("state.threshold_adjustments", self.thresholds.adjustments.len(), None),
+            CollectionSize::new(
+                "state.own_sales",
+                self.external_activity.own_sales.len(),
+                Some(crate::engine::reconcile::MAX_OWN_SALES),
+            ),
+        ]
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Cycle report
 // ---------------------------------------------------------------------------
@@ -749,6 +1463,12 @@ pub struct CycleReport {
     pub cycle_cost: Decimal,
     pub cycle_pnl: Decimal,
     pub bankroll_after: Decimal,
+    #[serde(default)]
+    pub bets_failed: u64,
+    #[serde(default)]
+    pub mana_bankroll_after: Decimal,
+    #[serde(default)]
+    pub status: Option<AgentStatus>,
 }
 
 impl fmt::Display for CycleReport {
@@ -795,6 +1515,16 @@ pub enum OracleError {
     #[error("Market not found: {0}")]
     MarketNotFound(String),
 
+    /// The venue stopped accepting trades on this market (closed,
+    /// suspended or resolved) between scan and execution.
+    #[error("Market closed ({platform}): {market_id} — {reason}")]
+    MarketClosed { platform: String, market_id: String, reason: String },
+
+    /// The venue rejected an order whose idempotency key it had already
+    /// seen: an earlier submission under the same key reached it.
+    #[error("Duplicate order ({platform}): key {key} was already submitted")]
+    DuplicateOrder { platform: String, key: String },
+
     #[error("Invalid estimate: {0}")]
     InvalidEstimate(String),
 
@@ -880,11 +1610,12 @@ mod tests {
         assert_eq!(format!("{}", AgentStatus::Alive), "🟢 ALIVE");
         assert_eq!(format!("{}", AgentStatus::Died), "🔴 DIED");
         assert_eq!(format!("{}", AgentStatus::Paused), "🟡 PAUSED");
+        assert_eq!(format!("{}", AgentStatus::Standby), "🔵 STANDBY");
     }
 
     #[test]
     fn test_agent_status_serialization_roundtrip() {
-        for status in [AgentStatus::Alive, AgentStatus::Died, AgentStatus::Paused] {
+        for status in [AgentStatus::Alive, AgentStatus::Died, AgentStatus::Paused, AgentStatus::Standby] {
             let json = serde_json::to_string(&status).unwrap();
             let parsed: AgentStatus = serde_json::from_str(&json).unwrap();
             assert_eq!(status, parsed);
@@ -1058,6 +1789,7 @@ mod tests {
             metaculus_forecasters: Some(200),
             manifold_prob: Some(dec!(0.55)),
             forecastex_price: Some(dec!(0.50)),
+            ..Default::default()
         };
         let display = format!("{refs}");
         assert!(display.contains("Metaculus"));
@@ -1078,6 +1810,7 @@ mod tests {
             metaculus_forecasters: Some(100),
             manifold_prob: None,
             forecastex_price: Some(dec!(0.70)),
+            ..Default::default()
         };
         let json = serde_json::to_string(&refs).unwrap();
         let parsed: CrossReferences = serde_json::from_str(&json).unwrap();
@@ -1095,6 +1828,9 @@ mod tests {
             reasoning: "test".to_string(),
             tokens_used: 100,
             cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         assert!(e.is_valid());
     }
@@ -1107,6 +1843,9 @@ mod tests {
             reasoning: "overconfident".to_string(),
             tokens_used: 100,
             cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         assert!(!e.is_valid());
     }
@@ -1119,6 +1858,9 @@ mod tests {
             reasoning: "overconfident".to_string(),
             tokens_used: 100,
             cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         assert!(!e.is_valid());
     }
@@ -1131,6 +1873,9 @@ mod tests {
             reasoning: "".to_string(),
             tokens_used: 0,
             cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         let high = Estimate {
             probability: dec!(0.99),
@@ -1138,6 +1883,9 @@ mod tests {
             reasoning: "".to_string(),
             tokens_used: 0,
             cost: Decimal::ZERO,
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         assert!(low.is_valid());
         assert!(high.is_valid());
@@ -1151,6 +1899,9 @@ mod tests {
             reasoning: "".to_string(),
             tokens_used: 100,
             cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         // Market price 0.45, tolerance 0.02 → within tolerance → echo
         assert!(e.is_echo(dec!(0.45), dec!(0.02)));
@@ -1166,6 +1917,9 @@ mod tests {
             reasoning: "strong signal".to_string(),
             tokens_used: 250,
             cost: dec!(0.005),
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         let display = format!("{e}");
         assert!(display.contains("73"));
@@ -1180,6 +1934,9 @@ mod tests {
             reasoning: "Based on BOM data".to_string(),
             tokens_used: 350,
             cost: dec!(0.008),
+            critique: None,
+            served_by: None,
+            tier: None,
         };
         let json = serde_json::to_string(&e).unwrap();
         let parsed: Estimate = serde_json::from_str(&json).unwrap();
@@ -1201,6 +1958,14 @@ mod tests {
             fees: dec!(0.25),
             timestamp: Utc::now(),
             currency: "AUD".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         };
         assert_eq!(receipt.net_cost(), dec!(5.25));
     }
@@ -1217,6 +1982,14 @@ mod tests {
             fees: dec!(0.25),
             timestamp: Utc::now(),
             currency: "AUD".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         };
         let display = format!("{receipt}");
         assert!(display.contains("YES"));
@@ -1236,6 +2009,14 @@ mod tests {
             fees: Decimal::ZERO,
             timestamp: Utc::now(),
             currency: "Mana".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
         };
         let json = serde_json::to_string(&receipt).unwrap();
         let parsed: TradeReceipt = serde_json::from_str(&json).unwrap();
@@ -1255,6 +2036,8 @@ mod tests {
             size: dec!(10),
             entry_price: dec!(0.40),
             current_value: dec!(5),
+            cost: dec!(4),
+            category: None,
         };
         // PnL = 5.0 - (10.0 * 0.40) = 5.0 - 4.0 = 1.0
         assert_eq!(pos.unrealized_pnl(), dec!(1));
@@ -1269,6 +2052,8 @@ mod tests {
             size: dec!(10),
             entry_price: dec!(0.60),
             current_value: dec!(4),
+            cost: dec!(6),
+            category: None,
         };
         // PnL = 4.0 - (10.0 * 0.60) = 4.0 - 6.0 = -2.0
         assert_eq!(pos.unrealized_pnl(), dec!(-2));
@@ -1283,6 +2068,8 @@ mod tests {
             size: dec!(10),
             entry_price: dec!(0.40),
             current_value: dec!(5),
+            cost: dec!(4),
+            category: None,
         };
         let display = format!("{pos}");
         assert!(display.contains("YES"));
@@ -1298,6 +2085,8 @@ mod tests {
             size: dec!(10),
             entry_price: dec!(0.40),
             current_value: dec!(5),
+            cost: dec!(4),
+            category: None,
         };
         let json = serde_json::to_string(&pos).unwrap();
         let parsed: Position = serde_json::from_str(&json).unwrap();
@@ -1367,6 +2156,7 @@ mod tests {
             confidence: dec!(0.80),
             rationale: "Strong CPI signal".to_string(),
             data_sources_used: vec!["fred".to_string(), "metaculus".to_string()],
+            risk_context: None,
         };
         assert_eq!(decision.expected_value(), dec!(0.50));
     }
@@ -1383,6 +2173,7 @@ mod tests {
             confidence: dec!(0.80),
             rationale: "test".to_string(),
             data_sources_used: vec![],
+            risk_context: None,
         };
         assert_eq!(decision.market_price(), dec!(0.45));
 
@@ -1405,6 +2196,7 @@ mod tests {
             confidence: dec!(0.80),
             rationale: "test".to_string(),
             data_sources_used: vec![],
+            risk_context: None,
         };
         let display = format!("{decision}");
         assert!(display.contains("YES"));
@@ -1423,6 +2215,7 @@ mod tests {
             confidence: dec!(0.80),
             rationale: "Based on FRED data".to_string(),
             data_sources_used: vec!["fred".to_string()],
+            risk_context: None,
         };
         let json = serde_json::to_string(&decision).unwrap();
         let parsed: BetDecision = serde_json::from_str(&json).unwrap();
@@ -1461,6 +2254,7 @@ mod tests {
             metaculus_forecast: Some(dec!(0.55)),
             metaculus_forecasters: Some(200),
             manifold_price: None,
+            sections: Vec::new(),
         };
         let display = format!("{ctx}");
         assert!(display.contains("Economics"));
@@ -1479,6 +2273,7 @@ mod tests {
             metaculus_forecast: None,
             metaculus_forecasters: None,
             manifold_price: Some(dec!(0.60)),
+            sections: Vec::new(),
         };
         let json = serde_json::to_string(&ctx).unwrap();
         let parsed: DataContext = serde_json::from_str(&json).unwrap();
@@ -1486,6 +2281,22 @@ mod tests {
         assert_eq!(parsed.manifold_price, Some(dec!(0.60)));
     }
 
+    #[test]
+    fn test_data_context_single_summary_shape_migrates() {
+        // Contexts serialised before sections existed render their summary.
+        let json = r#"{"category":"Weather","raw_data":null,"summary":"Warm and windy",
+            "freshness":"2026-01-01T00:00:00Z","source":"bom","cost":"0",
+            "metaculus_forecast":null,"metaculus_forecasters":null,"manifold_price":null}"#;
+        let mut ctx: DataContext = serde_json::from_str(json).unwrap();
+        assert!(ctx.sections.is_empty());
+        assert_eq!(ctx.render(), "Warm and windy");
+
+        // Adding a section keeps the original summary as the first one.
+        ctx.push_section(ContextSection::unavailable("news"));
+        assert_eq!(ctx.sections[0].source, "bom");
+        assert_eq!(ctx.summary, "[bom @ 2026-01-01 00:00 UTC]\nWarm and windy\nnews: unavailable this cycle");
+    }
+
     // -- CycleReport tests --
 
     #[test]
@@ -1499,6 +2310,9 @@ mod tests {
             cycle_cost: dec!(0.15),
             cycle_pnl: dec!(3.50),
             bankroll_after: dec!(103.50),
+            bets_failed: 0,
+            mana_bankroll_after: Decimal::ZERO,
+            status: Some(AgentStatus::Alive),
         };
         let display = format!("{report}");
         assert!(display.contains("#42"));
@@ -1516,6 +2330,9 @@ mod tests {
             cycle_cost: dec!(0.12),
             cycle_pnl: dec!(-0.12),
             bankroll_after: dec!(99.88),
+            bets_failed: 0,
+            mana_bankroll_after: Decimal::ZERO,
+            status: Some(AgentStatus::Alive),
         };
         let json = serde_json::to_string(&report).unwrap();
         let parsed: CycleReport = serde_json::from_str(&json).unwrap();
@@ -1539,5 +2356,12 @@ mod tests {
         };
         assert!(format!("{e}").contains("10.00"));
         assert!(format!("{e}").contains("5.00"));
+
+        let e = OracleError::MarketClosed {
+            platform: "betfair".to_string(),
+            market_id: "1.234".to_string(),
+            reason: "MARKET_SUSPENDED".to_string(),
+        };
+        assert_eq!(format!("{e}"), "Market closed (betfair): 1.234 — MARKET_SUSPENDED");
     }
 }
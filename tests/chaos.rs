@@ -0,0 +1,340 @@
+//! Chaos scenario integration tests (`cargo test --features chaos`).
+//!
+//! Runs each canned `[chaos]` scenario against a mock-backed agent —
+//! scan → estimate → select → execute → reconcile, as in the main loop —
+//! and asserts the invariants the scenario is meant to protect.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use oracle::chaos::{wrap_estimator, wrap_platform, InjectedFault};
+use oracle::config::ChaosConfig;
+use oracle::engine::accountant::{Accountant, CycleCosts};
+use oracle::engine::executor::{ExecutionFailure, Executor};
+use oracle::llm::LlmEstimator;
+use oracle::platforms::PredictionPlatform;
+use oracle::strategy::edge::{EdgeConfig, EdgeDetector};
+use oracle::strategy::kelly::{KellyCalculator, KellyConfig};
+use oracle::strategy::risk::{RiskConfig, RiskManager};
+use oracle::strategy::StrategyOrchestrator;
+use oracle::types::*;
+
+const MARKETS_PER_SCAN: usize = 6;
+const CYCLES: usize = 12;
+
+// ---------------------------------------------------------------------------
+// Mocks
+// ---------------------------------------------------------------------------
+
+/// Paper venue: fresh underpriced markets every scan, unique order ids.
+struct MockManifold {
+    scans: AtomicU64,
+    orders: AtomicU64,
+}
+
+#[async_trait]
+impl PredictionPlatform for MockManifold {
+    async fn fetch_markets(&self) -> Result<Vec<Market>> {
+        let scan = self.scans.fetch_add(1, Ordering::SeqCst);
+        Ok((0..MARKETS_PER_SCAN)
+            .map(|i| Market {
+                id: format!("m{scan}-{i}"),
+                platform: "manifold".to_string(),
+                question: format!("Chaos market {scan}-{i}?"),
+                description: String::new(),
+                category: MarketCategory::Weather,
+                current_price_yes: dec!(0.40),
+                current_price_no: dec!(0.60),
+                volume_24h: dec!(1000),
+                liquidity: dec!(5000),
+                deadline: Utc::now() + chrono::Duration::days(30),
+                resolution_criteria: String::new(),
+                url: String::new(),
+                cross_refs: CrossReferences::default(),
+                event_group: None,
+                facts: None,
+                tags: Vec::new(),
+            })
+            .collect())
+    }
+
+    async fn place_bet(&self, market_id: &str, side: Side, amount: Decimal) -> Result<TradeReceipt> {
+        let n = self.orders.fetch_add(1, Ordering::SeqCst);
+        Ok(TradeReceipt {
+            order_id: format!("order-{n}"),
+            market_id: market_id.to_string(),
+            platform: "manifold".to_string(),
+            side,
+            amount,
+            fill_price: dec!(0.45),
+            fees: Decimal::ZERO,
+            timestamp: Utc::now(),
+            currency: "Mana".to_string(),
+            deadline: None,
+            category: None,
+            edge: None,
+            raw_response: None,
+            risk_context: None,
+            tags: Vec::new(),
+            correlation_key: None,
+            expected_price: None,
+        })
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(dec!(1_000_000))
+    }
+
+    async fn check_liquidity(&self, _market_id: &str) -> Result<LiquidityInfo> {
+        anyhow::bail!("not supported")
+    }
+
+    fn is_real_money(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &str {
+        "manifold"
+    }
+}
+
+/// Estimator that tags each estimate with its market id.
+struct MockLlm;
+
+#[async_trait]
+impl LlmEstimator for MockLlm {
+    async fn estimate_probability(&self, market: &Market, _context: &DataContext) -> Result<Estimate> {
+        Ok(Estimate {
+            probability: dec!(0.60),
+            confidence: dec!(0.8),
+            reasoning: market.id.clone(),
+            tokens_used: 100,
+            cost: dec!(0.01),
+            critique: None,
+            served_by: None,
+            tier: None,
+        })
+    }
+
+    async fn batch_estimate(&self, markets: &[(Market, DataContext)]) -> Result<Vec<Estimate>> {
+        let mut out = Vec::with_capacity(markets.len());
+        for (m, c) in markets {
+            out.push(self.estimate_probability(m, c).await?);
+        }
+        Ok(out)
+    }
+
+    fn cost_per_call(&self) -> Decimal {
+        dec!(0.01)
+    }
+
+    fn model_name(&self) -> &str {
+        "mock"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mock-backed agent
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct Tally {
+    cycles_ok: usize,
+    cycles_failed: usize,
+    selected: usize,
+    executed: usize,
+    failed: usize,
+    duplicates: usize,
+    committed: Decimal,
+    estimates: usize,
+    markets_estimated: usize,
+}
+
+struct Agent {
+    venue: Arc<dyn PredictionPlatform>,
+    llm: Box<dyn LlmEstimator>,
+    orchestrator: StrategyOrchestrator,
+    executor: Executor,
+    state: AgentState,
+    tally: Tally,
+}
+
+impl Agent {
+    fn new(scenario: &str, tweak: impl FnOnce(&mut ChaosConfig)) -> Self {
+        let mut chaos = ChaosConfig {
+            enabled: true,
+            scenario: Some(scenario.to_string()),
+            seed: 7,
+            ..ChaosConfig::default()
+        }
+        .resolved();
+        tweak(&mut chaos);
+
+        let venue = wrap_platform(
+            Arc::new(MockManifold { scans: AtomicU64::new(0), orders: AtomicU64::new(0) }),
+            &chaos,
+        );
+        let executor = Executor::new(None, false)
+            .with_venue(Arc::clone(&venue));
+        let orchestrator = StrategyOrchestrator::new(
+            EdgeDetector::new(EdgeConfig::default()),
+            KellyCalculator::new(KellyConfig { commission_per_trade: Decimal::ZERO, ..KellyConfig::default() }),
+            RiskManager::new(RiskConfig { max_positions: 1_000, ..RiskConfig::default() }),
+        );
+        let mut state = AgentState::new(dec!(1_000_000));
+        state.mana_bankroll = dec!(1_000_000);
+
+        Self {
+            venue,
+            llm: wrap_estimator(Box::new(MockLlm), &chaos),
+            orchestrator,
+            executor,
+            state,
+            tally: Tally::default(),
+        }
+    }
+
+    async fn run(&mut self, cycles: usize) {
+        for _ in 0..cycles {
+            match self.cycle().await {
+                Ok(()) => self.tally.cycles_ok += 1,
+                Err(_) => self.tally.cycles_failed += 1,
+            }
+        }
+    }
+
+    async fn cycle(&mut self) -> Result<()> {
+        let markets = self.venue.fetch_markets().await?;
+        let contexts: Vec<_> = markets
+            .iter()
+            .map(|m| (m.clone(), DataContext::empty(m.category)))
+            .collect();
+        let estimates = self.llm.batch_estimate(&contexts).await?;
+        self.tally.markets_estimated += contexts.len();
+        self.tally.estimates += estimates.len();
+        let paired: Vec<_> = markets.into_iter().zip(estimates).collect();
+        for (m, e) in &paired {
+            assert_eq!(m.id, e.reasoning, "estimate attributed to the wrong market");
+        }
+
+        self.orchestrator.sync_exposure_from_state(&self.state);
+        self.orchestrator.reset_cycle();
+        let (bets, _) = self.orchestrator.select_bets(&paired, &self.state);
+        let execution = self.executor.execute_batch(&bets).await?;
+
+        assert_eq!(
+            execution.executed.len() + execution.failed.len() + execution.duplicate_fills,
+            bets.len(),
+            "every selected bet must be accounted for exactly once"
+        );
+        let committed: Decimal = execution.executed.iter().map(|t| t.amount).sum();
+        assert_eq!(execution.total_committed, committed);
+
+        self.tally.selected += bets.len();
+        self.tally.executed += execution.executed.len();
+        self.tally.failed += execution.failed.len();
+        self.tally.duplicates += execution.duplicate_fills;
+        self.tally.committed += execution.total_committed;
+        for f in &execution.failed {
+            assert_eq!(f.code, ExecutionFailure::Other, "injected faults are ordinary failures");
+        }
+
+        for trade in &execution.executed {
+            self.state.open_bets.push(trade.receipt.clone());
+        }
+        let costs = CycleCosts {
+            llm_cost: paired.iter().map(|(_, e)| e.cost).sum(),
+            ..Default::default()
+        };
+        Accountant::reconcile(&mut self.state, &execution, &costs);
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scenarios
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn flaky_manifold_degrades_without_corrupting_accounts() {
+    let mut agent = Agent::new("flaky-manifold", |c| c.platform.latency_ms = 5);
+    agent.run(CYCLES).await;
+
+    let t = &agent.tally;
+    // Scans fail some of the time, but not every cycle.
+    assert!(t.cycles_failed > 0 && t.cycles_ok > 0, "ok={} failed={}", t.cycles_ok, t.cycles_failed);
+    // Some placements fail; the rest are booked.
+    assert!(t.failed > 0 && t.executed > 0, "executed={} failed={}", t.executed, t.failed);
+    assert_eq!(t.duplicates, 0);
+    assert_eq!(agent.state.trades_placed as usize, t.executed);
+    assert_eq!(agent.state.open_bets.len(), t.executed);
+    let exposure: Decimal = agent.state.open_bets.iter().map(|b| b.amount).sum();
+    assert_eq!(exposure, t.committed);
+}
+
+#[tokio::test]
+async fn slow_llm_keeps_estimates_aligned() {
+    let mut agent = Agent::new("slow-llm", |c| c.llm.latency_ms = 20);
+    let started = Instant::now();
+    agent.run(CYCLES).await;
+
+    let t = &agent.tally;
+    assert!(started.elapsed() >= Duration::from_millis(20 * CYCLES as u64));
+    assert!(t.cycles_ok > 0);
+    // Partial batches drop estimates (alignment is asserted per cycle);
+    // markets without an estimate are simply not traded.
+    assert!(t.estimates < t.markets_estimated, "no partial batch injected");
+    assert_eq!(agent.state.trades_placed as usize, t.executed);
+    assert_eq!(t.failed, 0);
+}
+
+#[tokio::test]
+async fn duplicate_fills_are_committed_once() {
+    let mut agent = Agent::new("duplicate-fills", |_| {});
+    agent.run(CYCLES).await;
+
+    let t = &agent.tally;
+    assert_eq!(t.cycles_failed, 0);
+    assert!(t.duplicates > 0, "scenario injected no duplicate fills");
+    assert_eq!(t.executed + t.duplicates, t.selected);
+
+    // The journal kept every replayed receipt out of the books.
+    let mut ids: Vec<_> = agent.state.open_bets.iter().map(|b| b.order_id.as_str()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), agent.state.open_bets.len(), "order booked twice");
+    assert_eq!(agent.state.trades_placed as usize, t.executed);
+    let exposure: Decimal = agent.state.open_bets.iter().map(|b| b.amount).sum();
+    assert_eq!(exposure, t.committed);
+}
+
+#[tokio::test]
+async fn injected_failure_is_typed() {
+    let mut chaos = ChaosConfig { enabled: true, ..ChaosConfig::default() };
+    chaos.platform.failure_rate = 1.0;
+    let venue = wrap_platform(
+        Arc::new(MockManifold { scans: AtomicU64::new(0), orders: AtomicU64::new(0) }),
+        &chaos,
+    );
+    let err = venue.fetch_markets().await.unwrap_err();
+    assert!(err.downcast_ref::<InjectedFault>().is_some());
+
+    // Disabled config leaves clients untouched.
+    chaos.enabled = false;
+    let venue = wrap_platform(
+        Arc::new(MockManifold { scans: AtomicU64::new(0), orders: AtomicU64::new(0) }),
+        &chaos,
+    );
+    assert!(venue.fetch_markets().await.is_ok());
+}
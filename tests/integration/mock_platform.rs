@@ -95,6 +95,7 @@ impl MockPlatform {
                     manifold_prob: Some(0.32),
                     forecastex_price: Some(0.30),
                 },
+                event_group: None,
             },
             Market {
                 id: "MOCK-SP-001".to_string(),
@@ -115,6 +116,7 @@ impl MockPlatform {
                     manifold_prob: Some(0.58),
                     forecastex_price: Some(0.55),
                 },
+                event_group: None,
             },
             Market {
                 id: "MOCK-EC-001".to_string(),
@@ -135,6 +137,7 @@ impl MockPlatform {
                     manifold_prob: Some(0.42),
                     forecastex_price: Some(0.40),
                 },
+                event_group: None,
             },
             Market {
                 id: "MOCK-PO-001".to_string(),
@@ -155,6 +158,7 @@ impl MockPlatform {
                     manifold_prob: Some(0.28),
                     forecastex_price: Some(0.25),
                 },
+                event_group: None,
             },
             // A low-liquidity market that should be filtered out
             Market {
@@ -171,6 +175,7 @@ impl MockPlatform {
                 resolution_criteria: "Official source".to_string(),
                 url: "https://mock.example.com/MOCK-OT-001".to_string(),
                 cross_refs: CrossReferences::default(),
+                event_group: None,
             },
         ]
     }
@@ -423,6 +428,7 @@ mod tests {
             resolution_criteria: "".to_string(),
             url: "".to_string(),
             cross_refs: CrossReferences::default(),
+            event_group: None,
         }];
 
         let platform = MockPlatform::with_markets("custom", true, 50.0, custom);